@@ -426,3 +426,23 @@ struct PathSecretMapCleanerCycled {
     #[measure("handshake_requests.retired")]
     handshake_requests_retired: usize,
 }
+
+#[event("path_secret_map:stale_credentials_received")]
+#[subject(endpoint)]
+/// Emitted when a peer uses credentials from an entry which was replaced by a newer handshake
+///
+/// These credentials are still accepted until the rotation overlap period elapses. Peers which
+/// keep using them after that will receive an UnknownPathSecret packet and need to handshake.
+struct StaleCredentialsReceived<'a> {
+    #[nominal_counter("peer_address.protocol")]
+    peer_address: SocketAddress<'a>,
+
+    #[snapshot("[HIDDEN]")]
+    credential_id: &'a [u8],
+
+    key_id: u64,
+
+    /// Time since insertion of this entry
+    #[measure("age", Duration)]
+    age: core::time::Duration,
+}
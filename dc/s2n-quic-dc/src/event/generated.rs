@@ -1707,6 +1707,33 @@ pub mod api {
     impl Event for PathSecretMapCleanerCycled {
         const NAME: &'static str = "path_secret_map:cleaner_cycled";
     }
+    #[derive(Clone, Debug)]
+    #[non_exhaustive]
+    #[doc = " Emitted when a peer uses credentials from an entry which was replaced by a newer handshake"]
+    #[doc = ""]
+    #[doc = " These credentials are still accepted until the rotation overlap period elapses. Peers which"]
+    #[doc = " keep using them after that will receive an UnknownPathSecret packet and need to handshake."]
+    pub struct StaleCredentialsReceived<'a> {
+        pub peer_address: SocketAddress<'a>,
+        pub credential_id: &'a [u8],
+        pub key_id: u64,
+        #[doc = " Time since insertion of this entry"]
+        pub age: core::time::Duration,
+    }
+    #[cfg(any(test, feature = "testing"))]
+    impl<'a> crate::event::snapshot::Fmt for StaleCredentialsReceived<'a> {
+        fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::fmt::Result {
+            let mut fmt = fmt.debug_struct("StaleCredentialsReceived");
+            fmt.field("peer_address", &self.peer_address);
+            fmt.field("credential_id", &"[HIDDEN]");
+            fmt.field("key_id", &self.key_id);
+            fmt.field("age", &self.age);
+            fmt.finish()
+        }
+    }
+    impl<'a> Event for StaleCredentialsReceived<'a> {
+        const NAME: &'static str = "path_secret_map:stale_credentials_received";
+    }
     impl IntoEvent<builder::AcceptorPacketDropReason> for s2n_codec::DecoderError {
         fn into_event(self) -> builder::AcceptorPacketDropReason {
             use builder::AcceptorPacketDropReason as Reason;
@@ -2666,6 +2693,21 @@ pub mod tracing {
             } = event;
             tracing :: event ! (target : "path_secret_map_cleaner_cycled" , parent : parent , tracing :: Level :: DEBUG , { id_entries = tracing :: field :: debug (id_entries) , id_entries_retired = tracing :: field :: debug (id_entries_retired) , id_entries_active = tracing :: field :: debug (id_entries_active) , id_entries_active_utilization = tracing :: field :: debug (id_entries_active_utilization) , id_entries_utilization = tracing :: field :: debug (id_entries_utilization) , id_entries_initial_utilization = tracing :: field :: debug (id_entries_initial_utilization) , address_entries = tracing :: field :: debug (address_entries) , address_entries_active = tracing :: field :: debug (address_entries_active) , address_entries_active_utilization = tracing :: field :: debug (address_entries_active_utilization) , address_entries_retired = tracing :: field :: debug (address_entries_retired) , address_entries_utilization = tracing :: field :: debug (address_entries_utilization) , address_entries_initial_utilization = tracing :: field :: debug (address_entries_initial_utilization) , handshake_requests = tracing :: field :: debug (handshake_requests) , handshake_requests_retired = tracing :: field :: debug (handshake_requests_retired) });
         }
+        #[inline]
+        fn on_stale_credentials_received(
+            &self,
+            meta: &api::EndpointMeta,
+            event: &api::StaleCredentialsReceived,
+        ) {
+            let parent = self.parent(meta);
+            let api::StaleCredentialsReceived {
+                peer_address,
+                credential_id,
+                key_id,
+                age,
+            } = event;
+            tracing :: event ! (target : "stale_credentials_received" , parent : parent , tracing :: Level :: DEBUG , { peer_address = tracing :: field :: debug (peer_address) , credential_id = tracing :: field :: debug (credential_id) , key_id = tracing :: field :: debug (key_id) , age = tracing :: field :: debug (age) });
+        }
     }
 }
 pub mod builder {
@@ -4313,6 +4355,35 @@ pub mod builder {
             }
         }
     }
+    #[derive(Clone, Debug)]
+    #[doc = " Emitted when a peer uses credentials from an entry which was replaced by a newer handshake"]
+    #[doc = ""]
+    #[doc = " These credentials are still accepted until the rotation overlap period elapses. Peers which"]
+    #[doc = " keep using them after that will receive an UnknownPathSecret packet and need to handshake."]
+    pub struct StaleCredentialsReceived<'a> {
+        pub peer_address: SocketAddress<'a>,
+        pub credential_id: &'a [u8],
+        pub key_id: u64,
+        #[doc = " Time since insertion of this entry"]
+        pub age: core::time::Duration,
+    }
+    impl<'a> IntoEvent<api::StaleCredentialsReceived<'a>> for StaleCredentialsReceived<'a> {
+        #[inline]
+        fn into_event(self) -> api::StaleCredentialsReceived<'a> {
+            let StaleCredentialsReceived {
+                peer_address,
+                credential_id,
+                key_id,
+                age,
+            } = self;
+            api::StaleCredentialsReceived {
+                peer_address: peer_address.into_event(),
+                credential_id: credential_id.into_event(),
+                key_id: key_id.into_event(),
+                age: age.into_event(),
+            }
+        }
+    }
 }
 pub use traits::*;
 mod traits {
@@ -5095,6 +5166,16 @@ mod traits {
             let _ = meta;
             let _ = event;
         }
+        #[doc = "Called when the `StaleCredentialsReceived` event is triggered"]
+        #[inline]
+        fn on_stale_credentials_received(
+            &self,
+            meta: &api::EndpointMeta,
+            event: &api::StaleCredentialsReceived,
+        ) {
+            let _ = meta;
+            let _ = event;
+        }
         #[doc = r" Called for each event that relates to the endpoint and all connections"]
         #[inline]
         fn on_event<M: Meta, E: Event>(&self, meta: &M, event: &E) {
@@ -5114,6 +5195,10 @@ mod traits {
             let _ = event;
         }
         #[doc = r" Used for querying the `Subscriber::ConnectionContext` on a Subscriber"]
+        #[doc = r""]
+        #[doc = r" Subscribers which wrap other subscribers should also forward the query to"]
+        #[doc = r" the contexts of the inner subscribers so they can be found by type,"]
+        #[doc = r" regardless of how the subscribers are composed."]
         #[inline]
         fn query(
             context: &Self::ConnectionContext,
@@ -5727,6 +5812,14 @@ mod traits {
             self.as_ref().on_path_secret_map_cleaner_cycled(meta, event);
         }
         #[inline]
+        fn on_stale_credentials_received(
+            &self,
+            meta: &api::EndpointMeta,
+            event: &api::StaleCredentialsReceived,
+        ) {
+            self.as_ref().on_stale_credentials_received(meta, event);
+        }
+        #[inline]
         fn on_event<M: Meta, E: Event>(&self, meta: &M, event: &E) {
             self.as_ref().on_event(meta, event);
         }
@@ -5739,6 +5832,13 @@ mod traits {
         ) {
             self.as_ref().on_connection_event(context, meta, event);
         }
+        #[inline]
+        fn query(
+            context: &Self::ConnectionContext,
+            query: &mut dyn query::Query,
+        ) -> query::ControlFlow {
+            T::query(context, query)
+        }
     }
     #[doc = r" Subscriber is implemented for a 2-element tuple to make it easy to compose multiple"]
     #[doc = r" subscribers."]
@@ -6396,6 +6496,15 @@ mod traits {
             (self.1).on_path_secret_map_cleaner_cycled(meta, event);
         }
         #[inline]
+        fn on_stale_credentials_received(
+            &self,
+            meta: &api::EndpointMeta,
+            event: &api::StaleCredentialsReceived,
+        ) {
+            (self.0).on_stale_credentials_received(meta, event);
+            (self.1).on_stale_credentials_received(meta, event);
+        }
+        #[inline]
         fn on_event<M: Meta, E: Event>(&self, meta: &M, event: &E) {
             self.0.on_event(meta, event);
             self.1.on_event(meta, event);
@@ -6558,6 +6667,8 @@ mod traits {
         );
         #[doc = "Publishes a `PathSecretMapCleanerCycled` event to the publisher's subscriber"]
         fn on_path_secret_map_cleaner_cycled(&self, event: builder::PathSecretMapCleanerCycled);
+        #[doc = "Publishes a `StaleCredentialsReceived` event to the publisher's subscriber"]
+        fn on_stale_credentials_received(&self, event: builder::StaleCredentialsReceived);
         #[doc = r" Returns the QUIC version, if any"]
         fn quic_version(&self) -> Option<u32>;
     }
@@ -6969,6 +7080,13 @@ mod traits {
             self.subscriber.on_event(&self.meta, &event);
         }
         #[inline]
+        fn on_stale_credentials_received(&self, event: builder::StaleCredentialsReceived) {
+            let event = event.into_event();
+            self.subscriber
+                .on_stale_credentials_received(&self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
         fn quic_version(&self) -> Option<u32> {
             self.quic_version
         }
@@ -7290,6 +7408,7 @@ pub mod testing {
             pub path_secret_map_id_cache_accessed: AtomicU64,
             pub path_secret_map_id_cache_accessed_hit: AtomicU64,
             pub path_secret_map_cleaner_cycled: AtomicU64,
+            pub stale_credentials_received: AtomicU64,
         }
         impl Drop for Subscriber {
             fn drop(&mut self) {
@@ -7371,6 +7490,7 @@ pub mod testing {
                     path_secret_map_id_cache_accessed: AtomicU64::new(0),
                     path_secret_map_id_cache_accessed_hit: AtomicU64::new(0),
                     path_secret_map_cleaner_cycled: AtomicU64::new(0),
+                    stale_credentials_received: AtomicU64::new(0),
                 }
             }
         }
@@ -7970,6 +8090,18 @@ pub mod testing {
                 let out = format!("{meta:?} {event:?}");
                 self.output.lock().unwrap().push(out);
             }
+            fn on_stale_credentials_received(
+                &self,
+                meta: &api::EndpointMeta,
+                event: &api::StaleCredentialsReceived,
+            ) {
+                self.stale_credentials_received
+                    .fetch_add(1, Ordering::Relaxed);
+                let meta = crate::event::snapshot::Fmt::to_snapshot(meta);
+                let event = crate::event::snapshot::Fmt::to_snapshot(event);
+                let out = format!("{meta:?} {event:?}");
+                self.output.lock().unwrap().push(out);
+            }
         }
     }
     #[derive(Debug)]
@@ -8045,6 +8177,7 @@ pub mod testing {
         pub path_secret_map_id_cache_accessed: AtomicU64,
         pub path_secret_map_id_cache_accessed_hit: AtomicU64,
         pub path_secret_map_cleaner_cycled: AtomicU64,
+        pub stale_credentials_received: AtomicU64,
     }
     impl Drop for Subscriber {
         fn drop(&mut self) {
@@ -8145,6 +8278,7 @@ pub mod testing {
                 path_secret_map_id_cache_accessed: AtomicU64::new(0),
                 path_secret_map_id_cache_accessed_hit: AtomicU64::new(0),
                 path_secret_map_cleaner_cycled: AtomicU64::new(0),
+                stale_credentials_received: AtomicU64::new(0),
             }
         }
     }
@@ -9018,6 +9152,18 @@ pub mod testing {
             let out = format!("{meta:?} {event:?}");
             self.output.lock().unwrap().push(out);
         }
+        fn on_stale_credentials_received(
+            &self,
+            meta: &api::EndpointMeta,
+            event: &api::StaleCredentialsReceived,
+        ) {
+            self.stale_credentials_received
+                .fetch_add(1, Ordering::Relaxed);
+            let meta = crate::event::snapshot::Fmt::to_snapshot(meta);
+            let event = crate::event::snapshot::Fmt::to_snapshot(event);
+            let out = format!("{meta:?} {event:?}");
+            self.output.lock().unwrap().push(out);
+        }
     }
     #[derive(Debug)]
     pub struct Publisher {
@@ -9092,6 +9238,7 @@ pub mod testing {
         pub path_secret_map_id_cache_accessed: AtomicU64,
         pub path_secret_map_id_cache_accessed_hit: AtomicU64,
         pub path_secret_map_cleaner_cycled: AtomicU64,
+        pub stale_credentials_received: AtomicU64,
     }
     impl Publisher {
         #[doc = r" Creates a publisher with snapshot assertions enabled"]
@@ -9182,6 +9329,7 @@ pub mod testing {
                 path_secret_map_id_cache_accessed: AtomicU64::new(0),
                 path_secret_map_id_cache_accessed_hit: AtomicU64::new(0),
                 path_secret_map_cleaner_cycled: AtomicU64::new(0),
+                stale_credentials_received: AtomicU64::new(0),
             }
         }
     }
@@ -9614,6 +9762,14 @@ pub mod testing {
             let out = format!("{event:?}");
             self.output.lock().unwrap().push(out);
         }
+        fn on_stale_credentials_received(&self, event: builder::StaleCredentialsReceived) {
+            self.stale_credentials_received
+                .fetch_add(1, Ordering::Relaxed);
+            let event = event.into_event();
+            let event = crate::event::snapshot::Fmt::to_snapshot(&event);
+            let out = format!("{event:?}");
+            self.output.lock().unwrap().push(out);
+        }
         fn quic_version(&self) -> Option<u32> {
             Some(1)
         }
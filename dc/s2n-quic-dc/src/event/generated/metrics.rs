@@ -7,6 +7,7 @@
 
 use crate::event::{self, api, metrics::Recorder};
 use core::sync::atomic::{AtomicU64, Ordering};
+use s2n_quic_core::query;
 pub(crate) mod aggregate;
 pub(crate) mod probe;
 #[derive(Debug)]
@@ -310,6 +311,15 @@ where
         self.subscriber
             .on_connection_closed(&context.recorder, meta, event);
     }
+    #[inline]
+    fn query(
+        context: &Self::ConnectionContext,
+        query: &mut dyn query::Query,
+    ) -> query::ControlFlow {
+        query
+            .execute(context)
+            .and_then(|| S::query(&context.recorder, query))
+    }
 }
 impl<R: Recorder> Drop for Context<R> {
     fn drop(&mut self) {
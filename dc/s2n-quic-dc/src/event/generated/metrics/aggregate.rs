@@ -13,7 +13,7 @@ use crate::event::{
     },
 };
 use core::sync::atomic::{AtomicU64, Ordering};
-static INFO: &[Info; 219usize] = &[
+static INFO: &[Info; 222usize] = &[
     info::Builder {
         id: 0usize,
         name: Str::new("acceptor_tcp_started\0"),
@@ -1328,6 +1328,24 @@ static INFO: &[Info; 219usize] = &[
         units: Units::None,
     }
     .build(),
+    info::Builder {
+        id: 219usize,
+        name: Str::new("stale_credentials_received\0"),
+        units: Units::None,
+    }
+    .build(),
+    info::Builder {
+        id: 220usize,
+        name: Str::new("stale_credentials_received.peer_address.protocol\0"),
+        units: Units::None,
+    }
+    .build(),
+    info::Builder {
+        id: 221usize,
+        name: Str::new("stale_credentials_received.age\0"),
+        units: Units::Duration,
+    }
+    .build(),
 ];
 #[derive(Debug)]
 #[allow(dead_code)]
@@ -1358,15 +1376,15 @@ pub struct ConnectionContext {
 }
 pub struct Subscriber<R: Registry> {
     #[allow(dead_code)]
-    counters: Box<[R::Counter; 74usize]>,
+    counters: Box<[R::Counter; 75usize]>,
     #[allow(dead_code)]
     bool_counters: Box<[R::BoolCounter; 12usize]>,
     #[allow(dead_code)]
     nominal_counters: Box<[R::NominalCounter]>,
     #[allow(dead_code)]
-    nominal_counter_offsets: Box<[usize; 29usize]>,
+    nominal_counter_offsets: Box<[usize; 30usize]>,
     #[allow(dead_code)]
-    measures: Box<[R::Measure; 87usize]>,
+    measures: Box<[R::Measure; 88usize]>,
     #[allow(dead_code)]
     gauges: Box<[R::Gauge; 0usize]>,
     #[allow(dead_code)]
@@ -1393,11 +1411,11 @@ impl<R: Registry> Subscriber<R> {
     #[allow(unused_mut)]
     #[inline]
     pub fn new(registry: R) -> Self {
-        let mut counters = Vec::with_capacity(74usize);
+        let mut counters = Vec::with_capacity(75usize);
         let mut bool_counters = Vec::with_capacity(12usize);
-        let mut nominal_counters = Vec::with_capacity(29usize);
-        let mut nominal_counter_offsets = Vec::with_capacity(29usize);
-        let mut measures = Vec::with_capacity(87usize);
+        let mut nominal_counters = Vec::with_capacity(30usize);
+        let mut nominal_counter_offsets = Vec::with_capacity(30usize);
+        let mut measures = Vec::with_capacity(88usize);
         let mut gauges = Vec::with_capacity(0usize);
         let mut timers = Vec::with_capacity(17usize);
         let mut nominal_timers = Vec::with_capacity(0usize);
@@ -1476,6 +1494,7 @@ impl<R: Registry> Subscriber<R> {
         counters.push(registry.register_counter(&INFO[200usize]));
         counters.push(registry.register_counter(&INFO[202usize]));
         counters.push(registry.register_counter(&INFO[204usize]));
+        counters.push(registry.register_counter(&INFO[219usize]));
         bool_counters.push(registry.register_bool_counter(&INFO[19usize]));
         bool_counters.push(registry.register_bool_counter(&INFO[20usize]));
         bool_counters.push(registry.register_bool_counter(&INFO[34usize]));
@@ -1810,6 +1829,17 @@ impl<R: Registry> Subscriber<R> {
                 debug_assert_ne!(count, 0, "field type needs at least one variant");
                 nominal_counter_offsets.push(offset);
             }
+            {
+                let offset = nominal_counters.len();
+                let mut count = 0;
+                for variant in <SocketAddress as AsVariant>::VARIANTS.iter() {
+                    nominal_counters
+                        .push(registry.register_nominal_counter(&INFO[220usize], variant));
+                    count += 1;
+                }
+                debug_assert_ne!(count, 0, "field type needs at least one variant");
+                nominal_counter_offsets.push(offset);
+            }
         }
         measures.push(registry.register_measure(&INFO[2usize]));
         measures.push(registry.register_measure(&INFO[3usize]));
@@ -1898,6 +1928,7 @@ impl<R: Registry> Subscriber<R> {
         measures.push(registry.register_measure(&INFO[216usize]));
         measures.push(registry.register_measure(&INFO[217usize]));
         measures.push(registry.register_measure(&INFO[218usize]));
+        measures.push(registry.register_measure(&INFO[221usize]));
         timers.push(registry.register_timer(&INFO[5usize]));
         timers.push(registry.register_timer(&INFO[15usize]));
         timers.push(registry.register_timer(&INFO[21usize]));
@@ -2023,6 +2054,7 @@ impl<R: Registry> Subscriber<R> {
                 71usize => (&INFO[200usize], entry),
                 72usize => (&INFO[202usize], entry),
                 73usize => (&INFO[204usize], entry),
+                74usize => (&INFO[219usize], entry),
                 _ => unsafe { core::hint::unreachable_unchecked() },
             })
     }
@@ -2246,6 +2278,12 @@ impl<R: Registry> Subscriber<R> {
                     let entries = &self.nominal_counters[offset..offset + variants.len()];
                     (&INFO[198usize], entries, variants)
                 }
+                29usize => {
+                    let offset = *entry;
+                    let variants = <SocketAddress as AsVariant>::VARIANTS;
+                    let entries = &self.nominal_counters[offset..offset + variants.len()];
+                    (&INFO[220usize], entries, variants)
+                }
                 _ => unsafe { core::hint::unreachable_unchecked() },
             })
     }
@@ -2351,6 +2389,7 @@ impl<R: Registry> Subscriber<R> {
                 84usize => (&INFO[216usize], entry),
                 85usize => (&INFO[217usize], entry),
                 86usize => (&INFO[218usize], entry),
+                87usize => (&INFO[221usize], entry),
                 _ => unsafe { core::hint::unreachable_unchecked() },
             })
     }
@@ -3563,4 +3602,18 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
         let _ = event;
         let _ = meta;
     }
+    #[inline]
+    fn on_stale_credentials_received(
+        &self,
+        meta: &api::EndpointMeta,
+        event: &api::StaleCredentialsReceived,
+    ) {
+        #[allow(unused_imports)]
+        use api::*;
+        self.count(219usize, 74usize, 1usize);
+        self.count_nominal(220usize, 29usize, &event.peer_address);
+        self.measure(221usize, 87usize, event.age);
+        let _ = event;
+        let _ = meta;
+    }
 }
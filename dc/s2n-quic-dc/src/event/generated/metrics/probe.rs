@@ -91,6 +91,7 @@ mod counter {
                 200usize => Self(path_secret_map_id_cache_accessed),
                 202usize => Self(path_secret_map_id_cache_accessed_hit),
                 204usize => Self(path_secret_map_cleaner_cycled),
+                219usize => Self(stale_credentials_received),
                 _ => unreachable!("invalid info: {info:?}"),
             }
         }
@@ -250,6 +251,8 @@ mod counter {
             fn path_secret_map_id_cache_accessed_hit(value: u64);
             # [link_name = s2n_quic_dc__event__counter__path_secret_map_cleaner_cycled]
             fn path_secret_map_cleaner_cycled(value: u64);
+            # [link_name = s2n_quic_dc__event__counter__stale_credentials_received]
+            fn stale_credentials_received(value: u64);
         }
     );
     pub mod bool {
@@ -352,6 +355,7 @@ mod counter {
                     198usize => {
                         Self(path_secret_map_address_cache_accessed_hit__peer_address__protocol)
                     }
+                    220usize => Self(stale_credentials_received__peer_address__protocol),
                     _ => unreachable!("invalid info: {info:?}"),
                 }
             }
@@ -542,6 +546,12 @@ mod counter {
                     variant: u64,
                     variant_name: &info::Str,
                 );
+                # [link_name = s2n_quic_dc__event__counter__nominal__stale_credentials_received__peer_address__protocol]
+                fn stale_credentials_received__peer_address__protocol(
+                    value: u64,
+                    variant: u64,
+                    variant_name: &info::Str,
+                );
             }
         );
     }
@@ -645,6 +655,7 @@ mod measure {
                 }
                 217usize => Self(path_secret_map_cleaner_cycled__handshake_requests),
                 218usize => Self(path_secret_map_cleaner_cycled__handshake_requests__retired),
+                221usize => Self(stale_credentials_received__age),
                 _ => unreachable!("invalid info: {info:?}"),
             }
         }
@@ -830,6 +841,8 @@ mod measure {
             fn path_secret_map_cleaner_cycled__handshake_requests(value: u64);
             # [link_name = s2n_quic_dc__event__measure__path_secret_map_cleaner_cycled__handshake_requests__retired]
             fn path_secret_map_cleaner_cycled__handshake_requests__retired(value: u64);
+            # [link_name = s2n_quic_dc__event__measure__stale_credentials_received__age]
+            fn stale_credentials_received__age(value: u64);
         }
     );
 }
@@ -0,0 +1,107 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Opt-in logging of the secrets exported by dcQUIC handshakes
+//!
+//! Each secret is logged as a line containing [`LABEL`] followed by the hex-encoded secret,
+//! which the dcQUIC Wireshark plugin loads to decrypt captured stream and datagram packets.
+//!
+//! Anyone with access to the logged secrets can decrypt all of the traffic protected by them, so
+//! secrets should only be logged while debugging.
+
+use core::fmt::{self, Write as _};
+use std::{
+    fs,
+    io::{self, Write as _},
+    path::Path,
+    sync::Mutex,
+};
+
+/// The label of each line in the key log
+pub const LABEL: &str = "DCQUIC_EXPORT_SECRET";
+
+/// Receives the secret exported by each dcQUIC handshake
+///
+/// Configured with [`Map::set_key_log`](super::Map::set_key_log).
+pub trait KeyLog: 'static + Send + Sync {
+    /// Called with the TLS exporter secret once the path secrets of a handshake are ready
+    fn export_secret(&self, secret: &[u8]);
+}
+
+/// Formats a key log line for `secret`, without the trailing newline
+pub fn format_line(secret: &[u8]) -> String {
+    let mut line = String::with_capacity(LABEL.len() + 1 + secret.len() * 2);
+    line.push_str(LABEL);
+    line.push(' ');
+    for byte in secret {
+        let _ = write!(line, "{byte:02x}");
+    }
+    line
+}
+
+/// Appends exported secrets to a file
+pub struct KeyLogFile {
+    file: Mutex<fs::File>,
+}
+
+impl fmt::Debug for KeyLogFile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("KeyLogFile").finish_non_exhaustive()
+    }
+}
+
+impl KeyLogFile {
+    /// Opens the file at `path` for appending, creating it if it doesn't exist
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?;
+
+        Ok(Self {
+            file: Mutex::new(file),
+        })
+    }
+}
+
+impl KeyLog for KeyLogFile {
+    fn export_secret(&self, secret: &[u8]) {
+        // format the line up front so it's written with a single call
+        let mut line = format_line(secret);
+        line.push('\n');
+
+        let mut file = self.file.lock().unwrap_or_else(|e| e.into_inner());
+
+        // failing to log a secret doesn't affect the handshake
+        let _ = file.write_all(line.as_bytes());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_log_file_test() {
+        let path = std::env::temp_dir().join(format!(
+            "s2n-quic-dc-key-log-{}-{:?}.txt",
+            std::process::id(),
+            std::thread::current().id()
+        ));
+        let _ = fs::remove_file(&path);
+
+        let key_log = KeyLogFile::open(&path).unwrap();
+        key_log.export_secret(&[0x01, 0xab]);
+        key_log.export_secret(&[0xff; 4]);
+        drop(key_log);
+
+        let contents = fs::read_to_string(&path).unwrap();
+        let _ = fs::remove_file(&path);
+
+        assert_eq!(
+            contents,
+            format!("{LABEL} 01ab\n{LABEL} ffffffff\n"),
+            "each secret is appended on its own line"
+        );
+    }
+}
@@ -5,11 +5,11 @@ use crate::{
     credentials::{Credentials, Id},
     event,
     packet::{secret_control as control, Packet},
-    path::secret::{open, seal, stateless_reset},
+    path::secret::{key_log::KeyLog, open, seal, stateless_reset},
     stream::TransportFeatures,
 };
 use s2n_quic_core::{dc, time};
-use std::{net::SocketAddr, sync::Arc};
+use std::{net::SocketAddr, sync::Arc, time::Duration};
 
 mod cleaner;
 mod entry;
@@ -99,6 +99,33 @@ impl Map {
         self.store.needs_handshake(peer)
     }
 
+    /// Requests a new generation of credentials for this peer.
+    ///
+    /// `needs_handshake` will return true for the peer until the next handshake completes. Once
+    /// it does, new streams use the new credentials while the previous credentials continue to be
+    /// accepted for the rotation overlap period (see [`Self::set_rotation_overlap`]).
+    pub fn rotate(&self, peer: &SocketAddr) {
+        self.store.rotate(peer)
+    }
+
+    /// Sets how long credentials remain valid after they are replaced by a newer handshake.
+    ///
+    /// Peers that are still using the previous credentials during this period are reported with
+    /// the `StaleCredentialsReceived` event. Replaced credentials are evicted by the background
+    /// cleaner, which runs roughly once a minute, so the overlap is rounded up to whole minutes.
+    /// The overlap never shortens the default grace period of 10 minutes.
+    pub fn set_rotation_overlap(&self, overlap: Duration) {
+        self.store.set_rotation_overlap(overlap)
+    }
+
+    /// Sets the [`KeyLog`] which receives the secret exported by each subsequent handshake.
+    ///
+    /// Logged secrets can decrypt all of the traffic protected by them, so this should only be
+    /// enabled while debugging. Passing `None` stops logging secrets.
+    pub fn set_key_log(&self, key_log: Option<Arc<dyn KeyLog>>) {
+        self.store.set_key_log(key_log)
+    }
+
     /// Gets the [`Peer`] entry for the given address
     ///
     /// NOTE: This function is used to track cache hit ratios so it
@@ -16,7 +16,11 @@ use std::{
     time::{Duration, Instant},
 };
 
-const EVICTION_CYCLES: u64 = if cfg!(test) { 0 } else { 10 };
+/// The default number of cycles a retired entry is retained for
+pub(super) const EVICTION_CYCLES: u64 = if cfg!(test) { 0 } else { 10 };
+
+/// The approximate period between cleaner cycles
+pub(super) const CYCLE_PERIOD: Duration = Duration::from_secs(60);
 
 pub struct Cleaner {
     should_stop: AtomicBool,
@@ -78,7 +82,7 @@ impl Cleaner {
                 if state.cleaner().should_stop.load(Ordering::Relaxed) {
                     break;
                 }
-                state.cleaner().clean(&state, EVICTION_CYCLES);
+                state.cleaner().clean(&state, state.eviction_cycles());
                 // pause the rest of the time to run once a minute, not twice a minute
                 std::thread::park_timeout(Duration::from_secs(60 - pause));
             })
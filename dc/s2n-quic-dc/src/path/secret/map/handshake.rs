@@ -91,6 +91,10 @@ impl dc::Path for HandshakingPath {
             )
             .unwrap();
 
+        if let Some(key_log) = self.map.store.key_log() {
+            key_log.export_secret(&material[..]);
+        }
+
         let cipher_suite = match session.cipher_suite() {
             s2n_quic_core::crypto::tls::CipherSuite::TLS_AES_128_GCM_SHA256 => {
                 schedule::Ciphersuite::AES_GCM_128_SHA256
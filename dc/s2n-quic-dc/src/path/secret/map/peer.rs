@@ -4,6 +4,7 @@
 use super::{dc, seal, Bidirectional, Credentials, Entry, Map, TransportFeatures};
 use std::sync::Arc;
 
+#[derive(Clone)]
 pub struct Peer {
     entry: Arc<Entry>,
     map: Map,
@@ -1,14 +1,17 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use super::{cleaner::Cleaner, stateless_reset, Entry, Store};
+use super::{
+    cleaner::{self, Cleaner},
+    stateless_reset, Entry, Store,
+};
 use crate::{
     credentials::{Credentials, Id},
     crypto,
     event::{self, EndpointPublisher as _, IntoEvent as _},
     fixed_map::{self, ReadGuard},
     packet::{secret_control as control, Packet},
-    path::secret::receiver,
+    path::secret::{key_log::KeyLog, receiver},
 };
 use s2n_quic_core::{
     inet::SocketAddress,
@@ -17,7 +20,10 @@ use s2n_quic_core::{
 use std::{
     hash::{BuildHasherDefault, Hasher},
     net::{Ipv4Addr, SocketAddr},
-    sync::{Arc, Mutex, Weak},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex, RwLock, Weak,
+    },
     time::Duration,
 };
 
@@ -34,7 +40,9 @@ mod tests;
 //
 // Previous entries can safely be removed after a grace period (EVICTION_TIME). EVICTION_TIME
 // is only needed because a stream/datagram might be opening/sent concurrently with the new
-// handshake (e.g., during regular rotation), and we don't want that to fail spuriously.
+// handshake (e.g., during regular rotation), and we don't want that to fail spuriously. The grace
+// period can be extended with `Map::set_rotation_overlap` for peers that are slow to switch over
+// to the new credentials.
 //
 // We also need to manage secrets for no longer existing peers. These are peers where typically the
 // underlying host has gone away and/or the address for it has changed. At 95% occupancy for the
@@ -52,6 +60,13 @@ where
 
     rehandshake_period: Duration,
 
+    // The number of cleaner cycles a retired entry remains valid for, after it was replaced by a
+    // newer handshake with the same peer.
+    eviction_cycles: AtomicU64,
+
+    // Receives the secret exported by each handshake, if secrets are being logged.
+    key_log: RwLock<Option<Arc<dyn KeyLog>>>,
+
     // peers is the most recent entry originating from a locally *or* remote initiated handshake.
     //
     // Handshakes use s2n-quic and the SocketAddr is the address of the handshake socket. Since
@@ -131,6 +146,8 @@ where
             max_capacity: capacity,
             // FIXME: Allow configuring the rehandshake_period.
             rehandshake_period: Duration::from_secs(3600 * 24),
+            eviction_cycles: AtomicU64::new(cleaner::EVICTION_CYCLES),
+            key_log: RwLock::new(None),
             peers: fixed_map::Map::with_capacity(capacity, Default::default()),
             ids: fixed_map::Map::with_capacity(capacity, Default::default()),
             requested_handshakes: Default::default(),
@@ -326,6 +343,10 @@ where
         &self.cleaner
     }
 
+    pub fn eviction_cycles(&self) -> u64 {
+        self.eviction_cycles.load(Ordering::Relaxed)
+    }
+
     // for tests
     #[allow(unused)]
     fn set_max_capacity(&mut self, new: usize) {
@@ -377,6 +398,30 @@ where
         self.requested_handshakes.pin().contains(peer)
     }
 
+    fn rotate(&self, peer: &SocketAddr) {
+        self.request_handshake(*peer);
+    }
+
+    fn set_rotation_overlap(&self, overlap: Duration) {
+        // round up so retired entries are never evicted before the overlap has elapsed, and never
+        // retain them for less than the default grace period
+        let period = cleaner::CYCLE_PERIOD.as_secs();
+        let cycles = (overlap.as_secs() + period - 1) / period;
+        let cycles = cycles.max(cleaner::EVICTION_CYCLES);
+        self.eviction_cycles.store(cycles, Ordering::Relaxed);
+    }
+
+    fn set_key_log(&self, key_log: Option<Arc<dyn KeyLog>>) {
+        *self.key_log.write().unwrap_or_else(|e| e.into_inner()) = key_log;
+    }
+
+    fn key_log(&self) -> Option<Arc<dyn KeyLog>> {
+        self.key_log
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
     fn on_new_path_secrets(&self, entry: Arc<Entry>) {
         let id = *entry.id();
         let peer = entry.peer();
@@ -631,6 +676,19 @@ where
                         gap,
                         forward_shift: (*creds.key_id).saturating_sub(starting),
                     });
+
+                // The peer hasn't switched to the credentials from the most recent handshake yet
+                if entry.retired_at().is_some() {
+                    self.subscriber().on_stale_credentials_received(
+                        event::builder::StaleCredentialsReceived {
+                            peer_address: SocketAddress::from(*entry.peer()).into_event(),
+                            credential_id: creds.id.into_event(),
+                            key_id: key_id.into_event(),
+                            age: entry.age(),
+                        },
+                    );
+                }
+
                 Ok(())
             }
             Err(receiver::Error::AlreadyExists) => {
@@ -45,6 +45,55 @@ fn cleans_after_delay() {
     assert!(map.ids.contains_key(third.id()));
 }
 
+#[test]
+fn rotation_overlap() {
+    let signer = stateless_reset::Signer::new(b"secret");
+    let subscriber = Arc::new(testing::Subscriber::no_snapshot());
+    let map = State::new(signer, 50, Clock, subscriber.clone());
+
+    // Stop background processing. We expect to manually invoke clean, and a background worker
+    // might interfere with our state.
+    map.cleaner.stop();
+
+    // rounded up to 2 cleaner cycles
+    map.set_rotation_overlap(Duration::from_secs(90));
+    assert_eq!(map.eviction_cycles(), 2);
+
+    let peer = (Ipv4Addr::LOCALHOST, 1).into();
+    let first = fake_entry(1);
+    let second = fake_entry(1);
+    map.test_insert(first.clone());
+
+    map.rotate(&peer);
+    assert!(map.needs_handshake(&peer));
+
+    // the new generation replaces the previous one for new streams
+    map.test_insert(second.clone());
+    assert!(!map.needs_handshake(&peer));
+    assert_eq!(map.get_by_addr_untracked(&peer).unwrap().id(), second.id());
+
+    // the previous generation is still accepted during the overlap
+    map.cleaner.clean(&map, map.eviction_cycles());
+    map.cleaner.clean(&map, map.eviction_cycles());
+    assert!(map.ids.contains_key(first.id()));
+
+    let stale = |subscriber: &testing::Subscriber| {
+        subscriber
+            .stale_credentials_received
+            .load(std::sync::atomic::Ordering::Relaxed)
+    };
+
+    map.check_dedup(&second, 0u8.into()).unwrap();
+    assert_eq!(stale(&subscriber), 0);
+    map.check_dedup(&first, 0u8.into()).unwrap();
+    assert_eq!(stale(&subscriber), 1);
+
+    // the previous generation is evicted once the overlap elapses
+    map.cleaner.clean(&map, map.eviction_cycles());
+    assert!(!map.ids.contains_key(first.id()));
+    assert!(map.ids.contains_key(second.id()));
+}
+
 #[test]
 fn thread_shutdown() {
     let signer = stateless_reset::Signer::new(b"secret");
@@ -6,7 +6,7 @@ use crate::{
     credentials::{Credentials, Id},
     fixed_map::ReadGuard,
     packet::{secret_control as control, Packet, WireVersion},
-    path::secret::{receiver, stateless_reset},
+    path::secret::{key_log::KeyLog, receiver, stateless_reset},
 };
 use core::time::Duration;
 use s2n_codec::EncoderBuffer;
@@ -29,6 +29,14 @@ pub trait Store: 'static + Send + Sync {
 
     fn needs_handshake(&self, peer: &SocketAddr) -> bool;
 
+    fn rotate(&self, peer: &SocketAddr);
+
+    fn set_rotation_overlap(&self, overlap: Duration);
+
+    fn set_key_log(&self, key_log: Option<Arc<dyn KeyLog>>);
+
+    fn key_log(&self) -> Option<Arc<dyn KeyLog>>;
+
     fn get_by_addr_untracked(&self, peer: &SocketAddr) -> Option<ReadGuard<Arc<Entry>>>;
 
     fn get_by_addr_tracked(&self, peer: &SocketAddr) -> Option<ReadGuard<Arc<Entry>>>;
@@ -1,7 +1,7 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use core::ops;
+use core::{fmt, ops, time::Duration};
 use crossbeam_channel as mpmc;
 use std::{
     mem::ManuallyDrop,
@@ -9,13 +9,15 @@ use std::{
         atomic::{AtomicUsize, Ordering},
         Arc,
     },
+    time::Instant,
 };
-use tracing::{info, trace};
+use tracing::{debug, info, trace};
 
 pub struct Pool<T: 'static + Send> {
-    release: mpmc::Sender<T>,
-    acquire: mpmc::Receiver<T>,
+    release: mpmc::Sender<Idle<T>>,
+    acquire: mpmc::Receiver<Idle<T>>,
     stats: Option<Arc<Stats>>,
+    health: Option<Arc<HealthCheck<T>>>,
 }
 
 impl<T: 'static + Send> Clone for Pool<T> {
@@ -25,6 +27,7 @@ impl<T: 'static + Send> Clone for Pool<T> {
             release: self.release.clone(),
             acquire: self.acquire.clone(),
             stats: self.stats.clone(),
+            health: self.health.clone(),
         }
     }
 }
@@ -44,6 +47,7 @@ impl<T: 'static + Send> Pool<T> {
             release,
             acquire,
             stats: None,
+            health: None,
         };
 
         if std::env::var("DC_QUIC_POOL_METRICS").is_ok() {
@@ -54,19 +58,76 @@ impl<T: 'static + Send> Pool<T> {
                 let hits = stats.hits.load(Ordering::Relaxed);
                 let misses = stats.misses.load(Ordering::Relaxed);
                 let errors = stats.errors.load(Ordering::Relaxed);
+                let evictions = stats.evictions.load(Ordering::Relaxed);
                 let hit_ratio = hits as f64 / (hits + misses) as f64 * 100.0;
-                info!(hits, misses, errors, hit_ratio);
+                info!(hits, misses, errors, evictions, hit_ratio);
             });
         }
 
         pool
     }
 
+    /// Evicts unhealthy entries from the pool, as configured by the provided [`HealthCheck`]
+    ///
+    /// Entries are checked when they are acquired from the pool. Applications which want
+    /// unhealthy entries evicted while the pool is idle can call [`Pool::check_health`]
+    /// periodically.
+    #[inline]
+    pub fn with_health_check(mut self, health: HealthCheck<T>) -> Self {
+        self.health = Some(Arc::new(health));
+        self
+    }
+
     #[inline]
     pub fn get(&self) -> Option<Entry<T>> {
-        let entry = self.acquire.try_recv().ok()?;
-        let entry = Entry::new(entry, self.release.clone());
-        Some(entry)
+        self.get_at(Instant::now())
+    }
+
+    #[inline]
+    fn get_at(&self, now: Instant) -> Option<Entry<T>> {
+        loop {
+            let mut entry = self.acquire.try_recv().ok()?;
+
+            if let Some(health) = self.health.as_ref() {
+                if let Some(reason) = health.check(&mut entry, now) {
+                    if health.evict(&entry.value, reason, self.stats.as_deref()) {
+                        continue;
+                    }
+                }
+            }
+
+            return Some(Entry::new(entry, self));
+        }
+    }
+
+    /// Checks the health of all of the idle entries, evicting the unhealthy ones
+    ///
+    /// This does nothing if the pool wasn't configured with a [`HealthCheck`].
+    #[inline]
+    pub fn check_health(&self) {
+        self.check_health_at(Instant::now())
+    }
+
+    #[inline]
+    fn check_health_at(&self, now: Instant) {
+        let Some(health) = self.health.as_ref() else {
+            return;
+        };
+
+        // only check the entries that are currently idle so released entries aren't checked twice
+        for _ in 0..self.acquire.len() {
+            let Ok(mut entry) = self.acquire.try_recv() else {
+                break;
+            };
+
+            if let Some(reason) = health.check(&mut entry, now) {
+                if health.evict(&entry.value, reason, self.stats.as_deref()) {
+                    continue;
+                }
+            }
+
+            let _ = self.release.try_send(entry);
+        }
     }
 
     #[inline]
@@ -91,7 +152,7 @@ impl<T: 'static + Send> Pool<T> {
 
             let entry = entry?;
 
-            let entry = Entry::new(entry, self.release.clone());
+            let entry = Entry::new(Idle::new(entry, Instant::now()), self);
             if let Some(stats) = self.stats.as_ref() {
                 stats.misses.fetch_add(1, Ordering::Relaxed);
             }
@@ -101,23 +162,218 @@ impl<T: 'static + Send> Pool<T> {
     }
 }
 
+/// The reason an entry is being evicted from the pool
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Eviction {
+    /// The entry failed a periodic probe
+    ProbeFailed,
+    /// The entry was idle for longer than the configured maximum
+    IdleAge,
+    /// The entry reported more errors than the configured maximum
+    Errors,
+}
+
+type Probe<T> = Box<dyn Fn(&T) -> bool + Send + Sync>;
+type OnEvict<T> = Box<dyn Fn(&T, Eviction) -> bool + Send + Sync>;
+
+/// Configures how unhealthy entries are evicted from a [`Pool`]
+pub struct HealthCheck<T> {
+    probe_interval: Option<Duration>,
+    probe: Option<Probe<T>>,
+    max_idle_age: Option<Duration>,
+    max_errors: Option<u32>,
+    on_evict: Option<OnEvict<T>>,
+}
+
+impl<T> Default for HealthCheck<T> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            probe_interval: None,
+            probe: None,
+            max_idle_age: None,
+            max_errors: None,
+            on_evict: None,
+        }
+    }
+}
+
+impl<T> fmt::Debug for HealthCheck<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("HealthCheck")
+            .field("probe_interval", &self.probe_interval)
+            .field("max_idle_age", &self.max_idle_age)
+            .field("max_errors", &self.max_errors)
+            .finish()
+    }
+}
+
+impl<T> HealthCheck<T> {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Probes entries which have been idle for at least `interval` since they were last probed,
+    /// evicting the entries for which `probe` returns `false`
+    #[inline]
+    pub fn with_probe<F>(mut self, interval: Duration, probe: F) -> Self
+    where
+        F: 'static + Fn(&T) -> bool + Send + Sync,
+    {
+        self.probe_interval = Some(interval);
+        self.probe = Some(Box::new(probe));
+        self
+    }
+
+    /// Evicts entries which have been idle in the pool for longer than `max_idle_age`
+    ///
+    #[inline]
+    pub fn with_max_idle_age(mut self, max_idle_age: Duration) -> Self {
+        self.max_idle_age = Some(max_idle_age);
+        self
+    }
+
+    /// Evicts entries which reported at least `max_errors` consecutive errors with
+    /// [`Entry::on_error`]
+    #[inline]
+    pub fn with_max_errors(mut self, max_errors: u32) -> Self {
+        self.max_errors = Some(max_errors.max(1));
+        self
+    }
+
+    /// Calls `on_evict` before an entry is evicted
+    ///
+    /// Returning `false` vetoes the eviction and keeps the entry in the pool.
+    #[inline]
+    pub fn on_evict<F>(mut self, on_evict: F) -> Self
+    where
+        F: 'static + Fn(&T, Eviction) -> bool + Send + Sync,
+    {
+        self.on_evict = Some(Box::new(on_evict));
+        self
+    }
+
+    #[inline]
+    fn is_expired(&self, entry: &Idle<T>, now: Instant) -> bool {
+        self.max_idle_age.map_or(false, |max| {
+            now.saturating_duration_since(entry.idle_since) >= max
+        })
+    }
+
+    #[inline]
+    fn has_too_many_errors(&self, errors: u32) -> bool {
+        self.max_errors.map_or(false, |max| errors >= max)
+    }
+
+    #[inline]
+    fn check(&self, entry: &mut Idle<T>, now: Instant) -> Option<Eviction> {
+        if self.is_expired(entry, now) {
+            return Some(Eviction::IdleAge);
+        }
+
+        if let (Some(probe), Some(interval)) = (self.probe.as_ref(), self.probe_interval) {
+            if now.saturating_duration_since(entry.last_probe) >= interval {
+                entry.last_probe = now;
+                if !probe(&entry.value) {
+                    return Some(Eviction::ProbeFailed);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Returns `true` if the entry should be dropped
+    #[inline]
+    fn evict(&self, entry: &T, reason: Eviction, stats: Option<&Stats>) -> bool {
+        if let Some(on_evict) = self.on_evict.as_ref() {
+            if !on_evict(entry, reason) {
+                debug!(?reason, "eviction vetoed");
+                return false;
+            }
+        }
+
+        if let Some(stats) = stats {
+            stats.evictions.fetch_add(1, Ordering::Relaxed);
+        }
+        debug!(?reason, "evict");
+        true
+    }
+}
+
 #[derive(Default)]
 struct Stats {
     hits: AtomicUsize,
     misses: AtomicUsize,
     errors: AtomicUsize,
+    evictions: AtomicUsize,
+}
+
+/// An entry which is sitting idle in the pool
+struct Idle<T> {
+    value: T,
+    idle_since: Instant,
+    /// The last time the entry was probed, or when it was created
+    last_probe: Instant,
+    errors: u32,
+}
+
+impl<T> Idle<T> {
+    #[inline]
+    fn new(value: T, now: Instant) -> Self {
+        Self {
+            value,
+            idle_since: now,
+            last_probe: now,
+            errors: 0,
+        }
+    }
 }
 
 pub struct Entry<T: 'static + Send> {
     entry: ManuallyDrop<T>,
-    pool: mpmc::Sender<T>,
+    last_probe: Instant,
+    errors: u32,
+    pool: mpmc::Sender<Idle<T>>,
+    stats: Option<Arc<Stats>>,
+    health: Option<Arc<HealthCheck<T>>>,
 }
 
 impl<T: Send> Entry<T> {
     #[inline]
-    fn new(entry: T, pool: mpmc::Sender<T>) -> Self {
-        let entry = ManuallyDrop::new(entry);
-        Self { entry, pool }
+    fn new(entry: Idle<T>, pool: &Pool<T>) -> Self {
+        let Idle {
+            value,
+            last_probe,
+            errors,
+            ..
+        } = entry;
+        let entry = ManuallyDrop::new(value);
+        Self {
+            entry,
+            last_probe,
+            errors,
+            pool: pool.release.clone(),
+            stats: pool.stats.clone(),
+            health: pool.health.clone(),
+        }
+    }
+
+    /// Records an error with the entry
+    ///
+    /// The entry is evicted when it's released if it reached the configured maximum number of
+    /// errors.
+    #[inline]
+    pub fn on_error(&mut self) {
+        self.errors = self.errors.saturating_add(1);
+    }
+
+    /// Records a successful use of the entry, which resets the error count
+    #[inline]
+    pub fn on_success(&mut self) {
+        self.errors = 0;
     }
 }
 
@@ -141,7 +397,138 @@ impl<T: Send> Drop for Entry<T> {
     #[inline]
     fn drop(&mut self) {
         let socket = unsafe { ManuallyDrop::take(&mut self.entry) };
+
+        if let Some(health) = self.health.as_ref() {
+            if health.has_too_many_errors(self.errors)
+                && health.evict(&socket, Eviction::Errors, self.stats.as_deref())
+            {
+                return;
+            }
+        }
+
         trace!("release");
+        let mut socket = Idle::new(socket, Instant::now());
+        socket.last_probe = self.last_probe;
+        socket.errors = self.errors;
         let _ = self.pool.try_send(socket);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[test]
+    fn idle_age_test() {
+        let pool = Pool::new(4)
+            .with_health_check(HealthCheck::new().with_max_idle_age(Duration::from_millis(10)));
+
+        drop(pool.get_or_init(|| Ok::<_, ()>(1)).unwrap());
+        let now = Instant::now();
+        assert_eq!(*pool.get_at(now).unwrap(), 1);
+
+        assert!(pool.get_at(now + Duration::from_millis(20)).is_none());
+    }
+
+    #[test]
+    fn probe_test() {
+        let healthy = Arc::new(Mutex::new(vec![1, 2]));
+        let probes = Arc::new(AtomicUsize::new(0));
+        let health = HealthCheck::new().with_probe(Duration::from_secs(1), {
+            let healthy = healthy.clone();
+            let probes = probes.clone();
+            move |value: &u32| {
+                probes.fetch_add(1, Ordering::Relaxed);
+                healthy.lock().unwrap().contains(value)
+            }
+        });
+        let mut pool = Pool::new(4).with_health_check(health);
+        let stats = Arc::new(Stats::default());
+        pool.stats = Some(stats.clone());
+
+        let first = pool.get_or_init(|| Ok::<_, ()>(1)).unwrap();
+        let second = pool.get_or_init(|| Ok::<_, ()>(2)).unwrap();
+        drop(first);
+        drop(second);
+        let now = Instant::now();
+
+        // entries aren't probed until the interval has elapsed
+        pool.check_health_at(now);
+        assert_eq!(probes.load(Ordering::Relaxed), 0);
+
+        healthy.lock().unwrap().retain(|value| *value != 2);
+        let now = now + Duration::from_secs(1);
+        pool.check_health_at(now);
+        assert_eq!(probes.load(Ordering::Relaxed), 2);
+        assert_eq!(stats.evictions.load(Ordering::Relaxed), 1);
+
+        // the healthy entry was probed recently so it isn't probed again when acquired
+        let entry = pool.get_at(now).unwrap();
+        assert_eq!(*entry, 1);
+        assert_eq!(probes.load(Ordering::Relaxed), 2);
+        assert!(pool.get_at(now).is_none());
+    }
+
+    #[test]
+    fn error_eviction_test() {
+        let evicted = Arc::new(Mutex::new(vec![]));
+        let health = HealthCheck::new().with_max_errors(2).on_evict({
+            let evicted = evicted.clone();
+            move |value: &u32, reason| {
+                evicted.lock().unwrap().push((*value, reason));
+                true
+            }
+        });
+        let mut pool = Pool::new(4).with_health_check(health);
+        let stats = Arc::new(Stats::default());
+        pool.stats = Some(stats.clone());
+
+        let mut entry = pool.get_or_init(|| Ok::<_, ()>(1)).unwrap();
+        entry.on_error();
+        drop(entry);
+
+        // errors are tracked across releases until the entry succeeds
+        let mut entry = pool.get().unwrap();
+        entry.on_error();
+        entry.on_success();
+        entry.on_error();
+        drop(entry);
+
+        let mut entry = pool.get().unwrap();
+        entry.on_error();
+        drop(entry);
+
+        assert!(pool.get().is_none());
+        assert_eq!(*evicted.lock().unwrap(), [(1, Eviction::Errors)]);
+        assert_eq!(stats.evictions.load(Ordering::Relaxed), 1);
+    }
+
+    fn vetoing_health_check() -> HealthCheck<u32> {
+        HealthCheck::new()
+            .with_max_errors(1)
+            .with_probe(Duration::from_secs(60), |_| false)
+            .on_evict(|value, _reason| *value != 1)
+    }
+
+    #[test]
+    fn veto_test() {
+        let pool = Pool::new(4).with_health_check(vetoing_health_check());
+
+        let mut entry = pool.get_or_init(|| Ok::<_, ()>(1)).unwrap();
+        entry.on_error();
+        drop(entry);
+
+        // the eviction was vetoed so the entry remains in the pool
+        assert_eq!(*pool.get().unwrap(), 1);
+
+        let health = vetoing_health_check();
+        let now = Instant::now();
+        let mut entry = Idle::new(1, now);
+        assert_eq!(health.check(&mut entry, now), None);
+        let now = now + Duration::from_secs(60);
+        assert_eq!(health.check(&mut entry, now), Some(Eviction::ProbeFailed));
+        assert!(!health.evict(&entry.value, Eviction::ProbeFailed, None));
+        assert!(health.evict(&2, Eviction::ProbeFailed, None));
+    }
+}
@@ -164,6 +164,12 @@ where
         self.read.read_into(out_buf).await
     }
 
+    /// Waits for the first packet from the peer
+    #[inline]
+    pub(crate) async fn peer_activity(&self) {
+        self.write.peer_activity().await
+    }
+
     #[inline]
     pub fn split(&mut self) -> (&mut Reader<Sub>, &mut Writer<Sub>) {
         (&mut self.read, &mut self.write)
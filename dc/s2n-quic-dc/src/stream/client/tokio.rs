@@ -7,12 +7,290 @@ use crate::{
     stream::{
         application::Stream,
         endpoint,
-        environment::tokio::{self as env, Environment},
+        environment::{
+            tokio::{self as env, Environment},
+            Environment as _,
+        },
         socket::Protocol,
     },
 };
-use std::{io, net::SocketAddr};
+use core::time::Duration;
+use s2n_quic_core::time::{Clock as _, Timestamp};
+use std::{
+    collections::HashMap,
+    io,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+};
 use tokio::net::TcpStream;
+use tracing::debug;
+
+/// Configures the transport selection for streams opened by a [`Connector`]
+#[derive(Clone, Debug, Default)]
+pub struct Builder {
+    protocol: Option<Protocol>,
+    fallback: Option<Fallback>,
+}
+
+impl Builder {
+    /// Opens streams over UDP (the default)
+    #[inline]
+    pub fn udp(mut self) -> Self {
+        self.protocol = Some(Protocol::Udp);
+        self
+    }
+
+    /// Opens streams over TCP
+    #[inline]
+    pub fn tcp(mut self) -> Self {
+        self.protocol = Some(Protocol::Tcp);
+        self
+    }
+
+    /// Opens streams over the provided transport protocol
+    #[inline]
+    pub fn protocol(mut self, protocol: Protocol) -> Self {
+        self.protocol = Some(protocol);
+        self
+    }
+
+    /// Falls back to TCP when UDP streams can't be opened, as configured by the provided policy
+    ///
+    /// The fallback only applies when UDP is the selected protocol.
+    #[inline]
+    pub fn with_tcp_fallback(mut self, fallback: Fallback) -> Self {
+        self.fallback = Some(fallback);
+        self
+    }
+
+    #[inline]
+    pub fn build(self) -> Connector {
+        let protocol = self.protocol.unwrap_or(Protocol::Udp);
+        let fallback = self
+            .fallback
+            .filter(|_| protocol == Protocol::Udp)
+            .map(|policy| {
+                Arc::new(FallbackState {
+                    policy,
+                    tcp_peers: Default::default(),
+                })
+            });
+
+        Connector { protocol, fallback }
+    }
+}
+
+/// The policy for falling back from UDP to TCP
+#[derive(Clone, Debug)]
+pub struct Fallback {
+    udp_timeout: Duration,
+    retry_udp_after: Duration,
+    tcp_port: Option<u16>,
+    should_fallback: fn(&io::Error) -> bool,
+}
+
+impl Default for Fallback {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            udp_timeout: Duration::from_secs(1),
+            retry_udp_after: Duration::from_secs(60),
+            tcp_port: None,
+            should_fallback: |_error| true,
+        }
+    }
+}
+
+impl Fallback {
+    /// Sets the maximum amount of time to wait for the peer to respond on a new UDP stream
+    /// before falling back to TCP
+    ///
+    /// The stream is only returned to the application once a packet has been received from the
+    /// peer, which detects paths that silently drop UDP datagrams. Defaults to 1 second.
+    #[inline]
+    pub fn with_udp_timeout(mut self, timeout: Duration) -> Self {
+        self.udp_timeout = timeout;
+        self
+    }
+
+    /// Sets how long streams to a peer go directly over TCP after falling back
+    ///
+    /// Setting the duration to zero attempts UDP for every stream. Defaults to 60 seconds.
+    #[inline]
+    pub fn with_retry_udp_after(mut self, duration: Duration) -> Self {
+        self.retry_udp_after = duration;
+        self
+    }
+
+    /// Sets the port of the peer's TCP acceptor, if it differs from the UDP acceptor
+    #[inline]
+    pub fn with_tcp_port(mut self, port: u16) -> Self {
+        self.tcp_port = Some(port);
+        self
+    }
+
+    /// Sets the function which decides if a UDP error should fall back to TCP
+    ///
+    /// Errors which don't fall back are returned to the application. Timeouts are reported
+    /// with [`io::ErrorKind::TimedOut`]. By default, all errors fall back.
+    #[inline]
+    pub fn with_error_filter(mut self, should_fallback: fn(&io::Error) -> bool) -> Self {
+        self.should_fallback = should_fallback;
+        self
+    }
+
+    #[inline]
+    fn tcp_addr(&self, acceptor_addr: SocketAddr) -> SocketAddr {
+        let mut addr = acceptor_addr;
+        if let Some(port) = self.tcp_port {
+            addr.set_port(port);
+        }
+        addr
+    }
+}
+
+struct FallbackState {
+    policy: Fallback,
+    /// Peers which recently fell back to TCP along with the time to retry UDP
+    tcp_peers: Mutex<HashMap<SocketAddr, Timestamp>>,
+}
+
+impl FallbackState {
+    #[inline]
+    fn is_tcp_preferred(&self, acceptor_addr: &SocketAddr, now: Timestamp) -> bool {
+        let mut tcp_peers = self.tcp_peers.lock().unwrap();
+
+        match tcp_peers.get(acceptor_addr) {
+            Some(retry_udp_at) if now < *retry_udp_at => true,
+            Some(_) => {
+                tcp_peers.remove(acceptor_addr);
+                false
+            }
+            None => false,
+        }
+    }
+
+    #[inline]
+    fn on_fallback(&self, acceptor_addr: SocketAddr, now: Timestamp) {
+        if self.policy.retry_udp_after.is_zero() {
+            return;
+        }
+
+        let mut tcp_peers = self.tcp_peers.lock().unwrap();
+        // clean up any peers which are ready to retry UDP
+        tcp_peers.retain(|_, retry_udp_at| now < *retry_udp_at);
+        tcp_peers.insert(acceptor_addr, now + self.policy.retry_udp_after);
+    }
+}
+
+/// Opens streams with the transport selection configured by a [`Builder`]
+#[derive(Clone)]
+pub struct Connector {
+    protocol: Protocol,
+    fallback: Option<Arc<FallbackState>>,
+}
+
+impl Default for Connector {
+    #[inline]
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+impl Connector {
+    #[inline]
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
+    /// Returns the configured transport protocol
+    #[inline]
+    pub fn protocol(&self) -> Protocol {
+        self.protocol
+    }
+
+    /// Opens a stream to the peer's acceptor
+    pub async fn connect<H, Sub>(
+        &self,
+        handshake: H,
+        acceptor_addr: SocketAddr,
+        env: &Environment<Sub>,
+        subscriber: Sub,
+    ) -> io::Result<Stream<Sub>>
+    where
+        H: core::future::Future<Output = io::Result<secret::map::Peer>>,
+        Sub: event::Subscriber + Clone,
+    {
+        match self.protocol {
+            Protocol::Tcp => connect_tcp(handshake, acceptor_addr, env, subscriber).await,
+            Protocol::Udp => {
+                if let Some(fallback) = self.fallback.as_ref() {
+                    connect_udp_with_fallback(fallback, handshake, acceptor_addr, env, subscriber)
+                        .await
+                } else {
+                    connect_udp(handshake, acceptor_addr, env, subscriber).await
+                }
+            }
+            Protocol::Other(name) => Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                format!("protocol {name:?} is not supported"),
+            )),
+        }
+    }
+}
+
+#[inline]
+async fn connect_udp_with_fallback<H, Sub>(
+    fallback: &FallbackState,
+    handshake: H,
+    acceptor_addr: SocketAddr,
+    env: &Environment<Sub>,
+    subscriber: Sub,
+) -> io::Result<Stream<Sub>>
+where
+    H: core::future::Future<Output = io::Result<secret::map::Peer>>,
+    Sub: event::Subscriber + Clone,
+{
+    // the secret is shared between both transports so only perform the handshake once
+    let peer = handshake.await?;
+    let policy = &fallback.policy;
+    let tcp_addr = policy.tcp_addr(acceptor_addr);
+
+    if fallback.is_tcp_preferred(&acceptor_addr, env.clock().get_time()) {
+        let handshake = core::future::ready(Ok(peer));
+        return connect_tcp(handshake, tcp_addr, env, subscriber).await;
+    }
+
+    let handshake = core::future::ready(Ok(peer.clone()));
+    let udp = async {
+        let stream = connect_udp(handshake, acceptor_addr, env, subscriber.clone()).await?;
+
+        // opening a UDP stream doesn't wait on the peer, so wait for the peer to respond to the
+        // prelude to detect paths which silently drop the datagrams
+        stream.peer_activity().await;
+
+        Ok(stream)
+    };
+
+    let error = match tokio::time::timeout(policy.udp_timeout, udp).await {
+        Ok(Ok(stream)) => return Ok(stream),
+        Ok(Err(error)) => error,
+        Err(_) => io::Error::new(
+            io::ErrorKind::TimedOut,
+            "the peer didn't respond to the UDP stream",
+        ),
+    };
+
+    if !(policy.should_fallback)(&error) {
+        return Err(error);
+    }
+
+    debug!(%acceptor_addr, %tcp_addr, %error, "falling back to TCP");
+    fallback.on_fallback(acceptor_addr, env.clock().get_time());
+
+    let handshake = core::future::ready(Ok(peer));
+    connect_tcp(handshake, tcp_addr, env, subscriber).await
+}
 
 /// Connects using the UDP transport layer
 #[inline]
@@ -149,3 +427,60 @@ where
         .await
         .map(|_| ())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use s2n_quic_core::time::clock::testing as time;
+
+    #[test]
+    fn fallback_retry_test() {
+        let connector = Connector::builder()
+            .with_tcp_fallback(Fallback::default().with_retry_udp_after(Duration::from_secs(1)))
+            .build();
+        let fallback = connector.fallback.as_ref().unwrap();
+
+        let addr: SocketAddr = "127.0.0.1:4433".parse().unwrap();
+        let other: SocketAddr = "127.0.0.1:4434".parse().unwrap();
+        let now = time::now();
+
+        assert!(!fallback.is_tcp_preferred(&addr, now));
+
+        fallback.on_fallback(addr, now);
+        assert!(fallback.is_tcp_preferred(&addr, now));
+        assert!(!fallback.is_tcp_preferred(&other, now));
+
+        // UDP is retried once the duration elapses
+        assert!(!fallback.is_tcp_preferred(&addr, now + Duration::from_secs(1)));
+        assert!(fallback.tcp_peers.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn fallback_disabled_retry_test() {
+        let connector = Connector::builder()
+            .with_tcp_fallback(Fallback::default().with_retry_udp_after(Duration::ZERO))
+            .build();
+        let fallback = connector.fallback.as_ref().unwrap();
+
+        let addr: SocketAddr = "127.0.0.1:4433".parse().unwrap();
+        let now = time::now();
+
+        fallback.on_fallback(addr, now);
+        assert!(!fallback.is_tcp_preferred(&addr, now));
+    }
+
+    #[test]
+    fn fallback_protocol_test() {
+        // the fallback only applies to UDP
+        let connector = Connector::builder()
+            .tcp()
+            .with_tcp_fallback(Fallback::default())
+            .build();
+        assert_eq!(connector.protocol(), Protocol::Tcp);
+        assert!(connector.fallback.is_none());
+
+        let fallback = Fallback::default().with_tcp_port(5555);
+        let addr: SocketAddr = "127.0.0.1:4433".parse().unwrap();
+        assert_eq!(fallback.tcp_addr(addr).port(), 5555);
+    }
+}
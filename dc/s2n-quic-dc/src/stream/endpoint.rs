@@ -245,6 +245,7 @@ where
             read_remote_port: remote_port.into(),
             write_remote_port: write_remote_port.into(),
             last_peer_activity: Default::default(),
+            peer_activity_waker: Default::default(),
             fixed,
             closed_halves: 0u8.into(),
             subscriber: shared::Subscriber {
@@ -136,6 +136,12 @@ where
     pub fn shutdown(&mut self) -> io::Result<()> {
         self.0.shutdown(ShutdownType::Explicit)
     }
+
+    /// Waits for the first packet from the peer
+    #[inline]
+    pub(crate) async fn peer_activity(&self) {
+        core::future::poll_fn(|cx| self.0.shared.common.poll_peer_activity(cx)).await
+    }
 }
 
 impl<Sub> Inner<Sub>
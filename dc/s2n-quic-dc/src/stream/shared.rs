@@ -10,10 +10,12 @@ use crate::{
         send::{application, shared as send},
     },
 };
+use atomic_waker::AtomicWaker;
 use core::{
     cell::UnsafeCell,
     ops,
     sync::atomic::{AtomicU16, AtomicU64, AtomicU8, Ordering},
+    task::{Context, Poll},
     time::Duration,
 };
 use s2n_quic_core::{
@@ -80,10 +82,15 @@ where
 
     #[inline]
     pub fn on_peer_activity(&self) {
-        self.last_peer_activity.fetch_max(
+        let prev = self.last_peer_activity.fetch_max(
             unsafe { self.clock.get_time().as_duration().as_micros() as _ },
             Ordering::Relaxed,
         );
+
+        // notify any tasks waiting for the first packet from the peer
+        if prev == 0 {
+            self.peer_activity_waker.wake();
+        }
     }
 }
 
@@ -168,6 +175,8 @@ where
     pub fixed: FixedValues,
     /// The last time we received a packet from the peer
     pub last_peer_activity: AtomicU64,
+    /// Notifies a task waiting for the first packet from the peer
+    pub peer_activity_waker: AtomicWaker,
     pub closed_halves: AtomicU8,
     pub subscriber: Subscriber<Sub>,
     pub clock: Clk,
@@ -193,6 +202,25 @@ where
         Ok(())
     }
 
+    /// Returns `Ready` once a packet has been received from the peer
+    #[inline]
+    pub fn poll_peer_activity(&self, cx: &mut Context) -> Poll<()> {
+        ensure!(
+            self.last_peer_activity.load(Ordering::Relaxed) == 0,
+            Poll::Ready(())
+        );
+
+        self.peer_activity_waker.register(cx.waker());
+
+        // check again in case the peer activity was recorded before the waker was registered
+        ensure!(
+            self.last_peer_activity.load(Ordering::Relaxed) == 0,
+            Poll::Ready(())
+        );
+
+        Poll::Pending
+    }
+
     #[inline]
     pub fn publisher(&self) -> event::ConnectionPublisherSubscriber<Sub> {
         self.publisher_with_timestamp(self.clock.get_time())
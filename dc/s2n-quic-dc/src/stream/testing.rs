@@ -66,19 +66,31 @@ impl Client {
 
         let subscriber = (self.subscriber(), event::tracing::Subscriber::default());
 
-        match server.protocol {
-            Protocol::Tcp => {
-                stream_client::connect_tcp(handshake, server.local_addr, &self.env, subscriber)
-                    .await
-            }
-            Protocol::Udp => {
-                stream_client::connect_udp(handshake, server.local_addr, &self.env, subscriber)
-                    .await
-            }
-            Protocol::Other(name) => {
-                todo!("protocol {name:?} not implemented")
-            }
-        }
+        stream_client::Connector::builder()
+            .protocol(server.protocol)
+            .build()
+            .connect(handshake, server.local_addr, &self.env, subscriber)
+            .await
+    }
+
+    /// Connects to the server with the provided connector
+    ///
+    /// `acceptor_addr` overrides the server's address, which allows the connector to be pointed
+    /// at an address which doesn't respond.
+    pub async fn connect_with<S: AsRef<server::Handle>>(
+        &self,
+        server: &S,
+        connector: &stream_client::Connector,
+        acceptor_addr: SocketAddr,
+    ) -> io::Result<Stream<Subscriber>> {
+        let server = server.as_ref();
+        let handshake = async { self.handshake_with(server) };
+
+        let subscriber = (self.subscriber(), event::tracing::Subscriber::default());
+
+        connector
+            .connect(handshake, acceptor_addr, &self.env, subscriber)
+            .await
     }
 
     pub async fn connect_tcp_with<S: AsRef<server::Handle>>(
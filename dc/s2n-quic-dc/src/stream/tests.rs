@@ -2,4 +2,5 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod accept_queue;
+mod fallback;
 mod key_update;
@@ -0,0 +1,99 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    stream::{
+        client::tokio::{Connector, Fallback},
+        socket::Protocol,
+        testing::{Client, Server},
+    },
+    testing::init_tracing,
+};
+use std::{io, net::SocketAddr, time::Duration};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tracing::{info_span, Instrument};
+
+async fn check_stream(
+    client: &Client,
+    server: &Server,
+    connector: &Connector,
+    acceptor_addr: SocketAddr,
+) -> io::Result<Protocol> {
+    let (protocol, ()) = tokio::try_join!(
+        async {
+            let mut a = client
+                .connect_with(server, connector, acceptor_addr)
+                .await?;
+            let protocol = a.protocol();
+
+            a.write_all(b"testing").await?;
+            a.shutdown().await?;
+
+            let mut buffer = vec![];
+            a.read_to_end(&mut buffer).await?;
+            assert_eq!(buffer, b"testing");
+
+            Ok(protocol)
+        }
+        .instrument(info_span!("client")),
+        async {
+            let (mut b, _) = server.accept().await?;
+            let mut buffer = vec![];
+            b.read_to_end(&mut buffer).await?;
+            assert_eq!(buffer, b"testing");
+
+            b.write_all(&buffer).await?;
+            b.shutdown().await?;
+
+            Ok(())
+        }
+        .instrument(info_span!("server"))
+    )?;
+
+    Ok(protocol)
+}
+
+/// Checks that streams stay on UDP when the peer responds
+#[tokio::test]
+async fn udp_test() {
+    init_tracing();
+
+    let client = Client::default();
+    let server = Server::udp().build();
+    let connector = Connector::builder()
+        .with_tcp_fallback(Fallback::default())
+        .build();
+
+    let protocol = check_stream(&client, &server, &connector, server.local_addr())
+        .await
+        .unwrap();
+    assert_eq!(protocol, Protocol::Udp);
+}
+
+/// Checks that streams fall back to TCP when the UDP datagrams are silently dropped
+#[tokio::test]
+async fn blackholed_udp_test() {
+    init_tracing();
+
+    let client = Client::default();
+    let server = Server::tcp().build();
+
+    // the socket is never read so all of the datagrams sent to it are dropped
+    let blackhole = tokio::net::UdpSocket::bind("127.0.0.1:0").await.unwrap();
+    let blackhole_addr = blackhole.local_addr().unwrap();
+
+    let connector = Connector::builder()
+        .with_tcp_fallback(
+            Fallback::default()
+                .with_udp_timeout(Duration::from_millis(100))
+                .with_tcp_port(server.local_addr().port()),
+        )
+        .build();
+
+    for _ in 0..2 {
+        let protocol = check_stream(&client, &server, &connector, blackhole_addr)
+            .await
+            .unwrap();
+        assert_eq!(protocol, Protocol::Tcp);
+    }
+}
@@ -71,6 +71,8 @@ OPTIONS=(
   --allowlist-type 'dissector_handle_t'
   --allowlist-type 'ftenum_t'
   --allowlist-type 'field_display_e'
+  --allowlist-type 'expert_field'
+  --allowlist-type 'ei_register_info'
   --allowlist-var 'COL_PROTOCOL'
   --allowlist-var 'ENC_BIG_ENDIAN'
   --allowlist-var 'DESEGMENT_ONE_MORE_SEGMENT'
@@ -112,6 +114,22 @@ bindgen \
   --allowlist-function 'col_clear' \
   --allowlist-function 'find_or_create_conversation' \
   --allowlist-function 'conversation_set_dissector' \
+  --allowlist-function 'prefs_register_protocol' \
+  --allowlist-function 'prefs_register_filename_preference' \
+  --allowlist-function 'expert_register_protocol' \
+  --allowlist-function 'expert_register_field_array' \
+  --allowlist-function 'expert_add_info' \
+  --allowlist-function 'wmem_alloc' \
+  --allowlist-function 'register_tap' \
+  --allowlist-function 'tap_queue_packet' \
+  --allowlist-function 'stats_tree_register_plugin' \
+  --allowlist-function 'stats_tree_create_node' \
+  --allowlist-function 'stats_tree_manip_node_int' \
+  --allowlist-var 'PI_SEQUENCE' \
+  --allowlist-var 'PI_MALFORMED' \
+  --allowlist-var 'PI_NOTE' \
+  --allowlist-var 'PI_WARN' \
+  --allowlist-var 'PI_ERROR' \
   wrapper.h \
   -o src/wireshark_sys/full.rs \
   -- ${INCLUDES[@]}
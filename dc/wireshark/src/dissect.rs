@@ -4,6 +4,7 @@
 use crate::{
     buffer::Buffer,
     field::Registration,
+    keylog,
     value::Parsed,
     wireshark::{Info, Item, Node},
 };
@@ -151,6 +152,12 @@ pub fn stream<T: Node>(
     let auth_tag = buffer.consume_bytes(16)?;
     auth_tag.record(buffer, tree, fields.auth_tag);
 
+    if let Some(decrypted) = keylog::decrypt_stream(&buffer.packet[tag.offset..buffer.offset]) {
+        payload
+            .with(&decrypted[..])
+            .record(buffer, tree, fields.decrypted_payload);
+    }
+
     info.append_delim(" ");
     info.append(format_args!(
         "Stream(ID={}, PN={},{control_info} LEN={})",
@@ -476,6 +483,12 @@ pub fn datagram<T: Node>(
     let auth_tag = buffer.consume_bytes(16)?;
     auth_tag.record(buffer, tree, fields.auth_tag);
 
+    if let Some(decrypted) = keylog::decrypt_datagram(&buffer.packet[tag.offset..buffer.offset]) {
+        payload
+            .with(&decrypted[..])
+            .record(buffer, tree, fields.decrypted_payload);
+    }
+
     info.append_delim(" ");
     if let Some(pn) = packet_number {
         info.append(format_args!(
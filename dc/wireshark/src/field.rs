@@ -47,6 +47,7 @@ pub struct Registration {
     pub application_header: i32,
     pub control_data: i32,
     pub payload: i32,
+    pub decrypted_payload: i32,
     pub auth_tag: i32,
 
     pub is_bidirectional: i32,
@@ -387,6 +388,15 @@ fn init() -> Registration {
         payload: protocol
             .field(c"Payload", c"dcquic.payload", BYTES, BASE_NONE, c"")
             .register(),
+        decrypted_payload: protocol
+            .field(
+                c"Decrypted payload",
+                c"dcquic.decrypted_payload",
+                BYTES,
+                BASE_NONE,
+                c"Payload decrypted with a secret from the key log file",
+            )
+            .register(),
         auth_tag: protocol
             .field(
                 c"Authentication tag",
@@ -0,0 +1,242 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Decryption of stream and datagram payloads with secrets from a key log file
+//!
+//! Each line of the key log file contains the TLS exporter secret of a dcQUIC handshake:
+//!
+//! ```text
+//! DCQUIC_EXPORT_SECRET <64 hex characters>
+//! ```
+//!
+//! Empty lines and lines starting with `#` are ignored. The path secret ID is derived from the
+//! exporter secret, so packets are matched to secrets without needing to know the endpoint type or
+//! ciphersuite of the handshake.
+//!
+//! The file is produced by configuring a `KeyLogFile` from the `s2n_quic_dc::path::secret::key_log`
+//! module on the endpoint's path secret map.
+
+use s2n_codec::DecoderBufferMut;
+use s2n_quic_dc::{
+    credentials::Id,
+    crypto::{self, awslc::open, KeyPhase, UninitSlice},
+    packet::{datagram, stream},
+    path::secret::schedule::{
+        endpoint, Ciphersuite, ExportSecret, Initiator, OpenUpdate, Secret, EXPORT_SECRET_LEN,
+    },
+};
+use std::{collections::HashMap, sync::RwLock};
+
+pub use s2n_quic_dc::path::secret::key_log::LABEL;
+
+const TAG_LEN: usize = 16;
+
+static KEYLOG: RwLock<Option<Keylog>> = RwLock::new(None);
+
+/// Replaces the secrets used to decrypt packets
+#[cfg_attr(test, allow(dead_code))]
+pub fn set(keylog: Option<Keylog>) {
+    *KEYLOG.write().unwrap_or_else(|e| e.into_inner()) = keylog;
+}
+
+/// Decrypts the payload of a stream packet, if its secret was loaded
+pub fn decrypt_stream(packet: &[u8]) -> Option<Vec<u8>> {
+    KEYLOG.read().ok()?.as_ref()?.decrypt_stream(packet)
+}
+
+/// Decrypts the payload of a datagram packet, if its secret was loaded
+pub fn decrypt_datagram(packet: &[u8]) -> Option<Vec<u8>> {
+    KEYLOG.read().ok()?.as_ref()?.decrypt_datagram(packet)
+}
+
+#[derive(Debug, Default)]
+pub struct Keylog {
+    // The endpoint type isn't recorded on the wire, so keep the secret for both sides
+    secrets: HashMap<Id, [Secret; 2]>,
+}
+
+impl Keylog {
+    pub fn parse(contents: &str) -> Self {
+        let mut keylog = Self::default();
+
+        for line in contents.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let Some((label, secret)) = line.split_once(' ') else {
+                continue;
+            };
+            if label != LABEL {
+                continue;
+            }
+            let Some(secret) = decode_hex(secret.trim()) else {
+                continue;
+            };
+
+            keylog.insert(&secret);
+        }
+
+        keylog
+    }
+
+    pub fn insert(&mut self, export_secret: &ExportSecret) {
+        // The ciphersuite isn't recorded either, so derive the ID with each of them
+        for ciphersuite in [
+            Ciphersuite::AES_GCM_128_SHA256,
+            Ciphersuite::AES_GCM_256_SHA384,
+        ] {
+            let secret = |endpoint| {
+                Secret::new(
+                    ciphersuite,
+                    s2n_quic_dc::SUPPORTED_VERSIONS[0],
+                    endpoint,
+                    export_secret,
+                )
+            };
+            let secrets = [
+                secret(endpoint::Type::Client),
+                secret(endpoint::Type::Server),
+            ];
+            self.secrets.insert(*secrets[0].id(), secrets);
+        }
+    }
+
+    pub fn decrypt_stream(&self, packet: &[u8]) -> Option<Vec<u8>> {
+        let mut buffer = packet.to_vec();
+        let (decoded, _) =
+            stream::decoder::Packet::decode(DecoderBufferMut::new(&mut buffer), (), TAG_LEN)
+                .ok()?;
+        let credentials = *decoded.credentials();
+        let secrets = self.secrets.get(&credentials.id)?;
+
+        for secret in secrets {
+            for initiator in [Initiator::Local, Initiator::Remote] {
+                let (_, _, opener, opener_ku) =
+                    secret.application_pair(credentials.key_id, initiator);
+                let opener = Opener::new(opener, opener_ku);
+                let (_, control) = secret.control_pair(credentials.key_id, initiator);
+
+                // decryption happens in place so start from a fresh copy for each attempt
+                let mut buffer = packet.to_vec();
+                let (mut decoded, _) = stream::decoder::Packet::decode(
+                    DecoderBufferMut::new(&mut buffer),
+                    (),
+                    TAG_LEN,
+                )
+                .ok()?;
+
+                if decoded.decrypt_in_place(&opener, &control).is_ok() {
+                    return Some(decoded.payload().to_vec());
+                }
+            }
+        }
+
+        None
+    }
+
+    pub fn decrypt_datagram(&self, packet: &[u8]) -> Option<Vec<u8>> {
+        let mut buffer = packet.to_vec();
+        let (decoded, _) =
+            datagram::decoder::Packet::decode(DecoderBufferMut::new(&mut buffer), (), TAG_LEN)
+                .ok()?;
+        let credentials = *decoded.credentials();
+        let secrets = self.secrets.get(&credentials.id)?;
+
+        let mut payload = vec![0; decoded.payload().len()];
+        for secret in secrets {
+            let opener = secret.application_opener(credentials.key_id);
+            let result = crypto::open::Application::decrypt(
+                &opener,
+                decoded.tag().key_phase(),
+                decoded.crypto_nonce(),
+                decoded.header(),
+                decoded.payload(),
+                decoded.auth_tag(),
+                UninitSlice::new(&mut payload),
+            );
+
+            if result.is_ok() {
+                return Some(payload);
+            }
+        }
+
+        None
+    }
+}
+
+/// Selects the key for the phase of the packet
+///
+/// Only the first key update is supported, which covers all but very long-lived streams.
+struct Opener {
+    openers: [open::Application; 2],
+}
+
+impl Opener {
+    fn new(opener: open::Application, ku: OpenUpdate) -> Self {
+        let (next, _) = ku.next();
+        Self {
+            openers: [opener, next],
+        }
+    }
+
+    fn get(&self, key_phase: KeyPhase) -> &open::Application {
+        match key_phase {
+            KeyPhase::Zero => &self.openers[0],
+            KeyPhase::One => &self.openers[1],
+        }
+    }
+}
+
+impl crypto::open::Application for Opener {
+    fn tag_len(&self) -> usize {
+        self.openers[0].tag_len()
+    }
+
+    fn decrypt(
+        &self,
+        key_phase: KeyPhase,
+        packet_number: u64,
+        header: &[u8],
+        payload_in: &[u8],
+        tag: &[u8],
+        payload_out: &mut UninitSlice,
+    ) -> crypto::open::Result {
+        // the underlying key doesn't perform rotation
+        self.get(key_phase).decrypt(
+            KeyPhase::Zero,
+            packet_number,
+            header,
+            payload_in,
+            tag,
+            payload_out,
+        )
+    }
+
+    fn decrypt_in_place(
+        &self,
+        key_phase: KeyPhase,
+        packet_number: u64,
+        header: &[u8],
+        payload_and_tag: &mut [u8],
+    ) -> crypto::open::Result {
+        self.get(key_phase)
+            .decrypt_in_place(KeyPhase::Zero, packet_number, header, payload_and_tag)
+    }
+}
+
+fn decode_hex(value: &str) -> Option<ExportSecret> {
+    let value = value.as_bytes();
+    if value.len() != EXPORT_SECRET_LEN * 2 {
+        return None;
+    }
+
+    let mut secret = [0; EXPORT_SECRET_LEN];
+    for (byte, chunk) in secret.iter_mut().zip(value.chunks_exact(2)) {
+        let chunk = core::str::from_utf8(chunk).ok()?;
+        *byte = u8::from_str_radix(chunk, 16).ok()?;
+    }
+
+    Some(secret)
+}
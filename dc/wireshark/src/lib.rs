@@ -4,8 +4,12 @@
 mod buffer;
 mod dissect;
 mod field;
+mod keylog;
 #[cfg(not(test))]
 mod plugin;
+mod stats;
+#[cfg(not(test))]
+mod tap;
 mod value;
 /// This wraps the underlying sys APIs in structures that support a cfg(test) mode that doesn't rely on Wireshark.
 mod wireshark;
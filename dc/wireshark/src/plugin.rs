@@ -1,7 +1,10 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{buffer::Buffer, dissect, field, field::Registration, wireshark::Item, wireshark_sys};
+use crate::{
+    buffer::Buffer, dissect, field, field::Registration, keylog, stats::Record, tap,
+    wireshark::Item, wireshark_sys,
+};
 use std::{ffi::CStr, sync::OnceLock};
 
 #[no_mangle]
@@ -35,7 +38,7 @@ static plugin_want_minor: std::ffi::c_int = env_version!("PLUGIN_MINOR_VERSION")
 #[no_mangle]
 pub extern "C" fn plugin_register() {
     static PLUGIN: wireshark_sys::proto_plugin = wireshark_sys::proto_plugin {
-        register_protoinfo: Some(field::proto_register),
+        register_protoinfo: Some(proto_register),
         register_handoff: Some(proto_reg_handoff),
     };
 
@@ -44,6 +47,39 @@ pub extern "C" fn plugin_register() {
     }
 }
 
+// Updated by Wireshark when the preference changes
+static mut KEYLOG_FILE: *const std::ffi::c_char = std::ptr::null();
+
+unsafe extern "C" fn proto_register() {
+    field::proto_register();
+    tap::register(field::get().protocol);
+
+    let module = wireshark_sys::prefs_register_protocol(field::get().protocol, Some(prefs_apply));
+    wireshark_sys::prefs_register_filename_preference(
+        module,
+        c"keylog_file".as_ptr(),
+        c"dcQUIC key log file".as_ptr(),
+        c"Path to a file containing DCQUIC_EXPORT_SECRET lines, used to decrypt payloads".as_ptr(),
+        std::ptr::addr_of_mut!(KEYLOG_FILE),
+        false,
+    );
+}
+
+unsafe extern "C" fn prefs_apply() {
+    let path = KEYLOG_FILE;
+    let keylog = if path.is_null() {
+        None
+    } else {
+        CStr::from_ptr(path)
+            .to_str()
+            .ok()
+            .filter(|path| !path.is_empty())
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .map(|contents| keylog::Keylog::parse(&contents))
+    };
+    keylog::set(keylog);
+}
+
 static STREAM_DISSECTOR: OnceLock<DissectorHandle> = OnceLock::new();
 
 struct DissectorHandle(wireshark_sys::dissector_handle_t);
@@ -122,9 +158,18 @@ unsafe extern "C" fn dissect_heur_udp<Ret: From<bool>>(
             &mut info,
             dissect::Protocol::Udp,
         ) else {
+            // the packet was already accepted by an earlier segment so this one is malformed,
+            // rather than belonging to some other protocol
+            if accepted_offset != 0 {
+                tap::malformed(pinfo, root);
+            }
             break;
         };
 
+        if let Some(record) = Record::parse(&packet[accepted_offset..buffer.offset]) {
+            tap::record(pinfo, root, record);
+        }
+
         accepted_offset = buffer.offset;
     }
 
@@ -183,6 +228,10 @@ unsafe extern "C" fn dissect_heur_tcp<Ret: From<bool>>(
             break;
         }
 
+        if let Some(record) = Record::parse(&packet[stream_frame_start..buffer.offset]) {
+            tap::record(pinfo, root, record);
+        }
+
         accepted_offset = buffer.offset;
 
         // If we successfully parsed, then mark this conversation as being dissected by us
@@ -0,0 +1,58 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use s2n_codec::{DecoderBufferMut, DecoderParameterizedValueMut};
+use s2n_quic_dc::{
+    credentials,
+    packet::{Kind, Packet},
+};
+
+/// Per-segment information passed to the dcQUIC tap
+///
+/// This is copied into packet-scoped Wireshark memory so it must not contain anything that
+/// needs to be dropped.
+#[derive(Clone, Copy, Debug)]
+pub struct Record {
+    pub kind: Kind,
+    pub path_secret_id: credentials::Id,
+    pub len: u32,
+    pub is_retransmission: bool,
+}
+
+impl Record {
+    /// Summarizes a segment which was already accepted by the dissector
+    pub fn parse(segment: &[u8]) -> Option<Self> {
+        // decoding may modify the buffer so work on a copy
+        let mut buffer = segment.to_vec();
+        let (packet, _) =
+            Packet::decode_parameterized_mut(16, DecoderBufferMut::new(&mut buffer)).ok()?;
+
+        let (path_secret_id, is_retransmission) = match &packet {
+            Packet::Stream(packet) => (packet.credentials().id, packet.is_retransmission()),
+            Packet::Datagram(packet) => (packet.credentials().id, false),
+            Packet::Control(packet) => (packet.credentials().id, false),
+            Packet::StaleKey(packet) => (*packet.credential_id(), false),
+            Packet::ReplayDetected(packet) => (*packet.credential_id(), false),
+            Packet::UnknownPathSecret(packet) => (*packet.credential_id(), false),
+        };
+
+        Some(Self {
+            kind: packet.kind(),
+            path_secret_id,
+            len: segment.len().try_into().ok()?,
+            is_retransmission,
+        })
+    }
+
+    #[cfg_attr(test, allow(dead_code))]
+    pub fn kind_name(&self) -> &'static std::ffi::CStr {
+        match self.kind {
+            Kind::Stream => c"Stream",
+            Kind::Datagram => c"Datagram",
+            Kind::Control => c"Control",
+            Kind::StaleKey => c"Stale Key",
+            Kind::ReplayDetected => c"Replay Detected",
+            Kind::UnknownPathSecret => c"Unknown Path Secret",
+        }
+    }
+}
@@ -0,0 +1,199 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Statistics and expert info registration
+//!
+//! Each accepted segment is queued to the `dcquic` tap, which feeds the `Statistics > dcQUIC`
+//! stats tree (also available in tshark with `-z dcquic,tree`).
+
+use crate::{stats::Record, wireshark_sys};
+use std::{
+    ffi::{c_int, c_void, CStr, CString},
+    ptr::{self, addr_of_mut},
+    sync::atomic::{AtomicI32, Ordering},
+};
+use wireshark_sys::{expert_field, packet_info, proto_item, stats_tree};
+
+const TAP_NAME: &CStr = c"dcquic";
+
+static TAP_ID: AtomicI32 = AtomicI32::new(-1);
+
+// EI_INIT
+static mut EI_MALFORMED: expert_field = expert_field { ei: -1, hf: -1 };
+static mut EI_RETRANSMISSION: expert_field = expert_field { ei: -1, hf: -1 };
+
+// Node IDs are assigned by Wireshark each time the stats tree is opened
+static PACKETS_NODE: AtomicI32 = AtomicI32::new(0);
+static PATH_SECRETS_NODE: AtomicI32 = AtomicI32::new(0);
+
+const PACKETS: &CStr = c"Packets by type";
+const RETRANSMISSIONS: &CStr = c"Retransmissions";
+const PATH_SECRETS: &CStr = c"Path secrets";
+const BYTES: &CStr = c"Bytes";
+
+pub unsafe fn register(protocol: c_int) {
+    let ei = Box::leak(Box::new([
+        expert_info(
+            addr_of_mut!(EI_MALFORMED),
+            c"dcquic.malformed",
+            wireshark_sys::PI_MALFORMED,
+            wireshark_sys::PI_ERROR,
+            c"Malformed dcQUIC packet",
+        ),
+        expert_info(
+            addr_of_mut!(EI_RETRANSMISSION),
+            c"dcquic.retransmission",
+            wireshark_sys::PI_SEQUENCE,
+            wireshark_sys::PI_NOTE,
+            c"Retransmitted stream packet",
+        ),
+    ]));
+    let module = wireshark_sys::expert_register_protocol(protocol);
+    wireshark_sys::expert_register_field_array(module, ei.as_mut_ptr(), ei.len() as _);
+
+    TAP_ID.store(
+        wireshark_sys::register_tap(TAP_NAME.as_ptr()),
+        Ordering::Relaxed,
+    );
+
+    wireshark_sys::stats_tree_register_plugin(
+        TAP_NAME.as_ptr(),
+        c"dcquic".as_ptr(),
+        c"dcQUIC/Packets".as_ptr(),
+        0,
+        Some(stats_packet),
+        Some(stats_init),
+        None,
+    );
+}
+
+fn expert_info(
+    id: *mut expert_field,
+    name: &'static CStr,
+    group: u32,
+    severity: u32,
+    summary: &'static CStr,
+) -> wireshark_sys::ei_register_info {
+    use wireshark_sys::{field_display_e_BASE_NONE as BASE_NONE, ftenum_FT_NONE as NONE};
+
+    wireshark_sys::ei_register_info {
+        ids: id,
+        eiinfo: wireshark_sys::expert_field_info {
+            name: name.as_ptr(),
+            group: group as _,
+            severity: severity as _,
+            summary: summary.as_ptr(),
+
+            // Following fields are filled/used by Wireshark internally.
+
+            // 0, NULL, 0, {0, {NULL, NULL, FT_NONE, BASE_NONE, NULL, 0, NULL, HFILL}}
+            id: 0,
+            protocol: ptr::null(),
+            orig_severity: 0,
+            hf_info: wireshark_sys::hf_register_info {
+                p_id: ptr::null_mut(),
+                hfinfo: wireshark_sys::_header_field_info {
+                    name: ptr::null(),
+                    abbrev: ptr::null(),
+                    type_: NONE,
+                    display: BASE_NONE as _,
+                    strings: ptr::null(),
+                    bitmask: 0,
+                    blurb: ptr::null(),
+                    id: -1,
+                    parent: 0,
+                    ref_type: wireshark_sys::hf_ref_type_HF_REF_TYPE_NONE,
+                    same_name_prev_id: -1,
+                    same_name_next: ptr::null_mut(),
+                },
+            },
+        },
+    }
+}
+
+/// Flags a segment which could not be parsed after the start of the packet was accepted
+pub unsafe fn malformed(pinfo: *mut packet_info, item: *mut proto_item) {
+    wireshark_sys::expert_add_info(pinfo, item, addr_of_mut!(EI_MALFORMED));
+}
+
+/// Flags and counts an accepted segment
+pub unsafe fn record(pinfo: *mut packet_info, item: *mut proto_item, record: Record) {
+    if record.is_retransmission {
+        wireshark_sys::expert_add_info(pinfo, item, addr_of_mut!(EI_RETRANSMISSION));
+    }
+
+    // taps run after the whole frame is dissected so the record needs to live in packet scope
+    let data = wireshark_sys::wmem_alloc((*pinfo).pool, core::mem::size_of::<Record>());
+    let data = data as *mut Record;
+    data.write(record);
+
+    wireshark_sys::tap_queue_packet(TAP_ID.load(Ordering::Relaxed), pinfo, data as *const c_void);
+}
+
+unsafe extern "C" fn stats_init(st: *mut stats_tree) {
+    let node = |name: &CStr, with_children| {
+        wireshark_sys::stats_tree_create_node(
+            st,
+            name.as_ptr(),
+            0,
+            wireshark_sys::_stat_node_datatype_STAT_DT_INT,
+            with_children,
+        )
+    };
+
+    PACKETS_NODE.store(node(PACKETS, true), Ordering::Relaxed);
+    node(RETRANSMISSIONS, false);
+    PATH_SECRETS_NODE.store(node(PATH_SECRETS, true), Ordering::Relaxed);
+}
+
+unsafe extern "C" fn stats_packet(
+    st: *mut stats_tree,
+    _pinfo: *mut packet_info,
+    _edt: *mut wireshark_sys::epan_dissect_t,
+    data: *const c_void,
+    _flags: wireshark_sys::tap_flags_t,
+) -> wireshark_sys::tap_packet_status {
+    let record = *(data as *const Record);
+
+    let increase = |name: &CStr, parent, with_children, value| {
+        wireshark_sys::stats_tree_manip_node_int(
+            wireshark_sys::_manip_node_mode_MN_INCREASE,
+            st,
+            name.as_ptr(),
+            parent,
+            with_children,
+            value,
+        )
+    };
+
+    increase(PACKETS, 0, true, 1);
+    increase(
+        record.kind_name(),
+        PACKETS_NODE.load(Ordering::Relaxed),
+        false,
+        1,
+    );
+
+    if record.is_retransmission {
+        increase(RETRANSMISSIONS, 0, false, 1);
+    }
+
+    // each path secret corresponds to a pair of peers, so this is the dcQUIC equivalent of a
+    // conversation
+    increase(PATH_SECRETS, 0, true, 1);
+    let Ok(name) = CString::new(format!("{:?}", record.path_secret_id)) else {
+        return wireshark_sys::tap_packet_status_TAP_PACKET_REDRAW;
+    };
+    let path_secret = increase(&name, PATH_SECRETS_NODE.load(Ordering::Relaxed), true, 1);
+    increase(
+        BYTES,
+        path_secret,
+        false,
+        record.len.try_into().unwrap_or(c_int::MAX),
+    );
+    if record.is_retransmission {
+        increase(RETRANSMISSIONS, path_secret, false, 1);
+    }
+
+    wireshark_sys::tap_packet_status_TAP_PACKET_REDRAW
+}
@@ -319,6 +319,150 @@ fn check_datagram_parse() {
         });
 }
 
+/// Encrypts `payload` into a datagram packet with a client secret derived from `export_secret`
+fn keylog_datagram(export_secret: &[u8; 32], payload: &[u8]) -> Vec<u8> {
+    use s2n_quic_dc::path::secret::schedule::{endpoint, Ciphersuite, Secret};
+
+    let secret = Secret::new(
+        Ciphersuite::AES_GCM_256_SHA384,
+        s2n_quic_dc::SUPPORTED_VERSIONS[0],
+        endpoint::Type::Client,
+        export_secret,
+    );
+    let credentials = credentials::Credentials {
+        id: *secret.id(),
+        key_id: VarInt::from_u8(3),
+    };
+    let key = secret.application_sealer(credentials.key_id);
+
+    let mut buffer = vec![0; 256];
+    let length = s2n_quic_dc::packet::datagram::encoder::encode(
+        EncoderBuffer::new(&mut buffer),
+        1234,
+        None,
+        None,
+        VarInt::ZERO,
+        &mut &[][..],
+        &(),
+        VarInt::new(payload.len() as u64).unwrap(),
+        &mut &payload[..],
+        &key,
+        &credentials,
+    );
+    buffer.truncate(length);
+    buffer
+}
+
+#[test]
+fn keylog_datagram_decrypt() {
+    let export_secret = [7; 32];
+    let sent_payload = b"hello from the keylog";
+    let packet = &keylog_datagram(&export_secret, sent_payload)[..];
+
+    let hex: String = export_secret.iter().map(|b| format!("{b:02x}")).collect();
+
+    // unrelated secrets can't decrypt the packet
+    let keylog = crate::keylog::Keylog::parse(&format!(
+        "# comment\n\n{} {}\n",
+        crate::keylog::LABEL,
+        "00".repeat(32)
+    ));
+    assert_eq!(keylog.decrypt_datagram(packet), None);
+
+    let keylog =
+        crate::keylog::Keylog::parse(&format!("# comment\n\n{} {hex}\n", crate::keylog::LABEL));
+    assert_eq!(
+        keylog.decrypt_datagram(packet).as_deref(),
+        Some(&sent_payload[..])
+    );
+}
+
+/// Checks that the secrets logged by an endpoint are loaded by the dissector
+#[test]
+fn keylog_file_round_trip() {
+    use s2n_quic_dc::path::secret::key_log::{KeyLog, KeyLogFile};
+
+    let export_secret = [9; 32];
+    let sent_payload = b"hello from the key log file";
+    let packet = &keylog_datagram(&export_secret, sent_payload)[..];
+
+    let path = std::env::temp_dir().join(format!(
+        "dcquic-wireshark-keylog-{}.txt",
+        std::process::id()
+    ));
+    let _ = std::fs::remove_file(&path);
+
+    let key_log = KeyLogFile::open(&path).unwrap();
+    key_log.export_secret(&[1; 32]);
+    key_log.export_secret(&export_secret);
+    drop(key_log);
+
+    let contents = std::fs::read_to_string(&path).unwrap();
+    let _ = std::fs::remove_file(&path);
+
+    let keylog = crate::keylog::Keylog::parse(&contents);
+    assert_eq!(
+        keylog.decrypt_datagram(packet).as_deref(),
+        Some(&sent_payload[..])
+    );
+}
+
+#[test]
+fn stats_record_retransmission() {
+    use crate::stats::Record;
+
+    let key = TestKey(KeyPhase::Zero);
+    let credentials = credentials::Credentials {
+        id: [1; 16].into(),
+        key_id: VarInt::from_u8(2),
+    };
+    let stream_id = stream::Id {
+        key_id: VarInt::from_u8(3),
+        is_reliable: true,
+        is_bidirectional: true,
+    };
+
+    let mut payload = Data::new(100);
+    let mut buffer = vec![
+        0;
+        100 + s2n_quic_dc::packet::stream::encoder::MAX_HEADER_LEN
+            + s2n_quic_dc::packet::stream::encoder::MAX_RETRANSMISSION_HEADER_LEN
+    ];
+    let length = s2n_quic_dc::packet::stream::encoder::encode(
+        EncoderBuffer::new(&mut buffer),
+        1234,
+        None,
+        stream_id,
+        VarInt::from_u8(4),
+        VarInt::ZERO,
+        VarInt::ZERO,
+        &mut &[][..],
+        VarInt::ZERO,
+        &(),
+        &mut payload,
+        &key,
+        &credentials,
+    );
+    let buffer = &mut buffer[..length];
+
+    let record = Record::parse(buffer).unwrap();
+    assert!(matches!(record.kind, packet::Kind::Stream));
+    assert_eq!(record.path_secret_id, credentials.id);
+    assert_eq!(record.len as usize, length);
+    assert!(!record.is_retransmission);
+
+    stream::decoder::Packet::retransmit(
+        s2n_codec::DecoderBufferMut::new(buffer),
+        stream::PacketSpace::Recovery,
+        VarInt::from_u8(10),
+        &key,
+    )
+    .unwrap();
+
+    let record = Record::parse(buffer).unwrap();
+    assert!(record.is_retransmission);
+}
+
 #[derive(Clone, Debug, bolero::TypeGenerator)]
 struct ControlPacket {
     credentials: s2n_quic_dc::credentials::Credentials,
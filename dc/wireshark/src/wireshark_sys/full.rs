@@ -673,8 +673,16 @@ pub type fvalue_t = _fvalue_t;
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
 pub struct expert_field {
-    _unused: [u8; 0],
+    pub ei: ::std::os::raw::c_int,
+    pub hf: ::std::os::raw::c_int,
 }
+#[allow(clippy::unnecessary_operation, clippy::identity_op)]
+const _: () = {
+    ["Size of expert_field"][::std::mem::size_of::<expert_field>() - 8usize];
+    ["Alignment of expert_field"][::std::mem::align_of::<expert_field>() - 4usize];
+    ["Offset of field: expert_field::ei"][::std::mem::offset_of!(expert_field, ei) - 0usize];
+    ["Offset of field: expert_field::hf"][::std::mem::offset_of!(expert_field, hf) - 4usize];
+};
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
 pub struct _protocol {
@@ -2816,6 +2824,231 @@ extern "C" {
         handle: dissector_handle_t,
     );
 }
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct pref_module {
+    _unused: [u8; 0],
+}
+pub type module_t = pref_module;
+extern "C" {
+    #[doc = " Register that a protocol has preferences.
+ @param id the value returned by \"proto_register_protocol()\" when
+                              the protocol was registered.
+ @param apply_cb callback routine that is called when preferences are
+                  applied. It may be NULL, which inhibits the callback.
+ @return a preferences module which can be used to register a user 'preference'"]
+    pub fn prefs_register_protocol(
+        id: ::std::os::raw::c_int,
+        apply_cb: ::std::option::Option<unsafe extern "C" fn()>,
+    ) -> *mut module_t;
+}
+extern "C" {
+    #[doc = " Register a preference with a file name (string) value.
+
+ File name preferences are basically like string preferences
+ except that the GUI gives the user the ability to browse for the
+ file.
+
+ @param module the preferences module returned by prefs_register_protocol() or
+               prefs_register_protocol_subtree()
+ @param name the preference's identifier. This is appended to the name of the
+             protocol, with a \".\" between them, to create a unique identifier.
+             The identifier should not include the protocol name, as
+             the preference file will already have it. Make sure that
+             only lower-case ASCII letters, numbers, underscores and
+             dots appear in the preference name.
+ @param title Field's title in the preferences dialog
+ @param description description to include in the preferences file
+                    and shown as tooltip in the GUI, or NULL
+ @param var pointer to the storage location that is updated when the
+                    field is changed in the preference dialog box. Note that
+           with string preferences the given pointer is overwritten
+           with a pointer to a new copy of the string during the
+           preference registration. The passed-in string may be
+           freed, but you must keep another pointer to the string
+           in order to free it
+ @param for_writing true to display a Save dialog, false to display an Open dialog."]
+    pub fn prefs_register_filename_preference(
+        module: *mut module_t,
+        name: *const ::std::os::raw::c_char,
+        title: *const ::std::os::raw::c_char,
+        description: *const ::std::os::raw::c_char,
+        var: *mut *const ::std::os::raw::c_char,
+        for_writing: bool,
+    );
+}
+pub const PI_SEQUENCE: u32 = 33554432;
+pub const PI_MALFORMED: u32 = 117440512;
+pub const PI_NOTE: u32 = 4194304;
+pub const PI_WARN: u32 = 6291456;
+pub const PI_ERROR: u32 = 8388608;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct expert_module {
+    _unused: [u8; 0],
+}
+pub type expert_module_t = expert_module;
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct expert_field_info {
+    pub name: *const ::std::os::raw::c_char,
+    pub group: ::std::os::raw::c_int,
+    pub severity: ::std::os::raw::c_int,
+    pub summary: *const ::std::os::raw::c_char,
+    pub id: ::std::os::raw::c_int,
+    pub protocol: *const ::std::os::raw::c_char,
+    pub orig_severity: ::std::os::raw::c_int,
+    pub hf_info: hf_register_info,
+}
+#[allow(clippy::unnecessary_operation, clippy::identity_op)]
+const _: () = {
+    ["Size of expert_field_info"][::std::mem::size_of::<expert_field_info>() - 128usize];
+    ["Alignment of expert_field_info"][::std::mem::align_of::<expert_field_info>() - 8usize];
+    ["Offset of field: expert_field_info::name"]
+        [::std::mem::offset_of!(expert_field_info, name) - 0usize];
+    ["Offset of field: expert_field_info::group"]
+        [::std::mem::offset_of!(expert_field_info, group) - 8usize];
+    ["Offset of field: expert_field_info::severity"]
+        [::std::mem::offset_of!(expert_field_info, severity) - 12usize];
+    ["Offset of field: expert_field_info::summary"]
+        [::std::mem::offset_of!(expert_field_info, summary) - 16usize];
+    ["Offset of field: expert_field_info::id"]
+        [::std::mem::offset_of!(expert_field_info, id) - 24usize];
+    ["Offset of field: expert_field_info::protocol"]
+        [::std::mem::offset_of!(expert_field_info, protocol) - 32usize];
+    ["Offset of field: expert_field_info::orig_severity"]
+        [::std::mem::offset_of!(expert_field_info, orig_severity) - 40usize];
+    ["Offset of field: expert_field_info::hf_info"]
+        [::std::mem::offset_of!(expert_field_info, hf_info) - 48usize];
+};
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct ei_register_info {
+    #[doc = "< written to by register() function"]
+    pub ids: *mut expert_field,
+    #[doc = "< the field info to be registered"]
+    pub eiinfo: expert_field_info,
+}
+#[allow(clippy::unnecessary_operation, clippy::identity_op)]
+const _: () = {
+    ["Size of ei_register_info"][::std::mem::size_of::<ei_register_info>() - 136usize];
+    ["Alignment of ei_register_info"][::std::mem::align_of::<ei_register_info>() - 8usize];
+    ["Offset of field: ei_register_info::ids"]
+        [::std::mem::offset_of!(ei_register_info, ids) - 0usize];
+    ["Offset of field: ei_register_info::eiinfo"]
+        [::std::mem::offset_of!(ei_register_info, eiinfo) - 8usize];
+};
+extern "C" {
+    #[doc = " Register a protocol's expert info. Returns a module which can be used to register\nexpert info fields."]
+    pub fn expert_register_protocol(id: ::std::os::raw::c_int) -> *mut expert_module_t;
+}
+extern "C" {
+    #[doc = " Register a expert field array.\n@param module the protocol handle from expert_register_protocol()\n@param ei the ei_register_info array\n@param num_records the number of records in exp"]
+    pub fn expert_register_field_array(
+        module: *mut expert_module_t,
+        ei: *mut ei_register_info,
+        num_records: ::std::os::raw::c_int,
+    );
+}
+extern "C" {
+    #[doc = " Add an expert info.\nAdd an expert info tree to a protocol item using registered expert info item\n@param pinfo Packet info of the currently processed packet. May be NULL if\npi is supplied\n@param pi Current protocol item (or NULL)\n@param eiindex The registered expert info item\n@return the newly added expert info item"]
+    pub fn expert_add_info(
+        pinfo: *mut packet_info,
+        pi: *mut proto_item,
+        eiindex: *mut expert_field,
+    ) -> *mut proto_item;
+}
+extern "C" {
+    #[doc = " Allocate the requested amount of memory in the given pool.\n\n@param allocator The allocator object to use to allocate the memory.\n@param size The amount of memory to allocate.\n@return A void pointer to the newly allocated memory."]
+    pub fn wmem_alloc(allocator: *mut wmem_allocator_t, size: usize)
+        -> *mut ::std::os::raw::c_void;
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct epan_dissect {
+    _unused: [u8; 0],
+}
+pub type epan_dissect_t = epan_dissect;
+pub type tap_flags_t = ::std::os::raw::c_uint;
+#[doc = "< Packet processed successfully, no need to redraw"]
+pub const tap_packet_status_TAP_PACKET_DONT_REDRAW: tap_packet_status = 0;
+#[doc = "< Packet processed successfully, need to redraw"]
+pub const tap_packet_status_TAP_PACKET_REDRAW: tap_packet_status = 1;
+#[doc = "< Packet processing failed, stop calling this tap"]
+pub const tap_packet_status_TAP_PACKET_FAILED: tap_packet_status = 2;
+pub type tap_packet_status = ::std::os::raw::c_uint;
+extern "C" {
+    pub fn register_tap(name: *const ::std::os::raw::c_char) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    pub fn tap_queue_packet(
+        tap_id: ::std::os::raw::c_int,
+        pinfo: *mut packet_info,
+        tap_specific_data: *const ::std::os::raw::c_void,
+    );
+}
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct _stats_tree {
+    _unused: [u8; 0],
+}
+pub type stats_tree = _stats_tree;
+pub type stat_tree_packet_cb = ::std::option::Option<
+    unsafe extern "C" fn(
+        arg1: *mut stats_tree,
+        arg2: *mut packet_info,
+        arg3: *mut epan_dissect_t,
+        arg4: *const ::std::os::raw::c_void,
+        flags: tap_flags_t,
+    ) -> tap_packet_status,
+>;
+pub type stat_tree_init_cb = ::std::option::Option<unsafe extern "C" fn(arg1: *mut stats_tree)>;
+pub type stat_tree_cleanup_cb = ::std::option::Option<unsafe extern "C" fn(arg1: *mut stats_tree)>;
+pub const _stat_node_datatype_STAT_DT_INT: _stat_node_datatype = 0;
+pub const _stat_node_datatype_STAT_DT_FLOAT: _stat_node_datatype = 1;
+pub type _stat_node_datatype = ::std::os::raw::c_uint;
+pub use self::_stat_node_datatype as stat_node_datatype;
+pub const _manip_node_mode_MN_INCREASE: _manip_node_mode = 0;
+pub const _manip_node_mode_MN_SET: _manip_node_mode = 1;
+pub const _manip_node_mode_MN_AVERAGE: _manip_node_mode = 2;
+pub const _manip_node_mode_MN_AVERAGE_NOTICK: _manip_node_mode = 3;
+pub const _manip_node_mode_MN_SET_FLAGS: _manip_node_mode = 4;
+pub const _manip_node_mode_MN_CLEAR_FLAGS: _manip_node_mode = 5;
+pub type _manip_node_mode = ::std::os::raw::c_uint;
+pub use self::_manip_node_mode as manip_node_mode;
+extern "C" {
+    #[doc = " Registers a new stats tree from a plugin\n@param tapname tap's name\n@param abbr tree abbr (used for tshark -z option)\n@param path tree display name in GUI menu and window (use \"/\" for sub menus)\n@param flags tap listener flags for per-packet callback\n@param packet per packet callback\n@param init tree initialization callback\n@param cleanup cleanup callback"]
+    pub fn stats_tree_register_plugin(
+        tapname: *const ::std::os::raw::c_char,
+        abbr: *const ::std::os::raw::c_char,
+        path: *const ::std::os::raw::c_char,
+        flags: ::std::os::raw::c_uint,
+        packet: stat_tree_packet_cb,
+        init: stat_tree_init_cb,
+        cleanup: stat_tree_cleanup_cb,
+    );
+}
+extern "C" {
+    #[doc = " Creates a node in the tree (to be used in the in init_cb)\n@param st the stats_tree in which to create it\n@param name the name of the new node\n@param parent_id the id of the parent_node (NULL for root)\n@param datatype datatype of the new node, INT or FLOAT.\n@param with_children TRUE if this node will have \"dynamically created\" children\n@return id of the new node"]
+    pub fn stats_tree_create_node(
+        st: *mut stats_tree,
+        name: *const ::std::os::raw::c_char,
+        parent_id: ::std::os::raw::c_int,
+        datatype: stat_node_datatype,
+        with_children: bool,
+    ) -> ::std::os::raw::c_int;
+}
+extern "C" {
+    #[doc = " Manipulates the value of the node whose name is given\nif the node does not exist yet it's created (with counter=1)\nusing parent_name as parent node (NULL for root).\nwith_children=TRUE to indicate that the created node will be a parent"]
+    pub fn stats_tree_manip_node_int(
+        mode: manip_node_mode,
+        st: *mut stats_tree,
+        name: *const ::std::os::raw::c_char,
+        parent_id: ::std::os::raw::c_int,
+        with_children: bool,
+        value: ::std::os::raw::c_int,
+    ) -> ::std::os::raw::c_int;
+}
 pub type __builtin_va_list = [__va_list_tag; 1usize];
 #[repr(C)]
 #[derive(Debug, Copy, Clone)]
@@ -1,2 +1,6 @@
 #include "epan/packet_info.h"
 #include "epan/conversation.h"
+#include "epan/prefs.h"
+#include "epan/expert.h"
+#include "epan/tap.h"
+#include "epan/stats_tree.h"
@@ -98,6 +98,93 @@ fn lossy_handshake() -> Result {
     Ok(())
 }
 
+#[test]
+fn wan_link() -> Result {
+    use io::turmoil::{Link, Network};
+
+    let mut sim = Builder::new()
+        .simulation_duration(core::time::Duration::from_secs(20))
+        .build();
+
+    // every host shares the same model so the link can be configured once
+    let network = Network::new(42);
+
+    sim.host("server", {
+        let network = network.clone();
+        move || {
+            let network = network.clone();
+            async move {
+                let io = io::turmoil::Builder::default()
+                    .with_address(bind_to(443))?
+                    .with_network(network)
+                    .build()?;
+
+                let mut server = Server::builder()
+                    .with_io(io)?
+                    .with_tls((CERT_PEM, KEY_PEM))?
+                    .with_event(events())?
+                    .start()?;
+
+                while let Some(mut connection) = server.accept().await {
+                    tokio::spawn(async move {
+                        while let Ok(Some(mut stream)) =
+                            connection.accept_bidirectional_stream().await
+                        {
+                            tokio::spawn(async move {
+                                while let Ok(Some(data)) = stream.receive().await {
+                                    stream.send(data).await.expect("stream should be open");
+                                }
+                            });
+                        }
+                    });
+                }
+
+                Ok(())
+            }
+        }
+    });
+
+    sim.client("client", async move {
+        network.set_link(
+            "client",
+            "server",
+            Link::new()
+                .with_latency(core::time::Duration::from_millis(75))
+                .with_jitter(core::time::Duration::from_millis(10))
+                .with_loss(0.05),
+        );
+
+        let io = io::turmoil::Builder::default()
+            .with_address(bind_to(1234))?
+            .with_network(network)
+            .build()?;
+
+        let client = Client::builder()
+            .with_io(io)?
+            .with_tls(CERT_PEM)?
+            .with_event(events())?
+            .start()?;
+
+        let server_addr: SocketAddr = (lookup("server"), 443).into();
+        let mut connection = client
+            .connect(Connect::new(server_addr).with_server_name("localhost"))
+            .await?;
+
+        let mut stream = connection.open_bidirectional_stream().await?;
+        stream.send(vec![1, 2, 3].into()).await?;
+        stream.finish()?;
+
+        let response = stream.receive().await?.unwrap();
+        assert_eq!(&response[..], &[1, 2, 3]);
+
+        Ok(())
+    });
+
+    sim.run()?;
+
+    Ok(())
+}
+
 pub fn events() -> event::tracing::Provider {
     use std::sync::Once;
 
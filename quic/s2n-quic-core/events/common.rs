@@ -120,6 +120,14 @@ impl<'a> core::fmt::Debug for ConnectionId<'a> {
     }
 }
 
+/// Serialized as a hex string, e.g. `"0x0a0b0c"`
+#[cfg(feature = "serde")]
+impl<'a> serde::Serialize for ConnectionId<'a> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&format_args!("{self:?}"))
+    }
+}
+
 macro_rules! impl_conn_id {
     ($name:ident) => {
         impl<'a> IntoEvent<builder::ConnectionId<'a>> for &'a crate::connection::id::$name {
@@ -161,6 +169,14 @@ impl<'a> core::fmt::Debug for SocketAddress<'a> {
     }
 }
 
+/// Serialized as a string, e.g. `"127.0.0.1:443"`
+#[cfg(feature = "serde")]
+impl<'a> serde::Serialize for SocketAddress<'a> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&format_args!("{self:?}"))
+    }
+}
+
 impl<'a> core::fmt::Display for SocketAddress<'a> {
     fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         match self {
@@ -371,6 +387,17 @@ enum Frame {
         len: u16,
     },
     DcStatelessResetTokens,
+    GoAway,
+    ResetStreamAt {
+        id: u64,
+        error_code: u64,
+        final_size: u64,
+        reliable_size: u64,
+    },
+    ExpiredStreamData {
+        id: u64,
+        offset: u64,
+    },
 }
 
 impl IntoEvent<builder::Frame> for &crate::frame::Padding {
@@ -581,6 +608,35 @@ impl<'a> IntoEvent<builder::Frame> for &crate::frame::DcStatelessResetTokens<'a>
     }
 }
 
+impl IntoEvent<builder::Frame> for &crate::frame::GoAway {
+    #[inline]
+    fn into_event(self) -> builder::Frame {
+        builder::Frame::GoAway {}
+    }
+}
+
+impl IntoEvent<builder::Frame> for &crate::frame::ResetStreamAt {
+    #[inline]
+    fn into_event(self) -> builder::Frame {
+        builder::Frame::ResetStreamAt {
+            id: self.stream_id.as_u64(),
+            error_code: self.application_error_code.as_u64(),
+            final_size: self.final_size.as_u64(),
+            reliable_size: self.reliable_size.as_u64(),
+        }
+    }
+}
+
+impl IntoEvent<builder::Frame> for &crate::frame::ExpiredStreamData {
+    #[inline]
+    fn into_event(self) -> builder::Frame {
+        builder::Frame::ExpiredStreamData {
+            id: self.stream_id.as_u64(),
+            offset: self.offset.as_u64(),
+        }
+    }
+}
+
 #[derive(Clone)]
 struct ConnectionCloseFrame<'a> {
     error_code: u64,
@@ -626,6 +682,20 @@ impl<'a> core::fmt::Debug for ConnectionCloseFrame<'a> {
     }
 }
 
+/// Serialized with the reason converted to a UTF-8 string, including invalid characters
+#[cfg(feature = "serde")]
+impl<'a> serde::Serialize for ConnectionCloseFrame<'a> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut s = serializer.serialize_struct("ConnectionCloseFrame", 3)?;
+        s.serialize_field("error_code", &self.error_code)?;
+        s.serialize_field("frame_type", &self.frame_type)?;
+        s.serialize_field("reason", &self.reason_lossy_utf8())?;
+        s.end()
+    }
+}
+
 enum StreamType {
     Bidirectional,
     Unidirectional,
@@ -789,6 +859,12 @@ enum DatagramDropReason {
     PathLimitExceeded,
     /// The peer initiated a connection migration without supplying enough connection IDs to use.
     InsufficientConnectionIds,
+    /// The connection attempt exceeded the endpoint's connection rate limit.
+    ConnectionRateLimited,
+    /// The connection attempt exceeded the endpoint's limit on inflight handshakes.
+    InflightHandshakeLimitExceeded,
+    /// The datagram exceeded the max_udp_payload_size advertised to the peer.
+    PayloadSizeExceeded,
 }
 
 struct MtuConfig {
@@ -909,6 +985,26 @@ enum RetryDiscardReason<'a> {
     InvalidIntegrityTag,
 }
 
+/// An operation performed by the TLS provider during the handshake
+enum TlsOperation {
+    /// Validating the certificate chain presented by the peer
+    CertificateValidation,
+    /// Signing the handshake transcript with the local private key
+    SignatureGeneration,
+    /// Verifying the peer's signature of the handshake transcript
+    SignatureVerification,
+    /// Decrypting a session ticket presented by a resuming client
+    TicketDecryption,
+}
+
+/// A strictness check which was relaxed by the compatibility policy
+enum CompatibilityRelaxation {
+    /// The peer omitted the `initial_source_connection_id` transport parameter
+    MissingInitialSourceConnectionId,
+    /// The server omitted the `original_destination_connection_id` transport parameter
+    MissingOriginalDestinationConnectionId,
+}
+
 enum MigrationDenyReason {
     BlockedPort,
     PortScopeChanged,
@@ -977,6 +1073,14 @@ impl CipherSuite {
     }
 }
 
+/// Serialized as the standard identifier, e.g. `"TLS_AES_128_GCM_SHA256"`
+#[cfg(feature = "serde")]
+impl serde::Serialize for CipherSuite {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
 enum PathChallengeStatus {
     Validated,
     Abandoned,
@@ -1014,6 +1118,16 @@ enum MtuUpdatedCause {
     LargerProbesLost,
 }
 
+/// A budget limiting the work performed when processing a single packet
+enum PacketBudget {
+    /// The number of frames in the packet
+    Frames,
+    /// The number of ACK ranges in all of the ACK frames in the packet
+    AckRanges,
+    /// The number of bytes in all of the CRYPTO frames in the packet
+    CryptoBytes,
+}
+
 /// A bandwidth delivery rate estimate with associated metadata
 struct RateSample {
     /// The length of the sampling interval
@@ -1055,3 +1169,25 @@ enum DcState {
     PathSecretsReady,
     Complete,
 }
+
+/// The reason a DATAGRAM frame was dropped locally
+enum DatagramFrameDropReason {
+    /// The datagram queue was at capacity
+    QueueFull,
+    /// The datagram was too large to fit in the available packet space
+    ExceedsPacketCapacity,
+    /// The datagram was larger than the maximum DATAGRAM frame size
+    ExceedsTransportLimits,
+}
+
+impl IntoEvent<builder::DatagramFrameDropReason> for crate::datagram::DropReason {
+    #[inline]
+    fn into_event(self) -> builder::DatagramFrameDropReason {
+        use builder::DatagramFrameDropReason as Reason;
+        match self {
+            Self::QueueFull => Reason::QueueFull,
+            Self::ExceedsPacketCapacity => Reason::ExceedsPacketCapacity,
+            Self::ExceedsTransportLimits => Reason::ExceedsTransportLimits,
+        }
+    }
+}
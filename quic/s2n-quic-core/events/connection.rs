@@ -199,6 +199,18 @@ struct PacketDropped<'a> {
     reason: PacketDropReason<'a>,
 }
 
+#[event("security:packet_budget_exceeded")]
+/// Processing a packet exceeded one of the configured budgets
+///
+/// The remainder of the packet is discarded and the connection is closed.
+struct PacketBudgetExceeded {
+    #[nominal_counter("packet")]
+    packet_header: PacketHeader,
+    #[nominal_counter("budget")]
+    budget: PacketBudget,
+    limit: u64,
+}
+
 #[event("security:key_update")]
 //= https://tools.ietf.org/id/draft-marx-qlog-event-definitions-quic-h3-02#5.2.1
 /// Crypto key updated
@@ -308,6 +320,13 @@ struct ConnectionMigrationDenied {
     reason: MigrationDenyReason,
 }
 
+#[event("transport:compatibility_relaxation_applied")]
+/// A strictness check was relaxed for a nonconformant peer
+struct CompatibilityRelaxationApplied {
+    #[nominal_counter("relaxation")]
+    relaxation: CompatibilityRelaxation,
+}
+
 #[event("connectivity:handshake_status_updated")]
 #[checkpoint("complete.latency", |evt| matches!(evt.status, HandshakeStatus::Complete { .. }))]
 #[checkpoint("confirmed.latency", |evt| matches!(evt.status, HandshakeStatus::Confirmed { .. }))]
@@ -343,6 +362,16 @@ struct TlsServerHello<'a> {
     payload: &'a [&'a [u8]],
 }
 
+#[event("tls:operation_completed")]
+/// The TLS provider completed an operation which contributes to the latency of the handshake
+struct TlsOperationCompleted {
+    #[nominal_counter("operation")]
+    operation: TlsOperation,
+    /// The amount of time the TLS provider spent in the operation
+    #[timer("duration")]
+    duration: core::time::Duration,
+}
+
 #[event("transport:rx_stream_progress")]
 struct RxStreamProgress {
     #[measure("bytes", Bytes)]
@@ -427,6 +456,67 @@ struct DcStateChanged {
     state: DcState,
 }
 
+#[event("connectivity:go_away_received")]
+/// The server has requested the client move to a new connection
+struct GoAwayReceived {}
+
+#[event("transport:stream_limit_pressure")]
+/// The application has used more of the peer's stream limit than the configured threshold
+///
+/// This is emitted at most once for each stream limit issued by the peer.
+struct StreamLimitPressure {
+    stream_type: StreamType,
+    /// The cumulative number of streams opened by the application
+    opened_streams: u64,
+    /// The cumulative stream limit set by the peer
+    stream_limit: u64,
+}
+
+#[event("transport:connection_data_pressure")]
+/// The application has used more of the peer's connection flow control limit than the
+/// configured threshold
+///
+/// This is emitted at most once for each MAX_DATA limit issued by the peer.
+struct ConnectionDataPressure {
+    /// The total number of stream bytes sent on the connection
+    bytes_sent: u64,
+    /// The connection flow control limit set by the peer
+    max_data: u64,
+}
+
+#[event("transport:stream_open_blocked")]
+/// The application has been waiting to open a stream for longer than the configured threshold
+/// because the peer's stream limit has been reached
+struct StreamOpenBlocked {
+    #[nominal_counter("stream_type")]
+    stream_type: StreamType,
+    /// The cumulative stream limit set by the peer
+    stream_limit: u64,
+    /// The amount of time the application has been blocked
+    #[measure("duration", Duration)]
+    duration: Duration,
+}
+
+#[event("transport:connection_hibernated")]
+/// The connection has been idle for longer than the configured threshold and released its
+/// buffers
+///
+/// Buffers are reallocated when the connection becomes active again.
+struct ConnectionHibernated {
+    /// The amount of time the connection was idle before hibernating
+    idle_threshold: Duration,
+}
+
+#[event("transport:datagram_frame_dropped")]
+/// A datagram was dropped locally before it was sent to the peer or read by the application
+struct DatagramFrameDropped {
+    /// The length of the datagram payload
+    len: usize,
+    /// Whether the datagram was being sent to the peer, rather than received from it
+    is_outgoing: bool,
+    reason: DatagramFrameDropReason,
+}
+
 // NOTE - This event MUST come last, since connection-level aggregation depends on it
 #[event("connectivity:connection_closed")]
 //= https://tools.ietf.org/id/draft-marx-qlog-event-definitions-quic-h3-02#5.1.3
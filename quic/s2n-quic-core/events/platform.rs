@@ -148,3 +148,27 @@ struct PlatformEventLoopStarted<'a> {
     /// The local address of the socket
     local_address: SocketAddress<'a>,
 }
+
+#[event("platform:event_loop_profile")]
+#[subject(endpoint)]
+/// Emitted at the end of each profiling interval with the time the endpoint event loop
+/// spent in each phase
+struct PlatformEventLoopProfile {
+    /// The amount of time covered by this profile
+    interval: core::time::Duration,
+    /// Time spent parsing received datagrams and routing them to connections
+    #[timer("rx_parse")]
+    rx_parse: core::time::Duration,
+    /// Time spent protecting and unprotecting packets
+    #[timer("crypto")]
+    crypto: core::time::Duration,
+    /// Time spent processing received packets and application wakeups in connections
+    #[timer("connection_processing")]
+    connection_processing: core::time::Duration,
+    /// Time spent writing frames into outgoing packets
+    #[timer("tx_packing")]
+    tx_packing: core::time::Duration,
+    /// Time spent processing expired timers
+    #[timer("timers")]
+    timers: core::time::Duration,
+}
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod error;
+pub mod registry;
 #[cfg(feature = "alloc")]
 pub(crate) mod server_name;
 
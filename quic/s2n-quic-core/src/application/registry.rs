@@ -0,0 +1,210 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Symbolic names for application error codes
+//!
+//! The meaning of application error codes is defined by the application protocol, so by default
+//! they can only be rendered as numbers. Applications can declare their codes in a [`Registry`]
+//! and format errors with [`Registry::named`], which includes the name of the code. Since
+//! endpoints can speak different application protocols, there is no process-wide registry.
+//!
+//! To send the names to the peer, the endpoint's connection close formatter can be wrapped in a
+//! [`NamedCodes`](crate::connection::close::NamedCodes) formatter.
+//!
+//! ```rust
+//! use s2n_quic_core::application::{
+//!     registry::{Code, Registry},
+//!     Error,
+//! };
+//!
+//! static CODES: Registry = Registry::new(&[
+//!     Code::new(0x100, "REQUEST_CANCELLED", "The request was cancelled by the client"),
+//!     Code::new(0x101, "INTERNAL_ERROR", "The server encountered an internal error"),
+//! ]);
+//!
+//! let error = Error::from(0x100u16);
+//! assert_eq!(
+//!     format!("{:?}", CODES.named(error)),
+//!     "application::Error(256, REQUEST_CANCELLED)"
+//! );
+//! ```
+
+use super::Error;
+use core::fmt;
+
+/// An application error code declared by an application protocol
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Code {
+    value: u64,
+    name: &'static str,
+    description: &'static str,
+}
+
+impl Code {
+    /// Declares an error code with a name and a description
+    pub const fn new(value: u64, name: &'static str, description: &'static str) -> Self {
+        Self {
+            value,
+            name,
+            description,
+        }
+    }
+
+    /// Returns the numeric value of the code
+    #[inline]
+    pub const fn value(&self) -> u64 {
+        self.value
+    }
+
+    /// Returns the symbolic name of the code
+    #[inline]
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns a human-readable description of the code
+    #[inline]
+    pub const fn description(&self) -> &'static str {
+        self.description
+    }
+}
+
+impl fmt::Display for Code {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.description.is_empty() {
+            write!(f, "{}", self.name)
+        } else {
+            write!(f, "{}: {}", self.name, self.description)
+        }
+    }
+}
+
+/// A set of application error codes
+///
+/// Registries are usually declared as a `static` so they can be shared with
+/// [`NamedCodes`](crate::connection::close::NamedCodes) formatters.
+#[derive(Clone, Copy, Debug)]
+pub struct Registry {
+    codes: &'static [Code],
+}
+
+impl Registry {
+    /// Creates a registry from a list of codes
+    ///
+    /// If a value is declared more than once, the first declaration is used.
+    pub const fn new(codes: &'static [Code]) -> Self {
+        Self { codes }
+    }
+
+    /// Returns all of the codes in the registry
+    #[inline]
+    pub fn codes(&self) -> &'static [Code] {
+        self.codes
+    }
+
+    /// Returns the declaration for the given error, if any
+    #[inline]
+    pub fn get(&self, error: Error) -> Option<&'static Code> {
+        self.codes.iter().find(|code| code.value == *error)
+    }
+
+    /// Returns a value which formats the error along with the name of its code, if declared
+    #[inline]
+    pub fn named(&self, error: Error) -> Named {
+        Named {
+            error,
+            code: self.get(error),
+        }
+    }
+}
+
+/// Formats an application error along with its declaration in a [`Registry`]
+///
+/// This is returned by [`Registry::named`].
+#[derive(Clone, Copy)]
+pub struct Named {
+    error: Error,
+    code: Option<&'static Code>,
+}
+
+impl Named {
+    /// Returns the application error
+    #[inline]
+    pub fn error(&self) -> Error {
+        self.error
+    }
+
+    /// Returns the declaration of the error code, if any
+    #[inline]
+    pub fn code(&self) -> Option<&'static Code> {
+        self.code
+    }
+}
+
+impl fmt::Debug for Named {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(code) = self.code {
+            write!(f, "application::Error({}, {})", *self.error, code.name())
+        } else {
+            fmt::Debug::fmt(&self.error, f)
+        }
+    }
+}
+
+impl fmt::Display for Named {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(code) = self.code {
+            write!(f, "{} ({code})", self.error)
+        } else {
+            fmt::Display::fmt(&self.error, f)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static CODES: Registry = Registry::new(&[
+        Code::new(0x1234_5678, "TEST_ERROR", "A test error"),
+        Code::new(0x1234_5679, "TEST_ERROR_NO_DESCRIPTION", ""),
+        Code::new(0x1234_5678, "TEST_ERROR_DUPLICATE", ""),
+    ]);
+
+    #[test]
+    fn get_test() {
+        let code = CODES.get(Error::from(0x1234_5678u32)).unwrap();
+        assert_eq!(code.name(), "TEST_ERROR");
+        assert_eq!(code.description(), "A test error");
+        assert!(CODES.get(Error::from(0x1234_5677u32)).is_none());
+    }
+
+    #[test]
+    fn format_test() {
+        let error = Error::from(0x1234_5678u32);
+        assert_eq!(
+            format!("{:?}", CODES.named(error)),
+            "application::Error(305419896, TEST_ERROR)"
+        );
+        assert_eq!(
+            format!("{}", CODES.named(error)),
+            "QUIC application error code: 305419896 (TEST_ERROR: A test error)"
+        );
+
+        // the registry is only used when explicitly requested
+        assert_eq!(format!("{error:?}"), "application::Error(305419896)");
+
+        let error = Error::from(0x1234_5679u32);
+        assert_eq!(
+            format!("{}", CODES.named(error)),
+            "QUIC application error code: 305419897 (TEST_ERROR_NO_DESCRIPTION)"
+        );
+
+        // codes which aren't declared are rendered as numbers
+        let error = Error::from(0x1234_5677u32);
+        assert_eq!(
+            format!("{:?}", CODES.named(error)),
+            "application::Error(305419895)"
+        );
+    }
+}
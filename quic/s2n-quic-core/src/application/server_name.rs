@@ -42,6 +42,33 @@ impl ServerName {
         // before creating an instance of Sni.
         unsafe { core::str::from_utf8_unchecked(&self.0) }
     }
+
+    /// Returns `true` if the server name matches the provided pattern
+    ///
+    /// Patterns are compared case-insensitively and can either be an exact name
+    /// (e.g. `example.com`) or a wildcard name (e.g. `*.example.com`). Wildcards
+    /// only match a single, non-empty label so `*.example.com` matches `a.example.com`
+    /// but not `example.com` or `a.b.example.com`.
+    ///
+    /// ```rust
+    /// # use s2n_quic_core::application::ServerName;
+    /// let name = ServerName::from("api.example.com");
+    /// assert!(name.matches("*.example.com"));
+    /// assert!(!name.matches("*.com"));
+    /// ```
+    #[inline]
+    pub fn matches(&self, pattern: &str) -> bool {
+        let name = self.as_str();
+
+        if let Some(suffix) = pattern.strip_prefix("*.") {
+            let Some((label, rest)) = name.split_once('.') else {
+                return false;
+            };
+            return !label.is_empty() && rest.eq_ignore_ascii_case(suffix);
+        }
+
+        name.eq_ignore_ascii_case(pattern)
+    }
 }
 
 impl From<&str> for ServerName {
@@ -73,3 +100,22 @@ impl core::ops::Deref for ServerName {
         self.as_str()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pattern_test() {
+        let name = ServerName::from("www.Example.com");
+
+        assert!(name.matches("www.example.com"));
+        assert!(name.matches("*.example.com"));
+        assert!(!name.matches("example.com"));
+        assert!(!name.matches("*.com"));
+        assert!(!name.matches("*.www.example.com"));
+
+        assert!(!ServerName::from(".example.com").matches("*.example.com"));
+        assert!(!ServerName::from("example.com").matches("*.example.com"));
+    }
+}
@@ -546,6 +546,16 @@ impl Reassembler {
         offset
     }
 
+    /// Releases any allocations which aren't currently storing received data
+    ///
+    /// Slots are reallocated as needed on the next write.
+    #[inline]
+    pub fn shrink_to_fit(&mut self) {
+        self.slots.retain(|slot| !slot.is_empty());
+        self.slots.shrink_to_fit();
+        self.invariants();
+    }
+
     /// Resets the receive buffer.
     ///
     /// This will drop all previously received data.
@@ -113,6 +113,27 @@ fn model_test() {
     })
 }
 
+#[test]
+fn shrink_to_fit_test() {
+    let mut buffer = Reassembler::new();
+    buffer.write_at(0u32.into(), &[0, 1, 2, 3]).unwrap();
+    buffer.write_at(8u32.into(), &[8, 9]).unwrap();
+    assert_eq!(&buffer.pop().unwrap()[..], &[0, 1, 2, 3]);
+
+    // out-of-order data is retained
+    buffer.shrink_to_fit();
+    assert!(buffer.is_empty());
+    assert_eq!(buffer.consumed_len(), 4);
+
+    buffer.write_at(4u32.into(), &[4, 5, 6, 7]).unwrap();
+    let data: Vec<u8> = buffer.drain().flat_map(|chunk| chunk.to_vec()).collect();
+    assert_eq!(data, [4, 5, 6, 7, 8, 9]);
+
+    // an empty buffer doesn't hold any allocations
+    buffer.shrink_to_fit();
+    assert_eq!(buffer.slots.capacity(), 0);
+}
+
 #[test]
 #[cfg_attr(miri, ignore)] // This test is too expensive for miri to complete in a reasonable amount of time
 fn write_and_pop() {
@@ -0,0 +1,55 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Reusable test suites for validating custom provider implementations
+//!
+//! Each suite drives an implementation through the same trait methods the endpoint calls and
+//! panics with a description of the violated requirement. The inputs generated by the suites
+//! are derived from fixed seeds, so a failure is reproducible as long as the provider itself is
+//! deterministic. The suites can be called from a regular `#[test]` function in the provider's
+//! own CI.
+
+pub mod congestion_controller;
+pub mod connection_id;
+pub mod token;
+
+/// A deterministic random generator so failures are reproducible
+#[derive(Clone, Debug)]
+struct Generator(u64);
+
+impl Generator {
+    fn new(seed: u64) -> Self {
+        // xorshift requires a non-zero state
+        Self(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut x = self.0;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.0 = x;
+        x
+    }
+
+    /// Returns a value in the range `0..bound`
+    fn gen_range(&mut self, bound: u64) -> u64 {
+        if bound == 0 {
+            return 0;
+        }
+        self.next_u64() % bound
+    }
+}
+
+impl crate::random::Generator for Generator {
+    fn public_random_fill(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(8) {
+            let bytes = self.next_u64().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+
+    fn private_random_fill(&mut self, dest: &mut [u8]) {
+        self.public_random_fill(dest)
+    }
+}
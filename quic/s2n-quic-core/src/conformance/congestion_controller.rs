@@ -0,0 +1,393 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Conformance checks for congestion controller implementations
+//!
+//! The checks model the way the recovery manager drives a congestion controller and verify
+//! that `bytes_in_flight` stays consistent with the packets in flight, and that the congestion
+//! window never drops below the minimum window, including after persistent congestion.
+
+use super::Generator;
+use crate::{
+    event::builder::{BbrState, SlowStartExitCause},
+    inet::SocketAddress,
+    packet::number::PacketNumberSpace,
+    path,
+    recovery::{
+        bandwidth::{Bandwidth, RateSample},
+        congestion_controller::{CongestionController, Endpoint, PathInfo, Publisher},
+        RttEstimator,
+    },
+    time::{Clock, NoopClock, Timestamp},
+};
+use core::time::Duration;
+use num_rational::Ratio;
+use std::collections::VecDeque;
+
+/// The number of randomized operation sequences applied by [`check`]
+const RANDOM_SEEDS: u64 = 32;
+
+/// The number of operations in each randomized sequence
+const RANDOM_OPERATIONS: usize = 1000;
+
+/// Bounds the number of packets sent while filling the congestion window
+const MAX_PACKETS_PER_ROUND: usize = 10_000;
+
+/// Runs all of the congestion controller checks against controllers created by `endpoint`
+pub fn check<E: Endpoint>(endpoint: &mut E) {
+    initial_window(endpoint);
+    bytes_in_flight(endpoint);
+    minimum_window(endpoint);
+    persistent_congestion(endpoint);
+    for seed in 0..RANDOM_SEEDS {
+        random_operations(endpoint, seed);
+    }
+}
+
+/// Checks that a new congestion controller allows at least the minimum window to be sent
+pub fn initial_window<E: Endpoint>(endpoint: &mut E) {
+    let model = Model::new(endpoint, 0);
+    model.invariants();
+    assert!(
+        !model.subject.is_congestion_limited(),
+        "a new congestion controller must allow packets to be sent"
+    );
+}
+
+/// Checks that `bytes_in_flight` tracks every packet sent, acknowledged, lost and discarded
+pub fn bytes_in_flight<E: Endpoint>(endpoint: &mut E) {
+    let mut model = Model::new(endpoint, 0);
+
+    model.fill_window();
+    model.invariants();
+    assert!(
+        model.subject.is_congestion_limited(),
+        "the congestion controller must be limited once the congestion window is full"
+    );
+
+    let count = model.sent_packets.len();
+    model.advance(Duration::from_millis(50));
+    model.ack(count / 2, Duration::from_millis(50));
+    model.invariants();
+
+    model.lose(count / 4, false);
+    model.invariants();
+
+    while model.discard() {}
+    model.invariants();
+    assert_eq!(
+        model.subject.bytes_in_flight(),
+        0,
+        "bytes_in_flight must be 0 once all packets are acknowledged, lost or discarded"
+    );
+}
+
+/// Checks that repeated congestion events never reduce the window below the minimum
+pub fn minimum_window<E: Endpoint>(endpoint: &mut E) {
+    let mut model = Model::new(endpoint, 0);
+
+    for _ in 0..100 {
+        model.send(model.max_datagram_size as usize);
+        model.advance(Duration::from_millis(10));
+        model.lose(1, false);
+        model.invariants();
+
+        model
+            .subject
+            .on_explicit_congestion(1, model.now, &mut NoopPublisher);
+        model.invariants();
+    }
+}
+
+/// Checks that the controller recovers from persistent congestion
+//= https://www.rfc-editor.org/rfc/rfc9002#section-7.6.2
+//# When persistent congestion is declared, the sender's congestion
+//# window MUST be reduced to the minimum congestion window
+//# (kMinimumWindow), similar to a TCP sender's response on an RTO
+//# [RFC5681].
+pub fn persistent_congestion<E: Endpoint>(endpoint: &mut E) {
+    let mut model = Model::new(endpoint, 0);
+
+    model.fill_window();
+    model.advance(Duration::from_secs(5));
+    let count = model.sent_packets.len();
+    model.lose(count, true);
+    model.invariants();
+    assert_eq!(
+        model.subject.congestion_window(),
+        model.minimum_window(),
+        "the congestion window must be reduced to the minimum window after persistent congestion"
+    );
+    assert!(
+        !model.subject.is_congestion_limited(),
+        "the congestion controller must allow packets to be sent after persistent congestion"
+    );
+
+    // the controller must continue to function normally afterwards
+    model.fill_window();
+    model.advance(Duration::from_millis(50));
+    let count = model.sent_packets.len();
+    model.ack(count, Duration::from_millis(50));
+    model.invariants();
+}
+
+/// Applies a random sequence of operations seeded with `seed`, checking the invariants after
+/// each one
+pub fn random_operations<E: Endpoint>(endpoint: &mut E, seed: u64) {
+    let mut model = Model::new(endpoint, seed);
+
+    for _ in 0..RANDOM_OPERATIONS {
+        match model.random.gen_range(6) {
+            0 => {
+                let millis = model.random.gen_range(100);
+                model.advance(Duration::from_millis(millis));
+            }
+            1 => {
+                let count = model.random.gen_range(20) + 1;
+                for _ in 0..count {
+                    let bytes = model.random.gen_range(model.max_datagram_size as u64 + 1);
+                    model.send(bytes as usize);
+                }
+            }
+            2 => {
+                let count = model.random.gen_range(20) as usize + 1;
+                let rtt = Duration::from_millis(model.random.gen_range(200) + 1);
+                model.ack(count, rtt);
+            }
+            3 => {
+                let persistent_congestion = model.random.gen_range(10) == 0;
+                model.lose(1, persistent_congestion);
+            }
+            4 => {
+                let ce_count = model.random.gen_range(model.sent_packets.len() as u64 + 1);
+                model
+                    .subject
+                    .on_explicit_congestion(ce_count, model.now, &mut NoopPublisher);
+            }
+            _ => {
+                model.discard();
+            }
+        }
+
+        model.invariants();
+    }
+}
+
+struct SentPacket<PacketInfo> {
+    bytes: usize,
+    time_sent: Timestamp,
+    info: PacketInfo,
+}
+
+struct Model<CC: CongestionController> {
+    subject: CC,
+    sent_packets: VecDeque<SentPacket<CC::PacketInfo>>,
+    rtt_estimator: RttEstimator,
+    random: Generator,
+    now: Timestamp,
+    max_datagram_size: u16,
+}
+
+impl<CC: CongestionController> Model<CC> {
+    fn new<E: Endpoint<CongestionController = CC>>(endpoint: &mut E, seed: u64) -> Self {
+        let mtu_config = path::mtu::Config::default();
+        let remote_address = SocketAddress::default();
+        let path_info = PathInfo::new(&mtu_config, &remote_address);
+        let max_datagram_size = path_info.max_datagram_size;
+
+        Self {
+            subject: endpoint.new_congestion_controller(path_info),
+            sent_packets: VecDeque::new(),
+            rtt_estimator: RttEstimator::default(),
+            random: Generator::new(seed),
+            now: NoopClock.get_time(),
+            max_datagram_size,
+        }
+    }
+
+    fn advance(&mut self, duration: Duration) {
+        self.now += duration;
+    }
+
+    /// Sends a packet if the congestion controller allows it
+    fn send(&mut self, bytes: usize) -> bool {
+        // packets that only contain ACK frames are not congestion controlled
+        if bytes > 0
+            && self.subject.is_congestion_limited()
+            && !self.subject.requires_fast_retransmission()
+        {
+            return false;
+        }
+
+        let info = self.subject.on_packet_sent(
+            self.now,
+            bytes,
+            Some(false),
+            &self.rtt_estimator,
+            &mut NoopPublisher,
+        );
+        self.sent_packets.push_back(SentPacket {
+            bytes,
+            time_sent: self.now,
+            info,
+        });
+        true
+    }
+
+    /// Sends full-sized packets until the congestion controller is limited
+    fn fill_window(&mut self) {
+        for _ in 0..MAX_PACKETS_PER_ROUND {
+            if !self.send(self.max_datagram_size as usize) {
+                return;
+            }
+        }
+
+        panic!(
+            "the congestion controller allowed more than {MAX_PACKETS_PER_ROUND} packets without acknowledgements"
+        );
+    }
+
+    /// Acknowledges the `count` oldest packets
+    fn ack(&mut self, count: usize, rtt: Duration) {
+        let mut rtt_updated = false;
+
+        for _ in 0..count {
+            let Some(packet) = self.sent_packets.pop_front() else {
+                break;
+            };
+
+            // the recovery manager doesn't notify the controller about pure ACK packets
+            if packet.bytes == 0 {
+                continue;
+            }
+
+            if !rtt_updated {
+                self.rtt_estimator.update_rtt(
+                    Duration::ZERO,
+                    rtt,
+                    self.now,
+                    true,
+                    PacketNumberSpace::ApplicationData,
+                );
+                self.subject.on_rtt_update(
+                    packet.time_sent,
+                    self.now,
+                    &self.rtt_estimator,
+                    &mut NoopPublisher,
+                );
+                rtt_updated = true;
+            }
+
+            self.subject.on_ack(
+                packet.time_sent,
+                packet.bytes,
+                packet.info,
+                &self.rtt_estimator,
+                &mut self.random,
+                self.now,
+                &mut NoopPublisher,
+            );
+        }
+    }
+
+    /// Declares the `count` oldest packets lost
+    fn lose(&mut self, count: usize, persistent_congestion: bool) {
+        let mut new_loss_burst = true;
+
+        for _ in 0..count {
+            let Some(packet) = self.sent_packets.pop_front() else {
+                break;
+            };
+
+            // the recovery manager doesn't notify the controller about pure ACK packets
+            if packet.bytes == 0 {
+                continue;
+            }
+
+            self.subject.on_packet_lost(
+                packet.bytes as u32,
+                packet.info,
+                persistent_congestion,
+                new_loss_burst,
+                &mut self.random,
+                self.now,
+                &mut NoopPublisher,
+            );
+            new_loss_burst = false;
+        }
+    }
+
+    /// Discards the oldest packet, as happens when a packet number space is discarded
+    fn discard(&mut self) -> bool {
+        let Some(packet) = self.sent_packets.pop_front() else {
+            return false;
+        };
+
+        self.subject
+            .on_packet_discarded(packet.bytes, &mut NoopPublisher);
+        true
+    }
+
+    //= https://www.rfc-editor.org/rfc/rfc9002#section-7.2
+    //# The minimum congestion window is the smallest value the congestion
+    //# window can attain in response to loss, an increase in the peer-
+    //# reported ECN-CE count, or persistent congestion.  The RECOMMENDED
+    //# value is 2 * max_datagram_size.
+    fn minimum_window(&self) -> u32 {
+        2 * self.max_datagram_size as u32
+    }
+
+    fn invariants(&self) {
+        let bytes_in_flight: usize = self.sent_packets.iter().map(|packet| packet.bytes).sum();
+        assert_eq!(
+            self.subject.bytes_in_flight() as usize,
+            bytes_in_flight,
+            "bytes_in_flight must equal the bytes of packets that have been sent and not yet acknowledged, lost or discarded"
+        );
+
+        let minimum_window = self.minimum_window();
+        assert!(
+            self.subject.congestion_window() >= minimum_window,
+            "the congestion window ({}) must not be reduced below the minimum window ({})",
+            self.subject.congestion_window(),
+            minimum_window,
+        );
+    }
+}
+
+/// Discards all of the events emitted by the congestion controller
+struct NoopPublisher;
+
+impl Publisher for NoopPublisher {
+    fn on_slow_start_exited(&mut self, _cause: SlowStartExitCause, _congestion_window: u32) {}
+
+    fn on_delivery_rate_sampled(&mut self, _rate_sample: RateSample) {}
+
+    fn on_pacing_rate_updated(
+        &mut self,
+        _pacing_rate: Bandwidth,
+        _burst_size: u32,
+        _pacing_gain: Ratio<u64>,
+    ) {
+    }
+
+    fn on_bbr_state_changed(&mut self, _state: BbrState) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recovery::{bbr, cubic};
+
+    #[test]
+    #[cfg_attr(miri, ignore)] // This test is too expensive for miri to complete in a reasonable amount of time
+    fn cubic_test() {
+        check(&mut cubic::Endpoint::default());
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)] // This test is too expensive for miri to complete in a reasonable amount of time
+    fn bbr_test() {
+        check(&mut bbr::Endpoint::default());
+    }
+}
@@ -0,0 +1,107 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Conformance checks for connection ID formats
+//!
+//! The checks verify that generated connection IDs are unique, that their length can be
+//! recovered by the validator without state, and that the configured lifetime is accepted by
+//! the endpoint.
+
+use super::Generator;
+use crate::{
+    connection::id::{ConnectionInfo, Format, MAX_LEN, MAX_LIFETIME, MIN_LIFETIME},
+    inet::{SocketAddress, SocketAddressV4},
+    random::Generator as _,
+};
+use std::collections::HashSet;
+
+/// The maximum number of connection IDs generated by [`uniqueness`]
+const UNIQUENESS_COUNT: usize = 10_000;
+
+/// Bounds the probability of a randomly generated connection ID colliding in [`uniqueness`]
+///
+/// By the birthday bound, generating `n` IDs with `bits` bits of entropy collides with a
+/// probability of about `n^2 / 2^(bits + 1)`. Limiting `n` to `2^(bits / 2 - MARGIN_BITS)` keeps
+/// that probability below `2^-(2 * MARGIN_BITS + 1)`.
+const MARGIN_BITS: u32 = 10;
+
+/// Runs all of the connection ID format checks against `format`
+pub fn check<F: Format>(format: &mut F) {
+    uniqueness(format);
+    length(format);
+    lifetime(format);
+}
+
+/// Checks that every generated connection ID is unique, regardless of the peer address
+///
+/// Formats which randomly generate IDs can't guarantee uniqueness, so the number of IDs
+/// generated is scaled to the length of the first ID to keep the chance of a collision
+/// negligible. Short IDs are therefore only checked with a few IDs.
+pub fn uniqueness<F: Format>(format: &mut F) {
+    let mut ids = HashSet::new();
+
+    let address = remote_address(0);
+    let count = uniqueness_count(format.generate(&ConnectionInfo::new(&address)).len());
+
+    for i in 0..count {
+        // alternate between a few peer addresses to make sure the address isn't the only input
+        let remote_address = remote_address(i % 4);
+        let connection_info = ConnectionInfo::new(&remote_address);
+        let id = format.generate(&connection_info);
+
+        assert!(
+            ids.insert(id),
+            "connection ID {id:?} was generated more than once"
+        );
+    }
+}
+
+/// Checks that the validator recovers the length of each generated connection ID
+//= https://www.rfc-editor.org/rfc/rfc9000#section-10.3.2
+//# An endpoint that uses this design MUST
+//# either use the same connection ID length for all connections or
+//# encode the length of the connection ID such that it can be recovered
+//# without state.
+pub fn length<F: Format>(format: &mut F) {
+    let mut random = Generator::new(0);
+
+    for i in 0..100 {
+        let remote_address = remote_address(i % 4);
+        let connection_info = ConnectionInfo::new(&remote_address);
+        let id = format.generate(&connection_info);
+
+        // short header packets include the rest of the packet after the connection ID
+        let mut buffer = [0u8; MAX_LEN * 2];
+        buffer[..id.len()].copy_from_slice(id.as_bytes());
+        random.public_random_fill(&mut buffer[id.len()..]);
+
+        assert_eq!(
+            format.validate(&connection_info, &buffer),
+            Some(id.len()),
+            "the validator must recover the length of the generated connection ID {id:?}"
+        );
+    }
+}
+
+/// Checks that the connection ID lifetime is within the range accepted by the endpoint
+pub fn lifetime<F: Format>(format: &mut F) {
+    if let Some(lifetime) = format.lifetime() {
+        assert!(
+            (MIN_LIFETIME..=MAX_LIFETIME).contains(&lifetime),
+            "the connection ID lifetime must be between {MIN_LIFETIME:?} and {MAX_LIFETIME:?}"
+        );
+    }
+}
+
+/// Returns the number of IDs to generate for IDs of `len` bytes
+fn uniqueness_count(len: usize) -> usize {
+    let bits = (len as u32 * 8 / 2).saturating_sub(MARGIN_BITS);
+    1usize
+        .checked_shl(bits)
+        .unwrap_or(usize::MAX)
+        .min(UNIQUENESS_COUNT)
+}
+
+fn remote_address(i: usize) -> SocketAddress {
+    SocketAddressV4::new([127, 0, 0, 1 + i as u8], 443 + i as u16).into()
+}
@@ -0,0 +1,127 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Conformance checks for address validation token formats
+//!
+//! Tokens don't carry a clock through the [`Format`] trait, so the expiry check is given a
+//! callback which advances the format's clock past the lifetime of a token.
+
+use super::Generator;
+use crate::{
+    connection::{InitialId, PeerId},
+    inet::{SocketAddress, SocketAddressV4},
+    token::{Context, Format},
+};
+
+/// Runs all of the token format checks against `format`
+///
+/// `expire` is called once a token has been issued and must advance the clock used by `format`
+/// past the lifetime of the token.
+pub fn check<F: Format>(format: &mut F, expire: impl FnOnce()) {
+    retry_token(format);
+    expiry(format, expire);
+}
+
+/// Checks that a Retry token is only accepted once, unmodified, from the address it was
+/// issued to
+pub fn retry_token<F: Format>(format: &mut F) {
+    let mut random = Generator::new(0);
+    let address = remote_address(1, 443);
+    let peer_id = PeerId::try_from_bytes(&[2, 4, 6, 8, 10]).unwrap();
+    let odcid = InitialId::try_from_bytes(&[0, 1, 2, 3, 4, 5, 6, 7]).unwrap();
+
+    let mut token = vec![0; F::TOKEN_LEN];
+    let mut context = Context::new(&address, &peer_id, &mut random);
+    format
+        .generate_retry_token(&mut context, &odcid, &mut token)
+        .expect("the format must generate Retry tokens");
+
+    // modifying any byte of the token must cause validation to fail
+    for i in 0..token.len() {
+        let mut modified = token.clone();
+        modified[i] ^= 1;
+        let mut context = Context::new(&address, &peer_id, &mut random);
+        assert_eq!(
+            format.validate_token(&mut context, &modified),
+            None,
+            "a token modified at byte {i} must not be accepted"
+        );
+    }
+
+    // truncated and extended tokens must be rejected
+    let mut context = Context::new(&address, &peer_id, &mut random);
+    assert_eq!(
+        format.validate_token(&mut context, &token[..token.len() - 1]),
+        None,
+        "a truncated token must not be accepted"
+    );
+    let mut extended = token.clone();
+    extended.push(0);
+    let mut context = Context::new(&address, &peer_id, &mut random);
+    assert_eq!(
+        format.validate_token(&mut context, &extended),
+        None,
+        "a token with trailing data must not be accepted"
+    );
+
+    //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.4
+    //# Tokens
+    //# sent in Retry packets SHOULD include information that allows the
+    //# server to verify that the source IP address and port in client
+    //# packets remain constant.
+    for other_address in [remote_address(2, 443), remote_address(1, 444)] {
+        let mut context = Context::new(&other_address, &peer_id, &mut random);
+        assert_eq!(
+            format.validate_token(&mut context, &token),
+            None,
+            "a token must not be accepted from a different address or port"
+        );
+    }
+
+    let mut context = Context::new(&address, &peer_id, &mut random);
+    assert_eq!(
+        format.validate_token(&mut context, &token),
+        Some(odcid),
+        "a token must be accepted from the address it was issued to"
+    );
+
+    //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.4
+    //# To protect against such attacks, servers MUST ensure that
+    //# replay of tokens is prevented or limited.
+    let mut context = Context::new(&address, &peer_id, &mut random);
+    assert_eq!(
+        format.validate_token(&mut context, &token),
+        None,
+        "a token must not be accepted more than once"
+    );
+}
+
+/// Checks that a Retry token is rejected once `expire` advances the clock past its lifetime
+//= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.4
+//# Servers SHOULD ensure that
+//# tokens sent in Retry packets are only accepted for a short time.
+pub fn expiry<F: Format>(format: &mut F, expire: impl FnOnce()) {
+    let mut random = Generator::new(1);
+    let address = remote_address(1, 443);
+    let peer_id = PeerId::try_from_bytes(&[1, 3, 5, 7, 9]).unwrap();
+    let odcid = InitialId::try_from_bytes(&[7, 6, 5, 4, 3, 2, 1, 0]).unwrap();
+
+    let mut token = vec![0; F::TOKEN_LEN];
+    let mut context = Context::new(&address, &peer_id, &mut random);
+    format
+        .generate_retry_token(&mut context, &odcid, &mut token)
+        .expect("the format must generate Retry tokens");
+
+    expire();
+
+    let mut context = Context::new(&address, &peer_id, &mut random);
+    assert_eq!(
+        format.validate_token(&mut context, &token),
+        None,
+        "an expired token must not be accepted"
+    );
+}
+
+fn remote_address(i: u8, port: u16) -> SocketAddress {
+    SocketAddressV4::new([127, 0, 0, i], port).into()
+}
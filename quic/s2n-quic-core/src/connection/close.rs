@@ -1,7 +1,11 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{application, crypto::tls, transport};
+use crate::{
+    application::{self, registry::Registry},
+    crypto::tls,
+    transport,
+};
 pub use crate::{frame::ConnectionClose, inet::SocketAddress};
 
 /// Provides a hook for applications to rewrite CONNECTION_CLOSE frames
@@ -158,3 +162,71 @@ impl Formatter for Production {
         transport::Error::APPLICATION_ERROR.into()
     }
 }
+
+/// A formatter that sends the names of registered application error codes to the peer
+///
+/// When an application error is declared in the [`Registry`] and the wrapped formatter doesn't
+/// include a reason, the name of the code is sent as the reason phrase in 1-RTT packets. This
+/// allows the peer to log the name of the code along with the value. Reasons provided by the
+/// application when closing the connection take precedence.
+///
+/// Note that this exposes the names of the application error codes to the peer.
+#[derive(Clone, Copy, Debug)]
+pub struct NamedCodes<F> {
+    formatter: F,
+    registry: &'static Registry,
+}
+
+impl<F: Formatter> NamedCodes<F> {
+    /// Wraps the formatter with the given registry
+    pub fn new(formatter: F, registry: &'static Registry) -> Self {
+        Self {
+            formatter,
+            registry,
+        }
+    }
+}
+
+impl<F: Formatter> Formatter for NamedCodes<F> {
+    fn format_transport_error(
+        &self,
+        context: &Context,
+        error: transport::Error,
+    ) -> ConnectionClose {
+        self.formatter.format_transport_error(context, error)
+    }
+
+    fn format_application_error(
+        &self,
+        context: &Context,
+        error: application::Error,
+    ) -> ConnectionClose {
+        let mut frame = self.formatter.format_application_error(context, error);
+
+        // don't name codes which the formatter converted to transport errors
+        if frame.frame_type.is_none() && frame.reason.is_none() {
+            if let Some(code) = self.registry.get(error) {
+                frame.reason = Some(code.name().as_bytes());
+            }
+        }
+
+        frame
+    }
+
+    fn format_early_transport_error(
+        &self,
+        context: &Context,
+        error: transport::Error,
+    ) -> ConnectionClose {
+        self.formatter.format_early_transport_error(context, error)
+    }
+
+    fn format_early_application_error(
+        &self,
+        context: &Context,
+        error: application::Error,
+    ) -> ConnectionClose {
+        self.formatter
+            .format_early_application_error(context, error)
+    }
+}
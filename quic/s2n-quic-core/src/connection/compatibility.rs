@@ -0,0 +1,155 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Allows applications to relax selected strictness checks for peers which are known to be
+//! nonconformant
+//!
+//! By default, all of the checks required by the QUIC specifications are enforced. Some deployed
+//! peers, however, are known to deviate from the specifications in benign ways. A [`Policy`]
+//! can selectively tolerate these deviations on a per-peer basis, keyed by the negotiated server
+//! name, application protocol, or the peer's address.
+//!
+//! Every time a relaxation is applied, the `compatibility_relaxation_applied` event is emitted.
+
+use crate::{
+    application::ServerName,
+    event::{api::SocketAddress, IntoEvent},
+    inet,
+};
+
+/// Information about the peer used to select relaxations
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct Peer<'a> {
+    /// The address of the peer
+    pub remote_address: SocketAddress<'a>,
+    /// The server name negotiated for the connection, if any
+    pub server_name: Option<&'a ServerName>,
+    /// The application protocol negotiated for the connection
+    pub application_protocol: &'a [u8],
+}
+
+impl<'a> Peer<'a> {
+    #[inline]
+    #[doc(hidden)]
+    pub fn new(
+        remote_address: &'a inet::SocketAddress,
+        server_name: Option<&'a ServerName>,
+        application_protocol: &'a [u8],
+    ) -> Self {
+        Self {
+            remote_address: remote_address.into_event(),
+            server_name,
+            application_protocol,
+        }
+    }
+}
+
+/// The set of strictness checks that are relaxed for a peer
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Relaxations {
+    missing_initial_source_connection_id: bool,
+    missing_original_destination_connection_id: bool,
+}
+
+impl Relaxations {
+    /// Enforces all of the checks
+    pub const STRICT: Self = Self {
+        missing_initial_source_connection_id: false,
+        missing_original_destination_connection_id: false,
+    };
+
+    /// Tolerates a peer omitting the `initial_source_connection_id` transport parameter
+    ///
+    /// This parameter was introduced late in the QUIC drafts and some stacks fail to send it.
+    #[must_use]
+    pub const fn with_missing_initial_source_connection_id(mut self, enabled: bool) -> Self {
+        self.missing_initial_source_connection_id = enabled;
+        self
+    }
+
+    /// Tolerates a server omitting the `original_destination_connection_id` transport parameter
+    ///
+    /// This relaxation only applies to clients.
+    #[must_use]
+    pub const fn with_missing_original_destination_connection_id(mut self, enabled: bool) -> Self {
+        self.missing_original_destination_connection_id = enabled;
+        self
+    }
+
+    /// Returns `true` if a missing `initial_source_connection_id` is tolerated
+    #[inline]
+    pub const fn missing_initial_source_connection_id(&self) -> bool {
+        self.missing_initial_source_connection_id
+    }
+
+    /// Returns `true` if a missing `original_destination_connection_id` is tolerated
+    #[inline]
+    pub const fn missing_original_destination_connection_id(&self) -> bool {
+        self.missing_original_destination_connection_id
+    }
+
+    /// Returns the union of both sets of relaxations
+    #[must_use]
+    pub const fn union(self, other: Self) -> Self {
+        Self {
+            missing_initial_source_connection_id: self.missing_initial_source_connection_id
+                || other.missing_initial_source_connection_id,
+            missing_original_destination_connection_id: self
+                .missing_original_destination_connection_id
+                || other.missing_original_destination_connection_id,
+        }
+    }
+
+    /// Returns `true` if none of the checks are relaxed
+    #[inline]
+    pub const fn is_strict(&self) -> bool {
+        !(self.missing_initial_source_connection_id
+            || self.missing_original_destination_connection_id)
+    }
+}
+
+/// Selects the relaxations that apply to a peer
+pub trait Policy: 'static + Send + Sync {
+    /// Called once the server name and application protocol have been negotiated, before the
+    /// peer's transport parameters are validated
+    fn on_peer(&self, peer: &Peer) -> Relaxations;
+}
+
+/// Applies the same relaxations to all of the peers
+impl Policy for Relaxations {
+    #[inline]
+    fn on_peer(&self, _peer: &Peer) -> Relaxations {
+        *self
+    }
+}
+
+/// Enforces all of the checks for every peer
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Strict;
+
+impl Policy for Strict {
+    #[inline]
+    fn on_peer(&self, _peer: &Peer) -> Relaxations {
+        Relaxations::STRICT
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn union_test() {
+        let a = Relaxations::STRICT.with_missing_initial_source_connection_id(true);
+        let b = Relaxations::STRICT.with_missing_original_destination_connection_id(true);
+
+        assert!(Relaxations::default().is_strict());
+        assert!(!a.is_strict());
+
+        let c = a.union(b);
+        assert!(c.missing_initial_source_connection_id());
+        assert!(c.missing_original_destination_connection_id());
+        assert_eq!(a.union(Relaxations::STRICT), a);
+    }
+}
@@ -14,6 +14,8 @@ pub enum Error {
     #[non_exhaustive]
     Closed {
         initiator: endpoint::Location,
+        /// What closed the connection, including the code and frame type sent by the peer
+        close_source: CloseSource,
         source: &'static panic::Location<'static>,
     },
 
@@ -26,6 +28,9 @@ pub enum Error {
     Transport {
         code: transport::error::Code,
         frame_type: u64,
+        /// The reason for locally initiated errors
+        ///
+        /// The reason sent by the peer isn't included here, but is retained by the connection.
         reason: &'static str,
         initiator: endpoint::Location,
         source: &'static panic::Location<'static>,
@@ -39,6 +44,8 @@ pub enum Error {
     #[non_exhaustive]
     Application {
         error: application::Error,
+        /// The reason phrase supplied with the error code, which is empty if none was provided
+        reason: Reason,
         initiator: endpoint::Location,
         source: &'static panic::Location<'static>,
     },
@@ -99,6 +106,23 @@ pub enum Error {
     Unspecified {
         source: &'static panic::Location<'static>,
     },
+
+    /// The host of the connection attempt couldn't be resolved
+    ///
+    /// The reason contains the error message returned by the resolver.
+    #[non_exhaustive]
+    HostResolutionFailed {
+        reason: Reason,
+        source: &'static panic::Location<'static>,
+    },
+
+    /// A stream couldn't be opened within the timeout of the open call
+    ///
+    /// The connection remains open when this error is returned.
+    #[non_exhaustive]
+    OpenStreamTimeout {
+        source: &'static panic::Location<'static>,
+    },
 }
 
 #[cfg(feature = "std")]
@@ -122,6 +146,10 @@ impl fmt::Display for Error {
                     "The connection was closed on the transport level with error {error} by {initiator}"
                 )
             },
+            Self::Application { error, reason, initiator, .. } if !reason.is_empty() => write!(
+                f,
+                "The connection was closed on the application level with error {error:?} and reason {reason:?} by {initiator}"
+            ),
             Self::Application { error, initiator, .. } => write!(
                 f,
                 "The connection was closed on the application level with error {error:?} by {initiator}"
@@ -163,14 +191,40 @@ impl fmt::Display for Error {
             Self::Unspecified { .. } => {
                 write!(f, "The connection was closed due to an unspecified reason")
             }
+            Self::HostResolutionFailed { reason, .. } => write!(
+                f,
+                "The connection attempt failed because the host could not be resolved: {reason}"
+            ),
+            Self::OpenStreamTimeout { .. } => write!(
+                f,
+                "The stream could not be opened before the open stream timeout expired"
+            ),
         }
     }
 }
 
+/// Serializes the error as its kind, which matches the name used by the aggregate metrics, along
+/// with the formatted message
+#[cfg(feature = "serde")]
+impl serde::Serialize for Error {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use crate::event::metrics::aggregate::AsVariant;
+        use serde::ser::SerializeStruct;
+
+        let kind: &str = &self.as_variant().name;
+
+        let mut s = serializer.serialize_struct("Error", 2)?;
+        s.serialize_field("kind", kind)?;
+        s.serialize_field("message", &format_args!("{self}"))?;
+        s.end()
+    }
+}
+
 impl PartialEq for Error {
     #[inline]
     fn eq(&self, other: &Self) -> bool {
-        // ignore the `source` attribute when considering if errors are equal
+        // ignore the `source` attribute when considering if errors are equal. The
+        // `close_source` of a `Closed` error is informational, like the application reason.
         match (self, other) {
             (Error::Closed { initiator: a, .. }, Error::Closed { initiator: b, .. }) => a.eq(b),
             (
@@ -194,6 +248,7 @@ impl PartialEq for Error {
                     && a_reason.eq(b_reason)
                     && a_initiator.eq(b_initiator)
             }
+            // the reason phrase is informational so it's ignored, like the `source`
             (
                 Error::Application {
                     error: a_error,
@@ -233,6 +288,8 @@ impl PartialEq for Error {
                 },
             ) => a_reason.eq(b_reason),
             (Error::Unspecified { .. }, Error::Unspecified { .. }) => true,
+            (Error::HostResolutionFailed { .. }, Error::HostResolutionFailed { .. }) => true,
+            (Error::OpenStreamTimeout { .. }, Error::OpenStreamTimeout { .. }) => true,
             _ => false,
         }
     }
@@ -256,18 +313,83 @@ impl Error {
             Error::EndpointClosing { source } => source,
             Error::InvalidConfiguration { source, .. } => source,
             Error::Unspecified { source } => source,
+            Error::HostResolutionFailed { source, .. } => source,
+            Error::OpenStreamTimeout { source } => source,
         }
     }
 
+    /// Returns what caused the connection to close
+    ///
+    /// This allows applications to decide programmatically if a connection should be retried.
+    /// `None` is returned for errors which don't close the connection, such as
+    /// [`Error::OpenStreamTimeout`].
+    pub fn close_source(&self) -> Option<CloseSource> {
+        let initiator = match self {
+            Error::Closed { close_source, .. } => return Some(*close_source),
+            Error::Transport {
+                code,
+                frame_type,
+                initiator,
+                ..
+            } => {
+                if initiator.is_remote() {
+                    return Some(CloseSource::PeerTransport {
+                        code: *code,
+                        frame_type: *frame_type,
+                    });
+                }
+                *initiator
+            }
+            Error::Application {
+                error, initiator, ..
+            } => {
+                if initiator.is_remote() {
+                    return Some(CloseSource::PeerApplication { error: *error });
+                }
+                *initiator
+            }
+            Error::StatelessReset { .. } => return Some(CloseSource::StatelessReset),
+            Error::IdleTimerExpired { .. } => return Some(CloseSource::IdleTimeout),
+            Error::OpenStreamTimeout { .. } => return None,
+            Error::NoValidPath { .. }
+            | Error::StreamIdExhausted { .. }
+            | Error::MaxHandshakeDurationExceeded { .. }
+            | Error::ImmediateClose { .. }
+            | Error::EndpointClosing { .. }
+            | Error::InvalidConfiguration { .. }
+            | Error::Unspecified { .. }
+            | Error::HostResolutionFailed { .. } => endpoint::Location::Local,
+        };
+
+        // the remaining errors are all detected locally
+        debug_assert!(initiator.is_local());
+        Some(CloseSource::Local)
+    }
+
     #[track_caller]
     fn from_transport_error(error: transport::Error, initiator: endpoint::Location) -> Self {
         let source = panic::Location::caller();
+        let close_source = match initiator {
+            endpoint::Location::Local => CloseSource::Local,
+            endpoint::Location::Remote => CloseSource::PeerTransport {
+                code: error.code,
+                frame_type: error.frame_type.into(),
+            },
+        };
         match error.code {
             // The connection closed without an error
-            code if code == transport::Error::NO_ERROR.code => Self::Closed { initiator, source },
+            code if code == transport::Error::NO_ERROR.code => Self::Closed {
+                initiator,
+                close_source,
+                source,
+            },
             // The connection closed without an error at the application layer
             code if code == transport::Error::APPLICATION_ERROR.code && initiator.is_remote() => {
-                Self::Closed { initiator, source }
+                Self::Closed {
+                    initiator,
+                    close_source,
+                    source,
+                }
             }
             // The connection closed with an actual error
             _ => Self::Transport {
@@ -280,12 +402,16 @@ impl Error {
         }
     }
 
+    /// Returns an error for a connection closed without an error
+    ///
+    /// Remote closes are treated as if the peer sent a `NO_ERROR` CONNECTION_CLOSE frame with a
+    /// frame type of 0. Errors for closes received from the peer should be created from the
+    /// frame instead, which preserves the code and frame type it sent.
     #[inline]
     #[track_caller]
     #[doc(hidden)]
     pub fn closed(initiator: endpoint::Location) -> Error {
-        let source = panic::Location::caller();
-        Error::Closed { initiator, source }
+        Self::from_transport_error(transport::Error::NO_ERROR, initiator)
     }
 
     #[inline]
@@ -343,9 +469,17 @@ impl Error {
     #[track_caller]
     #[doc(hidden)]
     pub fn application(error: application::Error) -> Error {
+        Self::application_with_reason(error, Reason::default())
+    }
+
+    #[inline]
+    #[track_caller]
+    #[doc(hidden)]
+    pub fn application_with_reason(error: application::Error, reason: Reason) -> Error {
         let source = panic::Location::caller();
         Error::Application {
             error,
+            reason,
             initiator: endpoint::Location::Local,
             source,
         }
@@ -375,6 +509,22 @@ impl Error {
         Error::Unspecified { source }
     }
 
+    #[inline]
+    #[track_caller]
+    #[doc(hidden)]
+    pub fn host_resolution_failed(reason: Reason) -> Error {
+        let source = panic::Location::caller();
+        Error::HostResolutionFailed { reason, source }
+    }
+
+    #[inline]
+    #[track_caller]
+    #[doc(hidden)]
+    pub fn open_stream_timeout() -> Error {
+        let source = panic::Location::caller();
+        Error::OpenStreamTimeout { source }
+    }
+
     #[inline]
     #[doc(hidden)]
     pub fn into_accept_error(error: connection::Error) -> Result<(), connection::Error> {
@@ -400,11 +550,11 @@ impl Error {
 /// The first item will be a close frame for an early (initial, handshake) packet.
 /// The second item will be a close frame for a 1-RTT (application data) packet.
 pub fn as_frame<'a, F: connection::close::Formatter>(
-    error: Error,
+    error: &'a Error,
     formatter: &'a F,
     context: &'a connection::close::Context<'a>,
 ) -> Option<(ConnectionClose<'a>, ConnectionClose<'a>)> {
-    match error {
+    match *error {
         Error::Closed { initiator, .. } => {
             // don't send CONNECTION_CLOSE frames on remote-initiated errors
             if initiator.is_remote() {
@@ -440,7 +590,10 @@ pub fn as_frame<'a, F: connection::close::Formatter>(
             Some((early, one_rtt))
         }
         Error::Application {
-            error, initiator, ..
+            error,
+            ref reason,
+            initiator,
+            ..
         } => {
             // don't send CONNECTION_CLOSE frames on remote-initiated errors
             if initiator.is_remote() {
@@ -448,7 +601,17 @@ pub fn as_frame<'a, F: connection::close::Formatter>(
             }
 
             let early = formatter.format_early_application_error(context, error);
-            let one_rtt = formatter.format_application_error(context, error);
+            let mut one_rtt = formatter.format_application_error(context, error);
+
+            // The reason was explicitly provided by the application so it's included, unless the
+            // formatter converted the error to a transport error.
+            //
+            // Early packets never include the reason, since application errors can't be sent
+            // before the handshake is confirmed.
+            if !reason.is_empty() && one_rtt.frame_type.is_none() {
+                one_rtt.reason = Some(reason.as_bytes());
+            }
+
             Some((early, one_rtt))
         }
         // This error comes from the peer so we don't respond with a CONNECTION_CLOSE
@@ -478,6 +641,10 @@ pub fn as_frame<'a, F: connection::close::Formatter>(
 
             Some((early, one_rtt))
         }
+        // The connection is never created if the host can't be resolved
+        Error::HostResolutionFailed { .. } => None,
+        // The connection isn't closed when opening a stream times out
+        Error::OpenStreamTimeout { .. } => None,
     }
 }
 
@@ -514,6 +681,10 @@ impl From<ConnectionClose<'_>> for Error {
             let source = panic::Location::caller();
             Self::Application {
                 error: error.error_code.into(),
+                reason: error
+                    .reason
+                    .map(Reason::from_utf8_lossy)
+                    .unwrap_or_default(),
                 initiator: endpoint::Location::Remote,
                 source,
             }
@@ -549,10 +720,175 @@ impl From<Error> for std::io::ErrorKind {
             Error::EndpointClosing { .. } => ErrorKind::Other,
             Error::InvalidConfiguration { .. } => ErrorKind::Other,
             Error::Unspecified { .. } => ErrorKind::Other,
+            Error::HostResolutionFailed { .. } => ErrorKind::NotFound,
+            Error::OpenStreamTimeout { .. } => ErrorKind::TimedOut,
         }
     }
 }
 
+/// The cause of a connection close, as returned by [`Error::close_source`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CloseSource {
+    /// The local endpoint closed the connection, either from the application or due to an error
+    Local,
+
+    /// The peer closed the connection with an application error code
+    #[non_exhaustive]
+    PeerApplication { error: application::Error },
+
+    /// The peer closed the connection with a transport error code
+    ///
+    /// This includes peers which close the connection without an error, which send the
+    /// `NO_ERROR` code.
+    #[non_exhaustive]
+    PeerTransport {
+        code: transport::error::Code,
+        /// The type of the frame which triggered the error, as sent by the peer
+        frame_type: u64,
+    },
+
+    /// The connection's idle timer expired
+    IdleTimeout,
+
+    /// A stateless reset was received from the peer
+    StatelessReset,
+}
+
+impl CloseSource {
+    /// Returns the endpoint which initiated the close
+    #[inline]
+    pub fn initiator(&self) -> endpoint::Location {
+        match self {
+            Self::Local | Self::IdleTimeout => endpoint::Location::Local,
+            Self::PeerApplication { .. } | Self::PeerTransport { .. } | Self::StatelessReset => {
+                endpoint::Location::Remote
+            }
+        }
+    }
+}
+
+/// A reason phrase sent with an application error when closing a connection
+///
+/// Reasons are stored inline so [`Error`] can remain `Copy` without growing in size, which limits
+/// them to [`Reason::MAX_LEN`] bytes. Longer reasons are truncated to the nearest character
+/// boundary. Reasons are meant for diagnostics, so they're ignored when comparing errors.
+///
+/// The complete reason phrase, of up to [`Reason::MAX_FRAME_LEN`] bytes, is sent to the peer and
+/// retained by the connection for every kind of close, including transport errors and closes
+/// without an error.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Reason {
+    len: u8,
+    bytes: [u8; Reason::MAX_LEN],
+}
+
+impl Reason {
+    /// The maximum length of a reason, in bytes
+    ///
+    /// This is the largest length which fits in the space already taken up by the other
+    /// [`Error`] variants.
+    pub const MAX_LEN: usize = 29;
+
+    /// The maximum length of a reason sent in a CONNECTION_CLOSE frame, in bytes
+    ///
+    /// This ensures the frame fits in a packet of the smallest allowed size.
+    pub const MAX_FRAME_LEN: usize = 1024;
+
+    /// Creates a reason from a string, truncating it to [`Reason::MAX_LEN`] bytes
+    pub fn new(reason: &str) -> Self {
+        Self::from_valid_utf8(truncate(reason, Self::MAX_LEN).as_bytes())
+    }
+
+    /// Truncates a reason to [`Reason::MAX_FRAME_LEN`] bytes on a character boundary
+    #[inline]
+    pub fn truncate_for_frame(reason: &str) -> &str {
+        truncate(reason, Self::MAX_FRAME_LEN)
+    }
+
+    /// Creates a reason from the bytes received from the peer
+    ///
+    /// The reason is truncated to [`Reason::MAX_LEN`] bytes and at the first invalid UTF-8
+    /// sequence.
+    pub fn from_utf8_lossy(reason: &[u8]) -> Self {
+        let reason = &reason[..reason.len().min(Self::MAX_LEN)];
+        let len = match core::str::from_utf8(reason) {
+            Ok(_) => reason.len(),
+            Err(error) => error.valid_up_to(),
+        };
+
+        Self::from_valid_utf8(&reason[..len])
+    }
+
+    #[inline]
+    fn from_valid_utf8(reason: &[u8]) -> Self {
+        debug_assert!(reason.len() <= Self::MAX_LEN);
+        let mut bytes = [0; Self::MAX_LEN];
+        bytes[..reason.len()].copy_from_slice(reason);
+        Self {
+            len: reason.len() as u8,
+            bytes,
+        }
+    }
+
+    /// Returns the reason as a string
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        // the constructors only store valid UTF-8
+        core::str::from_utf8(self.as_bytes()).unwrap_or_default()
+    }
+
+    /// Returns the reason as bytes
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes[..self.len as usize]
+    }
+
+    /// Returns `true` if no reason was provided
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+#[inline]
+fn truncate(reason: &str, max_len: usize) -> &str {
+    let mut len = reason.len().min(max_len);
+    while !reason.is_char_boundary(len) {
+        len -= 1;
+    }
+    &reason[..len]
+}
+
+impl Default for Reason {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            len: 0,
+            bytes: [0; Self::MAX_LEN],
+        }
+    }
+}
+
+impl From<&str> for Reason {
+    #[inline]
+    fn from(reason: &str) -> Self {
+        Self::new(reason)
+    }
+}
+
+impl fmt::Debug for Reason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_str().fmt(f)
+    }
+}
+
+impl fmt::Display for Reason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.as_str().fmt(f)
+    }
+}
+
 /// Some connection methods may need to indicate both `ConnectionError`s and `DecryptError`s. This
 /// enum is used to allow for either error type to be returned as appropriate.
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -588,15 +924,20 @@ mod metrics {
         AsVariant,
     };
 
+    // Variants which were added after the transport codes are listed separately so the ids of
+    // the existing variants don't change
     macro_rules! impl_variants {
-        ($($name:ident => $name_str:literal),* $(,)?) => {
+        (
+            $($name:ident => $name_str:literal),* $(,)?;
+            $($ext_name:ident => $ext_name_str:literal),* $(,)?
+        ) => {
             impl AsVariant for Error {
                 const VARIANTS: &'static [Variant] = &{
                     const fn count(_id: &str) -> usize {
                         1
                     }
 
-                    const VARIANTS: usize = 0 $( + count($name_str))*;
+                    const VARIANTS: usize = 0 $( + count($name_str))* $( + count($ext_name_str))*;
 
                     const TRANSPORT: &'static [Variant] = transport::error::Code::VARIANTS;
 
@@ -626,6 +967,16 @@ mod metrics {
                         transport_idx += 1;
                     }
 
+                    $(
+                        array[id] = Variant {
+                            name: Str::new(concat!($ext_name_str, "\0")),
+                            id,
+                        };
+                        id += 1;
+                    )*
+
+                    let _ = id;
+
                     array
                 };
 
@@ -641,10 +992,19 @@ mod metrics {
                     )*
 
                     if let Error::Transport { code, ..} = self {
-                        code.variant_idx() + idx
-                    } else {
-                        panic!()
+                        return code.variant_idx() + idx;
                     }
+                    idx += transport::error::Code::VARIANTS.len();
+
+                    $(
+                        if matches!(self, Error::$ext_name { .. }) {
+                            return idx;
+                        }
+                        idx += 1;
+                    )*
+
+                    let _ = idx;
+                    panic!()
                 }
             }
 
@@ -655,6 +1015,9 @@ mod metrics {
                         Error::$name { .. } => {},
                     )*
                     Error::Transport { .. } => {},
+                    $(
+                        Error::$ext_name { .. } => {},
+                    )*
                 }
             }
 
@@ -682,6 +1045,168 @@ mod metrics {
         ImmediateClose => "IMMEDIATE_CLOSE",
         EndpointClosing => "ENDPOINT_CLOSING",
         InvalidConfiguration => "INVALID_CONFIGURATION",
-        Unspecified => "UNSPECIFIED",
+        Unspecified => "UNSPECIFIED";
+        HostResolutionFailed => "HOST_RESOLUTION_FAILED",
+        OpenStreamTimeout => "OPEN_STREAM_TIMEOUT",
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reason_test() {
+        assert!(Reason::default().is_empty());
+        assert_eq!(Reason::new("draining").as_str(), "draining");
+
+        // long reasons are truncated on a character boundary
+        for prefix in ["", "a"] {
+            let long = format!("{prefix}{}", "é".repeat(Reason::MAX_FRAME_LEN));
+
+            let reason = Reason::new(&long);
+            assert!(Reason::MAX_LEN - reason.as_bytes().len() < 2);
+            assert!(long.starts_with(reason.as_str()));
+
+            let reason = Reason::truncate_for_frame(&long);
+            assert!(Reason::MAX_FRAME_LEN - reason.len() < 2);
+            assert!(long.starts_with(reason));
+        }
+
+        // invalid UTF-8 from the peer is truncated
+        let reason = Reason::from_utf8_lossy(b"abc\xffdef");
+        assert_eq!(reason.as_str(), "abc");
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn error_size_test() {
+        // the inline reason shouldn't make the error larger than the transport variant
+        assert_eq!(core::mem::size_of::<Error>(), 48);
+    }
+
+    #[test]
+    fn reason_eq_test() {
+        let a = Error::application_with_reason(1u8.into(), Reason::new("draining"));
+        let b = Error::application_with_reason(1u8.into(), Reason::new("shutting down"));
+        assert_eq!(a, b);
+        assert_ne!(a, Error::application(2u8.into()));
+    }
+
+    #[test]
+    fn close_reason_frame_test() {
+        let address = Default::default();
+        let context = connection::close::Context::new(&address);
+        let formatter = connection::close::Development;
+
+        let error = Error::application_with_reason(1u8.into(), Reason::new("draining"));
+        let (early, one_rtt) = as_frame(&error, &formatter, &context).unwrap();
+        assert_eq!(early.reason, None);
+        assert_eq!(one_rtt.reason, Some(&b"draining"[..]));
+
+        // the reason is received by the peer
+        let error = Error::from(one_rtt);
+        assert!(matches!(
+            error,
+            Error::Application { reason, initiator: endpoint::Location::Remote, .. }
+                if reason.as_str() == "draining"
+        ));
+    }
+
+    #[test]
+    fn close_source_test() {
+        assert_eq!(
+            Error::closed(endpoint::Location::Local).close_source(),
+            Some(CloseSource::Local)
+        );
+        assert_eq!(
+            Error::application(1u8.into()).close_source(),
+            Some(CloseSource::Local)
+        );
+        assert_eq!(
+            Error::from(transport::Error::PROTOCOL_VIOLATION).close_source(),
+            Some(CloseSource::Local)
+        );
+        assert_eq!(
+            Error::idle_timer_expired().close_source(),
+            Some(CloseSource::IdleTimeout)
+        );
+        assert_eq!(
+            Error::stateless_reset().close_source(),
+            Some(CloseSource::StatelessReset)
+        );
+        assert_eq!(Error::open_stream_timeout().close_source(), None);
+
+        let mut frame = ConnectionClose {
+            error_code: 1u8.into(),
+            frame_type: None,
+            reason: None,
+        };
+        assert_eq!(
+            Error::from(frame).close_source(),
+            Some(CloseSource::PeerApplication { error: 1u8.into() })
+        );
+
+        frame.error_code = transport::Error::FLOW_CONTROL_ERROR.code.as_varint();
+        frame.frame_type = Some(8u8.into());
+        let source = Error::from(frame).close_source().unwrap();
+        assert_eq!(
+            source,
+            CloseSource::PeerTransport {
+                code: transport::Error::FLOW_CONTROL_ERROR.code,
+                frame_type: 8,
+            }
+        );
+        assert!(source.initiator().is_remote());
+
+        // closes without an error keep the code and frame type sent by the peer
+        for code in [
+            transport::Error::NO_ERROR,
+            transport::Error::APPLICATION_ERROR,
+        ] {
+            frame.error_code = code.code.as_varint();
+            let error = Error::from(frame);
+            assert!(matches!(error, Error::Closed { .. }), "{error:?}");
+            assert_eq!(
+                error.close_source(),
+                Some(CloseSource::PeerTransport {
+                    code: code.code,
+                    frame_type: 8,
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn close_registered_code_frame_test() {
+        use application::registry::{Code, Registry};
+
+        static CODES: Registry = Registry::new(&[Code::new(0x1234_5678, "TEST_ERROR", "")]);
+
+        let address = Default::default();
+        let context = connection::close::Context::new(&address);
+        let error = Error::application(0x1234_5678u32.into());
+
+        // names aren't sent unless the formatter opts in
+        let formatter = connection::close::Development;
+        let (_early, one_rtt) = as_frame(&error, &formatter, &context).unwrap();
+        assert_eq!(one_rtt.reason, None);
+
+        let formatter = connection::close::NamedCodes::new(connection::close::Development, &CODES);
+
+        // the name of a registered code is sent if no reason was provided
+        let (early, one_rtt) = as_frame(&error, &formatter, &context).unwrap();
+        assert_eq!(early.reason, None);
+        assert_eq!(one_rtt.reason, Some(&b"TEST_ERROR"[..]));
+
+        // explicit reasons take precedence
+        let error = Error::application_with_reason(0x1234_5678u32.into(), Reason::new("draining"));
+        let (_early, one_rtt) = as_frame(&error, &formatter, &context).unwrap();
+        assert_eq!(one_rtt.reason, Some(&b"draining"[..]));
+
+        // unregistered codes don't include a reason
+        let error = Error::application(1u8.into());
+        let (_early, one_rtt) = as_frame(&error, &formatter, &context).unwrap();
+        assert_eq!(one_rtt.reason, None);
+    }
+}
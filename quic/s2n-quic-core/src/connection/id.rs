@@ -31,6 +31,9 @@ pub const MAX_LIFETIME: Duration = Duration::from_secs(24 * 60 * 60); // one day
 
 macro_rules! id {
     ($type:ident, $min_len:expr) => {
+        id!($type, $min_len, false);
+    };
+    ($type:ident, $min_len:expr, $allow_empty:expr) => {
         /// Uniquely identifies a QUIC connection between 2 peers
         #[derive(Copy, Clone, Eq)]
         #[cfg_attr(any(feature = "generator", test), derive(TypeGenerator))]
@@ -74,6 +77,9 @@ macro_rules! id {
             /// The minimum length for this connection ID type
             pub const MIN_LEN: usize = $min_len;
 
+            /// Whether a zero-length connection ID is accepted in addition to `MIN_LEN`
+            pub const ALLOWS_EMPTY: bool = $allow_empty;
+
             #[cfg(any(feature = "generator", test))]
             const GENERATOR: core::ops::RangeInclusive<u8> = $min_len..=(MAX_LEN as u8);
 
@@ -151,7 +157,8 @@ macro_rules! id {
             #[inline]
             fn try_from(slice: &[u8]) -> Result<Self, Self::Error> {
                 let len = slice.len();
-                if !($type::MIN_LEN..=MAX_LEN).contains(&len) {
+                let is_valid_empty = $type::ALLOWS_EMPTY && len == 0;
+                if !is_valid_empty && !($type::MIN_LEN..=MAX_LEN).contains(&len) {
                     return Err(Error::InvalidLength);
                 }
                 let mut bytes = [0; MAX_LEN];
@@ -206,9 +213,10 @@ macro_rules! id {
 }
 
 // Connection IDs that are generated locally and used to route packets from the peer to the local
-// endpoint. s2n-QUIC does not provide zero-length connection IDs, the minimum allowable LocalId
-// is 4 bytes.
-id!(LocalId, 4);
+// endpoint. The minimum allowable non-empty LocalId is 4 bytes. Zero-length LocalIds are only
+// provided when explicitly configured for point-to-point deployments, where the endpoint does
+// not rely on connection IDs to demultiplex packets.
+id!(LocalId, 4, true);
 
 // Connection IDs used to route packets to the peer. The peer may choose to use zero-length
 // connection IDs.
@@ -403,6 +411,33 @@ pub trait Generator {
     }
 }
 
+/// Determines when the connection ID used to send packets to the peer is rotated
+///
+/// Regardless of the policy, a new connection ID is always used when migrating to a new path.
+/// Rotating more frequently makes it harder for on-path observers to correlate packets sent on
+/// the same path, at the cost of consuming connection IDs provided by the peer. Rotation only
+/// occurs if the peer has provided an unused connection ID.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RotationPolicy {
+    /// Connection IDs are only rotated when migrating to a new path
+    #[default]
+    OnMigration,
+    /// Connection IDs are rotated after the given number of packets have been sent with them
+    AfterPackets(u64),
+}
+
+impl RotationPolicy {
+    /// Returns `true` if the connection ID should be rotated after sending `packets` with it
+    #[inline]
+    pub fn should_rotate(&self, packets: u64) -> bool {
+        match self {
+            Self::OnMigration => false,
+            Self::AfterPackets(limit) => packets >= *limit,
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Interest {
     /// No new connection Ids are required
@@ -455,6 +490,9 @@ mod tests {
         let connection_id_bytes = [0u8; LocalId::MIN_LEN - 1];
         assert!(LocalId::try_from_bytes(&connection_id_bytes).is_none());
 
+        // zero-length local connection IDs are permitted
+        assert!(LocalId::try_from_bytes(&[]).is_some());
+
         let connection_id_bytes = [0u8; InitialId::MIN_LEN - 1];
         assert!(InitialId::try_from_bytes(&connection_id_bytes).is_none());
     }
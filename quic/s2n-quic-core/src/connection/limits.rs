@@ -3,14 +3,20 @@
 
 use crate::{
     ack,
+    connection::id::RotationPolicy,
     event::{api::SocketAddress, IntoEvent},
-    inet, recovery, stream,
+    inet,
+    path::probe,
+    recovery, stream,
     transport::parameters::{
-        AckDelayExponent, ActiveConnectionIdLimit, InitialFlowControlLimits, InitialMaxData,
-        InitialMaxStreamDataBidiLocal, InitialMaxStreamDataBidiRemote, InitialMaxStreamDataUni,
-        InitialMaxStreamsBidi, InitialMaxStreamsUni, InitialStreamLimits, MaxAckDelay,
-        MaxDatagramFrameSize, MaxIdleTimeout, MigrationSupport, TransportParameters,
+        AckDelayExponent, ActiveConnectionIdLimit, CompressionDictionaryId, GoAwaySupport,
+        InitialFlowControlLimits, InitialMaxData, InitialMaxStreamDataBidiLocal,
+        InitialMaxStreamDataBidiRemote, InitialMaxStreamDataUni, InitialMaxStreamsBidi,
+        InitialMaxStreamsUni, InitialStreamLimits, MaxAckDelay, MaxDatagramFrameSize,
+        MaxIdleTimeout, MaxUdpPayloadSize, MigrationSupport, PartialReliabilitySupport,
+        ResetStreamAtSupport, TransportParameters,
     },
+    varint::VarInt,
 };
 use core::time::Duration;
 use s2n_codec::decoder_invariant;
@@ -35,8 +41,21 @@ const MAX_KEEP_ALIVE_PERIOD_DEFAULT: Duration = Duration::from_secs(30);
 //# received.
 pub const ANTI_AMPLIFICATION_MULTIPLIER: u8 = 3;
 
+//= https://www.rfc-editor.org/rfc/rfc9000#section-10.2
+//# The closing and draining connection states exist to ensure that
+//# connections close cleanly and that delayed or reordered packets are
+//# properly discarded.  These states SHOULD persist for at least three
+//# times the current PTO interval as defined in [QUIC-RECOVERY].
+pub const CLOSING_PERIOD_MULTIPLIER: u8 = 3;
+
 pub const DEFAULT_STREAM_BATCH_SIZE: u8 = 1;
 
+/// The largest supported offset for the first packet number in each packet number space
+///
+/// Until the peer acknowledges a packet, packet numbers are encoded relative to 0. Keeping the
+/// offset well below 2^31 ensures they can always be encoded in the 4 bytes available.
+pub const MAX_INITIAL_PACKET_NUMBER_OFFSET: u32 = 1 << 30;
+
 #[non_exhaustive]
 #[derive(Debug)]
 pub struct ConnectionInfo<'a> {
@@ -73,10 +92,32 @@ pub struct Limits {
     pub(crate) max_handshake_duration: Duration,
     pub(crate) max_keep_alive_period: Duration,
     pub(crate) max_datagram_frame_size: MaxDatagramFrameSize,
+    pub(crate) max_udp_payload_size: MaxUdpPayloadSize,
     pub(crate) initial_round_trip_time: Duration,
     pub(crate) migration_support: MigrationSupport,
     pub(crate) anti_amplification_multiplier: u8,
+    pub(crate) closing_period_multiplier: u8,
+    pub(crate) draining_period: Option<Duration>,
+    pub(crate) max_address_validation_duration: Option<Duration>,
     pub(crate) stream_batch_size: u8,
+    pub(crate) interleave_stream_initiators: bool,
+    pub(crate) datagram_only: bool,
+    pub(crate) compression_dictionary_id: CompressionDictionaryId,
+    pub(crate) go_away_support: GoAwaySupport,
+    pub(crate) reset_stream_at_support: ResetStreamAtSupport,
+    pub(crate) stream_limit_pressure_threshold: Option<u8>,
+    pub(crate) connection_data_pressure_threshold: Option<u8>,
+    pub(crate) partial_reliability_support: PartialReliabilitySupport,
+    pub(crate) max_initial_packet_number_offset: u32,
+    pub(crate) max_frames_per_packet: Option<u32>,
+    pub(crate) max_ack_ranges_per_packet: Option<u32>,
+    pub(crate) max_crypto_bytes_per_packet: Option<u32>,
+    pub(crate) stream_open_blocked_threshold: Option<Duration>,
+    pub(crate) idle_hibernation_threshold: Option<Duration>,
+    pub(crate) path_probe_policy: Option<probe::Policy>,
+    pub(crate) connection_id_rotation_policy: RotationPolicy,
+    pub(crate) keying_material_export: bool,
+    pub(crate) transmission_weight: u8,
 }
 
 impl Default for Limits {
@@ -120,10 +161,32 @@ impl Limits {
             max_handshake_duration: MAX_HANDSHAKE_DURATION_DEFAULT,
             max_keep_alive_period: MAX_KEEP_ALIVE_PERIOD_DEFAULT,
             max_datagram_frame_size: MaxDatagramFrameSize::DEFAULT,
+            max_udp_payload_size: MaxUdpPayloadSize::DEFAULT,
             initial_round_trip_time: recovery::DEFAULT_INITIAL_RTT,
             migration_support: MigrationSupport::RECOMMENDED,
             anti_amplification_multiplier: ANTI_AMPLIFICATION_MULTIPLIER,
+            closing_period_multiplier: CLOSING_PERIOD_MULTIPLIER,
+            draining_period: None,
+            max_address_validation_duration: None,
             stream_batch_size: DEFAULT_STREAM_BATCH_SIZE,
+            interleave_stream_initiators: false,
+            datagram_only: false,
+            compression_dictionary_id: CompressionDictionaryId::DEFAULT,
+            go_away_support: GoAwaySupport::Disabled,
+            reset_stream_at_support: ResetStreamAtSupport::Disabled,
+            stream_limit_pressure_threshold: None,
+            connection_data_pressure_threshold: None,
+            partial_reliability_support: PartialReliabilitySupport::Disabled,
+            max_initial_packet_number_offset: 0,
+            max_frames_per_packet: None,
+            max_ack_ranges_per_packet: None,
+            max_crypto_bytes_per_packet: None,
+            stream_open_blocked_threshold: None,
+            idle_hibernation_threshold: None,
+            path_probe_policy: None,
+            connection_id_rotation_policy: RotationPolicy::OnMigration,
+            keying_material_export: false,
+            transmission_weight: 1,
         }
     }
 
@@ -190,6 +253,10 @@ impl Limits {
 
     /// Sets the max local limits for bidirectional streams
     ///
+    /// The local limit bounds the number of concurrent streams initiated by this endpoint. For
+    /// servers, this is the number of concurrent server-initiated bidirectional streams on each
+    /// connection.
+    ///
     /// The value set is used instead of `with_max_open_bidirectional_streams` when set.
     pub fn with_max_open_local_bidirectional_streams(
         mut self,
@@ -211,6 +278,11 @@ impl Limits {
     }
 
     setter!(
+        /// Sets the max local limits for unidirectional streams
+        ///
+        /// The local limit bounds the number of concurrent streams initiated by this endpoint.
+        /// For servers, this is the number of concurrent server-initiated unidirectional streams
+        /// on each connection.
         with_max_open_local_unidirectional_streams,
         max_open_local_unidirectional_streams,
         u64
@@ -227,6 +299,22 @@ impl Limits {
         u64
     );
     setter!(with_stream_batch_size, stream_batch_size, u8);
+
+    /// Sets whether transmission alternates between locally and peer-initiated streams
+    /// (default: false)
+    ///
+    /// By default, streams with data to send are served in the order they became ready, so an
+    /// endpoint opening many streams, such as a server pushing to a client, can crowd out the
+    /// responses on streams the peer initiated. When enabled, each transmission alternates which
+    /// of the two sets of streams is served first, giving each direction an equal share of the
+    /// packets when both have data to send.
+    pub fn with_stream_initiator_interleaving(
+        mut self,
+        enabled: bool,
+    ) -> Result<Self, ValidationError> {
+        self.interleave_stream_initiators = enabled;
+        Ok(self)
+    }
     setter!(with_ack_elicitation_interval, ack_elicitation_interval, u8);
     setter!(with_max_ack_ranges, ack_ranges_limit, u8);
     setter!(
@@ -246,6 +334,11 @@ impl Limits {
         u32
     );
     setter!(
+        /// Sets the maximum amount of time a connection may spend completing the handshake
+        /// (default: 10s)
+        ///
+        /// Connections which are still handshaking after this duration are closed with a
+        /// `MaxHandshakeDurationExceeded` error, regardless of the idle timeout.
         with_max_handshake_duration,
         max_handshake_duration,
         Duration
@@ -287,6 +380,367 @@ impl Limits {
         Ok(self)
     }
 
+    /// Sets whether the connection is restricted to QUIC DATAGRAM frames (default: false)
+    ///
+    /// If set to true, zero stream and flow control limits are advertised to the peer, so the
+    /// peer is unable to open any streams, and any attempt by the application to open a stream
+    /// fails. The connection skips all of the stream processing and never allocates any stream
+    /// state, which avoids the memory cost for applications which only exchange datagrams.
+    ///
+    /// A datagram provider must be configured on the endpoint, otherwise the connection attempt
+    /// fails with an invalid configuration error.
+    pub fn with_datagram_only(mut self, enabled: bool) -> Result<Self, ValidationError> {
+        self.datagram_only = enabled;
+        Ok(self)
+    }
+
+    setter!(
+        /// Sets the identifier of the shared dictionary used for compressing stream data
+        /// (default: 0)
+        ///
+        /// The identifier is advertised to the peer in a transport parameter. Stream compression
+        /// is only available on a connection if both endpoints advertise the same non-zero
+        /// identifier.
+        with_compression_dictionary_id,
+        compression_dictionary_id,
+        u64
+    );
+
+    /// Sets whether the GOAWAY extension frame is supported (default: false)
+    ///
+    /// Support is advertised to the peer in a transport parameter. A server can only request a
+    /// client move to a new connection with a GOAWAY frame if both endpoints enable support.
+    pub fn with_go_away(mut self, enabled: bool) -> Result<Self, ValidationError> {
+        self.go_away_support = if enabled {
+            GoAwaySupport::Enabled
+        } else {
+            GoAwaySupport::Disabled
+        };
+        Ok(self)
+    }
+
+    /// Sets whether the RESET_STREAM_AT extension frame is supported (default: false)
+    ///
+    /// Support is advertised to the peer in a transport parameter. Streams can only be reset
+    /// after delivering a prefix of their data with `reset_at` if both endpoints enable
+    /// support. Otherwise, `reset_at` behaves like `reset`.
+    pub fn with_reset_stream_at(mut self, enabled: bool) -> Result<Self, ValidationError> {
+        self.reset_stream_at_support = if enabled {
+            ResetStreamAtSupport::Enabled
+        } else {
+            ResetStreamAtSupport::Disabled
+        };
+        Ok(self)
+    }
+
+    /// Sets whether partially reliable streams are supported (default: false)
+    ///
+    /// Support is advertised to the peer in a transport parameter. Data written with an
+    /// expiration is only dropped once it expires if both endpoints enable support. Otherwise,
+    /// the expiration is ignored and the data is delivered reliably.
+    pub fn with_partial_reliability(mut self, enabled: bool) -> Result<Self, ValidationError> {
+        self.partial_reliability_support = if enabled {
+            PartialReliabilitySupport::Enabled
+        } else {
+            PartialReliabilitySupport::Disabled
+        };
+        Ok(self)
+    }
+
+    setter!(
+        /// Sets the maximum UDP payload size the endpoint is willing to receive (default: 65527)
+        ///
+        /// The value is advertised to the peer in the `max_udp_payload_size` transport parameter,
+        /// which prevents it from sending packets that exceed the limit. This is useful when
+        /// packets travel through a tunnel with overhead, where larger packets would be
+        /// fragmented. Once the handshake is confirmed, received datagrams that exceed the limit
+        /// are dropped and reported with the `PayloadSizeExceeded` reason in the
+        /// `DatagramDropped` event.
+        ///
+        /// The value must be between 1200 and 65527.
+        with_max_udp_payload_size,
+        max_udp_payload_size,
+        u16
+    );
+
+    /// Sets how long a locally closed connection remains in the closing state, as a multiple
+    /// of the current probe timeout (PTO) (default: 3)
+    ///
+    /// While closing, the connection only retains its connection IDs, so delayed packets from
+    /// the peer are still routed to it, and the CONNECTION_CLOSE packet sent in response to
+    /// them. All other connection state, such as packet protection keys and stream buffers,
+    /// is released as soon as the connection enters the closing state. Connections closed by
+    /// the peer enter the draining state instead, which is configured with
+    /// [`Self::with_draining_period`].
+    ///
+    /// The value must be at least 3, as recommended by RFC 9000.
+    pub fn with_closing_period_multiplier(mut self, value: u8) -> Result<Self, ValidationError> {
+        ensure!(
+            value >= CLOSING_PERIOD_MULTIPLIER,
+            Err(ValidationError("provided value must be at least 3"))
+        );
+
+        self.closing_period_multiplier = value;
+        Ok(self)
+    }
+
+    /// Sets the percentage of the peer's stream limit which can be used before a
+    /// `StreamLimitPressure` event is emitted (default: disabled)
+    ///
+    /// The event is emitted at most once for each stream limit issued by the peer, which gives
+    /// applications a chance to shed load before opening a stream blocks.
+    ///
+    /// The value must be between 1 and 100.
+    pub fn with_stream_limit_pressure_threshold(
+        mut self,
+        percent: u8,
+    ) -> Result<Self, ValidationError> {
+        ensure!(
+            (1..=100).contains(&percent),
+            Err(ValidationError("provided value must be between 1 and 100"))
+        );
+
+        self.stream_limit_pressure_threshold = Some(percent);
+        Ok(self)
+    }
+
+    /// Sets the percentage of the peer's connection flow control limit which can be used before a
+    /// `ConnectionDataPressure` event is emitted (default: disabled)
+    ///
+    /// The event is emitted at most once for each MAX_DATA limit issued by the peer.
+    ///
+    /// The value must be between 1 and 100.
+    pub fn with_connection_data_pressure_threshold(
+        mut self,
+        percent: u8,
+    ) -> Result<Self, ValidationError> {
+        ensure!(
+            (1..=100).contains(&percent),
+            Err(ValidationError("provided value must be between 1 and 100"))
+        );
+
+        self.connection_data_pressure_threshold = Some(percent);
+        Ok(self)
+    }
+
+    /// Sets how long a connection closed by the peer remains in the draining state
+    /// (default: released immediately)
+    ///
+    /// While draining, the connection retains its connection IDs so delayed packets from the
+    /// peer are discarded, rather than being answered with a stateless reset. Nothing is sent on
+    /// a draining connection.
+    pub fn with_draining_period(mut self, value: Duration) -> Result<Self, ValidationError> {
+        self.draining_period = Some(value);
+        Ok(self)
+    }
+
+    /// Sets the maximum amount of time a server waits for the peer to validate its address
+    /// (default: disabled)
+    ///
+    /// Until the client address is validated, either with a Retry token or by receiving a
+    /// Handshake packet, the server is limited by the anti-amplification limit and the address
+    /// may be spoofed. Connections which fail to validate the address within this duration are
+    /// discarded silently, without waiting for the handshake or idle timeouts.
+    ///
+    /// This setting only applies to server connections.
+    pub fn with_max_address_validation_duration(
+        mut self,
+        value: Duration,
+    ) -> Result<Self, ValidationError> {
+        self.max_address_validation_duration = Some(value);
+        Ok(self)
+    }
+
+    /// Sets the largest random offset for the first packet number sent in each packet number
+    /// space (default: 0)
+    ///
+    /// When set, each packet number space starts at a value chosen at random between 0
+    /// and the provided value, rather than at 0. Greasing the initial packet numbers flushes out
+    /// peers that assume packet numbers start at 0 and exercises the longer packet number
+    /// encodings early in the connection.
+    ///
+    /// Note that any non-zero value deviates from [RFC 9000 Section 12.3], which states that
+    /// packet numbers in each space start at 0. Peers are not required to accept packet numbers
+    /// which start elsewhere, so this should only be enabled for testing or when all peers are
+    /// known to tolerate it.
+    ///
+    /// The value must be at most 2^30.
+    ///
+    /// [RFC 9000 Section 12.3]: https://www.rfc-editor.org/rfc/rfc9000#section-12.3
+    pub fn with_max_initial_packet_number_offset(
+        mut self,
+        value: u32,
+    ) -> Result<Self, ValidationError> {
+        ensure!(
+            value <= MAX_INITIAL_PACKET_NUMBER_OFFSET,
+            Err(ValidationError("provided value must be at most 2^30"))
+        );
+
+        self.max_initial_packet_number_offset = value;
+        Ok(self)
+    }
+
+    /// Sets the maximum number of frames processed in a single packet (default: unlimited)
+    ///
+    /// This is one of the packet processing budgets, which defend against peers sending packets
+    /// which are valid but expensive to process. If a packet exceeds any of the budgets, the
+    /// remainder of the packet is discarded, a `PacketBudgetExceeded` event is emitted and the
+    /// connection is closed with a `PROTOCOL_VIOLATION` error. Budgets should be set well above
+    /// what well-behaved peers send, as exceeding them closes the connection.
+    ///
+    /// The value must be greater than 0.
+    pub fn with_max_frames_per_packet(mut self, value: u32) -> Result<Self, ValidationError> {
+        ensure!(
+            value > 0,
+            Err(ValidationError("provided value must be greater than 0"))
+        );
+
+        self.max_frames_per_packet = Some(value);
+        Ok(self)
+    }
+
+    /// Sets the maximum number of ACK ranges processed in a single packet (default: unlimited)
+    ///
+    /// The ranges of all of the ACK frames in the packet count towards the budget. See
+    /// [`Self::with_max_frames_per_packet`] for how the budgets are enforced.
+    ///
+    /// The value must be greater than 0.
+    pub fn with_max_ack_ranges_per_packet(mut self, value: u32) -> Result<Self, ValidationError> {
+        ensure!(
+            value > 0,
+            Err(ValidationError("provided value must be greater than 0"))
+        );
+
+        self.max_ack_ranges_per_packet = Some(value);
+        Ok(self)
+    }
+
+    /// Sets the maximum number of CRYPTO frame bytes processed in a single packet
+    /// (default: unlimited)
+    ///
+    /// The data of all of the CRYPTO frames in the packet counts towards the budget. See
+    /// [`Self::with_max_frames_per_packet`] for how the budgets are enforced.
+    ///
+    /// The value must be greater than 0.
+    pub fn with_max_crypto_bytes_per_packet(mut self, value: u32) -> Result<Self, ValidationError> {
+        ensure!(
+            value > 0,
+            Err(ValidationError("provided value must be greater than 0"))
+        );
+
+        self.max_crypto_bytes_per_packet = Some(value);
+        Ok(self)
+    }
+
+    /// Sets how long the application may wait to open a stream on the peer's stream limit before
+    /// a `StreamOpenBlocked` event is emitted (default: disabled)
+    ///
+    /// A peer which never issues additional MAX_STREAMS credit causes calls to open a stream to
+    /// wait indefinitely. The event makes these waits visible. Each blocked period is reported at
+    /// most once.
+    pub fn with_stream_open_blocked_threshold(
+        mut self,
+        value: Duration,
+    ) -> Result<Self, ValidationError> {
+        self.stream_open_blocked_threshold = Some(value);
+        Ok(self)
+    }
+
+    /// Sets how long an established connection may be idle before it releases its buffers
+    /// (default: disabled)
+    ///
+    /// A hibernating connection only retains the state needed to process the next packet, such as
+    /// its keys and connection IDs. Buffers are reallocated lazily once the connection becomes
+    /// active again, which reduces the memory footprint of large numbers of mostly-idle
+    /// connections.
+    ///
+    /// The value must be non-zero.
+    pub fn with_idle_hibernation_threshold(
+        mut self,
+        value: Duration,
+    ) -> Result<Self, ValidationError> {
+        ensure!(
+            value > Duration::ZERO,
+            Err(ValidationError("provided value must be non-zero"))
+        );
+
+        self.idle_hibernation_threshold = Some(value);
+        Ok(self)
+    }
+
+    /// Sets the policy for probing idle paths for liveness (default: disabled)
+    ///
+    /// Validated paths which haven't received a packet within the probe interval are sent a
+    /// PATH_CHALLENGE. Unlike keep-alive PINGs, unanswered probes mark the path as unresponsive,
+    /// which can be observed in the path statistics. See [`probe::Policy`] for the available
+    /// options.
+    ///
+    /// The probe interval must be non-zero.
+    pub fn with_path_probe_policy(
+        mut self,
+        policy: probe::Policy,
+    ) -> Result<Self, ValidationError> {
+        ensure!(
+            policy.initial_interval() > Duration::ZERO,
+            Err(ValidationError("probe interval must be non-zero"))
+        );
+
+        self.path_probe_policy = Some(policy);
+        Ok(self)
+    }
+
+    /// Sets when the connection ID used to send packets to the peer is rotated
+    /// (default: [`RotationPolicy::OnMigration`])
+    ///
+    /// Privacy-sensitive applications can rotate connection IDs more aggressively to make
+    /// packets harder to correlate, as long as the peer provides enough connection IDs.
+    /// Rotation can also be requested at any time with `Connection::rotate_connection_id`.
+    ///
+    /// The packet interval must be non-zero.
+    pub fn with_connection_id_rotation_policy(
+        mut self,
+        policy: RotationPolicy,
+    ) -> Result<Self, ValidationError> {
+        ensure!(
+            policy != RotationPolicy::AfterPackets(0),
+            Err(ValidationError("packet interval must be non-zero"))
+        );
+
+        self.connection_id_rotation_policy = policy;
+        Ok(self)
+    }
+
+    /// Sets whether keying material can be exported from the connection (default: false)
+    ///
+    /// If set to true, the TLS session is retained after the handshake completes so that
+    /// applications can derive keying material bound to the connection with
+    /// `Connection::export_keying_material`. Retaining the session increases the memory
+    /// used by each connection for its lifetime.
+    pub fn with_keying_material_export(mut self, enabled: bool) -> Result<Self, ValidationError> {
+        self.keying_material_export = enabled;
+        Ok(self)
+    }
+
+    /// Sets the share of the endpoint's transmission opportunities given to the connection
+    /// (default: 1)
+    ///
+    /// The endpoint services connections which are ready to transmit in a round-robin fashion.
+    /// On each turn, a connection may transmit a number of datagrams proportional to its weight,
+    /// so connections with a higher weight, such as control-plane connections, are able to make
+    /// more progress than connections with a lower weight, such as bulk transfers, when the
+    /// endpoint is busy.
+    ///
+    /// The value must be non-zero.
+    pub fn with_transmission_weight(mut self, weight: u8) -> Result<Self, ValidationError> {
+        ensure!(
+            weight > 0,
+            Err(ValidationError("provided value must be non-zero"))
+        );
+
+        self.transmission_weight = weight;
+        Ok(self)
+    }
+
     #[cfg(feature = "unstable-limits")]
     setter!(
         /// Limit how many bytes the Server sends prior to address validation (default: 3)
@@ -306,6 +760,26 @@ impl Limits {
     pub fn load_peer<A, B, C, D>(&mut self, peer_parameters: &TransportParameters<A, B, C, D>) {
         self.max_idle_timeout
             .load_peer(&peer_parameters.max_idle_timeout);
+
+        // compression is only available if both endpoints advertised the same dictionary
+        if peer_parameters.compression_dictionary_id != self.compression_dictionary_id {
+            self.compression_dictionary_id = CompressionDictionaryId::DEFAULT;
+        }
+
+        // GOAWAY frames are only exchanged if both endpoints advertised support
+        if !peer_parameters.go_away_support.is_enabled() {
+            self.go_away_support = GoAwaySupport::Disabled;
+        }
+
+        // RESET_STREAM_AT frames are only exchanged if both endpoints advertised support
+        if !peer_parameters.reset_stream_at_support.is_enabled() {
+            self.reset_stream_at_support = ResetStreamAtSupport::Disabled;
+        }
+
+        // EXPIRED_STREAM_DATA frames are only exchanged if both endpoints advertised support
+        if !peer_parameters.partial_reliability_support.is_enabled() {
+            self.partial_reliability_support = PartialReliabilitySupport::Disabled;
+        }
     }
 
     #[doc(hidden)]
@@ -322,6 +796,15 @@ impl Limits {
     #[doc(hidden)]
     #[inline]
     pub const fn initial_flow_control_limits(&self) -> InitialFlowControlLimits {
+        if self.datagram_only {
+            return InitialFlowControlLimits {
+                stream_limits: self.initial_stream_limits(),
+                max_data: VarInt::ZERO,
+                max_open_remote_bidirectional_streams: VarInt::ZERO,
+                max_open_remote_unidirectional_streams: VarInt::ZERO,
+            };
+        }
+
         InitialFlowControlLimits {
             stream_limits: self.initial_stream_limits(),
             max_data: self.data_window.as_varint(),
@@ -337,6 +820,14 @@ impl Limits {
     #[doc(hidden)]
     #[inline]
     pub const fn initial_stream_limits(&self) -> InitialStreamLimits {
+        if self.datagram_only {
+            return InitialStreamLimits {
+                max_data_bidi_local: VarInt::ZERO,
+                max_data_bidi_remote: VarInt::ZERO,
+                max_data_uni: VarInt::ZERO,
+            };
+        }
+
         InitialStreamLimits {
             max_data_bidi_local: self.bidirectional_local_data_window.as_varint(),
             max_data_bidi_remote: self.bidirectional_remote_data_window.as_varint(),
@@ -347,6 +838,14 @@ impl Limits {
     #[doc(hidden)]
     #[inline]
     pub fn stream_limits(&self) -> stream::Limits {
+        if self.datagram_only {
+            return stream::Limits {
+                max_send_buffer_size: self.max_send_buffer_size,
+                max_open_local_unidirectional_streams: 0u64.try_into().unwrap(),
+                max_open_local_bidirectional_streams: 0u64.try_into().unwrap(),
+            };
+        }
+
         stream::Limits {
             max_send_buffer_size: self.max_send_buffer_size,
             max_open_local_unidirectional_streams: self.max_open_local_unidirectional_streams,
@@ -372,6 +871,12 @@ impl Limits {
         self.max_keep_alive_period
     }
 
+    #[doc(hidden)]
+    #[inline]
+    pub fn max_udp_payload_size(&self) -> u64 {
+        self.max_udp_payload_size.as_u64()
+    }
+
     #[doc(hidden)]
     #[inline]
     pub fn initial_round_trip_time(&self) -> Duration {
@@ -390,11 +895,300 @@ impl Limits {
         self.anti_amplification_multiplier
     }
 
+    #[doc(hidden)]
+    #[inline]
+    pub fn closing_period_multiplier(&self) -> u8 {
+        self.closing_period_multiplier
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn draining_period(&self) -> Option<Duration> {
+        self.draining_period
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn max_address_validation_duration(&self) -> Option<Duration> {
+        self.max_address_validation_duration
+    }
+
     #[doc(hidden)]
     #[inline]
     pub fn stream_batch_size(&self) -> u8 {
         self.stream_batch_size
     }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn stream_initiator_interleaving(&self) -> bool {
+        self.interleave_stream_initiators
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn datagram_only(&self) -> bool {
+        self.datagram_only
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn compression_dictionary_id(&self) -> Option<VarInt> {
+        self.compression_dictionary_id.get()
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn go_away_enabled(&self) -> bool {
+        self.go_away_support.is_enabled()
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn reset_stream_at_enabled(&self) -> bool {
+        self.reset_stream_at_support.is_enabled()
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn stream_limit_pressure_threshold(&self) -> Option<u8> {
+        self.stream_limit_pressure_threshold
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn connection_data_pressure_threshold(&self) -> Option<u8> {
+        self.connection_data_pressure_threshold
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn partial_reliability_enabled(&self) -> bool {
+        self.partial_reliability_support.is_enabled()
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn max_initial_packet_number_offset(&self) -> u32 {
+        self.max_initial_packet_number_offset
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn max_frames_per_packet(&self) -> Option<u32> {
+        self.max_frames_per_packet
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn max_ack_ranges_per_packet(&self) -> Option<u32> {
+        self.max_ack_ranges_per_packet
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn max_crypto_bytes_per_packet(&self) -> Option<u32> {
+        self.max_crypto_bytes_per_packet
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn stream_open_blocked_threshold(&self) -> Option<Duration> {
+        self.stream_open_blocked_threshold
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn idle_hibernation_threshold(&self) -> Option<Duration> {
+        self.idle_hibernation_threshold
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn path_probe_policy(&self) -> Option<probe::Policy> {
+        self.path_probe_policy
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn connection_id_rotation_policy(&self) -> RotationPolicy {
+        self.connection_id_rotation_policy
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn keying_material_export(&self) -> bool {
+        self.keying_material_export
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn transmission_weight(&self) -> u8 {
+        self.transmission_weight
+    }
+}
+
+/// Overrides for the limits of a single connection
+///
+/// The limits which aren't overridden are taken from the endpoint's [`Limiter`]. Servers provide
+/// overrides when accepting a connection attempt with an accept filter, while clients provide
+/// them when connecting. The overridden flow control, stream and idle timeout values are
+/// advertised to the peer in the endpoint's transport parameters, so they apply before the
+/// handshake completes.
+///
+/// ```rust
+/// # mod s2n_quic { pub mod provider { pub mod accept_filter { pub use s2n_quic_core::endpoint::accept_filter::*; } } }
+/// use core::time::Duration;
+/// use s2n_quic::provider::accept_filter::{Outcome, Overrides};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let overrides = Overrides::default()
+///     .with_data_window(64 * 1024 * 1024)?
+///     .with_max_open_remote_bidirectional_streams(1000)?
+///     .with_max_idle_timeout(Duration::from_secs(300))?;
+///
+/// let outcome = Outcome::allow_with_overrides(overrides);
+/// # let _ = outcome;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct Overrides {
+    data_window: Option<InitialMaxData>,
+    bidirectional_local_data_window: Option<InitialMaxStreamDataBidiLocal>,
+    bidirectional_remote_data_window: Option<InitialMaxStreamDataBidiRemote>,
+    unidirectional_data_window: Option<InitialMaxStreamDataUni>,
+    max_open_remote_bidirectional_streams: Option<InitialMaxStreamsBidi>,
+    max_open_remote_unidirectional_streams: Option<InitialMaxStreamsUni>,
+    max_idle_timeout: Option<MaxIdleTimeout>,
+    transmission_weight: Option<u8>,
+}
+
+impl Overrides {
+    /// Overrides the connection-level flow control window
+    ///
+    /// See [`Limits::with_data_window`].
+    pub fn with_data_window(mut self, value: u64) -> Result<Self, ValidationError> {
+        decoder_invariant!(value <= u32::MAX.into(), "data_window must be <= u32::MAX");
+        self.data_window = Some(value.try_into()?);
+        Ok(self)
+    }
+
+    /// Overrides the flow control window of locally initiated bidirectional streams
+    ///
+    /// See [`Limits::with_bidirectional_local_data_window`].
+    pub fn with_bidirectional_local_data_window(
+        mut self,
+        value: u64,
+    ) -> Result<Self, ValidationError> {
+        decoder_invariant!(
+            value <= u32::MAX.into(),
+            "bidirectional_local_data_window must be <= u32::MAX"
+        );
+        self.bidirectional_local_data_window = Some(value.try_into()?);
+        Ok(self)
+    }
+
+    /// Overrides the flow control window of remotely initiated bidirectional streams
+    ///
+    /// See [`Limits::with_bidirectional_remote_data_window`].
+    pub fn with_bidirectional_remote_data_window(
+        mut self,
+        value: u64,
+    ) -> Result<Self, ValidationError> {
+        decoder_invariant!(
+            value <= u32::MAX.into(),
+            "bidirectional_remote_data_window must be <= u32::MAX"
+        );
+        self.bidirectional_remote_data_window = Some(value.try_into()?);
+        Ok(self)
+    }
+
+    /// Overrides the flow control window of remotely initiated unidirectional streams
+    ///
+    /// See [`Limits::with_unidirectional_data_window`].
+    pub fn with_unidirectional_data_window(mut self, value: u64) -> Result<Self, ValidationError> {
+        decoder_invariant!(
+            value <= u32::MAX.into(),
+            "unidirectional_data_window must be <= u32::MAX"
+        );
+        self.unidirectional_data_window = Some(value.try_into()?);
+        Ok(self)
+    }
+
+    /// Overrides the number of bidirectional streams the peer is allowed to open
+    ///
+    /// See [`Limits::with_max_open_remote_bidirectional_streams`].
+    pub fn with_max_open_remote_bidirectional_streams(
+        mut self,
+        value: u64,
+    ) -> Result<Self, ValidationError> {
+        self.max_open_remote_bidirectional_streams = Some(value.try_into()?);
+        Ok(self)
+    }
+
+    /// Overrides the number of unidirectional streams the peer is allowed to open
+    ///
+    /// See [`Limits::with_max_open_remote_unidirectional_streams`].
+    pub fn with_max_open_remote_unidirectional_streams(
+        mut self,
+        value: u64,
+    ) -> Result<Self, ValidationError> {
+        self.max_open_remote_unidirectional_streams = Some(value.try_into()?);
+        Ok(self)
+    }
+
+    /// Overrides the amount of time the connection can be idle before it's closed
+    ///
+    /// See [`Limits::with_max_idle_timeout`].
+    pub fn with_max_idle_timeout(mut self, value: Duration) -> Result<Self, ValidationError> {
+        self.max_idle_timeout = Some(value.try_into()?);
+        Ok(self)
+    }
+
+    /// Overrides the share of the endpoint's transmission opportunities given to the connection
+    ///
+    /// See [`Limits::with_transmission_weight`].
+    pub fn with_transmission_weight(mut self, weight: u8) -> Result<Self, ValidationError> {
+        ensure!(
+            weight > 0,
+            Err(ValidationError("provided value must be non-zero"))
+        );
+
+        self.transmission_weight = Some(weight);
+        Ok(self)
+    }
+
+    /// Applies the overrides to the limits of the endpoint
+    #[inline]
+    #[doc(hidden)]
+    pub fn apply(&self, mut limits: Limits) -> Limits {
+        if let Some(value) = self.data_window {
+            limits.data_window = value;
+        }
+        if let Some(value) = self.bidirectional_local_data_window {
+            limits.bidirectional_local_data_window = value;
+        }
+        if let Some(value) = self.bidirectional_remote_data_window {
+            limits.bidirectional_remote_data_window = value;
+        }
+        if let Some(value) = self.unidirectional_data_window {
+            limits.unidirectional_data_window = value;
+        }
+        if let Some(value) = self.max_open_remote_bidirectional_streams {
+            limits.max_open_remote_bidirectional_streams = value;
+        }
+        if let Some(value) = self.max_open_remote_unidirectional_streams {
+            limits.max_open_remote_unidirectional_streams = value;
+        }
+        if let Some(value) = self.max_idle_timeout {
+            limits.max_idle_timeout = value;
+        }
+        if let Some(value) = self.transmission_weight {
+            limits.transmission_weight = value;
+        }
+        limits
+    }
 }
 
 /// Creates limits for a given connection
@@ -412,6 +1206,7 @@ impl Limiter for Limits {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::stream::limits::LocalLimits;
 
     // Local max data limits should be <= u32::MAX
     #[test]
@@ -429,4 +1224,280 @@ mod tests {
         assert!(limits.with_bidirectional_remote_data_window(data).is_ok());
         assert!(limits.with_unidirectional_data_window(data).is_ok());
     }
+
+    // Datagram-only connections should not allow any streams to be opened
+    #[test]
+    fn datagram_only_limits() {
+        let limits = Limits::default()
+            .with_max_open_remote_bidirectional_streams(100)
+            .unwrap()
+            .with_datagram_only(true)
+            .unwrap();
+        assert!(limits.datagram_only());
+
+        let flow_control = limits.initial_flow_control_limits();
+        assert_eq!(flow_control.max_data, VarInt::ZERO);
+        assert_eq!(
+            flow_control.max_open_remote_bidirectional_streams,
+            VarInt::ZERO
+        );
+        assert_eq!(
+            flow_control.max_open_remote_unidirectional_streams,
+            VarInt::ZERO
+        );
+        assert_eq!(flow_control.stream_limits.max_data_bidi_local, VarInt::ZERO);
+
+        let stream_limits = limits.stream_limits();
+        assert_eq!(
+            stream_limits
+                .max_open_local_bidirectional_streams
+                .as_varint(),
+            VarInt::ZERO
+        );
+        assert_eq!(
+            stream_limits
+                .max_open_local_unidirectional_streams
+                .as_varint(),
+            VarInt::ZERO
+        );
+    }
+
+    // The advertised max_udp_payload_size must be a valid transport parameter value
+    #[test]
+    fn max_udp_payload_size_validation() {
+        let limits = Limits::default();
+        assert_eq!(limits.max_udp_payload_size(), 65527);
+        assert!(limits.with_max_udp_payload_size(1199).is_err());
+        assert!(limits.with_max_udp_payload_size(65528).is_err());
+
+        let limits = limits.with_max_udp_payload_size(1350).unwrap();
+        assert_eq!(limits.max_udp_payload_size(), 1350);
+    }
+
+    // The closing period can't be shorter than recommended by RFC 9000
+    #[test]
+    fn closing_period_validation() {
+        let limits = Limits::default();
+        assert_eq!(
+            limits.closing_period_multiplier(),
+            CLOSING_PERIOD_MULTIPLIER
+        );
+        assert!(limits.with_closing_period_multiplier(0).is_err());
+        assert!(limits.with_closing_period_multiplier(2).is_err());
+
+        let limits = limits.with_closing_period_multiplier(10).unwrap();
+        assert_eq!(limits.closing_period_multiplier(), 10);
+    }
+
+    // Pressure thresholds are percentages of the peer's limits
+    #[test]
+    fn pressure_threshold_validation() {
+        let limits = Limits::default();
+        assert_eq!(limits.stream_limit_pressure_threshold(), None);
+        assert_eq!(limits.connection_data_pressure_threshold(), None);
+
+        assert!(limits.with_stream_limit_pressure_threshold(0).is_err());
+        assert!(limits.with_stream_limit_pressure_threshold(101).is_err());
+        assert!(limits.with_connection_data_pressure_threshold(0).is_err());
+        assert!(limits.with_connection_data_pressure_threshold(101).is_err());
+
+        let limits = limits
+            .with_stream_limit_pressure_threshold(80)
+            .unwrap()
+            .with_connection_data_pressure_threshold(100)
+            .unwrap();
+        assert_eq!(limits.stream_limit_pressure_threshold(), Some(80));
+        assert_eq!(limits.connection_data_pressure_threshold(), Some(100));
+    }
+
+    // The per-state timeouts are disabled unless configured
+    #[test]
+    fn per_state_timeouts() {
+        let limits = Limits::default();
+        assert_eq!(limits.draining_period(), None);
+        assert_eq!(limits.max_address_validation_duration(), None);
+        assert_eq!(limits.stream_open_blocked_threshold(), None);
+        assert_eq!(limits.idle_hibernation_threshold(), None);
+        assert!(limits
+            .with_idle_hibernation_threshold(Duration::ZERO)
+            .is_err());
+
+        let limits = limits
+            .with_draining_period(Duration::from_millis(500))
+            .unwrap()
+            .with_max_address_validation_duration(Duration::from_secs(2))
+            .unwrap()
+            .with_stream_open_blocked_threshold(Duration::from_secs(1))
+            .unwrap()
+            .with_idle_hibernation_threshold(Duration::from_secs(60))
+            .unwrap();
+        assert_eq!(
+            limits.idle_hibernation_threshold(),
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(
+            limits.stream_open_blocked_threshold(),
+            Some(Duration::from_secs(1))
+        );
+        assert_eq!(limits.draining_period(), Some(Duration::from_millis(500)));
+        assert_eq!(
+            limits.max_address_validation_duration(),
+            Some(Duration::from_secs(2))
+        );
+    }
+
+    // Path probing is disabled unless configured with a non-zero interval
+    #[test]
+    fn path_probe_policy_validation() {
+        let limits = Limits::default();
+        assert_eq!(limits.path_probe_policy(), None);
+        assert!(limits
+            .with_path_probe_policy(probe::Policy::new(Duration::ZERO))
+            .is_err());
+
+        let policy = probe::Policy::new(Duration::from_millis(200));
+        let limits = limits.with_path_probe_policy(policy).unwrap();
+        assert_eq!(limits.path_probe_policy(), Some(policy));
+    }
+
+    // Connection IDs are only rotated on migration unless a non-zero packet interval is configured
+    #[test]
+    fn connection_id_rotation_policy_validation() {
+        let limits = Limits::default();
+        assert_eq!(
+            limits.connection_id_rotation_policy(),
+            RotationPolicy::OnMigration
+        );
+        assert!(limits
+            .with_connection_id_rotation_policy(RotationPolicy::AfterPackets(0))
+            .is_err());
+
+        let policy = RotationPolicy::AfterPackets(1000);
+        let limits = limits.with_connection_id_rotation_policy(policy).unwrap();
+        assert_eq!(limits.connection_id_rotation_policy(), policy);
+        assert!(!policy.should_rotate(999));
+        assert!(policy.should_rotate(1000));
+    }
+
+    // The TLS session is only retained for exporting keying material when enabled
+    #[test]
+    fn keying_material_export_limits() {
+        let limits = Limits::default();
+        assert!(!limits.keying_material_export());
+
+        let limits = limits.with_keying_material_export(true).unwrap();
+        assert!(limits.keying_material_export());
+    }
+
+    #[test]
+    fn transmission_weight_limits() {
+        let limits = Limits::default();
+        assert_eq!(limits.transmission_weight(), 1);
+        assert!(limits.with_transmission_weight(0).is_err());
+
+        let limits = limits.with_transmission_weight(4).unwrap();
+        assert_eq!(limits.transmission_weight(), 4);
+    }
+
+    // Initial packet numbers must always be encodable before the peer acknowledges a packet
+    #[test]
+    fn max_initial_packet_number_offset_validation() {
+        let limits = Limits::default();
+        assert_eq!(limits.max_initial_packet_number_offset(), 0);
+        assert!(limits
+            .with_max_initial_packet_number_offset(MAX_INITIAL_PACKET_NUMBER_OFFSET + 1)
+            .is_err());
+
+        let limits = limits
+            .with_max_initial_packet_number_offset(MAX_INITIAL_PACKET_NUMBER_OFFSET)
+            .unwrap();
+        assert_eq!(
+            limits.max_initial_packet_number_offset(),
+            MAX_INITIAL_PACKET_NUMBER_OFFSET
+        );
+    }
+
+    // Packet processing budgets are disabled unless configured
+    #[test]
+    fn packet_budget_validation() {
+        let limits = Limits::default();
+        assert_eq!(limits.max_frames_per_packet(), None);
+        assert_eq!(limits.max_ack_ranges_per_packet(), None);
+        assert_eq!(limits.max_crypto_bytes_per_packet(), None);
+
+        assert!(limits.with_max_frames_per_packet(0).is_err());
+        assert!(limits.with_max_ack_ranges_per_packet(0).is_err());
+        assert!(limits.with_max_crypto_bytes_per_packet(0).is_err());
+
+        let limits = limits
+            .with_max_frames_per_packet(64)
+            .unwrap()
+            .with_max_ack_ranges_per_packet(256)
+            .unwrap()
+            .with_max_crypto_bytes_per_packet(4096)
+            .unwrap();
+        assert_eq!(limits.max_frames_per_packet(), Some(64));
+        assert_eq!(limits.max_ack_ranges_per_packet(), Some(256));
+        assert_eq!(limits.max_crypto_bytes_per_packet(), Some(4096));
+    }
+
+    #[test]
+    fn overrides_test() {
+        let limits = Limits::new();
+
+        // no overrides keeps the endpoint's limits
+        let unchanged = Overrides::default().apply(limits);
+        assert_eq!(unchanged.data_window, limits.data_window);
+        assert_eq!(unchanged.max_idle_timeout, limits.max_idle_timeout);
+
+        let overrides = Overrides::default()
+            .with_data_window(1 << 30)
+            .unwrap()
+            .with_bidirectional_local_data_window(1 << 20)
+            .unwrap()
+            .with_max_open_remote_bidirectional_streams(1000)
+            .unwrap()
+            .with_max_idle_timeout(Duration::from_secs(300))
+            .unwrap()
+            .with_transmission_weight(8)
+            .unwrap();
+        let overridden = overrides.apply(limits);
+        assert_eq!(
+            overridden.data_window,
+            InitialMaxData::try_from(1u64 << 30).unwrap()
+        );
+        assert_eq!(
+            overridden.bidirectional_local_data_window,
+            InitialMaxStreamDataBidiLocal::try_from(1u64 << 20).unwrap()
+        );
+        assert_eq!(
+            overridden.bidirectional_remote_data_window,
+            limits.bidirectional_remote_data_window
+        );
+        assert_eq!(
+            overridden.max_open_remote_bidirectional_streams,
+            InitialMaxStreamsBidi::try_from(1000u64).unwrap()
+        );
+        assert_eq!(
+            overridden.max_open_remote_unidirectional_streams,
+            limits.max_open_remote_unidirectional_streams
+        );
+        assert_eq!(
+            overridden.max_idle_timeout(),
+            Some(Duration::from_secs(300))
+        );
+        assert_eq!(overridden.transmission_weight(), 8);
+
+        // the values are validated like the endpoint's limits
+        assert!(Overrides::default()
+            .with_data_window(u32::MAX as u64 + 1)
+            .is_err());
+        assert!(Overrides::default()
+            .with_unidirectional_data_window(u32::MAX as u64 + 1)
+            .is_err());
+        assert!(Overrides::default()
+            .with_max_open_remote_bidirectional_streams(1 << 61)
+            .is_err());
+        assert!(Overrides::default().with_transmission_weight(0).is_err());
+    }
 }
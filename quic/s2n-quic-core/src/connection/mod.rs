@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod close;
+pub mod compatibility;
 pub mod error;
 pub mod id;
 pub mod limits;
@@ -171,8 +171,9 @@ pub trait CryptoSuite {
     type RetryKey: RetryKey;
 }
 
-use crate::packet::number::{
-    PacketNumber, PacketNumberLen, PacketNumberSpace, TruncatedPacketNumber,
+use crate::{
+    io::event_loop::profile,
+    packet::number::{PacketNumber, PacketNumberLen, PacketNumberSpace, TruncatedPacketNumber},
 };
 pub use s2n_codec::encoder::scatter;
 use s2n_codec::{DecoderBufferMut, DecoderError, Encoder, EncoderBuffer};
@@ -238,7 +239,10 @@ pub fn encrypt<'a, K: Key>(
     let mut body = EncoderBuffer::new(body);
     body.advance_position(inline_len);
     let mut body = scatter::Buffer::new_with_extra(body, extra);
-    key.encrypt(packet_number.as_crypto_nonce(), header, &mut body)?;
+    {
+        let _scope = profile::scope(profile::Phase::Crypto);
+        key.encrypt(packet_number.as_crypto_nonce(), header, &mut body)?;
+    }
 
     let encrypted_payload = EncryptedPayload::new(header_len, packet_number_len, payload);
     let remaining = EncoderBuffer::new(remaining);
@@ -254,7 +258,10 @@ pub fn decrypt<'a, K: Key>(
     payload: EncryptedPayload<'a>,
 ) -> Result<(DecoderBufferMut<'a>, DecoderBufferMut<'a>), packet_protection::Error> {
     let (header, payload) = payload.split_mut();
-    key.decrypt(packet_number.as_crypto_nonce(), header, payload)?;
+    {
+        let _scope = profile::scope(profile::Phase::Crypto);
+        key.decrypt(packet_number.as_crypto_nonce(), header, payload)?;
+    }
 
     // remove the key tag from payload
     let payload_len = payload.len() - key.tag_len();
@@ -11,9 +11,18 @@ use zerocopy::{AsBytes, FromBytes, FromZeroes, Unaligned};
 mod error;
 pub use error::Error;
 
+#[cfg(feature = "alloc")]
+pub mod ocsp;
+
+#[cfg(feature = "alloc")]
+pub mod signer;
+
 #[cfg(any(test, feature = "testing"))]
 pub mod testing;
 
+#[cfg(all(feature = "alloc", any(test, feature = "testing")))]
+pub mod mock;
+
 #[cfg(all(feature = "alloc", any(test, feature = "testing")))]
 pub mod null;
 
@@ -135,6 +144,16 @@ pub trait Context<Crypto: crate::crypto::CryptoSuite> {
         session: &impl TlsSession,
     ) -> Result<(), crate::transport::Error>;
 
+    /// Called when the TLS provider completes an operation which contributes to the latency of
+    /// the handshake
+    ///
+    /// The `duration` is the amount of time the provider spent in the operation. Providers
+    /// report the operations they are able to measure, so not every operation is reported for
+    /// every handshake. The operation is ignored by default.
+    fn on_tls_operation(&mut self, operation: Operation, duration: core::time::Duration) {
+        let _ = (operation, duration);
+    }
+
     /// Receives data from the initial packet space
     ///
     /// A `max_len` may be provided to indicate how many bytes the TLS implementation
@@ -202,6 +221,28 @@ pub trait Session: crate::crypto::CryptoSuite + Sized + Send + Debug {
         true
     }
 
+    /// Releases any memory the session doesn't need while the connection is idle
+    ///
+    /// This is called when a connection hibernates with a session which is still retained after
+    /// the handshake. The memory should be reallocated on demand once the session is used again.
+    /// The default implementation does nothing.
+    fn release_buffers(&mut self) {}
+
+    /// Exports keying material from the session after the handshake has completed
+    ///
+    /// See <https://datatracker.ietf.org/doc/html/rfc5705> and <https://www.rfc-editor.org/rfc/rfc8446>.
+    ///
+    /// The default implementation returns an error for providers which do not support
+    /// exporting keying material.
+    fn export_keying_material(
+        &self,
+        _label: &[u8],
+        _context: &[u8],
+        _output: &mut [u8],
+    ) -> Result<(), TlsExportError> {
+        Err(TlsExportError::failure())
+    }
+
     /// Parses a hello message of the provided type
     ///
     /// The default implementation of this function assumes TLS messages are being exchanged.
@@ -321,6 +362,41 @@ impl crate::event::IntoEvent<crate::event::api::CipherSuite> for CipherSuite {
     }
 }
 
+/// An operation performed by the TLS provider during the handshake
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Operation {
+    /// Validating the certificate chain presented by the peer
+    CertificateValidation,
+    /// Signing the handshake transcript with the local private key
+    SignatureGeneration,
+    /// Verifying the peer's signature of the handshake transcript
+    SignatureVerification,
+    /// Decrypting a session ticket presented by a resuming client
+    TicketDecryption,
+}
+
+impl crate::event::IntoEvent<crate::event::builder::TlsOperation> for Operation {
+    #[inline]
+    fn into_event(self) -> crate::event::builder::TlsOperation {
+        use crate::event::builder::TlsOperation::*;
+        match self {
+            Self::CertificateValidation => CertificateValidation {},
+            Self::SignatureGeneration => SignatureGeneration {},
+            Self::SignatureVerification => SignatureVerification {},
+            Self::TicketDecryption => TicketDecryption {},
+        }
+    }
+}
+
+impl crate::event::IntoEvent<crate::event::api::TlsOperation> for Operation {
+    #[inline]
+    fn into_event(self) -> crate::event::api::TlsOperation {
+        let builder: crate::event::builder::TlsOperation = self.into_event();
+        builder.into_event()
+    }
+}
+
 macro_rules! handshake_type {
     ($($variant:ident($value:literal)),* $(,)?) => {
         #[derive(Clone, Copy, Debug, PartialEq, Eq, AsBytes, Unaligned)]
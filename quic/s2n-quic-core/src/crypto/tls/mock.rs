@@ -0,0 +1,541 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A TLS endpoint wrapper which injects scripted faults into the handshake
+//!
+//! NOTE: this should only be used for testing
+//!
+//! The [`Endpoint`] wraps another TLS endpoint and forwards all of the handshake messages to it.
+//! Once the session reaches the configured [`Stage`], the configured [`Fault`] is injected, which
+//! makes it possible to test how applications handle failed, stalled, or misbehaving handshakes.
+//!
+//! ```rust
+//! use s2n_quic_core::crypto::tls::{self, mock};
+//!
+//! // fail the handshake with a `HANDSHAKE_FAILURE` alert as soon as the handshake keys are derived
+//! let endpoint = mock::Endpoint::new(tls::null::Endpoint::default())
+//!     .with_fault(mock::Stage::Handshake, mock::Fault::Alert(tls::Error::HANDSHAKE_FAILURE));
+//! ```
+
+use crate::{
+    application::ServerName,
+    crypto::{
+        tls::{self, ApplicationParameters, HandshakeType, HelloOffsets, TlsSession},
+        CryptoSuite,
+    },
+    transport,
+};
+use alloc::vec::Vec;
+use bytes::Bytes;
+use core::task::{Poll, Waker};
+
+/// The stage of the handshake at which a [`Fault`] is injected
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Stage {
+    /// Before the session has produced or processed any messages
+    Initial,
+    /// After the handshake keys have been derived
+    Handshake,
+    /// After the 1-RTT keys have been derived
+    OneRtt,
+    /// After the TLS implementation has reported the handshake is complete
+    Complete,
+}
+
+/// The fault which is injected once the session reaches the configured [`Stage`]
+#[derive(Clone, Copy, Debug)]
+pub enum Fault {
+    /// The session fails with the provided TLS alert
+    Alert(tls::Error),
+    /// The session fails with the provided transport error
+    Error(transport::Error),
+    /// The session stops making progress and never completes the handshake
+    Stall,
+    /// The session sends a `Certificate` message of the provided length, which simulates a peer
+    /// delivering an oversized certificate chain
+    ///
+    /// The message is sent in the packet space of the configured [`Stage`]. Unlike the other
+    /// faults, the handshake continues to make progress afterwards.
+    OversizedCertificate(usize),
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Script {
+    stage: Stage,
+    fault: Fault,
+}
+
+impl Script {
+    /// Returns the stage at which the session stops forwarding events to the transport
+    #[inline]
+    fn stop_at(self) -> Option<Stage> {
+        match self.fault {
+            // the keys for the space need to be forwarded in order to send the certificate
+            Fault::OversizedCertificate(_) => None,
+            _ => Some(self.stage),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Endpoint<E> {
+    inner: E,
+    server: Option<Script>,
+    client: Option<Script>,
+}
+
+impl<E: tls::Endpoint> Endpoint<E> {
+    /// Wraps the provided endpoint without any faults configured
+    pub fn new(inner: E) -> Self {
+        Self {
+            inner,
+            server: None,
+            client: None,
+        }
+    }
+
+    /// Injects the `fault` into both server and client sessions once they reach the `stage`
+    #[must_use]
+    pub fn with_fault(self, stage: Stage, fault: Fault) -> Self {
+        self.with_server_fault(stage, fault)
+            .with_client_fault(stage, fault)
+    }
+
+    /// Injects the `fault` into server sessions once they reach the `stage`
+    #[must_use]
+    pub fn with_server_fault(mut self, stage: Stage, fault: Fault) -> Self {
+        self.server = Some(Script { stage, fault });
+        self
+    }
+
+    /// Injects the `fault` into client sessions once they reach the `stage`
+    #[must_use]
+    pub fn with_client_fault(mut self, stage: Stage, fault: Fault) -> Self {
+        self.client = Some(Script { stage, fault });
+        self
+    }
+}
+
+impl<E: tls::Endpoint> tls::Endpoint for Endpoint<E> {
+    type Session = Session<E::Session>;
+
+    fn new_server_session<Params: s2n_codec::EncoderValue>(
+        &mut self,
+        transport_parameters: &Params,
+    ) -> Self::Session {
+        let inner = self.inner.new_server_session(transport_parameters);
+        Session::new(inner, self.server)
+    }
+
+    fn new_client_session<Params: s2n_codec::EncoderValue>(
+        &mut self,
+        transport_parameters: &Params,
+        server_name: ServerName,
+    ) -> Self::Session {
+        let inner = self
+            .inner
+            .new_client_session(transport_parameters, server_name);
+        Session::new(inner, self.client)
+    }
+
+    fn max_tag_length(&self) -> usize {
+        self.inner.max_tag_length()
+    }
+}
+
+#[derive(Debug)]
+pub struct Session<S> {
+    inner: S,
+    script: Option<Script>,
+    stage: Stage,
+    stalled: bool,
+}
+
+impl<S> Session<S> {
+    fn new(inner: S, script: Option<Script>) -> Self {
+        Self {
+            inner,
+            script,
+            stage: Stage::Initial,
+            stalled: false,
+        }
+    }
+
+    /// Returns the current stage of the handshake
+    pub fn stage(&self) -> Stage {
+        self.stage
+    }
+
+    /// Returns the script if the session has reached the scripted stage
+    fn pending_script(&self) -> Option<Script> {
+        let script = self.script?;
+        if self.stage >= script.stage {
+            Some(script)
+        } else {
+            None
+        }
+    }
+
+    /// Applies any pending fault, returning `Some` if the session should stop polling
+    fn inject<C: tls::Context<Self>>(
+        &mut self,
+        context: &mut C,
+    ) -> Option<Poll<Result<(), transport::Error>>>
+    where
+        Self: CryptoSuite,
+    {
+        if self.stalled {
+            return Some(Poll::Pending);
+        }
+
+        let script = self.pending_script()?;
+
+        match script.fault {
+            Fault::Alert(error) => Some(Poll::Ready(Err(error.into()))),
+            Fault::Error(error) => Some(Poll::Ready(Err(error))),
+            Fault::Stall => {
+                self.stalled = true;
+                Some(Poll::Pending)
+            }
+            Fault::OversizedCertificate(len) => {
+                // the fault is only injected once
+                self.script = None;
+
+                let mut message = Vec::with_capacity(len + 4);
+                message.push(HandshakeType::Certificate as u8);
+                message.extend_from_slice(&(len as u32).to_be_bytes()[1..]);
+                message.resize(len + 4, 0);
+                let message = Bytes::from(message);
+
+                match script.stage {
+                    Stage::Initial if context.can_send_initial() => {
+                        context.send_initial(message);
+                    }
+                    Stage::Handshake if context.can_send_handshake() => {
+                        context.send_handshake(message);
+                    }
+                    Stage::OneRtt | Stage::Complete if context.can_send_application() => {
+                        context.send_application(message);
+                    }
+                    _ => {
+                        let error = transport::Error::INTERNAL_ERROR
+                            .with_reason("the packet space for the certificate is not available");
+                        return Some(Poll::Ready(Err(error)));
+                    }
+                }
+
+                None
+            }
+        }
+    }
+}
+
+impl<S: CryptoSuite> CryptoSuite for Session<S> {
+    type HandshakeKey = S::HandshakeKey;
+    type HandshakeHeaderKey = S::HandshakeHeaderKey;
+    type InitialKey = S::InitialKey;
+    type InitialHeaderKey = S::InitialHeaderKey;
+    type OneRttKey = S::OneRttKey;
+    type OneRttHeaderKey = S::OneRttHeaderKey;
+    type ZeroRttKey = S::ZeroRttKey;
+    type ZeroRttHeaderKey = S::ZeroRttHeaderKey;
+    type RetryKey = S::RetryKey;
+}
+
+impl<S: tls::Session> tls::Session for Session<S> {
+    fn poll<C: tls::Context<Self>>(
+        &mut self,
+        context: &mut C,
+    ) -> Poll<Result<(), transport::Error>> {
+        if let Some(outcome) = self.inject(context) {
+            return outcome;
+        }
+
+        let mut inner_context = Context {
+            inner: context,
+            stage: &mut self.stage,
+            stop_at: self.script.and_then(Script::stop_at),
+        };
+        let outcome = self.inner.poll(&mut inner_context);
+
+        // check if the session progressed to the scripted stage
+        if let Some(outcome) = self.inject(context) {
+            return outcome;
+        }
+
+        outcome
+    }
+
+    fn process_post_handshake_message<C: tls::Context<Self>>(
+        &mut self,
+        context: &mut C,
+    ) -> Result<(), transport::Error> {
+        let mut inner_context = Context {
+            inner: context,
+            stage: &mut self.stage,
+            stop_at: None,
+        };
+        self.inner
+            .process_post_handshake_message(&mut inner_context)
+    }
+
+    fn should_discard_session(&self) -> bool {
+        self.inner.should_discard_session()
+    }
+
+    fn export_keying_material(
+        &self,
+        label: &[u8],
+        context: &[u8],
+        output: &mut [u8],
+    ) -> Result<(), tls::TlsExportError> {
+        self.inner.export_keying_material(label, context, output)
+    }
+
+    fn parse_hello(
+        msg_type: HandshakeType,
+        header_chunk: &[u8],
+        total_received_len: u64,
+        max_hello_size: u64,
+    ) -> Result<Option<HelloOffsets>, transport::Error> {
+        S::parse_hello(msg_type, header_chunk, total_received_len, max_hello_size)
+    }
+}
+
+/// Forwards all of the calls to the outer context while tracking the handshake stage
+///
+/// Once the stage returned by [`Script::stop_at`] is reached, none of the following events are
+/// forwarded so the fault is observed before the handshake makes any further progress.
+struct Context<'a, C> {
+    inner: &'a mut C,
+    stage: &'a mut Stage,
+    stop_at: Option<Stage>,
+}
+
+impl<'a, C> Context<'a, C> {
+    #[inline]
+    fn is_stopped(&self) -> bool {
+        self.stop_at.map_or(false, |stage| *self.stage >= stage)
+    }
+
+    #[inline]
+    fn advance(&mut self, stage: Stage) {
+        *self.stage = (*self.stage).max(stage);
+    }
+}
+
+impl<'a, S, C> tls::Context<S> for Context<'a, C>
+where
+    S: tls::Session,
+    C: tls::Context<Session<S>>,
+{
+    fn on_client_application_params(
+        &mut self,
+        client_params: ApplicationParameters,
+        server_params: &mut Vec<u8>,
+    ) -> Result<(), transport::Error> {
+        self.inner
+            .on_client_application_params(client_params, server_params)
+    }
+
+    fn on_handshake_keys(
+        &mut self,
+        key: S::HandshakeKey,
+        header_key: S::HandshakeHeaderKey,
+    ) -> Result<(), transport::Error> {
+        if self.is_stopped() {
+            return Ok(());
+        }
+        self.advance(Stage::Handshake);
+        if self.is_stopped() {
+            return Ok(());
+        }
+        self.inner.on_handshake_keys(key, header_key)
+    }
+
+    fn on_zero_rtt_keys(
+        &mut self,
+        key: S::ZeroRttKey,
+        header_key: S::ZeroRttHeaderKey,
+        application_parameters: ApplicationParameters,
+    ) -> Result<(), transport::Error> {
+        if self.is_stopped() {
+            return Ok(());
+        }
+        self.inner
+            .on_zero_rtt_keys(key, header_key, application_parameters)
+    }
+
+    fn on_one_rtt_keys(
+        &mut self,
+        key: S::OneRttKey,
+        header_key: S::OneRttHeaderKey,
+        application_parameters: ApplicationParameters,
+    ) -> Result<(), transport::Error> {
+        if self.is_stopped() {
+            return Ok(());
+        }
+        self.advance(Stage::OneRtt);
+        if self.is_stopped() {
+            return Ok(());
+        }
+        self.inner
+            .on_one_rtt_keys(key, header_key, application_parameters)
+    }
+
+    fn on_server_name(&mut self, server_name: ServerName) -> Result<(), transport::Error> {
+        self.inner.on_server_name(server_name)
+    }
+
+    fn on_application_protocol(
+        &mut self,
+        application_protocol: Bytes,
+    ) -> Result<(), transport::Error> {
+        self.inner.on_application_protocol(application_protocol)
+    }
+
+    fn on_handshake_complete(&mut self) -> Result<(), transport::Error> {
+        if self.is_stopped() {
+            return Ok(());
+        }
+        self.advance(Stage::Complete);
+        if self.is_stopped() {
+            return Ok(());
+        }
+        self.inner.on_handshake_complete()
+    }
+
+    fn on_tls_exporter_ready(&mut self, session: &impl TlsSession) -> Result<(), transport::Error> {
+        self.inner.on_tls_exporter_ready(session)
+    }
+
+    fn on_tls_operation(&mut self, operation: tls::Operation, duration: core::time::Duration) {
+        self.inner.on_tls_operation(operation, duration)
+    }
+
+    fn receive_initial(&mut self, max_len: Option<usize>) -> Option<Bytes> {
+        self.inner.receive_initial(max_len)
+    }
+
+    fn receive_handshake(&mut self, max_len: Option<usize>) -> Option<Bytes> {
+        self.inner.receive_handshake(max_len)
+    }
+
+    fn receive_application(&mut self, max_len: Option<usize>) -> Option<Bytes> {
+        self.inner.receive_application(max_len)
+    }
+
+    fn can_send_initial(&self) -> bool {
+        self.inner.can_send_initial()
+    }
+
+    fn send_initial(&mut self, transmission: Bytes) {
+        if !self.is_stopped() {
+            self.inner.send_initial(transmission)
+        }
+    }
+
+    fn can_send_handshake(&self) -> bool {
+        self.inner.can_send_handshake()
+    }
+
+    fn send_handshake(&mut self, transmission: Bytes) {
+        if !self.is_stopped() {
+            self.inner.send_handshake(transmission)
+        }
+    }
+
+    fn can_send_application(&self) -> bool {
+        self.inner.can_send_application()
+    }
+
+    fn send_application(&mut self, transmission: Bytes) {
+        if !self.is_stopped() {
+            self.inner.send_application(transmission)
+        }
+    }
+
+    fn waker(&self) -> &Waker {
+        self.inner.waker()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crypto::tls::{null, testing::Pair};
+
+    fn run<E: tls::Endpoint>(mut server: E, mut client: E) -> Result<(), transport::Error> {
+        let mut pair = Pair::new(&mut server, &mut client, "localhost".into());
+
+        // drive the sessions directly since faults violate the expected handshake progression
+        for _ in 0..10 {
+            if !pair.is_handshaking() {
+                return Ok(());
+            }
+            pair.poll_start()?;
+            pair.client.context.transfer(&mut pair.server.context);
+        }
+
+        Err(transport::Error::INTERNAL_ERROR.with_reason("handshake stalled"))
+    }
+
+    fn endpoint() -> Endpoint<null::Endpoint> {
+        Endpoint::new(null::Endpoint::default())
+    }
+
+    #[test]
+    fn no_fault_test() {
+        run(endpoint(), endpoint()).unwrap();
+    }
+
+    #[test]
+    fn alert_test() {
+        for stage in [Stage::Initial, Stage::Handshake, Stage::OneRtt] {
+            let fault = Fault::Alert(tls::Error::HANDSHAKE_FAILURE);
+            let server = endpoint().with_server_fault(stage, fault);
+            let error = run(server, endpoint()).unwrap_err();
+            assert_eq!(
+                error.try_into_tls_error(),
+                Some(tls::Error::HANDSHAKE_FAILURE)
+            );
+        }
+    }
+
+    #[test]
+    fn stall_test() {
+        let client = endpoint().with_client_fault(Stage::Initial, Fault::Stall);
+        let error = run(endpoint(), client).unwrap_err();
+        assert_eq!(error.reason, "handshake stalled");
+    }
+
+    #[test]
+    fn oversized_certificate_test() {
+        let len = 100_000;
+
+        for stage in [Stage::Handshake, Stage::OneRtt, Stage::Complete] {
+            let mut server = endpoint().with_server_fault(stage, Fault::OversizedCertificate(len));
+            let mut client = endpoint();
+            let mut pair = Pair::new(&mut server, &mut client, "localhost".into());
+
+            // the handshake keeps making progress after the certificate is sent
+            for _ in 0..10 {
+                if !pair.is_handshaking() {
+                    break;
+                }
+                pair.poll_start().unwrap();
+                pair.client.context.transfer(&mut pair.server.context);
+            }
+            assert!(!pair.is_handshaking());
+
+            let context = &pair.client.context;
+            let rx = if stage == Stage::Handshake {
+                &context.handshake.rx
+            } else {
+                &context.application.rx
+            };
+            let certificate = rx.iter().find(|chunk| chunk.len() == len + 4).unwrap();
+            assert_eq!(certificate[0], HandshakeType::Certificate as u8);
+        }
+    }
+}
@@ -0,0 +1,54 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Staples OCSP responses to the server's certificates
+//!
+//! A stapled response allows clients to check the revocation status of the server's certificate
+//! without contacting the OCSP responder, as described in
+//! [RFC 6066](https://www.rfc-editor.org/rfc/rfc6066#section-8). The response is only sent to
+//! clients which request it.
+
+use alloc::sync::Arc;
+
+/// Provides the current OCSP responses for the server's certificates
+///
+/// OCSP responses expire, so long-running servers need to periodically fetch a new one from the
+/// OCSP responder. The TLS providers check the source on every handshake and staple whichever
+/// response it returns for the certificate that was selected.
+pub trait Source: 'static + Send + Sync {
+    /// Returns the current DER-encoded OCSP response for a certificate
+    ///
+    /// `server_name` is the name the certificate was added with as an SNI certificate, or `None`
+    /// for the default certificate.
+    ///
+    /// This is called for every handshake so it should return a cached response, which is
+    /// refreshed outside of the handshake. Returning `None` sends the certificate without a
+    /// stapled response.
+    fn ocsp_response(&self, server_name: Option<&str>) -> Option<Arc<[u8]>>;
+}
+
+impl<S: Source> Source for Arc<S> {
+    #[inline]
+    fn ocsp_response(&self, server_name: Option<&str>) -> Option<Arc<[u8]>> {
+        (**self).ocsp_response(server_name)
+    }
+}
+
+/// Always staples the same OCSP response to the default certificate
+#[derive(Clone, Debug)]
+pub struct Fixed(Arc<[u8]>);
+
+impl Fixed {
+    #[inline]
+    pub fn new<R: Into<Arc<[u8]>>>(response: R) -> Self {
+        Self(response.into())
+    }
+}
+
+impl Source for Fixed {
+    #[inline]
+    fn ocsp_response(&self, server_name: Option<&str>) -> Option<Arc<[u8]>> {
+        // the response is only valid for the default certificate
+        server_name.is_none().then(|| self.0.clone())
+    }
+}
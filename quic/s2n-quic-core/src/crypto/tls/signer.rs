@@ -0,0 +1,90 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Offloads handshake signatures to a remote signer, such as an HSM or KMS
+//!
+//! This allows an endpoint to authenticate with a certificate without the private key being
+//! loaded into the process. The TLS provider computes the digest of the handshake transcript and
+//! passes it to the [`Signer`], which returns the signature asynchronously.
+
+use super::Error;
+use alloc::{boxed::Box, vec::Vec};
+use core::{future::Future, pin::Pin};
+
+/// A pending signature returned by a [`Signer`]
+pub type SignFuture = Pin<Box<dyn Future<Output = Result<Vec<u8>, Error>> + Send>>;
+
+/// The type of the private key held by a [`Signer`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeyType {
+    EcdsaP256,
+    EcdsaP384,
+    EcdsaP521,
+    Rsa,
+}
+
+impl KeyType {
+    /// Returns the schemes the key can produce in a TLS 1.3 handshake, in order of preference
+    #[inline]
+    pub fn schemes(self) -> &'static [SignatureScheme] {
+        match self {
+            Self::EcdsaP256 => &[SignatureScheme::EcdsaSha256],
+            Self::EcdsaP384 => &[SignatureScheme::EcdsaSha384],
+            Self::EcdsaP521 => &[SignatureScheme::EcdsaSha512],
+            Self::Rsa => &[
+                SignatureScheme::RsaPssSha256,
+                SignatureScheme::RsaPssSha384,
+                SignatureScheme::RsaPssSha512,
+            ],
+        }
+    }
+}
+
+/// A signature scheme which can be offloaded to a [`Signer`]
+///
+/// ECDSA signatures are DER-encoded, as defined in
+/// [RFC 8446](https://www.rfc-editor.org/rfc/rfc8446#section-4.2.3). RSA-PSS signatures use a
+/// salt length equal to the length of the digest.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SignatureScheme {
+    EcdsaSha256,
+    EcdsaSha384,
+    EcdsaSha512,
+    RsaPssSha256,
+    RsaPssSha384,
+    RsaPssSha512,
+}
+
+impl SignatureScheme {
+    /// Returns the length of the digest passed to [`Signer::sign`]
+    #[inline]
+    pub fn digest_len(self) -> usize {
+        match self {
+            Self::EcdsaSha256 | Self::RsaPssSha256 => 32,
+            Self::EcdsaSha384 | Self::RsaPssSha384 => 48,
+            Self::EcdsaSha512 | Self::RsaPssSha512 => 64,
+        }
+    }
+}
+
+pub trait Signer: 'static + Send + Sync {
+    /// Returns the type of the private key
+    fn key_type(&self) -> KeyType;
+
+    /// Signs the `digest` of a handshake message with the private key
+    ///
+    /// Returning an error fails the handshake.
+    fn sign(&self, scheme: SignatureScheme, digest: &[u8]) -> SignFuture;
+}
+
+impl<S: Signer> Signer for alloc::sync::Arc<S> {
+    #[inline]
+    fn key_type(&self) -> KeyType {
+        (**self).key_type()
+    }
+
+    #[inline]
+    fn sign(&self, scheme: SignatureScheme, digest: &[u8]) -> SignFuture {
+        (**self).sign(scheme, digest)
+    }
+}
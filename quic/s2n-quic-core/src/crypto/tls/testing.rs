@@ -96,6 +96,15 @@ impl super::Session for Session {
     ) -> Poll<Result<(), transport::Error>> {
         todo!("implement dummy handshake")
     }
+
+    fn export_keying_material(
+        &self,
+        label: &[u8],
+        context: &[u8],
+        output: &mut [u8],
+    ) -> Result<(), TlsExportError> {
+        self.tls_exporter(label, context, output)
+    }
 }
 
 impl CryptoSuite for Session {
@@ -403,6 +412,8 @@ where
     pub server_name: Option<Bytes>,
     pub application_protocol: Option<Bytes>,
     pub transport_parameters: Option<Bytes>,
+    /// The operations reported by the TLS provider, in order
+    pub operations: Vec<tls::Operation>,
     endpoint: endpoint::Type,
     pub state: State,
     waker: Waker,
@@ -423,6 +434,7 @@ where
             .field("sni", &self.server_name)
             .field("application_protocol", &self.application_protocol)
             .field("transport_parameters", &self.transport_parameters)
+            .field("operations", &self.operations)
             .field("endpoint", &self.endpoint)
             .finish()
     }
@@ -443,6 +455,7 @@ where
             server_name: None,
             application_protocol: None,
             transport_parameters: None,
+            operations: Vec::new(),
             endpoint,
             state,
             waker,
@@ -755,6 +768,11 @@ where
         Ok(())
     }
 
+    fn on_tls_operation(&mut self, operation: tls::Operation, _duration: core::time::Duration) {
+        self.log("tls operation");
+        self.operations.push(operation);
+    }
+
     fn receive_initial(&mut self, max_len: Option<usize>) -> Option<Bytes> {
         self.log("rx initial");
         self.initial.rx(max_len)
@@ -5,10 +5,12 @@
 
 use crate::{
     connection,
-    datagram::{ConnectionInfo, Packet, PreConnectionInfo, ReceiveContext},
+    datagram::{
+        ConnectionInfo, DropReason, Dropped, Packet, PreConnectionInfo, ReceiveContext, WriteError,
+    },
     transport::parameters::MaxDatagramFrameSize,
 };
-use alloc::collections::VecDeque;
+use alloc::{collections::VecDeque, vec::Vec};
 use bytes::Bytes;
 use core::{
     fmt,
@@ -23,10 +25,22 @@ use core::{
 /// and Receiver behavior can be swapped out by implementing the respective [`Sender`](s2n-quic-core::datagram::traits::Sender) and
 /// [`Receiver`](s2n-quic-core::datagram::traits::Receiver) traits.
 ///
-#[derive(Debug, Default)]
+/// By default, each connection can queue up to 200 datagrams in each direction. Sending a
+/// datagram on a full queue is rejected, while receiving a datagram on a full queue drops the
+/// oldest one. Both behaviors can be configured with the [`EndpointBuilder`]. Datagrams which
+/// are dropped locally are reported with the `DatagramFrameDropped` event.
+#[derive(Debug)]
 pub struct Endpoint {
     send_queue_capacity: usize,
     recv_queue_capacity: usize,
+    send_queue_policy: QueuePolicy,
+    recv_queue_policy: QueuePolicy,
+}
+
+impl Default for Endpoint {
+    fn default() -> Self {
+        EndpointBuilder::default().build().unwrap()
+    }
 }
 
 impl Endpoint {
@@ -37,10 +51,35 @@ impl Endpoint {
 }
 
 /// A builder for the default datagram endpoint
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct EndpointBuilder {
     send_queue_capacity: usize,
     recv_queue_capacity: usize,
+    send_queue_policy: QueuePolicy,
+    recv_queue_policy: QueuePolicy,
+}
+
+impl Default for EndpointBuilder {
+    fn default() -> Self {
+        Self {
+            send_queue_capacity: DEFAULT_QUEUE_CAPACITY,
+            recv_queue_capacity: DEFAULT_QUEUE_CAPACITY,
+            send_queue_policy: QueuePolicy::RejectNew,
+            recv_queue_policy: QueuePolicy::DropOldest,
+        }
+    }
+}
+
+const DEFAULT_QUEUE_CAPACITY: usize = 200;
+
+/// The behavior of a datagram queue once it reaches capacity
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QueuePolicy {
+    /// The oldest datagram on the queue is dropped to make room for the new one
+    DropOldest,
+    /// The new datagram is not added to the queue
+    RejectNew,
 }
 
 #[non_exhaustive]
@@ -64,6 +103,9 @@ impl fmt::Display for BuilderError {
 
 /// Builder for the datagram endpoint
 impl EndpointBuilder {
+    /// Sets the number of datagrams each connection can queue for sending
+    ///
+    /// Defaults to 200.
     pub fn with_send_capacity(mut self, capacity: usize) -> Result<Self, BuilderError> {
         if capacity == 0 {
             return Err(BuilderError::ZeroCapacity);
@@ -72,6 +114,10 @@ impl EndpointBuilder {
         Ok(self)
     }
 
+    /// Sets the number of received datagrams each connection buffers until the application
+    /// reads them
+    ///
+    /// Defaults to 200.
     pub fn with_recv_capacity(mut self, capacity: usize) -> Result<Self, BuilderError> {
         if capacity == 0 {
             return Err(BuilderError::ZeroCapacity);
@@ -80,10 +126,29 @@ impl EndpointBuilder {
         Ok(self)
     }
 
+    /// Sets the behavior of [`Sender::send_datagram`] when the send queue is full
+    ///
+    /// Defaults to [`QueuePolicy::RejectNew`], which returns
+    /// [`DatagramError::QueueAtCapacity`] to the application.
+    pub fn with_send_policy(mut self, policy: QueuePolicy) -> Self {
+        self.send_queue_policy = policy;
+        self
+    }
+
+    /// Sets the behavior of the receive queue when a datagram arrives while it is full
+    ///
+    /// Defaults to [`QueuePolicy::DropOldest`].
+    pub fn with_recv_policy(mut self, policy: QueuePolicy) -> Self {
+        self.recv_queue_policy = policy;
+        self
+    }
+
     pub fn build(self) -> Result<Endpoint, core::convert::Infallible> {
         Ok(Endpoint {
             send_queue_capacity: self.send_queue_capacity,
             recv_queue_capacity: self.recv_queue_capacity,
+            send_queue_policy: self.send_queue_policy,
+            recv_queue_policy: self.recv_queue_policy,
         })
     }
 }
@@ -96,11 +161,13 @@ impl super::Endpoint for Endpoint {
         (
             Sender::builder()
                 .with_capacity(self.send_queue_capacity)
+                .with_policy(self.send_queue_policy)
                 .with_connection_info(info)
                 .build()
                 .unwrap(),
             Receiver::builder()
                 .with_capacity(self.recv_queue_capacity)
+                .with_policy(self.recv_queue_policy)
                 .with_max_datagram_frame_size(MaxDatagramFrameSize::RECOMMENDED)
                 .build()
                 .unwrap(),
@@ -115,11 +182,13 @@ impl super::Endpoint for Endpoint {
 /// Handles receiving unreliable datagrams.
 ///
 /// Stores the queue of datagrams received from the peer.
-/// Old datagrams will be popped off the queue in favor of new datagrams if the
+/// By default, old datagrams will be popped off the queue in favor of new datagrams if the
 /// queue capacity is reached.
 pub struct Receiver {
     queue: VecDeque<Bytes>,
     capacity: usize,
+    policy: QueuePolicy,
+    dropped: Vec<Dropped>,
     waker: Option<Waker>,
     max_datagram_frame_size: u64,
     error: Option<connection::Error>,
@@ -162,12 +231,27 @@ impl Receiver {
 impl super::Receiver for Receiver {
     fn on_datagram(&mut self, _: &ReceiveContext, datagram: &[u8]) {
         if datagram.len() as u64 > self.max_datagram_frame_size {
+            self.dropped.push(Dropped::new(
+                datagram.len(),
+                DropReason::ExceedsTransportLimits,
+            ));
             return;
         }
-        // The oldest datagram on the queue is popped off if the queue is full.
-        // Configure this behavior by implementing a custom Receiver for datagrams.
+
         if self.queue.len() == self.capacity {
-            self.queue.pop_front();
+            match self.policy {
+                QueuePolicy::DropOldest => {
+                    if let Some(oldest) = self.queue.pop_front() {
+                        self.dropped
+                            .push(Dropped::new(oldest.len(), DropReason::QueueFull));
+                    }
+                }
+                QueuePolicy::RejectNew => {
+                    self.dropped
+                        .push(Dropped::new(datagram.len(), DropReason::QueueFull));
+                    return;
+                }
+            }
         }
 
         self.queue
@@ -185,6 +269,11 @@ impl super::Receiver for Receiver {
             w.wake();
         }
     }
+
+    #[inline]
+    fn drain_dropped<F: FnMut(Dropped)>(&mut self, on_dropped: F) {
+        self.dropped.drain(..).for_each(on_dropped);
+    }
 }
 
 // A builder for the default datagram receiver
@@ -194,13 +283,15 @@ impl super::Receiver for Receiver {
 #[derive(Debug)]
 struct ReceiverBuilder {
     queue_capacity: usize,
+    policy: QueuePolicy,
     max_datagram_frame_size: u64,
 }
 
 impl Default for ReceiverBuilder {
     fn default() -> Self {
         Self {
-            queue_capacity: 200,
+            queue_capacity: DEFAULT_QUEUE_CAPACITY,
+            policy: QueuePolicy::DropOldest,
             max_datagram_frame_size: MaxDatagramFrameSize::RECOMMENDED,
         }
     }
@@ -213,6 +304,12 @@ impl ReceiverBuilder {
         self
     }
 
+    /// Sets the behavior of the receiver queue when it is full
+    pub fn with_policy(mut self, policy: QueuePolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
     pub fn with_max_datagram_frame_size(mut self, size: u64) -> Self {
         self.max_datagram_frame_size = size;
         self
@@ -223,6 +320,8 @@ impl ReceiverBuilder {
         Ok(Receiver {
             queue: VecDeque::with_capacity(self.queue_capacity),
             capacity: self.queue_capacity,
+            policy: self.policy,
+            dropped: Vec::new(),
             waker: None,
             max_datagram_frame_size: self.max_datagram_frame_size,
             error: None,
@@ -251,6 +350,8 @@ impl ReceiverBuilder {
 pub struct Sender {
     queue: VecDeque<Datagram>,
     capacity: usize,
+    policy: QueuePolicy,
+    dropped: Vec<Dropped>,
     min_packet_space: usize,
     max_packet_space: usize,
     dropped_datagrams: u64,
@@ -258,6 +359,9 @@ pub struct Sender {
     waker: Option<Waker>,
     max_datagram_payload: u64,
     error: Option<connection::Error>,
+    /// The number of datagrams at the front of the queue which were enqueued by
+    /// `send_datagram_batch` and are written without ceding packet space to stream data
+    batch_len: usize,
 }
 
 #[non_exhaustive]
@@ -366,7 +470,7 @@ impl Sender {
         // Pop oldest datagram off the queue if it is at capacity
         let mut oldest = None;
         if self.queue.len() == self.capacity {
-            oldest = self.queue.pop_front();
+            oldest = self.pop_front();
         }
 
         let datagram = Datagram { data };
@@ -380,7 +484,10 @@ impl Sender {
 
     /// Adds datagrams on the queue to be sent
     ///
-    /// If the queue is full the newest datagram is not added and an error is returned.
+    /// The behavior when the queue is full depends on the configured [`QueuePolicy`]. With
+    /// [`QueuePolicy::RejectNew`] the newest datagram is not added and an error is returned.
+    /// With [`QueuePolicy::DropOldest`] the oldest datagram on the queue is dropped to make
+    /// space for the newest datagram.
     ///
     /// # Return value
     /// - `Ok()` if the datagram was enqueued for sending
@@ -397,7 +504,10 @@ impl Sender {
         }
 
         if self.queue.len() == self.capacity {
-            return Err(DatagramError::QueueAtCapacity);
+            match self.policy {
+                QueuePolicy::DropOldest => self.drop_oldest(),
+                QueuePolicy::RejectNew => return Err(DatagramError::QueueAtCapacity),
+            }
         }
 
         let datagram = Datagram { data };
@@ -405,6 +515,70 @@ impl Sender {
         Ok(())
     }
 
+    /// Adds a batch of datagrams on the queue to be sent
+    ///
+    /// The datagrams of a batch are written back-to-back: packets carrying them don't cede
+    /// space to pending stream data until the whole batch has been written. This fills each
+    /// packet with DATAGRAM frames so the packets of a transmission have the same size, which
+    /// allows the platform to send them as segments of a single GSO datagram. Sending datagrams
+    /// one at a time instead alternates packets with stream data, which produces differently
+    /// sized packets that can't be coalesced.
+    ///
+    /// The behavior when the queue is full depends on the configured [`QueuePolicy`]. With
+    /// [`QueuePolicy::RejectNew`] datagrams are taken from the iterator until the queue reaches
+    /// capacity and the remaining datagrams are left in the iterator, so callers can pass
+    /// `iter.by_ref()` and retry the rest once there is space on the queue. With
+    /// [`QueuePolicy::DropOldest`] the whole iterator is consumed and the oldest datagrams on
+    /// the queue are dropped to make space.
+    ///
+    /// # Return value
+    /// - `Ok(usize)` with the number of datagrams that were enqueued for sending
+    /// - `Err(DatagramError)` if some error occurred. Datagrams preceding the one which caused
+    ///   the error remain enqueued.
+    pub fn send_datagram_batch<I>(&mut self, datagrams: I) -> Result<usize, DatagramError>
+    where
+        I: IntoIterator<Item = Bytes>,
+    {
+        // If there was some connection-level error the user is not allowed to add
+        // datagrams to the queue as they will never be sent.
+        if let Some(err) = self.error {
+            return Err(DatagramError::ConnectionError { error: err });
+        }
+
+        let mut datagrams = datagrams.into_iter();
+        let mut count = 0;
+
+        let result = loop {
+            // check the capacity before taking the next datagram so it isn't lost when the queue
+            // rejects new datagrams
+            if self.queue.len() == self.capacity && self.policy == QueuePolicy::RejectNew {
+                break Ok(count);
+            }
+
+            let Some(data) = datagrams.next() else {
+                break Ok(count);
+            };
+
+            if data.len() as u64 > self.max_datagram_payload {
+                break Err(DatagramError::ExceedsPeerTransportLimits);
+            }
+
+            if self.queue.len() == self.capacity {
+                self.drop_oldest();
+            }
+
+            self.queue.push_back(Datagram { data });
+            count += 1;
+        };
+
+        if count > 0 {
+            // everything up to the end of the batch is written without ceding to stream data
+            self.batch_len = self.queue.len();
+        }
+
+        result
+    }
+
     /// Filter through the datagrams in the send queue and only keep those that
     /// match a predicate
     pub fn retain_datagrams<F>(&mut self, f: F)
@@ -412,6 +586,24 @@ impl Sender {
         F: FnMut(&Datagram) -> bool,
     {
         self.queue.retain(f);
+        self.batch_len = self.batch_len.min(self.queue.len());
+    }
+
+    /// Pops the oldest datagram off the queue
+    #[inline]
+    fn pop_front(&mut self) -> Option<Datagram> {
+        let datagram = self.queue.pop_front()?;
+        self.batch_len = self.batch_len.saturating_sub(1);
+        Some(datagram)
+    }
+
+    /// Drops the oldest datagram on the queue to make space for a new one
+    #[inline]
+    fn drop_oldest(&mut self) {
+        if let Some(oldest) = self.pop_front() {
+            self.dropped
+                .push(Dropped::new(oldest.data.len(), DropReason::QueueFull));
+        }
     }
 
     fn record_capacity_stats(&mut self, capacity: usize) {
@@ -464,8 +656,9 @@ impl Sender {
 impl super::Sender for Sender {
     #[inline]
     fn on_transmit<P: Packet>(&mut self, packet: &mut P) {
-        // Cede space to stream data when datagrams are not prioritized
-        if packet.has_pending_streams() && !packet.datagrams_prioritized() {
+        // Cede space to stream data when datagrams are not prioritized, unless a batch of
+        // datagrams is still being written
+        if packet.has_pending_streams() && !packet.datagrams_prioritized() && self.batch_len == 0 {
             return;
         }
 
@@ -474,7 +667,8 @@ impl super::Sender for Sender {
         let mut has_written = false;
 
         while packet.remaining_capacity() > 0 {
-            let Some(datagram) = self.queue.pop_front() else {
+            let is_batched = self.batch_len > 0;
+            let Some(datagram) = self.pop_front() else {
                 break;
             };
 
@@ -484,20 +678,31 @@ impl super::Sender for Sender {
                 // queue when packet space remaining is smaller than the datagram.
                 if has_written {
                     self.queue.push_front(datagram);
+                    self.batch_len += is_batched as usize;
                     break;
                 }
 
                 // the datagram is too large for the current packet and unlikely to ever fit so
                 // record a metric and try the next datagram in the queue
                 self.dropped_datagrams += 1;
+                self.dropped.push(Dropped::new(
+                    datagram.data.len(),
+                    DropReason::ExceedsPacketCapacity,
+                ));
                 continue;
             }
 
             match packet.write_datagram(&datagram.data) {
                 Ok(()) => has_written = true,
-                Err(_error) => {
-                    // TODO log this
+                Err(error) => {
+                    let reason = match error {
+                        WriteError::ExceedsPacketCapacity => DropReason::ExceedsPacketCapacity,
+                        WriteError::ExceedsPeerTransportLimits => {
+                            DropReason::ExceedsTransportLimits
+                        }
+                    };
                     self.dropped_datagrams += 1;
+                    self.dropped.push(Dropped::new(datagram.data.len(), reason));
                     continue;
                 }
             }
@@ -523,6 +728,11 @@ impl super::Sender for Sender {
             w.wake();
         }
     }
+
+    #[inline]
+    fn drain_dropped<F: FnMut(Dropped)>(&mut self, on_dropped: F) {
+        self.dropped.drain(..).for_each(on_dropped);
+    }
 }
 
 /// A builder for the default datagram sender
@@ -531,13 +741,15 @@ impl super::Sender for Sender {
 #[derive(Debug)]
 struct SenderBuilder {
     queue_capacity: usize,
+    policy: QueuePolicy,
     max_datagram_payload: u64,
 }
 
 impl Default for SenderBuilder {
     fn default() -> Self {
         Self {
-            queue_capacity: 200,
+            queue_capacity: DEFAULT_QUEUE_CAPACITY,
+            policy: QueuePolicy::RejectNew,
             max_datagram_payload: 0,
         }
     }
@@ -550,6 +762,12 @@ impl SenderBuilder {
         self
     }
 
+    /// Sets the behavior of [`Sender::send_datagram`] when the queue is full
+    pub fn with_policy(mut self, policy: QueuePolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
     /// Gives the default sender relevant connection info
     pub fn with_connection_info(mut self, connection_info: &ConnectionInfo) -> Self {
         self.max_datagram_payload = connection_info.max_datagram_payload;
@@ -561,6 +779,8 @@ impl SenderBuilder {
         Ok(Sender {
             queue: VecDeque::with_capacity(self.queue_capacity),
             capacity: self.queue_capacity,
+            policy: self.policy,
+            dropped: Vec::new(),
             max_datagram_payload: self.max_datagram_payload,
             dropped_datagrams: 0,
             max_packet_space: 0,
@@ -568,6 +788,7 @@ impl SenderBuilder {
             smoothed_packet_size: 0.0,
             waker: None,
             error: None,
+            batch_len: 0,
         })
     }
 }
@@ -659,6 +880,189 @@ mod tests {
         assert!(default_sender.queue.is_empty());
     }
 
+    #[test]
+    fn send_datagram_drop_oldest() {
+        let conn_info = ConnectionInfo::new(100, noop_waker());
+        let mut default_sender = Sender::builder()
+            .with_capacity(2)
+            .with_policy(QueuePolicy::DropOldest)
+            .with_connection_info(&conn_info)
+            .build()
+            .unwrap();
+        let datagram_0 = bytes::Bytes::from_static(&[1, 2, 3]);
+        let datagram_1 = bytes::Bytes::from_static(&[4, 5, 6]);
+        let datagram_2 = bytes::Bytes::from_static(&[7, 8, 9, 10]);
+        assert_eq!(default_sender.send_datagram(datagram_0), Ok(()));
+        assert_eq!(default_sender.send_datagram(datagram_1), Ok(()));
+        // The oldest datagram is dropped to make space for the third datagram
+        assert_eq!(default_sender.send_datagram(datagram_2), Ok(()));
+
+        let first = default_sender.queue.pop_front().unwrap();
+        assert_eq!(first.data[..], [4, 5, 6]);
+        let second = default_sender.queue.pop_front().unwrap();
+        assert_eq!(second.data[..], [7, 8, 9, 10]);
+        assert!(default_sender.queue.is_empty());
+
+        // The dropped datagram is reported once
+        let mut dropped = vec![];
+        crate::datagram::Sender::drain_dropped(&mut default_sender, |d| dropped.push(d));
+        assert_eq!(dropped, [Dropped::new(3, DropReason::QueueFull)]);
+        crate::datagram::Sender::drain_dropped(&mut default_sender, |_| panic!());
+    }
+
+    #[test]
+    fn send_datagram_batch() {
+        let conn_info = ConnectionInfo {
+            max_datagram_payload: 100,
+            waker: noop_waker(),
+        };
+        // Create a default sender queue that only holds three elements
+        let mut default_sender = Sender::builder()
+            .with_capacity(3)
+            .with_connection_info(&conn_info)
+            .build()
+            .unwrap();
+
+        let mut datagrams = (0..5u8).map(|i| bytes::Bytes::from(vec![i; 3]));
+
+        // Only enough datagrams to fill the queue are taken from the iterator
+        assert_eq!(
+            default_sender.send_datagram_batch(datagrams.by_ref()),
+            Ok(3)
+        );
+        assert_eq!(
+            default_sender.send_datagram_batch(datagrams.by_ref()),
+            Ok(0)
+        );
+
+        // All of the queued datagrams are written to a single packet without ceding the space
+        // to pending stream data
+        let mut packet = MockPacket {
+            has_pending_streams: true,
+            datagrams_prioritized: false,
+            remaining_capacity: 100,
+        };
+        crate::datagram::Sender::on_transmit(&mut default_sender, &mut packet);
+        assert_eq!(packet.remaining_capacity, 100 - 3 * 3);
+        assert!(default_sender.queue.is_empty());
+        assert_eq!(default_sender.batch_len, 0);
+
+        // The remaining datagrams were left in the iterator
+        assert_eq!(
+            default_sender.send_datagram_batch(datagrams.by_ref()),
+            Ok(2)
+        );
+        assert_eq!(
+            default_sender.queue.pop_front().unwrap().data[..],
+            [3, 3, 3]
+        );
+        assert_eq!(
+            default_sender.queue.pop_front().unwrap().data[..],
+            [4, 4, 4]
+        );
+
+        // Datagrams preceding an oversized datagram remain enqueued
+        let batch = [
+            bytes::Bytes::from_static(&[1]),
+            bytes::Bytes::from(vec![0; 101]),
+        ];
+        assert_eq!(
+            default_sender.send_datagram_batch(batch),
+            Err(DatagramError::ExceedsPeerTransportLimits)
+        );
+        assert_eq!(default_sender.queue.len(), 1);
+        default_sender.queue.clear();
+
+        // Connection-level error means new datagrams are not added to the queue
+        let conn_err = connection::Error::closed(crate::endpoint::Location::Remote);
+        default_sender.error = Some(conn_err);
+        assert_eq!(
+            default_sender.send_datagram_batch([bytes::Bytes::from_static(&[7, 8, 9])]),
+            Err(DatagramError::ConnectionError { error: conn_err })
+        );
+        assert!(default_sender.queue.is_empty());
+    }
+
+    #[test]
+    fn send_datagram_batch_prioritization() {
+        let conn_info = ConnectionInfo::new(100, noop_waker());
+        let mut default_sender = Sender::builder()
+            .with_capacity(10)
+            .with_connection_info(&conn_info)
+            .build()
+            .unwrap();
+
+        let batch = (0..4u8).map(|i| bytes::Bytes::from(vec![i; 40]));
+        assert_eq!(default_sender.send_datagram_batch(batch), Ok(4));
+        assert_eq!(default_sender.batch_len, 4);
+
+        // the batch spans multiple packets, none of which cede space to stream data
+        for expected_batch_len in [2, 0] {
+            let mut packet = MockPacket {
+                has_pending_streams: true,
+                datagrams_prioritized: false,
+                remaining_capacity: 100,
+            };
+            crate::datagram::Sender::on_transmit(&mut default_sender, &mut packet);
+            assert_eq!(packet.remaining_capacity, 100 - 2 * 40);
+            assert_eq!(default_sender.batch_len, expected_batch_len);
+        }
+
+        // datagrams sent individually cede space to stream data
+        default_sender
+            .send_datagram(bytes::Bytes::from_static(&[1, 2, 3]))
+            .unwrap();
+        let mut packet = MockPacket {
+            has_pending_streams: true,
+            datagrams_prioritized: false,
+            remaining_capacity: 100,
+        };
+        crate::datagram::Sender::on_transmit(&mut default_sender, &mut packet);
+        assert_eq!(packet.remaining_capacity, 100);
+        assert_eq!(default_sender.queue.len(), 1);
+    }
+
+    #[test]
+    fn send_datagram_batch_drop_oldest() {
+        let conn_info = ConnectionInfo::new(100, noop_waker());
+        let mut default_sender = Sender::builder()
+            .with_capacity(3)
+            .with_connection_info(&conn_info)
+            .with_policy(QueuePolicy::DropOldest)
+            .build()
+            .unwrap();
+
+        default_sender
+            .send_datagram(bytes::Bytes::from_static(&[9]))
+            .unwrap();
+
+        // the whole batch is consumed and the oldest datagrams make room for it
+        let mut datagrams = (0..4u8).map(|i| bytes::Bytes::from(vec![i; 3]));
+        assert_eq!(
+            default_sender.send_datagram_batch(datagrams.by_ref()),
+            Ok(4)
+        );
+        assert!(datagrams.next().is_none());
+
+        let queued: Vec<_> = default_sender
+            .queue
+            .iter()
+            .map(|datagram| datagram.data[0])
+            .collect();
+        assert_eq!(queued, [1, 2, 3]);
+        assert_eq!(default_sender.batch_len, 3);
+
+        let mut dropped = vec![];
+        crate::datagram::Sender::drain_dropped(&mut default_sender, |d| dropped.push(d));
+        assert_eq!(
+            dropped,
+            [
+                Dropped::new(1, DropReason::QueueFull),
+                Dropped::new(3, DropReason::QueueFull),
+            ]
+        );
+    }
+
     #[test]
     fn poll_send_datagram() {
         let conn_info = ConnectionInfo::new(100, noop_waker());
@@ -896,6 +1300,38 @@ mod tests {
         assert!(receiver.queue.pop_front().is_none());
     }
 
+    #[test]
+    fn on_datagram_reject_new() {
+        let mut receiver = Receiver::builder()
+            .with_capacity(2)
+            .with_policy(QueuePolicy::RejectNew)
+            .with_max_datagram_frame_size(5)
+            .build()
+            .unwrap();
+
+        let ctx = fake_receive_context();
+        crate::datagram::Receiver::on_datagram(&mut receiver, &ctx, &[1, 2, 3]);
+        crate::datagram::Receiver::on_datagram(&mut receiver, &ctx, &[4, 5, 6]);
+        // The queue is full so the newest datagram is dropped
+        crate::datagram::Receiver::on_datagram(&mut receiver, &ctx, &[7, 8, 9, 10]);
+        // The datagram is larger than max_datagram_frame_size
+        crate::datagram::Receiver::on_datagram(&mut receiver, &ctx, &[0; 6]);
+
+        assert_eq!(receiver.queue.pop_front().unwrap(), vec![1, 2, 3]);
+        assert_eq!(receiver.queue.pop_front().unwrap(), vec![4, 5, 6]);
+        assert!(receiver.queue.pop_front().is_none());
+
+        let mut dropped = vec![];
+        crate::datagram::Receiver::drain_dropped(&mut receiver, |d| dropped.push(d));
+        assert_eq!(
+            dropped,
+            [
+                Dropped::new(4, DropReason::QueueFull),
+                Dropped::new(6, DropReason::ExceedsTransportLimits),
+            ]
+        );
+    }
+
     #[test]
     fn recv_datagram() {
         let mut receiver = Receiver::builder().build().unwrap();
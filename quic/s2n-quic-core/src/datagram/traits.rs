@@ -81,6 +81,15 @@ pub trait Receiver: 'static + Send {
 
     /// A callback used to notify the application in the case of a connection error
     fn on_connection_error(&mut self, error: connection::Error);
+
+    /// Drains the received datagrams which were dropped before the application read them
+    ///
+    /// This is called after each DATAGRAM frame is received. A `DatagramFrameDropped` event is
+    /// emitted for each of the reported datagrams.
+    #[inline]
+    fn drain_dropped<F: FnMut(Dropped)>(&mut self, on_dropped: F) {
+        let _ = on_dropped;
+    }
 }
 
 /// Allows users to configure the behavior of sending datagrams.
@@ -95,6 +104,44 @@ pub trait Sender: 'static + Send {
 
     /// A callback used to notify the application in the case of a connection error
     fn on_connection_error(&mut self, error: connection::Error);
+
+    /// Drains the datagrams which were dropped before they could be sent to the peer
+    ///
+    /// This is called after each transmission. A `DatagramFrameDropped` event is emitted for
+    /// each of the reported datagrams.
+    #[inline]
+    fn drain_dropped<F: FnMut(Dropped)>(&mut self, on_dropped: F) {
+        let _ = on_dropped;
+    }
+}
+
+/// A datagram which was dropped locally
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Dropped {
+    /// The length of the datagram payload
+    pub len: usize,
+    /// The reason the datagram was dropped
+    pub reason: DropReason,
+}
+
+impl Dropped {
+    #[inline]
+    pub fn new(len: usize, reason: DropReason) -> Self {
+        Self { len, reason }
+    }
+}
+
+/// The reason a datagram was dropped locally
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DropReason {
+    /// The datagram queue was at capacity
+    QueueFull,
+    /// The datagram was too large to fit in the available packet space
+    ExceedsPacketCapacity,
+    /// The datagram was larger than the maximum DATAGRAM frame size
+    ExceedsTransportLimits,
 }
 
 /// A packet will be available during the on_transmit callback. Use the methods
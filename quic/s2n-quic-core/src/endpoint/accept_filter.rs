@@ -0,0 +1,406 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Allows servers to reject connection attempts before performing any handshake work
+//!
+//! The filter is invoked once for every Initial packet which would create a new connection. At
+//! this point, only the Initial packet protection has been removed; no TLS session has processed
+//! the ClientHello.
+//!
+//! Allowed connections can also be given [`Overrides`] for some of the endpoint's limits, which
+//! are advertised to the peer in the server's transport parameters.
+
+use crate::{
+    crypto::tls::{HandshakeHeader, HandshakeType},
+    event::{api::SocketAddress, IntoEvent, Timestamp},
+    inet, transport,
+};
+use core::fmt;
+use s2n_codec::DecoderBuffer;
+
+pub use crate::connection::limits::Overrides;
+
+/// Outcome describes how the library should proceed on a connection attempt
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Outcome {
+    /// Allow the connection to continue
+    ///
+    /// Use `Outcome::allow()` or `Outcome::allow_with_overrides()` to construct this variant
+    #[non_exhaustive]
+    Allow { overrides: Overrides },
+
+    /// Require the peer to validate its address by sending a Retry packet
+    ///
+    /// If the peer's address was already validated, the connection is allowed to continue.
+    ///
+    /// Use `Outcome::retry()` to construct this variant
+    #[non_exhaustive]
+    Retry,
+
+    /// Silently drop the connection attempt
+    ///
+    /// Use `Outcome::drop()` to construct this variant
+    #[non_exhaustive]
+    Drop,
+
+    /// Close the connection attempt by sending a CONNECTION_CLOSE frame with the provided error
+    ///
+    /// Use `Outcome::close()` or `Outcome::close_with_error()` to construct this variant
+    #[non_exhaustive]
+    Close { error: transport::Error },
+}
+
+impl Outcome {
+    /// Allow the connection to continue
+    pub fn allow() -> Self {
+        Self::allow_with_overrides(Overrides::default())
+    }
+
+    /// Allow the connection to continue with limits which differ from the endpoint's limits
+    pub fn allow_with_overrides(overrides: Overrides) -> Self {
+        Self::Allow { overrides }
+    }
+
+    /// Require the peer to validate its address by sending a Retry packet
+    pub fn retry() -> Self {
+        Self::Retry
+    }
+
+    /// Silently drop the connection attempt
+    pub fn drop() -> Self {
+        Self::Drop
+    }
+
+    /// Close the connection attempt with a `CONNECTION_REFUSED` error
+    pub fn close() -> Self {
+        Self::close_with_error(
+            transport::Error::CONNECTION_REFUSED.with_reason("connection attempt was rejected"),
+        )
+    }
+
+    /// Close the connection attempt with the provided transport error
+    pub fn close_with_error(error: transport::Error) -> Self {
+        Self::Close { error }
+    }
+}
+
+/// Information about a connection attempt, used to decide its [`Outcome`]
+#[non_exhaustive]
+#[derive(Debug)]
+pub struct ConnectionAttempt<'a> {
+    /// The unverified address of the connecting peer
+    ///
+    /// This address comes from the datagram, unless `is_address_validated` is set.
+    pub remote_address: SocketAddress<'a>,
+
+    /// The destination connection ID of the peer's Initial packet
+    ///
+    /// This is chosen by the peer, unless the attempt is in response to a Retry packet, in which
+    /// case it was chosen by the server.
+    pub destination_connection_id: &'a [u8],
+
+    /// Set if the peer has already validated its address with a Retry token or an out-of-band
+    /// attestation
+    pub is_address_validated: bool,
+
+    /// The server name indication in the ClientHello, if it was available
+    pub server_name: Option<&'a str>,
+
+    /// The application protocols offered in the ClientHello, if they were available
+    pub application_protocols: Option<ApplicationProtocols<'a>>,
+
+    pub timestamp: Timestamp,
+}
+
+impl<'a> ConnectionAttempt<'a> {
+    /// Creates a connection attempt from the beginning of the peer's CRYPTO stream
+    ///
+    /// The ClientHello may span multiple Initial packets, in which case only the extensions
+    /// contained in `client_hello` are reported.
+    #[doc(hidden)]
+    pub fn new(
+        remote_address: &'a inet::SocketAddress,
+        destination_connection_id: &'a [u8],
+        is_address_validated: bool,
+        client_hello: &'a [u8],
+        timestamp: Timestamp,
+    ) -> Self {
+        let mut attempt = Self {
+            remote_address: remote_address.into_event(),
+            destination_connection_id,
+            is_address_validated,
+            server_name: None,
+            application_protocols: None,
+            timestamp,
+        };
+
+        // the ClientHello is untrusted and possibly truncated so any decoding errors just
+        // leave the remaining fields empty
+        let _ = attempt.parse_client_hello(DecoderBuffer::new(client_hello));
+
+        attempt
+    }
+
+    /// Parses the ClientHello as defined in <https://www.rfc-editor.org/rfc/rfc8446#section-4.1.2>
+    fn parse_client_hello(
+        &mut self,
+        buffer: DecoderBuffer<'a>,
+    ) -> Result<(), s2n_codec::DecoderError> {
+        let (header, buffer) = buffer.decode::<HandshakeHeader>()?;
+
+        if header.msg_type() != Some(HandshakeType::ClientHello) {
+            return Ok(());
+        }
+
+        // legacy_version and random
+        let buffer = buffer.skip(2 + 32)?;
+        let buffer = buffer.skip_with_len_prefix::<u8>()?;
+        let buffer = buffer.skip_with_len_prefix::<u16>()?;
+        let buffer = buffer.skip_with_len_prefix::<u8>()?;
+
+        // the extensions may be cut off if the ClientHello spans multiple packets
+        let (len, buffer) = buffer.decode::<u16>()?;
+        let len = buffer.len().min(len as usize);
+        let (mut extensions, _) = buffer.decode_slice(len)?;
+
+        while !extensions.is_empty() {
+            let (extension_type, remaining) = extensions.decode::<u16>()?;
+            let (data, remaining) = remaining.decode_slice_with_len_prefix::<u16>()?;
+            extensions = remaining;
+
+            match extension_type {
+                SERVER_NAME => self.server_name = parse_server_name(data),
+                APPLICATION_LAYER_PROTOCOL_NEGOTIATION => {
+                    let (protocols, _) = data.decode_slice_with_len_prefix::<u16>()?;
+                    self.application_protocols = Some(ApplicationProtocols(protocols));
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// Extension types from <https://www.rfc-editor.org/rfc/rfc8446#section-4.2>
+const SERVER_NAME: u16 = 0;
+const APPLICATION_LAYER_PROTOCOL_NEGOTIATION: u16 = 16;
+
+/// Parses the first `host_name` entry in the `server_name` extension
+///
+/// See <https://www.rfc-editor.org/rfc/rfc6066#section-3>
+fn parse_server_name(data: DecoderBuffer) -> Option<&str> {
+    let (list, _) = data.decode_slice_with_len_prefix::<u16>().ok()?;
+    let (name_type, list) = list.decode::<u8>().ok()?;
+
+    if name_type != 0 {
+        return None;
+    }
+
+    let (name, _) = list.decode_slice_with_len_prefix::<u16>().ok()?;
+    core::str::from_utf8(name.into_less_safe_slice()).ok()
+}
+
+/// The list of application protocols offered by the peer
+///
+/// The protocols are yielded in the peer's order of preference.
+#[derive(Clone, Copy)]
+pub struct ApplicationProtocols<'a>(DecoderBuffer<'a>);
+
+impl<'a> Iterator for ApplicationProtocols<'a> {
+    type Item = &'a [u8];
+
+    // each protocol name is prefixed with a single length byte
+    // See <https://www.rfc-editor.org/rfc/rfc7301#section-3.1>
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let (protocol, remaining) = self.0.decode_slice_with_len_prefix::<u8>().ok()?;
+        self.0 = remaining;
+        Some(protocol.into_less_safe_slice())
+    }
+}
+
+impl fmt::Debug for ApplicationProtocols<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(*self).finish()
+    }
+}
+
+pub trait Filter: 'static + Send {
+    /// Determines the outcome of a connection attempt before any handshake work is performed
+    ///
+    /// ```rust
+    /// # mod s2n_quic { pub mod provider { pub mod accept_filter { pub use s2n_quic_core::endpoint::accept_filter::*; } } }
+    /// use s2n_quic::provider::accept_filter::{ConnectionAttempt, Filter, Outcome};
+    ///
+    /// struct MyFilter;
+    ///
+    /// impl Filter for MyFilter {
+    ///    fn on_connection_attempt(&mut self, attempt: &ConnectionAttempt) -> Outcome {
+    ///        match attempt.server_name {
+    ///            Some("internal.example.com") => Outcome::close(),
+    ///            _ => Outcome::allow(),
+    ///        }
+    ///    }
+    /// }
+    /// ```
+    fn on_connection_attempt(&mut self, attempt: &ConnectionAttempt) -> Outcome;
+}
+
+/// A filter which allows all of the connection attempts
+#[derive(Clone, Copy, Debug, Default)]
+pub struct AllowAll;
+
+impl Filter for AllowAll {
+    #[inline]
+    fn on_connection_attempt(&mut self, _attempt: &ConnectionAttempt) -> Outcome {
+        Outcome::allow()
+    }
+}
+
+/// Creates a filter which calls the provided closure for each connection attempt
+///
+/// This allows applications to make accept decisions without implementing [`Filter`].
+///
+/// ```rust
+/// # mod s2n_quic { pub mod provider { pub mod accept_filter { pub use s2n_quic_core::endpoint::accept_filter::*; } } }
+/// use s2n_quic::provider::accept_filter::{self, Outcome};
+///
+/// let filter = accept_filter::from_fn(|attempt| {
+///     if attempt.is_address_validated {
+///         Outcome::allow()
+///     } else {
+///         // make the peer prove it owns its address before performing any handshake work
+///         Outcome::retry()
+///     }
+/// });
+/// ```
+pub fn from_fn<F>(f: F) -> FromFn<F>
+where
+    F: 'static + Send + FnMut(&ConnectionAttempt) -> Outcome,
+{
+    FromFn(f)
+}
+
+/// A filter which calls a closure for each connection attempt
+///
+/// Use [`from_fn`] to construct this filter.
+#[derive(Clone, Copy)]
+pub struct FromFn<F>(F);
+
+impl<F> Filter for FromFn<F>
+where
+    F: 'static + Send + FnMut(&ConnectionAttempt) -> Outcome,
+{
+    #[inline]
+    fn on_connection_attempt(&mut self, attempt: &ConnectionAttempt) -> Outcome {
+        (self.0)(attempt)
+    }
+}
+
+impl<F> fmt::Debug for FromFn<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("FromFn").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time::{Clock, NoopClock};
+    use s2n_codec::{Encoder, EncoderBuffer};
+
+    fn client_hello(server_name: &[u8], protocols: &[&[u8]]) -> Vec<u8> {
+        let mut extensions = vec![];
+
+        // an unrelated extension which should be skipped
+        extensions.extend_from_slice(&43u16.to_be_bytes());
+        extensions.extend_from_slice(&3u16.to_be_bytes());
+        extensions.extend_from_slice(&[2, 3, 4]);
+
+        let mut sni = vec![0];
+        sni.extend_from_slice(&(server_name.len() as u16).to_be_bytes());
+        sni.extend_from_slice(server_name);
+        extensions.extend_from_slice(&SERVER_NAME.to_be_bytes());
+        extensions.extend_from_slice(&(sni.len() as u16 + 2).to_be_bytes());
+        extensions.extend_from_slice(&(sni.len() as u16).to_be_bytes());
+        extensions.extend_from_slice(&sni);
+
+        let mut alpn = vec![];
+        for protocol in protocols {
+            alpn.push(protocol.len() as u8);
+            alpn.extend_from_slice(protocol);
+        }
+        extensions.extend_from_slice(&APPLICATION_LAYER_PROTOCOL_NEGOTIATION.to_be_bytes());
+        extensions.extend_from_slice(&(alpn.len() as u16 + 2).to_be_bytes());
+        extensions.extend_from_slice(&(alpn.len() as u16).to_be_bytes());
+        extensions.extend_from_slice(&alpn);
+
+        let mut body = vec![3, 3];
+        body.extend_from_slice(&[0; 32]);
+        // legacy_session_id
+        body.push(0);
+        // cipher_suites
+        body.extend_from_slice(&[0, 2, 0x13, 0x01]);
+        // legacy_compression_methods
+        body.extend_from_slice(&[1, 0]);
+        body.extend_from_slice(&(extensions.len() as u16).to_be_bytes());
+        body.extend_from_slice(&extensions);
+
+        let mut message = vec![0u8; 4 + body.len()];
+        let mut encoder = EncoderBuffer::new(&mut message);
+        encoder.encode(&(HandshakeType::ClientHello as u8));
+        encoder.write_slice(&(body.len() as u32).to_be_bytes()[1..]);
+        encoder.write_slice(&body);
+
+        message
+    }
+
+    #[test]
+    fn client_hello_test() {
+        let addr = inet::SocketAddress::default();
+        let now = NoopClock.get_time().into_event();
+        let hello = client_hello(b"example.com", &[b"h3", b"hq-29"]);
+
+        let attempt = ConnectionAttempt::new(&addr, &[1; 8], false, &hello, now);
+        assert_eq!(attempt.server_name, Some("example.com"));
+        let protocols: Vec<_> = attempt.application_protocols.unwrap().collect();
+        assert_eq!(protocols, [&b"h3"[..], &b"hq-29"[..]]);
+
+        // the ALPN extension is cut off
+        let truncated = &hello[..hello.len() - 4];
+        let attempt = ConnectionAttempt::new(&addr, &[1; 8], false, truncated, now);
+        assert_eq!(attempt.server_name, Some("example.com"));
+        assert!(attempt.application_protocols.is_none());
+
+        // the extensions are missing entirely
+        let attempt = ConnectionAttempt::new(&addr, &[1; 8], false, &hello[..40], now);
+        assert!(attempt.server_name.is_none());
+        assert!(attempt.application_protocols.is_none());
+
+        // not a ClientHello
+        let mut server_hello = hello.clone();
+        server_hello[0] = HandshakeType::ServerHello as u8;
+        let attempt = ConnectionAttempt::new(&addr, &[1; 8], false, &server_hello, now);
+        assert!(attempt.server_name.is_none());
+    }
+
+    #[test]
+    fn from_fn_test() {
+        let addr = inet::SocketAddress::default();
+        let now = NoopClock.get_time().into_event();
+        let hello = client_hello(b"example.com", &[b"h3"]);
+
+        let mut filter = from_fn(|attempt| match attempt.destination_connection_id {
+            [0, ..] => Outcome::drop(),
+            _ => Outcome::allow(),
+        });
+
+        let attempt = ConnectionAttempt::new(&addr, &[0; 8], false, &hello, now);
+        assert_eq!(filter.on_connection_attempt(&attempt), Outcome::drop());
+
+        let attempt = ConnectionAttempt::new(&addr, &[1; 8], false, &hello, now);
+        assert_eq!(filter.on_connection_attempt(&attempt), Outcome::allow());
+    }
+}
@@ -0,0 +1,80 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Allows servers to validate a client's address with an out-of-band attestation
+//!
+//! Servers normally validate a client's address by sending a Retry packet, which costs a round
+//! trip. In environments where clients are issued an attestation for their address ahead of time,
+//! such as a signed voucher distributed by a control plane, the client can include the
+//! attestation in the token field of its Initial packets and the server can consider the address
+//! validated without sending a Retry packet.
+
+use crate::{
+    event::{api::SocketAddress, IntoEvent, Timestamp},
+    inet,
+};
+
+/// Information about an Initial packet which would create a new connection
+#[non_exhaustive]
+#[derive(Debug)]
+pub struct Attestation<'a> {
+    /// The unverified address of the connecting peer
+    pub remote_address: SocketAddress<'a>,
+
+    /// The token in the Initial packet
+    ///
+    /// This is empty if the peer didn't provide a token.
+    pub token: &'a [u8],
+
+    pub timestamp: Timestamp,
+}
+
+impl<'a> Attestation<'a> {
+    #[doc(hidden)]
+    pub fn new(
+        remote_address: &'a inet::SocketAddress,
+        token: &'a [u8],
+        timestamp: Timestamp,
+    ) -> Self {
+        Self {
+            remote_address: remote_address.into_event(),
+            token,
+            timestamp,
+        }
+    }
+}
+
+pub trait Validator: 'static + Send {
+    /// Returns `true` if the peer's address is attested
+    ///
+    /// Attested peers are considered to have validated their address, so they are never sent a
+    /// Retry packet. If the peer is not attested, the token is validated as an address token
+    /// and the Initial packet is discarded if the token is invalid.
+    ///
+    /// ```rust
+    /// # mod s2n_quic { pub mod provider { pub mod address_attestation { pub use s2n_quic_core::endpoint::address_attestation::*; } } }
+    /// use s2n_quic::provider::address_attestation::{Attestation, Validator};
+    ///
+    /// struct MyValidator;
+    ///
+    /// impl Validator for MyValidator {
+    ///    fn is_attested(&mut self, attestation: &Attestation) -> bool {
+    ///        // verify the signature on the voucher and check that it was issued for
+    ///        // `attestation.remote_address`
+    ///        attestation.token.starts_with(b"voucher:")
+    ///    }
+    /// }
+    /// ```
+    fn is_attested(&mut self, attestation: &Attestation) -> bool;
+}
+
+/// A validator which doesn't attest any peers
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Disabled;
+
+impl Validator for Disabled {
+    #[inline]
+    fn is_attested(&mut self, _attestation: &Attestation) -> bool {
+        false
+    }
+}
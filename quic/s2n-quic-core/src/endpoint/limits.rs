@@ -26,9 +26,9 @@ pub enum Outcome {
 
     /// Silently drop the connection attempt
     ///
-    /// Use `Outcome::drop()` to construct this variant
+    /// Use `Outcome::drop()` or `Outcome::drop_with_reason()` to construct this variant
     #[non_exhaustive]
-    Drop,
+    Drop { reason: DropReason },
 
     /// Cleanly close the connection
     ///
@@ -50,7 +50,13 @@ impl Outcome {
 
     /// Silently drop the connection attempt
     pub fn drop() -> Self {
-        Self::Drop
+        Self::drop_with_reason(DropReason::Rejected)
+    }
+
+    /// Silently drop the connection attempt, reporting the provided reason in the
+    /// `EndpointDatagramDropped` event
+    pub fn drop_with_reason(reason: DropReason) -> Self {
+        Self::Drop { reason }
     }
 
     /// Cleanly close the connection
@@ -59,6 +65,21 @@ impl Outcome {
     }
 }
 
+/// The reason a connection attempt was dropped
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum DropReason {
+    /// The connection attempt was rejected by the limiter
+    #[default]
+    Rejected,
+
+    /// The connection attempt exceeded the rate of new connections
+    RateLimited,
+
+    /// The connection attempt exceeded the number of inflight handshakes
+    InflightHandshakeLimitExceeded,
+}
+
 /// A ConnectionAttempt holds information about the state of endpoint receiving a connect, along
 /// with information about the connection. This can be used to make decisions about the Outcome of
 /// an attempted connection
@@ -12,6 +12,8 @@ use core::{
     task::{Context, Poll},
 };
 
+pub mod accept_filter;
+pub mod address_attestation;
 pub mod limits;
 pub use limits::Limiter;
 
@@ -49,6 +51,7 @@ impl Type {
 
 /// Enumerates endpoint locations
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum Location {
     /// The local endpoint
     Local,
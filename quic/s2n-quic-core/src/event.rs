@@ -6,6 +6,9 @@ use crate::{connection, endpoint};
 use alloc::vec::Vec;
 use core::{fmt, ops::RangeInclusive, time::Duration};
 
+pub mod accounting;
+#[cfg(feature = "serde")]
+pub mod export;
 mod generated;
 pub mod metrics;
 pub use generated::*;
@@ -14,6 +17,9 @@ pub use generated::*;
 #[doc(hidden)]
 pub mod snapshot;
 
+#[cfg(test)]
+mod tests;
+
 /// All event types which can be emitted from this library.
 pub trait Event: core::fmt::Debug {
     const NAME: &'static str;
@@ -140,6 +146,14 @@ impl Timestamp {
     }
 }
 
+/// Serialized as the [duration since the start](Self::duration_since_start) of the process
+#[cfg(feature = "serde")]
+impl serde::Serialize for Timestamp {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serde::Serialize::serialize(&self.duration_since_start(), serializer)
+    }
+}
+
 impl IntoEvent<Timestamp> for crate::time::Timestamp {
     #[inline]
     fn into_event(self) -> Timestamp {
@@ -199,6 +213,20 @@ impl core::fmt::Debug for TlsSession<'_> {
     }
 }
 
+/// Serializes the negotiated parameters of the session
+///
+/// Secrets and other sensitive values are never included.
+#[cfg(feature = "serde")]
+impl serde::Serialize for TlsSession<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+
+        let mut s = serializer.serialize_struct("TlsSession", 1)?;
+        s.serialize_field("cipher_suite", &self.cipher_suite())?;
+        s.end()
+    }
+}
+
 #[cfg(feature = "std")]
 impl<'a> IntoEvent<&'a std::io::Error> for &'a std::io::Error {
     #[inline]
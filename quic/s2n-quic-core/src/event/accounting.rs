@@ -0,0 +1,191 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Exact per-connection totals for accounting and billing
+//!
+//! Event subscribers are free to sample or filter the events they receive, which makes them a
+//! poor fit for values that need to add up exactly. The [`Accounting`] trait is instead notified
+//! exactly once when a connection is opened and exactly once with its final [`Totals`] when the
+//! connection is finalized, regardless of how the connection was closed.
+//!
+//! An [`Accounting`] implementation is installed by wrapping it in a [`Subscriber`], which can be
+//! composed with any other event subscriber in a tuple.
+//! The [`Ledger`] of a connection can be queried in the same way as any other
+//! [`ConnectionContext`](event::Subscriber::ConnectionContext).
+
+use crate::{
+    connection,
+    event::{self, api},
+    query,
+};
+
+/// Receives exact totals for each connection
+///
+/// Unlike [`event::Subscriber`], none of the values passed to an `Accounting` implementation
+/// are sampled: every byte and every connection is counted exactly once.
+pub trait Accounting: 'static + Send {
+    /// The per-connection ledger which receives the connection's totals
+    type Ledger: Ledger;
+
+    /// Called exactly once for each connection when it is opened
+    fn on_connection_opened(
+        &mut self,
+        meta: &api::ConnectionMeta,
+        info: &api::ConnectionInfo,
+    ) -> Self::Ledger;
+}
+
+/// Receives the totals of a single connection
+pub trait Ledger: 'static + Send {
+    /// Called exactly once when the connection is finalized
+    fn on_connection_finalized(&mut self, totals: &Totals);
+}
+
+/// The totals of a connection over its lifetime
+#[derive(Clone, Copy, Debug, Default)]
+#[non_exhaustive]
+pub struct Totals {
+    /// The number of UDP payload bytes sent by the connection
+    pub bytes_sent: u64,
+    /// The number of UDP payload bytes received by the connection
+    pub bytes_received: u64,
+    /// The number of application bytes sent on streams
+    pub stream_bytes_sent: u64,
+    /// The number of application bytes received on streams
+    pub stream_bytes_received: u64,
+    /// The error which closed the connection
+    ///
+    /// This is `None` if the connection was finalized without a close event being emitted.
+    pub error: Option<connection::Error>,
+}
+
+/// Adapts an [`Accounting`] implementation into an [`event::Subscriber`]
+#[derive(Debug, Default)]
+pub struct Subscriber<A: Accounting> {
+    accounting: A,
+}
+
+impl<A: Accounting> Subscriber<A> {
+    pub fn new(accounting: A) -> Self {
+        Self { accounting }
+    }
+
+    pub fn accounting(&self) -> &A {
+        &self.accounting
+    }
+
+    pub fn accounting_mut(&mut self) -> &mut A {
+        &mut self.accounting
+    }
+}
+
+/// Accumulates the totals of a connection and reports them to the ledger on drop
+///
+/// The connection context is dropped exactly once for each connection, which guarantees the
+/// totals are reported exactly once, even if the connection closed without emitting a
+/// `ConnectionClosed` event.
+pub struct Context<L: Ledger> {
+    ledger: L,
+    totals: Totals,
+}
+
+impl<L: Ledger> Context<L> {
+    /// Returns the totals accumulated so far
+    pub fn totals(&self) -> &Totals {
+        &self.totals
+    }
+}
+
+impl<L: Ledger> Drop for Context<L> {
+    fn drop(&mut self) {
+        self.ledger.on_connection_finalized(&self.totals);
+    }
+}
+
+impl<A: Accounting> event::Subscriber for Subscriber<A> {
+    type ConnectionContext = Context<A::Ledger>;
+
+    fn create_connection_context(
+        &mut self,
+        meta: &api::ConnectionMeta,
+        info: &api::ConnectionInfo,
+    ) -> Self::ConnectionContext {
+        Context {
+            ledger: self.accounting.on_connection_opened(meta, info),
+            totals: Totals::default(),
+        }
+    }
+
+    #[inline]
+    fn on_datagram_sent(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &api::ConnectionMeta,
+        event: &api::DatagramSent,
+    ) {
+        context.totals.bytes_sent += event.len as u64;
+    }
+
+    #[inline]
+    fn on_datagram_received(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &api::ConnectionMeta,
+        event: &api::DatagramReceived,
+    ) {
+        context.totals.bytes_received += event.len as u64;
+    }
+
+    #[inline]
+    fn on_tx_stream_progress(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &api::ConnectionMeta,
+        event: &api::TxStreamProgress,
+    ) {
+        context.totals.stream_bytes_sent += event.bytes as u64;
+    }
+
+    #[inline]
+    fn on_rx_stream_progress(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &api::ConnectionMeta,
+        event: &api::RxStreamProgress,
+    ) {
+        context.totals.stream_bytes_received += event.bytes as u64;
+    }
+
+    #[inline]
+    fn on_connection_closed(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &api::ConnectionMeta,
+        event: &api::ConnectionClosed,
+    ) {
+        // only the first close is recorded
+        if context.totals.error.is_none() {
+            context.totals.error = Some(event.error);
+        }
+    }
+
+    #[inline]
+    fn query(
+        context: &Self::ConnectionContext,
+        query: &mut dyn query::Query,
+    ) -> query::ControlFlow {
+        query
+            .execute(context)
+            .and_then(|| query.execute(&context.ledger))
+    }
+
+    #[inline]
+    fn query_mut(
+        context: &mut Self::ConnectionContext,
+        query: &mut dyn query::QueryMut,
+    ) -> query::ControlFlow {
+        query
+            .execute_mut(context)
+            .and_then(|| query.execute_mut(&mut context.ledger))
+    }
+}
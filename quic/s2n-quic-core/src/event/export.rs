@@ -0,0 +1,209 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Exports events with [`serde`](https://docs.rs/serde)
+//!
+//! With the `serde` feature enabled, all of the types in [`api`](super::api) implement
+//! [`serde::Serialize`]. The [`Subscriber`] in this module forwards each event to an
+//! [`Exporter`], wrapped in a [`Record`] which identifies the event and the version of the schema.
+//! This makes it possible to ship events to another system without writing a converter for each
+//! event type.
+//!
+//! ```rust
+//! use s2n_quic_core::event::{
+//!     export::{Exporter, Record, Subscriber},
+//!     Event, Meta,
+//! };
+//!
+//! #[derive(Default)]
+//! struct Lines(Vec<String>);
+//!
+//! impl Exporter for Lines {
+//!     fn export<M, E>(&mut self, record: Record<M, E>)
+//!     where
+//!         M: Meta + serde::Serialize,
+//!         E: Event + serde::Serialize,
+//!     {
+//!         self.0.push(serde_json::to_string(&record).unwrap());
+//!     }
+//! }
+//!
+//! // the subscriber can now be passed to an endpoint
+//! let subscriber = Subscriber::new(Lines::default());
+//! ```
+//!
+//! # Stability
+//!
+//! Each field is serialized with the name of the Rust field. Enums are serialized as a map with
+//! the name of the variant in the `type` field, along with the fields of the variant.
+//!
+//! Adding events, fields and variants is not considered a breaking change so consumers should
+//! ignore unknown fields and tolerate unknown variants. Any other change which could break an
+//! existing consumer, such as removing or renaming a field or changing how it's encoded,
+//! increments [`VERSION`].
+
+use super::{Event, Meta};
+use serde::Serialize;
+
+/// The version of the serialized event schema
+///
+/// This is included in each [`Record`] so consumers can detect incompatible changes.
+pub const VERSION: u32 = 1;
+
+/// An event along with the metadata needed to interpret it
+#[derive(Clone, Copy, Debug, Serialize)]
+#[non_exhaustive]
+pub struct Record<'a, M, E> {
+    /// The [schema version](VERSION) of the event
+    pub version: u32,
+    /// The name of the event, e.g. `"transport:packet_sent"`
+    pub name: &'static str,
+    /// The metadata of the endpoint or connection which emitted the event
+    pub meta: &'a M,
+    /// The event
+    pub event: &'a E,
+}
+
+impl<'a, M: Meta, E: Event> Record<'a, M, E> {
+    #[inline]
+    pub fn new(meta: &'a M, event: &'a E) -> Self {
+        Self {
+            version: VERSION,
+            name: E::NAME,
+            meta,
+            event,
+        }
+    }
+}
+
+/// Receives each event emitted by the [`Subscriber`]
+pub trait Exporter: 'static + Send {
+    fn export<M, E>(&mut self, record: Record<M, E>)
+    where
+        M: Meta + Serialize,
+        E: Event + Serialize;
+}
+
+/// A [`Subscriber`](super::Subscriber) which forwards all events to an [`Exporter`]
+#[derive(Clone, Debug, Default)]
+pub struct Subscriber<X> {
+    exporter: X,
+}
+
+impl<X: Exporter> Subscriber<X> {
+    #[inline]
+    pub fn new(exporter: X) -> Self {
+        Self { exporter }
+    }
+
+    #[inline]
+    pub fn exporter(&self) -> &X {
+        &self.exporter
+    }
+
+    #[inline]
+    pub fn exporter_mut(&mut self) -> &mut X {
+        &mut self.exporter
+    }
+
+    #[inline]
+    pub fn into_inner(self) -> X {
+        self.exporter
+    }
+
+    #[inline]
+    pub(crate) fn export<M, E>(&mut self, meta: &M, event: &E)
+    where
+        M: Meta + Serialize,
+        E: Event + Serialize,
+    {
+        self.exporter.export(Record::new(meta, event));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        endpoint,
+        event::{api, builder, IntoEvent, Subscriber as _},
+        time::{Duration, Timestamp},
+    };
+    use alloc::{string::String, vec::Vec};
+    use serde_json::json;
+
+    #[derive(Default)]
+    struct Lines(Vec<String>);
+
+    impl Exporter for Lines {
+        fn export<M, E>(&mut self, record: Record<M, E>)
+        where
+            M: Meta + Serialize,
+            E: Event + Serialize,
+        {
+            self.0.push(serde_json::to_string(&record).unwrap());
+        }
+    }
+
+    fn meta() -> api::ConnectionMeta {
+        builder::ConnectionMeta {
+            endpoint_type: endpoint::Type::Server,
+            id: 1,
+            timestamp: unsafe { Timestamp::from_duration(Duration::from_millis(1500)) },
+        }
+        .into_event()
+    }
+
+    #[test]
+    fn connection_event_test() {
+        let mut subscriber = Subscriber::new(Lines::default());
+        let meta = meta();
+        let event: api::ConnectionIdUpdated = builder::ConnectionIdUpdated {
+            path_id: 0,
+            cid_consumer: endpoint::Location::Remote,
+            previous: builder::ConnectionId { bytes: &[1, 2] },
+            current: builder::ConnectionId { bytes: &[10, 11] },
+        }
+        .into_event();
+
+        subscriber.on_connection_id_updated(&mut (), &meta, &event);
+
+        let lines = subscriber.into_inner().0;
+        assert_eq!(lines.len(), 1);
+
+        let record: serde_json::Value = serde_json::from_str(&lines[0]).unwrap();
+        assert_eq!(
+            record,
+            json!({
+                "version": VERSION,
+                "name": "connectivity:connection_id_updated",
+                "meta": {
+                    "endpoint_type": { "type": "Server" },
+                    "id": 1,
+                    "timestamp": { "secs": 1, "nanos": 500_000_000 },
+                },
+                "event": {
+                    "path_id": 0,
+                    "cid_consumer": "Remote",
+                    "previous": "0x0102",
+                    "current": "0x0a0b",
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn connection_error_test() {
+        let event: api::ConnectionClosed = builder::ConnectionClosed {
+            error: crate::connection::Error::idle_timer_expired(),
+        }
+        .into_event();
+
+        let value = serde_json::to_value(Record::new(&meta(), &event)).unwrap();
+        assert_eq!(value["event"]["error"]["kind"], "IDLE_TIMER_EXPIRED");
+        assert_eq!(
+            value["event"]["error"]["message"],
+            crate::connection::Error::idle_timer_expired().to_string()
+        );
+    }
+}
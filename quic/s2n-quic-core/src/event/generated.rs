@@ -15,6 +15,7 @@ pub mod api {
     use crate::event::metrics::aggregate;
     pub use traits::Subscriber;
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     pub struct ConnectionMeta {
         pub endpoint_type: EndpointType,
@@ -32,6 +33,7 @@ pub mod api {
         }
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     pub struct EndpointMeta {
         pub endpoint_type: EndpointType,
@@ -47,6 +49,7 @@ pub mod api {
         }
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     pub struct ConnectionInfo {}
     #[cfg(any(test, feature = "testing"))]
@@ -57,6 +60,7 @@ pub mod api {
         }
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     pub struct TransportParameters<'a> {
         pub original_destination_connection_id: Option<ConnectionId<'a>>,
@@ -125,6 +129,7 @@ pub mod api {
         }
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     pub struct PreferredAddress<'a> {
         pub ipv4_address: Option<SocketAddress<'a>>,
@@ -144,6 +149,7 @@ pub mod api {
         }
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     pub struct Path<'a> {
         pub local_addr: SocketAddress<'a>,
@@ -180,6 +186,7 @@ pub mod api {
         }
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     pub struct EcnCounts {
         #[doc = " A variable-length integer representing the total number of packets"]
@@ -220,6 +227,7 @@ pub mod api {
         }
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     pub struct MtuConfig {
         pub initial_mtu: u16,
@@ -237,6 +245,7 @@ pub mod api {
         }
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     #[doc = " A bandwidth delivery rate estimate with associated metadata"]
     pub struct RateSample {
@@ -311,6 +320,7 @@ pub mod api {
         }
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type"))]
     #[non_exhaustive]
     pub enum DuplicatePacketError {
         #[non_exhaustive]
@@ -347,6 +357,7 @@ pub mod api {
         }
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type"))]
     #[non_exhaustive]
     pub enum Frame {
         #[non_exhaustive]
@@ -419,6 +430,17 @@ pub mod api {
         Datagram { len: u16 },
         #[non_exhaustive]
         DcStatelessResetTokens {},
+        #[non_exhaustive]
+        GoAway {},
+        #[non_exhaustive]
+        ResetStreamAt {
+            id: u64,
+            error_code: u64,
+            final_size: u64,
+            reliable_size: u64,
+        },
+        #[non_exhaustive]
+        ExpiredStreamData { id: u64, offset: u64 },
     }
     impl aggregate::AsVariant for Frame {
         const VARIANTS: &'static [aggregate::info::Variant] = &[
@@ -532,6 +554,21 @@ pub mod api {
                 id: 21usize,
             }
             .build(),
+            aggregate::info::variant::Builder {
+                name: aggregate::info::Str::new("GO_AWAY\0"),
+                id: 22usize,
+            }
+            .build(),
+            aggregate::info::variant::Builder {
+                name: aggregate::info::Str::new("RESET_STREAM_AT\0"),
+                id: 23usize,
+            }
+            .build(),
+            aggregate::info::variant::Builder {
+                name: aggregate::info::Str::new("EXPIRED_STREAM_DATA\0"),
+                id: 24usize,
+            }
+            .build(),
         ];
         #[inline]
         fn variant_idx(&self) -> usize {
@@ -558,10 +595,14 @@ pub mod api {
                 Self::HandshakeDone { .. } => 19usize,
                 Self::Datagram { .. } => 20usize,
                 Self::DcStatelessResetTokens { .. } => 21usize,
+                Self::GoAway { .. } => 22usize,
+                Self::ResetStreamAt { .. } => 23usize,
+                Self::ExpiredStreamData { .. } => 24usize,
             }
         }
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type"))]
     #[non_exhaustive]
     pub enum StreamType {
         #[non_exhaustive]
@@ -591,6 +632,7 @@ pub mod api {
         }
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type"))]
     #[non_exhaustive]
     pub enum PacketHeader {
         #[non_exhaustive]
@@ -660,6 +702,7 @@ pub mod api {
         }
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type"))]
     #[non_exhaustive]
     pub enum PacketType {
         #[non_exhaustive]
@@ -729,6 +772,7 @@ pub mod api {
         }
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type"))]
     #[non_exhaustive]
     pub enum KeyType {
         #[non_exhaustive]
@@ -774,6 +818,7 @@ pub mod api {
         }
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type"))]
     #[non_exhaustive]
     #[doc = " A context from which the event is being emitted"]
     #[doc = ""]
@@ -807,6 +852,7 @@ pub mod api {
         }
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type"))]
     #[doc = " An endpoint may be either a Server or a Client"]
     pub enum EndpointType {
         #[non_exhaustive]
@@ -836,6 +882,7 @@ pub mod api {
         }
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type"))]
     #[non_exhaustive]
     pub enum DatagramDropReason {
         #[non_exhaustive]
@@ -885,6 +932,15 @@ pub mod api {
         #[non_exhaustive]
         #[doc = " The peer initiated a connection migration without supplying enough connection IDs to use."]
         InsufficientConnectionIds {},
+        #[non_exhaustive]
+        #[doc = " The connection attempt exceeded the endpoint's connection rate limit."]
+        ConnectionRateLimited {},
+        #[non_exhaustive]
+        #[doc = " The connection attempt exceeded the endpoint's limit on inflight handshakes."]
+        InflightHandshakeLimitExceeded {},
+        #[non_exhaustive]
+        #[doc = " The datagram exceeded the max_udp_payload_size advertised to the peer."]
+        PayloadSizeExceeded {},
     }
     impl aggregate::AsVariant for DatagramDropReason {
         const VARIANTS: &'static [aggregate::info::Variant] = &[
@@ -953,6 +1009,21 @@ pub mod api {
                 id: 12usize,
             }
             .build(),
+            aggregate::info::variant::Builder {
+                name: aggregate::info::Str::new("CONNECTION_RATE_LIMITED\0"),
+                id: 13usize,
+            }
+            .build(),
+            aggregate::info::variant::Builder {
+                name: aggregate::info::Str::new("INFLIGHT_HANDSHAKE_LIMIT_EXCEEDED\0"),
+                id: 14usize,
+            }
+            .build(),
+            aggregate::info::variant::Builder {
+                name: aggregate::info::Str::new("PAYLOAD_SIZE_EXCEEDED\0"),
+                id: 15usize,
+            }
+            .build(),
         ];
         #[inline]
         fn variant_idx(&self) -> usize {
@@ -970,10 +1041,14 @@ pub mod api {
                 Self::RejectedConnectionMigration { .. } => 10usize,
                 Self::PathLimitExceeded { .. } => 11usize,
                 Self::InsufficientConnectionIds { .. } => 12usize,
+                Self::ConnectionRateLimited { .. } => 13usize,
+                Self::InflightHandshakeLimitExceeded { .. } => 14usize,
+                Self::PayloadSizeExceeded { .. } => 15usize,
             }
         }
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type"))]
     #[non_exhaustive]
     pub enum KeySpace {
         #[non_exhaustive]
@@ -1019,6 +1094,7 @@ pub mod api {
         }
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type"))]
     #[non_exhaustive]
     pub enum PacketSkipReason {
         #[non_exhaustive]
@@ -1050,6 +1126,7 @@ pub mod api {
         }
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type"))]
     #[non_exhaustive]
     pub enum PacketDropReason<'a> {
         #[non_exhaustive]
@@ -1220,6 +1297,7 @@ pub mod api {
         }
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type"))]
     #[non_exhaustive]
     pub enum RetryDiscardReason<'a> {
         #[non_exhaustive]
@@ -1270,6 +1348,91 @@ pub mod api {
         }
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type"))]
+    #[non_exhaustive]
+    #[doc = " An operation performed by the TLS provider during the handshake"]
+    pub enum TlsOperation {
+        #[non_exhaustive]
+        #[doc = " Validating the certificate chain presented by the peer"]
+        CertificateValidation {},
+        #[non_exhaustive]
+        #[doc = " Signing the handshake transcript with the local private key"]
+        SignatureGeneration {},
+        #[non_exhaustive]
+        #[doc = " Verifying the peer's signature of the handshake transcript"]
+        SignatureVerification {},
+        #[non_exhaustive]
+        #[doc = " Decrypting a session ticket presented by a resuming client"]
+        TicketDecryption {},
+    }
+    impl aggregate::AsVariant for TlsOperation {
+        const VARIANTS: &'static [aggregate::info::Variant] = &[
+            aggregate::info::variant::Builder {
+                name: aggregate::info::Str::new("CERTIFICATE_VALIDATION\0"),
+                id: 0usize,
+            }
+            .build(),
+            aggregate::info::variant::Builder {
+                name: aggregate::info::Str::new("SIGNATURE_GENERATION\0"),
+                id: 1usize,
+            }
+            .build(),
+            aggregate::info::variant::Builder {
+                name: aggregate::info::Str::new("SIGNATURE_VERIFICATION\0"),
+                id: 2usize,
+            }
+            .build(),
+            aggregate::info::variant::Builder {
+                name: aggregate::info::Str::new("TICKET_DECRYPTION\0"),
+                id: 3usize,
+            }
+            .build(),
+        ];
+        #[inline]
+        fn variant_idx(&self) -> usize {
+            match self {
+                Self::CertificateValidation { .. } => 0usize,
+                Self::SignatureGeneration { .. } => 1usize,
+                Self::SignatureVerification { .. } => 2usize,
+                Self::TicketDecryption { .. } => 3usize,
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type"))]
+    #[non_exhaustive]
+    #[doc = " A strictness check which was relaxed by the compatibility policy"]
+    pub enum CompatibilityRelaxation {
+        #[non_exhaustive]
+        #[doc = " The peer omitted the `initial_source_connection_id` transport parameter"]
+        MissingInitialSourceConnectionId {},
+        #[non_exhaustive]
+        #[doc = " The server omitted the `original_destination_connection_id` transport parameter"]
+        MissingOriginalDestinationConnectionId {},
+    }
+    impl aggregate::AsVariant for CompatibilityRelaxation {
+        const VARIANTS: &'static [aggregate::info::Variant] = &[
+            aggregate::info::variant::Builder {
+                name: aggregate::info::Str::new("MISSING_INITIAL_SOURCE_CONNECTION_ID\0"),
+                id: 0usize,
+            }
+            .build(),
+            aggregate::info::variant::Builder {
+                name: aggregate::info::Str::new("MISSING_ORIGINAL_DESTINATION_CONNECTION_ID\0"),
+                id: 1usize,
+            }
+            .build(),
+        ];
+        #[inline]
+        fn variant_idx(&self) -> usize {
+            match self {
+                Self::MissingInitialSourceConnectionId { .. } => 0usize,
+                Self::MissingOriginalDestinationConnectionId { .. } => 1usize,
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type"))]
     #[non_exhaustive]
     pub enum MigrationDenyReason {
         #[non_exhaustive]
@@ -1315,6 +1478,7 @@ pub mod api {
         }
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type"))]
     #[non_exhaustive]
     #[doc = " The current state of the ECN controller for the path"]
     pub enum EcnState {
@@ -1365,6 +1529,7 @@ pub mod api {
         }
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type"))]
     #[non_exhaustive]
     #[doc = " Events tracking the progress of handshake status"]
     pub enum HandshakeStatus {
@@ -1422,6 +1587,7 @@ pub mod api {
         }
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type"))]
     #[non_exhaustive]
     #[doc = " The source that caused a congestion event"]
     pub enum CongestionSource {
@@ -1500,6 +1666,7 @@ pub mod api {
         }
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type"))]
     #[non_exhaustive]
     pub enum PathChallengeStatus {
         #[non_exhaustive]
@@ -1529,6 +1696,7 @@ pub mod api {
         }
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type"))]
     #[non_exhaustive]
     #[doc = " The reason the slow start congestion controller state has been exited"]
     pub enum SlowStartExitCause {
@@ -1583,6 +1751,7 @@ pub mod api {
         }
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type"))]
     #[non_exhaustive]
     #[doc = " The reason the MTU was updated"]
     pub enum MtuUpdatedCause {
@@ -1651,6 +1820,49 @@ pub mod api {
         }
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type"))]
+    #[non_exhaustive]
+    #[doc = " A budget limiting the work performed when processing a single packet"]
+    pub enum PacketBudget {
+        #[non_exhaustive]
+        #[doc = " The number of frames in the packet"]
+        Frames {},
+        #[non_exhaustive]
+        #[doc = " The number of ACK ranges in all of the ACK frames in the packet"]
+        AckRanges {},
+        #[non_exhaustive]
+        #[doc = " The number of bytes in all of the CRYPTO frames in the packet"]
+        CryptoBytes {},
+    }
+    impl aggregate::AsVariant for PacketBudget {
+        const VARIANTS: &'static [aggregate::info::Variant] = &[
+            aggregate::info::variant::Builder {
+                name: aggregate::info::Str::new("FRAMES\0"),
+                id: 0usize,
+            }
+            .build(),
+            aggregate::info::variant::Builder {
+                name: aggregate::info::Str::new("ACK_RANGES\0"),
+                id: 1usize,
+            }
+            .build(),
+            aggregate::info::variant::Builder {
+                name: aggregate::info::Str::new("CRYPTO_BYTES\0"),
+                id: 2usize,
+            }
+            .build(),
+        ];
+        #[inline]
+        fn variant_idx(&self) -> usize {
+            match self {
+                Self::Frames { .. } => 0usize,
+                Self::AckRanges { .. } => 1usize,
+                Self::CryptoBytes { .. } => 2usize,
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type"))]
     #[non_exhaustive]
     pub enum BbrState {
         #[non_exhaustive]
@@ -1720,6 +1932,7 @@ pub mod api {
         }
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type"))]
     #[non_exhaustive]
     pub enum DcState {
         #[non_exhaustive]
@@ -1765,6 +1978,49 @@ pub mod api {
         }
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type"))]
+    #[non_exhaustive]
+    #[doc = " The reason a DATAGRAM frame was dropped locally"]
+    pub enum DatagramFrameDropReason {
+        #[non_exhaustive]
+        #[doc = " The datagram queue was at capacity"]
+        QueueFull {},
+        #[non_exhaustive]
+        #[doc = " The datagram was too large to fit in the available packet space"]
+        ExceedsPacketCapacity {},
+        #[non_exhaustive]
+        #[doc = " The datagram was larger than the maximum DATAGRAM frame size"]
+        ExceedsTransportLimits {},
+    }
+    impl aggregate::AsVariant for DatagramFrameDropReason {
+        const VARIANTS: &'static [aggregate::info::Variant] = &[
+            aggregate::info::variant::Builder {
+                name: aggregate::info::Str::new("QUEUE_FULL\0"),
+                id: 0usize,
+            }
+            .build(),
+            aggregate::info::variant::Builder {
+                name: aggregate::info::Str::new("EXCEEDS_PACKET_CAPACITY\0"),
+                id: 1usize,
+            }
+            .build(),
+            aggregate::info::variant::Builder {
+                name: aggregate::info::Str::new("EXCEEDS_TRANSPORT_LIMITS\0"),
+                id: 2usize,
+            }
+            .build(),
+        ];
+        #[inline]
+        fn variant_idx(&self) -> usize {
+            match self {
+                Self::QueueFull { .. } => 0usize,
+                Self::ExceedsPacketCapacity { .. } => 1usize,
+                Self::ExceedsTransportLimits { .. } => 2usize,
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     #[doc = " Application level protocol"]
     pub struct ApplicationProtocolInformation<'a> {
@@ -1785,6 +2041,7 @@ pub mod api {
         const NAME: &'static str = "transport:application_protocol_information";
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     #[doc = " Server Name was negotiated for the connection"]
     pub struct ServerNameInformation<'a> {
@@ -1802,6 +2059,7 @@ pub mod api {
         const NAME: &'static str = "transport:server_name_information";
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     #[doc = " Packet was skipped with a given reason"]
     pub struct PacketSkipped {
@@ -1823,6 +2081,7 @@ pub mod api {
         const NAME: &'static str = "transport:packet_skipped";
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     #[doc = " Packet was sent by a connection"]
     pub struct PacketSent {
@@ -1842,6 +2101,7 @@ pub mod api {
         const NAME: &'static str = "transport:packet_sent";
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     #[doc = " Packet was received by a connection"]
     pub struct PacketReceived {
@@ -1859,6 +2119,7 @@ pub mod api {
         const NAME: &'static str = "transport:packet_received";
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     #[doc = " Active path was updated"]
     pub struct ActivePathUpdated<'a> {
@@ -1878,6 +2139,7 @@ pub mod api {
         const NAME: &'static str = "connectivity:active_path_updated";
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     #[doc = " A new path was created"]
     pub struct PathCreated<'a> {
@@ -1897,6 +2159,7 @@ pub mod api {
         const NAME: &'static str = "transport:path_created";
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     #[doc = " Frame was sent"]
     pub struct FrameSent {
@@ -1918,6 +2181,7 @@ pub mod api {
         const NAME: &'static str = "transport:frame_sent";
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     #[doc = " Frame was received"]
     pub struct FrameReceived<'a> {
@@ -1939,6 +2203,7 @@ pub mod api {
         const NAME: &'static str = "transport:frame_received";
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     #[doc = " A `CONNECTION_CLOSE` frame was received"]
     #[doc = ""]
@@ -1963,6 +2228,7 @@ pub mod api {
         const NAME: &'static str = "transport:connection_close_frame_received";
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     #[doc = " Packet was lost"]
     pub struct PacketLost<'a> {
@@ -1986,6 +2252,7 @@ pub mod api {
         const NAME: &'static str = "recovery:packet_lost";
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     #[doc = " Recovery metrics updated"]
     pub struct RecoveryMetrics<'a> {
@@ -2021,6 +2288,7 @@ pub mod api {
         const NAME: &'static str = "recovery:metrics_updated";
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     #[doc = " Congestion (ECN or packet loss) has occurred"]
     pub struct Congestion<'a> {
@@ -2063,6 +2331,7 @@ pub mod api {
         const NAME: &'static str = "recovery:ack_processed";
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     #[doc = " Ack range for received packets was dropped due to space constraints"]
     #[doc = ""]
@@ -2096,6 +2365,7 @@ pub mod api {
         const NAME: &'static str = "recovery:rx_ack_range_dropped";
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     #[doc = " ACK range was received"]
     pub struct AckRangeReceived<'a> {
@@ -2117,6 +2387,7 @@ pub mod api {
         const NAME: &'static str = "recovery:ack_range_received";
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     #[doc = " ACK range was sent"]
     pub struct AckRangeSent {
@@ -2138,6 +2409,7 @@ pub mod api {
         const NAME: &'static str = "recovery:ack_range_sent";
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     #[doc = " Packet was dropped with the given reason"]
     pub struct PacketDropped<'a> {
@@ -2155,6 +2427,31 @@ pub mod api {
         const NAME: &'static str = "transport:packet_dropped";
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
+    #[non_exhaustive]
+    #[doc = " Processing a packet exceeded one of the configured budgets"]
+    #[doc = ""]
+    #[doc = " The remainder of the packet is discarded and the connection is closed."]
+    pub struct PacketBudgetExceeded {
+        pub packet_header: PacketHeader,
+        pub budget: PacketBudget,
+        pub limit: u64,
+    }
+    #[cfg(any(test, feature = "testing"))]
+    impl crate::event::snapshot::Fmt for PacketBudgetExceeded {
+        fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::fmt::Result {
+            let mut fmt = fmt.debug_struct("PacketBudgetExceeded");
+            fmt.field("packet_header", &self.packet_header);
+            fmt.field("budget", &self.budget);
+            fmt.field("limit", &self.limit);
+            fmt.finish()
+        }
+    }
+    impl Event for PacketBudgetExceeded {
+        const NAME: &'static str = "security:packet_budget_exceeded";
+    }
+    #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     #[doc = " Crypto key updated"]
     pub struct KeyUpdate {
@@ -2174,6 +2471,7 @@ pub mod api {
         const NAME: &'static str = "security:key_update";
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     pub struct KeySpaceDiscarded {
         pub space: KeySpace,
@@ -2190,6 +2488,7 @@ pub mod api {
         const NAME: &'static str = "security:key_space_discarded";
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     #[doc = " Connection started"]
     pub struct ConnectionStarted<'a> {
@@ -2207,6 +2506,7 @@ pub mod api {
         const NAME: &'static str = "connectivity:connection_started";
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     #[doc = " Duplicate packet received"]
     pub struct DuplicatePacket<'a> {
@@ -2228,6 +2528,7 @@ pub mod api {
         const NAME: &'static str = "transport:duplicate_packet";
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     #[doc = " Transport parameters received by connection"]
     pub struct TransportParametersReceived<'a> {
@@ -2245,6 +2546,7 @@ pub mod api {
         const NAME: &'static str = "transport:transport_parameters_received";
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     #[doc = " Datagram sent by a connection"]
     pub struct DatagramSent {
@@ -2270,6 +2572,7 @@ pub mod api {
         const NAME: &'static str = "transport:datagram_sent";
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     #[doc = " Datagram received by a connection"]
     pub struct DatagramReceived {
@@ -2287,6 +2590,7 @@ pub mod api {
         const NAME: &'static str = "transport:datagram_received";
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     #[doc = " Datagram dropped by a connection"]
     pub struct DatagramDropped<'a> {
@@ -2314,6 +2618,7 @@ pub mod api {
         const NAME: &'static str = "transport:datagram_dropped";
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     #[doc = " ConnectionId updated"]
     pub struct ConnectionIdUpdated<'a> {
@@ -2338,6 +2643,7 @@ pub mod api {
         const NAME: &'static str = "connectivity:connection_id_updated";
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     pub struct EcnStateChanged<'a> {
         pub path: Path<'a>,
@@ -2356,6 +2662,7 @@ pub mod api {
         const NAME: &'static str = "recovery:ecn_state_changed";
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     pub struct ConnectionMigrationDenied {
         pub reason: MigrationDenyReason,
@@ -2372,6 +2679,25 @@ pub mod api {
         const NAME: &'static str = "connectivity:connection_migration_denied";
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
+    #[non_exhaustive]
+    #[doc = " A strictness check was relaxed for a nonconformant peer"]
+    pub struct CompatibilityRelaxationApplied {
+        pub relaxation: CompatibilityRelaxation,
+    }
+    #[cfg(any(test, feature = "testing"))]
+    impl crate::event::snapshot::Fmt for CompatibilityRelaxationApplied {
+        fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::fmt::Result {
+            let mut fmt = fmt.debug_struct("CompatibilityRelaxationApplied");
+            fmt.field("relaxation", &self.relaxation);
+            fmt.finish()
+        }
+    }
+    impl Event for CompatibilityRelaxationApplied {
+        const NAME: &'static str = "transport:compatibility_relaxation_applied";
+    }
+    #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     pub struct HandshakeStatusUpdated {
         pub status: HandshakeStatus,
@@ -2388,6 +2714,7 @@ pub mod api {
         const NAME: &'static str = "connectivity:handshake_status_updated";
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     pub struct TlsExporterReady<'a> {
         pub session: crate::event::TlsSession<'a>,
@@ -2404,6 +2731,7 @@ pub mod api {
         const NAME: &'static str = "connectivity:tls_exporter_ready";
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     #[doc = " Path challenge updated"]
     pub struct PathChallengeUpdated<'a> {
@@ -2425,6 +2753,7 @@ pub mod api {
         const NAME: &'static str = "connectivity:path_challenge_updated";
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     pub struct TlsClientHello<'a> {
         pub payload: &'a [&'a [u8]],
@@ -2441,6 +2770,7 @@ pub mod api {
         const NAME: &'static str = "tls:client_hello";
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     pub struct TlsServerHello<'a> {
         pub payload: &'a [&'a [u8]],
@@ -2457,6 +2787,28 @@ pub mod api {
         const NAME: &'static str = "tls:server_hello";
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
+    #[non_exhaustive]
+    #[doc = " The TLS provider completed an operation which contributes to the latency of the handshake"]
+    pub struct TlsOperationCompleted {
+        pub operation: TlsOperation,
+        #[doc = " The amount of time the TLS provider spent in the operation"]
+        pub duration: core::time::Duration,
+    }
+    #[cfg(any(test, feature = "testing"))]
+    impl crate::event::snapshot::Fmt for TlsOperationCompleted {
+        fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::fmt::Result {
+            let mut fmt = fmt.debug_struct("TlsOperationCompleted");
+            fmt.field("operation", &self.operation);
+            fmt.field("duration", &self.duration);
+            fmt.finish()
+        }
+    }
+    impl Event for TlsOperationCompleted {
+        const NAME: &'static str = "tls:operation_completed";
+    }
+    #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     pub struct RxStreamProgress {
         pub bytes: usize,
@@ -2473,6 +2825,7 @@ pub mod api {
         const NAME: &'static str = "transport:rx_stream_progress";
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     pub struct TxStreamProgress {
         pub bytes: usize,
@@ -2489,6 +2842,7 @@ pub mod api {
         const NAME: &'static str = "transport:tx_stream_progress";
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     pub struct KeepAliveTimerExpired {
         pub timeout: Duration,
@@ -2505,6 +2859,7 @@ pub mod api {
         const NAME: &'static str = "connectivity::keep_alive_timer_expired";
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     #[doc = " The maximum transmission unit (MTU) and/or MTU probing status for the path has changed"]
     pub struct MtuUpdated {
@@ -2530,6 +2885,7 @@ pub mod api {
         const NAME: &'static str = "connectivity:mtu_updated";
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     #[doc = " The slow start congestion controller state has been exited"]
     pub struct SlowStartExited {
@@ -2551,6 +2907,7 @@ pub mod api {
         const NAME: &'static str = "recovery:slow_start_exited";
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     #[doc = " A new delivery rate sample has been generated"]
     #[doc = " Note: This event is only recorded for congestion controllers that support"]
@@ -2572,6 +2929,7 @@ pub mod api {
         const NAME: &'static str = "recovery:delivery_rate_sampled";
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     #[doc = " The pacing rate has been updated"]
     pub struct PacingRateUpdated {
@@ -2595,6 +2953,7 @@ pub mod api {
         const NAME: &'static str = "recovery:pacing_rate_updated";
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     #[doc = " The BBR state has changed"]
     pub struct BbrStateChanged {
@@ -2614,6 +2973,7 @@ pub mod api {
         const NAME: &'static str = "recovery:bbr_state_changed";
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     #[doc = " The DC state has changed"]
     pub struct DcStateChanged {
@@ -2631,6 +2991,144 @@ pub mod api {
         const NAME: &'static str = "transport:dc_state_changed";
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
+    #[non_exhaustive]
+    #[doc = " The server has requested the client move to a new connection"]
+    pub struct GoAwayReceived {}
+    #[cfg(any(test, feature = "testing"))]
+    impl crate::event::snapshot::Fmt for GoAwayReceived {
+        fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::fmt::Result {
+            let mut fmt = fmt.debug_struct("GoAwayReceived");
+            fmt.finish()
+        }
+    }
+    impl Event for GoAwayReceived {
+        const NAME: &'static str = "connectivity:go_away_received";
+    }
+    #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
+    #[non_exhaustive]
+    #[doc = " The application has used more of the peer's stream limit than the configured threshold"]
+    #[doc = ""]
+    #[doc = " This is emitted at most once for each stream limit issued by the peer."]
+    pub struct StreamLimitPressure {
+        pub stream_type: StreamType,
+        #[doc = " The cumulative number of streams opened by the application"]
+        pub opened_streams: u64,
+        #[doc = " The cumulative stream limit set by the peer"]
+        pub stream_limit: u64,
+    }
+    #[cfg(any(test, feature = "testing"))]
+    impl crate::event::snapshot::Fmt for StreamLimitPressure {
+        fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::fmt::Result {
+            let mut fmt = fmt.debug_struct("StreamLimitPressure");
+            fmt.field("stream_type", &self.stream_type);
+            fmt.field("opened_streams", &self.opened_streams);
+            fmt.field("stream_limit", &self.stream_limit);
+            fmt.finish()
+        }
+    }
+    impl Event for StreamLimitPressure {
+        const NAME: &'static str = "transport:stream_limit_pressure";
+    }
+    #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
+    #[non_exhaustive]
+    #[doc = " The application has used more of the peer's connection flow control limit than the"]
+    #[doc = " configured threshold"]
+    #[doc = ""]
+    #[doc = " This is emitted at most once for each MAX_DATA limit issued by the peer."]
+    pub struct ConnectionDataPressure {
+        #[doc = " The total number of stream bytes sent on the connection"]
+        pub bytes_sent: u64,
+        #[doc = " The connection flow control limit set by the peer"]
+        pub max_data: u64,
+    }
+    #[cfg(any(test, feature = "testing"))]
+    impl crate::event::snapshot::Fmt for ConnectionDataPressure {
+        fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::fmt::Result {
+            let mut fmt = fmt.debug_struct("ConnectionDataPressure");
+            fmt.field("bytes_sent", &self.bytes_sent);
+            fmt.field("max_data", &self.max_data);
+            fmt.finish()
+        }
+    }
+    impl Event for ConnectionDataPressure {
+        const NAME: &'static str = "transport:connection_data_pressure";
+    }
+    #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
+    #[non_exhaustive]
+    #[doc = " The application has been waiting to open a stream for longer than the configured threshold"]
+    #[doc = " because the peer's stream limit has been reached"]
+    pub struct StreamOpenBlocked {
+        pub stream_type: StreamType,
+        #[doc = " The cumulative stream limit set by the peer"]
+        pub stream_limit: u64,
+        #[doc = " The amount of time the application has been blocked"]
+        pub duration: Duration,
+    }
+    #[cfg(any(test, feature = "testing"))]
+    impl crate::event::snapshot::Fmt for StreamOpenBlocked {
+        fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::fmt::Result {
+            let mut fmt = fmt.debug_struct("StreamOpenBlocked");
+            fmt.field("stream_type", &self.stream_type);
+            fmt.field("stream_limit", &self.stream_limit);
+            fmt.field("duration", &self.duration);
+            fmt.finish()
+        }
+    }
+    impl Event for StreamOpenBlocked {
+        const NAME: &'static str = "transport:stream_open_blocked";
+    }
+    #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
+    #[non_exhaustive]
+    #[doc = " The connection has been idle for longer than the configured threshold and released its"]
+    #[doc = " buffers"]
+    #[doc = ""]
+    #[doc = " Buffers are reallocated when the connection becomes active again."]
+    pub struct ConnectionHibernated {
+        #[doc = " The amount of time the connection was idle before hibernating"]
+        pub idle_threshold: Duration,
+    }
+    #[cfg(any(test, feature = "testing"))]
+    impl crate::event::snapshot::Fmt for ConnectionHibernated {
+        fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::fmt::Result {
+            let mut fmt = fmt.debug_struct("ConnectionHibernated");
+            fmt.field("idle_threshold", &self.idle_threshold);
+            fmt.finish()
+        }
+    }
+    impl Event for ConnectionHibernated {
+        const NAME: &'static str = "transport:connection_hibernated";
+    }
+    #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
+    #[non_exhaustive]
+    #[doc = " A datagram was dropped locally before it was sent to the peer or read by the application"]
+    pub struct DatagramFrameDropped {
+        #[doc = " The length of the datagram payload"]
+        pub len: usize,
+        #[doc = " Whether the datagram was being sent to the peer, rather than received from it"]
+        pub is_outgoing: bool,
+        pub reason: DatagramFrameDropReason,
+    }
+    #[cfg(any(test, feature = "testing"))]
+    impl crate::event::snapshot::Fmt for DatagramFrameDropped {
+        fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::fmt::Result {
+            let mut fmt = fmt.debug_struct("DatagramFrameDropped");
+            fmt.field("len", &self.len);
+            fmt.field("is_outgoing", &self.is_outgoing);
+            fmt.field("reason", &self.reason);
+            fmt.finish()
+        }
+    }
+    impl Event for DatagramFrameDropped {
+        const NAME: &'static str = "transport:datagram_frame_dropped";
+    }
+    #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     #[doc = " Connection closed"]
     pub struct ConnectionClosed {
@@ -2648,6 +3146,7 @@ pub mod api {
         const NAME: &'static str = "connectivity:connection_closed";
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     #[doc = " QUIC version"]
     pub struct VersionInformation<'a> {
@@ -2669,6 +3168,7 @@ pub mod api {
         const NAME: &'static str = "transport::version_information";
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     #[doc = " Packet was sent by the endpoint"]
     pub struct EndpointPacketSent {
@@ -2686,6 +3186,7 @@ pub mod api {
         const NAME: &'static str = "transport:packet_sent";
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     #[doc = " Packet was received by the endpoint"]
     pub struct EndpointPacketReceived {
@@ -2703,6 +3204,7 @@ pub mod api {
         const NAME: &'static str = "transport:packet_received";
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     #[doc = " Datagram sent by the endpoint"]
     pub struct EndpointDatagramSent {
@@ -2728,6 +3230,7 @@ pub mod api {
         const NAME: &'static str = "transport:datagram_sent";
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     #[doc = " Datagram received by the endpoint"]
     pub struct EndpointDatagramReceived {
@@ -2745,6 +3248,7 @@ pub mod api {
         const NAME: &'static str = "transport:datagram_received";
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     #[doc = " Datagram dropped by the endpoint"]
     pub struct EndpointDatagramDropped {
@@ -2764,6 +3268,7 @@ pub mod api {
         const NAME: &'static str = "transport:datagram_dropped";
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     pub struct EndpointConnectionAttemptFailed {
         pub error: crate::connection::Error,
@@ -2780,6 +3285,7 @@ pub mod api {
         const NAME: &'static str = "transport:connection_attempt_failed";
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     #[doc = " Emitted when the platform sends at least one packet"]
     pub struct PlatformTx {
@@ -2812,6 +3318,7 @@ pub mod api {
         const NAME: &'static str = "platform:tx";
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     #[doc = " Emitted when the platform returns an error while sending datagrams"]
     pub struct PlatformTxError {
@@ -2830,6 +3337,7 @@ pub mod api {
         const NAME: &'static str = "platform:tx_error";
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     #[doc = " Emitted when the platform receives at least one packet"]
     pub struct PlatformRx {
@@ -2862,6 +3370,7 @@ pub mod api {
         const NAME: &'static str = "platform:rx";
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     #[doc = " Emitted when the platform returns an error while receiving datagrams"]
     pub struct PlatformRxError {
@@ -2880,6 +3389,7 @@ pub mod api {
         const NAME: &'static str = "platform:rx_error";
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     #[doc = " Emitted when a platform feature is configured"]
     pub struct PlatformFeatureConfigured {
@@ -2897,6 +3407,7 @@ pub mod api {
         const NAME: &'static str = "platform:feature_configured";
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     pub struct PlatformEventLoopWakeup {
         pub timeout_expired: bool,
@@ -2919,6 +3430,7 @@ pub mod api {
         const NAME: &'static str = "platform:event_loop_wakeup";
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     pub struct PlatformEventLoopSleep {
         #[doc = " The next time at which the event loop will wake"]
@@ -2939,6 +3451,7 @@ pub mod api {
         const NAME: &'static str = "platform:event_loop_sleep";
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
     #[non_exhaustive]
     pub struct PlatformEventLoopStarted<'a> {
         #[doc = " The local address of the socket"]
@@ -2956,6 +3469,42 @@ pub mod api {
         const NAME: &'static str = "platform:started";
     }
     #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize))]
+    #[non_exhaustive]
+    #[doc = " Emitted at the end of each profiling interval with the time the endpoint event loop"]
+    #[doc = " spent in each phase"]
+    pub struct PlatformEventLoopProfile {
+        #[doc = " The amount of time covered by this profile"]
+        pub interval: core::time::Duration,
+        #[doc = " Time spent parsing received datagrams and routing them to connections"]
+        pub rx_parse: core::time::Duration,
+        #[doc = " Time spent protecting and unprotecting packets"]
+        pub crypto: core::time::Duration,
+        #[doc = " Time spent processing received packets and application wakeups in connections"]
+        pub connection_processing: core::time::Duration,
+        #[doc = " Time spent writing frames into outgoing packets"]
+        pub tx_packing: core::time::Duration,
+        #[doc = " Time spent processing expired timers"]
+        pub timers: core::time::Duration,
+    }
+    #[cfg(any(test, feature = "testing"))]
+    impl crate::event::snapshot::Fmt for PlatformEventLoopProfile {
+        fn fmt(&self, fmt: &mut core::fmt::Formatter) -> core::fmt::Result {
+            let mut fmt = fmt.debug_struct("PlatformEventLoopProfile");
+            fmt.field("interval", &self.interval);
+            fmt.field("rx_parse", &self.rx_parse);
+            fmt.field("crypto", &self.crypto);
+            fmt.field("connection_processing", &self.connection_processing);
+            fmt.field("tx_packing", &self.tx_packing);
+            fmt.field("timers", &self.timers);
+            fmt.finish()
+        }
+    }
+    impl Event for PlatformEventLoopProfile {
+        const NAME: &'static str = "platform:event_loop_profile";
+    }
+    #[derive(Clone, Debug)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type"))]
     #[non_exhaustive]
     pub enum PlatformFeatureConfiguration {
         #[non_exhaustive]
@@ -3076,8 +3625,15 @@ pub mod api {
             Ok(())
         }
     }
+    #[doc = " Serialized as a hex string, e.g. `\"0x0a0b0c\"`"]
+    #[cfg(feature = "serde")]
+    impl<'a> serde::Serialize for ConnectionId<'a> {
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_str(&format_args!("{self:?}"))
+        }
+    }
     macro_rules! impl_conn_id {
-        ($name:ident) => {
+        ($ name : ident) => {
             impl<'a> IntoEvent<builder::ConnectionId<'a>> for &'a crate::connection::id::$name {
                 #[inline]
                 fn into_event(self) -> builder::ConnectionId<'a> {
@@ -3107,6 +3663,13 @@ pub mod api {
             Ok(())
         }
     }
+    #[doc = " Serialized as a string, e.g. `\"127.0.0.1:443\"`"]
+    #[cfg(feature = "serde")]
+    impl<'a> serde::Serialize for SocketAddress<'a> {
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.collect_str(&format_args!("{self:?}"))
+        }
+    }
     impl<'a> core::fmt::Display for SocketAddress<'a> {
         fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
             match self {
@@ -3402,6 +3965,32 @@ pub mod api {
             builder::Frame::DcStatelessResetTokens {}
         }
     }
+    impl IntoEvent<builder::Frame> for &crate::frame::GoAway {
+        #[inline]
+        fn into_event(self) -> builder::Frame {
+            builder::Frame::GoAway {}
+        }
+    }
+    impl IntoEvent<builder::Frame> for &crate::frame::ResetStreamAt {
+        #[inline]
+        fn into_event(self) -> builder::Frame {
+            builder::Frame::ResetStreamAt {
+                id: self.stream_id.as_u64(),
+                error_code: self.application_error_code.as_u64(),
+                final_size: self.final_size.as_u64(),
+                reliable_size: self.reliable_size.as_u64(),
+            }
+        }
+    }
+    impl IntoEvent<builder::Frame> for &crate::frame::ExpiredStreamData {
+        #[inline]
+        fn into_event(self) -> builder::Frame {
+            builder::Frame::ExpiredStreamData {
+                id: self.stream_id.as_u64(),
+                offset: self.offset.as_u64(),
+            }
+        }
+    }
     #[cfg(feature = "alloc")]
     impl<'a> ConnectionCloseFrame<'a> {
         #[doc = " Converts the reason to a UTF-8 `str`, including invalid characters"]
@@ -3436,6 +4025,18 @@ pub mod api {
             write!(f, "{:?}", self)
         }
     }
+    #[doc = " Serialized with the reason converted to a UTF-8 string, including invalid characters"]
+    #[cfg(feature = "serde")]
+    impl<'a> serde::Serialize for ConnectionCloseFrame<'a> {
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            use serde::ser::SerializeStruct;
+            let mut s = serializer.serialize_struct("ConnectionCloseFrame", 3)?;
+            s.serialize_field("error_code", &self.error_code)?;
+            s.serialize_field("frame_type", &self.frame_type)?;
+            s.serialize_field("reason", &self.reason_lossy_utf8())?;
+            s.end()
+        }
+    }
     impl IntoEvent<builder::StreamType> for &crate::stream::StreamType {
         #[inline]
         fn into_event(self) -> builder::StreamType {
@@ -3515,6 +4116,24 @@ pub mod api {
             }
         }
     }
+    #[doc = " Serialized as the standard identifier, e.g. `\"TLS_AES_128_GCM_SHA256\"`"]
+    #[cfg(feature = "serde")]
+    impl serde::Serialize for CipherSuite {
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_str(self.as_str())
+        }
+    }
+    impl IntoEvent<builder::DatagramFrameDropReason> for crate::datagram::DropReason {
+        #[inline]
+        fn into_event(self) -> builder::DatagramFrameDropReason {
+            use builder::DatagramFrameDropReason as Reason;
+            match self {
+                Self::QueueFull => Reason::QueueFull,
+                Self::ExceedsPacketCapacity => Reason::ExceedsPacketCapacity,
+                Self::ExceedsTransportLimits => Reason::ExceedsTransportLimits,
+            }
+        }
+    }
     #[cfg(feature = "std")]
     impl From<PlatformTxError> for std::io::Error {
         fn from(error: PlatformTxError) -> Self {
@@ -3817,6 +4436,21 @@ pub mod tracing {
             tracing :: event ! (target : "packet_dropped" , parent : id , tracing :: Level :: DEBUG , { reason = tracing :: field :: debug (reason) });
         }
         #[inline]
+        fn on_packet_budget_exceeded(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            _meta: &api::ConnectionMeta,
+            event: &api::PacketBudgetExceeded,
+        ) {
+            let id = context.id();
+            let api::PacketBudgetExceeded {
+                packet_header,
+                budget,
+                limit,
+            } = event;
+            tracing :: event ! (target : "packet_budget_exceeded" , parent : id , tracing :: Level :: DEBUG , { packet_header = tracing :: field :: debug (packet_header) , budget = tracing :: field :: debug (budget) , limit = tracing :: field :: debug (limit) });
+        }
+        #[inline]
         fn on_key_update(
             &mut self,
             context: &mut Self::ConnectionContext,
@@ -3959,6 +4593,17 @@ pub mod tracing {
             tracing :: event ! (target : "connection_migration_denied" , parent : id , tracing :: Level :: DEBUG , { reason = tracing :: field :: debug (reason) });
         }
         #[inline]
+        fn on_compatibility_relaxation_applied(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            _meta: &api::ConnectionMeta,
+            event: &api::CompatibilityRelaxationApplied,
+        ) {
+            let id = context.id();
+            let api::CompatibilityRelaxationApplied { relaxation } = event;
+            tracing :: event ! (target : "compatibility_relaxation_applied" , parent : id , tracing :: Level :: DEBUG , { relaxation = tracing :: field :: debug (relaxation) });
+        }
+        #[inline]
         fn on_handshake_status_updated(
             &mut self,
             context: &mut Self::ConnectionContext,
@@ -4018,6 +4663,20 @@ pub mod tracing {
             tracing :: event ! (target : "tls_server_hello" , parent : id , tracing :: Level :: DEBUG , { payload = tracing :: field :: debug (payload) });
         }
         #[inline]
+        fn on_tls_operation_completed(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            _meta: &api::ConnectionMeta,
+            event: &api::TlsOperationCompleted,
+        ) {
+            let id = context.id();
+            let api::TlsOperationCompleted {
+                operation,
+                duration,
+            } = event;
+            tracing :: event ! (target : "tls_operation_completed" , parent : id , tracing :: Level :: DEBUG , { operation = tracing :: field :: debug (operation) , duration = tracing :: field :: debug (duration) });
+        }
+        #[inline]
         fn on_rx_stream_progress(
             &mut self,
             context: &mut Self::ConnectionContext,
@@ -4134,6 +4793,87 @@ pub mod tracing {
             tracing :: event ! (target : "dc_state_changed" , parent : id , tracing :: Level :: DEBUG , { state = tracing :: field :: debug (state) });
         }
         #[inline]
+        fn on_go_away_received(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            _meta: &api::ConnectionMeta,
+            event: &api::GoAwayReceived,
+        ) {
+            let id = context.id();
+            let api::GoAwayReceived {} = event;
+            tracing :: event ! (target : "go_away_received" , parent : id , tracing :: Level :: DEBUG , { });
+        }
+        #[inline]
+        fn on_stream_limit_pressure(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            _meta: &api::ConnectionMeta,
+            event: &api::StreamLimitPressure,
+        ) {
+            let id = context.id();
+            let api::StreamLimitPressure {
+                stream_type,
+                opened_streams,
+                stream_limit,
+            } = event;
+            tracing :: event ! (target : "stream_limit_pressure" , parent : id , tracing :: Level :: DEBUG , { stream_type = tracing :: field :: debug (stream_type) , opened_streams = tracing :: field :: debug (opened_streams) , stream_limit = tracing :: field :: debug (stream_limit) });
+        }
+        #[inline]
+        fn on_connection_data_pressure(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            _meta: &api::ConnectionMeta,
+            event: &api::ConnectionDataPressure,
+        ) {
+            let id = context.id();
+            let api::ConnectionDataPressure {
+                bytes_sent,
+                max_data,
+            } = event;
+            tracing :: event ! (target : "connection_data_pressure" , parent : id , tracing :: Level :: DEBUG , { bytes_sent = tracing :: field :: debug (bytes_sent) , max_data = tracing :: field :: debug (max_data) });
+        }
+        #[inline]
+        fn on_stream_open_blocked(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            _meta: &api::ConnectionMeta,
+            event: &api::StreamOpenBlocked,
+        ) {
+            let id = context.id();
+            let api::StreamOpenBlocked {
+                stream_type,
+                stream_limit,
+                duration,
+            } = event;
+            tracing :: event ! (target : "stream_open_blocked" , parent : id , tracing :: Level :: DEBUG , { stream_type = tracing :: field :: debug (stream_type) , stream_limit = tracing :: field :: debug (stream_limit) , duration = tracing :: field :: debug (duration) });
+        }
+        #[inline]
+        fn on_connection_hibernated(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            _meta: &api::ConnectionMeta,
+            event: &api::ConnectionHibernated,
+        ) {
+            let id = context.id();
+            let api::ConnectionHibernated { idle_threshold } = event;
+            tracing :: event ! (target : "connection_hibernated" , parent : id , tracing :: Level :: DEBUG , { idle_threshold = tracing :: field :: debug (idle_threshold) });
+        }
+        #[inline]
+        fn on_datagram_frame_dropped(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            _meta: &api::ConnectionMeta,
+            event: &api::DatagramFrameDropped,
+        ) {
+            let id = context.id();
+            let api::DatagramFrameDropped {
+                len,
+                is_outgoing,
+                reason,
+            } = event;
+            tracing :: event ! (target : "datagram_frame_dropped" , parent : id , tracing :: Level :: DEBUG , { len = tracing :: field :: debug (len) , is_outgoing = tracing :: field :: debug (is_outgoing) , reason = tracing :: field :: debug (reason) });
+        }
+        #[inline]
         fn on_connection_closed(
             &mut self,
             context: &mut Self::ConnectionContext,
@@ -4302,6 +5042,613 @@ pub mod tracing {
             let api::PlatformEventLoopStarted { local_address } = event;
             tracing :: event ! (target : "platform_event_loop_started" , parent : parent , tracing :: Level :: DEBUG , { local_address = tracing :: field :: debug (local_address) });
         }
+        #[inline]
+        fn on_platform_event_loop_profile(
+            &mut self,
+            meta: &api::EndpointMeta,
+            event: &api::PlatformEventLoopProfile,
+        ) {
+            let parent = self.parent(meta);
+            let api::PlatformEventLoopProfile {
+                interval,
+                rx_parse,
+                crypto,
+                connection_processing,
+                tx_packing,
+                timers,
+            } = event;
+            tracing :: event ! (target : "platform_event_loop_profile" , parent : parent , tracing :: Level :: DEBUG , { interval = tracing :: field :: debug (interval) , rx_parse = tracing :: field :: debug (rx_parse) , crypto = tracing :: field :: debug (crypto) , connection_processing = tracing :: field :: debug (connection_processing) , tx_packing = tracing :: field :: debug (tx_packing) , timers = tracing :: field :: debug (timers) });
+        }
+    }
+}
+#[cfg(feature = "serde")]
+impl<X: export::Exporter> Subscriber for export::Subscriber<X> {
+    type ConnectionContext = ();
+    fn create_connection_context(
+        &mut self,
+        _meta: &api::ConnectionMeta,
+        _info: &api::ConnectionInfo,
+    ) -> Self::ConnectionContext {
+    }
+    #[inline]
+    fn on_application_protocol_information(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::ApplicationProtocolInformation,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_server_name_information(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::ServerNameInformation,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_packet_skipped(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::PacketSkipped,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_packet_sent(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::PacketSent,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_packet_received(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::PacketReceived,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_active_path_updated(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::ActivePathUpdated,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_path_created(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::PathCreated,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_frame_sent(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::FrameSent,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_frame_received(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::FrameReceived,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_connection_close_frame_received(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::ConnectionCloseFrameReceived,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_packet_lost(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::PacketLost,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_recovery_metrics(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::RecoveryMetrics,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_congestion(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::Congestion,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_rx_ack_range_dropped(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::RxAckRangeDropped,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_ack_range_received(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::AckRangeReceived,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_ack_range_sent(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::AckRangeSent,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_packet_dropped(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::PacketDropped,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_packet_budget_exceeded(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::PacketBudgetExceeded,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_key_update(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::KeyUpdate,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_key_space_discarded(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::KeySpaceDiscarded,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_connection_started(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::ConnectionStarted,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_duplicate_packet(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::DuplicatePacket,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_transport_parameters_received(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::TransportParametersReceived,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_datagram_sent(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::DatagramSent,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_datagram_received(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::DatagramReceived,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_datagram_dropped(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::DatagramDropped,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_connection_id_updated(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::ConnectionIdUpdated,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_ecn_state_changed(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::EcnStateChanged,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_connection_migration_denied(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::ConnectionMigrationDenied,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_compatibility_relaxation_applied(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::CompatibilityRelaxationApplied,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_handshake_status_updated(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::HandshakeStatusUpdated,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_tls_exporter_ready(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::TlsExporterReady,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_path_challenge_updated(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::PathChallengeUpdated,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_tls_client_hello(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::TlsClientHello,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_tls_server_hello(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::TlsServerHello,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_tls_operation_completed(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::TlsOperationCompleted,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_rx_stream_progress(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::RxStreamProgress,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_tx_stream_progress(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::TxStreamProgress,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_keep_alive_timer_expired(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::KeepAliveTimerExpired,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_mtu_updated(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::MtuUpdated,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_slow_start_exited(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::SlowStartExited,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_delivery_rate_sampled(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::DeliveryRateSampled,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_pacing_rate_updated(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::PacingRateUpdated,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_bbr_state_changed(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::BbrStateChanged,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_dc_state_changed(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::DcStateChanged,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_go_away_received(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::GoAwayReceived,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_stream_limit_pressure(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::StreamLimitPressure,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_connection_data_pressure(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::ConnectionDataPressure,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_stream_open_blocked(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::StreamOpenBlocked,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_connection_hibernated(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::ConnectionHibernated,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_datagram_frame_dropped(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::DatagramFrameDropped,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_connection_closed(
+        &mut self,
+        _context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::ConnectionClosed,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_version_information(
+        &mut self,
+        meta: &api::EndpointMeta,
+        event: &api::VersionInformation,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_endpoint_packet_sent(
+        &mut self,
+        meta: &api::EndpointMeta,
+        event: &api::EndpointPacketSent,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_endpoint_packet_received(
+        &mut self,
+        meta: &api::EndpointMeta,
+        event: &api::EndpointPacketReceived,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_endpoint_datagram_sent(
+        &mut self,
+        meta: &api::EndpointMeta,
+        event: &api::EndpointDatagramSent,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_endpoint_datagram_received(
+        &mut self,
+        meta: &api::EndpointMeta,
+        event: &api::EndpointDatagramReceived,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_endpoint_datagram_dropped(
+        &mut self,
+        meta: &api::EndpointMeta,
+        event: &api::EndpointDatagramDropped,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_endpoint_connection_attempt_failed(
+        &mut self,
+        meta: &api::EndpointMeta,
+        event: &api::EndpointConnectionAttemptFailed,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_platform_tx(&mut self, meta: &api::EndpointMeta, event: &api::PlatformTx) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_platform_tx_error(&mut self, meta: &api::EndpointMeta, event: &api::PlatformTxError) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_platform_rx(&mut self, meta: &api::EndpointMeta, event: &api::PlatformRx) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_platform_rx_error(&mut self, meta: &api::EndpointMeta, event: &api::PlatformRxError) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_platform_feature_configured(
+        &mut self,
+        meta: &api::EndpointMeta,
+        event: &api::PlatformFeatureConfigured,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_platform_event_loop_wakeup(
+        &mut self,
+        meta: &api::EndpointMeta,
+        event: &api::PlatformEventLoopWakeup,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_platform_event_loop_sleep(
+        &mut self,
+        meta: &api::EndpointMeta,
+        event: &api::PlatformEventLoopSleep,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_platform_event_loop_started(
+        &mut self,
+        meta: &api::EndpointMeta,
+        event: &api::PlatformEventLoopStarted,
+    ) {
+        self.export(meta, event);
+    }
+    #[inline]
+    fn on_platform_event_loop_profile(
+        &mut self,
+        meta: &api::EndpointMeta,
+        event: &api::PlatformEventLoopProfile,
+    ) {
+        self.export(meta, event);
     }
 }
 pub mod builder {
@@ -4718,6 +6065,17 @@ pub mod builder {
             len: u16,
         },
         DcStatelessResetTokens,
+        GoAway,
+        ResetStreamAt {
+            id: u64,
+            error_code: u64,
+            final_size: u64,
+            reliable_size: u64,
+        },
+        ExpiredStreamData {
+            id: u64,
+            offset: u64,
+        },
     }
     impl IntoEvent<api::Frame> for Frame {
         #[inline]
@@ -4813,6 +6171,22 @@ pub mod builder {
                     len: len.into_event(),
                 },
                 Self::DcStatelessResetTokens => DcStatelessResetTokens {},
+                Self::GoAway => GoAway {},
+                Self::ResetStreamAt {
+                    id,
+                    error_code,
+                    final_size,
+                    reliable_size,
+                } => ResetStreamAt {
+                    id: id.into_event(),
+                    error_code: error_code.into_event(),
+                    final_size: final_size.into_event(),
+                    reliable_size: reliable_size.into_event(),
+                },
+                Self::ExpiredStreamData { id, offset } => ExpiredStreamData {
+                    id: id.into_event(),
+                    offset: offset.into_event(),
+                },
             }
         }
     }
@@ -4990,6 +6364,12 @@ pub mod builder {
         PathLimitExceeded,
         #[doc = " The peer initiated a connection migration without supplying enough connection IDs to use."]
         InsufficientConnectionIds,
+        #[doc = " The connection attempt exceeded the endpoint's connection rate limit."]
+        ConnectionRateLimited,
+        #[doc = " The connection attempt exceeded the endpoint's limit on inflight handshakes."]
+        InflightHandshakeLimitExceeded,
+        #[doc = " The datagram exceeded the max_udp_payload_size advertised to the peer."]
+        PayloadSizeExceeded,
     }
     impl IntoEvent<api::DatagramDropReason> for DatagramDropReason {
         #[inline]
@@ -5013,6 +6393,9 @@ pub mod builder {
                 Self::RejectedConnectionMigration => RejectedConnectionMigration {},
                 Self::PathLimitExceeded => PathLimitExceeded {},
                 Self::InsufficientConnectionIds => InsufficientConnectionIds {},
+                Self::ConnectionRateLimited => ConnectionRateLimited {},
+                Self::InflightHandshakeLimitExceeded => InflightHandshakeLimitExceeded {},
+                Self::PayloadSizeExceeded => PayloadSizeExceeded {},
             }
         }
     }
@@ -5214,6 +6597,50 @@ pub mod builder {
         }
     }
     #[derive(Clone, Debug)]
+    #[doc = " An operation performed by the TLS provider during the handshake"]
+    pub enum TlsOperation {
+        #[doc = " Validating the certificate chain presented by the peer"]
+        CertificateValidation,
+        #[doc = " Signing the handshake transcript with the local private key"]
+        SignatureGeneration,
+        #[doc = " Verifying the peer's signature of the handshake transcript"]
+        SignatureVerification,
+        #[doc = " Decrypting a session ticket presented by a resuming client"]
+        TicketDecryption,
+    }
+    impl IntoEvent<api::TlsOperation> for TlsOperation {
+        #[inline]
+        fn into_event(self) -> api::TlsOperation {
+            use api::TlsOperation::*;
+            match self {
+                Self::CertificateValidation => CertificateValidation {},
+                Self::SignatureGeneration => SignatureGeneration {},
+                Self::SignatureVerification => SignatureVerification {},
+                Self::TicketDecryption => TicketDecryption {},
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    #[doc = " A strictness check which was relaxed by the compatibility policy"]
+    pub enum CompatibilityRelaxation {
+        #[doc = " The peer omitted the `initial_source_connection_id` transport parameter"]
+        MissingInitialSourceConnectionId,
+        #[doc = " The server omitted the `original_destination_connection_id` transport parameter"]
+        MissingOriginalDestinationConnectionId,
+    }
+    impl IntoEvent<api::CompatibilityRelaxation> for CompatibilityRelaxation {
+        #[inline]
+        fn into_event(self) -> api::CompatibilityRelaxation {
+            use api::CompatibilityRelaxation::*;
+            match self {
+                Self::MissingInitialSourceConnectionId => MissingInitialSourceConnectionId {},
+                Self::MissingOriginalDestinationConnectionId => {
+                    MissingOriginalDestinationConnectionId {}
+                }
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
     pub enum MigrationDenyReason {
         BlockedPort,
         PortScopeChanged,
@@ -5399,6 +6826,27 @@ pub mod builder {
         }
     }
     #[derive(Clone, Debug)]
+    #[doc = " A budget limiting the work performed when processing a single packet"]
+    pub enum PacketBudget {
+        #[doc = " The number of frames in the packet"]
+        Frames,
+        #[doc = " The number of ACK ranges in all of the ACK frames in the packet"]
+        AckRanges,
+        #[doc = " The number of bytes in all of the CRYPTO frames in the packet"]
+        CryptoBytes,
+    }
+    impl IntoEvent<api::PacketBudget> for PacketBudget {
+        #[inline]
+        fn into_event(self) -> api::PacketBudget {
+            use api::PacketBudget::*;
+            match self {
+                Self::Frames => Frames {},
+                Self::AckRanges => AckRanges {},
+                Self::CryptoBytes => CryptoBytes {},
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
     pub enum BbrState {
         Startup,
         Drain,
@@ -5445,6 +6893,27 @@ pub mod builder {
         }
     }
     #[derive(Clone, Debug)]
+    #[doc = " The reason a DATAGRAM frame was dropped locally"]
+    pub enum DatagramFrameDropReason {
+        #[doc = " The datagram queue was at capacity"]
+        QueueFull,
+        #[doc = " The datagram was too large to fit in the available packet space"]
+        ExceedsPacketCapacity,
+        #[doc = " The datagram was larger than the maximum DATAGRAM frame size"]
+        ExceedsTransportLimits,
+    }
+    impl IntoEvent<api::DatagramFrameDropReason> for DatagramFrameDropReason {
+        #[inline]
+        fn into_event(self) -> api::DatagramFrameDropReason {
+            use api::DatagramFrameDropReason::*;
+            match self {
+                Self::QueueFull => QueueFull {},
+                Self::ExceedsPacketCapacity => ExceedsPacketCapacity {},
+                Self::ExceedsTransportLimits => ExceedsTransportLimits {},
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
     #[doc = " Application level protocol"]
     pub struct ApplicationProtocolInformation<'a> {
         pub chosen_application_protocol: &'a [u8],
@@ -5825,6 +7294,30 @@ pub mod builder {
         }
     }
     #[derive(Clone, Debug)]
+    #[doc = " Processing a packet exceeded one of the configured budgets"]
+    #[doc = ""]
+    #[doc = " The remainder of the packet is discarded and the connection is closed."]
+    pub struct PacketBudgetExceeded {
+        pub packet_header: PacketHeader,
+        pub budget: PacketBudget,
+        pub limit: u64,
+    }
+    impl IntoEvent<api::PacketBudgetExceeded> for PacketBudgetExceeded {
+        #[inline]
+        fn into_event(self) -> api::PacketBudgetExceeded {
+            let PacketBudgetExceeded {
+                packet_header,
+                budget,
+                limit,
+            } = self;
+            api::PacketBudgetExceeded {
+                packet_header: packet_header.into_event(),
+                budget: budget.into_event(),
+                limit: limit.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
     #[doc = " Crypto key updated"]
     pub struct KeyUpdate {
         pub key_type: KeyType,
@@ -6030,6 +7523,20 @@ pub mod builder {
         }
     }
     #[derive(Clone, Debug)]
+    #[doc = " A strictness check was relaxed for a nonconformant peer"]
+    pub struct CompatibilityRelaxationApplied {
+        pub relaxation: CompatibilityRelaxation,
+    }
+    impl IntoEvent<api::CompatibilityRelaxationApplied> for CompatibilityRelaxationApplied {
+        #[inline]
+        fn into_event(self) -> api::CompatibilityRelaxationApplied {
+            let CompatibilityRelaxationApplied { relaxation } = self;
+            api::CompatibilityRelaxationApplied {
+                relaxation: relaxation.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
     pub struct HandshakeStatusUpdated {
         pub status: HandshakeStatus,
     }
@@ -6104,6 +7611,26 @@ pub mod builder {
         }
     }
     #[derive(Clone, Debug)]
+    #[doc = " The TLS provider completed an operation which contributes to the latency of the handshake"]
+    pub struct TlsOperationCompleted {
+        pub operation: TlsOperation,
+        #[doc = " The amount of time the TLS provider spent in the operation"]
+        pub duration: core::time::Duration,
+    }
+    impl IntoEvent<api::TlsOperationCompleted> for TlsOperationCompleted {
+        #[inline]
+        fn into_event(self) -> api::TlsOperationCompleted {
+            let TlsOperationCompleted {
+                operation,
+                duration,
+            } = self;
+            api::TlsOperationCompleted {
+                operation: operation.into_event(),
+                duration: duration.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
     pub struct RxStreamProgress {
         pub bytes: usize,
     }
@@ -6268,6 +7795,133 @@ pub mod builder {
         }
     }
     #[derive(Clone, Debug)]
+    #[doc = " The server has requested the client move to a new connection"]
+    pub struct GoAwayReceived {}
+    impl IntoEvent<api::GoAwayReceived> for GoAwayReceived {
+        #[inline]
+        fn into_event(self) -> api::GoAwayReceived {
+            let GoAwayReceived {} = self;
+            api::GoAwayReceived {}
+        }
+    }
+    #[derive(Clone, Debug)]
+    #[doc = " The application has used more of the peer's stream limit than the configured threshold"]
+    #[doc = ""]
+    #[doc = " This is emitted at most once for each stream limit issued by the peer."]
+    pub struct StreamLimitPressure {
+        pub stream_type: StreamType,
+        #[doc = " The cumulative number of streams opened by the application"]
+        pub opened_streams: u64,
+        #[doc = " The cumulative stream limit set by the peer"]
+        pub stream_limit: u64,
+    }
+    impl IntoEvent<api::StreamLimitPressure> for StreamLimitPressure {
+        #[inline]
+        fn into_event(self) -> api::StreamLimitPressure {
+            let StreamLimitPressure {
+                stream_type,
+                opened_streams,
+                stream_limit,
+            } = self;
+            api::StreamLimitPressure {
+                stream_type: stream_type.into_event(),
+                opened_streams: opened_streams.into_event(),
+                stream_limit: stream_limit.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    #[doc = " The application has used more of the peer's connection flow control limit than the"]
+    #[doc = " configured threshold"]
+    #[doc = ""]
+    #[doc = " This is emitted at most once for each MAX_DATA limit issued by the peer."]
+    pub struct ConnectionDataPressure {
+        #[doc = " The total number of stream bytes sent on the connection"]
+        pub bytes_sent: u64,
+        #[doc = " The connection flow control limit set by the peer"]
+        pub max_data: u64,
+    }
+    impl IntoEvent<api::ConnectionDataPressure> for ConnectionDataPressure {
+        #[inline]
+        fn into_event(self) -> api::ConnectionDataPressure {
+            let ConnectionDataPressure {
+                bytes_sent,
+                max_data,
+            } = self;
+            api::ConnectionDataPressure {
+                bytes_sent: bytes_sent.into_event(),
+                max_data: max_data.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    #[doc = " The application has been waiting to open a stream for longer than the configured threshold"]
+    #[doc = " because the peer's stream limit has been reached"]
+    pub struct StreamOpenBlocked {
+        pub stream_type: StreamType,
+        #[doc = " The cumulative stream limit set by the peer"]
+        pub stream_limit: u64,
+        #[doc = " The amount of time the application has been blocked"]
+        pub duration: Duration,
+    }
+    impl IntoEvent<api::StreamOpenBlocked> for StreamOpenBlocked {
+        #[inline]
+        fn into_event(self) -> api::StreamOpenBlocked {
+            let StreamOpenBlocked {
+                stream_type,
+                stream_limit,
+                duration,
+            } = self;
+            api::StreamOpenBlocked {
+                stream_type: stream_type.into_event(),
+                stream_limit: stream_limit.into_event(),
+                duration: duration.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    #[doc = " The connection has been idle for longer than the configured threshold and released its"]
+    #[doc = " buffers"]
+    #[doc = ""]
+    #[doc = " Buffers are reallocated when the connection becomes active again."]
+    pub struct ConnectionHibernated {
+        #[doc = " The amount of time the connection was idle before hibernating"]
+        pub idle_threshold: Duration,
+    }
+    impl IntoEvent<api::ConnectionHibernated> for ConnectionHibernated {
+        #[inline]
+        fn into_event(self) -> api::ConnectionHibernated {
+            let ConnectionHibernated { idle_threshold } = self;
+            api::ConnectionHibernated {
+                idle_threshold: idle_threshold.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
+    #[doc = " A datagram was dropped locally before it was sent to the peer or read by the application"]
+    pub struct DatagramFrameDropped {
+        #[doc = " The length of the datagram payload"]
+        pub len: usize,
+        #[doc = " Whether the datagram was being sent to the peer, rather than received from it"]
+        pub is_outgoing: bool,
+        pub reason: DatagramFrameDropReason,
+    }
+    impl IntoEvent<api::DatagramFrameDropped> for DatagramFrameDropped {
+        #[inline]
+        fn into_event(self) -> api::DatagramFrameDropped {
+            let DatagramFrameDropped {
+                len,
+                is_outgoing,
+                reason,
+            } = self;
+            api::DatagramFrameDropped {
+                len: len.into_event(),
+                is_outgoing: is_outgoing.into_event(),
+                reason: reason.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
     #[doc = " Connection closed"]
     pub struct ConnectionClosed {
         pub error: crate::connection::Error,
@@ -6569,6 +8223,44 @@ pub mod builder {
         }
     }
     #[derive(Clone, Debug)]
+    #[doc = " Emitted at the end of each profiling interval with the time the endpoint event loop"]
+    #[doc = " spent in each phase"]
+    pub struct PlatformEventLoopProfile {
+        #[doc = " The amount of time covered by this profile"]
+        pub interval: core::time::Duration,
+        #[doc = " Time spent parsing received datagrams and routing them to connections"]
+        pub rx_parse: core::time::Duration,
+        #[doc = " Time spent protecting and unprotecting packets"]
+        pub crypto: core::time::Duration,
+        #[doc = " Time spent processing received packets and application wakeups in connections"]
+        pub connection_processing: core::time::Duration,
+        #[doc = " Time spent writing frames into outgoing packets"]
+        pub tx_packing: core::time::Duration,
+        #[doc = " Time spent processing expired timers"]
+        pub timers: core::time::Duration,
+    }
+    impl IntoEvent<api::PlatformEventLoopProfile> for PlatformEventLoopProfile {
+        #[inline]
+        fn into_event(self) -> api::PlatformEventLoopProfile {
+            let PlatformEventLoopProfile {
+                interval,
+                rx_parse,
+                crypto,
+                connection_processing,
+                tx_packing,
+                timers,
+            } = self;
+            api::PlatformEventLoopProfile {
+                interval: interval.into_event(),
+                rx_parse: rx_parse.into_event(),
+                crypto: crypto.into_event(),
+                connection_processing: connection_processing.into_event(),
+                tx_packing: tx_packing.into_event(),
+                timers: timers.into_event(),
+            }
+        }
+    }
+    #[derive(Clone, Debug)]
     pub enum PlatformFeatureConfiguration {
         #[doc = " Emitted when segment offload was configured"]
         Gso {
@@ -6670,7 +8362,8 @@ pub mod supervisor {
 pub use traits::*;
 mod traits {
     use super::*;
-    use crate::{event::Meta, query};
+    use crate::event::Meta;
+    use crate::query;
     use core::fmt;
     #[doc = r" Allows for events to be subscribed to"]
     pub trait Subscriber: 'static + Send {
@@ -6975,6 +8668,18 @@ mod traits {
             let _ = meta;
             let _ = event;
         }
+        #[doc = "Called when the `PacketBudgetExceeded` event is triggered"]
+        #[inline]
+        fn on_packet_budget_exceeded(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::PacketBudgetExceeded,
+        ) {
+            let _ = context;
+            let _ = meta;
+            let _ = event;
+        }
         #[doc = "Called when the `KeyUpdate` event is triggered"]
         #[inline]
         fn on_key_update(
@@ -7107,6 +8812,18 @@ mod traits {
             let _ = meta;
             let _ = event;
         }
+        #[doc = "Called when the `CompatibilityRelaxationApplied` event is triggered"]
+        #[inline]
+        fn on_compatibility_relaxation_applied(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::CompatibilityRelaxationApplied,
+        ) {
+            let _ = context;
+            let _ = meta;
+            let _ = event;
+        }
         #[doc = "Called when the `HandshakeStatusUpdated` event is triggered"]
         #[inline]
         fn on_handshake_status_updated(
@@ -7167,6 +8884,18 @@ mod traits {
             let _ = meta;
             let _ = event;
         }
+        #[doc = "Called when the `TlsOperationCompleted` event is triggered"]
+        #[inline]
+        fn on_tls_operation_completed(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::TlsOperationCompleted,
+        ) {
+            let _ = context;
+            let _ = meta;
+            let _ = event;
+        }
         #[doc = "Called when the `RxStreamProgress` event is triggered"]
         #[inline]
         fn on_rx_stream_progress(
@@ -7275,39 +9004,111 @@ mod traits {
             let _ = meta;
             let _ = event;
         }
-        #[doc = "Called when the `ConnectionClosed` event is triggered"]
+        #[doc = "Called when the `GoAwayReceived` event is triggered"]
         #[inline]
-        fn on_connection_closed(
+        fn on_go_away_received(
             &mut self,
             context: &mut Self::ConnectionContext,
             meta: &api::ConnectionMeta,
-            event: &api::ConnectionClosed,
+            event: &api::GoAwayReceived,
         ) {
             let _ = context;
             let _ = meta;
             let _ = event;
         }
-        #[doc = "Called when the `VersionInformation` event is triggered"]
+        #[doc = "Called when the `StreamLimitPressure` event is triggered"]
         #[inline]
-        fn on_version_information(
+        fn on_stream_limit_pressure(
             &mut self,
-            meta: &api::EndpointMeta,
-            event: &api::VersionInformation,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::StreamLimitPressure,
         ) {
+            let _ = context;
             let _ = meta;
             let _ = event;
         }
-        #[doc = "Called when the `EndpointPacketSent` event is triggered"]
+        #[doc = "Called when the `ConnectionDataPressure` event is triggered"]
         #[inline]
-        fn on_endpoint_packet_sent(
+        fn on_connection_data_pressure(
             &mut self,
-            meta: &api::EndpointMeta,
-            event: &api::EndpointPacketSent,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::ConnectionDataPressure,
         ) {
+            let _ = context;
             let _ = meta;
             let _ = event;
         }
-        #[doc = "Called when the `EndpointPacketReceived` event is triggered"]
+        #[doc = "Called when the `StreamOpenBlocked` event is triggered"]
+        #[inline]
+        fn on_stream_open_blocked(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::StreamOpenBlocked,
+        ) {
+            let _ = context;
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `ConnectionHibernated` event is triggered"]
+        #[inline]
+        fn on_connection_hibernated(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::ConnectionHibernated,
+        ) {
+            let _ = context;
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `DatagramFrameDropped` event is triggered"]
+        #[inline]
+        fn on_datagram_frame_dropped(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::DatagramFrameDropped,
+        ) {
+            let _ = context;
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `ConnectionClosed` event is triggered"]
+        #[inline]
+        fn on_connection_closed(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::ConnectionClosed,
+        ) {
+            let _ = context;
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `VersionInformation` event is triggered"]
+        #[inline]
+        fn on_version_information(
+            &mut self,
+            meta: &api::EndpointMeta,
+            event: &api::VersionInformation,
+        ) {
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `EndpointPacketSent` event is triggered"]
+        #[inline]
+        fn on_endpoint_packet_sent(
+            &mut self,
+            meta: &api::EndpointMeta,
+            event: &api::EndpointPacketSent,
+        ) {
+            let _ = meta;
+            let _ = event;
+        }
+        #[doc = "Called when the `EndpointPacketReceived` event is triggered"]
         #[inline]
         fn on_endpoint_packet_received(
             &mut self,
@@ -7421,6 +9222,16 @@ mod traits {
             let _ = meta;
             let _ = event;
         }
+        #[doc = "Called when the `PlatformEventLoopProfile` event is triggered"]
+        #[inline]
+        fn on_platform_event_loop_profile(
+            &mut self,
+            meta: &api::EndpointMeta,
+            event: &api::PlatformEventLoopProfile,
+        ) {
+            let _ = meta;
+            let _ = event;
+        }
         #[doc = r" Called for each event that relates to the endpoint and all connections"]
         #[inline]
         fn on_event<M: Meta, E: Event>(&mut self, meta: &M, event: &E) {
@@ -7440,6 +9251,10 @@ mod traits {
             let _ = event;
         }
         #[doc = r" Used for querying the `Subscriber::ConnectionContext` on a Subscriber"]
+        #[doc = r""]
+        #[doc = r" Subscribers which wrap other subscribers should also forward the query to"]
+        #[doc = r" the contexts of the inner subscribers so they can be found by type,"]
+        #[doc = r" regardless of how the subscribers are composed."]
         #[inline]
         fn query(
             context: &Self::ConnectionContext,
@@ -7701,6 +9516,16 @@ mod traits {
             (self.1).on_packet_dropped(&mut context.1, meta, event);
         }
         #[inline]
+        fn on_packet_budget_exceeded(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::PacketBudgetExceeded,
+        ) {
+            (self.0).on_packet_budget_exceeded(&mut context.0, meta, event);
+            (self.1).on_packet_budget_exceeded(&mut context.1, meta, event);
+        }
+        #[inline]
         fn on_key_update(
             &mut self,
             context: &mut Self::ConnectionContext,
@@ -7811,6 +9636,16 @@ mod traits {
             (self.1).on_connection_migration_denied(&mut context.1, meta, event);
         }
         #[inline]
+        fn on_compatibility_relaxation_applied(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::CompatibilityRelaxationApplied,
+        ) {
+            (self.0).on_compatibility_relaxation_applied(&mut context.0, meta, event);
+            (self.1).on_compatibility_relaxation_applied(&mut context.1, meta, event);
+        }
+        #[inline]
         fn on_handshake_status_updated(
             &mut self,
             context: &mut Self::ConnectionContext,
@@ -7861,6 +9696,16 @@ mod traits {
             (self.1).on_tls_server_hello(&mut context.1, meta, event);
         }
         #[inline]
+        fn on_tls_operation_completed(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::TlsOperationCompleted,
+        ) {
+            (self.0).on_tls_operation_completed(&mut context.0, meta, event);
+            (self.1).on_tls_operation_completed(&mut context.1, meta, event);
+        }
+        #[inline]
         fn on_rx_stream_progress(
             &mut self,
             context: &mut Self::ConnectionContext,
@@ -7951,6 +9796,66 @@ mod traits {
             (self.1).on_dc_state_changed(&mut context.1, meta, event);
         }
         #[inline]
+        fn on_go_away_received(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::GoAwayReceived,
+        ) {
+            (self.0).on_go_away_received(&mut context.0, meta, event);
+            (self.1).on_go_away_received(&mut context.1, meta, event);
+        }
+        #[inline]
+        fn on_stream_limit_pressure(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::StreamLimitPressure,
+        ) {
+            (self.0).on_stream_limit_pressure(&mut context.0, meta, event);
+            (self.1).on_stream_limit_pressure(&mut context.1, meta, event);
+        }
+        #[inline]
+        fn on_connection_data_pressure(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::ConnectionDataPressure,
+        ) {
+            (self.0).on_connection_data_pressure(&mut context.0, meta, event);
+            (self.1).on_connection_data_pressure(&mut context.1, meta, event);
+        }
+        #[inline]
+        fn on_stream_open_blocked(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::StreamOpenBlocked,
+        ) {
+            (self.0).on_stream_open_blocked(&mut context.0, meta, event);
+            (self.1).on_stream_open_blocked(&mut context.1, meta, event);
+        }
+        #[inline]
+        fn on_connection_hibernated(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::ConnectionHibernated,
+        ) {
+            (self.0).on_connection_hibernated(&mut context.0, meta, event);
+            (self.1).on_connection_hibernated(&mut context.1, meta, event);
+        }
+        #[inline]
+        fn on_datagram_frame_dropped(
+            &mut self,
+            context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::DatagramFrameDropped,
+        ) {
+            (self.0).on_datagram_frame_dropped(&mut context.0, meta, event);
+            (self.1).on_datagram_frame_dropped(&mut context.1, meta, event);
+        }
+        #[inline]
         fn on_connection_closed(
             &mut self,
             context: &mut Self::ConnectionContext,
@@ -8080,6 +9985,15 @@ mod traits {
             (self.1).on_platform_event_loop_started(meta, event);
         }
         #[inline]
+        fn on_platform_event_loop_profile(
+            &mut self,
+            meta: &api::EndpointMeta,
+            event: &api::PlatformEventLoopProfile,
+        ) {
+            (self.0).on_platform_event_loop_profile(meta, event);
+            (self.1).on_platform_event_loop_profile(meta, event);
+        }
+        #[inline]
         fn on_event<M: Meta, E: Event>(&mut self, meta: &M, event: &E) {
             self.0.on_event(meta, event);
             self.1.on_event(meta, event);
@@ -8149,6 +10063,8 @@ mod traits {
         fn on_platform_event_loop_sleep(&mut self, event: builder::PlatformEventLoopSleep);
         #[doc = "Publishes a `PlatformEventLoopStarted` event to the publisher's subscriber"]
         fn on_platform_event_loop_started(&mut self, event: builder::PlatformEventLoopStarted);
+        #[doc = "Publishes a `PlatformEventLoopProfile` event to the publisher's subscriber"]
+        fn on_platform_event_loop_profile(&mut self, event: builder::PlatformEventLoopProfile);
         #[doc = r" Returns the QUIC version, if any"]
         fn quic_version(&self) -> Option<u32>;
     }
@@ -8283,6 +10199,13 @@ mod traits {
             self.subscriber.on_event(&self.meta, &event);
         }
         #[inline]
+        fn on_platform_event_loop_profile(&mut self, event: builder::PlatformEventLoopProfile) {
+            let event = event.into_event();
+            self.subscriber
+                .on_platform_event_loop_profile(&self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
         fn quic_version(&self) -> Option<u32> {
             self.quic_version
         }
@@ -8330,6 +10253,8 @@ mod traits {
         fn on_ack_range_sent(&mut self, event: builder::AckRangeSent);
         #[doc = "Publishes a `PacketDropped` event to the publisher's subscriber"]
         fn on_packet_dropped(&mut self, event: builder::PacketDropped);
+        #[doc = "Publishes a `PacketBudgetExceeded` event to the publisher's subscriber"]
+        fn on_packet_budget_exceeded(&mut self, event: builder::PacketBudgetExceeded);
         #[doc = "Publishes a `KeyUpdate` event to the publisher's subscriber"]
         fn on_key_update(&mut self, event: builder::KeyUpdate);
         #[doc = "Publishes a `KeySpaceDiscarded` event to the publisher's subscriber"]
@@ -8352,6 +10277,11 @@ mod traits {
         fn on_ecn_state_changed(&mut self, event: builder::EcnStateChanged);
         #[doc = "Publishes a `ConnectionMigrationDenied` event to the publisher's subscriber"]
         fn on_connection_migration_denied(&mut self, event: builder::ConnectionMigrationDenied);
+        #[doc = "Publishes a `CompatibilityRelaxationApplied` event to the publisher's subscriber"]
+        fn on_compatibility_relaxation_applied(
+            &mut self,
+            event: builder::CompatibilityRelaxationApplied,
+        );
         #[doc = "Publishes a `HandshakeStatusUpdated` event to the publisher's subscriber"]
         fn on_handshake_status_updated(&mut self, event: builder::HandshakeStatusUpdated);
         #[doc = "Publishes a `TlsExporterReady` event to the publisher's subscriber"]
@@ -8362,6 +10292,8 @@ mod traits {
         fn on_tls_client_hello(&mut self, event: builder::TlsClientHello);
         #[doc = "Publishes a `TlsServerHello` event to the publisher's subscriber"]
         fn on_tls_server_hello(&mut self, event: builder::TlsServerHello);
+        #[doc = "Publishes a `TlsOperationCompleted` event to the publisher's subscriber"]
+        fn on_tls_operation_completed(&mut self, event: builder::TlsOperationCompleted);
         #[doc = "Publishes a `RxStreamProgress` event to the publisher's subscriber"]
         fn on_rx_stream_progress(&mut self, event: builder::RxStreamProgress);
         #[doc = "Publishes a `TxStreamProgress` event to the publisher's subscriber"]
@@ -8380,6 +10312,18 @@ mod traits {
         fn on_bbr_state_changed(&mut self, event: builder::BbrStateChanged);
         #[doc = "Publishes a `DcStateChanged` event to the publisher's subscriber"]
         fn on_dc_state_changed(&mut self, event: builder::DcStateChanged);
+        #[doc = "Publishes a `GoAwayReceived` event to the publisher's subscriber"]
+        fn on_go_away_received(&mut self, event: builder::GoAwayReceived);
+        #[doc = "Publishes a `StreamLimitPressure` event to the publisher's subscriber"]
+        fn on_stream_limit_pressure(&mut self, event: builder::StreamLimitPressure);
+        #[doc = "Publishes a `ConnectionDataPressure` event to the publisher's subscriber"]
+        fn on_connection_data_pressure(&mut self, event: builder::ConnectionDataPressure);
+        #[doc = "Publishes a `StreamOpenBlocked` event to the publisher's subscriber"]
+        fn on_stream_open_blocked(&mut self, event: builder::StreamOpenBlocked);
+        #[doc = "Publishes a `ConnectionHibernated` event to the publisher's subscriber"]
+        fn on_connection_hibernated(&mut self, event: builder::ConnectionHibernated);
+        #[doc = "Publishes a `DatagramFrameDropped` event to the publisher's subscriber"]
+        fn on_datagram_frame_dropped(&mut self, event: builder::DatagramFrameDropped);
         #[doc = "Publishes a `ConnectionClosed` event to the publisher's subscriber"]
         fn on_connection_closed(&mut self, event: builder::ConnectionClosed);
         #[doc = r" Returns the QUIC version negotiated for the current connection, if any"]
@@ -8588,6 +10532,15 @@ mod traits {
             self.subscriber.on_event(&self.meta, &event);
         }
         #[inline]
+        fn on_packet_budget_exceeded(&mut self, event: builder::PacketBudgetExceeded) {
+            let event = event.into_event();
+            self.subscriber
+                .on_packet_budget_exceeded(self.context, &self.meta, &event);
+            self.subscriber
+                .on_connection_event(self.context, &self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
         fn on_key_update(&mut self, event: builder::KeyUpdate) {
             let event = event.into_event();
             self.subscriber
@@ -8690,6 +10643,18 @@ mod traits {
             self.subscriber.on_event(&self.meta, &event);
         }
         #[inline]
+        fn on_compatibility_relaxation_applied(
+            &mut self,
+            event: builder::CompatibilityRelaxationApplied,
+        ) {
+            let event = event.into_event();
+            self.subscriber
+                .on_compatibility_relaxation_applied(self.context, &self.meta, &event);
+            self.subscriber
+                .on_connection_event(self.context, &self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
         fn on_handshake_status_updated(&mut self, event: builder::HandshakeStatusUpdated) {
             let event = event.into_event();
             self.subscriber
@@ -8735,6 +10700,15 @@ mod traits {
             self.subscriber.on_event(&self.meta, &event);
         }
         #[inline]
+        fn on_tls_operation_completed(&mut self, event: builder::TlsOperationCompleted) {
+            let event = event.into_event();
+            self.subscriber
+                .on_tls_operation_completed(self.context, &self.meta, &event);
+            self.subscriber
+                .on_connection_event(self.context, &self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
         fn on_rx_stream_progress(&mut self, event: builder::RxStreamProgress) {
             let event = event.into_event();
             self.subscriber
@@ -8816,6 +10790,60 @@ mod traits {
             self.subscriber.on_event(&self.meta, &event);
         }
         #[inline]
+        fn on_go_away_received(&mut self, event: builder::GoAwayReceived) {
+            let event = event.into_event();
+            self.subscriber
+                .on_go_away_received(self.context, &self.meta, &event);
+            self.subscriber
+                .on_connection_event(self.context, &self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
+        fn on_stream_limit_pressure(&mut self, event: builder::StreamLimitPressure) {
+            let event = event.into_event();
+            self.subscriber
+                .on_stream_limit_pressure(self.context, &self.meta, &event);
+            self.subscriber
+                .on_connection_event(self.context, &self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
+        fn on_connection_data_pressure(&mut self, event: builder::ConnectionDataPressure) {
+            let event = event.into_event();
+            self.subscriber
+                .on_connection_data_pressure(self.context, &self.meta, &event);
+            self.subscriber
+                .on_connection_event(self.context, &self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
+        fn on_stream_open_blocked(&mut self, event: builder::StreamOpenBlocked) {
+            let event = event.into_event();
+            self.subscriber
+                .on_stream_open_blocked(self.context, &self.meta, &event);
+            self.subscriber
+                .on_connection_event(self.context, &self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
+        fn on_connection_hibernated(&mut self, event: builder::ConnectionHibernated) {
+            let event = event.into_event();
+            self.subscriber
+                .on_connection_hibernated(self.context, &self.meta, &event);
+            self.subscriber
+                .on_connection_event(self.context, &self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
+        fn on_datagram_frame_dropped(&mut self, event: builder::DatagramFrameDropped) {
+            let event = event.into_event();
+            self.subscriber
+                .on_datagram_frame_dropped(self.context, &self.meta, &event);
+            self.subscriber
+                .on_connection_event(self.context, &self.meta, &event);
+            self.subscriber.on_event(&self.meta, &event);
+        }
+        #[inline]
         fn on_connection_closed(&mut self, event: builder::ConnectionClosed) {
             let event = event.into_event();
             self.subscriber
@@ -8858,6 +10886,7 @@ pub mod testing {
             pub platform_event_loop_wakeup: u64,
             pub platform_event_loop_sleep: u64,
             pub platform_event_loop_started: u64,
+            pub platform_event_loop_profile: u64,
         }
         impl Drop for Subscriber {
             fn drop(&mut self) {
@@ -8904,6 +10933,7 @@ pub mod testing {
                     platform_event_loop_wakeup: 0,
                     platform_event_loop_sleep: 0,
                     platform_event_loop_started: 0,
+                    platform_event_loop_profile: 0,
                 }
             }
         }
@@ -9072,6 +11102,17 @@ pub mod testing {
                 let out = format!("{meta:?} {event:?}");
                 self.output.push(out);
             }
+            fn on_platform_event_loop_profile(
+                &mut self,
+                meta: &api::EndpointMeta,
+                event: &api::PlatformEventLoopProfile,
+            ) {
+                self.platform_event_loop_profile += 1;
+                let meta = crate::event::snapshot::Fmt::to_snapshot(meta);
+                let event = crate::event::snapshot::Fmt::to_snapshot(event);
+                let out = format!("{meta:?} {event:?}");
+                self.output.push(out);
+            }
         }
     }
     #[derive(Debug)]
@@ -9096,6 +11137,7 @@ pub mod testing {
         pub ack_range_received: u64,
         pub ack_range_sent: u64,
         pub packet_dropped: u64,
+        pub packet_budget_exceeded: u64,
         pub key_update: u64,
         pub key_space_discarded: u64,
         pub connection_started: u64,
@@ -9107,11 +11149,13 @@ pub mod testing {
         pub connection_id_updated: u64,
         pub ecn_state_changed: u64,
         pub connection_migration_denied: u64,
+        pub compatibility_relaxation_applied: u64,
         pub handshake_status_updated: u64,
         pub tls_exporter_ready: u64,
         pub path_challenge_updated: u64,
         pub tls_client_hello: u64,
         pub tls_server_hello: u64,
+        pub tls_operation_completed: u64,
         pub rx_stream_progress: u64,
         pub tx_stream_progress: u64,
         pub keep_alive_timer_expired: u64,
@@ -9121,6 +11165,12 @@ pub mod testing {
         pub pacing_rate_updated: u64,
         pub bbr_state_changed: u64,
         pub dc_state_changed: u64,
+        pub go_away_received: u64,
+        pub stream_limit_pressure: u64,
+        pub connection_data_pressure: u64,
+        pub stream_open_blocked: u64,
+        pub connection_hibernated: u64,
+        pub datagram_frame_dropped: u64,
         pub connection_closed: u64,
         pub version_information: u64,
         pub endpoint_packet_sent: u64,
@@ -9137,6 +11187,7 @@ pub mod testing {
         pub platform_event_loop_wakeup: u64,
         pub platform_event_loop_sleep: u64,
         pub platform_event_loop_started: u64,
+        pub platform_event_loop_profile: u64,
     }
     impl Drop for Subscriber {
         fn drop(&mut self) {
@@ -9186,6 +11237,7 @@ pub mod testing {
                 ack_range_received: 0,
                 ack_range_sent: 0,
                 packet_dropped: 0,
+                packet_budget_exceeded: 0,
                 key_update: 0,
                 key_space_discarded: 0,
                 connection_started: 0,
@@ -9197,11 +11249,13 @@ pub mod testing {
                 connection_id_updated: 0,
                 ecn_state_changed: 0,
                 connection_migration_denied: 0,
+                compatibility_relaxation_applied: 0,
                 handshake_status_updated: 0,
                 tls_exporter_ready: 0,
                 path_challenge_updated: 0,
                 tls_client_hello: 0,
                 tls_server_hello: 0,
+                tls_operation_completed: 0,
                 rx_stream_progress: 0,
                 tx_stream_progress: 0,
                 keep_alive_timer_expired: 0,
@@ -9211,6 +11265,12 @@ pub mod testing {
                 pacing_rate_updated: 0,
                 bbr_state_changed: 0,
                 dc_state_changed: 0,
+                go_away_received: 0,
+                stream_limit_pressure: 0,
+                connection_data_pressure: 0,
+                stream_open_blocked: 0,
+                connection_hibernated: 0,
+                datagram_frame_dropped: 0,
                 connection_closed: 0,
                 version_information: 0,
                 endpoint_packet_sent: 0,
@@ -9227,6 +11287,7 @@ pub mod testing {
                 platform_event_loop_wakeup: 0,
                 platform_event_loop_sleep: 0,
                 platform_event_loop_started: 0,
+                platform_event_loop_profile: 0,
             }
         }
     }
@@ -9491,6 +11552,20 @@ pub mod testing {
                 self.output.push(out);
             }
         }
+        fn on_packet_budget_exceeded(
+            &mut self,
+            _context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::PacketBudgetExceeded,
+        ) {
+            self.packet_budget_exceeded += 1;
+            if self.location.is_some() {
+                let meta = crate::event::snapshot::Fmt::to_snapshot(meta);
+                let event = crate::event::snapshot::Fmt::to_snapshot(event);
+                let out = format!("{meta:?} {event:?}");
+                self.output.push(out);
+            }
+        }
         fn on_key_update(
             &mut self,
             _context: &mut Self::ConnectionContext,
@@ -9645,6 +11720,20 @@ pub mod testing {
                 self.output.push(out);
             }
         }
+        fn on_compatibility_relaxation_applied(
+            &mut self,
+            _context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::CompatibilityRelaxationApplied,
+        ) {
+            self.compatibility_relaxation_applied += 1;
+            if self.location.is_some() {
+                let meta = crate::event::snapshot::Fmt::to_snapshot(meta);
+                let event = crate::event::snapshot::Fmt::to_snapshot(event);
+                let out = format!("{meta:?} {event:?}");
+                self.output.push(out);
+            }
+        }
         fn on_handshake_status_updated(
             &mut self,
             _context: &mut Self::ConnectionContext,
@@ -9715,6 +11804,20 @@ pub mod testing {
                 self.output.push(out);
             }
         }
+        fn on_tls_operation_completed(
+            &mut self,
+            _context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::TlsOperationCompleted,
+        ) {
+            self.tls_operation_completed += 1;
+            if self.location.is_some() {
+                let meta = crate::event::snapshot::Fmt::to_snapshot(meta);
+                let event = crate::event::snapshot::Fmt::to_snapshot(event);
+                let out = format!("{meta:?} {event:?}");
+                self.output.push(out);
+            }
+        }
         fn on_rx_stream_progress(
             &mut self,
             _context: &mut Self::ConnectionContext,
@@ -9841,6 +11944,90 @@ pub mod testing {
                 self.output.push(out);
             }
         }
+        fn on_go_away_received(
+            &mut self,
+            _context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::GoAwayReceived,
+        ) {
+            self.go_away_received += 1;
+            if self.location.is_some() {
+                let meta = crate::event::snapshot::Fmt::to_snapshot(meta);
+                let event = crate::event::snapshot::Fmt::to_snapshot(event);
+                let out = format!("{meta:?} {event:?}");
+                self.output.push(out);
+            }
+        }
+        fn on_stream_limit_pressure(
+            &mut self,
+            _context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::StreamLimitPressure,
+        ) {
+            self.stream_limit_pressure += 1;
+            if self.location.is_some() {
+                let meta = crate::event::snapshot::Fmt::to_snapshot(meta);
+                let event = crate::event::snapshot::Fmt::to_snapshot(event);
+                let out = format!("{meta:?} {event:?}");
+                self.output.push(out);
+            }
+        }
+        fn on_connection_data_pressure(
+            &mut self,
+            _context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::ConnectionDataPressure,
+        ) {
+            self.connection_data_pressure += 1;
+            if self.location.is_some() {
+                let meta = crate::event::snapshot::Fmt::to_snapshot(meta);
+                let event = crate::event::snapshot::Fmt::to_snapshot(event);
+                let out = format!("{meta:?} {event:?}");
+                self.output.push(out);
+            }
+        }
+        fn on_stream_open_blocked(
+            &mut self,
+            _context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::StreamOpenBlocked,
+        ) {
+            self.stream_open_blocked += 1;
+            if self.location.is_some() {
+                let meta = crate::event::snapshot::Fmt::to_snapshot(meta);
+                let event = crate::event::snapshot::Fmt::to_snapshot(event);
+                let out = format!("{meta:?} {event:?}");
+                self.output.push(out);
+            }
+        }
+        fn on_connection_hibernated(
+            &mut self,
+            _context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::ConnectionHibernated,
+        ) {
+            self.connection_hibernated += 1;
+            if self.location.is_some() {
+                let meta = crate::event::snapshot::Fmt::to_snapshot(meta);
+                let event = crate::event::snapshot::Fmt::to_snapshot(event);
+                let out = format!("{meta:?} {event:?}");
+                self.output.push(out);
+            }
+        }
+        fn on_datagram_frame_dropped(
+            &mut self,
+            _context: &mut Self::ConnectionContext,
+            meta: &api::ConnectionMeta,
+            event: &api::DatagramFrameDropped,
+        ) {
+            self.datagram_frame_dropped += 1;
+            if self.location.is_some() {
+                let meta = crate::event::snapshot::Fmt::to_snapshot(meta);
+                let event = crate::event::snapshot::Fmt::to_snapshot(event);
+                let out = format!("{meta:?} {event:?}");
+                self.output.push(out);
+            }
+        }
         fn on_connection_closed(
             &mut self,
             _context: &mut Self::ConnectionContext,
@@ -10004,6 +12191,17 @@ pub mod testing {
             let out = format!("{meta:?} {event:?}");
             self.output.push(out);
         }
+        fn on_platform_event_loop_profile(
+            &mut self,
+            meta: &api::EndpointMeta,
+            event: &api::PlatformEventLoopProfile,
+        ) {
+            self.platform_event_loop_profile += 1;
+            let meta = crate::event::snapshot::Fmt::to_snapshot(meta);
+            let event = crate::event::snapshot::Fmt::to_snapshot(event);
+            let out = format!("{meta:?} {event:?}");
+            self.output.push(out);
+        }
     }
     #[derive(Debug)]
     pub struct Publisher {
@@ -10027,6 +12225,7 @@ pub mod testing {
         pub ack_range_received: u64,
         pub ack_range_sent: u64,
         pub packet_dropped: u64,
+        pub packet_budget_exceeded: u64,
         pub key_update: u64,
         pub key_space_discarded: u64,
         pub connection_started: u64,
@@ -10038,11 +12237,13 @@ pub mod testing {
         pub connection_id_updated: u64,
         pub ecn_state_changed: u64,
         pub connection_migration_denied: u64,
+        pub compatibility_relaxation_applied: u64,
         pub handshake_status_updated: u64,
         pub tls_exporter_ready: u64,
         pub path_challenge_updated: u64,
         pub tls_client_hello: u64,
         pub tls_server_hello: u64,
+        pub tls_operation_completed: u64,
         pub rx_stream_progress: u64,
         pub tx_stream_progress: u64,
         pub keep_alive_timer_expired: u64,
@@ -10052,6 +12253,12 @@ pub mod testing {
         pub pacing_rate_updated: u64,
         pub bbr_state_changed: u64,
         pub dc_state_changed: u64,
+        pub go_away_received: u64,
+        pub stream_limit_pressure: u64,
+        pub connection_data_pressure: u64,
+        pub stream_open_blocked: u64,
+        pub connection_hibernated: u64,
+        pub datagram_frame_dropped: u64,
         pub connection_closed: u64,
         pub version_information: u64,
         pub endpoint_packet_sent: u64,
@@ -10068,6 +12275,7 @@ pub mod testing {
         pub platform_event_loop_wakeup: u64,
         pub platform_event_loop_sleep: u64,
         pub platform_event_loop_started: u64,
+        pub platform_event_loop_profile: u64,
     }
     impl Publisher {
         #[doc = r" Creates a publisher with snapshot assertions enabled"]
@@ -10107,6 +12315,7 @@ pub mod testing {
                 ack_range_received: 0,
                 ack_range_sent: 0,
                 packet_dropped: 0,
+                packet_budget_exceeded: 0,
                 key_update: 0,
                 key_space_discarded: 0,
                 connection_started: 0,
@@ -10118,11 +12327,13 @@ pub mod testing {
                 connection_id_updated: 0,
                 ecn_state_changed: 0,
                 connection_migration_denied: 0,
+                compatibility_relaxation_applied: 0,
                 handshake_status_updated: 0,
                 tls_exporter_ready: 0,
                 path_challenge_updated: 0,
                 tls_client_hello: 0,
                 tls_server_hello: 0,
+                tls_operation_completed: 0,
                 rx_stream_progress: 0,
                 tx_stream_progress: 0,
                 keep_alive_timer_expired: 0,
@@ -10132,6 +12343,12 @@ pub mod testing {
                 pacing_rate_updated: 0,
                 bbr_state_changed: 0,
                 dc_state_changed: 0,
+                go_away_received: 0,
+                stream_limit_pressure: 0,
+                connection_data_pressure: 0,
+                stream_open_blocked: 0,
+                connection_hibernated: 0,
+                datagram_frame_dropped: 0,
                 connection_closed: 0,
                 version_information: 0,
                 endpoint_packet_sent: 0,
@@ -10148,6 +12365,7 @@ pub mod testing {
                 platform_event_loop_wakeup: 0,
                 platform_event_loop_sleep: 0,
                 platform_event_loop_started: 0,
+                platform_event_loop_profile: 0,
             }
         }
     }
@@ -10260,6 +12478,13 @@ pub mod testing {
             let out = format!("{event:?}");
             self.output.push(out);
         }
+        fn on_platform_event_loop_profile(&mut self, event: builder::PlatformEventLoopProfile) {
+            self.platform_event_loop_profile += 1;
+            let event = event.into_event();
+            let event = crate::event::snapshot::Fmt::to_snapshot(&event);
+            let out = format!("{event:?}");
+            self.output.push(out);
+        }
         fn quic_version(&self) -> Option<u32> {
             Some(1)
         }
@@ -10434,6 +12659,15 @@ pub mod testing {
                 self.output.push(out);
             }
         }
+        fn on_packet_budget_exceeded(&mut self, event: builder::PacketBudgetExceeded) {
+            self.packet_budget_exceeded += 1;
+            let event = event.into_event();
+            if self.location.is_some() {
+                let event = crate::event::snapshot::Fmt::to_snapshot(&event);
+                let out = format!("{event:?}");
+                self.output.push(out);
+            }
+        }
         fn on_key_update(&mut self, event: builder::KeyUpdate) {
             self.key_update += 1;
             let event = event.into_event();
@@ -10536,6 +12770,18 @@ pub mod testing {
                 self.output.push(out);
             }
         }
+        fn on_compatibility_relaxation_applied(
+            &mut self,
+            event: builder::CompatibilityRelaxationApplied,
+        ) {
+            self.compatibility_relaxation_applied += 1;
+            let event = event.into_event();
+            if self.location.is_some() {
+                let event = crate::event::snapshot::Fmt::to_snapshot(&event);
+                let out = format!("{event:?}");
+                self.output.push(out);
+            }
+        }
         fn on_handshake_status_updated(&mut self, event: builder::HandshakeStatusUpdated) {
             self.handshake_status_updated += 1;
             let event = event.into_event();
@@ -10581,6 +12827,15 @@ pub mod testing {
                 self.output.push(out);
             }
         }
+        fn on_tls_operation_completed(&mut self, event: builder::TlsOperationCompleted) {
+            self.tls_operation_completed += 1;
+            let event = event.into_event();
+            if self.location.is_some() {
+                let event = crate::event::snapshot::Fmt::to_snapshot(&event);
+                let out = format!("{event:?}");
+                self.output.push(out);
+            }
+        }
         fn on_rx_stream_progress(&mut self, event: builder::RxStreamProgress) {
             self.rx_stream_progress += 1;
             let event = event.into_event();
@@ -10662,6 +12917,60 @@ pub mod testing {
                 self.output.push(out);
             }
         }
+        fn on_go_away_received(&mut self, event: builder::GoAwayReceived) {
+            self.go_away_received += 1;
+            let event = event.into_event();
+            if self.location.is_some() {
+                let event = crate::event::snapshot::Fmt::to_snapshot(&event);
+                let out = format!("{event:?}");
+                self.output.push(out);
+            }
+        }
+        fn on_stream_limit_pressure(&mut self, event: builder::StreamLimitPressure) {
+            self.stream_limit_pressure += 1;
+            let event = event.into_event();
+            if self.location.is_some() {
+                let event = crate::event::snapshot::Fmt::to_snapshot(&event);
+                let out = format!("{event:?}");
+                self.output.push(out);
+            }
+        }
+        fn on_connection_data_pressure(&mut self, event: builder::ConnectionDataPressure) {
+            self.connection_data_pressure += 1;
+            let event = event.into_event();
+            if self.location.is_some() {
+                let event = crate::event::snapshot::Fmt::to_snapshot(&event);
+                let out = format!("{event:?}");
+                self.output.push(out);
+            }
+        }
+        fn on_stream_open_blocked(&mut self, event: builder::StreamOpenBlocked) {
+            self.stream_open_blocked += 1;
+            let event = event.into_event();
+            if self.location.is_some() {
+                let event = crate::event::snapshot::Fmt::to_snapshot(&event);
+                let out = format!("{event:?}");
+                self.output.push(out);
+            }
+        }
+        fn on_connection_hibernated(&mut self, event: builder::ConnectionHibernated) {
+            self.connection_hibernated += 1;
+            let event = event.into_event();
+            if self.location.is_some() {
+                let event = crate::event::snapshot::Fmt::to_snapshot(&event);
+                let out = format!("{event:?}");
+                self.output.push(out);
+            }
+        }
+        fn on_datagram_frame_dropped(&mut self, event: builder::DatagramFrameDropped) {
+            self.datagram_frame_dropped += 1;
+            let event = event.into_event();
+            if self.location.is_some() {
+                let event = crate::event::snapshot::Fmt::to_snapshot(&event);
+                let out = format!("{event:?}");
+                self.output.push(out);
+            }
+        }
         fn on_connection_closed(&mut self, event: builder::ConnectionClosed) {
             self.connection_closed += 1;
             let event = event.into_event();
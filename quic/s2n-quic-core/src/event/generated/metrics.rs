@@ -6,6 +6,7 @@
 // changes should be made there.
 
 use crate::event::{self, api, metrics::Recorder};
+use crate::query;
 #[cfg(feature = "alloc")]
 pub(crate) mod aggregate;
 pub(crate) mod probe;
@@ -44,6 +45,7 @@ pub struct Context<R: Recorder> {
     ack_range_received: u64,
     ack_range_sent: u64,
     packet_dropped: u64,
+    packet_budget_exceeded: u64,
     key_update: u64,
     key_space_discarded: u64,
     connection_started: u64,
@@ -55,11 +57,13 @@ pub struct Context<R: Recorder> {
     connection_id_updated: u64,
     ecn_state_changed: u64,
     connection_migration_denied: u64,
+    compatibility_relaxation_applied: u64,
     handshake_status_updated: u64,
     tls_exporter_ready: u64,
     path_challenge_updated: u64,
     tls_client_hello: u64,
     tls_server_hello: u64,
+    tls_operation_completed: u64,
     rx_stream_progress: u64,
     tx_stream_progress: u64,
     keep_alive_timer_expired: u64,
@@ -69,6 +73,12 @@ pub struct Context<R: Recorder> {
     pacing_rate_updated: u64,
     bbr_state_changed: u64,
     dc_state_changed: u64,
+    go_away_received: u64,
+    stream_limit_pressure: u64,
+    connection_data_pressure: u64,
+    stream_open_blocked: u64,
+    connection_hibernated: u64,
+    datagram_frame_dropped: u64,
     connection_closed: u64,
 }
 impl<S: event::Subscriber> event::Subscriber for Subscriber<S>
@@ -101,6 +111,7 @@ where
             ack_range_received: 0,
             ack_range_sent: 0,
             packet_dropped: 0,
+            packet_budget_exceeded: 0,
             key_update: 0,
             key_space_discarded: 0,
             connection_started: 0,
@@ -112,11 +123,13 @@ where
             connection_id_updated: 0,
             ecn_state_changed: 0,
             connection_migration_denied: 0,
+            compatibility_relaxation_applied: 0,
             handshake_status_updated: 0,
             tls_exporter_ready: 0,
             path_challenge_updated: 0,
             tls_client_hello: 0,
             tls_server_hello: 0,
+            tls_operation_completed: 0,
             rx_stream_progress: 0,
             tx_stream_progress: 0,
             keep_alive_timer_expired: 0,
@@ -126,6 +139,12 @@ where
             pacing_rate_updated: 0,
             bbr_state_changed: 0,
             dc_state_changed: 0,
+            go_away_received: 0,
+            stream_limit_pressure: 0,
+            connection_data_pressure: 0,
+            stream_open_blocked: 0,
+            connection_hibernated: 0,
+            datagram_frame_dropped: 0,
             connection_closed: 0,
         }
     }
@@ -329,6 +348,17 @@ where
             .on_packet_dropped(&mut context.recorder, meta, event);
     }
     #[inline]
+    fn on_packet_budget_exceeded(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::PacketBudgetExceeded,
+    ) {
+        context.packet_budget_exceeded += 1;
+        self.subscriber
+            .on_packet_budget_exceeded(&mut context.recorder, meta, event);
+    }
+    #[inline]
     fn on_key_update(
         &mut self,
         context: &mut Self::ConnectionContext,
@@ -450,6 +480,17 @@ where
             .on_connection_migration_denied(&mut context.recorder, meta, event);
     }
     #[inline]
+    fn on_compatibility_relaxation_applied(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::CompatibilityRelaxationApplied,
+    ) {
+        context.compatibility_relaxation_applied += 1;
+        self.subscriber
+            .on_compatibility_relaxation_applied(&mut context.recorder, meta, event);
+    }
+    #[inline]
     fn on_handshake_status_updated(
         &mut self,
         context: &mut Self::ConnectionContext,
@@ -505,6 +546,17 @@ where
             .on_tls_server_hello(&mut context.recorder, meta, event);
     }
     #[inline]
+    fn on_tls_operation_completed(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::TlsOperationCompleted,
+    ) {
+        context.tls_operation_completed += 1;
+        self.subscriber
+            .on_tls_operation_completed(&mut context.recorder, meta, event);
+    }
+    #[inline]
     fn on_rx_stream_progress(
         &mut self,
         context: &mut Self::ConnectionContext,
@@ -604,6 +656,72 @@ where
             .on_dc_state_changed(&mut context.recorder, meta, event);
     }
     #[inline]
+    fn on_go_away_received(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::GoAwayReceived,
+    ) {
+        context.go_away_received += 1;
+        self.subscriber
+            .on_go_away_received(&mut context.recorder, meta, event);
+    }
+    #[inline]
+    fn on_stream_limit_pressure(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::StreamLimitPressure,
+    ) {
+        context.stream_limit_pressure += 1;
+        self.subscriber
+            .on_stream_limit_pressure(&mut context.recorder, meta, event);
+    }
+    #[inline]
+    fn on_connection_data_pressure(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::ConnectionDataPressure,
+    ) {
+        context.connection_data_pressure += 1;
+        self.subscriber
+            .on_connection_data_pressure(&mut context.recorder, meta, event);
+    }
+    #[inline]
+    fn on_stream_open_blocked(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::StreamOpenBlocked,
+    ) {
+        context.stream_open_blocked += 1;
+        self.subscriber
+            .on_stream_open_blocked(&mut context.recorder, meta, event);
+    }
+    #[inline]
+    fn on_connection_hibernated(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::ConnectionHibernated,
+    ) {
+        context.connection_hibernated += 1;
+        self.subscriber
+            .on_connection_hibernated(&mut context.recorder, meta, event);
+    }
+    #[inline]
+    fn on_datagram_frame_dropped(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::DatagramFrameDropped,
+    ) {
+        context.datagram_frame_dropped += 1;
+        self.subscriber
+            .on_datagram_frame_dropped(&mut context.recorder, meta, event);
+    }
+    #[inline]
     fn on_connection_closed(
         &mut self,
         context: &mut Self::ConnectionContext,
@@ -614,6 +732,24 @@ where
         self.subscriber
             .on_connection_closed(&mut context.recorder, meta, event);
     }
+    #[inline]
+    fn query(
+        context: &Self::ConnectionContext,
+        query: &mut dyn query::Query,
+    ) -> query::ControlFlow {
+        query
+            .execute(context)
+            .and_then(|| S::query(&context.recorder, query))
+    }
+    #[inline]
+    fn query_mut(
+        context: &mut Self::ConnectionContext,
+        query: &mut dyn query::QueryMut,
+    ) -> query::ControlFlow {
+        query
+            .execute_mut(context)
+            .and_then(|| S::query_mut(&mut context.recorder, query))
+    }
 }
 impl<R: Recorder> Drop for Context<R> {
     fn drop(&mut self) {
@@ -657,6 +793,8 @@ impl<R: Recorder> Drop for Context<R> {
             .increment_counter("ack_range_sent", self.ack_range_sent as _);
         self.recorder
             .increment_counter("packet_dropped", self.packet_dropped as _);
+        self.recorder
+            .increment_counter("packet_budget_exceeded", self.packet_budget_exceeded as _);
         self.recorder
             .increment_counter("key_update", self.key_update as _);
         self.recorder
@@ -683,6 +821,10 @@ impl<R: Recorder> Drop for Context<R> {
             "connection_migration_denied",
             self.connection_migration_denied as _,
         );
+        self.recorder.increment_counter(
+            "compatibility_relaxation_applied",
+            self.compatibility_relaxation_applied as _,
+        );
         self.recorder.increment_counter(
             "handshake_status_updated",
             self.handshake_status_updated as _,
@@ -695,6 +837,8 @@ impl<R: Recorder> Drop for Context<R> {
             .increment_counter("tls_client_hello", self.tls_client_hello as _);
         self.recorder
             .increment_counter("tls_server_hello", self.tls_server_hello as _);
+        self.recorder
+            .increment_counter("tls_operation_completed", self.tls_operation_completed as _);
         self.recorder
             .increment_counter("rx_stream_progress", self.rx_stream_progress as _);
         self.recorder
@@ -715,6 +859,20 @@ impl<R: Recorder> Drop for Context<R> {
             .increment_counter("bbr_state_changed", self.bbr_state_changed as _);
         self.recorder
             .increment_counter("dc_state_changed", self.dc_state_changed as _);
+        self.recorder
+            .increment_counter("go_away_received", self.go_away_received as _);
+        self.recorder
+            .increment_counter("stream_limit_pressure", self.stream_limit_pressure as _);
+        self.recorder.increment_counter(
+            "connection_data_pressure",
+            self.connection_data_pressure as _,
+        );
+        self.recorder
+            .increment_counter("stream_open_blocked", self.stream_open_blocked as _);
+        self.recorder
+            .increment_counter("connection_hibernated", self.connection_hibernated as _);
+        self.recorder
+            .increment_counter("datagram_frame_dropped", self.datagram_frame_dropped as _);
         self.recorder
             .increment_counter("connection_closed", self.connection_closed as _);
     }
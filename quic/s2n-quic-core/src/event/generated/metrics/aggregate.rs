@@ -13,7 +13,7 @@ use crate::event::{
     },
 };
 use alloc::{boxed::Box, vec::Vec};
-static INFO: &[Info; 161usize] = &[
+static INFO: &[Info; 183usize] = &[
     info::Builder {
         id: 0usize,
         name: Str::new("application_protocol_information\0"),
@@ -274,710 +274,842 @@ static INFO: &[Info; 161usize] = &[
     .build(),
     info::Builder {
         id: 43usize,
-        name: Str::new("key_update\0"),
+        name: Str::new("packet_budget_exceeded\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
         id: 44usize,
-        name: Str::new("key_update.key_type\0"),
+        name: Str::new("packet_budget_exceeded.packet\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
         id: 45usize,
-        name: Str::new("key_update.cipher_suite\0"),
+        name: Str::new("packet_budget_exceeded.budget\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
         id: 46usize,
-        name: Str::new("key_space_discarded\0"),
+        name: Str::new("key_update\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
         id: 47usize,
+        name: Str::new("key_update.key_type\0"),
+        units: Units::None,
+    }
+    .build(),
+    info::Builder {
+        id: 48usize,
+        name: Str::new("key_update.cipher_suite\0"),
+        units: Units::None,
+    }
+    .build(),
+    info::Builder {
+        id: 49usize,
+        name: Str::new("key_space_discarded\0"),
+        units: Units::None,
+    }
+    .build(),
+    info::Builder {
+        id: 50usize,
         name: Str::new("key_space_discarded.initial.latency\0"),
         units: Units::Duration,
     }
     .build(),
     info::Builder {
-        id: 48usize,
+        id: 51usize,
         name: Str::new("key_space_discarded.handshake.latency\0"),
         units: Units::Duration,
     }
     .build(),
     info::Builder {
-        id: 49usize,
+        id: 52usize,
         name: Str::new("key_space_discarded.one_rtt.latency\0"),
         units: Units::Duration,
     }
     .build(),
     info::Builder {
-        id: 50usize,
+        id: 53usize,
         name: Str::new("key_space_discarded.space\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 51usize,
+        id: 54usize,
         name: Str::new("connection_started\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 52usize,
+        id: 55usize,
         name: Str::new("duplicate_packet\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 53usize,
+        id: 56usize,
         name: Str::new("duplicate_packet.kind\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 54usize,
+        id: 57usize,
         name: Str::new("duplicate_packet.error\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 55usize,
+        id: 58usize,
         name: Str::new("transport_parameters_received\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 56usize,
+        id: 59usize,
         name: Str::new("transport_parameters_received.latency\0"),
         units: Units::Duration,
     }
     .build(),
     info::Builder {
-        id: 57usize,
+        id: 60usize,
         name: Str::new("datagram_sent\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 58usize,
+        id: 61usize,
         name: Str::new("datagram_sent.bytes.total\0"),
         units: Units::Bytes,
     }
     .build(),
     info::Builder {
-        id: 59usize,
+        id: 62usize,
         name: Str::new("datagram_sent.bytes\0"),
         units: Units::Bytes,
     }
     .build(),
     info::Builder {
-        id: 60usize,
+        id: 63usize,
         name: Str::new("datagram_sent.gso_offset\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 61usize,
+        id: 64usize,
         name: Str::new("datagram_received\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 62usize,
+        id: 65usize,
         name: Str::new("datagram_received.bytes.total\0"),
         units: Units::Bytes,
     }
     .build(),
     info::Builder {
-        id: 63usize,
+        id: 66usize,
         name: Str::new("datagram_received.bytes\0"),
         units: Units::Bytes,
     }
     .build(),
     info::Builder {
-        id: 64usize,
+        id: 67usize,
         name: Str::new("datagram_dropped\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 65usize,
+        id: 68usize,
         name: Str::new("datagram_dropped.bytes.total\0"),
         units: Units::Bytes,
     }
     .build(),
     info::Builder {
-        id: 66usize,
+        id: 69usize,
         name: Str::new("datagram_dropped.bytes\0"),
         units: Units::Bytes,
     }
     .build(),
     info::Builder {
-        id: 67usize,
+        id: 70usize,
         name: Str::new("datagram_dropped.reason\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 68usize,
+        id: 71usize,
         name: Str::new("connection_id_updated\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 69usize,
+        id: 72usize,
         name: Str::new("ecn_state_changed\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 70usize,
+        id: 73usize,
         name: Str::new("ecn_state_changed.state\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 71usize,
+        id: 74usize,
         name: Str::new("connection_migration_denied\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 72usize,
+        id: 75usize,
         name: Str::new("connection_migration_denied.reason\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 73usize,
+        id: 76usize,
+        name: Str::new("compatibility_relaxation_applied\0"),
+        units: Units::None,
+    }
+    .build(),
+    info::Builder {
+        id: 77usize,
+        name: Str::new("compatibility_relaxation_applied.relaxation\0"),
+        units: Units::None,
+    }
+    .build(),
+    info::Builder {
+        id: 78usize,
         name: Str::new("handshake_status_updated\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 74usize,
+        id: 79usize,
         name: Str::new("handshake_status_updated.complete.latency\0"),
         units: Units::Duration,
     }
     .build(),
     info::Builder {
-        id: 75usize,
+        id: 80usize,
         name: Str::new("handshake_status_updated.confirmed.latency\0"),
         units: Units::Duration,
     }
     .build(),
     info::Builder {
-        id: 76usize,
+        id: 81usize,
         name: Str::new("handshake_status_updated.handshake_done_acked.latency\0"),
         units: Units::Duration,
     }
     .build(),
     info::Builder {
-        id: 77usize,
+        id: 82usize,
         name: Str::new("handshake_status_updated.status\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 78usize,
+        id: 83usize,
         name: Str::new("tls_exporter_ready\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 79usize,
+        id: 84usize,
         name: Str::new("path_challenge_updated\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 80usize,
+        id: 85usize,
         name: Str::new("path_challenge_updated.status\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 81usize,
+        id: 86usize,
         name: Str::new("tls_client_hello\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 82usize,
+        id: 87usize,
         name: Str::new("tls_client_hello.latency\0"),
         units: Units::Duration,
     }
     .build(),
     info::Builder {
-        id: 83usize,
+        id: 88usize,
         name: Str::new("tls_server_hello\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 84usize,
+        id: 89usize,
         name: Str::new("tls_server_hello.latency\0"),
         units: Units::Duration,
     }
     .build(),
     info::Builder {
-        id: 85usize,
+        id: 90usize,
+        name: Str::new("tls_operation_completed\0"),
+        units: Units::None,
+    }
+    .build(),
+    info::Builder {
+        id: 91usize,
+        name: Str::new("tls_operation_completed.operation\0"),
+        units: Units::None,
+    }
+    .build(),
+    info::Builder {
+        id: 92usize,
+        name: Str::new("tls_operation_completed.duration\0"),
+        units: Units::Duration,
+    }
+    .build(),
+    info::Builder {
+        id: 93usize,
         name: Str::new("rx_stream_progress\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 86usize,
+        id: 94usize,
         name: Str::new("rx_stream_progress.bytes.total\0"),
         units: Units::Bytes,
     }
     .build(),
     info::Builder {
-        id: 87usize,
+        id: 95usize,
         name: Str::new("rx_stream_progress.bytes\0"),
         units: Units::Bytes,
     }
     .build(),
     info::Builder {
-        id: 88usize,
+        id: 96usize,
         name: Str::new("tx_stream_progress\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 89usize,
+        id: 97usize,
         name: Str::new("tx_stream_progress.bytes.total\0"),
         units: Units::Bytes,
     }
     .build(),
     info::Builder {
-        id: 90usize,
+        id: 98usize,
         name: Str::new("tx_stream_progress.bytes\0"),
         units: Units::Bytes,
     }
     .build(),
     info::Builder {
-        id: 91usize,
+        id: 99usize,
         name: Str::new("keep_alive_timer_expired\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 92usize,
+        id: 100usize,
         name: Str::new("mtu_updated\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 93usize,
+        id: 101usize,
         name: Str::new("mtu_updated.mtu\0"),
         units: Units::Bytes,
     }
     .build(),
     info::Builder {
-        id: 94usize,
+        id: 102usize,
         name: Str::new("mtu_updated.cause\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 95usize,
+        id: 103usize,
         name: Str::new("mtu_updated.search_complete\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 96usize,
+        id: 104usize,
         name: Str::new("slow_start_exited\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 97usize,
+        id: 105usize,
         name: Str::new("slow_start_exited.cause\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 98usize,
+        id: 106usize,
         name: Str::new("slow_start_exited.latency\0"),
         units: Units::Duration,
     }
     .build(),
     info::Builder {
-        id: 99usize,
+        id: 107usize,
         name: Str::new("slow_start_exited.congestion_window\0"),
         units: Units::Bytes,
     }
     .build(),
     info::Builder {
-        id: 100usize,
+        id: 108usize,
         name: Str::new("delivery_rate_sampled\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 101usize,
+        id: 109usize,
         name: Str::new("pacing_rate_updated\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 102usize,
+        id: 110usize,
         name: Str::new("pacing_rate_updated.bytes_per_second\0"),
         units: Units::Bytes,
     }
     .build(),
     info::Builder {
-        id: 103usize,
+        id: 111usize,
         name: Str::new("pacing_rate_updated.burst_size\0"),
         units: Units::Bytes,
     }
     .build(),
     info::Builder {
-        id: 104usize,
+        id: 112usize,
         name: Str::new("pacing_rate_updated.pacing_gain\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 105usize,
+        id: 113usize,
         name: Str::new("bbr_state_changed\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 106usize,
+        id: 114usize,
         name: Str::new("bbr_state_changed.state\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 107usize,
+        id: 115usize,
         name: Str::new("dc_state_changed\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 108usize,
+        id: 116usize,
         name: Str::new("dc_state_changed.version_negotiated.latency\0"),
         units: Units::Duration,
     }
     .build(),
     info::Builder {
-        id: 109usize,
+        id: 117usize,
         name: Str::new("dc_state_changed.no_version_negotiated.latency\0"),
         units: Units::Duration,
     }
     .build(),
     info::Builder {
-        id: 110usize,
+        id: 118usize,
         name: Str::new("dc_state_changed.path_secrets.latency\0"),
         units: Units::Duration,
     }
     .build(),
     info::Builder {
-        id: 111usize,
+        id: 119usize,
         name: Str::new("dc_state_changed.complete.latency\0"),
         units: Units::Duration,
     }
     .build(),
     info::Builder {
-        id: 112usize,
+        id: 120usize,
         name: Str::new("dc_state_changed.state\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 113usize,
+        id: 121usize,
+        name: Str::new("go_away_received\0"),
+        units: Units::None,
+    }
+    .build(),
+    info::Builder {
+        id: 122usize,
+        name: Str::new("stream_limit_pressure\0"),
+        units: Units::None,
+    }
+    .build(),
+    info::Builder {
+        id: 123usize,
+        name: Str::new("connection_data_pressure\0"),
+        units: Units::None,
+    }
+    .build(),
+    info::Builder {
+        id: 124usize,
+        name: Str::new("stream_open_blocked\0"),
+        units: Units::None,
+    }
+    .build(),
+    info::Builder {
+        id: 125usize,
+        name: Str::new("stream_open_blocked.stream_type\0"),
+        units: Units::None,
+    }
+    .build(),
+    info::Builder {
+        id: 126usize,
+        name: Str::new("stream_open_blocked.duration\0"),
+        units: Units::Duration,
+    }
+    .build(),
+    info::Builder {
+        id: 127usize,
+        name: Str::new("connection_hibernated\0"),
+        units: Units::None,
+    }
+    .build(),
+    info::Builder {
+        id: 128usize,
+        name: Str::new("datagram_frame_dropped\0"),
+        units: Units::None,
+    }
+    .build(),
+    info::Builder {
+        id: 129usize,
         name: Str::new("connection_closed\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 114usize,
+        id: 130usize,
         name: Str::new("connection_closed.latency\0"),
         units: Units::Duration,
     }
     .build(),
     info::Builder {
-        id: 115usize,
+        id: 131usize,
         name: Str::new("connection_closed.error\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 116usize,
+        id: 132usize,
         name: Str::new("version_information\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 117usize,
+        id: 133usize,
         name: Str::new("endpoint_packet_sent\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 118usize,
+        id: 134usize,
         name: Str::new("endpoint_packet_received\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 119usize,
+        id: 135usize,
         name: Str::new("endpoint_datagram_sent\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 120usize,
+        id: 136usize,
         name: Str::new("endpoint_datagram_sent.bytes\0"),
         units: Units::Bytes,
     }
     .build(),
     info::Builder {
-        id: 121usize,
+        id: 137usize,
         name: Str::new("endpoint_datagram_sent.bytes.total\0"),
         units: Units::Bytes,
     }
     .build(),
     info::Builder {
-        id: 122usize,
+        id: 138usize,
         name: Str::new("endpoint_datagram_sent.gso_offset\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 123usize,
+        id: 139usize,
         name: Str::new("endpoint_datagram_received\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 124usize,
+        id: 140usize,
         name: Str::new("endpoint_datagram_received.bytes\0"),
         units: Units::Bytes,
     }
     .build(),
     info::Builder {
-        id: 125usize,
+        id: 141usize,
         name: Str::new("endpoint_datagram_received.bytes.total\0"),
         units: Units::Bytes,
     }
     .build(),
     info::Builder {
-        id: 126usize,
+        id: 142usize,
         name: Str::new("endpoint_datagram_dropped\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 127usize,
+        id: 143usize,
         name: Str::new("endpoint_datagram_dropped.bytes\0"),
         units: Units::Bytes,
     }
     .build(),
     info::Builder {
-        id: 128usize,
+        id: 144usize,
         name: Str::new("endpoint_datagram_dropped.bytes.total\0"),
         units: Units::Bytes,
     }
     .build(),
     info::Builder {
-        id: 129usize,
+        id: 145usize,
         name: Str::new("endpoint_datagram_dropped.reason\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 130usize,
+        id: 146usize,
         name: Str::new("endpoint_connection_attempt_failed\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 131usize,
+        id: 147usize,
         name: Str::new("endpoint_connection_attempt_failed.error\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 132usize,
+        id: 148usize,
         name: Str::new("platform_tx\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 133usize,
+        id: 149usize,
         name: Str::new("platform_tx.packets.total\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 134usize,
+        id: 150usize,
         name: Str::new("platform_tx.packets\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 135usize,
+        id: 151usize,
         name: Str::new("platform_tx.syscalls.total\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 136usize,
+        id: 152usize,
         name: Str::new("platform_tx.syscalls\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 137usize,
+        id: 153usize,
         name: Str::new("platform_tx.syscalls.blocked.total\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 138usize,
+        id: 154usize,
         name: Str::new("platform_tx.syscalls.blocked\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 139usize,
+        id: 155usize,
         name: Str::new("platform_tx.errors.total\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 140usize,
+        id: 156usize,
         name: Str::new("platform_tx.errors\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 141usize,
+        id: 157usize,
         name: Str::new("platform_tx.errors.dropped.total\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 142usize,
+        id: 158usize,
         name: Str::new("platform_tx.errors.dropped\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 143usize,
+        id: 159usize,
         name: Str::new("platform_tx_error\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 144usize,
+        id: 160usize,
         name: Str::new("platform_rx\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 145usize,
+        id: 161usize,
         name: Str::new("platform_rx.packets.total\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 146usize,
+        id: 162usize,
         name: Str::new("platform_rx.packets\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 147usize,
+        id: 163usize,
         name: Str::new("platform_rx.syscalls.total\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 148usize,
+        id: 164usize,
         name: Str::new("platform_rx.syscalls\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 149usize,
+        id: 165usize,
         name: Str::new("platform_rx.syscalls.blocked.total\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 150usize,
+        id: 166usize,
         name: Str::new("platform_rx.syscalls.blocked\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 151usize,
+        id: 167usize,
         name: Str::new("platform_rx.errors.total\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 152usize,
+        id: 168usize,
         name: Str::new("platform_rx.errors\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 153usize,
+        id: 169usize,
         name: Str::new("platform_rx.errors.dropped.total\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 154usize,
+        id: 170usize,
         name: Str::new("platform_rx.errors.dropped\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 155usize,
-        name: Str::new("platform_rx_error\0"),
+        id: 171usize,
+        name: Str::new("platform_rx_error\0"),
+        units: Units::None,
+    }
+    .build(),
+    info::Builder {
+        id: 172usize,
+        name: Str::new("platform_feature_configured\0"),
+        units: Units::None,
+    }
+    .build(),
+    info::Builder {
+        id: 173usize,
+        name: Str::new("platform_event_loop_wakeup\0"),
+        units: Units::None,
+    }
+    .build(),
+    info::Builder {
+        id: 174usize,
+        name: Str::new("platform_event_loop_sleep\0"),
+        units: Units::None,
+    }
+    .build(),
+    info::Builder {
+        id: 175usize,
+        name: Str::new("platform_event_loop_sleep.processing_duration\0"),
+        units: Units::Duration,
+    }
+    .build(),
+    info::Builder {
+        id: 176usize,
+        name: Str::new("platform_event_loop_started\0"),
+        units: Units::None,
+    }
+    .build(),
+    info::Builder {
+        id: 177usize,
+        name: Str::new("platform_event_loop_profile\0"),
         units: Units::None,
     }
     .build(),
     info::Builder {
-        id: 156usize,
-        name: Str::new("platform_feature_configured\0"),
-        units: Units::None,
+        id: 178usize,
+        name: Str::new("platform_event_loop_profile.rx_parse\0"),
+        units: Units::Duration,
     }
     .build(),
     info::Builder {
-        id: 157usize,
-        name: Str::new("platform_event_loop_wakeup\0"),
-        units: Units::None,
+        id: 179usize,
+        name: Str::new("platform_event_loop_profile.crypto\0"),
+        units: Units::Duration,
     }
     .build(),
     info::Builder {
-        id: 158usize,
-        name: Str::new("platform_event_loop_sleep\0"),
-        units: Units::None,
+        id: 180usize,
+        name: Str::new("platform_event_loop_profile.connection_processing\0"),
+        units: Units::Duration,
     }
     .build(),
     info::Builder {
-        id: 159usize,
-        name: Str::new("platform_event_loop_sleep.processing_duration\0"),
+        id: 181usize,
+        name: Str::new("platform_event_loop_profile.tx_packing\0"),
         units: Units::Duration,
     }
     .build(),
     info::Builder {
-        id: 160usize,
-        name: Str::new("platform_event_loop_started\0"),
-        units: Units::None,
+        id: 182usize,
+        name: Str::new("platform_event_loop_profile.timers\0"),
+        units: Units::Duration,
     }
     .build(),
 ];
@@ -988,19 +1120,19 @@ pub struct ConnectionContext {
 }
 pub struct Subscriber<R: Registry> {
     #[allow(dead_code)]
-    counters: Box<[R::Counter; 75usize]>,
+    counters: Box<[R::Counter; 85usize]>,
     #[allow(dead_code)]
     bool_counters: Box<[R::BoolCounter; 3usize]>,
     #[allow(dead_code)]
     nominal_counters: Box<[R::NominalCounter]>,
     #[allow(dead_code)]
-    nominal_counter_offsets: Box<[usize; 29usize]>,
+    nominal_counter_offsets: Box<[usize; 34usize]>,
     #[allow(dead_code)]
-    measures: Box<[R::Measure; 38usize]>,
+    measures: Box<[R::Measure; 39usize]>,
     #[allow(dead_code)]
     gauges: Box<[R::Gauge; 0usize]>,
     #[allow(dead_code)]
-    timers: Box<[R::Timer; 15usize]>,
+    timers: Box<[R::Timer; 21usize]>,
     #[allow(dead_code)]
     nominal_timers: Box<[R::NominalTimer]>,
     #[allow(dead_code)]
@@ -1023,13 +1155,13 @@ impl<R: Registry> Subscriber<R> {
     #[allow(unused_mut)]
     #[inline]
     pub fn new(registry: R) -> Self {
-        let mut counters = Vec::with_capacity(75usize);
+        let mut counters = Vec::with_capacity(85usize);
         let mut bool_counters = Vec::with_capacity(3usize);
-        let mut nominal_counters = Vec::with_capacity(29usize);
-        let mut nominal_counter_offsets = Vec::with_capacity(29usize);
-        let mut measures = Vec::with_capacity(38usize);
+        let mut nominal_counters = Vec::with_capacity(34usize);
+        let mut nominal_counter_offsets = Vec::with_capacity(34usize);
+        let mut measures = Vec::with_capacity(39usize);
         let mut gauges = Vec::with_capacity(0usize);
-        let mut timers = Vec::with_capacity(15usize);
+        let mut timers = Vec::with_capacity(21usize);
         let mut nominal_timers = Vec::with_capacity(1usize);
         let mut nominal_timer_offsets = Vec::with_capacity(1usize);
         counters.push(registry.register_counter(&INFO[0usize]));
@@ -1053,63 +1185,73 @@ impl<R: Registry> Subscriber<R> {
         counters.push(registry.register_counter(&INFO[41usize]));
         counters.push(registry.register_counter(&INFO[43usize]));
         counters.push(registry.register_counter(&INFO[46usize]));
-        counters.push(registry.register_counter(&INFO[51usize]));
-        counters.push(registry.register_counter(&INFO[52usize]));
+        counters.push(registry.register_counter(&INFO[49usize]));
+        counters.push(registry.register_counter(&INFO[54usize]));
         counters.push(registry.register_counter(&INFO[55usize]));
-        counters.push(registry.register_counter(&INFO[57usize]));
         counters.push(registry.register_counter(&INFO[58usize]));
+        counters.push(registry.register_counter(&INFO[60usize]));
         counters.push(registry.register_counter(&INFO[61usize]));
-        counters.push(registry.register_counter(&INFO[62usize]));
         counters.push(registry.register_counter(&INFO[64usize]));
         counters.push(registry.register_counter(&INFO[65usize]));
+        counters.push(registry.register_counter(&INFO[67usize]));
         counters.push(registry.register_counter(&INFO[68usize]));
-        counters.push(registry.register_counter(&INFO[69usize]));
         counters.push(registry.register_counter(&INFO[71usize]));
-        counters.push(registry.register_counter(&INFO[73usize]));
+        counters.push(registry.register_counter(&INFO[72usize]));
+        counters.push(registry.register_counter(&INFO[74usize]));
+        counters.push(registry.register_counter(&INFO[76usize]));
         counters.push(registry.register_counter(&INFO[78usize]));
-        counters.push(registry.register_counter(&INFO[79usize]));
-        counters.push(registry.register_counter(&INFO[81usize]));
         counters.push(registry.register_counter(&INFO[83usize]));
-        counters.push(registry.register_counter(&INFO[85usize]));
+        counters.push(registry.register_counter(&INFO[84usize]));
         counters.push(registry.register_counter(&INFO[86usize]));
         counters.push(registry.register_counter(&INFO[88usize]));
-        counters.push(registry.register_counter(&INFO[89usize]));
-        counters.push(registry.register_counter(&INFO[91usize]));
-        counters.push(registry.register_counter(&INFO[92usize]));
+        counters.push(registry.register_counter(&INFO[90usize]));
+        counters.push(registry.register_counter(&INFO[93usize]));
+        counters.push(registry.register_counter(&INFO[94usize]));
         counters.push(registry.register_counter(&INFO[96usize]));
+        counters.push(registry.register_counter(&INFO[97usize]));
+        counters.push(registry.register_counter(&INFO[99usize]));
         counters.push(registry.register_counter(&INFO[100usize]));
-        counters.push(registry.register_counter(&INFO[101usize]));
-        counters.push(registry.register_counter(&INFO[105usize]));
-        counters.push(registry.register_counter(&INFO[107usize]));
+        counters.push(registry.register_counter(&INFO[104usize]));
+        counters.push(registry.register_counter(&INFO[108usize]));
+        counters.push(registry.register_counter(&INFO[109usize]));
         counters.push(registry.register_counter(&INFO[113usize]));
-        counters.push(registry.register_counter(&INFO[116usize]));
-        counters.push(registry.register_counter(&INFO[117usize]));
-        counters.push(registry.register_counter(&INFO[118usize]));
-        counters.push(registry.register_counter(&INFO[119usize]));
+        counters.push(registry.register_counter(&INFO[115usize]));
+        counters.push(registry.register_counter(&INFO[121usize]));
+        counters.push(registry.register_counter(&INFO[122usize]));
         counters.push(registry.register_counter(&INFO[123usize]));
-        counters.push(registry.register_counter(&INFO[126usize]));
-        counters.push(registry.register_counter(&INFO[130usize]));
+        counters.push(registry.register_counter(&INFO[124usize]));
+        counters.push(registry.register_counter(&INFO[127usize]));
+        counters.push(registry.register_counter(&INFO[128usize]));
+        counters.push(registry.register_counter(&INFO[129usize]));
         counters.push(registry.register_counter(&INFO[132usize]));
         counters.push(registry.register_counter(&INFO[133usize]));
+        counters.push(registry.register_counter(&INFO[134usize]));
         counters.push(registry.register_counter(&INFO[135usize]));
-        counters.push(registry.register_counter(&INFO[137usize]));
         counters.push(registry.register_counter(&INFO[139usize]));
-        counters.push(registry.register_counter(&INFO[141usize]));
-        counters.push(registry.register_counter(&INFO[143usize]));
-        counters.push(registry.register_counter(&INFO[144usize]));
-        counters.push(registry.register_counter(&INFO[145usize]));
-        counters.push(registry.register_counter(&INFO[147usize]));
+        counters.push(registry.register_counter(&INFO[142usize]));
+        counters.push(registry.register_counter(&INFO[146usize]));
+        counters.push(registry.register_counter(&INFO[148usize]));
         counters.push(registry.register_counter(&INFO[149usize]));
         counters.push(registry.register_counter(&INFO[151usize]));
         counters.push(registry.register_counter(&INFO[153usize]));
         counters.push(registry.register_counter(&INFO[155usize]));
-        counters.push(registry.register_counter(&INFO[156usize]));
         counters.push(registry.register_counter(&INFO[157usize]));
-        counters.push(registry.register_counter(&INFO[158usize]));
+        counters.push(registry.register_counter(&INFO[159usize]));
         counters.push(registry.register_counter(&INFO[160usize]));
+        counters.push(registry.register_counter(&INFO[161usize]));
+        counters.push(registry.register_counter(&INFO[163usize]));
+        counters.push(registry.register_counter(&INFO[165usize]));
+        counters.push(registry.register_counter(&INFO[167usize]));
+        counters.push(registry.register_counter(&INFO[169usize]));
+        counters.push(registry.register_counter(&INFO[171usize]));
+        counters.push(registry.register_counter(&INFO[172usize]));
+        counters.push(registry.register_counter(&INFO[173usize]));
+        counters.push(registry.register_counter(&INFO[174usize]));
+        counters.push(registry.register_counter(&INFO[176usize]));
+        counters.push(registry.register_counter(&INFO[177usize]));
         bool_counters.push(registry.register_bool_counter(&INFO[23usize]));
         bool_counters.push(registry.register_bool_counter(&INFO[33usize]));
-        bool_counters.push(registry.register_bool_counter(&INFO[95usize]));
+        bool_counters.push(registry.register_bool_counter(&INFO[103usize]));
         {
             #[allow(unused_imports)]
             use api::*;
@@ -1248,7 +1390,7 @@ impl<R: Registry> Subscriber<R> {
             {
                 let offset = nominal_counters.len();
                 let mut count = 0;
-                for variant in <KeyType as AsVariant>::VARIANTS.iter() {
+                for variant in <PacketHeader as AsVariant>::VARIANTS.iter() {
                     nominal_counters
                         .push(registry.register_nominal_counter(&INFO[44usize], variant));
                     count += 1;
@@ -1259,7 +1401,7 @@ impl<R: Registry> Subscriber<R> {
             {
                 let offset = nominal_counters.len();
                 let mut count = 0;
-                for variant in <CipherSuite as AsVariant>::VARIANTS.iter() {
+                for variant in <PacketBudget as AsVariant>::VARIANTS.iter() {
                     nominal_counters
                         .push(registry.register_nominal_counter(&INFO[45usize], variant));
                     count += 1;
@@ -1267,12 +1409,34 @@ impl<R: Registry> Subscriber<R> {
                 debug_assert_ne!(count, 0, "field type needs at least one variant");
                 nominal_counter_offsets.push(offset);
             }
+            {
+                let offset = nominal_counters.len();
+                let mut count = 0;
+                for variant in <KeyType as AsVariant>::VARIANTS.iter() {
+                    nominal_counters
+                        .push(registry.register_nominal_counter(&INFO[47usize], variant));
+                    count += 1;
+                }
+                debug_assert_ne!(count, 0, "field type needs at least one variant");
+                nominal_counter_offsets.push(offset);
+            }
+            {
+                let offset = nominal_counters.len();
+                let mut count = 0;
+                for variant in <CipherSuite as AsVariant>::VARIANTS.iter() {
+                    nominal_counters
+                        .push(registry.register_nominal_counter(&INFO[48usize], variant));
+                    count += 1;
+                }
+                debug_assert_ne!(count, 0, "field type needs at least one variant");
+                nominal_counter_offsets.push(offset);
+            }
             {
                 let offset = nominal_counters.len();
                 let mut count = 0;
                 for variant in <KeySpace as AsVariant>::VARIANTS.iter() {
                     nominal_counters
-                        .push(registry.register_nominal_counter(&INFO[50usize], variant));
+                        .push(registry.register_nominal_counter(&INFO[53usize], variant));
                     count += 1;
                 }
                 debug_assert_ne!(count, 0, "field type needs at least one variant");
@@ -1283,7 +1447,7 @@ impl<R: Registry> Subscriber<R> {
                 let mut count = 0;
                 for variant in <PacketHeader as AsVariant>::VARIANTS.iter() {
                     nominal_counters
-                        .push(registry.register_nominal_counter(&INFO[53usize], variant));
+                        .push(registry.register_nominal_counter(&INFO[56usize], variant));
                     count += 1;
                 }
                 debug_assert_ne!(count, 0, "field type needs at least one variant");
@@ -1294,7 +1458,7 @@ impl<R: Registry> Subscriber<R> {
                 let mut count = 0;
                 for variant in <DuplicatePacketError as AsVariant>::VARIANTS.iter() {
                     nominal_counters
-                        .push(registry.register_nominal_counter(&INFO[54usize], variant));
+                        .push(registry.register_nominal_counter(&INFO[57usize], variant));
                     count += 1;
                 }
                 debug_assert_ne!(count, 0, "field type needs at least one variant");
@@ -1305,7 +1469,7 @@ impl<R: Registry> Subscriber<R> {
                 let mut count = 0;
                 for variant in <DatagramDropReason as AsVariant>::VARIANTS.iter() {
                     nominal_counters
-                        .push(registry.register_nominal_counter(&INFO[67usize], variant));
+                        .push(registry.register_nominal_counter(&INFO[70usize], variant));
                     count += 1;
                 }
                 debug_assert_ne!(count, 0, "field type needs at least one variant");
@@ -1316,7 +1480,7 @@ impl<R: Registry> Subscriber<R> {
                 let mut count = 0;
                 for variant in <EcnState as AsVariant>::VARIANTS.iter() {
                     nominal_counters
-                        .push(registry.register_nominal_counter(&INFO[70usize], variant));
+                        .push(registry.register_nominal_counter(&INFO[73usize], variant));
                     count += 1;
                 }
                 debug_assert_ne!(count, 0, "field type needs at least one variant");
@@ -1327,7 +1491,7 @@ impl<R: Registry> Subscriber<R> {
                 let mut count = 0;
                 for variant in <MigrationDenyReason as AsVariant>::VARIANTS.iter() {
                     nominal_counters
-                        .push(registry.register_nominal_counter(&INFO[72usize], variant));
+                        .push(registry.register_nominal_counter(&INFO[75usize], variant));
                     count += 1;
                 }
                 debug_assert_ne!(count, 0, "field type needs at least one variant");
@@ -1336,7 +1500,7 @@ impl<R: Registry> Subscriber<R> {
             {
                 let offset = nominal_counters.len();
                 let mut count = 0;
-                for variant in <HandshakeStatus as AsVariant>::VARIANTS.iter() {
+                for variant in <CompatibilityRelaxation as AsVariant>::VARIANTS.iter() {
                     nominal_counters
                         .push(registry.register_nominal_counter(&INFO[77usize], variant));
                     count += 1;
@@ -1344,12 +1508,34 @@ impl<R: Registry> Subscriber<R> {
                 debug_assert_ne!(count, 0, "field type needs at least one variant");
                 nominal_counter_offsets.push(offset);
             }
+            {
+                let offset = nominal_counters.len();
+                let mut count = 0;
+                for variant in <HandshakeStatus as AsVariant>::VARIANTS.iter() {
+                    nominal_counters
+                        .push(registry.register_nominal_counter(&INFO[82usize], variant));
+                    count += 1;
+                }
+                debug_assert_ne!(count, 0, "field type needs at least one variant");
+                nominal_counter_offsets.push(offset);
+            }
             {
                 let offset = nominal_counters.len();
                 let mut count = 0;
                 for variant in <PathChallengeStatus as AsVariant>::VARIANTS.iter() {
                     nominal_counters
-                        .push(registry.register_nominal_counter(&INFO[80usize], variant));
+                        .push(registry.register_nominal_counter(&INFO[85usize], variant));
+                    count += 1;
+                }
+                debug_assert_ne!(count, 0, "field type needs at least one variant");
+                nominal_counter_offsets.push(offset);
+            }
+            {
+                let offset = nominal_counters.len();
+                let mut count = 0;
+                for variant in <TlsOperation as AsVariant>::VARIANTS.iter() {
+                    nominal_counters
+                        .push(registry.register_nominal_counter(&INFO[91usize], variant));
                     count += 1;
                 }
                 debug_assert_ne!(count, 0, "field type needs at least one variant");
@@ -1360,7 +1546,7 @@ impl<R: Registry> Subscriber<R> {
                 let mut count = 0;
                 for variant in <MtuUpdatedCause as AsVariant>::VARIANTS.iter() {
                     nominal_counters
-                        .push(registry.register_nominal_counter(&INFO[94usize], variant));
+                        .push(registry.register_nominal_counter(&INFO[102usize], variant));
                     count += 1;
                 }
                 debug_assert_ne!(count, 0, "field type needs at least one variant");
@@ -1371,7 +1557,7 @@ impl<R: Registry> Subscriber<R> {
                 let mut count = 0;
                 for variant in <SlowStartExitCause as AsVariant>::VARIANTS.iter() {
                     nominal_counters
-                        .push(registry.register_nominal_counter(&INFO[97usize], variant));
+                        .push(registry.register_nominal_counter(&INFO[105usize], variant));
                     count += 1;
                 }
                 debug_assert_ne!(count, 0, "field type needs at least one variant");
@@ -1382,7 +1568,7 @@ impl<R: Registry> Subscriber<R> {
                 let mut count = 0;
                 for variant in <BbrState as AsVariant>::VARIANTS.iter() {
                     nominal_counters
-                        .push(registry.register_nominal_counter(&INFO[106usize], variant));
+                        .push(registry.register_nominal_counter(&INFO[114usize], variant));
                     count += 1;
                 }
                 debug_assert_ne!(count, 0, "field type needs at least one variant");
@@ -1393,7 +1579,18 @@ impl<R: Registry> Subscriber<R> {
                 let mut count = 0;
                 for variant in <DcState as AsVariant>::VARIANTS.iter() {
                     nominal_counters
-                        .push(registry.register_nominal_counter(&INFO[112usize], variant));
+                        .push(registry.register_nominal_counter(&INFO[120usize], variant));
+                    count += 1;
+                }
+                debug_assert_ne!(count, 0, "field type needs at least one variant");
+                nominal_counter_offsets.push(offset);
+            }
+            {
+                let offset = nominal_counters.len();
+                let mut count = 0;
+                for variant in <StreamType as AsVariant>::VARIANTS.iter() {
+                    nominal_counters
+                        .push(registry.register_nominal_counter(&INFO[125usize], variant));
                     count += 1;
                 }
                 debug_assert_ne!(count, 0, "field type needs at least one variant");
@@ -1404,7 +1601,7 @@ impl<R: Registry> Subscriber<R> {
                 let mut count = 0;
                 for variant in <crate::connection::Error as AsVariant>::VARIANTS.iter() {
                     nominal_counters
-                        .push(registry.register_nominal_counter(&INFO[115usize], variant));
+                        .push(registry.register_nominal_counter(&INFO[131usize], variant));
                     count += 1;
                 }
                 debug_assert_ne!(count, 0, "field type needs at least one variant");
@@ -1415,7 +1612,7 @@ impl<R: Registry> Subscriber<R> {
                 let mut count = 0;
                 for variant in <DatagramDropReason as AsVariant>::VARIANTS.iter() {
                     nominal_counters
-                        .push(registry.register_nominal_counter(&INFO[129usize], variant));
+                        .push(registry.register_nominal_counter(&INFO[145usize], variant));
                     count += 1;
                 }
                 debug_assert_ne!(count, 0, "field type needs at least one variant");
@@ -1426,7 +1623,7 @@ impl<R: Registry> Subscriber<R> {
                 let mut count = 0;
                 for variant in <crate::connection::Error as AsVariant>::VARIANTS.iter() {
                     nominal_counters
-                        .push(registry.register_nominal_counter(&INFO[131usize], variant));
+                        .push(registry.register_nominal_counter(&INFO[147usize], variant));
                     count += 1;
                 }
                 debug_assert_ne!(count, 0, "field type needs at least one variant");
@@ -1443,49 +1640,56 @@ impl<R: Registry> Subscriber<R> {
         measures.push(registry.register_measure(&INFO[30usize]));
         measures.push(registry.register_measure(&INFO[31usize]));
         measures.push(registry.register_measure(&INFO[32usize]));
-        measures.push(registry.register_measure(&INFO[59usize]));
-        measures.push(registry.register_measure(&INFO[60usize]));
+        measures.push(registry.register_measure(&INFO[62usize]));
         measures.push(registry.register_measure(&INFO[63usize]));
         measures.push(registry.register_measure(&INFO[66usize]));
-        measures.push(registry.register_measure(&INFO[87usize]));
-        measures.push(registry.register_measure(&INFO[90usize]));
-        measures.push(registry.register_measure(&INFO[93usize]));
-        measures.push(registry.register_measure(&INFO[99usize]));
-        measures.push(registry.register_measure(&INFO[102usize]));
-        measures.push(registry.register_measure(&INFO[103usize]));
-        measures.push(registry.register_measure(&INFO[104usize]));
-        measures.push(registry.register_measure(&INFO[120usize]));
-        measures.push(registry.register_measure(&INFO[121usize]));
-        measures.push(registry.register_measure(&INFO[122usize]));
-        measures.push(registry.register_measure(&INFO[124usize]));
-        measures.push(registry.register_measure(&INFO[125usize]));
-        measures.push(registry.register_measure(&INFO[127usize]));
-        measures.push(registry.register_measure(&INFO[128usize]));
-        measures.push(registry.register_measure(&INFO[134usize]));
+        measures.push(registry.register_measure(&INFO[69usize]));
+        measures.push(registry.register_measure(&INFO[95usize]));
+        measures.push(registry.register_measure(&INFO[98usize]));
+        measures.push(registry.register_measure(&INFO[101usize]));
+        measures.push(registry.register_measure(&INFO[107usize]));
+        measures.push(registry.register_measure(&INFO[110usize]));
+        measures.push(registry.register_measure(&INFO[111usize]));
+        measures.push(registry.register_measure(&INFO[112usize]));
+        measures.push(registry.register_measure(&INFO[126usize]));
         measures.push(registry.register_measure(&INFO[136usize]));
+        measures.push(registry.register_measure(&INFO[137usize]));
         measures.push(registry.register_measure(&INFO[138usize]));
         measures.push(registry.register_measure(&INFO[140usize]));
-        measures.push(registry.register_measure(&INFO[142usize]));
-        measures.push(registry.register_measure(&INFO[146usize]));
-        measures.push(registry.register_measure(&INFO[148usize]));
+        measures.push(registry.register_measure(&INFO[141usize]));
+        measures.push(registry.register_measure(&INFO[143usize]));
+        measures.push(registry.register_measure(&INFO[144usize]));
         measures.push(registry.register_measure(&INFO[150usize]));
         measures.push(registry.register_measure(&INFO[152usize]));
         measures.push(registry.register_measure(&INFO[154usize]));
-        timers.push(registry.register_timer(&INFO[47usize]));
-        timers.push(registry.register_timer(&INFO[48usize]));
-        timers.push(registry.register_timer(&INFO[49usize]));
-        timers.push(registry.register_timer(&INFO[56usize]));
-        timers.push(registry.register_timer(&INFO[74usize]));
-        timers.push(registry.register_timer(&INFO[75usize]));
-        timers.push(registry.register_timer(&INFO[76usize]));
-        timers.push(registry.register_timer(&INFO[82usize]));
-        timers.push(registry.register_timer(&INFO[84usize]));
-        timers.push(registry.register_timer(&INFO[108usize]));
-        timers.push(registry.register_timer(&INFO[109usize]));
-        timers.push(registry.register_timer(&INFO[110usize]));
-        timers.push(registry.register_timer(&INFO[111usize]));
-        timers.push(registry.register_timer(&INFO[114usize]));
-        timers.push(registry.register_timer(&INFO[159usize]));
+        measures.push(registry.register_measure(&INFO[156usize]));
+        measures.push(registry.register_measure(&INFO[158usize]));
+        measures.push(registry.register_measure(&INFO[162usize]));
+        measures.push(registry.register_measure(&INFO[164usize]));
+        measures.push(registry.register_measure(&INFO[166usize]));
+        measures.push(registry.register_measure(&INFO[168usize]));
+        measures.push(registry.register_measure(&INFO[170usize]));
+        timers.push(registry.register_timer(&INFO[50usize]));
+        timers.push(registry.register_timer(&INFO[51usize]));
+        timers.push(registry.register_timer(&INFO[52usize]));
+        timers.push(registry.register_timer(&INFO[59usize]));
+        timers.push(registry.register_timer(&INFO[79usize]));
+        timers.push(registry.register_timer(&INFO[80usize]));
+        timers.push(registry.register_timer(&INFO[81usize]));
+        timers.push(registry.register_timer(&INFO[87usize]));
+        timers.push(registry.register_timer(&INFO[89usize]));
+        timers.push(registry.register_timer(&INFO[92usize]));
+        timers.push(registry.register_timer(&INFO[116usize]));
+        timers.push(registry.register_timer(&INFO[117usize]));
+        timers.push(registry.register_timer(&INFO[118usize]));
+        timers.push(registry.register_timer(&INFO[119usize]));
+        timers.push(registry.register_timer(&INFO[130usize]));
+        timers.push(registry.register_timer(&INFO[175usize]));
+        timers.push(registry.register_timer(&INFO[178usize]));
+        timers.push(registry.register_timer(&INFO[179usize]));
+        timers.push(registry.register_timer(&INFO[180usize]));
+        timers.push(registry.register_timer(&INFO[181usize]));
+        timers.push(registry.register_timer(&INFO[182usize]));
         {
             #[allow(unused_imports)]
             use api::*;
@@ -1493,7 +1697,7 @@ impl<R: Registry> Subscriber<R> {
                 let offset = nominal_timers.len();
                 let mut count = 0;
                 for variant in <SlowStartExitCause as AsVariant>::VARIANTS.iter() {
-                    nominal_timers.push(registry.register_nominal_timer(&INFO[98usize], variant));
+                    nominal_timers.push(registry.register_nominal_timer(&INFO[106usize], variant));
                     count += 1;
                 }
                 debug_assert_ne!(count, 0, "field type needs at least one variant");
@@ -1551,60 +1755,70 @@ impl<R: Registry> Subscriber<R> {
                 18usize => (&INFO[41usize], entry),
                 19usize => (&INFO[43usize], entry),
                 20usize => (&INFO[46usize], entry),
-                21usize => (&INFO[51usize], entry),
-                22usize => (&INFO[52usize], entry),
+                21usize => (&INFO[49usize], entry),
+                22usize => (&INFO[54usize], entry),
                 23usize => (&INFO[55usize], entry),
-                24usize => (&INFO[57usize], entry),
-                25usize => (&INFO[58usize], entry),
+                24usize => (&INFO[58usize], entry),
+                25usize => (&INFO[60usize], entry),
                 26usize => (&INFO[61usize], entry),
-                27usize => (&INFO[62usize], entry),
-                28usize => (&INFO[64usize], entry),
-                29usize => (&INFO[65usize], entry),
+                27usize => (&INFO[64usize], entry),
+                28usize => (&INFO[65usize], entry),
+                29usize => (&INFO[67usize], entry),
                 30usize => (&INFO[68usize], entry),
-                31usize => (&INFO[69usize], entry),
-                32usize => (&INFO[71usize], entry),
-                33usize => (&INFO[73usize], entry),
-                34usize => (&INFO[78usize], entry),
-                35usize => (&INFO[79usize], entry),
-                36usize => (&INFO[81usize], entry),
-                37usize => (&INFO[83usize], entry),
-                38usize => (&INFO[85usize], entry),
-                39usize => (&INFO[86usize], entry),
-                40usize => (&INFO[88usize], entry),
-                41usize => (&INFO[89usize], entry),
-                42usize => (&INFO[91usize], entry),
-                43usize => (&INFO[92usize], entry),
-                44usize => (&INFO[96usize], entry),
-                45usize => (&INFO[100usize], entry),
-                46usize => (&INFO[101usize], entry),
-                47usize => (&INFO[105usize], entry),
-                48usize => (&INFO[107usize], entry),
-                49usize => (&INFO[113usize], entry),
-                50usize => (&INFO[116usize], entry),
-                51usize => (&INFO[117usize], entry),
-                52usize => (&INFO[118usize], entry),
-                53usize => (&INFO[119usize], entry),
+                31usize => (&INFO[71usize], entry),
+                32usize => (&INFO[72usize], entry),
+                33usize => (&INFO[74usize], entry),
+                34usize => (&INFO[76usize], entry),
+                35usize => (&INFO[78usize], entry),
+                36usize => (&INFO[83usize], entry),
+                37usize => (&INFO[84usize], entry),
+                38usize => (&INFO[86usize], entry),
+                39usize => (&INFO[88usize], entry),
+                40usize => (&INFO[90usize], entry),
+                41usize => (&INFO[93usize], entry),
+                42usize => (&INFO[94usize], entry),
+                43usize => (&INFO[96usize], entry),
+                44usize => (&INFO[97usize], entry),
+                45usize => (&INFO[99usize], entry),
+                46usize => (&INFO[100usize], entry),
+                47usize => (&INFO[104usize], entry),
+                48usize => (&INFO[108usize], entry),
+                49usize => (&INFO[109usize], entry),
+                50usize => (&INFO[113usize], entry),
+                51usize => (&INFO[115usize], entry),
+                52usize => (&INFO[121usize], entry),
+                53usize => (&INFO[122usize], entry),
                 54usize => (&INFO[123usize], entry),
-                55usize => (&INFO[126usize], entry),
-                56usize => (&INFO[130usize], entry),
-                57usize => (&INFO[132usize], entry),
-                58usize => (&INFO[133usize], entry),
-                59usize => (&INFO[135usize], entry),
-                60usize => (&INFO[137usize], entry),
-                61usize => (&INFO[139usize], entry),
-                62usize => (&INFO[141usize], entry),
-                63usize => (&INFO[143usize], entry),
-                64usize => (&INFO[144usize], entry),
-                65usize => (&INFO[145usize], entry),
-                66usize => (&INFO[147usize], entry),
+                55usize => (&INFO[124usize], entry),
+                56usize => (&INFO[127usize], entry),
+                57usize => (&INFO[128usize], entry),
+                58usize => (&INFO[129usize], entry),
+                59usize => (&INFO[132usize], entry),
+                60usize => (&INFO[133usize], entry),
+                61usize => (&INFO[134usize], entry),
+                62usize => (&INFO[135usize], entry),
+                63usize => (&INFO[139usize], entry),
+                64usize => (&INFO[142usize], entry),
+                65usize => (&INFO[146usize], entry),
+                66usize => (&INFO[148usize], entry),
                 67usize => (&INFO[149usize], entry),
                 68usize => (&INFO[151usize], entry),
                 69usize => (&INFO[153usize], entry),
                 70usize => (&INFO[155usize], entry),
-                71usize => (&INFO[156usize], entry),
-                72usize => (&INFO[157usize], entry),
-                73usize => (&INFO[158usize], entry),
-                74usize => (&INFO[160usize], entry),
+                71usize => (&INFO[157usize], entry),
+                72usize => (&INFO[159usize], entry),
+                73usize => (&INFO[160usize], entry),
+                74usize => (&INFO[161usize], entry),
+                75usize => (&INFO[163usize], entry),
+                76usize => (&INFO[165usize], entry),
+                77usize => (&INFO[167usize], entry),
+                78usize => (&INFO[169usize], entry),
+                79usize => (&INFO[171usize], entry),
+                80usize => (&INFO[172usize], entry),
+                81usize => (&INFO[173usize], entry),
+                82usize => (&INFO[174usize], entry),
+                83usize => (&INFO[176usize], entry),
+                84usize => (&INFO[177usize], entry),
                 _ => unsafe { core::hint::unreachable_unchecked() },
             })
     }
@@ -1624,7 +1838,7 @@ impl<R: Registry> Subscriber<R> {
             .map(|(idx, entry)| match idx {
                 0usize => (&INFO[23usize], entry),
                 1usize => (&INFO[33usize], entry),
-                2usize => (&INFO[95usize], entry),
+                2usize => (&INFO[103usize], entry),
                 _ => unsafe { core::hint::unreachable_unchecked() },
             })
     }
@@ -1719,106 +1933,136 @@ impl<R: Registry> Subscriber<R> {
                 }
                 12usize => {
                     let offset = *entry;
-                    let variants = <KeyType as AsVariant>::VARIANTS;
+                    let variants = <PacketHeader as AsVariant>::VARIANTS;
                     let entries = &self.nominal_counters[offset..offset + variants.len()];
                     (&INFO[44usize], entries, variants)
                 }
                 13usize => {
                     let offset = *entry;
-                    let variants = <CipherSuite as AsVariant>::VARIANTS;
+                    let variants = <PacketBudget as AsVariant>::VARIANTS;
                     let entries = &self.nominal_counters[offset..offset + variants.len()];
                     (&INFO[45usize], entries, variants)
                 }
                 14usize => {
                     let offset = *entry;
-                    let variants = <KeySpace as AsVariant>::VARIANTS;
+                    let variants = <KeyType as AsVariant>::VARIANTS;
                     let entries = &self.nominal_counters[offset..offset + variants.len()];
-                    (&INFO[50usize], entries, variants)
+                    (&INFO[47usize], entries, variants)
                 }
                 15usize => {
                     let offset = *entry;
-                    let variants = <PacketHeader as AsVariant>::VARIANTS;
+                    let variants = <CipherSuite as AsVariant>::VARIANTS;
                     let entries = &self.nominal_counters[offset..offset + variants.len()];
-                    (&INFO[53usize], entries, variants)
+                    (&INFO[48usize], entries, variants)
                 }
                 16usize => {
                     let offset = *entry;
-                    let variants = <DuplicatePacketError as AsVariant>::VARIANTS;
+                    let variants = <KeySpace as AsVariant>::VARIANTS;
                     let entries = &self.nominal_counters[offset..offset + variants.len()];
-                    (&INFO[54usize], entries, variants)
+                    (&INFO[53usize], entries, variants)
                 }
                 17usize => {
                     let offset = *entry;
-                    let variants = <DatagramDropReason as AsVariant>::VARIANTS;
+                    let variants = <PacketHeader as AsVariant>::VARIANTS;
                     let entries = &self.nominal_counters[offset..offset + variants.len()];
-                    (&INFO[67usize], entries, variants)
+                    (&INFO[56usize], entries, variants)
                 }
                 18usize => {
                     let offset = *entry;
-                    let variants = <EcnState as AsVariant>::VARIANTS;
+                    let variants = <DuplicatePacketError as AsVariant>::VARIANTS;
                     let entries = &self.nominal_counters[offset..offset + variants.len()];
-                    (&INFO[70usize], entries, variants)
+                    (&INFO[57usize], entries, variants)
                 }
                 19usize => {
                     let offset = *entry;
-                    let variants = <MigrationDenyReason as AsVariant>::VARIANTS;
+                    let variants = <DatagramDropReason as AsVariant>::VARIANTS;
                     let entries = &self.nominal_counters[offset..offset + variants.len()];
-                    (&INFO[72usize], entries, variants)
+                    (&INFO[70usize], entries, variants)
                 }
                 20usize => {
                     let offset = *entry;
-                    let variants = <HandshakeStatus as AsVariant>::VARIANTS;
+                    let variants = <EcnState as AsVariant>::VARIANTS;
                     let entries = &self.nominal_counters[offset..offset + variants.len()];
-                    (&INFO[77usize], entries, variants)
+                    (&INFO[73usize], entries, variants)
                 }
                 21usize => {
                     let offset = *entry;
-                    let variants = <PathChallengeStatus as AsVariant>::VARIANTS;
+                    let variants = <MigrationDenyReason as AsVariant>::VARIANTS;
                     let entries = &self.nominal_counters[offset..offset + variants.len()];
-                    (&INFO[80usize], entries, variants)
+                    (&INFO[75usize], entries, variants)
                 }
                 22usize => {
                     let offset = *entry;
-                    let variants = <MtuUpdatedCause as AsVariant>::VARIANTS;
+                    let variants = <CompatibilityRelaxation as AsVariant>::VARIANTS;
                     let entries = &self.nominal_counters[offset..offset + variants.len()];
-                    (&INFO[94usize], entries, variants)
+                    (&INFO[77usize], entries, variants)
                 }
                 23usize => {
                     let offset = *entry;
-                    let variants = <SlowStartExitCause as AsVariant>::VARIANTS;
+                    let variants = <HandshakeStatus as AsVariant>::VARIANTS;
                     let entries = &self.nominal_counters[offset..offset + variants.len()];
-                    (&INFO[97usize], entries, variants)
+                    (&INFO[82usize], entries, variants)
                 }
                 24usize => {
                     let offset = *entry;
-                    let variants = <BbrState as AsVariant>::VARIANTS;
+                    let variants = <PathChallengeStatus as AsVariant>::VARIANTS;
                     let entries = &self.nominal_counters[offset..offset + variants.len()];
-                    (&INFO[106usize], entries, variants)
+                    (&INFO[85usize], entries, variants)
                 }
                 25usize => {
                     let offset = *entry;
-                    let variants = <DcState as AsVariant>::VARIANTS;
+                    let variants = <TlsOperation as AsVariant>::VARIANTS;
                     let entries = &self.nominal_counters[offset..offset + variants.len()];
-                    (&INFO[112usize], entries, variants)
+                    (&INFO[91usize], entries, variants)
                 }
                 26usize => {
                     let offset = *entry;
-                    let variants = <crate::connection::Error as AsVariant>::VARIANTS;
+                    let variants = <MtuUpdatedCause as AsVariant>::VARIANTS;
                     let entries = &self.nominal_counters[offset..offset + variants.len()];
-                    (&INFO[115usize], entries, variants)
+                    (&INFO[102usize], entries, variants)
                 }
                 27usize => {
                     let offset = *entry;
-                    let variants = <DatagramDropReason as AsVariant>::VARIANTS;
+                    let variants = <SlowStartExitCause as AsVariant>::VARIANTS;
                     let entries = &self.nominal_counters[offset..offset + variants.len()];
-                    (&INFO[129usize], entries, variants)
+                    (&INFO[105usize], entries, variants)
                 }
                 28usize => {
+                    let offset = *entry;
+                    let variants = <BbrState as AsVariant>::VARIANTS;
+                    let entries = &self.nominal_counters[offset..offset + variants.len()];
+                    (&INFO[114usize], entries, variants)
+                }
+                29usize => {
+                    let offset = *entry;
+                    let variants = <DcState as AsVariant>::VARIANTS;
+                    let entries = &self.nominal_counters[offset..offset + variants.len()];
+                    (&INFO[120usize], entries, variants)
+                }
+                30usize => {
+                    let offset = *entry;
+                    let variants = <StreamType as AsVariant>::VARIANTS;
+                    let entries = &self.nominal_counters[offset..offset + variants.len()];
+                    (&INFO[125usize], entries, variants)
+                }
+                31usize => {
                     let offset = *entry;
                     let variants = <crate::connection::Error as AsVariant>::VARIANTS;
                     let entries = &self.nominal_counters[offset..offset + variants.len()];
                     (&INFO[131usize], entries, variants)
                 }
+                32usize => {
+                    let offset = *entry;
+                    let variants = <DatagramDropReason as AsVariant>::VARIANTS;
+                    let entries = &self.nominal_counters[offset..offset + variants.len()];
+                    (&INFO[145usize], entries, variants)
+                }
+                33usize => {
+                    let offset = *entry;
+                    let variants = <crate::connection::Error as AsVariant>::VARIANTS;
+                    let entries = &self.nominal_counters[offset..offset + variants.len()];
+                    (&INFO[147usize], entries, variants)
+                }
                 _ => unsafe { core::hint::unreachable_unchecked() },
             })
     }
@@ -1847,34 +2091,35 @@ impl<R: Registry> Subscriber<R> {
                 7usize => (&INFO[30usize], entry),
                 8usize => (&INFO[31usize], entry),
                 9usize => (&INFO[32usize], entry),
-                10usize => (&INFO[59usize], entry),
-                11usize => (&INFO[60usize], entry),
-                12usize => (&INFO[63usize], entry),
-                13usize => (&INFO[66usize], entry),
-                14usize => (&INFO[87usize], entry),
-                15usize => (&INFO[90usize], entry),
-                16usize => (&INFO[93usize], entry),
-                17usize => (&INFO[99usize], entry),
-                18usize => (&INFO[102usize], entry),
-                19usize => (&INFO[103usize], entry),
-                20usize => (&INFO[104usize], entry),
-                21usize => (&INFO[120usize], entry),
-                22usize => (&INFO[121usize], entry),
-                23usize => (&INFO[122usize], entry),
-                24usize => (&INFO[124usize], entry),
-                25usize => (&INFO[125usize], entry),
-                26usize => (&INFO[127usize], entry),
-                27usize => (&INFO[128usize], entry),
-                28usize => (&INFO[134usize], entry),
-                29usize => (&INFO[136usize], entry),
-                30usize => (&INFO[138usize], entry),
-                31usize => (&INFO[140usize], entry),
-                32usize => (&INFO[142usize], entry),
-                33usize => (&INFO[146usize], entry),
-                34usize => (&INFO[148usize], entry),
-                35usize => (&INFO[150usize], entry),
-                36usize => (&INFO[152usize], entry),
-                37usize => (&INFO[154usize], entry),
+                10usize => (&INFO[62usize], entry),
+                11usize => (&INFO[63usize], entry),
+                12usize => (&INFO[66usize], entry),
+                13usize => (&INFO[69usize], entry),
+                14usize => (&INFO[95usize], entry),
+                15usize => (&INFO[98usize], entry),
+                16usize => (&INFO[101usize], entry),
+                17usize => (&INFO[107usize], entry),
+                18usize => (&INFO[110usize], entry),
+                19usize => (&INFO[111usize], entry),
+                20usize => (&INFO[112usize], entry),
+                21usize => (&INFO[126usize], entry),
+                22usize => (&INFO[136usize], entry),
+                23usize => (&INFO[137usize], entry),
+                24usize => (&INFO[138usize], entry),
+                25usize => (&INFO[140usize], entry),
+                26usize => (&INFO[141usize], entry),
+                27usize => (&INFO[143usize], entry),
+                28usize => (&INFO[144usize], entry),
+                29usize => (&INFO[150usize], entry),
+                30usize => (&INFO[152usize], entry),
+                31usize => (&INFO[154usize], entry),
+                32usize => (&INFO[156usize], entry),
+                33usize => (&INFO[158usize], entry),
+                34usize => (&INFO[162usize], entry),
+                35usize => (&INFO[164usize], entry),
+                36usize => (&INFO[166usize], entry),
+                37usize => (&INFO[168usize], entry),
+                38usize => (&INFO[170usize], entry),
                 _ => unsafe { core::hint::unreachable_unchecked() },
             })
     }
@@ -1904,21 +2149,27 @@ impl<R: Registry> Subscriber<R> {
             .iter()
             .enumerate()
             .map(|(idx, entry)| match idx {
-                0usize => (&INFO[47usize], entry),
-                1usize => (&INFO[48usize], entry),
-                2usize => (&INFO[49usize], entry),
-                3usize => (&INFO[56usize], entry),
-                4usize => (&INFO[74usize], entry),
-                5usize => (&INFO[75usize], entry),
-                6usize => (&INFO[76usize], entry),
-                7usize => (&INFO[82usize], entry),
-                8usize => (&INFO[84usize], entry),
-                9usize => (&INFO[108usize], entry),
-                10usize => (&INFO[109usize], entry),
-                11usize => (&INFO[110usize], entry),
-                12usize => (&INFO[111usize], entry),
-                13usize => (&INFO[114usize], entry),
-                14usize => (&INFO[159usize], entry),
+                0usize => (&INFO[50usize], entry),
+                1usize => (&INFO[51usize], entry),
+                2usize => (&INFO[52usize], entry),
+                3usize => (&INFO[59usize], entry),
+                4usize => (&INFO[79usize], entry),
+                5usize => (&INFO[80usize], entry),
+                6usize => (&INFO[81usize], entry),
+                7usize => (&INFO[87usize], entry),
+                8usize => (&INFO[89usize], entry),
+                9usize => (&INFO[92usize], entry),
+                10usize => (&INFO[116usize], entry),
+                11usize => (&INFO[117usize], entry),
+                12usize => (&INFO[118usize], entry),
+                13usize => (&INFO[119usize], entry),
+                14usize => (&INFO[130usize], entry),
+                15usize => (&INFO[175usize], entry),
+                16usize => (&INFO[178usize], entry),
+                17usize => (&INFO[179usize], entry),
+                18usize => (&INFO[180usize], entry),
+                19usize => (&INFO[181usize], entry),
+                20usize => (&INFO[182usize], entry),
                 _ => unsafe { core::hint::unreachable_unchecked() },
             })
     }
@@ -2220,6 +2471,22 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
         let _ = event;
     }
     #[inline]
+    fn on_packet_budget_exceeded(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::PacketBudgetExceeded,
+    ) {
+        #[allow(unused_imports)]
+        use api::*;
+        self.count(43usize, 19usize, 1usize);
+        self.count_nominal(44usize, 12usize, &event.packet_header);
+        self.count_nominal(45usize, 13usize, &event.budget);
+        let _ = context;
+        let _ = meta;
+        let _ = event;
+    }
+    #[inline]
     fn on_key_update(
         &mut self,
         context: &mut Self::ConnectionContext,
@@ -2228,9 +2495,9 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(43usize, 19usize, 1usize);
-        self.count_nominal(44usize, 12usize, &event.key_type);
-        self.count_nominal(45usize, 13usize, &event.cipher_suite);
+        self.count(46usize, 20usize, 1usize);
+        self.count_nominal(47usize, 14usize, &event.key_type);
+        self.count_nominal(48usize, 15usize, &event.cipher_suite);
         let _ = context;
         let _ = meta;
         let _ = event;
@@ -2244,14 +2511,14 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(46usize, 20usize, 1usize);
+        self.count(49usize, 21usize, 1usize);
         {
             fn check(evt: &api::KeySpaceDiscarded) -> bool {
                 matches!(evt.space, KeySpace::Initial { .. })
             }
             if check(event) {
                 self.time(
-                    47usize,
+                    50usize,
                     0usize,
                     meta.timestamp.saturating_duration_since(context.start_time),
                 );
@@ -2263,7 +2530,7 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
             }
             if check(event) {
                 self.time(
-                    48usize,
+                    51usize,
                     1usize,
                     meta.timestamp.saturating_duration_since(context.start_time),
                 );
@@ -2275,13 +2542,13 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
             }
             if check(event) {
                 self.time(
-                    49usize,
+                    52usize,
                     2usize,
                     meta.timestamp.saturating_duration_since(context.start_time),
                 );
             }
         }
-        self.count_nominal(50usize, 14usize, &event.space);
+        self.count_nominal(53usize, 16usize, &event.space);
         let _ = context;
         let _ = meta;
         let _ = event;
@@ -2295,7 +2562,7 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(51usize, 21usize, 1usize);
+        self.count(54usize, 22usize, 1usize);
         let _ = context;
         let _ = meta;
         let _ = event;
@@ -2309,9 +2576,9 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(52usize, 22usize, 1usize);
-        self.count_nominal(53usize, 15usize, &event.packet_header);
-        self.count_nominal(54usize, 16usize, &event.error);
+        self.count(55usize, 23usize, 1usize);
+        self.count_nominal(56usize, 17usize, &event.packet_header);
+        self.count_nominal(57usize, 18usize, &event.error);
         let _ = context;
         let _ = meta;
         let _ = event;
@@ -2325,9 +2592,9 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(55usize, 23usize, 1usize);
+        self.count(58usize, 24usize, 1usize);
         self.time(
-            56usize,
+            59usize,
             3usize,
             meta.timestamp.saturating_duration_since(context.start_time),
         );
@@ -2344,10 +2611,10 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(57usize, 24usize, 1usize);
-        self.count(58usize, 25usize, event.len);
-        self.measure(59usize, 10usize, event.len);
-        self.measure(60usize, 11usize, event.gso_offset);
+        self.count(60usize, 25usize, 1usize);
+        self.count(61usize, 26usize, event.len);
+        self.measure(62usize, 10usize, event.len);
+        self.measure(63usize, 11usize, event.gso_offset);
         let _ = context;
         let _ = meta;
         let _ = event;
@@ -2361,9 +2628,9 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(61usize, 26usize, 1usize);
-        self.count(62usize, 27usize, event.len);
-        self.measure(63usize, 12usize, event.len);
+        self.count(64usize, 27usize, 1usize);
+        self.count(65usize, 28usize, event.len);
+        self.measure(66usize, 12usize, event.len);
         let _ = context;
         let _ = meta;
         let _ = event;
@@ -2377,10 +2644,10 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(64usize, 28usize, 1usize);
-        self.count(65usize, 29usize, event.len);
-        self.measure(66usize, 13usize, event.len);
-        self.count_nominal(67usize, 17usize, &event.reason);
+        self.count(67usize, 29usize, 1usize);
+        self.count(68usize, 30usize, event.len);
+        self.measure(69usize, 13usize, event.len);
+        self.count_nominal(70usize, 19usize, &event.reason);
         let _ = context;
         let _ = meta;
         let _ = event;
@@ -2394,7 +2661,7 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(68usize, 30usize, 1usize);
+        self.count(71usize, 31usize, 1usize);
         let _ = context;
         let _ = meta;
         let _ = event;
@@ -2408,8 +2675,8 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(69usize, 31usize, 1usize);
-        self.count_nominal(70usize, 18usize, &event.state);
+        self.count(72usize, 32usize, 1usize);
+        self.count_nominal(73usize, 20usize, &event.state);
         let _ = context;
         let _ = meta;
         let _ = event;
@@ -2423,8 +2690,23 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(71usize, 32usize, 1usize);
-        self.count_nominal(72usize, 19usize, &event.reason);
+        self.count(74usize, 33usize, 1usize);
+        self.count_nominal(75usize, 21usize, &event.reason);
+        let _ = context;
+        let _ = meta;
+        let _ = event;
+    }
+    #[inline]
+    fn on_compatibility_relaxation_applied(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::CompatibilityRelaxationApplied,
+    ) {
+        #[allow(unused_imports)]
+        use api::*;
+        self.count(76usize, 34usize, 1usize);
+        self.count_nominal(77usize, 22usize, &event.relaxation);
         let _ = context;
         let _ = meta;
         let _ = event;
@@ -2438,14 +2720,14 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(73usize, 33usize, 1usize);
+        self.count(78usize, 35usize, 1usize);
         {
             fn check(evt: &api::HandshakeStatusUpdated) -> bool {
                 matches!(evt.status, HandshakeStatus::Complete { .. })
             }
             if check(event) {
                 self.time(
-                    74usize,
+                    79usize,
                     4usize,
                     meta.timestamp.saturating_duration_since(context.start_time),
                 );
@@ -2457,7 +2739,7 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
             }
             if check(event) {
                 self.time(
-                    75usize,
+                    80usize,
                     5usize,
                     meta.timestamp.saturating_duration_since(context.start_time),
                 );
@@ -2469,13 +2751,13 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
             }
             if check(event) {
                 self.time(
-                    76usize,
+                    81usize,
                     6usize,
                     meta.timestamp.saturating_duration_since(context.start_time),
                 );
             }
         }
-        self.count_nominal(77usize, 20usize, &event.status);
+        self.count_nominal(82usize, 23usize, &event.status);
         let _ = context;
         let _ = meta;
         let _ = event;
@@ -2489,7 +2771,7 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(78usize, 34usize, 1usize);
+        self.count(83usize, 36usize, 1usize);
         let _ = context;
         let _ = meta;
         let _ = event;
@@ -2503,8 +2785,8 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(79usize, 35usize, 1usize);
-        self.count_nominal(80usize, 21usize, &event.path_challenge_status);
+        self.count(84usize, 37usize, 1usize);
+        self.count_nominal(85usize, 24usize, &event.path_challenge_status);
         let _ = context;
         let _ = meta;
         let _ = event;
@@ -2518,9 +2800,9 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(81usize, 36usize, 1usize);
+        self.count(86usize, 38usize, 1usize);
         self.time(
-            82usize,
+            87usize,
             7usize,
             meta.timestamp.saturating_duration_since(context.start_time),
         );
@@ -2537,9 +2819,9 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(83usize, 37usize, 1usize);
+        self.count(88usize, 39usize, 1usize);
         self.time(
-            84usize,
+            89usize,
             8usize,
             meta.timestamp.saturating_duration_since(context.start_time),
         );
@@ -2548,6 +2830,22 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
         let _ = event;
     }
     #[inline]
+    fn on_tls_operation_completed(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::TlsOperationCompleted,
+    ) {
+        #[allow(unused_imports)]
+        use api::*;
+        self.count(90usize, 40usize, 1usize);
+        self.count_nominal(91usize, 25usize, &event.operation);
+        self.time(92usize, 9usize, event.duration);
+        let _ = context;
+        let _ = meta;
+        let _ = event;
+    }
+    #[inline]
     fn on_rx_stream_progress(
         &mut self,
         context: &mut Self::ConnectionContext,
@@ -2556,9 +2854,9 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(85usize, 38usize, 1usize);
-        self.count(86usize, 39usize, event.bytes);
-        self.measure(87usize, 14usize, event.bytes);
+        self.count(93usize, 41usize, 1usize);
+        self.count(94usize, 42usize, event.bytes);
+        self.measure(95usize, 14usize, event.bytes);
         let _ = context;
         let _ = meta;
         let _ = event;
@@ -2572,9 +2870,9 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(88usize, 40usize, 1usize);
-        self.count(89usize, 41usize, event.bytes);
-        self.measure(90usize, 15usize, event.bytes);
+        self.count(96usize, 43usize, 1usize);
+        self.count(97usize, 44usize, event.bytes);
+        self.measure(98usize, 15usize, event.bytes);
         let _ = context;
         let _ = meta;
         let _ = event;
@@ -2588,7 +2886,7 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(91usize, 42usize, 1usize);
+        self.count(99usize, 45usize, 1usize);
         let _ = context;
         let _ = meta;
         let _ = event;
@@ -2602,10 +2900,10 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(92usize, 43usize, 1usize);
-        self.measure(93usize, 16usize, event.mtu);
-        self.count_nominal(94usize, 22usize, &event.cause);
-        self.count_bool(95usize, 2usize, event.search_complete);
+        self.count(100usize, 46usize, 1usize);
+        self.measure(101usize, 16usize, event.mtu);
+        self.count_nominal(102usize, 26usize, &event.cause);
+        self.count_bool(103usize, 2usize, event.search_complete);
         let _ = context;
         let _ = meta;
         let _ = event;
@@ -2619,15 +2917,15 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(96usize, 44usize, 1usize);
-        self.count_nominal(97usize, 23usize, &event.cause);
+        self.count(104usize, 47usize, 1usize);
+        self.count_nominal(105usize, 27usize, &event.cause);
         self.time_nominal(
-            98usize,
+            106usize,
             0usize,
             &event.cause,
             meta.timestamp.saturating_duration_since(context.start_time),
         );
-        self.measure(99usize, 17usize, event.congestion_window);
+        self.measure(107usize, 17usize, event.congestion_window);
         let _ = context;
         let _ = meta;
         let _ = event;
@@ -2641,7 +2939,7 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(100usize, 45usize, 1usize);
+        self.count(108usize, 48usize, 1usize);
         let _ = context;
         let _ = meta;
         let _ = event;
@@ -2655,10 +2953,10 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(101usize, 46usize, 1usize);
-        self.measure(102usize, 18usize, event.bytes_per_second);
-        self.measure(103usize, 19usize, event.burst_size);
-        self.measure(104usize, 20usize, event.pacing_gain);
+        self.count(109usize, 49usize, 1usize);
+        self.measure(110usize, 18usize, event.bytes_per_second);
+        self.measure(111usize, 19usize, event.burst_size);
+        self.measure(112usize, 20usize, event.pacing_gain);
         let _ = context;
         let _ = meta;
         let _ = event;
@@ -2672,8 +2970,8 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(105usize, 47usize, 1usize);
-        self.count_nominal(106usize, 24usize, &event.state);
+        self.count(113usize, 50usize, 1usize);
+        self.count_nominal(114usize, 28usize, &event.state);
         let _ = context;
         let _ = meta;
         let _ = event;
@@ -2687,15 +2985,15 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(107usize, 48usize, 1usize);
+        self.count(115usize, 51usize, 1usize);
         {
             fn check(evt: &api::DcStateChanged) -> bool {
                 matches!(evt.state, DcState::VersionNegotiated { .. })
             }
             if check(event) {
                 self.time(
-                    108usize,
-                    9usize,
+                    116usize,
+                    10usize,
                     meta.timestamp.saturating_duration_since(context.start_time),
                 );
             }
@@ -2706,8 +3004,8 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
             }
             if check(event) {
                 self.time(
-                    109usize,
-                    10usize,
+                    117usize,
+                    11usize,
                     meta.timestamp.saturating_duration_since(context.start_time),
                 );
             }
@@ -2718,8 +3016,8 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
             }
             if check(event) {
                 self.time(
-                    110usize,
-                    11usize,
+                    118usize,
+                    12usize,
                     meta.timestamp.saturating_duration_since(context.start_time),
                 );
             }
@@ -2730,13 +3028,99 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
             }
             if check(event) {
                 self.time(
-                    111usize,
-                    12usize,
+                    119usize,
+                    13usize,
                     meta.timestamp.saturating_duration_since(context.start_time),
                 );
             }
         }
-        self.count_nominal(112usize, 25usize, &event.state);
+        self.count_nominal(120usize, 29usize, &event.state);
+        let _ = context;
+        let _ = meta;
+        let _ = event;
+    }
+    #[inline]
+    fn on_go_away_received(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::GoAwayReceived,
+    ) {
+        #[allow(unused_imports)]
+        use api::*;
+        self.count(121usize, 52usize, 1usize);
+        let _ = context;
+        let _ = meta;
+        let _ = event;
+    }
+    #[inline]
+    fn on_stream_limit_pressure(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::StreamLimitPressure,
+    ) {
+        #[allow(unused_imports)]
+        use api::*;
+        self.count(122usize, 53usize, 1usize);
+        let _ = context;
+        let _ = meta;
+        let _ = event;
+    }
+    #[inline]
+    fn on_connection_data_pressure(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::ConnectionDataPressure,
+    ) {
+        #[allow(unused_imports)]
+        use api::*;
+        self.count(123usize, 54usize, 1usize);
+        let _ = context;
+        let _ = meta;
+        let _ = event;
+    }
+    #[inline]
+    fn on_stream_open_blocked(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::StreamOpenBlocked,
+    ) {
+        #[allow(unused_imports)]
+        use api::*;
+        self.count(124usize, 55usize, 1usize);
+        self.count_nominal(125usize, 30usize, &event.stream_type);
+        self.measure(126usize, 21usize, event.duration);
+        let _ = context;
+        let _ = meta;
+        let _ = event;
+    }
+    #[inline]
+    fn on_connection_hibernated(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::ConnectionHibernated,
+    ) {
+        #[allow(unused_imports)]
+        use api::*;
+        self.count(127usize, 56usize, 1usize);
+        let _ = context;
+        let _ = meta;
+        let _ = event;
+    }
+    #[inline]
+    fn on_datagram_frame_dropped(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        meta: &api::ConnectionMeta,
+        event: &api::DatagramFrameDropped,
+    ) {
+        #[allow(unused_imports)]
+        use api::*;
+        self.count(128usize, 57usize, 1usize);
         let _ = context;
         let _ = meta;
         let _ = event;
@@ -2750,13 +3134,13 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(113usize, 49usize, 1usize);
+        self.count(129usize, 58usize, 1usize);
         self.time(
-            114usize,
-            13usize,
+            130usize,
+            14usize,
             meta.timestamp.saturating_duration_since(context.start_time),
         );
-        self.count_nominal(115usize, 26usize, &event.error);
+        self.count_nominal(131usize, 31usize, &event.error);
         let _ = context;
         let _ = meta;
         let _ = event;
@@ -2769,7 +3153,7 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(116usize, 50usize, 1usize);
+        self.count(132usize, 59usize, 1usize);
         let _ = event;
         let _ = meta;
     }
@@ -2781,7 +3165,7 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(117usize, 51usize, 1usize);
+        self.count(133usize, 60usize, 1usize);
         let _ = event;
         let _ = meta;
     }
@@ -2793,7 +3177,7 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(118usize, 52usize, 1usize);
+        self.count(134usize, 61usize, 1usize);
         let _ = event;
         let _ = meta;
     }
@@ -2805,10 +3189,10 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(119usize, 53usize, 1usize);
-        self.measure(120usize, 21usize, event.len);
-        self.measure(121usize, 22usize, event.len);
-        self.measure(122usize, 23usize, event.gso_offset);
+        self.count(135usize, 62usize, 1usize);
+        self.measure(136usize, 22usize, event.len);
+        self.measure(137usize, 23usize, event.len);
+        self.measure(138usize, 24usize, event.gso_offset);
         let _ = event;
         let _ = meta;
     }
@@ -2820,9 +3204,9 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(123usize, 54usize, 1usize);
-        self.measure(124usize, 24usize, event.len);
-        self.measure(125usize, 25usize, event.len);
+        self.count(139usize, 63usize, 1usize);
+        self.measure(140usize, 25usize, event.len);
+        self.measure(141usize, 26usize, event.len);
         let _ = event;
         let _ = meta;
     }
@@ -2834,10 +3218,10 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(126usize, 55usize, 1usize);
-        self.measure(127usize, 26usize, event.len);
-        self.measure(128usize, 27usize, event.len);
-        self.count_nominal(129usize, 27usize, &event.reason);
+        self.count(142usize, 64usize, 1usize);
+        self.measure(143usize, 27usize, event.len);
+        self.measure(144usize, 28usize, event.len);
+        self.count_nominal(145usize, 32usize, &event.reason);
         let _ = event;
         let _ = meta;
     }
@@ -2849,8 +3233,8 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(130usize, 56usize, 1usize);
-        self.count_nominal(131usize, 28usize, &event.error);
+        self.count(146usize, 65usize, 1usize);
+        self.count_nominal(147usize, 33usize, &event.error);
         let _ = event;
         let _ = meta;
     }
@@ -2858,17 +3242,17 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     fn on_platform_tx(&mut self, meta: &api::EndpointMeta, event: &api::PlatformTx) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(132usize, 57usize, 1usize);
-        self.count(133usize, 58usize, event.count);
-        self.measure(134usize, 28usize, event.count);
-        self.count(135usize, 59usize, event.syscalls);
-        self.measure(136usize, 29usize, event.syscalls);
-        self.count(137usize, 60usize, event.blocked_syscalls);
-        self.measure(138usize, 30usize, event.blocked_syscalls);
-        self.count(139usize, 61usize, event.total_errors);
-        self.measure(140usize, 31usize, event.total_errors);
-        self.count(141usize, 62usize, event.dropped_errors);
-        self.measure(142usize, 32usize, event.dropped_errors);
+        self.count(148usize, 66usize, 1usize);
+        self.count(149usize, 67usize, event.count);
+        self.measure(150usize, 29usize, event.count);
+        self.count(151usize, 68usize, event.syscalls);
+        self.measure(152usize, 30usize, event.syscalls);
+        self.count(153usize, 69usize, event.blocked_syscalls);
+        self.measure(154usize, 31usize, event.blocked_syscalls);
+        self.count(155usize, 70usize, event.total_errors);
+        self.measure(156usize, 32usize, event.total_errors);
+        self.count(157usize, 71usize, event.dropped_errors);
+        self.measure(158usize, 33usize, event.dropped_errors);
         let _ = event;
         let _ = meta;
     }
@@ -2876,7 +3260,7 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     fn on_platform_tx_error(&mut self, meta: &api::EndpointMeta, event: &api::PlatformTxError) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(143usize, 63usize, 1usize);
+        self.count(159usize, 72usize, 1usize);
         let _ = event;
         let _ = meta;
     }
@@ -2884,17 +3268,17 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     fn on_platform_rx(&mut self, meta: &api::EndpointMeta, event: &api::PlatformRx) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(144usize, 64usize, 1usize);
-        self.count(145usize, 65usize, event.count);
-        self.measure(146usize, 33usize, event.count);
-        self.count(147usize, 66usize, event.syscalls);
-        self.measure(148usize, 34usize, event.syscalls);
-        self.count(149usize, 67usize, event.blocked_syscalls);
-        self.measure(150usize, 35usize, event.blocked_syscalls);
-        self.count(151usize, 68usize, event.total_errors);
-        self.measure(152usize, 36usize, event.total_errors);
-        self.count(153usize, 69usize, event.dropped_errors);
-        self.measure(154usize, 37usize, event.dropped_errors);
+        self.count(160usize, 73usize, 1usize);
+        self.count(161usize, 74usize, event.count);
+        self.measure(162usize, 34usize, event.count);
+        self.count(163usize, 75usize, event.syscalls);
+        self.measure(164usize, 35usize, event.syscalls);
+        self.count(165usize, 76usize, event.blocked_syscalls);
+        self.measure(166usize, 36usize, event.blocked_syscalls);
+        self.count(167usize, 77usize, event.total_errors);
+        self.measure(168usize, 37usize, event.total_errors);
+        self.count(169usize, 78usize, event.dropped_errors);
+        self.measure(170usize, 38usize, event.dropped_errors);
         let _ = event;
         let _ = meta;
     }
@@ -2902,7 +3286,7 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     fn on_platform_rx_error(&mut self, meta: &api::EndpointMeta, event: &api::PlatformRxError) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(155usize, 70usize, 1usize);
+        self.count(171usize, 79usize, 1usize);
         let _ = event;
         let _ = meta;
     }
@@ -2914,7 +3298,7 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(156usize, 71usize, 1usize);
+        self.count(172usize, 80usize, 1usize);
         let _ = event;
         let _ = meta;
     }
@@ -2926,7 +3310,7 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(157usize, 72usize, 1usize);
+        self.count(173usize, 81usize, 1usize);
         let _ = event;
         let _ = meta;
     }
@@ -2938,8 +3322,8 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(158usize, 73usize, 1usize);
-        self.time(159usize, 14usize, event.processing_duration);
+        self.count(174usize, 82usize, 1usize);
+        self.time(175usize, 15usize, event.processing_duration);
         let _ = event;
         let _ = meta;
     }
@@ -2951,7 +3335,24 @@ impl<R: Registry> event::Subscriber for Subscriber<R> {
     ) {
         #[allow(unused_imports)]
         use api::*;
-        self.count(160usize, 74usize, 1usize);
+        self.count(176usize, 83usize, 1usize);
+        let _ = event;
+        let _ = meta;
+    }
+    #[inline]
+    fn on_platform_event_loop_profile(
+        &mut self,
+        meta: &api::EndpointMeta,
+        event: &api::PlatformEventLoopProfile,
+    ) {
+        #[allow(unused_imports)]
+        use api::*;
+        self.count(177usize, 84usize, 1usize);
+        self.time(178usize, 16usize, event.rx_parse);
+        self.time(179usize, 17usize, event.crypto);
+        self.time(180usize, 18usize, event.connection_processing);
+        self.time(181usize, 19usize, event.tx_packing);
+        self.time(182usize, 20usize, event.timers);
         let _ = event;
         let _ = meta;
     }
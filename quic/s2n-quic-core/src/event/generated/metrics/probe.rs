@@ -5,12 +5,10 @@
 // This file was generated with the `s2n-quic-events` crate and any required
 // changes should be made there.
 
-use crate::{
-    event::metrics::aggregate::{
-        self, info, BoolRecorder, Info, NominalRecorder, Recorder as MetricRecorder,
-    },
-    probe::define,
+use crate::event::metrics::aggregate::{
+    self, info, BoolRecorder, Info, NominalRecorder, Recorder as MetricRecorder,
 };
+use crate::probe::define;
 mod counter {
     #![allow(non_snake_case)]
     use super::*;
@@ -38,62 +36,72 @@ mod counter {
                 37usize => Self(ack_range_received),
                 39usize => Self(ack_range_sent),
                 41usize => Self(packet_dropped),
-                43usize => Self(key_update),
-                46usize => Self(key_space_discarded),
-                51usize => Self(connection_started),
-                52usize => Self(duplicate_packet),
-                55usize => Self(transport_parameters_received),
-                57usize => Self(datagram_sent),
-                58usize => Self(datagram_sent__bytes__total),
-                61usize => Self(datagram_received),
-                62usize => Self(datagram_received__bytes__total),
-                64usize => Self(datagram_dropped),
-                65usize => Self(datagram_dropped__bytes__total),
-                68usize => Self(connection_id_updated),
-                69usize => Self(ecn_state_changed),
-                71usize => Self(connection_migration_denied),
-                73usize => Self(handshake_status_updated),
-                78usize => Self(tls_exporter_ready),
-                79usize => Self(path_challenge_updated),
-                81usize => Self(tls_client_hello),
-                83usize => Self(tls_server_hello),
-                85usize => Self(rx_stream_progress),
-                86usize => Self(rx_stream_progress__bytes__total),
-                88usize => Self(tx_stream_progress),
-                89usize => Self(tx_stream_progress__bytes__total),
-                91usize => Self(keep_alive_timer_expired),
-                92usize => Self(mtu_updated),
-                96usize => Self(slow_start_exited),
-                100usize => Self(delivery_rate_sampled),
-                101usize => Self(pacing_rate_updated),
-                105usize => Self(bbr_state_changed),
-                107usize => Self(dc_state_changed),
-                113usize => Self(connection_closed),
-                116usize => Self(version_information),
-                117usize => Self(endpoint_packet_sent),
-                118usize => Self(endpoint_packet_received),
-                119usize => Self(endpoint_datagram_sent),
-                123usize => Self(endpoint_datagram_received),
-                126usize => Self(endpoint_datagram_dropped),
-                130usize => Self(endpoint_connection_attempt_failed),
-                132usize => Self(platform_tx),
-                133usize => Self(platform_tx__packets__total),
-                135usize => Self(platform_tx__syscalls__total),
-                137usize => Self(platform_tx__syscalls__blocked__total),
-                139usize => Self(platform_tx__errors__total),
-                141usize => Self(platform_tx__errors__dropped__total),
-                143usize => Self(platform_tx_error),
-                144usize => Self(platform_rx),
-                145usize => Self(platform_rx__packets__total),
-                147usize => Self(platform_rx__syscalls__total),
-                149usize => Self(platform_rx__syscalls__blocked__total),
-                151usize => Self(platform_rx__errors__total),
-                153usize => Self(platform_rx__errors__dropped__total),
-                155usize => Self(platform_rx_error),
-                156usize => Self(platform_feature_configured),
-                157usize => Self(platform_event_loop_wakeup),
-                158usize => Self(platform_event_loop_sleep),
-                160usize => Self(platform_event_loop_started),
+                43usize => Self(packet_budget_exceeded),
+                46usize => Self(key_update),
+                49usize => Self(key_space_discarded),
+                54usize => Self(connection_started),
+                55usize => Self(duplicate_packet),
+                58usize => Self(transport_parameters_received),
+                60usize => Self(datagram_sent),
+                61usize => Self(datagram_sent__bytes__total),
+                64usize => Self(datagram_received),
+                65usize => Self(datagram_received__bytes__total),
+                67usize => Self(datagram_dropped),
+                68usize => Self(datagram_dropped__bytes__total),
+                71usize => Self(connection_id_updated),
+                72usize => Self(ecn_state_changed),
+                74usize => Self(connection_migration_denied),
+                76usize => Self(compatibility_relaxation_applied),
+                78usize => Self(handshake_status_updated),
+                83usize => Self(tls_exporter_ready),
+                84usize => Self(path_challenge_updated),
+                86usize => Self(tls_client_hello),
+                88usize => Self(tls_server_hello),
+                90usize => Self(tls_operation_completed),
+                93usize => Self(rx_stream_progress),
+                94usize => Self(rx_stream_progress__bytes__total),
+                96usize => Self(tx_stream_progress),
+                97usize => Self(tx_stream_progress__bytes__total),
+                99usize => Self(keep_alive_timer_expired),
+                100usize => Self(mtu_updated),
+                104usize => Self(slow_start_exited),
+                108usize => Self(delivery_rate_sampled),
+                109usize => Self(pacing_rate_updated),
+                113usize => Self(bbr_state_changed),
+                115usize => Self(dc_state_changed),
+                121usize => Self(go_away_received),
+                122usize => Self(stream_limit_pressure),
+                123usize => Self(connection_data_pressure),
+                124usize => Self(stream_open_blocked),
+                127usize => Self(connection_hibernated),
+                128usize => Self(datagram_frame_dropped),
+                129usize => Self(connection_closed),
+                132usize => Self(version_information),
+                133usize => Self(endpoint_packet_sent),
+                134usize => Self(endpoint_packet_received),
+                135usize => Self(endpoint_datagram_sent),
+                139usize => Self(endpoint_datagram_received),
+                142usize => Self(endpoint_datagram_dropped),
+                146usize => Self(endpoint_connection_attempt_failed),
+                148usize => Self(platform_tx),
+                149usize => Self(platform_tx__packets__total),
+                151usize => Self(platform_tx__syscalls__total),
+                153usize => Self(platform_tx__syscalls__blocked__total),
+                155usize => Self(platform_tx__errors__total),
+                157usize => Self(platform_tx__errors__dropped__total),
+                159usize => Self(platform_tx_error),
+                160usize => Self(platform_rx),
+                161usize => Self(platform_rx__packets__total),
+                163usize => Self(platform_rx__syscalls__total),
+                165usize => Self(platform_rx__syscalls__blocked__total),
+                167usize => Self(platform_rx__errors__total),
+                169usize => Self(platform_rx__errors__dropped__total),
+                171usize => Self(platform_rx_error),
+                172usize => Self(platform_feature_configured),
+                173usize => Self(platform_event_loop_wakeup),
+                174usize => Self(platform_event_loop_sleep),
+                176usize => Self(platform_event_loop_started),
+                177usize => Self(platform_event_loop_profile),
                 _ => unreachable!("invalid info: {info:?}"),
             }
         }
@@ -143,6 +151,8 @@ mod counter {
             fn ack_range_sent(value: u64);
             # [link_name = s2n_quic__event__counter__packet_dropped]
             fn packet_dropped(value: u64);
+            # [link_name = s2n_quic__event__counter__packet_budget_exceeded]
+            fn packet_budget_exceeded(value: u64);
             # [link_name = s2n_quic__event__counter__key_update]
             fn key_update(value: u64);
             # [link_name = s2n_quic__event__counter__key_space_discarded]
@@ -171,6 +181,8 @@ mod counter {
             fn ecn_state_changed(value: u64);
             # [link_name = s2n_quic__event__counter__connection_migration_denied]
             fn connection_migration_denied(value: u64);
+            # [link_name = s2n_quic__event__counter__compatibility_relaxation_applied]
+            fn compatibility_relaxation_applied(value: u64);
             # [link_name = s2n_quic__event__counter__handshake_status_updated]
             fn handshake_status_updated(value: u64);
             # [link_name = s2n_quic__event__counter__tls_exporter_ready]
@@ -181,6 +193,8 @@ mod counter {
             fn tls_client_hello(value: u64);
             # [link_name = s2n_quic__event__counter__tls_server_hello]
             fn tls_server_hello(value: u64);
+            # [link_name = s2n_quic__event__counter__tls_operation_completed]
+            fn tls_operation_completed(value: u64);
             # [link_name = s2n_quic__event__counter__rx_stream_progress]
             fn rx_stream_progress(value: u64);
             # [link_name = s2n_quic__event__counter__rx_stream_progress__bytes__total]
@@ -203,6 +217,18 @@ mod counter {
             fn bbr_state_changed(value: u64);
             # [link_name = s2n_quic__event__counter__dc_state_changed]
             fn dc_state_changed(value: u64);
+            # [link_name = s2n_quic__event__counter__go_away_received]
+            fn go_away_received(value: u64);
+            # [link_name = s2n_quic__event__counter__stream_limit_pressure]
+            fn stream_limit_pressure(value: u64);
+            # [link_name = s2n_quic__event__counter__connection_data_pressure]
+            fn connection_data_pressure(value: u64);
+            # [link_name = s2n_quic__event__counter__stream_open_blocked]
+            fn stream_open_blocked(value: u64);
+            # [link_name = s2n_quic__event__counter__connection_hibernated]
+            fn connection_hibernated(value: u64);
+            # [link_name = s2n_quic__event__counter__datagram_frame_dropped]
+            fn datagram_frame_dropped(value: u64);
             # [link_name = s2n_quic__event__counter__connection_closed]
             fn connection_closed(value: u64);
             # [link_name = s2n_quic__event__counter__version_information]
@@ -255,6 +281,8 @@ mod counter {
             fn platform_event_loop_sleep(value: u64);
             # [link_name = s2n_quic__event__counter__platform_event_loop_started]
             fn platform_event_loop_started(value: u64);
+            # [link_name = s2n_quic__event__counter__platform_event_loop_profile]
+            fn platform_event_loop_profile(value: u64);
         }
     );
     pub mod bool {
@@ -266,7 +294,7 @@ mod counter {
                 match info.id {
                     23usize => Self(packet_lost__is_mtu_probe),
                     33usize => Self(recovery_metrics__congestion_limited),
-                    95usize => Self(mtu_updated__search_complete),
+                    103usize => Self(mtu_updated__search_complete),
                     _ => unreachable!("invalid info: {info:?}"),
                 }
             }
@@ -307,23 +335,28 @@ mod counter {
                     38usize => Self(ack_range_received__packet),
                     40usize => Self(ack_range_sent__packet),
                     42usize => Self(packet_dropped__reason),
-                    44usize => Self(key_update__key_type),
-                    45usize => Self(key_update__cipher_suite),
-                    50usize => Self(key_space_discarded__space),
-                    53usize => Self(duplicate_packet__kind),
-                    54usize => Self(duplicate_packet__error),
-                    67usize => Self(datagram_dropped__reason),
-                    70usize => Self(ecn_state_changed__state),
-                    72usize => Self(connection_migration_denied__reason),
-                    77usize => Self(handshake_status_updated__status),
-                    80usize => Self(path_challenge_updated__status),
-                    94usize => Self(mtu_updated__cause),
-                    97usize => Self(slow_start_exited__cause),
-                    106usize => Self(bbr_state_changed__state),
-                    112usize => Self(dc_state_changed__state),
-                    115usize => Self(connection_closed__error),
-                    129usize => Self(endpoint_datagram_dropped__reason),
-                    131usize => Self(endpoint_connection_attempt_failed__error),
+                    44usize => Self(packet_budget_exceeded__packet),
+                    45usize => Self(packet_budget_exceeded__budget),
+                    47usize => Self(key_update__key_type),
+                    48usize => Self(key_update__cipher_suite),
+                    53usize => Self(key_space_discarded__space),
+                    56usize => Self(duplicate_packet__kind),
+                    57usize => Self(duplicate_packet__error),
+                    70usize => Self(datagram_dropped__reason),
+                    73usize => Self(ecn_state_changed__state),
+                    75usize => Self(connection_migration_denied__reason),
+                    77usize => Self(compatibility_relaxation_applied__relaxation),
+                    82usize => Self(handshake_status_updated__status),
+                    85usize => Self(path_challenge_updated__status),
+                    91usize => Self(tls_operation_completed__operation),
+                    102usize => Self(mtu_updated__cause),
+                    105usize => Self(slow_start_exited__cause),
+                    114usize => Self(bbr_state_changed__state),
+                    120usize => Self(dc_state_changed__state),
+                    125usize => Self(stream_open_blocked__stream_type),
+                    131usize => Self(connection_closed__error),
+                    145usize => Self(endpoint_datagram_dropped__reason),
+                    147usize => Self(endpoint_connection_attempt_failed__error),
                     _ => unreachable!("invalid info: {info:?}"),
                 }
             }
@@ -368,6 +401,18 @@ mod counter {
                 fn ack_range_sent__packet(value: u64, variant: u64, variant_name: &info::Str);
                 # [link_name = s2n_quic__event__counter__nominal__packet_dropped__reason]
                 fn packet_dropped__reason(value: u64, variant: u64, variant_name: &info::Str);
+                # [link_name = s2n_quic__event__counter__nominal__packet_budget_exceeded__packet]
+                fn packet_budget_exceeded__packet(
+                    value: u64,
+                    variant: u64,
+                    variant_name: &info::Str,
+                );
+                # [link_name = s2n_quic__event__counter__nominal__packet_budget_exceeded__budget]
+                fn packet_budget_exceeded__budget(
+                    value: u64,
+                    variant: u64,
+                    variant_name: &info::Str,
+                );
                 # [link_name = s2n_quic__event__counter__nominal__key_update__key_type]
                 fn key_update__key_type(value: u64, variant: u64, variant_name: &info::Str);
                 # [link_name = s2n_quic__event__counter__nominal__key_update__cipher_suite]
@@ -388,6 +433,12 @@ mod counter {
                     variant: u64,
                     variant_name: &info::Str,
                 );
+                # [link_name = s2n_quic__event__counter__nominal__compatibility_relaxation_applied__relaxation]
+                fn compatibility_relaxation_applied__relaxation(
+                    value: u64,
+                    variant: u64,
+                    variant_name: &info::Str,
+                );
                 # [link_name = s2n_quic__event__counter__nominal__handshake_status_updated__status]
                 fn handshake_status_updated__status(
                     value: u64,
@@ -400,6 +451,12 @@ mod counter {
                     variant: u64,
                     variant_name: &info::Str,
                 );
+                # [link_name = s2n_quic__event__counter__nominal__tls_operation_completed__operation]
+                fn tls_operation_completed__operation(
+                    value: u64,
+                    variant: u64,
+                    variant_name: &info::Str,
+                );
                 # [link_name = s2n_quic__event__counter__nominal__mtu_updated__cause]
                 fn mtu_updated__cause(value: u64, variant: u64, variant_name: &info::Str);
                 # [link_name = s2n_quic__event__counter__nominal__slow_start_exited__cause]
@@ -408,6 +465,12 @@ mod counter {
                 fn bbr_state_changed__state(value: u64, variant: u64, variant_name: &info::Str);
                 # [link_name = s2n_quic__event__counter__nominal__dc_state_changed__state]
                 fn dc_state_changed__state(value: u64, variant: u64, variant_name: &info::Str);
+                # [link_name = s2n_quic__event__counter__nominal__stream_open_blocked__stream_type]
+                fn stream_open_blocked__stream_type(
+                    value: u64,
+                    variant: u64,
+                    variant_name: &info::Str,
+                );
                 # [link_name = s2n_quic__event__counter__nominal__connection_closed__error]
                 fn connection_closed__error(value: u64, variant: u64, variant_name: &info::Str);
                 # [link_name = s2n_quic__event__counter__nominal__endpoint_datagram_dropped__reason]
@@ -444,34 +507,35 @@ mod measure {
                 30usize => Self(recovery_metrics__pto_count),
                 31usize => Self(recovery_metrics__congestion_window),
                 32usize => Self(recovery_metrics__bytes_in_flight),
-                59usize => Self(datagram_sent__bytes),
-                60usize => Self(datagram_sent__gso_offset),
-                63usize => Self(datagram_received__bytes),
-                66usize => Self(datagram_dropped__bytes),
-                87usize => Self(rx_stream_progress__bytes),
-                90usize => Self(tx_stream_progress__bytes),
-                93usize => Self(mtu_updated__mtu),
-                99usize => Self(slow_start_exited__congestion_window),
-                102usize => Self(pacing_rate_updated__bytes_per_second),
-                103usize => Self(pacing_rate_updated__burst_size),
-                104usize => Self(pacing_rate_updated__pacing_gain),
-                120usize => Self(endpoint_datagram_sent__bytes),
-                121usize => Self(endpoint_datagram_sent__bytes__total),
-                122usize => Self(endpoint_datagram_sent__gso_offset),
-                124usize => Self(endpoint_datagram_received__bytes),
-                125usize => Self(endpoint_datagram_received__bytes__total),
-                127usize => Self(endpoint_datagram_dropped__bytes),
-                128usize => Self(endpoint_datagram_dropped__bytes__total),
-                134usize => Self(platform_tx__packets),
-                136usize => Self(platform_tx__syscalls),
-                138usize => Self(platform_tx__syscalls__blocked),
-                140usize => Self(platform_tx__errors),
-                142usize => Self(platform_tx__errors__dropped),
-                146usize => Self(platform_rx__packets),
-                148usize => Self(platform_rx__syscalls),
-                150usize => Self(platform_rx__syscalls__blocked),
-                152usize => Self(platform_rx__errors),
-                154usize => Self(platform_rx__errors__dropped),
+                62usize => Self(datagram_sent__bytes),
+                63usize => Self(datagram_sent__gso_offset),
+                66usize => Self(datagram_received__bytes),
+                69usize => Self(datagram_dropped__bytes),
+                95usize => Self(rx_stream_progress__bytes),
+                98usize => Self(tx_stream_progress__bytes),
+                101usize => Self(mtu_updated__mtu),
+                107usize => Self(slow_start_exited__congestion_window),
+                110usize => Self(pacing_rate_updated__bytes_per_second),
+                111usize => Self(pacing_rate_updated__burst_size),
+                112usize => Self(pacing_rate_updated__pacing_gain),
+                126usize => Self(stream_open_blocked__duration),
+                136usize => Self(endpoint_datagram_sent__bytes),
+                137usize => Self(endpoint_datagram_sent__bytes__total),
+                138usize => Self(endpoint_datagram_sent__gso_offset),
+                140usize => Self(endpoint_datagram_received__bytes),
+                141usize => Self(endpoint_datagram_received__bytes__total),
+                143usize => Self(endpoint_datagram_dropped__bytes),
+                144usize => Self(endpoint_datagram_dropped__bytes__total),
+                150usize => Self(platform_tx__packets),
+                152usize => Self(platform_tx__syscalls),
+                154usize => Self(platform_tx__syscalls__blocked),
+                156usize => Self(platform_tx__errors),
+                158usize => Self(platform_tx__errors__dropped),
+                162usize => Self(platform_rx__packets),
+                164usize => Self(platform_rx__syscalls),
+                166usize => Self(platform_rx__syscalls__blocked),
+                168usize => Self(platform_rx__errors),
+                170usize => Self(platform_rx__errors__dropped),
                 _ => unreachable!("invalid info: {info:?}"),
             }
         }
@@ -525,6 +589,8 @@ mod measure {
             fn pacing_rate_updated__burst_size(value: u64);
             # [link_name = s2n_quic__event__measure__pacing_rate_updated__pacing_gain]
             fn pacing_rate_updated__pacing_gain(value: u64);
+            # [link_name = s2n_quic__event__measure__stream_open_blocked__duration]
+            fn stream_open_blocked__duration(value: u64);
             # [link_name = s2n_quic__event__measure__endpoint_datagram_sent__bytes]
             fn endpoint_datagram_sent__bytes(value: u64);
             # [link_name = s2n_quic__event__measure__endpoint_datagram_sent__bytes__total]
@@ -586,21 +652,27 @@ mod timer {
     impl Recorder {
         pub(crate) fn new(info: &'static Info) -> Self {
             match info.id {
-                47usize => Self(key_space_discarded__initial__latency),
-                48usize => Self(key_space_discarded__handshake__latency),
-                49usize => Self(key_space_discarded__one_rtt__latency),
-                56usize => Self(transport_parameters_received__latency),
-                74usize => Self(handshake_status_updated__complete__latency),
-                75usize => Self(handshake_status_updated__confirmed__latency),
-                76usize => Self(handshake_status_updated__handshake_done_acked__latency),
-                82usize => Self(tls_client_hello__latency),
-                84usize => Self(tls_server_hello__latency),
-                108usize => Self(dc_state_changed__version_negotiated__latency),
-                109usize => Self(dc_state_changed__no_version_negotiated__latency),
-                110usize => Self(dc_state_changed__path_secrets__latency),
-                111usize => Self(dc_state_changed__complete__latency),
-                114usize => Self(connection_closed__latency),
-                159usize => Self(platform_event_loop_sleep__processing_duration),
+                50usize => Self(key_space_discarded__initial__latency),
+                51usize => Self(key_space_discarded__handshake__latency),
+                52usize => Self(key_space_discarded__one_rtt__latency),
+                59usize => Self(transport_parameters_received__latency),
+                79usize => Self(handshake_status_updated__complete__latency),
+                80usize => Self(handshake_status_updated__confirmed__latency),
+                81usize => Self(handshake_status_updated__handshake_done_acked__latency),
+                87usize => Self(tls_client_hello__latency),
+                89usize => Self(tls_server_hello__latency),
+                92usize => Self(tls_operation_completed__duration),
+                116usize => Self(dc_state_changed__version_negotiated__latency),
+                117usize => Self(dc_state_changed__no_version_negotiated__latency),
+                118usize => Self(dc_state_changed__path_secrets__latency),
+                119usize => Self(dc_state_changed__complete__latency),
+                130usize => Self(connection_closed__latency),
+                175usize => Self(platform_event_loop_sleep__processing_duration),
+                178usize => Self(platform_event_loop_profile__rx_parse),
+                179usize => Self(platform_event_loop_profile__crypto),
+                180usize => Self(platform_event_loop_profile__connection_processing),
+                181usize => Self(platform_event_loop_profile__tx_packing),
+                182usize => Self(platform_event_loop_profile__timers),
                 _ => unreachable!("invalid info: {info:?}"),
             }
         }
@@ -630,6 +702,8 @@ mod timer {
             fn tls_client_hello__latency(value: core::time::Duration);
             # [link_name = s2n_quic__event__timer__tls_server_hello__latency]
             fn tls_server_hello__latency(value: core::time::Duration);
+            # [link_name = s2n_quic__event__timer__tls_operation_completed__duration]
+            fn tls_operation_completed__duration(value: core::time::Duration);
             # [link_name = s2n_quic__event__timer__dc_state_changed__version_negotiated__latency]
             fn dc_state_changed__version_negotiated__latency(value: core::time::Duration);
             # [link_name = s2n_quic__event__timer__dc_state_changed__no_version_negotiated__latency]
@@ -642,6 +716,16 @@ mod timer {
             fn connection_closed__latency(value: core::time::Duration);
             # [link_name = s2n_quic__event__timer__platform_event_loop_sleep__processing_duration]
             fn platform_event_loop_sleep__processing_duration(value: core::time::Duration);
+            # [link_name = s2n_quic__event__timer__platform_event_loop_profile__rx_parse]
+            fn platform_event_loop_profile__rx_parse(value: core::time::Duration);
+            # [link_name = s2n_quic__event__timer__platform_event_loop_profile__crypto]
+            fn platform_event_loop_profile__crypto(value: core::time::Duration);
+            # [link_name = s2n_quic__event__timer__platform_event_loop_profile__connection_processing]
+            fn platform_event_loop_profile__connection_processing(value: core::time::Duration);
+            # [link_name = s2n_quic__event__timer__platform_event_loop_profile__tx_packing]
+            fn platform_event_loop_profile__tx_packing(value: core::time::Duration);
+            # [link_name = s2n_quic__event__timer__platform_event_loop_profile__timers]
+            fn platform_event_loop_profile__timers(value: core::time::Duration);
         }
     );
     pub mod nominal {
@@ -652,7 +736,7 @@ mod timer {
         impl Recorder {
             pub(crate) fn new(info: &'static Info, _variant: &'static info::Variant) -> Self {
                 match info.id {
-                    98usize => Self(slow_start_exited__latency),
+                    106usize => Self(slow_start_exited__latency),
                     _ => unreachable!("invalid info: {info:?}"),
                 }
             }
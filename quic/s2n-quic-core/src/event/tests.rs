@@ -0,0 +1,119 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::{query, time::Timestamp};
+
+#[derive(Debug, PartialEq)]
+struct Context(u64);
+
+impl metrics::Recorder for Context {
+    fn increment_counter(&self, _name: &str, _amount: usize) {}
+
+    fn set_value<V: fmt::Display>(&self, _key: &str, _value: V) {}
+}
+
+struct Inner(u64);
+
+impl Subscriber for Inner {
+    type ConnectionContext = Context;
+
+    fn create_connection_context(
+        &mut self,
+        _meta: &api::ConnectionMeta,
+        _info: &api::ConnectionInfo,
+    ) -> Self::ConnectionContext {
+        Context(self.0)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Ledger(u64);
+
+impl accounting::Ledger for Ledger {
+    fn on_connection_finalized(&mut self, _totals: &accounting::Totals) {}
+}
+
+struct Accounting;
+
+impl accounting::Accounting for Accounting {
+    type Ledger = Ledger;
+
+    fn on_connection_opened(
+        &mut self,
+        _meta: &api::ConnectionMeta,
+        _info: &api::ConnectionInfo,
+    ) -> Self::Ledger {
+        Ledger(3)
+    }
+}
+
+fn create_context<S: Subscriber>(subscriber: &mut S) -> S::ConnectionContext {
+    let meta = builder::ConnectionMeta {
+        endpoint_type: crate::endpoint::Type::Server,
+        id: 0,
+        timestamp: unsafe { Timestamp::from_duration(Duration::from_secs(1)) },
+    }
+    .into_event();
+    let info = builder::ConnectionInfo {}.into_event();
+    subscriber.create_connection_context(&meta, &info)
+}
+
+fn find<S: Subscriber, T: 'static, R>(
+    context: &S::ConnectionContext,
+    f: impl FnOnce(&T) -> R,
+) -> Result<R, query::Error> {
+    let mut query = query::Once::new(f);
+    let _ = S::query(context, &mut query);
+    query.into()
+}
+
+fn find_mut<S: Subscriber, T: 'static, R>(
+    context: &mut S::ConnectionContext,
+    f: impl FnOnce(&mut T) -> R,
+) -> Result<R, query::Error> {
+    let mut query = query::Once::new_mut(f);
+    let _ = S::query_mut(context, &mut query);
+    query.into()
+}
+
+/// Ensures contexts can be found by type regardless of how deeply the subscribers are composed
+#[test]
+fn nested_query_test() {
+    type Composed = (
+        metrics::Subscriber<Inner>,
+        (accounting::Subscriber<Accounting>, Inner),
+    );
+
+    let mut subscriber: Composed = (
+        metrics::Subscriber::new(Inner(1)),
+        (accounting::Subscriber::new(Accounting), Inner(2)),
+    );
+    let mut context = create_context(&mut subscriber);
+
+    // the first match is returned
+    assert_eq!(
+        find::<Composed, Context, _>(&context, |ctx| ctx.0).unwrap(),
+        1
+    );
+    assert_eq!(
+        find::<Composed, Ledger, _>(&context, |ctx| ctx.0).unwrap(),
+        3
+    );
+    assert!(find::<Composed, metrics::Context<Context>, _>(&context, |_| ()).is_ok());
+    assert!(find::<Composed, u64, _>(&context, |_| ()).is_err());
+
+    find_mut::<Composed, Context, _>(&mut context, |ctx| ctx.0 = 4).unwrap();
+    find_mut::<Composed, Ledger, _>(&mut context, |ctx| ctx.0 = 5).unwrap();
+
+    assert_eq!(
+        find::<Composed, Context, _>(&context, |ctx| ctx.0).unwrap(),
+        4
+    );
+    assert_eq!(
+        find::<Composed, Ledger, _>(&context, |ctx| ctx.0).unwrap(),
+        5
+    );
+    // the other contexts aren't affected
+    assert_eq!((context.1).1, Context(2));
+}
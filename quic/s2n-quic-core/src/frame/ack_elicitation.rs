@@ -83,6 +83,8 @@ impl AckElicitable for crate::frame::DataBlocked {}
 //# Extension frames MUST be congestion controlled and MUST cause
 //# an ACK frame to be sent.
 impl AckElicitable for crate::frame::DcStatelessResetTokens<'_> {}
+impl AckElicitable for crate::frame::ExpiredStreamData {}
+impl AckElicitable for crate::frame::GoAway {}
 impl AckElicitable for crate::frame::HandshakeDone {}
 impl AckElicitable for crate::frame::MaxData {}
 impl AckElicitable for crate::frame::MaxStreamData {}
@@ -99,6 +101,7 @@ impl AckElicitable for crate::frame::PathChallenge<'_> {}
 impl AckElicitable for crate::frame::PathResponse<'_> {}
 impl AckElicitable for crate::frame::Ping {}
 impl AckElicitable for crate::frame::ResetStream {}
+impl AckElicitable for crate::frame::ResetStreamAt {}
 impl AckElicitable for crate::frame::RetireConnectionId {}
 impl AckElicitable for crate::frame::StopSending {}
 impl<Data> AckElicitable for crate::frame::Stream<Data> {}
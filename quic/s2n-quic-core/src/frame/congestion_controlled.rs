@@ -29,6 +29,8 @@ impl CongestionControlled for crate::frame::DataBlocked {}
 //# Extension frames MUST be congestion controlled and MUST cause
 //# an ACK frame to be sent.
 impl CongestionControlled for crate::frame::DcStatelessResetTokens<'_> {}
+impl CongestionControlled for crate::frame::ExpiredStreamData {}
+impl CongestionControlled for crate::frame::GoAway {}
 impl CongestionControlled for crate::frame::HandshakeDone {}
 impl CongestionControlled for crate::frame::MaxData {}
 impl CongestionControlled for crate::frame::MaxStreamData {}
@@ -56,6 +58,7 @@ impl CongestionControlled for crate::frame::PathChallenge<'_> {}
 impl CongestionControlled for crate::frame::PathResponse<'_> {}
 impl CongestionControlled for crate::frame::Ping {}
 impl CongestionControlled for crate::frame::ResetStream {}
+impl CongestionControlled for crate::frame::ResetStreamAt {}
 impl CongestionControlled for crate::frame::RetireConnectionId {}
 impl CongestionControlled for crate::frame::StopSending {}
 impl CongestionControlled for crate::frame::StreamsBlocked {}
@@ -0,0 +1,93 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{frame::ExtensionTag, varint::VarInt};
+use s2n_codec::{Encoder, EncoderValue};
+
+const TAG: VarInt = VarInt::from_u32(0x60a8);
+
+macro_rules! expired_stream_data_tag {
+    () => {
+        0x60a8u64
+    };
+}
+
+//# EXPIRED_STREAM_DATA Frame {
+//#   Type (i) = 0x60a8,
+//#   Stream ID (i),
+//#   Offset (i),
+//# }
+
+/// Sent by a partially reliable stream to indicate the data before `offset` expired and will not
+/// be retransmitted
+///
+/// The receiver skips over any data it hasn't received yet before the offset, instead of
+/// waiting for it to be retransmitted. This removes the head-of-line blocking caused by
+/// data which is no longer useful to the application, such as late frames of a live stream.
+///
+/// The frame is only sent if both endpoints advertised support for it with the
+/// `partial_reliability_support` transport parameter.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ExpiredStreamData {
+    /// A variable-length integer encoding of the Stream ID of the
+    /// stream with the expired data.
+    pub stream_id: VarInt,
+
+    /// A variable-length integer indicating the offset of the first
+    /// byte of the stream which hasn't expired.
+    pub offset: VarInt,
+}
+
+impl ExpiredStreamData {
+    pub const fn tag(&self) -> ExtensionTag {
+        TAG
+    }
+}
+
+s2n_codec::decoder_parameterized_value!(
+    impl<'a> ExpiredStreamData {
+        fn decode(_tag: ExtensionTag, buffer: Buffer) -> Result<Self> {
+            let (stream_id, buffer) = buffer.decode()?;
+            let (offset, buffer) = buffer.decode()?;
+
+            let frame = ExpiredStreamData { stream_id, offset };
+
+            Ok((frame, buffer))
+        }
+    }
+);
+
+impl EncoderValue for ExpiredStreamData {
+    #[inline]
+    fn encode<E: Encoder>(&self, buffer: &mut E) {
+        buffer.encode(&TAG);
+        buffer.encode(&self.stream_id);
+        buffer.encode(&self.offset);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        frame::{expired_stream_data::TAG, ExpiredStreamData, ExtensionTag},
+        varint::VarInt,
+    };
+    use s2n_codec::{DecoderBuffer, DecoderParameterizedValue, EncoderValue};
+
+    #[test]
+    fn round_trip() {
+        let frame = ExpiredStreamData {
+            stream_id: VarInt::from_u32(4),
+            offset: VarInt::from_u32(1234),
+        };
+        let encoded = frame.encode_to_vec();
+
+        let buffer = DecoderBuffer::new(encoded.as_slice());
+        let (tag, buffer) = buffer.decode::<ExtensionTag>().expect("decoding succeeds");
+        assert_eq!(TAG, tag);
+        let (decoded, remaining) =
+            ExpiredStreamData::decode_parameterized(TAG, buffer).expect("decoding succeeds");
+        assert!(remaining.is_empty());
+        assert_eq!(frame, decoded);
+    }
+}
@@ -0,0 +1,68 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{frame::ExtensionTag, varint::VarInt};
+use s2n_codec::{Encoder, EncoderValue};
+
+const TAG: VarInt = VarInt::from_u32(0x60a7);
+
+macro_rules! go_away_tag {
+    () => {
+        0x60a7u64
+    };
+}
+
+//# GOAWAY Frame {
+//#   Type (i) = 0x60a7,
+//# }
+
+/// Sent by a server to indicate it would prefer the client to move to a new connection
+///
+/// This is used to coordinate connection draining, for example when the server is being removed
+/// from a load balancer. Receipt of the frame does not change the state of the connection; the
+/// client is expected to stop opening new streams and reconnect at a time of its choosing.
+///
+/// The frame is only sent if both endpoints advertised support for it with the
+/// `go_away_support` transport parameter.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct GoAway;
+
+impl GoAway {
+    pub const fn tag(&self) -> ExtensionTag {
+        TAG
+    }
+}
+
+s2n_codec::decoder_parameterized_value!(
+    impl<'a> GoAway {
+        fn decode(_tag: ExtensionTag, buffer: Buffer) -> Result<Self> {
+            Ok((GoAway, buffer))
+        }
+    }
+);
+
+impl EncoderValue for GoAway {
+    #[inline]
+    fn encode<E: Encoder>(&self, buffer: &mut E) {
+        buffer.encode(&TAG);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::frame::{go_away::TAG, ExtensionTag, GoAway};
+    use s2n_codec::{DecoderBuffer, DecoderParameterizedValue, EncoderValue};
+
+    #[test]
+    fn round_trip() {
+        let encoded = GoAway.encode_to_vec();
+
+        let buffer = DecoderBuffer::new(encoded.as_slice());
+        let (tag, buffer) = buffer.decode::<ExtensionTag>().expect("decoding succeeds");
+        assert_eq!(TAG, tag);
+        let (frame, remaining) =
+            GoAway::decode_parameterized(TAG, buffer).expect("decoding succeeds");
+        assert!(remaining.is_empty());
+        assert_eq!(GoAway, frame);
+    }
+}
@@ -263,7 +263,10 @@ frames! {
     [connection_close_tag] => connection_close, handle_connection_close_frame, ConnectionClose['a];
     [handshake_done_tag] => handshake_done, handle_handshake_done_frame, HandshakeDone;
     [datagram_tag] => datagram, handle_datagram_frame, Datagram[Data];
+    [reset_stream_at_tag] => reset_stream_at, handle_reset_stream_at_frame, ResetStreamAt;
     extension[dc_stateless_reset_tokens_tag] => dc_stateless_reset_tokens, handle_dc_stateless_reset_tokens_frame, DcStatelessResetTokens['a];
+    extension[go_away_tag] => go_away, handle_go_away_frame, GoAway;
+    extension[expired_stream_data_tag] => expired_stream_data, handle_expired_stream_data_frame, ExpiredStreamData;
 }
 
 #[derive(Clone, Copy, Debug, Default)]
@@ -70,6 +70,8 @@ impl<Data> Probing for crate::frame::Crypto<Data> {}
 impl<Data> Probing for crate::frame::Datagram<Data> {}
 impl Probing for crate::frame::DataBlocked {}
 impl Probing for crate::frame::DcStatelessResetTokens<'_> {}
+impl Probing for crate::frame::ExpiredStreamData {}
+impl Probing for crate::frame::GoAway {}
 impl Probing for crate::frame::HandshakeDone {}
 impl Probing for crate::frame::MaxData {}
 impl Probing for crate::frame::MaxStreamData {}
@@ -101,6 +103,7 @@ impl Probing for crate::frame::PathResponse<'_> {
 }
 impl Probing for crate::frame::Ping {}
 impl Probing for crate::frame::ResetStream {}
+impl Probing for crate::frame::ResetStreamAt {}
 impl Probing for crate::frame::RetireConnectionId {}
 impl Probing for crate::frame::StopSending {}
 impl<Data> Probing for crate::frame::Stream<Data> {}
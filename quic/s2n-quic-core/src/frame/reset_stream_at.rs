@@ -0,0 +1,61 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::varint::VarInt;
+
+// The RESET_STREAM_AT frame is defined in the QUIC Stream Resets with Partial Delivery
+// extension (draft-ietf-quic-reliable-stream-reset). It abruptly terminates the sending part
+// of a stream, while guaranteeing delivery of stream data up to a certain byte offset.
+//
+// The frame is only sent if both endpoints advertised support for it with the
+// `reset_stream_at` transport parameter.
+
+macro_rules! reset_stream_at_tag {
+    () => {
+        0x24u8
+    };
+}
+
+// RESET_STREAM_AT Frame {
+//   Type (i) = 0x24,
+//   Stream ID (i),
+//   Application Protocol Error Code (i),
+//   Final Size (i),
+//   Reliable Size (i),
+// }
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ResetStreamAt {
+    /// A variable-length integer encoding of the Stream ID of the
+    /// stream being terminated.
+    pub stream_id: VarInt,
+
+    /// A variable-length integer containing the application protocol
+    /// error code which indicates why the stream is being closed.
+    pub application_error_code: VarInt,
+
+    /// A variable-length integer indicating the final size of
+    /// the stream by the RESET_STREAM_AT sender, in unit of bytes.
+    pub final_size: VarInt,
+
+    /// A variable-length integer indicating the amount of data that
+    /// needs to be delivered to the application before the reset
+    /// takes effect, in unit of bytes.
+    pub reliable_size: VarInt,
+}
+
+impl ResetStreamAt {
+    pub const fn tag(&self) -> u8 {
+        reset_stream_at_tag!()
+    }
+}
+
+simple_frame_codec!(
+    ResetStreamAt {
+        stream_id,
+        application_error_code,
+        final_size,
+        reliable_size
+    },
+    reset_stream_at_tag!()
+);
@@ -235,6 +235,12 @@ impl<T> IntervalSet<T> {
         self.intervals.clear()
     }
 
+    /// Releases any excess capacity held by the set
+    #[inline]
+    pub fn shrink_to_fit(&mut self) {
+        self.intervals.shrink_to_fit()
+    }
+
     /// Removes the lowest `Interval` in the set, if any
     ///
     /// # Examples
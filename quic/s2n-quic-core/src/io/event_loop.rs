@@ -11,7 +11,9 @@ use crate::{
 };
 use core::pin::Pin;
 
+pub mod profile;
 pub mod select;
+use profile::{Phase, Profiler};
 use select::Select;
 
 pub trait Stats {
@@ -25,6 +27,7 @@ pub struct EventLoop<E, C, R, T, S> {
     pub tx: T,
     pub cooldown: Cooldown,
     pub stats: S,
+    pub profiler: Profiler,
 }
 
 impl<E, C, R, T, S> EventLoop<E, C, R, T, S>
@@ -44,6 +47,7 @@ where
             mut tx,
             mut cooldown,
             mut stats,
+            mut profiler,
         } = self;
 
         /// Creates a event publisher with the endpoint's subscriber
@@ -89,6 +93,9 @@ where
                 Pin::new_unchecked(&mut wakeups)
             };
 
+            // Connections process application wakeups while the future is polled
+            let wakeups = profiler.measure_poll(Phase::ConnectionProcessing, wakeups);
+
             // Poll for timer expiration
             let timer_ready = timer.ready();
 
@@ -128,7 +135,7 @@ where
                 Some(Ok(())) => {
                     // we received some packets. give them to the endpoint.
                     rx.queue(|queue| {
-                        endpoint.receive(queue, &clock);
+                        profiler.measure(Phase::RxParse, || endpoint.receive(queue, &clock));
                     });
                 }
                 Some(Err(error)) => {
@@ -162,7 +169,7 @@ where
 
             // Let the endpoint transmit, if possible
             tx.queue(|queue| {
-                endpoint.transmit(queue, &clock);
+                profiler.measure(Phase::TxPacking, || endpoint.transmit(queue, &clock));
             });
 
             // Get the next expiration from the endpoint and update the timer
@@ -184,6 +191,10 @@ where
                     processing_duration,
                 },
             );
+
+            if let Some(profile) = profiler.on_interval(sleep_timestamp) {
+                publisher!(sleep_timestamp).on_platform_event_loop_profile(profile);
+            }
         }
     }
 }
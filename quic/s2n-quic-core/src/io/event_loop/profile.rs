@@ -0,0 +1,364 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Self-profiling for the endpoint event loop
+//!
+//! When enabled, the event loop measures the wall-clock time spent in each [`Phase`] and
+//! publishes the totals in a `PlatformEventLoopProfile` event once per interval. Code running
+//! inside of the event loop can attribute time to a more specific phase with [`scope`]. That time
+//! is taken out of the enclosing phase so the phases never overlap.
+//!
+//! Scopes only touch the profiler's thread-local state while at least one profiler is enabled in
+//! the process so they're cheap enough for per-packet code, like encryption, when profiling is off.
+//!
+//! Profiling requires the `std` feature; without it, the profiler is always disabled.
+
+use crate::{event, time::Timestamp};
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Phase {
+    RxParse,
+    Crypto,
+    ConnectionProcessing,
+    TxPacking,
+    Timers,
+}
+
+const PHASES: usize = 5;
+
+type Totals = [Duration; PHASES];
+
+#[cfg(feature = "std")]
+mod state {
+    use super::*;
+    use std::{
+        cell::RefCell,
+        sync::atomic::{AtomicUsize, Ordering},
+        time::Instant,
+    };
+
+    /// The number of enabled profilers in the process
+    static ENABLED: AtomicUsize = AtomicUsize::new(0);
+
+    #[inline]
+    pub fn is_any_enabled() -> bool {
+        ENABLED.load(Ordering::Relaxed) > 0
+    }
+
+    #[inline]
+    pub fn on_enabled() {
+        ENABLED.fetch_add(1, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub fn on_disabled() {
+        ENABLED.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    /// The profile for the event loop currently running on this thread
+    pub struct Active {
+        phase: Phase,
+        since: Instant,
+        pub totals: Totals,
+    }
+
+    impl Active {
+        #[inline]
+        pub fn new(phase: Phase, totals: Totals) -> Self {
+            Self {
+                phase,
+                since: Instant::now(),
+                totals,
+            }
+        }
+
+        /// Charges the time since the last transition to the current phase and switches to `next`
+        #[inline]
+        pub fn transition(&mut self, next: Phase) -> Phase {
+            let now = Instant::now();
+            self.totals[self.phase as usize] += now.saturating_duration_since(self.since);
+            self.since = now;
+            core::mem::replace(&mut self.phase, next)
+        }
+    }
+
+    std::thread_local! {
+        pub static ACTIVE: RefCell<Option<Active>> = const { RefCell::new(None) };
+    }
+}
+
+/// Attributes the time until the returned guard is dropped to `phase`
+///
+/// This has no effect outside of a phase measured by an enabled [`Profiler`].
+#[inline]
+pub fn scope(phase: Phase) -> Scope {
+    #[cfg(feature = "std")]
+    {
+        if !state::is_any_enabled() {
+            return Scope { previous: None };
+        }
+
+        let previous = state::ACTIVE.with(|active| {
+            let mut active = active.try_borrow_mut().ok()?;
+            Some(active.as_mut()?.transition(phase))
+        });
+        Scope { previous }
+    }
+
+    #[cfg(not(feature = "std"))]
+    {
+        let _ = phase;
+        Scope {}
+    }
+}
+
+#[must_use = "the phase ends when the scope is dropped"]
+pub struct Scope {
+    #[cfg(feature = "std")]
+    previous: Option<Phase>,
+}
+
+impl Drop for Scope {
+    #[inline]
+    fn drop(&mut self) {
+        #[cfg(feature = "std")]
+        if let Some(previous) = self.previous {
+            state::ACTIVE.with(|active| {
+                if let Ok(mut active) = active.try_borrow_mut() {
+                    if let Some(active) = active.as_mut() {
+                        active.transition(previous);
+                    }
+                }
+            });
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Profiler {
+    interval: Option<Duration>,
+    interval_start: Option<Timestamp>,
+    totals: Totals,
+}
+
+impl Profiler {
+    /// Creates a profiler which publishes the time spent in each phase every `interval`
+    ///
+    /// Profiling is disabled if `interval` is `None`.
+    pub fn new(interval: Option<Duration>) -> Self {
+        let interval = interval.filter(|_| cfg!(feature = "std"));
+
+        #[cfg(feature = "std")]
+        if interval.is_some() {
+            state::on_enabled();
+        }
+
+        Self {
+            interval,
+            interval_start: None,
+            totals: Totals::default(),
+        }
+    }
+
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        self.interval.is_some()
+    }
+
+    /// Calls `f`, attributing the time it takes to `phase`
+    #[inline]
+    pub fn measure<R>(&mut self, phase: Phase, f: impl FnOnce() -> R) -> R {
+        if !self.is_enabled() {
+            return f();
+        }
+
+        #[cfg(feature = "std")]
+        {
+            let active = state::Active::new(phase, self.totals);
+            state::ACTIVE.with(|cell| *cell.borrow_mut() = Some(active));
+
+            let result = f();
+
+            if let Some(mut active) = state::ACTIVE.with(|cell| cell.borrow_mut().take()) {
+                active.transition(phase);
+                self.totals = active.totals;
+            }
+
+            result
+        }
+
+        #[cfg(not(feature = "std"))]
+        {
+            let _ = phase;
+            f()
+        }
+    }
+
+    /// Wraps `future` so the time spent polling it is attributed to `phase`
+    #[inline]
+    pub fn measure_poll<F: Future + Unpin>(&mut self, phase: Phase, future: F) -> Measure<'_, F> {
+        Measure {
+            profiler: self,
+            phase,
+            future,
+        }
+    }
+
+    /// Returns the profile for the current interval, if it has elapsed
+    pub fn on_interval(
+        &mut self,
+        now: Timestamp,
+    ) -> Option<event::builder::PlatformEventLoopProfile> {
+        let interval = self.interval?;
+        let start = *self.interval_start.get_or_insert(now);
+        let elapsed = now.saturating_duration_since(start);
+
+        if elapsed < interval {
+            return None;
+        }
+
+        self.interval_start = Some(now);
+        let totals = core::mem::take(&mut self.totals);
+
+        Some(event::builder::PlatformEventLoopProfile {
+            interval: elapsed,
+            rx_parse: totals[Phase::RxParse as usize],
+            crypto: totals[Phase::Crypto as usize],
+            connection_processing: totals[Phase::ConnectionProcessing as usize],
+            tx_packing: totals[Phase::TxPacking as usize],
+            timers: totals[Phase::Timers as usize],
+        })
+    }
+}
+
+impl Drop for Profiler {
+    #[inline]
+    fn drop(&mut self) {
+        #[cfg(feature = "std")]
+        if self.is_enabled() {
+            state::on_disabled();
+        }
+    }
+}
+
+/// A future which attributes the time spent polling it to a phase
+pub struct Measure<'a, F> {
+    profiler: &'a mut Profiler,
+    phase: Phase,
+    future: F,
+}
+
+impl<F: Future + Unpin> Future for Measure<'_, F> {
+    type Output = F::Output;
+
+    #[inline]
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let Self {
+            profiler,
+            phase,
+            future,
+        } = &mut *self;
+        profiler.measure(*phase, || Pin::new(future).poll(cx))
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::time::{testing::Clock, Clock as _};
+
+    fn spin(duration: Duration) {
+        let start = std::time::Instant::now();
+        while start.elapsed() < duration {}
+    }
+
+    #[test]
+    fn disabled_test() {
+        let mut profiler = Profiler::new(None);
+        let now = Clock::default().get_time();
+
+        assert_eq!(profiler.measure(Phase::RxParse, || 123), 123);
+        assert!(profiler.on_interval(now).is_none());
+        assert!(profiler
+            .on_interval(now + Duration::from_secs(10))
+            .is_none());
+    }
+
+    #[test]
+    fn phases_test() {
+        let mut profiler = Profiler::new(Some(Duration::from_secs(1)));
+        let now = Clock::default().get_time();
+
+        // the first call starts the interval
+        assert!(profiler.on_interval(now).is_none());
+
+        profiler.measure(Phase::RxParse, || {
+            spin(Duration::from_millis(1));
+            let _scope = scope(Phase::ConnectionProcessing);
+            spin(Duration::from_millis(1));
+            {
+                let _scope = scope(Phase::Crypto);
+                spin(Duration::from_millis(1));
+            }
+        });
+
+        profiler.measure(Phase::TxPacking, || {
+            let _scope = scope(Phase::Timers);
+            spin(Duration::from_millis(1));
+        });
+
+        // scopes have no effect outside of a measured phase
+        {
+            let _scope = scope(Phase::Crypto);
+            spin(Duration::from_millis(1));
+        }
+
+        assert!(profiler
+            .on_interval(now + Duration::from_millis(999))
+            .is_none());
+
+        let profile = profiler.on_interval(now + Duration::from_secs(1)).unwrap();
+        assert_eq!(profile.interval, Duration::from_secs(1));
+        for duration in [
+            profile.rx_parse,
+            profile.connection_processing,
+            profile.crypto,
+            profile.timers,
+        ] {
+            assert!(duration >= Duration::from_millis(1), "{profile:?}");
+        }
+
+        // the totals are reset for the next interval
+        let profile = profiler.on_interval(now + Duration::from_secs(2)).unwrap();
+        assert_eq!(profile.rx_parse, Duration::ZERO);
+        assert_eq!(profile.crypto, Duration::ZERO);
+    }
+
+    #[test]
+    fn measure_poll_test() {
+        let mut profiler = Profiler::new(Some(Duration::from_secs(1)));
+        let now = Clock::default().get_time();
+        assert!(profiler.on_interval(now).is_none());
+
+        let future = core::future::poll_fn(|_| {
+            spin(Duration::from_millis(1));
+            Poll::Ready(())
+        });
+        let mut future = profiler.measure_poll(Phase::ConnectionProcessing, future);
+        let waker = crate::task::waker::noop();
+        let mut cx = Context::from_waker(&waker);
+        assert!(Pin::new(&mut future).poll(&mut cx).is_ready());
+
+        let profile = profiler.on_interval(now + Duration::from_secs(1)).unwrap();
+        assert!(
+            profile.connection_processing >= Duration::from_millis(1),
+            "{profile:?}"
+        );
+    }
+}
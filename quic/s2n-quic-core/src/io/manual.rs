@@ -0,0 +1,348 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Drives an endpoint from a manual poll loop
+//!
+//! This is useful for environments without an async runtime, such as `no_std` targets, where
+//! the application owns the clock and the UDP socket. Each call to [`Driver::poll`] receives any
+//! pending datagrams, processes application wakeups, and transmits outgoing datagrams. The
+//! returned [`Timestamp`] is the latest time at which `poll` should be called again.
+//! Applications should also call `poll` as soon as a datagram arrives or the [`Context`] waker is
+//! woken.
+
+use crate::{
+    endpoint::{CloseError, Endpoint},
+    inet::datagram,
+    io::{rx, tx},
+    path::{self, mtu},
+    time::{Clock, Timestamp},
+};
+use alloc::vec::Vec;
+use core::task::{Context, Poll};
+
+/// Drives an [`Endpoint`] with user-supplied UDP send and receive callbacks
+pub struct Driver<E: Endpoint> {
+    endpoint: E,
+    rx_buffer: Vec<u8>,
+    tx_buffer: Vec<u8>,
+    /// A datagram in `tx_buffer` which the socket wasn't able to accept
+    tx_pending: Option<(E::PathHandle, usize)>,
+    is_closed: bool,
+}
+
+impl<E: Endpoint> Driver<E> {
+    /// Creates a driver for the provided endpoint
+    ///
+    /// The datagram buffers are sized to the configured maximum MTU.
+    pub fn new(mut endpoint: E, mtu_config: mtu::Config) -> Self {
+        let max_mtu: u16 = mtu_config.max_mtu().into();
+        let max_mtu = max_mtu as usize;
+
+        endpoint.set_mtu_config(mtu_config);
+
+        Self {
+            endpoint,
+            rx_buffer: alloc::vec![0; max_mtu],
+            tx_buffer: alloc::vec![0; max_mtu],
+            tx_pending: None,
+            is_closed: false,
+        }
+    }
+
+    /// Returns a reference to the driven endpoint
+    #[inline]
+    pub fn endpoint(&self) -> &E {
+        &self.endpoint
+    }
+
+    /// Returns a mutable reference to the driven endpoint
+    #[inline]
+    pub fn endpoint_mut(&mut self) -> &mut E {
+        &mut self.endpoint
+    }
+
+    /// Performs a single iteration of the endpoint loop
+    ///
+    /// `recv` is called with a buffer to fill with the next received datagram and returns its
+    /// header and length, or `None` if no datagrams are pending.
+    ///
+    /// `send` is called with each outgoing datagram and returns `true` if the socket accepted
+    /// it. Datagrams which fail to send for any other reason should also return `true` and are
+    /// recovered by the endpoint like any other lost packet. Returning `false` means the socket
+    /// is currently full and the datagram was not sent: the driver holds on to it, stops
+    /// transmitting, and retries it first on the next call to `poll`. Applications should call
+    /// `poll` again once the socket is writable.
+    ///
+    /// On success, the latest time at which `poll` should be called again is returned. An error
+    /// is returned once the endpoint has closed.
+    pub fn poll<C, Recv, Send>(
+        &mut self,
+        cx: &mut Context,
+        clock: &C,
+        recv: Recv,
+        mut send: Send,
+    ) -> Result<Option<Timestamp>, CloseError>
+    where
+        C: Clock,
+        Recv: FnMut(&mut [u8]) -> Option<(datagram::Header<E::PathHandle>, usize)>,
+        Send: FnMut(&E::PathHandle, &[u8]) -> bool,
+    {
+        if self.is_closed {
+            return Err(CloseError);
+        }
+
+        let mut rx = RxQueue::new(&mut self.rx_buffer, recv);
+        if !rx::Queue::is_empty(&rx) {
+            self.endpoint.receive(&mut rx, clock);
+        }
+
+        if let Poll::Ready(Err(err)) = self.endpoint.poll_wakeups(cx, clock) {
+            self.is_closed = true;
+            return Err(err);
+        }
+
+        // the previously blocked datagram needs to go out before any new ones
+        if let Some((handle, len)) = self.tx_pending {
+            if !send(&handle, &self.tx_buffer[..len]) {
+                return Ok(self.endpoint.timeout());
+            }
+            self.tx_pending = None;
+        }
+
+        let mut tx = TxQueue {
+            buffer: &mut self.tx_buffer,
+            pending: &mut self.tx_pending,
+            send,
+        };
+        self.endpoint.transmit(&mut tx, clock);
+
+        Ok(self.endpoint.timeout())
+    }
+}
+
+struct RxQueue<'a, Handle, Recv> {
+    buffer: &'a mut [u8],
+    recv: Recv,
+    pending: Option<(datagram::Header<Handle>, usize)>,
+}
+
+impl<'a, Handle, Recv> RxQueue<'a, Handle, Recv>
+where
+    Recv: FnMut(&mut [u8]) -> Option<(datagram::Header<Handle>, usize)>,
+{
+    #[inline]
+    fn new(buffer: &'a mut [u8], mut recv: Recv) -> Self {
+        // receive the first datagram so we know if the queue is empty
+        let pending = recv(buffer);
+        Self {
+            buffer,
+            recv,
+            pending,
+        }
+    }
+}
+
+impl<Handle, Recv> rx::Queue for RxQueue<'_, Handle, Recv>
+where
+    Handle: path::Handle,
+    Recv: FnMut(&mut [u8]) -> Option<(datagram::Header<Handle>, usize)>,
+{
+    type Handle = Handle;
+
+    #[inline]
+    fn for_each<F: FnMut(datagram::Header<Self::Handle>, &mut [u8])>(&mut self, mut on_packet: F) {
+        while let Some((header, len)) = self.pending.take() {
+            let len = len.min(self.buffer.len());
+            on_packet(header, &mut self.buffer[..len]);
+            self.pending = (self.recv)(self.buffer);
+        }
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.pending.is_none()
+    }
+}
+
+struct TxQueue<'a, Handle, Send> {
+    buffer: &'a mut [u8],
+    pending: &'a mut Option<(Handle, usize)>,
+    send: Send,
+}
+
+impl<Handle, Send> tx::Queue for TxQueue<'_, Handle, Send>
+where
+    Handle: path::Handle,
+    Send: FnMut(&Handle, &[u8]) -> bool,
+{
+    type Handle = Handle;
+
+    #[inline]
+    fn push<M: tx::Message<Handle = Self::Handle>>(
+        &mut self,
+        mut message: M,
+    ) -> Result<tx::Outcome, tx::Error> {
+        if self.pending.is_some() {
+            return Err(tx::Error::AtCapacity);
+        }
+
+        let len = message.write_payload(tx::PayloadBuffer::new(self.buffer), 0)?;
+
+        if len == 0 {
+            return Err(tx::Error::EmptyPayload);
+        }
+
+        let handle = *message.path_handle();
+        if !(self.send)(&handle, &self.buffer[..len]) {
+            // the payload has already been written so keep it around until the socket has room
+            *self.pending = Some((handle, len));
+        }
+
+        Ok(tx::Outcome { len, index: 0 })
+    }
+
+    #[inline]
+    fn capacity(&self) -> usize {
+        if self.pending.is_some() {
+            0
+        } else {
+            usize::MAX
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        event::testing::endpoint::Subscriber,
+        inet::{ExplicitCongestionNotification, SocketAddressV4},
+        path::RemoteAddress,
+        time::{clock::testing::Clock as TestClock, Duration},
+    };
+
+    struct Echo {
+        received: Vec<(RemoteAddress, Vec<u8>)>,
+        wakeups: usize,
+        subscriber: Subscriber,
+    }
+
+    impl Endpoint for Echo {
+        type PathHandle = RemoteAddress;
+        type Subscriber = Subscriber;
+
+        const ENDPOINT_TYPE: crate::endpoint::Type = crate::endpoint::Type::Server;
+
+        fn receive<Rx, C>(&mut self, rx: &mut Rx, _clock: &C)
+        where
+            Rx: rx::Queue<Handle = Self::PathHandle>,
+            C: Clock,
+        {
+            rx.for_each(|header, payload| {
+                self.received.push((header.path, payload.to_vec()));
+            });
+        }
+
+        fn transmit<Tx, C>(&mut self, tx: &mut Tx, _clock: &C)
+        where
+            Tx: tx::Queue<Handle = Self::PathHandle>,
+            C: Clock,
+        {
+            while tx.has_capacity() {
+                let Some((addr, payload)) = self.received.pop() else {
+                    break;
+                };
+                let _ = tx.push((addr, payload));
+            }
+        }
+
+        fn poll_wakeups<C: Clock>(
+            &mut self,
+            _cx: &mut Context<'_>,
+            _clock: &C,
+        ) -> Poll<Result<usize, CloseError>> {
+            self.wakeups += 1;
+            if self.wakeups > 2 {
+                return Poll::Ready(Err(CloseError));
+            }
+            Poll::Pending
+        }
+
+        fn timeout(&self) -> Option<Timestamp> {
+            None
+        }
+
+        fn set_mtu_config(&mut self, _mtu_config: mtu::Config) {}
+
+        fn subscriber(&mut self) -> &mut Self::Subscriber {
+            &mut self.subscriber
+        }
+    }
+
+    #[test]
+    fn driver_test() {
+        let endpoint = Echo {
+            received: Vec::new(),
+            wakeups: 0,
+            subscriber: Subscriber::no_snapshot(),
+        };
+        let mut driver = Driver::new(endpoint, mtu::Config::default());
+        let mut clock = TestClock::default();
+        let waker = futures_test::task::noop_waker();
+        let mut cx = Context::from_waker(&waker);
+
+        let addr: RemoteAddress = SocketAddressV4::new([127, 0, 0, 1], 4433).into();
+        let mut inbox = alloc::vec![&b"hello"[..], &b"world"[..]];
+        let mut outbox = Vec::new();
+        let mut attempts = 0;
+
+        let timeout = driver
+            .poll(
+                &mut cx,
+                &clock,
+                |buffer| {
+                    let payload = inbox.pop()?;
+                    buffer[..payload.len()].copy_from_slice(payload);
+                    let header = datagram::Header {
+                        path: addr,
+                        ecn: ExplicitCongestionNotification::default(),
+                    };
+                    Some((header, payload.len()))
+                },
+                |_handle, _payload| {
+                    attempts += 1;
+                    // the socket is full
+                    false
+                },
+            )
+            .unwrap();
+
+        assert_eq!(timeout, None);
+        // transmission stops after the first blocked datagram
+        assert_eq!(attempts, 1);
+
+        clock.inc_by(Duration::from_millis(1));
+
+        driver
+            .poll(
+                &mut cx,
+                &clock,
+                |_| None,
+                |handle, payload| {
+                    outbox.push((*handle, payload.to_vec()));
+                    true
+                },
+            )
+            .unwrap();
+
+        // the blocked datagram is retried before the remaining ones
+        assert_eq!(
+            outbox,
+            [(addr, b"hello".to_vec()), (addr, b"world".to_vec())]
+        );
+
+        // the endpoint closes on the third poll
+        assert!(driver.poll(&mut cx, &clock, |_| None, |_, _| true).is_err());
+        assert!(driver.poll(&mut cx, &clock, |_| None, |_, _| true).is_err());
+    }
+}
@@ -8,6 +8,7 @@ use core::{
 };
 
 pub mod handle_map;
+pub mod limit;
 pub mod router;
 
 pub trait Tx: Sized {
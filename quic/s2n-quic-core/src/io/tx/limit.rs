@@ -0,0 +1,94 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A transmission queue which limits the number of messages pushed into another queue
+
+use crate::io::tx;
+
+pub struct Queue<'a, Tx: tx::Queue> {
+    tx: &'a mut Tx,
+    remaining: usize,
+}
+
+impl<'a, Tx: tx::Queue> Queue<'a, Tx> {
+    /// Wraps the queue so at most `limit` messages are pushed into it
+    #[inline]
+    pub fn new(tx: &'a mut Tx, limit: usize) -> Self {
+        Self {
+            tx,
+            remaining: limit,
+        }
+    }
+
+    /// Returns the number of messages which can still be pushed before reaching the limit
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.remaining
+    }
+}
+
+impl<Tx: tx::Queue> tx::Queue for Queue<'_, Tx> {
+    type Handle = Tx::Handle;
+
+    const SUPPORTS_ECN: bool = Tx::SUPPORTS_ECN;
+    const SUPPORTS_PACING: bool = Tx::SUPPORTS_PACING;
+    const SUPPORTS_FLOW_LABELS: bool = Tx::SUPPORTS_FLOW_LABELS;
+
+    #[inline]
+    fn push<M: tx::Message<Handle = Self::Handle>>(
+        &mut self,
+        message: M,
+    ) -> Result<tx::Outcome, tx::Error> {
+        if self.remaining == 0 {
+            return Err(tx::Error::AtCapacity);
+        }
+
+        let outcome = self.tx.push(message)?;
+        self.remaining -= 1;
+        Ok(outcome)
+    }
+
+    #[inline]
+    fn flush(&mut self) {
+        self.tx.flush()
+    }
+
+    #[inline]
+    fn capacity(&self) -> usize {
+        self.tx.capacity().min(self.remaining)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        io::{testing, tx::Queue as _},
+        path::Handle as _,
+    };
+
+    #[test]
+    fn limit_test() {
+        let mut channel = testing::Channel::default();
+
+        tx::Tx::queue(&mut channel, |queue| {
+            let mut limited = Queue::new(queue, 2);
+            let handle = testing::Handle::from_remote_address(Default::default());
+
+            assert_eq!(limited.capacity(), 2);
+            limited.push((handle, &[1, 2, 3][..])).unwrap();
+            limited.push((handle, &[4, 5, 6][..])).unwrap();
+
+            assert!(!limited.has_capacity());
+            assert_eq!(limited.remaining(), 0);
+            assert!(matches!(
+                limited.push((handle, &[7, 8, 9][..])),
+                Err(tx::Error::AtCapacity)
+            ));
+        });
+
+        assert!(channel.pop().is_some());
+        assert!(channel.pop().is_some());
+        assert!(channel.pop().is_none());
+    }
+}
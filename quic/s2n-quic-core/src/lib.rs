@@ -17,6 +17,8 @@ pub mod ack;
 pub mod application;
 #[cfg(feature = "alloc")]
 pub mod buffer;
+#[cfg(any(test, feature = "conformance"))]
+pub mod conformance;
 pub mod connection;
 pub mod counter;
 pub mod crypto;
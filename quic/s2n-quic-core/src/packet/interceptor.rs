@@ -9,11 +9,23 @@ use crate::{
     varint::VarInt,
 };
 use core::ops::RangeInclusive;
-use s2n_codec::encoder::scatter;
+use s2n_codec::{encoder::scatter, Encoder as _};
 
 pub use s2n_codec::{DecoderBufferMut, EncoderBuffer};
+pub mod frame;
 pub mod loss;
+#[cfg(feature = "std")]
+pub mod mirror;
+#[cfg(feature = "std")]
+pub mod pcap;
+#[cfg(feature = "alloc")]
+pub mod rewrite;
+pub use frame::{FrameInterceptor, Frames};
 pub use loss::Loss;
+#[cfg(feature = "std")]
+pub use mirror::Mirror;
+#[cfg(feature = "alloc")]
+pub use rewrite::Rewrite;
 
 /// TODO add `non_exhaustive` once/if this feature is stable
 #[derive(Debug)]
@@ -74,6 +86,17 @@ pub trait Interceptor: 'static + Send {
         payload
     }
 
+    /// Returns `true` if the packet passed to `intercept_rx_payload` should be dropped
+    ///
+    /// Dropped packets are treated as if they never arrived, so none of the frames are processed
+    /// and the packet isn't acknowledged.
+    #[inline(always)]
+    fn drop_rx_packet(&mut self, subject: &Subject, packet: &Packet) -> bool {
+        let _ = subject;
+        let _ = packet;
+        false
+    }
+
     #[inline(always)]
     fn intercept_tx_datagram(
         &mut self,
@@ -97,6 +120,23 @@ pub trait Interceptor: 'static + Send {
         let _ = packet;
         let _ = payload;
     }
+
+    /// Writes a datagram previously held by the interceptor into the empty `payload`
+    ///
+    /// This is called before the connection writes any packets into a datagram, which allows
+    /// interceptors to delay or duplicate transmitted datagrams. If anything is written, the
+    /// datagram is sent as-is in place of new packets.
+    #[inline(always)]
+    fn release_tx_datagram(
+        &mut self,
+        subject: &Subject,
+        datagram: &Datagram,
+        payload: &mut EncoderBuffer,
+    ) {
+        let _ = subject;
+        let _ = datagram;
+        let _ = payload;
+    }
 }
 
 #[derive(Debug, Default)]
@@ -143,6 +183,14 @@ where
         self.1.intercept_rx_payload(subject, packet, payload)
     }
 
+    #[inline(always)]
+    fn drop_rx_packet(&mut self, subject: &Subject, packet: &Packet) -> bool {
+        // query both interceptors so neither carries the decision over to the next packet
+        let a = self.0.drop_rx_packet(subject, packet);
+        let b = self.1.drop_rx_packet(subject, packet);
+        a || b
+    }
+
     #[inline(always)]
     fn intercept_tx_datagram(
         &mut self,
@@ -164,6 +212,21 @@ where
         self.0.intercept_tx_payload(subject, packet, payload);
         self.1.intercept_tx_payload(subject, packet, payload);
     }
+
+    #[inline(always)]
+    fn release_tx_datagram(
+        &mut self,
+        subject: &Subject,
+        datagram: &Datagram,
+        payload: &mut EncoderBuffer,
+    ) {
+        self.0.release_tx_datagram(subject, datagram, payload);
+
+        // only one datagram can be released at a time
+        if payload.is_empty() {
+            self.1.release_tx_datagram(subject, datagram, payload);
+        }
+    }
 }
 
 #[derive(Debug, Default)]
@@ -235,6 +298,20 @@ impl<T: Interceptor> Interceptor for Option<T> {
         }
     }
 
+    #[inline]
+    fn intercept_rx_datagram<'a>(
+        &mut self,
+        subject: &Subject,
+        datagram: &Datagram,
+        payload: DecoderBufferMut<'a>,
+    ) -> DecoderBufferMut<'a> {
+        if let Some(inner) = self.as_mut() {
+            inner.intercept_rx_datagram(subject, datagram, payload)
+        } else {
+            payload
+        }
+    }
+
     #[inline]
     fn intercept_rx_payload<'a>(
         &mut self,
@@ -249,6 +326,24 @@ impl<T: Interceptor> Interceptor for Option<T> {
         }
     }
 
+    #[inline]
+    fn drop_rx_packet(&mut self, subject: &Subject, packet: &Packet) -> bool {
+        self.as_mut()
+            .map_or(false, |inner| inner.drop_rx_packet(subject, packet))
+    }
+
+    #[inline]
+    fn intercept_tx_datagram(
+        &mut self,
+        subject: &Subject,
+        datagram: &Datagram,
+        payload: &mut EncoderBuffer,
+    ) {
+        if let Some(inner) = self.as_mut() {
+            inner.intercept_tx_datagram(subject, datagram, payload)
+        }
+    }
+
     #[inline]
     fn intercept_tx_payload(
         &mut self,
@@ -260,4 +355,16 @@ impl<T: Interceptor> Interceptor for Option<T> {
             inner.intercept_tx_payload(subject, packet, payload)
         }
     }
+
+    #[inline]
+    fn release_tx_datagram(
+        &mut self,
+        subject: &Subject,
+        datagram: &Datagram,
+        payload: &mut EncoderBuffer,
+    ) {
+        if let Some(inner) = self.as_mut() {
+            inner.release_tx_datagram(subject, datagram, payload)
+        }
+    }
 }
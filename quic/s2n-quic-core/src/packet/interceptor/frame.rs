@@ -0,0 +1,151 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Observes and injects individual frames on live connections
+//!
+//! This is useful for testing how a peer handles frames the library doesn't normally send,
+//! such as prototyped extension frames or frame types the peer doesn't understand.
+
+use super::{DecoderBufferMut, EncoderBuffer, Interceptor, Packet};
+use crate::{
+    event::api::Subject,
+    frame::{Frame, FrameRef},
+    varint::VarInt,
+};
+use s2n_codec::{encoder::scatter, DecoderBuffer, Encoder};
+
+/// Trait which enables an application to observe and inject frames
+pub trait FrameInterceptor: 'static + Send {
+    /// Called for each frame in a packet before it is encrypted and transmitted
+    ///
+    /// PADDING frames are not reported.
+    #[inline(always)]
+    fn on_tx_frame(&mut self, subject: &Subject, packet: &Packet, frame: &FrameRef) {
+        let _ = subject;
+        let _ = packet;
+        let _ = frame;
+    }
+
+    /// Called for each frame in a received packet after it is decrypted, but before it is
+    /// processed
+    ///
+    /// PADDING frames are not reported.
+    #[inline(always)]
+    fn on_rx_frame(&mut self, subject: &Subject, packet: &Packet, frame: &FrameRef) {
+        let _ = subject;
+        let _ = packet;
+        let _ = frame;
+    }
+
+    /// Called when a received packet contains a frame that can't be decoded, such as a frame
+    /// with an unknown type
+    ///
+    /// The remaining frames in the packet are not reported.
+    #[inline(always)]
+    fn on_rx_unknown_frame(&mut self, subject: &Subject, packet: &Packet, tag: VarInt) {
+        let _ = subject;
+        let _ = packet;
+        let _ = tag;
+    }
+
+    /// Writes additional frames into a packet before it is encrypted
+    ///
+    /// The frames are written after all of the frames produced by the library, replacing any
+    /// trailing padding. Implementations should only write frames which fit in the
+    /// `remaining_capacity` of the buffer.
+    ///
+    /// Note that injected frames are not tracked for loss recovery and are not retransmitted.
+    #[inline(always)]
+    fn inject_tx_frames(&mut self, subject: &Subject, packet: &Packet, buffer: &mut EncoderBuffer) {
+        let _ = subject;
+        let _ = packet;
+        let _ = buffer;
+    }
+}
+
+/// A packet interceptor which calls a [`FrameInterceptor`] for the frames in each packet
+#[derive(Debug, Default)]
+pub struct Frames<F: FrameInterceptor>(pub F);
+
+impl<F: FrameInterceptor> Interceptor for Frames<F> {
+    #[inline]
+    fn intercept_rx_payload<'a>(
+        &mut self,
+        subject: &Subject,
+        packet: &Packet,
+        payload: DecoderBufferMut<'a>,
+    ) -> DecoderBufferMut<'a> {
+        let payload = payload.into_less_safe_slice();
+        let len = payload.len();
+
+        let mut unknown_frame = None;
+        {
+            let mut buffer = DecoderBufferMut::new(&mut payload[..]);
+            while !buffer.is_empty() {
+                let offset = len - buffer.len();
+
+                match buffer.decode::<FrameRef>() {
+                    Ok((Frame::Padding(_), remaining)) => buffer = remaining,
+                    Ok((frame, remaining)) => {
+                        self.0.on_rx_frame(subject, packet, &frame);
+                        buffer = remaining;
+                    }
+                    Err(_) => {
+                        unknown_frame = Some(offset);
+                        break;
+                    }
+                }
+            }
+        }
+
+        if let Some(offset) = unknown_frame {
+            if let Ok((tag, _)) = DecoderBuffer::new(&payload[offset..]).decode::<VarInt>() {
+                self.0.on_rx_unknown_frame(subject, packet, tag);
+            }
+        }
+
+        DecoderBufferMut::new(payload)
+    }
+
+    #[inline]
+    fn intercept_tx_payload(
+        &mut self,
+        subject: &Subject,
+        packet: &Packet,
+        payload: &mut scatter::Buffer,
+    ) {
+        let payload = payload.flatten();
+        let len = payload.len();
+
+        // find where the trailing padding starts so it can be replaced with injected frames
+        let mut padding_start = len;
+        {
+            let mut buffer = DecoderBufferMut::new(payload.as_mut_slice());
+            while !buffer.is_empty() {
+                let offset = len - buffer.len();
+
+                let Ok((frame, remaining)) = buffer.decode::<FrameRef>() else {
+                    padding_start = len;
+                    break;
+                };
+
+                if let Frame::Padding(_) = frame {
+                    padding_start = padding_start.min(offset);
+                } else {
+                    padding_start = len;
+                    self.0.on_tx_frame(subject, packet, &frame);
+                }
+
+                buffer = remaining;
+            }
+        }
+
+        payload.set_position(padding_start);
+        self.0.inject_tx_frames(subject, packet, payload);
+
+        // restore the padding so the packet doesn't shrink
+        if let Some(padding) = len.checked_sub(payload.len()) {
+            payload.write_repeated(padding, 0);
+        }
+    }
+}
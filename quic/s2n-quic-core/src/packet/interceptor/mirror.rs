@@ -0,0 +1,428 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Copies decrypted packets to an external sink, such as an intrusion detection system
+//!
+//! The mirror never blocks or modifies packets. Packets are sampled, and the number of records
+//! and bytes sent to the sink are capped per second. Records which exceed the caps, or which
+//! the sink can't accept, are dropped and counted in [`Stats`].
+
+use super::{DecoderBufferMut, Interceptor, Packet};
+use crate::{event::api::Subject, packet::number::PacketNumberSpace, time::Timestamp};
+use alloc::vec::Vec;
+use core::time::Duration;
+use s2n_codec::{encoder::scatter, Encoder, EncoderValue};
+
+/// The direction of a mirrored packet
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    Rx,
+    Tx,
+}
+
+/// A mirrored packet
+///
+/// When encoded, for example by the unix socket sink, the record uses the following layout,
+/// with all integers in network byte order:
+///
+/// ```text
+/// Record {
+///   Direction (8), // 0 for Rx, 1 for Tx
+///   Connection ID (64),
+///   Packet Number Space (8), // 0 for Initial, 1 for Handshake, 2 for ApplicationData
+///   Packet Number (64),
+///   Elapsed Microseconds (64),
+///   Payload Length (32),
+///   Frames Length (32),
+///   Frames (..),
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Record {
+    pub direction: Direction,
+    /// The internal connection ID, which is stable across connection ID changes
+    pub connection_id: u64,
+    pub packet_number_space: PacketNumberSpace,
+    pub packet_number: u64,
+    /// The time the packet was processed
+    pub timestamp: Timestamp,
+    /// The time since the first packet was mirrored
+    pub elapsed: Duration,
+    /// The length of the decrypted payload
+    pub payload_len: u32,
+    /// The decrypted frames, if enabled with [`Mirror::with_frames`]
+    pub frames: Option<Vec<u8>>,
+}
+
+impl EncoderValue for Record {
+    #[inline]
+    fn encode<E: Encoder>(&self, encoder: &mut E) {
+        let direction: u8 = match self.direction {
+            Direction::Rx => 0,
+            Direction::Tx => 1,
+        };
+        encoder.encode(&direction);
+        encoder.encode(&self.connection_id);
+        let space: u8 = match self.packet_number_space {
+            PacketNumberSpace::Initial => 0,
+            PacketNumberSpace::Handshake => 1,
+            PacketNumberSpace::ApplicationData => 2,
+        };
+        encoder.encode(&space);
+        encoder.encode(&self.packet_number);
+        let elapsed = self.elapsed.as_micros().try_into().unwrap_or(u64::MAX);
+        encoder.encode(&elapsed);
+        encoder.encode(&self.payload_len);
+        let frames = self.frames.as_deref().unwrap_or_default();
+        encoder.encode(&(frames.len() as u32));
+        encoder.write_slice(frames);
+    }
+}
+
+/// A destination for mirrored packets
+///
+/// Implementations must never block, since they are called on the data path.
+pub trait Sink: 'static + Send {
+    /// Sends a record to the sink, returning `false` if it was dropped
+    fn send(&mut self, record: Record) -> bool;
+}
+
+/// Sends records to a bounded channel, dropping them if the channel is full
+impl Sink for std::sync::mpsc::SyncSender<Record> {
+    #[inline]
+    fn send(&mut self, record: Record) -> bool {
+        self.try_send(record).is_ok()
+    }
+}
+
+/// Sends each encoded record as a datagram
+///
+/// The socket should be connected and in non-blocking mode, so records are dropped rather than
+/// blocking the endpoint when the receiver falls behind.
+#[cfg(unix)]
+impl Sink for std::os::unix::net::UnixDatagram {
+    #[inline]
+    fn send(&mut self, record: Record) -> bool {
+        let mut buffer = alloc::vec![0; record.encoding_size()];
+        s2n_codec::EncoderBuffer::new(&mut buffer).encode(&record);
+        std::os::unix::net::UnixDatagram::send(self, &buffer).is_ok()
+    }
+}
+
+/// Counters for the mirrored packets
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Stats {
+    /// The number of records accepted by the sink
+    pub sent: u64,
+    /// The number of sampled packets which exceeded the rate caps
+    pub rate_limited: u64,
+    /// The number of records the sink was unable to accept
+    pub sink_dropped: u64,
+}
+
+/// A packet interceptor which copies sampled packets to a [`Sink`]
+///
+/// By default, every packet is sampled, only metadata is mirrored, and at most 1000 records and
+/// 1MB are sent per second.
+#[derive(Debug)]
+pub struct Mirror<S: Sink> {
+    sink: S,
+    sample_interval: u32,
+    frames: bool,
+    max_records_per_second: u32,
+    max_bytes_per_second: u32,
+    counter: u32,
+    first: Option<Timestamp>,
+    window: Option<Window>,
+    stats: Stats,
+}
+
+#[derive(Debug)]
+struct Window {
+    start: Timestamp,
+    records: u32,
+    bytes: u32,
+}
+
+impl<S: Sink> Mirror<S> {
+    pub fn new(sink: S) -> Self {
+        Self {
+            sink,
+            sample_interval: 1,
+            frames: false,
+            max_records_per_second: 1000,
+            max_bytes_per_second: 1_000_000,
+            counter: 0,
+            first: None,
+            window: None,
+            stats: Stats::default(),
+        }
+    }
+
+    /// Mirrors one out of every `interval` packets
+    ///
+    /// A value of 0 is treated as 1.
+    pub fn with_sample_interval(mut self, interval: u32) -> Self {
+        self.sample_interval = interval.max(1);
+        self
+    }
+
+    /// Includes the decrypted frames in each record, rather than only the packet metadata
+    pub fn with_frames(mut self, enabled: bool) -> Self {
+        self.frames = enabled;
+        self
+    }
+
+    /// Caps the number of records sent to the sink each second
+    pub fn with_max_records_per_second(mut self, value: u32) -> Self {
+        self.max_records_per_second = value;
+        self
+    }
+
+    /// Caps the number of frame bytes sent to the sink each second
+    ///
+    /// Records which would exceed the cap are dropped entirely, rather than being truncated.
+    pub fn with_max_bytes_per_second(mut self, value: u32) -> Self {
+        self.max_bytes_per_second = value;
+        self
+    }
+
+    /// Returns the counters for the mirrored packets
+    #[inline]
+    pub fn stats(&self) -> Stats {
+        self.stats
+    }
+
+    /// Returns the sink
+    #[inline]
+    pub fn sink(&self) -> &S {
+        &self.sink
+    }
+
+    #[inline]
+    fn should_sample(&mut self) -> bool {
+        self.counter += 1;
+        if self.counter < self.sample_interval {
+            return false;
+        }
+        self.counter = 0;
+        true
+    }
+
+    /// Reserves space for a record in the current window, returning `false` if it exceeds the
+    /// caps
+    #[inline]
+    fn reserve(&mut self, now: Timestamp, bytes: usize) -> bool {
+        let window = match &mut self.window {
+            Some(window)
+                if now.saturating_duration_since(window.start) < Duration::from_secs(1) =>
+            {
+                window
+            }
+            window => window.insert(Window {
+                start: now,
+                records: 0,
+                bytes: 0,
+            }),
+        };
+
+        let bytes = bytes.try_into().unwrap_or(u32::MAX);
+        let records = window.records.saturating_add(1);
+        let Some(bytes) = window.bytes.checked_add(bytes) else {
+            return false;
+        };
+
+        if records > self.max_records_per_second || bytes > self.max_bytes_per_second {
+            return false;
+        }
+
+        window.records = records;
+        window.bytes = bytes;
+        true
+    }
+
+    #[inline]
+    fn mirror(
+        &mut self,
+        direction: Direction,
+        subject: &Subject,
+        packet: &Packet,
+        payload_len: usize,
+        frames: impl FnOnce() -> Vec<u8>,
+    ) {
+        if !self.should_sample() {
+            return;
+        }
+
+        let frames_len = if self.frames { payload_len } else { 0 };
+        if !self.reserve(packet.timestamp, frames_len) {
+            self.stats.rate_limited += 1;
+            return;
+        }
+
+        let connection_id = match subject {
+            Subject::Connection { id, .. } => *id,
+            _ => u64::MAX,
+        };
+        let first = *self.first.get_or_insert(packet.timestamp);
+
+        let record = Record {
+            direction,
+            connection_id,
+            packet_number_space: packet.number.space(),
+            packet_number: packet.number.as_u64(),
+            timestamp: packet.timestamp,
+            elapsed: packet.timestamp.saturating_duration_since(first),
+            payload_len: payload_len.try_into().unwrap_or(u32::MAX),
+            frames: self.frames.then(frames),
+        };
+
+        if self.sink.send(record) {
+            self.stats.sent += 1;
+        } else {
+            self.stats.sink_dropped += 1;
+        }
+    }
+}
+
+impl<S: Sink> Interceptor for Mirror<S> {
+    #[inline]
+    fn intercept_rx_payload<'a>(
+        &mut self,
+        subject: &Subject,
+        packet: &Packet,
+        payload: DecoderBufferMut<'a>,
+    ) -> DecoderBufferMut<'a> {
+        let payload = payload.into_less_safe_slice();
+        self.mirror(Direction::Rx, subject, packet, payload.len(), || {
+            payload.to_vec()
+        });
+        DecoderBufferMut::new(payload)
+    }
+
+    #[inline]
+    fn intercept_tx_payload(
+        &mut self,
+        subject: &Subject,
+        packet: &Packet,
+        payload: &mut scatter::Buffer,
+    ) {
+        let (inner, extra) = payload.inner_mut();
+        let extra = extra.as_deref().unwrap_or_default();
+        let inner: &[u8] = inner.as_mut_slice();
+        let len = inner.len() + extra.len();
+        self.mirror(Direction::Tx, subject, packet, len, || {
+            let mut frames = Vec::with_capacity(len);
+            frames.extend_from_slice(inner);
+            frames.extend_from_slice(extra);
+            frames
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{time::clock::testing::now, varint::VarInt};
+    use std::sync::mpsc;
+
+    fn packet(number: u8, timestamp: Timestamp) -> Packet {
+        Packet {
+            number: PacketNumberSpace::ApplicationData.new_packet_number(VarInt::from_u8(number)),
+            timestamp,
+        }
+    }
+
+    fn rx<S: Sink>(mirror: &mut Mirror<S>, packet: &Packet, payload: &mut [u8]) {
+        let subject = Subject::Connection { id: 1 };
+        mirror.intercept_rx_payload(&subject, packet, DecoderBufferMut::new(payload));
+    }
+
+    #[test]
+    fn sample_test() {
+        let (sender, receiver) = mpsc::sync_channel(16);
+        let mut mirror = Mirror::new(sender).with_sample_interval(3);
+        let now = now();
+
+        for number in 0..9 {
+            rx(&mut mirror, &packet(number, now), &mut [1, 2, 3]);
+        }
+
+        let records: Vec<_> = receiver.try_iter().collect();
+        assert_eq!(records.len(), 3);
+        for record in &records {
+            assert_eq!(record.direction, Direction::Rx);
+            assert_eq!(record.connection_id, 1);
+            assert_eq!(record.payload_len, 3);
+            assert_eq!(record.frames, None);
+        }
+        assert_eq!(records[0].packet_number, 2);
+        assert_eq!(mirror.stats().sent, 3);
+    }
+
+    #[test]
+    fn rate_limit_test() {
+        let (sender, receiver) = mpsc::sync_channel(16);
+        let mut mirror = Mirror::new(sender)
+            .with_frames(true)
+            .with_max_records_per_second(4)
+            .with_max_bytes_per_second(10);
+        let now = now();
+
+        // the byte cap is reached before the record cap
+        for number in 0..4 {
+            rx(&mut mirror, &packet(number, now), &mut [number; 4]);
+        }
+        assert_eq!(receiver.try_iter().count(), 2);
+        assert_eq!(mirror.stats().rate_limited, 2);
+
+        // the caps reset after a second
+        let later = now + Duration::from_secs(1);
+        rx(&mut mirror, &packet(4, later), &mut [4; 4]);
+        let record = receiver.try_recv().unwrap();
+        assert_eq!(record.frames.as_deref(), Some(&[4; 4][..]));
+        assert_eq!(record.elapsed, Duration::from_secs(1));
+    }
+
+    #[test]
+    fn sink_full_test() {
+        let (sender, receiver) = mpsc::sync_channel(1);
+        let mut mirror = Mirror::new(sender);
+        let now = now();
+
+        rx(&mut mirror, &packet(0, now), &mut []);
+        rx(&mut mirror, &packet(1, now), &mut []);
+
+        assert_eq!(receiver.try_iter().count(), 1);
+        assert_eq!(mirror.stats().sink_dropped, 1);
+    }
+
+    #[test]
+    fn encoding_test() {
+        let record = Record {
+            direction: Direction::Tx,
+            connection_id: 1,
+            packet_number_space: PacketNumberSpace::Handshake,
+            packet_number: 2,
+            timestamp: now(),
+            elapsed: Duration::from_micros(3),
+            payload_len: 4,
+            frames: Some(alloc::vec![5, 6]),
+        };
+
+        let mut buffer = alloc::vec![0; record.encoding_size()];
+        s2n_codec::EncoderBuffer::new(&mut buffer).encode(&record);
+
+        let mut expected = alloc::vec![1];
+        expected.extend_from_slice(&1u64.to_be_bytes());
+        expected.push(1);
+        expected.extend_from_slice(&2u64.to_be_bytes());
+        expected.extend_from_slice(&3u64.to_be_bytes());
+        expected.extend_from_slice(&4u32.to_be_bytes());
+        expected.extend_from_slice(&2u32.to_be_bytes());
+        expected.extend_from_slice(&[5, 6]);
+        assert_eq!(buffer, expected);
+    }
+}
@@ -0,0 +1,838 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Records sent and received datagrams to a pcapng capture for debugging
+//!
+//! IP and UDP headers are synthesized for each datagram so the capture can be opened directly in
+//! Wireshark. TLS secrets read from a key log are embedded in the capture as Decryption Secrets
+//! Blocks, which allows the QUIC packets to be decrypted without shipping the key log separately.
+//! Since the secrets are usually logged after the first packets of a handshake, captures should
+//! be analyzed in two passes, for example with `tshark -2`.
+//!
+//! Captures can be truncated to a snapshot length and rotated across a fixed number of files,
+//! which bounds the disk usage when capturing on demand. The writer never modifies datagrams and
+//! stops capturing after the first write error, which is reported on stderr and available from
+//! [`Writer::error`].
+
+use super::{Datagram, DecoderBufferMut, EncoderBuffer, Interceptor};
+use crate::{
+    event::api::{SocketAddress, Subject},
+    time::Timestamp,
+};
+use alloc::vec::Vec;
+use core::time::Duration;
+use std::{
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// The interval at which the output is flushed and the key log is checked for new secrets
+const FLUSH_INTERVAL: Duration = Duration::from_millis(100);
+
+const SECTION_HEADER: u32 = 0x0A0D_0D0A;
+const INTERFACE_DESCRIPTION: u32 = 0x0000_0001;
+const ENHANCED_PACKET: u32 = 0x0000_0006;
+const DECRYPTION_SECRETS: u32 = 0x0000_000A;
+const BYTE_ORDER_MAGIC: u32 = 0x1A2B_3C4D;
+/// Raw IP packets, without a link-layer header
+const LINKTYPE_RAW: u16 = 101;
+/// Secrets in the NSS key log format
+const TLS_KEY_LOG: u32 = 0x544C_534B;
+
+const IPV4_HEADER_LEN: usize = 20;
+const UDP_HEADER_LEN: usize = 8;
+
+/// A capture file opened by the [`Writer`]
+pub type Output = Box<dyn Write + Send>;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Direction {
+    Rx,
+    Tx,
+}
+
+/// Counters for the captured datagrams
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Stats {
+    /// The number of datagrams written to the capture
+    pub packets: u64,
+    /// The number of datagrams which were truncated to the snapshot length
+    pub truncated: u64,
+    /// The number of capture files opened
+    pub files: u64,
+    /// The number of write errors, after which capturing stops
+    pub errors: u64,
+}
+
+/// A packet interceptor which writes datagrams to pcapng files
+///
+/// By default, datagrams are captured in full to a single file.
+pub struct Writer {
+    open: Box<dyn FnMut(usize) -> io::Result<Output> + Send>,
+    output: Option<Output>,
+    output_len: u64,
+    next_file: usize,
+    snap_len: u32,
+    max_file_size: Option<u64>,
+    max_files: Option<usize>,
+    key_log: Option<KeyLog>,
+    /// Maps the first captured timestamp to microseconds since the unix epoch
+    epoch: Option<(Timestamp, u64)>,
+    last_flush: Option<Timestamp>,
+    block: Vec<u8>,
+    /// The write error which stopped the capture
+    error: Option<io::Error>,
+    stats: Stats,
+}
+
+impl core::fmt::Debug for Writer {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Writer")
+            .field("snap_len", &self.snap_len)
+            .field("max_file_size", &self.max_file_size)
+            .field("max_files", &self.max_files)
+            .field("key_log", &self.key_log.is_some())
+            .field("stats", &self.stats)
+            .finish()
+    }
+}
+
+impl Writer {
+    /// Creates a writer which calls `open` with the index of each capture file it starts
+    ///
+    /// The index increases by one for each rotation, wrapping around at
+    /// [`Writer::with_max_files`].
+    pub fn new<F>(open: F) -> Self
+    where
+        F: 'static + Send + FnMut(usize) -> io::Result<Output>,
+    {
+        Self {
+            open: Box::new(open),
+            output: None,
+            output_len: 0,
+            next_file: 0,
+            snap_len: 0,
+            max_file_size: None,
+            max_files: None,
+            key_log: None,
+            epoch: None,
+            last_flush: None,
+            block: Vec::new(),
+            error: None,
+            stats: Stats::default(),
+        }
+    }
+
+    /// Creates a writer which captures to the provided path
+    ///
+    /// When rotating, the first file is written to `path` and subsequent files append the index
+    /// as an extension, e.g. `capture.pcapng.1`. Existing files are overwritten.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Self {
+        let path = path.as_ref().to_path_buf();
+        Self::new(move |index| {
+            let path = file_path(&path, index);
+            let file = std::fs::File::create(path)?;
+            Ok(Box::new(io::BufWriter::new(file)))
+        })
+    }
+
+    /// Truncates each captured datagram, including the synthesized headers, to `len` bytes
+    ///
+    /// A value of 0 disables truncation.
+    pub fn with_snap_len(mut self, len: u32) -> Self {
+        self.snap_len = len;
+        self
+    }
+
+    /// Starts a new capture file once the current one exceeds `size` bytes
+    pub fn with_max_file_size(mut self, size: u64) -> Self {
+        self.max_file_size = Some(size);
+        self
+    }
+
+    /// Reuses the oldest capture file after `count` files have been written
+    ///
+    /// This has no effect unless [`Writer::with_max_file_size`] is also set. A value of 0 is
+    /// treated as 1.
+    pub fn with_max_files(mut self, count: usize) -> Self {
+        self.max_files = Some(count.max(1));
+        self
+    }
+
+    /// Embeds the TLS secrets read from `key_log` in the capture
+    ///
+    /// The reader is expected to produce lines in the NSS key log format, such as the file
+    /// written by the TLS provider when `SSLKEYLOGFILE` is set. Reading a file continues from
+    /// the last position, so new secrets are picked up as they are appended. When rotating, each
+    /// capture file starts with the secrets read while the previous file was open, so connections
+    /// which span a rotation can still be decrypted. Older secrets are only kept in the files
+    /// they were originally written to.
+    pub fn with_key_log<R: 'static + Read + Send>(mut self, key_log: R) -> Self {
+        self.key_log = Some(KeyLog {
+            reader: Box::new(key_log),
+            pending: Vec::new(),
+            secrets: Vec::new(),
+            previous: Vec::new(),
+        });
+        self
+    }
+
+    /// Returns the counters for the captured datagrams
+    #[inline]
+    pub fn stats(&self) -> Stats {
+        self.stats
+    }
+
+    /// Returns the write error which stopped the capture, if any
+    #[inline]
+    pub fn error(&self) -> Option<&io::Error> {
+        self.error.as_ref()
+    }
+
+    #[inline]
+    fn record(&mut self, direction: Direction, datagram: &Datagram, payload: &[u8]) {
+        if self.error.is_some() {
+            return;
+        }
+
+        if let Err(err) = self.try_record(direction, datagram, payload) {
+            // capturing stops here so make sure the reason isn't lost
+            eprintln!("pcap capture stopped after a write error: {err}");
+            self.error = Some(err);
+            self.output = None;
+            self.stats.errors += 1;
+        }
+    }
+
+    fn try_record(
+        &mut self,
+        direction: Direction,
+        datagram: &Datagram,
+        payload: &[u8],
+    ) -> io::Result<()> {
+        if self.output.is_none() {
+            self.open()?;
+        }
+
+        let now = datagram.timestamp;
+        let is_due = self.last_flush.map_or(true, |last| {
+            now.saturating_duration_since(last) >= FLUSH_INTERVAL
+        });
+        if is_due {
+            self.last_flush = Some(now);
+            self.write_new_secrets()?;
+            self.output_mut().flush()?;
+        }
+
+        let is_truncated = self.encode_packet(direction, datagram, payload);
+        self.write_block()?;
+
+        self.stats.packets += 1;
+        if is_truncated {
+            self.stats.truncated += 1;
+        }
+
+        if self
+            .max_file_size
+            .map_or(false, |max| self.output_len >= max)
+        {
+            self.output_mut().flush()?;
+            self.output = None;
+        }
+
+        Ok(())
+    }
+
+    /// Starts the next capture file and writes the section header
+    fn open(&mut self) -> io::Result<()> {
+        let mut index = self.next_file;
+        self.next_file += 1;
+        if let Some(max_files) = self.max_files {
+            index %= max_files;
+        }
+
+        self.output = Some((self.open)(index)?);
+        self.output_len = 0;
+        self.stats.files += 1;
+
+        let block = &mut self.block;
+        block.clear();
+
+        let start = begin_block(block, SECTION_HEADER);
+        block.extend_from_slice(&BYTE_ORDER_MAGIC.to_le_bytes());
+        // version 1.0
+        block.extend_from_slice(&1u16.to_le_bytes());
+        block.extend_from_slice(&0u16.to_le_bytes());
+        // the section length is unspecified
+        block.extend_from_slice(&(-1i64).to_le_bytes());
+        end_block(block, start);
+
+        // timestamps use the default resolution of microseconds
+        let start = begin_block(block, INTERFACE_DESCRIPTION);
+        block.extend_from_slice(&LINKTYPE_RAW.to_le_bytes());
+        block.extend_from_slice(&0u16.to_le_bytes());
+        block.extend_from_slice(&self.snap_len.to_le_bytes());
+        end_block(block, start);
+
+        if let Some(key_log) = self.key_log.as_mut() {
+            let secrets = key_log.rotate();
+            if !secrets.is_empty() {
+                encode_secrets(block, secrets);
+            }
+        }
+
+        self.write_block()
+    }
+
+    /// Embeds any secrets which have been appended to the key log
+    fn write_new_secrets(&mut self) -> io::Result<()> {
+        let Some(key_log) = self.key_log.as_mut() else {
+            return Ok(());
+        };
+
+        let secrets = key_log.poll();
+        if secrets.is_empty() {
+            return Ok(());
+        }
+
+        self.block.clear();
+        encode_secrets(&mut self.block, secrets);
+        self.write_block()
+    }
+
+    /// Encodes the datagram as an Enhanced Packet Block, returning `true` if it was truncated
+    fn encode_packet(&mut self, direction: Direction, datagram: &Datagram, payload: &[u8]) -> bool {
+        let micros = self.micros(datagram.timestamp);
+
+        let (source, destination) = match direction {
+            Direction::Rx => (&datagram.remote_address, &datagram.local_address),
+            Direction::Tx => (&datagram.local_address, &datagram.remote_address),
+        };
+
+        let block = &mut self.block;
+        block.clear();
+
+        let start = begin_block(block, ENHANCED_PACKET);
+        // interface ID
+        block.extend_from_slice(&0u32.to_le_bytes());
+        block.extend_from_slice(&((micros >> 32) as u32).to_le_bytes());
+        block.extend_from_slice(&(micros as u32).to_le_bytes());
+        let lengths = block.len();
+        block.extend_from_slice(&[0; 8]);
+
+        let frame = block.len();
+        encode_headers(block, source, destination, payload.len());
+        block.extend_from_slice(payload);
+
+        let original_len = block.len() - frame;
+        let mut captured_len = original_len;
+        if self.snap_len > 0 {
+            captured_len = captured_len.min(self.snap_len as usize);
+        }
+        block.truncate(frame + captured_len);
+
+        block[lengths..lengths + 4].copy_from_slice(&(captured_len as u32).to_le_bytes());
+        block[lengths + 4..lengths + 8].copy_from_slice(&(original_len as u32).to_le_bytes());
+        end_block(block, start);
+
+        captured_len < original_len
+    }
+
+    /// Converts the timestamp to microseconds since the unix epoch
+    #[inline]
+    fn micros(&mut self, timestamp: Timestamp) -> u64 {
+        let (first, base) = *self.epoch.get_or_insert_with(|| {
+            let base = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |elapsed| elapsed.as_micros() as u64);
+            (timestamp, base)
+        });
+
+        let after = timestamp.saturating_duration_since(first).as_micros() as u64;
+        let before = first.saturating_duration_since(timestamp).as_micros() as u64;
+        (base + after).saturating_sub(before)
+    }
+
+    #[inline]
+    fn output_mut(&mut self) -> &mut Output {
+        self.output
+            .as_mut()
+            .expect("the capture file should be open")
+    }
+
+    #[inline]
+    fn write_block(&mut self) -> io::Result<()> {
+        let output = self
+            .output
+            .as_mut()
+            .expect("the capture file should be open");
+        output.write_all(&self.block)?;
+        self.output_len += self.block.len() as u64;
+        Ok(())
+    }
+}
+
+impl Interceptor for Writer {
+    #[inline]
+    fn intercept_rx_datagram<'a>(
+        &mut self,
+        _subject: &Subject,
+        datagram: &Datagram,
+        payload: DecoderBufferMut<'a>,
+    ) -> DecoderBufferMut<'a> {
+        let payload = payload.into_less_safe_slice();
+        self.record(Direction::Rx, datagram, payload);
+        DecoderBufferMut::new(payload)
+    }
+
+    #[inline]
+    fn intercept_tx_datagram(
+        &mut self,
+        _subject: &Subject,
+        datagram: &Datagram,
+        payload: &mut EncoderBuffer,
+    ) {
+        self.record(Direction::Tx, datagram, payload.as_mut_slice());
+    }
+}
+
+struct KeyLog {
+    reader: Box<dyn Read + Send>,
+    /// A partially read line
+    pending: Vec<u8>,
+    /// The complete lines read since the current file was opened
+    secrets: Vec<u8>,
+    /// The complete lines read while the previous file was open
+    previous: Vec<u8>,
+}
+
+impl KeyLog {
+    /// Reads any new complete lines from the key log and returns them
+    fn poll(&mut self) -> &[u8] {
+        let mut buffer = [0; 1024];
+        loop {
+            match self.reader.read(&mut buffer) {
+                Ok(0) => break,
+                Ok(len) => self.pending.extend_from_slice(&buffer[..len]),
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                // the key log is best effort, so try again on the next interval
+                Err(_) => break,
+            }
+        }
+
+        let Some(end) = self.pending.iter().rposition(|byte| *byte == b'\n') else {
+            return &[];
+        };
+
+        let start = self.secrets.len();
+        self.secrets.extend(self.pending.drain(..=end));
+        &self.secrets[start..]
+    }
+
+    /// Starts tracking the secrets for a new file and returns the ones from the previous file
+    fn rotate(&mut self) -> &[u8] {
+        core::mem::swap(&mut self.secrets, &mut self.previous);
+        self.secrets.clear();
+        &self.previous
+    }
+}
+
+fn file_path(path: &Path, index: usize) -> PathBuf {
+    if index == 0 {
+        return path.to_path_buf();
+    }
+
+    let mut path = path.as_os_str().to_owned();
+    path.push(format!(".{index}"));
+    path.into()
+}
+
+/// Writes the block type and a placeholder for the length, returning the start of the block
+#[inline]
+fn begin_block(block: &mut Vec<u8>, block_type: u32) -> usize {
+    let start = block.len();
+    block.extend_from_slice(&block_type.to_le_bytes());
+    block.extend_from_slice(&0u32.to_le_bytes());
+    start
+}
+
+/// Pads the block body and writes the block length at the start and end
+#[inline]
+fn end_block(block: &mut Vec<u8>, start: usize) {
+    while block.len() % 4 != 0 {
+        block.push(0);
+    }
+    let len = (block.len() - start + 4) as u32;
+    block.extend_from_slice(&len.to_le_bytes());
+    block[start + 4..start + 8].copy_from_slice(&len.to_le_bytes());
+}
+
+fn encode_secrets(block: &mut Vec<u8>, secrets: &[u8]) {
+    let start = begin_block(block, DECRYPTION_SECRETS);
+    block.extend_from_slice(&TLS_KEY_LOG.to_le_bytes());
+    block.extend_from_slice(&(secrets.len() as u32).to_le_bytes());
+    block.extend_from_slice(secrets);
+    end_block(block, start);
+}
+
+/// Writes the IP and UDP headers for a datagram
+///
+/// IPv4 is used when both addresses are IPv4, otherwise any IPv4 address is mapped to IPv6.
+/// The UDP checksum is omitted.
+fn encode_headers(
+    block: &mut Vec<u8>,
+    source: &SocketAddress,
+    destination: &SocketAddress,
+    payload_len: usize,
+) {
+    let udp_len = (UDP_HEADER_LEN + payload_len).min(u16::MAX as usize) as u16;
+
+    let (source_port, destination_port) = match (source, destination) {
+        (
+            SocketAddress::IpV4 {
+                ip: source,
+                port: source_port,
+            },
+            SocketAddress::IpV4 {
+                ip: destination,
+                port: destination_port,
+            },
+        ) => {
+            let total_len = (IPV4_HEADER_LEN + udp_len as usize).min(u16::MAX as usize) as u16;
+            let start = block.len();
+            // version 4 and a header length of 5 words
+            block.push(0x45);
+            // DSCP and ECN
+            block.push(0);
+            block.extend_from_slice(&total_len.to_be_bytes());
+            // identification
+            block.extend_from_slice(&0u16.to_be_bytes());
+            // don't fragment
+            block.extend_from_slice(&0x4000u16.to_be_bytes());
+            // TTL
+            block.push(64);
+            // UDP
+            block.push(17);
+            let checksum = block.len();
+            block.extend_from_slice(&0u16.to_be_bytes());
+            block.extend_from_slice(*source);
+            block.extend_from_slice(*destination);
+
+            let checksum_value = ipv4_checksum(&block[start..]);
+            block[checksum..checksum + 2].copy_from_slice(&checksum_value.to_be_bytes());
+
+            (*source_port, *destination_port)
+        }
+        _ => {
+            let (source, source_port) = ipv6(source);
+            let (destination, destination_port) = ipv6(destination);
+            // version 6 with no traffic class or flow label
+            block.extend_from_slice(&0x6000_0000u32.to_be_bytes());
+            block.extend_from_slice(&udp_len.to_be_bytes());
+            // UDP
+            block.push(17);
+            // hop limit
+            block.push(64);
+            block.extend_from_slice(&source);
+            block.extend_from_slice(&destination);
+
+            (source_port, destination_port)
+        }
+    };
+
+    block.extend_from_slice(&source_port.to_be_bytes());
+    block.extend_from_slice(&destination_port.to_be_bytes());
+    block.extend_from_slice(&udp_len.to_be_bytes());
+    block.extend_from_slice(&0u16.to_be_bytes());
+}
+
+#[inline]
+fn ipv6(address: &SocketAddress) -> ([u8; 16], u16) {
+    match address {
+        SocketAddress::IpV4 { ip, port } => {
+            let mut mapped = [0; 16];
+            mapped[10] = 0xff;
+            mapped[11] = 0xff;
+            mapped[12..].copy_from_slice(*ip);
+            (mapped, *port)
+        }
+        SocketAddress::IpV6 { ip, port } => (**ip, *port),
+    }
+}
+
+#[inline]
+fn ipv4_checksum(header: &[u8]) -> u16 {
+    let mut sum: u32 = header
+        .chunks(2)
+        .map(|word| u16::from_be_bytes([word[0], word[1]]) as u32)
+        .sum();
+    while sum > 0xffff {
+        sum = (sum & 0xffff) + (sum >> 16);
+    }
+    !(sum as u16)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time::clock::testing::now;
+    use std::sync::{Arc, Mutex};
+
+    type Files = Arc<Mutex<Vec<(usize, Vec<u8>)>>>;
+
+    /// Captures each file into memory, recording the index it was opened with
+    struct Memory {
+        files: Files,
+        index: usize,
+    }
+
+    impl Write for Memory {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.files.lock().unwrap()[self.index]
+                .1
+                .extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn writer() -> (Writer, Files) {
+        let files = Files::default();
+        let writer = Writer::new({
+            let files = files.clone();
+            move |index| {
+                let mut list = files.lock().unwrap();
+                list.push((index, Vec::new()));
+                Ok(Box::new(Memory {
+                    files: files.clone(),
+                    index: list.len() - 1,
+                }))
+            }
+        });
+        (writer, files)
+    }
+
+    /// Splits a capture into its block types and bodies
+    fn blocks(mut file: &[u8]) -> Vec<(u32, Vec<u8>)> {
+        let mut blocks = Vec::new();
+        while !file.is_empty() {
+            let block_type = u32::from_le_bytes(file[..4].try_into().unwrap());
+            let len = u32::from_le_bytes(file[4..8].try_into().unwrap()) as usize;
+            let trailer = u32::from_le_bytes(file[len - 4..len].try_into().unwrap()) as usize;
+            assert_eq!(len, trailer);
+            blocks.push((block_type, file[8..len - 4].to_vec()));
+            file = &file[len..];
+        }
+        blocks
+    }
+
+    fn datagram(timestamp: Timestamp) -> Datagram<'static> {
+        Datagram {
+            remote_address: SocketAddress::IpV4 {
+                ip: &[192, 0, 2, 1],
+                port: 443,
+            },
+            local_address: SocketAddress::IpV4 {
+                ip: &[192, 0, 2, 2],
+                port: 4433,
+            },
+            timestamp,
+        }
+    }
+
+    fn rx(writer: &mut Writer, datagram: &Datagram, payload: &mut [u8]) {
+        let subject = Subject::Connection { id: 1 };
+        writer.intercept_rx_datagram(&subject, datagram, DecoderBufferMut::new(payload));
+    }
+
+    fn tx(writer: &mut Writer, datagram: &Datagram, payload: &[u8]) {
+        let subject = Subject::Connection { id: 1 };
+        let mut buffer = payload.to_vec();
+        let mut encoder = EncoderBuffer::new(&mut buffer);
+        encoder.set_position(payload.len());
+        writer.intercept_tx_datagram(&subject, datagram, &mut encoder);
+    }
+
+    #[test]
+    fn layout_test() {
+        let (mut writer, files) = writer();
+        let now = now();
+
+        rx(&mut writer, &datagram(now), &mut [1, 2, 3]);
+        tx(
+            &mut writer,
+            &datagram(now + Duration::from_micros(5)),
+            &[4, 5],
+        );
+
+        let files = files.lock().unwrap();
+        assert_eq!(files.len(), 1);
+        let blocks = blocks(&files[0].1);
+        let types: Vec<_> = blocks.iter().map(|(block_type, _)| *block_type).collect();
+        assert_eq!(
+            types,
+            [
+                SECTION_HEADER,
+                INTERFACE_DESCRIPTION,
+                ENHANCED_PACKET,
+                ENHANCED_PACKET
+            ]
+        );
+
+        let rx = &blocks[2].1;
+        let rx_frame = &rx[20..];
+        assert_eq!(&rx[12..16], &31u32.to_le_bytes());
+        assert_eq!(&rx[16..20], &31u32.to_le_bytes());
+        // the IPv4 checksum should verify
+        assert_eq!(ipv4_checksum(&rx_frame[..IPV4_HEADER_LEN]), 0);
+        // received datagrams are sent from the remote address
+        assert_eq!(&rx_frame[12..16], &[192, 0, 2, 1]);
+        assert_eq!(&rx_frame[16..20], &[192, 0, 2, 2]);
+        assert_eq!(&rx_frame[20..22], &443u16.to_be_bytes());
+        assert_eq!(&rx_frame[28..31], &[1, 2, 3]);
+
+        let tx = &blocks[3].1;
+        let tx_frame = &tx[20..];
+        assert_eq!(&tx_frame[12..16], &[192, 0, 2, 2]);
+        assert_eq!(&tx_frame[28..30], &[4, 5]);
+
+        let timestamp = |block: &[u8]| {
+            let high = u32::from_le_bytes(block[4..8].try_into().unwrap()) as u64;
+            let low = u32::from_le_bytes(block[8..12].try_into().unwrap()) as u64;
+            (high << 32) | low
+        };
+        assert_eq!(timestamp(tx) - timestamp(rx), 5);
+
+        assert_eq!(writer.stats().packets, 2);
+    }
+
+    const IPV6_HEADER_LEN: usize = 40;
+
+    #[test]
+    fn ipv6_test() {
+        let (mut writer, files) = writer();
+        let datagram = Datagram {
+            remote_address: SocketAddress::IpV6 {
+                ip: &[1; 16],
+                port: 443,
+            },
+            local_address: SocketAddress::IpV4 {
+                ip: &[192, 0, 2, 2],
+                port: 4433,
+            },
+            timestamp: now(),
+        };
+
+        tx(&mut writer, &datagram, &[1]);
+
+        let files = files.lock().unwrap();
+        let blocks = blocks(&files[0].1);
+        let frame = &blocks[2].1[20..];
+        assert_eq!(frame[0] >> 4, 6);
+        // the IPv4 address is mapped to IPv6
+        assert_eq!(
+            &frame[8..24],
+            &[0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0xff, 0xff, 192, 0, 2, 2]
+        );
+        assert_eq!(&frame[24..40], &[1; 16]);
+        assert_eq!(frame.len(), IPV6_HEADER_LEN + UDP_HEADER_LEN + 1);
+    }
+
+    #[test]
+    fn truncate_rotate_test() {
+        let (writer, files) = writer();
+        let mut writer = writer
+            .with_snap_len(32)
+            .with_max_file_size(150)
+            .with_max_files(2);
+        let now = now();
+
+        for _ in 0..6 {
+            rx(&mut writer, &datagram(now), &mut [0; 100]);
+        }
+
+        let files = files.lock().unwrap();
+        let indexes: Vec<_> = files.iter().map(|(index, _)| *index).collect();
+        assert_eq!(indexes, [0, 1, 0]);
+
+        for (_, file) in files.iter() {
+            let blocks = blocks(file);
+            assert_eq!(&blocks[1].1[4..8], &32u32.to_le_bytes());
+            for (_, packet) in &blocks[2..] {
+                assert_eq!(&packet[12..16], &32u32.to_le_bytes());
+                assert_eq!(&packet[16..20], &128u32.to_le_bytes());
+            }
+        }
+
+        let stats = writer.stats();
+        assert_eq!(stats.packets, 6);
+        assert_eq!(stats.truncated, 6);
+        assert_eq!(stats.files, 3);
+    }
+
+    #[test]
+    fn key_log_test() {
+        let (writer, files) = writer();
+        let key_log = io::Cursor::new(b"CLIENT_RANDOM 01 02\nSERVER_HANDSHAKE".to_vec());
+        let mut writer = writer.with_key_log(key_log).with_max_file_size(1);
+        let now = now();
+
+        rx(&mut writer, &datagram(now), &mut [1]);
+        rx(&mut writer, &datagram(now), &mut [2]);
+        rx(&mut writer, &datagram(now), &mut [3]);
+
+        let files = files.lock().unwrap();
+        assert_eq!(files.len(), 3);
+
+        let secrets = |block: &[u8]| {
+            assert_eq!(&block[..4], &TLS_KEY_LOG.to_le_bytes());
+            let len = u32::from_le_bytes(block[4..8].try_into().unwrap()) as usize;
+            block[8..8 + len].to_vec()
+        };
+
+        // only complete lines are embedded
+        let first = blocks(&files[0].1);
+        assert_eq!(first[2].0, DECRYPTION_SECRETS);
+        assert_eq!(secrets(&first[2].1), b"CLIENT_RANDOM 01 02\n");
+        assert_eq!(first[3].0, ENHANCED_PACKET);
+
+        // rotated files start with the secrets read while the previous file was open
+        let second = blocks(&files[1].1);
+        assert_eq!(second[2].0, DECRYPTION_SECRETS);
+        assert_eq!(secrets(&second[2].1), b"CLIENT_RANDOM 01 02\n");
+        assert_eq!(second[3].0, ENHANCED_PACKET);
+
+        // no new secrets were read while the second file was open
+        let third = blocks(&files[2].1);
+        assert_eq!(third[2].0, ENHANCED_PACKET);
+    }
+
+    #[test]
+    fn error_test() {
+        let mut writer = Writer::new(|_| Err(io::ErrorKind::PermissionDenied.into()));
+        let now = now();
+
+        rx(&mut writer, &datagram(now), &mut [1]);
+        rx(&mut writer, &datagram(now), &mut [2]);
+
+        let stats = writer.stats();
+        assert_eq!(stats.errors, 1);
+        assert_eq!(stats.packets, 0);
+        assert_eq!(
+            writer.error().map(io::Error::kind),
+            Some(io::ErrorKind::PermissionDenied)
+        );
+    }
+
+    #[test]
+    fn file_path_test() {
+        let path = Path::new("/tmp/capture.pcapng");
+        assert_eq!(file_path(path, 0), path);
+        assert_eq!(file_path(path, 2), Path::new("/tmp/capture.pcapng.2"));
+    }
+}
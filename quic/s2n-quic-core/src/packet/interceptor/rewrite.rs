@@ -0,0 +1,471 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Applies fault-injection rules to packets which contain specific frame types
+//!
+//! Rules are matched against the cleartext frames of each packet. Transmitted datagrams
+//! containing a matching packet can be dropped, delayed or duplicated, and the matching frames
+//! themselves can be corrupted before encryption. Received datagrams containing a matching
+//! packet can be dropped, and matching frames can be corrupted before they are processed.
+//!
+//! Delayed and duplicated datagrams are held by the interceptor and released the next time the
+//! connection transmits, at or after the configured delay. They are sent on the path the
+//! connection is transmitting on at that time. Released datagrams are subject to the same
+//! congestion and amplification limits as new datagrams. The packets they contain were recorded
+//! for loss recovery when they were first written, so a delay longer than the probe timeout
+//! causes the contents to be retransmitted, just as a delay on the network would.
+
+use super::{Datagram, DecoderBufferMut, EncoderBuffer, Interceptor, Packet};
+use crate::{
+    event::api::Subject,
+    frame::{Frame, FrameRef},
+    time::Timestamp,
+    varint::VarInt,
+};
+use alloc::{collections::VecDeque, vec::Vec};
+use core::{ops::RangeInclusive, time::Duration};
+use s2n_codec::{encoder::scatter, DecoderBuffer, Encoder};
+
+/// What happens to a packet matching a [`Rule`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Action {
+    /// Drops the datagram containing the packet
+    ///
+    /// For received datagrams, the matching packet and the packets following it in the datagram
+    /// are discarded without being processed or acknowledged. Packets coalesced before the
+    /// matching packet have already been processed.
+    Drop,
+    /// Holds the datagram containing the packet for the given duration before sending it
+    ///
+    /// This has no effect on received packets.
+    Delay(Duration),
+    /// Sends the datagram containing the packet an additional number of times
+    ///
+    /// This has no effect on received packets.
+    Duplicate(u8),
+    /// Inverts the bytes following the frame type of each matching frame
+    Corrupt,
+}
+
+/// Matches frames by type and applies an [`Action`] to the packets containing them
+#[derive(Clone, Debug)]
+pub struct Rule {
+    frame_types: RangeInclusive<u64>,
+    action: Action,
+    remaining: Option<usize>,
+}
+
+impl Rule {
+    /// Creates a rule for a single frame type
+    pub fn new(frame_type: u64, action: Action) -> Self {
+        Self::for_frame_types(frame_type..=frame_type, action)
+    }
+
+    /// Creates a rule for a range of frame types, such as `0x08..=0x0f` for STREAM frames
+    pub fn for_frame_types(frame_types: RangeInclusive<u64>, action: Action) -> Self {
+        Self {
+            frame_types,
+            action,
+            remaining: None,
+        }
+    }
+
+    /// Limits the number of frames the rule applies to
+    #[must_use]
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.remaining = Some(limit);
+        self
+    }
+
+    #[inline]
+    fn matches(&self, tag: VarInt) -> bool {
+        self.remaining != Some(0) && self.frame_types.contains(&tag.as_u64())
+    }
+
+    #[inline]
+    fn on_applied(&mut self) {
+        if let Some(remaining) = self.remaining.as_mut() {
+            *remaining = remaining.saturating_sub(1);
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Held {
+    release: Timestamp,
+    payload: Vec<u8>,
+}
+
+/// A packet interceptor which applies [`Rule`]s to transmitted and received packets
+#[derive(Debug, Default)]
+pub struct Rewrite {
+    tx: Vec<Rule>,
+    rx: Vec<Rule>,
+    /// The datagram-level action for the datagram currently being built
+    pending: Option<Action>,
+    held: VecDeque<Held>,
+    /// Set when the datagram currently being received should be dropped
+    rx_dropping: bool,
+}
+
+impl Rewrite {
+    /// Adds a rule applied to transmitted packets
+    #[must_use]
+    pub fn with_tx_rule(mut self, rule: Rule) -> Self {
+        self.tx.push(rule);
+        self
+    }
+
+    /// Adds a rule applied to received packets
+    #[must_use]
+    pub fn with_rx_rule(mut self, rule: Rule) -> Self {
+        self.rx.push(rule);
+        self
+    }
+
+    /// Returns the number of datagrams waiting to be released
+    pub fn held_datagrams(&self) -> usize {
+        self.held.len()
+    }
+}
+
+/// Walks the frames in `payload`, calling `on_frame` with the frame type and byte range of each
+/// non-PADDING frame
+#[inline]
+fn for_each_frame<F: FnMut(VarInt, core::ops::Range<usize>)>(payload: &mut [u8], mut on_frame: F) {
+    let len = payload.len();
+    let mut frames = Vec::new();
+
+    {
+        let mut buffer = DecoderBufferMut::new(payload);
+        while !buffer.is_empty() {
+            let start = len - buffer.len();
+            match buffer.decode::<FrameRef>() {
+                Ok((Frame::Padding(_), remaining)) => buffer = remaining,
+                Ok((_frame, remaining)) => {
+                    frames.push(start..len - remaining.len());
+                    buffer = remaining;
+                }
+                Err(_) => break,
+            }
+        }
+    }
+
+    for range in frames {
+        if let Ok((tag, _)) = DecoderBuffer::new(&payload[range.clone()]).decode::<VarInt>() {
+            on_frame(tag, range);
+        }
+    }
+}
+
+#[inline]
+fn corrupt(frame: &mut [u8], tag: VarInt) {
+    let tag_len = s2n_codec::EncoderValue::encoding_size(&tag);
+    for byte in frame.iter_mut().skip(tag_len) {
+        *byte = !*byte;
+    }
+}
+
+impl Interceptor for Rewrite {
+    #[inline]
+    fn intercept_rx_datagram<'a>(
+        &mut self,
+        _subject: &Subject,
+        _datagram: &Datagram,
+        payload: DecoderBufferMut<'a>,
+    ) -> DecoderBufferMut<'a> {
+        // a new datagram was received so stop dropping packets
+        self.rx_dropping = false;
+        payload
+    }
+
+    #[inline]
+    fn intercept_rx_payload<'a>(
+        &mut self,
+        _subject: &Subject,
+        _packet: &Packet,
+        payload: DecoderBufferMut<'a>,
+    ) -> DecoderBufferMut<'a> {
+        if self.rx.is_empty() || self.rx_dropping {
+            return payload;
+        }
+
+        let payload = payload.into_less_safe_slice();
+        let mut corruptions = Vec::new();
+
+        let rules = &mut self.rx;
+        let rx_dropping = &mut self.rx_dropping;
+        for_each_frame(payload, |tag, range| {
+            if let Some(rule) = rules.iter_mut().find(|rule| rule.matches(tag)) {
+                rule.on_applied();
+                match rule.action {
+                    Action::Drop => *rx_dropping = true,
+                    Action::Corrupt => corruptions.push((tag, range)),
+                    Action::Delay(_) | Action::Duplicate(_) => {}
+                }
+            }
+        });
+
+        for (tag, range) in corruptions {
+            corrupt(&mut payload[range], tag);
+        }
+
+        DecoderBufferMut::new(payload)
+    }
+
+    #[inline]
+    fn drop_rx_packet(&mut self, _subject: &Subject, _packet: &Packet) -> bool {
+        self.rx_dropping
+    }
+
+    #[inline]
+    fn intercept_tx_payload(
+        &mut self,
+        _subject: &Subject,
+        _packet: &Packet,
+        payload: &mut scatter::Buffer,
+    ) {
+        if self.tx.is_empty() {
+            return;
+        }
+
+        let payload = payload.flatten();
+        let payload = payload.as_mut_slice();
+        let mut corruptions = Vec::new();
+
+        let rules = &mut self.tx;
+        let pending = &mut self.pending;
+        for_each_frame(payload, |tag, range| {
+            if let Some(rule) = rules.iter_mut().find(|rule| rule.matches(tag)) {
+                rule.on_applied();
+                match rule.action {
+                    Action::Corrupt => corruptions.push((tag, range)),
+                    // the first datagram-level action for a datagram wins
+                    action => {
+                        pending.get_or_insert(action);
+                    }
+                }
+            }
+        });
+
+        for (tag, range) in corruptions {
+            corrupt(&mut payload[range], tag);
+        }
+    }
+
+    #[inline]
+    fn intercept_tx_datagram(
+        &mut self,
+        _subject: &Subject,
+        datagram: &Datagram,
+        payload: &mut EncoderBuffer,
+    ) {
+        let Some(action) = self.pending.take() else {
+            return;
+        };
+
+        match action {
+            Action::Drop => payload.set_position(0),
+            Action::Delay(delay) => {
+                self.held.push_back(Held {
+                    release: datagram.timestamp + delay,
+                    payload: payload.as_mut_slice().to_vec(),
+                });
+                payload.set_position(0);
+            }
+            Action::Duplicate(count) => {
+                for _ in 0..count {
+                    self.held.push_back(Held {
+                        release: datagram.timestamp,
+                        payload: payload.as_mut_slice().to_vec(),
+                    });
+                }
+            }
+            Action::Corrupt => {}
+        }
+    }
+
+    #[inline]
+    fn release_tx_datagram(
+        &mut self,
+        _subject: &Subject,
+        datagram: &Datagram,
+        payload: &mut EncoderBuffer,
+    ) {
+        let Some(index) = self
+            .held
+            .iter()
+            .position(|held| held.release <= datagram.timestamp)
+        else {
+            return;
+        };
+
+        // wait for a buffer large enough to hold the datagram
+        if self.held[index].payload.len() > payload.remaining_capacity() {
+            return;
+        }
+
+        if let Some(held) = self.held.remove(index) {
+            payload.write_slice(&held.payload);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        event::api::SocketAddress, frame, packet::number::PacketNumberSpace,
+        time::clock::testing as time,
+    };
+    use s2n_codec::EncoderValue;
+
+    fn subject() -> Subject {
+        Subject::Connection { id: 0 }
+    }
+
+    fn packet() -> Packet {
+        Packet {
+            number: PacketNumberSpace::ApplicationData.new_packet_number(VarInt::from_u8(1)),
+            timestamp: time::now(),
+        }
+    }
+
+    fn datagram(timestamp: Timestamp) -> Datagram<'static> {
+        let address = SocketAddress::IpV4 {
+            ip: &[127, 0, 0, 1],
+            port: 443,
+        };
+        Datagram {
+            remote_address: address,
+            local_address: address,
+            timestamp,
+        }
+    }
+
+    /// Encodes a PING followed by a MAX_DATA frame
+    fn frames() -> Vec<u8> {
+        let mut bytes = frame::Ping.encode_to_vec();
+        bytes.extend(
+            frame::MaxData {
+                maximum_data: VarInt::from_u16(1234),
+            }
+            .encode_to_vec(),
+        );
+        bytes
+    }
+
+    fn transmit(rewrite: &mut Rewrite, timestamp: Timestamp) -> Vec<u8> {
+        let mut payload = frames();
+        let len = payload.len();
+        let mut encoder = EncoderBuffer::new(&mut payload);
+        encoder.set_position(len);
+        let mut buffer = scatter::Buffer::new(encoder);
+        rewrite.intercept_tx_payload(&subject(), &packet(), &mut buffer);
+        let len = buffer.flatten().len();
+
+        let mut datagram_bytes = payload[..len].to_vec();
+        let mut encoder = EncoderBuffer::new(&mut datagram_bytes);
+        encoder.set_position(len);
+        rewrite.intercept_tx_datagram(&subject(), &datagram(timestamp), &mut encoder);
+        let len = encoder.len();
+        datagram_bytes.truncate(len);
+        datagram_bytes
+    }
+
+    fn release(rewrite: &mut Rewrite, timestamp: Timestamp) -> Option<Vec<u8>> {
+        let mut buffer = [0u8; 1500];
+        let mut encoder = EncoderBuffer::new(&mut buffer);
+        rewrite.release_tx_datagram(&subject(), &datagram(timestamp), &mut encoder);
+        let len = encoder.len();
+        (len > 0).then(|| buffer[..len].to_vec())
+    }
+
+    #[test]
+    fn drop_test() {
+        let mut rewrite = Rewrite::default().with_tx_rule(Rule::new(0x10, Action::Drop));
+        assert!(transmit(&mut rewrite, time::now()).is_empty());
+
+        // rules that don't match leave the datagram alone
+        let mut rewrite = Rewrite::default().with_tx_rule(Rule::new(0x06, Action::Drop));
+        assert_eq!(transmit(&mut rewrite, time::now()), frames());
+    }
+
+    #[test]
+    fn delay_test() {
+        let now = time::now();
+        let delay = Duration::from_millis(100);
+        let mut rewrite =
+            Rewrite::default().with_tx_rule(Rule::new(0x01, Action::Delay(delay)).with_limit(1));
+
+        assert!(transmit(&mut rewrite, now).is_empty());
+        assert_eq!(rewrite.held_datagrams(), 1);
+
+        assert_eq!(release(&mut rewrite, now), None);
+        assert_eq!(release(&mut rewrite, now + delay), Some(frames()));
+        assert_eq!(rewrite.held_datagrams(), 0);
+
+        // the rule has been exhausted
+        assert_eq!(transmit(&mut rewrite, now), frames());
+    }
+
+    #[test]
+    fn duplicate_test() {
+        let now = time::now();
+        let mut rewrite = Rewrite::default().with_tx_rule(Rule::new(0x01, Action::Duplicate(2)));
+
+        assert_eq!(transmit(&mut rewrite, now), frames());
+        assert_eq!(release(&mut rewrite, now), Some(frames()));
+        assert_eq!(release(&mut rewrite, now), Some(frames()));
+        assert_eq!(release(&mut rewrite, now), None);
+    }
+
+    #[test]
+    fn corrupt_test() {
+        let mut rewrite = Rewrite::default().with_tx_rule(Rule::new(0x10, Action::Corrupt));
+        let expected = frames();
+        let actual = transmit(&mut rewrite, time::now());
+
+        // the PING frame is untouched
+        assert_eq!(actual[0], expected[0]);
+        // the MAX_DATA frame type is preserved but the body is inverted
+        assert_eq!(actual[1], expected[1]);
+        for (actual, expected) in actual[2..].iter().zip(&expected[2..]) {
+            assert_eq!(*actual, !*expected);
+        }
+    }
+
+    fn receive_datagram(rewrite: &mut Rewrite) {
+        let mut payload = [0u8; 1];
+        let payload = DecoderBufferMut::new(&mut payload);
+        let _ = rewrite.intercept_rx_datagram(&subject(), &datagram(time::now()), payload);
+    }
+
+    /// Returns `true` if the packet was dropped
+    fn receive_packet(rewrite: &mut Rewrite, payload: &mut [u8]) -> bool {
+        let _ = rewrite.intercept_rx_payload(&subject(), &packet(), DecoderBufferMut::new(payload));
+        rewrite.drop_rx_packet(&subject(), &packet())
+    }
+
+    #[test]
+    fn rx_drop_test() {
+        let mut rewrite =
+            Rewrite::default().with_rx_rule(Rule::new(0x10, Action::Drop).with_limit(1));
+
+        // the matching packet is dropped without being modified
+        receive_datagram(&mut rewrite);
+        let mut payload = frames();
+        assert!(receive_packet(&mut rewrite, &mut payload));
+        assert_eq!(payload, frames());
+
+        // the packets following it in the same datagram are dropped as well
+        let mut ping = frame::Ping.encode_to_vec();
+        assert!(receive_packet(&mut rewrite, &mut ping));
+
+        // the next datagram is processed normally since the rule has been exhausted
+        receive_datagram(&mut rewrite);
+        let mut payload = frames();
+        assert!(!receive_packet(&mut rewrite, &mut payload));
+    }
+}
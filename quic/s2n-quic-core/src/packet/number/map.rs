@@ -90,6 +90,14 @@ impl<V> Default for Map<V> {
 }
 
 impl<V> Map<V> {
+    /// Releases any excess capacity if the map is empty
+    #[inline]
+    pub fn shrink_to_fit(&mut self) {
+        if self.is_empty() && self.values.len() > DEFAULT_CAPACITY {
+            *self = Self::default();
+        }
+    }
+
     /// Inserts the given `value`
     pub fn insert(&mut self, packet_number: PacketNumber, value: V) {
         if self.is_empty() {
@@ -592,6 +600,28 @@ mod tests {
         assert!(!sent_packets.is_empty());
     }
 
+    #[test]
+    fn shrink_to_fit() {
+        let mut sent_packets = TestMap::default();
+        let mut packet_number = PacketNumberSpace::Initial.new_packet_number(VarInt::from_u8(1));
+        for i in 0..100 {
+            sent_packets.insert(packet_number, i);
+            packet_number = packet_number.next().unwrap();
+        }
+        assert!(sent_packets.values.len() > DEFAULT_CAPACITY);
+
+        // the capacity is retained while the map holds values
+        sent_packets.shrink_to_fit();
+        assert!(sent_packets.values.len() > DEFAULT_CAPACITY);
+
+        sent_packets.clear();
+        sent_packets.shrink_to_fit();
+        assert_eq!(sent_packets.values.len(), DEFAULT_CAPACITY);
+
+        sent_packets.insert(packet_number, 100);
+        assert_eq!(sent_packets.get(packet_number), Some(&100));
+    }
+
     #[test]
     #[should_panic]
     fn wrong_packet_space_on_insert() {
@@ -110,32 +110,53 @@ impl<'a> PseudoRetry<'a> {
     }
 }
 
+/// The fields of a client's Initial packet which are required to respond with a Retry packet
+#[derive(Clone, Copy, Debug)]
+pub struct InitialHeader<'a> {
+    pub version: Version,
+    pub destination_connection_id: &'a [u8],
+    pub source_connection_id: &'a [u8],
+}
+
+impl<'a> From<&'a ProtectedInitial<'_>> for InitialHeader<'a> {
+    #[inline]
+    fn from(packet: &'a ProtectedInitial<'_>) -> Self {
+        Self {
+            version: packet.version,
+            destination_connection_id: packet.destination_connection_id(),
+            source_connection_id: packet.source_connection_id(),
+        }
+    }
+}
+
 pub type ProtectedRetry<'a> = Retry<'a>;
 pub type EncryptedRetry<'a> = Retry<'a>;
 pub type CleartextRetry<'a> = Retry<'a>;
 
 impl<'a> Retry<'a> {
-    pub fn encode_packet<T: token::Format, C: RetryKey>(
+    pub fn encode_packet<'b, T: token::Format, C: RetryKey>(
         remote_address: &SocketAddress,
-        packet: &ProtectedInitial,
+        packet: impl Into<InitialHeader<'b>>,
         local_connection_id: &connection::LocalId,
         random: &mut dyn random::Generator,
         token_format: &mut T,
         packet_buf: &mut [u8],
     ) -> Option<Range<usize>> {
+        let packet = packet.into();
+
         //= https://www.rfc-editor.org/rfc/rfc9000#section-17.2.5.1
         //# This value MUST NOT be equal to the Destination
         //# Connection ID field of the packet sent by the client.
         debug_assert_ne!(
             local_connection_id.as_ref(),
-            packet.destination_connection_id()
+            packet.destination_connection_id
         );
-        if local_connection_id.as_ref() == packet.destination_connection_id() {
+        if local_connection_id.as_ref() == packet.destination_connection_id {
             return None;
         }
 
         let retry_packet = Retry::from_initial(packet, local_connection_id.as_ref());
-        let pseudo_packet = retry_packet.pseudo_packet(packet.destination_connection_id());
+        let pseudo_packet = retry_packet.pseudo_packet(packet.destination_connection_id);
 
         let mut buffer = EncoderBuffer::new(packet_buf);
         pseudo_packet.encode(&mut buffer);
@@ -149,7 +170,7 @@ impl<'a> Retry<'a> {
         buffer.write_sized(T::TOKEN_LEN, |token_buf| {
             outcome = token_format.generate_retry_token(
                 &mut context,
-                &connection::InitialId::try_from_bytes(packet.destination_connection_id()).unwrap(),
+                &connection::InitialId::try_from_bytes(packet.destination_connection_id).unwrap(),
                 token_buf,
             );
         });
@@ -160,7 +181,7 @@ impl<'a> Retry<'a> {
         buffer.write_slice(&tag);
         let end = buffer.len();
         let start =
-            packet.destination_connection_id().len() + size_of::<DestinationConnectionIdLen>();
+            packet.destination_connection_id.len() + size_of::<DestinationConnectionIdLen>();
 
         Some(start..end)
     }
@@ -195,9 +216,11 @@ impl<'a> Retry<'a> {
     }
 
     pub fn from_initial(
-        initial_packet: &'a ProtectedInitial,
+        initial_packet: impl Into<InitialHeader<'a>>,
         local_connection_id: &'a [u8],
     ) -> Self {
+        let initial_packet = initial_packet.into();
+
         // The destination and source connection IDs are flipped because this packet is being sent
         // back to the client.
 
@@ -210,7 +233,7 @@ impl<'a> Retry<'a> {
             // https://www.rfc-editor.org/rfc/rfc9001#section-A.2
             tag: (retry_tag!() << 4) | 0x0f,
             version: initial_packet.version,
-            destination_connection_id: initial_packet.source_connection_id(),
+            destination_connection_id: initial_packet.source_connection_id,
             source_connection_id: local_connection_id,
             retry_token: &[][..],
             retry_integrity_tag: {
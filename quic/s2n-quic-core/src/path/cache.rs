@@ -0,0 +1,90 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Shares path characteristics between connections to the same peer
+//!
+//! When a connection closes, a snapshot of its active path is stored in the [`Cache`]. New
+//! connections to the same peer look up the snapshot to seed their initial RTT estimate, MTU,
+//! and congestion window, instead of rediscovering them from the conservative defaults. This
+//! follows the temporal sharing described in
+//! [RFC 9040](https://www.rfc-editor.org/rfc/rfc9040#section-4).
+
+use crate::event::{api::SocketAddress, IntoEvent};
+use core::{fmt::Debug, time::Duration};
+
+/// The maximum multiple of the initial congestion window a new connection resumes with
+///
+/// The cached window may be stale by the time it's used, so it's limited to avoid sending a
+/// large burst into a path which has since become congested.
+const MAX_RESUMED_WINDOW_MULTIPLIER: u32 = 4;
+
+/// The characteristics of a path at the time its connection closed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Entry {
+    /// The smoothed RTT of the path
+    pub smoothed_rtt: Duration,
+    /// The minimum RTT observed on the path
+    pub min_rtt: Duration,
+    /// The path MTU, including the IP and UDP headers
+    pub mtu: u16,
+    /// The congestion window, in bytes
+    pub congestion_window: u32,
+}
+
+impl Entry {
+    /// Returns the congestion window a new connection resumes with, given the `initial_window`
+    /// it would otherwise start with
+    ///
+    /// Half of the cached window is used, as if the connection had just experienced a congestion
+    /// event, to account for changes in the path since. The result is never lower than the
+    /// `initial_window` and never higher than a small multiple of it.
+    #[inline]
+    pub fn resumed_congestion_window(&self, initial_window: u32) -> u32 {
+        let max_window = initial_window.saturating_mul(MAX_RESUMED_WINDOW_MULTIPLIER);
+        (self.congestion_window / 2).clamp(initial_window, max_window)
+    }
+}
+
+/// Information about a path that is being stored in or looked up from the cache
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct PathInfo<'a> {
+    pub remote_address: SocketAddress<'a>,
+}
+
+impl<'a> PathInfo<'a> {
+    #[inline]
+    #[doc(hidden)]
+    pub fn new(remote_address: &'a crate::inet::SocketAddress) -> Self {
+        Self {
+            remote_address: remote_address.into_event(),
+        }
+    }
+}
+
+/// Stores path characteristics for reuse by later connections
+///
+/// The cache is shared by all of the connections on an endpoint, so implementations need to
+/// synchronize access internally.
+pub trait Cache: 'static + Debug + Send + Sync {
+    /// Called when a new connection is created, before any packets are sent on the path
+    fn get(&self, path: &PathInfo) -> Option<Entry>;
+
+    /// Called when a connection closes after at least one RTT sample was taken on its active
+    /// path
+    fn insert(&self, path: &PathInfo, entry: Entry);
+}
+
+/// Doesn't share any path characteristics between connections
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Disabled;
+
+impl Cache for Disabled {
+    #[inline]
+    fn get(&self, _path: &PathInfo) -> Option<Entry> {
+        None
+    }
+
+    #[inline]
+    fn insert(&self, _path: &PathInfo, _entry: Entry) {}
+}
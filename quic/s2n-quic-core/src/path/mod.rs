@@ -3,18 +3,24 @@
 
 use crate::{
     event,
-    inet::{IpV4Address, IpV6Address, SocketAddress, SocketAddressV4, SocketAddressV6},
+    inet::{
+        IpV4Address, IpV6Address, SocketAddress, SocketAddressV4, SocketAddressV6, Unspecified as _,
+    },
 };
 use core::fmt;
 
 #[cfg(any(test, feature = "generator"))]
 use bolero_generator::prelude::*;
 
+pub mod cache;
 pub mod ecn;
 pub mod migration;
 pub mod mtu;
+pub mod probe;
+mod stats;
 
 pub use mtu::{BaseMtu, Config, Endpoint, InitialMtu, MaxMtu, MtuError, MINIMUM_MAX_DATAGRAM_SIZE};
+pub use stats::Stats;
 
 // Initial PTO backoff multiplier is 1 indicating no additional increase to the backoff.
 pub const INITIAL_PTO_BACKOFF: u32 = 1;
@@ -247,6 +253,14 @@ impl Handle for Tuple {
         // once we discover our path, or the port changes, update the address with the new information
         if self.local_address.port() != other.local_address.port() {
             self.local_address = other.local_address;
+            return;
+        }
+
+        // the IP can change without the port if the local socket is rebound
+        if !other.local_address.ip().is_unspecified()
+            && self.local_address.unmap() != other.local_address.unmap()
+        {
+            self.local_address = other.local_address;
         }
     }
 }
@@ -20,9 +20,12 @@ use core::{
 };
 use s2n_codec::EncoderValue;
 
+mod history;
 #[cfg(test)]
 mod tests;
 
+pub use history::{History, HistoryEntry, HISTORY_CAPACITY};
+
 #[cfg(any(test, feature = "testing"))]
 pub mod testing {
     use super::*;
@@ -335,6 +338,7 @@ pub struct Config {
     initial_mtu: InitialMtu,
     base_mtu: BaseMtu,
     max_mtu: MaxMtu,
+    history: bool,
 }
 
 impl Endpoint for Config {
@@ -352,6 +356,7 @@ impl Config {
         initial_mtu: InitialMtu::MIN,
         base_mtu: BaseMtu::MIN,
         max_mtu: MaxMtu::MIN,
+        history: false,
     };
 
     pub fn builder() -> Builder {
@@ -373,6 +378,11 @@ impl Config {
         self.max_mtu
     }
 
+    /// Returns true if the MTU probing history is recorded for each path
+    pub fn history(&self) -> bool {
+        self.history
+    }
+
     /// Returns true if the MTU configuration is valid
     ///
     /// A valid MTU configuration must have base_mtu <= initial_mtu <= max_mtu
@@ -380,6 +390,21 @@ impl Config {
     pub fn is_valid(&self) -> bool {
         self.base_mtu.0 <= self.initial_mtu.0 && self.initial_mtu.0 <= self.max_mtu.0
     }
+
+    /// Raises the initial MTU to the `mtu` of a previous connection on the same path
+    ///
+    /// The `mtu` is limited to the `max_mtu` and is ignored if it is lower than the configured
+    /// `initial_mtu`.
+    #[inline]
+    pub fn with_cached_mtu(mut self, mtu: u16) -> Self {
+        let mtu = mtu.min(self.max_mtu.0.get());
+        if mtu > self.initial_mtu.0.get() {
+            if let Ok(initial_mtu) = mtu.try_into() {
+                self.initial_mtu = initial_mtu;
+            }
+        }
+        self
+    }
 }
 
 #[derive(Debug, Default)]
@@ -387,6 +412,7 @@ pub struct Builder {
     initial_mtu: Option<InitialMtu>,
     base_mtu: Option<BaseMtu>,
     max_mtu: Option<MaxMtu>,
+    history: bool,
 }
 
 impl Builder {
@@ -445,6 +471,15 @@ impl Builder {
         Ok(self)
     }
 
+    /// Sets whether the MTU probing history is recorded for each path (default: false)
+    ///
+    /// The history is retrieved with `Connection::mtu_history` and is allocated for each path
+    /// when enabled, so it should only be enabled while diagnosing path MTU issues.
+    pub fn with_history(mut self, enabled: bool) -> Self {
+        self.history = enabled;
+        self
+    }
+
     pub fn build(self) -> Result<Config, MtuError> {
         let base_mtu = self.base_mtu.unwrap_or_default();
         let max_mtu = self.max_mtu.unwrap_or_default();
@@ -465,6 +500,7 @@ impl Builder {
             initial_mtu,
             max_mtu,
             base_mtu,
+            history: self.history,
         };
 
         ensure!(config.is_valid(), Err(MtuError));
@@ -515,6 +551,11 @@ pub struct Controller {
     //# sender will continue to use the current PLPMTU, after which it
     //# reenters the Search Phase.
     pmtu_raise_timer: Timer,
+    /// The most recent MTU probing events, used for diagnosing path MTU issues
+    ///
+    /// This is only allocated if the history was enabled in the `Config`.
+    #[cfg(feature = "alloc")]
+    history: Option<alloc::boxed::Box<History>>,
 }
 
 impl Controller {
@@ -579,6 +620,8 @@ impl Controller {
             black_hole_counter: Default::default(),
             largest_acked_mtu_sized_packet: None,
             pmtu_raise_timer: Timer::default(),
+            #[cfg(feature = "alloc")]
+            history: config.history.then(Default::default),
         }
     }
 
@@ -594,6 +637,30 @@ impl Controller {
         self.request_new_search(None);
     }
 
+    /// Limits the datagram size to the `max_udp_payload_size` transport parameter of the peer
+    ///
+    //= https://www.rfc-editor.org/rfc/rfc9000#section-18.2
+    //# It is expected that this is the space
+    //# available for QUIC packets and that the peer will not send packets
+    //# larger than this value.
+    #[inline]
+    pub fn on_peer_max_udp_payload_size(&mut self, max_udp_payload_size: u16) {
+        // the peer is required to advertise at least 1200 so never go below the base PLPMTU
+        let limit = max_udp_payload_size.max(self.base_plpmtu);
+        ensure!(limit < self.max_udp_payload);
+
+        self.max_udp_payload = limit;
+        self.max_probe_size = self.max_probe_size.min(limit);
+        self.plpmtu = self.plpmtu.min(limit);
+        self.probed_size = self.probed_size.min(limit);
+
+        if !self.is_next_probe_size_above_threshold() {
+            // there's no longer any room to probe for a larger MTU
+            self.state = State::SearchComplete;
+            self.pmtu_raise_timer.cancel();
+        }
+    }
+
     /// Called when the connection timer expires
     #[inline]
     pub fn on_timeout(&mut self, now: Timestamp) {
@@ -624,6 +691,8 @@ impl Controller {
                 self.state = State::SearchComplete;
             }
 
+            self.record(HistoryEntry::InitialMtuPacketAcknowledged { mtu: self.plpmtu });
+
             // Publish an `on_mtu_updated` event since the cause
             // and possibly search_complete status have changed
             publisher.on_mtu_updated(event::builder::MtuUpdated {
@@ -657,6 +726,7 @@ impl Controller {
         if let State::Searching(probe_packet_number, transmit_time) = self.state {
             if packet_number == probe_packet_number {
                 self.plpmtu = self.probed_size;
+                self.record(HistoryEntry::ProbeAcknowledged { size: self.plpmtu });
                 // A new MTU has been confirmed, notify the congestion controller
                 congestion_controller.on_mtu_update(
                     self.plpmtu,
@@ -734,6 +804,11 @@ impl Controller {
                     self.state = State::SearchComplete;
                 }
 
+                self.record(HistoryEntry::InitialMtuPacketLost {
+                    mtu: self.plpmtu,
+                    time: now,
+                });
+
                 publisher.on_mtu_updated(event::builder::MtuUpdated {
                     path_id: path_id.into_event(),
                     mtu: self.plpmtu,
@@ -745,6 +820,11 @@ impl Controller {
             }
             State::Searching(probe_pn, _) if *probe_pn == packet_number => {
                 // The MTU probe was lost
+                self.record(HistoryEntry::ProbeLost {
+                    size: self.probed_size,
+                    time: now,
+                });
+
                 if self.probe_count == MAX_PROBES {
                     // We've sent MAX_PROBES without acknowledgement, so
                     // attempt a smaller probe size
@@ -800,6 +880,18 @@ impl Controller {
         self.plpmtu as usize
     }
 
+    /// Gets the currently validated MTU
+    ///
+    /// This includes the size of the UDP header and the minimum size of the IP header.
+    #[inline]
+    pub fn mtu(&self, peer_socket_address: &inet::SocketAddress) -> u16 {
+        let min_ip_header_len = match peer_socket_address {
+            inet::SocketAddress::IpV4(_) => IPV4_MIN_HEADER_LEN,
+            inet::SocketAddress::IpV6(_) => IPV6_MIN_HEADER_LEN,
+        };
+        self.plpmtu + UDP_HEADER_LEN + min_ip_header_len
+    }
+
     /// Gets the max datagram size currently being probed for
     #[inline]
     pub fn probed_sized(&self) -> usize {
@@ -811,6 +903,26 @@ impl Controller {
         self.state.is_search_complete()
     }
 
+    /// Returns the most recent MTU probing events for the path
+    ///
+    /// `None` is returned if the history was not enabled in the `Config`.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn history(&self) -> Option<&History> {
+        self.history.as_deref()
+    }
+
+    /// Records an entry in the MTU probing history, if enabled
+    #[inline]
+    fn record(&mut self, entry: HistoryEntry) {
+        #[cfg(feature = "alloc")]
+        if let Some(history) = self.history.as_mut() {
+            history.push(entry);
+        }
+        #[cfg(not(feature = "alloc"))]
+        let _ = entry;
+    }
+
     /// Sets `probed_size` to the next MTU size to probe for based on a binary search
     #[inline]
     fn update_probed_size(&mut self) {
@@ -874,6 +986,11 @@ impl Controller {
         // Arm the PMTU raise timer to try a larger MTU again after a cooling off period
         self.arm_pmtu_raise_timer(now + BLACK_HOLE_COOL_OFF_DURATION);
 
+        self.record(HistoryEntry::BlackholeDetected {
+            mtu: self.plpmtu,
+            time: now,
+        });
+
         publisher.on_mtu_updated(event::builder::MtuUpdated {
             path_id: path_id.into_event(),
             mtu: self.plpmtu,
@@ -958,6 +1075,10 @@ impl transmission::Provider for Controller {
         }) {
             self.probe_count += 1;
             self.state = State::Searching(packet_number, context.current_time());
+            self.record(HistoryEntry::ProbeSent {
+                size: self.probed_size,
+                time: context.current_time(),
+            });
         }
     }
 }
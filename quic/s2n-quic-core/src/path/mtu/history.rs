@@ -0,0 +1,90 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::time::Timestamp;
+
+/// The number of entries retained in the MTU probing history of a path
+pub const HISTORY_CAPACITY: usize = 16;
+
+/// An entry in the MTU probing history of a path
+///
+/// Sizes are the maximum QUIC datagram size, not including UDP and IP headers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum HistoryEntry {
+    /// A probe packet was sent
+    #[non_exhaustive]
+    ProbeSent { size: u16, time: Timestamp },
+    /// A probe packet was acknowledged and the MTU was raised to its size
+    #[non_exhaustive]
+    ProbeAcknowledged { size: u16 },
+    /// A probe packet was declared lost
+    #[non_exhaustive]
+    ProbeLost { size: u16, time: Timestamp },
+    /// A black hole was detected and the MTU was reset to the base MTU
+    #[non_exhaustive]
+    BlackholeDetected { mtu: u16, time: Timestamp },
+    /// A packet sent with the configured `InitialMtu` was acknowledged
+    #[non_exhaustive]
+    InitialMtuPacketAcknowledged { mtu: u16 },
+    /// A packet sent with the configured `InitialMtu` was lost and the MTU was reset to the
+    /// base MTU
+    #[non_exhaustive]
+    InitialMtuPacketLost { mtu: u16, time: Timestamp },
+}
+
+/// The most recent MTU probing events for a path
+///
+/// Only the last [`HISTORY_CAPACITY`] entries are retained, which is enough to observe
+/// oscillation between probe sizes while bounding the memory used by each path. The history is
+/// only allocated for paths when enabled with `mtu::Builder::with_history`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct History {
+    entries: [Option<HistoryEntry>; HISTORY_CAPACITY],
+    /// The index the next entry will be written to
+    next: usize,
+}
+
+impl History {
+    /// Records an entry, evicting the oldest entry if the history is at capacity
+    #[inline]
+    pub fn push(&mut self, entry: HistoryEntry) {
+        self.entries[self.next] = Some(entry);
+        self.next = (self.next + 1) % HISTORY_CAPACITY;
+    }
+
+    /// Returns the retained entries, from oldest to newest
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = &HistoryEntry> + '_ {
+        let (newest, oldest) = self.entries.split_at(self.next);
+        oldest.iter().chain(newest).flatten()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time::{Clock, NoopClock};
+
+    #[test]
+    fn history_test() {
+        let mut history = History::default();
+        assert_eq!(history.iter().count(), 0);
+
+        let time = NoopClock.get_time();
+        for size in 0..HISTORY_CAPACITY as u16 + 4 {
+            history.push(HistoryEntry::ProbeSent { size, time });
+        }
+
+        // only the most recent entries are retained, from oldest to newest
+        let sizes: Vec<_> = history
+            .iter()
+            .map(|entry| match entry {
+                HistoryEntry::ProbeSent { size, .. } => *size,
+                entry => panic!("unexpected entry {entry:?}"),
+            })
+            .collect();
+        let expected: Vec<_> = (4..HISTORY_CAPACITY as u16 + 4).collect();
+        assert_eq!(sizes, expected);
+    }
+}
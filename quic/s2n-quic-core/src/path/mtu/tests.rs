@@ -33,6 +33,7 @@ fn mtu_config_is_valid() {
         initial_mtu: 1500.try_into().unwrap(),
         base_mtu: 1228.try_into().unwrap(),
         max_mtu: 9000.try_into().unwrap(),
+        ..Default::default()
     };
 
     assert!(config.is_valid());
@@ -41,6 +42,7 @@ fn mtu_config_is_valid() {
         initial_mtu: 1500.try_into().unwrap(),
         base_mtu: 1500.try_into().unwrap(),
         max_mtu: 1500.try_into().unwrap(),
+        ..Default::default()
     };
 
     assert!(config.is_valid());
@@ -49,6 +51,7 @@ fn mtu_config_is_valid() {
         initial_mtu: 1500.try_into().unwrap(),
         base_mtu: 1501.try_into().unwrap(),
         max_mtu: 9000.try_into().unwrap(),
+        ..Default::default()
     };
 
     assert!(!config.is_valid());
@@ -57,6 +60,7 @@ fn mtu_config_is_valid() {
         initial_mtu: 1500.try_into().unwrap(),
         base_mtu: 1228.try_into().unwrap(),
         max_mtu: 1400.try_into().unwrap(),
+        ..Default::default()
     };
 
     assert!(!config.is_valid());
@@ -121,6 +125,35 @@ fn mtu_config_builder() {
     assert_eq!(Some(MtuError), result.err());
 }
 
+#[test]
+fn mtu_config_with_cached_mtu() {
+    let config = mtu::Config::builder()
+        .with_initial_mtu(1300)
+        .unwrap()
+        .with_max_mtu(1400)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    let cached = config.with_cached_mtu(1350);
+    assert_eq!(1350, u16::from(cached.initial_mtu()));
+    assert!(cached.is_valid());
+
+    // the cached MTU is limited to the max MTU
+    let cached = config.with_cached_mtu(9001);
+    assert_eq!(1400, u16::from(cached.initial_mtu()));
+
+    // a cached MTU lower than the initial MTU is ignored
+    let cached = config.with_cached_mtu(1250);
+    assert_eq!(1300, u16::from(cached.initial_mtu()));
+
+    // the MTU of a controller round trips through the cache
+    let addr: SocketAddr = "[::1]:443".parse().unwrap();
+    let addr = addr.into();
+    let controller = Controller::new(cached, &addr);
+    assert_eq!(1300, controller.mtu(&addr));
+}
+
 #[test]
 fn mtu_manager() {
     let remote = inet::SocketAddress::default();
@@ -148,6 +181,7 @@ fn mtu_manager() {
         initial_mtu: InitialMtu::MIN,
         base_mtu: BaseMtu(NonZeroU16::new(1500).unwrap()),
         max_mtu: MaxMtu::MIN,
+        ..Default::default()
     };
     assert!(!mtu_provider.is_valid());
     let mut manager: Manager<Config> = Manager::new(mtu_provider);
@@ -377,6 +411,50 @@ fn enable() {
     assert_eq!(State::SearchRequested, controller.state);
 }
 
+#[test]
+fn on_peer_max_udp_payload_size() {
+    // a limit larger than the local max is ignored
+    let mut controller = new_controller(1500);
+    let max_udp_payload = controller.max_udp_payload;
+    controller.on_peer_max_udp_payload_size(u16::MAX);
+    assert_eq!(max_udp_payload, controller.max_udp_payload);
+    assert_eq!(State::Disabled, controller.state);
+
+    // the probed size and max probe size are clamped to the peer limit
+    let mut controller = new_controller(9000);
+    controller.on_peer_max_udp_payload_size(1400);
+    assert_eq!(1400, controller.max_udp_payload);
+    assert_eq!(1400, controller.max_probe_size);
+    assert_eq!(1400, controller.probed_size);
+    assert_eq!(State::Disabled, controller.state);
+    controller.enable();
+    assert_eq!(State::SearchRequested, controller.state);
+
+    // the limit can't go below the base PLPMTU and completes the search
+    let mut controller = new_controller(9000);
+    controller.on_peer_max_udp_payload_size(1000);
+    assert_eq!(MINIMUM_MAX_DATAGRAM_SIZE, controller.max_udp_payload);
+    assert_eq!(
+        MINIMUM_MAX_DATAGRAM_SIZE as usize,
+        controller.max_datagram_size()
+    );
+    assert_eq!(State::SearchComplete, controller.state);
+
+    // a configured initial MTU above the peer limit is lowered
+    let addr: SocketAddr = "127.0.0.1:443".parse().unwrap();
+    let mut controller = Controller::new(
+        Config {
+            initial_mtu: 1500.try_into().unwrap(),
+            max_mtu: 9000.try_into().unwrap(),
+            ..Default::default()
+        },
+        &addr.into(),
+    );
+    controller.on_peer_max_udp_payload_size(1300);
+    assert_eq!(1300, controller.max_datagram_size());
+    assert_eq!(State::SearchComplete, controller.state);
+}
+
 //= https://www.rfc-editor.org/rfc/rfc8899#section-4.2
 //= type=test
 //# When
@@ -1012,3 +1090,84 @@ fn on_transmit() {
     );
     assert_eq!(State::Searching(packet_number, now), controller.state);
 }
+
+#[test]
+fn history() {
+    // the history is only recorded if enabled
+    assert!(new_controller(1500).history().is_none());
+
+    let ip = IpV4Address::new([127, 0, 0, 1]);
+    let addr = inet::SocketAddress::IpV4(SocketAddressV4::new(ip, 443));
+    let config = Config::builder()
+        .with_max_mtu(1500)
+        .unwrap()
+        .with_history(true)
+        .build()
+        .unwrap();
+    let mut controller = Controller::new(config, &addr);
+    controller.state = State::SearchRequested;
+    let now = now();
+    let mut cc = CongestionController::default();
+    let mut publisher = Publisher::no_snapshot();
+    let probed_size = controller.probed_size;
+
+    let send_probe = |controller: &mut Controller| {
+        let mut frame_buffer = OutgoingFrameBuffer::new();
+        frame_buffer.set_max_packet_size(Some(probed_size as usize));
+        let mut write_context = MockWriteContext::new(
+            now,
+            &mut frame_buffer,
+            transmission::Constraint::None,
+            transmission::Mode::MtuProbing,
+            endpoint::Type::Server,
+        );
+        controller.on_transmit(&mut write_context);
+
+        match controller.state {
+            State::Searching(packet_number, _) => packet_number,
+            ref state => panic!("unexpected state {state:?}"),
+        }
+    };
+
+    // the first probe is lost
+    let packet_number = send_probe(&mut controller);
+    controller.on_packet_loss(
+        packet_number,
+        probed_size,
+        false,
+        now,
+        &mut cc,
+        path::Id::test_id(),
+        &mut publisher,
+    );
+
+    // the second probe is acknowledged
+    let packet_number = send_probe(&mut controller);
+    controller.on_packet_ack(
+        packet_number,
+        probed_size,
+        &mut cc,
+        path::Id::test_id(),
+        &mut publisher,
+    );
+
+    let history: Vec<_> = controller.history().unwrap().iter().copied().collect();
+    assert_eq!(
+        history,
+        vec![
+            HistoryEntry::ProbeSent {
+                size: probed_size,
+                time: now
+            },
+            HistoryEntry::ProbeLost {
+                size: probed_size,
+                time: now
+            },
+            HistoryEntry::ProbeSent {
+                size: probed_size,
+                time: now
+            },
+            HistoryEntry::ProbeAcknowledged { size: probed_size },
+        ]
+    );
+}
@@ -0,0 +1,191 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Configures how validated paths are probed for liveness while they are idle
+//!
+//! Keep-alive PINGs only prevent the idle timeout from expiring and are sent on the active path.
+//! Liveness probes instead send a PATH_CHALLENGE on each eligible path after it has been idle
+//! for the probe interval. A path which stops answering its probes is marked as
+//! [`Liveness::Unresponsive`], which allows failover to be detected well before the idle timeout
+//! or loss recovery would close the connection.
+
+use core::time::Duration;
+
+/// Selects the paths which are probed for liveness
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Paths {
+    /// Only the active path is probed
+    #[default]
+    Active,
+    /// Every validated path is probed, including paths the connection migrated away from
+    ///
+    /// If the active path becomes unresponsive, the connection falls back to the last path it
+    /// migrated away from, as long as that path is still responsive.
+    Validated,
+}
+
+/// The liveness of a path, as observed by the probes sent on it
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Liveness {
+    /// The path hasn't been probed or no packets have been received on it yet
+    #[default]
+    Unknown,
+    /// A packet or probe response was received on the path since the last probe was sent
+    Responsive,
+    /// The last probe sent on the path wasn't answered before it timed out
+    Unresponsive,
+}
+
+impl Liveness {
+    #[inline]
+    pub fn is_responsive(&self) -> bool {
+        matches!(self, Self::Responsive)
+    }
+
+    #[inline]
+    pub fn is_unresponsive(&self) -> bool {
+        matches!(self, Self::Unresponsive)
+    }
+}
+
+/// The policy for probing idle paths for liveness
+///
+/// # Examples
+///
+/// ```rust
+/// use core::time::Duration;
+/// use s2n_quic_core::path::probe::{Paths, Policy};
+///
+/// // probe every 250ms, backing off to once per second while the path stays idle
+/// let policy = Policy::new(Duration::from_millis(250))
+///     .with_backoff(2)
+///     .with_max_interval(Duration::from_secs(1))
+///     .with_timeout(Duration::from_millis(500))
+///     .with_paths(Paths::Validated);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Policy {
+    interval: Duration,
+    max_interval: Duration,
+    backoff: u8,
+    timeout: Option<Duration>,
+    paths: Paths,
+}
+
+impl Policy {
+    /// Creates a policy which probes idle paths every `interval`
+    ///
+    /// By default the interval is not backed off, probes time out after three times the PTO of
+    /// the path, and only the active path is probed.
+    pub const fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            max_interval: interval,
+            backoff: 1,
+            timeout: None,
+            paths: Paths::Active,
+        }
+    }
+
+    /// Multiplies the interval by `backoff` after each probe sent while the path is idle
+    /// (default: 1)
+    ///
+    /// The interval is reset once a packet is received on the path. A value of 0 is treated
+    /// as 1.
+    pub const fn with_backoff(mut self, backoff: u8) -> Self {
+        self.backoff = if backoff == 0 { 1 } else { backoff };
+        self
+    }
+
+    /// Limits how far the interval is backed off (default: the initial interval)
+    ///
+    /// Values below the initial interval are raised to it.
+    pub fn with_max_interval(mut self, max_interval: Duration) -> Self {
+        self.max_interval = max_interval.max(self.interval);
+        self
+    }
+
+    /// Sets how long to wait for a probe response before the path is marked as unresponsive
+    /// (default: three times the PTO of the path)
+    pub const fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Selects the paths which are probed (default: [`Paths::Active`])
+    pub const fn with_paths(mut self, paths: Paths) -> Self {
+        self.paths = paths;
+        self
+    }
+
+    #[inline]
+    pub fn initial_interval(&self) -> Duration {
+        self.interval
+    }
+
+    #[inline]
+    pub fn max_interval(&self) -> Duration {
+        self.max_interval
+    }
+
+    #[inline]
+    pub fn backoff(&self) -> u8 {
+        self.backoff
+    }
+
+    #[inline]
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    #[inline]
+    pub fn paths(&self) -> Paths {
+        self.paths
+    }
+
+    /// Returns the interval to wait before sending the next probe, after `probes` probes have
+    /// been sent since the path was last active
+    #[inline]
+    pub fn interval(&self, probes: u32) -> Duration {
+        let mut interval = self.interval;
+
+        for _ in 0..probes {
+            if self.backoff == 1 || interval.is_zero() || interval >= self.max_interval {
+                break;
+            }
+            interval = interval.saturating_mul(self.backoff as u32);
+        }
+
+        interval.min(self.max_interval)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interval_test() {
+        let policy = Policy::new(Duration::from_millis(100));
+        assert_eq!(policy.interval(0), Duration::from_millis(100));
+        assert_eq!(policy.interval(10), Duration::from_millis(100));
+
+        let policy = policy
+            .with_backoff(2)
+            .with_max_interval(Duration::from_millis(500));
+        assert_eq!(policy.interval(0), Duration::from_millis(100));
+        assert_eq!(policy.interval(1), Duration::from_millis(200));
+        assert_eq!(policy.interval(2), Duration::from_millis(400));
+        assert_eq!(policy.interval(3), Duration::from_millis(500));
+        assert_eq!(policy.interval(u32::MAX), Duration::from_millis(500));
+
+        // the max interval can't be lower than the initial interval
+        let policy = Policy::new(Duration::from_secs(1))
+            .with_backoff(0)
+            .with_max_interval(Duration::from_millis(1));
+        assert_eq!(policy.backoff(), 1);
+        assert_eq!(policy.max_interval(), Duration::from_secs(1));
+        assert_eq!(policy.interval(5), Duration::from_secs(1));
+    }
+}
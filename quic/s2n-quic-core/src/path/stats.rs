@@ -0,0 +1,38 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{frame::ack::EcnCounts, inet::SocketAddress, path::probe::Liveness};
+use core::time::Duration;
+
+/// A snapshot of the recovery statistics for a single path
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Stats {
+    /// The local address of the path
+    pub local_address: SocketAddress,
+    /// The remote address of the path
+    pub remote_address: SocketAddress,
+    /// `true` if the path is currently used to send non-probing packets
+    pub is_active: bool,
+    /// The exponentially-weighted moving average of RTT samples
+    pub smoothed_rtt: Duration,
+    /// The mean deviation of RTT samples
+    pub rtt_variance: Duration,
+    /// The minimum RTT observed on the path
+    pub min_rtt: Duration,
+    /// The most recent RTT sample
+    pub latest_rtt: Duration,
+    /// The number of consecutive probe timeouts without receiving an acknowledgement
+    pub pto_count: u32,
+    /// The number of packets sent on the path which were declared lost
+    pub lost_packets: u64,
+    /// The number of bytes sent on the path which were declared lost
+    pub lost_bytes: u64,
+    /// The ECN counts reported by the peer for packets sent on the path
+    ///
+    /// The peer reports cumulative counts for all paths, so each increase is attributed to the
+    /// path of the largest packet it acknowledges.
+    pub ecn_counts: EcnCounts,
+    /// The liveness of the path, if it is probed by the path probe policy
+    pub liveness: Liveness,
+}
@@ -486,7 +486,7 @@ impl CongestionController for BbrCongestionController {
         &mut self,
         lost_bytes: u32,
         packet_info: Self::PacketInfo,
-        _persistent_congestion: bool,
+        persistent_congestion: bool,
         new_loss_burst: bool,
         random_generator: &mut dyn random::Generator,
         timestamp: Timestamp,
@@ -510,6 +510,17 @@ impl CongestionController for BbrCongestionController {
             timestamp,
             publisher,
         );
+
+        //= https://www.rfc-editor.org/rfc/rfc9002#section-7.6.2
+        //# When persistent congestion is declared, the sender's congestion
+        //# window MUST be reduced to the minimum congestion window
+        //# (kMinimumWindow), similar to a TCP sender's response on an RTO
+        //# [RFC5681].
+        if persistent_congestion {
+            // kMinimumWindow is 2 * max_datagram_size, which is below BBRMinPipeCwnd. The window
+            // is raised back to BBRMinPipeCwnd by `set_cwnd` as acknowledgements arrive.
+            self.cwnd = 2 * self.max_datagram_size as u32;
+        }
     }
 
     #[inline]
@@ -1151,7 +1162,24 @@ impl congestion_controller::Endpoint for Endpoint {
         &mut self,
         path_info: congestion_controller::PathInfo,
     ) -> Self::CongestionController {
-        BbrCongestionController::new(path_info.max_datagram_size, self.app_settings)
+        let mut controller =
+            BbrCongestionController::new(path_info.max_datagram_size, self.app_settings);
+
+        // An initial window configured by the application takes precedence over the cache
+        if self.app_settings.initial_congestion_window.is_none() {
+            if let Some(entry) = path_info.cached_path {
+                controller.cwnd = entry.resumed_congestion_window(controller.cwnd);
+                // Pace the resumed window over the previous connection's minimum RTT rather than
+                // the 1ms used without an RTT estimate, so it isn't sent as a burst
+                controller.pacer.on_cached_path(
+                    controller.cwnd,
+                    entry.min_rtt,
+                    &controller.app_settings,
+                );
+            }
+        }
+
+        controller
     }
 }
 
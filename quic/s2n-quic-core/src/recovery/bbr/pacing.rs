@@ -68,6 +68,21 @@ impl Pacer {
         self.capacity -= bytes_sent as u32;
     }
 
+    /// Initializes the pacing rate with the resumed `cwnd` and the `min_rtt` of a previous
+    /// connection on the same path
+    #[inline]
+    pub(super) fn on_cached_path(
+        &mut self,
+        cwnd: u32,
+        min_rtt: Duration,
+        app_settings: &ApplicationSettings,
+    ) {
+        // the RTT is limited to the 1ms used before there is an RTT estimate
+        let bw = Bandwidth::new(cwnd as u64, min_rtt.max(Duration::from_millis(1)));
+        self.pacing_rate =
+            Self::bandwidth_to_pacing_rate(bw, State::Startup.pacing_gain(app_settings));
+    }
+
     /// Initialize the pacing rate with the given rtt and cwnd
     #[inline]
     pub(super) fn initialize_pacing_rate<Pub: Publisher>(
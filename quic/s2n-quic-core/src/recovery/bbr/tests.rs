@@ -46,6 +46,53 @@ fn initial_window_with_app_settings() {
     );
 }
 
+#[test]
+fn initial_window_with_cached_path() {
+    let mtu_config = path::mtu::Config::default();
+    let remote_address = crate::inet::SocketAddress::default();
+    let min_rtt = Duration::from_millis(50);
+    let new_controller = |endpoint: &mut bbr::Endpoint, congestion_window: Option<u32>| {
+        let mut path_info =
+            crate::recovery::congestion_controller::PathInfo::new(&mtu_config, &remote_address);
+        path_info.cached_path = congestion_window.map(|congestion_window| path::cache::Entry {
+            smoothed_rtt: Duration::from_millis(60),
+            min_rtt,
+            mtu: 1500,
+            congestion_window,
+        });
+        crate::recovery::congestion_controller::Endpoint::new_congestion_controller(
+            endpoint, path_info,
+        )
+    };
+
+    let mut endpoint = bbr::Endpoint::default();
+    let default = new_controller(&mut endpoint, None);
+    let default_window = default.congestion_window();
+
+    // half of the cached window is used and paced over the cached min RTT
+    let controller = new_controller(&mut endpoint, Some(default_window * 4));
+    assert_eq!(default_window * 2, controller.congestion_window());
+    let expected_rate = Bandwidth::new((default_window * 2) as u64, min_rtt)
+        * State::Startup.pacing_gain(&Default::default());
+    assert!(controller.pacing_rate() < default.pacing_rate());
+    assert_delta!(
+        expected_rate.as_bytes_per_second() as f64,
+        controller.pacing_rate().as_bytes_per_second() as f64,
+        expected_rate.as_bytes_per_second() as f64 * 0.02
+    );
+
+    // the window is limited to a multiple of the default
+    let controller = new_controller(&mut endpoint, Some(u32::MAX));
+    assert_eq!(default_window * 4, controller.congestion_window());
+
+    // the window configured by the application takes precedence
+    let mut endpoint = bbr::builder::Builder::default()
+        .with_initial_congestion_window(20_000)
+        .build();
+    let controller = new_controller(&mut endpoint, Some(default_window * 4));
+    assert_eq!(20_000, controller.congestion_window());
+}
+
 //= https://tools.ietf.org/id/draft-cardwell-iccrg-bbr-congestion-control-02#4.5.6.3
 //= type=test
 //# When not explicitly accelerating to probe for bandwidth (Drain, ProbeRTT,
@@ -33,6 +33,9 @@ pub struct PathInfo<'a> {
     pub remote_address: SocketAddress<'a>,
     pub application_protocol: Option<&'a [u8]>,
     pub max_datagram_size: u16,
+    /// The characteristics of a previous connection's path to the same peer, if they were
+    /// stored in the path cache
+    pub cached_path: Option<path::cache::Entry>,
 }
 
 impl<'a> PathInfo<'a> {
@@ -42,6 +45,7 @@ impl<'a> PathInfo<'a> {
             remote_address: remote_address.into_event(),
             application_protocol: None,
             max_datagram_size: mtu_config.initial_mtu().max_datagram_size(remote_address),
+            cached_path: None,
         }
     }
 }
@@ -894,7 +894,18 @@ impl congestion_controller::Endpoint for Endpoint {
         &mut self,
         path_info: congestion_controller::PathInfo,
     ) -> Self::CongestionController {
-        CubicCongestionController::new(path_info.max_datagram_size, self.app_settings)
+        let mut controller =
+            CubicCongestionController::new(path_info.max_datagram_size, self.app_settings);
+
+        // An initial window configured by the application takes precedence over the cache
+        if self.app_settings.initial_congestion_window.is_none() {
+            if let Some(entry) = path_info.cached_path {
+                controller.congestion_window =
+                    entry.resumed_congestion_window(controller.congestion_window as u32) as f32;
+            }
+        }
+
+        controller
     }
 }
 
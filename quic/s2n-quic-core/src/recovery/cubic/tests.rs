@@ -222,6 +222,44 @@ fn initial_window_with_app_settings() {
     );
 }
 
+#[test]
+fn initial_window_with_cached_path() {
+    let mtu_config = path::mtu::Config::default();
+    let remote_address = crate::inet::SocketAddress::default();
+    let new_controller = |endpoint: &mut Endpoint, congestion_window: Option<u32>| {
+        let mut path_info = congestion_controller::PathInfo::new(&mtu_config, &remote_address);
+        path_info.cached_path = congestion_window.map(|congestion_window| path::cache::Entry {
+            smoothed_rtt: Duration::from_millis(100),
+            min_rtt: Duration::from_millis(90),
+            mtu: 1500,
+            congestion_window,
+        });
+        congestion_controller::Endpoint::new_congestion_controller(endpoint, path_info)
+    };
+
+    let mut endpoint = Endpoint::default();
+    let default_window = new_controller(&mut endpoint, None).congestion_window();
+
+    // half of the cached window is used
+    let controller = new_controller(&mut endpoint, Some(default_window * 4));
+    assert_eq!(default_window * 2, controller.congestion_window());
+
+    // the window is never lower than the default
+    let controller = new_controller(&mut endpoint, Some(default_window));
+    assert_eq!(default_window, controller.congestion_window());
+
+    // the window is limited to a multiple of the default
+    let controller = new_controller(&mut endpoint, Some(u32::MAX));
+    assert_eq!(default_window * 4, controller.congestion_window());
+
+    // the window configured by the application takes precedence
+    let mut endpoint = builder::Builder::default()
+        .with_initial_congestion_window(20_000)
+        .build();
+    let controller = new_controller(&mut endpoint, Some(default_window * 4));
+    assert_eq!(20_000, controller.congestion_window());
+}
+
 //= https://www.rfc-editor.org/rfc/rfc9002#section-7.2
 //= type=test
 //# The RECOMMENDED
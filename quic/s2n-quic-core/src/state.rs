@@ -105,6 +105,22 @@ macro_rules! __state_event__ {
             );
         )*
 
+        /// The names of all of the events, in declaration order
+        #[allow(dead_code)] // only used by model checkers and fuzzers
+        pub const EVENTS: &'static [&'static str] = &[$(stringify!($event)),*];
+
+        /// Applies the event at `index` in [`Self::EVENTS`]
+        ///
+        /// This allows model checkers and fuzzers to drive the state machine without knowing the
+        /// names of its events. Returns `None` if `index` is out of bounds.
+        #[inline]
+        #[allow(dead_code)] // only used by model checkers and fuzzers
+        pub fn apply_event(&mut self, index: usize) -> Option<$crate::state::Result<Self>> {
+            let events: &[fn(&mut Self) -> $crate::state::Result<Self>] = &[$(Self::$event),*];
+            let event = events.get(index)?;
+            Some(event(self))
+        }
+
         #[cfg(test)]
         pub fn test_transitions() -> impl ::core::fmt::Debug {
             use $crate::state::Error;
@@ -254,6 +270,51 @@ macro_rules! __state_is__ {
 
 pub use crate::__state_is__ as is;
 
+/// Applies a sequence of events to a state machine and checks the invariants that hold for every
+/// machine defined with [`event!`]:
+///
+/// * A failed transition leaves the state unchanged and reports it as the current state
+/// * Terminal states, as reported by `is_terminal`, are never left
+///
+/// Event indices are wrapped to the number of events, so any input is valid. This makes it
+/// usable with fuzzers and model checkers, e.g. `check_model(&mut state, events, T::EVENTS.len(),
+/// T::apply_event, T::is_terminal)`.
+#[cfg(any(test, feature = "testing"))]
+pub fn check_model<T, A, I>(
+    state: &mut T,
+    events: &[u8],
+    event_count: usize,
+    apply: A,
+    is_terminal: I,
+) where
+    T: Clone + PartialEq + fmt::Debug,
+    A: Fn(&mut T, usize) -> Option<Result<T>>,
+    I: Fn(&T) -> bool,
+{
+    assert!(event_count > 0);
+
+    for event in events {
+        let index = *event as usize % event_count;
+        let prev = state.clone();
+        let result = apply(state, index).expect("index should be in bounds");
+
+        match result {
+            Ok(()) => {}
+            Err(Error::NoOp { current }) | Err(Error::InvalidTransition { current, .. }) => {
+                assert_eq!(
+                    prev, *state,
+                    "failed transitions should not change the state"
+                );
+                assert_eq!(prev, current);
+            }
+        }
+
+        if is_terminal(&prev) {
+            assert_eq!(prev, *state, "terminal states should not be left");
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Error<T> {
     NoOp { current: T },
@@ -95,3 +95,31 @@ fn microwave_snapshots() {
 fn microwave_dot_test() {
     assert_snapshot!(Microwave::dot());
 }
+
+#[test]
+fn apply_event_test() {
+    assert_eq!(Lr::EVENTS, &["on_left", "on_right"]);
+
+    let mut state = Lr::default();
+    assert!(state.apply_event(0).unwrap().is_ok());
+    assert_eq!(state, Lr::Left);
+    assert!(state.apply_event(1).unwrap().is_ok());
+    assert_eq!(state, Lr::LeftRight);
+    assert!(state.apply_event(1).unwrap().is_err());
+    assert!(state.apply_event(2).is_none());
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn microwave_model_test() {
+    bolero::check!().with_type::<Vec<u8>>().for_each(|events| {
+        let mut state = Microwave::default();
+        check_model(
+            &mut state,
+            events,
+            Microwave::EVENTS.len(),
+            Microwave::apply_event,
+            |_| false,
+        );
+    });
+}
@@ -0,0 +1,52 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Hooks for transparently compressing stream data
+//!
+//! Compression is applied by the stream itself, below the application API and above
+//! packetization. The application sends and receives uncompressed data, while the compressed
+//! data is what gets buffered, counted against flow control and packetized.
+
+use crate::stream::StreamError;
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Compresses the data written to a stream
+pub trait Encoder: 'static + Send {
+    /// Compresses `input` and appends any available output to `output`
+    fn encode(&mut self, input: &[u8], output: &mut Vec<u8>) -> Result<(), StreamError>;
+
+    /// Appends all of the buffered output to `output` so the peer is able to decode everything
+    /// encoded so far
+    ///
+    /// This is called when the application flushes the stream.
+    fn flush(&mut self, output: &mut Vec<u8>) -> Result<(), StreamError>;
+
+    /// Appends the remaining output to `output` and terminates the compressed stream
+    ///
+    /// This is called when the application finishes the stream.
+    fn finish(&mut self, output: &mut Vec<u8>) -> Result<(), StreamError>;
+}
+
+impl fmt::Debug for dyn Encoder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Encoder").finish_non_exhaustive()
+    }
+}
+
+/// Decompresses the data read from a stream
+pub trait Decoder: 'static + Send {
+    /// Decompresses `input` and appends any available output to `output`
+    fn decode(&mut self, input: &[u8], output: &mut Vec<u8>) -> Result<(), StreamError>;
+
+    /// Appends the remaining output to `output` once the peer has finished the stream
+    ///
+    /// Implementations should return an error if the compressed stream was truncated.
+    fn finish(&mut self, output: &mut Vec<u8>) -> Result<(), StreamError>;
+}
+
+impl fmt::Debug for dyn Decoder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Decoder").finish_non_exhaustive()
+    }
+}
@@ -1,7 +1,10 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{application, connection, frame::ConnectionClose, transport};
+use crate::{
+    application, connection, frame::ConnectionClose, transport,
+    transport::parameters::ValidationError,
+};
 use core::{fmt, panic};
 
 /// Errors that a stream can encounter.
@@ -66,6 +69,27 @@ pub enum StreamError {
     NonEmptyOutput {
         source: &'static panic::Location<'static>,
     },
+    /// The operation did not complete before the read or write timeout configured on the stream
+    ///
+    /// The stream remains usable and the operation can be retried.
+    #[non_exhaustive]
+    Timeout {
+        source: &'static panic::Location<'static>,
+    },
+    /// The codec configured on the stream failed to compress or decompress the stream data
+    #[non_exhaustive]
+    CompressionError {
+        reason: &'static str,
+        source: &'static panic::Location<'static>,
+    },
+    /// The stream was configured with an invalid value
+    ///
+    /// The invalid value is ignored and the stream remains usable.
+    #[non_exhaustive]
+    InvalidConfiguration {
+        error: ValidationError,
+        source: &'static panic::Location<'static>,
+    },
 }
 
 #[cfg(feature = "std")]
@@ -98,6 +122,13 @@ impl fmt::Display for StreamError {
                 f,
                 "The stream was provided a non-empty placeholder buffer for receiving data."
             ),
+            Self::Timeout { .. } => write!(f, "The stream operation timed out"),
+            Self::CompressionError { reason, .. } => {
+                write!(f, "The stream data could not be compressed: {reason}")
+            }
+            Self::InvalidConfiguration { error, .. } => {
+                write!(f, "The stream configuration is invalid: {error}")
+            }
         }
     }
 }
@@ -115,6 +146,20 @@ impl StreamError {
             StreamError::NonWritable { source } => source,
             StreamError::SendingBlocked { source } => source,
             StreamError::NonEmptyOutput { source } => source,
+            StreamError::Timeout { source } => source,
+            StreamError::CompressionError { source, .. } => source,
+            StreamError::InvalidConfiguration { source, .. } => source,
+        }
+    }
+
+    /// Returns what caused the connection to close, if the stream failed because of it
+    ///
+    /// See [`connection::Error::close_source`].
+    pub fn close_source(&self) -> Option<connection::error::CloseSource> {
+        if let StreamError::ConnectionError { error } = self {
+            error.close_source()
+        } else {
+            None
         }
     }
 
@@ -181,6 +226,31 @@ impl StreamError {
         let source = panic::Location::caller();
         StreamError::NonEmptyOutput { source }
     }
+
+    #[track_caller]
+    #[inline]
+    #[doc(hidden)]
+    pub fn timeout() -> StreamError {
+        let source = panic::Location::caller();
+        StreamError::Timeout { source }
+    }
+
+    /// Creates an error for a stream compression codec which failed for the provided reason
+    #[track_caller]
+    #[inline]
+    pub fn compression_error(reason: &'static str) -> StreamError {
+        let source = panic::Location::caller();
+        StreamError::CompressionError { reason, source }
+    }
+
+    #[track_caller]
+    #[inline]
+    #[doc(hidden)]
+    pub fn invalid_configuration(reason: &'static str) -> StreamError {
+        let source = panic::Location::caller();
+        let error = ValidationError(reason);
+        StreamError::InvalidConfiguration { error, source }
+    }
 }
 
 impl application::error::TryInto for StreamError {
@@ -237,6 +307,9 @@ impl From<StreamError> for std::io::ErrorKind {
             StreamError::NonWritable { .. } => ErrorKind::Other,
             StreamError::SendingBlocked { .. } => ErrorKind::WouldBlock,
             StreamError::NonEmptyOutput { .. } => ErrorKind::InvalidInput,
+            StreamError::Timeout { .. } => ErrorKind::TimedOut,
+            StreamError::CompressionError { .. } => ErrorKind::InvalidData,
+            StreamError::InvalidConfiguration { .. } => ErrorKind::InvalidInput,
         }
     }
 }
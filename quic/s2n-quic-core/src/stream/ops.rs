@@ -28,8 +28,13 @@
 //!     .await?;
 //! ```
 
-use crate::{application, stream};
-use core::task::Poll;
+use crate::{
+    application,
+    stream::{self, compression},
+    varint::VarInt,
+};
+use alloc::boxed::Box;
+use core::{task::Poll, time::Duration};
 
 /// A request made on a stream
 #[derive(Default, Debug)]
@@ -54,6 +59,65 @@ impl<'a> Request<'a> {
         self
     }
 
+    /// Resets the tx stream with an error code after the data up to `reliable_size` has been
+    /// delivered to the peer
+    pub fn reset_at(&mut self, error: application::Error, reliable_size: VarInt) -> &mut Self {
+        let tx = self.tx_mut();
+        tx.reset = Some(error);
+        tx.reliable_size = Some(reliable_size);
+        self
+    }
+
+    /// Sets the maximum amount of time small writes on the tx stream are delayed before being
+    /// transmitted
+    ///
+    /// See [`tx::Request::coalescing_delay`] for more details.
+    pub fn with_coalescing_delay(&mut self, delay: Duration) -> &mut Self {
+        self.tx_mut().coalescing_delay = Some(delay);
+        self
+    }
+
+    /// Sets the maximum amount of time an operation on the tx stream may block
+    ///
+    /// See [`tx::Request::timeout`] for more details.
+    pub fn with_write_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.tx_mut().timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the maximum amount of unacknowledged data buffered on the tx stream
+    ///
+    /// See [`tx::Request::buffer_size`] for more details.
+    pub fn with_send_buffer_size(&mut self, size: u32) -> &mut Self {
+        self.tx_mut().buffer_size = Some(size);
+        self
+    }
+
+    /// Sets the amount of time the chunks sent in the request remain useful to the peer
+    ///
+    /// See [`tx::Request::expiration`] for more details.
+    pub fn with_expiration(&mut self, lifetime: Duration) -> &mut Self {
+        self.tx_mut().expiration = Some(lifetime);
+        self
+    }
+
+    /// Sets the amount of time chunks sent on the tx stream without an expiration remain useful
+    /// to the peer
+    ///
+    /// See [`tx::Request::default_expiration`] for more details.
+    pub fn with_default_expiration(&mut self, lifetime: Duration) -> &mut Self {
+        self.tx_mut().default_expiration = Some(lifetime);
+        self
+    }
+
+    /// Compresses all of the following data sent on the tx stream with the provided encoder
+    ///
+    /// See [`tx::Request::encoder`] for more details.
+    pub fn with_encoder(&mut self, encoder: Box<dyn compression::Encoder>) -> &mut Self {
+        self.tx_mut().encoder = Some(encoder);
+        self
+    }
+
     /// Flushes any pending tx data to be ACKed before unblocking
     pub fn flush(&mut self) -> &mut Self {
         self.tx_mut().flush = true;
@@ -66,6 +130,13 @@ impl<'a> Request<'a> {
         self
     }
 
+    /// Waits for the peer to acknowledge all of the tx data, including the end of the stream,
+    /// before unblocking
+    pub fn await_finished(&mut self) -> &mut Self {
+        self.tx_mut().await_finished = true;
+        self
+    }
+
     /// Requests data on the rx stream to be received into the provided slice of chunks
     pub fn receive(&mut self, chunks: &'a mut [bytes::Bytes]) -> &mut Self {
         self.rx_mut().chunks = Some(chunks);
@@ -113,6 +184,31 @@ impl<'a> Request<'a> {
         self
     }
 
+    /// Sets the maximum amount of time an operation on the rx stream may block
+    ///
+    /// See [`rx::Request::timeout`] for more details.
+    pub fn with_read_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.rx_mut().timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the maximum amount of data the peer may send ahead of the application on the rx
+    /// stream
+    ///
+    /// See [`rx::Request::buffer_size`] for more details.
+    pub fn with_receive_buffer_size(&mut self, size: u32) -> &mut Self {
+        self.rx_mut().buffer_size = Some(size);
+        self
+    }
+
+    /// Decompresses all of the data received on the rx stream with the provided decoder
+    ///
+    /// See [`rx::Request::decoder`] for more details.
+    pub fn with_decoder(&mut self, decoder: Box<dyn compression::Decoder>) -> &mut Self {
+        self.rx_mut().decoder = Some(decoder);
+        self
+    }
+
     pub fn detach_tx(&mut self) -> &mut Self {
         let tx = self.tx_mut();
         tx.detached = true;
@@ -187,12 +283,75 @@ pub mod tx {
         /// Optionally reset the stream with an error
         pub reset: Option<application::Error>,
 
+        /// The amount of data which is delivered to the peer before the reset takes effect
+        ///
+        /// This is only used if `reset` is set and the peer supports the RESET_STREAM_AT
+        /// extension.
+        pub reliable_size: Option<VarInt>,
+
+        /// Optionally updates the maximum amount of time small writes are delayed
+        ///
+        /// While enabled, data is held back until at least a full packet of data is buffered,
+        /// the delay expires, or the stream is flushed or finished. This trades latency for
+        /// fewer, fuller packets, similar to disabling `TCP_NODELAY`. A delay of zero disables
+        /// coalescing, which is the default.
+        pub coalescing_delay: Option<Duration>,
+
+        /// Optionally updates the maximum amount of time an operation may block on the stream
+        ///
+        /// Once an operation has been blocked for longer than the timeout, it fails with a
+        /// `Timeout` error. The stream itself remains usable. A timeout of zero disables the
+        /// deadline, which is the default.
+        pub timeout: Option<Duration>,
+
+        /// Optionally updates the maximum amount of unacknowledged data buffered on the stream
+        ///
+        /// Writes are blocked once the buffer is full, until the peer acknowledges some of the
+        /// data. Lowering the size doesn't drop any data which is already buffered. The default
+        /// is the connection's configured maximum send buffer size.
+        pub buffer_size: Option<u32>,
+
+        /// Optionally expires the chunks sent in the request after the given lifetime
+        ///
+        /// The lifetime starts when the chunk is written. Once it has passed, the chunk is no
+        /// longer transmitted or retransmitted and the peer skips over it if it hasn't received it
+        /// yet. Data written before the chunk without an expiration is still delivered reliably
+        /// first.
+        ///
+        /// This is only used if the peer supports partially reliable streams. Otherwise, the
+        /// chunks are delivered reliably.
+        pub expiration: Option<Duration>,
+
+        /// Optionally updates the expiration of chunks sent without an `expiration`
+        ///
+        /// This applies the same partial reliability to all of the following writes on the
+        /// stream, including writes through APIs which can't specify an expiration. As with
+        /// `expiration`, the lifetime of each chunk starts when it's written, so chunks which
+        /// are still waiting to be sent are dropped as well. A lifetime of zero delivers the
+        /// chunks reliably again, which is the default.
+        pub default_expiration: Option<Duration>,
+
+        /// Optionally compresses the stream data with the provided encoder
+        ///
+        /// The encoder is applied to all of the chunks sent in this and any following requests.
+        /// The compressed data is what gets buffered, counted against flow control and
+        /// transmitted, while the responses report the number of uncompressed bytes consumed.
+        /// Flushing and finishing the stream also flush and finish the encoder. The encoder can
+        /// only be set before any data was sent on the stream.
+        pub encoder: Option<Box<dyn compression::Encoder>>,
+
         /// Waits for an ACK on resets and finishes
         pub flush: bool,
 
         /// Marks the tx stream as finished (e.g. no more data will be sent)
         pub finish: bool,
 
+        /// Waits for an ACK of all of the data and the end of the stream
+        ///
+        /// Unlike `finish`, this doesn't end the stream itself. The request will only unblock
+        /// once the application has finished the stream and the peer acknowledged everything.
+        pub await_finished: bool,
+
         /// Marks the tx stream as detached, which makes the stream make progress, regardless of
         /// application observations.
         pub detached: bool,
@@ -271,6 +430,30 @@ pub mod rx {
         /// Optionally requests the peer to stop sending data with an error
         pub stop_sending: Option<application::Error>,
 
+        /// Optionally updates the maximum amount of time an operation may block on the stream
+        ///
+        /// Once an operation has been blocked for longer than the timeout, it fails with a
+        /// `Timeout` error. The stream itself remains usable. A timeout of zero disables the
+        /// deadline, which is the default.
+        pub timeout: Option<Duration>,
+
+        /// Optionally updates the maximum amount of data the peer may send ahead of the
+        /// application
+        ///
+        /// This is the stream flow control window advertised to the peer once the application
+        /// reads data. Since an advertised window can't be revoked, lowering the size only takes
+        /// effect as the already buffered data is read. The default is the connection's
+        /// configured stream data window.
+        pub buffer_size: Option<u32>,
+
+        /// Optionally decompresses the stream data with the provided decoder
+        ///
+        /// The decoder is applied to all of the data received in this and any following requests.
+        /// The watermarks refer to the compressed data which is read from the stream, while the
+        /// responses report the decompressed data. The decoder can only be set before any data
+        /// was read from the stream.
+        pub decoder: Option<Box<dyn compression::Decoder>>,
+
         /// Marks the rx stream as detached, which makes the stream make progress, regardless of
         /// application observations.
         pub detached: bool,
@@ -283,6 +466,9 @@ pub mod rx {
                 low_watermark: 0,
                 high_watermark: usize::MAX,
                 stop_sending: None,
+                timeout: None,
+                buffer_size: None,
+                decoder: None,
                 detached: false,
             }
         }
@@ -303,6 +489,12 @@ pub mod rx {
 
         /// The current status of the stream
         pub status: Status,
+
+        /// The range of stream data which the peer gave up on before it was read
+        ///
+        /// The range immediately precedes the received chunks, or the end of the stream if the
+        /// stream finished. This is only set on partially reliable streams.
+        pub expired: Option<core::ops::Range<u64>>,
     }
 
     impl Default for Response {
@@ -312,6 +504,7 @@ pub mod rx {
                 chunks: Chunks::default(),
                 will_wake: false,
                 status: Status::Open,
+                expired: None,
             }
         }
     }
@@ -510,7 +703,15 @@ mod tests {
                     chunks: Some(tx_chunks),
                     finish: true,
                     flush: true,
+                    await_finished: false,
                     reset: Some(reset),
+                    reliable_size: None,
+                    coalescing_delay: None,
+                    timeout: None,
+                    buffer_size: None,
+                    expiration: None,
+                    default_expiration: None,
+                    encoder: None,
                     detached: false,
                 }),
                 rx: Some(rx::Request {
@@ -518,6 +719,9 @@ mod tests {
                     low_watermark: 5,
                     high_watermark: 10,
                     stop_sending: Some(stop_sending),
+                    timeout: None,
+                    buffer_size: None,
+                    decoder: None,
                     detached: false,
                 })
             } if reset == application::Error::new(1).unwrap()
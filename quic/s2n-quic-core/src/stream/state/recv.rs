@@ -69,6 +69,7 @@ impl Receiver {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::testing::InlineVec;
     use insta::{assert_debug_snapshot, assert_snapshot};
 
     #[test]
@@ -82,4 +83,23 @@ mod tests {
     fn dot_test() {
         assert_snapshot!(Receiver::dot());
     }
+
+    const LEN: usize = if cfg!(kani) { 4 } else { 16 };
+
+    #[test]
+    #[cfg_attr(kani, kani::proof, kani::unwind(5), kani::solver(kissat))]
+    fn model_test() {
+        bolero::check!()
+            .with_type::<InlineVec<u8, LEN>>()
+            .for_each(|events| {
+                let mut state = Receiver::default();
+                crate::state::check_model(
+                    &mut state,
+                    events,
+                    Receiver::EVENTS.len(),
+                    Receiver::apply_event,
+                    Receiver::is_terminal,
+                );
+            });
+    }
 }
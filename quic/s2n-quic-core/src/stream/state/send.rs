@@ -75,6 +75,7 @@ impl Sender {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::testing::InlineVec;
     use insta::{assert_debug_snapshot, assert_snapshot};
 
     #[test]
@@ -88,4 +89,23 @@ mod tests {
     fn dot_test() {
         assert_snapshot!(Sender::dot());
     }
+
+    const LEN: usize = if cfg!(kani) { 4 } else { 16 };
+
+    #[test]
+    #[cfg_attr(kani, kani::proof, kani::unwind(5), kani::solver(kissat))]
+    fn model_test() {
+        bolero::check!()
+            .with_type::<InlineVec<u8, LEN>>()
+            .for_each(|events| {
+                let mut state = Sender::default();
+                crate::state::check_model(
+                    &mut state,
+                    events,
+                    Sender::EVENTS.len(),
+                    Sender::apply_event,
+                    Sender::is_terminal,
+                );
+            });
+    }
 }
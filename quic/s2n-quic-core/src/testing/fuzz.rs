@@ -0,0 +1,196 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Fuzz entry points for the QUIC wire codecs
+//!
+//! Each function accepts arbitrary bytes and panics if the decoded values violate an internal
+//! invariant, such as failing to round trip or reporting an incorrect encoding size. This makes
+//! them suitable to pass directly to a fuzz target, e.g. `check!().for_each(fuzz::frames)`.
+
+use crate::{
+    connection::{id::ConnectionInfo, ProcessingError},
+    crypto::key::testing,
+    frame::FrameRef,
+    inet::SocketAddress,
+    packet::{
+        encoding::PacketEncoder, number::PacketNumberSpace, CleartextPacket, ProtectedPacket,
+    },
+    varint::VarInt,
+};
+use s2n_codec::{DecoderBufferMut, Encoder, EncoderBuffer, EncoderLenEstimator, EncoderValue};
+
+/// Decodes a sequence of variable-length integers and checks that each one round trips
+pub fn varint(input: &[u8]) {
+    s2n_codec::assert_codec_round_trip_bytes!(VarInt, input);
+}
+
+/// Decodes a sequence of frames and checks that each one round trips and reports the length
+/// that it actually encodes to
+pub fn frames(input: &[u8]) {
+    let mut input = input.to_vec();
+    let frames = s2n_codec::assert_codec_round_trip_bytes_mut!(FrameRef, &mut input);
+
+    for frame in frames {
+        // make sure the frames encoding size matches what would actually
+        // be written to an encoder
+        let mut estimator = EncoderLenEstimator::new(usize::MAX);
+        frame.encode(&mut estimator);
+        assert_eq!(frame.encoding_size(), estimator.len());
+    }
+}
+
+/// Decodes a sequence of coalesced packets and checks that the connection IDs and tokens are
+/// preserved through header protection removal, decryption and re-encoding
+///
+/// Packets are protected with the no-op keys from [`crate::crypto::key::testing`].
+pub fn packets(input: &[u8]) {
+    let mut data = input.to_vec();
+    // add a few bytes to the end for padding
+    let mut encoder_data = vec![0; data.len() * 2];
+
+    let mut decoder_buffer = DecoderBufferMut::new(&mut data);
+    let mut encoder_buffer = EncoderBuffer::new(&mut encoder_data);
+    let remote_address = SocketAddress::default();
+    let connection_info = ConnectionInfo::new(&remote_address);
+
+    while let Ok((packet, remaining)) =
+        ProtectedPacket::decode(decoder_buffer, &connection_info, &20)
+    {
+        if let Ok(cleartext_packet) = decrypt_packet(packet) {
+            encoder_buffer = encode_packet(cleartext_packet, encoder_buffer);
+        }
+        decoder_buffer = remaining;
+    }
+}
+
+fn decrypt_packet(packet: ProtectedPacket) -> Result<CleartextPacket, ProcessingError> {
+    use ProtectedPacket::*;
+    match packet {
+        Handshake(packet) => {
+            let dcid = packet.destination_connection_id().to_vec();
+            let scid = packet.source_connection_id().to_vec();
+
+            let packet = packet.unprotect(
+                &testing::HeaderKey::new(),
+                PacketNumberSpace::Handshake.new_packet_number(Default::default()),
+            )?;
+            assert_eq!(dcid, packet.destination_connection_id());
+            assert_eq!(scid, packet.source_connection_id());
+
+            let packet = packet.decrypt(&testing::Key::new())?;
+            assert_eq!(dcid, packet.destination_connection_id());
+            assert_eq!(scid, packet.source_connection_id());
+
+            Ok(CleartextPacket::Handshake(packet))
+        }
+        Initial(packet) => {
+            let dcid = packet.destination_connection_id().to_vec();
+            let scid = packet.source_connection_id().to_vec();
+            let token = packet.token().to_vec();
+
+            let packet = packet.unprotect(
+                &testing::HeaderKey::new(),
+                PacketNumberSpace::Initial.new_packet_number(Default::default()),
+            )?;
+            assert_eq!(dcid, packet.destination_connection_id());
+            assert_eq!(scid, packet.source_connection_id());
+            assert_eq!(token, packet.token());
+
+            let packet = packet.decrypt(&testing::Key::new())?;
+            assert_eq!(dcid, packet.destination_connection_id());
+            assert_eq!(scid, packet.source_connection_id());
+            assert_eq!(token, packet.token());
+
+            Ok(CleartextPacket::Initial(packet))
+        }
+        Retry(packet) => {
+            let _ = packet.destination_connection_id();
+            let _ = packet.source_connection_id();
+
+            Ok(CleartextPacket::Retry(packet))
+        }
+        Short(packet) => {
+            let dcid = packet.destination_connection_id().to_vec();
+
+            let packet = packet.unprotect(
+                &testing::HeaderKey::new(),
+                PacketNumberSpace::ApplicationData.new_packet_number(Default::default()),
+            )?;
+            assert_eq!(dcid, packet.destination_connection_id());
+
+            let packet = packet.decrypt(&testing::Key::new())?;
+            assert_eq!(dcid, packet.destination_connection_id());
+
+            Ok(CleartextPacket::Short(packet))
+        }
+        ZeroRtt(packet) => {
+            let dcid = packet.destination_connection_id().to_vec();
+            let scid = packet.source_connection_id().to_vec();
+
+            let packet = packet.unprotect(
+                &testing::HeaderKey::new(),
+                PacketNumberSpace::ApplicationData.new_packet_number(Default::default()),
+            )?;
+            assert_eq!(dcid, packet.destination_connection_id());
+            assert_eq!(scid, packet.source_connection_id());
+
+            let packet = packet.decrypt(&testing::Key::new())?;
+            assert_eq!(dcid, packet.destination_connection_id());
+            assert_eq!(scid, packet.source_connection_id());
+
+            Ok(CleartextPacket::ZeroRtt(packet))
+        }
+        VersionNegotiation(packet) => {
+            let _: Vec<_> = packet.iter().collect();
+
+            Ok(CleartextPacket::VersionNegotiation(packet))
+        }
+    }
+}
+
+fn encode_packet<'a>(packet: CleartextPacket, mut encoder: EncoderBuffer<'a>) -> EncoderBuffer<'a> {
+    use CleartextPacket::*;
+    let result = match packet {
+        Handshake(packet) => packet.encode_packet(
+            &mut testing::Key::new(),
+            &testing::HeaderKey::new(),
+            PacketNumberSpace::Handshake.new_packet_number(Default::default()),
+            None,
+            encoder,
+        ),
+        Initial(packet) => packet.encode_packet(
+            &mut testing::Key::new(),
+            &testing::HeaderKey::new(),
+            PacketNumberSpace::Initial.new_packet_number(Default::default()),
+            None,
+            encoder,
+        ),
+        Retry(packet) => {
+            encoder.encode(&packet);
+            return encoder;
+        }
+        Short(packet) => packet.encode_packet(
+            &mut testing::Key::new(),
+            &testing::HeaderKey::new(),
+            PacketNumberSpace::ApplicationData.new_packet_number(Default::default()),
+            None,
+            encoder,
+        ),
+        ZeroRtt(packet) => packet.encode_packet(
+            &mut testing::Key::new(),
+            &testing::HeaderKey::new(),
+            PacketNumberSpace::ApplicationData.new_packet_number(Default::default()),
+            None,
+            encoder,
+        ),
+        VersionNegotiation(packet) => {
+            encoder.encode(&packet);
+            return encoder;
+        }
+    };
+
+    match result {
+        Ok((_, encoder)) => encoder,
+        Err(err) => err.take_buffer(),
+    }
+}
@@ -222,7 +222,7 @@ where
     }
 }
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub struct ValidationError(pub(crate) &'static str);
 
 const MAX_ENCODABLE_VALUE: ValidationError =
@@ -272,7 +272,7 @@ macro_rules! transport_parameter {
         );
     };
     ($name:ident($encodable_type:ty), $tag:expr, $default:expr) => {
-        #[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Ord)]
+        #[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Eq, Ord, Hash)]
         pub struct $name($encodable_type);
 
         impl Default for $name {
@@ -588,6 +588,10 @@ impl TransportParameterValidator for stateless_reset::Token {}
 
 transport_parameter!(MaxUdpPayloadSize(VarInt), 0x03, VarInt::from_u16(65527));
 
+impl MaxUdpPayloadSize {
+    pub const DEFAULT: Self = Self(VarInt::from_u16(65527));
+}
+
 impl TransportParameterValidator for MaxUdpPayloadSize {
     fn validate(self) -> Result<Self, DecoderError> {
         decoder_invariant!(
@@ -1208,6 +1212,188 @@ impl<'a> IntoEvent<&'a [u32]> for &'a DcSupportedVersions {
     }
 }
 
+/// Used by endpoints to indicate the shared dictionary they support for compressing stream data
+///
+/// A value of `0` indicates that stream compression is not supported.
+#[derive(Clone, Copy, Debug, Default, PartialEq, PartialOrd, Eq, Ord)]
+pub struct CompressionDictionaryId(VarInt);
+
+impl CompressionDictionaryId {
+    pub const DEFAULT: Self = Self(VarInt::ZERO);
+
+    /// Returns the advertised dictionary identifier, if any
+    pub fn get(&self) -> Option<VarInt> {
+        Some(self.0).filter(|id| *id != VarInt::ZERO)
+    }
+}
+
+impl From<VarInt> for CompressionDictionaryId {
+    fn from(value: VarInt) -> Self {
+        Self(value)
+    }
+}
+
+impl TryFrom<u64> for CompressionDictionaryId {
+    type Error = ValidationError;
+
+    fn try_from(value: u64) -> Result<Self, Self::Error> {
+        Ok(Self(VarInt::new(value)?))
+    }
+}
+
+/// The experimental codepoint of the [`CompressionDictionaryId`] transport parameter
+///
+/// The extension is not registered with IANA. The codepoint is taken from the range assigned
+/// via the Specification Required policy, which allows provisional registrations (see
+/// [RFC 9000 §22.1](https://www.rfc-editor.org/rfc/rfc9000#section-22.1)), and isn't one of the
+/// reserved `31 * N + 27` values. The codepoint should be updated once the extension is registered.
+pub const COMPRESSION_DICTIONARY_ID: u32 = 0xc0d100;
+
+impl TransportParameter for CompressionDictionaryId {
+    const ID: TransportParameterId = TransportParameterId::from_u32(COMPRESSION_DICTIONARY_ID);
+    type CodecValue = VarInt;
+
+    fn from_codec_value(value: Self::CodecValue) -> Self {
+        Self(value)
+    }
+
+    fn try_into_codec_value(&self) -> Option<&Self::CodecValue> {
+        if *self == Self::default_value() {
+            None
+        } else {
+            Some(&self.0)
+        }
+    }
+
+    fn default_value() -> Self {
+        Self::default()
+    }
+}
+
+impl TransportParameterValidator for CompressionDictionaryId {}
+
+/// Used by endpoints to indicate they support the GOAWAY extension frame for coordinating
+/// connection draining
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GoAwaySupport {
+    Enabled,
+    #[default]
+    Disabled,
+}
+
+impl GoAwaySupport {
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        matches!(self, Self::Enabled)
+    }
+}
+
+impl TransportParameter for GoAwaySupport {
+    type CodecValue = ();
+
+    const ID: TransportParameterId = TransportParameterId::from_u32(0x60a7);
+
+    fn from_codec_value(_value: ()) -> Self {
+        GoAwaySupport::Enabled
+    }
+
+    fn try_into_codec_value(&self) -> Option<&()> {
+        if let GoAwaySupport::Enabled = self {
+            Some(&())
+        } else {
+            None
+        }
+    }
+
+    fn default_value() -> Self {
+        Self::default()
+    }
+}
+
+impl TransportParameterValidator for GoAwaySupport {}
+
+/// Used by endpoints to indicate they support the RESET_STREAM_AT extension frame for
+/// resetting streams after delivering a prefix of the stream data
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ResetStreamAtSupport {
+    Enabled,
+    #[default]
+    Disabled,
+}
+
+impl ResetStreamAtSupport {
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        matches!(self, Self::Enabled)
+    }
+}
+
+impl TransportParameter for ResetStreamAtSupport {
+    type CodecValue = ();
+
+    // Safety: the value is less than VarInt::MAX
+    const ID: TransportParameterId =
+        unsafe { TransportParameterId::new_unchecked(0x17f7586d2cb571) };
+
+    fn from_codec_value(_value: ()) -> Self {
+        ResetStreamAtSupport::Enabled
+    }
+
+    fn try_into_codec_value(&self) -> Option<&()> {
+        if let ResetStreamAtSupport::Enabled = self {
+            Some(&())
+        } else {
+            None
+        }
+    }
+
+    fn default_value() -> Self {
+        Self::default()
+    }
+}
+
+impl TransportParameterValidator for ResetStreamAtSupport {}
+
+/// Used by endpoints to indicate they support the EXPIRED_STREAM_DATA extension frame for
+/// partially reliable streams
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PartialReliabilitySupport {
+    Enabled,
+    #[default]
+    Disabled,
+}
+
+impl PartialReliabilitySupport {
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        matches!(self, Self::Enabled)
+    }
+}
+
+impl TransportParameter for PartialReliabilitySupport {
+    type CodecValue = ();
+
+    const ID: TransportParameterId = TransportParameterId::from_u32(0x60a8);
+
+    fn from_codec_value(_value: ()) -> Self {
+        PartialReliabilitySupport::Enabled
+    }
+
+    fn try_into_codec_value(&self) -> Option<&()> {
+        if let PartialReliabilitySupport::Enabled = self {
+            Some(&())
+        } else {
+            None
+        }
+    }
+
+    fn default_value() -> Self {
+        Self::default()
+    }
+}
+
+impl TransportParameterValidator for PartialReliabilitySupport {}
+
 //= https://www.rfc-editor.org/rfc/rfc9000#section-18.2
 //# If present, transport parameters that set initial per-stream flow
 //# control limits (initial_max_stream_data_bidi_local,
@@ -1567,6 +1753,10 @@ impl_transport_parameters!(
         initial_source_connection_id: Option<InitialSourceConnectionId>,
         retry_source_connection_id: RetrySourceConnectionId,
         dc_supported_versions: DcSupportedVersions,
+        compression_dictionary_id: CompressionDictionaryId,
+        go_away_support: GoAwaySupport,
+        reset_stream_at_support: ResetStreamAtSupport,
+        partial_reliability_support: PartialReliabilitySupport,
     }
 );
 
@@ -1614,6 +1804,20 @@ impl<
         load!(ack_delay_exponent, ack_delay_exponent);
         load!(max_active_connection_ids, active_connection_id_limit);
         load!(max_datagram_frame_size, max_datagram_frame_size);
+        load!(max_udp_payload_size, max_udp_payload_size);
         load!(migration_support, migration_support);
+        load!(compression_dictionary_id, compression_dictionary_id);
+        load!(go_away_support, go_away_support);
+        load!(reset_stream_at_support, reset_stream_at_support);
+        load!(partial_reliability_support, partial_reliability_support);
+
+        if limits.datagram_only {
+            self.initial_max_data = Default::default();
+            self.initial_max_stream_data_bidi_local = Default::default();
+            self.initial_max_stream_data_bidi_remote = Default::default();
+            self.initial_max_stream_data_uni = Default::default();
+            self.initial_max_streams_bidi = Default::default();
+            self.initial_max_streams_uni = Default::default();
+        }
     }
 }
@@ -84,6 +84,10 @@ fn server_transport_parameters() -> ServerTransportParameters {
             len: 1,
             versions: [3, 0, 0, 0],
         },
+        compression_dictionary_id: Default::default(),
+        go_away_support: Default::default(),
+        reset_stream_at_support: Default::default(),
+        partial_reliability_support: Default::default(),
     }
 }
 
@@ -125,6 +129,10 @@ fn client_transport_parameters() -> ClientTransportParameters {
             len: 4,
             versions: [1, 2, 3, 4],
         },
+        compression_dictionary_id: Default::default(),
+        go_away_support: Default::default(),
+        reset_stream_at_support: Default::default(),
+        partial_reliability_support: Default::default(),
     }
 }
 
@@ -270,6 +278,85 @@ fn dc_supported_versions() {
     }
 }
 
+#[test]
+fn compression_dictionary_id_codepoint() {
+    // the experimental codepoint is outside of the Standards Action range
+    assert!(COMPRESSION_DICTIONARY_ID > 0x3f);
+    // and isn't reserved for greasing
+    assert_ne!((COMPRESSION_DICTIONARY_ID - 27) % 31, 0);
+}
+
+#[test]
+fn compression_dictionary_id() {
+    let mut value = server_transport_parameters();
+    assert!(value.compression_dictionary_id.get().is_none());
+
+    value.compression_dictionary_id = 1234u64.try_into().unwrap();
+
+    let encoded = value.encode_to_vec();
+    let decoder = DecoderBuffer::new(&encoded);
+    let (decoded_params, remaining) =
+        ServerTransportParameters::decode(decoder).expect("Decoding succeeds");
+
+    assert_eq!(
+        Some(VarInt::from_u16(1234)),
+        decoded_params.compression_dictionary_id.get()
+    );
+    assert_eq!(value, decoded_params);
+    assert_eq!(0, remaining.len());
+}
+
+#[test]
+fn go_away_support() {
+    let mut value = client_transport_parameters();
+    assert!(!value.go_away_support.is_enabled());
+
+    value.go_away_support = GoAwaySupport::Enabled;
+
+    let encoded = value.encode_to_vec();
+    let decoder = DecoderBuffer::new(&encoded);
+    let (decoded_params, remaining) =
+        ClientTransportParameters::decode(decoder).expect("Decoding succeeds");
+
+    assert!(decoded_params.go_away_support.is_enabled());
+    assert_eq!(value, decoded_params);
+    assert_eq!(0, remaining.len());
+}
+
+#[test]
+fn reset_stream_at_support() {
+    let mut value = server_transport_parameters();
+    assert!(!value.reset_stream_at_support.is_enabled());
+
+    value.reset_stream_at_support = ResetStreamAtSupport::Enabled;
+
+    let encoded = value.encode_to_vec();
+    let decoder = DecoderBuffer::new(&encoded);
+    let (decoded_params, remaining) =
+        ServerTransportParameters::decode(decoder).expect("Decoding succeeds");
+
+    assert!(decoded_params.reset_stream_at_support.is_enabled());
+    assert_eq!(value, decoded_params);
+    assert_eq!(0, remaining.len());
+}
+
+#[test]
+fn partial_reliability_support() {
+    let mut value = client_transport_parameters();
+    assert!(!value.partial_reliability_support.is_enabled());
+
+    value.partial_reliability_support = PartialReliabilitySupport::Enabled;
+
+    let encoded = value.encode_to_vec();
+    let decoder = DecoderBuffer::new(&encoded);
+    let (decoded_params, remaining) =
+        ClientTransportParameters::decode(decoder).expect("Decoding succeeds");
+
+    assert!(decoded_params.partial_reliability_support.is_enabled());
+    assert_eq!(value, decoded_params);
+    assert_eq!(0, remaining.len());
+}
+
 #[test]
 fn future_larger_supported_versions() {
     use s2n_codec::EncoderBuffer;
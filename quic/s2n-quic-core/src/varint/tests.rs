@@ -5,16 +5,12 @@ use super::*;
 use crate::varint::VarInt;
 use bolero::check;
 use core::mem::size_of;
-use s2n_codec::{
-    assert_codec_round_trip_bytes, assert_codec_round_trip_value, DecoderBuffer, EncoderBuffer,
-};
+use s2n_codec::{assert_codec_round_trip_value, DecoderBuffer, EncoderBuffer};
 
 #[test]
 #[cfg_attr(miri, ignore)] // This test is too expensive for miri to complete in a reasonable amount of time
 fn round_trip_bytes_test() {
-    check!().for_each(|input| {
-        assert_codec_round_trip_bytes!(VarInt, input);
-    });
+    check!().for_each(crate::testing::fuzz::varint);
 }
 
 #[test]
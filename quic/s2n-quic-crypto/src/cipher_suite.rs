@@ -94,6 +94,16 @@ macro_rules! impl_cipher_suite {
                     key
                 }
 
+                /// Returns the packet protection key and IV so they can be loaded into an
+                /// accelerator
+                #[inline]
+                pub(crate) fn key_material(&self) -> (Zeroizing<[u8; KEY_LEN]>, iv::Iv) {
+                    (
+                        Self::new_key_secret(&self.secret),
+                        Self::new_iv(&self.secret),
+                    )
+                }
+
                 fn new_iv(secret: &hkdf::Prk) -> iv::Iv {
                     iv::Iv::new(secret, &$iv_label)
                 }
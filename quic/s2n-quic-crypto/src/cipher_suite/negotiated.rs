@@ -6,6 +6,8 @@ use crate::{
     cipher_suite::{TLS_AES_128_GCM_SHA256, TLS_AES_256_GCM_SHA384, TLS_CHACHA20_POLY1305_SHA256},
     header_key::HeaderKey,
     hkdf,
+    iv::Iv,
+    offload,
 };
 use core::fmt;
 use s2n_quic_core::crypto::{self, packet_protection, scatter};
@@ -72,6 +74,24 @@ impl NegotiatedCipherSuite {
     pub fn update(&self) -> Self {
         dispatch!(self, |cipher| cipher.update().into())
     }
+
+    /// Calls `f` with the algorithm, packet protection key and IV of the cipher_suite
+    #[inline]
+    pub(crate) fn with_key_material<R>(
+        &self,
+        f: impl FnOnce(offload::Algorithm, &[u8], Iv) -> R,
+    ) -> R {
+        let algorithm = match self {
+            Self::TLS_AES_256_GCM_SHA384(_) => offload::Algorithm::AES_256_GCM,
+            Self::TLS_CHACHA20_POLY1305_SHA256(_) => offload::Algorithm::CHACHA20_POLY1305,
+            Self::TLS_AES_128_GCM_SHA256(_) => offload::Algorithm::AES_128_GCM,
+        };
+
+        dispatch!(self, |cipher| {
+            let (key, iv) = cipher.key_material();
+            f(algorithm, &key[..], iv)
+        })
+    }
 }
 
 impl crypto::Key for NegotiatedCipherSuite {
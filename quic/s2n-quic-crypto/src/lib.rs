@@ -26,7 +26,9 @@ pub struct SecretPair {
 
 pub mod handshake;
 pub mod initial;
+pub mod offload;
 pub mod one_rtt;
+pub mod pinning;
 pub mod retry;
 pub mod zero_rtt;
 
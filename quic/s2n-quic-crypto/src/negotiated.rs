@@ -135,6 +135,19 @@ macro_rules! negotiated_crypto {
             pub fn update(&self) -> Self {
                 Self(self.0.update())
             }
+
+            /// Loads the packet protection keys into the accelerator of the `dispatcher`
+            ///
+            /// The keys need to be loaded again after each key update.
+            pub fn offload_keys<O: $crate::offload::Offload>(
+                &self,
+                dispatcher: &mut $crate::offload::Dispatcher<O>,
+            ) -> Result<
+                $crate::offload::KeyPair<O>,
+                s2n_quic_core::crypto::packet_protection::Error,
+            > {
+                dispatcher.key_pair(&self.0)
+            }
         }
 
         impl s2n_quic_core::crypto::Key for $name {
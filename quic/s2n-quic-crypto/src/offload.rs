@@ -0,0 +1,744 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Offloading of AEAD seal and open operations to hardware accelerators
+//!
+//! Accelerators such as Intel QAT process operations asynchronously: requests are submitted to a
+//! queue and the results are reaped later, once the device signals completion. The [`Offload`]
+//! trait models this submission/completion interface and the [`Dispatcher`] drives it, falling
+//! back to the in-process AWS-LC implementation whenever the accelerator doesn't support an
+//! algorithm or its queue is full. Completions are always delivered through the dispatcher,
+//! regardless of which path processed a request.
+//!
+//! Keys are derived from the packet protection keys negotiated for a connection (see
+//! `OneRttKey::offload_keys`) and requests operate in place on packets laid out the same way as
+//! the transport lays them out: the header, which is authenticated but not encrypted, followed by
+//! the payload and the authentication tag. Requests are generic over the packet buffer so
+//! applications can dispatch packets from their own buffer pools without allocating per request.
+
+use crate::{
+    aws_lc_aead::{self as aead, Aad, LessSafeKey, Nonce, UnboundKey, NONCE_LEN},
+    cipher_suite::NegotiatedCipherSuite,
+    hkdf,
+    iv::Iv,
+    negotiated,
+};
+use core::{
+    fmt,
+    marker::PhantomData,
+    task::{Context, Poll},
+};
+use s2n_quic_core::crypto::packet_protection::Error;
+use std::collections::VecDeque;
+
+/// The length of the authentication tag for all supported algorithms
+pub const TAG_LEN: usize = 16;
+
+/// An AEAD algorithm used for QUIC packet protection
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[allow(non_camel_case_types)]
+pub enum Algorithm {
+    AES_128_GCM,
+    AES_256_GCM,
+    CHACHA20_POLY1305,
+}
+
+impl Algorithm {
+    #[inline]
+    pub fn key_len(self) -> usize {
+        match self {
+            Self::AES_128_GCM => 16,
+            Self::AES_256_GCM | Self::CHACHA20_POLY1305 => 32,
+        }
+    }
+
+    #[inline]
+    fn aead(self) -> &'static aead::Algorithm {
+        match self {
+            Self::AES_128_GCM => &aead::AES_128_GCM,
+            Self::AES_256_GCM => &aead::AES_256_GCM,
+            Self::CHACHA20_POLY1305 => &aead::CHACHA20_POLY1305,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Operation {
+    /// Encrypts the payload and writes the authentication tag into the last [`TAG_LEN`] bytes
+    Seal,
+    /// Verifies the trailing authentication tag and decrypts the payload
+    Open,
+}
+
+/// A single AEAD operation on a packet
+///
+/// The packet is owned by the request since an accelerator may access it after
+/// [`Offload::submit`] returns. It is returned to the caller in the matching [`Completion`].
+#[derive(Clone, Debug)]
+pub struct Request<B> {
+    /// An application-assigned identifier used to match the request with its completion
+    pub id: u64,
+    pub operation: Operation,
+    /// The full nonce, i.e. the IV combined with the packet number
+    pub nonce: [u8; NONCE_LEN],
+    /// The length of the packet header, which is used as the additional authenticated data
+    pub header_len: usize,
+    /// The header, followed by the payload and [`TAG_LEN`] bytes for the authentication tag
+    pub packet: B,
+}
+
+/// The result of a [`Request`]
+#[derive(Debug)]
+pub struct Completion<B> {
+    /// The identifier of the request
+    pub id: u64,
+    /// The packet from the request, which was encrypted or decrypted in place if successful
+    pub packet: B,
+    pub result: Result<(), Error>,
+}
+
+/// An asynchronous AEAD accelerator
+pub trait Offload: 'static + Send {
+    /// A key which has been loaded into the accelerator
+    type Key: Send + Sync;
+
+    /// The buffer holding the packet of each request
+    type Buffer: AsMut<[u8]> + Send;
+
+    /// Loads a key into the accelerator
+    ///
+    /// Returns `None` if the accelerator doesn't support the algorithm, in which case all
+    /// operations with the key are performed in-process.
+    fn load_key(&mut self, algorithm: Algorithm, key: &[u8]) -> Option<Self::Key>;
+
+    /// Submits a request to the accelerator
+    ///
+    /// If the accelerator can't accept the request, e.g. because its queue is full, the request
+    /// is returned and will be performed in-process.
+    fn submit(
+        &mut self,
+        key: &Self::Key,
+        request: Request<Self::Buffer>,
+    ) -> Result<(), Request<Self::Buffer>>;
+
+    /// Reaps finished requests into `completions`
+    ///
+    /// Returns `Poll::Pending` and arranges for the task to be woken if no requests have finished
+    /// yet and some are still in flight.
+    fn poll_completions(
+        &mut self,
+        cx: &mut Context,
+        completions: &mut VecDeque<Completion<Self::Buffer>>,
+    ) -> Poll<()>;
+}
+
+/// An [`Offload`] implementation without an accelerator, which performs all operations in-process
+pub struct Disabled<B = Vec<u8>>(PhantomData<fn() -> B>);
+
+impl<B> Default for Disabled<B> {
+    #[inline]
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<B> fmt::Debug for Disabled<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Disabled").finish()
+    }
+}
+
+impl<B: 'static + AsMut<[u8]> + Send> Offload for Disabled<B> {
+    type Key = core::convert::Infallible;
+    type Buffer = B;
+
+    #[inline]
+    fn load_key(&mut self, _algorithm: Algorithm, _key: &[u8]) -> Option<Self::Key> {
+        None
+    }
+
+    #[inline]
+    fn submit(&mut self, key: &Self::Key, _request: Request<B>) -> Result<(), Request<B>> {
+        match *key {}
+    }
+
+    #[inline]
+    fn poll_completions(
+        &mut self,
+        _cx: &mut Context,
+        _completions: &mut VecDeque<Completion<B>>,
+    ) -> Poll<()> {
+        Poll::Ready(())
+    }
+}
+
+/// A packet protection key which can be used with a [`Dispatcher`]
+pub struct Key<O: Offload> {
+    software: LessSafeKey,
+    iv: Iv,
+    offloaded: Option<O::Key>,
+}
+
+impl<O: Offload> Key<O> {
+    /// Returns `true` if operations with the key are submitted to the accelerator
+    #[inline]
+    pub fn is_offloaded(&self) -> bool {
+        self.offloaded.is_some()
+    }
+
+    /// Creates a request to seal or open the packet with the given packet number
+    #[inline]
+    pub fn request(
+        &self,
+        id: u64,
+        operation: Operation,
+        packet_number: u64,
+        header_len: usize,
+        packet: O::Buffer,
+    ) -> Request<O::Buffer> {
+        Request {
+            id,
+            operation,
+            nonce: self.iv.nonce(packet_number),
+            header_len,
+            packet,
+        }
+    }
+}
+
+impl<O: Offload> fmt::Debug for Key<O> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Key")
+            .field("algorithm", self.software.algorithm())
+            .field("is_offloaded", &self.is_offloaded())
+            .finish()
+    }
+}
+
+/// The keys for sealing outgoing packets and opening incoming packets of a connection
+#[derive(Debug)]
+pub struct KeyPair<O: Offload> {
+    pub sealer: Key<O>,
+    pub opener: Key<O>,
+}
+
+/// Dispatches batches of requests to an [`Offload`] implementation, falling back to the
+/// in-process implementation when the accelerator can't accept them
+#[derive(Default)]
+pub struct Dispatcher<O: Offload> {
+    offload: O,
+    /// Requests which have finished but haven't been reaped yet
+    ready: VecDeque<Completion<O::Buffer>>,
+    /// The number of requests submitted to the accelerator which haven't been reaped yet
+    in_flight: usize,
+    /// The number of requests which were performed in-process
+    fallbacks: u64,
+}
+
+impl<O: Offload + fmt::Debug> fmt::Debug for Dispatcher<O> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Dispatcher")
+            .field("offload", &self.offload)
+            .field("ready", &self.ready.len())
+            .field("in_flight", &self.in_flight)
+            .field("fallbacks", &self.fallbacks)
+            .finish()
+    }
+}
+
+impl<O: Offload> Dispatcher<O> {
+    #[inline]
+    pub fn new(offload: O) -> Self {
+        Self {
+            offload,
+            ready: VecDeque::new(),
+            in_flight: 0,
+            fallbacks: 0,
+        }
+    }
+
+    /// Creates a key from a QUIC packet protection secret, loading it into the accelerator if the
+    /// algorithm is supported
+    ///
+    /// The key and IV are derived from the secret as described in
+    /// <https://www.rfc-editor.org/rfc/rfc9001#section-5.1>.
+    pub fn key(&mut self, algorithm: Algorithm, secret: hkdf::Prk) -> Result<Key<O>, Error> {
+        let (cipher_suite, _header_key) =
+            NegotiatedCipherSuite::new(algorithm.aead(), secret).ok_or(Error::INTERNAL_ERROR)?;
+        self.cipher_suite_key(&cipher_suite)
+    }
+
+    /// Loads the negotiated keys of a connection
+    pub(crate) fn key_pair(&mut self, keys: &negotiated::KeyPair) -> Result<KeyPair<O>, Error> {
+        Ok(KeyPair {
+            sealer: self.cipher_suite_key(&keys.sealer)?,
+            opener: self.cipher_suite_key(&keys.opener)?,
+        })
+    }
+
+    fn cipher_suite_key(&mut self, cipher_suite: &NegotiatedCipherSuite) -> Result<Key<O>, Error> {
+        cipher_suite.with_key_material(|algorithm, key, iv| {
+            let software =
+                UnboundKey::new(algorithm.aead(), key).map_err(|_| Error::INTERNAL_ERROR)?;
+            let software = LessSafeKey::new(software);
+            let offloaded = self.offload.load_key(algorithm, key);
+
+            Ok(Key {
+                software,
+                iv,
+                offloaded,
+            })
+        })
+    }
+
+    /// Submits a single request
+    pub fn submit(&mut self, key: &Key<O>, request: Request<O::Buffer>) {
+        let request = match &key.offloaded {
+            Some(offloaded) => match self.offload.submit(offloaded, request) {
+                Ok(()) => {
+                    self.in_flight += 1;
+                    return;
+                }
+                Err(request) => request,
+            },
+            None => request,
+        };
+
+        self.fallbacks += 1;
+        let completion = software(&key.software, request);
+        self.ready.push_back(completion);
+    }
+
+    /// Submits a batch of requests
+    ///
+    /// Requests are submitted in order, so if the accelerator's queue fills up part of the way
+    /// through the batch the remaining requests are performed in-process.
+    pub fn submit_batch<I>(&mut self, key: &Key<O>, requests: I)
+    where
+        I: IntoIterator<Item = Request<O::Buffer>>,
+    {
+        for request in requests {
+            self.submit(key, request);
+        }
+    }
+
+    /// Reaps finished requests into `completions`
+    ///
+    /// Returns the number of reaped requests, or `Poll::Pending` if none have finished and some
+    /// are still in flight on the accelerator.
+    pub fn poll_completions(
+        &mut self,
+        cx: &mut Context,
+        completions: &mut Vec<Completion<O::Buffer>>,
+    ) -> Poll<usize> {
+        if self.in_flight > 0 {
+            let before = self.ready.len();
+            let _ = self.offload.poll_completions(cx, &mut self.ready);
+            let reaped = self.ready.len() - before;
+            debug_assert!(reaped <= self.in_flight);
+            self.in_flight = self.in_flight.saturating_sub(reaped);
+        }
+
+        if self.ready.is_empty() {
+            return if self.in_flight == 0 {
+                Poll::Ready(0)
+            } else {
+                Poll::Pending
+            };
+        }
+
+        let count = self.ready.len();
+        completions.extend(self.ready.drain(..));
+        Poll::Ready(count)
+    }
+
+    /// The number of requests submitted to the accelerator which haven't been reaped yet
+    #[inline]
+    pub fn in_flight(&self) -> usize {
+        self.in_flight
+    }
+
+    /// The number of requests which were performed in-process rather than on the accelerator
+    #[inline]
+    pub fn fallbacks(&self) -> u64 {
+        self.fallbacks
+    }
+
+    #[inline]
+    pub fn offload(&self) -> &O {
+        &self.offload
+    }
+
+    #[inline]
+    pub fn offload_mut(&mut self) -> &mut O {
+        &mut self.offload
+    }
+}
+
+/// Performs a request in-process
+///
+/// This is also useful for [`Offload`] implementations that need to emulate an accelerator.
+pub fn software<B: AsMut<[u8]>>(key: &LessSafeKey, request: Request<B>) -> Completion<B> {
+    let Request {
+        id,
+        operation,
+        nonce,
+        header_len,
+        mut packet,
+    } = request;
+
+    let result = process(key, operation, nonce, header_len, packet.as_mut());
+
+    Completion { id, packet, result }
+}
+
+#[inline]
+fn process(
+    key: &LessSafeKey,
+    operation: Operation,
+    nonce: [u8; NONCE_LEN],
+    header_len: usize,
+    packet: &mut [u8],
+) -> Result<(), Error> {
+    let error = match operation {
+        Operation::Seal => Error::INTERNAL_ERROR,
+        Operation::Open => Error::DECRYPT_ERROR,
+    };
+
+    let payload_len = packet
+        .len()
+        .checked_sub(header_len)
+        .and_then(|len| len.checked_sub(TAG_LEN))
+        .ok_or(error)?;
+
+    let (header, payload) = packet.split_at_mut(header_len);
+    let nonce = Nonce::assume_unique_for_key(nonce);
+    let aad = Aad::from(&*header);
+
+    match operation {
+        Operation::Seal => {
+            let (payload, tag) = payload.split_at_mut(payload_len);
+            let sealed_tag = key
+                .seal_in_place_separate_tag(nonce, aad, payload)
+                .map_err(|_| error)?;
+            tag.copy_from_slice(sealed_tag.as_ref());
+        }
+        Operation::Open => {
+            key.open_in_place(nonce, aad, payload).map_err(|_| error)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{one_rtt::OneRttKey, SecretPair};
+    use s2n_codec::{encoder::scatter, EncoderBuffer};
+    use s2n_quic_core::{crypto::Key as _, task::waker};
+
+    const HEADER_LEN: usize = 6;
+
+    /// Emulates an accelerator with a bounded queue
+    #[derive(Default)]
+    struct Accelerator {
+        queue: VecDeque<(u8, Request<Vec<u8>>)>,
+        keys: Vec<LessSafeKey>,
+        capacity: usize,
+    }
+
+    impl Offload for Accelerator {
+        type Key = u8;
+        type Buffer = Vec<u8>;
+
+        fn load_key(&mut self, algorithm: Algorithm, key: &[u8]) -> Option<Self::Key> {
+            // only AES is accelerated
+            if algorithm == Algorithm::CHACHA20_POLY1305 {
+                return None;
+            }
+            let id = self.keys.len() as u8;
+            let key = UnboundKey::new(algorithm.aead(), key).unwrap();
+            self.keys.push(LessSafeKey::new(key));
+            Some(id)
+        }
+
+        fn submit(
+            &mut self,
+            key: &Self::Key,
+            request: Request<Vec<u8>>,
+        ) -> Result<(), Request<Vec<u8>>> {
+            if self.queue.len() == self.capacity {
+                return Err(request);
+            }
+            self.queue.push_back((*key, request));
+            Ok(())
+        }
+
+        fn poll_completions(
+            &mut self,
+            _cx: &mut Context,
+            completions: &mut VecDeque<Completion<Vec<u8>>>,
+        ) -> Poll<()> {
+            for (key, request) in self.queue.drain(..) {
+                completions.push_back(software(&self.keys[key as usize], request));
+            }
+            Poll::Ready(())
+        }
+    }
+
+    fn secret(value: u8) -> hkdf::Prk {
+        hkdf::Prk::new_less_safe(hkdf::HKDF_SHA256, &[value; 32])
+    }
+
+    /// Returns a packet with the header, the payload and space for the tag
+    fn packet(payload: &[u8]) -> Vec<u8> {
+        let mut packet = b"header".to_vec();
+        packet.extend_from_slice(payload);
+        packet.extend_from_slice(&[0; TAG_LEN]);
+        packet
+    }
+
+    fn reap<O: Offload>(dispatcher: &mut Dispatcher<O>) -> Vec<Completion<O::Buffer>> {
+        let waker = waker::noop();
+        let mut cx = Context::from_waker(&waker);
+        let mut completions = vec![];
+        let _ = dispatcher.poll_completions(&mut cx, &mut completions);
+        completions.sort_by_key(|completion| completion.id);
+        completions
+    }
+
+    fn round_trip<O: Offload<Buffer = Vec<u8>>>(
+        dispatcher: &mut Dispatcher<O>,
+        algorithm: Algorithm,
+    ) {
+        let key = dispatcher.key(algorithm, secret(1)).unwrap();
+        let plaintext: Vec<_> = (0..8u8).map(|i| vec![i; 100 + i as usize]).collect();
+
+        dispatcher.submit_batch(
+            &key,
+            plaintext.iter().enumerate().map(|(id, payload)| {
+                key.request(
+                    id as _,
+                    Operation::Seal,
+                    id as _,
+                    HEADER_LEN,
+                    packet(payload),
+                )
+            }),
+        );
+        let sealed = reap(dispatcher);
+        assert_eq!(sealed.len(), plaintext.len());
+        assert_eq!(dispatcher.in_flight(), 0);
+
+        dispatcher.submit_batch(
+            &key,
+            sealed.into_iter().map(|completion| {
+                completion.result.unwrap();
+                let id = completion.id;
+                key.request(id, Operation::Open, id, HEADER_LEN, completion.packet)
+            }),
+        );
+        let opened = reap(dispatcher);
+        assert_eq!(opened.len(), plaintext.len());
+
+        for (completion, expected) in opened.into_iter().zip(plaintext) {
+            completion.result.unwrap();
+            let payload = &completion.packet[HEADER_LEN..completion.packet.len() - TAG_LEN];
+            assert_eq!(payload, &expected[..]);
+        }
+    }
+
+    #[test]
+    fn disabled_test() {
+        let mut dispatcher = Dispatcher::new(Disabled::default());
+        round_trip(&mut dispatcher, Algorithm::AES_128_GCM);
+        assert_eq!(dispatcher.fallbacks(), 16);
+    }
+
+    #[test]
+    fn offload_test() {
+        let mut dispatcher = Dispatcher::new(Accelerator {
+            capacity: 8,
+            ..Default::default()
+        });
+        round_trip(&mut dispatcher, Algorithm::AES_256_GCM);
+        assert_eq!(dispatcher.fallbacks(), 0);
+    }
+
+    #[test]
+    fn queue_full_test() {
+        let mut dispatcher = Dispatcher::new(Accelerator {
+            capacity: 3,
+            ..Default::default()
+        });
+        round_trip(&mut dispatcher, Algorithm::AES_128_GCM);
+        // 5 out of each batch of 8 don't fit in the queue
+        assert_eq!(dispatcher.fallbacks(), 10);
+    }
+
+    #[test]
+    fn unsupported_algorithm_test() {
+        let mut dispatcher = Dispatcher::new(Accelerator {
+            capacity: 8,
+            ..Default::default()
+        });
+        let key = dispatcher
+            .key(Algorithm::CHACHA20_POLY1305, secret(1))
+            .unwrap();
+        assert!(!key.is_offloaded());
+        round_trip(&mut dispatcher, Algorithm::CHACHA20_POLY1305);
+        assert_eq!(dispatcher.fallbacks(), 16);
+    }
+
+    #[test]
+    fn pending_test() {
+        /// Never finishes any requests
+        struct Stuck;
+
+        impl Offload for Stuck {
+            type Key = ();
+            type Buffer = Vec<u8>;
+
+            fn load_key(&mut self, _algorithm: Algorithm, _key: &[u8]) -> Option<()> {
+                Some(())
+            }
+
+            fn submit(
+                &mut self,
+                _key: &(),
+                _request: Request<Vec<u8>>,
+            ) -> Result<(), Request<Vec<u8>>> {
+                Ok(())
+            }
+
+            fn poll_completions(
+                &mut self,
+                _cx: &mut Context,
+                _completions: &mut VecDeque<Completion<Vec<u8>>>,
+            ) -> Poll<()> {
+                Poll::Pending
+            }
+        }
+
+        let mut dispatcher = Dispatcher::new(Stuck);
+        let key = dispatcher.key(Algorithm::AES_128_GCM, secret(1)).unwrap();
+        dispatcher.submit(
+            &key,
+            key.request(1, Operation::Seal, 1, HEADER_LEN, packet(&[1, 2, 3])),
+        );
+
+        let waker = waker::noop();
+        let mut cx = Context::from_waker(&waker);
+        let mut completions = vec![];
+        assert!(dispatcher
+            .poll_completions(&mut cx, &mut completions)
+            .is_pending());
+        assert_eq!(dispatcher.in_flight(), 1);
+    }
+
+    #[test]
+    fn open_error_test() {
+        let mut dispatcher = Dispatcher::new(Disabled::default());
+        let key = dispatcher.key(Algorithm::AES_128_GCM, secret(1)).unwrap();
+
+        // the tag doesn't match
+        dispatcher.submit(
+            &key,
+            key.request(1, Operation::Open, 1, HEADER_LEN, packet(&[0; 16])),
+        );
+        // the packet is too short to contain a tag
+        dispatcher.submit(
+            &key,
+            key.request(2, Operation::Open, 2, HEADER_LEN, vec![0; 8]),
+        );
+
+        let completions = reap(&mut dispatcher);
+        assert_eq!(completions[0].result, Err(Error::DECRYPT_ERROR));
+        assert_eq!(completions[1].result, Err(Error::DECRYPT_ERROR));
+    }
+
+    /// Ensures offloaded operations are interchangeable with the packet protection of the
+    /// negotiated 1-RTT keys
+    #[test]
+    fn packet_protection_test() {
+        let secrets = || SecretPair {
+            server: secret(1),
+            client: secret(2),
+        };
+        let algorithm = &aead::AES_128_GCM;
+        let (mut server, _) = OneRttKey::new_server(algorithm, secrets()).unwrap();
+        let (mut client, _) = OneRttKey::new_client(algorithm, secrets()).unwrap();
+
+        let mut dispatcher = Dispatcher::new(Accelerator {
+            capacity: 8,
+            ..Default::default()
+        });
+        let offloaded = server.offload_keys(&mut dispatcher).unwrap();
+        assert!(offloaded.sealer.is_offloaded());
+
+        let payload = b"hello world";
+        let packet_number = 123;
+
+        // seal with the accelerator and open with the client's packet protection
+        let sealed = packet(payload);
+        dispatcher.submit(
+            &offloaded.sealer,
+            offloaded
+                .sealer
+                .request(1, Operation::Seal, packet_number, HEADER_LEN, sealed),
+        );
+        let mut sealed = reap(&mut dispatcher).pop().unwrap().packet;
+        let (header, payload_and_tag) = sealed.split_at_mut(HEADER_LEN);
+        client
+            .decrypt(packet_number, header, payload_and_tag)
+            .unwrap();
+        assert_eq!(&payload_and_tag[..payload.len()], payload);
+
+        // seal with the client's packet protection and open with the accelerator
+        let mut sealed = packet(payload);
+        {
+            let (header, payload_and_tag) = sealed.split_at_mut(HEADER_LEN);
+            let mut buffer = EncoderBuffer::new(payload_and_tag);
+            buffer.advance_position(payload.len());
+            let mut buffer = scatter::Buffer::new(buffer);
+            client.encrypt(packet_number, header, &mut buffer).unwrap();
+        }
+        dispatcher.submit(
+            &offloaded.opener,
+            offloaded
+                .opener
+                .request(2, Operation::Open, packet_number, HEADER_LEN, sealed),
+        );
+        let completion = reap(&mut dispatcher).pop().unwrap();
+        completion.result.unwrap();
+        assert_eq!(
+            &completion.packet[HEADER_LEN..HEADER_LEN + payload.len()],
+            payload
+        );
+
+        // the server's own packet protection agrees with the accelerator
+        let mut sealed = packet(payload);
+        {
+            let (header, payload_and_tag) = sealed.split_at_mut(HEADER_LEN);
+            let mut buffer = EncoderBuffer::new(payload_and_tag);
+            buffer.advance_position(payload.len());
+            let mut buffer = scatter::Buffer::new(buffer);
+            server.encrypt(packet_number, header, &mut buffer).unwrap();
+        }
+        let offloaded_sealed = {
+            dispatcher.submit(
+                &offloaded.sealer,
+                offloaded.sealer.request(
+                    3,
+                    Operation::Seal,
+                    packet_number,
+                    HEADER_LEN,
+                    packet(payload),
+                ),
+            );
+            reap(&mut dispatcher).pop().unwrap().packet
+        };
+        assert_eq!(sealed, offloaded_sealed);
+    }
+}
@@ -0,0 +1,186 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Validation of peer certificate chains against a set of pinned public keys
+//!
+//! A pin is the SHA-256 digest of a DER-encoded `SubjectPublicKeyInfo`, which is the same format
+//! used by HPKP ([RFC 7469](https://www.rfc-editor.org/rfc/rfc7469#section-2.4)). A chain is
+//! accepted if the public key of any of its certificates is pinned.
+
+use aws_lc_rs::digest;
+use std::sync::Arc;
+
+/// The length of a pin in bytes
+pub const PIN_LEN: usize = 32;
+
+pub type Pin = [u8; PIN_LEN];
+
+#[derive(Clone, Debug, Default)]
+pub struct Pins(Arc<[Pin]>);
+
+impl Pins {
+    /// Creates a pin set, returning an error if any of the pins isn't a SHA-256 digest
+    pub fn new<I: IntoIterator<Item = P>, P: AsRef<[u8]>>(pins: I) -> Result<Self, &'static str> {
+        let pins = pins
+            .into_iter()
+            .map(|pin| Pin::try_from(pin.as_ref()))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| "certificate pins must be SHA-256 digests")?;
+
+        if pins.is_empty() {
+            return Err("at least one certificate pin must be provided");
+        }
+
+        Ok(Self(pins.into()))
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns `true` if the public key of any of the DER-encoded certificates is pinned
+    pub fn matches<'a, I: IntoIterator<Item = &'a [u8]>>(&self, chain: I) -> bool {
+        chain
+            .into_iter()
+            .filter_map(pin)
+            .any(|pin| self.0.contains(&pin))
+    }
+
+    /// Returns `true` if the DER-encoded `SubjectPublicKeyInfo` is pinned
+    pub fn contains_key(&self, subject_public_key_info: &[u8]) -> bool {
+        self.0.contains(&key_pin(subject_public_key_info))
+    }
+}
+
+/// Computes the pin for a DER-encoded certificate
+///
+/// Returns `None` if the certificate can't be parsed.
+pub fn pin(certificate: &[u8]) -> Option<Pin> {
+    let spki = subject_public_key_info(certificate)?;
+    Some(key_pin(spki))
+}
+
+/// Computes the pin for a DER-encoded `SubjectPublicKeyInfo`
+pub fn key_pin(subject_public_key_info: &[u8]) -> Pin {
+    let digest = digest::digest(&digest::SHA256, subject_public_key_info);
+    digest
+        .as_ref()
+        .try_into()
+        .expect("SHA-256 digests are 32 bytes")
+}
+
+/// Returns the DER encoding of the `SubjectPublicKeyInfo` in a certificate
+///
+/// See [RFC 5280](https://www.rfc-editor.org/rfc/rfc5280#section-4.1).
+fn subject_public_key_info(certificate: &[u8]) -> Option<&[u8]> {
+    const SEQUENCE: u8 = 0x30;
+    const VERSION: u8 = 0xa0;
+
+    let certificate = split(certificate)?;
+    if certificate.tag != SEQUENCE {
+        return None;
+    }
+
+    let tbs_certificate = split(certificate.contents)?;
+    if tbs_certificate.tag != SEQUENCE {
+        return None;
+    }
+    let mut fields = tbs_certificate.contents;
+
+    // the version is optional and defaults to v1
+    if fields.first() == Some(&VERSION) {
+        fields = split(fields)?.remaining;
+    }
+
+    // skip the serialNumber, signature, issuer, validity and subject fields
+    for _ in 0..5 {
+        fields = split(fields)?.remaining;
+    }
+
+    let spki = split(fields)?;
+    if spki.tag != SEQUENCE {
+        return None;
+    }
+
+    Some(spki.encoding)
+}
+
+/// A DER element split off of the front of some input
+struct Element<'a> {
+    tag: u8,
+    /// The full encoding of the element, including the tag and length
+    encoding: &'a [u8],
+    contents: &'a [u8],
+    /// The input following the element
+    remaining: &'a [u8],
+}
+
+/// Splits the next DER element off of `input`
+fn split(input: &[u8]) -> Option<Element> {
+    let (&tag, rest) = input.split_first()?;
+    let (&len, mut rest) = rest.split_first()?;
+
+    let len = if len < 0x80 {
+        len as usize
+    } else {
+        // certificates are never large enough to need more than 4 length bytes
+        let count = (len & 0x7f) as usize;
+        if count == 0 || count > 4 || rest.len() < count {
+            return None;
+        }
+        let (len, remaining) = rest.split_at(count);
+        rest = remaining;
+        len.iter().fold(0, |len, byte| (len << 8) | *byte as usize)
+    };
+
+    if rest.len() < len {
+        return None;
+    }
+
+    let header_len = input.len() - rest.len();
+    let (contents, remaining) = rest.split_at(len);
+    Some(Element {
+        tag,
+        encoding: &input[..header_len + len],
+        contents,
+        remaining,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hex_literal::hex;
+    use s2n_quic_core::crypto::tls::testing::certificates::CERT_DER;
+
+    #[test]
+    fn pin_test() {
+        // openssl x509 -in cert.der -inform der -pubkey -noout \
+        //   | openssl pkey -pubin -outform der | sha256sum
+        let expected = hex!("87086095bf3ef97d6f2c739116d7615fa20d010e26b8ed20e00402675e7b5005");
+        assert_eq!(pin(CERT_DER), Some(expected));
+
+        assert_eq!(pin(&[]), None);
+        assert_eq!(pin(&CERT_DER[..100]), None);
+    }
+
+    #[test]
+    fn pins_test() {
+        let pins = Pins::new([pin(CERT_DER).unwrap()]).unwrap();
+        assert!(pins.matches([CERT_DER]));
+        assert!(pins.matches([&[][..], CERT_DER]));
+        assert!(!pins.matches([]));
+
+        let pins = Pins::new([[0u8; PIN_LEN]]).unwrap();
+        assert!(!pins.matches([CERT_DER]));
+
+        let spki = subject_public_key_info(CERT_DER).unwrap();
+        let pins = Pins::new([key_pin(spki)]).unwrap();
+        assert!(pins.contains_key(spki));
+        assert!(!pins.contains_key(&spki[1..]));
+
+        assert!(Pins::new([[0u8; 16]]).is_err());
+        assert!(Pins::new(Vec::<Pin>::new()).is_err());
+    }
+}
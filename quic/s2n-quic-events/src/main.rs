@@ -26,6 +26,7 @@ struct EventInfo<'a> {
     tracing_subscriber_attr: TokenStream,
     tracing_subscriber_def: TokenStream,
     feature_alloc: TokenStream,
+    serde: bool,
 }
 
 impl EventInfo<'_> {
@@ -77,6 +78,7 @@ impl EventInfo<'_> {
             },
             tracing_subscriber_def,
             feature_alloc: quote!(#[cfg(feature = "alloc")]),
+            serde: true,
         }
     }
 
@@ -134,6 +136,7 @@ impl EventInfo<'_> {
             tracing_subscriber_attr: quote!(),
             tracing_subscriber_def,
             feature_alloc: quote!(),
+            serde: false,
         }
     }
 }
@@ -171,6 +174,7 @@ fn main() -> Result<()> {
             tracing_subscriber_attr: event_info.tracing_subscriber_attr,
             tracing_subscriber_def: event_info.tracing_subscriber_def,
             feature_alloc: event_info.feature_alloc,
+            serde: event_info.serde,
             crate_name: event_info.crate_name,
             root,
             ..Default::default()
@@ -20,6 +20,7 @@ pub struct Output {
     pub tracing_subscriber: TokenStream,
     pub tracing_subscriber_attr: TokenStream,
     pub tracing_subscriber_def: TokenStream,
+    pub export_subscriber: TokenStream,
     pub builders: TokenStream,
     pub api: TokenStream,
     pub testing_fields: TokenStream,
@@ -36,6 +37,7 @@ pub struct Output {
     pub s2n_quic_core_path: TokenStream,
     pub top_level: TokenStream,
     pub feature_alloc: TokenStream,
+    pub serde: bool,
     pub root: PathBuf,
 }
 
@@ -99,6 +101,7 @@ impl ToTokens for Output {
             tracing_subscriber,
             tracing_subscriber_attr,
             tracing_subscriber_def,
+            export_subscriber,
             builders,
             api,
             testing_fields,
@@ -114,6 +117,7 @@ impl ToTokens for Output {
             s2n_quic_core_path,
             top_level,
             feature_alloc: _,
+            serde,
             crate_name,
             root: _,
         } = self;
@@ -157,8 +161,33 @@ impl ToTokens for Output {
             ) {
                 self.as_ref().on_connection_event(context, meta, event);
             }
+
+            #[inline]
+            fn query(context: &Self::ConnectionContext, query: &mut dyn query::Query) -> query::ControlFlow {
+                T::query(context, query)
+            }
         ));
 
+        let export_subscriber = if *serde {
+            quote!(
+                #[cfg(feature = "serde")]
+                impl<X: export::Exporter> Subscriber for export::Subscriber<X> {
+                    type ConnectionContext = ();
+
+                    fn create_connection_context(
+                        &#mode self,
+                        _meta: &api::ConnectionMeta,
+                        _info: &api::ConnectionInfo
+                    ) -> Self::ConnectionContext {
+                    }
+
+                    #export_subscriber
+                }
+            )
+        } else {
+            quote!()
+        };
+
         tokens.extend(quote!(
             #![allow(clippy::needless_lifetimes)]
 
@@ -204,6 +233,8 @@ impl ToTokens for Output {
                 }
             }
 
+            #export_subscriber
+
             pub mod builder {
                 use super::*;
 
@@ -298,6 +329,10 @@ impl ToTokens for Output {
                     }
 
                     /// Used for querying the `Subscriber::ConnectionContext` on a Subscriber
+                    ///
+                    /// Subscribers which wrap other subscribers should also forward the query to
+                    /// the contexts of the inner subscribers so they can be found by type,
+                    /// regardless of how the subscribers are composed.
                     #[inline]
                     fn query(context: &Self::ConnectionContext, query: &mut dyn query::Query) -> query::ControlFlow {
                         query.execute(context)
@@ -1,7 +1,7 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{parser::File, Output};
+use crate::{parser::File, Output, OutputMode};
 use proc_macro2::TokenStream;
 use quote::quote;
 
@@ -18,6 +18,7 @@ pub fn emit(output: &Output, files: &[File]) -> TokenStream {
         });
 
     let mode = &output.mode;
+    let s2n_quic_core_path = &output.s2n_quic_core_path;
 
     let imports = output.mode.imports();
     let receiver = output.mode.receiver();
@@ -64,11 +65,27 @@ pub fn emit(output: &Output, files: &[File]) -> TokenStream {
         ));
     }
 
+    let query_mut = match output.mode {
+        OutputMode::Ref => quote!(),
+        OutputMode::Mut => quote!(
+            #[inline]
+            fn query_mut(
+                context: &mut Self::ConnectionContext,
+                query: &mut dyn query::QueryMut,
+            ) -> query::ControlFlow {
+                query
+                    .execute_mut(context)
+                    .and_then(|| S::query_mut(&mut context.recorder, query))
+            }
+        ),
+    };
+
     let aggregate = aggregate::emit(output, files);
 
     let tokens = quote!(
         #imports
         use crate::event::{metrics::Recorder, api, self};
+        use #s2n_quic_core_path::query;
 
         #aggregate
 
@@ -110,6 +127,18 @@ pub fn emit(output: &Output, files: &[File]) -> TokenStream {
             }
 
             #subscriber
+
+            #[inline]
+            fn query(
+                context: &Self::ConnectionContext,
+                query: &mut dyn query::Query,
+            ) -> query::ControlFlow {
+                query
+                    .execute(context)
+                    .and_then(|| S::query(&context.recorder, query))
+            }
+
+            #query_mut
         }
 
         impl<R: Recorder> Drop for Context<R> {
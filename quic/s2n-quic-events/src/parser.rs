@@ -147,6 +147,14 @@ impl Struct {
 
         if attrs.derive {
             output.api.extend(quote!(#[derive(Clone, Debug)]));
+
+            // types which override the default derives provide their own `Serialize`
+            // implementation and deprecated types aren't part of the schema
+            if output.serde && deprecated.is_empty() {
+                output
+                    .api
+                    .extend(quote!(#[cfg_attr(feature = "serde", derive(serde::Serialize))]));
+            }
         }
 
         if !attrs.exhaustive {
@@ -247,6 +255,16 @@ impl Struct {
                         }
                     ));
 
+                    // deprecated events aren't part of the schema so they aren't exported
+                    if deprecated.is_empty() {
+                        output.export_subscriber.extend(quote!(
+                            #[inline]
+                            fn #function(&#receiver self, meta: &api::EndpointMeta, event: &api::#ident) {
+                                self.export(meta, event);
+                            }
+                        ));
+                    }
+
                     output.endpoint_publisher.extend(quote!(
                         #[doc = #publisher_doc]
                         fn #function(&#receiver self, event: builder::#ident);
@@ -359,6 +377,21 @@ impl Struct {
                         }
                     ));
 
+                    // deprecated events aren't part of the schema so they aren't exported
+                    if deprecated.is_empty() {
+                        output.export_subscriber.extend(quote!(
+                            #[inline]
+                            fn #function(
+                                &#receiver self,
+                                _context: &#receiver Self::ConnectionContext,
+                                meta: &api::ConnectionMeta,
+                                event: &api::#ident
+                            ) {
+                                self.export(meta, event);
+                            }
+                        ));
+                    }
+
                     output.connection_publisher.extend(quote!(
                         #[doc = #publisher_doc]
                         fn #function(&#receiver self, event: builder::#ident);
@@ -481,6 +514,16 @@ impl Enum {
 
         if attrs.derive {
             output.api.extend(quote!(#[derive(Clone, Debug)]));
+
+            // types which override the default derives provide their own `Serialize`
+            // implementation and deprecated types aren't part of the schema
+            //
+            // all of the variants are struct-like so they can be internally tagged
+            if output.serde && deprecated.is_empty() {
+                output.api.extend(quote!(
+                    #[cfg_attr(feature = "serde", derive(serde::Serialize), serde(tag = "type"))]
+                ));
+            }
         }
 
         if !attrs.exhaustive {
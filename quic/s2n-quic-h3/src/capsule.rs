@@ -0,0 +1,339 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! The Capsule Protocol, as defined in [RFC 9297](https://www.rfc-editor.org/rfc/rfc9297#section-3.2)
+//!
+//! Capsules are sent on the request stream once an extended CONNECT request is accepted. They
+//! carry HTTP Datagrams when QUIC DATAGRAM frames aren't available, along with any other
+//! extension-defined messages.
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use std::fmt;
+
+/// The capsule type for HTTP Datagrams sent on the request stream
+pub const DATAGRAM: u64 = 0x00;
+
+/// The default maximum length of a capsule value accepted by the [`Decoder`]
+///
+/// This fits a DATAGRAM capsule carrying any UDP payload.
+pub const DEFAULT_MAX_LEN: usize = 1 << 16;
+
+/// A capsule sent on the request stream
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Capsule {
+    pub capsule_type: u64,
+    pub value: Bytes,
+}
+
+impl Capsule {
+    #[inline]
+    pub fn new(capsule_type: u64, value: Bytes) -> Self {
+        Self {
+            capsule_type,
+            value,
+        }
+    }
+
+    /// Creates a DATAGRAM capsule carrying the given HTTP Datagram payload
+    #[inline]
+    pub fn datagram(payload: Bytes) -> Self {
+        Self::new(DATAGRAM, payload)
+    }
+
+    #[inline]
+    pub fn is_datagram(&self) -> bool {
+        self.capsule_type == DATAGRAM
+    }
+
+    /// Returns the number of bytes needed to encode the capsule
+    #[inline]
+    pub fn encoding_size(&self) -> usize {
+        varint::encoding_size(self.capsule_type)
+            + varint::encoding_size(self.value.len() as u64)
+            + self.value.len()
+    }
+
+    #[inline]
+    pub fn encode<B: BufMut>(&self, buf: &mut B) {
+        varint::encode(self.capsule_type, buf);
+        varint::encode(self.value.len() as u64, buf);
+        buf.put_slice(&self.value);
+    }
+
+    /// Encodes the capsule into a new buffer, which can be sent on the request stream
+    #[inline]
+    pub fn to_bytes(&self) -> Bytes {
+        let mut buf = BytesMut::with_capacity(self.encoding_size());
+        self.encode(&mut buf);
+        buf.freeze()
+    }
+
+    /// Decodes a capsule from the front of `buf`
+    ///
+    /// Returns `None` and leaves `buf` untouched if it doesn't contain a complete capsule yet.
+    #[inline]
+    pub fn decode(buf: &mut Bytes) -> Option<Self> {
+        let (capsule_type, header_len, len) = decode_header(buf)?;
+        let len = usize::try_from(len).ok()?;
+        if buf.len() - header_len < len {
+            return None;
+        }
+
+        buf.advance(header_len);
+        let value = buf.split_to(len);
+        Some(Self::new(capsule_type, value))
+    }
+}
+
+/// Returns the capsule type, header length and declared value length of the capsule at the
+/// front of `buf`
+///
+/// The value itself may not have been received yet.
+#[inline]
+fn decode_header(buf: &[u8]) -> Option<(u64, usize, u64)> {
+    let mut peek = buf;
+    let capsule_type = varint::decode(&mut peek)?;
+    let len = varint::decode(&mut peek)?;
+
+    Some((capsule_type, buf.len() - peek.len(), len))
+}
+
+/// Reassembles capsules from the chunks received on a request stream
+///
+/// Capsules with a value longer than the maximum length are rejected as soon as their header is
+/// received, rather than buffering them. The request stream should be aborted in that case.
+///
+/// ```ignore
+/// let mut decoder = capsule::Decoder::default();
+/// while let Some(chunk) = stream.recv_data().await? {
+///     decoder.push(chunk);
+///     while let Some(capsule) = decoder.next_capsule()? {
+///         // handle the capsule
+///     }
+/// }
+/// ```
+#[derive(Debug)]
+pub struct Decoder {
+    buffer: BytesMut,
+    max_len: usize,
+}
+
+impl Default for Decoder {
+    #[inline]
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_LEN)
+    }
+}
+
+impl Decoder {
+    /// Creates a decoder which accepts capsule values of up to `max_len` bytes
+    #[inline]
+    pub fn new(max_len: usize) -> Self {
+        Self {
+            buffer: BytesMut::new(),
+            max_len,
+        }
+    }
+
+    /// Appends data received on the request stream
+    #[inline]
+    pub fn push<B: Buf>(&mut self, mut chunk: B) {
+        while chunk.has_remaining() {
+            let bytes = chunk.chunk();
+            let len = bytes.len();
+            self.buffer.extend_from_slice(bytes);
+            chunk.advance(len);
+        }
+    }
+
+    /// Returns the next complete capsule, if any
+    ///
+    /// Returns an error if the next capsule is longer than the maximum length.
+    #[inline]
+    pub fn next_capsule(&mut self) -> Result<Option<Capsule>, TooLargeError> {
+        let Some((capsule_type, header_len, len)) = decode_header(&self.buffer) else {
+            return Ok(None);
+        };
+
+        if len > self.max_len as u64 {
+            return Err(TooLargeError {
+                len,
+                max_len: self.max_len,
+            });
+        }
+
+        let len = len as usize;
+        if self.buffer.len() - header_len < len {
+            return Ok(None);
+        }
+
+        let mut value = self.buffer.split_to(header_len + len).freeze();
+        value.advance(header_len);
+        Ok(Some(Capsule::new(capsule_type, value)))
+    }
+
+    /// Returns `true` if there is no partially received capsule
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.buffer.is_empty()
+    }
+}
+
+/// A capsule was longer than the maximum length accepted by the [`Decoder`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TooLargeError {
+    /// The length of the capsule value declared by the peer
+    pub len: u64,
+    pub max_len: usize,
+}
+
+impl std::error::Error for TooLargeError {}
+
+impl fmt::Display for TooLargeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "the capsule length {} exceeds the maximum of {}",
+            self.len, self.max_len
+        )
+    }
+}
+
+/// QUIC variable-length integer encoding, as defined in
+/// [RFC 9000](https://www.rfc-editor.org/rfc/rfc9000#section-16)
+pub(crate) mod varint {
+    use bytes::BufMut;
+
+    pub const MAX: u64 = (1 << 62) - 1;
+
+    #[inline]
+    pub fn encoding_size(value: u64) -> usize {
+        match value {
+            0..=0x3f => 1,
+            0x40..=0x3fff => 2,
+            0x4000..=0x3fff_ffff => 4,
+            _ => 8,
+        }
+    }
+
+    #[inline]
+    pub fn encode<B: BufMut>(value: u64, buf: &mut B) {
+        debug_assert!(value <= MAX, "value exceeds the varint range");
+        match encoding_size(value) {
+            1 => buf.put_u8(value as u8),
+            2 => buf.put_u16(value as u16 | 0x4000),
+            4 => buf.put_u32(value as u32 | 0x8000_0000),
+            _ => buf.put_u64(value | 0xc000_0000_0000_0000),
+        }
+    }
+
+    #[inline]
+    pub fn decode(buf: &mut &[u8]) -> Option<u64> {
+        let first = *buf.first()?;
+        let len = 1 << (first >> 6);
+        if buf.len() < len {
+            return None;
+        }
+
+        let mut value = (first & 0x3f) as u64;
+        for byte in &buf[1..len] {
+            value = (value << 8) | *byte as u64;
+        }
+        *buf = &buf[len..];
+
+        Some(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn varint_round_trip_test() {
+        for value in [
+            0,
+            63,
+            64,
+            16383,
+            16384,
+            1_073_741_823,
+            1_073_741_824,
+            varint::MAX,
+        ] {
+            let mut buf = vec![];
+            varint::encode(value, &mut buf);
+            assert_eq!(buf.len(), varint::encoding_size(value));
+
+            let mut slice = &buf[..];
+            assert_eq!(varint::decode(&mut slice), Some(value));
+            assert!(slice.is_empty());
+
+            // truncated integers can't be decoded
+            let mut slice = &buf[..buf.len() - 1];
+            assert_eq!(varint::decode(&mut slice), None);
+        }
+    }
+
+    #[test]
+    fn capsule_round_trip_test() {
+        let capsule = Capsule::datagram(Bytes::from_static(b"hello"));
+        let mut encoded = capsule.to_bytes();
+        assert_eq!(encoded.len(), capsule.encoding_size());
+        assert_eq!(&encoded[..], b"\x00\x05hello");
+
+        let mut partial = encoded.slice(..4);
+        assert_eq!(Capsule::decode(&mut partial), None);
+        assert_eq!(partial.len(), 4);
+
+        assert_eq!(Capsule::decode(&mut encoded), Some(capsule));
+        assert!(encoded.is_empty());
+    }
+
+    #[test]
+    fn decoder_test() {
+        let first = Capsule::datagram(Bytes::from_static(b"first"));
+        let second = Capsule::new(0x1234, Bytes::from(vec![7; 100]));
+
+        let mut stream = BytesMut::new();
+        first.encode(&mut stream);
+        second.encode(&mut stream);
+        let stream = stream.freeze();
+
+        // deliver the stream one byte at a time
+        let mut decoder = Decoder::default();
+        let mut capsules = vec![];
+        for offset in 0..stream.len() {
+            decoder.push(stream.slice(offset..offset + 1));
+            while let Some(capsule) = decoder.next_capsule().unwrap() {
+                capsules.push(capsule);
+            }
+        }
+
+        assert_eq!(capsules, [first, second]);
+        assert!(decoder.is_empty());
+    }
+
+    #[test]
+    fn decoder_max_len_test() {
+        let capsule = Capsule::datagram(Bytes::from(vec![1; 100]));
+
+        let mut decoder = Decoder::new(100);
+        decoder.push(capsule.to_bytes());
+        assert_eq!(decoder.next_capsule(), Ok(Some(capsule)));
+
+        // the capsule is rejected once the header arrives, without waiting for the value
+        let mut decoder = Decoder::new(99);
+        let mut header = vec![];
+        varint::encode(DATAGRAM, &mut header);
+        varint::encode(varint::MAX, &mut header);
+        decoder.push(&header[..]);
+        assert_eq!(
+            decoder.next_capsule(),
+            Err(TooLargeError {
+                len: varint::MAX,
+                max_len: 99
+            })
+        );
+    }
+}
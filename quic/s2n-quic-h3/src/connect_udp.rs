@@ -0,0 +1,460 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Proxying UDP in HTTP, as defined in [RFC 9298](https://www.rfc-editor.org/rfc/rfc9298)
+//!
+//! A CONNECT-UDP session is established with an extended CONNECT request on a request stream.
+//! Once the proxy accepts the request, UDP payloads are exchanged as HTTP Datagrams, which are
+//! associated with the session by the request stream ID.
+//!
+//! HTTP Datagrams are sent in QUIC DATAGRAM frames with a [`Session`], which requires the
+//! `unstable-provider-datagram` feature. The h3 server needs to be built with extended CONNECT
+//! and datagrams enabled, and the s2n-quic endpoint needs to be configured with the default
+//! datagram provider. Without datagram support, HTTP Datagrams are instead sent on the request
+//! stream in DATAGRAM capsules with [`Datagram::to_capsule`] and [`Datagram::from_capsule`].
+//!
+//! ```ignore
+//! let handle = connection.handle().clone();
+//! let mut h3 = h3::server::builder()
+//!     .enable_extended_connect(true)
+//!     .enable_datagram(true)
+//!     .build(connection)
+//!     .await?;
+//!
+//! while let Some((request, mut stream)) = h3.accept().await? {
+//!     let target = match connect_udp::validate(&request) {
+//!         Ok(target) => target,
+//!         Err(error) => {
+//!             stream.send_response(error.response()).await?;
+//!             continue;
+//!         }
+//!     };
+//!
+//!     stream.send_response(connect_udp::response()).await?;
+//!     // the session is identified by the ID of the request stream
+//!     let session = connect_udp::Session::new(handle.clone(), stream_id);
+//!     // forward payloads between `session` and a UDP socket bound to `target`
+//! }
+//! ```
+
+use crate::capsule::{varint, Capsule};
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use h3::ext::Protocol;
+use http::{header::HeaderValue, Method, Request, Response, StatusCode};
+use std::fmt;
+
+#[cfg(feature = "unstable-provider-datagram")]
+use core::task::{ready, Context, Poll};
+#[cfg(feature = "unstable-provider-datagram")]
+use s2n_quic::provider::datagram::default::{DatagramError, Receiver, Sender};
+
+/// The value of the `:protocol` pseudo-header for CONNECT-UDP requests
+pub const PROTOCOL: &str = "connect-udp";
+
+/// The context ID for HTTP Datagrams which carry UDP payloads
+pub const UDP_PAYLOAD_CONTEXT_ID: u64 = 0;
+
+/// The header which indicates that the capsule protocol is used on the request stream
+pub const CAPSULE_PROTOCOL: &str = "capsule-protocol";
+
+/// The path prefix of the default URI template
+/// `/.well-known/masque/udp/{target_host}/{target_port}/`
+const WELL_KNOWN_PREFIX: &str = "/.well-known/masque/udp/";
+
+/// The UDP target requested by a client
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Target {
+    pub host: String,
+    pub port: u16,
+}
+
+impl Target {
+    #[inline]
+    pub fn new(host: impl Into<String>, port: u16) -> Self {
+        Self {
+            host: host.into(),
+            port,
+        }
+    }
+
+    /// Parses the target from a path following the default URI template
+    pub fn from_path(path: &str) -> Result<Self, RequestError> {
+        let path = path
+            .strip_prefix(WELL_KNOWN_PREFIX)
+            .ok_or(RequestError::InvalidTarget)?;
+        // the trailing slash is part of the template
+        let path = path.strip_suffix('/').ok_or(RequestError::InvalidTarget)?;
+        let (host, port) = path.split_once('/').ok_or(RequestError::InvalidTarget)?;
+
+        let host = percent_decode(host).ok_or(RequestError::InvalidTarget)?;
+        if host.is_empty() {
+            return Err(RequestError::InvalidTarget);
+        }
+
+        let port: u16 = port.parse().map_err(|_| RequestError::InvalidTarget)?;
+        if port == 0 {
+            return Err(RequestError::InvalidTarget);
+        }
+
+        Ok(Self { host, port })
+    }
+
+    /// Returns the path for the target following the default URI template
+    pub fn to_path(&self) -> String {
+        format!(
+            "{WELL_KNOWN_PREFIX}{}/{}/",
+            percent_encode(&self.host),
+            self.port
+        )
+    }
+}
+
+impl fmt::Display for Target {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.host.contains(':') {
+            write!(f, "[{}]:{}", self.host, self.port)
+        } else {
+            write!(f, "{}:{}", self.host, self.port)
+        }
+    }
+}
+
+/// The reason a CONNECT-UDP request was rejected
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RequestError {
+    /// The request method was not CONNECT
+    InvalidMethod,
+    /// The `:protocol` pseudo-header was missing or not `connect-udp`
+    InvalidProtocol,
+    /// The path did not contain a valid target host and port
+    InvalidTarget,
+}
+
+impl RequestError {
+    /// Returns the response which should be sent to the client
+    #[inline]
+    pub fn response(&self) -> Response<()> {
+        let status = match self {
+            Self::InvalidMethod => StatusCode::METHOD_NOT_ALLOWED,
+            Self::InvalidProtocol | Self::InvalidTarget => StatusCode::BAD_REQUEST,
+        };
+
+        Response::builder().status(status).body(()).unwrap()
+    }
+}
+
+impl std::error::Error for RequestError {}
+
+impl fmt::Display for RequestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidMethod => write!(f, "CONNECT-UDP requests must use the CONNECT method"),
+            Self::InvalidProtocol => write!(f, "the :protocol pseudo-header must be {PROTOCOL}"),
+            Self::InvalidTarget => write!(f, "the request path does not contain a valid target"),
+        }
+    }
+}
+
+/// Validates a CONNECT-UDP request received by a proxy and returns the requested target
+pub fn validate<T>(request: &Request<T>) -> Result<Target, RequestError> {
+    if request.method() != Method::CONNECT {
+        return Err(RequestError::InvalidMethod);
+    }
+
+    if request.extensions().get::<Protocol>() != Some(&Protocol::CONNECT_UDP) {
+        return Err(RequestError::InvalidProtocol);
+    }
+
+    Target::from_path(request.uri().path())
+}
+
+/// Creates a CONNECT-UDP request for `target` through the proxy at `authority`
+pub fn request(authority: &str, target: &Target) -> Result<Request<()>, http::Error> {
+    Request::builder()
+        .method(Method::CONNECT)
+        .uri(format!("https://{authority}{}", target.to_path()))
+        .header(CAPSULE_PROTOCOL, HeaderValue::from_static("?1"))
+        .extension(Protocol::CONNECT_UDP)
+        .body(())
+}
+
+/// Creates the response a proxy sends to accept a CONNECT-UDP request
+pub fn response() -> Response<()> {
+    Response::builder()
+        .status(StatusCode::OK)
+        .header(CAPSULE_PROTOCOL, HeaderValue::from_static("?1"))
+        .body(())
+        .unwrap()
+}
+
+/// An HTTP Datagram, as defined in [RFC 9297](https://www.rfc-editor.org/rfc/rfc9297#section-2.1)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Datagram {
+    /// The ID of the request stream the datagram is associated with
+    pub stream_id: u64,
+    pub context_id: u64,
+    pub payload: Bytes,
+}
+
+impl Datagram {
+    /// Creates an HTTP Datagram carrying a UDP payload for the session on the given stream
+    #[inline]
+    pub fn udp_payload(stream_id: u64, payload: Bytes) -> Self {
+        Self {
+            stream_id,
+            context_id: UDP_PAYLOAD_CONTEXT_ID,
+            payload,
+        }
+    }
+
+    /// Decodes an HTTP Datagram from the payload of a QUIC DATAGRAM frame
+    pub fn decode(mut datagram: Bytes) -> Option<Self> {
+        let mut peek = &datagram[..];
+        let quarter_stream_id = varint::decode(&mut peek)?;
+        let context_id = varint::decode(&mut peek)?;
+        let header_len = datagram.len() - peek.len();
+        datagram.advance(header_len);
+
+        Some(Self {
+            stream_id: quarter_stream_id.checked_mul(4)?,
+            context_id,
+            payload: datagram,
+        })
+    }
+
+    /// Encodes the HTTP Datagram into the payload of a QUIC DATAGRAM frame
+    pub fn encode(&self) -> Bytes {
+        let mut buf = BytesMut::with_capacity(16 + self.payload.len());
+        varint::encode(self.stream_id / 4, &mut buf);
+        varint::encode(self.context_id, &mut buf);
+        buf.put_slice(&self.payload);
+        buf.freeze()
+    }
+
+    /// Decodes an HTTP Datagram from a capsule received on the request stream with the given ID
+    ///
+    /// Returns `None` if the capsule isn't a valid DATAGRAM capsule.
+    pub fn from_capsule(stream_id: u64, capsule: &Capsule) -> Option<Self> {
+        if !capsule.is_datagram() {
+            return None;
+        }
+
+        let mut peek = &capsule.value[..];
+        let context_id = varint::decode(&mut peek)?;
+        let payload = capsule.value.slice(capsule.value.len() - peek.len()..);
+
+        Some(Self {
+            stream_id,
+            context_id,
+            payload,
+        })
+    }
+
+    /// Encodes the HTTP Datagram into a DATAGRAM capsule, to be sent on its request stream
+    ///
+    /// This is used instead of [`Self::encode`] when QUIC DATAGRAM frames aren't available.
+    pub fn to_capsule(&self) -> Capsule {
+        let mut value = BytesMut::with_capacity(8 + self.payload.len());
+        varint::encode(self.context_id, &mut value);
+        value.put_slice(&self.payload);
+        Capsule::datagram(value.freeze())
+    }
+}
+
+/// Polls for the next HTTP Datagram received on the connection
+///
+/// HTTP Datagrams for all of the sessions on the connection share the same queue, so the
+/// caller is responsible for routing them to the [`Session`] with the matching stream ID.
+/// Datagrams which can't be decoded are discarded.
+#[cfg(feature = "unstable-provider-datagram")]
+pub fn poll_recv_datagram(
+    handle: &s2n_quic::connection::Handle,
+    cx: &mut Context<'_>,
+) -> Poll<Result<Datagram, SessionError>> {
+    loop {
+        let datagram = handle
+            .datagram_mut(|receiver: &mut Receiver| receiver.poll_recv_datagram(cx))
+            .map_err(|_| SessionError::Unavailable)?;
+        let datagram = ready!(datagram).map_err(SessionError::Datagram)?;
+
+        if let Some(datagram) = Datagram::decode(datagram) {
+            return Poll::Ready(Ok(datagram));
+        }
+    }
+}
+
+/// Forwards UDP payloads for an accepted CONNECT-UDP request
+#[cfg(feature = "unstable-provider-datagram")]
+#[derive(Clone, Debug)]
+pub struct Session {
+    handle: s2n_quic::connection::Handle,
+    stream_id: u64,
+}
+
+#[cfg(feature = "unstable-provider-datagram")]
+impl Session {
+    /// Creates a session for the CONNECT-UDP request on the stream with the given ID
+    #[inline]
+    pub fn new(handle: s2n_quic::connection::Handle, stream_id: u64) -> Self {
+        Self { handle, stream_id }
+    }
+
+    /// Returns the ID of the request stream the session is associated with
+    #[inline]
+    pub fn stream_id(&self) -> u64 {
+        self.stream_id
+    }
+
+    /// Sends a UDP payload to the peer in an HTTP Datagram
+    pub fn send(&self, payload: Bytes) -> Result<(), SessionError> {
+        let datagram = Datagram::udp_payload(self.stream_id, payload).encode();
+
+        self.handle
+            .datagram_mut(|sender: &mut Sender| sender.send_datagram(datagram))
+            .map_err(|_| SessionError::Unavailable)?
+            .map_err(SessionError::Datagram)
+    }
+
+    /// Returns the UDP payload if the datagram belongs to the session
+    ///
+    /// Datagrams with an unknown context ID are ignored, as required by RFC 9298.
+    #[inline]
+    pub fn on_datagram(&self, datagram: Datagram) -> Option<Bytes> {
+        if datagram.stream_id != self.stream_id || datagram.context_id != UDP_PAYLOAD_CONTEXT_ID {
+            return None;
+        }
+
+        Some(datagram.payload)
+    }
+}
+
+/// An error which occurred while forwarding UDP payloads
+#[cfg(feature = "unstable-provider-datagram")]
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum SessionError {
+    /// The connection isn't configured with the default datagram provider or is no longer
+    /// usable
+    Unavailable,
+    /// The datagram could not be sent or received
+    Datagram(DatagramError),
+}
+
+#[cfg(feature = "unstable-provider-datagram")]
+impl std::error::Error for SessionError {}
+
+#[cfg(feature = "unstable-provider-datagram")]
+impl fmt::Display for SessionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unavailable => write!(f, "datagrams are not available on the connection"),
+            Self::Datagram(error) => error.fmt(f),
+        }
+    }
+}
+
+/// Decodes the percent-encoded characters in a URI template variable
+fn percent_decode(value: &str) -> Option<String> {
+    let mut bytes = value.bytes();
+    let mut out = Vec::with_capacity(value.len());
+
+    while let Some(byte) = bytes.next() {
+        if byte != b'%' {
+            out.push(byte);
+            continue;
+        }
+
+        let high = (bytes.next()? as char).to_digit(16)?;
+        let low = (bytes.next()? as char).to_digit(16)?;
+        out.push((high * 16 + low) as u8);
+    }
+
+    String::from_utf8(out).ok()
+}
+
+/// Percent-encodes the characters which aren't allowed in a URI template variable
+fn percent_encode(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            out.push(byte as char);
+        } else {
+            out.push_str(&format!("%{byte:02X}"));
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn target_path_test() {
+        let target = Target::from_path("/.well-known/masque/udp/192.0.2.6/443/").unwrap();
+        assert_eq!(target, Target::new("192.0.2.6", 443));
+        assert_eq!(target.to_path(), "/.well-known/masque/udp/192.0.2.6/443/");
+
+        // IPv6 addresses are percent-encoded
+        let target = Target::from_path("/.well-known/masque/udp/2001%3Adb8%3A%3A42/53/").unwrap();
+        assert_eq!(target, Target::new("2001:db8::42", 53));
+        assert_eq!(target.to_string(), "[2001:db8::42]:53");
+        assert_eq!(Target::from_path(&target.to_path()).unwrap(), target);
+
+        for path in [
+            "/.well-known/masque/udp/example.com/443",
+            "/.well-known/masque/udp//443/",
+            "/.well-known/masque/udp/example.com/0/",
+            "/.well-known/masque/udp/example.com/65536/",
+            "/.well-known/masque/udp/example%2/443/",
+            "/.well-known/masque/tcp/example.com/443/",
+        ] {
+            assert_eq!(
+                Target::from_path(path),
+                Err(RequestError::InvalidTarget),
+                "{path}"
+            );
+        }
+    }
+
+    #[test]
+    fn validate_test() {
+        let target = Target::new("example.com", 443);
+        let request = request("proxy.example.com", &target).unwrap();
+        assert_eq!(validate(&request), Ok(target.clone()));
+
+        let mut get = Request::get(request.uri().clone()).body(()).unwrap();
+        assert_eq!(validate(&get), Err(RequestError::InvalidMethod));
+        *get.method_mut() = Method::CONNECT;
+        assert_eq!(validate(&get), Err(RequestError::InvalidProtocol));
+    }
+
+    #[test]
+    fn datagram_round_trip_test() {
+        let datagram = Datagram::udp_payload(8, Bytes::from_static(b"payload"));
+        let encoded = datagram.encode();
+        assert_eq!(&encoded[..], b"\x02\x00payload");
+        assert_eq!(Datagram::decode(encoded), Some(datagram));
+
+        assert_eq!(Datagram::decode(Bytes::from_static(b"\x02")), None);
+    }
+
+    #[test]
+    fn datagram_capsule_round_trip_test() {
+        let datagram = Datagram::udp_payload(8, Bytes::from_static(b"payload"));
+        let capsule = datagram.to_capsule();
+        assert_eq!(&capsule.to_bytes()[..], b"\x00\x08\x00payload");
+        assert_eq!(Datagram::from_capsule(8, &capsule), Some(datagram));
+
+        // only DATAGRAM capsules carry HTTP Datagrams
+        let other = Capsule::new(0x1234, capsule.value.clone());
+        assert_eq!(Datagram::from_capsule(8, &other), None);
+        assert_eq!(
+            Datagram::from_capsule(8, &Capsule::datagram(Bytes::new())),
+            None
+        );
+    }
+}
@@ -1,6 +1,8 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod capsule;
+pub mod connect_udp;
 mod s2n_quic;
 
 pub use self::s2n_quic::*;
@@ -35,6 +35,14 @@ impl Connection {
             recv_acceptor: recv,
         }
     }
+
+    /// Returns the handle for the underlying QUIC connection
+    ///
+    /// The handle can be cloned before the connection is passed to h3, which allows the
+    /// application to keep interacting with the connection, e.g. to send datagrams.
+    pub fn handle(&self) -> &s2n_quic::connection::Handle {
+        &self.conn
+    }
 }
 
 #[derive(Debug)]
@@ -0,0 +1,122 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+#![cfg(feature = "unstable-provider-datagram")]
+
+use bytes::Bytes;
+use core::future::poll_fn;
+use s2n_quic::{
+    client::Connect, connection::Handle, provider::datagram::default::Endpoint,
+    stream::BidirectionalStream, Client, Server,
+};
+use s2n_quic_core::crypto::tls::testing::certificates::{CERT_PEM, KEY_PEM};
+use s2n_quic_h3::{
+    capsule,
+    connect_udp::{self, Datagram, Session},
+};
+
+fn datagram_endpoint() -> Endpoint {
+    Endpoint::builder().build().unwrap()
+}
+
+/// Waits for the next HTTP Datagram received in a QUIC DATAGRAM frame
+async fn recv_datagram(handle: &Handle) -> Datagram {
+    poll_fn(|cx| connect_udp::poll_recv_datagram(handle, cx))
+        .await
+        .unwrap()
+}
+
+/// Waits for the next HTTP Datagram received in a capsule on the request stream
+async fn recv_capsule(
+    stream: &mut BidirectionalStream,
+    decoder: &mut capsule::Decoder,
+) -> Datagram {
+    loop {
+        if let Some(capsule) = decoder.next_capsule().unwrap() {
+            return Datagram::from_capsule(stream.id(), &capsule).unwrap();
+        }
+
+        let chunk = stream.receive().await.unwrap().unwrap();
+        decoder.push(chunk);
+    }
+}
+
+/// Ensures UDP payloads are forwarded in QUIC DATAGRAM frames as well as in capsules on the
+/// request stream
+#[tokio::test]
+async fn connect_udp_test() {
+    let mut server = Server::builder()
+        .with_tls((CERT_PEM, KEY_PEM))
+        .unwrap()
+        .with_io("127.0.0.1:0")
+        .unwrap()
+        .with_datagram(datagram_endpoint())
+        .unwrap()
+        .start()
+        .unwrap();
+    let server_addr = server.local_addr().unwrap();
+
+    // the proxy echoes each payload back over the same transport it was received on
+    let proxy = tokio::spawn(async move {
+        let mut connection = server.accept().await.unwrap();
+        let handle = connection.handle();
+        let mut stream = connection
+            .accept_bidirectional_stream()
+            .await
+            .unwrap()
+            .unwrap();
+        let session = Session::new(handle.clone(), stream.id());
+
+        let datagram = recv_datagram(&handle).await;
+        let payload = session.on_datagram(datagram).unwrap();
+        session.send(payload).unwrap();
+
+        let mut decoder = capsule::Decoder::default();
+        let datagram = recv_capsule(&mut stream, &mut decoder).await;
+        let payload = session.on_datagram(datagram).unwrap();
+        let capsule = Datagram::udp_payload(stream.id(), payload).to_capsule();
+        stream.send(capsule.to_bytes()).await.unwrap();
+        stream.finish().unwrap();
+
+        // keep the connection open until the client is done
+        assert!(stream.receive().await.unwrap().is_none());
+        assert!(decoder.is_empty());
+    });
+
+    let client = Client::builder()
+        .with_tls(CERT_PEM)
+        .unwrap()
+        .with_io("0.0.0.0:0")
+        .unwrap()
+        .with_datagram(datagram_endpoint())
+        .unwrap()
+        .start()
+        .unwrap();
+
+    let connect = Connect::new(server_addr).with_server_name("localhost");
+    let mut connection = client.connect(connect).await.unwrap();
+    let handle = connection.handle();
+    let mut stream = connection.open_bidirectional_stream().await.unwrap();
+    let session = Session::new(handle.clone(), stream.id());
+
+    // the capsule is sent first so the proxy learns about the request stream
+    let capsule_payload = Bytes::from_static(b"in a capsule");
+    let capsule = Datagram::udp_payload(stream.id(), capsule_payload.clone()).to_capsule();
+    stream.send(capsule.to_bytes()).await.unwrap();
+
+    let datagram_payload = Bytes::from_static(b"in a QUIC datagram");
+    session.send(datagram_payload.clone()).unwrap();
+
+    let datagram = recv_datagram(&handle).await;
+    assert_eq!(session.on_datagram(datagram), Some(datagram_payload));
+
+    let mut decoder = capsule::Decoder::default();
+    let datagram = recv_capsule(&mut stream, &mut decoder).await;
+    assert_eq!(session.on_datagram(datagram), Some(capsule_payload));
+
+    stream.finish().unwrap();
+    assert!(stream.receive().await.unwrap().is_none());
+    assert!(decoder.is_empty());
+
+    proxy.await.unwrap();
+}
@@ -9,6 +9,7 @@ enum Feature {
     ControlMessage,
     SocketMessage,
     SocketMultiMessage,
+    SocketMessageBatch,
     MtuDiscovery,
     GenericSegmentationOffload,
     GenericReceiveOffload,
@@ -22,6 +23,7 @@ impl Feature {
             ControlMessage => "cmsg",
             SocketMessage => "socket_msg",
             SocketMultiMessage => "socket_mmsg",
+            SocketMessageBatch => "socket_msg_x",
             MtuDiscovery => "mtu_disc",
             GenericSegmentationOffload => "gso",
             GenericReceiveOffload => "gro",
@@ -43,10 +45,11 @@ impl From<&str> for Feature {
     }
 }
 
-const ALL_FEATURES: [Feature; 8] = [
+const ALL_FEATURES: [Feature; 9] = [
     ControlMessage,
     SocketMessage,
     SocketMultiMessage,
+    SocketMessageBatch,
     MtuDiscovery,
     GenericSegmentationOffload,
     GenericReceiveOffload,
@@ -96,7 +99,7 @@ fn main() -> Result<(), Error> {
             features.insert(PacketInfo);
             features.insert(TypeOfService);
         }
-        "macos" => {
+        "macos" | "ios" => {
             // miri doesn't support the way we detect syscall support so override it
             if is_miri {
                 features.insert(SocketMessage);
@@ -133,7 +136,10 @@ struct Features {
 impl Features {
     fn insert(&mut self, feature: Feature) {
         // supporting any kind message implies cmsg support
-        if matches!(feature, SocketMessage | SocketMultiMessage) {
+        if matches!(
+            feature,
+            SocketMessage | SocketMultiMessage | SocketMessageBatch
+        ) {
             self.insert(ControlMessage);
         }
 
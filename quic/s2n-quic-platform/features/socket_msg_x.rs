@@ -0,0 +1,24 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Detects platform capability of the batched `sendmsg_x` and `recvmsg_x` calls available on
+//! Apple platforms.
+//!
+//! These calls aren't part of Apple's public SDK so they are only used when explicitly enabled
+//! with the `unstable-apple-msg-x` feature.
+
+fn main() {
+    println!("sendmsg_x {:?}", unsafe { SENDMSG_X });
+    println!("recvmsg_x {:?}", unsafe { RECVMSG_X });
+}
+
+/// Try to resolve the required references from the linker
+///
+/// The build will fail if they don't exist or the feature isn't enabled.
+#[cfg(all(unix, feature = "std", feature = "unstable-apple-msg-x"))]
+extern "C" {
+    #[link_name = "sendmsg_x"]
+    static SENDMSG_X: *const u8;
+    #[link_name = "recvmsg_x"]
+    static RECVMSG_X: *const u8;
+}
@@ -1,7 +1,12 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-#[cfg(feature = "tokio")]
+#[cfg(feature = "std")]
+pub mod datagram;
+#[cfg(feature = "std")]
+pub mod spawner;
+
+#[cfg(all(feature = "tokio", not(target_family = "wasm")))]
 pub mod tokio;
 
 #[cfg(any(test, feature = "io-testing"))]
@@ -0,0 +1,446 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! An IO provider which runs over a host-provided datagram transport
+//!
+//! This is intended for environments without direct access to OS sockets, such as
+//! `wasm32-wasi`, where the host exposes a UDP-like socket. The endpoint is driven by a single
+//! task which is handed to the configured [`Spawner`].
+
+use crate::io::spawner::{Spawner, Task};
+use core::{
+    cell::RefCell,
+    fmt,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+use s2n_quic_core::{
+    endpoint::Endpoint,
+    inet::{datagram, ExplicitCongestionNotification, SocketAddress},
+    io::manual::Driver,
+    path::{mtu, Tuple},
+    time::{Clock, StdClock, Timestamp},
+};
+use std::{io, sync::Arc};
+
+pub type PathHandle = Tuple;
+
+/// A UDP-like socket provided by the host
+pub trait Transport: 'static + Send {
+    /// Returns the local address of the socket
+    fn local_address(&self) -> io::Result<SocketAddress>;
+
+    /// Polls for a datagram, writing it into `buffer`
+    ///
+    /// On success, the remote address and length of the datagram are returned.
+    fn poll_recv(
+        &mut self,
+        cx: &mut Context,
+        buffer: &mut [u8],
+    ) -> Poll<io::Result<(SocketAddress, usize)>>;
+
+    /// Polls for the ability to send `payload` to the remote address
+    fn poll_send(
+        &mut self,
+        cx: &mut Context,
+        remote_address: SocketAddress,
+        payload: &[u8],
+    ) -> Poll<io::Result<()>>;
+
+    /// Polls a timer which expires after `duration` has elapsed since the call
+    ///
+    /// The transport is responsible for waking the task when the timer expires.
+    fn poll_timer(&mut self, cx: &mut Context, duration: Duration) -> Poll<()>;
+}
+
+/// A clock provided by the application
+struct BoxedClock(Box<dyn Clock + Send + Sync>);
+
+impl Clock for BoxedClock {
+    #[inline]
+    fn get_time(&self) -> Timestamp {
+        self.0.get_time()
+    }
+}
+
+#[derive(Default)]
+pub struct Builder {
+    transport: Option<Box<dyn Transport>>,
+    spawner: Option<Arc<dyn Spawner>>,
+    clock: Option<BoxedClock>,
+    mtu_config_builder: mtu::Builder,
+}
+
+impl fmt::Debug for Builder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Builder")
+            .field("has_transport", &self.transport.is_some())
+            .field("has_spawner", &self.spawner.is_some())
+            .field("has_clock", &self.clock.is_some())
+            .field("mtu_config_builder", &self.mtu_config_builder)
+            .finish()
+    }
+}
+
+impl Builder {
+    /// Sets the transport used to send and receive datagrams
+    #[must_use]
+    pub fn with_transport<T: Transport>(mut self, transport: T) -> Self {
+        self.transport = Some(Box::new(transport));
+        self
+    }
+
+    /// Sets the spawner used to run the endpoint task
+    #[must_use]
+    pub fn with_spawner<S: Spawner>(mut self, spawner: S) -> Self {
+        self.spawner = Some(Arc::new(spawner));
+        self
+    }
+
+    /// Sets the clock used by the endpoint
+    ///
+    /// By default, the clock is derived from `std::time::Instant`, which is supported on
+    /// `wasm32-wasi`. Hosts without a monotonic `Instant` should provide their own clock.
+    #[must_use]
+    pub fn with_clock<C: 'static + Clock + Send + Sync>(mut self, clock: C) -> Self {
+        self.clock = Some(BoxedClock(Box::new(clock)));
+        self
+    }
+
+    /// Sets the largest maximum transmission unit (MTU) that can be sent on a path
+    ///
+    /// This should not exceed the largest datagram the transport can carry.
+    pub fn with_max_mtu(mut self, max_mtu: u16) -> io::Result<Self> {
+        self.mtu_config_builder = self
+            .mtu_config_builder
+            .with_max_mtu(max_mtu)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, format!("{err}")))?;
+        Ok(self)
+    }
+
+    pub fn build(self) -> io::Result<Io> {
+        Ok(Io { builder: self })
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Io {
+    builder: Builder,
+}
+
+impl Io {
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
+    pub fn start<E: Endpoint<PathHandle = PathHandle>>(
+        self,
+        endpoint: E,
+    ) -> io::Result<SocketAddress> {
+        let Builder {
+            transport,
+            spawner,
+            clock,
+            mtu_config_builder,
+        } = self.builder;
+
+        let transport = transport.ok_or_else(|| {
+            io::Error::new(io::ErrorKind::NotConnected, "missing datagram transport")
+        })?;
+        let spawner = spawner
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "missing task spawner"))?;
+        let clock = match clock {
+            Some(clock) => clock,
+            None => BoxedClock(Box::<StdClock>::default()),
+        };
+
+        let local_address = transport.local_address()?;
+
+        let mtu_config = mtu_config_builder
+            .build()
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, format!("{err}")))?;
+
+        let task: Task = Box::pin(EndpointTask {
+            driver: Box::new(Driver::new(endpoint, mtu_config)),
+            transport: RefCell::new(transport),
+            clock,
+            local_address,
+        });
+        spawner.spawn(task);
+
+        Ok(local_address)
+    }
+}
+
+/// Drives the endpoint with the host transport
+struct EndpointTask<E: Endpoint<PathHandle = PathHandle>> {
+    /// The driver is boxed so the task is `Unpin` regardless of the endpoint type
+    driver: Box<Driver<E>>,
+    transport: RefCell<Box<dyn Transport>>,
+    clock: BoxedClock,
+    local_address: SocketAddress,
+}
+
+impl<E: Endpoint<PathHandle = PathHandle>> Future for EndpointTask<E> {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<()> {
+        let this = self.get_mut();
+        let local_address = this.local_address;
+        let transport = &this.transport;
+        // the socket callbacks are polled with their own context, since the driver requires
+        // exclusive access to `cx`
+        let waker = cx.waker().clone();
+
+        loop {
+            let result = this.driver.poll(
+                cx,
+                &this.clock,
+                |buffer| {
+                    let mut cx = Context::from_waker(&waker);
+                    let mut transport = transport.borrow_mut();
+                    match transport.poll_recv(&mut cx, buffer) {
+                        Poll::Ready(Ok((remote_address, len))) => {
+                            let header = datagram::Header {
+                                path: Tuple {
+                                    remote_address: remote_address.into(),
+                                    local_address: local_address.into(),
+                                },
+                                ecn: ExplicitCongestionNotification::default(),
+                            };
+                            Some((header, len))
+                        }
+                        Poll::Ready(Err(_)) => {
+                            // treat the error as a dropped datagram and make sure the transport
+                            // is polled again
+                            waker.wake_by_ref();
+                            None
+                        }
+                        Poll::Pending => None,
+                    }
+                },
+                |handle, payload| {
+                    let mut cx = Context::from_waker(&waker);
+                    let mut transport = transport.borrow_mut();
+                    let remote_address = *handle.remote_address;
+                    // the driver holds on to a pending datagram and retries it once the transport
+                    // wakes the task. Any errors are treated as a dropped datagram.
+                    transport
+                        .poll_send(&mut cx, remote_address, payload)
+                        .is_ready()
+                },
+            );
+
+            let timeout = match result {
+                Ok(timeout) => timeout,
+                // the endpoint has closed
+                Err(_) => return Poll::Ready(()),
+            };
+
+            let Some(timeout) = timeout else {
+                return Poll::Pending;
+            };
+
+            let now = this.clock.get_time();
+            if timeout <= now {
+                continue;
+            }
+
+            if this
+                .transport
+                .borrow_mut()
+                .poll_timer(cx, timeout.saturating_duration_since(now))
+                .is_pending()
+            {
+                return Poll::Pending;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use s2n_quic_core::{
+        endpoint::{self, CloseError},
+        event,
+        inet::SocketAddressV4,
+        io::{rx, tx},
+    };
+    use std::{collections::VecDeque, sync::Mutex};
+
+    type Datagrams = Arc<Mutex<VecDeque<(SocketAddress, Vec<u8>)>>>;
+
+    struct TestTransport {
+        local_address: SocketAddress,
+        inbox: Datagrams,
+        outbox: Datagrams,
+    }
+
+    impl Transport for TestTransport {
+        fn local_address(&self) -> io::Result<SocketAddress> {
+            Ok(self.local_address)
+        }
+
+        fn poll_recv(
+            &mut self,
+            _cx: &mut Context,
+            buffer: &mut [u8],
+        ) -> Poll<io::Result<(SocketAddress, usize)>> {
+            let Some((addr, payload)) = self.inbox.lock().unwrap().pop_front() else {
+                return Poll::Pending;
+            };
+            buffer[..payload.len()].copy_from_slice(&payload);
+            Poll::Ready(Ok((addr, payload.len())))
+        }
+
+        fn poll_send(
+            &mut self,
+            _cx: &mut Context,
+            remote_address: SocketAddress,
+            payload: &[u8],
+        ) -> Poll<io::Result<()>> {
+            self.outbox
+                .lock()
+                .unwrap()
+                .push_back((remote_address, payload.to_vec()));
+            Poll::Ready(Ok(()))
+        }
+
+        fn poll_timer(&mut self, _cx: &mut Context, _duration: Duration) -> Poll<()> {
+            Poll::Pending
+        }
+    }
+
+    struct NoopSubscriber;
+
+    impl event::Subscriber for NoopSubscriber {
+        type ConnectionContext = ();
+
+        fn create_connection_context(
+            &mut self,
+            _meta: &event::api::ConnectionMeta,
+            _info: &event::api::ConnectionInfo,
+        ) -> Self::ConnectionContext {
+        }
+    }
+
+    /// Echoes a single datagram and then closes
+    struct EchoEndpoint {
+        pending: Vec<(PathHandle, Vec<u8>)>,
+        echoed: bool,
+        subscriber: NoopSubscriber,
+    }
+
+    impl Endpoint for EchoEndpoint {
+        type PathHandle = PathHandle;
+        type Subscriber = NoopSubscriber;
+
+        const ENDPOINT_TYPE: endpoint::Type = endpoint::Type::Server;
+
+        fn receive<Rx: rx::Queue<Handle = PathHandle>, C: Clock>(
+            &mut self,
+            queue: &mut Rx,
+            _clock: &C,
+        ) {
+            queue.for_each(|header, payload| self.pending.push((header.path, payload.to_vec())));
+        }
+
+        fn transmit<Tx: tx::Queue<Handle = PathHandle>, C: Clock>(
+            &mut self,
+            queue: &mut Tx,
+            _clock: &C,
+        ) {
+            for message in self.pending.drain(..) {
+                if queue.push(message).is_ok() {
+                    self.echoed = true;
+                }
+            }
+        }
+
+        fn poll_wakeups<C: Clock>(
+            &mut self,
+            _cx: &mut Context<'_>,
+            _clock: &C,
+        ) -> Poll<Result<usize, CloseError>> {
+            if self.echoed {
+                Poll::Ready(Err(CloseError))
+            } else {
+                Poll::Pending
+            }
+        }
+
+        fn timeout(&self) -> Option<Timestamp> {
+            None
+        }
+
+        fn set_mtu_config(&mut self, _mtu_config: mtu::Config) {}
+
+        fn subscriber(&mut self) -> &mut Self::Subscriber {
+            &mut self.subscriber
+        }
+    }
+
+    #[test]
+    fn echo_test() {
+        let local_address: SocketAddress = SocketAddressV4::new([10, 0, 0, 1], 443).into();
+        let remote_address: SocketAddress = SocketAddressV4::new([10, 0, 0, 2], 4433).into();
+
+        let inbox = Datagrams::default();
+        let outbox = Datagrams::default();
+        inbox
+            .lock()
+            .unwrap()
+            .push_back((remote_address, b"hello".to_vec()));
+
+        let tasks = Arc::new(Mutex::new(Vec::<Task>::new()));
+
+        let io = Io::builder()
+            .with_transport(TestTransport {
+                local_address,
+                inbox,
+                outbox: outbox.clone(),
+            })
+            .with_spawner({
+                let tasks = tasks.clone();
+                move |task| tasks.lock().unwrap().push(task)
+            })
+            .build()
+            .unwrap();
+
+        let endpoint = EchoEndpoint {
+            pending: vec![],
+            echoed: false,
+            subscriber: NoopSubscriber,
+        };
+
+        assert_eq!(io.start(endpoint).unwrap(), local_address);
+
+        let mut task = tasks.lock().unwrap().pop().expect("task should be spawned");
+        let waker = futures::task::noop_waker();
+        let mut cx = Context::from_waker(&waker);
+
+        // the first poll receives and echoes the datagram
+        assert!(task.as_mut().poll(&mut cx).is_pending());
+        assert_eq!(
+            outbox.lock().unwrap().pop_front(),
+            Some((remote_address, b"hello".to_vec()))
+        );
+
+        // the endpoint closes after echoing
+        assert!(task.as_mut().poll(&mut cx).is_ready());
+    }
+
+    #[test]
+    fn missing_transport_test() {
+        let endpoint = EchoEndpoint {
+            pending: vec![],
+            echoed: false,
+            subscriber: NoopSubscriber,
+        };
+        let err = Io::default().start(endpoint).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotConnected);
+    }
+}
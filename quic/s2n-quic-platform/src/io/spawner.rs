@@ -0,0 +1,20 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use core::{future::Future, pin::Pin};
+
+/// A task produced by the IO provider which must be polled to completion
+pub type Task = Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
+
+/// Spawns the tasks that drive an endpoint
+pub trait Spawner: 'static + Send + Sync {
+    /// Spawns a task to be polled to completion
+    fn spawn(&self, task: Task);
+}
+
+impl<F: 'static + Send + Sync + Fn(Task)> Spawner for F {
+    #[inline]
+    fn spawn(&self, task: Task) {
+        (self)(task)
+    }
+}
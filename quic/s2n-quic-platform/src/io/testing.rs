@@ -2,9 +2,12 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use bach::time::scheduler;
-use core::task::Poll;
+use core::{task::Poll, time::Duration};
 use s2n_quic_core::{
-    endpoint::Endpoint, inet::SocketAddress, io::event_loop::EventLoop, path::mtu,
+    endpoint::Endpoint,
+    inet::SocketAddress,
+    io::event_loop::{profile::Profiler, EventLoop},
+    path::mtu,
 };
 
 type Error = std::io::Error;
@@ -68,6 +71,8 @@ pub struct Executor<N: Network> {
 
 impl<N: Network> Executor<N> {
     pub fn new(network: N, seed: u64) -> Self {
+        time::reset();
+
         let mut executor = bach::executor::Executor::new(|handle| Env {
             handle: handle.clone(),
             time: scheduler::Scheduler::new(),
@@ -231,6 +236,7 @@ impl Handle {
             mtu_config_builder: mtu::Config::builder(),
             queue_recv_buffer_size: None,
             queue_send_buffer_size: None,
+            event_loop_profile_interval: None,
         }
     }
 }
@@ -242,6 +248,7 @@ pub struct Builder {
     mtu_config_builder: mtu::Builder,
     queue_recv_buffer_size: Option<u32>,
     queue_send_buffer_size: Option<u32>,
+    event_loop_profile_interval: Option<Duration>,
 }
 
 impl Builder {
@@ -249,6 +256,14 @@ impl Builder {
         Ok(Io { builder: self })
     }
 
+    /// Sets the address of the endpoint on the simulated network
+    ///
+    /// If not set, a unique address is generated.
+    pub fn with_address(mut self, address: SocketAddress) -> Self {
+        self.address = Some(address);
+        self
+    }
+
     pub fn with_base_mtu(mut self, base_mtu: u16) -> Self {
         self.mtu_config_builder = self.mtu_config_builder.with_base_mtu(base_mtu).unwrap();
         self
@@ -293,6 +308,12 @@ impl Builder {
         })?);
         Ok(self)
     }
+
+    /// Publishes the time spent in each phase of the endpoint event loop every `interval`
+    pub fn with_event_loop_profiling(mut self, interval: Duration) -> Self {
+        self.event_loop_profile_interval = Some(interval);
+        self
+    }
 }
 
 pub struct Io {
@@ -312,6 +333,7 @@ impl Io {
             mtu_config_builder,
             queue_recv_buffer_size: _,
             queue_send_buffer_size: _,
+            event_loop_profile_interval: _,
         } = self.builder;
 
         let handle = address.unwrap_or_else(|| buffers.generate_addr());
@@ -336,6 +358,7 @@ impl Io {
             mtu_config_builder,
             queue_recv_buffer_size,
             queue_send_buffer_size,
+            event_loop_profile_interval,
         } = self.builder;
         let mtu_config = mtu_config_builder.build().unwrap();
         endpoint.set_mtu_config(mtu_config);
@@ -365,6 +388,7 @@ impl Io {
             rx,
             cooldown: Default::default(),
             stats: stats_recv,
+            profiler: Profiler::new(event_loop_profile_interval),
         };
         let join = executor.spawn(event_loop.start(handle));
         Ok((join, handle))
@@ -184,6 +184,20 @@ impl Buffers {
         }
     }
 
+    pub fn pending_transmission_mut<F: FnMut(&mut Packet)>(&self, mut f: F) {
+        if let Ok(mut lock) = self.inner.lock() {
+            // visit the hosts in a consistent order so the results are deterministic
+            let mut queues: Vec<_> = lock.tx.iter_mut().collect();
+            queues.sort_by_key(|(host, _)| host.0);
+
+            for (_host, queue) in queues {
+                for packet in &mut queue.packets {
+                    f(packet);
+                }
+            }
+        }
+    }
+
     pub fn drain_pending_transmissions<F: FnMut(Packet) -> Result<(), ()>>(&self, mut f: F) {
         let mut lock = if let Ok(lock) = self.inner.lock() {
             lock
@@ -3,15 +3,54 @@
 
 use bach::time::{self, scheduler};
 use core::{
+    cell::RefCell,
     future::Future,
     pin::Pin,
-    task::{ready, Context, Poll},
+    task::{Context, Poll, Waker},
     time::Duration,
 };
 use s2n_quic_core::time::{clock, Timestamp};
+use std::collections::BTreeMap;
+
+thread_local! {
+    static SKIP: RefCell<Skip> = RefCell::new(Skip::default());
+}
+
+/// Tracks the amount of time skipped with [`advance`]
+#[derive(Debug, Default)]
+struct Skip {
+    offset: Duration,
+    next_id: u64,
+    waiting: BTreeMap<u64, Waker>,
+}
+
+fn offset() -> Duration {
+    SKIP.with(|skip| skip.borrow().offset)
+}
 
 pub fn now() -> Timestamp {
-    unsafe { Timestamp::from_duration(time::now()) }
+    unsafe { Timestamp::from_duration(time::now() + offset()) }
+}
+
+/// Immediately moves the simulated clock forward by `duration`
+///
+/// Unlike [`delay`], the calling task doesn't wait for the other tasks in the simulation to go
+/// idle. Every pending timer is woken so the ones with a deadline in the skipped window fire.
+pub fn advance(duration: Duration) {
+    let waiting = SKIP.with(|skip| {
+        let mut skip = skip.borrow_mut();
+        skip.offset += duration;
+        core::mem::take(&mut skip.waiting)
+    });
+
+    for waker in waiting.into_values() {
+        waker.wake();
+    }
+}
+
+/// Resets the skipped time for a new simulation
+pub(crate) fn reset() {
+    SKIP.with(|skip| *skip.borrow_mut() = Skip::default());
 }
 
 pub fn delay(duration: Duration) -> Timer {
@@ -44,6 +83,9 @@ impl clock::ClockWithTimer for Clock {
 pub struct Timer {
     timer: scheduler::Timer,
     deadline: Option<Timestamp>,
+    /// The skipped time when `timer` was scheduled
+    offset: Duration,
+    id: u64,
 }
 
 impl Default for Timer {
@@ -53,32 +95,75 @@ impl Default for Timer {
         Self {
             timer,
             deadline: None,
+            offset: offset(),
+            id: next_id(),
         }
     }
 }
 
+fn next_id() -> u64 {
+    SKIP.with(|skip| {
+        let mut skip = skip.borrow_mut();
+        skip.next_id += 1;
+        skip.next_id
+    })
+}
+
 impl Timer {
     fn new(deadline: Timestamp, delay: Duration) -> Self {
         Self {
             timer: time::delay(delay),
             deadline: Some(deadline),
+            offset: offset(),
+            id: next_id(),
         }
     }
 
     pub fn cancel(&mut self) {
+        self.unregister();
         self.deadline = None;
         self.timer.cancel()
     }
+
+    fn unregister(&self) {
+        // the thread local may have already been destroyed if the thread is exiting
+        let _ = SKIP.try_with(|skip| {
+            if let Ok(mut skip) = skip.try_borrow_mut() {
+                skip.waiting.remove(&self.id);
+            }
+        });
+    }
+}
+
+impl Drop for Timer {
+    fn drop(&mut self) {
+        self.unregister();
+    }
 }
 
 impl clock::Timer for Timer {
     fn poll_ready(&mut self, cx: &mut Context) -> Poll<()> {
-        if self.deadline.is_none() {
+        let Some(deadline) = self.deadline else {
             return Poll::Pending;
+        };
+
+        // reschedule the timer if the clock was advanced since it was scheduled
+        let offset = offset();
+        if self.offset != offset {
+            self.timer.cancel();
+            self.timer = time::delay(deadline.saturating_duration_since(now()));
+            self.offset = offset;
         }
 
-        ready!(Pin::new(&mut self.timer).poll(cx));
+        if deadline > now() && Pin::new(&mut self.timer).poll(cx).is_pending() {
+            SKIP.with(|skip| {
+                let waker = cx.waker().clone();
+                skip.borrow_mut().waiting.insert(self.id, waker);
+            });
+            return Poll::Pending;
+        }
 
+        self.unregister();
         self.deadline = None;
         Poll::Ready(())
     }
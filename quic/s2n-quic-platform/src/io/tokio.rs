@@ -6,16 +6,17 @@ use s2n_quic_core::{
     endpoint::Endpoint,
     event::{self, EndpointPublisher as _},
     inet::{self, SocketAddress},
-    io::event_loop::EventLoop,
+    io::event_loop::{profile::Profiler, EventLoop},
     path::{mtu, MaxMtu},
     task::cooldown::Cooldown,
     time::Clock as ClockTrait,
 };
-use std::{convert::TryInto, io, io::ErrorKind};
+use std::{convert::TryInto, io, io::ErrorKind, time::Duration};
 use tokio::runtime::Handle;
 
 mod builder;
 mod clock;
+mod spawner;
 pub(crate) mod task;
 #[cfg(test)]
 mod tests;
@@ -23,6 +24,7 @@ mod tests;
 pub type PathHandle = message::Handle;
 pub use builder::Builder;
 pub(crate) use clock::Clock;
+pub use spawner::{Spawner, Task};
 
 #[derive(Debug, Default)]
 pub struct Io {
@@ -46,19 +48,26 @@ impl Io {
     ) -> io::Result<(tokio::task::JoinHandle<()>, SocketAddress)> {
         let Builder {
             handle,
+            spawner,
             rx_socket,
             tx_socket,
             recv_addr,
             send_addr,
+            additional_addrs,
             socket_recv_buffer_size,
             socket_send_buffer_size,
             queue_recv_buffer_size,
             queue_send_buffer_size,
             mtu_config_builder,
             max_segments,
+            max_gso_payload_size,
             gro_enabled,
             reuse_address,
             reuse_port,
+            dscp,
+            bind_device,
+            dont_fragment,
+            event_loop_profile_interval,
         } = self.builder;
 
         let clock = Clock::default();
@@ -72,12 +81,6 @@ impl Io {
             endpoint.subscriber(),
         );
 
-        publisher.on_platform_feature_configured(event::builder::PlatformFeatureConfigured {
-            configuration: event::builder::PlatformFeatureConfiguration::Gso {
-                max_segments: max_segments.into(),
-            },
-        });
-
         // try to use the tokio runtime handle if provided, otherwise try to use the implicit tokio
         // runtime in the current scope of the application.
         let handle = if let Some(handle) = handle {
@@ -86,12 +89,22 @@ impl Io {
             Handle::try_current().map_err(|err| std::io::Error::new(io::ErrorKind::Other, err))?
         };
 
-        let guard = handle.enter();
+        let runtime = spawner::Runtime::new(handle, spawner);
+        let guard = runtime.handle().enter();
+
+        let bind_udp = |addr: std::net::SocketAddr| {
+            if additional_addrs.is_empty() {
+                syscall::bind_udp(addr, reuse_address, reuse_port)
+            } else {
+                // allow IPv4 and IPv6 sockets to share a port
+                syscall::bind_udp_v6_only(addr, reuse_address, reuse_port)
+            }
+        };
 
         let rx_socket = if let Some(rx_socket) = rx_socket {
             rx_socket
         } else if let Some(recv_addr) = recv_addr {
-            syscall::bind_udp(recv_addr, reuse_address, reuse_port)?
+            bind_udp(recv_addr)?
         } else {
             return Err(io::Error::new(
                 io::ErrorKind::InvalidInput,
@@ -104,13 +117,27 @@ impl Io {
         let tx_socket = if let Some(tx_socket) = tx_socket {
             tx_socket
         } else if let Some(send_addr) = send_addr {
-            syscall::bind_udp(send_addr, reuse_address, reuse_port)?
+            bind_udp(send_addr)?
         } else {
             // No tx_socket or send address was specified, so the tx socket
             // will be a handle to the rx socket.
             rx_socket.try_clone()?
         };
 
+        if let Some(device) = bind_device.as_deref() {
+            syscall::bind_device(&rx_socket, device)?;
+            syscall::bind_device(&tx_socket, device)?;
+        }
+
+        if let Some(dscp) = dscp {
+            if !syscall::configure_dscp(&tx_socket, dscp) {
+                return Err(io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    "dscp marking is not supported on the current platform",
+                ));
+            }
+        }
+
         if let Some(size) = socket_send_buffer_size {
             tx_socket.set_send_buffer_size(size)?;
         }
@@ -119,15 +146,54 @@ impl Io {
             rx_socket.set_recv_buffer_size(size)?;
         }
 
+        // Each additional address is served by a single socket for both receiving and transmitting
+        let mut additional_sockets = vec![];
+        for addr in additional_addrs.iter() {
+            let socket = bind_udp(*addr)?;
+
+            if let Some(device) = bind_device.as_deref() {
+                syscall::bind_device(&socket, device)?;
+            }
+
+            if let Some(dscp) = dscp {
+                syscall::configure_dscp(&socket, dscp);
+            }
+
+            if let Some(size) = socket_send_buffer_size {
+                socket.set_send_buffer_size(size)?;
+            }
+
+            if let Some(size) = socket_recv_buffer_size {
+                socket.set_recv_buffer_size(size)?;
+            }
+
+            let addr = convert_addr_to_std(socket.local_addr()?)?;
+            additional_sockets.push((socket, addr));
+        }
+
         let mut mtu_config = mtu_config_builder
             .build()
             .map_err(|err| io::Error::new(ErrorKind::InvalidInput, format!("{err}")))?;
         let original_max_mtu = mtu_config.max_mtu();
 
         // Configure MTU discovery
-        if !syscall::configure_mtu_disc(&tx_socket) {
-            // disable MTU probing if we can't prevent fragmentation
+        if !dont_fragment.unwrap_or(true) {
+            syscall::configure_fragmentation(&tx_socket);
+            for (socket, _addr) in &additional_sockets {
+                syscall::configure_fragmentation(socket);
+            }
+            // probes could be fragmented so there's no use in MTU probing
             mtu_config = mtu::Config::MIN;
+        } else {
+            let mut mtu_disc = syscall::configure_mtu_disc(&tx_socket);
+            for (socket, _addr) in &additional_sockets {
+                mtu_disc &= syscall::configure_mtu_disc(socket);
+            }
+
+            if !mtu_disc {
+                // disable MTU probing if we can't prevent fragmentation
+                mtu_config = mtu::Config::MIN;
+            }
         }
 
         publisher.on_platform_feature_configured(event::builder::PlatformFeatureConfigured {
@@ -148,9 +214,25 @@ impl Io {
             },
         });
 
+        let max_segments =
+            limit_max_segments(max_segments, mtu_config.max_mtu(), max_gso_payload_size)?;
+
+        publisher.on_platform_feature_configured(event::builder::PlatformFeatureConfigured {
+            configuration: event::builder::PlatformFeatureConfiguration::Gso {
+                max_segments: max_segments.into(),
+            },
+        });
+
         // Configure the socket with GRO
         let gro_enabled = gro_enabled.unwrap_or(true) && syscall::configure_gro(&rx_socket);
 
+        // The receive buffers are only sized for GRO if it's enabled on the primary socket
+        if gro_enabled {
+            for (socket, _addr) in &additional_sockets {
+                syscall::configure_gro(socket);
+            }
+        }
+
         publisher.on_platform_feature_configured(event::builder::PlatformFeatureConfigured {
             configuration: event::builder::PlatformFeatureConfiguration::Gro {
                 enabled: gro_enabled,
@@ -163,6 +245,11 @@ impl Io {
         // Configure TOS/ECN
         let tos_enabled = syscall::configure_tos(&rx_socket);
 
+        for (socket, _addr) in &additional_sockets {
+            syscall::configure_pktinfo(socket);
+            syscall::configure_tos(socket);
+        }
+
         publisher.on_platform_feature_configured(event::builder::PlatformFeatureConfigured {
             configuration: event::builder::PlatformFeatureConfiguration::Ecn {
                 enabled: tos_enabled,
@@ -204,7 +291,7 @@ impl Io {
 
                 // spawn a task that actually reads from the socket into the ring buffer
                 if idx + 1 == rx_socket_count {
-                    handle.spawn(task::rx(
+                    runtime.spawn(task::rx(
                         rx_socket,
                         producer,
                         rx_cooldown,
@@ -213,7 +300,7 @@ impl Io {
                     break;
                 } else {
                     let rx_socket = rx_socket.try_clone()?;
-                    handle.spawn(task::rx(
+                    runtime.spawn(task::rx(
                         rx_socket,
                         producer,
                         rx_cooldown.clone(),
@@ -225,7 +312,24 @@ impl Io {
             // construct the RX side for the endpoint event loop
             let max_mtu = MaxMtu::try_from(payload_len as u16).unwrap();
             let addr: inet::SocketAddress = rx_addr.into();
-            socket::io::rx::Rx::new(consumers, max_mtu, addr.into())
+            let mut rx = socket::io::rx::Rx::new(consumers, max_mtu, addr.into());
+
+            // each additional socket feeds its own channel so received packets are associated
+            // with the socket's local address
+            for (additional_socket, addr) in &additional_sockets {
+                let (producer, consumer) = socket::ring::pair(entries, payload_len);
+                runtime.spawn(task::rx(
+                    additional_socket.try_clone()?,
+                    producer,
+                    cooldown("RX"),
+                    stats_sender.clone(),
+                ));
+
+                let addr: inet::SocketAddress = (*addr).into();
+                rx = rx.with_channels(vec![consumer], addr.into());
+            }
+
+            rx
         };
 
         let tx = {
@@ -261,7 +365,7 @@ impl Io {
 
                 // spawn a task that actually flushes the ring buffer to the socket
                 if idx + 1 == tx_socket_count {
-                    handle.spawn(task::tx(
+                    runtime.spawn(task::tx(
                         tx_socket,
                         consumer,
                         gso.clone(),
@@ -271,7 +375,7 @@ impl Io {
                     break;
                 } else {
                     let tx_socket = tx_socket.try_clone()?;
-                    handle.spawn(task::tx(
+                    runtime.spawn(task::tx(
                         tx_socket,
                         consumer,
                         gso.clone(),
@@ -282,13 +386,30 @@ impl Io {
             }
 
             // construct the TX side for the endpoint event loop
-            socket::io::tx::Tx::new(producers, gso, mtu_config.max_mtu())
+            let mut tx = socket::io::tx::Tx::new(producers, gso.clone(), mtu_config.max_mtu())
+                .with_dscp(dscp.unwrap_or(0));
+
+            // packets are routed to the additional sockets based on the local address of the path
+            for (additional_socket, addr) in additional_sockets {
+                let (producer, consumer) = socket::ring::pair(entries, payload_len);
+                runtime.spawn(task::tx(
+                    additional_socket,
+                    consumer,
+                    gso.clone(),
+                    cooldown("TX"),
+                    stats_sender.clone(),
+                ));
+
+                tx = tx.with_route(addr.into(), vec![producer]);
+            }
+
+            tx
         };
 
         // Notify the endpoint of the MTU that we chose
         endpoint.set_mtu_config(mtu_config);
 
-        let task = handle.spawn(
+        let task = runtime.spawn_joined(
             EventLoop {
                 endpoint,
                 clock,
@@ -296,6 +417,7 @@ impl Io {
                 tx,
                 cooldown: cooldown("ENDPOINT"),
                 stats: stats_recv,
+                profiler: Profiler::new(event_loop_profile_interval),
             }
             .start(rx_addr.into()),
         );
@@ -311,6 +433,33 @@ fn convert_addr_to_std(addr: socket2::SockAddr) -> io::Result<std::net::SocketAd
         .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "invalid domain for socket"))
 }
 
+/// Reduces the number of GSO segments so each transmission fits in the max GSO payload size
+fn limit_max_segments(
+    max_segments: gso::MaxSegments,
+    max_mtu: MaxMtu,
+    max_gso_payload_size: Option<u16>,
+) -> io::Result<gso::MaxSegments> {
+    let Some(max_gso_payload_size) = max_gso_payload_size else {
+        return Ok(max_segments);
+    };
+
+    let max_mtu: u16 = max_mtu.into();
+
+    if max_gso_payload_size < max_mtu {
+        return Err(io::Error::new(
+            ErrorKind::InvalidInput,
+            format!("max_gso_payload_size ({max_gso_payload_size}) must be >= max_mtu ({max_mtu})"),
+        ));
+    }
+
+    let segments = (max_gso_payload_size / max_mtu) as usize;
+
+    Ok(usize::from(max_segments)
+        .min(segments)
+        .try_into()
+        .expect("the segment count is at least 1 and no larger than the original value"))
+}
+
 fn parse_env<T: core::str::FromStr>(name: &str) -> Option<T> {
     std::env::var(name).ok().and_then(|v| v.parse().ok())
 }
@@ -6,19 +6,26 @@ use super::*;
 #[derive(Debug, Default)]
 pub struct Builder {
     pub(super) handle: Option<Handle>,
+    pub(super) spawner: Option<spawner::Custom>,
     pub(super) rx_socket: Option<socket2::Socket>,
     pub(super) tx_socket: Option<socket2::Socket>,
     pub(super) recv_addr: Option<std::net::SocketAddr>,
     pub(super) send_addr: Option<std::net::SocketAddr>,
+    pub(super) additional_addrs: Vec<std::net::SocketAddr>,
     pub(super) socket_recv_buffer_size: Option<usize>,
     pub(super) socket_send_buffer_size: Option<usize>,
     pub(super) queue_recv_buffer_size: Option<u32>,
     pub(super) queue_send_buffer_size: Option<u32>,
     pub(super) mtu_config_builder: mtu::Builder,
     pub(super) max_segments: gso::MaxSegments,
+    pub(super) max_gso_payload_size: Option<u16>,
     pub(super) gro_enabled: Option<bool>,
     pub(super) reuse_address: bool,
     pub(super) reuse_port: bool,
+    pub(super) dscp: Option<u8>,
+    pub(super) bind_device: Option<String>,
+    pub(super) dont_fragment: Option<bool>,
+    pub(super) event_loop_profile_interval: Option<Duration>,
 }
 
 impl Builder {
@@ -28,6 +35,17 @@ impl Builder {
         self
     }
 
+    /// Sets the spawner used to run the socket and endpoint tasks
+    ///
+    /// By default, tasks are spawned on the tokio runtime handle. A tokio runtime is still
+    /// required to drive socket readiness and timers, but the tasks themselves will be
+    /// polled by the provided spawner.
+    #[must_use]
+    pub fn with_spawner<S: Spawner>(mut self, spawner: S) -> Self {
+        self.spawner = Some(spawner::Custom(std::sync::Arc::new(spawner)));
+        self
+    }
+
     /// Sets the local address for the runtime to listen on. If no send address
     /// or tx socket is specified, this address will also be used for transmitting from.
     ///
@@ -48,6 +66,19 @@ impl Builder {
         Ok(self)
     }
 
+    /// Binds an additional socket to the provided address, which is managed by the same endpoint
+    ///
+    /// This allows a single endpoint to accept connections on several addresses, such as an IPv4
+    /// and an IPv6 address, or the addresses of several network interfaces. Packets for each
+    /// connection are sent from the socket bound to the local address the connection is using.
+    ///
+    /// When additional addresses are configured, IPv6 sockets bound by the runtime only accept
+    /// IPv6 traffic so they can share a port with IPv4 sockets.
+    pub fn with_additional_address(mut self, addr: std::net::SocketAddr) -> io::Result<Self> {
+        self.additional_addrs.push(addr);
+        Ok(self)
+    }
+
     /// Sets the socket used for receiving for the runtime. If no tx_socket or send address is
     /// specified, this socket will be used for transmitting.
     ///
@@ -197,6 +228,48 @@ impl Builder {
         }
     }
 
+    /// Sets the maximum number of segments sent in a single Generic Segmentation Offload (GSO)
+    /// transmission (default: 10)
+    ///
+    /// Each GSO transmission is sent by the network interface as a burst of packets. Some networks
+    /// drop large bursts, in which case lowering the number of segments reduces the size of each
+    /// burst without disabling GSO entirely. Values larger than the platform supports are capped
+    /// to the platform maximum and setting the value to 1 disables GSO.
+    pub fn with_max_gso_segments(mut self, max_segments: usize) -> io::Result<Self> {
+        let max_segments = max_segments.min(gso::MaxSegments::MAX.into());
+        self.max_segments = max_segments
+            .try_into()
+            .map_err(|err| io::Error::new(ErrorKind::InvalidInput, format!("{err}")))?;
+        Ok(self)
+    }
+
+    /// Sets the largest payload passed to the socket in a single GSO transmission (default: 65535)
+    ///
+    /// With Generic Segmentation Offload (GSO), a single transmission contains multiple segments,
+    /// each of which is sent as a separate UDP datagram of up to `max_mtu` bytes. The number of
+    /// segments is reduced to `max_gso_payload_size / max_mtu` so that each transmission fits in
+    /// the configured payload size, which limits the size of the bursts sent by the network
+    /// interface. Setting the value to a multiple of `max_mtu` avoids wasting the remainder.
+    ///
+    /// This doesn't change the size of the individual datagrams, which is configured with
+    /// [`Self::with_max_mtu`], nor the `max_udp_payload_size` transport parameter advertised to
+    /// the peer, which is configured with the connection limits.
+    ///
+    /// Note: `max_gso_payload_size` must be >= `max_mtu`, otherwise building the IO provider fails
+    pub fn with_max_gso_payload_size(mut self, max_gso_payload_size: u16) -> io::Result<Self> {
+        if max_gso_payload_size < MaxMtu::MIN.into() {
+            return Err(io::Error::new(
+                ErrorKind::InvalidInput,
+                format!(
+                    "max_gso_payload_size must be at least {}",
+                    u16::from(MaxMtu::MIN)
+                ),
+            ));
+        }
+        self.max_gso_payload_size = Some(max_gso_payload_size);
+        Ok(self)
+    }
+
     /// Disables Generic Receive Offload (GRO)
     ///
     /// By default, GRO will be used unless the platform does not support it. If it is known that
@@ -236,6 +309,69 @@ impl Builder {
         Ok(self)
     }
 
+    /// Marks all transmitted packets with the given Differentiated Services Code Point (DSCP)
+    ///
+    /// The DSCP is set in the upper 6 bits of the IPv4 TOS and IPv6 Traffic Class fields and is
+    /// preserved on packets that carry ECN markings.
+    ///
+    /// Note: `dscp` must be <= 63
+    pub fn with_dscp(mut self, dscp: u8) -> io::Result<Self> {
+        if dscp > 0b11_1111 {
+            return Err(io::Error::new(
+                ErrorKind::InvalidInput,
+                format!("dscp ({dscp}) must be <= 63"),
+            ));
+        }
+        self.dscp = Some(dscp);
+        Ok(self)
+    }
+
+    /// Binds the sockets to the network device with the given name (SO_BINDTODEVICE)
+    ///
+    /// Packets will only be received from and transmitted on the given device, regardless of the
+    /// routing table. This option is only supported on Linux.
+    pub fn with_bind_device(mut self, device: &str) -> io::Result<Self> {
+        if !cfg!(any(target_os = "android", target_os = "linux")) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "bind_device is not supported on the current platform",
+            ));
+        }
+        if device.is_empty() || device.len() >= 16 || device.contains('\0') {
+            return Err(io::Error::new(
+                ErrorKind::InvalidInput,
+                format!("invalid device name: {device:?}"),
+            ));
+        }
+        self.bind_device = Some(device.to_string());
+        Ok(self)
+    }
+
+    /// Configures the Don't Fragment (DF) behavior of transmitted packets (default: true)
+    ///
+    /// By default, packets are sent with fragmentation disabled, as required by QUIC, which also
+    /// allows s2n-quic to probe the path for larger MTUs. Allowing the IP layer to fragment
+    /// packets disables MTU probing, limiting the MTU to the minimum configuration.
+    pub fn with_dont_fragment(mut self, enabled: bool) -> io::Result<Self> {
+        self.dont_fragment = Some(enabled);
+        Ok(self)
+    }
+
+    /// Publishes the time spent in each phase of the endpoint event loop every `interval`
+    ///
+    /// The profile is published with the `PlatformEventLoopProfile` event. Profiling is disabled
+    /// by default.
+    pub fn with_event_loop_profiling(mut self, interval: Duration) -> io::Result<Self> {
+        if interval.is_zero() {
+            return Err(io::Error::new(
+                ErrorKind::InvalidInput,
+                "event loop profiling interval must be greater than zero",
+            ));
+        }
+        self.event_loop_profile_interval = Some(interval);
+        Ok(self)
+    }
+
     pub fn build(self) -> io::Result<Io> {
         Ok(Io { builder: self })
     }
@@ -0,0 +1,112 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use core::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use std::sync::Arc;
+use tokio::runtime::Handle;
+
+pub use crate::io::spawner::{Spawner, Task};
+
+/// Spawns tasks directly on the tokio runtime
+///
+/// The socket and endpoint tasks are always registered with the tokio reactor and timers of
+/// the configured runtime handle, which means that runtime must be driven for the tasks to
+/// make progress. A custom spawner only controls where the tasks are polled.
+impl Spawner for Handle {
+    #[inline]
+    fn spawn(&self, task: Task) {
+        Handle::spawn(self, task);
+    }
+}
+
+#[derive(Clone)]
+pub(super) struct Custom(pub(super) Arc<dyn Spawner>);
+
+impl fmt::Debug for Custom {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Spawner").finish()
+    }
+}
+
+/// Spawns tasks on either the tokio runtime or the configured custom spawner
+pub(super) struct Runtime {
+    handle: Handle,
+    spawner: Option<Custom>,
+}
+
+impl Runtime {
+    #[inline]
+    pub(super) fn new(handle: Handle, spawner: Option<Custom>) -> Self {
+        Self { handle, spawner }
+    }
+
+    #[inline]
+    pub(super) fn handle(&self) -> &Handle {
+        &self.handle
+    }
+
+    /// Spawns a task that is not joined
+    pub(super) fn spawn<F>(&self, task: F)
+    where
+        F: 'static + Send + Future,
+        F::Output: Send,
+    {
+        if let Some(spawner) = self.spawner.as_ref() {
+            let task = Entered {
+                handle: self.handle.clone(),
+                task: Box::pin(task),
+            };
+            spawner.0.spawn(Box::pin(async move {
+                let _ = task.await;
+            }));
+        } else {
+            self.handle.spawn(task);
+        }
+    }
+
+    /// Spawns a task and returns a tokio `JoinHandle` which completes along with it
+    pub(super) fn spawn_joined<F>(&self, task: F) -> tokio::task::JoinHandle<()>
+    where
+        F: 'static + Send + Future<Output = ()>,
+    {
+        if self.spawner.is_none() {
+            return self.handle.spawn(task);
+        }
+
+        // bridge the completion of the custom task back to the tokio runtime so callers
+        // can join the endpoint regardless of where it is polled
+        let (sender, receiver) = tokio::sync::oneshot::channel::<()>();
+        self.spawn(async move {
+            task.await;
+            let _ = sender.send(());
+        });
+        self.handle.spawn(async move {
+            let _ = receiver.await;
+        })
+    }
+}
+
+/// Enters the tokio runtime context on every poll
+///
+/// Tasks polled by a custom spawner may run on threads that are not owned by the tokio
+/// runtime, so the context needs to be entered for socket registration and timers.
+struct Entered<F> {
+    handle: Handle,
+    task: Pin<Box<F>>,
+}
+
+impl<F: Future> Future for Entered<F> {
+    type Output = F::Output;
+
+    #[inline]
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let _guard = this.handle.enter();
+        this.task.as_mut().poll(cx)
+    }
+}
@@ -260,3 +260,146 @@ async fn ipv6_two_socket_test() -> io::Result<()> {
         other => other,
     }
 }
+
+/// Ensures an endpoint can receive and transmit on an additional address
+#[tokio::test]
+#[cfg_attr(miri, ignore)]
+async fn additional_address_test() -> io::Result<()> {
+    // bind the IPv6 address to the same port as the IPv4 address
+    let rx_socket = std::net::UdpSocket::bind(IPV4_LOCALHOST)?;
+    rx_socket.set_nonblocking(true)?;
+    let port = rx_socket.local_addr()?.port();
+    let server_addr = ("::1", port).to_socket_addrs()?.next().unwrap();
+
+    let result = async {
+        let server_io = Io::builder()
+            .with_rx_socket(rx_socket)?
+            .with_additional_address(server_addr)?
+            .build()?;
+        let (client_io, client_addr) = runtime(IPV6_LOCALHOST, None).await?;
+        let server_addr: SocketAddress = server_addr.into();
+
+        let server_endpoint = {
+            let mut handle = PathHandle::from_remote_address(client_addr.into());
+            handle.local_address = server_addr.into();
+            TestEndpoint::<true>::new(handle)
+        };
+
+        let client_endpoint = {
+            let mut handle = PathHandle::from_remote_address(server_addr.into());
+            handle.local_address = client_addr.into();
+            TestEndpoint::<false>::new(handle)
+        };
+
+        let (server_task, _) = server_io.start(server_endpoint)?;
+        let (client_task, _) = client_io.start(client_endpoint)?;
+
+        tokio::time::timeout(core::time::Duration::from_secs(60), client_task).await??;
+
+        server_task.abort();
+
+        Ok(())
+    }
+    .await;
+
+    match result {
+        Err(err) if err.kind() == io::ErrorKind::AddrNotAvailable => {
+            eprintln!("The current environment does not support IPv6; skipping");
+            Ok(())
+        }
+        other => other,
+    }
+}
+
+#[tokio::test]
+#[cfg_attr(miri, ignore)]
+async fn spawner_test() -> io::Result<()> {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    let spawned = Arc::new(AtomicUsize::new(0));
+
+    // poll each task on its own thread, outside of the tokio runtime
+    let spawner = {
+        let spawned = spawned.clone();
+        move |task: Task| {
+            spawned.fetch_add(1, Ordering::Relaxed);
+            std::thread::spawn(move || futures::executor::block_on(task));
+        }
+    };
+
+    let (server_io, server_addr) = runtime(IPV4_LOCALHOST, None).await?;
+    let (client_io, client_addr) = runtime(IPV4_LOCALHOST, None).await?;
+
+    let client_io = Io {
+        builder: client_io.builder.with_spawner(spawner),
+    };
+
+    let server_endpoint = {
+        let mut handle = PathHandle::from_remote_address(client_addr.into());
+        handle.local_address = server_addr.into();
+        TestEndpoint::<true>::new(handle)
+    };
+
+    let client_endpoint = {
+        let mut handle = PathHandle::from_remote_address(server_addr.into());
+        handle.local_address = client_addr.into();
+        TestEndpoint::<false>::new(handle)
+    };
+
+    let (server_task, _) = server_io.start(server_endpoint)?;
+    let (client_task, _) = client_io.start(client_endpoint)?;
+
+    tokio::time::timeout(core::time::Duration::from_secs(60), client_task).await??;
+
+    server_task.abort();
+
+    // the rx, tx, and endpoint tasks should all go through the spawner
+    assert!(spawned.load(Ordering::Relaxed) >= 3);
+
+    Ok(())
+}
+
+#[test]
+fn limit_max_segments_test() {
+    let max_segments = gso::MaxSegments::MAX;
+    let max_mtu = MaxMtu::try_from(1500).unwrap();
+
+    // the segment count isn't changed without a max payload size
+    let segments = limit_max_segments(max_segments, max_mtu, None).unwrap();
+    assert_eq!(usize::from(segments), usize::from(max_segments));
+
+    // a single segment always fits
+    let segments = limit_max_segments(max_segments, max_mtu, Some(1500)).unwrap();
+    assert_eq!(usize::from(segments), 1);
+
+    let segments = limit_max_segments(max_segments, max_mtu, Some(4000)).unwrap();
+    assert_eq!(usize::from(segments), 2.min(usize::from(max_segments)));
+
+    // the payload size can't be smaller than a single packet
+    assert!(limit_max_segments(max_segments, max_mtu, Some(1499)).is_err());
+}
+
+#[test]
+fn builder_validation_test() {
+    assert!(Io::builder().with_max_gso_segments(0).is_err());
+    assert!(Io::builder().with_max_gso_segments(usize::MAX).is_ok());
+
+    assert!(Io::builder().with_max_gso_payload_size(1000).is_err());
+    assert!(Io::builder().with_max_gso_payload_size(1500).is_ok());
+
+    assert!(Io::builder().with_dscp(46).is_ok());
+    assert!(Io::builder().with_dscp(63).is_ok());
+    assert!(Io::builder().with_dscp(64).is_err());
+
+    assert!(Io::builder().with_bind_device("").is_err());
+    assert!(Io::builder()
+        .with_bind_device("a-very-long-device-name")
+        .is_err());
+    assert_eq!(
+        Io::builder().with_bind_device("lo").is_ok(),
+        cfg!(any(target_os = "android", target_os = "linux"))
+    );
+}
@@ -18,15 +18,17 @@ use s2n_quic_core::{
     io::event_loop::{select::Select, EventLoop},
     path::{self, mtu},
 };
-use std::{io, io::ErrorKind};
+use std::{io, io::ErrorKind, sync::Arc};
 use tokio::runtime::Handle;
 use turmoil::net::UdpSocket;
 
 mod builder;
+mod network;
 #[cfg(test)]
 mod tests;
 
 pub use builder::Builder;
+pub use network::{Link, Network};
 pub type PathHandle = path::Tuple;
 
 #[derive(Default)]
@@ -53,6 +55,7 @@ impl Io {
             socket,
             addr,
             mtu_config_builder,
+            network,
         } = self.builder;
 
         let mtu_config = mtu_config_builder
@@ -115,7 +118,13 @@ impl Io {
 
         // Spawn a task that does the actual socket calls and coordinates with the event loop
         // through the ring buffers
-        tokio::spawn(run_io(socket, rx_producer, tx_consumer, stats_sender));
+        tokio::spawn(run_io(
+            socket,
+            rx_producer,
+            tx_consumer,
+            stats_sender,
+            network,
+        ));
 
         let event_loop = EventLoop {
             clock,
@@ -124,6 +133,7 @@ impl Io {
             endpoint,
             cooldown: Default::default(),
             stats: stats_recv,
+            profiler: Default::default(),
         }
         .start(local_addr);
 
@@ -164,8 +174,12 @@ async fn run_io(
     mut producer: Producer<Message>,
     mut consumer: Consumer<Message>,
     stats: stats::Sender,
+    network: Option<Network>,
 ) -> io::Result<()> {
     let mut poll_producer = false;
+    let socket = Arc::new(socket);
+    // turmoil assigns the host's address to sockets bound to an unspecified address
+    let local_ip = socket.local_addr()?.ip();
 
     loop {
         let socket_ready = socket.readable();
@@ -227,8 +241,33 @@ async fn run_io(
                 let addr = *entry.remote_address();
                 let addr: std::net::SocketAddr = addr.into();
                 let payload = entry.payload_mut();
+
+                if let Some(network) = network.as_ref() {
+                    match network.outcome(local_ip, addr.ip()) {
+                        network::Outcome::Send => {}
+                        network::Outcome::Drop => {
+                            // the datagram was "sent" but lost on the link
+                            stats.send().on_operation_ready(1);
+                            count += 1;
+                            continue;
+                        }
+                        network::Outcome::Delay(delay) => {
+                            let payload = payload.to_vec();
+                            let socket = socket.clone();
+                            let stats = stats.clone();
+                            tokio::spawn(async move {
+                                tokio::time::sleep(delay).await;
+                                let res = socket.send_to(&payload, addr).await;
+                                stats.send().on_operation_result(&res, |_len| 1);
+                            });
+                            count += 1;
+                            continue;
+                        }
+                    }
+                }
+
                 let res = socket.try_send_to(payload, addr);
-                stats.recv().on_operation_result(&res, |_len| 1);
+                stats.send().on_operation_result(&res, |_len| 1);
                 // Since UDP sockets are stateless, the only errors we should back is a WouldBlock.
                 // If we get any errors, we'll try again later.
                 if res.is_ok() {
@@ -9,6 +9,7 @@ pub struct Builder {
     pub(super) socket: Option<UdpSocket>,
     pub(super) addr: Option<Box<dyn turmoil::ToSocketAddrs + Send + Sync + 'static>>,
     pub(super) mtu_config_builder: mtu::Builder,
+    pub(super) network: Option<Network>,
 }
 
 impl Builder {
@@ -48,6 +49,15 @@ impl Builder {
         Ok(self)
     }
 
+    /// Sets the network model used to apply latency, jitter and loss to outgoing datagrams
+    ///
+    /// The same [`Network`] can be shared between hosts and updated while the simulation runs.
+    #[must_use]
+    pub fn with_network(mut self, network: Network) -> Self {
+        self.network = Some(network);
+        self
+    }
+
     pub fn build(self) -> io::Result<Io> {
         Ok(Io { builder: self })
     }
@@ -0,0 +1,188 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use core::time::Duration;
+use std::{
+    collections::HashMap,
+    net::IpAddr,
+    sync::{Arc, Mutex},
+};
+
+/// The conditions applied to datagrams sent from one host to another
+///
+/// Links are applied on top of any conditions configured on the turmoil simulation itself.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Link {
+    latency: Duration,
+    jitter: Duration,
+    loss: f64,
+}
+
+impl Link {
+    /// Creates a link with no added latency, jitter or loss
+    pub const fn new() -> Self {
+        Self {
+            latency: Duration::ZERO,
+            jitter: Duration::ZERO,
+            loss: 0.0,
+        }
+    }
+
+    /// Sets the fixed amount of time each datagram is delayed before being sent
+    #[must_use]
+    pub const fn with_latency(mut self, latency: Duration) -> Self {
+        self.latency = latency;
+        self
+    }
+
+    /// Sets the maximum amount of additional, uniformly distributed, delay for each datagram
+    ///
+    /// Since each datagram is delayed independently, non-zero jitter can reorder datagrams.
+    #[must_use]
+    pub const fn with_jitter(mut self, jitter: Duration) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Sets the probability, between `0.0` and `1.0`, that a datagram is dropped
+    #[must_use]
+    pub fn with_loss(mut self, loss: f64) -> Self {
+        self.loss = loss.clamp(0.0, 1.0);
+        self
+    }
+
+    #[inline]
+    pub fn latency(&self) -> Duration {
+        self.latency
+    }
+
+    #[inline]
+    pub fn jitter(&self) -> Duration {
+        self.jitter
+    }
+
+    #[inline]
+    pub fn loss(&self) -> f64 {
+        self.loss
+    }
+}
+
+/// What should happen to an outgoing datagram
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Outcome {
+    Send,
+    Delay(Duration),
+    Drop,
+}
+
+/// A programmable model of the links between hosts in a simulation
+///
+/// The model is shared between all of the IO providers which are configured with it, so links
+/// can be changed while the simulation is running. Decisions are made with a seeded generator,
+/// which keeps simulations deterministic.
+#[derive(Clone, Debug)]
+pub struct Network {
+    state: Arc<Mutex<State>>,
+}
+
+impl Default for Network {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl Network {
+    /// Creates a network where every link is initially unimpaired
+    pub fn new(seed: u64) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(State {
+                default: Link::new(),
+                links: HashMap::new(),
+                // xorshift requires a non-zero state
+                rng: seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1,
+            })),
+        }
+    }
+
+    /// Sets the link used for host pairs without a specific link configured
+    #[must_use]
+    pub fn with_default_link(self, link: Link) -> Self {
+        self.state.lock().unwrap().default = link;
+        self
+    }
+
+    /// Sets the link in both directions between `a` and `b`
+    pub fn set_link<A: turmoil::ToIpAddr, B: turmoil::ToIpAddr>(&self, a: A, b: B, link: Link) {
+        let a = turmoil::lookup(a);
+        let b = turmoil::lookup(b);
+        let mut state = self.state.lock().unwrap();
+        state.links.insert((a, b), link);
+        state.links.insert((b, a), link);
+    }
+
+    /// Sets the link for datagrams sent from `from` to `to`
+    pub fn set_directed_link<A: turmoil::ToIpAddr, B: turmoil::ToIpAddr>(
+        &self,
+        from: A,
+        to: B,
+        link: Link,
+    ) {
+        let from = turmoil::lookup(from);
+        let to = turmoil::lookup(to);
+        self.state.lock().unwrap().links.insert((from, to), link);
+    }
+
+    /// Removes any links configured between `a` and `b`, in both directions
+    pub fn clear_link<A: turmoil::ToIpAddr, B: turmoil::ToIpAddr>(&self, a: A, b: B) {
+        let a = turmoil::lookup(a);
+        let b = turmoil::lookup(b);
+        let mut state = self.state.lock().unwrap();
+        state.links.remove(&(a, b));
+        state.links.remove(&(b, a));
+    }
+
+    pub(crate) fn outcome(&self, from: IpAddr, to: IpAddr) -> Outcome {
+        let mut state = self.state.lock().unwrap();
+        let link = state
+            .links
+            .get(&(from, to))
+            .copied()
+            .unwrap_or(state.default);
+
+        if link.loss > 0.0 && state.gen_f64() < link.loss {
+            return Outcome::Drop;
+        }
+
+        let mut delay = link.latency;
+        if !link.jitter.is_zero() {
+            let jitter = link.jitter.as_nanos() as f64 * state.gen_f64();
+            delay += Duration::from_nanos(jitter as u64);
+        }
+
+        if delay.is_zero() {
+            Outcome::Send
+        } else {
+            Outcome::Delay(delay)
+        }
+    }
+}
+
+#[derive(Debug)]
+struct State {
+    default: Link,
+    links: HashMap<(IpAddr, IpAddr), Link>,
+    rng: u64,
+}
+
+impl State {
+    /// Returns a value in the range `[0.0, 1.0)`
+    fn gen_f64(&mut self) -> f64 {
+        // xorshift64
+        let mut x = self.rng;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        self.rng = x;
+        (x >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
@@ -161,3 +161,73 @@ fn sim_test() -> io::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn shaped_network_test() -> io::Result<()> {
+    use turmoil::lookup;
+
+    let mut sim = turmoil::Builder::new().build();
+
+    let network = Network::new(123);
+
+    sim.client("client", {
+        let network = network.clone();
+        async move {
+            network.set_link(
+                "client",
+                "server",
+                Link::new()
+                    .with_latency(Duration::from_millis(20))
+                    .with_jitter(Duration::from_millis(5))
+                    .with_loss(0.25),
+            );
+
+            let io = Io::builder()
+                .with_address(bind(123))?
+                .with_network(network)
+                .build()?;
+
+            let endpoint = TestEndpoint::new((lookup("server"), 456).into());
+
+            let (task, _) = io.start(endpoint)?;
+
+            task.await?;
+
+            Ok(())
+        }
+    });
+
+    sim.client("server", async move {
+        let io = Io::builder()
+            .with_address(bind(456))?
+            .with_network(network)
+            .build()?;
+
+        let endpoint = TestEndpoint::new((lookup("client"), 123).into());
+
+        let (task, _) = io.start(endpoint)?;
+
+        task.await?;
+
+        Ok(())
+    });
+
+    sim.run().unwrap();
+
+    Ok(())
+}
+
+#[test]
+fn link_loss_test() {
+    let network = Network::new(1).with_default_link(Link::new().with_loss(0.5));
+    let total = 10_000;
+
+    let a = std::net::Ipv4Addr::new(192, 168, 0, 1).into();
+    let b = std::net::Ipv4Addr::new(192, 168, 0, 2).into();
+
+    let dropped = (0..total)
+        .filter(|_| network.outcome(a, b) == network::Outcome::Drop)
+        .count();
+
+    assert!((4_000..6_000).contains(&dropped), "dropped {dropped}");
+}
@@ -166,6 +166,7 @@ where
             tx,
             cooldown: crate::io::tokio::cooldown("ENDPOINT"),
             stats,
+            profiler: Default::default(),
         };
 
         let local_addr = SocketAddress::default();
@@ -14,6 +14,7 @@ pub mod features;
 pub mod io;
 pub mod message;
 pub mod socket;
+#[cfg(not(target_family = "wasm"))]
 pub mod syscall;
 #[doc(hidden)] // TODO remove this module: https://github.com/aws/s2n-quic/issues/1738
 pub mod time;
@@ -129,6 +129,19 @@ pub trait Message: 'static + Copy {
         &mut self,
         message: M,
     ) -> Result<usize, tx::Error>;
+
+    /// Writes the message into the TX packet, marked with the given DSCP
+    ///
+    /// Messages which don't encode per-packet TOS values rely on the marking of the socket.
+    #[inline]
+    fn tx_write_with_dscp<M: tx::Message<Handle = Self::Handle>>(
+        &mut self,
+        message: M,
+        dscp: u8,
+    ) -> Result<usize, tx::Error> {
+        let _ = dscp;
+        self.tx_write(message)
+    }
 }
 
 pub struct RxMessage<'a, Handle: Copy> {
@@ -26,21 +26,45 @@ pub trait Encoder {
         ecn: ExplicitCongestionNotification,
         remote_address: &SocketAddress,
     ) -> Result<usize, Error> {
-        // no need to encode for the default case
+        self.encode_tos(0, ecn, remote_address)
+    }
+
+    /// Encodes the DSCP and ECN markings into the cmsg encoder
+    ///
+    /// The per-packet TOS value replaces the value configured on the socket so the DSCP needs to
+    /// be included with the ECN markings in order to be preserved.
+    #[inline]
+    fn encode_tos(
+        &mut self,
+        dscp: u8,
+        ecn: ExplicitCongestionNotification,
+        remote_address: &SocketAddress,
+    ) -> Result<usize, Error> {
+        // no need to encode for the default case, since the socket marks the packet with the DSCP
         if ecn == ExplicitCongestionNotification::NotEct {
             return Ok(0);
         }
 
+        let tos = ((dscp & 0b11_1111) << 2) | ecn as u8;
+
         // the remote address needs to be unmapped in order to set the appropriate cmsg
-        match remote_address.unmap() {
+        //
+        // Apple platforms reject `IP_TOS` on dual-stack sockets, even when the peer is an
+        // IPv4-mapped address, so the address family of the socket is used instead.
+        #[cfg(any(target_os = "macos", target_os = "ios"))]
+        let remote_address = *remote_address;
+        #[cfg(not(any(target_os = "macos", target_os = "ios")))]
+        let remote_address = remote_address.unmap();
+
+        match remote_address {
             SocketAddress::IpV4(_) => {
                 if let (Some(level), Some(ty)) = (features::tos_v4::LEVEL, features::tos_v4::TYPE) {
-                    return self.encode_cmsg(level, ty, ecn as u8 as features::tos_v4::Cmsg);
+                    return self.encode_cmsg(level, ty, tos as features::tos_v4::Cmsg);
                 }
             }
             SocketAddress::IpV6(_) => {
                 if let (Some(level), Some(ty)) = (features::tos_v6::LEVEL, features::tos_v6::TYPE) {
-                    return self.encode_cmsg(level, ty, ecn as u8 as features::tos_v6::Cmsg);
+                    return self.encode_cmsg(level, ty, tos as features::tos_v6::Cmsg);
                 }
             }
         }
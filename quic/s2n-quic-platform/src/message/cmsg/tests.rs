@@ -121,3 +121,38 @@ type Ops = s2n_quic_core::testing::InlineVec<Op, 8>;
 fn round_trip_test() {
     check!().with_type::<Ops>().for_each(|ops| round_trip(ops));
 }
+
+/// Ensures ECN markings for IPv4-mapped peers use the control message accepted by the platform
+#[test]
+#[cfg(s2n_quic_platform_tos)]
+fn ecn_mapped_address_test() {
+    use crate::features::{tos_v4, tos_v6};
+    use s2n_quic_core::inet::{ExplicitCongestionNotification, SocketAddress, SocketAddressV4};
+
+    let ipv4 = SocketAddress::IpV4(SocketAddressV4::new([127, 0, 0, 1], 4433));
+    let mapped = SocketAddress::IpV6(ipv4.to_ipv6_mapped());
+
+    let v4 = (tos_v4::LEVEL, tos_v4::TYPE);
+    let v6 = (tos_v6::LEVEL, tos_v6::TYPE);
+
+    // Apple platforms reject `IP_TOS` on dual-stack sockets so the mapped address isn't unmapped
+    let expected_mapped = if cfg!(any(target_os = "macos", target_os = "ios")) {
+        v6
+    } else {
+        v4
+    };
+
+    for (address, (level, ty)) in [(ipv4, v4), (mapped, expected_mapped)] {
+        let mut storage = Storage::<32>::default();
+        let mut encoder = storage.encoder();
+        encoder
+            .encode_ecn(ExplicitCongestionNotification::Ect0, &address)
+            .unwrap();
+
+        let mut iter = encoder.iter();
+        let (cmsghdr, _value) = iter.next().unwrap();
+        assert_eq!(Some(cmsghdr.cmsg_level), level, "{address:?}");
+        assert_eq!(Some(cmsghdr.cmsg_type), ty, "{address:?}");
+        assert!(iter.next().is_none());
+    }
+}
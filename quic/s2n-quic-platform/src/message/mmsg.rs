@@ -78,7 +78,16 @@ impl MessageTrait for mmsghdr {
         &mut self,
         message: M,
     ) -> Result<usize, tx::Error> {
-        let len = self.msg_hdr.tx_write(message)?;
+        self.tx_write_with_dscp(message, 0)
+    }
+
+    #[inline]
+    fn tx_write_with_dscp<M: tx::Message<Handle = Self::Handle>>(
+        &mut self,
+        message: M,
+        dscp: u8,
+    ) -> Result<usize, tx::Error> {
+        let len = self.msg_hdr.tx_write_with_dscp(message, dscp)?;
         // We need to replicate the len with the `msg_len` field after delegating to `msg_hdr`
         debug_assert!(len <= u16::MAX as usize);
         self.msg_len = len as _;
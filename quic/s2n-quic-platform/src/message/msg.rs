@@ -165,8 +165,17 @@ impl MessageTrait for msghdr {
 
     #[inline]
     fn tx_write<M: tx::Message<Handle = Self::Handle>>(
+        &mut self,
+        message: M,
+    ) -> Result<usize, tx::Error> {
+        self.tx_write_with_dscp(message, 0)
+    }
+
+    #[inline]
+    fn tx_write_with_dscp<M: tx::Message<Handle = Self::Handle>>(
         &mut self,
         mut message: M,
+        dscp: u8,
     ) -> Result<usize, tx::Error> {
         let payload = self.payload_mut();
 
@@ -191,7 +200,7 @@ impl MessageTrait for msghdr {
         let handle = *message.path_handle();
         handle.update_msg_hdr(self);
         self.cmsg_encoder()
-            .encode_ecn(message.ecn(), &handle.remote_address.0)
+            .encode_tos(dscp, message.ecn(), &handle.remote_address.0)
             .unwrap();
 
         Ok(len)
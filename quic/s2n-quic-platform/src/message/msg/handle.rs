@@ -5,7 +5,7 @@ use super::ext::Ext as _;
 use crate::{features, message::cmsg::Encoder};
 use libc::msghdr;
 use s2n_quic_core::{
-    inet::{AncillaryData, SocketAddressV4},
+    inet::{AncillaryData, SocketAddressV4, Unspecified as _},
     path::{self, LocalAddress, RemoteAddress},
 };
 
@@ -89,6 +89,14 @@ impl path::Handle for Handle {
         // once we discover our path, or the port changes, update the address with the new information
         if self.local_address.port() != other.local_address.port() {
             self.local_address = other.local_address;
+            return;
+        }
+
+        // the IP can change without the port if the local socket is rebound
+        if !other.local_address.ip().is_unspecified()
+            && self.local_address.unmap() != other.local_address.unmap()
+        {
+            self.local_address = other.local_address;
         }
     }
 }
@@ -2,7 +2,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod io;
+#[cfg(not(target_family = "wasm"))]
 pub mod options;
 pub mod ring;
+#[cfg(target_os = "linux")]
+pub mod shard;
 pub mod stats;
+#[cfg(not(target_family = "wasm"))]
 pub mod task;
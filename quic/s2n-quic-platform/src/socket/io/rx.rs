@@ -15,18 +15,33 @@ use s2n_quic_core::{
 pub struct Rx<T: Message> {
     channels: Vec<Consumer<T>>,
     max_mtu: MaxMtu,
-    local_address: LocalAddress,
+    /// The local address of the socket feeding each channel
+    local_addresses: Vec<LocalAddress>,
 }
 
 impl<T: Message> Rx<T> {
     #[inline]
     pub fn new(channels: Vec<Consumer<T>>, max_mtu: MaxMtu, local_address: LocalAddress) -> Self {
+        let local_addresses = vec![local_address; channels.len()];
         Self {
             channels,
             max_mtu,
-            local_address,
+            local_addresses,
         }
     }
+
+    /// Adds channels which are fed by a socket bound to a different local address
+    #[inline]
+    pub fn with_channels(
+        mut self,
+        channels: Vec<Consumer<T>>,
+        local_address: LocalAddress,
+    ) -> Self {
+        self.local_addresses
+            .extend(core::iter::repeat(local_address).take(channels.len()));
+        self.channels.extend(channels);
+        self
+    }
 }
 
 impl<T: Message> rx::Rx for Rx<T> {
@@ -89,7 +104,7 @@ impl<T: Message> rx::Rx for Rx<T> {
         let mut queue = RxQueue {
             channels: &mut this.channels,
             max_mtu: this.max_mtu,
-            local_address: &this.local_address,
+            local_addresses: &this.local_addresses,
         };
 
         f(&mut queue);
@@ -106,7 +121,7 @@ impl<T: Message> rx::Rx for Rx<T> {
 pub struct RxQueue<'a, T: Message> {
     channels: &'a mut [Consumer<T>],
     max_mtu: MaxMtu,
-    local_address: &'a LocalAddress,
+    local_addresses: &'a [LocalAddress],
 }
 
 impl<T: Message> rx::Queue for RxQueue<'_, T> {
@@ -114,7 +129,7 @@ impl<T: Message> rx::Queue for RxQueue<'_, T> {
 
     #[inline]
     fn for_each<F: FnMut(datagram::Header<Self::Handle>, &mut [u8])>(&mut self, mut on_packet: F) {
-        for channel in self.channels.iter_mut() {
+        for (channel, local_address) in self.channels.iter_mut().zip(self.local_addresses) {
             // one last effort to acquire items if some were received since we last polled
             let len = channel.acquire(u32::MAX);
 
@@ -126,7 +141,7 @@ impl<T: Message> rx::Queue for RxQueue<'_, T> {
                 //
                 // NOTE: it's important that we process all of the messages in the queue as the
                 //       channel is completely drained here.
-                if let Some(message) = message.rx_read(self.local_address) {
+                if let Some(message) = message.rx_read(local_address) {
                     message.for_each(&mut on_packet);
                 }
 
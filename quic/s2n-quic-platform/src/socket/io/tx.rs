@@ -2,10 +2,13 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{features::Gso, message::Message, socket::ring::Producer};
-use core::task::{Context, Poll};
+use core::{
+    ops::Range,
+    task::{Context, Poll},
+};
 use s2n_quic_core::{
     event,
-    inet::ExplicitCongestionNotification,
+    inet::{ExplicitCongestionNotification, SocketAddress, Unspecified as _},
     io::tx,
     path::{Handle as _, MaxMtu},
     task::waker,
@@ -14,8 +17,10 @@ use s2n_quic_core::{
 /// Structure for sending messages to producer channels
 pub struct Tx<T: Message> {
     channels: Vec<Producer<T>>,
+    routes: Vec<Route>,
     gso: Gso,
     max_mtu: usize,
+    dscp: u8,
     is_full: bool,
 }
 
@@ -24,11 +29,83 @@ impl<T: Message> Tx<T> {
     pub fn new(channels: Vec<Producer<T>>, gso: Gso, max_mtu: MaxMtu) -> Self {
         Self {
             channels,
+            routes: Vec::new(),
             gso,
             max_mtu: max_mtu.into(),
+            dscp: 0,
             is_full: true,
         }
     }
+
+    /// Marks each message with the given Differentiated Services Code Point (DSCP)
+    ///
+    /// Messages with ECN markings carry the DSCP along with the ECN codepoint, since the
+    /// per-packet value replaces the marking configured on the socket.
+    #[inline]
+    pub fn with_dscp(mut self, dscp: u8) -> Self {
+        self.dscp = dscp;
+        self
+    }
+
+    /// Adds channels which transmit from a socket bound to the given local address
+    ///
+    /// Messages on paths using the local address are sent on these channels so the source
+    /// address of each packet matches the address the peer is using. All other messages are
+    /// sent on the channels passed to [`Tx::new`].
+    #[inline]
+    pub fn with_route(mut self, local_address: SocketAddress, channels: Vec<Producer<T>>) -> Self {
+        let start = self.channels.len();
+        self.channels.extend(channels);
+        let end = self.channels.len();
+        self.routes.push(Route {
+            local_address,
+            channels: start..end,
+        });
+        self
+    }
+}
+
+/// Channels which transmit from a socket bound to a specific local address
+#[derive(Debug)]
+struct Route {
+    local_address: SocketAddress,
+    channels: Range<usize>,
+}
+
+impl Route {
+    /// Returns `true` if messages on the given path should be sent on the route's channels
+    #[inline]
+    fn matches<H: s2n_quic_core::path::Handle>(&self, handle: &H) -> bool {
+        let bound = self.local_address.unmap();
+        let local = handle.local_address().0.unmap();
+
+        if local.ip().is_unspecified() {
+            // The path hasn't been assigned a local address yet, which happens for locally
+            // initiated connections. Use the first socket accepting any address of the same
+            // family as the peer.
+            let remote = handle.remote_address().0.unmap();
+            return bound.ip().is_unspecified() && is_same_family(&bound, &remote);
+        }
+
+        if bound.port() != local.port() {
+            return false;
+        }
+
+        if bound.ip().is_unspecified() {
+            is_same_family(&bound, &local)
+        } else {
+            bound.ip() == local.ip()
+        }
+    }
+}
+
+#[inline]
+fn is_same_family(a: &SocketAddress, b: &SocketAddress) -> bool {
+    matches!(
+        (a, b),
+        (SocketAddress::IpV4(_), SocketAddress::IpV4(_))
+            | (SocketAddress::IpV6(_), SocketAddress::IpV6(_))
+    )
 }
 
 impl<T: Message> tx::Tx for Tx<T> {
@@ -113,9 +190,15 @@ impl<T: Message> tx::Tx for Tx<T> {
         // mark that we're still full so we need to poll and wake up next iteration
         this.is_full = capacity == 0;
 
-        // start with the first queue that has free slots, otherwise set the index to the length,
-        // which will return an AtCapacity error immediately.
-        let channel_index = first_with_free_slots.unwrap_or(this.channels.len());
+        let (channel_index, channel_end) = if this.routes.is_empty() {
+            // start with the first queue that has free slots, otherwise set the index to the
+            // length, which will return an AtCapacity error immediately.
+            let channel_index = first_with_free_slots.unwrap_or(this.channels.len());
+            (channel_index, this.channels.len())
+        } else {
+            // the channels are selected for each message based on its route
+            (this.channels.len(), 0)
+        };
 
         // query the maximum number of segments we can fill at this point in time
         //
@@ -125,12 +208,15 @@ impl<T: Message> tx::Tx for Tx<T> {
 
         let mut queue = TxQueue {
             channels: &mut this.channels,
+            routes: &this.routes,
             channel_index,
+            channel_end,
             message_index: 0,
             pending_release: 0,
             gso_segment: None,
             max_segments,
             max_mtu: this.max_mtu,
+            dscp: this.dscp,
             capacity,
             is_full: &mut this.is_full,
         };
@@ -169,10 +255,14 @@ pub struct GsoSegment<Handle> {
 
 pub struct TxQueue<'a, T: Message> {
     channels: &'a mut [Producer<T>],
+    /// The routes to the channels of sockets bound to specific local addresses, if any
+    routes: &'a [Route],
     /// The channel index that we are currently operating on.
     ///
-    /// This will be incremented after each channel is filled until it exceeds the len of `channels`.
+    /// This will be incremented after each channel is filled until it reaches `channel_end`.
     channel_index: usize,
+    /// The end of the range of channels the current message can be sent on
+    channel_end: usize,
     /// The message index into the current channel that we are operating on.
     ///
     /// This is incremented after each message is finished until it exceeds the acquired free
@@ -189,6 +279,8 @@ pub struct TxQueue<'a, T: Message> {
     max_segments: usize,
     /// The maximum MTU for any given packet
     max_mtu: usize,
+    /// The DSCP to mark each message with
+    dscp: u8,
     /// The maximum number of packets that can be sent in the current iteration
     capacity: usize,
     /// Used to track if we have filled up the producer queue and waiting on free slots to be
@@ -369,6 +461,23 @@ impl<T: Message> TxQueue<'_, T> {
         self.pending_release += 1;
     }
 
+    /// Returns the range of channels the message on the given path should be sent on
+    #[inline]
+    fn route(&self, handle: &T::Handle) -> Range<usize> {
+        for route in self.routes {
+            if route.matches(handle) {
+                return route.channels.clone();
+            }
+        }
+
+        // fall back to the default channels, which are the ones before the first route
+        let end = self
+            .routes
+            .first()
+            .map_or(self.channels.len(), |r| r.channels.start);
+        0..end
+    }
+
     /// Flushes the current channel and releases any pending messages
     #[inline]
     fn flush_channel(&mut self) {
@@ -399,8 +508,24 @@ impl<T: Message> tx::Queue for TxQueue<'_, T> {
             Err(message) => message,
         };
 
+        if !self.routes.is_empty() {
+            let channels = self.route(message.path_handle());
+
+            if self.channel_end != channels.end {
+                // the message is sent from a different socket so flush the current one
+                self.flush_gso();
+                self.flush_channel();
+                self.channel_index = channels.start;
+                self.channel_end = channels.end;
+            }
+        }
+
         // find the next free entry, if any
         let entry = loop {
+            if self.channel_index >= self.channel_end {
+                return Err(tx::Error::AtCapacity);
+            }
+
             let channel = self
                 .channels
                 .get_mut(self.channel_index)
@@ -427,7 +552,7 @@ impl<T: Message> tx::Queue for TxQueue<'_, T> {
         let can_gso = message.can_gso(self.max_mtu, 0);
 
         // write the message to the entry
-        let payload_len = entry.tx_write(message)?;
+        let payload_len = entry.tx_write_with_dscp(message, self.dscp)?;
 
         // if GSO is supported and we are allowed to have additional segments, store the GSO state
         // for another potential message to be written later
@@ -0,0 +1,166 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Shards a QUIC endpoint across multiple sockets which share a port with `SO_REUSEPORT`
+//!
+//! By default, the kernel selects a socket in the reuse port group by hashing the 4-tuple of the
+//! packet, which sends packets to the wrong socket after the peer migrates. Instead, a program is
+//! attached to the group which selects the socket from the first byte of the destination
+//! connection ID. Each shard issues connection IDs which start with its index so all of the
+//! packets for a connection are received by the same shard, regardless of the peer's address.
+
+use super::options::{Options, ReusePort};
+use crate::bpf::cbpf::*;
+use std::{io, net::UdpSocket};
+
+/// The maximum number of shards which can be addressed by the first byte of a connection ID
+pub const MAX_SHARDS: usize = u8::MAX as usize + 1;
+
+/// The offset of the destination connection ID in a long header packet
+///
+/// The first byte is followed by the 4 byte version and the 1 byte connection ID length.
+const LONG_HEADER_DCID_OFFSET: u32 = 6;
+
+/// The offset of the destination connection ID in a short header packet
+const SHORT_HEADER_DCID_OFFSET: u32 = 1;
+
+const LONG_HEADER_BIT: u32 = 0x80;
+
+/// Returns the program which steers packets to a shard by their destination connection ID
+///
+/// Shards are identified by the order in which their sockets joined the reuse port group. The
+/// first byte of the destination connection ID is reduced modulo `count` so IDs chosen by
+/// clients for their Initial packets are spread across the shards, while IDs issued by a shard
+/// always map back to it.
+pub fn steering_program(count: usize) -> Vec<Instruction> {
+    assert!(
+        (1..=MAX_SHARDS).contains(&count),
+        "shard count must be between 1 and {MAX_SHARDS}"
+    );
+
+    let mut program = vec![
+        // load the first byte of the packet
+        ldb(0),
+        // IF:
+        // the long header bit is set
+        jset(LONG_HEADER_BIT, 0, 2),
+        // THEN:
+        // load the first byte of the long header destination connection ID
+        ldb(LONG_HEADER_DCID_OFFSET),
+        ja(1),
+        // ELSE:
+        // load the first byte of the short header destination connection ID
+        ldb(SHORT_HEADER_DCID_OFFSET),
+        // select the shard for the byte
+        rem(count as u32),
+    ];
+
+    // the assembler only supports returning constants, so jump to the instruction which returns
+    // the selected index
+    for index in 0..count as u32 {
+        program.push(jeq(index, 0, 1));
+        program.push(ret(index));
+    }
+
+    // unreachable, since the index is always less than the count
+    program.push(ret(0));
+
+    program
+}
+
+/// Binds `count` sockets to `options.addr` which share the port with `SO_REUSEPORT`
+///
+/// The returned sockets are ordered by their shard index. Closing one of the sockets moves the
+/// last socket of the group into its index, so all of them should be kept open until the shards
+/// are no longer in use.
+pub fn bind(mut options: Options, count: usize) -> io::Result<Vec<UdpSocket>> {
+    if !(1..=MAX_SHARDS).contains(&count) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("shard count must be between 1 and {MAX_SHARDS}"),
+        ));
+    }
+
+    // GRO is not compatible with this mode of operation
+    options.gro = false;
+    // set the reuse port option after binding to avoid port collisions
+    options.reuse_port = ReusePort::AfterBind;
+
+    let first = options.build_udp()?;
+
+    // bind the rest of the sockets to the same address
+    options.addr = first.local_addr()?;
+    // now that we have a concrete port from the OS, we set the option before the bind call
+    options.reuse_port = ReusePort::BeforeBind;
+
+    let mut sockets = Vec::with_capacity(count);
+    sockets.push(first);
+
+    for _ in 1..count {
+        sockets.push(options.build_udp()?);
+    }
+
+    // the program applies to the entire reuse port group
+    let program = steering_program(count);
+    Program::new(&program).attach(&sockets[0])?;
+
+    Ok(sockets)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::time::Duration;
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn steering_test() {
+        const COUNT: usize = 4;
+
+        let mut options = Options::new("127.0.0.1:0".parse().unwrap());
+        options.blocking = true;
+
+        let sockets = bind(options, COUNT).unwrap();
+        let addr = sockets[0].local_addr().unwrap();
+
+        for socket in &sockets {
+            assert_eq!(socket.local_addr().unwrap(), addr);
+            socket
+                .set_read_timeout(Some(Duration::from_millis(100)))
+                .unwrap();
+        }
+
+        let sender = UdpSocket::bind("127.0.0.1:0").unwrap();
+
+        for id in 0..=u8::MAX {
+            let expected = id as usize % COUNT;
+
+            // short header
+            sender.send_to(&[0x40, id, 1, 2, 3], addr).unwrap();
+            // long header
+            sender
+                .send_to(&[0xc0, 0, 0, 0, 1, 8, id, 1, 2, 3], addr)
+                .unwrap();
+
+            let mut buffer = [0; 32];
+            for _ in 0..2 {
+                let (len, _) = sockets[expected].recv_from(&mut buffer).unwrap();
+                assert!(len > 0);
+            }
+        }
+
+        // no packets were received on the other shards
+        for socket in &sockets {
+            socket.set_nonblocking(true).unwrap();
+            let mut buffer = [0; 32];
+            assert!(socket.recv_from(&mut buffer).is_err());
+        }
+    }
+
+    #[test]
+    fn invalid_count_test() {
+        let options = Options::new("127.0.0.1:0".parse().unwrap());
+        assert!(bind(options.clone(), 0).is_err());
+        assert!(bind(options, MAX_SHARDS + 1).is_err());
+    }
+}
@@ -25,6 +25,8 @@ macro_rules! libc {
 pub mod mmsg;
 #[cfg(s2n_quic_platform_socket_msg)]
 pub mod msg;
+#[cfg(all(s2n_quic_platform_socket_msg, s2n_quic_platform_socket_msg_x))]
+pub mod msg_x;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[allow(dead_code)] // this isn't constructed on some platforms
@@ -84,6 +86,27 @@ pub fn bind_udp<A: std::net::ToSocketAddrs>(
     addr: A,
     reuse_address: bool,
     reuse_port: bool,
+) -> io::Result<Socket> {
+    bind(addr, reuse_address, reuse_port, false)
+}
+
+/// Creates a UDP socket bound to the provided address
+///
+/// If the address is IPv6, the socket only accepts IPv6 traffic. This allows IPv4 sockets to be
+/// bound to the same port.
+pub fn bind_udp_v6_only<A: std::net::ToSocketAddrs>(
+    addr: A,
+    reuse_address: bool,
+    reuse_port: bool,
+) -> io::Result<Socket> {
+    bind(addr, reuse_address, reuse_port, true)
+}
+
+fn bind<A: std::net::ToSocketAddrs>(
+    addr: A,
+    reuse_address: bool,
+    reuse_port: bool,
+    only_v6: bool,
 ) -> io::Result<Socket> {
     let addr = addr.to_socket_addrs()?.next().ok_or_else(|| {
         std::io::Error::new(
@@ -93,6 +116,10 @@ pub fn bind_udp<A: std::net::ToSocketAddrs>(
     })?;
     let socket = udp_socket(addr)?;
 
+    if only_v6 && addr.is_ipv6() {
+        socket.set_only_v6(true)?;
+    }
+
     socket.set_reuse_address(reuse_address)?;
 
     #[cfg(unix)]
@@ -173,6 +200,127 @@ pub fn configure_mtu_disc(tx_socket: &Socket) -> bool {
     success
 }
 
+/// Allows the IP layer to fragment packets sent on the socket
+///
+/// This clears the Don't Fragment (DF) bit on IPv4 packets and allows IPv6 packets to be
+/// fragmented by the sender.
+pub fn configure_fragmentation(tx_socket: &Socket) -> bool {
+    let mut success = false;
+
+    #[cfg(s2n_quic_platform_mtu_disc)]
+    {
+        use std::os::unix::io::AsRawFd;
+
+        success |= libc!(setsockopt(
+            tx_socket.as_raw_fd(),
+            libc::IPPROTO_IP,
+            libc::IP_MTU_DISCOVER,
+            &libc::IP_PMTUDISC_DONT as *const _ as _,
+            core::mem::size_of_val(&libc::IP_PMTUDISC_DONT) as _,
+        ))
+        .is_ok();
+
+        success |= libc!(setsockopt(
+            tx_socket.as_raw_fd(),
+            libc::IPPROTO_IPV6,
+            libc::IPV6_MTU_DISCOVER,
+            &libc::IPV6_PMTUDISC_DONT as *const _ as _,
+            core::mem::size_of_val(&libc::IPV6_PMTUDISC_DONT) as _,
+        ))
+        .is_ok();
+    }
+
+    success
+}
+
+/// Marks all of the packets sent on the socket with the given Differentiated Services Code Point
+/// (DSCP)
+///
+/// The DSCP is set in the upper 6 bits of the IPv4 TOS and IPv6 Traffic Class fields, leaving the
+/// lower 2 bits for ECN.
+pub fn configure_dscp(tx_socket: &Socket, dscp: u8) -> bool {
+    let mut success = false;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::io::AsRawFd;
+        let tos = (dscp as libc::c_int) << 2;
+
+        if let (Some(level), Some(ty)) = (
+            crate::features::tos_v4::LEVEL,
+            crate::features::tos_v4::TYPE,
+        ) {
+            success |= libc!(setsockopt(
+                tx_socket.as_raw_fd(),
+                level,
+                ty,
+                &tos as *const _ as _,
+                core::mem::size_of_val(&tos) as _,
+            ))
+            .is_ok();
+        }
+
+        if let (Some(level), Some(ty)) = (
+            crate::features::tos_v6::LEVEL,
+            crate::features::tos_v6::TYPE,
+        ) {
+            success |= libc!(setsockopt(
+                tx_socket.as_raw_fd(),
+                level,
+                ty,
+                &tos as *const _ as _,
+                core::mem::size_of_val(&tos) as _,
+            ))
+            .is_ok();
+        }
+    }
+
+    success
+}
+
+/// Binds the socket to the network device with the given name (SO_BINDTODEVICE)
+pub fn bind_device(socket: &Socket, device: &str) -> io::Result<()> {
+    #[cfg(any(target_os = "android", target_os = "linux"))]
+    return socket.bind_device(Some(device.as_bytes()));
+
+    #[cfg(not(any(target_os = "android", target_os = "linux")))]
+    return Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "binding to a device is not supported on the current platform",
+    ));
+}
+
+/// Pins the calling thread to the given CPU
+pub fn pin_current_thread(cpu: usize) -> io::Result<()> {
+    #[cfg(target_os = "linux")]
+    {
+        if cpu >= libc::CPU_SETSIZE as usize {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "the cpu index exceeds the maximum cpu set size",
+            ));
+        }
+
+        let mut set: libc::cpu_set_t = unsafe { core::mem::zeroed() };
+        unsafe { libc::CPU_SET(cpu, &mut set) };
+        libc!(sched_setaffinity(
+            0,
+            core::mem::size_of::<libc::cpu_set_t>(),
+            &set
+        ))?;
+        Ok(())
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = cpu;
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "pinning threads is not supported on the current platform",
+        ))
+    }
+}
+
 /// Configures the socket to return TOS/ECN information as part of the ancillary data
 pub fn configure_tos(rx_socket: &Socket) -> bool {
     let mut success = false;
@@ -265,3 +413,51 @@ pub fn configure_gro(rx_socket: &Socket) -> bool {
 
     success
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    #[cfg_attr(miri, ignore)]
+    fn pin_current_thread_test() {
+        fn affinity() -> libc::cpu_set_t {
+            let mut set: libc::cpu_set_t = unsafe { core::mem::zeroed() };
+            libc!(sched_getaffinity(
+                0,
+                core::mem::size_of::<libc::cpu_set_t>(),
+                &mut set
+            ))
+            .unwrap();
+            set
+        }
+
+        // pin a separate thread so the affinity of the test thread is left unchanged
+        std::thread::spawn(|| {
+            let allowed = affinity();
+            let cpu = (0..libc::CPU_SETSIZE as usize)
+                .find(|cpu| unsafe { libc::CPU_ISSET(*cpu, &allowed) })
+                .unwrap();
+
+            pin_current_thread(cpu).unwrap();
+
+            let pinned = affinity();
+            for other in 0..libc::CPU_SETSIZE as usize {
+                assert_eq!(unsafe { libc::CPU_ISSET(other, &pinned) }, other == cpu);
+            }
+
+            let error = pin_current_thread(libc::CPU_SETSIZE as usize).unwrap_err();
+            assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+        })
+        .join()
+        .unwrap();
+    }
+
+    #[test]
+    #[cfg(not(target_os = "linux"))]
+    fn pin_current_thread_test() {
+        let error = pin_current_thread(0).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::Unsupported);
+    }
+}
@@ -14,6 +14,10 @@ impl UnixMessage for msghdr {
         events: &mut E,
         stats: &stats::Sender,
     ) {
+        #[cfg(s2n_quic_platform_socket_msg_x)]
+        return super::msg_x::send(&fd, entries, events, stats);
+
+        #[cfg(not(s2n_quic_platform_socket_msg_x))]
         send(&fd, entries, events, stats)
     }
 
@@ -25,6 +29,10 @@ impl UnixMessage for msghdr {
         events: &mut E,
         stats: &stats::Sender,
     ) {
+        #[cfg(s2n_quic_platform_socket_msg_x)]
+        return super::msg_x::recv(&fd, ty, entries, events, stats);
+
+        #[cfg(not(s2n_quic_platform_socket_msg_x))]
         recv(&fd, ty, entries, events, stats)
     }
 }
@@ -0,0 +1,317 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Batched message syscalls for Apple platforms
+//!
+//! Darwin doesn't provide `sendmmsg`/`recvmmsg` but does export `sendmsg_x`/`recvmsg_x`, which
+//! accept an array of `msghdr_x` structures. The calls aren't part of the public SDK headers so
+//! the definitions are mirrored from `bsd/sys/socket_private.h` in XNU.
+
+use super::{SocketEvents, SocketType};
+use crate::{message::Message as _, socket::stats};
+use core::sync::atomic::{AtomicBool, Ordering};
+use libc::{c_int, c_uint, c_void, iovec, msghdr, size_t, socklen_t, ssize_t};
+use std::os::unix::io::AsRawFd;
+
+/// The maximum number of messages passed to a single call
+const BATCH_SIZE: usize = 32;
+
+#[repr(C)]
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy)]
+struct msghdr_x {
+    msg_name: *mut c_void,
+    msg_namelen: socklen_t,
+    msg_iov: *mut iovec,
+    msg_iovlen: c_int,
+    msg_control: *mut c_void,
+    msg_controllen: socklen_t,
+    msg_flags: c_int,
+    msg_datalen: size_t,
+}
+
+impl msghdr_x {
+    const EMPTY: Self = Self {
+        msg_name: core::ptr::null_mut(),
+        msg_namelen: 0,
+        msg_iov: core::ptr::null_mut(),
+        msg_iovlen: 0,
+        msg_control: core::ptr::null_mut(),
+        msg_controllen: 0,
+        msg_flags: 0,
+        msg_datalen: 0,
+    };
+
+    #[inline]
+    fn new(msg: &msghdr) -> Self {
+        // macOS doesn't like when msg_control have valid pointers but the len is 0
+        let msg_control = if msg.msg_controllen == 0 {
+            core::ptr::null_mut()
+        } else {
+            msg.msg_control
+        };
+
+        Self {
+            msg_name: msg.msg_name,
+            msg_namelen: msg.msg_namelen,
+            msg_iov: msg.msg_iov,
+            msg_iovlen: msg.msg_iovlen as _,
+            msg_control,
+            msg_controllen: msg.msg_controllen as _,
+            msg_flags: 0,
+            msg_datalen: msg.payload_len(),
+        }
+    }
+}
+
+extern "C" {
+    fn sendmsg_x(s: c_int, msgp: *const msghdr_x, cnt: c_uint, flags: c_int) -> ssize_t;
+    fn recvmsg_x(s: c_int, msgp: *const msghdr_x, cnt: c_uint, flags: c_int) -> ssize_t;
+}
+
+/// Set if the kernel rejects batched sends, in which case individual `sendmsg` calls are used
+///
+/// Some kernel versions only accept `sendmsg_x` on connected sockets.
+static SEND_UNSUPPORTED: AtomicBool = AtomicBool::new(false);
+
+#[inline]
+fn is_unsupported(error: &std::io::Error) -> bool {
+    matches!(
+        error.raw_os_error(),
+        Some(libc::EINVAL | libc::ENOSYS | libc::EOPNOTSUPP | libc::EISCONN | libc::EDESTADDRREQ)
+    )
+}
+
+#[inline]
+pub fn send<Sock: AsRawFd, E: SocketEvents>(
+    socket: &Sock,
+    packets: &mut [msghdr],
+    events: &mut E,
+    stats: &stats::Sender,
+) {
+    if SEND_UNSUPPORTED.load(Ordering::Relaxed) {
+        return super::msg::send(socket, packets, events, stats);
+    }
+
+    let sockfd = socket.as_raw_fd();
+    let mut offset = 0;
+
+    while offset < packets.len() {
+        let chunk = &packets[offset..];
+        let len = chunk.len().min(BATCH_SIZE);
+
+        let mut hdrs = [msghdr_x::EMPTY; BATCH_SIZE];
+        for (hdr, msg) in hdrs.iter_mut().zip(chunk[..len].iter()) {
+            *hdr = msghdr_x::new(msg);
+        }
+
+        // Safety: the headers point to the buffers owned by `packets`, which outlive the call.
+        //
+        // > sendmsg_x() returns the number of messages sent or -1 if an error occurred
+        let res = unsafe { sendmsg_x(sockfd, hdrs.as_ptr(), len as _, 0) };
+        let res = if res < 0 {
+            Err(std::io::Error::last_os_error())
+        } else {
+            Ok(res as usize)
+        };
+
+        match res {
+            Err(error) if offset == 0 && is_unsupported(&error) => {
+                SEND_UNSUPPORTED.store(true, Ordering::Relaxed);
+                return super::msg::send(socket, packets, events, stats);
+            }
+            Ok(0) => {
+                // nothing could be sent so yield to the caller
+                return;
+            }
+            res => {
+                stats.send().on_operation_result(&res, |count| *count as _);
+
+                let cf = match res {
+                    Ok(count) => {
+                        offset += count;
+                        let cf = events.on_complete(count);
+
+                        // a partial send means the socket buffer is full
+                        if count < len {
+                            return;
+                        }
+
+                        cf
+                    }
+                    Err(error) => events.on_error(error),
+                };
+
+                if cf.is_break() {
+                    return;
+                }
+            }
+        }
+    }
+}
+
+#[inline]
+pub fn recv<Sock: AsRawFd, E: SocketEvents>(
+    socket: &Sock,
+    socket_type: SocketType,
+    packets: &mut [msghdr],
+    events: &mut E,
+    stats: &stats::Sender,
+) {
+    let sockfd = socket.as_raw_fd();
+    let mut flags = match socket_type {
+        SocketType::Blocking => Default::default(),
+        SocketType::NonBlocking => libc::MSG_DONTWAIT,
+    };
+
+    for chunk in packets.chunks_mut(BATCH_SIZE) {
+        let len = chunk.len();
+
+        let mut hdrs = [msghdr_x::EMPTY; BATCH_SIZE];
+        for (hdr, msg) in hdrs.iter_mut().zip(chunk.iter()) {
+            *hdr = msghdr_x::new(msg);
+        }
+
+        // Safety: the headers point to the buffers owned by `packets`, which outlive the call.
+        //
+        // > recvmsg_x() returns the number of messages received or -1 if an error occurred. On
+        // > return, the msg_datalen, msg_namelen, msg_controllen and msg_flags fields of each
+        // > received message are updated.
+        let res = unsafe { recvmsg_x(sockfd, hdrs.as_ptr(), len as _, flags) };
+        let res = if res < 0 {
+            Err(std::io::Error::last_os_error())
+        } else {
+            Ok(res as usize)
+        };
+
+        stats.recv().on_operation_result(&res, |count| *count as _);
+
+        let cf = match res {
+            Ok(count) => {
+                for (msg, hdr) in chunk.iter_mut().zip(hdrs.iter()).take(count) {
+                    msg.msg_namelen = hdr.msg_namelen;
+                    msg.msg_controllen = hdr.msg_controllen as _;
+                    msg.msg_flags = hdr.msg_flags;
+                    // update the message based on the return size of the syscall
+                    unsafe {
+                        msg.set_payload_len(hdr.msg_datalen.min(u16::MAX as _));
+                    }
+                }
+
+                let cf = events.on_complete(count);
+
+                // the socket has been drained
+                if count < len {
+                    return;
+                }
+
+                cf
+            }
+            Err(error) => events.on_error(error),
+        };
+
+        if cf.is_break() {
+            return;
+        }
+
+        // don't block the follow-up calls
+        flags = libc::MSG_DONTWAIT;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        message::{msg::Message, Message as _},
+        socket::ring,
+    };
+    use core::ops::ControlFlow;
+    use s2n_quic_core::{
+        inet::{ExplicitCongestionNotification, SocketAddress},
+        path::{Handle as _, LocalAddress, RemoteAddress},
+    };
+    use std::net::UdpSocket;
+
+    #[derive(Default)]
+    struct Events {
+        completed: usize,
+        errors: Vec<std::io::Error>,
+    }
+
+    impl SocketEvents for Events {
+        fn on_complete(&mut self, count: usize) -> ControlFlow<(), ()> {
+            self.completed += count;
+            ControlFlow::Continue(())
+        }
+
+        fn on_error(&mut self, error: std::io::Error) -> ControlFlow<(), ()> {
+            self.errors.push(error);
+            ControlFlow::Break(())
+        }
+    }
+
+    #[test]
+    fn batch_round_trip() {
+        let rx_socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        rx_socket.set_nonblocking(true).unwrap();
+        let tx_socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+
+        let remote_address: SocketAddress = rx_socket.local_addr().unwrap().into();
+        let handle = <Message as crate::message::Message>::Handle::from_remote_address(
+            RemoteAddress::from(remote_address),
+        );
+
+        // send more than a single batch to exercise the chunking
+        let count = BATCH_SIZE as u32 + 3;
+        let entries = 64;
+        let payload_len = 128;
+        let (_, stats) = crate::socket::stats::channel();
+
+        let (mut tx_producer, _tx_consumer) = ring::pair::<Message>(entries, payload_len);
+        assert_eq!(tx_producer.acquire(count), count);
+        for (idx, entry) in tx_producer.data()[..count as usize].iter_mut().enumerate() {
+            unsafe {
+                entry.reset(payload_len as _);
+            }
+            let payload = (idx as u32).to_le_bytes();
+            let msg = (handle, ExplicitCongestionNotification::Ect0, &payload[..]);
+            entry.tx_write(msg).unwrap();
+        }
+
+        let mut events = Events::default();
+        send(
+            &tx_socket,
+            &mut tx_producer.data()[..count as usize],
+            &mut events,
+            &stats,
+        );
+        assert!(events.errors.is_empty(), "{:?}", events.errors);
+        assert_eq!(events.completed, count as usize);
+
+        // give the loopback interface a moment to deliver the datagrams
+        std::thread::sleep(core::time::Duration::from_millis(50));
+
+        let (mut rx_producer, _rx_consumer) = ring::pair::<Message>(entries, payload_len);
+        assert_eq!(rx_producer.acquire(entries), entries);
+        let mut events = Events::default();
+        recv(
+            &rx_socket,
+            SocketType::NonBlocking,
+            rx_producer.data(),
+            &mut events,
+            &stats,
+        );
+        assert!(events.errors.is_empty(), "{:?}", events.errors);
+        assert_eq!(events.completed, count as usize);
+
+        let local_address = LocalAddress::from(remote_address);
+        for (idx, entry) in rx_producer.data()[..count as usize].iter_mut().enumerate() {
+            let message = entry.rx_read(&local_address).unwrap();
+            message.for_each(|_header, payload| {
+                let counter: &[u8; 4] = (&*payload).try_into().unwrap();
+                assert_eq!(u32::from_le_bytes(*counter), idx as u32);
+            });
+        }
+    }
+}
@@ -0,0 +1,329 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A sans-IO interface for s2n-quic endpoints
+//!
+//! The built-in IO providers bind sockets, read the system clock and drive the endpoint from a
+//! task on an async runtime. The endpoint returned by this crate does none of these. It only makes
+//! progress when the application calls into it, with the current time as an argument:
+//!
+//! * received datagrams are passed to [`Endpoint::handle_datagram`]
+//! * expired timers are reported with [`Endpoint::handle_timeout`]
+//! * requests made through the application handles, such as opening a stream, are processed by
+//!   [`Endpoint::handle_wakeups`]
+//!
+//! Each call returns the datagrams the endpoint produced as [`Transmit`]s for the application to
+//! send however it likes.
+//!
+//! The [`Io`] provider is only used to construct the endpoint. It's passed to the
+//! `s2n_quic::Server` or `s2n_quic::Client` builder in place of a socket-based provider, and the
+//! application API, such as accepting connections and opening streams, is unchanged. The futures
+//! returned by the application handles can be polled by any executor, or manually.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! # use std::error::Error;
+//! use s2n_quic::Server;
+//! use s2n_quic_proto::Io;
+//! use std::{path::Path, time::Instant};
+//!
+//! # fn run() -> Result<(), Box<dyn Error>> {
+//! let io = Io::new("127.0.0.1:4433".parse()?);
+//! let mut endpoint = io.endpoint();
+//!
+//! let server = Server::builder()
+//!     .with_tls((Path::new("./certs/cert.pem"), Path::new("./certs/key.pem")))?
+//!     .with_io(io)?
+//!     .start()?;
+//!
+//! // read a datagram from the network
+//! let (remote_address, payload) = ("127.0.0.1:1234".parse()?, vec![]);
+//!
+//! for transmit in endpoint.handle_datagram(Instant::now(), remote_address, &payload) {
+//!     // send `transmit.payload` to `transmit.remote_address`
+//! }
+//!
+//! // arm a timer for `endpoint.poll_timeout()` and call `endpoint.handle_timeout` once it expires
+//!
+//! // after using the `server` handle, let the endpoint process the requests
+//! for transmit in endpoint.handle_wakeups(Instant::now())? {
+//!     // send `transmit.payload` to `transmit.remote_address`
+//! }
+//! #
+//! #    Ok(())
+//! # }
+//! ```
+
+use core::task::{Context, Poll};
+use s2n_quic::provider::io;
+use s2n_quic_core::{
+    endpoint::{self, CloseError},
+    inet::SocketAddress,
+    path::{mtu, Tuple},
+    task::waker,
+    time::{self, Timestamp},
+};
+use std::{
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+mod queue;
+
+#[cfg(test)]
+mod tests;
+
+/// A datagram produced by the endpoint which should be sent to the peer
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Transmit {
+    /// The address of the peer
+    pub remote_address: SocketAddr,
+    /// The local address the datagram should be sent from
+    ///
+    /// The IP may be unspecified, in which case the application should pick the source address.
+    pub local_address: SocketAddr,
+    /// The contents of the datagram
+    pub payload: Vec<u8>,
+}
+
+/// An IO provider which is driven by the application
+///
+/// Each `Io` can start a single endpoint. The [`Endpoint`] handle returned by [`Io::endpoint`]
+/// drives the endpoint once the server or client has been started.
+pub struct Io {
+    local_address: SocketAddress,
+    mtu_config: mtu::Config,
+    shared: Shared,
+}
+
+impl Io {
+    /// Creates an IO provider for an endpoint with the given local address
+    ///
+    /// No socket is bound; the address is the one reported to the endpoint and the application.
+    pub fn new(local_address: SocketAddr) -> Self {
+        Self {
+            local_address: local_address.into(),
+            mtu_config: mtu::Config::default(),
+            shared: Shared::default(),
+        }
+    }
+
+    /// Sets the MTU configuration of the endpoint
+    ///
+    /// The `max_mtu` limits the size of the [`Transmit`] payloads.
+    pub fn with_mtu_config(mut self, mtu_config: mtu::Config) -> Self {
+        self.mtu_config = mtu_config;
+        self
+    }
+
+    /// Returns a handle to drive the endpoint started with this provider
+    pub fn endpoint(&self) -> Endpoint {
+        Endpoint {
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+impl io::Provider for Io {
+    type PathHandle = Tuple;
+    type Error = std::io::Error;
+
+    fn start<E: endpoint::Endpoint<PathHandle = Self::PathHandle>>(
+        self,
+        mut endpoint: E,
+    ) -> Result<SocketAddress, Self::Error> {
+        endpoint.set_mtu_config(self.mtu_config);
+
+        let max_mtu: u16 = self.mtu_config.max_mtu().into();
+        let driver = Driver {
+            endpoint,
+            local_address: self.local_address,
+            max_mtu: max_mtu as usize,
+            rx_buffer: Vec::new(),
+        };
+
+        let mut shared = self.shared.lock().unwrap();
+        if shared.driver.is_some() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::AlreadyExists,
+                "an endpoint was already started with this provider",
+            ));
+        }
+        shared.driver = Some(Box::new(driver));
+
+        Ok(self.local_address)
+    }
+}
+
+type Shared = Arc<Mutex<State>>;
+
+#[derive(Default)]
+struct State {
+    driver: Option<Box<dyn Drive>>,
+    /// The time passed by the application in its first call, which all timestamps are relative to
+    epoch: Option<Instant>,
+}
+
+/// Drives an endpoint started with an [`Io`] provider
+///
+/// All of the methods take the current time, which must not go backwards.
+///
+/// # Panics
+///
+/// The methods panic if the endpoint hasn't been started yet.
+pub struct Endpoint {
+    shared: Shared,
+}
+
+impl Endpoint {
+    /// Processes a datagram received from `remote_address` and returns the datagrams to send in
+    /// response
+    pub fn handle_datagram(
+        &mut self,
+        now: Instant,
+        remote_address: SocketAddr,
+        payload: &[u8],
+    ) -> Vec<Transmit> {
+        self.with_driver(now, |driver, clock| {
+            driver.receive(clock, remote_address.into(), payload);
+            let mut transmits = vec![];
+            driver.transmit(clock, &mut transmits);
+            transmits
+        })
+    }
+
+    /// Processes any expired timers and returns the datagrams to send
+    ///
+    /// This should be called once the time returned by [`Self::poll_timeout`] has passed. Calling
+    /// it early is harmless.
+    pub fn handle_timeout(&mut self, now: Instant) -> Vec<Transmit> {
+        self.with_driver(now, |driver, clock| {
+            let mut transmits = vec![];
+            driver.transmit(clock, &mut transmits);
+            transmits
+        })
+    }
+
+    /// Returns the time at which [`Self::handle_timeout`] should be called next
+    pub fn poll_timeout(&self) -> Option<Instant> {
+        let state = self.shared.lock().unwrap();
+        let driver = state
+            .driver
+            .as_deref()
+            .expect("the endpoint has not been started");
+        // the endpoint can't have any timers before it's first called
+        let epoch = state.epoch?;
+        let timeout = driver.timeout()?;
+        let timeout = unsafe {
+            // Safety: the timestamp was derived from `epoch`
+            timeout.as_duration()
+        };
+        Some(epoch + timeout)
+    }
+
+    /// Processes the requests made through the application handles and returns the datagrams to
+    /// send
+    ///
+    /// Opening connections, writing to streams and closing connections are queued by the
+    /// application handles without notifying the application. This should be called after using
+    /// them, for example once per iteration of the event loop.
+    ///
+    /// An error is returned once the endpoint has closed and won't make any more progress.
+    pub fn handle_wakeups(&mut self, now: Instant) -> Result<Vec<Transmit>, CloseError> {
+        self.with_driver(now, |driver, clock| {
+            driver.handle_wakeups(clock)?;
+            let mut transmits = vec![];
+            driver.transmit(clock, &mut transmits);
+            Ok(transmits)
+        })
+    }
+
+    #[inline]
+    fn with_driver<R>(&self, now: Instant, f: impl FnOnce(&mut dyn Drive, &Clock) -> R) -> R {
+        let mut state = self.shared.lock().unwrap();
+        let state = &mut *state;
+        let epoch = *state.epoch.get_or_insert(now);
+        let driver = state
+            .driver
+            .as_deref_mut()
+            .expect("the endpoint has not been started");
+
+        let duration = now.saturating_duration_since(epoch);
+        let clock = Clock(unsafe {
+            // Safety: time duration is only derived from a single `Instant`
+            Timestamp::from_duration(duration)
+        });
+
+        f(driver, &clock)
+    }
+}
+
+/// A clock which is fixed at the time passed by the application
+struct Clock(Timestamp);
+
+impl time::Clock for Clock {
+    #[inline]
+    fn get_time(&self) -> Timestamp {
+        self.0
+    }
+}
+
+/// Erases the type of the endpoint so [`Endpoint`] doesn't need to be generic
+trait Drive: Send {
+    fn receive(&mut self, clock: &Clock, remote_address: SocketAddress, payload: &[u8]);
+
+    fn transmit(&mut self, clock: &Clock, transmits: &mut Vec<Transmit>);
+
+    fn handle_wakeups(&mut self, clock: &Clock) -> Result<(), CloseError>;
+
+    fn timeout(&self) -> Option<Timestamp>;
+}
+
+struct Driver<E> {
+    endpoint: E,
+    local_address: SocketAddress,
+    max_mtu: usize,
+    rx_buffer: Vec<u8>,
+}
+
+impl<E: endpoint::Endpoint<PathHandle = Tuple>> Drive for Driver<E> {
+    #[inline]
+    fn receive(&mut self, clock: &Clock, remote_address: SocketAddress, payload: &[u8]) {
+        // the endpoint decrypts packets in place so the payload needs to be copied
+        self.rx_buffer.clear();
+        self.rx_buffer.extend_from_slice(payload);
+
+        let path = Tuple {
+            remote_address: remote_address.into(),
+            local_address: self.local_address.into(),
+        };
+        let mut queue = queue::Rx::new(path, &mut self.rx_buffer);
+        self.endpoint.receive(&mut queue, clock);
+    }
+
+    #[inline]
+    fn transmit(&mut self, clock: &Clock, transmits: &mut Vec<Transmit>) {
+        let mut queue = queue::Tx::new(transmits, self.max_mtu);
+        self.endpoint.transmit(&mut queue, clock);
+    }
+
+    #[inline]
+    fn handle_wakeups(&mut self, clock: &Clock) -> Result<(), CloseError> {
+        // the application calls this method instead of being notified of wakeups
+        let waker = waker::noop();
+        let mut cx = Context::from_waker(&waker);
+
+        while let Poll::Ready(result) = self.endpoint.poll_wakeups(&mut cx, clock) {
+            result?;
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn timeout(&self) -> Option<Timestamp> {
+        self.endpoint.timeout()
+    }
+}
@@ -0,0 +1,87 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::Transmit;
+use s2n_quic_core::{
+    inet::datagram,
+    io::{rx, tx},
+    path::Tuple,
+};
+
+/// A receive queue containing a single datagram
+pub struct Rx<'a> {
+    datagram: Option<(Tuple, &'a mut [u8])>,
+}
+
+impl<'a> Rx<'a> {
+    #[inline]
+    pub fn new(path: Tuple, payload: &'a mut [u8]) -> Self {
+        Self {
+            datagram: Some((path, payload)),
+        }
+    }
+}
+
+impl rx::Queue for Rx<'_> {
+    type Handle = Tuple;
+
+    #[inline]
+    fn for_each<F: FnMut(datagram::Header<Self::Handle>, &mut [u8])>(&mut self, mut on_packet: F) {
+        if let Some((path, payload)) = self.datagram.take() {
+            let header = datagram::Header {
+                path,
+                ecn: Default::default(),
+            };
+            on_packet(header, payload);
+        }
+    }
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.datagram.is_none()
+    }
+}
+
+/// A transmit queue which collects datagrams for the application to send
+pub struct Tx<'a> {
+    transmits: &'a mut Vec<Transmit>,
+    max_mtu: usize,
+}
+
+impl<'a> Tx<'a> {
+    #[inline]
+    pub fn new(transmits: &'a mut Vec<Transmit>, max_mtu: usize) -> Self {
+        Self { transmits, max_mtu }
+    }
+}
+
+impl tx::Queue for Tx<'_> {
+    type Handle = Tuple;
+
+    #[inline]
+    fn push<M: tx::Message<Handle = Self::Handle>>(
+        &mut self,
+        mut message: M,
+    ) -> Result<tx::Outcome, tx::Error> {
+        let path = *message.path_handle();
+
+        let mut payload = vec![0; self.max_mtu];
+        let len = message.write_payload(tx::PayloadBuffer::new(&mut payload), 0)?;
+        payload.truncate(len);
+
+        let index = self.transmits.len();
+        self.transmits.push(Transmit {
+            remote_address: (*path.remote_address).into(),
+            local_address: (*path.local_address).into(),
+            payload,
+        });
+
+        Ok(tx::Outcome { len, index })
+    }
+
+    #[inline]
+    fn capacity(&self) -> usize {
+        // the application is responsible for applying backpressure
+        usize::MAX - self.transmits.len()
+    }
+}
@@ -0,0 +1,137 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use bytes::Bytes;
+use core::time::Duration;
+use futures::{task::noop_waker_ref, FutureExt};
+use s2n_quic::{client::Connect, Client, Server};
+use s2n_quic_core::crypto::tls::testing::certificates::{CERT_PEM, KEY_PEM};
+
+struct Peer {
+    address: SocketAddr,
+    endpoint: Endpoint,
+}
+
+impl Peer {
+    fn new(address: &str) -> (Self, Io) {
+        let address = address.parse().unwrap();
+        let io = Io::new(address);
+        let endpoint = io.endpoint();
+        (Self { address, endpoint }, io)
+    }
+
+    /// Returns all of the datagrams the endpoint wants to send at `now`
+    fn poll(&mut self, now: Instant) -> Vec<Transmit> {
+        let mut transmits = self.endpoint.handle_timeout(now);
+        transmits.extend(self.endpoint.handle_wakeups(now).unwrap());
+        transmits
+    }
+}
+
+struct Pair {
+    client: Peer,
+    server: Peer,
+    now: Instant,
+}
+
+impl Pair {
+    /// Delivers datagrams between the endpoints until `f` is ready
+    fn drive<T>(&mut self, mut f: impl FnMut(&mut Context) -> Poll<T>) -> T {
+        let mut cx = Context::from_waker(noop_waker_ref());
+
+        for _ in 0..100 {
+            if let Poll::Ready(value) = f(&mut cx) {
+                return value;
+            }
+
+            let mut to_server = self.client.poll(self.now);
+            let mut to_client = self.server.poll(self.now);
+
+            while !(to_server.is_empty() && to_client.is_empty()) {
+                for transmit in core::mem::take(&mut to_server) {
+                    assert_eq!(transmit.remote_address, self.server.address);
+                    to_client.extend(self.server.endpoint.handle_datagram(
+                        self.now,
+                        self.client.address,
+                        &transmit.payload,
+                    ));
+                }
+
+                for transmit in core::mem::take(&mut to_client) {
+                    assert_eq!(transmit.remote_address, self.client.address);
+                    to_server.extend(self.client.endpoint.handle_datagram(
+                        self.now,
+                        self.server.address,
+                        &transmit.payload,
+                    ));
+                }
+            }
+
+            self.now += Duration::from_millis(1);
+        }
+
+        panic!("the endpoints did not make progress");
+    }
+}
+
+#[test]
+fn round_trip_test() {
+    let (server_peer, io) = Peer::new("127.0.0.1:4433");
+    let mut server = Server::builder()
+        .with_tls((CERT_PEM, KEY_PEM))
+        .unwrap()
+        .with_io(io)
+        .unwrap()
+        .start()
+        .unwrap();
+    assert_eq!(server.local_addr().unwrap(), server_peer.address);
+
+    let (client_peer, io) = Peer::new("127.0.0.1:4434");
+    let client = Client::builder()
+        .with_tls(CERT_PEM)
+        .unwrap()
+        .with_io(io)
+        .unwrap()
+        .start()
+        .unwrap();
+
+    let mut pair = Pair {
+        client: client_peer,
+        server: server_peer,
+        now: Instant::now(),
+    };
+
+    let connect = Connect::new(pair.server.address).with_server_name("localhost");
+    let mut attempt = client.connect(connect);
+    let mut client_connection = pair.drive(|cx| attempt.poll_unpin(cx)).unwrap();
+    let mut server_connection = pair.drive(|cx| server.poll_accept(cx)).unwrap();
+
+    // the connection's timers are exposed to the application
+    let timeout = pair.client.endpoint.poll_timeout().unwrap();
+    assert!(timeout > pair.now);
+
+    let mut client_stream = pair
+        .drive(|cx| client_connection.poll_open_bidirectional_stream(cx))
+        .unwrap();
+    client_stream
+        .send_data(Bytes::from_static(b"hello"))
+        .unwrap();
+
+    let mut server_stream = pair
+        .drive(|cx| server_connection.poll_accept_bidirectional_stream(cx))
+        .unwrap()
+        .unwrap();
+    let chunk = pair
+        .drive(|cx| server_stream.poll_receive(cx))
+        .unwrap()
+        .unwrap();
+    assert_eq!(chunk, Bytes::from_static(b"hello"));
+}
+
+#[test]
+#[should_panic = "the endpoint has not been started"]
+fn not_started_test() {
+    let (mut peer, _io) = Peer::new("127.0.0.1:4433");
+    peer.endpoint.handle_timeout(Instant::now());
+}
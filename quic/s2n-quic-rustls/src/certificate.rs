@@ -80,6 +80,12 @@ cert_type!(
     into_certificate,
     Vec<rustls::pki_types::CertificateDer<'static>>
 );
+cert_type!(
+    PublicKey,
+    IntoPublicKey,
+    into_public_key,
+    rustls::pki_types::SubjectPublicKeyInfoDer<'static>
+);
 
 impl IntoCertificate for Vec<Vec<u8>> {
     fn into_certificate(self) -> Result<Certificate, Error> {
@@ -94,7 +100,7 @@ impl IntoCertificate for Vec<Vec<u8>> {
 
 mod pem {
     use rustls::{
-        pki_types::{CertificateDer, PrivateKeyDer},
+        pki_types::{CertificateDer, PrivateKeyDer, SubjectPublicKeyInfoDer},
         Error,
     };
 
@@ -148,11 +154,18 @@ mod pem {
             "could not load any valid private keys".to_string(),
         ))
     }
+
+    pub fn into_public_key(contents: &[u8]) -> Result<SubjectPublicKeyInfoDer<'static>, Error> {
+        let mut cursor = std::io::Cursor::new(contents);
+        let key = rustls_pemfile::public_keys(&mut cursor).next();
+        key.and_then(|key| key.ok())
+            .ok_or_else(|| Error::General("Could not read public key".to_string()))
+    }
 }
 
 mod der {
     use rustls::{
-        pki_types::{CertificateDer, PrivateKeyDer},
+        pki_types::{CertificateDer, PrivateKeyDer, SubjectPublicKeyInfoDer},
         Error,
     };
 
@@ -169,6 +182,11 @@ mod der {
         // https://stackoverflow.com/a/48960291
         Ok(PrivateKeyDer::Pkcs8(contents.into()))
     }
+
+    pub fn into_public_key(contents: Vec<u8>) -> Result<SubjectPublicKeyInfoDer<'static>, Error> {
+        // the key is expected to be a DER-encoded SubjectPublicKeyInfo structure
+        Ok(SubjectPublicKeyInfoDer::from(contents))
+    }
 }
 
 #[cfg(test)]
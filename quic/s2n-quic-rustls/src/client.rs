@@ -1,11 +1,18 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{certificate, cipher_suite::default_crypto_provider, session::Session, Error};
+use crate::{
+    certificate, cipher_suite::default_crypto_provider, pinning, raw_public_key, session::Session,
+    signer, telemetry, Error,
+};
 use core::convert::TryFrom;
 use rustls::{ClientConfig, ConfigBuilder, WantsVerifier};
 use s2n_codec::EncoderValue;
-use s2n_quic_core::{application::ServerName, crypto::tls};
+use s2n_quic_core::{
+    application::ServerName,
+    crypto::tls::{self, signer::Signer},
+};
+use s2n_quic_crypto::pinning::Pins;
 use std::sync::Arc;
 
 /// Create a QUIC client specific [rustls::ConfigBuilder].
@@ -20,6 +27,11 @@ fn default_config_builder() -> Result<ConfigBuilder<ClientConfig, WantsVerifier>
 #[derive(Clone)]
 pub struct Client {
     config: Arc<ClientConfig>,
+    /// The verifier the config was built with, so the time spent validating servers can be
+    /// measured
+    verifier: Option<Arc<dyn rustls::client::danger::ServerCertVerifier>>,
+    /// Whether the handshake may block on a remote signer
+    remote_signer: bool,
 }
 
 impl Client {
@@ -32,9 +44,7 @@ impl Client {
     /// - QUIC compliant ciphersuites
     #[deprecated = "client and server builders should be used instead"]
     pub fn new(config: ClientConfig) -> Self {
-        Self {
-            config: Arc::new(config),
-        }
+        Self::from(Arc::new(config))
     }
 
     pub fn builder() -> Builder {
@@ -61,7 +71,11 @@ impl From<ClientConfig> for Client {
 // TODO this should be removed after removing deprecated re-exports
 impl From<Arc<ClientConfig>> for Client {
     fn from(config: Arc<ClientConfig>) -> Self {
-        Self { config }
+        Self {
+            config,
+            verifier: None,
+            remote_signer: false,
+        }
     }
 }
 
@@ -87,15 +101,23 @@ impl tls::Endpoint for Client {
         let rustls_server_name = rustls::pki_types::ServerName::try_from(server_name.to_string())
             .expect("invalid server name");
 
+        let telemetry = telemetry::Recorder::default();
+        let config = telemetry.client_config(&self.config, self.verifier.as_ref());
+
         let session = rustls::quic::ClientConnection::new(
-            self.config.clone(),
+            config,
             crate::QUIC_VERSION,
             rustls_server_name,
             transport_parameters,
         )
         .expect("could not create rustls client session");
 
-        Session::new(session.into(), Some(server_name))
+        Session::new(
+            session.into(),
+            Some(server_name),
+            telemetry,
+            self.remote_signer,
+        )
     }
 
     fn max_tag_length(&self) -> usize {
@@ -105,6 +127,11 @@ impl tls::Endpoint for Client {
 
 pub struct Builder {
     cert_store: rustls::RootCertStore,
+    client_certificate: Option<Arc<rustls::sign::CertifiedKey>>,
+    remote_signer: bool,
+    raw_public_key: Option<Arc<rustls::sign::CertifiedKey>>,
+    server_raw_public_keys: Vec<rustls::pki_types::SubjectPublicKeyInfoDer<'static>>,
+    certificate_pins: Pins,
     application_protocols: Vec<Vec<u8>>,
     key_log: Option<Arc<dyn rustls::KeyLog>>,
 }
@@ -119,6 +146,11 @@ impl Builder {
     pub fn new() -> Self {
         Self {
             cert_store: rustls::RootCertStore::empty(),
+            client_certificate: None,
+            remote_signer: false,
+            raw_public_key: None,
+            server_raw_public_keys: Vec::new(),
+            certificate_pins: Pins::default(),
             application_protocols: vec![b"h3".to_vec()],
             key_log: None,
         }
@@ -138,6 +170,100 @@ impl Builder {
         Ok(self)
     }
 
+    /// Trusts all of the certificates in the given Windows certificate store
+    ///
+    /// Certificates in the store which can't be parsed are ignored.
+    #[cfg(windows)]
+    pub fn with_windows_root_certificates(
+        mut self,
+        store: crate::windows::Store,
+    ) -> Result<Self, Error> {
+        let certificates = store.certificates()?;
+        let (added, _ignored) = self.cert_store.add_parsable_certificates(certificates);
+        if added == 0 {
+            return Err(rustls::Error::General(format!(
+                "no trusted root certificates found in store {:?}",
+                store.name()
+            ))
+            .into());
+        }
+        Ok(self)
+    }
+
+    /// Offers `certificate` to the server and offloads handshake signatures to `signer`
+    ///
+    /// This allows the private key to be held outside of the process, for example in an HSM or
+    /// KMS. rustls doesn't support asynchronous signing, so the server's handshake messages are
+    /// processed on a separate thread which waits for the signature, rather than the endpoint's.
+    pub fn with_client_identity_signer<C: certificate::IntoCertificate, S: Signer>(
+        mut self,
+        certificate: C,
+        signer: S,
+    ) -> Result<Self, Error> {
+        let certificate = certificate.into_certificate()?;
+        self.client_certificate = Some(signer::certified_key(certificate, signer));
+        self.remote_signer = true;
+        Ok(self)
+    }
+
+    /// Authenticates the client with a certificate from the given Windows certificate store
+    ///
+    /// The first certificate whose subject contains `subject` and has an associated private
+    /// key is used. The key is never exported from the store, so keys which are marked as
+    /// non-exportable are supported.
+    #[cfg(windows)]
+    pub fn with_windows_client_certificate(
+        mut self,
+        store: crate::windows::Store,
+        subject: &str,
+    ) -> Result<Self, Error> {
+        self.client_certificate = Some(store.certified_key(subject)?);
+        Ok(self)
+    }
+
+    /// Only accepts servers which authenticate with one of the given raw public keys
+    ///
+    /// Each key is a `SubjectPublicKeyInfo` structure, either PEM or DER encoded. The server
+    /// is expected to send its public key in place of a certificate chain, as described in
+    /// [RFC 7250](https://www.rfc-editor.org/rfc/rfc7250). Once any keys are pinned, the
+    /// trusted root certificates are no longer used.
+    pub fn with_server_raw_public_keys<I: IntoIterator<Item = K>, K: certificate::IntoPublicKey>(
+        mut self,
+        keys: I,
+    ) -> Result<Self, Error> {
+        for key in keys {
+            self.server_raw_public_keys.push(key.into_public_key()?.0);
+        }
+        Ok(self)
+    }
+
+    /// Authenticates the client with a raw public key instead of a certificate
+    ///
+    /// The public key is derived from `private_key` and is only sent if the server requests
+    /// client authentication with raw public keys.
+    pub fn with_raw_public_key<PK: certificate::IntoPrivateKey>(
+        mut self,
+        private_key: PK,
+    ) -> Result<Self, Error> {
+        let private_key = private_key.into_private_key()?;
+        self.raw_public_key = Some(raw_public_key::certified_key(private_key)?);
+        Ok(self)
+    }
+
+    /// Only accepts servers whose certificate chain contains one of the pinned public keys
+    ///
+    /// Each pin is the SHA-256 digest of a DER-encoded `SubjectPublicKeyInfo`, as described in
+    /// [RFC 7469](https://www.rfc-editor.org/rfc/rfc7469#section-2.4). The chain must still be
+    /// trusted by one of the root certificates; the pins are checked after it's validated.
+    pub fn with_certificate_pinning<I: IntoIterator<Item = P>, P: AsRef<[u8]>>(
+        mut self,
+        pins: I,
+    ) -> Result<Self, Error> {
+        self.certificate_pins =
+            Pins::new(pins).map_err(|reason| rustls::Error::General(reason.to_string()))?;
+        Ok(self)
+    }
+
     pub fn with_max_cert_chain_depth(self, len: u16) -> Result<Self, Error> {
         // TODO is there a way to configure this?
         let _ = len;
@@ -158,18 +284,62 @@ impl Builder {
     }
 
     pub fn build(self) -> Result<Client, Error> {
-        // TODO load system root store?
-        if self.cert_store.is_empty() {
-            //= https://www.rfc-editor.org/rfc/rfc9001#section-4.4
-            //# A client MUST authenticate the identity of the server.
-            return Err(
-                rustls::Error::General("missing trusted root certificate(s)".to_string()).into(),
-            );
-        }
+        let verifier: Arc<dyn rustls::client::danger::ServerCertVerifier> =
+            if !self.server_raw_public_keys.is_empty() {
+                if !self.certificate_pins.is_empty() {
+                    return Err(rustls::Error::General(
+                        "certificate pins can't be combined with raw public keys".to_string(),
+                    )
+                    .into());
+                }
+                Arc::new(raw_public_key::Verifier::new(self.server_raw_public_keys)?)
+            } else if self.cert_store.is_empty() {
+                // TODO load system root store?
+                //= https://www.rfc-editor.org/rfc/rfc9001#section-4.4
+                //# A client MUST authenticate the identity of the server.
+                return Err(rustls::Error::General(
+                    "missing trusted root certificate(s)".to_string(),
+                )
+                .into());
+            } else {
+                let roots = Arc::new(self.cert_store);
+                let verifier = rustls::client::WebPkiServerVerifier::builder_with_provider(
+                    roots.clone(),
+                    Arc::new(default_crypto_provider()?),
+                )
+                .build()
+                .map_err(|err| rustls::Error::General(err.to_string()))?;
+
+                if self.certificate_pins.is_empty() {
+                    verifier
+                } else {
+                    Arc::new(pinning::ServerVerifier::new(
+                        verifier,
+                        roots,
+                        self.certificate_pins,
+                    )?)
+                }
+            };
+
+        let builder = default_config_builder()?
+            .dangerous()
+            .with_custom_certificate_verifier(verifier.clone());
 
-        let mut config = default_config_builder()?
-            .with_root_certificates(self.cert_store)
-            .with_no_client_auth();
+        let mut config = if let Some(key) = self.raw_public_key {
+            if self.client_certificate.is_some() {
+                return Err(rustls::Error::General(
+                    "a raw public key can't be combined with a client certificate".to_string(),
+                )
+                .into());
+            }
+            builder.with_client_cert_resolver(Arc::new(
+                rustls::client::AlwaysResolvesClientRawPublicKeys::new(key),
+            ))
+        } else if let Some(certificate) = self.client_certificate {
+            builder.with_client_cert_resolver(Arc::new(AlwaysResolvesClientCert(certificate)))
+        } else {
+            builder.with_no_client_auth()
+        };
 
         config.max_fragment_size = None;
         config.alpn_protocols = self.application_protocols;
@@ -178,7 +348,27 @@ impl Builder {
             config.key_log = key_log;
         }
 
-        #[allow(deprecated)]
-        Ok(Client::new(config))
+        Ok(Client {
+            config: Arc::new(config),
+            verifier: Some(verifier),
+            remote_signer: self.remote_signer,
+        })
+    }
+}
+
+#[derive(Debug)]
+struct AlwaysResolvesClientCert(Arc<rustls::sign::CertifiedKey>);
+
+impl rustls::client::ResolvesClientCert for AlwaysResolvesClientCert {
+    fn resolve(
+        &self,
+        _root_hint_subjects: &[&[u8]],
+        _sigschemes: &[rustls::SignatureScheme],
+    ) -> Option<Arc<rustls::sign::CertifiedKey>> {
+        Some(Arc::clone(&self.0))
+    }
+
+    fn has_certs(&self) -> bool {
+        true
     }
 }
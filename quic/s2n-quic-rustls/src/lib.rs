@@ -25,11 +25,17 @@ type Error = Box<dyn std::error::Error + Send + Sync + 'static>;
 
 mod cipher_suite;
 mod error;
+mod pinning;
+mod raw_public_key;
 mod session;
+mod signer;
+mod telemetry;
 
 pub mod certificate;
 pub mod client;
 pub mod server;
+#[cfg(windows)]
+pub mod windows;
 
 pub use client::Client;
 pub use server::Server;
@@ -114,4 +120,105 @@ mod tests {
 
         pair.finish();
     }
+
+    /// Returns the DER-encoded public key for the given PEM private key
+    fn public_key(private_key: &str) -> Vec<u8> {
+        use certificate::IntoPrivateKey;
+        let private_key = private_key.into_private_key().unwrap();
+        raw_public_key::certified_key(private_key).unwrap().cert[0].to_vec()
+    }
+
+    #[test]
+    fn raw_public_key_test() {
+        let mut client = client::Builder::new()
+            .with_server_raw_public_keys([public_key(KEY_PEM)])
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let mut server = server::Builder::new()
+            .with_raw_public_key(KEY_PEM)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let mut pair = tls::testing::Pair::new(&mut server, &mut client, "localhost".into());
+
+        while pair.is_handshaking() {
+            pair.poll(None).unwrap();
+        }
+
+        pair.finish();
+    }
+
+    #[test]
+    fn mutual_raw_public_key_test() {
+        let mut client = client::Builder::new()
+            .with_server_raw_public_keys([public_key(KEY_PEM)])
+            .unwrap()
+            .with_raw_public_key(UNTRUSTED_KEY_PEM)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let mut server = server::Builder::new()
+            .with_raw_public_key(KEY_PEM)
+            .unwrap()
+            .with_client_raw_public_keys([public_key(UNTRUSTED_KEY_PEM)])
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let mut pair = tls::testing::Pair::new(&mut server, &mut client, "localhost".into());
+
+        while pair.is_handshaking() {
+            pair.poll(None).unwrap();
+        }
+
+        pair.finish();
+    }
+
+    #[test]
+    fn raw_public_key_mismatch_test() {
+        let mut client = client::Builder::new()
+            .with_server_raw_public_keys([public_key(UNTRUSTED_KEY_PEM)])
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let mut server = server::Builder::new()
+            .with_raw_public_key(KEY_PEM)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let mut pair = tls::testing::Pair::new(&mut server, &mut client, "localhost".into());
+
+        // the server's key isn't pinned by the client
+        while pair.poll(None).is_ok() {
+            assert!(pair.is_handshaking(), "handshake should not succeed");
+        }
+    }
+
+    #[test]
+    fn raw_public_key_with_certificate_test() {
+        let result = server::Builder::new()
+            .with_certificate(CERT_PEM, KEY_PEM)
+            .unwrap()
+            .with_raw_public_key(KEY_PEM)
+            .unwrap()
+            .build();
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    #[cfg(windows)]
+    fn windows_root_certificates_test() {
+        let _client = client::Builder::new()
+            .with_windows_root_certificates(windows::Store::root())
+            .unwrap()
+            .build()
+            .unwrap();
+    }
 }
@@ -0,0 +1,232 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Certificate pinning on top of the WebPKI verifiers
+//!
+//! The pins are checked against the path which was built from the peer's certificates to one of
+//! the trusted roots rather than every certificate the peer sent. Otherwise a peer could satisfy
+//! a pin by including a certificate which isn't part of its chain.
+
+use crate::cipher_suite::default_crypto_provider;
+use rustls::{
+    client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier},
+    crypto::WebPkiSupportedAlgorithms,
+    pki_types::{CertificateDer, ServerName, TrustAnchor, UnixTime},
+    server::danger::{ClientCertVerified, ClientCertVerifier},
+    CertificateError, DigitallySignedStruct, DistinguishedName, Error, RootCertStore,
+    SignatureScheme,
+};
+use s2n_quic_crypto::pinning::Pins;
+use std::sync::Arc;
+
+/// Checks the pins against the validated chain
+#[derive(Debug)]
+struct Path {
+    roots: Arc<RootCertStore>,
+    pins: Pins,
+    algorithms: WebPkiSupportedAlgorithms,
+}
+
+impl Path {
+    fn new(roots: Arc<RootCertStore>, pins: Pins) -> Result<Self, Error> {
+        let algorithms = default_crypto_provider()?.signature_verification_algorithms;
+        Ok(Self {
+            roots,
+            pins,
+            algorithms,
+        })
+    }
+
+    fn verify(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        now: UnixTime,
+        usage: webpki::KeyUsage,
+    ) -> Result<(), Error> {
+        let certificate = webpki::EndEntityCert::try_from(end_entity)
+            .map_err(|_| Error::InvalidCertificate(CertificateError::BadEncoding))?;
+
+        // returning an error makes webpki try the other paths to the trusted roots, if any
+        let is_pinned = |path: &webpki::VerifiedPath<'_>| {
+            let mut keys = core::iter::once(path.end_entity().subject_public_key_info()).chain(
+                path.intermediate_certificates()
+                    .map(|certificate| certificate.subject_public_key_info()),
+            );
+
+            if keys.any(|key| self.pins.contains_key(key.as_ref()))
+                || self.pins.contains_key(&anchor_key(path.anchor()))
+            {
+                Ok(())
+            } else {
+                Err(webpki::Error::UnknownIssuer)
+            }
+        };
+
+        certificate
+            .verify_for_usage(
+                self.algorithms.all,
+                &self.roots.roots,
+                intermediates,
+                now,
+                usage,
+                None,
+                Some(&is_pinned),
+            )
+            .map(|_| ())
+            .map_err(|_| {
+                Error::InvalidCertificate(CertificateError::ApplicationVerificationFailure)
+            })
+    }
+}
+
+/// Returns the DER-encoded `SubjectPublicKeyInfo` of a trust anchor
+///
+/// Trust anchors only store the contents of the `SEQUENCE` so the header is added back.
+fn anchor_key(anchor: &TrustAnchor<'_>) -> Vec<u8> {
+    const SEQUENCE: u8 = 0x30;
+
+    let contents = anchor.subject_public_key_info.as_ref();
+    let len = contents.len();
+    let mut key = Vec::with_capacity(len + 4);
+    key.push(SEQUENCE);
+    if len < 0x80 {
+        key.push(len as u8);
+    } else if len <= 0xff {
+        key.extend_from_slice(&[0x81, len as u8]);
+    } else {
+        key.extend_from_slice(&[0x82, (len >> 8) as u8, len as u8]);
+    }
+    key.extend_from_slice(contents);
+    key
+}
+
+/// Checks the server's validated certificate chain against the pinned public keys
+#[derive(Debug)]
+pub struct ServerVerifier {
+    inner: Arc<dyn ServerCertVerifier>,
+    path: Path,
+}
+
+impl ServerVerifier {
+    pub fn new(
+        inner: Arc<dyn ServerCertVerifier>,
+        roots: Arc<RootCertStore>,
+        pins: Pins,
+    ) -> Result<Self, Error> {
+        let path = Path::new(roots, pins)?;
+        Ok(Self { inner, path })
+    }
+}
+
+impl ServerCertVerifier for ServerVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> Result<ServerCertVerified, Error> {
+        let verified = self.inner.verify_server_cert(
+            end_entity,
+            intermediates,
+            server_name,
+            ocsp_response,
+            now,
+        )?;
+        self.path.verify(
+            end_entity,
+            intermediates,
+            now,
+            webpki::KeyUsage::server_auth(),
+        )?;
+        Ok(verified)
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, Error> {
+        self.inner.verify_tls12_signature(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, Error> {
+        self.inner.verify_tls13_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.inner.supported_verify_schemes()
+    }
+}
+
+/// Checks the client's validated certificate chain against the pinned public keys
+#[derive(Debug)]
+pub struct ClientVerifier {
+    inner: Arc<dyn ClientCertVerifier>,
+    path: Path,
+}
+
+impl ClientVerifier {
+    pub fn new(
+        inner: Arc<dyn ClientCertVerifier>,
+        roots: Arc<RootCertStore>,
+        pins: Pins,
+    ) -> Result<Self, Error> {
+        let path = Path::new(roots, pins)?;
+        Ok(Self { inner, path })
+    }
+}
+
+impl ClientCertVerifier for ClientVerifier {
+    fn root_hint_subjects(&self) -> &[DistinguishedName] {
+        self.inner.root_hint_subjects()
+    }
+
+    fn verify_client_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        now: UnixTime,
+    ) -> Result<ClientCertVerified, Error> {
+        let verified = self
+            .inner
+            .verify_client_cert(end_entity, intermediates, now)?;
+        self.path.verify(
+            end_entity,
+            intermediates,
+            now,
+            webpki::KeyUsage::client_auth(),
+        )?;
+        Ok(verified)
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, Error> {
+        self.inner.verify_tls12_signature(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, Error> {
+        self.inner.verify_tls13_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.inner.supported_verify_schemes()
+    }
+}
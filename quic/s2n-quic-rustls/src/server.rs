@@ -1,11 +1,18 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{certificate, cipher_suite::default_crypto_provider, session::Session, Error};
+use crate::{
+    certificate, cipher_suite::default_crypto_provider, pinning, raw_public_key, session::Session,
+    signer, telemetry, Error,
+};
 use rustls::{crypto::aws_lc_rs, ConfigBuilder, ServerConfig, WantsVerifier};
 use s2n_codec::EncoderValue;
-use s2n_quic_core::{application::ServerName, crypto::tls};
-use std::sync::Arc;
+use s2n_quic_core::{
+    application::ServerName,
+    crypto::tls::{self, ocsp, signer::Signer},
+};
+use s2n_quic_crypto::pinning::Pins;
+use std::sync::{Arc, Mutex};
 
 /// Create a QUIC server specific [rustls::ConfigBuilder].
 ///
@@ -19,6 +26,8 @@ fn default_config_builder() -> Result<ConfigBuilder<ServerConfig, WantsVerifier>
 #[derive(Clone)]
 pub struct Server {
     config: Arc<ServerConfig>,
+    /// Whether the handshake may block on a remote signer
+    remote_signer: bool,
 }
 
 impl Server {
@@ -31,9 +40,7 @@ impl Server {
     /// - QUIC compliant ciphersuites
     #[deprecated = "client and server builders should be used instead"]
     pub fn new(config: ServerConfig) -> Self {
-        Self {
-            config: Arc::new(config),
-        }
+        Self::from(Arc::new(config))
     }
 
     pub fn builder() -> Builder {
@@ -59,7 +66,10 @@ impl From<ServerConfig> for Server {
 // TODO this should be removed after removing deprecated re-exports
 impl From<Arc<ServerConfig>> for Server {
     fn from(config: Arc<ServerConfig>) -> Self {
-        Self { config }
+        Self {
+            config,
+            remote_signer: false,
+        }
     }
 }
 
@@ -74,14 +84,16 @@ impl tls::Endpoint for Server {
         //# Endpoints MUST send the quic_transport_parameters extension;
         let transport_parameters = transport_parameters.encode_to_vec();
 
+        let telemetry = telemetry::Recorder::default();
+
         let session = rustls::quic::ServerConnection::new(
-            self.config.clone(),
+            telemetry.server_config(&self.config),
             crate::QUIC_VERSION,
             transport_parameters,
         )
         .expect("could not create rustls server session");
 
-        Session::new(session.into(), None)
+        Session::new(session.into(), None, telemetry, self.remote_signer)
     }
 
     fn new_client_session<Params: EncoderValue>(
@@ -99,6 +111,13 @@ impl tls::Endpoint for Server {
 
 pub struct Builder {
     cert_resolver: Option<Arc<dyn rustls::server::ResolvesServerCert>>,
+    sni_certificates: Vec<(String, Arc<rustls::sign::CertifiedKey>)>,
+    raw_public_key: Option<Arc<rustls::sign::CertifiedKey>>,
+    ocsp: Option<Arc<dyn ocsp::Source>>,
+    remote_signer: bool,
+    client_raw_public_keys: Vec<rustls::pki_types::SubjectPublicKeyInfoDer<'static>>,
+    client_cert_store: rustls::RootCertStore,
+    certificate_pins: Pins,
     application_protocols: Vec<Vec<u8>>,
     key_log: Option<Arc<dyn rustls::KeyLog>>,
     prefer_server_cipher_suite_order: bool,
@@ -114,6 +133,13 @@ impl Builder {
     pub fn new() -> Self {
         Self {
             cert_resolver: None,
+            sni_certificates: Vec::new(),
+            raw_public_key: None,
+            ocsp: None,
+            remote_signer: false,
+            client_raw_public_keys: Vec::new(),
+            client_cert_store: rustls::RootCertStore::empty(),
+            certificate_pins: Pins::default(),
             application_protocols: vec![b"h3".to_vec()],
             key_log: None,
             prefer_server_cipher_suite_order: true,
@@ -133,6 +159,145 @@ impl Builder {
         Ok(self)
     }
 
+    /// Authenticates with `certificate` and offloads handshake signatures to `signer`
+    ///
+    /// This allows the private key to be held outside of the process, for example in an HSM or
+    /// KMS. rustls doesn't support asynchronous signing, so the client's handshake messages are
+    /// processed on a separate thread which waits for the signature, rather than the endpoint's.
+    pub fn with_remote_signer<C: certificate::IntoCertificate, S: Signer>(
+        mut self,
+        certificate: C,
+        signer: S,
+    ) -> Result<Self, Error> {
+        let certificate = certificate.into_certificate()?;
+        let key = signer::certified_key(certificate, signer);
+        self.cert_resolver = Some(Arc::new(AlwaysResolvesChain(key)));
+        self.remote_signer = true;
+        Ok(self)
+    }
+
+    /// Uses a certificate from the given Windows certificate store
+    ///
+    /// The first certificate whose subject contains `subject` and has an associated private
+    /// key is used. The key is never exported from the store, so keys which are marked as
+    /// non-exportable are supported.
+    #[cfg(windows)]
+    pub fn with_windows_certificate(
+        mut self,
+        store: crate::windows::Store,
+        subject: &str,
+    ) -> Result<Self, Error> {
+        let key = store.certified_key(subject)?;
+        self.cert_resolver = Some(Arc::new(AlwaysResolvesChain(key)));
+        Ok(self)
+    }
+
+    /// Adds a certificate which is selected when the client's SNI matches `server_name`
+    ///
+    /// The `server_name` can either be an exact name (e.g. `example.com`) or a wildcard
+    /// name (e.g. `*.example.com`). Patterns are checked in the order they were added.
+    ///
+    /// If the client doesn't send a server name, or none of the patterns match, the
+    /// certificate configured with [`Self::with_certificate`] is used. If no such
+    /// certificate was configured, the first SNI certificate is used instead.
+    pub fn with_sni_certificate<
+        N: Into<String>,
+        C: certificate::IntoCertificate,
+        PK: certificate::IntoPrivateKey,
+    >(
+        mut self,
+        server_name: N,
+        certificate: C,
+        private_key: PK,
+    ) -> Result<Self, Error> {
+        let certificate = certificate.into_certificate()?;
+        let private_key = private_key.into_private_key()?;
+        let key = certified_key(certificate, private_key)?;
+        self.sni_certificates.push((server_name.into(), key));
+        Ok(self)
+    }
+
+    /// Staples an OCSP response to the default certificate
+    ///
+    /// The `response` must be a DER-encoded OCSP response for the certificate configured with
+    /// [`Self::with_certificate`]. It's only sent to clients which request it.
+    pub fn with_ocsp_response<R: AsRef<[u8]>>(self, response: R) -> Result<Self, Error> {
+        self.with_ocsp_source(ocsp::Fixed::new(response.as_ref()))
+    }
+
+    /// Staples the OCSP responses returned by `source` to the default and SNI certificates
+    ///
+    /// The source is checked on every handshake with the name of the selected certificate (see
+    /// [`ocsp::Source::ocsp_response`]), which allows the responses to be refreshed before they
+    /// expire without restarting the server.
+    pub fn with_ocsp_source<S: ocsp::Source>(mut self, source: S) -> Result<Self, Error> {
+        self.ocsp = Some(Arc::new(source));
+        Ok(self)
+    }
+
+    /// Authenticates the server with a raw public key instead of a certificate
+    ///
+    /// The public key is derived from `private_key` and sent to the client in place of a
+    /// certificate chain, as described in [RFC 7250](https://www.rfc-editor.org/rfc/rfc7250).
+    /// Clients must pin the server's public key in order to connect.
+    ///
+    /// This can't be combined with [`Self::with_certificate`] or
+    /// [`Self::with_sni_certificate`].
+    pub fn with_raw_public_key<PK: certificate::IntoPrivateKey>(
+        mut self,
+        private_key: PK,
+    ) -> Result<Self, Error> {
+        let private_key = private_key.into_private_key()?;
+        self.raw_public_key = Some(raw_public_key::certified_key(private_key)?);
+        Ok(self)
+    }
+
+    /// Requires clients to authenticate with one of the given raw public keys
+    ///
+    /// Each key is a `SubjectPublicKeyInfo` structure, either PEM or DER encoded. Clients
+    /// presenting any other key, or a certificate, are rejected.
+    pub fn with_client_raw_public_keys<I: IntoIterator<Item = K>, K: certificate::IntoPublicKey>(
+        mut self,
+        keys: I,
+    ) -> Result<Self, Error> {
+        for key in keys {
+            self.client_raw_public_keys.push(key.into_public_key()?.0);
+        }
+        Ok(self)
+    }
+
+    /// Requires clients to authenticate with a certificate chain trusted by `certificate`
+    ///
+    /// Can be called multiple times to trust several certificates.
+    pub fn with_trusted_certificate<C: certificate::IntoCertificate>(
+        mut self,
+        certificate: C,
+    ) -> Result<Self, Error> {
+        let certificates = certificate.into_certificate()?;
+        let root_certificate = certificates.0.first().ok_or_else(|| {
+            rustls::Error::General("Certificate chain needs to have at least one entry".to_string())
+        })?;
+        self.client_cert_store
+            .add(root_certificate.to_owned())
+            .map_err(|err| rustls::Error::General(err.to_string()))?;
+        Ok(self)
+    }
+
+    /// Only accepts clients whose certificate chain contains one of the pinned public keys
+    ///
+    /// Each pin is the SHA-256 digest of a DER-encoded `SubjectPublicKeyInfo`, as described in
+    /// [RFC 7469](https://www.rfc-editor.org/rfc/rfc7469#section-2.4). The chain must still be
+    /// trusted by one of the certificates passed to [`Self::with_trusted_certificate`]; the pins
+    /// are checked after it's validated.
+    pub fn with_certificate_pinning<I: IntoIterator<Item = P>, P: AsRef<[u8]>>(
+        mut self,
+        pins: I,
+    ) -> Result<Self, Error> {
+        self.certificate_pins =
+            Pins::new(pins).map_err(|reason| rustls::Error::General(reason.to_string()))?;
+        Ok(self)
+    }
+
     #[deprecated = "client and server builders should be used instead"]
     pub fn with_cert_resolver(
         mut self,
@@ -163,9 +328,89 @@ impl Builder {
     }
 
     pub fn build(self) -> Result<Server, Error> {
-        let builder = default_config_builder()?.with_no_client_auth();
+        let builder = default_config_builder()?;
+        let builder = if !self.client_raw_public_keys.is_empty() {
+            if !self.client_cert_store.is_empty() || !self.certificate_pins.is_empty() {
+                return Err(rustls::Error::General(
+                    "client raw public keys can't be combined with trusted certificates"
+                        .to_string(),
+                )
+                .into());
+            }
+            let verifier = raw_public_key::Verifier::new(self.client_raw_public_keys)?;
+            builder.with_client_cert_verifier(Arc::new(verifier))
+        } else if self.client_cert_store.is_empty() {
+            if !self.certificate_pins.is_empty() {
+                return Err(rustls::Error::General(
+                    "certificate pins require a trusted certificate".to_string(),
+                )
+                .into());
+            }
+            builder.with_no_client_auth()
+        } else {
+            let roots = Arc::new(self.client_cert_store);
+            let verifier = rustls::server::WebPkiClientVerifier::builder_with_provider(
+                roots.clone(),
+                Arc::new(default_crypto_provider()?),
+            )
+            .build()
+            .map_err(|err| rustls::Error::General(err.to_string()))?;
 
-        let mut config = if let Some(cert_resolver) = self.cert_resolver {
+            if self.certificate_pins.is_empty() {
+                builder.with_client_cert_verifier(verifier)
+            } else {
+                let verifier =
+                    pinning::ClientVerifier::new(verifier, roots, self.certificate_pins)?;
+                builder.with_client_cert_verifier(Arc::new(verifier))
+            }
+        };
+
+        let cert_resolver = if let Some(key) = self.raw_public_key {
+            if self.cert_resolver.is_some() || !self.sni_certificates.is_empty() {
+                return Err(rustls::Error::General(
+                    "a raw public key can't be combined with certificates".to_string(),
+                )
+                .into());
+            }
+            if self.ocsp.is_some() {
+                return Err(rustls::Error::General(
+                    "an OCSP response can't be combined with a raw public key".to_string(),
+                )
+                .into());
+            }
+            let resolver = rustls::server::AlwaysResolvesServerRawPublicKeys::new(key);
+            Some(Arc::new(resolver) as Arc<dyn rustls::server::ResolvesServerCert>)
+        } else if self.sni_certificates.is_empty() {
+            self.cert_resolver
+        } else {
+            let resolver = SniResolver {
+                certificates: self.sni_certificates.clone(),
+                default: self.cert_resolver,
+            };
+            Some(Arc::new(resolver) as Arc<dyn rustls::server::ResolvesServerCert>)
+        };
+
+        let cert_resolver = match (cert_resolver, self.ocsp) {
+            (Some(resolver), Some(source)) => {
+                let stapled = (0..=self.sni_certificates.len()).map(|_| None).collect();
+                let stapler = OcspStapler {
+                    resolver,
+                    certificates: self.sni_certificates,
+                    source,
+                    stapled: Mutex::new(stapled),
+                };
+                Some(Arc::new(stapler) as Arc<dyn rustls::server::ResolvesServerCert>)
+            }
+            (None, Some(_)) => {
+                return Err(rustls::Error::General(
+                    "an OCSP response requires a certificate".to_string(),
+                )
+                .into());
+            }
+            (resolver, None) => resolver,
+        };
+
+        let mut config = if let Some(cert_resolver) = cert_resolver {
             builder.with_cert_resolver(cert_resolver)
         } else {
             return Err(rustls::Error::General(
@@ -182,11 +427,22 @@ impl Builder {
             config.key_log = key_log;
         }
 
-        #[allow(deprecated)]
-        Ok(Server::new(config))
+        Ok(Server {
+            config: Arc::new(config),
+            remote_signer: self.remote_signer,
+        })
     }
 }
 
+fn certified_key(
+    chain: certificate::Certificate,
+    priv_key: certificate::PrivateKey,
+) -> Result<Arc<rustls::sign::CertifiedKey>, rustls::Error> {
+    let key = aws_lc_rs::sign::any_supported_type(&priv_key.0)
+        .map_err(|_| rustls::Error::General("invalid private key".into()))?;
+    Ok(Arc::new(rustls::sign::CertifiedKey::new(chain.0, key)))
+}
+
 #[derive(Debug)]
 struct AlwaysResolvesChain(Arc<rustls::sign::CertifiedKey>);
 
@@ -195,11 +451,7 @@ impl AlwaysResolvesChain {
         chain: certificate::Certificate,
         priv_key: certificate::PrivateKey,
     ) -> Result<Self, rustls::Error> {
-        let key = aws_lc_rs::sign::any_supported_type(&priv_key.0)
-            .map_err(|_| rustls::Error::General("invalid private key".into()))?;
-        Ok(Self(Arc::new(rustls::sign::CertifiedKey::new(
-            chain.0, key,
-        ))))
+        Ok(Self(certified_key(chain, priv_key)?))
     }
 }
 
@@ -211,3 +463,104 @@ impl rustls::server::ResolvesServerCert for AlwaysResolvesChain {
         Some(Arc::clone(&self.0))
     }
 }
+
+/// Selects a certificate based on the server name sent by the client
+#[derive(Debug)]
+struct SniResolver {
+    certificates: Vec<(String, Arc<rustls::sign::CertifiedKey>)>,
+    default: Option<Arc<dyn rustls::server::ResolvesServerCert>>,
+}
+
+impl rustls::server::ResolvesServerCert for SniResolver {
+    fn resolve(
+        &self,
+        client_hello: rustls::server::ClientHello,
+    ) -> Option<Arc<rustls::sign::CertifiedKey>> {
+        if let Some(server_name) = client_hello.server_name().map(ServerName::from) {
+            let key = self
+                .certificates
+                .iter()
+                .find(|(pattern, _)| server_name.matches(pattern));
+
+            if let Some((_, key)) = key {
+                return Some(Arc::clone(key));
+            }
+        }
+
+        if let Some(default) = self.default.as_ref() {
+            return default.resolve(client_hello);
+        }
+
+        // fall back to the first SNI certificate if a default one wasn't configured
+        self.certificates.first().map(|(_, key)| Arc::clone(key))
+    }
+}
+
+/// Staples the current OCSP response to the certificate selected by `resolver`
+struct OcspStapler {
+    resolver: Arc<dyn rustls::server::ResolvesServerCert>,
+    /// The SNI certificates, which are used to look up the name of the selected certificate
+    certificates: Vec<(String, Arc<rustls::sign::CertifiedKey>)>,
+    source: Arc<dyn ocsp::Source>,
+    /// The last response stapled to the default certificate, followed by the last response
+    /// stapled to each SNI certificate, so a new key is only created when a response changes
+    stapled: Mutex<Vec<Option<Stapled>>>,
+}
+
+struct Stapled {
+    response: Arc<[u8]>,
+    key: Arc<rustls::sign::CertifiedKey>,
+    stapled_key: Arc<rustls::sign::CertifiedKey>,
+}
+
+impl core::fmt::Debug for OcspStapler {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("OcspStapler")
+            .field("resolver", &self.resolver)
+            .finish_non_exhaustive()
+    }
+}
+
+impl rustls::server::ResolvesServerCert for OcspStapler {
+    fn resolve(
+        &self,
+        client_hello: rustls::server::ClientHello,
+    ) -> Option<Arc<rustls::sign::CertifiedKey>> {
+        let key = self.resolver.resolve(client_hello)?;
+
+        // any key which isn't one of the SNI certificates came from the default resolver
+        let sni_index = self
+            .certificates
+            .iter()
+            .position(|(_, sni_key)| Arc::ptr_eq(sni_key, &key));
+        let server_name = sni_index.map(|index| self.certificates[index].0.as_str());
+
+        let Some(response) = self.source.ocsp_response(server_name) else {
+            return Some(key);
+        };
+
+        let mut stapled = self
+            .stapled
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        let stapled = &mut stapled[sni_index.map_or(0, |index| index + 1)];
+
+        if let Some(stapled) = stapled.as_ref() {
+            if Arc::ptr_eq(&stapled.response, &response) && Arc::ptr_eq(&stapled.key, &key) {
+                return Some(stapled.stapled_key.clone());
+            }
+        }
+
+        let mut stapled_key = rustls::sign::CertifiedKey::clone(&key);
+        stapled_key.ocsp = Some(response.to_vec());
+        let stapled_key = Arc::new(stapled_key);
+
+        *stapled = Some(Stapled {
+            response,
+            key,
+            stapled_key: stapled_key.clone(),
+        });
+
+        Some(stapled_key)
+    }
+}
@@ -1,20 +1,35 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::cipher_suite::{
-    HeaderProtectionKey, HeaderProtectionKeys, OneRttKey, PacketKey, PacketKeys,
+use crate::{
+    cipher_suite::{HeaderProtectionKey, HeaderProtectionKeys, OneRttKey, PacketKey, PacketKeys},
+    telemetry,
 };
 use bytes::Bytes;
-use core::{fmt, fmt::Debug, task::Poll};
+use core::{
+    fmt,
+    fmt::Debug,
+    task::{Poll, Waker},
+};
 use rustls::quic::{self, Connection};
 use s2n_quic_core::{
     application::ServerName,
     crypto::{self, tls, tls::CipherSuite},
     transport,
 };
+use std::{
+    sync::{Arc, Mutex},
+    thread,
+};
 
 pub struct Session {
-    connection: Connection,
+    /// The rustls connection, which is taken while handshake messages are processed on another
+    /// thread
+    connection: Option<Connection>,
+    /// Set when handshake messages are processed off of the endpoint's thread
+    receiving: Option<Arc<Receiving>>,
+    /// Whether the handshake may block on a remote signer
+    remote_signer: bool,
     rx_phase: HandshakePhase,
     tx_phase: HandshakePhase,
     emitted_zero_rtt_keys: bool,
@@ -22,6 +37,7 @@ pub struct Session {
     emitted_server_name: bool,
     emitted_application_protocol: bool,
     server_name: Option<ServerName>,
+    telemetry: telemetry::Recorder,
 }
 
 impl tls::TlsSession for Session {
@@ -31,17 +47,22 @@ impl tls::TlsSession for Session {
         context: &[u8],
         output: &mut [u8],
     ) -> Result<(), tls::TlsExportError> {
-        match self
+        let connection = self
             .connection
-            .export_keying_material(output, label, Some(context))
-        {
+            .as_ref()
+            .ok_or_else(tls::TlsExportError::failure)?;
+        match connection.export_keying_material(output, label, Some(context)) {
             Ok(_) => Ok(()),
             Err(_) => Err(tls::TlsExportError::failure()),
         }
     }
 
     fn cipher_suite(&self) -> CipherSuite {
-        if let Some(rustls_cipher_suite) = self.connection.negotiated_cipher_suite() {
+        let cipher_suite = self
+            .connection
+            .as_ref()
+            .and_then(|connection| connection.negotiated_cipher_suite());
+        if let Some(rustls_cipher_suite) = cipher_suite {
             match rustls_cipher_suite.suite() {
                 rustls::CipherSuite::TLS13_AES_128_GCM_SHA256 => {
                     CipherSuite::TLS_AES_128_GCM_SHA256
@@ -63,7 +84,8 @@ impl tls::TlsSession for Session {
         let err = tls::ChainError::failure();
         Ok(self
             .connection
-            .peer_certificates()
+            .as_ref()
+            .and_then(|connection| connection.peer_certificates())
             .ok_or(err)?
             .iter()
             .map(|v| v.to_vec())
@@ -81,9 +103,16 @@ impl fmt::Debug for Session {
 }
 
 impl Session {
-    pub fn new(connection: Connection, server_name: Option<ServerName>) -> Self {
+    pub fn new(
+        connection: Connection,
+        server_name: Option<ServerName>,
+        telemetry: telemetry::Recorder,
+        remote_signer: bool,
+    ) -> Self {
         Self {
-            connection,
+            connection: Some(connection),
+            receiving: None,
+            remote_signer,
             rx_phase: Default::default(),
             tx_phase: Default::default(),
             emitted_zero_rtt_keys: false,
@@ -91,37 +120,92 @@ impl Session {
             emitted_server_name: false,
             emitted_application_protocol: false,
             server_name,
+            telemetry,
         }
     }
 
-    fn receive(&mut self, crypto_data: &[u8]) -> Result<(), transport::Error> {
+    #[inline]
+    fn connection(&self) -> &Connection {
+        self.connection
+            .as_ref()
+            .expect("the connection is only taken while receiving")
+    }
+
+    #[inline]
+    fn connection_mut(&mut self) -> &mut Connection {
         self.connection
-            .read_hs(crypto_data)
-            .map_err(crate::error::reason)
-            .map_err(|reason| {
-                //= https://www.rfc-editor.org/rfc/rfc9001#section-4.8
-                //# QUIC is only able to convey an alert level of "fatal".  In TLS 1.3,
-                //# the only existing uses for the "warning" level are to signal
-                //# connection close; see Section 6.1 of [TLS13].  As QUIC provides
-                //# alternative mechanisms for connection termination and the TLS
-                //# connection is only closed if an error is encountered, a QUIC endpoint
-                //# MUST treat any alert from TLS as if it were at the "fatal" level.
-
-                // According to the rustls docs, `alert` only returns fatal alerts:
-                // > https://docs.rs/rustls/0.19.0/rustls/quic/trait.QuicExt#tymethod.get_alert
-                // > Emit the TLS description code of a fatal alert, if one has arisen.
-
-                self.connection
-                    .alert()
-                    .map(|alert| {
-                        // Explicitly annotate the type to detect if rustls starts
-                        // returning a large array
-                        let code: [u8; 1] = alert.to_array();
-                        let code = code[0];
-                        tls::Error { code, reason }
-                    })
-                    .unwrap_or(tls::Error::INTERNAL_ERROR)
-            })?;
+            .as_mut()
+            .expect("the connection is only taken while receiving")
+    }
+
+    fn receive(&mut self, crypto_data: &[u8]) -> Result<(), transport::Error> {
+        let result = self.connection_mut().read_hs(crypto_data);
+        self.on_received(result)
+    }
+
+    /// Processes `crypto_data` on another thread, waking `waker` once it's done
+    ///
+    /// rustls signs handshake messages while processing the peer's flight and doesn't support
+    /// asynchronous signing. Moving the processing off of the endpoint's thread keeps a remote
+    /// signer from blocking every other connection.
+    fn receive_offloaded(&mut self, crypto_data: Bytes, waker: Waker) {
+        let mut connection = self
+            .connection
+            .take()
+            .expect("the connection is only taken while receiving");
+        let receiving = Arc::new(Receiving::default());
+        self.receiving = Some(receiving.clone());
+
+        thread::Builder::new()
+            .name("s2n-quic-rustls-signer".into())
+            .spawn(move || {
+                let result = connection.read_hs(&crypto_data);
+                *receiving.received.lock().unwrap() = Some((connection, result));
+                waker.wake();
+            })
+            .expect("could not spawn the signing thread");
+    }
+
+    /// Returns the outcome of processing handshake messages on another thread, if any
+    fn poll_receiving(&mut self) -> Poll<Result<(), transport::Error>> {
+        let Some(receiving) = self.receiving.as_ref() else {
+            return Poll::Ready(Ok(()));
+        };
+
+        let Some((connection, result)) = receiving.received.lock().unwrap().take() else {
+            return Poll::Pending;
+        };
+
+        self.receiving = None;
+        self.connection = Some(connection);
+        Poll::Ready(self.on_received(result))
+    }
+
+    fn on_received(&mut self, result: Result<(), rustls::Error>) -> Result<(), transport::Error> {
+        result.map_err(crate::error::reason).map_err(|reason| {
+            //= https://www.rfc-editor.org/rfc/rfc9001#section-4.8
+            //# QUIC is only able to convey an alert level of "fatal".  In TLS 1.3,
+            //# the only existing uses for the "warning" level are to signal
+            //# connection close; see Section 6.1 of [TLS13].  As QUIC provides
+            //# alternative mechanisms for connection termination and the TLS
+            //# connection is only closed if an error is encountered, a QUIC endpoint
+            //# MUST treat any alert from TLS as if it were at the "fatal" level.
+
+            // According to the rustls docs, `alert` only returns fatal alerts:
+            // > https://docs.rs/rustls/0.19.0/rustls/quic/trait.QuicExt#tymethod.get_alert
+            // > Emit the TLS description code of a fatal alert, if one has arisen.
+
+            self.connection()
+                .alert()
+                .map(|alert| {
+                    // Explicitly annotate the type to detect if rustls starts
+                    // returning a large array
+                    let code: [u8; 1] = alert.to_array();
+                    let code = code[0];
+                    tls::Error { code, reason }
+                })
+                .unwrap_or(tls::Error::INTERNAL_ERROR)
+        })?;
         Ok(())
     }
 
@@ -133,9 +217,11 @@ impl Session {
         //# error of type 0x16d (equivalent to a fatal TLS missing_extension
         //# alert, see Section 4.8).
         let transport_parameters =
-            self.connection.quic_transport_parameters().ok_or_else(|| {
-                tls::Error::MISSING_EXTENSION.with_reason("Missing QUIC transport parameters")
-            })?;
+            self.connection()
+                .quic_transport_parameters()
+                .ok_or_else(|| {
+                    tls::Error::MISSING_EXTENSION.with_reason("Missing QUIC transport parameters")
+                })?;
 
         Ok(tls::ApplicationParameters {
             transport_parameters,
@@ -159,11 +245,11 @@ impl Session {
     //#    Finished                        -------->
     //#    Application Data                <------->       Application Data
     fn application_protocol(&self) -> Option<&[u8]> {
-        self.connection.alpn_protocol()
+        self.connection().alpn_protocol()
     }
 
     fn server_name(&self) -> Option<ServerName> {
-        match &self.connection {
+        match self.connection() {
             Connection::Client(_) => self.server_name.clone(),
             Connection::Server(server) => {
                 server.server_name().map(|server_name| server_name.into())
@@ -176,7 +262,7 @@ impl Session {
             return None;
         }
 
-        let keys = self.connection.zero_rtt_keys()?;
+        let keys = self.connection().zero_rtt_keys()?;
         self.emitted_zero_rtt_keys = true;
         Some(keys)
     }
@@ -188,7 +274,7 @@ impl Session {
         &mut self,
         context: &mut C,
     ) -> Poll<Result<(), transport::Error>> {
-        if self.tx_phase == HandshakePhase::Application && !self.connection.is_handshaking() {
+        if self.tx_phase == HandshakePhase::Application && !self.connection().is_handshaking() {
             // attempt to emit server_name and application_protocol events prior to completing the
             // handshake
             self.emit_events(context)?;
@@ -214,6 +300,13 @@ impl Session {
         &mut self,
         context: &mut C,
     ) -> Poll<Result<(), transport::Error>> {
+        // finish processing any messages which were handed off to another thread
+        if self.receiving.is_some() {
+            let result = core::task::ready!(self.poll_receiving());
+            self.telemetry.drain(context);
+            result?;
+        }
+
         // Tracks if we have attempted to receive data at least once
         let mut has_tried_receive = false;
 
@@ -226,7 +319,16 @@ impl Session {
 
             // receive anything in the incoming buffer
             if let Some(crypto_data) = crypto_data {
-                self.receive(&crypto_data)?;
+                if self.remote_signer {
+                    self.receive_offloaded(crypto_data, context.waker().clone());
+                    return Poll::Pending;
+                }
+
+                let result = self.receive(&crypto_data);
+                // report the operations performed while processing the data, even if it failed,
+                // so they aren't attributed to another session
+                self.telemetry.drain(context);
+                result?;
             } else if has_tried_receive {
                 return self.poll_complete_handshake(context);
                 // If there's nothing to receive then we're done for now
@@ -263,7 +365,7 @@ impl Session {
 
                 let mut transmission_buffer = vec![];
 
-                let key_change = self.connection.write_hs(&mut transmission_buffer);
+                let key_change = self.connection_mut().write_hs(&mut transmission_buffer);
 
                 // if we didn't upgrade the key or transmit anything then we're waiting for
                 // more reads
@@ -282,7 +384,7 @@ impl Session {
 
                 if let Some(key_change) = key_change {
                     let cipher_suite = self
-                        .connection
+                        .connection()
                         .negotiated_cipher_suite()
                         .expect("cipher_suite should be negotiated")
                         .suite();
@@ -357,10 +459,27 @@ impl tls::Session for Session {
     ) -> Poll<Result<(), transport::Error>> {
         let result = self.poll_impl(context);
         // attempt to emit server_name and application_protocol events prior to possibly
-        // returning with an error
-        self.emit_events(context)?;
+        // returning with an error, unless the connection is processing messages on another thread
+        if self.connection.is_some() {
+            self.emit_events(context)?;
+        }
         result
     }
+
+    fn export_keying_material(
+        &self,
+        label: &[u8],
+        context: &[u8],
+        output: &mut [u8],
+    ) -> Result<(), tls::TlsExportError> {
+        tls::TlsSession::tls_exporter(self, label, context, output)
+    }
+}
+
+/// The outcome of processing handshake messages on another thread
+#[derive(Default)]
+struct Receiving {
+    received: Mutex<Option<(Connection, Result<(), rustls::Error>)>>,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
@@ -0,0 +1,127 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Adapts a [`Signer`] to the rustls [`SigningKey`] interface
+//!
+//! rustls signs handshake messages synchronously, so the calling thread is blocked until the
+//! signature is returned. Sessions with a remote signer process the peer's handshake messages on
+//! a separate thread so this never blocks the endpoint.
+
+use crate::certificate;
+use core::{fmt, future::Future, pin::Pin};
+use rustls::{
+    sign::{self, CertifiedKey, SigningKey},
+    Error, SignatureAlgorithm,
+};
+use s2n_quic_core::crypto::tls::signer::{KeyType, SignatureScheme, Signer};
+use s2n_quic_crypto::digest;
+use std::{
+    sync::Arc,
+    task::{Context, Poll, Wake, Waker},
+    thread,
+};
+
+pub fn certified_key<S: Signer>(
+    certificate: certificate::Certificate,
+    signer: S,
+) -> Arc<CertifiedKey> {
+    let key: Arc<dyn SigningKey> = Arc::new(RemoteKey(Arc::new(signer)));
+    Arc::new(CertifiedKey::new(certificate.0, key))
+}
+
+struct RemoteKey<S>(Arc<S>);
+
+impl<S> fmt::Debug for RemoteKey<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RemoteKey").finish_non_exhaustive()
+    }
+}
+
+impl<S: Signer> SigningKey for RemoteKey<S> {
+    fn choose_scheme(&self, offered: &[rustls::SignatureScheme]) -> Option<Box<dyn sign::Signer>> {
+        let scheme = self
+            .0
+            .key_type()
+            .schemes()
+            .iter()
+            .copied()
+            .find(|scheme| offered.contains(&rustls_scheme(*scheme)))?;
+
+        Some(Box::new(RemoteSigner {
+            signer: self.0.clone(),
+            scheme,
+        }))
+    }
+
+    fn algorithm(&self) -> SignatureAlgorithm {
+        match self.0.key_type() {
+            KeyType::EcdsaP256 | KeyType::EcdsaP384 | KeyType::EcdsaP521 => {
+                SignatureAlgorithm::ECDSA
+            }
+            KeyType::Rsa => SignatureAlgorithm::RSA,
+        }
+    }
+}
+
+struct RemoteSigner<S> {
+    signer: Arc<S>,
+    scheme: SignatureScheme,
+}
+
+impl<S> fmt::Debug for RemoteSigner<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RemoteSigner")
+            .field("scheme", &self.scheme)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S: Signer> sign::Signer for RemoteSigner<S> {
+    fn sign(&self, message: &[u8]) -> Result<Vec<u8>, Error> {
+        let algorithm = match self.scheme {
+            SignatureScheme::EcdsaSha256 | SignatureScheme::RsaPssSha256 => &digest::SHA256,
+            SignatureScheme::EcdsaSha384 | SignatureScheme::RsaPssSha384 => &digest::SHA384,
+            SignatureScheme::EcdsaSha512 | SignatureScheme::RsaPssSha512 => &digest::SHA512,
+        };
+        let digest = digest::digest(algorithm, message);
+
+        block_on(self.signer.sign(self.scheme, digest.as_ref()))
+            .map_err(|err| Error::General(err.to_string()))
+    }
+
+    fn scheme(&self) -> rustls::SignatureScheme {
+        rustls_scheme(self.scheme)
+    }
+}
+
+fn rustls_scheme(scheme: SignatureScheme) -> rustls::SignatureScheme {
+    match scheme {
+        SignatureScheme::EcdsaSha256 => rustls::SignatureScheme::ECDSA_NISTP256_SHA256,
+        SignatureScheme::EcdsaSha384 => rustls::SignatureScheme::ECDSA_NISTP384_SHA384,
+        SignatureScheme::EcdsaSha512 => rustls::SignatureScheme::ECDSA_NISTP521_SHA512,
+        SignatureScheme::RsaPssSha256 => rustls::SignatureScheme::RSA_PSS_SHA256,
+        SignatureScheme::RsaPssSha384 => rustls::SignatureScheme::RSA_PSS_SHA384,
+        SignatureScheme::RsaPssSha512 => rustls::SignatureScheme::RSA_PSS_SHA512,
+    }
+}
+
+/// Polls `future` on the current thread until it completes
+fn block_on<F: Future + Unpin>(mut future: F) -> F::Output {
+    struct Unpark(thread::Thread);
+
+    impl Wake for Unpark {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    let waker = Waker::from(Arc::new(Unpark(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+
+    loop {
+        if let Poll::Ready(output) = Pin::new(&mut future).poll(&mut cx) {
+            return output;
+        }
+        thread::park();
+    }
+}
@@ -0,0 +1,281 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Measures the time rustls spends in handshake operations
+//!
+//! The certificate verifier, signing keys and ticketer are shared by all of the connections using
+//! a config. To attribute each measurement to the right connection, every session gets a copy of
+//! the config with those components wrapped in timers which record into the session's
+//! [`Recorder`]. The recorded operations are drained into the session's [`tls::Context`] after
+//! rustls makes progress.
+
+use core::time::Duration;
+use rustls::{
+    client::{
+        danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier},
+        ResolvesClientCert,
+    },
+    pki_types::{CertificateDer, ServerName, SubjectPublicKeyInfoDer, UnixTime},
+    server::{ClientHello, ProducesTickets, ResolvesServerCert},
+    sign::{self, CertifiedKey},
+    ClientConfig, DigitallySignedStruct, ServerConfig, SignatureAlgorithm, SignatureScheme,
+};
+use s2n_quic_core::crypto::{tls, CryptoSuite};
+use std::{
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+/// The operations measured for a single session
+#[derive(Clone, Debug, Default)]
+pub struct Recorder(Arc<Mutex<Vec<(tls::Operation, Duration)>>>);
+
+impl Recorder {
+    /// Calls `f` and records the amount of time it took
+    #[inline]
+    fn measure<R>(&self, operation: tls::Operation, f: impl FnOnce() -> R) -> R {
+        let start = Instant::now();
+        let result = f();
+        let duration = start.elapsed();
+        if let Ok(mut operations) = self.0.lock() {
+            operations.push((operation, duration));
+        }
+        result
+    }
+
+    /// Reports all of the operations measured since the last call
+    #[inline]
+    pub fn drain<S: CryptoSuite, C: tls::Context<S>>(&self, context: &mut C) {
+        let Ok(mut operations) = self.0.lock() else {
+            return;
+        };
+        for (operation, duration) in operations.drain(..) {
+            context.on_tls_operation(operation, duration);
+        }
+    }
+
+    /// Returns a copy of `config` which records the client's handshake operations
+    ///
+    /// The verifier can't be read back out of a [`ClientConfig`], so certificate validation is
+    /// only measured if the `verifier` it was built with is provided.
+    pub fn client_config(
+        &self,
+        config: &ClientConfig,
+        verifier: Option<&Arc<dyn ServerCertVerifier>>,
+    ) -> Arc<ClientConfig> {
+        let mut config = config.clone();
+        config.client_auth_cert_resolver = Arc::new(ClientCertResolver {
+            inner: config.client_auth_cert_resolver.clone(),
+            recorder: self.clone(),
+        });
+        if let Some(verifier) = verifier {
+            config
+                .dangerous()
+                .set_certificate_verifier(Arc::new(CertificateVerifier {
+                    inner: verifier.clone(),
+                    recorder: self.clone(),
+                }));
+        }
+        Arc::new(config)
+    }
+
+    /// Returns a copy of `config` which records the server's handshake operations
+    pub fn server_config(&self, config: &ServerConfig) -> Arc<ServerConfig> {
+        let mut config = config.clone();
+        config.cert_resolver = Arc::new(ServerCertResolver {
+            inner: config.cert_resolver.clone(),
+            recorder: self.clone(),
+        });
+        config.ticketer = Arc::new(Ticketer {
+            inner: config.ticketer.clone(),
+            recorder: self.clone(),
+        });
+        Arc::new(config)
+    }
+
+    /// Replaces the signing key of `key` with one which times each signature
+    fn certified_key(&self, key: &CertifiedKey) -> Arc<CertifiedKey> {
+        let key_signer = SigningKey {
+            inner: key.key.clone(),
+            recorder: self.clone(),
+        };
+        let mut timed = CertifiedKey::new(key.cert.clone(), Arc::new(key_signer));
+        timed.ocsp.clone_from(&key.ocsp);
+        Arc::new(timed)
+    }
+}
+
+/// Times the validation of the server's certificate chain and signature
+#[derive(Debug)]
+struct CertificateVerifier {
+    inner: Arc<dyn ServerCertVerifier>,
+    recorder: Recorder,
+}
+
+impl ServerCertVerifier for CertificateVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        self.recorder
+            .measure(tls::Operation::CertificateValidation, || {
+                self.inner.verify_server_cert(
+                    end_entity,
+                    intermediates,
+                    server_name,
+                    ocsp_response,
+                    now,
+                )
+            })
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.recorder
+            .measure(tls::Operation::SignatureVerification, || {
+                self.inner.verify_tls12_signature(message, cert, dss)
+            })
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.recorder
+            .measure(tls::Operation::SignatureVerification, || {
+                self.inner.verify_tls13_signature(message, cert, dss)
+            })
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.inner.supported_verify_schemes()
+    }
+
+    fn requires_raw_public_keys(&self) -> bool {
+        self.inner.requires_raw_public_keys()
+    }
+}
+
+#[derive(Debug)]
+struct SigningKey {
+    inner: Arc<dyn sign::SigningKey>,
+    recorder: Recorder,
+}
+
+impl sign::SigningKey for SigningKey {
+    fn choose_scheme(&self, offered: &[SignatureScheme]) -> Option<Box<dyn sign::Signer>> {
+        let inner = self.inner.choose_scheme(offered)?;
+        Some(Box::new(Signer {
+            inner,
+            recorder: self.recorder.clone(),
+        }))
+    }
+
+    fn public_key(&self) -> Option<SubjectPublicKeyInfoDer<'_>> {
+        self.inner.public_key()
+    }
+
+    fn algorithm(&self) -> SignatureAlgorithm {
+        self.inner.algorithm()
+    }
+}
+
+#[derive(Debug)]
+struct Signer {
+    inner: Box<dyn sign::Signer>,
+    recorder: Recorder,
+}
+
+impl sign::Signer for Signer {
+    fn sign(&self, message: &[u8]) -> Result<Vec<u8>, rustls::Error> {
+        self.recorder
+            .measure(tls::Operation::SignatureGeneration, || {
+                self.inner.sign(message)
+            })
+    }
+
+    fn scheme(&self) -> SignatureScheme {
+        self.inner.scheme()
+    }
+}
+
+/// Times each signature made with the keys returned by the inner resolver
+#[derive(Debug)]
+struct ClientCertResolver {
+    inner: Arc<dyn ResolvesClientCert>,
+    recorder: Recorder,
+}
+
+impl ResolvesClientCert for ClientCertResolver {
+    fn resolve(
+        &self,
+        root_hint_subjects: &[&[u8]],
+        sigschemes: &[SignatureScheme],
+    ) -> Option<Arc<CertifiedKey>> {
+        let key = self.inner.resolve(root_hint_subjects, sigschemes)?;
+        Some(self.recorder.certified_key(&key))
+    }
+
+    fn only_raw_public_keys(&self) -> bool {
+        self.inner.only_raw_public_keys()
+    }
+
+    fn has_certs(&self) -> bool {
+        self.inner.has_certs()
+    }
+}
+
+/// Times each signature made with the keys returned by the inner resolver
+#[derive(Debug)]
+struct ServerCertResolver {
+    inner: Arc<dyn ResolvesServerCert>,
+    recorder: Recorder,
+}
+
+impl ResolvesServerCert for ServerCertResolver {
+    fn resolve(&self, client_hello: ClientHello) -> Option<Arc<CertifiedKey>> {
+        let key = self.inner.resolve(client_hello)?;
+        Some(self.recorder.certified_key(&key))
+    }
+
+    fn only_raw_public_keys(&self) -> bool {
+        self.inner.only_raw_public_keys()
+    }
+}
+
+/// Times the decryption of session tickets
+#[derive(Debug)]
+struct Ticketer {
+    inner: Arc<dyn ProducesTickets>,
+    recorder: Recorder,
+}
+
+impl ProducesTickets for Ticketer {
+    fn enabled(&self) -> bool {
+        self.inner.enabled()
+    }
+
+    fn lifetime(&self) -> u32 {
+        self.inner.lifetime()
+    }
+
+    fn encrypt(&self, plain: &[u8]) -> Option<Vec<u8>> {
+        self.inner.encrypt(plain)
+    }
+
+    fn decrypt(&self, cipher: &[u8]) -> Option<Vec<u8>> {
+        self.recorder.measure(tls::Operation::TicketDecryption, || {
+            self.inner.decrypt(cipher)
+        })
+    }
+}
@@ -0,0 +1,132 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Loads trust roots and identities from the Windows certificate store
+//!
+//! Private keys are never exported from the store. Instead, signing operations are performed
+//! by CNG, which allows keys that are marked as non-exportable or backed by hardware to be used.
+
+use crate::Error;
+use rustls::{
+    pki_types::CertificateDer,
+    sign::{CertifiedKey, SigningKey},
+};
+use rustls_cng::{
+    signer::CngSigningKey,
+    store::{CertStore, CertStoreType},
+};
+use std::sync::Arc;
+
+/// The location of a Windows certificate store
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Location {
+    /// Stores associated with the current user
+    CurrentUser,
+    /// Stores shared by all users on the local machine
+    LocalMachine,
+}
+
+/// A Windows certificate store
+#[derive(Clone, Debug)]
+pub struct Store {
+    location: Location,
+    name: String,
+}
+
+impl Store {
+    /// Selects the store with the given name for the current user
+    pub fn current_user<N: Into<String>>(name: N) -> Self {
+        Self {
+            location: Location::CurrentUser,
+            name: name.into(),
+        }
+    }
+
+    /// Selects the store with the given name for the local machine
+    pub fn local_machine<N: Into<String>>(name: N) -> Self {
+        Self {
+            location: Location::LocalMachine,
+            name: name.into(),
+        }
+    }
+
+    /// Selects the trusted root certification authorities of the local machine
+    pub fn root() -> Self {
+        Self::local_machine("Root")
+    }
+
+    /// Selects the personal certificates of the current user
+    pub fn my() -> Self {
+        Self::current_user("My")
+    }
+
+    pub fn location(&self) -> Location {
+        self.location
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn open(&self) -> Result<CertStore, Error> {
+        let location = match self.location {
+            Location::CurrentUser => CertStoreType::CurrentUser,
+            Location::LocalMachine => CertStoreType::LocalMachine,
+        };
+        CertStore::open(location, &self.name).map_err(|err| {
+            rustls::Error::General(format!(
+                "could not open certificate store {:?}: {err}",
+                self.name
+            ))
+            .into()
+        })
+    }
+
+    /// Returns all of the certificates in the store
+    pub(crate) fn certificates(&self) -> Result<Vec<CertificateDer<'static>>, Error> {
+        let store = self.open()?;
+        let contexts = store
+            .find_all()
+            .map_err(|err| rustls::Error::General(err.to_string()))?;
+
+        Ok(contexts
+            .iter()
+            .map(|context| CertificateDer::from(context.as_der().to_vec()))
+            .collect())
+    }
+
+    /// Returns the certificate chain and CNG-backed signing key of the first certificate whose
+    /// subject contains `subject`
+    pub(crate) fn certified_key(&self, subject: &str) -> Result<Arc<CertifiedKey>, Error> {
+        let store = self.open()?;
+        let contexts = store
+            .find_by_subject_str(subject)
+            .map_err(|err| rustls::Error::General(err.to_string()))?;
+
+        // only consider certificates that have an associated private key
+        let (context, key) = contexts
+            .into_iter()
+            .find_map(|context| {
+                let key = context.acquire_key().ok()?;
+                Some((context, key))
+            })
+            .ok_or_else(|| {
+                rustls::Error::General(format!(
+                    "no certificate with a private key matching {subject:?} in store {:?}",
+                    self.name
+                ))
+            })?;
+
+        let key = CngSigningKey::new(key).map_err(|err| rustls::Error::General(err.to_string()))?;
+        let key: Arc<dyn SigningKey> = Arc::new(key);
+
+        let chain = context
+            .as_chain_der()
+            .map_err(|err| rustls::Error::General(err.to_string()))?
+            .into_iter()
+            .map(CertificateDer::from)
+            .collect();
+
+        Ok(Arc::new(CertifiedKey::new(chain, key)))
+    }
+}
@@ -1,6 +1,7 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::telemetry;
 use bytes::{Bytes, BytesMut};
 use core::{ffi::c_void, marker::PhantomData};
 use s2n_quic_core::{
@@ -111,6 +112,12 @@ where
                 .unwrap();
             connection.set_waker(None).unwrap();
 
+            // report the operations performed while s2n-tls was making progress
+            let context = &mut *self.context;
+            telemetry::drain(connection, |operation, duration| {
+                context.on_tls_operation(operation, duration)
+            });
+
             // Flush the send buffer before returning to the connection
             self.flush();
             // attempt to emit server name after making progress and prior to error handling
@@ -26,7 +26,6 @@ impl Format {
 pub(crate) enum Format {
     Pem(Bytes),
     Der(Bytes),
-    #[allow(dead_code)] // Only used if private key offloading supported
     None,
 }
 
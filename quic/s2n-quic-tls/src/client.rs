@@ -3,17 +3,24 @@
 
 use crate::{
     certificate::{IntoCertificate, IntoPrivateKey},
+    external_psk,
     keylog::KeyLogHandle,
     params::Params,
     session::Session,
+    signer::Offload,
     ConfigLoader,
 };
 use s2n_codec::EncoderValue;
-use s2n_quic_core::{application::ServerName, crypto::tls, endpoint};
+use s2n_quic_core::{
+    application::ServerName,
+    crypto::tls::{self, signer::Signer},
+    endpoint,
+};
+use s2n_quic_crypto::pinning::Pins;
 use s2n_tls::{
     callbacks::VerifyHostNameCallback,
     config::{self, Config},
-    enums::ClientAuthType,
+    enums::{ClientAuthType, PskHmac},
     error::Error,
 };
 use std::sync::Arc;
@@ -23,6 +30,8 @@ pub struct Client<L: ConfigLoader = Config> {
     #[allow(dead_code)] // we need to hold on to the handle to ensure it is cleaned up correctly
     keylog: Option<KeyLogHandle>,
     params: Params,
+    psks: external_psk::Psks,
+    certificate_pins: Pins,
 }
 
 impl Client {
@@ -44,6 +53,8 @@ impl<L: ConfigLoader> Client<L> {
             loader,
             keylog: None,
             params: Default::default(),
+            psks: Default::default(),
+            certificate_pins: Default::default(),
         }
     }
 }
@@ -66,6 +77,8 @@ impl<L: ConfigLoader> ConfigLoader for Client<L> {
 pub struct Builder {
     config: config::Builder,
     keylog: Option<KeyLogHandle>,
+    psks: external_psk::List,
+    certificate_pins: Pins,
 }
 
 impl Default for Builder {
@@ -79,6 +92,8 @@ impl Default for Builder {
         Self {
             config,
             keylog: None,
+            psks: Default::default(),
+            certificate_pins: Default::default(),
         }
     }
 }
@@ -146,6 +161,27 @@ impl Builder {
         Ok(self)
     }
 
+    /// Offers `certificate` to the server and offloads handshake signatures to `signer`
+    ///
+    /// This is equivalent to [`Self::with_client_identity`] but allows the private key to be
+    /// held outside of the process, for example in an HSM or KMS.
+    pub fn with_client_identity_signer<C: IntoCertificate, S: Signer>(
+        mut self,
+        certificate: C,
+        signer: S,
+    ) -> Result<Self, Error> {
+        let certificate = certificate.into_certificate()?;
+        self.config.load_public_pem(
+            certificate
+                .0
+                .as_pem()
+                .expect("pem is currently the only certificate format supported"),
+        )?;
+        self.config.set_private_key_callback(Offload(signer))?;
+        self.config.set_client_auth_type(ClientAuthType::Required)?;
+        Ok(self)
+    }
+
     /// Set the host name verification callback.
     ///
     /// This will be invoked when a server certificate is presented during a TLS
@@ -160,11 +196,46 @@ impl Builder {
         Ok(self)
     }
 
+    /// Only accepts servers whose certificate chain contains one of the pinned public keys
+    ///
+    /// Each pin is the SHA-256 digest of a DER-encoded `SubjectPublicKeyInfo`, as described in
+    /// [RFC 7469](https://www.rfc-editor.org/rfc/rfc7469#section-2.4). The chain must still be
+    /// trusted by the trust store; the pins are checked once the handshake has validated it.
+    pub fn with_certificate_pinning<I: IntoIterator<Item = P>, P: AsRef<[u8]>>(
+        mut self,
+        pins: I,
+    ) -> Result<Self, Error> {
+        self.certificate_pins =
+            Pins::new(pins).map_err(|reason| Error::application(reason.into()))?;
+        Ok(self)
+    }
+
     pub fn with_max_cert_chain_depth(mut self, len: u16) -> Result<Self, Error> {
         self.config.set_max_cert_chain_depth(len)?;
         Ok(self)
     }
 
+    /// Adds an external pre-shared key (PSK) which can authenticate the handshake
+    ///
+    /// When the client and server are configured with a PSK with the same `identity`, the
+    /// handshake is authenticated with the shared `secret` and certificates aren't exchanged. The
+    /// key exchange still uses (EC)DHE so connections have forward secrecy.
+    ///
+    /// The `identity` must be unique, non-empty and at most 65535 bytes. The `secret` must be at
+    /// least 16 bytes and should be generated with a cryptographically secure RNG. The `hmac`
+    /// algorithm restricts the handshake to the cipher suites using the same hash.
+    ///
+    /// PSKs are offered in the order they were added.
+    pub fn with_external_psk(
+        mut self,
+        identity: &[u8],
+        secret: &[u8],
+        hmac: PskHmac,
+    ) -> Result<Self, Error> {
+        self.psks.push(identity, secret, hmac)?;
+        Ok(self)
+    }
+
     pub fn with_key_logging(mut self) -> Result<Self, Error> {
         use crate::keylog::KeyLog;
 
@@ -193,6 +264,8 @@ impl Builder {
             loader: self.config.build()?,
             keylog: self.keylog,
             params: Default::default(),
+            psks: self.psks.build()?,
+            certificate_pins: self.certificate_pins,
         })
     }
 }
@@ -212,9 +285,18 @@ impl<L: ConfigLoader> tls::Endpoint for Client<L> {
         let config = self.loader.load(crate::ConnectionContext {
             server_name: Some(&server_name),
         });
-        self.params.with(params, |params| {
+        let mut session = self.params.with(params, |params| {
             Session::new(endpoint::Type::Client, config, params, Some(server_name)).unwrap()
-        })
+        });
+        if self.psks.append_to(&mut session.connection).is_err() {
+            session.fail(
+                tls::Error::INTERNAL_ERROR
+                    .with_reason("failed to offer the external PSKs")
+                    .into(),
+            );
+        }
+        session.certificate_pins = self.certificate_pins.clone();
+        session
     }
 
     fn max_tag_length(&self) -> usize {
@@ -0,0 +1,153 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! External pre-shared keys
+//!
+//! An external PSK is a secret which is shared between the client and server ahead of time. When
+//! both peers are configured with a PSK with the same identity, the handshake is authenticated
+//! with the PSK instead of certificates. The key exchange still uses (EC)DHE so each connection
+//! has forward secrecy.
+
+use core::fmt;
+use s2n_tls::{connection::Connection, enums::PskHmac as Hmac, error::Error, psk::Psk};
+
+/// The minimum length of a PSK secret
+///
+/// [RFC 9257](https://www.rfc-editor.org/rfc/rfc9257#section-6) requires external PSKs to have at
+/// least 128 bits of entropy.
+const MIN_SECRET_LEN: usize = 16;
+
+/// The maximum length of a PSK identity, as limited by its encoding in the `pre_shared_key`
+/// extension
+const MAX_IDENTITY_LEN: usize = u16::MAX as usize;
+
+#[derive(Clone)]
+pub(crate) struct ExternalPsk {
+    identity: Vec<u8>,
+    secret: Vec<u8>,
+    hmac: Hmac,
+}
+
+impl fmt::Debug for ExternalPsk {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // don't include the secret
+        f.debug_struct("ExternalPsk")
+            .field("identity", &self.identity)
+            .field("hmac", &self.hmac)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ExternalPsk {
+    fn new(identity: &[u8], secret: &[u8], hmac: Hmac) -> Result<Self, Error> {
+        if identity.is_empty() {
+            return Err(invalid("the PSK identity must not be empty"));
+        }
+
+        if identity.len() > MAX_IDENTITY_LEN {
+            return Err(invalid("the PSK identity must not exceed 65535 bytes"));
+        }
+
+        if secret.len() < MIN_SECRET_LEN {
+            return Err(invalid("the PSK secret must be at least 16 bytes"));
+        }
+
+        let psk = Self {
+            identity: identity.to_vec(),
+            secret: secret.to_vec(),
+            hmac,
+        };
+
+        // make sure s2n-tls accepts the PSK before a connection needs it
+        psk.build()?;
+
+        Ok(psk)
+    }
+
+    fn build(&self) -> Result<Psk, Error> {
+        let mut builder = Psk::builder()?;
+        builder.set_identity(&self.identity)?;
+        builder.set_secret(&self.secret)?;
+        builder.set_hmac(self.hmac)?;
+        builder.build()
+    }
+}
+
+/// The external PSKs configured for an endpoint
+#[derive(Clone, Debug, Default)]
+pub(crate) struct List(Vec<ExternalPsk>);
+
+impl List {
+    /// Adds a PSK to the list
+    pub fn push(&mut self, identity: &[u8], secret: &[u8], hmac: Hmac) -> Result<(), Error> {
+        // the peer selects a PSK by its identity so they must be unique
+        if self.0.iter().any(|psk| psk.identity == identity) {
+            return Err(invalid("a PSK with the same identity was already added"));
+        }
+
+        self.0.push(ExternalPsk::new(identity, secret, hmac)?);
+        Ok(())
+    }
+
+    /// Builds the PSKs which are offered on each connection
+    pub fn build(&self) -> Result<Psks, Error> {
+        let psks = self
+            .0
+            .iter()
+            .map(ExternalPsk::build)
+            .collect::<Result<_, _>>()?;
+        Ok(Psks(psks))
+    }
+}
+
+/// The built external PSKs of an endpoint
+#[derive(Default)]
+pub(crate) struct Psks(Vec<Psk>);
+
+impl Psks {
+    /// Offers each PSK on the connection
+    pub fn append_to(&self, connection: &mut Connection) -> Result<(), Error> {
+        for psk in &self.0 {
+            connection.append_psk(psk)?;
+        }
+        Ok(())
+    }
+}
+
+fn invalid(reason: &'static str) -> Error {
+    Error::application(reason.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &[u8] = &[42; MIN_SECRET_LEN];
+
+    #[test]
+    fn validation_test() {
+        let mut list = List::default();
+
+        assert!(list.push(b"", SECRET, Hmac::SHA256).is_err());
+        assert!(list
+            .push(&[1; MAX_IDENTITY_LEN + 1], SECRET, Hmac::SHA256)
+            .is_err());
+        assert!(list.push(b"identity", &SECRET[1..], Hmac::SHA256).is_err());
+
+        list.push(b"identity", SECRET, Hmac::SHA256).unwrap();
+        list.push(b"other", SECRET, Hmac::SHA384).unwrap();
+        assert!(
+            list.push(b"identity", SECRET, Hmac::SHA384).is_err(),
+            "identities must be unique"
+        );
+        assert_eq!(list.0.len(), 2);
+        assert_eq!(list.build().unwrap().0.len(), 2);
+    }
+
+    #[test]
+    fn debug_test() {
+        let psk = ExternalPsk::new(b"identity", SECRET, Hmac::SHA256).unwrap();
+        let debug = format!("{psk:?}");
+        assert!(!debug.contains("42"), "{debug}");
+    }
+}
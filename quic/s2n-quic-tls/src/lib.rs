@@ -47,9 +47,12 @@ impl ConfigLoader for Box<dyn ConfigLoader> {
 }
 
 mod callback;
+mod external_psk;
 mod keylog;
 mod params;
 mod session;
+mod signer;
+mod telemetry;
 
 pub mod certificate;
 pub mod client;
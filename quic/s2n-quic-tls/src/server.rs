@@ -2,31 +2,39 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    certificate::{Format, IntoCertificate, IntoPrivateKey},
+    certificate::{Certificate, Format, IntoCertificate, IntoPrivateKey, PrivateKey},
+    external_psk,
     keylog::KeyLogHandle,
     params::Params,
     session::Session,
+    signer::Offload,
     ConfigLoader,
 };
 use s2n_codec::EncoderValue;
-use s2n_quic_core::{application::ServerName, crypto::tls, endpoint};
-#[cfg(any(test, feature = "unstable_client_hello"))]
-use s2n_tls::callbacks::ClientHelloCallback;
+use s2n_quic_core::{
+    application::ServerName,
+    crypto::tls::{self, ocsp, signer::Signer},
+    endpoint,
+};
+use s2n_quic_crypto::pinning::Pins;
 #[cfg(any(test, feature = "unstable_private_key"))]
 use s2n_tls::callbacks::PrivateKeyCallback;
 use s2n_tls::{
-    callbacks::VerifyHostNameCallback,
+    callbacks::{ClientHelloCallback, ConnectionFuture, VerifyHostNameCallback},
     config::{self, Config},
-    enums::ClientAuthType,
+    connection::Connection,
+    enums::{ClientAuthType, PskHmac},
     error::Error,
 };
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 pub struct Server<L: ConfigLoader = Config> {
     loader: L,
     #[allow(dead_code)] // we need to hold on to the handle to ensure it is cleaned up correctly
     keylog: Option<KeyLogHandle>,
     params: Params,
+    psks: external_psk::Psks,
+    certificate_pins: Pins,
 }
 
 impl Server {
@@ -48,6 +56,8 @@ impl<L: ConfigLoader> Server<L> {
             loader,
             keylog: None,
             params: Default::default(),
+            psks: Default::default(),
+            certificate_pins: Default::default(),
         }
     }
 }
@@ -73,9 +83,22 @@ impl<L: ConfigLoader> From<Server<L>> for Config {
     }
 }
 
+/// A builder setting which is also applied to the configs built for each certificate
+type Setting = Box<dyn Fn(&mut config::Builder) -> Result<(), Error> + Send + Sync>;
+
 pub struct Builder {
     config: config::Builder,
     keylog: Option<KeyLogHandle>,
+    psks: external_psk::List,
+    application_protocols: Vec<Vec<u8>>,
+    settings: Vec<Setting>,
+    has_certificate: bool,
+    certificate: Option<(Certificate, PrivateKey)>,
+    sni_certificates: Vec<(String, Certificate, PrivateKey)>,
+    ocsp_source: Option<Arc<dyn ocsp::Source>>,
+    certificate_pins: Pins,
+    #[cfg(any(test, feature = "unstable_client_hello"))]
+    client_hello_handler: Option<Arc<dyn ClientHelloCallback>>,
 }
 
 impl Default for Builder {
@@ -89,6 +112,16 @@ impl Default for Builder {
         Self {
             config,
             keylog: None,
+            psks: Default::default(),
+            application_protocols: vec![b"h3".to_vec()],
+            settings: Vec::new(),
+            has_certificate: false,
+            certificate: None,
+            sni_certificates: Vec::new(),
+            ocsp_source: None,
+            certificate_pins: Default::default(),
+            #[cfg(any(test, feature = "unstable_client_hello"))]
+            client_hello_handler: None,
         }
     }
 }
@@ -103,7 +136,9 @@ impl Builder {
         mut self,
         handler: T,
     ) -> Result<Self, Error> {
-        self.config.set_client_hello_callback(handler)?;
+        // the handler is installed in `build`, since it may need to be chained with the
+        // certificate resolver
+        self.client_hello_handler = Some(Arc::new(handler));
         Ok(self)
     }
 
@@ -120,7 +155,13 @@ impl Builder {
         mut self,
         protocols: P,
     ) -> Result<Self, Error> {
-        self.config.set_application_protocol_preference(protocols)?;
+        let protocols: Vec<Vec<u8>> = protocols
+            .into_iter()
+            .map(|protocol| protocol.as_ref().to_vec())
+            .collect();
+        self.config
+            .set_application_protocol_preference(&protocols)?;
+        self.application_protocols = protocols;
         Ok(self)
     }
 
@@ -129,16 +170,79 @@ impl Builder {
         certificate: C,
         private_key: PK,
     ) -> Result<Self, Error> {
-        let private_key = private_key.into_private_key()?.0;
-        let certificate = certificate.into_certificate()?.0;
-        let certificate = certificate
-            .as_pem()
-            .expect("pem is currently the only certificate format supported");
-        match private_key {
-            Format::Pem(bytes) => self.config.load_pem(certificate, bytes.as_ref())?,
-            Format::None => self.config.load_public_pem(certificate)?,
-            Format::Der(_) => panic!("der private keys not supported"),
-        };
+        let certificate = certificate.into_certificate()?;
+        let private_key = private_key.into_private_key()?;
+        load_certificate(&mut self.config, &certificate, &private_key)?;
+        self.has_certificate = true;
+        self.certificate = Some((certificate, private_key));
+        Ok(self)
+    }
+
+    /// Authenticates with `certificate` and offloads handshake signatures to `signer`
+    ///
+    /// This allows the private key to be held outside of the process, for example in an HSM or
+    /// KMS. The handshake is suspended until the signature is returned, without blocking the
+    /// endpoint.
+    ///
+    /// Note that offloading is implemented with a private key callback and will replace any
+    /// handler configured with `with_private_key_handler`.
+    pub fn with_remote_signer<C: IntoCertificate, S: Signer>(
+        mut self,
+        certificate: C,
+        signer: S,
+    ) -> Result<Self, Error> {
+        let certificate = certificate.into_certificate()?;
+        load_certificate(&mut self.config, &certificate, &PrivateKey(Format::None))?;
+        self.config.set_private_key_callback(Offload(signer))?;
+        self.has_certificate = true;
+        self.certificate = None;
+        Ok(self)
+    }
+
+    /// Adds a certificate which is selected when the client's SNI matches `server_name`
+    ///
+    /// The `server_name` can either be an exact name (e.g. `example.com`) or a wildcard
+    /// name (e.g. `*.example.com`). Patterns are checked in the order they were added.
+    ///
+    /// If the client doesn't send a server name, or none of the patterns match, the
+    /// certificate configured with [`Self::with_certificate`] is used. If no such
+    /// certificate was configured, the first SNI certificate is used instead.
+    ///
+    /// The config for each certificate inherits the application protocols, trust store, client
+    /// authentication, host name verification and key logging settings of this builder. Changes
+    /// made directly with [`Self::config_mut`] aren't inherited. A handler configured with
+    /// `with_client_hello_handler` is invoked after the certificate has been selected.
+    pub fn with_sni_certificate<N: Into<String>, C: IntoCertificate, PK: IntoPrivateKey>(
+        mut self,
+        server_name: N,
+        certificate: C,
+        private_key: PK,
+    ) -> Result<Self, Error> {
+        let certificate = certificate.into_certificate()?;
+        let private_key = private_key.into_private_key()?;
+        self.sni_certificates
+            .push((server_name.into(), certificate, private_key));
+        Ok(self)
+    }
+
+    /// Staples an OCSP response to the default certificate
+    ///
+    /// The `response` must be a DER-encoded OCSP response for the certificate configured with
+    /// [`Self::with_certificate`]. It's only sent to clients which request it.
+    pub fn with_ocsp_response<R: AsRef<[u8]>>(mut self, response: R) -> Result<Self, Error> {
+        self.config.set_ocsp_data(response.as_ref())?;
+        Ok(self)
+    }
+
+    /// Staples the OCSP responses returned by `source` to the default and SNI certificates
+    ///
+    /// The source is checked on every handshake with the name of the selected certificate (see
+    /// [`ocsp::Source::ocsp_response`]), which allows the responses to be refreshed before they
+    /// expire without restarting the server. A new config is built each time a response
+    /// changes, which inherits the same settings as the configs for SNI certificates (see
+    /// [`Self::with_sni_certificate`]).
+    pub fn with_ocsp_source<S: ocsp::Source>(mut self, source: S) -> Result<Self, Error> {
+        self.ocsp_source = Some(Arc::new(source));
         Ok(self)
     }
 
@@ -150,9 +254,12 @@ impl Builder {
         let certificate = certificate
             .0
             .as_pem()
-            .expect("pem is currently the only certificate format supported");
-        self.config.trust_pem(certificate)?;
-        Ok(self)
+            .expect("pem is currently the only certificate format supported")
+            .to_vec();
+        self.with_setting(move |config| {
+            config.trust_pem(&certificate)?;
+            Ok(())
+        })
     }
 
     /// Clears the default trust store for this client.
@@ -164,14 +271,33 @@ impl Builder {
     /// Note that call ordering matters. The caller should call this
     /// method before making any calls to `with_trusted_certificate()`.
     /// Calling this method after a method that modifies the trust store will clear it.
-    pub fn with_empty_trust_store(mut self) -> Result<Self, Error> {
-        self.config.wipe_trust_store()?;
-        Ok(self)
+    pub fn with_empty_trust_store(self) -> Result<Self, Error> {
+        self.with_setting(|config| {
+            config.wipe_trust_store()?;
+            Ok(())
+        })
     }
 
     /// Configures this server instance to require client authentication (mutual TLS).
-    pub fn with_client_authentication(mut self) -> Result<Self, Error> {
-        self.config.set_client_auth_type(ClientAuthType::Required)?;
+    pub fn with_client_authentication(self) -> Result<Self, Error> {
+        self.with_setting(|config| {
+            config.set_client_auth_type(ClientAuthType::Required)?;
+            Ok(())
+        })
+    }
+
+    /// Only accepts clients whose certificate chain contains one of the pinned public keys
+    ///
+    /// Each pin is the SHA-256 digest of a DER-encoded `SubjectPublicKeyInfo`, as described in
+    /// [RFC 7469](https://www.rfc-editor.org/rfc/rfc7469#section-2.4). Client authentication
+    /// must be enabled with [`Self::with_client_authentication`] and the chain must still be
+    /// trusted by the trust store; the pins are checked once the handshake has validated it.
+    pub fn with_certificate_pinning<I: IntoIterator<Item = P>, P: AsRef<[u8]>>(
+        mut self,
+        pins: I,
+    ) -> Result<Self, Error> {
+        self.certificate_pins =
+            Pins::new(pins).map_err(|reason| Error::application(reason.into()))?;
         Ok(self)
     }
 
@@ -179,11 +305,10 @@ impl Builder {
     /// server instance when a client certificate is presented during the mutual TLS handshake.
     #[deprecated(note = "use `with_verify_host_name_callback` instead")]
     pub fn with_verify_client_certificate_handler<T: 'static + VerifyHostNameCallback>(
-        mut self,
+        self,
         handler: T,
     ) -> Result<Self, Error> {
-        self.config.set_verify_host_callback(handler)?;
-        Ok(self)
+        self.with_verify_host_name_callback(handler)
     }
 
     /// Set the host name verification callback.
@@ -191,10 +316,35 @@ impl Builder {
     /// This will be invoked when a client certificate is presented during a mutual TLS
     /// handshake.
     pub fn with_verify_host_name_callback<T: 'static + VerifyHostNameCallback>(
-        mut self,
+        self,
         handler: T,
     ) -> Result<Self, Error> {
-        self.config.set_verify_host_callback(handler)?;
+        let handler = Arc::new(handler);
+        self.with_setting(move |config| {
+            config.set_verify_host_callback(Shared(handler.clone()))?;
+            Ok(())
+        })
+    }
+
+    /// Adds an external pre-shared key (PSK) which can authenticate the handshake
+    ///
+    /// When the client and server are configured with a PSK with the same `identity`, the
+    /// handshake is authenticated with the shared `secret` and certificates aren't exchanged. The
+    /// key exchange still uses (EC)DHE so connections have forward secrecy.
+    ///
+    /// The `identity` must be unique, non-empty and at most 65535 bytes. The `secret` must be at
+    /// least 16 bytes and should be generated with a cryptographically secure RNG. The `hmac`
+    /// algorithm restricts the handshake to the cipher suites using the same hash.
+    ///
+    /// The first PSK offered by the client with a matching identity is selected. A certificate is
+    /// only needed if clients without a matching PSK should also be accepted.
+    pub fn with_external_psk(
+        mut self,
+        identity: &[u8],
+        secret: &[u8],
+        hmac: PskHmac,
+    ) -> Result<Self, Error> {
+        self.psks.push(identity, secret, hmac)?;
         Ok(self)
     }
 
@@ -202,19 +352,33 @@ impl Builder {
         use crate::keylog::KeyLog;
 
         self.keylog = KeyLog::try_open();
+        let keylog = self.keylog.clone();
 
-        unsafe {
-            // Safety: the KeyLog is stored on `self` to ensure it outlives `config`
-            if let Some(keylog) = self.keylog.as_ref() {
-                self.config
-                    .set_key_log_callback(Some(KeyLog::callback), Arc::as_ptr(keylog) as *mut _)?;
-            } else {
-                // disable key logging if it failed to create a file
-                self.config
-                    .set_key_log_callback(None, core::ptr::null_mut())?;
+        self.with_setting(move |config| {
+            unsafe {
+                // Safety: the KeyLog is stored on the `Server` to ensure it outlives `config`
+                if let Some(keylog) = keylog.as_ref() {
+                    config.set_key_log_callback(
+                        Some(KeyLog::callback),
+                        Arc::as_ptr(keylog) as *mut _,
+                    )?;
+                } else {
+                    // disable key logging if it failed to create a file
+                    config.set_key_log_callback(None, core::ptr::null_mut())?;
+                }
             }
-        }
+            Ok(())
+        })
+    }
 
+    /// Applies `setting` to the config and records it for the configs built for each
+    /// certificate
+    fn with_setting<F>(mut self, setting: F) -> Result<Self, Error>
+    where
+        F: 'static + Fn(&mut config::Builder) -> Result<(), Error> + Send + Sync,
+    {
+        setting(&mut self.config)?;
+        self.settings.push(Box::new(setting));
         Ok(self)
     }
 
@@ -222,14 +386,265 @@ impl Builder {
         #[cfg(feature = "fips")]
         assert!(s2n_tls::init::fips_mode()?.is_enabled());
 
+        let mut config = self.config;
+        let template = Arc::new(Template {
+            application_protocols: self.application_protocols,
+            settings: self.settings,
+        });
+
+        if self.ocsp_source.is_some() && !self.has_certificate && self.sni_certificates.is_empty() {
+            return Err(Error::application(
+                "an OCSP source requires a certificate".into(),
+            ));
+        }
+
+        if self.ocsp_source.is_some() && self.has_certificate && self.certificate.is_none() {
+            return Err(Error::application(
+                "an OCSP source can't be combined with a certificate signer".into(),
+            ));
+        }
+
+        if !self.sni_certificates.is_empty() || self.ocsp_source.is_some() {
+            let mut configs = Vec::with_capacity(self.sni_certificates.len());
+
+            for (server_name, certificate, private_key) in &self.sni_certificates {
+                let sni_config = template.certificate_config(certificate, private_key)?;
+                configs.push((server_name.clone(), sni_config.build()?));
+            }
+
+            // fall back to the first SNI certificate if a default one wasn't configured
+            if !self.has_certificate {
+                let (_, certificate, private_key) = &self.sni_certificates[0];
+                load_certificate(&mut config, certificate, private_key)?;
+            }
+
+            let stapler = self.ocsp_source.map(|source| OcspStapler {
+                template: template.clone(),
+                source,
+                default: self.certificate.map(|(certificate, private_key)| {
+                    StapledCertificate::new(None, certificate, private_key)
+                }),
+                sni: self
+                    .sni_certificates
+                    .into_iter()
+                    .map(|(server_name, certificate, private_key)| {
+                        StapledCertificate::new(Some(server_name), certificate, private_key)
+                    })
+                    .collect(),
+            });
+
+            config.set_client_hello_callback(CertificateResolver {
+                configs,
+                stapler,
+                #[cfg(any(test, feature = "unstable_client_hello"))]
+                handler: self.client_hello_handler,
+            })?;
+        } else {
+            #[cfg(any(test, feature = "unstable_client_hello"))]
+            if let Some(handler) = self.client_hello_handler {
+                config.set_client_hello_callback(Shared(handler))?;
+            }
+        }
+
         Ok(Server {
-            loader: self.config.build()?,
+            loader: config.build()?,
             keylog: self.keylog,
             params: Default::default(),
+            psks: self.psks.build()?,
+            certificate_pins: self.certificate_pins,
         })
     }
 }
 
+fn load_certificate(
+    config: &mut config::Builder,
+    certificate: &Certificate,
+    private_key: &PrivateKey,
+) -> Result<(), Error> {
+    let certificate = certificate
+        .0
+        .as_pem()
+        .expect("pem is currently the only certificate format supported");
+    match &private_key.0 {
+        Format::Pem(bytes) => config.load_pem(certificate, bytes.as_ref())?,
+        Format::None => config.load_public_pem(certificate)?,
+        Format::Der(_) => panic!("der private keys not supported"),
+    };
+    Ok(())
+}
+
+/// The settings from the [`Builder`] which are applied to the config for each certificate
+struct Template {
+    application_protocols: Vec<Vec<u8>>,
+    settings: Vec<Setting>,
+}
+
+impl Template {
+    /// Creates a config which authenticates with `certificate`
+    fn certificate_config(
+        &self,
+        certificate: &Certificate,
+        private_key: &PrivateKey,
+    ) -> Result<config::Builder, Error> {
+        let mut config = config::Builder::default();
+        config.enable_quic()?;
+        config.set_security_policy(crate::DEFAULT_POLICY)?;
+        config.set_application_protocol_preference(&self.application_protocols)?;
+        for setting in &self.settings {
+            setting(&mut config)?;
+        }
+        load_certificate(&mut config, certificate, private_key)?;
+        Ok(config)
+    }
+}
+
+/// Shares a callback between the configs built for each certificate
+struct Shared<T: ?Sized>(Arc<T>);
+
+impl<T: ?Sized + VerifyHostNameCallback> VerifyHostNameCallback for Shared<T> {
+    fn verify_host_name(&self, host_name: &str) -> bool {
+        self.0.verify_host_name(host_name)
+    }
+}
+
+impl<T: ?Sized + ClientHelloCallback> ClientHelloCallback for Shared<T> {
+    fn on_client_hello(
+        &self,
+        connection: &mut Connection,
+    ) -> Result<Option<core::pin::Pin<Box<dyn ConnectionFuture>>>, Error> {
+        self.0.on_client_hello(connection)
+    }
+}
+
+/// Selects a [`Config`] based on the server name sent by the client and the current OCSP
+/// response
+struct CertificateResolver {
+    configs: Vec<(String, Config)>,
+    stapler: Option<OcspStapler>,
+    /// The handler configured by the application, which is invoked after the config is selected
+    #[cfg(any(test, feature = "unstable_client_hello"))]
+    handler: Option<Arc<dyn ClientHelloCallback>>,
+}
+
+impl CertificateResolver {
+    fn select(&self, connection: &mut Connection) -> Result<(), Error> {
+        let index = connection
+            .server_name()
+            .map(ServerName::from)
+            .and_then(|server_name| {
+                self.configs
+                    .iter()
+                    .position(|(pattern, _)| server_name.matches(pattern))
+            });
+
+        if let Some(config) = self
+            .stapler
+            .as_ref()
+            .map(|stapler| stapler.config(index))
+            .transpose()?
+            .flatten()
+        {
+            connection.set_config(config)?;
+        } else if let Some(index) = index {
+            connection.set_config(self.configs[index].1.clone())?;
+        }
+
+        Ok(())
+    }
+}
+
+impl ClientHelloCallback for CertificateResolver {
+    fn on_client_hello(
+        &self,
+        connection: &mut Connection,
+    ) -> Result<Option<core::pin::Pin<Box<dyn ConnectionFuture>>>, Error> {
+        self.select(connection)?;
+
+        #[cfg(any(test, feature = "unstable_client_hello"))]
+        if let Some(handler) = self.handler.as_ref() {
+            return handler.on_client_hello(connection);
+        }
+
+        Ok(None)
+    }
+}
+
+/// Builds configs for each certificate with its current OCSP response stapled
+struct OcspStapler {
+    template: Arc<Template>,
+    source: Arc<dyn ocsp::Source>,
+    /// The certificate configured with [`Builder::with_certificate`], if any
+    default: Option<StapledCertificate>,
+    /// The SNI certificates, in the same order as the resolver's configs
+    sni: Vec<StapledCertificate>,
+}
+
+impl OcspStapler {
+    /// Returns the stapled config for the SNI certificate at `index`, or the default certificate
+    ///
+    /// `None` is returned if the source doesn't have a response for the certificate.
+    fn config(&self, index: Option<usize>) -> Result<Option<Config>, Error> {
+        let certificate = match (index, self.default.as_ref()) {
+            (Some(index), _) => &self.sni[index],
+            (None, Some(default)) => default,
+            // the default config falls back to the first SNI certificate
+            (None, None) => &self.sni[0],
+        };
+
+        certificate.config(&self.template, &*self.source)
+    }
+}
+
+/// A certificate along with the config for its last stapled response
+struct StapledCertificate {
+    server_name: Option<String>,
+    certificate: Certificate,
+    private_key: PrivateKey,
+    /// The last response along with its config, so a new config is only built when the
+    /// response changes
+    stapled: Mutex<Option<(Arc<[u8]>, Config)>>,
+}
+
+impl StapledCertificate {
+    fn new(server_name: Option<String>, certificate: Certificate, private_key: PrivateKey) -> Self {
+        Self {
+            server_name,
+            certificate,
+            private_key,
+            stapled: Mutex::new(None),
+        }
+    }
+
+    fn config(
+        &self,
+        template: &Template,
+        source: &dyn ocsp::Source,
+    ) -> Result<Option<Config>, Error> {
+        let Some(response) = source.ocsp_response(self.server_name.as_deref()) else {
+            return Ok(None);
+        };
+
+        let mut stapled = self
+            .stapled
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        if let Some((stapled_response, config)) = stapled.as_ref() {
+            if Arc::ptr_eq(stapled_response, &response) {
+                return Ok(Some(config.clone()));
+            }
+        }
+
+        let mut config = template.certificate_config(&self.certificate, &self.private_key)?;
+        config.set_ocsp_data(&response)?;
+        let config = config.build()?;
+
+        *stapled = Some((response, config.clone()));
+
+        Ok(Some(config))
+    }
+}
+
 impl<L: ConfigLoader> tls::Endpoint for Server<L> {
     type Session = Session;
 
@@ -237,9 +652,18 @@ impl<L: ConfigLoader> tls::Endpoint for Server<L> {
         let config = self
             .loader
             .load(crate::ConnectionContext { server_name: None });
-        self.params.with(params, |params| {
+        let mut session = self.params.with(params, |params| {
             Session::new(endpoint::Type::Server, config, params, None).unwrap()
-        })
+        });
+        if self.psks.append_to(&mut session.connection).is_err() {
+            session.fail(
+                tls::Error::INTERNAL_ERROR
+                    .with_reason("failed to offer the external PSKs")
+                    .into(),
+            );
+        }
+        session.certificate_pins = self.certificate_pins.clone();
+        session
     }
 
     fn new_client_session<Params: EncoderValue>(
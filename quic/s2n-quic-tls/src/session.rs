@@ -1,7 +1,10 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::callback::{self, Callback};
+use crate::{
+    callback::{self, Callback},
+    telemetry,
+};
 use bytes::BytesMut;
 use core::{marker::PhantomData, task::Poll};
 use s2n_quic_core::{
@@ -9,7 +12,7 @@ use s2n_quic_core::{
     crypto::{tls, tls::CipherSuite, CryptoSuite},
     endpoint, ensure, transport,
 };
-use s2n_quic_crypto::Suite;
+use s2n_quic_crypto::{pinning::Pins, Suite};
 use s2n_tls::{
     config::Config,
     connection::Connection,
@@ -29,6 +32,9 @@ pub struct Session {
     server_name: Option<ServerName>,
     received_ticket: bool,
     server_params: Vec<u8>,
+    pub(crate) certificate_pins: Pins,
+    // Set if the session couldn't be configured, which fails the handshake
+    error: Option<transport::Error>,
 }
 
 impl Session {
@@ -45,6 +51,7 @@ impl Session {
 
         connection.set_config(config)?;
         connection.enable_quic()?;
+        telemetry::instrument(&mut connection);
 
         let server_params = {
             if endpoint.is_client() {
@@ -76,8 +83,42 @@ impl Session {
             server_name,
             received_ticket: false,
             server_params,
+            certificate_pins: Pins::default(),
+            error: None,
         })
     }
+
+    /// Fails the handshake with `error` the next time the session is polled
+    pub(crate) fn fail(&mut self, error: transport::Error) {
+        self.error = Some(error);
+    }
+
+    /// Checks the peer's certificate chain against the pinned public keys, if any
+    fn verify_certificate_pins(&self) -> Result<(), transport::Error> {
+        if self.certificate_pins.is_empty() {
+            return Ok(());
+        }
+
+        // resumed sessions don't carry the peer's chain; it was checked against the same pins in
+        // the handshake which issued the session ticket
+        if self.connection.resumed() {
+            return Ok(());
+        }
+
+        // s2n-tls only exposes the chain once it's been validated against the trust store
+        let chain = tls::TlsSession::peer_cert_chain_der(self).map_err(|_| {
+            tls::Error::BAD_CERTIFICATE.with_reason("the peer certificate chain is unavailable")
+        })?;
+        ensure!(
+            self.certificate_pins
+                .matches(chain.iter().map(|certificate| &certificate[..])),
+            Err(tls::Error::BAD_CERTIFICATE
+                .with_reason("peer certificate chain doesn't contain a pinned public key")
+                .into())
+        );
+
+        Ok(())
+    }
 }
 
 impl CryptoSuite for Session {
@@ -124,6 +165,10 @@ impl tls::Session for Session {
     where
         W: tls::Context<Self>,
     {
+        if let Some(error) = self.error {
+            return Poll::Ready(Err(error));
+        }
+
         let mut callback: Callback<W, Self> = Callback {
             context,
             endpoint: self.endpoint,
@@ -150,6 +195,8 @@ impl tls::Session for Session {
             Poll::Ready(Ok(())) => {
                 // s2n-tls has indicated that the handshake is complete
                 if !self.handshake_complete {
+                    // s2n-tls has already validated the chain so only the pins need checking
+                    self.verify_certificate_pins()?;
                     self.state.on_handshake_complete();
                     context.on_handshake_complete()?;
                     context.on_tls_exporter_ready(self)?;
@@ -217,6 +264,12 @@ impl tls::Session for Session {
         }
     }
 
+    fn release_buffers(&mut self) {
+        self.send_buffer = BytesMut::new();
+        // s2n-tls refuses to release buffers which still hold data, in which case they're kept
+        let _ = self.connection.release_buffers();
+    }
+
     fn should_discard_session(&self) -> bool {
         // Only clients process post-handshake messages currently
         ensure!(self.endpoint.is_client(), true);
@@ -227,4 +280,13 @@ impl tls::Session for Session {
         // Discard the session once a ticket is received
         self.received_ticket
     }
+
+    fn export_keying_material(
+        &self,
+        label: &[u8],
+        context: &[u8],
+        output: &mut [u8],
+    ) -> Result<(), tls::TlsExportError> {
+        tls::TlsSession::tls_exporter(self, label, context, output)
+    }
 }
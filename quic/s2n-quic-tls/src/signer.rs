@@ -0,0 +1,117 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::telemetry;
+use core::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+use s2n_quic_core::crypto::tls::{
+    self,
+    signer::{SignFuture, SignatureScheme, Signer},
+};
+use s2n_tls::{
+    callbacks::{ConnectionFuture, OperationType, PrivateKeyCallback, PrivateKeyOperation},
+    connection::Connection,
+    enums::{HashAlgorithm, SignatureAlgorithm},
+    error::Error,
+};
+use std::{sync::Mutex, time::Instant};
+
+/// Offloads s2n-tls private key operations to a [`Signer`]
+pub struct Offload<S>(pub S);
+
+impl<S: Signer> PrivateKeyCallback for Offload<S> {
+    fn handle_operation(
+        &self,
+        _connection: &mut Connection,
+        operation: PrivateKeyOperation,
+    ) -> Result<Option<Pin<Box<dyn ConnectionFuture>>>, Error> {
+        let scheme = match operation.kind()? {
+            OperationType::Sign(algorithm, hash) => scheme(algorithm, hash)?,
+            // RSA key exchange is only used by TLS 1.2
+            _ => {
+                return Err(Error::application(
+                    "only signing operations can be offloaded".into(),
+                ))
+            }
+        };
+
+        let mut digest = vec![0; operation.input_size()?];
+        operation.input(&mut digest)?;
+
+        let future = self.0.sign(scheme, &digest);
+
+        Ok(Some(Box::pin(Pending {
+            // `ConnectionFuture` requires `Sync` but the signing future is only ever polled
+            // through an exclusive reference so the lock is never contended
+            future: Mutex::new(future),
+            operation: Some(operation),
+            start: Instant::now(),
+        })))
+    }
+}
+
+fn scheme(algorithm: &SignatureAlgorithm, hash: &HashAlgorithm) -> Result<SignatureScheme, Error> {
+    Ok(match (algorithm, hash) {
+        (SignatureAlgorithm::ECDSA, HashAlgorithm::SHA256) => SignatureScheme::EcdsaSha256,
+        (SignatureAlgorithm::ECDSA, HashAlgorithm::SHA384) => SignatureScheme::EcdsaSha384,
+        (SignatureAlgorithm::ECDSA, HashAlgorithm::SHA512) => SignatureScheme::EcdsaSha512,
+        (
+            SignatureAlgorithm::RSA_PSS_RSAE | SignatureAlgorithm::RSA_PSS_PSS,
+            HashAlgorithm::SHA256,
+        ) => SignatureScheme::RsaPssSha256,
+        (
+            SignatureAlgorithm::RSA_PSS_RSAE | SignatureAlgorithm::RSA_PSS_PSS,
+            HashAlgorithm::SHA384,
+        ) => SignatureScheme::RsaPssSha384,
+        (
+            SignatureAlgorithm::RSA_PSS_RSAE | SignatureAlgorithm::RSA_PSS_PSS,
+            HashAlgorithm::SHA512,
+        ) => SignatureScheme::RsaPssSha512,
+        _ => {
+            return Err(Error::application(
+                "unsupported signature scheme for private key offloading".into(),
+            ))
+        }
+    })
+}
+
+struct Pending {
+    future: Mutex<SignFuture>,
+    operation: Option<PrivateKeyOperation>,
+    start: Instant,
+}
+
+impl ConnectionFuture for Pending {
+    fn poll(
+        self: Pin<&mut Self>,
+        connection: &mut Connection,
+        cx: &mut Context,
+    ) -> Poll<Result<(), Error>> {
+        let this = self.get_mut();
+        let future = this
+            .future
+            .get_mut()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+        let signature = match future.as_mut().poll(cx) {
+            Poll::Ready(signature) => signature,
+            Poll::Pending => return Poll::Pending,
+        };
+        let signature = signature.map_err(|err| Error::application(err.to_string().into()))?;
+
+        let operation = this
+            .operation
+            .take()
+            .expect("the operation is only completed once");
+        operation.set_output(connection, &signature)?;
+        telemetry::record(
+            connection,
+            tls::Operation::SignatureGeneration,
+            this.start.elapsed(),
+        );
+
+        Poll::Ready(Ok(()))
+    }
+}
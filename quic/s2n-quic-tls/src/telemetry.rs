@@ -0,0 +1,47 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Measures the time s2n-tls spends in handshake operations
+//!
+//! s2n-tls doesn't expose timings for its internal operations, so only the operations which are
+//! performed outside of s2n-tls are reported. These are the signatures offloaded to a
+//! [`Signer`](s2n_quic_core::crypto::tls::signer::Signer), which are timed from the start of the
+//! private key operation until the signature is handed back to s2n-tls. Certificate validation,
+//! signature verification and ticket decryption happen inside of s2n-tls and aren't reported.
+//!
+//! The measurements are stored in the application context of the s2n-tls connection and drained
+//! into the [`tls::Context`](s2n_quic_core::crypto::tls::Context) once s2n-tls returns.
+
+use core::time::Duration;
+use s2n_quic_core::crypto::tls;
+use s2n_tls::connection::Connection;
+
+/// The operations measured since the last time s2n-tls returned
+#[derive(Debug, Default)]
+struct Operations(Vec<(tls::Operation, Duration)>);
+
+/// Allows the operations performed on `connection` to be recorded
+#[inline]
+pub fn instrument(connection: &mut Connection) {
+    connection.set_application_context(Operations::default());
+}
+
+/// Records an operation performed on `connection`
+///
+/// The operation is ignored if the application context of the connection has been replaced.
+#[inline]
+pub fn record(connection: &mut Connection, operation: tls::Operation, duration: Duration) {
+    if let Some(operations) = connection.application_context_mut::<Operations>() {
+        operations.0.push((operation, duration));
+    }
+}
+
+/// Reports all of the operations recorded since the last call
+#[inline]
+pub fn drain<F: FnMut(tls::Operation, Duration)>(connection: &mut Connection, mut on_operation: F) {
+    if let Some(operations) = connection.application_context_mut::<Operations>() {
+        for (operation, duration) in operations.0.drain(..) {
+            on_operation(operation, duration);
+        }
+    }
+}
@@ -4,14 +4,17 @@
 use crate::{certificate, client, server};
 use core::{
     sync::atomic::{AtomicBool, AtomicU8, Ordering},
-    task::Poll,
+    task::{Context, Poll, Waker},
 };
 use openssl::{ec::EcKey, ecdsa::EcdsaSig};
 use pin_project::pin_project;
 use s2n_quic_core::{
     crypto::tls::{
-        self,
-        testing::certificates::{CERT_PEM, KEY_PEM, UNTRUSTED_CERT_PEM, UNTRUSTED_KEY_PEM},
+        self, ocsp,
+        signer::{KeyType, SignFuture, SignatureScheme, Signer},
+        testing::certificates::{
+            CERT_DER, CERT_PEM, KEY_PEM, UNTRUSTED_CERT_PEM, UNTRUSTED_KEY_PEM,
+        },
         Endpoint,
     },
     transport,
@@ -23,9 +26,13 @@ use s2n_tls::callbacks::{PrivateKeyCallback, PrivateKeyOperation};
 use s2n_tls::{
     callbacks::{ConnectionFuture, VerifyHostNameCallback},
     connection::Connection,
+    enums::PskHmac,
     error::Error,
 };
-use std::{sync::Arc, time::SystemTime};
+use std::{
+    sync::{Arc, Mutex},
+    time::SystemTime,
+};
 
 pub struct MyCallbackHandler {
     done: Arc<AtomicBool>,
@@ -277,6 +284,24 @@ fn s2n_server_with_client_hello_callback(wait_counter: u8) -> (server::Server, A
     (tls, done)
 }
 
+#[cfg(any(test, feature = "unstable_client_hello"))]
+fn s2n_server_with_sni_client_hello_callback(
+    wait_counter: u8,
+) -> (server::Server, Arc<AtomicBool>) {
+    let handle = MyCallbackHandler::new(wait_counter);
+    let done = handle.done.clone();
+    let tls = server::Builder::default()
+        .with_certificate(UNTRUSTED_CERT_PEM, UNTRUSTED_KEY_PEM)
+        .unwrap()
+        .with_sni_certificate("localhost", CERT_PEM, KEY_PEM)
+        .unwrap()
+        .with_client_hello_handler(handle)
+        .unwrap()
+        .build()
+        .unwrap();
+    (tls, done)
+}
+
 #[cfg(any(test, feature = "unstable_private_key"))]
 fn s2n_server_with_private_key_callback(wait_counter: u8) -> (server::Server, Arc<AtomicBool>) {
     let handle = MyCallbackHandler::new(wait_counter);
@@ -291,6 +316,46 @@ fn s2n_server_with_private_key_callback(wait_counter: u8) -> (server::Server, Ar
     (tls, done)
 }
 
+fn s2n_server_with_sni(pattern: &str) -> Result<server::Server, Error> {
+    server::Builder::default()
+        .with_certificate(UNTRUSTED_CERT_PEM, UNTRUSTED_KEY_PEM)?
+        .with_sni_certificate(pattern, CERT_PEM, KEY_PEM)?
+        .build()
+}
+
+fn s2n_server_with_sni_client_auth() -> Result<server::Server, Error> {
+    server::Builder::default()
+        .with_empty_trust_store()?
+        .with_client_authentication()?
+        .with_verify_host_name_callback(VerifyHostNameClientCertVerifier::new("qlaws.qlaws"))?
+        .with_trusted_certificate(CERT_PEM)?
+        .with_sni_certificate("localhost", CERT_PEM, KEY_PEM)?
+        .build()
+}
+
+fn s2n_client_with_psk(identity: &[u8], secret: &[u8]) -> Result<client::Client, Error> {
+    client::Builder::default()
+        .with_empty_trust_store()?
+        .with_external_psk(identity, secret, PskHmac::SHA256)?
+        .build()
+}
+
+fn s2n_server_with_psk(identity: &[u8], secret: &[u8]) -> Result<server::Server, Error> {
+    server::Builder::default()
+        .with_external_psk(identity, secret, PskHmac::SHA256)?
+        .build()
+}
+
+fn rustls_server_with_sni(pattern: &str) -> s2n_quic_rustls::server::Server {
+    s2n_quic_rustls::server::Builder::default()
+        .with_certificate(UNTRUSTED_CERT_PEM, UNTRUSTED_KEY_PEM)
+        .unwrap()
+        .with_sni_certificate(pattern, CERT_PEM, KEY_PEM)
+        .unwrap()
+        .build()
+        .unwrap()
+}
+
 fn rustls_server() -> s2n_quic_rustls::server::Server {
     s2n_quic_rustls::server::Builder::default()
         .with_certificate(CERT_PEM, KEY_PEM)
@@ -318,6 +383,18 @@ fn s2n_client_s2n_server_ch_callback_test() {
     }
 }
 
+#[test]
+#[cfg_attr(miri, ignore)]
+fn s2n_client_s2n_server_sni_ch_callback_test() {
+    // the application's handler is still invoked after the SNI certificate is selected
+    for wait_counter in [0, 5] {
+        let mut client_endpoint = s2n_client();
+        let (mut server_endpoint, done) = s2n_server_with_sni_client_hello_callback(wait_counter);
+
+        run(&mut server_endpoint, &mut client_endpoint, Some(done));
+    }
+}
+
 #[test]
 #[cfg_attr(miri, ignore)]
 fn s2n_client_s2n_server_pkey_callback_test() {
@@ -474,6 +551,735 @@ fn s2n_client_with_custom_hostname_auth_accepts_server_name() {
     run_result(&mut server_endpoint, &mut client_endpoint, None).unwrap();
 }
 
+#[test]
+#[cfg_attr(miri, ignore)]
+fn s2n_client_s2n_server_sni_test() {
+    for pattern in ["localhost", "LOCALHOST"] {
+        let mut client_endpoint = s2n_client();
+        let mut server_endpoint = s2n_server_with_sni(pattern).unwrap();
+
+        run(&mut server_endpoint, &mut client_endpoint, None);
+    }
+
+    // the default certificate is used when the pattern doesn't match
+    let mut client_endpoint = s2n_client();
+    let mut server_endpoint = s2n_server_with_sni("*.localhost").unwrap();
+
+    let test_result = run_result(&mut server_endpoint, &mut client_endpoint, None);
+    assert!(test_result.is_err());
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn s2n_client_s2n_server_sni_client_auth_test() {
+    // the config for the SNI certificate inherits the client authentication settings
+    let mut client_endpoint = s2n_client_with_client_auth().unwrap();
+    let mut server_endpoint = s2n_server_with_sni_client_auth().unwrap();
+
+    run(&mut server_endpoint, &mut client_endpoint, None);
+
+    let mut client_endpoint = s2n_client();
+    let mut server_endpoint = s2n_server_with_sni_client_auth().unwrap();
+
+    let test_result = run_result(&mut server_endpoint, &mut client_endpoint, None);
+    assert!(test_result.is_err());
+    let e = test_result.unwrap_err();
+    assert_eq!(e.description().unwrap(), "CERTIFICATE_REQUIRED");
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn s2n_client_rustls_server_sni_test() {
+    let mut client_endpoint = s2n_client();
+    let mut server_endpoint = rustls_server_with_sni("localhost");
+
+    run(&mut server_endpoint, &mut client_endpoint, None);
+
+    // the default certificate is used when the pattern doesn't match
+    let mut client_endpoint = s2n_client();
+    let mut server_endpoint = rustls_server_with_sni("*.localhost");
+
+    let test_result = run_result(&mut server_endpoint, &mut client_endpoint, None);
+    assert!(test_result.is_err());
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn s2n_client_s2n_server_external_psk_test() {
+    const SECRET: &[u8] = b"an external secret of 32 bytes!!";
+
+    // neither endpoint has a certificate so the PSK must authenticate the handshake
+    let mut client_endpoint = s2n_client_with_psk(b"identity", SECRET).unwrap();
+    let mut server_endpoint = s2n_server_with_psk(b"identity", SECRET).unwrap();
+
+    let pair = run_result(&mut server_endpoint, &mut client_endpoint, None).unwrap();
+    assert!(pair.client.context.operations.is_empty());
+    assert!(pair.server.context.operations.is_empty());
+
+    // the server selects the PSK matching the client's identity
+    let mut server_endpoint = server::Builder::default()
+        .with_external_psk(
+            b"other",
+            b"another secret of 32 bytes!!!!!!",
+            PskHmac::SHA384,
+        )
+        .unwrap()
+        .with_external_psk(b"identity", SECRET, PskHmac::SHA256)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    run(&mut server_endpoint, &mut client_endpoint, None);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn s2n_client_s2n_server_external_psk_mismatch_test() {
+    const SECRET: &[u8] = b"an external secret of 32 bytes!!";
+
+    // the identities match but the secrets don't
+    let mut client_endpoint = s2n_client_with_psk(b"identity", SECRET).unwrap();
+    let mut server_endpoint =
+        s2n_server_with_psk(b"identity", b"a different secret of 32 bytes!!").unwrap();
+    assert!(run_result(&mut server_endpoint, &mut client_endpoint, None).is_err());
+
+    // the server doesn't have a PSK with the client's identity or a certificate to fall back to
+    let mut server_endpoint = s2n_server_with_psk(b"other", SECRET).unwrap();
+    assert!(run_result(&mut server_endpoint, &mut client_endpoint, None).is_err());
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn certificate_pinning_test() {
+    let pin = s2n_quic_crypto::pinning::pin(CERT_DER).unwrap();
+    let other = [42u8; 32];
+
+    let s2n_client = |pins: &[[u8; 32]]| {
+        client::Builder::default()
+            .with_certificate(CERT_PEM)
+            .unwrap()
+            .with_certificate_pinning(pins)
+            .unwrap()
+            .build()
+            .unwrap()
+    };
+    let rustls_client = |pins: &[[u8; 32]]| {
+        s2n_quic_rustls::client::Builder::default()
+            .with_certificate(CERT_PEM)
+            .unwrap()
+            .with_certificate_pinning(pins)
+            .unwrap()
+            .build()
+            .unwrap()
+    };
+
+    // the server's public key only needs to match one of the pins
+    run(&mut s2n_server(), &mut s2n_client(&[other, pin]), None);
+    run(&mut rustls_server(), &mut s2n_client(&[pin]), None);
+    run(&mut s2n_server(), &mut rustls_client(&[other, pin]), None);
+    run(&mut rustls_server(), &mut rustls_client(&[pin]), None);
+
+    let err = run_result(&mut s2n_server(), &mut s2n_client(&[other]), None).unwrap_err();
+    assert_eq!(err.description().unwrap(), "BAD_CERTIFICATE");
+    assert!(run_result(&mut rustls_server(), &mut s2n_client(&[other]), None).is_err());
+    assert!(run_result(&mut s2n_server(), &mut rustls_client(&[other]), None).is_err());
+    assert!(run_result(&mut rustls_server(), &mut rustls_client(&[other]), None).is_err());
+
+    // pins must be SHA-256 digests
+    assert!(client::Builder::default()
+        .with_certificate_pinning([[0u8; 16]])
+        .is_err());
+    assert!(s2n_quic_rustls::client::Builder::default()
+        .with_certificate_pinning([[0u8; 16]])
+        .is_err());
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn client_certificate_pinning_test() {
+    let pin = s2n_quic_crypto::pinning::pin(CERT_DER).unwrap();
+    let other = [42u8; 32];
+
+    let s2n_server = |pins: &[[u8; 32]]| {
+        server::Builder::default()
+            .with_empty_trust_store()
+            .unwrap()
+            .with_client_authentication()
+            .unwrap()
+            .with_verify_host_name_callback(VerifyHostNameClientCertVerifier::new("qlaws.qlaws"))
+            .unwrap()
+            .with_certificate(CERT_PEM, KEY_PEM)
+            .unwrap()
+            .with_trusted_certificate(CERT_PEM)
+            .unwrap()
+            .with_certificate_pinning(pins)
+            .unwrap()
+            .build()
+            .unwrap()
+    };
+    let rustls_server = |pins: &[[u8; 32]]| {
+        s2n_quic_rustls::server::Builder::default()
+            .with_certificate(CERT_PEM, KEY_PEM)
+            .unwrap()
+            .with_trusted_certificate(CERT_PEM)
+            .unwrap()
+            .with_certificate_pinning(pins)
+            .unwrap()
+            .build()
+            .unwrap()
+    };
+    let s2n_client = || s2n_client_with_client_auth().unwrap();
+    let rustls_client = || {
+        let signer = TestSigner {
+            wait_counter: 0,
+            fail: false,
+        };
+        s2n_quic_rustls::client::Builder::default()
+            .with_certificate(CERT_PEM)
+            .unwrap()
+            .with_client_identity_signer(CERT_PEM, signer)
+            .unwrap()
+            .build()
+            .unwrap()
+    };
+
+    run(&mut s2n_server(&[other, pin]), &mut s2n_client(), None);
+    run(
+        &mut rustls_server(&[other, pin]),
+        &mut rustls_client(),
+        None,
+    );
+
+    let err = run_result(&mut s2n_server(&[other]), &mut s2n_client(), None).unwrap_err();
+    assert_eq!(err.description().unwrap(), "BAD_CERTIFICATE");
+    assert!(run_result(&mut rustls_server(&[other]), &mut rustls_client(), None).is_err());
+
+    // the pins are checked against a client certificate so it must be requested
+    let mut server = server::Builder::default()
+        .with_certificate(CERT_PEM, KEY_PEM)
+        .unwrap()
+        .with_certificate_pinning([pin])
+        .unwrap()
+        .build()
+        .unwrap();
+    assert!(run_result(&mut server, &mut s2n_client(), None).is_err());
+    assert!(s2n_quic_rustls::server::Builder::default()
+        .with_certificate(CERT_PEM, KEY_PEM)
+        .unwrap()
+        .with_certificate_pinning([pin])
+        .unwrap()
+        .build()
+        .is_err());
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn certificate_pinning_path_test() {
+    use s2n_quic_core::crypto::tls::testing::certificates::{
+        MTLS_CA_CERT, MTLS_SERVER_CERT, MTLS_SERVER_KEY,
+    };
+
+    let der = |pem: &str| {
+        openssl::x509::X509::from_pem(pem.as_bytes())
+            .unwrap()
+            .to_der()
+            .unwrap()
+    };
+    let pin = |pem: &str| s2n_quic_crypto::pinning::pin(&der(pem)).unwrap();
+
+    // the server also sends a certificate which isn't part of the path to the trusted root
+    let chain = format!("{MTLS_SERVER_CERT}{CERT_PEM}");
+    let mut server = s2n_quic_rustls::server::Builder::default()
+        .with_certificate(chain, MTLS_SERVER_KEY)
+        .unwrap()
+        .build()
+        .unwrap();
+
+    let client = |pin: [u8; 32]| {
+        s2n_quic_rustls::client::Builder::default()
+            .with_certificate(MTLS_CA_CERT)
+            .unwrap()
+            .with_certificate_pinning([pin])
+            .unwrap()
+            .build()
+            .unwrap()
+    };
+
+    // any key in the validated path satisfies the pins, including the trust anchor
+    run(&mut server, &mut client(pin(MTLS_SERVER_CERT)), None);
+    run(&mut server, &mut client(pin(MTLS_CA_CERT)), None);
+
+    // certificates outside of the validated path are ignored
+    assert!(run_result(&mut server, &mut client(pin(CERT_PEM)), None).is_err());
+}
+
+/// Signs with the test key after returning `Pending` `wait_counter` times
+struct TestSigner {
+    wait_counter: u8,
+    fail: bool,
+}
+
+impl Signer for TestSigner {
+    fn key_type(&self) -> KeyType {
+        KeyType::EcdsaP256
+    }
+
+    fn sign(&self, scheme: SignatureScheme, digest: &[u8]) -> SignFuture {
+        assert_eq!(scheme, SignatureScheme::EcdsaSha256);
+        assert_eq!(digest.len(), scheme.digest_len());
+
+        let key = EcKey::private_key_from_pem(KEY_PEM.as_bytes()).unwrap();
+        let signature = EcdsaSig::sign(digest, &key).unwrap().to_der().unwrap();
+        let mut signature = Some(if self.fail {
+            Err(tls::Error::INTERNAL_ERROR.with_reason("signer unavailable"))
+        } else {
+            Ok(signature)
+        });
+        let mut wait_counter = self.wait_counter;
+
+        Box::pin(core::future::poll_fn(move |cx| {
+            if wait_counter > 0 {
+                wait_counter -= 1;
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+            Poll::Ready(signature.take().expect("polled after completion"))
+        }))
+    }
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn remote_signer_test() {
+    let s2n_server = |wait_counter, fail| {
+        server::Builder::default()
+            .with_remote_signer(CERT_PEM, TestSigner { wait_counter, fail })
+            .unwrap()
+            .build()
+            .unwrap()
+    };
+    let rustls_server = |wait_counter, fail| {
+        s2n_quic_rustls::server::Builder::default()
+            .with_remote_signer(CERT_PEM, TestSigner { wait_counter, fail })
+            .unwrap()
+            .build()
+            .unwrap()
+    };
+
+    for wait_counter in 0..=3 {
+        run(
+            &mut s2n_server(wait_counter, false),
+            &mut s2n_client(),
+            None,
+        );
+        run(
+            &mut s2n_server(wait_counter, false),
+            &mut rustls_client(),
+            None,
+        );
+        run(
+            &mut rustls_server(wait_counter, false),
+            &mut s2n_client(),
+            None,
+        );
+    }
+
+    // the client can offload signing of its certificate verify message
+    let mut client = client::Builder::default()
+        .with_empty_trust_store()
+        .unwrap()
+        .with_certificate(CERT_PEM)
+        .unwrap()
+        .with_client_identity_signer(
+            CERT_PEM,
+            TestSigner {
+                wait_counter: 2,
+                fail: false,
+            },
+        )
+        .unwrap()
+        .build()
+        .unwrap();
+    run(
+        &mut s2n_server_with_client_auth().unwrap(),
+        &mut client,
+        None,
+    );
+
+    // a signing failure fails the handshake
+    assert!(run_result(&mut s2n_server(1, true), &mut s2n_client(), None).is_err());
+    assert!(run_result(&mut rustls_server(1, true), &mut s2n_client(), None).is_err());
+
+    // the rustls client can offload signing of its certificate verify message
+    let rustls_client = |wait_counter, fail| {
+        s2n_quic_rustls::client::Builder::default()
+            .with_certificate(CERT_PEM)
+            .unwrap()
+            .with_client_identity_signer(CERT_PEM, TestSigner { wait_counter, fail })
+            .unwrap()
+            .build()
+            .unwrap()
+    };
+    let rustls_server_with_client_auth = || {
+        s2n_quic_rustls::server::Builder::default()
+            .with_certificate(CERT_PEM, KEY_PEM)
+            .unwrap()
+            .with_trusted_certificate(CERT_PEM)
+            .unwrap()
+            .build()
+            .unwrap()
+    };
+    run(
+        &mut s2n_server_with_client_auth().unwrap(),
+        &mut rustls_client(2, false),
+        None,
+    );
+    run(
+        &mut rustls_server_with_client_auth(),
+        &mut rustls_client(2, false),
+        None,
+    );
+    assert!(run_result(
+        &mut rustls_server_with_client_auth(),
+        &mut rustls_client(1, true),
+        None
+    )
+    .is_err());
+}
+
+/// Holds signatures back until the gate is opened
+#[derive(Default)]
+struct Gate {
+    open: AtomicBool,
+    waker: Mutex<Option<Waker>>,
+}
+
+impl Gate {
+    fn open(&self) {
+        self.open.store(true, Ordering::SeqCst);
+        if let Some(waker) = self.waker.lock().unwrap().take() {
+            waker.wake();
+        }
+    }
+
+    fn poll_open(&self, cx: &mut Context) -> Poll<()> {
+        *self.waker.lock().unwrap() = Some(cx.waker().clone());
+        if self.open.load(Ordering::SeqCst) {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+struct GatedSigner(Arc<Gate>);
+
+impl Signer for GatedSigner {
+    fn key_type(&self) -> KeyType {
+        KeyType::EcdsaP256
+    }
+
+    fn sign(&self, scheme: SignatureScheme, digest: &[u8]) -> SignFuture {
+        let signer = TestSigner {
+            wait_counter: 0,
+            fail: false,
+        };
+        let signature = signer.sign(scheme, digest);
+        let gate = self.0.clone();
+        Box::pin(async move {
+            core::future::poll_fn(|cx| gate.poll_open(cx)).await;
+            signature.await
+        })
+    }
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn rustls_remote_signer_nonblocking_test() {
+    let gate = Arc::new(Gate::default());
+    let mut server = s2n_quic_rustls::server::Builder::default()
+        .with_remote_signer(CERT_PEM, GatedSigner(gate.clone()))
+        .unwrap()
+        .build()
+        .unwrap();
+    let mut client = s2n_client();
+    let mut pair = tls::testing::Pair::new(&mut server, &mut client, "localhost".into());
+
+    // the handshake waits for the signature without blocking the thread polling the sessions
+    for _ in 0..10 {
+        pair.poll(None).unwrap();
+    }
+    assert!(pair.is_handshaking());
+
+    gate.open();
+    while pair.is_handshaking() {
+        pair.poll(None).unwrap();
+    }
+    pair.finish();
+}
+
+/// Returns a client which accepts any server certificate and records the stapled OCSP responses
+#[allow(deprecated)]
+fn rustls_client_with_ocsp_recorder() -> (s2n_quic_rustls::client::Client, Arc<Mutex<Vec<Vec<u8>>>>)
+{
+    use s2n_quic_rustls::rustls::{
+        self,
+        client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier},
+        crypto::{self, CryptoProvider},
+        pki_types::{CertificateDer, ServerName, UnixTime},
+        DigitallySignedStruct,
+    };
+
+    #[derive(Debug)]
+    struct Recorder {
+        provider: Arc<CryptoProvider>,
+        responses: Arc<Mutex<Vec<Vec<u8>>>>,
+    }
+
+    impl ServerCertVerifier for Recorder {
+        fn verify_server_cert(
+            &self,
+            _end_entity: &CertificateDer<'_>,
+            _intermediates: &[CertificateDer<'_>],
+            _server_name: &ServerName<'_>,
+            ocsp_response: &[u8],
+            _now: UnixTime,
+        ) -> Result<ServerCertVerified, rustls::Error> {
+            self.responses.lock().unwrap().push(ocsp_response.to_vec());
+            Ok(ServerCertVerified::assertion())
+        }
+
+        fn verify_tls12_signature(
+            &self,
+            message: &[u8],
+            cert: &CertificateDer<'_>,
+            dss: &DigitallySignedStruct,
+        ) -> Result<HandshakeSignatureValid, rustls::Error> {
+            let algorithms = &self.provider.signature_verification_algorithms;
+            crypto::verify_tls12_signature(message, cert, dss, algorithms)
+        }
+
+        fn verify_tls13_signature(
+            &self,
+            message: &[u8],
+            cert: &CertificateDer<'_>,
+            dss: &DigitallySignedStruct,
+        ) -> Result<HandshakeSignatureValid, rustls::Error> {
+            let algorithms = &self.provider.signature_verification_algorithms;
+            crypto::verify_tls13_signature(message, cert, dss, algorithms)
+        }
+
+        fn supported_verify_schemes(&self) -> Vec<rustls::SignatureScheme> {
+            self.provider
+                .signature_verification_algorithms
+                .supported_schemes()
+        }
+    }
+
+    let provider = Arc::new(crypto::aws_lc_rs::default_provider());
+    let responses = Arc::new(Mutex::new(vec![]));
+    let recorder = Recorder {
+        provider: provider.clone(),
+        responses: responses.clone(),
+    };
+
+    let mut config = rustls::ClientConfig::builder_with_provider(provider)
+        .with_protocol_versions(&[&rustls::version::TLS13])
+        .unwrap()
+        .dangerous()
+        .with_custom_certificate_verifier(Arc::new(recorder))
+        .with_no_client_auth();
+    config.alpn_protocols = vec![b"h3".to_vec()];
+
+    (s2n_quic_rustls::client::Client::new(config), responses)
+}
+
+/// An OCSP source whose responses can be replaced by the test
+#[derive(Clone, Default)]
+struct TestOcspSource(Arc<Mutex<Vec<(Option<String>, Arc<[u8]>)>>>);
+
+impl TestOcspSource {
+    fn set(&self, server_name: Option<&str>, response: Option<&[u8]>) {
+        let mut responses = self.0.lock().unwrap();
+        responses.retain(|(name, _)| name.as_deref() != server_name);
+        if let Some(response) = response {
+            responses.push((server_name.map(String::from), Arc::from(response)));
+        }
+    }
+}
+
+impl ocsp::Source for TestOcspSource {
+    fn ocsp_response(&self, server_name: Option<&str>) -> Option<Arc<[u8]>> {
+        self.0
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|(name, _)| name.as_deref() == server_name)
+            .map(|(_, response)| response.clone())
+    }
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn ocsp_stapling_test() {
+    // a fixed response is stapled by both providers
+    let mut s2n_server = server::Builder::default()
+        .with_certificate(CERT_PEM, KEY_PEM)
+        .unwrap()
+        .with_ocsp_response(b"fixed")
+        .unwrap()
+        .build()
+        .unwrap();
+    let mut rustls_server = s2n_quic_rustls::server::Builder::default()
+        .with_certificate(CERT_PEM, KEY_PEM)
+        .unwrap()
+        .with_ocsp_response(b"fixed")
+        .unwrap()
+        .build()
+        .unwrap();
+
+    let (mut client, responses) = rustls_client_with_ocsp_recorder();
+    run(&mut s2n_server, &mut client, None);
+    run(&mut rustls_server, &mut client, None);
+    assert_eq!(
+        *responses.lock().unwrap(),
+        [b"fixed".to_vec(), b"fixed".to_vec()]
+    );
+
+    // a refreshed response is stapled to the following handshakes
+    let source = TestOcspSource::default();
+    let mut s2n_server = server::Builder::default()
+        .with_certificate(CERT_PEM, KEY_PEM)
+        .unwrap()
+        .with_ocsp_source(source.clone())
+        .unwrap()
+        .build()
+        .unwrap();
+    let mut rustls_server = s2n_quic_rustls::server::Builder::default()
+        .with_certificate(CERT_PEM, KEY_PEM)
+        .unwrap()
+        .with_ocsp_source(source.clone())
+        .unwrap()
+        .build()
+        .unwrap();
+
+    for response in [
+        Some(&b"first"[..]),
+        Some(&b"second"[..]),
+        Some(&b"second"[..]),
+        None,
+    ] {
+        source.set(None, response);
+        let expected = response.unwrap_or_default().to_vec();
+
+        let (mut client, responses) = rustls_client_with_ocsp_recorder();
+        run(&mut s2n_server, &mut client, None);
+        run(&mut rustls_server, &mut client, None);
+        assert_eq!(*responses.lock().unwrap(), [expected.clone(), expected]);
+    }
+
+    // SNI certificates are stapled with their own responses
+    let source = TestOcspSource::default();
+    source.set(None, Some(b"default"));
+    source.set(Some("localhost"), Some(b"sni"));
+    let mut s2n_server = server::Builder::default()
+        .with_certificate(UNTRUSTED_CERT_PEM, UNTRUSTED_KEY_PEM)
+        .unwrap()
+        .with_sni_certificate("localhost", CERT_PEM, KEY_PEM)
+        .unwrap()
+        .with_ocsp_source(source.clone())
+        .unwrap()
+        .build()
+        .unwrap();
+    let mut rustls_server = s2n_quic_rustls::server::Builder::default()
+        .with_certificate(UNTRUSTED_CERT_PEM, UNTRUSTED_KEY_PEM)
+        .unwrap()
+        .with_sni_certificate("localhost", CERT_PEM, KEY_PEM)
+        .unwrap()
+        .with_ocsp_source(source.clone())
+        .unwrap()
+        .build()
+        .unwrap();
+
+    for response in [Some(&b"sni"[..]), Some(&b"refreshed"[..]), None] {
+        source.set(Some("localhost"), response);
+        let expected = response.unwrap_or_default().to_vec();
+
+        let (mut client, responses) = rustls_client_with_ocsp_recorder();
+        run(&mut s2n_server, &mut client, None);
+        run(&mut rustls_server, &mut client, None);
+        assert_eq!(*responses.lock().unwrap(), [expected.clone(), expected]);
+    }
+
+    // the fixed response only applies to the default certificate
+    let mut s2n_server = server::Builder::default()
+        .with_sni_certificate("localhost", CERT_PEM, KEY_PEM)
+        .unwrap()
+        .with_ocsp_response(b"fixed")
+        .unwrap()
+        .build()
+        .unwrap();
+    let mut rustls_server = s2n_quic_rustls::server::Builder::default()
+        .with_sni_certificate("localhost", CERT_PEM, KEY_PEM)
+        .unwrap()
+        .with_ocsp_response(b"fixed")
+        .unwrap()
+        .build()
+        .unwrap();
+
+    let (mut client, responses) = rustls_client_with_ocsp_recorder();
+    run(&mut s2n_server, &mut client, None);
+    run(&mut rustls_server, &mut client, None);
+    assert_eq!(*responses.lock().unwrap(), [Vec::new(), Vec::new()]);
+
+    // the source needs a certificate to staple to
+    assert!(server::Builder::default()
+        .with_ocsp_source(source.clone())
+        .unwrap()
+        .build()
+        .is_err());
+    assert!(s2n_quic_rustls::server::Builder::default()
+        .with_ocsp_source(source)
+        .unwrap()
+        .build()
+        .is_err());
+}
+
+/// Ensures both providers report the time spent in the handshake operations they can measure
+#[test]
+#[cfg_attr(miri, ignore)]
+fn tls_operation_test() {
+    use tls::Operation::*;
+
+    // rustls wraps each of the components performing the operations
+    let pair = run_result(&mut rustls_server(), &mut rustls_client(), None).unwrap();
+    let client = &pair.client.context.operations;
+    assert!(client.contains(&CertificateValidation), "{client:?}");
+    assert!(client.contains(&SignatureVerification), "{client:?}");
+    let server = &pair.server.context.operations;
+    assert!(server.contains(&SignatureGeneration), "{server:?}");
+
+    // s2n-tls performs the operations internally so they aren't measured
+    let pair = run_result(&mut s2n_server(), &mut s2n_client(), None).unwrap();
+    assert!(pair.client.context.operations.is_empty());
+    assert!(pair.server.context.operations.is_empty());
+
+    // signatures offloaded from s2n-tls are measured
+    let mut server = server::Builder::default()
+        .with_remote_signer(
+            CERT_PEM,
+            TestSigner {
+                wait_counter: 1,
+                fail: false,
+            },
+        )
+        .unwrap()
+        .build()
+        .unwrap();
+    let pair = run_result(&mut server, &mut s2n_client(), None).unwrap();
+    assert_eq!(pair.server.context.operations, [SignatureGeneration]);
+}
+
 /// Executes the handshake to completion
 fn run_result<S: Endpoint, C: Endpoint>(
     server: &mut S,
@@ -85,6 +85,11 @@ impl AckManager {
         }
     }
 
+    /// Releases any excess capacity held for tracking received packets
+    pub fn shrink_to_fit(&mut self) {
+        self.ack_ranges.shrink_to_fit();
+    }
+
     /// Called when an outgoing packet is being assembled
     pub fn on_transmit<W: WriteContext>(&mut self, context: &mut W) -> bool {
         let constraint = context.transmission_constraint();
@@ -7,16 +7,19 @@ use crate::{
     connection::{self, ConnectionApi, OpenToken},
     stream::{ops, Stream, StreamError, StreamId},
 };
+use alloc::vec::Vec;
 use bytes::Bytes;
 use core::{
     fmt,
     sync::atomic::{self, Ordering},
     task::{Context, Poll},
+    time::Duration,
 };
 use s2n_quic_core::{
     application,
     application::ServerName,
     inet::SocketAddress,
+    path,
     query::{Query, QueryMut},
     stream::StreamType,
 };
@@ -78,7 +81,7 @@ impl Drop for Connection {
         }
 
         atomic::fence(Ordering::Acquire);
-        self.api.close_connection(None);
+        self.api.close_connection(None, None);
     }
 }
 
@@ -143,8 +146,62 @@ impl Connection {
         stream_type: StreamType,
         context: &Context,
     ) -> Poll<Result<Stream, connection::Error>> {
+        self.poll_open_stream_with_timeout(stream_type, None, context)
+    }
+
+    /// Polls opening a [`Stream`], failing if it's blocked on stream capacity for longer than the
+    /// `timeout`
+    ///
+    /// The deadline is fixed when the request first blocks and is tracked by the connection. See
+    /// [`connection::Error::OpenStreamTimeout`] for details. `None` waits indefinitely.
+    #[inline]
+    pub fn poll_open_stream_with_timeout(
+        &mut self,
+        stream_type: StreamType,
+        timeout: Option<Duration>,
+        context: &Context,
+    ) -> Poll<Result<Stream, connection::Error>> {
+        self.open_token.timeout = timeout.unwrap_or(Duration::ZERO);
         self.api
-            .poll_open_stream(&self.api, stream_type, &mut self.open_token, context)
+            .poll_open_stream(&self.api, stream_type, &mut self.open_token, Some(context))
+    }
+
+    /// Opens a [`Stream`] without waiting for capacity
+    ///
+    /// Returns `Ok(None)` if the stream can't currently be opened due to the local or peer
+    /// stream limits.
+    #[inline]
+    pub fn try_open_stream(
+        &mut self,
+        stream_type: StreamType,
+    ) -> Result<Option<Stream>, connection::Error> {
+        match self
+            .api
+            .poll_open_stream(&self.api, stream_type, &mut self.open_token, None)
+        {
+            Poll::Ready(result) => result.map(Some),
+            Poll::Pending => Ok(None),
+        }
+    }
+
+    /// Cancels a pending [`Self::poll_open_stream`] request
+    ///
+    /// This should be called when the caller stops polling before the stream was opened, so the
+    /// request is no longer considered blocked on the peer's limits.
+    #[inline]
+    pub fn cancel_open_stream(&mut self, stream_type: StreamType) {
+        self.api
+            .cancel_open_stream(stream_type, &mut self.open_token)
+    }
+
+    /// Returns `true` if opening a [`Stream`] of the given type requires additional credit from
+    /// the peer
+    #[inline]
+    pub fn is_open_stream_blocked(
+        &self,
+        stream_type: StreamType,
+    ) -> Result<bool, connection::Error> {
+        self.api.is_open_stream_blocked(stream_type)
     }
 
     #[inline]
@@ -157,12 +214,50 @@ impl Connection {
         self.api.poll_request(stream_id, request, context)
     }
 
+    #[inline]
+    pub fn poll_flush_transport(&self, context: &Context) -> Poll<Result<(), connection::Error>> {
+        self.api.poll_flush_transport(context)
+    }
+
+    #[inline]
+    pub fn poll_handshake_completed(
+        &self,
+        context: &Context,
+    ) -> Poll<Result<(), connection::Error>> {
+        self.api.poll_handshake_completed(context)
+    }
+
     /// Closes the Connection with the provided error code
     ///
     /// This will immediately terminate all outstanding streams.
     #[inline]
     pub fn close(&self, error_code: application::Error) {
-        self.api.close_connection(Some(error_code));
+        self.api
+            .close_connection(Some(connection::Error::application(error_code)), None);
+    }
+
+    /// Closes the Connection with the provided error code and reason phrase
+    ///
+    /// Reasons longer than [`Reason::MAX_FRAME_LEN`](connection::error::Reason::MAX_FRAME_LEN)
+    /// bytes are truncated.
+    ///
+    /// This will immediately terminate all outstanding streams.
+    #[inline]
+    pub fn close_with_reason(&self, error_code: application::Error, reason: &str) {
+        let reason = connection::error::Reason::truncate_for_frame(reason);
+        let error = connection::Error::application_with_reason(error_code, reason.into());
+        self.api
+            .close_connection(Some(error), Some(Bytes::copy_from_slice(reason.as_bytes())));
+    }
+
+    /// Returns the reason phrase of the CONNECTION_CLOSE frame which closed the connection
+    ///
+    /// This is either the reason received from the peer, for any error code, or the reason
+    /// provided to [`Self::close_with_reason`]. `None` is returned while the connection is open
+    /// or if the close didn't include a reason.
+    #[inline]
+    pub fn close_reason(&self) -> Result<Option<Bytes>, connection::Error> {
+        self.api.close_reason()
     }
 
     #[inline]
@@ -175,6 +270,11 @@ impl Connection {
         self.api.application_protocol()
     }
 
+    #[inline]
+    pub fn compression_dictionary_id(&self) -> Result<Option<u64>, connection::Error> {
+        self.api.compression_dictionary_id()
+    }
+
     #[inline]
     pub fn id(&self) -> u64 {
         self.api.id()
@@ -189,6 +289,39 @@ impl Connection {
         self.api.keep_alive(enabled)
     }
 
+    pub fn set_idle_timeout(&self, timeout: Duration) -> Result<(), connection::Error> {
+        self.api.set_idle_timeout(timeout)
+    }
+
+    pub fn insert_send_barrier(&self, streams: &[StreamId]) -> Result<(), connection::Error> {
+        self.api.insert_send_barrier(streams)
+    }
+
+    #[inline]
+    pub fn go_away(&self) -> Result<bool, connection::Error> {
+        self.api.go_away()
+    }
+
+    #[inline]
+    pub fn is_go_away_received(&self) -> Result<bool, connection::Error> {
+        self.api.is_go_away_received()
+    }
+
+    #[inline]
+    pub fn rotate_connection_id(&self) -> Result<bool, connection::Error> {
+        self.api.rotate_connection_id()
+    }
+
+    #[inline]
+    pub fn export_keying_material(
+        &self,
+        label: &[u8],
+        context: &[u8],
+        output: &mut [u8],
+    ) -> Result<(), connection::Error> {
+        self.api.export_keying_material(label, context, output)
+    }
+
     #[inline]
     pub fn local_address(&self) -> Result<SocketAddress, connection::Error> {
         self.api.local_address()
@@ -199,6 +332,16 @@ impl Connection {
         self.api.remote_address()
     }
 
+    #[inline]
+    pub fn path_stats(&self) -> Result<Vec<path::Stats>, connection::Error> {
+        self.api.path_stats()
+    }
+
+    #[inline]
+    pub fn mtu_history(&self) -> Result<path::mtu::History, connection::Error> {
+        self.api.mtu_history()
+    }
+
     #[inline]
     pub fn query_event_context(&self, query: &mut dyn Query) -> Result<(), connection::Error> {
         self.api.query_event_context(query)
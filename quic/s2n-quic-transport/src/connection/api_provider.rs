@@ -8,16 +8,17 @@ use crate::{
     connection,
     stream::{Stream, StreamError},
 };
-use alloc::sync::Arc;
+use alloc::{sync::Arc, vec::Vec};
 use bytes::Bytes;
 use core::{
     sync::atomic::AtomicUsize,
     task::{Context, Poll},
+    time::Duration,
 };
 use s2n_quic_core::{
-    application,
     application::ServerName,
     inet::SocketAddress,
+    path,
     query::{Query, QueryMut},
     stream::{ops, StreamId, StreamType},
 };
@@ -49,25 +50,58 @@ pub(crate) trait ConnectionApiProvider: Sync + Send {
         arc_self: &Arc<dyn ConnectionApiProvider>,
         stream_type: StreamType,
         open_token: &mut connection::OpenToken,
-        context: &Context,
+        context: Option<&Context>,
     ) -> Poll<Result<Stream, connection::Error>>;
 
-    fn close_connection(&self, code: Option<application::Error>);
+    fn cancel_open_stream(&self, stream_type: StreamType, open_token: &mut connection::OpenToken);
+
+    fn is_open_stream_blocked(&self, stream_type: StreamType) -> Result<bool, connection::Error>;
+
+    fn poll_flush_transport(&self, context: &Context) -> Poll<Result<(), connection::Error>>;
+
+    fn poll_handshake_completed(&self, context: &Context) -> Poll<Result<(), connection::Error>>;
+
+    fn close_connection(&self, error: Option<connection::Error>, reason: Option<Bytes>);
+
+    fn close_reason(&self) -> Result<Option<Bytes>, connection::Error>;
 
     fn server_name(&self) -> Result<Option<ServerName>, connection::Error>;
 
     fn application_protocol(&self) -> Result<Bytes, connection::Error>;
 
+    fn compression_dictionary_id(&self) -> Result<Option<u64>, connection::Error>;
+
     fn id(&self) -> u64;
 
     fn ping(&self) -> Result<(), connection::Error>;
 
     fn keep_alive(&self, enabled: bool) -> Result<(), connection::Error>;
 
+    fn set_idle_timeout(&self, timeout: Duration) -> Result<(), connection::Error>;
+
+    fn insert_send_barrier(&self, streams: &[StreamId]) -> Result<(), connection::Error>;
+
+    fn go_away(&self) -> Result<bool, connection::Error>;
+
+    fn is_go_away_received(&self) -> Result<bool, connection::Error>;
+
+    fn rotate_connection_id(&self) -> Result<bool, connection::Error>;
+
+    fn export_keying_material(
+        &self,
+        label: &[u8],
+        context: &[u8],
+        output: &mut [u8],
+    ) -> Result<(), connection::Error>;
+
     fn local_address(&self) -> Result<SocketAddress, connection::Error>;
 
     fn remote_address(&self) -> Result<SocketAddress, connection::Error>;
 
+    fn path_stats(&self) -> Result<Vec<path::Stats>, connection::Error>;
+
+    fn mtu_history(&self) -> Result<path::mtu::History, connection::Error>;
+
     fn query_event_context(&self, query: &mut dyn Query) -> Result<(), connection::Error>;
 
     fn query_event_context_mut(&self, query: &mut dyn QueryMut) -> Result<(), connection::Error>;
@@ -37,6 +37,18 @@ impl CloseSender {
         };
     }
 
+    /// Retains the connection in the draining state until the timeout expires
+    ///
+    /// Unlike [`Self::close`], nothing is transmitted while draining.
+    pub fn drain(&mut self, timeout: Duration, now: Timestamp) {
+        debug_assert!(matches!(self.state, State::Idle));
+
+        let mut drain_timer = Timer::default();
+        drain_timer.set(now + timeout);
+
+        self.state = State::Draining { drain_timer };
+    }
+
     pub fn on_timeout(&mut self, now: Timestamp) -> Poll<()> {
         self.state.on_timeout(now)
     }
@@ -90,7 +102,7 @@ impl finalization::Provider for CloseSender {
     fn finalization_status(&self) -> finalization::Status {
         match &self.state {
             State::Idle => finalization::Status::Idle,
-            State::Closing { .. } => finalization::Status::Draining,
+            State::Closing { .. } | State::Draining { .. } => finalization::Status::Draining,
             State::Closed => finalization::Status::Final,
         }
     }
@@ -199,6 +211,9 @@ enum State {
         transmission: TransmissionState,
         close_timer: Timer,
     },
+    Draining {
+        drain_timer: Timer,
+    },
     Closed,
 }
 
@@ -229,6 +244,14 @@ impl State {
 
                 Poll::Pending
             }
+            Self::Draining { drain_timer } => {
+                if drain_timer.poll_expiration(now).is_ready() {
+                    *self = Self::Closed;
+                    return Poll::Ready(());
+                }
+
+                Poll::Pending
+            }
             Self::Closed => Poll::Ready(()),
         }
     }
@@ -253,6 +276,10 @@ impl timer::Provider for State {
             limiter.timers(query)?;
         };
 
+        if let Self::Draining { drain_timer } = self {
+            drain_timer.timers(query)?;
+        }
+
         Ok(())
     }
 }
@@ -317,7 +344,7 @@ impl timer::Provider for Limiter {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::path::testing::helper_path_server;
+    use crate::{connection::finalization::Provider as _, path::testing::helper_path_server};
     use s2n_quic_core::{
         event::testing::Publisher,
         io::tx::Message as _,
@@ -408,6 +435,33 @@ mod tests {
             })
     }
 
+    #[test]
+    fn drain_test() {
+        let mut sender = CloseSender::default();
+        let mut clock = Clock::default();
+        let drain_time = Duration::from_millis(500);
+
+        sender.drain(drain_time, clock.get_time());
+
+        assert!(!sender.has_transmission_interest());
+        assert!(sender.finalization_status().is_draining());
+        assert_eq!(
+            sender.next_expiration(),
+            Some(clock.get_time() + drain_time)
+        );
+
+        // packets from the peer don't cause any transmissions
+        sender.on_datagram_received(Duration::from_millis(100), clock.get_time());
+        assert!(!sender.has_transmission_interest());
+
+        clock.inc_by(drain_time / 2);
+        assert!(sender.on_timeout(clock.get_time()).is_pending());
+
+        clock.inc_by(drain_time / 2);
+        assert!(sender.on_timeout(clock.get_time()).is_ready());
+        assert!(sender.finalization_status().is_final());
+    }
+
     #[test]
     fn limiter_test() {
         let mut limiter = Limiter::default();
@@ -17,7 +17,7 @@ use crate::{
     },
     stream,
 };
-use alloc::{collections::BTreeMap, sync::Arc};
+use alloc::{collections::BTreeMap, sync::Arc, vec::Vec};
 use bytes::Bytes;
 use core::{
     cell::Cell,
@@ -26,15 +26,16 @@ use core::{
     pin::Pin,
     sync::atomic::AtomicUsize,
     task::{Context, Poll},
+    time::Duration,
 };
 use intrusive_collections::{
     intrusive_adapter, KeyAdapter, LinkedList, LinkedListLink, RBTree, RBTreeLink,
 };
 use s2n_quic_core::{
-    application,
     application::ServerName,
     event::supervisor,
     inet::SocketAddress,
+    path,
     query::{Query, QueryMut},
     recovery::K_GRANULARITY,
     time::Timestamp,
@@ -277,7 +278,7 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionApiProvider for Con
         arc_self: &ConnectionApi,
         stream_type: stream::StreamType,
         open_token: &mut connection::OpenToken,
-        context: &Context,
+        context: Option<&Context>,
     ) -> Poll<Result<stream::Stream, connection::Error>> {
         let response =
             self.api_poll_call(|conn| conn.poll_open_stream(stream_type, open_token, context));
@@ -295,13 +296,43 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionApiProvider for Con
         }
     }
 
-    fn close_connection(&self, error: Option<application::Error>) {
+    fn cancel_open_stream(
+        &self,
+        stream_type: stream::StreamType,
+        open_token: &mut connection::OpenToken,
+    ) {
+        let _: Result<(), connection::Error> = self.api_write_call(|conn| {
+            conn.cancel_open_stream(stream_type, open_token);
+            Ok(())
+        });
+    }
+
+    fn is_open_stream_blocked(
+        &self,
+        stream_type: stream::StreamType,
+    ) -> Result<bool, connection::Error> {
+        self.api_read_call(|conn| conn.is_open_stream_blocked(stream_type))
+    }
+
+    fn poll_flush_transport(&self, context: &Context) -> Poll<Result<(), connection::Error>> {
+        self.api_poll_call(|conn| conn.poll_flush_transport(context))
+    }
+
+    fn poll_handshake_completed(&self, context: &Context) -> Poll<Result<(), connection::Error>> {
+        self.api_poll_call(|conn| conn.poll_handshake_completed(context))
+    }
+
+    fn close_connection(&self, error: Option<connection::Error>, reason: Option<Bytes>) {
         let _: Result<(), connection::Error> = self.api_write_call(|conn| {
-            conn.application_close(error);
+            conn.application_close(error, reason);
             Ok(())
         });
     }
 
+    fn close_reason(&self) -> Result<Option<Bytes>, connection::Error> {
+        self.api_read_call(|conn| Ok(conn.close_reason()))
+    }
+
     fn server_name(&self) -> Result<Option<ServerName>, connection::Error> {
         self.api_read_call(|conn| Ok(conn.server_name()))
     }
@@ -310,6 +341,10 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionApiProvider for Con
         self.api_read_call(|conn| Ok(conn.application_protocol()))
     }
 
+    fn compression_dictionary_id(&self) -> Result<Option<u64>, connection::Error> {
+        self.api_read_call(|conn| Ok(conn.compression_dictionary_id()))
+    }
+
     fn id(&self) -> u64 {
         self.internal_connection_id.into()
     }
@@ -322,6 +357,35 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionApiProvider for Con
         self.api_write_call(|conn| conn.keep_alive(enabled))
     }
 
+    fn set_idle_timeout(&self, timeout: Duration) -> Result<(), connection::Error> {
+        self.api_write_call(|conn| conn.set_idle_timeout(timeout))
+    }
+
+    fn insert_send_barrier(&self, streams: &[stream::StreamId]) -> Result<(), connection::Error> {
+        self.api_write_call(|conn| conn.insert_send_barrier(streams))
+    }
+
+    fn go_away(&self) -> Result<bool, connection::Error> {
+        self.api_write_call(|conn| conn.go_away())
+    }
+
+    fn is_go_away_received(&self) -> Result<bool, connection::Error> {
+        self.api_read_call(|conn| conn.is_go_away_received())
+    }
+
+    fn rotate_connection_id(&self) -> Result<bool, connection::Error> {
+        self.api_write_call(|conn| conn.rotate_connection_id())
+    }
+
+    fn export_keying_material(
+        &self,
+        label: &[u8],
+        context: &[u8],
+        output: &mut [u8],
+    ) -> Result<(), connection::Error> {
+        self.api_read_call(|conn| conn.export_keying_material(label, context, output))
+    }
+
     fn local_address(&self) -> Result<SocketAddress, connection::Error> {
         self.api_read_call(|conn| conn.local_address())
     }
@@ -330,6 +394,14 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionApiProvider for Con
         self.api_read_call(|conn| conn.remote_address())
     }
 
+    fn path_stats(&self) -> Result<Vec<path::Stats>, connection::Error> {
+        self.api_read_call(|conn| conn.path_stats())
+    }
+
+    fn mtu_history(&self) -> Result<path::mtu::History, connection::Error> {
+        self.api_read_call(|conn| conn.mtu_history())
+    }
+
     #[inline]
     fn query_event_context(&self, query: &mut dyn Query) -> Result<(), connection::Error> {
         self.api_read_call(|conn| {
@@ -510,7 +582,7 @@ impl<C: connection::Trait, L: connection::Lock<C>> InterestLists<C, L> {
             match <C::Config as endpoint::Config>::ENDPOINT_TYPE {
                 endpoint::Type::Server => {
                     if let Err(error) = accept_queue.unbounded_send(handle) {
-                        error.into_inner().api.close_connection(None);
+                        error.into_inner().api.close_connection(None, None);
                     }
                 }
                 endpoint::Type::Client => {
@@ -529,7 +601,7 @@ impl<C: connection::Trait, L: connection::Lock<C>> InterestLists<C, L> {
                             if let Err(Ok(handle)) = sender.send(Ok(handle)) {
                                 if !any_interest {
                                     // close the connection if the application is no longer waiting for the handshake
-                                    handle.api.close_connection(None);
+                                    handle.api.close_connection(None, None);
                                 }
                             }
                         }
@@ -819,6 +891,39 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionContainer<C, L> {
         }
     }
 
+    /// Closes client connections which are still opening but no longer have any attempts
+    /// waiting on them
+    ///
+    /// Returns the number of connections which were closed.
+    pub fn close_abandoned_connections(&mut self, cx: &mut Context) -> usize {
+        debug_assert!(<C::Config as endpoint::Config>::ENDPOINT_TYPE.is_client());
+
+        let abandoned: SmallVec<[InternalConnectionId; 4]> = self
+            .interest_lists
+            .waiting_for_open
+            .iter_mut()
+            .filter_map(|(id, senders)| {
+                let is_abandoned = senders
+                    .iter_mut()
+                    .all(|sender| sender.poll_canceled(cx).is_ready());
+                is_abandoned.then_some(*id)
+            })
+            .collect();
+
+        for id in abandoned.iter().copied() {
+            // remove the entry so the connection is only closed once
+            self.interest_lists.waiting_for_open.remove(&id);
+            self.with_connection(id, |conn| {
+                conn.application_close(
+                    Some(connection::Error::closed(endpoint::Location::Local)),
+                    None,
+                );
+            });
+        }
+
+        abandoned.len()
+    }
+
     pub(crate) fn poll_connection_request(
         &mut self,
         cx: &mut Context,
@@ -988,6 +1093,22 @@ impl<C: connection::Trait, L: connection::Lock<C>> ConnectionContainer<C, L> {
         }
     }
 
+    /// Executes the given function on every `Connection`
+    pub fn for_each<F>(&mut self, mut func: F)
+    where
+        F: FnMut(&mut C),
+    {
+        let ids: Vec<_> = self
+            .connection_map
+            .iter()
+            .map(|node| node.internal_connection_id)
+            .collect();
+
+        for id in ids {
+            self.with_connection(id, &mut func);
+        }
+    }
+
     /// Iterates over all `Connection`s which are waiting for transmission,
     /// and executes the given function on each `Connection`
     pub fn iterate_transmission_list<F>(&mut self, mut func: F)
@@ -17,7 +17,7 @@ use core::{
     time::Duration,
 };
 use s2n_quic_core::{
-    application, event,
+    event,
     event::builder::DatagramDropReason,
     inet::{DatagramInfo, SocketAddress},
     io::tx,
@@ -114,6 +114,10 @@ impl connection::Trait for TestConnection {
         Ok(())
     }
 
+    fn transmission_weight(&self) -> u8 {
+        1
+    }
+
     fn on_timeout(
         &mut self,
         _connection_id_mapper: &mut connection::ConnectionIdMapper,
@@ -138,6 +142,12 @@ impl connection::Trait for TestConnection {
         Ok(())
     }
 
+    fn on_network_change(
+        &mut self,
+        _random_generator: &mut <Self::Config as endpoint::Config>::RandomGenerator,
+    ) {
+    }
+
     fn handle_initial_packet(
         &mut self,
         _datagram: &DatagramInfo,
@@ -277,15 +287,45 @@ impl connection::Trait for TestConnection {
         &mut self,
         _stream_type: stream::StreamType,
         _token: &mut connection::OpenToken,
-        _context: &Context,
+        _context: Option<&Context>,
     ) -> Poll<Result<stream::StreamId, connection::Error>> {
         todo!()
     }
 
-    fn application_close(&mut self, _error: Option<application::Error>) {
+    fn cancel_open_stream(
+        &mut self,
+        _stream_type: stream::StreamType,
+        _open_token: &mut connection::OpenToken,
+    ) {
+        todo!()
+    }
+
+    fn is_open_stream_blocked(
+        &self,
+        _stream_type: stream::StreamType,
+    ) -> Result<bool, connection::Error> {
+        todo!()
+    }
+
+    fn poll_flush_transport(&mut self, _context: &Context) -> Poll<Result<(), connection::Error>> {
+        todo!()
+    }
+
+    fn poll_handshake_completed(
+        &mut self,
+        _context: &Context,
+    ) -> Poll<Result<(), connection::Error>> {
+        todo!()
+    }
+
+    fn application_close(&mut self, _error: Option<connection::Error>, _reason: Option<Bytes>) {
         // no-op
     }
 
+    fn close_reason(&self) -> Option<Bytes> {
+        todo!()
+    }
+
     fn server_name(&self) -> Option<ServerName> {
         todo!()
     }
@@ -294,6 +334,10 @@ impl connection::Trait for TestConnection {
         todo!()
     }
 
+    fn compression_dictionary_id(&self) -> Option<u64> {
+        todo!()
+    }
+
     fn ping(&mut self) -> Result<(), connection::Error> {
         todo!()
     }
@@ -302,6 +346,38 @@ impl connection::Trait for TestConnection {
         todo!()
     }
 
+    fn set_idle_timeout(&mut self, _timeout: Duration) -> Result<(), connection::Error> {
+        todo!()
+    }
+
+    fn insert_send_barrier(
+        &mut self,
+        _streams: &[stream::StreamId],
+    ) -> Result<(), connection::Error> {
+        todo!()
+    }
+
+    fn go_away(&mut self) -> Result<bool, connection::Error> {
+        todo!()
+    }
+
+    fn is_go_away_received(&self) -> Result<bool, connection::Error> {
+        todo!()
+    }
+
+    fn rotate_connection_id(&mut self) -> Result<bool, connection::Error> {
+        todo!()
+    }
+
+    fn export_keying_material(
+        &self,
+        _label: &[u8],
+        _context: &[u8],
+        _output: &mut [u8],
+    ) -> Result<(), connection::Error> {
+        todo!()
+    }
+
     fn local_address(&self) -> Result<SocketAddress, connection::Error> {
         todo!()
     }
@@ -310,6 +386,14 @@ impl connection::Trait for TestConnection {
         Ok(SocketAddress::default())
     }
 
+    fn path_stats(&self) -> Result<Vec<path::Stats>, connection::Error> {
+        Ok(Vec::new())
+    }
+
+    fn mtu_history(&self) -> Result<path::mtu::History, connection::Error> {
+        Ok(Default::default())
+    }
+
     fn error(&self) -> Option<connection::Error> {
         None
     }
@@ -418,7 +502,8 @@ fn container_test() {
     check!().with_type::<Vec<Operation>>().for_each(|ops| {
         let mut id_gen = InternalConnectionIdGenerator::new();
         let mut connections = vec![];
-        let (handle, acceptor, connector, _close_handle) = endpoint::handle::Handle::new(100);
+        let (handle, acceptor, connector, _close_handle, _network_change, _delay) =
+            endpoint::handle::Handle::new(100);
         let (waker, _wake_count) = futures_test::task::new_count_waker();
         let mut now = unsafe { Timestamp::from_duration(Duration::from_secs(0)) };
 
@@ -3,12 +3,14 @@
 
 //! Maps from external connection IDs to internal connection IDs
 
-use crate::connection::{local_id_registry::LocalIdRegistry, InternalConnectionId, PeerIdRegistry};
+use crate::connection::{
+    local_id_registry::LocalIdRegistry, InternalConnectionId, Mutex, PeerIdRegistry,
+};
+use alloc::sync::Arc;
 use core::{convert::TryFrom as _, hash::BuildHasher};
 use hashbrown::hash_map::{Entry, HashMap};
 use s2n_quic_core::{connection, endpoint, random, stateless_reset, time::Timestamp};
 use siphasher::sip::SipHasher13;
-use std::sync::{Arc, Mutex};
 
 // Since the input to the hash function (stateless reset token) come from the peer, we need to
 // ensure that maliciously crafted values do not result in poor bucketing and thus degraded
@@ -279,6 +281,15 @@ impl ConnectionIdMapper {
             })
     }
 
+    /// Returns true if the given local connection ID is currently registered to a connection
+    pub fn is_local_id_registered(&self, connection_id: &connection::LocalId) -> bool {
+        let guard = self
+            .state
+            .lock()
+            .expect("should succeed unless the lock is poisoned");
+        guard.local_id_map.get(connection_id).is_some()
+    }
+
     /// Inserts the given `InitialId` into the map if it is not already in the map,
     /// otherwise returns an Err
     pub fn try_insert_initial_id(
@@ -405,7 +416,7 @@ impl ConnectionIdMapper {
         match guard.open_request_map.open_request_map.entry(connect) {
             Entry::Occupied(e) => Ok(*e.get()),
             Entry::Vacant(e) => {
-                let connect = e.key().clone();
+                let connect = Box::new(e.key().clone());
                 e.insert(new_connection_internal_id);
                 Err(OpenRegistry {
                     state: self.state.clone(),
@@ -420,7 +431,8 @@ impl ConnectionIdMapper {
 pub struct OpenRegistry {
     /// The shared state between mapper and registration
     state: Arc<Mutex<ConnectionIdMapperState>>,
-    connect: crate::endpoint::connect::Connect,
+    // boxed to keep the registry small, since it's returned as an error from `lazy_open`
+    connect: Box<crate::endpoint::connect::Connect>,
 }
 
 impl Drop for OpenRegistry {
@@ -430,7 +442,7 @@ impl Drop for OpenRegistry {
             guard
                 .open_request_map
                 .open_request_map
-                .remove(&self.connect);
+                .remove(&*self.connect);
         }
     }
 }
@@ -482,6 +494,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn lazy_open_limits_test() {
+        use crate::endpoint::connect::Connect;
+        use s2n_quic_core::{connection::limits::Overrides, inet::SocketAddress};
+
+        let mut random_generator = random::testing::Generator(123);
+        let mapper = ConnectionIdMapper::new(&mut random_generator, endpoint::Type::Client);
+        let mut id_generator = InternalConnectionIdGenerator::new();
+        let first_id = id_generator.generate_id();
+        let second_id = id_generator.generate_id();
+
+        let connect = Connect::new(SocketAddress::default());
+        let _registry = mapper.lazy_open(first_id, connect.clone()).unwrap_err();
+        assert_eq!(
+            mapper.lazy_open(second_id, connect.clone()).unwrap(),
+            first_id
+        );
+
+        // requests with different limit overrides don't share a connection
+        let overrides = Overrides::default().with_data_window(1000).unwrap();
+        let _registry = mapper
+            .lazy_open(second_id, connect.with_limits(overrides))
+            .unwrap_err();
+    }
+
     #[test]
     fn initial_id_map() {
         let mut random_generator = random::testing::Generator(123);
@@ -19,13 +19,13 @@ use crate::{
     path::{self, path_event},
     processed_packet::ProcessedPacket,
     recovery::{recovery_event, RttEstimator},
-    space::{PacketSpace, PacketSpaceManager},
+    space::{PacketBudget, PacketSpace, PacketSpaceManager},
     stream::{self, Manager as _},
     transmission,
     transmission::interest::Provider as _,
     wakeup_queue::WakeupHandle,
 };
-use alloc::sync::Arc;
+use alloc::{sync::Arc, vec, vec::Vec};
 use bytes::Bytes;
 use core::{
     fmt,
@@ -33,9 +33,8 @@ use core::{
     time::Duration,
 };
 use s2n_quic_core::{
-    application,
     application::ServerName,
-    connection::{error::Error, id::Generator as _, InitialId, PeerId},
+    connection::{close::Formatter as _, error::Error, id::Generator as _, InitialId, PeerId},
     crypto::{tls, CryptoSuite},
     datagram::{Receiver, Sender},
     event::{
@@ -54,9 +53,12 @@ use s2n_quic_core::{
         version_negotiation::ProtectedVersionNegotiation,
         zero_rtt::ProtectedZeroRtt,
     },
-    path::{mtu, Handle as _},
+    path::{
+        cache::{self, Cache as _},
+        mtu, Handle as _,
+    },
     query,
-    recovery::CongestionController,
+    recovery::{CongestionController, MIN_RTT},
     stateless_reset::token::Generator as _,
     time::{timer, Timestamp},
     transport,
@@ -167,10 +169,19 @@ pub struct ConnectionImpl<Config: endpoint::Config> {
     path_manager: path::Manager<Config>,
     /// The limits applied to the current connection
     limits: Limits,
+    /// The idle timeout requested by the application after the handshake
+    ///
+    /// The effective idle timeout is bounded by the negotiated value.
+    idle_timeout: Option<Duration>,
     /// The error set on the connection
     ///
     /// This is stored so future calls from the application return the same error
     error: Result<(), connection::Error>,
+    /// The reason phrase of the CONNECTION_CLOSE frame which closed the connection
+    ///
+    /// Unlike the reason in the `error`, this isn't truncated and is retained for every kind of
+    /// close.
+    close_reason: Option<Bytes>,
     /// Sends CONNECTION_CLOSE close frames after the connection is closed
     close_sender: CloseSender,
     /// Manages all of the different packet spaces and their respective components
@@ -179,7 +190,13 @@ pub struct ConnectionImpl<Config: endpoint::Config> {
     wakeup_handle: Arc<WakeupHandle<InternalConnectionId>>,
     /// A Waker to the connection.
     waker: Waker,
+    /// The `Waker` for the task which is waiting for pending packets to be transmitted
+    flush_transport_waker: Option<Waker>,
+    /// The `Waker` for the task which is waiting for the handshake to be confirmed
+    handshake_waker: Option<Waker>,
     event_context: EventContext<Config>,
+    /// Stores the characteristics of the active path when the connection closes
+    path_cache: Arc<Config::PathCache>,
 }
 
 struct EventContext<Config: endpoint::Config> {
@@ -325,6 +342,8 @@ impl<Config: endpoint::Config> ConnectionImpl<Config> {
 
             // Cancel the max handshake duration timer as the handshake has completed in time
             self.timers.max_handshake_duration_timer.cancel();
+            // Completing the handshake also validates the peer address
+            self.timers.max_address_validation_duration_timer.cancel();
 
             // We don't expect any further initial packets on this connection, so start
             // a timer to remove the mapping from the initial ID to the internal connection ID
@@ -339,6 +358,26 @@ impl<Config: endpoint::Config> ConnectionImpl<Config> {
         Ok(())
     }
 
+    /// Returns the idle timeout negotiated with the peer, bounded by the value requested by the
+    /// application
+    fn effective_idle_timeout(&self) -> Option<Duration> {
+        let negotiated = self.limits.max_idle_timeout();
+
+        match (negotiated, self.idle_timeout) {
+            (Some(negotiated), Some(requested)) => Some(negotiated.min(requested)),
+            (negotiated, requested) => negotiated.or(requested),
+        }
+    }
+
+    /// Returns true if the application shortened the idle timeout below the negotiated value
+    fn is_idle_timeout_shortened(&self) -> bool {
+        self.idle_timeout.is_some_and(|requested| {
+            self.limits
+                .max_idle_timeout()
+                .map_or(true, |negotiated| requested < negotiated)
+        })
+    }
+
     /// Returns the idle timeout based on transport parameters of both peers
     fn get_idle_timer_duration(&self) -> Option<Duration> {
         //= https://www.rfc-editor.org/rfc/rfc9000#section-10.1
@@ -349,7 +388,7 @@ impl<Config: endpoint::Config> ConnectionImpl<Config> {
         //# commits to initiating an immediate close (Section 10.2) if it
         //# abandons the connection prior to the effective value.
 
-        let mut duration = self.limits.max_idle_timeout()?.as_millis() as u64;
+        let mut duration = self.effective_idle_timeout()?.as_millis() as u64;
 
         //= https://www.rfc-editor.org/rfc/rfc9000#section-10.1
         //# To avoid excessively small idle timeout periods, endpoints MUST
@@ -377,6 +416,8 @@ impl<Config: endpoint::Config> ConnectionImpl<Config> {
             self.timers.reset_peer_idle_timer_on_send = true;
         }
 
+        self.arm_hibernation_timer(packet.datagram.timestamp);
+
         let mut publisher = self
             .event_context
             .publisher(packet.datagram.timestamp, subscriber);
@@ -395,6 +436,8 @@ impl<Config: endpoint::Config> ConnectionImpl<Config> {
                 .discard_handshake(&mut self.path_manager, &mut publisher);
         }
 
+        self.wake_handshake();
+
         // check to see if we're flushing and should now close the connection
         if self.poll_flush().is_ready() {
             self.error?;
@@ -416,6 +459,15 @@ impl<Config: endpoint::Config> ConnectionImpl<Config> {
                 self.timers.peer_idle_timer.set(timestamp + duration);
             }
         }
+
+        self.arm_hibernation_timer(timestamp);
+    }
+
+    /// Restarts the hibernation timer after activity on the connection
+    fn arm_hibernation_timer(&mut self, timestamp: Timestamp) {
+        if let Some(threshold) = self.limits.idle_hibernation_threshold() {
+            self.timers.hibernation_timer.set(timestamp + threshold);
+        }
     }
 
     fn current_pto(&self) -> Duration {
@@ -567,6 +619,77 @@ impl<Config: endpoint::Config> ConnectionImpl<Config> {
 
         Poll::Pending
     }
+
+    /// Wakes the task waiting on `poll_flush_transport` once nothing else can be transmitted
+    fn wake_flush_transport(&mut self) {
+        if self.flush_transport_waker.is_none() {
+            return;
+        }
+
+        if self.error.is_err() || !self.has_pending_transmission() {
+            if let Some(waker) = self.flush_transport_waker.take() {
+                waker.wake();
+            }
+        }
+    }
+
+    /// Wakes the task waiting on `poll_handshake_completed` once the handshake is confirmed
+    fn wake_handshake(&mut self) {
+        if self.error.is_err() || self.space_manager.is_handshake_confirmed() {
+            if let Some(waker) = self.handshake_waker.take() {
+                waker.wake();
+            }
+        }
+    }
+
+    /// Returns `true` if the connection has packets which it is currently able to transmit
+    ///
+    /// Data which is blocked by the congestion controller or anti-amplification limits isn't
+    /// considered pending, while data delayed by pacing is.
+    fn has_pending_transmission(&self) -> bool {
+        use transmission::interest::Provider as _;
+
+        match self.state {
+            ConnectionState::Active | ConnectionState::Handshaking | ConnectionState::Flushing => {
+                self.can_transmit(self.path_manager.transmission_constraint())
+            }
+            ConnectionState::Closing | ConnectionState::Draining | ConnectionState::Finished => {
+                false
+            }
+        }
+    }
+
+    /// Stores the characteristics of the active path in the path cache
+    ///
+    /// Nothing is stored if the connection never measured the RTT of the path.
+    fn store_path_characteristics(&self) {
+        let path = self.path_manager.active_path();
+
+        if path.rtt_estimator.first_rtt_sample().is_none() {
+            return;
+        }
+
+        let remote_address = path.remote_address();
+        let entry = cache::Entry {
+            smoothed_rtt: path.rtt_estimator.smoothed_rtt(),
+            min_rtt: path.rtt_estimator.min_rtt(),
+            mtu: path.mtu_controller.mtu(&remote_address),
+            congestion_window: path.congestion_controller.congestion_window(),
+        };
+
+        self.path_cache
+            .insert(&cache::PathInfo::new(&remote_address), entry);
+    }
+
+    /// Validates the peer address of the path on which a Handshake packet was processed
+    fn on_handshake_packet_processed(&mut self, path_id: path::Id) {
+        self.path_manager[path_id].on_handshake_packet();
+
+        // The connection no longer needs to be closed for failing to validate the peer
+        if self.path_manager.active_path().is_validated() {
+            self.timers.max_address_validation_duration_timer.cancel();
+        }
+    }
 }
 
 impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
@@ -589,7 +712,13 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
             quic_version: parameters.quic_version,
         };
 
-        let rtt_estimator = RttEstimator::new(parameters.limits.initial_round_trip_time());
+        // Prefer the RTT measured by a previous connection to the same peer over the configured
+        // initial RTT
+        let initial_rtt = parameters
+            .cached_path
+            .map(|entry| entry.smoothed_rtt.max(MIN_RTT))
+            .unwrap_or_else(|| parameters.limits.initial_round_trip_time());
+        let rtt_estimator = RttEstimator::new(initial_rtt);
         // Assume clients validate the server's address implicitly.
         let peer_validated = Self::Config::ENDPOINT_TYPE.is_server();
         // The path manager always starts with a single path containing the known peer and local
@@ -605,7 +734,9 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
             parameters.limits.anti_amplification_multiplier(),
         );
 
-        let path_manager = path::Manager::new(initial_path, parameters.peer_id_registry);
+        let mut path_manager = path::Manager::new(initial_path, parameters.peer_id_registry);
+        path_manager.set_probe_policy(parameters.limits.path_probe_policy());
+        path_manager.set_rotation_policy(parameters.limits.connection_id_rotation_policy());
 
         let mut publisher =
             event_context.publisher(parameters.timestamp, parameters.event_subscriber);
@@ -644,12 +775,17 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
             state: ConnectionState::Handshaking,
             path_manager,
             limits: parameters.limits,
+            idle_timeout: None,
             error: Ok(()),
+            close_reason: None,
             close_sender: CloseSender::default(),
             space_manager: parameters.space_manager,
             wakeup_handle,
             waker,
+            flush_transport_waker: None,
+            handshake_waker: None,
             event_context,
+            path_cache: parameters.path_cache,
         };
 
         if Config::ENDPOINT_TYPE.is_client() {
@@ -696,6 +832,17 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
             .max_handshake_duration_timer
             .set(parameters.timestamp + connection.limits.max_handshake_duration());
 
+        if let Some(duration) = connection.limits.max_address_validation_duration() {
+            if Config::ENDPOINT_TYPE.is_server()
+                && !connection.path_manager.active_path().is_validated()
+            {
+                connection
+                    .timers
+                    .max_address_validation_duration_timer
+                    .set(parameters.timestamp + duration);
+            }
+        }
+
         Ok(connection)
     }
 
@@ -734,6 +881,7 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
 
         // We don't need any timers anymore
         self.timers.cancel();
+        self.store_path_characteristics();
         // Update the connection state based on the type of error
         self.state = error.into();
         self.error = Err(error);
@@ -749,9 +897,33 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         let close_context = s2n_quic_core::connection::close::Context::new(&remote_address);
         let active_path_id = self.path_manager.active_path_id();
 
-        if let Some((early_connection_close, connection_close)) =
-            s2n_quic_core::connection::error::as_frame(error, close_formatter, &close_context)
+        // the error only carries a truncated copy of the reason provided by the application
+        let close_reason = self.close_reason.clone();
+        let mut frames =
+            s2n_quic_core::connection::error::as_frame(&error, close_formatter, &close_context);
+
+        if let (Some(reason), Some((_, connection_close))) = (&close_reason, &mut frames) {
+            if connection_close.reason.is_some() && connection_close.frame_type.is_none() {
+                connection_close.reason = Some(reason);
+            }
+        }
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-10.1
+        //# By announcing a max_idle_timeout, an endpoint
+        //# commits to initiating an immediate close (Section 10.2) if it
+        //# abandons the connection prior to the effective value.
+        if matches!(error, connection::Error::IdleTimerExpired { .. })
+            && self.is_idle_timeout_shortened()
         {
+            let error = transport::Error::NO_ERROR.with_reason("idle timeout");
+            frames = Some((
+                close_formatter.format_early_transport_error(&close_context, error),
+                close_formatter.format_transport_error(&close_context, error),
+            ));
+            self.state = ConnectionState::Closing;
+        }
+
+        if let Some((early_connection_close, connection_close)) = frames {
             let mut outcome = transmission::Outcome::default();
             let mut context = transmission_context!(
                 self,
@@ -774,7 +946,9 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
                 //# connections close cleanly and that delayed or reordered packets are
                 //# properly discarded.  These states SHOULD persist for at least three
                 //# times the current PTO interval as defined in [QUIC-RECOVERY].
-                let timeout = 3 * self.current_pto();
+
+                // The multiplier is validated to be at least 3 when configuring the limits
+                let timeout = self.limits.closing_period_multiplier() as u32 * self.current_pto();
 
                 self.close_sender.close(packet, timeout, timestamp);
             } else if cfg!(debug_assertions) {
@@ -782,6 +956,14 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
             }
         }
 
+        if let (ConnectionState::Draining, Some(draining_period)) =
+            (self.state, self.limits.draining_period())
+        {
+            // Retain the connection IDs for the configured period so delayed packets from the
+            // peer are discarded by the connection
+            self.close_sender.drain(draining_period, timestamp);
+        }
+
         if self.close_sender.has_transmission_interest() {
             debug_assert_eq!(
                 self.state,
@@ -819,6 +1001,10 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         let mut publisher = self.event_context.publisher(timestamp, subscriber);
         self.space_manager
             .close(error, &mut self.path_manager, timestamp, &mut publisher);
+
+        // Notify any flush requests that the connection has closed
+        self.wake_flush_transport();
+        self.wake_handshake();
     }
 
     /// Generates and registers new connection IDs using the given `ConnectionIdFormat`
@@ -926,6 +1112,8 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
                     self.on_ack_eliciting_packet_sent(timestamp);
                 }
 
+                self.path_manager.on_active_path_packets_sent(count);
+
                 if let Some(edt) = self
                     .path_manager
                     .active_path()
@@ -977,6 +1165,9 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
                 }
 
                 let mut publisher = self.event_context.publisher(timestamp, subscriber);
+                self.path_manager
+                    .poll_connection_id_rotation(&mut publisher);
+
                 if outcome.bytes_progressed > 0 {
                     publisher.on_tx_stream_progress(TxStreamProgress {
                         bytes: outcome.bytes_progressed,
@@ -1008,6 +1199,8 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
             }
         }
 
+        self.wake_flush_transport();
+
         if count == 0 {
             Err(ConnectionOnTransmitError::NoDatagram)
         } else {
@@ -1015,6 +1208,11 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         }
     }
 
+    #[inline]
+    fn transmission_weight(&self) -> u8 {
+        self.limits.transmission_weight()
+    }
+
     /// Handles all timeouts on the `Connection`.
     ///
     /// `timestamp` passes the current time.
@@ -1063,6 +1261,23 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
             &mut publisher,
         );
 
+        if self
+            .timers
+            .hibernation_timer
+            .poll_expiration(timestamp)
+            .is_ready()
+            && self.state == ConnectionState::Active
+        {
+            // the connection has been idle so release the buffers until the next packet
+            self.space_manager.on_hibernate();
+
+            if let Some(idle_threshold) = self.limits.idle_hibernation_threshold() {
+                publisher.on_connection_hibernated(event::builder::ConnectionHibernated {
+                    idle_threshold,
+                });
+            }
+        }
+
         if self
             .timers
             .max_handshake_duration_timer
@@ -1075,6 +1290,19 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
             ));
         }
 
+        if self
+            .timers
+            .max_address_validation_duration_timer
+            .poll_expiration(timestamp)
+            .is_ready()
+            && !self.path_manager.active_path().is_validated()
+        {
+            //= https://www.rfc-editor.org/rfc/rfc9000#section-9
+            //# When an endpoint has no validated path on which to send packets, it
+            //# MAY discard connection state.
+            return Err(connection::Error::no_valid_path());
+        }
+
         if self
             .timers
             .peer_idle_timer
@@ -1098,6 +1326,9 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
             return self.error;
         }
 
+        // transmission may no longer be possible, e.g. if lost data was acknowledged
+        self.wake_flush_transport();
+
         // TODO: enable this check once all of the component timers are fixed
         /*
         if cfg!(debug_assertions) {
@@ -1138,9 +1369,61 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         // return an error if the application set one
         self.error?;
 
+        // apply the idle timeout if the application changed it
+        if core::mem::take(&mut self.timers.idle_timeout_updated) {
+            if self.timers.peer_idle_timer.is_armed() {
+                if let Some(duration) = self.get_idle_timer_duration() {
+                    self.timers.peer_idle_timer.set(timestamp + duration);
+                }
+            }
+
+            let idle_timeout = self.effective_idle_timeout();
+            if let Some((space, _)) = self.space_manager.application_mut() {
+                space.on_idle_timeout_update(idle_timeout, timestamp);
+            }
+        }
+
+        // start the lifetimes of any data which was written with an expiration
+        if let Some((space, _)) = self.space_manager.application_mut() {
+            space.stream_manager.on_wakeup(timestamp);
+        }
+
+        // rotate the connection ID if the application requested it
+        let mut publisher = self.event_context.publisher(timestamp, subscriber);
+        self.path_manager
+            .poll_connection_id_rotation(&mut publisher);
+
+        self.wake_flush_transport();
+
         Ok(())
     }
 
+    fn on_network_change(&mut self, random_generator: &mut Config::RandomGenerator) {
+        if !matches!(
+            self.state,
+            ConnectionState::Active | ConnectionState::Flushing
+        ) {
+            return;
+        }
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-9
+        //# An endpoint MUST NOT initiate
+        //# connection migration before the handshake is confirmed, as defined
+        //# in section 4.1.2 of [QUIC-TLS].
+        if !self.space_manager.is_handshake_confirmed() {
+            return;
+        }
+
+        if let Some((space, _)) = self.space_manager.application_mut() {
+            //= https://www.rfc-editor.org/rfc/rfc9000#section-9.2
+            //# An endpoint can migrate a connection to a new local address by
+            //# sending packets containing non-probing frames from that address.
+            space.ping();
+        }
+
+        self.path_manager.on_network_change(random_generator);
+    }
+
     // Packet handling
     fn on_datagram_received(
         &mut self,
@@ -1166,6 +1449,19 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         //# size of packets it receives from that address.
         let handshake_confirmed = self.space_manager.is_handshake_confirmed();
 
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-18.2
+        //# max_udp_payload_size (0x03):  The maximum UDP payload size parameter
+        //#    is an integer value that limits the size of UDP payloads that the
+        //#    endpoint is willing to receive.  UDP datagrams with payloads
+        //#    larger than this limit are not likely to be processed by the
+        //#    receiver.
+        //
+        // The peer isn't guaranteed to have received our transport parameters until the
+        // handshake is confirmed, so the limit is only enforced after that point.
+        if handshake_confirmed && datagram.payload_len as u64 > self.limits.max_udp_payload_size() {
+            return Err(DatagramDropReason::PayloadSizeExceeded);
+        }
+
         let (id, amplification_outcome) = self.path_manager.on_datagram_received(
             path_handle,
             datagram,
@@ -1320,7 +1616,7 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
             // token is received in the first Initial Packet. If that value is set, it should be
             // verified in all subsequent packets.
 
-            let processed_packet = space.handle_cleartext_payload(
+            let Some(processed_packet) = space.handle_cleartext_payload(
                 packet.packet_number,
                 packet.payload,
                 datagram,
@@ -1331,7 +1627,13 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
                 random_generator,
                 &mut publisher,
                 packet_interceptor,
-            )?;
+                PacketBudget::new(&self.limits),
+                &mut self.close_reason,
+            )?
+            else {
+                // the packet interceptor dropped the packet
+                return Ok(());
+            };
 
             // try to move the crypto state machine forward
             self.update_crypto_state(
@@ -1408,7 +1710,7 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
                 ),
             });
 
-            let processed_packet = space.handle_cleartext_payload(
+            let Some(processed_packet) = space.handle_cleartext_payload(
                 packet.packet_number,
                 packet.payload,
                 datagram,
@@ -1419,7 +1721,13 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
                 random_generator,
                 &mut publisher,
                 packet_interceptor,
-            )?;
+                PacketBudget::new(&self.limits),
+                &mut self.close_reason,
+            )?
+            else {
+                // the packet interceptor dropped the packet
+                return Ok(());
+            };
 
             if Self::Config::ENDPOINT_TYPE.is_server() {
                 //= https://www.rfc-editor.org/rfc/rfc9001#section-4.9.1
@@ -1436,7 +1744,7 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
             //# Once an endpoint has successfully processed a
             //# Handshake packet from the peer, it can consider the peer address to
             //# have been validated.
-            self.path_manager[path_id].on_handshake_packet();
+            self.on_handshake_packet_processed(path_id);
 
             // try to move the crypto state machine forward
             self.update_crypto_state(
@@ -1536,7 +1844,7 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
                 &mut publisher,
             );
 
-            let processed_packet = space.handle_cleartext_payload(
+            let Some(processed_packet) = space.handle_cleartext_payload(
                 packet.packet_number,
                 packet.payload,
                 datagram,
@@ -1547,7 +1855,13 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
                 random_generator,
                 &mut publisher,
                 packet_interceptor,
-            )?;
+                PacketBudget::new(&self.limits),
+                &mut self.close_reason,
+            )?
+            else {
+                // the packet interceptor dropped the packet
+                return Ok(());
+            };
 
             // try to process any post-handshake messages
             if Config::ENDPOINT_TYPE.is_client() && processed_packet.contains_crypto {
@@ -1825,12 +2139,18 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
                 interests.transmission = self.close_sender.can_transmit(constraint);
                 interests.finalization = self.close_sender.finalization_status().is_final();
             }
-            ConnectionState::Draining | ConnectionState::Finished => {
+            ConnectionState::Draining => {
                 //= https://www.rfc-editor.org/rfc/rfc9000#section-10.2.2
                 //# While otherwise identical to the closing state, an
                 //# endpoint in the draining state MUST NOT send any packets.
                 interests.transmission = false;
 
+                // Remove the connection from the endpoint once the draining period ends
+                interests.finalization = !self.close_sender.finalization_status().is_draining();
+            }
+            ConnectionState::Finished => {
+                interests.transmission = false;
+
                 // Remove the connection from the endpoint
                 interests.finalization = true;
             }
@@ -1897,7 +2217,7 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         &mut self,
         stream_type: stream::StreamType,
         open_token: &mut connection::OpenToken,
-        context: &Context,
+        context: Option<&Context>,
     ) -> Poll<Result<stream::StreamId, connection::Error>> {
         self.error?;
 
@@ -1916,7 +2236,78 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         )
     }
 
-    fn application_close(&mut self, error: Option<application::Error>) {
+    fn cancel_open_stream(
+        &mut self,
+        stream_type: stream::StreamType,
+        open_token: &mut connection::OpenToken,
+    ) {
+        if let Some((space, _)) = self.space_manager.application_mut() {
+            space
+                .stream_manager
+                .cancel_open_local_stream(stream_type, open_token);
+        }
+    }
+
+    fn is_open_stream_blocked(
+        &self,
+        stream_type: stream::StreamType,
+    ) -> Result<bool, connection::Error> {
+        self.error?;
+
+        Ok(self.space_manager.application().map_or(false, |space| {
+            space
+                .stream_manager
+                .is_local_stream_blocked_by_peer(stream_type)
+        }))
+    }
+
+    fn poll_flush_transport(&mut self, context: &Context) -> Poll<Result<(), connection::Error>> {
+        self.error?;
+
+        if !self.has_pending_transmission() {
+            return Poll::Ready(Ok(()));
+        }
+
+        // only the most recent task is notified, like the other connection-level poll calls
+        if !self
+            .flush_transport_waker
+            .as_ref()
+            .is_some_and(|waker| waker.will_wake(context.waker()))
+        {
+            self.flush_transport_waker = Some(context.waker().clone());
+        }
+
+        // make sure the endpoint gets a chance to transmit the pending packets
+        self.wakeup_handle.wakeup();
+
+        Poll::Pending
+    }
+
+    fn poll_handshake_completed(
+        &mut self,
+        context: &Context,
+    ) -> Poll<Result<(), connection::Error>> {
+        self.error?;
+
+        // Servers confirm the handshake as soon as it completes, while clients wait for the
+        // HANDSHAKE_DONE frame from the server
+        if self.space_manager.is_handshake_confirmed() {
+            return Poll::Ready(Ok(()));
+        }
+
+        // only the most recent task is notified, like the other connection-level poll calls
+        if !self
+            .handshake_waker
+            .as_ref()
+            .is_some_and(|waker| waker.will_wake(context.waker()))
+        {
+            self.handshake_waker = Some(context.waker().clone());
+        }
+
+        Poll::Pending
+    }
+
+    fn application_close(&mut self, error: Option<connection::Error>, reason: Option<Bytes>) {
         if self.error.is_err() {
             return;
         }
@@ -1925,7 +2316,8 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         self.open_registry = None;
 
         if let Some(error) = error {
-            self.error = Err(connection::Error::application(error));
+            self.error = Err(error);
+            self.close_reason = reason;
         } else {
             // give the connection some time to flush all outstanding streams
             self.state = ConnectionState::Flushing;
@@ -1936,6 +2328,10 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         self.wakeup_handle.wakeup();
     }
 
+    fn close_reason(&self) -> Option<Bytes> {
+        self.close_reason.clone()
+    }
+
     fn server_name(&self) -> Option<ServerName> {
         self.space_manager.server_name.clone()
     }
@@ -1944,6 +2340,12 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         self.space_manager.application_protocol.clone()
     }
 
+    fn compression_dictionary_id(&self) -> Option<u64> {
+        self.limits
+            .compression_dictionary_id()
+            .map(|id| id.as_u64())
+    }
+
     fn ping(&mut self) -> Result<(), connection::Error> {
         self.error?;
 
@@ -1980,6 +2382,124 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         Ok(())
     }
 
+    fn set_idle_timeout(&mut self, timeout: Duration) -> Result<(), connection::Error> {
+        self.error?;
+
+        if !self.space_manager.is_handshake_complete() {
+            return Err(connection::Error::invalid_configuration(
+                "the idle timeout can only be changed after the handshake has completed",
+            ));
+        }
+
+        if timeout.is_zero() {
+            return Err(connection::Error::invalid_configuration(
+                "the idle timeout must be greater than zero",
+            ));
+        }
+
+        self.idle_timeout = Some(timeout);
+        self.timers.idle_timeout_updated = true;
+        self.wakeup_handle.wakeup();
+
+        Ok(())
+    }
+
+    fn insert_send_barrier(
+        &mut self,
+        streams: &[stream::StreamId],
+    ) -> Result<(), connection::Error> {
+        self.error?;
+
+        if let Some((space, _)) = self.space_manager.application_mut() {
+            space.stream_manager.insert_send_barrier(streams);
+
+            self.wakeup_handle.wakeup();
+        } else {
+            debug_assert!(
+                false,
+                "applications can't interact with the connection until the application space is available"
+            );
+            return Err(connection::Error::unspecified());
+        }
+
+        Ok(())
+    }
+
+    fn go_away(&mut self) -> Result<bool, connection::Error> {
+        self.error?;
+
+        if Config::ENDPOINT_TYPE.is_client() {
+            return Err(connection::Error::invalid_configuration(
+                "only servers can send GOAWAY frames",
+            ));
+        }
+
+        // the peer is unable to process the frame if it didn't advertise support for it
+        if !self.limits.go_away_enabled() {
+            return Ok(false);
+        }
+
+        if let Some((space, _)) = self.space_manager.application_mut() {
+            space.go_away();
+
+            self.wakeup_handle.wakeup();
+        } else {
+            debug_assert!(
+                false,
+                "applications can't interact with the connection until the application space is available"
+            );
+            return Err(connection::Error::unspecified());
+        }
+
+        Ok(true)
+    }
+
+    fn is_go_away_received(&self) -> Result<bool, connection::Error> {
+        self.error?;
+
+        Ok(self
+            .space_manager
+            .application()
+            .map_or(false, |space| space.is_go_away_received()))
+    }
+
+    fn rotate_connection_id(&mut self) -> Result<bool, connection::Error> {
+        self.error?;
+
+        // the peer only provides additional connection IDs once the handshake is confirmed
+        if !self.space_manager.is_handshake_confirmed() {
+            return Ok(false);
+        }
+
+        // ask the peer to move to new connection IDs issued by the local endpoint
+        self.local_id_registry.retire_active_connection_ids();
+
+        let is_rotating = self.path_manager.request_connection_id_rotation();
+
+        self.wakeup_handle.wakeup();
+
+        Ok(is_rotating)
+    }
+
+    fn export_keying_material(
+        &self,
+        label: &[u8],
+        context: &[u8],
+        output: &mut [u8],
+    ) -> Result<(), connection::Error> {
+        self.error?;
+
+        if !self.limits.keying_material_export() {
+            return Err(connection::Error::invalid_configuration(
+                "keying material export is not enabled in the connection limits",
+            ));
+        }
+
+        self.space_manager
+            .export_keying_material(label, context, output)
+            .map_err(|_| connection::Error::unspecified())
+    }
+
     fn local_address(&self) -> Result<SocketAddress, connection::Error> {
         Ok(*self.path_manager.active_path().handle.local_address())
     }
@@ -1988,6 +2508,20 @@ impl<Config: endpoint::Config> connection::Trait for ConnectionImpl<Config> {
         Ok(*self.path_manager.active_path().handle.remote_address())
     }
 
+    fn path_stats(&self) -> Result<Vec<path::Stats>, connection::Error> {
+        Ok(self.path_manager.stats().collect())
+    }
+
+    fn mtu_history(&self) -> Result<path::mtu::History, connection::Error> {
+        Ok(self
+            .path_manager
+            .active_path()
+            .mtu_controller
+            .history()
+            .copied()
+            .unwrap_or_default())
+    }
+
     fn error(&self) -> Option<connection::Error> {
         self.error.err()
     }
@@ -2076,3 +2610,6 @@ impl<Config: endpoint::Config> transmission::interest::Provider for ConnectionIm
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests;
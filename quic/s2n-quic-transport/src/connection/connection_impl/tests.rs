@@ -0,0 +1,150 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::{
+    connection::{ConnectionIdMapper, InternalConnectionIdGenerator, Trait as _},
+    endpoint::testing::Server,
+    space::InitialPacketNumbers,
+    wakeup_queue::WakeupQueue,
+};
+use s2n_quic_core::{
+    connection::compatibility,
+    crypto::{key::testing as key, tls::testing as tls_testing},
+    datagram,
+    dc::testing::MockDcEndpoint,
+    event::testing::{Publisher, Subscriber},
+    path::RemoteAddress,
+    random,
+    recovery::congestion_controller::{self, Endpoint as _},
+    stateless_reset::token::testing::TEST_TOKEN_1,
+    time::{Clock as _, NoopClock},
+};
+
+const MAX_ADDRESS_VALIDATION_DURATION: Duration = Duration::from_secs(1);
+
+/// Creates a server connection which hasn't validated the address of the client
+fn server_connection(
+    mapper: &mut ConnectionIdMapper,
+    subscriber: &mut Subscriber,
+    now: Timestamp,
+) -> ConnectionImpl<Server> {
+    let internal_id = InternalConnectionIdGenerator::new().generate_id();
+    let local_id = connection::LocalId::TEST_ID;
+    let peer_id = connection::PeerId::TEST_ID;
+    let local_id_registry =
+        mapper.create_local_id_registry(internal_id, &local_id, None, TEST_TOKEN_1, true);
+    let peer_id_registry = mapper.create_server_peer_id_registry(internal_id, peer_id, true);
+    let wakeup_handle = WakeupQueue::new().create_wakeup_handle(internal_id);
+
+    let limits = connection::Limits::default()
+        .with_max_address_validation_duration(MAX_ADDRESS_VALIDATION_DURATION)
+        .unwrap();
+    let mtu_config = mtu::Config::default();
+    let remote_address = SocketAddress::default();
+    let congestion_controller =
+        congestion_controller::testing::mock::Endpoint::default().new_congestion_controller(
+            congestion_controller::PathInfo::new(&mtu_config, &remote_address),
+        );
+
+    let space_manager = PacketSpaceManager::new(
+        connection::InitialId::TEST_ID,
+        tls_testing::Session,
+        key::Key::default(),
+        key::HeaderKey::default(),
+        Arc::new(compatibility::Strict),
+        InitialPacketNumbers::default(),
+        now,
+        &mut Publisher::no_snapshot(),
+    );
+
+    let supervisor_context = supervisor::Context::new(1, 1, &remote_address, true);
+
+    ConnectionImpl::new(ConnectionParameters {
+        internal_connection_id: internal_id,
+        local_id_registry,
+        peer_id_registry,
+        open_registry: None,
+        peer_connection_id: peer_id,
+        local_connection_id: local_id,
+        path_handle: RemoteAddress::from(remote_address),
+        space_manager,
+        wakeup_handle,
+        congestion_controller,
+        timestamp: now,
+        quic_version: 1,
+        limits,
+        mtu_config,
+        path_cache: Arc::new(cache::Disabled),
+        cached_path: None,
+        event_context: (),
+        supervisor_context: &supervisor_context,
+        datagram_endpoint: &mut datagram::Disabled::default(),
+        dc_endpoint: &mut MockDcEndpoint::new(&[]),
+        event_subscriber: subscriber,
+    })
+    .unwrap()
+}
+
+#[test]
+fn max_address_validation_duration_test() {
+    let mut random = random::testing::Generator(123);
+    let mut mapper = ConnectionIdMapper::new(&mut random, endpoint::Type::Server);
+    let mut subscriber = Subscriber::no_snapshot();
+    let now = NoopClock.get_time();
+    let mut connection = server_connection(&mut mapper, &mut subscriber, now);
+
+    assert!(!connection.path_manager.active_path().is_validated());
+    assert!(connection
+        .timers
+        .max_address_validation_duration_timer
+        .is_armed());
+
+    let remote_address = SocketAddress::default();
+    let supervisor_context = supervisor::Context::new(1, 1, &remote_address, true);
+    let result = connection.on_timeout(
+        &mut mapper,
+        now + MAX_ADDRESS_VALIDATION_DURATION,
+        &supervisor_context,
+        &mut random,
+        &mut subscriber,
+    );
+
+    assert!(
+        matches!(result, Err(connection::Error::NoValidPath { .. })),
+        "the connection closes once the timer expires, got {result:?}"
+    );
+}
+
+#[test]
+fn max_address_validation_duration_validated_test() {
+    let mut random = random::testing::Generator(123);
+    let mut mapper = ConnectionIdMapper::new(&mut random, endpoint::Type::Server);
+    let mut subscriber = Subscriber::no_snapshot();
+    let now = NoopClock.get_time();
+    let mut connection = server_connection(&mut mapper, &mut subscriber, now);
+
+    let path_id = connection.path_manager.active_path_id();
+    connection.on_handshake_packet_processed(path_id);
+
+    assert!(connection.path_manager.active_path().is_validated());
+    assert!(!connection
+        .timers
+        .max_address_validation_duration_timer
+        .is_armed());
+
+    let remote_address = SocketAddress::default();
+    let supervisor_context = supervisor::Context::new(1, 1, &remote_address, true);
+    let result = connection.on_timeout(
+        &mut mapper,
+        now + MAX_ADDRESS_VALIDATION_DURATION,
+        &supervisor_context,
+        &mut random,
+        &mut subscriber,
+    );
+
+    assert!(
+        result.is_ok(),
+        "validated connections stay open: {result:?}"
+    );
+}
@@ -17,6 +17,9 @@ pub struct ConnectionTimers {
     //# eliciting packets have been sent since last receiving and processing
     //# a packet.
     pub reset_peer_idle_timer_on_send: bool,
+    /// Stores if the idle timers need to be rearmed after the application changed the idle
+    /// timeout
+    pub idle_timeout_updated: bool,
     /// The timer which is used to send packets to the peer before the idle
     /// timeout expires
     pub local_idle_timer: Timer,
@@ -26,8 +29,12 @@ pub struct ConnectionTimers {
     pub pacing_timer: Timer,
     /// The timer for closing the connection if the handshake is still in progress
     pub max_handshake_duration_timer: Timer,
+    /// The timer for discarding the connection if the peer address hasn't been validated
+    pub max_address_validation_duration_timer: Timer,
     /// The timer for calling the connection supervisor
     pub supervisor_timer: Timer,
+    /// The timer for releasing connection buffers after a period of inactivity
+    pub hibernation_timer: Timer,
 }
 
 impl ConnectionTimers {
@@ -37,7 +44,9 @@ impl ConnectionTimers {
         self.initial_id_expiration_timer.cancel();
         self.pacing_timer.cancel();
         self.max_handshake_duration_timer.cancel();
+        self.max_address_validation_duration_timer.cancel();
         self.supervisor_timer.cancel();
+        self.hibernation_timer.cancel();
     }
 }
 
@@ -49,7 +58,9 @@ impl timer::Provider for ConnectionTimers {
         self.initial_id_expiration_timer.timers(query)?;
         self.pacing_timer.timers(query)?;
         self.max_handshake_duration_timer.timers(query)?;
+        self.max_address_validation_duration_timer.timers(query)?;
         self.supervisor_timer.timers(query)?;
+        self.hibernation_timer.timers(query)?;
 
         Ok(())
     }
@@ -14,11 +14,14 @@ use crate::{
     path::{self, path_event},
     stream,
 };
+use alloc::vec::Vec;
 use bytes::Bytes;
-use core::task::{Context, Poll};
+use core::{
+    task::{Context, Poll},
+    time::Duration,
+};
 use s2n_codec::DecoderBufferMut;
 use s2n_quic_core::{
-    application,
     application::ServerName,
     event::{self, builder::DatagramDropReason, supervisor, ConnectionPublisher, IntoEvent},
     inet::{DatagramInfo, SocketAddress},
@@ -92,6 +95,9 @@ pub trait ConnectionTrait: 'static + Send + Sized {
     where
         Tx: tx::Queue<Handle = <Self::Config as endpoint::Config>::PathHandle>;
 
+    /// Returns the share of the endpoint's transmission opportunities given to the connection
+    fn transmission_weight(&self) -> u8;
+
     /// Handles all timeouts on the `Connection`.
     ///
     /// `timestamp` passes the current time.
@@ -113,6 +119,13 @@ pub trait ConnectionTrait: 'static + Send + Sized {
         dc_endpoint: &mut <Self::Config as endpoint::Config>::DcEndpoint,
     ) -> Result<(), connection::Error>;
 
+    /// Handles a change to the local network reported by the application, such as an
+    /// interface going up or down or a change of address.
+    fn on_network_change(
+        &mut self,
+        random_generator: &mut <Self::Config as endpoint::Config>::RandomGenerator,
+    );
+
     // Packet handling
 
     /// Is called when an initial packet had been received
@@ -463,27 +476,85 @@ pub trait ConnectionTrait: 'static + Send + Sized {
         context: &Context,
     ) -> Poll<Result<Option<stream::StreamId>, connection::Error>>;
 
+    /// Polls opening a local stream
+    ///
+    /// If no `context` is provided, the caller is not notified when the stream can be opened.
     fn poll_open_stream(
         &mut self,
         stream_type: stream::StreamType,
         open_token: &mut connection::OpenToken,
-        context: &Context,
+        context: Option<&Context>,
     ) -> Poll<Result<stream::StreamId, connection::Error>>;
 
-    fn application_close(&mut self, error: Option<application::Error>);
+    /// Cancels a pending request to open a local stream
+    fn cancel_open_stream(
+        &mut self,
+        stream_type: stream::StreamType,
+        open_token: &mut connection::OpenToken,
+    );
+
+    /// Returns `true` if opening a local stream of the given type requires additional credit
+    /// from the peer
+    fn is_open_stream_blocked(
+        &self,
+        stream_type: stream::StreamType,
+    ) -> Result<bool, connection::Error>;
+
+    /// Polls until all of the packets the connection is currently able to send are transmitted
+    fn poll_flush_transport(&mut self, context: &Context) -> Poll<Result<(), connection::Error>>;
+
+    /// Polls until the handshake is confirmed
+    fn poll_handshake_completed(
+        &mut self,
+        context: &Context,
+    ) -> Poll<Result<(), connection::Error>>;
+
+    /// Closes the connection with the provided error
+    ///
+    /// The `reason` is sent to the peer in place of the truncated reason in the error.
+    fn application_close(&mut self, error: Option<connection::Error>, reason: Option<Bytes>);
+
+    /// Returns the reason phrase of the CONNECTION_CLOSE frame which closed the connection
+    fn close_reason(&self) -> Option<Bytes>;
 
     fn server_name(&self) -> Option<ServerName>;
 
     fn application_protocol(&self) -> Bytes;
 
+    fn compression_dictionary_id(&self) -> Option<u64>;
+
     fn ping(&mut self) -> Result<(), connection::Error>;
 
     fn keep_alive(&mut self, enabled: bool) -> Result<(), connection::Error>;
 
+    fn set_idle_timeout(&mut self, timeout: Duration) -> Result<(), connection::Error>;
+
+    fn insert_send_barrier(
+        &mut self,
+        streams: &[stream::StreamId],
+    ) -> Result<(), connection::Error>;
+
+    fn go_away(&mut self) -> Result<bool, connection::Error>;
+
+    fn is_go_away_received(&self) -> Result<bool, connection::Error>;
+
+    fn rotate_connection_id(&mut self) -> Result<bool, connection::Error>;
+
+    fn export_keying_material(
+        &self,
+        label: &[u8],
+        context: &[u8],
+        output: &mut [u8],
+    ) -> Result<(), connection::Error>;
+
     fn local_address(&self) -> Result<SocketAddress, connection::Error>;
 
     fn remote_address(&self) -> Result<SocketAddress, connection::Error>;
 
+    fn path_stats(&self) -> Result<Vec<path::Stats>, connection::Error>;
+
+    fn mtu_history(&self) -> Result<path::mtu::History, connection::Error>;
+
     fn error(&self) -> Option<connection::Error>;
 
     fn query_event_context(&self, query: &mut dyn query::Query);
@@ -521,6 +592,26 @@ pub trait Lock<T>: 'static + Send + Sync {
     fn write<F: FnOnce(&mut T) -> R, R>(&self, f: F) -> Result<R, Self::Error>;
 }
 
+impl<T: 'static + Send> Lock<T> for super::SpinMutex<T> {
+    type Error = ();
+
+    fn new(value: T) -> Self {
+        super::SpinMutex::new(value)
+    }
+
+    fn read<F: FnOnce(&T) -> R, R>(&self, f: F) -> Result<R, Self::Error> {
+        let lock = self.lock().map_err(|_| ())?;
+        let result = f(&*lock);
+        Ok(result)
+    }
+
+    fn write<F: FnOnce(&mut T) -> R, R>(&self, f: F) -> Result<R, Self::Error> {
+        let mut lock = self.lock().map_err(|_| ())?;
+        let result = f(&mut *lock);
+        Ok(result)
+    }
+}
+
 #[cfg(feature = "std")]
 impl<T: 'static + Send> Lock<T> for std::sync::Mutex<T> {
     type Error = ();
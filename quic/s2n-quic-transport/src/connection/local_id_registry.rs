@@ -4,11 +4,12 @@
 use crate::{
     connection::{
         connection_id_mapper::ConnectionIdMapperState, local_id_registry::LocalIdStatus::*,
-        InternalConnectionId,
+        InternalConnectionId, Mutex,
     },
     contexts::WriteContext,
     transmission,
 };
+use alloc::sync::Arc;
 use core::convert::TryInto;
 use s2n_quic_core::{
     ack, connection, frame,
@@ -18,7 +19,6 @@ use s2n_quic_core::{
     time::{timer, Duration, Timer, Timestamp},
 };
 use smallvec::SmallVec;
-use std::sync::{Arc, Mutex};
 
 /// The amount of ConnectionIds we can register without dynamic memory allocation
 const NR_STATIC_REGISTRABLE_IDS: usize = 5;
@@ -89,6 +89,9 @@ pub struct LocalIdRegistry {
     /// If true, the connection ID used during the the handshake will be requested
     /// to be retired following confirmation of the handshake completing.
     rotate_handshake_connection_id: bool,
+    /// If true, a zero-length connection ID was selected during the handshake and
+    /// no further connection IDs will be issued.
+    is_zero_length: bool,
 }
 
 type RegisteredIds = SmallVec<[LocalIdInfo; NR_STATIC_REGISTRABLE_IDS]>;
@@ -242,6 +245,23 @@ impl LocalIdRegistry {
         stateless_reset_token: stateless_reset::Token,
         rotate_handshake_connection_id: bool,
     ) -> Self {
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-5.1.1
+        //# An endpoint that selects a zero-length connection ID during the
+        //# handshake cannot issue a new connection ID.
+        let is_zero_length = handshake_connection_id.is_empty();
+
+        // A zero-length connection ID can't be replaced, so it is neither
+        // rotated nor expired.
+        let (handshake_connection_id_expiration_time, rotate_handshake_connection_id) =
+            if is_zero_length {
+                (None, false)
+            } else {
+                (
+                    handshake_connection_id_expiration_time,
+                    rotate_handshake_connection_id,
+                )
+            };
+
         let mut registry = Self {
             internal_id,
             state,
@@ -284,6 +304,7 @@ impl LocalIdRegistry {
                 count
             }),
             rotate_handshake_connection_id,
+            is_zero_length,
         };
 
         let _ = registry.register_connection_id(
@@ -485,6 +506,10 @@ impl LocalIdRegistry {
     /// Returns the mappers interest in new connection IDs
     #[inline]
     pub fn connection_id_interest(&self) -> connection::id::Interest {
+        if self.is_zero_length {
+            return connection::id::Interest::None;
+        }
+
         let active_connection_id_count = self.active_id_count.get(&self.registered_ids);
 
         //= https://www.rfc-editor.org/rfc/rfc9000#section-5.1.1
@@ -661,6 +686,45 @@ impl LocalIdRegistry {
         self.check_consistency();
     }
 
+    /// Requests the peer to retire all of the connection ids it is currently able to use
+    ///
+    /// New connection ids are issued to replace the retired ones, along with an increased
+    /// retire_prior_to value. Nothing is retired while new connection ids are still being issued
+    /// or a previous retirement hasn't been confirmed by the peer, which bounds the number of
+    /// connection ids pending retirement.
+    pub fn retire_active_connection_ids(&mut self) {
+        if self.is_zero_length
+            || !self
+                .registered_ids
+                .iter()
+                .all(|id_info| matches!(id_info.status, Active | PendingRemoval(_)))
+        {
+            return;
+        }
+
+        for id_info in self
+            .registered_ids
+            .iter_mut()
+            .filter(|id_info| id_info.status == Active)
+        {
+            // Similar to the handshake connection id, the connection id is removed once the peer
+            // retires it or at its regularly scheduled retirement time.
+            id_info.retire(id_info.retirement_time);
+
+            //= https://www.rfc-editor.org/rfc/rfc9000#section-5.1.2
+            //# An endpoint can request that its peer retire connection IDs by
+            //# sending a NEW_CONNECTION_ID frame with an increased Retire Prior To
+            //# field.
+            self.retire_prior_to = self.retire_prior_to.max(id_info.sequence_number + 1);
+
+            self.active_id_count.clear();
+            self.next_expiration.clear();
+            self.transmission_interest.clear();
+        }
+
+        self.check_consistency();
+    }
+
     /// Validate that the current expiration timer is based on the next status change time
     fn check_consistency(&self) {
         if cfg!(debug_assertions) {
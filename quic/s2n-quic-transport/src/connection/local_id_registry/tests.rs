@@ -956,3 +956,83 @@ fn on_handshake_confirmed_rotate_handshake_connection_id_disabled() {
         assert_eq!(None, id_info.retirement_time);
     }
 }
+
+#[test]
+fn retire_active_connection_ids() {
+    let ext_id_1 = id(b"id01");
+    let ext_id_2 = id(b"id02");
+
+    let (_, mut reg1) = mapper(ext_id_1, None, TEST_TOKEN_1);
+    reg1.set_active_connection_id_limit(3);
+
+    assert!(reg1
+        .register_connection_id(&ext_id_2, None, TEST_TOKEN_2)
+        .is_ok());
+
+    // Nothing is retired while a connection ID is pending issuance
+    reg1.retire_active_connection_ids();
+    assert_eq!(0, reg1.retire_prior_to);
+    assert!(reg1
+        .registered_ids
+        .iter()
+        .all(|id_info| !id_info.is_retired()));
+
+    reg1.get_connection_id_info_mut(&ext_id_2).unwrap().status = Active;
+    reg1.transmission_interest.clear();
+
+    reg1.retire_active_connection_ids();
+    assert_eq!(2, reg1.retire_prior_to);
+    assert!(reg1
+        .registered_ids
+        .iter()
+        .all(|id_info| id_info.is_retired()));
+    // New connection IDs are requested to replace the retired ones
+    assert_eq!(
+        connection::id::Interest::New(3),
+        reg1.connection_id_interest()
+    );
+
+    // Nothing is retired until the peer confirms the previous retirement
+    let ext_id_3 = id(b"id03");
+    assert!(reg1
+        .register_connection_id(&ext_id_3, None, TEST_TOKEN_3)
+        .is_ok());
+    reg1.get_connection_id_info_mut(&ext_id_3).unwrap().status = Active;
+    reg1.transmission_interest.clear();
+
+    reg1.retire_active_connection_ids();
+    assert_eq!(2, reg1.retire_prior_to);
+    assert!(!reg1.get_connection_id_info(&ext_id_3).unwrap().is_retired());
+}
+
+#[test]
+fn zero_length_connection_id() {
+    let empty_id = id(&[]);
+    let expiration = time::now() + MIN_LIFETIME;
+
+    let (mapper, mut reg1) = mapper(empty_id, Some(expiration), TEST_TOKEN_1);
+    reg1.set_active_connection_id_limit(3);
+
+    assert!(mapper.is_local_id_registered(&empty_id));
+
+    //= https://www.rfc-editor.org/rfc/rfc9000#section-5.1.1
+    //= type=test
+    //# An endpoint that selects a zero-length connection ID during the
+    //# handshake cannot issue a new connection ID.
+    assert_eq!(
+        connection::id::Interest::None,
+        reg1.connection_id_interest()
+    );
+
+    // The zero-length connection ID is never expired or retired
+    assert_eq!(0, reg1.armed_timer_count());
+
+    reg1.on_handshake_confirmed();
+    reg1.retire_active_connection_ids();
+    assert_eq!(0, reg1.retire_prior_to);
+    assert!(!reg1.get_connection_id_info(&empty_id).unwrap().is_retired());
+    assert!(mapper.is_local_id_registered(&empty_id));
+
+    drop(reg1);
+    assert!(!mapper.is_local_id_registered(&empty_id));
+}
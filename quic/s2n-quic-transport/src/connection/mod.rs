@@ -7,7 +7,13 @@ use crate::{
     endpoint, recovery::congestion_controller, space::PacketSpaceManager,
     wakeup_queue::WakeupHandle,
 };
-use s2n_quic_core::{connection, event, event::supervisor, path::mtu, time::Timestamp};
+use alloc::sync::Arc;
+use s2n_quic_core::{
+    connection, event,
+    event::supervisor,
+    path::{cache, mtu},
+    time::Timestamp,
+};
 
 mod api;
 mod api_provider;
@@ -23,6 +29,7 @@ mod internal_connection_id;
 pub(crate) mod local_id_registry;
 pub(crate) mod open_token;
 pub(crate) mod peer_id_registry;
+mod spin;
 pub(crate) mod transmission;
 
 pub(crate) use api_provider::{ConnectionApi, ConnectionApiProvider};
@@ -36,12 +43,18 @@ pub(crate) use local_id_registry::LocalIdRegistry;
 pub(crate) use peer_id_registry::PeerIdRegistry;
 pub(crate) use transmission::{ConnectionTransmission, ConnectionTransmissionContext};
 
+#[cfg(not(any(test, feature = "std")))]
+pub(crate) use spin::Mutex;
+#[cfg(any(test, feature = "std"))]
+pub(crate) use std::sync::Mutex;
+
 pub use api::Connection;
 pub use connection_impl::ConnectionImpl as Implementation;
 pub use connection_trait::Lock;
 pub use open_token::Pair as OpenToken;
 /// re-export core
 pub use s2n_quic_core::connection::*;
+pub use spin::Mutex as SpinMutex;
 
 /// Parameters which are passed to a Connection.
 /// These are unique per created connection.
@@ -77,6 +90,10 @@ pub struct Parameters<'a, Cfg: endpoint::Config> {
     pub limits: connection::Limits,
     /// Configuration for the maximum transmission unit (MTU) that can be sent on a path
     pub mtu_config: mtu::Config,
+    /// The cache which stores the characteristics of the path when the connection closes
+    pub path_cache: Arc<Cfg::PathCache>,
+    /// The characteristics of a previous connection's path to the same peer, if any
+    pub cached_path: Option<cache::Entry>,
     /// The context that should be passed to all related connection events
     pub event_context: <Cfg::EventSubscriber as event::Subscriber>::ConnectionContext,
     /// The context passed to the connection supervisor
@@ -1,7 +1,7 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
-use core::num::NonZeroU64;
+use core::{num::NonZeroU64, time::Duration};
 
 /// An opaque token issued to each connection handle which allows the stream
 /// controller to track any pending open requests.
@@ -14,6 +14,11 @@ pub struct Pair {
     pub(crate) unidirectional: Token,
     /// Stores the token for the bididirectional stream type
     pub(crate) bidirectional: Token,
+    /// The maximum amount of time the current open request may wait for stream capacity
+    ///
+    /// This is set by each call to open a stream and only applies to the request which is
+    /// blocked by it. A zero timeout waits indefinitely.
+    pub(crate) timeout: Duration,
 }
 
 impl Pair {
@@ -26,6 +31,7 @@ impl Pair {
         Self {
             unidirectional: Token::new(),
             bidirectional: Token::new(),
+            timeout: Duration::ZERO,
         }
     }
 }
@@ -45,30 +51,12 @@ impl Token {
         Self(None)
     }
 
-    /// Returns the index that the caller's waker should be stored, given the expired token
-    #[inline]
-    pub fn index(&self, expired_token: &Self) -> Option<usize> {
-        let base = expired_token.0.map_or(0, |v| v.get());
-        // since we're dealing with NonZeroU64, add one to get the correct index
-        let base = base + 1;
-        let v = self.0?;
-        let v = v.get().checked_sub(base)?;
-        Some(v as usize)
-    }
-
-    /// Expires a `count` number of tokens
+    /// Returns the id of the request the caller is waiting on, if any
     ///
-    /// This should be called each time at least one waker is woken and removed from the waker list
+    /// Ids are issued in increasing order, so the waiting requests can be kept sorted by them.
     #[inline]
-    pub fn expire(&mut self, count: usize) {
-        if let Some(v) = self.0.as_mut() {
-            *v = unsafe {
-                // Safety: non-zero N + count is always non-zero
-                NonZeroU64::new_unchecked(v.get() + count as u64)
-            };
-        } else if let Some(v) = NonZeroU64::new(count as _) {
-            *self = Self(Some(v));
-        }
+    pub fn id(&self) -> Option<NonZeroU64> {
+        self.0
     }
 
     /// Resets the token state
@@ -108,57 +96,27 @@ mod tests {
 
     #[test]
     fn open_token_test() {
-        let mut caller_tokens = [Token::new(), Token::new(), Token::new()];
-        let mut expired_token = Token::new();
+        let mut token = Token::new();
         let mut token_counter = Counter::new();
 
-        assert_eq!(
-            caller_tokens[0].index(&expired_token),
-            None,
-            "empty caller token with no expired tokens"
-        );
+        assert_eq!(token.id(), None, "empty caller token should not have an id");
 
         // give the caller a token
-        caller_tokens[0] = token_counter.next();
-        assert_eq!(
-            caller_tokens[0].index(&expired_token),
-            Some(0),
-            "issued caller with no expired tokens should be index 0"
-        );
-
-        // expire the issued token
-        expired_token.expire(1);
-        assert_eq!(
-            caller_tokens[0].index(&expired_token),
-            None,
-            "issued caller token should now be expired"
-        );
-
-        for (idx, token) in caller_tokens.iter_mut().enumerate() {
-            *token = token_counter.next();
-            assert_eq!(
-                token.index(&expired_token),
-                Some(idx),
-                "issued caller should be indexed in order",
-            );
+        token = token_counter.next();
+        let mut previous = token.id().unwrap();
+
+        // issued ids always increase
+        for _ in 0..3 {
+            let next = token_counter.next().id().unwrap();
+            assert!(next > previous);
+            previous = next;
         }
 
-        // expire just the first token
-        expired_token.expire(1);
-
+        token.clear();
         assert_eq!(
-            caller_tokens[0].index(&expired_token),
+            token.id(),
             None,
-            "first caller token should now be expired"
+            "cleared caller token should not have an id"
         );
-
-        for (idx, token) in caller_tokens[1..].iter().enumerate() {
-            assert_eq!(
-                token.index(&expired_token),
-                Some(idx),
-                "caller {} should not be expired",
-                idx + 1,
-            );
-        }
     }
 }
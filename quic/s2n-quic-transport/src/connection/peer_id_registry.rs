@@ -14,11 +14,12 @@ use crate::{
                 PendingRetirementRetransmission,
             },
         },
-        InternalConnectionId,
+        InternalConnectionId, Mutex,
     },
     path,
     transmission::{self, WriteContext},
 };
+use alloc::sync::Arc;
 use s2n_quic_core::{
     ack, connection, endpoint,
     event::{self, IntoEvent},
@@ -28,7 +29,6 @@ use s2n_quic_core::{
     stateless_reset, transport,
 };
 use smallvec::SmallVec;
-use std::sync::{Arc, Mutex};
 
 /// The amount of ConnectionIds we can register without dynamic memory allocation
 const NR_STATIC_REGISTRABLE_IDS: usize = 5;
@@ -589,6 +589,57 @@ impl PeerIdRegistry {
         self.consume_new_id_inner()
     }
 
+    /// Returns true if there is a new peer_id that hasn't been used yet
+    pub fn has_new_id(&self) -> bool {
+        self.registered_ids
+            .iter()
+            .any(|id_info| id_info.status == New)
+    }
+
+    /// Tries to replace the peer_id used by an existing path with a new one.
+    ///
+    /// If `retire_current` is true, the current peer_id is retired with a RETIRE_CONNECTION_ID
+    /// frame. The rotation is skipped if retiring another connection ID could prevent the
+    /// retirements requested by the peer from being tracked.
+    pub fn rotate_id_for_existing_path<Pub: event::ConnectionPublisher>(
+        &mut self,
+        path_id: path::Id,
+        current_peer_connection_id: connection::PeerId,
+        retire_current: bool,
+        publisher: &mut Pub,
+    ) -> Option<connection::PeerId> {
+        let retired_id_count = self
+            .registered_ids
+            .iter()
+            .filter(|id_info| !id_info.is_active())
+            .count();
+
+        // Leave enough room for the peer to retire all of the active connection IDs
+        if retired_id_count + ACTIVE_CONNECTION_ID_LIMIT as usize
+            >= RETIRED_CONNECTION_ID_LIMIT as usize
+        {
+            return None;
+        }
+
+        let new_id =
+            self.consume_new_id_for_existing_path(path_id, current_peer_connection_id, publisher)?;
+
+        if retire_current {
+            if let Some(id_info) = self
+                .registered_ids
+                .iter_mut()
+                .find(|id_info| id_info.id == current_peer_connection_id && id_info.is_active())
+            {
+                id_info.status = PendingRetirement;
+                self.transmission_interest.clear();
+            }
+        }
+
+        self.check_consistency();
+
+        Some(new_id)
+    }
+
     // Validate that the ACTIVE_CONNECTION_ID_LIMIT has not been exceeded
     fn check_active_connection_id_limit(
         &self,
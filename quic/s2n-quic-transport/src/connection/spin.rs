@@ -0,0 +1,126 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A spin lock for targets without the standard library
+//!
+//! The API mirrors the subset of `std::sync::Mutex` used by the connection state so the two can
+//! be swapped depending on the `std` feature.
+
+use core::{
+    cell::UnsafeCell,
+    fmt, hint,
+    ops::{Deref, DerefMut},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+/// A mutual exclusion lock which spins while waiting to be acquired
+#[derive(Default)]
+pub struct Mutex<T> {
+    is_locked: AtomicBool,
+    value: UnsafeCell<T>,
+}
+
+// Safety: access to the value is synchronized by `is_locked`
+unsafe impl<T: Send> Send for Mutex<T> {}
+unsafe impl<T: Send> Sync for Mutex<T> {}
+
+impl<T> Mutex<T> {
+    /// Creates a new unlocked mutex
+    #[inline]
+    pub const fn new(value: T) -> Self {
+        Self {
+            is_locked: AtomicBool::new(false),
+            value: UnsafeCell::new(value),
+        }
+    }
+
+    /// Acquires the lock, spinning until it is available
+    ///
+    /// Unlike `std::sync::Mutex`, the lock is never poisoned so this always succeeds.
+    #[inline]
+    pub fn lock(&self) -> Result<MutexGuard<'_, T>, Poisoned> {
+        while self
+            .is_locked
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            while self.is_locked.load(Ordering::Relaxed) {
+                hint::spin_loop();
+            }
+        }
+
+        Ok(MutexGuard { mutex: self })
+    }
+}
+
+impl<T> fmt::Debug for Mutex<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Mutex")
+            .field("is_locked", &self.is_locked.load(Ordering::Relaxed))
+            .finish_non_exhaustive()
+    }
+}
+
+/// The error returned when a lock is poisoned
+///
+/// This is never returned by [`Mutex::lock`] and only exists for parity with `std::sync::Mutex`.
+#[derive(Clone, Copy, Debug)]
+pub struct Poisoned;
+
+/// A guard which releases the lock when dropped
+pub struct MutexGuard<'a, T> {
+    mutex: &'a Mutex<T>,
+}
+
+impl<T> Deref for MutexGuard<'_, T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        // Safety: the lock is held for the lifetime of the guard
+        unsafe { &*self.mutex.value.get() }
+    }
+}
+
+impl<T> DerefMut for MutexGuard<'_, T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut T {
+        // Safety: the lock is held for the lifetime of the guard
+        unsafe { &mut *self.mutex.value.get() }
+    }
+}
+
+impl<T> Drop for MutexGuard<'_, T> {
+    #[inline]
+    fn drop(&mut self) {
+        self.mutex.is_locked.store(false, Ordering::Release);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::sync::Arc;
+
+    #[test]
+    fn contention_test() {
+        let mutex = Arc::new(Mutex::new(0usize));
+
+        let threads: Vec<_> = (0..4)
+            .map(|_| {
+                let mutex = mutex.clone();
+                std::thread::spawn(move || {
+                    for _ in 0..1000 {
+                        *mutex.lock().unwrap() += 1;
+                    }
+                })
+            })
+            .collect();
+
+        for thread in threads {
+            thread.join().unwrap();
+        }
+
+        assert_eq!(*mutex.lock().unwrap(), 4000);
+    }
+}
@@ -148,6 +148,46 @@ impl<Config: endpoint::Config> tx::Message for ConnectionTransmission<'_, '_, Co
             "the amplification limit should be checked before trying to transmit"
         );
 
+        // give the packet interceptor a chance to release any datagrams it has held
+        //
+        // GSO segments need to be the same size so this is only done for the first segment. The
+        // released datagrams are subject to the same congestion and amplification limits as new
+        // ones.
+        if gso_offset == 0 && transmission_constraint.can_transmit() {
+            use s2n_quic_core::{
+                event::IntoEvent,
+                packet::interceptor::{Datagram, Interceptor},
+            };
+
+            let mut encoder = EncoderBuffer::new(&mut buffer[..max_datagram_size]);
+
+            let subject = self.context.publisher.subject();
+            let remote_address = self.context.path().remote_address();
+            let local_address = self.context.path().local_address();
+
+            let datagram = Datagram {
+                remote_address: remote_address.into_event(),
+                local_address: local_address.into_event(),
+                timestamp: self.context.timestamp,
+            };
+
+            self.context
+                .packet_interceptor
+                .release_tx_datagram(&subject, &datagram, &mut encoder);
+
+            if !encoder.is_empty() {
+                let datagram_len = encoder.len();
+                self.context.path_mut().on_bytes_transmitted(datagram_len);
+                self.context
+                    .publisher
+                    .on_datagram_sent(event::builder::DatagramSent {
+                        len: datagram_len as u16,
+                        gso_offset,
+                    });
+                return Ok(datagram_len);
+            }
+        }
+
         // limit the number of retries to the MAX_BURST_PACKETS
         for _ in 0..MAX_BURST_PACKETS {
             let encoder = EncoderBuffer::new(&mut buffer[..max_datagram_size]);
@@ -6,6 +6,7 @@ use crate::{
     endpoint,
     sync::{flag, flag::Writer},
 };
+use alloc::vec::Vec;
 use s2n_quic_core::{
     ack,
     crypto::tls,
@@ -294,6 +294,27 @@ fn dot_test() {
     assert_snapshot!(State::dot());
 }
 
+#[test]
+#[cfg_attr(miri, ignore)]
+fn model_test() {
+    bolero::check!()
+        .with_type::<(bool, Vec<u8>)>()
+        .for_each(|(is_server, events)| {
+            let mut state = if *is_server {
+                State::InitServer
+            } else {
+                State::InitClient
+            };
+            s2n_quic_core::state::check_model(
+                &mut state,
+                events,
+                State::EVENTS.len(),
+                State::apply_event,
+                State::is_complete,
+            );
+        });
+}
+
 /// Creates an application space packet number with the given value
 fn pn(nr: usize) -> PacketNumber {
     PacketNumberSpace::ApplicationData.new_packet_number(VarInt::new(nr as u64).unwrap())
@@ -4,6 +4,7 @@
 //! Configuration parameters for `Endpoint`s
 
 use crate::{connection, stream};
+use alloc::sync::Arc;
 use s2n_quic_core::{
     crypto::tls, datagram, dc, endpoint, event, packet, path, path::mtu, random,
     recovery::congestion_controller, stateless_reset,
@@ -28,6 +29,10 @@ pub trait Config: 'static + Send + Sized + core::fmt::Debug {
     type TokenFormat: s2n_quic_core::token::Format;
     /// The endpoint limits
     type EndpointLimits: endpoint::Limiter;
+    /// The filter for connection attempts
+    type AcceptFilter: endpoint::accept_filter::Filter;
+    /// The validator for out-of-band address attestations
+    type AddressAttestation: endpoint::address_attestation::Validator;
     /// The connection limits
     type ConnectionLimits: connection::limits::Limiter;
     /// The path specific mtu config
@@ -48,6 +53,10 @@ pub trait Config: 'static + Send + Sized + core::fmt::Debug {
     type DatagramEndpoint: datagram::Endpoint;
     /// The dc implementation for the endpoint
     type DcEndpoint: dc::Endpoint;
+    /// The compatibility policy for nonconformant peers
+    type CompatibilityPolicy: connection::compatibility::Policy;
+    /// The cache of path characteristics shared between connections
+    type PathCache: path::cache::Cache;
 
     /// The type of the local endpoint
     const ENDPOINT_TYPE: endpoint::Type;
@@ -76,6 +85,12 @@ pub struct Context<'a, Cfg: Config> {
     /// The endpoint limits
     pub endpoint_limits: &'a mut Cfg::EndpointLimits,
 
+    /// The filter for connection attempts, which is invoked before any handshake work
+    pub accept_filter: &'a mut Cfg::AcceptFilter,
+
+    /// The validator for out-of-band address attestations
+    pub address_attestation: &'a mut Cfg::AddressAttestation,
+
     /// Token generator / validator
     pub token: &'a mut Cfg::TokenFormat,
 
@@ -97,4 +112,10 @@ pub struct Context<'a, Cfg: Config> {
     pub datagram: &'a mut Cfg::DatagramEndpoint,
 
     pub dc: &'a mut Cfg::DcEndpoint,
+
+    /// The compatibility policy, which is shared with each of the connections
+    pub compatibility: &'a Arc<Cfg::CompatibilityPolicy>,
+
+    /// The cache of path characteristics, which is shared with each of the connections
+    pub path_cache: &'a Arc<Cfg::PathCache>,
 }
@@ -5,14 +5,19 @@ use crate::{
     connection::{self, Connection},
     endpoint::handle::ConnectorSender,
 };
+use alloc::{vec, vec::Vec};
 use core::{
     fmt,
     future::Future,
     pin::Pin,
     task::{Context, Poll},
+    time::Duration,
 };
 use futures_channel::oneshot;
-use s2n_quic_core::{application::ServerName, inet::SocketAddress, path::RemoteAddress};
+use s2n_quic_core::{
+    application::ServerName, connection::limits::Overrides, inet::SocketAddress,
+    path::RemoteAddress,
+};
 
 /// Held by connection Attempt future. Used to receive the actual connection.
 pub(crate) type ConnectionReceiver = oneshot::Receiver<Result<Connection, connection::Error>>;
@@ -21,16 +26,28 @@ pub(crate) type ConnectionReceiver = oneshot::Receiver<Result<Connection, connec
 /// its been created.
 pub(crate) type ConnectionSender = oneshot::Sender<Result<Connection, connection::Error>>;
 
+/// The default amount of time to wait between racing connection attempts
+///
+/// See [RFC 8305 Section 8](https://www.rfc-editor.org/rfc/rfc8305#section-8)
+pub const DEFAULT_ATTEMPT_DELAY: Duration = Duration::from_millis(250);
+
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub struct Connect {
     pub(crate) remote_address: RemoteAddress,
     pub(crate) server_name: Option<ServerName>,
     pub(crate) deduplicate: bool,
+    pub(crate) additional_addresses: Vec<RemoteAddress>,
+    pub(crate) attempt_delay: Duration,
+    pub(crate) host: Option<Host>,
+    pub(crate) token: Vec<u8>,
+    pub(crate) limits: Overrides,
 }
 
 impl fmt::Display for Connect {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        if f.alternate() {
+        if let (true, Some(host)) = (f.alternate(), &self.host) {
+            write!(f, "{host}")
+        } else if f.alternate() {
             if let Some(hostname) = self.server_name.as_deref() {
                 write!(f, "{hostname} at {}", &*self.remote_address)
             } else {
@@ -51,7 +68,94 @@ impl Connect {
             remote_address: addr.into().into(),
             server_name: None,
             deduplicate: false,
+            additional_addresses: Vec::new(),
+            attempt_delay: DEFAULT_ATTEMPT_DELAY,
+            host: None,
+            token: Vec::new(),
+            limits: Overrides::default(),
+        }
+    }
+
+    /// Creates a connection attempt for the specified `host:port`
+    ///
+    /// If the host is a hostname, rather than an IP address, it is resolved by the client with the
+    /// configured DNS resolver before connecting. The hostname is also used as the server name,
+    /// unless otherwise specified with [`Self::with_server_name`].
+    ///
+    /// IPv6 addresses must be enclosed in square brackets (e.g. `[::1]:443`).
+    pub fn from_host(host: &str) -> Result<Self, HostError> {
+        let (name, port) = host
+            .rsplit_once(':')
+            .ok_or(HostError::new("missing port"))?;
+        let port = port.parse().map_err(|_| HostError::new("invalid port"))?;
+
+        let name = name
+            .strip_prefix('[')
+            .and_then(|name| name.strip_suffix(']'))
+            .unwrap_or(name);
+
+        if name.is_empty() {
+            return Err(HostError::new("missing hostname"));
+        }
+
+        // IP literals can only be parsed with the standard library; without it they are treated
+        // as hostnames and left to the resolver
+        #[cfg(any(test, feature = "std"))]
+        if let Ok(ip) = name.parse::<std::net::IpAddr>() {
+            return Ok(Self::new((ip, port)));
         }
+
+        let host = Host {
+            name: name.into(),
+            port,
+        };
+
+        Ok(Self {
+            server_name: Some(host.name.clone()),
+            host: Some(host),
+            ..Self::new(SocketAddress::default())
+        })
+    }
+
+    /// Returns the host which needs to be resolved before connecting, if any
+    #[inline]
+    pub fn host(&self) -> Option<&Host> {
+        self.host.as_ref()
+    }
+
+    /// Replaces the unresolved host with the resolved addresses
+    ///
+    /// The addresses are raced against each other, in the order provided. See
+    /// [`Self::with_additional_addresses`] for more details.
+    ///
+    /// Returns `None` if `addrs` is empty.
+    pub fn with_resolved_addresses<I, Addr>(self, addrs: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = Addr>,
+        Addr: Into<SocketAddress>,
+    {
+        let resolved = Self::new_racing(addrs)?;
+        Some(Self {
+            remote_address: resolved.remote_address,
+            additional_addresses: resolved.additional_addresses,
+            host: None,
+            ..self
+        })
+    }
+
+    /// Creates a connection attempt which races the specified remote addresses
+    ///
+    /// Returns `None` if `addrs` is empty.
+    ///
+    /// See [`Self::with_additional_addresses`] for more details.
+    pub fn new_racing<I, Addr>(addrs: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = Addr>,
+        Addr: Into<SocketAddress>,
+    {
+        let mut addrs = addrs.into_iter();
+        let connect = Self::new(addrs.next()?);
+        Some(connect.with_additional_addresses(addrs))
     }
 
     /// Specifies the server name to use for the connection
@@ -63,11 +167,135 @@ impl Connect {
         }
     }
 
+    /// Specifies additional remote addresses for the same server
+    ///
+    /// When additional addresses are provided, the client will race connection attempts
+    /// across all of the addresses, following the
+    /// [Happy Eyeballs](https://www.rfc-editor.org/rfc/rfc8305) algorithm. Addresses are
+    /// interleaved by address family, starting with the family of the primary address, and a new
+    /// attempt is started every [attempt delay](Self::with_attempt_delay) or as soon as the
+    /// previous attempt fails. The first connection to complete the handshake is returned and all
+    /// of the other attempts are abandoned.
+    #[must_use]
+    pub fn with_additional_addresses<I, Addr>(mut self, addrs: I) -> Self
+    where
+        I: IntoIterator<Item = Addr>,
+        Addr: Into<SocketAddress>,
+    {
+        self.additional_addresses.extend(
+            addrs
+                .into_iter()
+                .map(|addr| RemoteAddress::from(addr.into())),
+        );
+        self
+    }
+
+    /// Specifies the amount of time to wait before starting the next connection attempt
+    /// when racing multiple addresses
+    ///
+    /// Defaults to [`DEFAULT_ATTEMPT_DELAY`].
+    #[must_use]
+    pub fn with_attempt_delay(self, attempt_delay: Duration) -> Self {
+        Self {
+            attempt_delay,
+            ..self
+        }
+    }
+
+    /// Specifies a token to include in the Initial packets of the connection
+    ///
+    /// This can be used to present an out-of-band attestation of the client's address, which
+    /// allows the server to skip address validation with a Retry packet. If the server sends a
+    /// Retry packet, the token is replaced with the one provided by the server.
+    #[must_use]
+    pub fn with_token<Token: Into<Vec<u8>>>(self, token: Token) -> Self {
+        Self {
+            token: token.into(),
+            ..self
+        }
+    }
+
+    /// Overrides the endpoint's limits for this connection
+    ///
+    /// Any limits which aren't overridden are taken from the client's
+    /// [`Limiter`](s2n_quic_core::connection::limits::Limiter).
+    ///
+    /// The overrides are part of the identity of a deduplicated request, so a request with
+    /// different overrides opens a new connection instead of sharing an existing one.
+    #[must_use]
+    pub fn with_limits(self, limits: Overrides) -> Self {
+        Self { limits, ..self }
+    }
+
+    /// Returns the amount of time to wait between connection attempts when racing multiple
+    /// addresses
+    #[inline]
+    pub fn attempt_delay(&self) -> Duration {
+        self.attempt_delay
+    }
+
+    /// Returns `true` if the connection attempt should be raced across multiple addresses
+    #[inline]
+    pub fn is_racing(&self) -> bool {
+        !self.additional_addresses.is_empty()
+    }
+
+    /// Splits the connection attempt into a single attempt per remote address
+    ///
+    /// The attempts are ordered by interleaving the address families, starting with the
+    /// family of the primary address.
+    pub fn into_racing_attempts(self) -> Vec<Self> {
+        // Alternate between address families, as described in
+        // https://www.rfc-editor.org/rfc/rfc8305#section-4
+        let is_ipv4 = |addr: &RemoteAddress| matches!(addr.unmap(), SocketAddress::IpV4(_));
+        let primary_is_ipv4 = is_ipv4(&self.remote_address);
+        let is_primary_family = |addr: &RemoteAddress| is_ipv4(addr) == primary_is_ipv4;
+
+        let mut primary = vec![self.remote_address];
+        let mut secondary = vec![];
+        for addr in &self.additional_addresses {
+            if is_primary_family(addr) {
+                primary.push(*addr);
+            } else {
+                secondary.push(*addr);
+            }
+        }
+
+        let mut primary = primary.into_iter();
+        let mut secondary = secondary.into_iter();
+        let mut attempts = Vec::with_capacity(self.additional_addresses.len() + 1);
+
+        loop {
+            let a = primary.next();
+            let b = secondary.next();
+
+            if a.is_none() && b.is_none() {
+                break;
+            }
+
+            for remote_address in a.into_iter().chain(b) {
+                attempts.push(Self {
+                    remote_address,
+                    server_name: self.server_name.clone(),
+                    deduplicate: self.deduplicate,
+                    additional_addresses: Vec::new(),
+                    attempt_delay: self.attempt_delay,
+                    host: None,
+                    token: self.token.clone(),
+                    limits: self.limits,
+                });
+            }
+        }
+
+        attempts
+    }
+
     /// Specifies whether to deduplicate this connect request with other concurrent connect
     /// requests and with any existing open connections.
     ///
     /// Only a connection opened with `deduplicate: true` can be later found by a subsequent
-    /// request.
+    /// request. Requests are only deduplicated if they are otherwise identical, including the
+    /// limit overrides from [`Self::with_limits`].
     ///
     /// Note that this is only supported with the `dc` provider enabled on the s2n-quic endpoint.
     #[must_use]
@@ -80,6 +308,54 @@ impl Connect {
     }
 }
 
+/// A hostname and port which has not yet been resolved to an address
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Host {
+    name: ServerName,
+    port: u16,
+}
+
+impl Host {
+    /// Returns the hostname to resolve
+    #[inline]
+    pub fn name(&self) -> &ServerName {
+        &self.name
+    }
+
+    /// Returns the port to connect to
+    #[inline]
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+}
+
+impl fmt::Display for Host {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", &*self.name, self.port)
+    }
+}
+
+/// An error indicating a host could not be parsed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HostError {
+    reason: &'static str,
+}
+
+impl HostError {
+    fn new(reason: &'static str) -> Self {
+        Self { reason }
+    }
+}
+
+impl fmt::Display for HostError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid host: {}", self.reason)
+    }
+}
+
+#[cfg(any(test, feature = "std"))]
+impl std::error::Error for HostError {}
+
 /// Make it easy for applications to create a connection attempt without importing the `Connect` struct
 impl<T: Into<SocketAddress>> From<T> for Connect {
     fn from(addr: T) -> Self {
@@ -118,7 +394,7 @@ impl Attempt {
             sender: response,
         };
         Self {
-            state: AttemptState::Connect(request, opener.clone(), receiver),
+            state: AttemptState::Connect(Box::new(request), opener.clone(), receiver),
         }
     }
 
@@ -129,7 +405,7 @@ impl Attempt {
                 AttemptState::Connect(request, mut opener, response) => {
                     match opener.poll_ready(cx) {
                         Poll::Ready(Ok(())) => {
-                            match opener.try_send(request) {
+                            match opener.try_send(*request) {
                                 Ok(_) => {
                                     // transition to the waiting state
                                     self.state = AttemptState::Waiting(response);
@@ -137,8 +413,11 @@ impl Attempt {
                                 }
                                 Err(err) if err.is_full() => {
                                     // reset to the original state
-                                    self.state =
-                                        AttemptState::Connect(err.into_inner(), opener, response);
+                                    self.state = AttemptState::Connect(
+                                        Box::new(err.into_inner()),
+                                        opener,
+                                        response,
+                                    );
 
                                     // yield and wake up the task since the opener misreported its ready state
                                     cx.waker().wake_by_ref();
@@ -186,7 +465,9 @@ impl Attempt {
 
 enum AttemptState {
     /// The attempt is currently waiting for capacity in the `ConnectorSender` to make the `Request`
-    Connect(Request, ConnectorSender, ConnectionReceiver),
+    ///
+    /// The request is boxed since it's much larger than the other states.
+    Connect(Box<Request>, ConnectorSender, ConnectionReceiver),
     /// The attempt is currently waiting for a response back from the endpoint on the `ConnectionReceiver`
     Waiting(ConnectionReceiver),
     /// This is an intermediate state and should not persist across calls to `poll`
@@ -200,3 +481,61 @@ impl Future for Attempt {
         s2n_quic_core::task::waker::debug_assert_contract(cx, |cx| self.poll_state(cx))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use s2n_quic_core::inet::{SocketAddressV4, SocketAddressV6};
+
+    #[test]
+    fn racing_attempts_interleave_families() {
+        let v6_a: SocketAddress = SocketAddressV6::new([1; 16], 443).into();
+        let v6_b: SocketAddress = SocketAddressV6::new([2; 16], 443).into();
+        let v4_a: SocketAddress = SocketAddressV4::new([1; 4], 443).into();
+        let v4_b: SocketAddress = SocketAddressV4::new([2; 4], 443).into();
+
+        let connect = Connect::new_racing([v6_a, v6_b, v4_a, v4_b])
+            .unwrap()
+            .with_server_name("localhost");
+        assert!(connect.is_racing());
+
+        let attempts = connect.into_racing_attempts();
+        let addresses: Vec<_> = attempts.iter().map(|a| *a.remote_address).collect();
+        assert_eq!(addresses, [v6_a, v4_a, v6_b, v4_b]);
+
+        for attempt in attempts {
+            assert!(!attempt.is_racing());
+            assert_eq!(attempt.server_name.as_deref(), Some("localhost"));
+        }
+
+        assert!(Connect::new_racing(core::iter::empty::<SocketAddress>()).is_none());
+    }
+
+    #[test]
+    fn from_host_test() {
+        let connect = Connect::from_host("example.com:4433").unwrap();
+        let host = connect.host().unwrap();
+        assert_eq!(&**host.name(), "example.com");
+        assert_eq!(host.port(), 4433);
+        assert_eq!(connect.server_name.as_deref(), Some("example.com"));
+
+        let addr: SocketAddress = SocketAddressV4::new([127, 0, 0, 1], 4433).into();
+        let connect = connect.with_resolved_addresses([addr]).unwrap();
+        assert!(connect.host().is_none());
+        assert_eq!(*connect.remote_address, addr);
+        assert_eq!(connect.server_name.as_deref(), Some("example.com"));
+
+        // IP addresses don't need to be resolved
+        let connect = Connect::from_host("127.0.0.1:4433").unwrap();
+        assert!(connect.host().is_none());
+        assert_eq!(*connect.remote_address, addr);
+
+        let connect = Connect::from_host("[::1]:4433").unwrap();
+        assert!(connect.host().is_none());
+        assert_eq!(connect.remote_address.port(), 4433);
+
+        for invalid in ["example.com", "example.com:", ":4433", "example.com:99999"] {
+            assert!(Connect::from_host(invalid).is_err(), "{invalid}");
+        }
+    }
+}
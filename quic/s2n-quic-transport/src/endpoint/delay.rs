@@ -0,0 +1,117 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Allows the application to wait for a duration measured by the endpoint clock
+
+use alloc::vec::Vec;
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+use futures_channel::{mpsc, oneshot};
+use futures_core::Stream;
+use s2n_quic_core::time::{Clock, Timestamp};
+
+/// Held by library. Used to receive delay requests from the application.
+pub(crate) type DelayReceiver = mpsc::UnboundedReceiver<Request>;
+/// Held by application. Used to submit delay requests to the library.
+pub(crate) type DelaySender = mpsc::UnboundedSender<Request>;
+
+/// A request to be notified once `duration` has elapsed
+#[derive(Debug)]
+pub(crate) struct Request {
+    duration: Duration,
+    sender: oneshot::Sender<()>,
+}
+
+/// A future which completes once the endpoint clock has advanced by the requested duration
+///
+/// Since the delay is driven by the endpoint, it doesn't depend on the async runtime and it
+/// follows the clock of the IO provider, including simulated clocks in tests. The delay
+/// completes immediately if the endpoint has shut down.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct Delay {
+    receiver: oneshot::Receiver<()>,
+}
+
+impl Delay {
+    pub(crate) fn new(sender: &DelaySender, duration: Duration) -> Self {
+        let (tx, receiver) = oneshot::channel();
+
+        // sending only fails if the endpoint has shut down, in which case dropping the request
+        // completes the delay
+        let _ = sender.unbounded_send(Request {
+            duration,
+            sender: tx,
+        });
+
+        Self { receiver }
+    }
+}
+
+impl Future for Delay {
+    type Output = ();
+
+    #[inline]
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        match Pin::new(&mut self.receiver).poll(cx) {
+            Poll::Ready(_) => Poll::Ready(()),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+/// Held by library. Tracks the pending delays for the application.
+#[derive(Debug)]
+pub(crate) struct Queue {
+    receiver: DelayReceiver,
+    pending: Vec<(Timestamp, oneshot::Sender<()>)>,
+}
+
+impl Queue {
+    pub(crate) fn new(receiver: DelayReceiver) -> Self {
+        Self {
+            receiver,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Receives new delay requests from the application
+    ///
+    /// Returns the number of requests received
+    pub(crate) fn poll_requests<C: Clock>(&mut self, cx: &mut Context, clock: &C) -> usize {
+        let mut count = 0;
+
+        while let Poll::Ready(Some(request)) = Stream::poll_next(Pin::new(&mut self.receiver), cx) {
+            count += 1;
+            let target = clock.get_time() + request.duration;
+            self.pending.push((target, request.sender));
+        }
+
+        count
+    }
+
+    /// Notifies the delays which have expired and removes the ones the application abandoned
+    pub(crate) fn on_timeout(&mut self, now: Timestamp) {
+        let mut index = 0;
+        while let Some((target, sender)) = self.pending.get(index) {
+            if *target <= now {
+                let (_, sender) = self.pending.swap_remove(index);
+                let _ = sender.send(());
+            } else if sender.is_canceled() {
+                self.pending.swap_remove(index);
+            } else {
+                index += 1;
+            }
+        }
+    }
+
+    /// Returns the earliest time at which a delay expires
+    #[inline]
+    pub(crate) fn next_expiration(&self) -> Option<Timestamp> {
+        self.pending.iter().map(|(target, _)| *target).min()
+    }
+}
@@ -6,11 +6,17 @@
 use crate::{
     connection,
     connection::Connection,
-    endpoint::{close, close::CloseHandle, connect},
+    endpoint::{
+        close,
+        close::CloseHandle,
+        connect,
+        delay::{self, DelayReceiver, DelaySender},
+    },
 };
 use core::{
     pin::Pin,
     task::{Context, Poll, Waker},
+    time::Duration,
 };
 use futures_channel::mpsc;
 use futures_core::Stream;
@@ -30,6 +36,11 @@ pub(crate) type CloseReceiver = mpsc::Receiver<Waker>;
 /// Held by the application. Used to submit connection close attempts to the library.
 pub(crate) type CloseSender = mpsc::Sender<Waker>;
 
+/// Held by library. Used to receive local network change notifications from the application.
+pub(crate) type NetworkChangeReceiver = mpsc::UnboundedReceiver<()>;
+/// Held by the application. Used to notify the library of local network changes.
+pub(crate) type NetworkChangeSender = mpsc::UnboundedSender<()>;
+
 /// The [`Handle`] allows applications to accept and open QUIC connections on an `Endpoint`.
 #[derive(Debug)]
 pub(crate) struct Handle {
@@ -41,9 +52,18 @@ impl Handle {
     /// Creates a new `Handle` with a limit opening connection limit.
     pub(crate) fn new(
         max_opening_connections: usize,
-    ) -> (Self, AcceptorSender, ConnectorReceiver, CloseHandle) {
+    ) -> (
+        Self,
+        AcceptorSender,
+        ConnectorReceiver,
+        CloseHandle,
+        NetworkChangeReceiver,
+        DelayReceiver,
+    ) {
         let (acceptor_sender, acceptor_receiver) = mpsc::unbounded();
         let (connector_sender, connector_receiver) = mpsc::channel(max_opening_connections);
+        let (network_change_sender, network_change_receiver) = mpsc::unbounded();
+        let (delay_sender, delay_receiver) = mpsc::unbounded();
 
         let (close_sender, close_receiver) = mpsc::channel(max_opening_connections);
 
@@ -52,10 +72,13 @@ impl Handle {
         let handle = Self {
             acceptor: Acceptor {
                 acceptor: acceptor_receiver,
+                network_change: network_change_sender.clone(),
             },
             connector: Connector {
                 connector: connector_sender,
                 closer,
+                network_change: network_change_sender,
+                delay: delay_sender,
             },
         };
         (
@@ -63,6 +86,8 @@ impl Handle {
             acceptor_sender,
             connector_receiver,
             CloseHandle::new(close_receiver, endpoint_state),
+            network_change_receiver,
+            delay_receiver,
         )
     }
 }
@@ -70,6 +95,7 @@ impl Handle {
 #[derive(Debug)]
 pub struct Acceptor {
     acceptor: AcceptorReceiver,
+    network_change: NetworkChangeSender,
 }
 
 impl Acceptor {
@@ -92,12 +118,19 @@ impl Acceptor {
             Poll::Pending => Poll::Pending,
         }
     }
+
+    /// Notifies the endpoint that the local network has changed
+    pub fn notify_network_change(&self) {
+        notify_network_change(&self.network_change)
+    }
 }
 
 #[derive(Clone, Debug)]
 pub struct Connector {
     connector: ConnectorSender,
     closer: close::Closer,
+    network_change: NetworkChangeSender,
+    delay: DelaySender,
 }
 
 impl Connector {
@@ -106,8 +139,28 @@ impl Connector {
         connect::Attempt::new(&self.connector, connect)
     }
 
+    /// Returns a future which completes once `duration` has elapsed on the endpoint clock
+    pub fn delay(&self, duration: Duration) -> delay::Delay {
+        delay::Delay::new(&self.delay, duration)
+    }
+
+    /// Returns `true` if the endpoint is still running
+    pub fn is_open(&self) -> bool {
+        !self.delay.is_closed()
+    }
+
     /// Polls to close the endpoint
     pub fn poll_close(&mut self, context: &mut Context) -> Poll<Result<(), connection::Error>> {
         self.closer.poll_close(context)
     }
+
+    /// Notifies the endpoint that the local network has changed
+    pub fn notify_network_change(&self) {
+        notify_network_change(&self.network_change)
+    }
+}
+
+fn notify_network_change(sender: &NetworkChangeSender) {
+    // sending only fails if the endpoint has shut down, in which case there is nothing to notify
+    let _ = sender.unbounded_send(());
 }
@@ -10,17 +10,20 @@ use crate::{
     },
     endpoint,
     recovery::congestion_controller::{self, Endpoint as _},
-    space::PacketSpaceManager,
+    space::{InitialPacketNumbers, PacketSpaceManager},
 };
 use core::convert::TryInto;
 use s2n_codec::DecoderBufferMut;
 use s2n_quic_core::{
+    connection::close::Formatter as _,
     crypto::{tls, tls::Endpoint as TLSEndpoint, CryptoSuite, InitialKey},
     datagram::{Endpoint, PreConnectionInfo},
+    endpoint::accept_filter::{self, Filter as _},
     event::{self, supervisor, ConnectionPublisher, EndpointPublisher, IntoEvent, Subscriber as _},
+    frame::{Frame, FrameMut},
     inet::{datagram, DatagramInfo},
-    packet::initial::ProtectedInitial,
-    path::Handle as _,
+    packet::{initial::ProtectedInitial, retry::InitialHeader},
+    path::{self, cache::Cache as _, Handle as _},
     stateless_reset::token::Generator as _,
     transport::{self, parameters::ServerTransportParameters},
 };
@@ -33,6 +36,7 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
         packet: ProtectedInitial,
         remaining: DecoderBufferMut,
         retry_token_dcid: Option<connection::InitialId>,
+        is_address_attested: bool,
     ) -> Result<(), connection::Error> {
         debug_assert!(
             Config::ENDPOINT_TYPE.is_server(),
@@ -93,10 +97,22 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
                 .generate(&connection_info);
         }
 
+        // Packets sent to a zero-length connection ID can't be routed to more than one
+        // connection, so refuse the attempt if the ID is already in use.
+        if initial_connection_id.is_empty()
+            && self
+                .connection_id_mapper
+                .is_local_id_registered(&initial_connection_id)
+        {
+            return Err(transport::Error::CONNECTION_REFUSED
+                .with_reason("zero-length connection id already in use")
+                .into());
+        }
+
         //= https://www.rfc-editor.org/rfc/rfc9000#section-17.2
         //# Endpoints that receive a version 1 long header
         //# with a value larger than 20 MUST drop the packet.
-        let source_connection_id = packet
+        let source_connection_id: connection::PeerId = packet
             .source_connection_id()
             .try_into()
             .map_err(transport::Error::from)?;
@@ -104,7 +120,7 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
         //= https://www.rfc-editor.org/rfc/rfc9000#section-17.2.5.2
         //# Changing the Destination Connection ID field also results in
         //# a change to the keys used to protect the Initial packet.
-        let (initial_key, initial_header_key) =
+        let (mut initial_key, initial_header_key) =
             <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::InitialKey::new_server(
                 datagram.destination_connection_id.as_bytes(),
             );
@@ -117,12 +133,104 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
         let packet = packet
             .unprotect(&initial_header_key, largest_packet_number)
             .map_err(|_| transport::Error::from(tls::Error::DECODE_ERROR))?;
-        let packet = packet
+        let mut packet = packet
             .decrypt(&initial_key)
             .map_err(|_| transport::Error::from(tls::Error::DECRYPT_ERROR))?;
 
         // TODO handle token with stateless retry
 
+        // Give the application a chance to reject the connection attempt before any state is
+        // allocated and the TLS session processes the ClientHello
+        let mut client_hello = [0u8; CLIENT_HELLO_PREFIX_LEN];
+        let payload = packet.payload.into_less_safe_slice();
+        let client_hello_len = copy_crypto_prefix(payload, &mut client_hello);
+        packet.payload = DecoderBufferMut::new(payload);
+
+        let endpoint_context = self.config.context();
+        let attempt = accept_filter::ConnectionAttempt::new(
+            &remote_address,
+            datagram.destination_connection_id.as_bytes(),
+            retry_token_dcid.is_some() || is_address_attested,
+            &client_hello[..client_hello_len],
+            datagram.timestamp.into_event(),
+        );
+
+        let mut overrides = accept_filter::Overrides::default();
+
+        match endpoint_context
+            .accept_filter
+            .on_connection_attempt(&attempt)
+        {
+            accept_filter::Outcome::Allow {
+                overrides: allowed_overrides,
+                ..
+            } => {
+                overrides = allowed_overrides;
+            }
+            // the peer already validated its address
+            accept_filter::Outcome::Retry { .. } if attempt.is_address_validated => {}
+            accept_filter::Outcome::Retry { .. } => {
+                let connection_info = ConnectionInfo::new(&remote_address);
+                let local_connection_id = endpoint_context
+                    .connection_id_format
+                    .generate(&connection_info);
+
+                self.retry_dispatch.queue::<
+                    _,
+                    <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::RetryKey,
+                >(
+                    header.path,
+                    InitialHeader {
+                        version: packet.version,
+                        destination_connection_id: datagram.destination_connection_id.as_bytes(),
+                        source_connection_id: source_connection_id.as_bytes(),
+                    },
+                    local_connection_id,
+                    endpoint_context.random_generator,
+                    endpoint_context.token,
+                );
+
+                return Ok(());
+            }
+            accept_filter::Outcome::Close { error, .. } => {
+                // The attempt is closed statelessly so the ClientHello is never processed and no
+                // connection state is allocated
+                let close_context = s2n_quic_core::connection::close::Context::new(&remote_address);
+                let connection_close = endpoint_context
+                    .connection_close_formatter
+                    .format_early_transport_error(&close_context, error);
+
+                self.reject_dispatch.queue(
+                    header.path,
+                    packet.version,
+                    source_connection_id.as_bytes(),
+                    datagram.destination_connection_id.as_bytes(),
+                    &connection_close,
+                    &mut initial_key,
+                    &initial_header_key,
+                );
+
+                return Ok(());
+            }
+            _ => {
+                // Outcome is non_exhaustive so drop on things we don't understand
+                let mut publisher = event::EndpointPublisherSubscriber::new(
+                    event::builder::EndpointMeta {
+                        endpoint_type: Config::ENDPOINT_TYPE,
+                        timestamp: datagram.timestamp,
+                    },
+                    Some(packet.version),
+                    endpoint_context.event_subscriber,
+                );
+                publisher.on_endpoint_datagram_dropped(event::builder::EndpointDatagramDropped {
+                    len: datagram.payload_len as u16,
+                    reason: event::builder::DatagramDropReason::RejectedConnectionAttempt,
+                });
+
+                return Ok(());
+            }
+        }
+
         let internal_connection_id = self.connection_id_generator.generate_id();
 
         let initial_connection_id_expiration_time = self
@@ -169,6 +277,7 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
             .context()
             .connection_limits
             .on_connection(&LimitsInfo::new(&remote_address));
+        let limits = overrides.apply(limits);
 
         transport_parameters.load_limits(&limits);
 
@@ -258,7 +367,7 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
             Some(quic_version),
             endpoint_context.event_subscriber,
         );
-        let mtu_config = endpoint_context
+        let mut mtu_config = endpoint_context
             .mtu
             .config(&remote_address)
             .map_err(|_err| {
@@ -271,6 +380,22 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
                 error
             })?;
 
+        // the connection would be unable to exchange any data
+        if limits.datagram_only()
+            && endpoint_context
+                .datagram
+                .max_datagram_frame_size(&PreConnectionInfo::new())
+                == 0
+        {
+            let error = connection::Error::invalid_configuration(
+                "datagram-only connections require a datagram provider",
+            );
+            endpoint_publisher.on_endpoint_connection_attempt_failed(
+                event::builder::EndpointConnectionAttemptFailed { error },
+            );
+            return Err(error);
+        }
+
         let mut publisher = event::ConnectionPublisherSubscriber::new(
             meta,
             quic_version,
@@ -278,7 +403,16 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
             &mut event_context,
         );
 
-        let path_info = congestion_controller::PathInfo::new(&mtu_config, &remote_address);
+        let cached_path = endpoint_context
+            .path_cache
+            .get(&path::cache::PathInfo::new(&remote_address));
+
+        if let Some(entry) = cached_path {
+            mtu_config = mtu_config.with_cached_mtu(entry.mtu);
+        }
+
+        let mut path_info = congestion_controller::PathInfo::new(&mtu_config, &remote_address);
+        path_info.cached_path = cached_path;
         let congestion_controller = endpoint_context
             .congestion_controller
             .new_congestion_controller(path_info);
@@ -288,6 +422,11 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
             tls_session,
             initial_key,
             initial_header_key,
+            endpoint_context.compatibility.clone(),
+            InitialPacketNumbers::new(
+                limits.max_initial_packet_number_offset(),
+                endpoint_context.random_generator,
+            ),
             datagram.timestamp,
             &mut publisher,
         );
@@ -309,6 +448,8 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
             quic_version,
             limits,
             mtu_config,
+            path_cache: endpoint_context.path_cache.clone(),
+            cached_path,
             event_context,
             supervisor_context: &supervisor_context,
             event_subscriber: endpoint_context.event_subscriber,
@@ -443,3 +584,49 @@ impl<Config: endpoint::Config> endpoint::Endpoint<Config> {
         Ok(())
     }
 }
+
+/// The maximum number of ClientHello bytes which are inspected by the accept filter
+///
+/// This is large enough for a ClientHello which fits in a single Initial packet.
+const CLIENT_HELLO_PREFIX_LEN: usize = 1500;
+
+/// Copies the contiguous beginning of the CRYPTO stream in `payload` into `buffer`, returning
+/// the number of bytes copied
+///
+/// Some clients split the ClientHello into several CRYPTO frames and reorder them within the
+/// packet so the frames are scanned until no more progress can be made.
+fn copy_crypto_prefix(payload: &mut [u8], buffer: &mut [u8]) -> usize {
+    let mut len = 0;
+
+    loop {
+        let mut progress = false;
+        let mut remaining = DecoderBufferMut::new(&mut *payload);
+
+        while !remaining.is_empty() {
+            let Ok((frame, next)) = remaining.decode::<FrameMut>() else {
+                return len;
+            };
+            remaining = next;
+
+            let Frame::Crypto(frame) = frame else {
+                continue;
+            };
+
+            let Ok(offset) = usize::try_from(frame.offset.as_u64()) else {
+                continue;
+            };
+            let data = frame.data.into_less_safe_slice();
+            let end = offset.saturating_add(data.len()).min(buffer.len());
+
+            if offset <= len && len < end {
+                buffer[len..end].copy_from_slice(&data[len - offset..end - offset]);
+                len = end;
+                progress = true;
+            }
+        }
+
+        if !progress || len == buffer.len() {
+            return len;
+        }
+    }
+}
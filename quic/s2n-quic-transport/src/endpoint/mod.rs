@@ -13,14 +13,16 @@ use crate::{
     endpoint,
     endpoint::close::CloseHandle,
     recovery::congestion_controller::{self, Endpoint as _},
-    space::PacketSpaceManager,
+    space::{InitialPacketNumbers, PacketSpaceManager},
     wakeup_queue::WakeupQueue,
 };
 use alloc::collections::VecDeque;
 use core::{
     convert::TryInto,
+    pin::Pin,
     task::{self, Poll},
 };
+use futures_core::Stream;
 use s2n_codec::{DecoderBuffer, DecoderBufferMut};
 use s2n_quic_core::{
     connection::{
@@ -31,15 +33,19 @@ use s2n_quic_core::{
     datagram::{Endpoint as DatagramEndpoint, PreConnectionInfo},
     dc,
     dc::Endpoint as _,
-    endpoint::{limits::Outcome, Limiter as _},
+    endpoint::{
+        address_attestation::Validator as _,
+        limits::{DropReason, Outcome},
+        Limiter as _,
+    },
     event::{
         self, supervisor, ConnectionPublisher, EndpointPublisher as _, IntoEvent, Subscriber as _,
     },
     inet::{datagram, DatagramInfo},
-    io::{rx, tx},
+    io::{event_loop::profile, rx, tx},
     packet::{initial::ProtectedInitial, interceptor::Interceptor, ProtectedPacket},
     path,
-    path::{mtu, Handle as _},
+    path::{cache::Cache as _, mtu, Handle as _},
     random::Generator as _,
     stateless_reset::token::{Generator as _, LEN as StatelessResetTokenLen},
     time::{Clock, Timestamp},
@@ -50,9 +56,11 @@ use s2n_quic_core::{
 pub mod close;
 mod config;
 pub mod connect;
+pub mod delay;
 pub mod handle;
 mod initial;
 mod packet_buffer;
+mod reject;
 mod retry;
 mod stateless_reset;
 mod version;
@@ -64,6 +72,9 @@ pub use s2n_quic_core::endpoint::*;
 
 const DEFAULT_MAX_PEERS: usize = 1024;
 
+/// The number of datagrams a connection with a transmission weight of 1 may transmit on each turn
+const TRANSMISSION_QUANTUM: usize = 16;
+
 /// A QUIC `Endpoint`
 pub struct Endpoint<Cfg: Config> {
     /// Configuration parameters for the endpoint
@@ -79,12 +90,17 @@ pub struct Endpoint<Cfg: Config> {
     wakeup_queue: WakeupQueue<InternalConnectionId>,
     /// Used to receive close attempts and track close state.
     close_handle: CloseHandle,
+    /// Used to receive local network change notifications from the application
+    network_change_receiver: handle::NetworkChangeReceiver,
+    /// Delays requested by the application, which are driven by the endpoint clock
+    delays: delay::Queue,
     /// This queue contains wakeups we retrieved from the [`Self::wakeup_queue`] earlier.
     /// This is not a local variable in order to reuse the allocated queue capacity in between
     /// [`Endpoint`] interactions.
     dequeued_wakeups: VecDeque<InternalConnectionId>,
     version_negotiator: version::Negotiator<Cfg>,
     retry_dispatch: retry::Dispatch<Cfg::PathHandle>,
+    reject_dispatch: reject::Dispatch<Cfg::PathHandle>,
     stateless_reset_dispatch: stateless_reset::Dispatch<Cfg::PathHandle>,
     close_packet_buffer: packet_buffer::Buffer,
 }
@@ -132,26 +148,42 @@ impl<Cfg: Config> s2n_quic_core::endpoint::Endpoint for Endpoint<Cfg> {
 
         let timestamp = clock.get_time();
 
-        self.connections.iterate_transmission_list(|connection| {
-            // if we no longer have capacity, then put the connection at the front of the queue for
-            // next time
-            if !queue.has_capacity() {
-                return ConnectionContainerIterationResult::BreakAndInsertAtFront;
-            }
+        // Connections are serviced in rounds. On each turn, a connection may transmit a number of
+        // datagrams proportional to its weight before it's moved to the back of the queue.
+        loop {
+            let mut has_more = false;
 
-            // ignore the transmission error and just query the queue capacity instead
-            let _ = connection.on_transmit(
-                queue,
-                timestamp,
-                endpoint_context.event_subscriber,
-                endpoint_context.packet_interceptor,
-            );
+            self.connections.iterate_transmission_list(|connection| {
+                // if we no longer have capacity, then put the connection at the front of the queue
+                // for next time
+                if !queue.has_capacity() {
+                    return ConnectionContainerIterationResult::BreakAndInsertAtFront;
+                }
 
-            // flush the TX queue between connections
-            queue.flush();
+                let limit = connection.transmission_weight() as usize * TRANSMISSION_QUANTUM;
+                let mut limited = tx::limit::Queue::new(queue, limit);
 
-            ConnectionContainerIterationResult::Continue
-        });
+                // ignore the transmission error and just query the queue capacity instead
+                let _ = connection.on_transmit(
+                    &mut limited,
+                    timestamp,
+                    endpoint_context.event_subscriber,
+                    endpoint_context.packet_interceptor,
+                );
+
+                // the connection may have more to transmit if it used its entire share
+                has_more |= limited.remaining() == 0;
+
+                // flush the TX queue between connections
+                queue.flush();
+
+                ConnectionContainerIterationResult::Continue
+            });
+
+            if !has_more || !queue.has_capacity() {
+                break;
+            }
+        }
 
         if queue.has_capacity() {
             let mut publisher = event::EndpointPublisherSubscriber::new(
@@ -164,6 +196,7 @@ impl<Cfg: Config> s2n_quic_core::endpoint::Endpoint for Endpoint<Cfg> {
             );
             self.version_negotiator.on_transmit(queue, &mut publisher);
             self.retry_dispatch.on_transmit(queue, &mut publisher);
+            self.reject_dispatch.on_transmit(queue, &mut publisher);
             self.stateless_reset_dispatch
                 .on_transmit(queue, &mut publisher);
         }
@@ -227,8 +260,30 @@ impl<Cfg: Config> s2n_quic_core::endpoint::Endpoint for Endpoint<Cfg> {
             });
         }
 
+        // multiple notifications are coalesced into a single network change
+        let mut network_changed = false;
+        while let Poll::Ready(Some(())) =
+            Stream::poll_next(Pin::new(&mut self.network_change_receiver), cx)
+        {
+            network_changed = true;
+        }
+
+        if network_changed {
+            wakeup_count += 1;
+
+            let random_generator = endpoint_context.random_generator;
+            self.connections.for_each(|conn| {
+                conn.on_network_change(random_generator);
+            });
+        }
+
+        wakeup_count += self.delays.poll_requests(cx, clock);
+
         // try to open connection requests from the application
         if Cfg::ENDPOINT_TYPE.is_client() {
+            // close any connections which are still opening but the application stopped waiting for
+            wakeup_count += self.connections.close_abandoned_connections(cx);
+
             loop {
                 match self.connections.poll_connection_request(cx) {
                     Poll::Pending => break,
@@ -236,10 +291,11 @@ impl<Cfg: Config> s2n_quic_core::endpoint::Endpoint for Endpoint<Cfg> {
                         wakeup_count += 1;
 
                         let time = clock.get_time();
-                        if let Err(err) = self.create_client_connection(request, time) {
+                        if let Err(_err) = self.create_client_connection(request, time) {
                             // TODO report that the connection was not successfully created
                             // TODO emit event
-                            dbg!(err);
+                            #[cfg(any(test, feature = "std"))]
+                            dbg!(_err);
                         }
                     }
                     Poll::Ready(None) => {
@@ -259,7 +315,11 @@ impl<Cfg: Config> s2n_quic_core::endpoint::Endpoint for Endpoint<Cfg> {
 
     #[inline]
     fn timeout(&self) -> Option<Timestamp> {
-        self.connections.next_expiration()
+        let timeout = self.connections.next_expiration();
+        match (timeout, self.delays.next_expiration()) {
+            (Some(timeout), Some(delay)) => Some(timeout.min(delay)),
+            (timeout, delay) => timeout.or(delay),
+        }
     }
 
     #[inline]
@@ -297,8 +357,14 @@ impl<Cfg: Config> Endpoint<Cfg> {
     fn new(mut config: Cfg) -> (Self, handle::Handle) {
         // TODO make this limit configurable
         let max_opening_connections = 1000;
-        let (handle, acceptor_sender, connector_receiver, close_handle) =
-            handle::Handle::new(max_opening_connections);
+        let (
+            handle,
+            acceptor_sender,
+            connector_receiver,
+            close_handle,
+            network_change_receiver,
+            delay_receiver,
+        ) = handle::Handle::new(max_opening_connections);
 
         let connection_id_mapper =
             ConnectionIdMapper::new(config.context().random_generator, Cfg::ENDPOINT_TYPE);
@@ -310,9 +376,12 @@ impl<Cfg: Config> Endpoint<Cfg> {
             connection_id_mapper,
             wakeup_queue: WakeupQueue::new(),
             close_handle,
+            network_change_receiver,
+            delays: delay::Queue::new(delay_receiver),
             dequeued_wakeups: VecDeque::new(),
             version_negotiator: version::Negotiator::default(),
             retry_dispatch: retry::Dispatch::default(),
+            reject_dispatch: reject::Dispatch::default(),
             stateless_reset_dispatch: stateless_reset::Dispatch::default(),
             close_packet_buffer: Default::default(),
         };
@@ -327,6 +396,7 @@ impl<Cfg: Config> Endpoint<Cfg> {
         packet: &ProtectedInitial,
         payload_len: usize,
         timestamp: Timestamp,
+        is_address_attested: bool,
     ) -> Option<()> {
         if !self.connections.can_accept() {
             return None;
@@ -354,6 +424,8 @@ impl<Cfg: Config> Endpoint<Cfg> {
 
         match outcome {
             Outcome::Allow { .. } => Some(()),
+            // the peer's address was already validated with an attestation
+            Outcome::Retry { .. } if is_address_attested => Some(()),
             Outcome::Retry { .. } => {
                 //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.2
                 //# A server can also use a Retry packet to defer the state and
@@ -396,10 +468,20 @@ impl<Cfg: Config> Endpoint<Cfg> {
 
                 None
             }
-            Outcome::Drop { .. } => {
+            Outcome::Drop { reason, .. } => {
+                let reason = match reason {
+                    DropReason::RateLimited => {
+                        event::builder::DatagramDropReason::ConnectionRateLimited
+                    }
+                    DropReason::InflightHandshakeLimitExceeded => {
+                        event::builder::DatagramDropReason::InflightHandshakeLimitExceeded
+                    }
+                    _ => event::builder::DatagramDropReason::RejectedConnectionAttempt,
+                };
+
                 publisher.on_endpoint_datagram_dropped(event::builder::EndpointDatagramDropped {
                     len: payload_len as u16,
-                    reason: event::builder::DatagramDropReason::RejectedConnectionAttempt,
+                    reason,
                 });
                 None
             }
@@ -555,6 +637,8 @@ impl<Cfg: Config> Endpoint<Cfg> {
             datagram.destination_connection_id_classification = dcid_classification;
 
             let _ = self.connections.with_connection(internal_id, |conn| {
+                let _scope = profile::scope(profile::Phase::ConnectionProcessing);
+
                 // The path `Id` needs to be passed around instead of the path to get around `&mut self` and
                 // `&mut self.path_manager` being borrowed at the same time
                 let path_id = conn
@@ -676,11 +760,24 @@ impl<Cfg: Config> Endpoint<Cfg> {
                 //# the peer uses a connection ID chosen by the endpoint and the
                 //# connection ID contains at least 64 bits of entropy
 
+                // Peers with an out-of-band attestation are considered to have validated their
+                // address, so they don't need to be sent a Retry packet
+                let is_address_attested = {
+                    let attestation = address_attestation::Attestation::new(
+                        &remote_address,
+                        packet.token(),
+                        timestamp.into_event(),
+                    );
+                    endpoint_context
+                        .address_attestation
+                        .is_attested(&attestation)
+                };
+
                 //= https://www.rfc-editor.org/rfc/rfc9000#section-8.1.2
                 //# In response to processing an Initial packet containing a token that
                 //# was provided in a Retry packet, a server cannot send another Retry
                 //# packet; it can only refuse the connection or permit it to proceed.
-                let retry_token_dcid = if !packet.token().is_empty() {
+                let retry_token_dcid = if !is_address_attested && !packet.token().is_empty() {
                     let mut context = token::Context::new(
                         &remote_address,
                         &source_connection_id,
@@ -728,7 +825,13 @@ impl<Cfg: Config> Endpoint<Cfg> {
                     //# address validation by sending a Retry packet (Section 17.2.5)
                     //# containing a token.
                     if self
-                        .connection_allowed(header, &packet, payload_len, timestamp)
+                        .connection_allowed(
+                            header,
+                            &packet,
+                            payload_len,
+                            timestamp,
+                            is_address_attested,
+                        )
                         .is_none()
                     {
                         //= https://www.rfc-editor.org/rfc/rfc9000#section-17.2.5.1
@@ -746,6 +849,7 @@ impl<Cfg: Config> Endpoint<Cfg> {
                     packet,
                     remaining,
                     retry_token_dcid,
+                    is_address_attested,
                 ) {
                     // TODO send a minimal connection close frame
                     let mut publisher = event::EndpointPublisherSubscriber::new(
@@ -905,6 +1009,9 @@ impl<Cfg: Config> Endpoint<Cfg> {
     }
 
     fn on_timeout(&mut self, timestamp: Timestamp) {
+        let _scope = profile::scope(profile::Phase::Timers);
+        self.delays.on_timeout(timestamp);
+
         let connection_id_mapper = &mut self.connection_id_mapper;
         let close_packet_buffer = &mut self.close_packet_buffer;
         let endpoint_context = self.config.context();
@@ -963,6 +1070,9 @@ impl<Cfg: Config> Endpoint<Cfg> {
                     remote_address,
                     server_name: hostname,
                     deduplicate,
+                    token,
+                    limits: limit_overrides,
+                    ..
                 },
             sender,
         } = request;
@@ -988,6 +1098,11 @@ impl<Cfg: Config> Endpoint<Cfg> {
                     remote_address,
                     server_name: hostname.clone(),
                     deduplicate,
+                    additional_addresses: Vec::new(),
+                    attempt_delay: endpoint::connect::DEFAULT_ATTEMPT_DELAY,
+                    host: None,
+                    token: token.clone(),
+                    limits: limit_overrides,
                 },
             ) {
                 Ok(existing) => {
@@ -1022,6 +1137,20 @@ impl<Cfg: Config> Endpoint<Cfg> {
             .connection_id_format
             .generate(&ConnectionInfo::new(&remote_address));
 
+        // Packets sent to a zero-length connection ID can't be routed to more than one
+        // connection, so only a single connection may use it at a time.
+        if local_connection_id.is_empty()
+            && self
+                .connection_id_mapper
+                .is_local_id_registered(&local_connection_id)
+        {
+            let error = connection::Error::invalid_configuration(
+                "zero-length connection IDs only support a single connection per endpoint",
+            );
+            let _ = sender.send(Err(error));
+            return Err(error);
+        }
+
         let local_connection_id_expiration_time = self
             .config
             .context()
@@ -1108,6 +1237,7 @@ impl<Cfg: Config> Endpoint<Cfg> {
         let limits = endpoint_context
             .connection_limits
             .on_connection(&LimitsInfo::new(&remote_address));
+        let limits = limit_overrides.apply(limits);
 
         let mut endpoint_publisher = event::EndpointPublisherSubscriber::new(
             event::builder::EndpointMeta {
@@ -1118,7 +1248,7 @@ impl<Cfg: Config> Endpoint<Cfg> {
             endpoint_context.event_subscriber,
         );
 
-        let mtu_config = endpoint_context
+        let mut mtu_config = endpoint_context
             .mtu
             .config(&remote_address)
             .map_err(|_err| {
@@ -1131,6 +1261,30 @@ impl<Cfg: Config> Endpoint<Cfg> {
                 error
             })?;
 
+        // the connection would be unable to exchange any data
+        if limits.datagram_only()
+            && endpoint_context
+                .datagram
+                .max_datagram_frame_size(&PreConnectionInfo::new())
+                == 0
+        {
+            let error = connection::Error::invalid_configuration(
+                "datagram-only connections require a datagram provider",
+            );
+            endpoint_publisher.on_endpoint_connection_attempt_failed(
+                event::builder::EndpointConnectionAttemptFailed { error },
+            );
+            return Err(error);
+        }
+
+        let cached_path = endpoint_context
+            .path_cache
+            .get(&path::cache::PathInfo::new(&remote_address));
+
+        if let Some(entry) = cached_path {
+            mtu_config = mtu_config.with_cached_mtu(entry.mtu);
+        }
+
         let mut publisher = event::ConnectionPublisherSubscriber::new(
             meta,
             quic_version,
@@ -1139,7 +1293,8 @@ impl<Cfg: Config> Endpoint<Cfg> {
         );
 
         let congestion_controller = {
-            let path_info = congestion_controller::PathInfo::new(&mtu_config, &remote_address);
+            let mut path_info = congestion_controller::PathInfo::new(&mtu_config, &remote_address);
+            path_info.cached_path = cached_path;
             endpoint_context
                 .congestion_controller
                 .new_congestion_controller(path_info)
@@ -1183,15 +1338,24 @@ impl<Cfg: Config> Endpoint<Cfg> {
                 &transport_parameters,
                 hostname.expect("application should provide a valid server name"),
             );
-        let space_manager = PacketSpaceManager::new(
+        let mut space_manager = PacketSpaceManager::new(
             original_destination_connection_id,
             tls_session,
             initial_key,
             initial_header_key,
+            endpoint_context.compatibility.clone(),
+            InitialPacketNumbers::new(
+                limits.max_initial_packet_number_offset(),
+                endpoint_context.random_generator,
+            ),
             timestamp,
             &mut publisher,
         );
 
+        if let Some((initial, _)) = space_manager.initial_mut() {
+            initial.set_token(token);
+        }
+
         let wakeup_handle = self
             .wakeup_queue
             .create_wakeup_handle(internal_connection_id);
@@ -1215,6 +1379,8 @@ impl<Cfg: Config> Endpoint<Cfg> {
             quic_version,
             limits,
             mtu_config,
+            path_cache: endpoint_context.path_cache.clone(),
+            cached_path,
             event_context,
             supervisor_context: &supervisor_context,
             event_subscriber: endpoint_context.event_subscriber,
@@ -1245,6 +1411,8 @@ pub mod testing {
         type Connection = connection::Implementation<Self>;
         type ConnectionLock = std::sync::Mutex<Self::Connection>;
         type EndpointLimits = Limits;
+        type AcceptFilter = endpoint::accept_filter::AllowAll;
+        type AddressAttestation = endpoint::address_attestation::Disabled;
         type ConnectionIdFormat = connection::id::testing::Format;
         type StatelessResetTokenGenerator = stateless_reset::token::testing::Generator;
         type RandomGenerator = random::testing::Generator;
@@ -1258,6 +1426,8 @@ pub mod testing {
         type PacketInterceptor = s2n_quic_core::packet::interceptor::Disabled;
         type DatagramEndpoint = s2n_quic_core::datagram::Disabled;
         type DcEndpoint = s2n_quic_core::dc::testing::MockDcEndpoint;
+        type CompatibilityPolicy = s2n_quic_core::connection::compatibility::Strict;
+        type PathCache = path::cache::Disabled;
 
         fn context(&mut self) -> super::Context<Self> {
             todo!()
@@ -1277,6 +1447,8 @@ pub mod testing {
         type Connection = connection::Implementation<Self>;
         type ConnectionLock = std::sync::Mutex<Self::Connection>;
         type EndpointLimits = Limits;
+        type AcceptFilter = endpoint::accept_filter::AllowAll;
+        type AddressAttestation = endpoint::address_attestation::Disabled;
         type ConnectionIdFormat = connection::id::testing::Format;
         type StatelessResetTokenGenerator = stateless_reset::token::testing::Generator;
         type RandomGenerator = random::testing::Generator;
@@ -1290,6 +1462,8 @@ pub mod testing {
         type PacketInterceptor = s2n_quic_core::packet::interceptor::Disabled;
         type DatagramEndpoint = s2n_quic_core::datagram::Disabled;
         type DcEndpoint = s2n_quic_core::dc::testing::MockDcEndpoint;
+        type CompatibilityPolicy = s2n_quic_core::connection::compatibility::Strict;
+        type PathCache = path::cache::Disabled;
 
         fn context(&mut self) -> super::Context<Self> {
             todo!()
@@ -0,0 +1,231 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Closes connection attempts which were rejected before any connection state was allocated
+
+use crate::endpoint;
+use alloc::collections::VecDeque;
+use s2n_codec::{encoder::scatter, Encoder, EncoderBuffer, EncoderValue};
+use s2n_quic_core::{
+    crypto::{InitialHeaderKey, InitialKey},
+    event,
+    frame::{ConnectionClose, Padding},
+    inet::ExplicitCongestionNotification,
+    io::tx,
+    packet::{
+        encoding::{PacketEncoder, PacketPayloadEncoder},
+        initial::Initial,
+        number::PacketNumberSpace,
+    },
+    path::{self, MINIMUM_MAX_DATAGRAM_SIZE},
+    time,
+    varint::VarInt,
+};
+
+#[derive(Debug)]
+pub struct Dispatch<Path: path::Handle> {
+    transmissions: VecDeque<Transmission<Path>>,
+}
+
+impl<Path: path::Handle> Default for Dispatch<Path> {
+    fn default() -> Self {
+        Self::new(endpoint::DEFAULT_MAX_PEERS)
+    }
+}
+
+impl<Path: path::Handle> Dispatch<Path> {
+    pub fn new(max_peers: usize) -> Self {
+        Self {
+            transmissions: VecDeque::with_capacity(max_peers),
+        }
+    }
+
+    /// Queues an Initial packet which closes the connection attempt with the provided frame
+    ///
+    /// `key` and `header_key` are the server's Initial keys derived from the destination
+    /// connection ID of the client's Initial packet.
+    #[allow(clippy::too_many_arguments)]
+    pub fn queue<K: InitialKey, H: InitialHeaderKey>(
+        &mut self,
+        path: Path,
+        version: u32,
+        destination_connection_id: &[u8],
+        source_connection_id: &[u8],
+        connection_close: &ConnectionClose,
+        key: &mut K,
+        header_key: &H,
+    ) {
+        if let Some(transmission) = Transmission::new(
+            path,
+            version,
+            destination_connection_id,
+            source_connection_id,
+            connection_close,
+            key,
+            header_key,
+        ) {
+            self.transmissions.push_back(transmission);
+        }
+    }
+
+    pub fn on_transmit<Tx: tx::Queue<Handle = Path>, Pub: event::EndpointPublisher>(
+        &mut self,
+        queue: &mut Tx,
+        publisher: &mut Pub,
+    ) {
+        while let Some(transmission) = self.transmissions.pop_front() {
+            match queue.push(&transmission) {
+                Ok(tx::Outcome { len, .. }) => {
+                    publisher.on_endpoint_packet_sent(event::builder::EndpointPacketSent {
+                        packet_header: event::builder::PacketHeader::Initial {
+                            number: 0,
+                            version: transmission.version,
+                        },
+                    });
+
+                    publisher.on_endpoint_datagram_sent(event::builder::EndpointDatagramSent {
+                        len: len as u16,
+                        gso_offset: 0,
+                    });
+                }
+                Err(_) => {
+                    self.transmissions.push_front(transmission);
+                    return;
+                }
+            }
+        }
+    }
+}
+
+pub struct Transmission<Path: path::Handle> {
+    path: Path,
+    packet: [u8; MINIMUM_MAX_DATAGRAM_SIZE as usize],
+    packet_len: usize,
+    version: u32,
+}
+
+impl<Path: path::Handle> core::fmt::Debug for Transmission<Path> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Transmission")
+            .field("remote_address", &self.path.remote_address())
+            .field("local_address", &self.path.local_address())
+            .field("packet", &&self.packet[..self.packet_len])
+            .finish()
+    }
+}
+
+impl<Path: path::Handle> Transmission<Path> {
+    pub fn new<K: InitialKey, H: InitialHeaderKey>(
+        path: Path,
+        version: u32,
+        destination_connection_id: &[u8],
+        source_connection_id: &[u8],
+        connection_close: &ConnectionClose,
+        key: &mut K,
+        header_key: &H,
+    ) -> Option<Self> {
+        let mut packet_buf = [0u8; MINIMUM_MAX_DATAGRAM_SIZE as usize];
+
+        // this is the only packet sent for the attempt so it always uses the first packet number
+        let packet_number = PacketNumberSpace::Initial.new_packet_number(VarInt::from_u8(0));
+
+        let packet = Initial {
+            version,
+            destination_connection_id,
+            source_connection_id,
+            token: &[0u8; 0][..],
+            packet_number,
+            payload: Payload { connection_close },
+        };
+
+        let (_protected_packet, buffer) = packet
+            .encode_packet(
+                key,
+                header_key,
+                packet_number,
+                None,
+                EncoderBuffer::new(&mut packet_buf),
+            )
+            .ok()?;
+        let packet_len = buffer.len();
+
+        Some(Self {
+            path,
+            packet: packet_buf,
+            packet_len,
+            version,
+        })
+    }
+}
+
+/// Writes the CONNECTION_CLOSE frame, padded to the length required by the packet
+struct Payload<'a> {
+    connection_close: &'a ConnectionClose<'a>,
+}
+
+impl PacketPayloadEncoder for Payload<'_> {
+    #[inline]
+    fn encoding_size_hint<E: Encoder>(&mut self, _encoder: &E, minimum_len: usize) -> usize {
+        self.connection_close.encoding_size().max(minimum_len)
+    }
+
+    #[inline]
+    fn encode(
+        &mut self,
+        buffer: &mut scatter::Buffer,
+        minimum_len: usize,
+        _header_len: usize,
+        _tag_len: usize,
+    ) {
+        buffer.encode(self.connection_close);
+
+        let length = minimum_len.saturating_sub(self.connection_close.encoding_size());
+        if length > 0 {
+            buffer.encode(&Padding { length });
+        }
+    }
+}
+
+impl<Path: path::Handle> AsRef<[u8]> for Transmission<Path> {
+    fn as_ref(&self) -> &[u8] {
+        &self.packet[..self.packet_len]
+    }
+}
+
+impl<Path: path::Handle> tx::Message for &Transmission<Path> {
+    type Handle = Path;
+
+    #[inline]
+    fn path_handle(&self) -> &Self::Handle {
+        &self.path
+    }
+
+    #[inline]
+    fn ecn(&mut self) -> ExplicitCongestionNotification {
+        Default::default()
+    }
+
+    #[inline]
+    fn delay(&mut self) -> time::Duration {
+        Default::default()
+    }
+
+    #[inline]
+    fn ipv6_flow_label(&mut self) -> u32 {
+        0
+    }
+
+    #[inline]
+    fn can_gso(&self, segment_len: usize, _segment_count: usize) -> bool {
+        segment_len >= self.as_ref().len()
+    }
+
+    #[inline]
+    fn write_payload(
+        &mut self,
+        mut buffer: tx::PayloadBuffer,
+        _gso_offset: usize,
+    ) -> Result<usize, tx::Error> {
+        buffer.write(self.as_ref())
+    }
+}
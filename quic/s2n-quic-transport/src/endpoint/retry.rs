@@ -35,10 +35,10 @@ impl<Path: path::Handle> Dispatch<Path> {
         }
     }
 
-    pub fn queue<T: token::Format, C: RetryKey>(
+    pub fn queue<'a, T: token::Format, C: RetryKey>(
         &mut self,
         path_handle: Path,
-        packet: &packet::initial::ProtectedInitial,
+        packet: impl Into<packet::retry::InitialHeader<'a>>,
         local_connection_id: connection::LocalId,
         random: &mut dyn random::Generator,
         token_format: &mut T,
@@ -100,13 +100,14 @@ impl<Path: path::Handle> core::fmt::Debug for Transmission<Path> {
 }
 
 impl<Path: path::Handle> Transmission<Path> {
-    pub fn new<T: token::Format, C: RetryKey>(
+    pub fn new<'a, T: token::Format, C: RetryKey>(
         path: Path,
-        packet: &packet::initial::ProtectedInitial,
+        packet: impl Into<packet::retry::InitialHeader<'a>>,
         local_connection_id: connection::LocalId,
         random: &mut dyn random::Generator,
         token_format: &mut T,
     ) -> Option<Self> {
+        let packet = packet.into();
         let mut packet_buf = [0u8; MINIMUM_MAX_DATAGRAM_SIZE as usize];
         let packet_range = packet::retry::Retry::encode_packet::<_, C>(
             &path.remote_address(),
@@ -4,6 +4,7 @@
 //! This module contains all main runtime components for receiving and sending
 //! data via the QUIC protocol.
 
+#![cfg_attr(not(any(test, feature = "std")), no_std)]
 #![deny(unused_must_use)]
 extern crate alloc;
 
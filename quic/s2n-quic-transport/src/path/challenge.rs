@@ -204,6 +204,9 @@ pub mod testing {
     }
 }
 
+#[cfg(test)]
+mod fuzz_target;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,228 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::{contexts::testing::*, transmission::interest::Provider as _};
+use bolero::{check, generator::*};
+use s2n_quic_core::{endpoint, event::testing::Publisher, time::clock::testing as time};
+
+const ABANDON_DURATION: Duration = Duration::from_millis(1000);
+const DATA: Data = [1; frame::path_challenge::DATA_LEN];
+
+#[derive(Debug)]
+struct Oracle {
+    now: Timestamp,
+    /// The number of PATH_CHALLENGE frames that can still be sent
+    remaining: u8,
+    /// Set once all of the PATH_CHALLENGE frames have been sent
+    awaiting_response: bool,
+    /// When the challenge is abandoned, which is armed on the first transmission
+    abandon_time: Option<Timestamp>,
+    validated: bool,
+    abandoned: bool,
+    transmissions: usize,
+}
+
+impl Oracle {
+    fn new() -> Self {
+        Self {
+            now: time::now(),
+            remaining: 2,
+            awaiting_response: false,
+            abandon_time: None,
+            validated: false,
+            abandoned: false,
+            transmissions: 0,
+        }
+    }
+
+    fn is_pending(&self) -> bool {
+        !self.validated && !self.abandoned
+    }
+
+    fn requires_transmission(&self) -> bool {
+        self.is_pending() && !self.awaiting_response
+    }
+}
+
+#[derive(Debug, TypeGenerator)]
+enum Operation {
+    /// Transmits a packet, which may not have space for a PATH_CHALLENGE frame
+    Transmit { fits: bool },
+    /// Advances the clock and fires any expired timers
+    Advance { millis: u16 },
+    /// The path is abandoned, e.g. because the connection migrated to another path
+    Abandon,
+    /// A PATH_RESPONSE frame was received
+    Response { matches: bool },
+}
+
+struct Model {
+    subject: Challenge,
+    oracle: Oracle,
+}
+
+impl Model {
+    fn new() -> Self {
+        Self {
+            subject: Challenge::new(ABANDON_DURATION, DATA),
+            oracle: Oracle::new(),
+        }
+    }
+
+    fn apply(&mut self, operation: &Operation) {
+        let prev = self.subject.state.clone();
+
+        match operation {
+            Operation::Transmit { fits } => self.on_transmit(*fits),
+            Operation::Advance { millis } => self.on_advance(*millis),
+            Operation::Abandon => self.on_abandon(),
+            Operation::Response { matches } => self.on_response(*matches),
+        }
+
+        // the validated and abandoned states are terminal
+        if matches!(prev, State::Validated | State::Abandoned) {
+            assert_eq!(prev, self.subject.state);
+        }
+
+        self.invariants();
+    }
+
+    fn on_transmit(&mut self, fits: bool) {
+        let mut frame_buffer = OutgoingFrameBuffer::new();
+        if !fits {
+            frame_buffer.set_max_packet_size(Some(1));
+        }
+        let mut context = MockWriteContext::new(
+            self.oracle.now,
+            &mut frame_buffer,
+            transmission::Constraint::None,
+            transmission::Mode::Normal,
+            endpoint::Type::Client,
+        );
+        self.subject.on_transmit(&mut context);
+
+        let mut written = 0;
+        while let Some(mut frame) = frame_buffer.pop_front() {
+            match frame.as_frame() {
+                frame::Frame::PathChallenge(frame) => assert_eq!(*frame.data, DATA),
+                frame => panic!("unexpected frame {frame:?}"),
+            }
+            written += 1;
+        }
+
+        if !self.oracle.requires_transmission() {
+            assert_eq!(written, 0);
+        } else if self.oracle.remaining == 0 {
+            assert_eq!(written, 0);
+            self.oracle.awaiting_response = true;
+        } else if fits {
+            //= https://www.rfc-editor.org/rfc/rfc9000#section-8.2.1
+            //= type=test
+            //# However, an endpoint SHOULD NOT send multiple
+            //# PATH_CHALLENGE frames in a single packet.
+            assert_eq!(written, 1);
+            self.oracle.remaining -= 1;
+            self.oracle.transmissions += 1;
+            let abandon_time = self.oracle.now + ABANDON_DURATION;
+            self.oracle.abandon_time.get_or_insert(abandon_time);
+        } else {
+            assert_eq!(written, 0);
+        }
+    }
+
+    fn on_advance(&mut self, millis: u16) {
+        self.oracle.now += Duration::from_millis(millis as u64);
+        self.subject.on_timeout(
+            self.oracle.now,
+            &mut Publisher::no_snapshot(),
+            event::builder::Path::test(),
+        );
+
+        let expired = self
+            .oracle
+            .abandon_time
+            .map_or(false, |time| time.has_elapsed(self.oracle.now));
+        if self.oracle.is_pending() && expired {
+            self.oracle.abandoned = true;
+        }
+    }
+
+    fn on_abandon(&mut self) {
+        self.subject
+            .abandon(&mut Publisher::no_snapshot(), event::builder::Path::test());
+
+        if self.oracle.is_pending() {
+            self.oracle.abandoned = true;
+        }
+    }
+
+    fn on_response(&mut self, matches: bool) {
+        let data = if matches {
+            DATA
+        } else {
+            [2; frame::path_challenge::DATA_LEN]
+        };
+        let validated = self.subject.on_validated(&data);
+
+        // only a pending challenge can be validated, and only with the matching data
+        assert_eq!(validated, self.oracle.is_pending() && matches);
+
+        if validated {
+            self.oracle.validated = true;
+        }
+    }
+
+    fn invariants(&self) {
+        let oracle = &self.oracle;
+
+        assert!(!(oracle.validated && oracle.abandoned));
+        assert!(!self.subject.is_disabled());
+        assert_eq!(self.subject.is_pending(), oracle.is_pending());
+        assert_eq!(
+            matches!(self.subject.state, State::Validated),
+            oracle.validated
+        );
+        assert_eq!(
+            matches!(self.subject.state, State::Abandoned),
+            oracle.abandoned
+        );
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-8.2.1
+        //= type=test
+        //# An endpoint SHOULD NOT probe a new path with packets containing a
+        //# PATH_CHALLENGE frame more frequently than it would send an Initial
+        //# packet.
+        assert!(oracle.transmissions <= 2);
+
+        assert_eq!(
+            self.subject.has_transmission_interest(),
+            oracle.requires_transmission()
+        );
+
+        // a pending challenge can only be abandoned once it has been transmitted
+        if oracle.is_pending() {
+            assert_eq!(
+                self.subject.abandon_timer.is_armed(),
+                oracle.abandon_time.is_some()
+            );
+        }
+
+        if oracle.abandoned {
+            assert!(!self.subject.abandon_timer.is_armed());
+        }
+    }
+}
+
+#[test]
+fn challenge_fuzz() {
+    check!()
+        .with_type::<Vec<Operation>>()
+        .for_each(|operations| {
+            let mut model = Model::new();
+
+            for operation in operations.iter() {
+                model.apply(operation);
+            }
+        });
+}
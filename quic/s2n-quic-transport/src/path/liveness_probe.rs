@@ -0,0 +1,153 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use s2n_quic_core::{
+    path::probe::{Liveness, Policy},
+    time::{timer, Timer, Timestamp},
+};
+
+/// Tracks the liveness probes sent on a path while it is idle
+#[derive(Clone, Debug, Default)]
+pub struct Prober {
+    /// Expires when the next probe should be sent
+    timer: Timer,
+    /// The number of probes sent since a packet was last received on the path
+    probes: u32,
+    liveness: Liveness,
+    /// True while the path challenge is a liveness probe awaiting its response
+    is_probing: bool,
+}
+
+impl Prober {
+    #[inline]
+    pub fn liveness(&self) -> Liveness {
+        self.liveness
+    }
+
+    /// Called when a packet was received and processed on the path
+    ///
+    /// The path is no longer idle, so the probe interval is reset. Any outstanding probe is no
+    /// longer needed to prove the path is alive.
+    #[inline]
+    pub fn on_activity(&mut self, timestamp: Timestamp, policy: &Policy) {
+        self.is_probing = false;
+        self.probes = 0;
+        self.liveness = Liveness::Responsive;
+        self.timer.set(timestamp + policy.interval(0));
+    }
+
+    /// Returns true if the probe interval expired and a probe should be sent
+    ///
+    /// The timer is re-armed with the backed off interval, regardless of whether the probe is
+    /// sent.
+    #[inline]
+    pub fn poll_probe(&mut self, timestamp: Timestamp, policy: &Policy) -> bool {
+        if self.timer.poll_expiration(timestamp).is_pending() {
+            return false;
+        }
+
+        self.probes = self.probes.saturating_add(1);
+        self.timer.set(timestamp + policy.interval(self.probes));
+        true
+    }
+
+    /// Called when a probe was sent on the path
+    #[inline]
+    pub fn on_probe_sent(&mut self) {
+        self.is_probing = true;
+    }
+
+    /// Called when the path challenge was answered
+    #[inline]
+    pub fn on_response(&mut self) {
+        if core::mem::take(&mut self.is_probing) {
+            self.liveness = Liveness::Responsive;
+        }
+    }
+
+    /// Called when the path challenge is no longer pending
+    ///
+    /// Returns true if the path became unresponsive, because the probe wasn't answered in time.
+    #[inline]
+    pub fn on_challenge_expired(&mut self) -> bool {
+        if !core::mem::take(&mut self.is_probing) {
+            return false;
+        }
+
+        let was_unresponsive = self.liveness.is_unresponsive();
+        self.liveness = Liveness::Unresponsive;
+        !was_unresponsive
+    }
+
+    /// Stops probing the path, for example when its challenge was abandoned after a migration
+    #[inline]
+    pub fn cancel(&mut self) {
+        self.timer.cancel();
+        self.is_probing = false;
+    }
+}
+
+impl timer::Provider for Prober {
+    #[inline]
+    fn timers<Q: timer::Query>(&self, query: &mut Q) -> timer::Result {
+        self.timer.timers(query)?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::time::Duration;
+    use s2n_quic_core::time::{Clock, NoopClock};
+
+    #[test]
+    fn prober_test() {
+        let policy = Policy::new(Duration::from_millis(100))
+            .with_backoff(2)
+            .with_max_interval(Duration::from_millis(300));
+        let now = NoopClock.get_time();
+        let mut prober = Prober::default();
+
+        // the prober isn't armed until the path is active
+        assert_eq!(prober.liveness(), Liveness::Unknown);
+        assert!(!prober.poll_probe(now + Duration::from_secs(10), &policy));
+
+        prober.on_activity(now, &policy);
+        assert!(prober.liveness().is_responsive());
+        assert!(!prober.poll_probe(now + Duration::from_millis(99), &policy));
+
+        // the interval is backed off while the path stays idle
+        let now = now + Duration::from_millis(100);
+        assert!(prober.poll_probe(now, &policy));
+        prober.on_probe_sent();
+        assert!(!prober.poll_probe(now + Duration::from_millis(199), &policy));
+        let now = now + Duration::from_millis(200);
+        assert!(prober.poll_probe(now, &policy));
+        assert!(!prober.poll_probe(now + Duration::from_millis(299), &policy));
+
+        // an unanswered probe marks the path as unresponsive once
+        assert!(prober.on_challenge_expired());
+        assert!(prober.liveness().is_unresponsive());
+        prober.on_probe_sent();
+        assert!(!prober.on_challenge_expired());
+
+        // a response only counts while probing
+        prober.on_response();
+        assert!(prober.liveness().is_unresponsive());
+        prober.on_probe_sent();
+        prober.on_response();
+        assert!(prober.liveness().is_responsive());
+
+        // activity resets the interval and answers the outstanding probe
+        prober.on_probe_sent();
+        prober.on_activity(now, &policy);
+        assert!(!prober.on_challenge_expired());
+        assert!(prober.liveness().is_responsive());
+        assert!(prober.poll_probe(now + Duration::from_millis(100), &policy));
+
+        prober.cancel();
+        assert!(!prober.poll_probe(now + Duration::from_secs(10), &policy));
+    }
+}
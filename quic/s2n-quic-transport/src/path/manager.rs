@@ -11,7 +11,7 @@ use crate::{
 };
 use s2n_quic_core::{
     ack,
-    connection::{self, Limits, PeerId},
+    connection::{self, id::RotationPolicy, Limits, PeerId},
     ensure,
     event::{
         self,
@@ -24,12 +24,12 @@ use s2n_quic_core::{
     packet::number::PacketNumberSpace,
     path::{
         migration::{self, Validator as _},
-        mtu, Handle as _, Id,
+        mtu, probe, Handle as _, Id,
     },
     random,
     recovery::congestion_controller::{self, Endpoint as _},
     stateless_reset,
-    time::{timer, Timestamp},
+    time::{timer, Duration, Timestamp},
     transport,
 };
 use smallvec::SmallVec;
@@ -70,6 +70,21 @@ pub struct Manager<Config: endpoint::Config> {
     /// The `paths` data structure will need to be enhanced to include garbage collection
     /// of old paths to overcome this limitation.
     pending_packet_authentication: Option<u8>,
+
+    /// The policy for probing idle paths for liveness, if enabled
+    probe_policy: Option<probe::Policy>,
+
+    /// The policy for rotating the peer connection ID used on the active path
+    rotation_policy: RotationPolicy,
+
+    /// The number of packets sent on the active path since its connection ID was last rotated
+    packets_since_rotation: u64,
+
+    /// Set when the application requested the connection ID of the active path to be rotated
+    rotation_requested: bool,
+
+    /// The `max_udp_payload_size` transport parameter of the peer, once it's known
+    peer_max_udp_payload_size: Option<u16>,
 }
 
 impl<Config: endpoint::Config> Manager<Config> {
@@ -80,12 +95,97 @@ impl<Config: endpoint::Config> Manager<Config> {
             active: 0,
             last_known_active_validated_path: None,
             pending_packet_authentication: None,
+            probe_policy: None,
+            rotation_policy: RotationPolicy::OnMigration,
+            packets_since_rotation: 0,
+            rotation_requested: false,
+            peer_max_udp_payload_size: None,
         };
         manager.paths[0].activated = true;
         manager.paths[0].is_active = true;
         manager
     }
 
+    /// Enables probing idle paths for liveness with the given policy
+    #[inline]
+    pub fn set_probe_policy(&mut self, policy: Option<probe::Policy>) {
+        self.probe_policy = policy;
+    }
+
+    /// Sets the policy for rotating the peer connection ID used on the active path
+    #[inline]
+    pub fn set_rotation_policy(&mut self, policy: RotationPolicy) {
+        self.rotation_policy = policy;
+    }
+
+    /// Limits the datagrams sent on every path to the peer's `max_udp_payload_size`
+    #[inline]
+    pub fn on_peer_max_udp_payload_size(&mut self, max_udp_payload_size: u64) {
+        let max_udp_payload_size = max_udp_payload_size.try_into().unwrap_or(u16::MAX);
+        self.peer_max_udp_payload_size = Some(max_udp_payload_size);
+
+        for path in self.paths.iter_mut() {
+            path.mtu_controller
+                .on_peer_max_udp_payload_size(max_udp_payload_size);
+        }
+    }
+
+    /// Requests the peer connection ID used on the active path to be rotated
+    ///
+    /// The rotation happens the next time `poll_connection_id_rotation` is called. Returns
+    /// `false` if the peer hasn't provided a connection ID to rotate to.
+    pub fn request_connection_id_rotation(&mut self) -> bool {
+        if !self.peer_id_registry.has_new_id() {
+            return false;
+        }
+
+        self.rotation_requested = true;
+        true
+    }
+
+    /// Called when packets were sent on the active path
+    #[inline]
+    pub fn on_active_path_packets_sent(&mut self, count: usize) {
+        self.packets_since_rotation = self.packets_since_rotation.saturating_add(count as u64);
+    }
+
+    /// Rotates the peer connection ID used on the active path if the application requested it
+    /// or the rotation policy requires it
+    pub fn poll_connection_id_rotation<Pub: event::ConnectionPublisher>(
+        &mut self,
+        publisher: &mut Pub,
+    ) {
+        if !self.rotation_requested
+            && !self
+                .rotation_policy
+                .should_rotate(self.packets_since_rotation)
+        {
+            return;
+        }
+
+        let path_id = self.active_path_id();
+        let current_id = self.active_path().peer_connection_id;
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-5.1.2
+        //# An endpoint can change the connection ID it uses for a peer to
+        //# another available one at any time during the connection.
+        let retire_current = self.paths.iter().enumerate().all(|(idx, path)| {
+            idx == self.active as usize || path.peer_connection_id != current_id
+        });
+
+        // If no connection IDs are available, try again once the peer provides more
+        if let Some(new_id) = self.peer_id_registry.rotate_id_for_existing_path(
+            path_id,
+            current_id,
+            retire_current,
+            publisher,
+        ) {
+            self.active_path_mut().peer_connection_id = new_id;
+            self.packets_since_rotation = 0;
+            self.rotation_requested = false;
+        }
+    }
+
     /// Update the active path
     fn update_active_path<Pub: event::ConnectionPublisher>(
         &mut self,
@@ -159,6 +259,12 @@ impl<Config: endpoint::Config> Manager<Config> {
         path_id(self.active)
     }
 
+    /// Returns the recovery statistics for each path
+    #[inline]
+    pub fn stats(&self) -> impl Iterator<Item = path::Stats> + '_ {
+        self.paths.iter().map(Path::stats)
+    }
+
     pub fn check_active_path_is_synced(&self) {
         if cfg!(debug_assertions) {
             for (idx, path) in self.paths.iter().enumerate() {
@@ -175,6 +281,7 @@ impl<Config: endpoint::Config> Manager<Config> {
     ) -> AmplificationOutcome {
         self.check_active_path_is_synced();
         self.active = new_path_id.as_u8();
+        self.packets_since_rotation = 0;
         self[prev_path_id].is_active = false;
         self[new_path_id].is_active = true;
         self[new_path_id].on_activated();
@@ -427,7 +534,14 @@ impl<Config: endpoint::Config> Manager<Config> {
         let cc = congestion_controller_endpoint.new_congestion_controller(path_info);
 
         let peer_connection_id = {
-            if active_migration {
+            if self.active_path().peer_connection_id.is_empty() {
+                //= https://www.rfc-editor.org/rfc/rfc9000#section-5.1.1
+                //# An endpoint that selects a zero-length connection ID during the
+                //# handshake cannot issue a new connection ID.  A zero-length
+                //# Destination Connection ID field is used in all packets sent toward
+                //# such an endpoint over any network path.
+                self.active_path().peer_connection_id
+            } else if active_migration {
                 //= https://www.rfc-editor.org/rfc/rfc9000#section-9.5
                 //# Similarly, an endpoint MUST NOT reuse a connection ID when sending to
                 //# more than one destination address.
@@ -469,6 +583,11 @@ impl<Config: endpoint::Config> Manager<Config> {
             limits.anti_amplification_multiplier(),
         );
 
+        if let Some(max_udp_payload_size) = self.peer_max_udp_payload_size {
+            path.mtu_controller
+                .on_peer_max_udp_payload_size(max_udp_payload_size);
+        }
+
         let amplification_outcome = path.on_bytes_received(datagram.payload_len);
 
         let active_path = self.active_path();
@@ -530,6 +649,49 @@ impl<Config: endpoint::Config> Manager<Config> {
         self[path_id].set_challenge(challenge);
     }
 
+    /// Sends a PATH_CHALLENGE to probe the liveness of an already validated path
+    fn set_probe(
+        &mut self,
+        path_id: Id,
+        timeout: Option<Duration>,
+        random_generator: &mut dyn random::Generator,
+    ) {
+        let mut data: challenge::Data = [0; 8];
+        random_generator.public_random_fill(&mut data);
+
+        let abandon_duration = timeout
+            .unwrap_or_else(|| 3 * self[path_id].pto_period(PacketNumberSpace::ApplicationData));
+
+        let challenge = challenge::Challenge::new(abandon_duration, data);
+        let path = &mut self[path_id];
+        path.set_challenge(challenge);
+        path.prober.on_probe_sent();
+    }
+
+    /// Called when a packet was received and processed on the given path
+    ///
+    /// Resets the probe interval of the path, since it is no longer idle.
+    #[inline]
+    pub fn on_path_activity(&mut self, path_id: Id, timestamp: Timestamp) {
+        if let Some(policy) = self.probe_policy {
+            self[path_id].prober.on_activity(timestamp, &policy);
+        }
+    }
+
+    /// Called when the application reports a change to the local network
+    ///
+    /// Rather than waiting for loss to be detected, the active path is probed immediately with
+    /// a PATH_CHALLENGE. If the local address changed, the peer observes the probe arriving
+    /// from the new address and validates it.
+    pub fn on_network_change(&mut self, random_generator: &mut dyn random::Generator) {
+        // the characteristics of the network may have changed so don't wait on a backed off PTO
+        self.active_path_mut().reset_pto_backoff();
+
+        if !self.active_path().is_challenge_pending() {
+            self.set_challenge(self.active_path_id(), random_generator);
+        }
+    }
+
     /// Returns true if a valid initial packet has been received
     pub fn valid_initial_received(&self) -> bool {
         if Config::ENDPOINT_TYPE.is_server() {
@@ -709,6 +871,8 @@ impl<Config: endpoint::Config> Manager<Config> {
     ) {
         for (idx, path) in self.paths.iter_mut().enumerate() {
             let path_id = idx as u64;
+            // abandoning a probe doesn't mean the path is unresponsive
+            path.prober.cancel();
             path.abandon_challenge(publisher, path_id);
         }
     }
@@ -779,6 +943,11 @@ impl<Config: endpoint::Config> Manager<Config> {
 
         let mut amplification_outcome = AmplificationOutcome::Unchanged;
 
+        if let Some(policy) = self.probe_policy {
+            amplification_outcome =
+                self.on_probe_timeout(timestamp, &policy, random_generator, publisher);
+        }
+
         if self.active_path().failed_validation() {
             match self.last_known_active_validated_path {
                 Some(last_known_active_validated_path) => {
@@ -820,6 +989,67 @@ impl<Config: endpoint::Config> Manager<Config> {
         Ok(amplification_outcome)
     }
 
+    /// Updates the liveness of the probed paths and sends probes on the paths which are idle
+    fn on_probe_timeout<Pub: event::ConnectionPublisher>(
+        &mut self,
+        timestamp: Timestamp,
+        policy: &probe::Policy,
+        random_generator: &mut dyn random::Generator,
+        publisher: &mut Pub,
+    ) -> AmplificationOutcome {
+        for index in 0..self.paths.len() {
+            let id = path_id(index as u8);
+            let path = &mut self.paths[index];
+
+            if !path.is_challenge_pending()
+                && path.prober.on_challenge_expired()
+                && path.is_active()
+            {
+                // the active path stopped responding so don't wait on a backed off PTO to
+                // detect the loss
+                path.reset_pto_backoff();
+            }
+
+            if !path.prober.poll_probe(timestamp, policy) {
+                continue;
+            }
+
+            let is_eligible = path.is_active() || policy.paths() == probe::Paths::Validated;
+
+            // paths which are still being validated are already sent a PATH_CHALLENGE
+            if is_eligible && path.is_validated() && !path.is_challenge_pending() {
+                self.set_probe(id, policy.timeout(), random_generator);
+            }
+        }
+
+        // Only fall back to the previous path if it is being probed, since its liveness is
+        // otherwise as old as the migration.
+        if policy.paths() != probe::Paths::Validated
+            || !self.active_path().prober.liveness().is_unresponsive()
+        {
+            return AmplificationOutcome::Unchanged;
+        }
+
+        let Some(last_known_active_validated_path) = self.last_known_active_validated_path else {
+            return AmplificationOutcome::Unchanged;
+        };
+
+        let prev_path_id = path_id(self.active);
+        let new_path_id = path_id(last_known_active_validated_path);
+
+        if prev_path_id == new_path_id
+            || !self[new_path_id].is_validated()
+            || !self[new_path_id].prober.liveness().is_responsive()
+        {
+            return AmplificationOutcome::Unchanged;
+        }
+
+        let amplification_outcome = self.activate_path(publisher, prev_path_id, new_path_id);
+        // the unresponsive path is still validated, so it can be fallen back to if it recovers
+        self.last_known_active_validated_path = Some(prev_path_id.as_u8());
+        amplification_outcome
+    }
+
     /// true if ALL paths are amplification_limited
     #[inline]
     pub fn is_amplification_limited(&self) -> bool {
@@ -10,6 +10,7 @@ use crate::{
         testing::{Client, Server},
     },
     path,
+    transmission::interest::Provider as _,
 };
 use core::time::Duration;
 use s2n_quic_core::{
@@ -1840,6 +1841,139 @@ fn last_known_validated_path_should_update_on_path_response() {
     assert_eq!(manager.last_known_active_validated_path, None);
 }
 
+#[test]
+fn probe_idle_path_for_liveness() {
+    let mut publisher = Publisher::no_snapshot();
+    let mut random_generator = random::testing::Generator(123);
+    let interval = Duration::from_millis(200);
+    let timeout = Duration::from_millis(100);
+    let policy = probe::Policy::new(interval).with_timeout(timeout);
+
+    let mut manager = manager_client(helper_client_path());
+    manager.set_probe_policy(Some(policy));
+    let id = manager.active_path_id();
+    assert!(manager.active_path().is_validated());
+    assert_eq!(
+        manager.active_path().stats().liveness,
+        probe::Liveness::Unknown
+    );
+
+    let now = NoopClock {}.get_time();
+    manager.on_path_activity(id, now);
+    let _ = manager
+        .on_timeout(
+            now + interval - Duration::from_millis(1),
+            &mut random_generator,
+            &mut publisher,
+        )
+        .unwrap();
+    assert!(!manager.active_path().is_challenge_pending());
+
+    // the path is idle for the probe interval
+    let now = now + interval;
+    let _ = manager
+        .on_timeout(now, &mut random_generator, &mut publisher)
+        .unwrap();
+    assert!(manager.active_path().is_challenge_pending());
+
+    let mut frame_buffer = OutgoingFrameBuffer::new();
+    let mut context = MockWriteContext::new(
+        now,
+        &mut frame_buffer,
+        transmission::Constraint::None,
+        transmission::Mode::Normal,
+        endpoint::Type::Client,
+    );
+    manager[id].on_transmit(&mut context);
+    assert_eq!(frame_buffer.len(), 1);
+
+    // the probe isn't answered
+    manager.active_path_mut().pto_backoff = 4;
+    let _ = manager
+        .on_timeout(now + timeout, &mut random_generator, &mut publisher)
+        .unwrap();
+    assert!(!manager.active_path().is_challenge_pending());
+    assert!(manager.active_path().is_validated());
+    assert_eq!(
+        manager.active_path().stats().liveness,
+        probe::Liveness::Unresponsive
+    );
+    assert_eq!(manager.active_path().pto_backoff, path::INITIAL_PTO_BACKOFF);
+
+    // the next probe is answered
+    let now = now + interval * 2;
+    let _ = manager
+        .on_timeout(now, &mut random_generator, &mut publisher)
+        .unwrap();
+    assert!(manager.active_path().is_challenge_pending());
+    let data: challenge::Data = manager
+        .active_path()
+        .challenge
+        .challenge_data()
+        .try_into()
+        .unwrap();
+    let frame = s2n_quic_core::frame::PathResponse { data: &data };
+    let _ = manager.on_path_response(&frame, &mut publisher);
+    assert_eq!(
+        manager.active_path().stats().liveness,
+        probe::Liveness::Responsive
+    );
+}
+
+#[test]
+fn rotate_active_path_connection_id() {
+    let mut publisher = Publisher::no_snapshot();
+    let id_1 = connection::PeerId::try_from_bytes(&[1]).unwrap();
+    let id_2 = connection::PeerId::try_from_bytes(&[2]).unwrap();
+    let id_3 = connection::PeerId::try_from_bytes(&[3]).unwrap();
+
+    let mut manager = manager_server(helper_path(id_1));
+    manager.set_rotation_policy(RotationPolicy::AfterPackets(10));
+
+    // the handshake connection ID is replaced as soon as a new one is available
+    manager
+        .on_new_connection_id(&id_2, 1, 0, &TEST_TOKEN_2, &mut publisher)
+        .unwrap();
+    assert_eq!(manager.active_path().peer_connection_id, id_2);
+    assert!(!manager.request_connection_id_rotation());
+
+    manager
+        .on_new_connection_id(&id_3, 2, 0, &TEST_TOKEN_3, &mut publisher)
+        .unwrap();
+
+    manager.on_active_path_packets_sent(9);
+    manager.poll_connection_id_rotation(&mut publisher);
+    assert_eq!(manager.active_path().peer_connection_id, id_2);
+
+    // the connection ID is rotated once the packet interval is reached
+    manager.on_active_path_packets_sent(1);
+    manager.poll_connection_id_rotation(&mut publisher);
+    assert_eq!(manager.active_path().peer_connection_id, id_3);
+    assert!(!manager.peer_id_registry.is_active(&id_2));
+    assert_eq!(
+        transmission::Interest::NewData,
+        manager.get_transmission_interest()
+    );
+
+    // the rotation is retried once the peer provides another connection ID
+    manager.on_active_path_packets_sent(10);
+    manager.poll_connection_id_rotation(&mut publisher);
+    assert_eq!(manager.active_path().peer_connection_id, id_3);
+}
+
+fn helper_client_path() -> ClientPath {
+    ClientPath::new(
+        Default::default(),
+        connection::PeerId::try_from_bytes(&[1]).unwrap(),
+        connection::LocalId::TEST_ID,
+        RttEstimator::new(Duration::from_millis(30)),
+        Default::default(),
+        true,
+        mtu::Config::default(),
+        ANTI_AMPLIFICATION_MULTIPLIER,
+    )
+}
+
 // creates a test path_manager. also check out `helper_manager_with_paths`
 // which calls this helper with preset options
 pub fn helper_manager_with_paths_base(
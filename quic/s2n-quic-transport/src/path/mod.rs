@@ -19,6 +19,7 @@ use s2n_quic_core::{
 };
 
 mod challenge;
+mod liveness_probe;
 mod manager;
 
 pub use challenge::Challenge;
@@ -58,6 +59,12 @@ pub struct Path<Config: endpoint::Config> {
     pub mtu_controller: mtu::Controller,
     /// Controller for determining the ECN capability of the path
     pub ecn_controller: ecn::Controller,
+    /// The number of packets sent on this path that were declared lost
+    pub lost_packets: u64,
+    /// The number of bytes sent on this path that were declared lost
+    pub lost_bytes: u64,
+    /// The cumulative ECN counts reported by the peer for packets sent on this path
+    pub ecn_counts: frame::ack::EcnCounts,
 
     /// True if the path has been validated by the peer
     peer_validated: bool,
@@ -68,6 +75,9 @@ pub struct Path<Config: endpoint::Config> {
     /// Received a Challenge and should echo back data in PATH_RESPONSE
     response_data: Option<challenge::Data>,
 
+    /// Probes the path for liveness while it is idle
+    prober: liveness_probe::Prober,
+
     /// True if the path is currently or at some point been an active path.
     ///
     /// A path becomes an active path if it receives a non-path-validation-probing
@@ -93,9 +103,13 @@ impl<Config: endpoint::Config> Clone for Path<Config> {
             state: self.state,
             mtu_controller: self.mtu_controller.clone(),
             ecn_controller: self.ecn_controller.clone(),
+            lost_packets: self.lost_packets,
+            lost_bytes: self.lost_bytes,
+            ecn_counts: self.ecn_counts,
             peer_validated: self.peer_validated,
             challenge: self.challenge.clone(),
             response_data: self.response_data,
+            prober: self.prober.clone(),
             activated: self.activated,
             is_active: self.is_active,
             anti_amplification_multiplier: self.anti_amplification_multiplier,
@@ -141,9 +155,13 @@ impl<Config: endpoint::Config> Path<Config> {
             state,
             mtu_controller: mtu::Controller::new(mtu_config, &peer_socket_address),
             ecn_controller: ecn::Controller::default(),
+            lost_packets: 0,
+            lost_bytes: 0,
+            ecn_counts: Default::default(),
             peer_validated,
             challenge: Challenge::disabled(),
             response_data: None,
+            prober: liveness_probe::Prober::default(),
             activated: false,
             is_active: false,
             anti_amplification_multiplier,
@@ -160,6 +178,26 @@ impl<Config: endpoint::Config> Path<Config> {
         self.handle.local_address()
     }
 
+    /// Returns a snapshot of the recovery statistics for the path
+    #[inline]
+    pub fn stats(&self) -> Stats {
+        let mut stats = Stats::default();
+        stats.local_address = *self.local_address();
+        stats.remote_address = *self.remote_address();
+        stats.is_active = self.is_active;
+        stats.smoothed_rtt = self.rtt_estimator.smoothed_rtt();
+        stats.rtt_variance = self.rtt_estimator.rttvar();
+        stats.min_rtt = self.rtt_estimator.min_rtt();
+        stats.latest_rtt = self.rtt_estimator.latest_rtt();
+        // the backoff doubles on every PTO so the count is its base-2 logarithm
+        stats.pto_count = self.pto_backoff.checked_ilog2().unwrap_or_default();
+        stats.lost_packets = self.lost_packets;
+        stats.lost_bytes = self.lost_bytes;
+        stats.ecn_counts = self.ecn_counts;
+        stats.liveness = self.prober.liveness();
+        stats
+    }
+
     #[inline]
     pub fn set_challenge(&mut self, challenge: Challenge) {
         self.challenge = challenge;
@@ -313,6 +351,7 @@ impl<Config: endpoint::Config> Path<Config> {
     #[inline]
     pub fn on_path_response(&mut self, response: &[u8]) -> bool {
         if self.challenge.on_validated(response) {
+            self.prober.on_response();
             self.on_validated();
 
             return true;
@@ -560,6 +599,7 @@ impl<Config: endpoint::Config> timer::Provider for Path<Config> {
     #[inline]
     fn timers<Q: timer::Query>(&self, query: &mut Q) -> timer::Result {
         self.challenge.timers(query)?;
+        self.prober.timers(query)?;
         self.mtu_controller.timers(query)?;
         self.ecn_controller.timers(query)?;
 
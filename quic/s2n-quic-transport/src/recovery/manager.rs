@@ -14,6 +14,7 @@ use s2n_quic_core::{
     frame,
     frame::ack::EcnCounts,
     inet::ExplicitCongestionNotification,
+    number::CheckedSub,
     packet::number::{PacketNumber, PacketNumberRange, PacketNumberSpace},
     recovery::{congestion_controller, persistent_congestion, CongestionController, Pto},
     time::{timer, timer::Provider, Timer, Timestamp},
@@ -641,6 +642,7 @@ impl<Config: endpoint::Config> Manager<Config> {
         let is_handshake_confirmed = context.is_handshake_confirmed();
         let mut current_path_acked_bytes = 0;
         let mut current_path_largest_newly_acked = None;
+        let mut largest_newly_acked_path_id: Option<(PacketNumber, path::Id)> = None;
         let mut newly_acked_ecn_counts = EcnCounts::default();
 
         for (packet_number, acked_packet_info) in newly_acked_packets {
@@ -649,6 +651,10 @@ impl<Config: endpoint::Config> Manager<Config> {
             let sent_bytes = acked_packet_info.sent_bytes as usize;
             newly_acked_ecn_counts.increment(acked_packet_info.ecn);
 
+            if largest_newly_acked_path_id.map_or(true, |(pn, _)| *packet_number > pn) {
+                largest_newly_acked_path_id = Some((*packet_number, acked_packet_info.path_id));
+            }
+
             if acked_packet_info.path_id == current_path_id {
                 current_path_acked_bytes += sent_bytes;
 
@@ -705,9 +711,12 @@ impl<Config: endpoint::Config> Manager<Config> {
         //# An endpoint MUST NOT fail ECN validation as a result of processing an
         //# ACK frame that does not increase the largest acknowledged packet number.
         if new_largest_packet {
+            let (_, largest_newly_acked_path_id) =
+                largest_newly_acked_path_id.expect("at least one packet was newly acked");
             self.process_ecn(
                 newly_acked_ecn_counts,
                 ecn_counts,
+                largest_newly_acked_path_id,
                 timestamp,
                 context,
                 publisher,
@@ -734,6 +743,7 @@ impl<Config: endpoint::Config> Manager<Config> {
         &mut self,
         newly_acked_ecn_counts: EcnCounts,
         ack_frame_ecn_counts: Option<EcnCounts>,
+        largest_newly_acked_path_id: path::Id,
         timestamp: Timestamp,
         context: &mut Ctx,
         publisher: &mut Pub,
@@ -773,6 +783,17 @@ impl<Config: endpoint::Config> Manager<Config> {
             })
         }
 
+        // The ECN counts in the ACK frame are cumulative for the packet number space rather than
+        // per path, so the increase is attributed to the path the largest newly acknowledged
+        // packet was sent on, rather than the path the ACK frame was received on.
+        if let Some(incremental_ecn_counts) =
+            ack_frame_ecn_counts.and_then(|counts| counts.checked_sub(self.baseline_ecn_counts))
+        {
+            context
+                .path_mut_by_id(largest_newly_acked_path_id)
+                .ecn_counts += incremental_ecn_counts;
+        }
+
         self.baseline_ecn_counts = ack_frame_ecn_counts.unwrap_or_default();
         self.sent_packet_ecn_counts -= newly_acked_ecn_counts;
     }
@@ -783,6 +804,11 @@ impl<Config: endpoint::Config> Manager<Config> {
         self.pto.has_transmission_interest()
     }
 
+    /// Releases any excess capacity held for tracking sent packets
+    pub fn shrink_to_fit(&mut self) {
+        self.sent_packets.shrink_to_fit();
+    }
+
     //= https://www.rfc-editor.org/rfc/rfc9002#appendix-B.9
     //# When Initial or Handshake keys are discarded, packets sent in that
     //# space no longer count toward bytes in flight.
@@ -1003,6 +1029,9 @@ impl<Config: endpoint::Config> Manager<Config> {
                 is_mtu_probe: sent_info.transmission_mode.is_mtu_probing(),
             });
 
+            path.lost_packets += 1;
+            path.lost_bytes += sent_info.sent_bytes as u64;
+
             let path_id = sent_info.path_id;
 
             // Notify the ECN controller of packet loss for blackhole detection.
@@ -1220,6 +1220,74 @@ fn process_new_acked_packets_process_ecn() {
     assert!(context.path().ecn_controller.is_capable());
 }
 
+#[test]
+// ECN counts should be attributed to the path the acknowledged packets were sent on
+//
+// Setup:
+// - create path manager with two validated paths
+// - send 2 ECT0 marked packets on the second path
+//
+// Trigger:
+// - send ack for packets 1 and 2 on the first path
+//
+// Expectation:
+// - the ECN counts of the second path are incremented
+// - the ECN counts of the first path are unchanged
+fn process_new_acked_packets_ecn_counts_per_path() {
+    // Setup:
+    let space = PacketNumberSpace::ApplicationData;
+    let mut publisher = Publisher::no_snapshot();
+    let (first_addr, first_path_id, _second_addr, second_path_id, mut manager, mut path_manager) =
+        helper_generate_multi_path_manager(space, &mut publisher);
+    let mut context = MockContext::new(&mut path_manager);
+    let time_sent = time::now() + Duration::from_secs(10);
+
+    context.set_path_id(second_path_id);
+    for i in 1..=2 {
+        manager.on_packet_sent(
+            space.new_packet_number(VarInt::from_u8(i)),
+            transmission::Outcome {
+                ack_elicitation: AckElicitation::Eliciting,
+                is_congestion_controlled: true,
+                bytes_sent: 128,
+                bytes_progressed: 0,
+            },
+            time_sent,
+            ExplicitCongestionNotification::Ect0,
+            transmission::Mode::Normal,
+            None,
+            &mut context,
+            &mut publisher,
+        );
+    }
+
+    // Trigger:
+    let ack_ecn_counts = EcnCounts {
+        ect_0_count: VarInt::from_u8(2),
+        ect_1_count: Default::default(),
+        ce_count: Default::default(),
+    };
+    helper_ack_packets_on_path(
+        1..=2,
+        time_sent + Duration::from_millis(500),
+        &mut context,
+        &mut manager,
+        first_addr,
+        Some(ack_ecn_counts),
+        &mut publisher,
+    );
+
+    // Expectation:
+    assert_eq!(
+        context.path_by_id(second_path_id).ecn_counts,
+        ack_ecn_counts
+    );
+    assert_eq!(
+        context.path_by_id(first_path_id).ecn_counts,
+        EcnCounts::default()
+    );
+}
+
 #[test]
 // Increase in ECN CE count should not cause congestion event if ECN validation fails
 //
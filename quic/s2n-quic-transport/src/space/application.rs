@@ -18,7 +18,7 @@ use crate::{
     transmission,
     transmission::interest::Provider,
 };
-use core::{convert::TryInto, fmt, marker::PhantomData};
+use core::{convert::TryInto, fmt, marker::PhantomData, task::Poll, time::Duration};
 use s2n_codec::EncoderBuffer;
 use s2n_quic_core::{
     counter::{Counter, Saturating},
@@ -27,9 +27,10 @@ use s2n_quic_core::{
     event::{self, ConnectionPublisher as _, IntoEvent},
     frame::{
         ack::AckRanges, crypto::CryptoRef, datagram::DatagramRef, stream::StreamRef, Ack,
-        ConnectionClose, DataBlocked, DcStatelessResetTokens, HandshakeDone, MaxData,
-        MaxStreamData, MaxStreams, NewConnectionId, NewToken, PathChallenge, PathResponse,
-        ResetStream, RetireConnectionId, StopSending, StreamDataBlocked, StreamsBlocked,
+        ConnectionClose, DataBlocked, DcStatelessResetTokens, ExpiredStreamData, GoAway,
+        HandshakeDone, MaxData, MaxStreamData, MaxStreams, NewConnectionId, NewToken,
+        PathChallenge, PathResponse, ResetStream, ResetStreamAt, RetireConnectionId, StopSending,
+        StreamDataBlocked, StreamsBlocked,
     },
     inet::DatagramInfo,
     packet::{
@@ -39,6 +40,7 @@ use s2n_quic_core::{
     },
     random::Generator,
     recovery::MAX_BURST_PACKETS,
+    stream::StreamType,
     time::{timer, Timestamp},
     transport,
 };
@@ -65,11 +67,15 @@ pub struct ApplicationSpace<Config: endpoint::Config> {
     //# An endpoint MUST NOT initiate a key update prior to having confirmed
     //# the handshake (Section 4.1.2).
     /// The crypto suite for application data
+    ///
+    /// The keys are discarded once the connection is closed.
     /// TODO: What about ZeroRtt?
-    key_set: KeySet<<<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::OneRttKey>,
-    header_key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::OneRttHeaderKey,
+    keys: Option<Keys<Config>>,
 
     ping: flag::Ping,
+    go_away: flag::GoAway,
+    /// Set once the server has requested the client move to a new connection
+    go_away_received: bool,
     keep_alive: KeepAlive,
     processed_packet_numbers: SlidingWindow,
     recovery_manager: recovery::Manager<Config>,
@@ -82,11 +88,18 @@ pub struct ApplicationSpace<Config: endpoint::Config> {
     pub buffer_crypto_frames: bool,
 }
 
+/// The packet protection keys for the application space
+struct Keys<Config: endpoint::Config> {
+    key_set: KeySet<<<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::OneRttKey>,
+    header_key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::OneRttHeaderKey,
+}
+
 impl<Config: endpoint::Config> fmt::Debug for ApplicationSpace<Config> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("ApplicationSpace")
             .field("ack_manager", &self.ack_manager)
             .field("ping", &self.ping)
+            .field("go_away_received", &self.go_away_received)
             .field("processed_packet_numbers", &self.processed_packet_numbers)
             .field("recovery_manager", &self.recovery_manager)
             .field("stream_manager", &self.stream_manager)
@@ -99,7 +112,7 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
     pub fn new(
         key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::OneRttKey,
         header_key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::OneRttHeaderKey,
-        now: Timestamp,
+        tx_packet_numbers: TxPacketNumbers,
         stream_manager: Config::StreamManager,
         ack_manager: AckManager,
         keep_alive: KeepAlive,
@@ -109,14 +122,18 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
         let key_set = KeySet::new(key, Self::key_limits());
 
         Self {
-            tx_packet_numbers: TxPacketNumbers::new(PacketNumberSpace::ApplicationData, now),
+            tx_packet_numbers,
             ack_manager,
             spin_bit: SpinBit::Zero,
             stream_manager,
             crypto_stream: CryptoStream::new(),
-            key_set,
-            header_key,
+            keys: Some(Keys {
+                key_set,
+                header_key,
+            }),
             ping: flag::Ping::default(),
+            go_away: flag::GoAway::default(),
+            go_away_received: false,
             keep_alive,
             processed_packet_numbers: SlidingWindow::default(),
             recovery_manager: recovery::Manager::new(PacketNumberSpace::ApplicationData),
@@ -219,6 +236,7 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
                 &mut self.ack_manager,
                 handshake_status,
                 &mut self.ping,
+                &mut self.go_away,
                 &mut self.stream_manager,
                 &mut self.recovery_manager,
                 &mut self.crypto_stream,
@@ -235,9 +253,12 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
         };
 
         let spin_bit = self.spin_bit;
-        let header_key = &self.header_key;
+        let Some(keys) = self.keys.as_mut() else {
+            return Err(PacketEncodingError::EmptyPayload(buffer));
+        };
+        let header_key = &keys.header_key;
         let (_protected_packet, buffer) =
-            self.key_set
+            keys.key_set
                 .encrypt_packet(buffer, |buffer, key, key_phase| {
                     let packet = Short {
                         spin_bit,
@@ -258,6 +279,8 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
         outcome.bytes_progressed +=
             (self.stream_manager.outgoing_bytes_progressed() - bytes_progressed).as_u64() as usize;
 
+        self.datagram_manager.on_transmit_complete(context.publisher);
+
         self.on_packet_sent(
             context,
             packet_number,
@@ -316,6 +339,8 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
                 packet_len: outcome.bytes_sent,
             });
 
+        self.publish_limit_pressure(context.publisher);
+
         if let Some(skip_packet_number) = skipped_packet_number.pto {
             Self::packet_skipped_event(
                 context,
@@ -335,6 +360,31 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
         }
     }
 
+    /// Notifies the application if it's approaching the peer's stream or data limits
+    ///
+    /// Streams are opened and written outside of the connection's clock, so usage is checked as
+    /// packets are sent.
+    fn publish_limit_pressure<Pub: event::ConnectionPublisher>(&mut self, publisher: &mut Pub) {
+        for stream_type in [StreamType::Bidirectional, StreamType::Unidirectional] {
+            if let Poll::Ready(pressure) =
+                self.stream_manager.poll_stream_limit_pressure(stream_type)
+            {
+                publisher.on_stream_limit_pressure(event::builder::StreamLimitPressure {
+                    stream_type: stream_type.into_event(),
+                    opened_streams: pressure.used.as_u64(),
+                    stream_limit: pressure.limit.as_u64(),
+                });
+            }
+        }
+
+        if let Poll::Ready(pressure) = self.stream_manager.poll_connection_data_pressure() {
+            publisher.on_connection_data_pressure(event::builder::ConnectionDataPressure {
+                bytes_sent: pressure.used.as_u64(),
+                max_data: pressure.limit.as_u64(),
+            });
+        }
+    }
+
     fn packet_skipped_event(
         context: &mut ConnectionTransmissionContext<Config>,
         skip_packet_number: PacketNumber,
@@ -391,9 +441,12 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
 
         let spin_bit = self.spin_bit;
         let min_packet_len = context.min_packet_len;
-        let header_key = &self.header_key;
+        let Some(keys) = self.keys.as_mut() else {
+            return Err(PacketEncodingError::EmptyPayload(buffer));
+        };
+        let header_key = &keys.header_key;
         let (_protected_packet, buffer) =
-            self.key_set
+            keys.key_set
                 .encrypt_packet(buffer, |buffer, key, key_phase| {
                     let packet = Short {
                         spin_bit,
@@ -424,6 +477,29 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
         Ok((outcome, buffer))
     }
 
+    /// Releases the state that is no longer needed once the connection is closed
+    ///
+    /// At this point the CONNECTION_CLOSE packet has already been encoded so the packet
+    /// protection keys, sent packet tracking and crypto buffers can be freed. The stream
+    /// manager is retained so the application can still observe the close error.
+    pub fn on_close(&mut self) {
+        self.keys = None;
+        self.recovery_manager = recovery::Manager::new(PacketNumberSpace::ApplicationData);
+        self.crypto_stream = CryptoStream::new();
+        self.buffer_crypto_frames = false;
+    }
+
+    /// Releases buffers which aren't needed while the connection is idle
+    ///
+    /// The packet protection keys and connection state are retained so the next packet can be
+    /// processed. Buffers are reallocated on demand once the connection becomes active again.
+    pub fn on_hibernate(&mut self) {
+        self.crypto_stream.shrink_to_fit();
+        self.ack_manager.shrink_to_fit();
+        self.recovery_manager.shrink_to_fit();
+        self.stream_manager.on_hibernate();
+    }
+
     /// Signals the handshake is confirmed
     pub fn on_handshake_confirmed(
         &mut self,
@@ -463,7 +539,9 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
         publisher: &mut Pub,
     ) {
         self.ack_manager.on_timeout(timestamp);
-        self.key_set.on_timeout(timestamp);
+        if let Some(keys) = self.keys.as_mut() {
+            keys.key_set.on_timeout(timestamp);
+        }
 
         let (recovery_manager, mut context) = self.recovery(
             handshake_status,
@@ -500,6 +578,19 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
 
         self.stream_manager.on_timeout(timestamp);
 
+        for stream_type in [StreamType::Bidirectional, StreamType::Unidirectional] {
+            if let Poll::Ready(blocked) = self
+                .stream_manager
+                .poll_peer_blocked_timeout(stream_type, timestamp)
+            {
+                publisher.on_stream_open_blocked(event::builder::StreamOpenBlocked {
+                    stream_type: stream_type.into_event(),
+                    stream_limit: blocked.stream_limit.as_u64(),
+                    duration: blocked.duration,
+                });
+            }
+        }
+
         if self.keep_alive.on_timeout(timestamp).is_ready() {
             publisher.on_keep_alive_timer_expired(event::builder::KeepAliveTimerExpired {
                 timeout: self.keep_alive.period(),
@@ -544,6 +635,21 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
         self.keep_alive.update(enabled);
     }
 
+    /// Called when the effective idle timeout of the connection changed
+    pub fn on_idle_timeout_update(&mut self, idle_timeout: Option<Duration>, now: Timestamp) {
+        self.keep_alive.on_idle_timeout_update(idle_timeout, now);
+    }
+
+    /// Starts sending a GOAWAY frame to the peer
+    pub fn go_away(&mut self) {
+        self.go_away.send()
+    }
+
+    /// Returns `true` if the peer has requested a move to a new connection
+    pub fn is_go_away_received(&self) -> bool {
+        self.go_away_received
+    }
+
     /// Returns the Packet Number to be used when encoding outgoing packets
     fn packet_number_encoder(&self) -> PacketNumber {
         self.tx_packet_numbers.largest_sent_packet_number_acked()
@@ -566,6 +672,7 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
                 crypto_stream: &mut self.crypto_stream,
                 handshake_status,
                 ping: &mut self.ping,
+                go_away: &mut self.go_away,
                 stream_manager: &mut self.stream_manager,
                 local_id_registry,
                 path_id,
@@ -593,9 +700,18 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
         path: &path::Path<Config>,
         publisher: &mut Pub,
     ) -> Result<CleartextShort<'a>, ProcessingError> {
+        let Some(keys) = self.keys.as_mut() else {
+            publisher.on_packet_dropped(event::builder::PacketDropped {
+                reason: event::builder::PacketDropReason::ConnectionError {
+                    path: path_event!(path, path_id),
+                },
+            });
+            return Err(ProcessingError::Other);
+        };
+
         let largest_acked = self.ack_manager.largest_received_packet_number_acked();
         let packet = protected
-            .unprotect(&self.header_key, largest_acked)
+            .unprotect(&keys.header_key, largest_acked)
             .map_err(|err| {
                 publisher.on_packet_dropped(event::builder::PacketDropped {
                     reason: event::builder::PacketDropReason::UnprotectFailed {
@@ -609,7 +725,7 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
         let packet_number = packet.packet_number;
         let packet_header =
             event::builder::PacketHeader::new(packet.packet_number, publisher.quic_version());
-        let decrypted = self.key_set.decrypt_packet(
+        let decrypted = keys.key_set.decrypt_packet(
             packet,
             largest_acked,
             //= https://www.rfc-editor.org/rfc/rfc9001#section-6.3
@@ -632,7 +748,7 @@ impl<Config: endpoint::Config> ApplicationSpace<Config> {
             Ok((_, Some(generation))) => {
                 publisher.on_key_update(event::builder::KeyUpdate {
                     key_type: event::builder::KeyType::OneRtt { generation },
-                    cipher_suite: self.key_set.cipher_suite().into_event(),
+                    cipher_suite: keys.key_set.cipher_suite().into_event(),
                 });
             }
             Ok(_) => {}
@@ -675,7 +791,9 @@ impl<Config: endpoint::Config> timer::Provider for ApplicationSpace<Config> {
     fn timers<Q: timer::Query>(&self, query: &mut Q) -> timer::Result {
         self.ack_manager.timers(query)?;
         self.recovery_manager.timers(query)?;
-        self.key_set.timers(query)?;
+        if let Some(keys) = self.keys.as_ref() {
+            keys.key_set.timers(query)?;
+        }
         self.stream_manager.timers(query)?;
         self.keep_alive.timers(query)?;
 
@@ -691,6 +809,7 @@ impl<Config: endpoint::Config> transmission::interest::Provider for ApplicationS
     ) -> transmission::interest::Result {
         self.ack_manager.transmission_interest(query)?;
         self.ping.transmission_interest(query)?;
+        self.go_away.transmission_interest(query)?;
         self.crypto_stream.transmission_interest(query)?;
         self.recovery_manager.transmission_interest(query)?;
         self.stream_manager.transmission_interest(query)?;
@@ -711,6 +830,7 @@ struct RecoveryContext<'a, Config: endpoint::Config> {
     handshake_status: &'a mut HandshakeStatus,
     crypto_stream: &'a mut CryptoStream,
     ping: &'a mut flag::Ping,
+    go_away: &'a mut flag::GoAway,
     stream_manager: &'a mut Config::StreamManager,
     local_id_registry: &'a mut connection::LocalIdRegistry,
     path_id: path::Id,
@@ -778,6 +898,7 @@ impl<Config: endpoint::Config> recovery::Context<Config> for RecoveryContext<'_,
             .on_packet_ack(packet_number_range, publisher);
         self.crypto_stream.on_packet_ack(packet_number_range);
         self.ping.on_packet_ack(packet_number_range);
+        self.go_away.on_packet_ack(packet_number_range);
         self.stream_manager.on_packet_ack(packet_number_range);
         self.local_id_registry.on_packet_ack(packet_number_range);
         self.path_manager.on_packet_ack(packet_number_range);
@@ -799,6 +920,7 @@ impl<Config: endpoint::Config> recovery::Context<Config> for RecoveryContext<'_,
             .on_packet_loss(packet_number_range, publisher);
         self.dc_manager.on_packet_loss(packet_number_range);
         self.ping.on_packet_loss(packet_number_range);
+        self.go_away.on_packet_loss(packet_number_range);
         self.stream_manager.on_packet_loss(packet_number_range);
         self.local_id_registry.on_packet_loss(packet_number_range);
         self.path_manager.on_packet_loss(packet_number_range);
@@ -813,6 +935,7 @@ impl<Config: endpoint::Config> recovery::Context<Config> for RecoveryContext<'_,
     }
 
     fn on_mtu_update(&mut self, max_datagram_size: u16) {
+        self.stream_manager.on_mtu_update(max_datagram_size);
         self.dc_manager.on_mtu_updated(max_datagram_size)
     }
 }
@@ -923,12 +1046,13 @@ impl<Config: endpoint::Config> PacketSpace<Config> for ApplicationSpace<Config>
         Ok(())
     }
 
-    fn handle_datagram_frame(
+    fn handle_datagram_frame<Pub: event::ConnectionPublisher>(
         &mut self,
         path: s2n_quic_core::event::api::Path<'_>,
         frame: DatagramRef,
+        publisher: &mut Pub,
     ) -> Result<(), transport::Error> {
-        self.datagram_manager.on_datagram_frame(path, frame);
+        self.datagram_manager.on_datagram_frame(path, frame, publisher);
         Ok(())
     }
 
@@ -955,6 +1079,20 @@ impl<Config: endpoint::Config> PacketSpace<Config> for ApplicationSpace<Config>
         self.stream_manager.on_reset_stream(&frame)
     }
 
+    fn handle_reset_stream_at_frame(
+        &mut self,
+        frame: ResetStreamAt,
+    ) -> Result<(), transport::Error> {
+        self.stream_manager.on_reset_stream_at(&frame)
+    }
+
+    fn handle_expired_stream_data_frame(
+        &mut self,
+        frame: ExpiredStreamData,
+    ) -> Result<(), transport::Error> {
+        self.stream_manager.on_expired_stream_data(&frame)
+    }
+
     fn handle_stop_sending_frame(&mut self, frame: StopSending) -> Result<(), transport::Error> {
         self.stream_manager.on_stop_sending(&frame)
     }
@@ -1138,6 +1276,26 @@ impl<Config: endpoint::Config> PacketSpace<Config> for ApplicationSpace<Config>
         Ok(())
     }
 
+    fn handle_go_away_frame<Pub: event::ConnectionPublisher>(
+        &mut self,
+        frame: GoAway,
+        publisher: &mut Pub,
+    ) -> Result<(), transport::Error> {
+        if Config::ENDPOINT_TYPE.is_server() {
+            return Err(transport::Error::PROTOCOL_VIOLATION
+                .with_reason("Clients MUST NOT send GOAWAY frames")
+                .with_frame_type(frame.tag()));
+        }
+
+        // the frame may be retransmitted so only notify the application once
+        if !self.go_away_received {
+            self.go_away_received = true;
+            publisher.on_go_away_received(event::builder::GoAwayReceived {});
+        }
+
+        Ok(())
+    }
+
     fn on_processed_packet<Pub: event::ConnectionPublisher>(
         &mut self,
         processed_packet: ProcessedPacket,
@@ -54,6 +54,12 @@ impl CryptoStream {
         }
     }
 
+    /// Releases any excess capacity held by the stream buffers
+    pub fn shrink_to_fit(&mut self) {
+        self.tx.shrink_to_fit();
+        self.rx.shrink_to_fit();
+    }
+
     pub fn can_send(&self) -> bool {
         !self.is_finished && self.tx.available_buffer_space() > 0
     }
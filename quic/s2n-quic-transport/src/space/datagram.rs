@@ -12,7 +12,8 @@ use crate::{
 use core::task::Poll;
 use s2n_codec::EncoderValue;
 use s2n_quic_core::{
-    datagram::{Endpoint, ReceiveContext, Receiver, Sender, WriteError},
+    datagram::{Dropped, Endpoint, ReceiveContext, Receiver, Sender, WriteError},
+    event::{self, IntoEvent},
     frame::{self, datagram::DatagramRef},
     query,
     varint::VarInt,
@@ -57,15 +58,26 @@ impl<Config: endpoint::Config> Manager<Config> {
         self.sender.on_transmit(&mut packet);
     }
 
+    /// Emits an event for each datagram the sender dropped before it could be transmitted
+    pub fn on_transmit_complete<Pub: event::ConnectionPublisher>(&mut self, publisher: &mut Pub) {
+        self.sender.drain_dropped(|dropped| {
+            publisher.on_datagram_frame_dropped(dropped_event(dropped, true));
+        });
+    }
+
     // A callback that allows users to access datagrams directly after they are
     // received.
-    pub fn on_datagram_frame(
+    pub fn on_datagram_frame<Pub: event::ConnectionPublisher>(
         &mut self,
         path: s2n_quic_core::event::api::Path<'_>,
         datagram: DatagramRef,
+        publisher: &mut Pub,
     ) {
         let context = ReceiveContext::new(path);
         self.receiver.on_datagram(&context, datagram.data);
+        self.receiver.drain_dropped(|dropped| {
+            publisher.on_datagram_frame_dropped(dropped_event(dropped, false));
+        });
     }
 
     pub fn datagram_mut(&mut self, query: &mut dyn query::QueryMut) -> Poll<()> {
@@ -95,6 +107,15 @@ impl<Config: endpoint::Config> interest::Provider for Manager<Config> {
     }
 }
 
+#[inline]
+fn dropped_event(dropped: Dropped, is_outgoing: bool) -> event::builder::DatagramFrameDropped {
+    event::builder::DatagramFrameDropped {
+        len: dropped.len,
+        is_outgoing,
+        reason: dropped.reason.into_event(),
+    }
+}
+
 struct Packet<'a, C: WriteContext> {
     context: &'a mut C,
     has_pending_streams: bool,
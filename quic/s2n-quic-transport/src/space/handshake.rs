@@ -60,7 +60,7 @@ impl<Config: endpoint::Config> HandshakeSpace<Config> {
     pub fn new(
         key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::HandshakeKey,
         header_key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::HandshakeHeaderKey,
-        now: Timestamp,
+        tx_packet_numbers: TxPacketNumbers,
         ack_manager: AckManager,
     ) -> Self {
         Self {
@@ -68,7 +68,7 @@ impl<Config: endpoint::Config> HandshakeSpace<Config> {
             key,
             header_key,
             crypto_stream: CryptoStream::new(),
-            tx_packet_numbers: TxPacketNumbers::new(PacketNumberSpace::Handshake, now),
+            tx_packet_numbers,
             processed_packet_numbers: SlidingWindow::default(),
             recovery_manager: recovery::Manager::new(PacketNumberSpace::Handshake),
         }
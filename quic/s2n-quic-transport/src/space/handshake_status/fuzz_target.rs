@@ -125,6 +125,10 @@ impl Model {
     }
 
     fn apply(&mut self, operation: &Operation) {
+        let was_complete = self.subject.is_complete();
+        let was_confirmed = self.subject.is_confirmed();
+        let was_terminal = matches!(self.subject, HandshakeStatus::Confirmed);
+
         match operation {
             Operation::Complete => self.on_complete(),
             Operation::TransmitHandshakeDone {} => self.packet_transmit(),
@@ -136,6 +140,15 @@ impl Model {
             } => self.packet_loss(*lost_handshake_done),
             Operation::ReceivedHandshakeDone {} => self.on_handshake_done_received(),
         }
+
+        // the handshake status only moves forward and Confirmed is terminal
+        assert!(!was_complete || self.subject.is_complete());
+        assert!(!was_confirmed || self.subject.is_confirmed());
+        if was_terminal {
+            assert!(matches!(self.subject, HandshakeStatus::Confirmed));
+        }
+
+        self.invariants();
     }
 
     fn on_complete(&mut self) {
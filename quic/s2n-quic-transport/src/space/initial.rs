@@ -11,6 +11,7 @@ use crate::{
     space::{CryptoStream, HandshakeStatus, PacketSpace, TxPacketNumbers},
     transmission,
 };
+use alloc::vec::Vec;
 use core::{fmt, marker::PhantomData};
 use s2n_codec::EncoderBuffer;
 use s2n_quic_core::{
@@ -47,6 +48,9 @@ pub struct InitialSpace<Config: endpoint::Config> {
     //= https://www.rfc-editor.org/rfc/rfc9000#section-17.2.5.3
     //# Subsequent Initial packets from the client include the connection ID
     //# and token values from the Retry packet.
+    //
+    // Before a Retry packet is received, this contains the token provided by the application,
+    // if any.
     retry_token: Vec<u8>,
     processed_packet_numbers: SlidingWindow,
     recovery_manager: recovery::Manager<Config>,
@@ -67,7 +71,7 @@ impl<Config: endpoint::Config> InitialSpace<Config> {
     pub fn new(
         key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::InitialKey,
         header_key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::InitialHeaderKey,
-        now: Timestamp,
+        tx_packet_numbers: TxPacketNumbers,
         ack_manager: AckManager,
     ) -> Self {
         Self {
@@ -75,7 +79,7 @@ impl<Config: endpoint::Config> InitialSpace<Config> {
             key,
             header_key,
             crypto_stream: CryptoStream::new(),
-            tx_packet_numbers: TxPacketNumbers::new(PacketNumberSpace::Initial, now),
+            tx_packet_numbers,
             received_hello_message: false,
             retry_token: Vec::new(),
             processed_packet_numbers: SlidingWindow::default(),
@@ -83,6 +87,12 @@ impl<Config: endpoint::Config> InitialSpace<Config> {
         }
     }
 
+    /// Sets the token to include in Initial packets before any Retry packet is received
+    pub fn set_token(&mut self, token: Vec<u8>) {
+        debug_assert!(Config::ENDPOINT_TYPE.is_client());
+        self.retry_token = token;
+    }
+
     /// This method gets called when a Retry packet is processed.
     ///
     /// Reset the TLS stack and recover state when the first Retry packet is processed.
@@ -8,12 +8,33 @@ use s2n_quic_core::time::{timer, Timer, Timestamp};
 pub struct KeepAlive {
     enabled: bool,
     period: Duration,
+    max_period: Duration,
     timer: Timer,
 }
 
 impl KeepAlive {
     pub fn new(max_idle_timeout: Option<Duration>, max_period: Duration) -> Self {
-        let period = if let Some(max_idle_timeout) = max_idle_timeout {
+        Self {
+            enabled: false,
+            period: Self::period_for(max_idle_timeout, max_period),
+            max_period,
+            timer: Timer::default(),
+        }
+    }
+
+    /// Recomputes the keep alive period after the idle timeout changed
+    #[inline]
+    pub fn on_idle_timeout_update(&mut self, max_idle_timeout: Option<Duration>, now: Timestamp) {
+        self.period = Self::period_for(max_idle_timeout, self.max_period);
+
+        // a shorter period needs to apply to the timer which is already armed
+        if self.timer.is_armed() {
+            self.reset(now);
+        }
+    }
+
+    fn period_for(max_idle_timeout: Option<Duration>, max_period: Duration) -> Duration {
+        if let Some(max_idle_timeout) = max_idle_timeout {
             // send a ping frame at 3/4 max idle timeout to ensure it is delivered in time
             (max_idle_timeout * 3 / 4).min(max_period)
         } else {
@@ -29,12 +50,6 @@ impl KeepAlive {
             // Even though we don't have an idle timeout, we should still have a default
             // keep-alive period to ensure middleboxes don't drop their UDP flow
             max_period
-        };
-
-        Self {
-            enabled: false,
-            period,
-            timer: Timer::default(),
         }
     }
 
@@ -10,6 +10,7 @@ use crate::{
     stream::Manager as _,
     transmission,
 };
+use alloc::{boxed::Box, sync::Arc};
 use bytes::Bytes;
 use core::{
     fmt,
@@ -24,9 +25,10 @@ use s2n_quic_core::{
     event::{self, IntoEvent},
     frame::{
         ack::AckRanges, crypto::CryptoRef, datagram::DatagramRef, stream::StreamRef, Ack,
-        ConnectionClose, DataBlocked, DcStatelessResetTokens, HandshakeDone, MaxData,
-        MaxStreamData, MaxStreams, NewConnectionId, NewToken, PathChallenge, PathResponse,
-        ResetStream, RetireConnectionId, StopSending, StreamDataBlocked, StreamsBlocked,
+        ConnectionClose, DataBlocked, DcStatelessResetTokens, ExpiredStreamData, GoAway,
+        HandshakeDone, MaxData, MaxStreamData, MaxStreams, NewConnectionId, NewToken,
+        PathChallenge, PathResponse, ResetStream, ResetStreamAt, RetireConnectionId, StopSending,
+        StreamDataBlocked, StreamsBlocked,
     },
     inet::DatagramInfo,
     packet::number::{PacketNumber, PacketNumberSpace},
@@ -42,6 +44,7 @@ mod handshake;
 mod handshake_status;
 mod initial;
 mod keep_alive;
+mod packet_budget;
 mod session_context;
 mod tx_packet_numbers;
 
@@ -50,16 +53,21 @@ pub(crate) use crypto_stream::CryptoStream;
 pub(crate) use handshake::HandshakeSpace;
 pub(crate) use handshake_status::HandshakeStatus;
 pub(crate) use initial::InitialSpace;
+pub(crate) use packet_budget::PacketBudget;
 pub(crate) use session_context::SessionContext;
-pub(crate) use tx_packet_numbers::TxPacketNumbers;
+pub(crate) use tx_packet_numbers::{InitialPacketNumbers, TxPacketNumbers};
 
 struct SessionInfo<Config: endpoint::Config> {
     session: <Config::TLSEndpoint as tls::Endpoint>::Session,
     initial_cid: InitialId,
+    compatibility: Arc<Config::CompatibilityPolicy>,
+    initial_packet_numbers: InitialPacketNumbers,
 }
 
 pub struct PacketSpaceManager<Config: endpoint::Config> {
     session_info: Option<SessionInfo<Config>>,
+    /// The TLS session retained after the handshake for exporting keying material
+    exporter_session: Option<Box<<Config::TLSEndpoint as tls::Endpoint>::Session>>,
     retry_cid: Option<Box<PeerId>>,
     initial: Option<Box<InitialSpace<Config>>>,
     handshake: Option<Box<HandshakeSpace<Config>>>,
@@ -110,6 +118,8 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
         session: <Config::TLSEndpoint as tls::Endpoint>::Session,
         initial_key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::InitialKey,
         header_key: <<Config::TLSEndpoint as tls::Endpoint>::Session as CryptoSuite>::InitialHeaderKey,
+        compatibility: Arc<Config::CompatibilityPolicy>,
+        initial_packet_numbers: InitialPacketNumbers,
         now: Timestamp,
         publisher: &mut Pub,
     ) -> Self {
@@ -123,12 +133,19 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
             session_info: Some(SessionInfo {
                 session,
                 initial_cid,
+                compatibility,
+                initial_packet_numbers,
             }),
+            exporter_session: None,
             retry_cid: None,
             initial: Some(Box::new(InitialSpace::new(
                 initial_key,
                 header_key,
-                now,
+                TxPacketNumbers::new(
+                    PacketNumberSpace::Initial,
+                    initial_packet_numbers.initial,
+                    now,
+                ),
                 ack_manager,
             ))),
             handshake: None,
@@ -249,11 +266,15 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
         datagram: &mut Config::DatagramEndpoint,
         dc: &mut Config::DcEndpoint,
     ) -> Poll<Result<(), transport::Error>> {
+        let retain_session = limits.keying_material_export();
+
         if let Some(session_info) = self.session_info.as_mut() {
             let mut context: SessionContext<Config, Pub> = SessionContext {
                 now,
                 initial_cid: &session_info.initial_cid,
                 retry_cid: self.retry_cid.as_deref(),
+                compatibility: &*session_info.compatibility,
+                initial_packet_numbers: session_info.initial_packet_numbers,
                 initial: &mut self.initial,
                 handshake: &mut self.handshake,
                 application: &mut self.application,
@@ -273,7 +294,7 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
             match session_info.session.poll(&mut context)? {
                 Poll::Ready(_success) => {
                     if session_info.session.should_discard_session() {
-                        self.discard_session();
+                        self.discard_session(retain_session);
                     }
 
                     self.retry_cid = None;
@@ -296,11 +317,15 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
         datagram: &mut Config::DatagramEndpoint,
         dc: &mut Config::DcEndpoint,
     ) -> Result<(), transport::Error> {
+        let retain_session = limits.keying_material_export();
+
         if let Some(session_info) = self.session_info.as_mut() {
             let mut context: SessionContext<Config, Pub> = SessionContext {
                 now,
                 initial_cid: &session_info.initial_cid,
                 retry_cid: self.retry_cid.as_deref(),
+                compatibility: &*session_info.compatibility,
+                initial_packet_numbers: session_info.initial_packet_numbers,
                 initial: &mut self.initial,
                 handshake: &mut self.handshake,
                 application: &mut self.application,
@@ -321,15 +346,37 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
                 .session
                 .process_post_handshake_message(&mut context)?;
             if session_info.session.should_discard_session() {
-                self.discard_session();
+                self.discard_session(retain_session);
             }
         }
 
         Ok(())
     }
 
-    fn discard_session(&mut self) {
-        self.session_info = None;
+    /// Releases the buffers and TLS state which aren't needed while the connection is idle
+    pub fn on_hibernate(&mut self) {
+        if let Some(session_info) = self.session_info.as_mut() {
+            session_info.session.release_buffers();
+        }
+
+        if let Some(session) = self.exporter_session.as_mut() {
+            session.release_buffers();
+        }
+
+        if let Some((space, _handshake_status)) = self.application_mut() {
+            space.on_hibernate();
+        }
+    }
+
+    fn discard_session(&mut self, retain_session: bool) {
+        if let Some(session_info) = self.session_info.take() {
+            // The session is kept around after the handshake so the application can export
+            // keying material for the lifetime of the connection
+            if retain_session {
+                self.exporter_session = Some(Box::new(session_info.session));
+            }
+        }
+
         if let Some((application_space, _status)) = self.application_mut() {
             application_space.crypto_stream.rx.reset();
             application_space.buffer_crypto_frames = false;
@@ -465,6 +512,26 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
         self.handshake_status.is_complete()
     }
 
+    /// Exports keying material from the TLS session once the handshake has completed
+    pub fn export_keying_material(
+        &self,
+        label: &[u8],
+        context: &[u8],
+        output: &mut [u8],
+    ) -> Result<(), tls::TlsExportError> {
+        if !self.is_handshake_complete() {
+            return Err(tls::TlsExportError::failure());
+        }
+
+        let session = self
+            .exporter_session
+            .as_deref()
+            .or_else(|| self.session_info.as_ref().map(|info| &info.session))
+            .ok_or_else(tls::TlsExportError::failure)?;
+
+        session.export_keying_material(label, context, output)
+    }
+
     pub(crate) fn on_transmit_close(
         &mut self,
         early_connection_close: &ConnectionClose,
@@ -566,6 +633,7 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
         publisher: &mut Pub,
     ) {
         self.session_info = None;
+        self.exporter_session = None;
         self.retry_cid = None;
         self.discard_initial(path_manager, now, publisher);
         self.discard_handshake(path_manager, publisher);
@@ -580,6 +648,9 @@ impl<Config: endpoint::Config> PacketSpaceManager<Config> {
 
             // Close all streams with the derived error
             application.stream_manager.close(error);
+
+            // Release everything else that isn't needed while closing or draining
+            application.on_close();
         }
     }
 
@@ -778,10 +849,11 @@ pub trait PacketSpace<Config: endpoint::Config>: Sized {
             .with_frame_type(frame.tag().into()))
     }
 
-    fn handle_datagram_frame(
+    fn handle_datagram_frame<Pub: event::ConnectionPublisher>(
         &mut self,
         _path: s2n_quic_core::event::api::Path<'_>,
         frame: DatagramRef,
+        _publisher: &mut Pub,
     ) -> Result<(), transport::Error> {
         Err(transport::Error::PROTOCOL_VIOLATION
             .with_reason(Self::INVALID_FRAME_ERROR)
@@ -798,11 +870,23 @@ pub trait PacketSpace<Config: endpoint::Config>: Sized {
             .with_frame_type(frame.tag()))
     }
 
+    fn handle_go_away_frame<Pub: event::ConnectionPublisher>(
+        &mut self,
+        frame: GoAway,
+        _publisher: &mut Pub,
+    ) -> Result<(), transport::Error> {
+        Err(transport::Error::PROTOCOL_VIOLATION
+            .with_reason(Self::INVALID_FRAME_ERROR)
+            .with_frame_type(frame.tag()))
+    }
+
     default_frame_handler!(handle_data_blocked_frame, DataBlocked);
     default_frame_handler!(handle_max_data_frame, MaxData);
     default_frame_handler!(handle_max_stream_data_frame, MaxStreamData);
     default_frame_handler!(handle_max_streams_frame, MaxStreams);
     default_frame_handler!(handle_reset_stream_frame, ResetStream);
+    default_frame_handler!(handle_reset_stream_at_frame, ResetStreamAt);
+    default_frame_handler!(handle_expired_stream_data_frame, ExpiredStreamData);
     default_frame_handler!(handle_stop_sending_frame, StopSending);
     default_frame_handler!(handle_stream_data_blocked_frame, StreamDataBlocked);
     default_frame_handler!(handle_streams_blocked_frame, StreamsBlocked);
@@ -816,6 +900,9 @@ pub trait PacketSpace<Config: endpoint::Config>: Sized {
         publisher: &mut Pub,
     ) -> Result<(), transport::Error>;
 
+    /// Processes the frames in a decrypted packet
+    ///
+    /// Returns `None` if the packet was dropped by the packet interceptor.
     fn handle_cleartext_payload<'a, Pub: event::ConnectionPublisher>(
         &mut self,
         packet_number: PacketNumber,
@@ -828,25 +915,54 @@ pub trait PacketSpace<Config: endpoint::Config>: Sized {
         random_generator: &mut Config::RandomGenerator,
         publisher: &mut Pub,
         packet_interceptor: &mut Config::PacketInterceptor,
-    ) -> Result<ProcessedPacket<'a>, connection::Error> {
+        mut budget: PacketBudget,
+        close_reason: &mut Option<Bytes>,
+    ) -> Result<Option<ProcessedPacket<'a>>, connection::Error> {
         use s2n_quic_core::frame::{Frame, FrameMut};
 
         let mut payload = {
             use s2n_quic_core::packet::interceptor::{Interceptor, Packet};
 
+            let subject = publisher.subject();
+            let packet = Packet {
+                number: packet_number,
+                timestamp: datagram.timestamp,
+            };
+
             // intercept the payload after it is decrypted, but before we process the frames
-            packet_interceptor.intercept_rx_payload(
-                &publisher.subject(),
-                &Packet {
-                    number: packet_number,
-                    timestamp: datagram.timestamp,
-                },
-                payload,
-            )
+            let payload = packet_interceptor.intercept_rx_payload(&subject, &packet, payload);
+
+            // the packet is treated as if it never arrived so it isn't acknowledged
+            if packet_interceptor.drop_rx_packet(&subject, &packet) {
+                return Ok(None);
+            }
+
+            payload
         };
 
         let mut processed_packet = ProcessedPacket::new(packet_number, datagram);
 
+        macro_rules! check_budget {
+            ($result:expr) => {
+                if let Err(exceeded) = $result {
+                    publisher.on_packet_budget_exceeded(event::builder::PacketBudgetExceeded {
+                        packet_header: event::builder::PacketHeader::new(
+                            packet_number,
+                            publisher.quic_version(),
+                        ),
+                        budget: exceeded.budget,
+                        limit: exceeded.limit,
+                    });
+
+                    // the remainder of the packet is discarded so close the connection rather than
+                    // acknowledging frames which weren't processed
+                    return Err(transport::Error::PROTOCOL_VIOLATION
+                        .with_reason("packet processing budget exceeded")
+                        .into());
+                }
+            };
+        }
+
         macro_rules! on_frame_processed {
             ($frame:ident) => {{
                 let frame_type = $frame.tag();
@@ -882,6 +998,8 @@ pub trait PacketSpace<Config: endpoint::Config>: Sized {
                 .decode::<FrameMut>()
                 .map_err(transport::Error::from)?;
 
+            check_budget!(budget.on_frame());
+
             let path = &path_manager[path_id];
             publisher.on_frame_received(event::builder::FrameReceived {
                 packet_header: event::builder::PacketHeader::new(
@@ -908,6 +1026,7 @@ pub trait PacketSpace<Config: endpoint::Config>: Sized {
                     let _ = on_frame_processed!(frame);
                 }
                 Frame::Crypto(frame) => {
+                    check_budget!(budget.on_crypto_bytes(frame.data.len()));
                     let on_error = on_frame_processed!(frame);
 
                     //= https://www.rfc-editor.org/rfc/rfc9000#section-7.5
@@ -926,6 +1045,7 @@ pub trait PacketSpace<Config: endpoint::Config>: Sized {
                     processed_packet.contains_crypto = true;
                 }
                 Frame::Ack(frame) => {
+                    check_budget!(budget.on_ack_ranges(frame.ack_ranges().len()));
                     let on_error = on_frame_processed!(frame);
                     self.handle_ack_frame(
                         frame,
@@ -951,6 +1071,12 @@ pub trait PacketSpace<Config: endpoint::Config>: Sized {
                     )
                     .map_err(on_error)?;
 
+                    // the error only carries a truncated copy of the reason so the complete
+                    // reason is retained separately
+                    if let Some(reason) = frame.reason.filter(|reason| !reason.is_empty()) {
+                        *close_reason = Some(Bytes::copy_from_slice(reason));
+                    }
+
                     // skip processing any other frames and return an error
 
                     // use `from` instead of `into` so the location is correctly captured
@@ -966,6 +1092,7 @@ pub trait PacketSpace<Config: endpoint::Config>: Sized {
                     self.handle_datagram_frame(
                         path_event!(path, path_id).into_event(),
                         frame.into(),
+                        publisher,
                     )
                     .map_err(on_error)?;
                 }
@@ -989,6 +1116,15 @@ pub trait PacketSpace<Config: endpoint::Config>: Sized {
                     let on_error = on_frame_processed!(frame);
                     self.handle_reset_stream_frame(frame).map_err(on_error)?;
                 }
+                Frame::ResetStreamAt(frame) => {
+                    let on_error = on_frame_processed!(frame);
+                    self.handle_reset_stream_at_frame(frame).map_err(on_error)?;
+                }
+                Frame::ExpiredStreamData(frame) => {
+                    let on_error = on_frame_processed!(frame);
+                    self.handle_expired_stream_data_frame(frame)
+                        .map_err(on_error)?;
+                }
                 Frame::StopSending(frame) => {
                     let on_error = on_frame_processed!(frame);
                     self.handle_stop_sending_frame(frame).map_err(on_error)?;
@@ -1062,6 +1198,11 @@ pub trait PacketSpace<Config: endpoint::Config>: Sized {
                     self.handle_dc_stateless_reset_tokens_frame(frame, publisher)
                         .map_err(on_error)?;
                 }
+                Frame::GoAway(frame) => {
+                    let on_error = on_frame_processed!(frame);
+                    self.handle_go_away_frame(frame, publisher)
+                        .map_err(on_error)?;
+                }
             }
 
             payload = remaining;
@@ -1086,6 +1227,8 @@ pub trait PacketSpace<Config: endpoint::Config>: Sized {
             publisher,
         )?;
 
+        path_manager.on_path_activity(path_id, datagram.timestamp);
+
         if amplification_outcome.is_active_path_unblocked() {
             self.on_amplification_unblocked(
                 path_manager,
@@ -1107,7 +1250,7 @@ pub trait PacketSpace<Config: endpoint::Config>: Sized {
 
         self.on_processed_packet(processed_packet, path_id, &path_manager[path_id], publisher)?;
 
-        Ok(processed_packet)
+        Ok(Some(processed_packet))
     }
 }
 
@@ -0,0 +1,120 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use s2n_quic_core::{connection::limits::Limits, event::builder::PacketBudget as Kind};
+
+/// Limits the amount of work performed when processing a single packet
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PacketBudget {
+    frames: Remaining,
+    ack_ranges: Remaining,
+    crypto_bytes: Remaining,
+}
+
+impl PacketBudget {
+    pub fn new(limits: &Limits) -> Self {
+        Self {
+            frames: Remaining::new(limits.max_frames_per_packet()),
+            ack_ranges: Remaining::new(limits.max_ack_ranges_per_packet()),
+            crypto_bytes: Remaining::new(limits.max_crypto_bytes_per_packet()),
+        }
+    }
+
+    /// Called before a frame is processed
+    #[inline]
+    pub fn on_frame(&mut self) -> Result<(), Exceeded> {
+        self.frames.consume(1, Kind::Frames)
+    }
+
+    /// Called before an ACK frame with the given number of ranges is processed
+    #[inline]
+    pub fn on_ack_ranges(&mut self, count: usize) -> Result<(), Exceeded> {
+        self.ack_ranges.consume(count, Kind::AckRanges)
+    }
+
+    /// Called before a CRYPTO frame with the given length is processed
+    #[inline]
+    pub fn on_crypto_bytes(&mut self, len: usize) -> Result<(), Exceeded> {
+        self.crypto_bytes.consume(len, Kind::CryptoBytes)
+    }
+}
+
+/// The budget which was exceeded, along with the configured limit
+#[derive(Clone, Debug)]
+pub struct Exceeded {
+    pub budget: Kind,
+    pub limit: u64,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct Remaining {
+    limit: Option<u32>,
+    consumed: u64,
+}
+
+impl Remaining {
+    #[inline]
+    fn new(limit: Option<u32>) -> Self {
+        Self { limit, consumed: 0 }
+    }
+
+    #[inline]
+    fn consume(&mut self, amount: usize, budget: Kind) -> Result<(), Exceeded> {
+        let Some(limit) = self.limit else {
+            return Ok(());
+        };
+
+        self.consumed = self.consumed.saturating_add(amount as u64);
+
+        if self.consumed > limit as u64 {
+            return Err(Exceeded {
+                budget,
+                limit: limit as u64,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unlimited_test() {
+        let mut budget = PacketBudget::new(&Limits::default());
+
+        for _ in 0..1000 {
+            assert!(budget.on_frame().is_ok());
+        }
+        assert!(budget.on_ack_ranges(usize::MAX).is_ok());
+        assert!(budget.on_crypto_bytes(usize::MAX).is_ok());
+    }
+
+    #[test]
+    fn exceeded_test() {
+        let limits = Limits::default()
+            .with_max_frames_per_packet(2)
+            .unwrap()
+            .with_max_ack_ranges_per_packet(10)
+            .unwrap()
+            .with_max_crypto_bytes_per_packet(100)
+            .unwrap();
+        let mut budget = PacketBudget::new(&limits);
+
+        assert!(budget.on_frame().is_ok());
+        assert!(budget.on_frame().is_ok());
+        let exceeded = budget.on_frame().unwrap_err();
+        assert!(matches!(exceeded.budget, Kind::Frames));
+        assert_eq!(exceeded.limit, 2);
+
+        // budgets are consumed across multiple frames
+        assert!(budget.on_ack_ranges(6).is_ok());
+        assert!(budget.on_ack_ranges(4).is_ok());
+        assert!(budget.on_ack_ranges(1).is_err());
+
+        assert!(budget.on_crypto_bytes(100).is_ok());
+        assert!(budget.on_crypto_bytes(1).is_err());
+    }
+}
@@ -3,16 +3,21 @@
 
 use crate::{
     ack::AckManager,
-    connection::{self, limits::Limits},
+    connection::{
+        self,
+        compatibility::{self, Policy as _, Relaxations},
+        limits::Limits,
+    },
     endpoint, path,
     space::{
         datagram, keep_alive::KeepAlive, ApplicationSpace, HandshakeSpace, HandshakeStatus,
-        InitialSpace,
+        InitialPacketNumbers, InitialSpace, TxPacketNumbers,
     },
     stream,
 };
+use alloc::{boxed::Box, vec::Vec};
 use bytes::Bytes;
-use core::{ops::Not, task::Waker};
+use core::{ops::Not, task::Waker, time::Duration};
 use s2n_codec::{DecoderBuffer, DecoderValue};
 use s2n_quic_core::{
     ack,
@@ -46,6 +51,8 @@ pub struct SessionContext<'a, Config: endpoint::Config, Pub: event::ConnectionPu
     pub now: Timestamp,
     pub initial_cid: &'a InitialId,
     pub retry_cid: Option<&'a PeerId>,
+    pub compatibility: &'a Config::CompatibilityPolicy,
+    pub initial_packet_numbers: InitialPacketNumbers,
     pub path_manager: &'a mut path::Manager<Config>,
     pub initial: &'a mut Option<Box<InitialSpace<Config>>>,
     pub handshake: &'a mut Option<Box<HandshakeSpace<Config>>>,
@@ -69,6 +76,7 @@ impl<Config: endpoint::Config, Pub: event::ConnectionPublisher> SessionContext<'
     fn on_server_params(
         &mut self,
         decoder: DecoderBuffer,
+        relaxations: Relaxations,
     ) -> Result<
         (
             InitialFlowControlLimits,
@@ -101,12 +109,11 @@ impl<Config: endpoint::Config, Pub: event::ConnectionPublisher> SessionContext<'
         //= https://www.rfc-editor.org/rfc/rfc9000#section-7.3
         //# An endpoint MUST treat the following as a connection error of type
         //# TRANSPORT_PARAMETER_ERROR or PROTOCOL_VIOLATION:
+        let peer_connection_id = self.path_manager.active_path().peer_connection_id;
         self.validate_initial_source_connection_id(
             &peer_parameters.initial_source_connection_id,
-            self.path_manager
-                .active_path()
-                .peer_connection_id
-                .as_bytes(),
+            peer_connection_id.as_bytes(),
+            relaxations,
         )?;
 
         match (self.retry_cid, peer_parameters.retry_source_connection_id) {
@@ -158,6 +165,13 @@ impl<Config: endpoint::Config, Pub: event::ConnectionPublisher> SessionContext<'
                 return Err(transport::Error::TRANSPORT_PARAMETER_ERROR
                     .with_reason("original_destination_connection_id mismatch"));
             }
+        } else if relaxations.missing_original_destination_connection_id() {
+            self.publisher.on_compatibility_relaxation_applied(
+                event::builder::CompatibilityRelaxationApplied {
+                    relaxation:
+                        event::builder::CompatibilityRelaxation::MissingOriginalDestinationConnectionId,
+                },
+            );
         } else {
             //= https://www.rfc-editor.org/rfc/rfc9000#section-7.3
             //# An endpoint MUST treat the absence of the
@@ -184,6 +198,8 @@ impl<Config: endpoint::Config, Pub: event::ConnectionPublisher> SessionContext<'
 
         // Load the peer's transport parameters into the connection's limits
         self.limits.load_peer(&peer_parameters);
+        self.path_manager
+            .on_peer_max_udp_payload_size(peer_parameters.max_udp_payload_size.as_u64());
 
         let initial_flow_control_limits = peer_parameters.flow_control_limits();
         let active_connection_id_limit = peer_parameters.active_connection_id_limit;
@@ -214,6 +230,7 @@ impl<Config: endpoint::Config, Pub: event::ConnectionPublisher> SessionContext<'
     fn on_client_params(
         &mut self,
         decoder: DecoderBuffer,
+        relaxations: Relaxations,
     ) -> Result<
         (
             InitialFlowControlLimits,
@@ -246,16 +263,17 @@ impl<Config: endpoint::Config, Pub: event::ConnectionPublisher> SessionContext<'
         //= https://www.rfc-editor.org/rfc/rfc9000#section-7.3
         //# An endpoint MUST treat the following as a connection error of type
         //# TRANSPORT_PARAMETER_ERROR or PROTOCOL_VIOLATION:
+        let peer_connection_id = self.path_manager.active_path().peer_connection_id;
         self.validate_initial_source_connection_id(
             &peer_parameters.initial_source_connection_id,
-            self.path_manager
-                .active_path()
-                .peer_connection_id
-                .as_bytes(),
+            peer_connection_id.as_bytes(),
+            relaxations,
         )?;
 
         // Load the peer's transport parameters into the connection's limits
         self.limits.load_peer(&peer_parameters);
+        self.path_manager
+            .on_peer_max_udp_payload_size(peer_parameters.max_udp_payload_size.as_u64());
 
         let initial_flow_control_limits = peer_parameters.flow_control_limits();
         let active_connection_id_limit = peer_parameters.active_connection_id_limit;
@@ -286,9 +304,10 @@ impl<Config: endpoint::Config, Pub: event::ConnectionPublisher> SessionContext<'
     // When the endpoint is a Client, this is the randomly generated
     // initial_connection_id which is locally generated for the first Initial packet.
     fn validate_initial_source_connection_id(
-        &self,
+        &mut self,
         peer_value: &Option<InitialSourceConnectionId>,
         expected_value: &[u8],
+        relaxations: Relaxations,
     ) -> Result<(), transport::Error> {
         //= https://www.rfc-editor.org/rfc/rfc9000#section-7.3
         //# * a mismatch between values received from a peer in these transport
@@ -305,6 +324,13 @@ impl<Config: endpoint::Config, Pub: event::ConnectionPublisher> SessionContext<'
                 return Err(transport::Error::TRANSPORT_PARAMETER_ERROR
                     .with_reason("initial_source_connection_id mismatch"));
             }
+        } else if relaxations.missing_initial_source_connection_id() {
+            self.publisher.on_compatibility_relaxation_applied(
+                event::builder::CompatibilityRelaxationApplied {
+                    relaxation:
+                        event::builder::CompatibilityRelaxation::MissingInitialSourceConnectionId,
+                },
+            );
         } else {
             //= https://www.rfc-editor.org/rfc/rfc9000#section-7.3
             //# An endpoint MUST treat the absence of the
@@ -346,7 +372,11 @@ impl<Config: endpoint::Config, Pub: event::ConnectionPublisher>
         *self.handshake = Some(Box::new(HandshakeSpace::new(
             key,
             header_key,
-            self.now,
+            TxPacketNumbers::new(
+                PacketNumberSpace::Handshake,
+                self.initial_packet_numbers.handshake,
+                self.now,
+            ),
             ack_manager,
         )));
         self.publisher.on_key_update(event::builder::KeyUpdate {
@@ -398,6 +428,18 @@ impl<Config: endpoint::Config, Pub: event::ConnectionPublisher>
             *self.zero_rtt_crypto = None;
         }
 
+        // At this point the server name and application protocol have been negotiated so the
+        // compatibility policy can select the relaxations for the peer
+        let relaxations = {
+            let remote_address = self.path_manager.active_path().remote_address().0;
+            let peer = compatibility::Peer::new(
+                &remote_address,
+                self.server_name.as_ref(),
+                &self.application_protocol[..],
+            );
+            self.compatibility.on_peer(&peer)
+        };
+
         // Parse transport parameters
         let param_decoder = DecoderBuffer::new(application_parameters.transport_parameters);
         let (
@@ -407,20 +449,27 @@ impl<Config: endpoint::Config, Pub: event::ConnectionPublisher>
             max_ack_delay,
             dc_version,
         ) = match Config::ENDPOINT_TYPE {
-            endpoint::Type::Client => self.on_server_params(param_decoder)?,
-            endpoint::Type::Server => self.on_client_params(param_decoder)?,
+            endpoint::Type::Client => self.on_server_params(param_decoder, relaxations)?,
+            endpoint::Type::Server => self.on_client_params(param_decoder, relaxations)?,
         };
 
         self.local_id_registry
             .set_active_connection_id_limit(active_connection_id_limit.as_u64());
 
-        let stream_manager = <Config::StreamManager as stream::Manager>::new(
+        let mut stream_manager = <Config::StreamManager as stream::Manager>::new(
             self.limits,
             Config::ENDPOINT_TYPE,
             self.limits.initial_flow_control_limits(),
             peer_flow_control_limits,
             self.path_manager.active_path().rtt_estimator.min_rtt(),
         );
+        <Config::StreamManager as stream::Manager>::on_mtu_update(
+            &mut stream_manager,
+            self.path_manager
+                .active_path()
+                .mtu_controller
+                .max_datagram_size() as u16,
+        );
 
         let ack_manager = AckManager::new(
             PacketNumberSpace::ApplicationData,
@@ -477,7 +526,11 @@ impl<Config: endpoint::Config, Pub: event::ConnectionPublisher>
         *self.application = Some(Box::new(ApplicationSpace::new(
             key,
             header_key,
-            self.now,
+            TxPacketNumbers::new(
+                PacketNumberSpace::ApplicationData,
+                self.initial_packet_numbers.application,
+                self.now,
+            ),
             stream_manager,
             ack_manager,
             keep_alive,
@@ -533,6 +586,14 @@ impl<Config: endpoint::Config, Pub: event::ConnectionPublisher>
         Ok(())
     }
 
+    fn on_tls_operation(&mut self, operation: tls::Operation, duration: Duration) {
+        self.publisher
+            .on_tls_operation_completed(event::builder::TlsOperationCompleted {
+                operation: operation.into_event(),
+                duration,
+            });
+    }
+
     fn on_handshake_complete(&mut self) -> Result<(), transport::Error> {
         // After the handshake is complete, the handshake crypto stream should be completely
         // finished
@@ -4,11 +4,51 @@
 use s2n_quic_core::{
     ack,
     packet::number::{PacketNumber, PacketNumberSpace},
+    random,
     time::Timestamp,
     transport,
     varint::VarInt,
 };
 
+/// The first packet number sent in each packet number space
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct InitialPacketNumbers {
+    pub initial: VarInt,
+    pub handshake: VarInt,
+    pub application: VarInt,
+}
+
+impl InitialPacketNumbers {
+    /// Chooses a random start for each packet number space, up to and including `max_offset`
+    ///
+    /// All of the spaces start at 0 if `max_offset` is 0.
+    pub fn new<R: random::Generator>(max_offset: u32, random_generator: &mut R) -> Self {
+        if max_offset == 0 {
+            return Self::default();
+        }
+
+        //= https://www.rfc-editor.org/rfc/rfc9000#section-12.3
+        //= type=exception
+        //= reason=Offsetting the initial packet numbers is opt-in and is only used to grease peers
+        //# Packet numbers in each space start
+        //# at packet number 0.
+        let mut offset = || {
+            let mut dest = [0; core::mem::size_of::<u32>()];
+            random_generator.public_random_fill(&mut dest);
+            let rand = u32::from_le_bytes(dest);
+
+            // `max_offset` is validated by the connection limits, so this can't overflow
+            VarInt::from_u32(rand % (max_offset + 1))
+        };
+
+        Self {
+            initial: offset(),
+            handshake: offset(),
+            application: offset(),
+        }
+    }
+}
+
 /// Context for tracking transmission of packet numbers
 #[derive(Debug)]
 pub struct TxPacketNumbers {
@@ -19,11 +59,17 @@ pub struct TxPacketNumbers {
 }
 
 impl TxPacketNumbers {
-    pub fn new(packet_space: PacketNumberSpace, now: Timestamp) -> Self {
-        let initial_packet_number = packet_space.new_packet_number(VarInt::from_u8(0));
+    pub fn new(
+        packet_space: PacketNumberSpace,
+        initial_packet_number: VarInt,
+        now: Timestamp,
+    ) -> Self {
+        // Until the peer acknowledges a packet, it expands packet numbers relative to 0, so they
+        // need to be truncated relative to 0 regardless of where the space starts.
+        let largest_sent_acked = packet_space.new_packet_number(VarInt::from_u8(0));
         Self {
-            largest_sent_acked: (initial_packet_number, now),
-            next: initial_packet_number,
+            largest_sent_acked: (largest_sent_acked, now),
+            next: packet_space.new_packet_number(initial_packet_number),
             skip_packet_number: None,
         }
     }
@@ -159,7 +205,9 @@ impl TxPacketNumbers {
 mod tests {
     use super::*;
     use core::time::Duration;
-    use s2n_quic_core::packet::number::PacketNumberRange;
+    use s2n_quic_core::{
+        connection::limits::MAX_INITIAL_PACKET_NUMBER_OFFSET, packet::number::PacketNumberRange,
+    };
 
     // Test behavior around tracking and clearing skip_packet_number value.
     //
@@ -178,7 +226,8 @@ mod tests {
         let mut ack_set = PacketNumberRange::new(start, end);
 
         let timestamp = unsafe { Timestamp::from_duration(Duration::from_millis(10)) };
-        let mut tx = TxPacketNumbers::new(PacketNumberSpace::ApplicationData, timestamp);
+        let mut tx =
+            TxPacketNumbers::new(PacketNumberSpace::ApplicationData, VarInt::ZERO, timestamp);
         // Set initial tx state. `tx.next` needs to be > than the largest ack received
         tx.next = end.next().unwrap();
 
@@ -203,4 +252,50 @@ mod tests {
         assert!(tx.on_packet_ack(timestamp, &ack_set, skip_plus_one).is_ok());
         assert!(tx.skip_packet_number.is_none());
     }
+
+    #[test]
+    fn initial_packet_numbers_test() {
+        let mut random = random::testing::Generator::default();
+
+        // all of the spaces start at 0 by default
+        assert_eq!(
+            InitialPacketNumbers::new(0, &mut random),
+            InitialPacketNumbers::default()
+        );
+
+        for max_offset in [1, 1000, MAX_INITIAL_PACKET_NUMBER_OFFSET] {
+            let initial_packet_numbers = InitialPacketNumbers::new(max_offset, &mut random);
+            for value in [
+                initial_packet_numbers.initial,
+                initial_packet_numbers.handshake,
+                initial_packet_numbers.application,
+            ] {
+                assert!(value <= VarInt::from_u32(max_offset));
+            }
+        }
+    }
+
+    // Packets sent before the first ACK should be encoded relative to 0
+    #[test]
+    fn initial_packet_number_offset_test() {
+        let timestamp = unsafe { Timestamp::from_duration(Duration::from_millis(10)) };
+        let space = PacketNumberSpace::Initial;
+        let offset = VarInt::from_u32(MAX_INITIAL_PACKET_NUMBER_OFFSET);
+        let tx = TxPacketNumbers::new(space, offset, timestamp);
+
+        assert_eq!(tx.next(), space.new_packet_number(offset));
+        assert_eq!(
+            tx.largest_sent_packet_number_acked(),
+            space.new_packet_number(VarInt::ZERO)
+        );
+
+        let truncated = tx
+            .next()
+            .truncate(tx.largest_sent_packet_number_acked())
+            .unwrap();
+        assert_eq!(
+            truncated.expand(tx.largest_sent_packet_number_acked()),
+            tx.next()
+        );
+    }
 }
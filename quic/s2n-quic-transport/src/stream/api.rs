@@ -4,16 +4,20 @@
 //! Defines the Stream objects that applications are interacting with
 
 use crate::connection::Connection;
+use alloc::boxed::Box;
 use bytes::Bytes;
 use core::{
     fmt,
     future::Future,
+    ops::Range,
     pin::Pin,
     task::{ready, Context, Poll},
+    time::Duration,
 };
+use s2n_quic_core::varint::VarInt;
 pub use s2n_quic_core::{
     application,
-    stream::{ops, StreamError, StreamId, StreamType},
+    stream::{compression, ops, StreamError, StreamId, StreamType},
 };
 
 #[derive(Clone)]
@@ -106,6 +110,31 @@ macro_rules! tx_stream_apis {
                 .into()
         }
 
+        /// Enqueues a chunk of data which expires after `lifetime` for sending it towards the
+        /// peer.
+        ///
+        /// Once the lifetime has passed, the chunk is no longer transmitted or retransmitted and
+        /// the peer is told to skip over it. The chunk is delivered reliably if
+        /// the peer doesn't support partial reliability.
+        ///
+        /// See [`Self::poll_send`] for a description of the return values.
+        pub fn poll_send_expiring(
+            &mut self,
+            chunk: &mut Bytes,
+            lifetime: Duration,
+            cx: &mut Context,
+        ) -> Poll<Result<(), StreamError>> {
+            if chunk.is_empty() {
+                return Poll::Ready(Ok(()));
+            }
+
+            self.tx_request()?
+                .send(core::slice::from_mut(chunk))
+                .with_expiration(lifetime)
+                .poll(Some(cx))?
+                .into()
+        }
+
         /// Enqueues a slice of chunks of data for sending it towards the peer.
         ///
         /// The method will return:
@@ -208,6 +237,20 @@ macro_rules! tx_stream_apis {
             self.tx_request()?.finish().flush().poll(Some(cx))?.into()
         }
 
+        /// Waits for the peer to acknowledge all of the data and the end of the stream
+        ///
+        /// Unlike `poll_close`, this doesn't finish the stream.
+        ///
+        /// The method will return:
+        /// - `Poll::Ready(Ok(()))` if the stream was finished and completely acknowledged.
+        /// - `Poll::Ready(Err(stream_error))` if the stream can't be acknowledged, because the
+        ///   stream had previously entered an error state.
+        /// - `Poll::Pending` if the stream hasn't been finished or acknowledged yet. In this case,
+        ///   the caller should retry after the `Waker` on the provided `Context` is notified.
+        pub fn poll_finished(&mut self, cx: &mut Context) -> Poll<Result<(), StreamError>> {
+            self.tx_request()?.await_finished().poll(Some(cx))?.into()
+        }
+
         /// Initiates a `RESET` on the stream.
         ///
         /// This will close the stream and notify the peer of the provided `error_code`.
@@ -215,6 +258,73 @@ macro_rules! tx_stream_apis {
             self.tx_request()?.reset(error_code).poll(None)?;
             Ok(())
         }
+
+        /// Initiates a `RESET` on the stream after delivering the data up to `reliable_size`.
+        ///
+        /// If the peer doesn't support `RESET_STREAM_AT` frames, this behaves like `reset`.
+        pub fn reset_at(
+            &mut self,
+            error_code: application::Error,
+            reliable_size: VarInt,
+        ) -> Result<(), StreamError> {
+            self.tx_request()?
+                .reset_at(error_code, reliable_size)
+                .poll(None)?;
+            Ok(())
+        }
+
+        /// Sets the maximum amount of time small writes are held back to be combined into fewer
+        /// packets.
+        ///
+        /// See [`ops::tx::Request::coalescing_delay`] for details. A zero delay disables
+        /// coalescing and immediately transmits any data being held.
+        pub fn set_coalescing_delay(&mut self, delay: Duration) -> Result<(), StreamError> {
+            self.tx_request()?.with_coalescing_delay(delay).poll(None)?;
+            Ok(())
+        }
+
+        /// Sets the maximum amount of time a write operation may block before failing.
+        ///
+        /// See [`ops::tx::Request::timeout`] for details. `None` disables the timeout.
+        pub fn set_write_timeout(&mut self, timeout: Option<Duration>) -> Result<(), StreamError> {
+            self.tx_request()?
+                .with_write_timeout(timeout.unwrap_or(Duration::ZERO))
+                .poll(None)?;
+            Ok(())
+        }
+
+        /// Sets the expiration of data which is sent without an explicit one.
+        ///
+        /// See [`ops::tx::Request::default_expiration`] for details. `None` delivers the data
+        /// reliably.
+        pub fn set_default_expiration(
+            &mut self,
+            lifetime: Option<Duration>,
+        ) -> Result<(), StreamError> {
+            self.tx_request()?
+                .with_default_expiration(lifetime.unwrap_or(Duration::ZERO))
+                .poll(None)?;
+            Ok(())
+        }
+
+        /// Sets the maximum amount of unacknowledged data buffered on the stream.
+        ///
+        /// See [`ops::tx::Request::buffer_size`] for details.
+        pub fn set_send_buffer_size(&mut self, size: u32) -> Result<(), StreamError> {
+            self.tx_request()?.with_send_buffer_size(size).poll(None)?;
+            Ok(())
+        }
+
+        /// Compresses all of the following data sent on the stream.
+        ///
+        /// See [`ops::tx::Request::encoder`] for details.
+        pub fn set_encoder(
+            &mut self,
+            encoder: Box<dyn compression::Encoder>,
+        ) -> Result<(), StreamError> {
+            self.tx_request()?.with_encoder(encoder).poll(None)?;
+            Ok(())
+        }
     };
 }
 
@@ -246,6 +356,27 @@ macro_rules! rx_stream_apis {
             .into()
         }
 
+        /// Receives a chunk of data from a partially reliable stream.
+        ///
+        /// Along with the chunk, the method returns the range of stream data which the peer gave
+        /// up on right before it. If the stream finished, the range precedes the end of the
+        /// stream instead. Otherwise, the return values are the same as
+        /// [`Self::poll_receive`].
+        pub fn poll_receive_expiring(
+            &mut self,
+            cx: &mut Context,
+        ) -> Poll<Result<(Option<Range<u64>>, Option<Bytes>), StreamError>> {
+            let mut chunk = Bytes::new();
+            let response = ready!(self
+                .rx_request()?
+                .receive(core::slice::from_mut(&mut chunk))
+                .poll(Some(cx))?
+                .into_poll());
+
+            let chunk = Some(chunk).filter(|_| response.chunks.consumed > 0);
+            Poll::Ready(Ok((response.expired, chunk)))
+        }
+
         /// Receives a slice of chunks of data from the stream.
         ///
         /// The method will return:
@@ -293,6 +424,37 @@ macro_rules! rx_stream_apis {
             self.rx_request()?.stop_sending(error_code).poll(None)?;
             Ok(())
         }
+
+        /// Sets the maximum amount of time a read operation may block before failing.
+        ///
+        /// See [`ops::rx::Request::timeout`] for details. `None` disables the timeout.
+        pub fn set_read_timeout(&mut self, timeout: Option<Duration>) -> Result<(), StreamError> {
+            self.rx_request()?
+                .with_read_timeout(timeout.unwrap_or(Duration::ZERO))
+                .poll(None)?;
+            Ok(())
+        }
+
+        /// Sets the maximum amount of data the peer may send ahead of the application.
+        ///
+        /// See [`ops::rx::Request::buffer_size`] for details.
+        pub fn set_receive_buffer_size(&mut self, size: u32) -> Result<(), StreamError> {
+            self.rx_request()?
+                .with_receive_buffer_size(size)
+                .poll(None)?;
+            Ok(())
+        }
+
+        /// Decompresses all of the data received on the stream.
+        ///
+        /// See [`ops::rx::Request::decoder`] for details.
+        pub fn set_decoder(
+            &mut self,
+            decoder: Box<dyn compression::Decoder>,
+        ) -> Result<(), StreamError> {
+            self.rx_request()?.with_decoder(decoder).poll(None)?;
+            Ok(())
+        }
     };
 }
 
@@ -481,10 +643,54 @@ macro_rules! tx_request_apis {
             self
         }
 
+        pub fn reset_at(
+            &mut self,
+            error_code: application::Error,
+            reliable_size: VarInt,
+        ) -> &mut Self {
+            self.request.reset_at(error_code, reliable_size);
+            self
+        }
+
         pub fn flush(&mut self) -> &mut Self {
             self.request.flush();
             self
         }
+
+        pub fn await_finished(&mut self) -> &mut Self {
+            self.request.await_finished();
+            self
+        }
+
+        pub fn with_coalescing_delay(&mut self, delay: Duration) -> &mut Self {
+            self.request.with_coalescing_delay(delay);
+            self
+        }
+
+        pub fn with_write_timeout(&mut self, timeout: Duration) -> &mut Self {
+            self.request.with_write_timeout(timeout);
+            self
+        }
+
+        pub fn with_expiration(&mut self, lifetime: Duration) -> &mut Self {
+            self.request.with_expiration(lifetime);
+            self
+        }
+
+        pub fn with_default_expiration(&mut self, lifetime: Duration) -> &mut Self {
+            self.request.with_default_expiration(lifetime);
+            self
+        }
+
+        pub fn with_send_buffer_size(&mut self, size: u32) -> &mut Self {
+            self.request.with_send_buffer_size(size);
+            self
+        }
+
+        pub fn with_encoder(&mut self, encoder: Box<dyn compression::Encoder>) -> &mut Self {
+            self.request.with_encoder(encoder);
+            self
+        }
     };
 }
 
@@ -514,6 +720,21 @@ macro_rules! rx_request_apis {
             self.request.stop_sending(error_code);
             self
         }
+
+        pub fn with_read_timeout(&mut self, timeout: Duration) -> &mut Self {
+            self.request.with_read_timeout(timeout);
+            self
+        }
+
+        pub fn with_receive_buffer_size(&mut self, size: u32) -> &mut Self {
+            self.request.with_receive_buffer_size(size);
+            self
+        }
+
+        pub fn with_decoder(&mut self, decoder: Box<dyn compression::Decoder>) -> &mut Self {
+            self.request.with_decoder(decoder);
+            self
+        }
     };
 }
 
@@ -0,0 +1,114 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use alloc::{collections::VecDeque, vec::Vec};
+use s2n_quic_core::{stream::StreamId, varint::VarInt};
+
+/// Tracks the send barriers which are pending on a connection
+///
+/// A barrier records the send offset of each of its streams at the time it was inserted. Data
+/// at or beyond that offset is held back until the data before the offset has been acknowledged
+/// on every stream in the barrier. Streams which weren't opened when the barrier was inserted are
+/// recorded at offset 0, which holds back all of their data.
+///
+/// Barriers are kept in insertion order, which means the first barrier which contains a stream
+/// always has the lowest offset for that stream.
+#[derive(Debug, Default)]
+pub(super) struct SendBarriers {
+    pending: VecDeque<Vec<(StreamId, VarInt)>>,
+}
+
+impl SendBarriers {
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Inserts a barrier after all of the currently pending barriers
+    pub fn insert(&mut self, barrier: Vec<(StreamId, VarInt)>) {
+        if !barrier.is_empty() {
+            self.pending.push_back(barrier);
+        }
+    }
+
+    /// Returns the offset up to which `stream_id` may transmit, if it's held back by a barrier
+    #[inline]
+    pub fn limit(&self, stream_id: StreamId) -> Option<VarInt> {
+        self.pending.iter().find_map(|barrier| {
+            barrier
+                .iter()
+                .find_map(|(id, offset)| (*id == stream_id).then_some(*offset))
+        })
+    }
+
+    /// Removes every barrier which was acknowledged on all of its streams
+    ///
+    /// `is_acknowledged` is called with each stream and offset in the pending barriers. The IDs
+    /// of the streams in the removed barriers are appended to `released` so their limits can be
+    /// updated.
+    pub fn release<F: FnMut(StreamId, VarInt) -> bool>(
+        &mut self,
+        mut is_acknowledged: F,
+        released: &mut Vec<StreamId>,
+    ) {
+        self.pending.retain(|barrier| {
+            if !barrier
+                .iter()
+                .all(|(id, offset)| is_acknowledged(*id, *offset))
+            {
+                return true;
+            }
+
+            released.extend(barrier.iter().map(|(id, _)| *id));
+            false
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use s2n_quic_core::{endpoint, stream::StreamType};
+
+    fn stream(index: u64) -> StreamId {
+        StreamId::nth(endpoint::Type::Client, StreamType::Unidirectional, index).unwrap()
+    }
+
+    #[test]
+    fn release_test() {
+        let a = stream(0);
+        let b = stream(1);
+        let c = stream(2);
+        let offset = VarInt::from_u32;
+
+        let mut barriers = SendBarriers::default();
+        barriers.insert(vec![(a, offset(10)), (b, offset(20))]);
+        barriers.insert(vec![(b, offset(30)), (c, offset(40))]);
+        barriers.insert(vec![]);
+
+        assert_eq!(barriers.limit(a), Some(offset(10)));
+        assert_eq!(barriers.limit(b), Some(offset(20)));
+        assert_eq!(barriers.limit(c), Some(offset(40)));
+
+        let mut released = vec![];
+
+        // only `a` was acknowledged
+        barriers.release(|id, _| id == a, &mut released);
+        assert!(released.is_empty());
+
+        // `b` was acknowledged up to the first barrier
+        barriers.release(
+            |id, offset| id != c && offset <= VarInt::from_u32(20),
+            &mut released,
+        );
+        assert_eq!(released, [a, b]);
+        assert_eq!(barriers.limit(a), None);
+        assert_eq!(barriers.limit(b), Some(offset(30)));
+
+        released.clear();
+        barriers.release(|_, _| true, &mut released);
+        assert_eq!(released, [b, c]);
+        assert!(barriers.is_empty());
+        assert_eq!(barriers.limit(b), None);
+    }
+}
@@ -1,6 +1,7 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
+use alloc::vec::Vec;
 use bytes::Bytes;
 use core::task::Context;
 use s2n_quic_core::{
@@ -64,6 +65,7 @@ pub mod tx {
         reset: Option<application::Error>,
         flush: bool,
         finish: bool,
+        await_finished: bool,
     }
 
     impl<'a> From<&'a ops::tx::Request<'a>> for Request {
@@ -73,6 +75,7 @@ pub mod tx {
                 reset: tx.reset,
                 flush: tx.flush,
                 finish: tx.finish,
+                await_finished: tx.await_finished,
             }
         }
     }
@@ -150,6 +153,7 @@ pub mod tx {
                 .map_or(true, |chunks| chunks.is_empty())
                 && !self.finish
                 && !self.flush
+                && !self.await_finished
                 && context.is_some()
             {
                 if let Ok(response) = response {
@@ -288,6 +292,18 @@ pub mod tx {
                 }
             }
 
+            if self.await_finished {
+                if let Ok(response) = response {
+                    if !response.will_wake && context.is_some() {
+                        assert!(
+                            response.is_finished(),
+                            "awaiting the stream to finish should only return a Finished status; actual: {:?}",
+                            response.status
+                        );
+                    }
+                }
+            }
+
             if self.flush {
                 if let Ok(response) = response {
                     if !response.will_wake && context.is_some() && response.is_open() {
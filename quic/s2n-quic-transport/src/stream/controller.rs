@@ -8,6 +8,7 @@ use self::{local_initiated::LocalInitiated, remote_initiated::RemoteInitiated};
 use crate::{
     connection,
     contexts::OnTransmitError,
+    stream::pressure::LimitPressure,
     transmission,
     transmission::{interest::Provider, WriteContext},
 };
@@ -104,29 +105,86 @@ impl Controller {
     /// `Poll::Pending` is returned when there isn't available capacity to open a stream,
     /// either because of local initiated concurrency limits or the peer's stream limits.
     /// If `Poll::Pending` is returned, the waker in the given `context` will be woken
-    /// when additional stream capacity becomes available.
+    /// when additional stream capacity becomes available. If no `context` is provided,
+    /// the caller is not notified.
+    ///
+    /// An error is returned if the request was blocked for longer than the open stream timeout
+    /// configured on the `open_tokens`.
     pub fn poll_open_local_stream(
         &mut self,
         stream_type: StreamType,
         open_tokens: &mut connection::OpenToken,
-        context: &Context,
-    ) -> Poll<()> {
+        context: Option<&Context>,
+    ) -> Poll<Result<(), connection::Error>> {
         let poll_open = match stream_type {
-            StreamType::Bidirectional => self
-                .local_bidi_controller
-                .poll_open_stream(&mut open_tokens.bidirectional, context),
-            StreamType::Unidirectional => self
-                .local_uni_controller
-                .poll_open_stream(&mut open_tokens.unidirectional, context),
+            StreamType::Bidirectional => self.local_bidi_controller.poll_open_stream(
+                &mut open_tokens.bidirectional,
+                open_tokens.timeout,
+                context,
+            ),
+            StreamType::Unidirectional => self.local_uni_controller.poll_open_stream(
+                &mut open_tokens.unidirectional,
+                open_tokens.timeout,
+                context,
+            ),
         };
 
         // returns Pending if there is no capacity available
-        ready!(poll_open);
+        ready!(poll_open)?;
 
         // only open streams if there is sufficient capacity based on limits
         let direction = self.direction(StreamId::initial(self.local_endpoint_type, stream_type));
         self.on_open_stream(direction);
-        Poll::Ready(())
+        Poll::Ready(Ok(()))
+    }
+
+    /// Returns `true` if opening a local stream of the given type requires additional
+    /// credit from the peer
+    /// Cancels the pending open request of the given type, if any
+    pub fn cancel_open_local_stream(
+        &mut self,
+        stream_type: StreamType,
+        open_tokens: &mut connection::OpenToken,
+    ) {
+        match stream_type {
+            StreamType::Bidirectional => self
+                .local_bidi_controller
+                .cancel_open_stream(&mut open_tokens.bidirectional),
+            StreamType::Unidirectional => self
+                .local_uni_controller
+                .cancel_open_stream(&mut open_tokens.unidirectional),
+        }
+    }
+
+    pub fn is_local_stream_blocked_by_peer(&self, stream_type: StreamType) -> bool {
+        match stream_type {
+            StreamType::Bidirectional => self.local_bidi_controller.is_blocked_by_peer(),
+            StreamType::Unidirectional => self.local_uni_controller.is_blocked_by_peer(),
+        }
+    }
+
+    /// Returns the blocked period if the application has been unable to open a stream of
+    /// the given type due to the peer's limits for longer than the configured threshold
+    pub fn poll_peer_blocked_timeout(
+        &mut self,
+        stream_type: StreamType,
+        now: Timestamp,
+    ) -> Poll<PeerBlocked> {
+        match stream_type {
+            StreamType::Bidirectional => self.local_bidi_controller.poll_peer_blocked_timeout(now),
+            StreamType::Unidirectional => self.local_uni_controller.poll_peer_blocked_timeout(now),
+        }
+    }
+
+    /// Returns the stream usage if the application has opened more streams of the given type
+    /// than the configured percentage of the peer's limit
+    ///
+    /// Each peer limit is only reported once.
+    pub fn poll_limit_pressure(&mut self, stream_type: StreamType) -> Poll<LimitPressure> {
+        match stream_type {
+            StreamType::Bidirectional => self.local_bidi_controller.poll_pressure(),
+            StreamType::Unidirectional => self.local_uni_controller.poll_pressure(),
+        }
     }
 
     /// This method is called when the remote peer wishes to open a new stream.
@@ -238,6 +296,22 @@ impl Controller {
             .update_sync_period(blocked_sync_period);
     }
 
+    /// Updates the percentage of the peer's stream limits which can be used before it's reported
+    pub fn update_pressure_threshold(&mut self, threshold: Option<u8>) {
+        self.local_bidi_controller
+            .update_pressure_threshold(threshold);
+        self.local_uni_controller
+            .update_pressure_threshold(threshold);
+    }
+
+    /// Updates how long the application may be blocked by peer limits before it's reported
+    pub fn update_peer_blocked_threshold(&mut self, threshold: Option<Duration>) {
+        self.local_bidi_controller
+            .update_peer_blocked_threshold(threshold);
+        self.local_uni_controller
+            .update_peer_blocked_threshold(threshold);
+    }
+
     pub fn update_min_rtt(&mut self, min_rtt: Duration, now: Timestamp) {
         self.remote_uni_controller.update_min_rtt(min_rtt, now);
         self.remote_bidi_controller.update_min_rtt(min_rtt, now);
@@ -250,6 +324,12 @@ impl Controller {
             return Ok(());
         }
 
+        let now = context.current_time();
+        self.local_bidi_controller.on_peer_blocked(now);
+        self.local_uni_controller.on_peer_blocked(now);
+        self.local_bidi_controller.on_open_deadline_transmit(now);
+        self.local_uni_controller.on_open_deadline_transmit(now);
+
         let peer_endpoint_type = self.local_endpoint_type.peer_type();
 
         macro_rules! on_transmit {
@@ -337,6 +417,16 @@ impl transmission::interest::Provider for Controller {
     }
 }
 
+/// The application has been unable to open a stream due to the peer's limits for longer than
+/// the configured threshold
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PeerBlocked {
+    /// The cumulative stream limit specified by the peer
+    pub stream_limit: VarInt,
+    /// How long the application has been blocked
+    pub duration: Duration,
+}
+
 #[derive(Debug, Copy, Clone)]
 enum StreamDirection {
     // A bidirectional stream opened by the local application to send
@@ -379,6 +469,13 @@ mod tests {
             }
         }
 
+        pub fn open_request_count(&self, stream_type: StreamType) -> usize {
+            match stream_type {
+                StreamType::Bidirectional => self.local_bidi_controller.open_request_count(),
+                StreamType::Unidirectional => self.local_uni_controller.open_request_count(),
+            }
+        }
+
         pub fn remote_initiated_max_streams_latest_value(&self, stream_type: StreamType) -> VarInt {
             match stream_type {
                 StreamType::Bidirectional => self.remote_bidi_controller.latest_limit(),
@@ -301,7 +301,7 @@ impl Model {
             let res = self.subject.poll_open_local_stream(
                 stream_type,
                 &mut token,
-                &Context::from_waker(&waker),
+                Some(&Context::from_waker(&waker)),
             );
 
             if can_open {
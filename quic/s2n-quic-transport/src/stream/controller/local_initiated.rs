@@ -1,15 +1,18 @@
 // Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
 // SPDX-License-Identifier: Apache-2.0
 
+use super::PeerBlocked;
 use crate::{
-    connection::open_token,
+    connection::{self, open_token},
     contexts::OnTransmitError,
+    stream::pressure::{LimitPressure, Pressure},
     sync::{OnceSync, PeriodicSync, ValueToFrameWriter},
     transmission,
     transmission::WriteContext,
 };
 use core::{
-    task::{Context, Poll, Waker},
+    num::NonZeroU64,
+    task::{ready, Context, Poll, Waker},
     time::Duration,
 };
 use s2n_quic_core::{
@@ -37,7 +40,8 @@ pub(super) struct LocalInitiated<L: LocalLimits, OpenNotify: OpenNotifyBehavior>
     ///
     /// Can be updated when MAX_STREAMS frame is received.
     peer_cumulative_stream_limit: VarInt,
-    wakers: SmallVec<[Waker; WAKERS_INITIAL_CAPACITY]>,
+    /// The requests waiting for stream capacity, sorted by the id of their open token
+    wakers: SmallVec<[Waiter; WAKERS_INITIAL_CAPACITY]>,
     streams_blocked_sync: PeriodicSync<VarInt, StreamsBlockedToFrameWriter>,
     /// opened_streams is needed to track the latest opened stream since
     /// peer_stream_limit is a cumulative limit.
@@ -45,9 +49,61 @@ pub(super) struct LocalInitiated<L: LocalLimits, OpenNotify: OpenNotifyBehavior>
     closed_streams: VarInt,
     /// Keeps track of all of the issued open tokens
     token_counter: open_token::Counter,
-    /// Keeps track of all of the expired open tokens
-    expired_token: open_token::Token,
     open_notify: OpenNotify,
+    /// Set while the application is waiting to open a stream due to the peer's limits
+    peer_blocked: bool,
+    /// The time the connection first observed the application being blocked by the peer
+    peer_blocked_since: Option<Timestamp>,
+    /// How long the application may be blocked by the peer before it's reported
+    peer_blocked_threshold: Option<Duration>,
+    peer_blocked_timer: timer::Timer,
+    /// Reports when the opened streams approach the peer's limit
+    pressure: Pressure,
+    /// Expires when the earliest armed open deadline expires
+    open_deadline_timer: timer::Timer,
+}
+
+/// An application task waiting for stream capacity
+#[derive(Debug)]
+struct Waiter {
+    /// The id of the open token the request is waiting with
+    id: NonZeroU64,
+    waker: Waker,
+    deadline: OpenDeadline,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum OpenDeadline {
+    /// The open request waits indefinitely
+    Disabled,
+    /// The open request is blocked but the deadline hasn't been armed yet
+    ///
+    /// Opening a stream happens outside of the connection's clock, so the deadline is armed on
+    /// the next transmission opportunity.
+    Pending(Duration),
+    /// The open request fails if it is still blocked at the given time
+    Armed(Timestamp),
+    /// The open request exceeded its timeout and no longer waits for capacity
+    ///
+    /// The request is removed once the application observes the error.
+    Expired,
+}
+
+impl OpenDeadline {
+    #[inline]
+    fn new(timeout: Duration) -> Self {
+        if timeout.is_zero() {
+            Self::Disabled
+        } else {
+            Self::Pending(timeout)
+        }
+    }
+
+    /// Returns `true` if the request is still waiting for stream capacity
+    #[inline]
+    fn is_waiting(&self) -> bool {
+        !matches!(self, Self::Expired)
+    }
 }
 
 impl<L: LocalLimits, OpenNotify: OpenNotifyBehavior> LocalInitiated<L, OpenNotify> {
@@ -60,8 +116,13 @@ impl<L: LocalLimits, OpenNotify: OpenNotifyBehavior> LocalInitiated<L, OpenNotif
             opened_streams: VarInt::from_u8(0),
             closed_streams: VarInt::from_u8(0),
             token_counter: open_token::Counter::new(),
-            expired_token: open_token::Token::new(),
             open_notify: Default::default(),
+            peer_blocked: false,
+            peer_blocked_since: None,
+            peer_blocked_threshold: None,
+            peer_blocked_timer: Default::default(),
+            pressure: Default::default(),
+            open_deadline_timer: Default::default(),
         }
     }
 
@@ -79,6 +140,7 @@ impl<L: LocalLimits, OpenNotify: OpenNotifyBehavior> LocalInitiated<L, OpenNotif
 
         // We now have more capacity from the peer so stop sending STREAMS_BLOCKED frames
         self.streams_blocked_sync.stop_sync();
+        self.on_peer_unblocked();
 
         self.wake_unblocked();
     }
@@ -88,24 +150,76 @@ impl<L: LocalLimits, OpenNotify: OpenNotifyBehavior> LocalInitiated<L, OpenNotif
             .update_sync_period(blocked_sync_period);
     }
 
+    pub fn update_peer_blocked_threshold(&mut self, threshold: Option<Duration>) {
+        self.peer_blocked_threshold = threshold;
+    }
+
+    pub fn update_pressure_threshold(&mut self, threshold: Option<u8>) {
+        self.pressure.update_threshold(threshold);
+    }
+
+    /// Returns the number of opened streams if it has crossed the configured percentage of the
+    /// peer's stream limit
+    #[inline]
+    pub fn poll_pressure(&mut self) -> Poll<LimitPressure> {
+        let opened = self.opened_streams;
+        let limit = self.peer_cumulative_stream_limit;
+        self.pressure.poll(opened, limit)
+    }
+
+    /// Polls opening a stream
+    ///
+    /// If no `context` is provided, the caller is not notified when capacity becomes available
+    /// and any request which was previously waiting with the same token is cancelled.
+    /// A non-zero `timeout` fails the request with [`connection::Error::OpenStreamTimeout`] once
+    /// it has been blocked for longer than the timeout. The deadline is fixed when the request
+    /// first blocks, so the timeout passed by later polls of the same request is ignored.
     #[inline]
     pub fn poll_open_stream(
         &mut self,
         open_token: &mut open_token::Token,
-        context: &Context,
-    ) -> Poll<()> {
+        timeout: Duration,
+        context: Option<&Context>,
+    ) -> Poll<Result<(), connection::Error>> {
         if self.available_stream_capacity() < VarInt::from_u32(1) {
-            if let Some(index) = open_token.index(&self.expired_token) {
-                let prev = &self.wakers[index];
+            let Some(context) = context else {
+                // non-blocking opens don't wait for capacity
+                self.cancel_open_stream(open_token);
+
+                //= https://www.rfc-editor.org/rfc/rfc9000#section-4.6
+                //# An endpoint that is unable to open a new stream due to the peer's
+                //# limits SHOULD send a STREAMS_BLOCKED frame (Section 19.14).
+                if self.is_blocked_by_peer() {
+                    self.streams_blocked_sync
+                        .request_delivery(self.peer_cumulative_stream_limit);
+                }
+
+                return Poll::Pending;
+            };
+
+            if let Some(index) = self.waiter_index(open_token) {
+                if self.wakers[index].deadline == OpenDeadline::Expired {
+                    // the request stops waiting once the application observes the error
+                    self.cancel_open_stream(open_token);
+                    return Err(connection::Error::open_stream_timeout()).into();
+                }
+
                 // update the waker if it's changed
-                if !prev.will_wake(context.waker()) {
-                    self.wakers[index].clone_from(context.waker())
+                let waiter = &mut self.wakers[index];
+                if !waiter.waker.will_wake(context.waker()) {
+                    waiter.waker.clone_from(context.waker())
                 }
             } else {
-                // Store a waker that can be woken when we get more credit
-                self.wakers.push(context.waker().clone());
-                // give them a waker to remember their position in the list
+                // give them a token to find their request in the list
                 *open_token = self.token_counter.next();
+                let id = open_token.id().expect("issued tokens always have an id");
+
+                // Store a waker that can be woken when we get more credit
+                self.wakers.push(Waiter {
+                    id,
+                    waker: context.waker().clone(),
+                    deadline: OpenDeadline::new(timeout),
+                });
             }
 
             //= https://www.rfc-editor.org/rfc/rfc9000#section-4.6
@@ -116,18 +230,59 @@ impl<L: LocalLimits, OpenNotify: OpenNotifyBehavior> LocalInitiated<L, OpenNotif
             //# A sender SHOULD send a STREAMS_BLOCKED frame (type=0x16 or 0x17) when
             //# it wishes to open a stream but is unable to do so due to the maximum
             //# stream limit set by its peer; see Section 19.11.
-            if self.peer_capacity() < VarInt::from_u32(1) {
+            if self.is_blocked_by_peer() {
                 self.streams_blocked_sync
-                    .request_delivery(self.peer_cumulative_stream_limit)
+                    .request_delivery(self.peer_cumulative_stream_limit);
+                self.peer_blocked = true;
             }
 
             return Poll::Pending;
         }
 
-        // reset the open token since they're no longer blocked
+        // remove any request the token was waiting with since it's no longer blocked
+        self.cancel_open_stream(open_token);
+
+        Poll::Ready(Ok(()))
+    }
+
+    /// Cancels the open request which is waiting with the given token, if any
+    #[inline]
+    pub fn cancel_open_stream(&mut self, open_token: &mut open_token::Token) {
+        let index = self.waiter_index(open_token);
         open_token.clear();
 
-        Poll::Ready(())
+        let Some(index) = index else {
+            return;
+        };
+        let waiter = self.wakers.remove(index);
+
+        if matches!(waiter.deadline, OpenDeadline::Armed(_)) {
+            self.update_open_deadline_timer();
+        }
+        self.on_waiter_removed();
+    }
+
+    /// Returns the position of the request which is waiting with the given token, if any
+    ///
+    /// Requests which were already woken by new capacity are no longer in the list.
+    #[inline]
+    fn waiter_index(&self, open_token: &open_token::Token) -> Option<usize> {
+        let id = open_token.id()?;
+        self.wakers
+            .binary_search_by_key(&id, |waiter| waiter.id)
+            .ok()
+    }
+
+    /// Ends the blocked period once no more requests are waiting for the peer
+    #[inline]
+    fn on_waiter_removed(&mut self) {
+        if !self
+            .wakers
+            .iter()
+            .any(|waiter| waiter.deadline.is_waiting())
+        {
+            self.on_peer_unblocked();
+        }
     }
 
     #[inline]
@@ -163,27 +318,146 @@ impl<L: LocalLimits, OpenNotify: OpenNotifyBehavior> LocalInitiated<L, OpenNotif
             .saturating_sub(self.opened_streams)
     }
 
+    /// Returns `true` if opening a stream requires additional credit from the peer
+    #[inline]
+    pub fn is_blocked_by_peer(&self) -> bool {
+        self.peer_capacity() < VarInt::from_u32(1)
+    }
+
+    /// Starts measuring how long the application has been blocked by the peer
+    ///
+    /// Opening a stream happens outside of the connection's clock, so the start of the blocked
+    /// period is recorded on the next transmission opportunity, which is requested in order to
+    /// send the STREAMS_BLOCKED frame.
+    #[inline]
+    pub fn on_peer_blocked(&mut self, now: Timestamp) {
+        if !self.peer_blocked || self.peer_blocked_since.is_some() {
+            return;
+        }
+
+        self.peer_blocked_since = Some(now);
+
+        if let Some(threshold) = self.peer_blocked_threshold {
+            self.peer_blocked_timer.set(now + threshold);
+        }
+    }
+
+    #[inline]
+    fn on_peer_unblocked(&mut self) {
+        self.peer_blocked = false;
+        self.peer_blocked_since = None;
+        self.peer_blocked_timer.cancel();
+    }
+
+    /// Arms the deadlines of the open requests which blocked since the last transmission
+    #[inline]
+    pub fn on_open_deadline_transmit(&mut self, now: Timestamp) {
+        let mut armed = false;
+
+        for waiter in self.wakers.iter_mut() {
+            if let OpenDeadline::Pending(timeout) = waiter.deadline {
+                waiter.deadline = OpenDeadline::Armed(now + timeout);
+                armed = true;
+            }
+        }
+
+        if armed {
+            self.update_open_deadline_timer();
+        }
+    }
+
+    /// Returns `true` if an open request is waiting for its deadline to be armed
+    #[inline]
+    fn has_pending_open_deadline(&self) -> bool {
+        self.wakers
+            .iter()
+            .any(|waiter| matches!(waiter.deadline, OpenDeadline::Pending(_)))
+    }
+
+    /// Wakes the open requests which exceeded their deadline so they can fail
+    #[inline]
+    fn on_open_deadline_timeout(&mut self, now: Timestamp) {
+        if self.open_deadline_timer.poll_expiration(now).is_pending() {
+            return;
+        }
+
+        for waiter in self.wakers.iter_mut() {
+            if matches!(waiter.deadline, OpenDeadline::Armed(deadline) if deadline <= now) {
+                waiter.deadline = OpenDeadline::Expired;
+                waiter.waker.wake_by_ref();
+            }
+        }
+
+        self.update_open_deadline_timer();
+    }
+
+    #[inline]
+    fn update_open_deadline_timer(&mut self) {
+        let deadline = self
+            .wakers
+            .iter()
+            .filter_map(|waiter| match waiter.deadline {
+                OpenDeadline::Armed(deadline) => Some(deadline),
+                _ => None,
+            })
+            .min();
+
+        if let Some(deadline) = deadline {
+            self.open_deadline_timer.set(deadline);
+        } else {
+            self.open_deadline_timer.cancel();
+        }
+    }
+
+    /// Returns how long the application has been blocked by the peer, if it exceeds the
+    /// configured threshold
+    ///
+    /// Each blocked period is only reported once.
+    #[inline]
+    pub fn poll_peer_blocked_timeout(&mut self, now: Timestamp) -> Poll<PeerBlocked> {
+        ready!(self.peer_blocked_timer.poll_expiration(now));
+
+        let since = self.peer_blocked_since.unwrap_or(now);
+        Poll::Ready(PeerBlocked {
+            stream_limit: self.peer_cumulative_stream_limit,
+            duration: now.saturating_duration_since(since),
+        })
+    }
+
     /// Wake all wakers
     fn wake_all(&mut self) {
         self.wakers
             .drain(..self.wakers.len())
-            .for_each(|waker| waker.wake())
+            .for_each(|waiter| waiter.waker.wake());
+        self.open_deadline_timer.cancel();
     }
 
     /// Wakes the wakers that have been unblocked by the current amount
     /// of available local stream capacity.
     fn wake_unblocked(&mut self) {
-        let unblocked_wakers_count = self
-            .wakers
-            .len()
-            .min(self.available_stream_capacity().as_u64() as usize);
+        let mut capacity = self.available_stream_capacity().as_u64() as usize;
+        let mut unblocked_wakers_count = 0;
+
+        // requests which exceeded their deadline were already woken and don't use any capacity
+        for waiter in self.wakers.iter() {
+            if waiter.deadline.is_waiting() {
+                if capacity == 0 {
+                    break;
+                }
+                capacity -= 1;
+            }
+            unblocked_wakers_count += 1;
+        }
+
+        if unblocked_wakers_count == 0 {
+            return;
+        }
 
         self.wakers
             .drain(..unblocked_wakers_count)
-            .for_each(|waker| waker.wake());
+            .for_each(|waiter| waiter.waker.wake());
 
-        // keep track of the number of tokens that have expired
-        self.expired_token.expire(unblocked_wakers_count);
+        self.update_open_deadline_timer();
     }
 
     /// Returns the number of streams currently open
@@ -197,9 +471,16 @@ impl<L: LocalLimits, OpenNotify: OpenNotifyBehavior> LocalInitiated<L, OpenNotif
         self.opened_streams
     }
 
+    /// Returns the number of open requests which are tracked by the controller
+    #[cfg(test)]
+    pub fn open_request_count(&self) -> usize {
+        self.wakers.len()
+    }
+
     #[inline]
     pub fn on_timeout(&mut self, now: Timestamp) {
         self.streams_blocked_sync.on_timeout(now);
+        self.on_open_deadline_timeout(now);
     }
 
     #[inline]
@@ -244,6 +525,7 @@ impl<L: LocalLimits, OpenNotify: OpenNotifyBehavior> LocalInitiated<L, OpenNotif
         self.wake_all();
         self.streams_blocked_sync.stop_sync();
         self.open_notify.close();
+        self.on_peer_unblocked();
     }
 
     #[inline]
@@ -268,6 +550,8 @@ impl<L: LocalLimits, OpenNotify: OpenNotifyBehavior> timer::Provider
     #[inline]
     fn timers<Q: timer::Query>(&self, query: &mut Q) -> timer::Result {
         self.streams_blocked_sync.timers(query)?;
+        self.peer_blocked_timer.timers(query)?;
+        self.open_deadline_timer.timers(query)?;
         Ok(())
     }
 }
@@ -280,6 +564,10 @@ impl<L: LocalLimits, OpenNotify: OpenNotifyBehavior> transmission::interest::Pro
         &self,
         query: &mut Q,
     ) -> transmission::interest::Result {
+        // open deadlines are armed on the next transmission
+        if self.has_pending_open_deadline() {
+            query.on_new_data()?;
+        }
         self.streams_blocked_sync.transmission_interest(query)?;
         self.open_notify.transmission_interest(query)?;
         Ok(())
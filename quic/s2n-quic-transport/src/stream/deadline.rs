@@ -0,0 +1,160 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use core::time::Duration;
+use s2n_quic_core::time::{timer, Timer, Timestamp};
+
+/// Fails application operations which are blocked on a stream for longer than a configured
+/// timeout
+///
+/// The deadline starts when an operation first blocks and is cleared as soon as an operation
+/// completes. Blocked operations are woken once the deadline expires and fail with a timeout
+/// error if they still can't make progress.
+#[derive(Debug, Default)]
+pub(super) struct Deadline {
+    /// The maximum amount of time an operation may block. A zero timeout disables the deadline.
+    timeout: Duration,
+    /// Whether an application operation is currently blocked on the stream
+    is_blocked: bool,
+    /// Whether the blocked operation exceeded the timeout
+    is_expired: bool,
+    /// Expires once the blocked operation exceeds the timeout
+    ///
+    /// The API calls don't have access to the current time so the timer is armed on the next
+    /// transmission attempt.
+    timer: Timer,
+}
+
+impl Deadline {
+    #[inline]
+    pub fn set_timeout(&mut self, timeout: Duration) {
+        self.timeout = timeout;
+
+        // restart any pending deadline with the new timeout
+        self.timer.cancel();
+        self.is_expired = false;
+        self.is_blocked &= self.is_enabled();
+    }
+
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        self.timeout > Duration::ZERO
+    }
+
+    /// Called when an application operation blocks on the stream
+    ///
+    /// Returns `true` if the operation exceeded the deadline and should fail.
+    #[inline]
+    pub fn on_blocked(&mut self) -> bool {
+        if self.is_expired {
+            self.on_ready();
+            return true;
+        }
+
+        self.is_blocked = self.is_enabled();
+        false
+    }
+
+    /// Called when an application operation completes without blocking
+    #[inline]
+    pub fn on_ready(&mut self) {
+        self.is_blocked = false;
+        self.is_expired = false;
+        self.timer.cancel();
+    }
+
+    /// Returns `true` if the timer needs to be armed on the next transmission
+    #[inline]
+    pub fn needs_arming(&self) -> bool {
+        self.is_blocked && !self.timer.is_armed()
+    }
+
+    #[inline]
+    pub fn is_armed(&self) -> bool {
+        self.timer.is_armed()
+    }
+
+    #[inline]
+    pub fn on_transmit(&mut self, now: Timestamp) {
+        if self.needs_arming() {
+            self.timer.set(now + self.timeout);
+        }
+    }
+
+    /// Returns `true` if the blocked operation exceeded the deadline and should be woken
+    #[inline]
+    pub fn on_timeout(&mut self, now: Timestamp) -> bool {
+        if self.timer.poll_expiration(now).is_pending() {
+            return false;
+        }
+
+        self.is_blocked = false;
+        self.is_expired = true;
+        true
+    }
+}
+
+impl timer::Provider for Deadline {
+    #[inline]
+    fn timers<Q: timer::Query>(&self, query: &mut Q) -> timer::Result {
+        self.timer.timers(query)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use s2n_quic_core::time::clock::testing as time;
+
+    #[test]
+    fn disabled_test() {
+        let mut deadline = Deadline::default();
+        assert!(!deadline.on_blocked());
+        assert!(!deadline.needs_arming());
+    }
+
+    #[test]
+    fn expiration_test() {
+        let timeout = Duration::from_millis(100);
+        let now = time::now();
+        let mut deadline = Deadline::default();
+        deadline.set_timeout(timeout);
+
+        assert!(!deadline.on_blocked());
+        assert!(deadline.needs_arming());
+        deadline.on_transmit(now);
+        assert!(deadline.is_armed());
+
+        // the deadline is measured from the first time the operation blocked
+        assert!(!deadline.on_blocked());
+        deadline.on_transmit(now + timeout / 2);
+        assert!(!deadline.on_timeout(now + timeout / 2));
+
+        assert!(deadline.on_timeout(now + timeout));
+        assert!(!deadline.is_armed());
+
+        // the operation fails if it still can't make progress
+        assert!(deadline.on_blocked());
+
+        // the next operation starts a new deadline
+        assert!(!deadline.on_blocked());
+        assert!(deadline.needs_arming());
+    }
+
+    #[test]
+    fn ready_test() {
+        let timeout = Duration::from_millis(100);
+        let now = time::now();
+        let mut deadline = Deadline::default();
+        deadline.set_timeout(timeout);
+
+        assert!(!deadline.on_blocked());
+        deadline.on_transmit(now);
+        assert!(deadline.on_timeout(now + timeout));
+
+        // an operation which makes progress after expiring doesn't fail
+        deadline.on_ready();
+        assert!(!deadline.on_blocked());
+    }
+}
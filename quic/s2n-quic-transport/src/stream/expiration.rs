@@ -0,0 +1,297 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use alloc::collections::VecDeque;
+use core::time::Duration;
+use s2n_quic_core::{
+    time::{timer, Timer, Timestamp},
+    varint::VarInt,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Lifetime {
+    /// The data was enqueued but the expiration hasn't been armed yet
+    ///
+    /// The API calls don't have access to the current time so the expiration is armed when the
+    /// connection wakes up to process the write, or on the next transmission attempt, whichever
+    /// comes first.
+    Pending(Duration),
+    /// The data expires at the given time
+    Armed(Timestamp),
+    /// The data expired and no longer needs to be delivered
+    Expired,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Entry {
+    start: VarInt,
+    end: VarInt,
+    lifetime: Lifetime,
+}
+
+/// Tracks the ranges of a partially reliable stream which expire after a configured lifetime
+///
+/// Data without an expiration is never tracked and is always delivered reliably. Since the
+/// peer skips all of the data before the expired offset, a range can only be given up once all
+/// of the data in front of it has either been acknowledged or expired. Expired data is given up
+/// whether or not it was transmitted yet.
+#[derive(Debug, Default)]
+pub(super) struct Expirations {
+    /// The expiring ranges, ordered by their offset
+    entries: VecDeque<Entry>,
+    /// Expires when the earliest armed range expires
+    timer: Timer,
+}
+
+impl Expirations {
+    /// Records that the data in `start..end` expires after `lifetime`
+    #[inline]
+    pub fn push(&mut self, start: VarInt, end: VarInt, lifetime: Duration) {
+        debug_assert!(start < end);
+
+        // merge adjacent writes with the same lifetime, since they expire at the same time
+        if let Some(entry) = self.entries.back_mut() {
+            debug_assert!(entry.end <= start);
+
+            if entry.end == start && entry.lifetime == Lifetime::Pending(lifetime) {
+                entry.end = end;
+                return;
+            }
+        }
+
+        self.entries.push_back(Entry {
+            start,
+            end,
+            lifetime: Lifetime::Pending(lifetime),
+        });
+    }
+
+    /// Stops tracking all of the ranges
+    #[inline]
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.timer.cancel();
+    }
+
+    /// Returns `true` if an expiration still needs to be armed
+    #[inline]
+    pub fn needs_arming(&self) -> bool {
+        self.entries
+            .back()
+            .is_some_and(|entry| matches!(entry.lifetime, Lifetime::Pending(_)))
+    }
+
+    #[inline]
+    pub fn is_armed(&self) -> bool {
+        self.timer.is_armed()
+    }
+
+    /// Starts the lifetime of all of the ranges which were enqueued since the last call
+    #[inline]
+    pub fn arm(&mut self, now: Timestamp) {
+        if !self.needs_arming() {
+            return;
+        }
+
+        // only the most recently enqueued ranges can still be pending
+        for entry in self.entries.iter_mut().rev() {
+            let Lifetime::Pending(lifetime) = entry.lifetime else {
+                break;
+            };
+            entry.lifetime = Lifetime::Armed(now + lifetime);
+        }
+
+        self.update_timer();
+    }
+
+    /// Returns `true` if any of the ranges expired
+    #[inline]
+    pub fn on_timeout(&mut self, now: Timestamp) -> bool {
+        if self.timer.poll_expiration(now).is_pending() {
+            return false;
+        }
+
+        for entry in self.entries.iter_mut() {
+            if matches!(entry.lifetime, Lifetime::Armed(deadline) if deadline <= now) {
+                entry.lifetime = Lifetime::Expired;
+            }
+        }
+
+        self.update_timer();
+
+        true
+    }
+
+    /// Returns the offset before which all of the data can be given up
+    ///
+    /// The ranges are only dropped once `is_acknowledged_up_to` reports them as delivered, since
+    /// the sender may not be able to give up all of the data at once. Callers should keep polling
+    /// until `None` is returned or no more data can be given up.
+    #[inline]
+    pub fn poll_expired(
+        &mut self,
+        is_acknowledged_up_to: impl Fn(VarInt) -> bool,
+    ) -> Option<VarInt> {
+        // the ranges which were delivered or given up don't need to be tracked anymore
+        while self
+            .entries
+            .front()
+            .is_some_and(|entry| is_acknowledged_up_to(entry.end))
+        {
+            self.entries.pop_front();
+        }
+
+        if self.entries.is_empty() {
+            self.timer.cancel();
+            return None;
+        }
+
+        let mut expired: Option<VarInt> = None;
+
+        for entry in self.entries.iter() {
+            if entry.lifetime != Lifetime::Expired {
+                break;
+            }
+
+            // data in front of the range still needs to be delivered reliably
+            let is_reachable = expired.map_or(false, |offset| entry.start <= offset)
+                || is_acknowledged_up_to(entry.start);
+            if !is_reachable {
+                break;
+            }
+
+            expired = Some(entry.end);
+        }
+
+        expired
+    }
+
+    #[inline]
+    fn update_timer(&mut self) {
+        let deadline = self
+            .entries
+            .iter()
+            .filter_map(|entry| match entry.lifetime {
+                Lifetime::Armed(deadline) => Some(deadline),
+                _ => None,
+            })
+            .min();
+
+        if let Some(deadline) = deadline {
+            self.timer.set(deadline);
+        } else {
+            self.timer.cancel();
+        }
+    }
+}
+
+impl timer::Provider for Expirations {
+    #[inline]
+    fn timers<Q: timer::Query>(&self, query: &mut Q) -> timer::Result {
+        self.timer.timers(query)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use s2n_quic_core::time::clock::testing as time;
+
+    fn offset(value: u32) -> VarInt {
+        VarInt::from_u32(value)
+    }
+
+    #[test]
+    fn expiration_test() {
+        let lifetime = Duration::from_millis(100);
+        let now = time::now();
+        let mut expirations = Expirations::default();
+
+        expirations.push(offset(0), offset(10), lifetime);
+        // adjacent writes with the same lifetime are merged
+        expirations.push(offset(10), offset(20), lifetime);
+        assert_eq!(expirations.entries.len(), 1);
+        assert!(expirations.needs_arming());
+        assert!(!expirations.is_armed());
+
+        expirations.arm(now);
+        assert!(!expirations.needs_arming());
+        assert!(expirations.is_armed());
+
+        assert!(!expirations.on_timeout(now + lifetime / 2));
+        assert_eq!(expirations.poll_expired(|o| o == offset(0)), None);
+
+        assert!(expirations.on_timeout(now + lifetime));
+        assert!(!expirations.is_armed());
+
+        assert_eq!(
+            expirations.poll_expired(|o| o == offset(0)),
+            Some(offset(20))
+        );
+
+        // the range is kept until all of it was given up
+        assert_eq!(
+            expirations.poll_expired(|o| o <= offset(15)),
+            Some(offset(20))
+        );
+        assert_eq!(expirations.poll_expired(|o| o <= offset(20)), None);
+        assert!(expirations.entries.is_empty());
+    }
+
+    #[test]
+    fn reliable_data_test() {
+        let lifetime = Duration::from_millis(100);
+        let now = time::now();
+        let mut expirations = Expirations::default();
+
+        // the data in 0..10 is reliable
+        expirations.push(offset(10), offset(20), lifetime);
+        expirations.arm(now);
+        assert!(expirations.on_timeout(now + lifetime));
+
+        // the expired data can't be given up until the reliable data is acknowledged
+        assert_eq!(expirations.poll_expired(|o| o == offset(0)), None);
+        assert_eq!(
+            expirations.poll_expired(|o| o <= offset(10)),
+            Some(offset(20))
+        );
+    }
+
+    #[test]
+    fn acknowledged_test() {
+        let lifetime = Duration::from_millis(100);
+        let now = time::now();
+        let mut expirations = Expirations::default();
+
+        expirations.push(offset(0), offset(10), lifetime);
+        expirations.arm(now);
+
+        // acknowledged ranges are dropped without expiring
+        assert_eq!(expirations.poll_expired(|_| true), None);
+        assert!(expirations.entries.is_empty());
+        assert!(!expirations.is_armed());
+    }
+
+    #[test]
+    fn lifetime_test() {
+        let now = time::now();
+        let mut expirations = Expirations::default();
+
+        expirations.push(offset(0), offset(10), Duration::from_millis(200));
+        expirations.push(offset(10), offset(20), Duration::from_millis(100));
+        assert_eq!(expirations.entries.len(), 2);
+        expirations.arm(now);
+
+        // the later range expires first but can't be given up before the earlier one
+        assert!(expirations.on_timeout(now + Duration::from_millis(100)));
+        assert!(expirations.is_armed());
+        assert_eq!(expirations.poll_expired(|o| o == offset(0)), None);
+
+        assert!(expirations.on_timeout(now + Duration::from_millis(200)));
+        assert_eq!(
+            expirations.poll_expired(|o| o == offset(0)),
+            Some(offset(20))
+        );
+    }
+}
@@ -9,15 +9,17 @@ use crate::{
     recovery::RttEstimator,
     stream::{
         self,
+        barrier::SendBarriers,
         incoming_connection_flow_controller::IncomingConnectionFlowController,
         outgoing_connection_flow_controller::OutgoingConnectionFlowController,
         stream_container::{StreamContainer, StreamContainerIterationResult},
         stream_events::StreamEvents,
         stream_impl::StreamConfig,
-        StreamError, StreamTrait,
+        LimitPressure, PeerBlocked, StreamError, StreamTrait,
     },
     transmission::{self, interest::Provider as _},
 };
+use alloc::vec::Vec;
 use core::{
     task::{ready, Context, Poll, Waker},
     time::Duration,
@@ -27,10 +29,11 @@ use s2n_quic_core::{
     connection::error::Error,
     endpoint,
     frame::{
-        stream::StreamRef, DataBlocked, MaxData, MaxStreamData, MaxStreams, ResetStream,
-        StopSending, StreamDataBlocked, StreamsBlocked,
+        stream::StreamRef, DataBlocked, ExpiredStreamData, MaxData, MaxStreamData, MaxStreams,
+        ResetStream, ResetStreamAt, StopSending, StreamDataBlocked, StreamsBlocked,
     },
     packet::number::PacketNumberSpace,
+    path::MINIMUM_MAX_DATAGRAM_SIZE,
     stream::{iter::StreamIter, ops, StreamId, StreamType},
     time::{timer, Timestamp},
     transport::{self, parameters::InitialFlowControlLimits},
@@ -187,6 +190,16 @@ pub struct StreamManagerState<S> {
     /// Limits for the Stream manager. Since only Stream limits are utilized at
     /// the moment we only store those
     stream_limits: stream::Limits,
+    /// Whether both peers negotiated support for `RESET_STREAM_AT` frames
+    reset_stream_at_enabled: bool,
+    /// Whether both peers negotiated support for partially reliable streams
+    partial_reliability_enabled: bool,
+    /// The maximum datagram size of the active path
+    max_datagram_size: u16,
+    /// The send barriers which are holding back streams
+    send_barriers: SendBarriers,
+    /// Set if any of the streams needs to be notified when the connection wakes up
+    needs_wakeup: bool,
 }
 
 impl<S: StreamTrait> StreamManagerState<S> {
@@ -238,7 +251,7 @@ impl<S: StreamTrait> StreamManagerState<S> {
             "Receive window must not exceed 32bit range"
         );
 
-        self.streams.insert_stream(S::new(StreamConfig {
+        let mut stream = S::new(StreamConfig {
             incoming_connection_flow_controller: self.incoming_connection_flow_controller.clone(),
             outgoing_connection_flow_controller: self.outgoing_connection_flow_controller.clone(),
             local_endpoint_type: self.local_endpoint_type,
@@ -247,7 +260,24 @@ impl<S: StreamTrait> StreamManagerState<S> {
             desired_flow_control_window: initial_receive_window.as_u64() as u32,
             initial_send_window,
             max_send_buffer_size: self.stream_limits.max_send_buffer_size.as_u32(),
-        }));
+            reset_stream_at_enabled: self.reset_stream_at_enabled,
+            partial_reliability_enabled: self.partial_reliability_enabled,
+            max_datagram_size: self.max_datagram_size,
+        });
+
+        // streams which were included in a barrier before they were opened start out held back
+        if let Some(limit) = self.send_barriers.limit(stream_id) {
+            stream.set_send_barrier(Some(limit));
+        }
+
+        self.streams.insert_stream(stream);
+    }
+
+    /// Returns `true` if `stream_id` has not been opened yet
+    fn is_unopened(&mut self, stream_id: StreamId) -> bool {
+        self.next_stream_ids
+            .get_mut(stream_id.initiator(), stream_id.stream_type())
+            .map_or(false, |next_id| stream_id >= next_id)
     }
 
     /// Opens a Stream which is referenced in a frame if it has not yet been
@@ -337,7 +367,7 @@ impl<S: StreamTrait> StreamManagerState<S> {
         &mut self,
         stream_type: StreamType,
         open_token: &mut connection::OpenToken,
-        context: &Context,
+        context: Option<&Context>,
     ) -> Poll<Result<StreamId, connection::Error>> {
         let first_unopened_id = self
             .next_stream_ids
@@ -355,7 +385,7 @@ impl<S: StreamTrait> StreamManagerState<S> {
                 .poll_open_local_stream(stream_type, open_token, context);
 
         // returns Pending if there is no capacity available
-        ready!(poll_open);
+        ready!(poll_open)?;
 
         self.insert_stream(first_unopened_id);
         Poll::Ready(Ok(first_unopened_id))
@@ -417,7 +447,15 @@ impl<S: StreamTrait> StreamManagerState<S> {
 /// `AbstractStreamManager` is parameterized over the `Stream` type.
 #[derive(Debug)]
 pub struct AbstractStreamManager<S> {
-    pub(super) inner: StreamManagerState<S>,
+    /// The state of all of the streams on the connection
+    ///
+    /// This is `None` for datagram-only connections, which skip all of the stream processing and
+    /// don't pay the memory cost of the state.
+    pub(super) inner: Option<Box<StreamManagerState<S>>>,
+    /// The type of our local endpoint (client or server)
+    local_endpoint_type: endpoint::Type,
+    /// The error passed to the `close()` call of a datagram-only connection
+    datagram_only_close_reason: Option<connection::Error>,
     last_blocked_sync_period: Duration,
     last_min_rtt: Duration,
 }
@@ -432,16 +470,18 @@ impl<S: 'static + StreamTrait> AbstractStreamManager<S> {
         &mut self,
         stream_type: StreamType,
     ) -> Result<Option<StreamId>, connection::Error> {
+        let Some(inner) = self.inner.as_deref_mut() else {
+            return Ok(None);
+        };
+
         // Check if the Stream exists
-        let next_id_to_accept = self
-            .inner
+        let next_id_to_accept = inner
             .accept_state
             .next_stream_id(stream_type)
             .ok_or_else(connection::Error::stream_id_exhausted)?;
 
-        if self.inner.streams.contains(next_id_to_accept) {
-            *self.inner.accept_state.next_stream_mut(stream_type) =
-                next_id_to_accept.next_of_type();
+        if inner.streams.contains(next_id_to_accept) {
+            *inner.accept_state.next_stream_mut(stream_type) = next_id_to_accept.next_of_type();
             Ok(Some(next_id_to_accept))
         } else {
             Ok(None)
@@ -484,11 +524,21 @@ impl<S: 'static + StreamTrait> AbstractStreamManager<S> {
     where
         F: FnMut(&mut S, &mut StreamEvents) -> Result<(), transport::Error>,
     {
+        let Some(inner) = self.inner.as_deref_mut() else {
+            // Neither peer was given any stream credits so the stream can't exist
+            let error = if stream_id.initiator() == self.local_endpoint_type {
+                transport::Error::STREAM_STATE_ERROR
+            } else {
+                transport::Error::STREAM_LIMIT_ERROR
+            };
+            return Err(error.with_reason("streams are disabled on datagram-only connections"));
+        };
+
         let mut events = StreamEvents::new();
 
         let result = {
             // If Stream handling causes an error, trigger an internal reset
-            self.inner.reset_streams_on_error(|state| {
+            inner.reset_streams_on_error(|state| {
                 // Open streams if necessary
                 state.open_stream_if_necessary(stream_id)?;
                 // Apply the provided function on the Stream.
@@ -527,18 +577,25 @@ impl<S: 'static + StreamTrait> AbstractStreamManager<S> {
     {
         let transmission_snapshot = self.transmission_snapshot();
 
-        let result = self
-            .inner
+        let Some(inner) = self.inner.as_deref_mut() else {
+            return unknown_stream_result;
+        };
+
+        let mut needs_wakeup = false;
+        let result = inner
             .streams
-            .with_stream(stream_id, &mut self.inner.stream_controller, |stream| {
-                func(stream)
+            .with_stream(stream_id, &mut inner.stream_controller, |stream| {
+                let result = func(stream);
+                needs_wakeup = stream.needs_wakeup();
+                result
             })
             .unwrap_or(unknown_stream_result);
+        inner.needs_wakeup |= needs_wakeup;
 
         // A wakeup is only triggered if the the transmission list is
         // now empty, but was previously not. The edge triggered behavior
         // minimizes the amount of necessary wakeups.
-        let require_wakeup = transmission_snapshot != self.transmission_snapshot();
+        let require_wakeup = needs_wakeup || transmission_snapshot != self.transmission_snapshot();
 
         // TODO: This currently wakes the connection task while inside the connection Mutex.
         // It will be better if we return the `Waker` instead and perform the wakeup afterwards.
@@ -550,10 +607,56 @@ impl<S: 'static + StreamTrait> AbstractStreamManager<S> {
     }
 
     #[inline]
+    /// Removes the barriers which were acknowledged and updates the limits of their streams
+    fn release_send_barriers(&mut self) {
+        let Some(inner) = self.inner.as_deref_mut() else {
+            return;
+        };
+
+        if inner.send_barriers.is_empty() {
+            return;
+        }
+
+        let streams = &mut inner.streams;
+        let controller = &mut inner.stream_controller;
+        let send_barriers = &mut inner.send_barriers;
+        let mut released = Vec::new();
+
+        send_barriers.release(
+            |stream_id, offset| {
+                // streams which have been closed don't hold back the barrier. Streams which
+                // weren't opened yet were included at offset 0, which is always acknowledged.
+                streams
+                    .with_stream(stream_id, controller, |stream| {
+                        stream.is_send_acknowledged(offset)
+                    })
+                    .unwrap_or(true)
+            },
+            &mut released,
+        );
+
+        for stream_id in released {
+            let limit = send_barriers.limit(stream_id);
+            streams.with_stream(stream_id, controller, |stream| {
+                stream.set_send_barrier(limit);
+            });
+        }
+    }
+
+    /// Returns the stream state of a connection which isn't datagram-only
+    #[inline]
+    fn state(&mut self) -> &mut StreamManagerState<S> {
+        self.inner
+            .as_deref_mut()
+            .expect("datagram-only connections don't have any stream state")
+    }
+
     fn transmission_snapshot(&self) -> (bool, bool) {
         (
             self.has_transmission_interest(),
-            self.inner.streams.has_transmission_interest(),
+            self.inner
+                .as_ref()
+                .map_or(false, |inner| inner.streams.has_transmission_interest()),
         )
     }
 }
@@ -577,22 +680,41 @@ impl<S: 'static + StreamTrait> stream::Manager for AbstractStreamManager<S> {
             "Receive window must not exceed 32bit range"
         );
 
+        if connection_limits.datagram_only() {
+            return Self {
+                inner: None,
+                local_endpoint_type,
+                datagram_only_close_reason: None,
+                last_blocked_sync_period: Duration::ZERO,
+                last_min_rtt: min_rtt,
+            };
+        }
+
+        let mut stream_controller = stream::Controller::new(
+            local_endpoint_type,
+            initial_peer_limits,
+            initial_local_limits,
+            connection_limits.stream_limits(),
+            min_rtt,
+        );
+        stream_controller
+            .update_peer_blocked_threshold(connection_limits.stream_open_blocked_threshold());
+        stream_controller
+            .update_pressure_threshold(connection_limits.stream_limit_pressure_threshold());
+
+        let mut outgoing_connection_flow_controller =
+            OutgoingConnectionFlowController::new(initial_peer_limits.max_data);
+        outgoing_connection_flow_controller
+            .update_pressure_threshold(connection_limits.connection_data_pressure_threshold());
+
         Self {
-            inner: StreamManagerState {
+            inner: Some(Box::new(StreamManagerState {
                 incoming_connection_flow_controller: IncomingConnectionFlowController::new(
                     initial_local_limits.max_data,
                     initial_local_limits.max_data.as_u64() as u32,
                 ),
-                outgoing_connection_flow_controller: OutgoingConnectionFlowController::new(
-                    initial_peer_limits.max_data,
-                ),
-                stream_controller: stream::Controller::new(
-                    local_endpoint_type,
-                    initial_peer_limits,
-                    initial_local_limits,
-                    connection_limits.stream_limits(),
-                    min_rtt,
-                ),
+                outgoing_connection_flow_controller,
+                stream_controller,
                 streams: StreamContainer::new(connection_limits),
                 next_stream_ids: StreamIdSet::initial(),
                 local_endpoint_type,
@@ -601,22 +723,29 @@ impl<S: 'static + StreamTrait> stream::Manager for AbstractStreamManager<S> {
                 close_reason: None,
                 accept_state: AcceptState::new(local_endpoint_type),
                 stream_limits: connection_limits.stream_limits(),
-            },
+                reset_stream_at_enabled: connection_limits.reset_stream_at_enabled(),
+                partial_reliability_enabled: connection_limits.partial_reliability_enabled(),
+                max_datagram_size: MINIMUM_MAX_DATAGRAM_SIZE,
+                send_barriers: SendBarriers::default(),
+                needs_wakeup: false,
+            })),
+            local_endpoint_type,
+            datagram_only_close_reason: None,
             last_blocked_sync_period: Duration::ZERO,
             last_min_rtt: min_rtt,
         }
     }
 
     fn incoming_bytes_progressed(&self) -> VarInt {
-        self.inner
-            .incoming_connection_flow_controller
-            .acquired_window()
+        self.inner.as_ref().map_or(VarInt::ZERO, |inner| {
+            inner.incoming_connection_flow_controller.acquired_window()
+        })
     }
 
     fn outgoing_bytes_progressed(&self) -> VarInt {
-        self.inner
-            .outgoing_connection_flow_controller
-            .acquired_window()
+        self.inner.as_ref().map_or(VarInt::ZERO, |inner| {
+            inner.outgoing_connection_flow_controller.acquired_window()
+        })
     }
 
     fn poll_accept(
@@ -637,8 +766,19 @@ impl<S: 'static + StreamTrait> stream::Manager for AbstractStreamManager<S> {
             };
         }
 
+        // The peer is unable to open any streams on datagram-only connections
+        if self.inner.is_none() {
+            return match self
+                .datagram_only_close_reason
+                .map(Error::into_accept_error)
+            {
+                Some(Err(err)) => Err(err).into(),
+                _ => Ok(None).into(),
+            };
+        }
+
         // Clear a stored Waker
-        with_stream_type!(|stream_type| *self.inner.accept_state.waker_mut(stream_type) = None);
+        with_stream_type!(|stream_type| *self.state().accept_state.waker_mut(stream_type) = None);
 
         // If the connection was closed we still allow the application to accept
         // Streams which are already known to the StreamManager.
@@ -656,7 +796,7 @@ impl<S: 'static + StreamTrait> stream::Manager for AbstractStreamManager<S> {
             return Ok(Some(stream_id)).into();
         });
 
-        if let Some(close_reason) = self.inner.close_reason {
+        if let Some(close_reason) = self.state().close_reason {
             match Error::into_accept_error(close_reason) {
                 Ok(_) => return Ok(None).into(),
                 Err(err) => return Err(err).into(),
@@ -665,7 +805,7 @@ impl<S: 'static + StreamTrait> stream::Manager for AbstractStreamManager<S> {
 
         // Store the `Waker` for notifying the application if we accept a Stream
         with_stream_type!(
-            |stream_type| *self.inner.accept_state.waker_mut(stream_type) =
+            |stream_type| *self.state().accept_state.waker_mut(stream_type) =
                 Some(context.waker().clone())
         );
 
@@ -677,26 +817,50 @@ impl<S: 'static + StreamTrait> stream::Manager for AbstractStreamManager<S> {
         stream_type: StreamType,
         open_token: &mut connection::OpenToken,
         api_call_context: &mut ConnectionApiCallContext,
-        context: &Context,
+        context: Option<&Context>,
     ) -> Poll<Result<StreamId, connection::Error>> {
+        let Some(inner) = self.inner.as_deref_mut() else {
+            if let Some(error) = self.datagram_only_close_reason {
+                return Err(error).into();
+            }
+
+            // The peer was never given any stream credits so the stream would never open
+            return Err(connection::Error::invalid_configuration(
+                "streams are disabled on datagram-only connections",
+            ))
+            .into();
+        };
+
         // If StreamManager was closed, return the error
-        if let Some(error) = self.inner.close_reason {
+        if let Some(error) = inner.close_reason {
             return Err(error).into();
         }
 
         let transmission_snapshot = self.transmission_snapshot();
 
-        let first_unopened_id =
-            ready!(self
-                .inner
-                .poll_open_local_stream(stream_type, open_token, context))?;
+        let poll_open = self
+            .state()
+            .poll_open_local_stream(stream_type, open_token, context);
+
+        if poll_open.is_pending() {
+            // The open deadline of a blocked request is armed on the next transmission
+            if !open_token.timeout.is_zero()
+                && transmission_snapshot != self.transmission_snapshot()
+            {
+                api_call_context.wakeup_handle().wakeup();
+            }
+
+            return Poll::Pending;
+        }
+
+        let first_unopened_id = ready!(poll_open)?;
 
         // Increase the next utilized Stream ID
+        let local_endpoint_type = self.local_endpoint_type;
         *self
-            .inner
+            .state()
             .next_stream_ids
-            .get_mut(self.inner.local_endpoint_type, stream_type) =
-            first_unopened_id.next_of_type();
+            .get_mut(local_endpoint_type, stream_type) = first_unopened_id.next_of_type();
 
         // A wakeup is only triggered if we now have transmission interest, but previously did not.
         // The edge triggered behavior minimizes the amount of necessary wakeups.
@@ -709,53 +873,115 @@ impl<S: 'static + StreamTrait> stream::Manager for AbstractStreamManager<S> {
         Ok(first_unopened_id).into()
     }
 
+    fn cancel_open_local_stream(
+        &mut self,
+        stream_type: StreamType,
+        open_token: &mut connection::OpenToken,
+    ) {
+        if let Some(inner) = self.inner.as_deref_mut() {
+            inner
+                .stream_controller
+                .cancel_open_local_stream(stream_type, open_token);
+        }
+    }
+
+    fn is_local_stream_blocked_by_peer(&self, stream_type: StreamType) -> bool {
+        self.inner.as_ref().map_or(false, |inner| {
+            inner
+                .stream_controller
+                .is_local_stream_blocked_by_peer(stream_type)
+        })
+    }
+
+    fn poll_peer_blocked_timeout(
+        &mut self,
+        stream_type: StreamType,
+        now: Timestamp,
+    ) -> Poll<PeerBlocked> {
+        let Some(inner) = self.inner.as_deref_mut() else {
+            return Poll::Pending;
+        };
+
+        inner
+            .stream_controller
+            .poll_peer_blocked_timeout(stream_type, now)
+    }
+
+    fn poll_stream_limit_pressure(&mut self, stream_type: StreamType) -> Poll<LimitPressure> {
+        let Some(inner) = self.inner.as_deref_mut() else {
+            return Poll::Pending;
+        };
+
+        inner.stream_controller.poll_limit_pressure(stream_type)
+    }
+
+    fn poll_connection_data_pressure(&mut self) -> Poll<LimitPressure> {
+        let Some(inner) = self.inner.as_deref_mut() else {
+            return Poll::Pending;
+        };
+
+        inner.outgoing_connection_flow_controller.poll_pressure()
+    }
+
     fn on_packet_ack<A: ack::Set>(&mut self, ack_set: &A) {
-        self.inner
+        let Some(inner) = self.inner.as_deref_mut() else {
+            return;
+        };
+
+        inner
             .incoming_connection_flow_controller
             .on_packet_ack(ack_set);
-        self.inner
+        inner
             .outgoing_connection_flow_controller
             .on_packet_ack(ack_set);
-        self.inner.stream_controller.on_packet_ack(ack_set);
+        inner.stream_controller.on_packet_ack(ack_set);
 
-        self.inner.streams.iterate_frame_delivery_list(
-            &mut self.inner.stream_controller,
-            |stream| {
+        inner
+            .streams
+            .iterate_frame_delivery_list(&mut inner.stream_controller, |stream| {
                 // We have to wake inside the lock, since `StreamEvent`s has no capacity
                 // to carry wakers in another iteration
                 let mut events = StreamEvents::new();
                 stream.on_packet_ack(ack_set, &mut events);
                 events.wake_all();
-            },
-        );
+            });
+
+        self.release_send_barriers();
     }
 
     fn on_packet_loss<A: ack::Set>(&mut self, ack_set: &A) {
-        self.inner
+        let Some(inner) = self.inner.as_deref_mut() else {
+            return;
+        };
+
+        inner
             .incoming_connection_flow_controller
             .on_packet_loss(ack_set);
-        self.inner
+        inner
             .outgoing_connection_flow_controller
             .on_packet_loss(ack_set);
-        self.inner.stream_controller.on_packet_loss(ack_set);
+        inner.stream_controller.on_packet_loss(ack_set);
 
-        self.inner.streams.iterate_frame_delivery_list(
-            &mut self.inner.stream_controller,
-            |stream| {
+        inner
+            .streams
+            .iterate_frame_delivery_list(&mut inner.stream_controller, |stream| {
                 // We have to wake inside the lock, since `StreamEvent`s has no capacity
                 // to carry wakers in another iteration
                 let mut events = StreamEvents::new();
                 stream.on_packet_loss(ack_set, &mut events);
                 events.wake_all();
-            },
-        );
+            });
     }
 
     fn on_rtt_update(&mut self, rtt_estimator: &RttEstimator, now: Timestamp) {
+        if self.inner.is_none() {
+            return;
+        }
+
         {
             let new_min_rtt = rtt_estimator.min_rtt();
             if new_min_rtt != self.last_min_rtt {
-                self.inner
+                self.state()
                     .stream_controller
                     .update_min_rtt(new_min_rtt, now);
                 self.last_min_rtt = new_min_rtt;
@@ -783,55 +1009,129 @@ impl<S: 'static + StreamTrait> stream::Manager for AbstractStreamManager<S> {
 
         self.last_blocked_sync_period = blocked_sync_period;
 
-        self.inner
+        let inner = self.state();
+        inner
             .stream_controller
             .update_blocked_sync_period(blocked_sync_period);
-        self.inner
+        inner
             .outgoing_connection_flow_controller
             .update_blocked_sync_period(blocked_sync_period);
-        self.inner.streams.iterate_stream_flow_credits_list(
-            &mut self.inner.stream_controller,
-            |stream| {
+        inner
+            .streams
+            .iterate_stream_flow_credits_list(&mut inner.stream_controller, |stream| {
                 stream.update_blocked_sync_period(blocked_sync_period);
                 StreamContainerIterationResult::Continue
-            },
-        );
+            });
     }
 
     fn on_timeout(&mut self, now: Timestamp) {
-        self.inner.stream_controller.on_timeout(now);
-        self.inner
-            .outgoing_connection_flow_controller
-            .on_timeout(now);
-        self.inner.streams.iterate_stream_flow_credits_list(
-            &mut self.inner.stream_controller,
-            |stream| {
-                stream.on_timeout(now);
+        let Some(inner) = self.inner.as_deref_mut() else {
+            return;
+        };
+
+        inner.stream_controller.on_timeout(now);
+        inner.outgoing_connection_flow_controller.on_timeout(now);
+        inner
+            .streams
+            .iterate_stream_flow_credits_list(&mut inner.stream_controller, |stream| {
+                // We have to wake inside the lock, since `StreamEvent`s has no capacity
+                // to carry wakers in another iteration
+                let mut events = StreamEvents::new();
+                stream.on_timeout(now, &mut events);
+                events.wake_all();
                 StreamContainerIterationResult::Continue
-            },
-        );
+            });
+        inner
+            .streams
+            .iterate_timeout_list(&mut inner.stream_controller, |stream| {
+                // We have to wake inside the lock, since `StreamEvent`s has no capacity
+                // to carry wakers in another iteration
+                let mut events = StreamEvents::new();
+                stream.on_timeout(now, &mut events);
+                events.wake_all();
+            });
+    }
+
+    fn on_mtu_update(&mut self, max_datagram_size: u16) {
+        let Some(inner) = self.inner.as_deref_mut() else {
+            return;
+        };
+
+        if inner.max_datagram_size == max_datagram_size {
+            return;
+        }
+
+        inner.max_datagram_size = max_datagram_size;
+        inner
+            .streams
+            .iterate_streams(&mut inner.stream_controller, |stream| {
+                stream.on_mtu_update(max_datagram_size)
+            });
+    }
+
+    fn on_wakeup(&mut self, now: Timestamp) {
+        let Some(inner) = self.inner.as_deref_mut() else {
+            return;
+        };
+
+        if !core::mem::take(&mut inner.needs_wakeup) {
+            return;
+        }
+
+        inner
+            .streams
+            .iterate_streams(&mut inner.stream_controller, |stream| stream.on_wakeup(now));
+    }
+
+    fn on_hibernate(&mut self) {
+        let Some(inner) = self.inner.as_deref_mut() else {
+            return;
+        };
+
+        inner
+            .streams
+            .iterate_streams(&mut inner.stream_controller, |stream| stream.on_hibernate());
     }
 
     fn close(&mut self, error: connection::Error) {
-        self.inner.close(error, false);
+        match self.inner.as_deref_mut() {
+            Some(inner) => inner.close(error, false),
+            None => {
+                // only the first error is reported
+                self.datagram_only_close_reason.get_or_insert(error);
+            }
+        }
     }
 
     fn close_reason(&self) -> Option<connection::Error> {
-        self.inner.close_reason
+        match self.inner.as_deref() {
+            Some(inner) => inner.close_reason,
+            None => self.datagram_only_close_reason,
+        }
     }
 
     fn flush(&mut self, error: connection::Error) -> Poll<()> {
-        self.inner.flush(error)
+        match self.inner.as_deref_mut() {
+            Some(inner) => inner.flush(error),
+            None => {
+                self.close(error);
+                Poll::Ready(())
+            }
+        }
     }
 
     fn on_transmit<W: WriteContext>(&mut self, context: &mut W) -> Result<(), OnTransmitError> {
-        self.inner
+        let Some(inner) = self.inner.as_deref_mut() else {
+            return Ok(());
+        };
+
+        inner
             .incoming_connection_flow_controller
             .on_transmit(context)?;
-        self.inner
+        inner
             .outgoing_connection_flow_controller
             .on_transmit(context)?;
-        self.inner.stream_controller.on_transmit(context)?;
+        inner.stream_controller.on_transmit(context)?;
 
         // Due to an error we could not transmit all data.
         // We add streams which could not send data back into the
@@ -849,8 +1149,8 @@ impl<S: 'static + StreamTrait> stream::Manager for AbstractStreamManager<S> {
                 transmission::context::RetransmissionContext::new(context);
 
             // Prioritize retransmitting lost data
-            self.inner.streams.send_on_retransmission_list(
-                &mut self.inner.stream_controller,
+            inner.streams.send_on_retransmission_list(
+                &mut inner.stream_controller,
                 |stream: &mut S| {
                     transmit_result = stream.on_transmit(&mut retransmission_context);
                     if transmit_result.is_err() {
@@ -866,8 +1166,8 @@ impl<S: 'static + StreamTrait> stream::Manager for AbstractStreamManager<S> {
         }
 
         if context.transmission_constraint().can_transmit() {
-            self.inner.streams.send_on_transmission_list(
-                &mut self.inner.stream_controller,
+            inner.streams.send_on_transmission_list(
+                &mut inner.stream_controller,
                 |stream: &mut S| {
                     transmit_result = stream.on_transmit(context);
                     if transmit_result.is_err() {
@@ -912,6 +1212,45 @@ impl<S: 'static + StreamTrait> stream::Manager for AbstractStreamManager<S> {
         self.handle_stream_frame(stream_id, |stream, events| stream.on_reset(frame, events))
     }
 
+    fn on_reset_stream_at(&mut self, frame: &ResetStreamAt) -> Result<(), transport::Error> {
+        // The frame is only allowed if we advertised support for it
+        if !self
+            .inner
+            .as_ref()
+            .map_or(false, |inner| inner.reset_stream_at_enabled)
+        {
+            return Err(transport::Error::PROTOCOL_VIOLATION
+                .with_reason("RESET_STREAM_AT frames were not negotiated")
+                .with_frame_type(frame.tag().into()));
+        }
+
+        let stream_id = StreamId::from_varint(frame.stream_id);
+        self.handle_stream_frame(stream_id, |stream, events| {
+            stream.on_reset_at(frame, events)
+        })
+    }
+
+    fn on_expired_stream_data(
+        &mut self,
+        frame: &ExpiredStreamData,
+    ) -> Result<(), transport::Error> {
+        // The frame is only allowed if we advertised support for it
+        if !self
+            .inner
+            .as_ref()
+            .map_or(false, |inner| inner.partial_reliability_enabled)
+        {
+            return Err(transport::Error::PROTOCOL_VIOLATION
+                .with_reason("EXPIRED_STREAM_DATA frames were not negotiated")
+                .with_frame_type(frame.tag()));
+        }
+
+        let stream_id = StreamId::from_varint(frame.stream_id);
+        self.handle_stream_frame(stream_id, |stream, events| {
+            stream.on_expired_stream_data(frame, events)
+        })
+    }
+
     fn on_max_stream_data(&mut self, frame: &MaxStreamData) -> Result<(), transport::Error> {
         let stream_id = StreamId::from_varint(frame.stream_id);
         self.handle_stream_frame(stream_id, |stream, events| {
@@ -927,16 +1266,13 @@ impl<S: 'static + StreamTrait> stream::Manager for AbstractStreamManager<S> {
     }
 
     fn on_max_data(&mut self, frame: MaxData) -> Result<(), transport::Error> {
-        self.inner
-            .outgoing_connection_flow_controller
-            .on_max_data(frame);
+        let Some(inner) = self.inner.as_deref_mut() else {
+            return Ok(());
+        };
 
-        if self
-            .inner
-            .outgoing_connection_flow_controller
-            .available_window()
-            == VarInt::from_u32(0)
-        {
+        inner.outgoing_connection_flow_controller.on_max_data(frame);
+
+        if inner.outgoing_connection_flow_controller.available_window() == VarInt::from_u32(0) {
             return Ok(());
         }
 
@@ -944,9 +1280,9 @@ impl<S: 'static + StreamTrait> stream::Manager for AbstractStreamManager<S> {
         // connection window. As soon as we run out of credits we stop
         // iterating and insert the remaining streams to the end of the list
         // again.
-        let conn_flow = &mut self.inner.outgoing_connection_flow_controller;
-        self.inner.streams.iterate_connection_flow_credits_list(
-            &mut self.inner.stream_controller,
+        let conn_flow = &mut inner.outgoing_connection_flow_controller;
+        inner.streams.iterate_connection_flow_credits_list(
+            &mut inner.stream_controller,
             |stream| {
                 stream.on_connection_window_available();
 
@@ -970,7 +1306,9 @@ impl<S: 'static + StreamTrait> stream::Manager for AbstractStreamManager<S> {
     }
 
     fn on_max_streams(&mut self, frame: &MaxStreams) -> Result<(), transport::Error> {
-        self.inner.stream_controller.on_max_streams(frame);
+        if let Some(inner) = self.inner.as_deref_mut() {
+            inner.stream_controller.on_max_streams(frame);
+        }
 
         Ok(())
     }
@@ -991,18 +1329,76 @@ impl<S: 'static + StreamTrait> stream::Manager for AbstractStreamManager<S> {
     }
 
     fn has_pending_streams(&self) -> bool {
-        self.inner.streams.has_pending_streams()
+        self.inner
+            .as_ref()
+            .map_or(false, |inner| inner.streams.has_pending_streams())
+    }
+
+    fn insert_send_barrier(&mut self, streams: &[StreamId]) {
+        let Some(inner) = self.inner.as_deref_mut() else {
+            return;
+        };
+
+        let mut barrier = Vec::with_capacity(streams.len());
+
+        for &stream_id in streams {
+            if barrier.iter().any(|(id, _)| *id == stream_id) {
+                continue;
+            }
+
+            // we never send on unidirectional streams which were opened by the peer
+            if stream_id.stream_type().is_unidirectional()
+                && stream_id.initiator() != inner.local_endpoint_type
+            {
+                continue;
+            }
+
+            // the stream will be held back from offset 0 once it's opened
+            if inner.is_unopened(stream_id) {
+                barrier.push((stream_id, VarInt::ZERO));
+                continue;
+            }
+
+            // only the first pending barrier for a stream limits its transmissions
+            let is_limited = inner.send_barriers.limit(stream_id).is_some();
+
+            // fully acknowledged streams are still held back, since the data written after the
+            // barrier must wait for the other streams. Closed streams have nothing to hold back.
+            let offset =
+                inner
+                    .streams
+                    .with_stream(stream_id, &mut inner.stream_controller, |stream| {
+                        let offset = stream.send_offset();
+
+                        if !is_limited {
+                            stream.set_send_barrier(Some(offset));
+                        }
+
+                        offset
+                    });
+
+            if let Some(offset) = offset {
+                barrier.push((stream_id, offset));
+            }
+        }
+
+        inner.send_barriers.insert(barrier);
+
+        // a barrier whose streams were all acknowledged already doesn't hold anything back
+        self.release_send_barriers();
     }
 }
 
 impl<S: StreamTrait> timer::Provider for AbstractStreamManager<S> {
     #[inline]
     fn timers<Q: timer::Query>(&self, query: &mut Q) -> timer::Result {
-        self.inner.stream_controller.timers(query)?;
-        self.inner
-            .outgoing_connection_flow_controller
-            .timers(query)?;
-        self.inner.streams.timers(query)?;
+        let Some(inner) = self.inner.as_deref() else {
+            return Ok(());
+        };
+
+        inner.stream_controller.timers(query)?;
+        inner.outgoing_connection_flow_controller.timers(query)?;
+        inner.streams.timers(query)?;
         Ok(())
     }
 }
@@ -1013,12 +1409,16 @@ impl<S: StreamTrait> transmission::interest::Provider for AbstractStreamManager<
         &self,
         query: &mut Q,
     ) -> transmission::interest::Result {
-        self.inner.streams.transmission_interest(query)?;
-        self.inner.stream_controller.transmission_interest(query)?;
-        self.inner
+        let Some(inner) = self.inner.as_deref() else {
+            return Ok(());
+        };
+
+        inner.streams.transmission_interest(query)?;
+        inner.stream_controller.transmission_interest(query)?;
+        inner
             .incoming_connection_flow_controller
             .transmission_interest(query)?;
-        self.inner
+        inner
             .outgoing_connection_flow_controller
             .transmission_interest(query)?;
 
@@ -1028,9 +1428,17 @@ impl<S: StreamTrait> transmission::interest::Provider for AbstractStreamManager<
 
 impl<S: StreamTrait> connection::finalization::Provider for AbstractStreamManager<S> {
     fn finalization_status(&self) -> connection::finalization::Status {
-        if self.inner.close_reason.is_some() && self.inner.streams.nr_active_streams() == 0 {
+        let (is_closed, nr_active_streams) = match self.inner.as_deref() {
+            Some(inner) => (
+                inner.close_reason.is_some(),
+                inner.streams.nr_active_streams(),
+            ),
+            None => (self.datagram_only_close_reason.is_some(), 0),
+        };
+
+        if is_closed && nr_active_streams == 0 {
             connection::finalization::Status::Final
-        } else if self.inner.close_reason.is_some() && self.inner.streams.nr_active_streams() > 0 {
+        } else if is_closed && nr_active_streams > 0 {
             connection::finalization::Status::Draining
         } else {
             connection::finalization::Status::Idle
@@ -1050,7 +1458,7 @@ impl<S: StreamTrait> AbstractStreamManager<S> {
     where
         F: FnOnce(&mut OutgoingConnectionFlowController) -> R,
     {
-        func(&mut self.inner.outgoing_connection_flow_controller)
+        func(&mut self.state().outgoing_connection_flow_controller)
     }
 
     /// Executes the given function using the stream controller
@@ -1058,7 +1466,7 @@ impl<S: StreamTrait> AbstractStreamManager<S> {
     where
         F: FnOnce(&mut stream::Controller) -> R,
     {
-        func(&mut self.inner.stream_controller)
+        func(&mut self.state().stream_controller)
     }
 
     /// Asserts that a Stream with the given ID exists, and executes the provided
@@ -1067,9 +1475,10 @@ impl<S: StreamTrait> AbstractStreamManager<S> {
     where
         F: FnOnce(&mut S) -> R,
     {
-        self.inner
+        let inner = self.state();
+        inner
             .streams
-            .with_stream(stream_id, &mut self.inner.stream_controller, func)
+            .with_stream(stream_id, &mut inner.stream_controller, func)
             .expect("Stream is open")
     }
 
@@ -1077,9 +1486,12 @@ impl<S: StreamTrait> AbstractStreamManager<S> {
     /// [`StreamManager`].
     pub fn active_streams(&mut self) -> Vec<StreamId> {
         let mut results = Vec::new();
-        self.inner
+        let Some(inner) = self.inner.as_deref_mut() else {
+            return results;
+        };
+        inner
             .streams
-            .iterate_streams(&mut self.inner.stream_controller, |stream| {
+            .iterate_streams(&mut inner.stream_controller, |stream| {
                 results.push(stream.stream_id())
             });
         results
@@ -1089,8 +1501,9 @@ impl<S: StreamTrait> AbstractStreamManager<S> {
     /// connection flow control credits.
     pub fn streams_waiting_for_connection_flow_control_credits(&mut self) -> Vec<StreamId> {
         let mut results = Vec::new();
-        self.inner.streams.iterate_connection_flow_credits_list(
-            &mut self.inner.stream_controller,
+        let inner = self.state();
+        inner.streams.iterate_connection_flow_credits_list(
+            &mut inner.stream_controller,
             |stream| {
                 results.push(stream.stream_id());
                 StreamContainerIterationResult::Continue
@@ -1103,12 +1516,12 @@ impl<S: StreamTrait> AbstractStreamManager<S> {
     /// delivery notifications.
     pub fn streams_waiting_for_delivery_notifications(&mut self) -> Vec<StreamId> {
         let mut results = Vec::new();
-        self.inner.streams.iterate_frame_delivery_list(
-            &mut self.inner.stream_controller,
-            |stream| {
+        let inner = self.state();
+        inner
+            .streams
+            .iterate_frame_delivery_list(&mut inner.stream_controller, |stream| {
                 results.push(stream.stream_id());
-            },
-        );
+            });
         results
     }
 
@@ -1116,9 +1529,10 @@ impl<S: StreamTrait> AbstractStreamManager<S> {
     /// transmission.
     pub fn streams_waiting_for_transmission(&mut self) -> Vec<StreamId> {
         let mut results = Vec::new();
-        self.inner
+        let inner = self.state();
+        inner
             .streams
-            .iterate_transmission_list(&mut self.inner.stream_controller, |stream| {
+            .iterate_transmission_list(&mut inner.stream_controller, |stream| {
                 results.push(stream.stream_id());
                 StreamContainerIterationResult::Continue
             });
@@ -1129,13 +1543,13 @@ impl<S: StreamTrait> AbstractStreamManager<S> {
     /// retransmission.
     pub fn streams_waiting_for_retransmission(&mut self) -> Vec<StreamId> {
         let mut results = Vec::new();
-        self.inner.streams.iterate_retransmission_list(
-            &mut self.inner.stream_controller,
-            |stream| {
+        let inner = self.state();
+        inner
+            .streams
+            .iterate_retransmission_list(&mut inner.stream_controller, |stream| {
                 results.push(stream.stream_id());
                 StreamContainerIterationResult::Continue
-            },
-        );
+            });
         results
     }
 }
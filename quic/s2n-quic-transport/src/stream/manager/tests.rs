@@ -36,8 +36,9 @@ use s2n_quic_core::{
     ack::Set as AckSet,
     application::Error as ApplicationErrorCode,
     frame::{
-        stream::StreamRef, DataBlocked, Frame, MaxData, MaxStreamData, MaxStreams, ResetStream,
-        StopSending, Stream as StreamFrame, StreamDataBlocked, StreamsBlocked,
+        stream::StreamRef, DataBlocked, ExpiredStreamData, Frame, MaxData, MaxStreamData,
+        MaxStreams, ResetStream, ResetStreamAt, StopSending, Stream as StreamFrame,
+        StreamDataBlocked, StreamsBlocked,
     },
     packet::number::{PacketNumberRange, PacketNumberSpace},
     stream::{ops, StreamId, StreamType},
@@ -57,6 +58,8 @@ use s2n_quic_core::{
 struct MockStream {
     config: StreamConfig,
     last_reset: Option<ResetStream>,
+    last_reset_at: Option<ResetStreamAt>,
+    last_expired_stream_data: Option<ExpiredStreamData>,
     last_on_stream_data_blocked: Option<StreamDataBlocked>,
     last_max_stream_data: Option<MaxStreamData>,
     last_stop_sending: Option<StopSending>,
@@ -75,6 +78,8 @@ struct MockStream {
     on_transmit_limit: Option<usize>,
     on_data_count: usize,
     on_reset_count: usize,
+    on_reset_at_count: usize,
+    on_expired_stream_data_count: usize,
     on_stream_data_blocked_count: usize,
     on_stop_sending_count: usize,
     on_max_stream_data_count: usize,
@@ -88,6 +93,9 @@ struct MockStream {
     poll_push_count: usize,
     poll_finish_count: usize,
     reset_count: usize,
+    send_offset: VarInt,
+    send_acknowledged: VarInt,
+    send_barrier: Option<VarInt>,
 }
 
 impl MockStream {
@@ -127,6 +135,8 @@ impl StreamTrait for MockStream {
         Self {
             config,
             last_reset: None,
+            last_reset_at: None,
+            last_expired_stream_data: None,
             last_on_stream_data_blocked: None,
             last_max_stream_data: None,
             last_stop_sending: None,
@@ -143,6 +153,8 @@ impl StreamTrait for MockStream {
             on_internal_reset_count: 0,
             on_data_count: 0,
             on_reset_count: 0,
+            on_reset_at_count: 0,
+            on_expired_stream_data_count: 0,
             on_stream_data_blocked_count: 0,
             on_stop_sending_count: 0,
             on_max_stream_data_count: 0,
@@ -161,6 +173,9 @@ impl StreamTrait for MockStream {
             poll_push_count: 0,
             poll_finish_count: 0,
             reset_count: 0,
+            send_offset: VarInt::from_u8(0),
+            send_acknowledged: VarInt::from_u8(0),
+            send_barrier: None,
         }
     }
 
@@ -212,6 +227,36 @@ impl StreamTrait for MockStream {
         Ok(())
     }
 
+    fn on_reset_at(
+        &mut self,
+        frame: &ResetStreamAt,
+        events: &mut StreamEvents,
+    ) -> Result<(), TransportError> {
+        assert_eq!(self.stream_id(), StreamId::from_varint(frame.stream_id));
+        self.on_reset_at_count += 1;
+        self.last_reset_at = Some(*frame);
+        self.store_wakers(events);
+        if let Some(err) = self.next_packet_error {
+            return Err(err);
+        };
+        Ok(())
+    }
+
+    fn on_expired_stream_data(
+        &mut self,
+        frame: &ExpiredStreamData,
+        events: &mut StreamEvents,
+    ) -> Result<(), TransportError> {
+        assert_eq!(self.stream_id(), StreamId::from_varint(frame.stream_id));
+        self.on_expired_stream_data_count += 1;
+        self.last_expired_stream_data = Some(*frame);
+        self.store_wakers(events);
+        if let Some(err) = self.next_packet_error {
+            return Err(err);
+        };
+        Ok(())
+    }
+
     fn on_max_stream_data(
         &mut self,
         frame: &MaxStreamData,
@@ -256,10 +301,20 @@ impl StreamTrait for MockStream {
         self.update_blocked_sync_period_count += 1;
     }
 
-    fn on_timeout(&mut self, _now: Timestamp) {
+    fn on_timeout(&mut self, _now: Timestamp, _events: &mut StreamEvents) {
         self.on_timeout_count += 1;
     }
 
+    fn needs_wakeup(&self) -> bool {
+        false
+    }
+
+    fn on_wakeup(&mut self, _now: Timestamp) {}
+
+    fn on_mtu_update(&mut self, _max_datagram_size: u16) {}
+
+    fn on_hibernate(&mut self) {}
+
     fn on_internal_reset(&mut self, _error: StreamError, events: &mut StreamEvents) {
         self.on_internal_reset_count += 1;
         if self.set_finalize_on_internal_reset {
@@ -303,6 +358,18 @@ impl StreamTrait for MockStream {
         self.on_connection_window_available_retrieve_window -= Into::<u64>::into(acquired_window);
     }
 
+    fn send_offset(&self) -> VarInt {
+        self.send_offset
+    }
+
+    fn set_send_barrier(&mut self, offset: Option<VarInt>) {
+        self.send_barrier = offset;
+    }
+
+    fn is_send_acknowledged(&self, offset: VarInt) -> bool {
+        self.send_acknowledged >= offset
+    }
+
     fn poll_request(
         &mut self,
         request: &mut ops::Request,
@@ -422,7 +489,7 @@ fn try_open(
         stream_type,
         &mut token,
         &mut ConnectionApiCallContext::from_wakeup_handle(&wakeup_handle),
-        &Context::from_waker(&accept_waker),
+        Some(&Context::from_waker(&accept_waker)),
     ) {
         Poll::Ready(res) => res,
         Poll::Pending => Err(connection::Error::unspecified()),
@@ -506,6 +573,58 @@ fn remote_streams_do_not_open_if_manager_is_closed() {
     }
 }
 
+#[test]
+fn datagram_only_rejects_streams() {
+    for local_ep_type in [endpoint::Type::Client, endpoint::Type::Server] {
+        let limits = ConnectionLimits::default()
+            .with_datagram_only(true)
+            .unwrap();
+
+        let mut manager = AbstractStreamManager::<MockStream>::new(
+            &limits,
+            local_ep_type,
+            limits.initial_flow_control_limits(),
+            create_default_initial_flow_control_limits(),
+            DEFAULT_INITIAL_RTT,
+        );
+
+        for stream_type in [StreamType::Bidirectional, StreamType::Unidirectional] {
+            assert!(matches!(
+                try_open(&mut manager, stream_type),
+                Err(connection::Error::InvalidConfiguration { .. })
+            ));
+
+            // the peer was never granted any stream credits
+            let reset_frame = ResetStream {
+                stream_id: StreamId::initial(local_ep_type.peer_type(), stream_type).into(),
+                application_error_code: VarInt::from_u32(0),
+                final_size: VarInt::from_u32(0),
+            };
+            assert_is_transport_error(
+                manager.on_reset_stream(&reset_frame),
+                TransportError::STREAM_LIMIT_ERROR,
+            );
+        }
+
+        // the stream state is never allocated
+        assert!(manager.inner.is_none());
+        assert!(!manager.has_transmission_interest());
+
+        // the peer is unable to open any streams
+        let waker = new_count_waker().0;
+        assert_eq!(
+            Poll::Ready(Ok(None)),
+            manager.poll_accept(None, &Context::from_waker(&waker))
+        );
+
+        manager.close(connection::Error::unspecified());
+        assert_eq!(
+            connection::finalization::Status::Final,
+            manager.finalization_status()
+        );
+    }
+}
+
 #[test]
 fn opens_locally_initiated_streams() {
     for local_ep_type in [endpoint::Type::Client, endpoint::Type::Server] {
@@ -689,7 +808,7 @@ fn max_streams_replenishes_stream_control_capacity() {
                     ctrl.poll_open_local_stream(
                         stream_id.stream_type(),
                         &mut token,
-                        &Context::from_waker(&waker),
+                        Some(&Context::from_waker(&waker)),
                     )
                 })
                 .is_ready());
@@ -702,7 +821,7 @@ fn max_streams_replenishes_stream_control_capacity() {
                 stream_type,
                 &mut token,
                 &mut ConnectionApiCallContext::from_wakeup_handle(&wakeup_handle),
-                &Context::from_waker(&waker)
+                Some(&Context::from_waker(&waker))
             )
             .is_pending());
 
@@ -729,7 +848,7 @@ fn max_streams_replenishes_stream_control_capacity() {
                 stream_type,
                 &mut token,
                 &mut ConnectionApiCallContext::from_wakeup_handle(&wakeup_handle),
-                &Context::from_waker(&waker)
+                Some(&Context::from_waker(&waker))
             )
             .is_ready());
 
@@ -854,7 +973,7 @@ fn send_streams_blocked_frame_when_blocked_by_peer() {
                 stream_type,
                 &mut token,
                 &mut ConnectionApiCallContext::from_wakeup_handle(&wakeup_handle),
-                &Context::from_waker(&waker),
+                Some(&Context::from_waker(&waker)),
             )
             .is_ready()
         {
@@ -957,7 +1076,7 @@ fn send_streams_blocked_frame_when_blocked_by_peer() {
                 stream_type,
                 &mut token,
                 &mut ConnectionApiCallContext::from_wakeup_handle(&wakeup_handle),
-                &Context::from_waker(&waker),
+                Some(&Context::from_waker(&waker)),
             )
             .is_ready()
         {
@@ -1001,7 +1120,7 @@ fn streams_blocked_period() {
                     stream_type,
                     &mut token,
                     &mut ConnectionApiCallContext::from_wakeup_handle(&wakeup_handle),
-                    &Context::from_waker(&waker),
+                    Some(&Context::from_waker(&waker)),
                 )
                 .is_ready()
             {
@@ -1247,6 +1366,82 @@ fn stream_limit_error_on_peer_open_stream_too_large() {
     }
 }
 
+#[test]
+fn open_stream_timeout_test() {
+    let mut manager = create_stream_manager(endpoint::Type::Server);
+    let stream_type = StreamType::Unidirectional;
+    let timeout = Duration::from_millis(100);
+
+    let (waker, wake_counter) = new_count_waker();
+    let (mut wakeup_queue, wakeup_handle) = create_wakeup_queue_and_handle();
+    let mut token = connection::OpenToken::new();
+    token.timeout = timeout;
+
+    let mut poll_open = |manager: &mut AbstractStreamManager<MockStream>| {
+        manager.poll_open_local_stream(
+            stream_type,
+            &mut token,
+            &mut ConnectionApiCallContext::from_wakeup_handle(&wakeup_handle),
+            Some(&Context::from_waker(&waker)),
+        )
+    };
+
+    let available_outgoing_stream_capacity = manager
+        .with_stream_controller(|ctrl| ctrl.available_local_initiated_stream_capacity(stream_type));
+
+    for _i in 0..*available_outgoing_stream_capacity {
+        assert!(poll_open(&mut manager).is_ready());
+    }
+
+    assert!(poll_open(&mut manager).is_pending());
+
+    // the connection is woken up to arm the deadline on the next transmission
+    assert_wakeups(&mut wakeup_queue, 1);
+
+    let mut frame_buffer = OutgoingFrameBuffer::new();
+    let mut write_context = MockWriteContext::new(
+        time::now(),
+        &mut frame_buffer,
+        transmission::Constraint::None,
+        transmission::Mode::Normal,
+        endpoint::Type::Server,
+    );
+    manager.on_transmit(&mut write_context).unwrap();
+
+    let deadline = write_context.current_time + timeout;
+    assert_eq!(Some(deadline), manager.next_expiration());
+
+    manager.on_timeout(deadline - Duration::from_millis(1));
+    assert_eq!(wake_counter, 0);
+    assert!(poll_open(&mut manager).is_pending());
+
+    manager.on_timeout(deadline);
+    assert_eq!(wake_counter, 1);
+    assert!(matches!(
+        poll_open(&mut manager),
+        Poll::Ready(Err(connection::Error::OpenStreamTimeout { .. }))
+    ));
+
+    // the expired request is removed once the error is observed
+    let open_requests = |manager: &mut AbstractStreamManager<MockStream>| {
+        manager.with_stream_controller(|ctrl| ctrl.open_request_count(stream_type))
+    };
+    assert_eq!(open_requests(&mut manager), 0);
+
+    // the next request waits for capacity again
+    assert!(poll_open(&mut manager).is_pending());
+    assert_eq!(open_requests(&mut manager), 1);
+
+    // the request is able to open a stream once the peer issues more credit
+    assert!(manager
+        .on_max_streams(&MaxStreams {
+            stream_type,
+            maximum_streams: available_outgoing_stream_capacity + VarInt::from_u8(1),
+        })
+        .is_ok());
+    assert!(matches!(poll_open(&mut manager), Poll::Ready(Ok(_))));
+}
+
 #[test]
 fn blocked_on_local_concurrent_stream_limit() {
     for stream_type in [StreamType::Bidirectional, StreamType::Unidirectional] {
@@ -1276,7 +1471,7 @@ fn blocked_on_local_concurrent_stream_limit() {
                     stream_type,
                     &mut token,
                     &mut ConnectionApiCallContext::from_wakeup_handle(&wakeup_handle),
-                    &Context::from_waker(&waker)
+                    Some(&Context::from_waker(&waker))
                 )
                 .is_ready());
         }
@@ -1289,7 +1484,7 @@ fn blocked_on_local_concurrent_stream_limit() {
                 stream_type,
                 &mut token,
                 &mut ConnectionApiCallContext::from_wakeup_handle(&wakeup_handle),
-                &Context::from_waker(&waker)
+                Some(&Context::from_waker(&waker))
             )
             .is_pending());
 
@@ -1353,7 +1548,7 @@ fn blocked_on_local_concurrent_stream_limit() {
                 stream_type,
                 &mut token,
                 &mut ConnectionApiCallContext::from_wakeup_handle(&wakeup_handle),
-                &Context::from_waker(&waker)
+                Some(&Context::from_waker(&waker))
             )
             .is_ready());
         assert_eq!(wake_counter, 1);
@@ -1362,7 +1557,7 @@ fn blocked_on_local_concurrent_stream_limit() {
                 stream_type,
                 &mut token,
                 &mut ConnectionApiCallContext::from_wakeup_handle(&wakeup_handle),
-                &Context::from_waker(&waker)
+                Some(&Context::from_waker(&waker))
             )
             .is_pending());
 
@@ -1491,7 +1686,7 @@ fn asymmetric_stream_limits_local_initiated() {
                         stream_type,
                         &mut token,
                         &mut ConnectionApiCallContext::from_wakeup_handle(&wakeup_handle),
-                        &Context::from_waker(&accept_waker),
+                        Some(&Context::from_waker(&accept_waker)),
                     );
                     assert!(
                         result.is_ready(),
@@ -1687,7 +1882,7 @@ fn accept_returns_remotely_initiated_stream() {
                 );
 
                 // Check Stream ID exhaustion
-                *manager.inner.accept_state.next_stream_mut(stream_type) = None;
+                *manager.state().accept_state.next_stream_mut(stream_type) = None;
 
                 assert_matches!(
                     manager.poll_accept(Some(stream_type), &Context::from_waker(&accept_waker)),
@@ -2282,6 +2477,81 @@ fn on_packet_ack_and_loss_is_forwarded_to_interested_streams() {
     });
 }
 
+#[test]
+fn send_barriers_hold_back_streams_until_acknowledged() {
+    let mut manager = create_stream_manager(endpoint::Type::Server);
+
+    let stream_1 = try_open(&mut manager, StreamType::Unidirectional).unwrap();
+    let stream_2 = try_open(&mut manager, StreamType::Unidirectional).unwrap();
+    let stream_3 = try_open(&mut manager, StreamType::Unidirectional).unwrap();
+
+    for (stream_id, offset) in [(stream_1, 100), (stream_2, 200), (stream_3, 300)] {
+        manager.with_asserted_stream(stream_id, |stream| {
+            stream.send_offset = VarInt::from_u32(offset);
+        });
+    }
+
+    // stream 3 has no unacknowledged data but is still held back
+    manager.with_asserted_stream(stream_3, |stream| {
+        stream.send_acknowledged = VarInt::from_u32(300);
+    });
+
+    // stream 4 isn't opened yet so it's held back from the start
+    let stream_4 = stream_3.next_of_type().unwrap();
+
+    manager.insert_send_barrier(&[stream_1, stream_2, stream_3, stream_4]);
+
+    manager.with_asserted_stream(stream_2, |stream| {
+        stream.send_offset = VarInt::from_u32(250);
+    });
+    manager.insert_send_barrier(&[stream_2]);
+
+    assert_eq!(
+        stream_4,
+        try_open(&mut manager, StreamType::Unidirectional).unwrap()
+    );
+
+    let barriers = |manager: &mut AbstractStreamManager<MockStream>| {
+        [stream_1, stream_2, stream_3, stream_4].map(|stream_id| {
+            manager.with_asserted_stream(stream_id, |stream| {
+                stream.send_barrier.map(|offset| offset.as_u64())
+            })
+        })
+    };
+
+    assert_eq!(
+        [Some(100), Some(200), Some(300), Some(0)],
+        barriers(&mut manager)
+    );
+
+    // the first barrier is still waiting on stream 2
+    manager.with_asserted_stream(stream_1, |stream| {
+        stream.send_acknowledged = VarInt::from_u32(100);
+    });
+    manager.on_packet_ack(&pn(1));
+    assert_eq!(
+        [Some(100), Some(200), Some(300), Some(0)],
+        barriers(&mut manager)
+    );
+
+    // stream 2 moves on to the second barrier
+    manager.with_asserted_stream(stream_2, |stream| {
+        stream.send_acknowledged = VarInt::from_u32(200);
+    });
+    manager.on_packet_ack(&pn(2));
+    assert_eq!([None, Some(250), None, None], barriers(&mut manager));
+
+    manager.with_asserted_stream(stream_2, |stream| {
+        stream.send_acknowledged = VarInt::from_u32(250);
+    });
+    manager.on_packet_ack(&pn(3));
+    assert_eq!([None, None, None, None], barriers(&mut manager));
+
+    // a barrier which was already acknowledged on every stream is released right away
+    manager.insert_send_barrier(&[stream_1, stream_3]);
+    assert_eq!([None, None, None, None], barriers(&mut manager));
+}
+
 #[test]
 fn close_is_forwarded_to_all_streams() {
     let (read_waker, read_wake_counter) = new_count_waker();
@@ -2943,6 +3213,116 @@ fn forwards_on_reset() {
     assert_eq!(write_wake_counter, 2);
 }
 
+#[test]
+fn forwards_on_reset_at() {
+    let initial_local_limits = create_default_initial_flow_control_limits();
+    let initial_peer_limits = create_default_initial_flow_control_limits();
+    let create_manager = |enabled: bool| {
+        let limits = ConnectionLimits::default()
+            .with_reset_stream_at(enabled)
+            .unwrap();
+
+        AbstractStreamManager::<MockStream>::new(
+            &limits,
+            endpoint::Type::Server,
+            initial_local_limits,
+            initial_peer_limits,
+            DEFAULT_INITIAL_RTT,
+        )
+    };
+
+    let mut manager = create_manager(true);
+    let (read_waker, read_wake_counter) = new_count_waker();
+
+    let stream_1 = try_open(&mut manager, StreamType::Bidirectional).unwrap();
+
+    manager.with_asserted_stream(stream_1, |stream| {
+        stream.read_waker_to_return = Some(read_waker);
+    });
+
+    let frame = ResetStreamAt {
+        stream_id: stream_1.into(),
+        application_error_code: VarInt::from_u32(3_928_776),
+        final_size: VarInt::from_u32(44_222_111),
+        reliable_size: VarInt::from_u32(1_000),
+    };
+
+    assert_eq!(Ok(()), manager.on_reset_stream_at(&frame));
+    assert_eq!(read_wake_counter, 1);
+
+    manager.with_asserted_stream(stream_1, |stream| {
+        assert_eq!(stream.on_reset_at_count, 1);
+        assert_eq!(Some(frame), stream.last_reset_at);
+    });
+
+    // The frame is rejected if the extension wasn't negotiated
+    let mut manager = create_manager(false);
+    let stream_1 = try_open(&mut manager, StreamType::Bidirectional).unwrap();
+    let frame = ResetStreamAt {
+        stream_id: stream_1.into(),
+        ..frame
+    };
+
+    assert_is_transport_error(
+        manager.on_reset_stream_at(&frame),
+        TransportError::PROTOCOL_VIOLATION,
+    );
+}
+
+#[test]
+fn forwards_on_expired_stream_data() {
+    let initial_local_limits = create_default_initial_flow_control_limits();
+    let initial_peer_limits = create_default_initial_flow_control_limits();
+    let create_manager = |enabled: bool| {
+        let limits = ConnectionLimits::default()
+            .with_partial_reliability(enabled)
+            .unwrap();
+
+        AbstractStreamManager::<MockStream>::new(
+            &limits,
+            endpoint::Type::Server,
+            initial_local_limits,
+            initial_peer_limits,
+            DEFAULT_INITIAL_RTT,
+        )
+    };
+
+    let mut manager = create_manager(true);
+    let (read_waker, read_wake_counter) = new_count_waker();
+
+    let stream_1 = try_open(&mut manager, StreamType::Bidirectional).unwrap();
+
+    manager.with_asserted_stream(stream_1, |stream| {
+        stream.read_waker_to_return = Some(read_waker);
+    });
+
+    let frame = ExpiredStreamData {
+        stream_id: stream_1.into(),
+        offset: VarInt::from_u32(1_000),
+    };
+
+    assert_eq!(Ok(()), manager.on_expired_stream_data(&frame));
+    assert_eq!(read_wake_counter, 1);
+
+    manager.with_asserted_stream(stream_1, |stream| {
+        assert_eq!(stream.on_expired_stream_data_count, 1);
+        assert_eq!(Some(frame), stream.last_expired_stream_data);
+    });
+
+    // The frame is rejected if the extension wasn't negotiated
+    let mut manager = create_manager(false);
+    let stream_1 = try_open(&mut manager, StreamType::Bidirectional).unwrap();
+    let frame = ExpiredStreamData {
+        stream_id: stream_1.into(),
+        ..frame
+    };
+
+    assert_is_transport_error(
+        manager.on_expired_stream_data(&frame),
+        TransportError::PROTOCOL_VIOLATION,
+    );
+}
+
 #[test]
 fn forwards_poll_pop() {
     let (mut wakeup_queue, wakeup_handle) = create_wakeup_queue_and_handle();
@@ -3282,7 +3662,7 @@ fn stream_batching_test() {
                     StreamType::Bidirectional,
                     &mut token,
                     &mut ConnectionApiCallContext::from_wakeup_handle(&wakeup_handle),
-                    &Context::from_waker(&accept_waker),
+                    Some(&Context::from_waker(&accept_waker)),
                 ) {
                     Poll::Ready(res) => res,
                     Poll::Pending => Err(connection::Error::unspecified()),
@@ -3335,3 +3715,73 @@ fn stream_batching_test() {
         }
     }
 }
+
+#[test]
+fn stream_initiator_interleaving_test() {
+    const TRANSMISSIONS: usize = 6;
+
+    for interleave in [false, true] {
+        let limits = ConnectionLimits::default()
+            .with_max_open_local_bidirectional_streams(1000)
+            .unwrap()
+            .with_stream_initiator_interleaving(interleave)
+            .unwrap();
+
+        let mut manager = AbstractStreamManager::<MockStream>::new(
+            &limits,
+            endpoint::Type::Server,
+            create_default_initial_flow_control_limits(),
+            create_default_initial_flow_control_limits(),
+            DEFAULT_INITIAL_RTT,
+        );
+
+        // The server pushes on several streams
+        let local_streams: Vec<_> = (0..3)
+            .map(|_| try_open(&mut manager, StreamType::Bidirectional).unwrap())
+            .collect();
+
+        // The client opens a single stream
+        let remote_stream =
+            StreamId::nth(endpoint::Type::Client, StreamType::Bidirectional, 0).unwrap();
+        assert_eq!(
+            Ok(()),
+            manager.on_data(&stream_data(remote_stream, VarInt::from_u32(0), &[], false))
+        );
+
+        for stream_id in local_streams.iter().chain(Some(&remote_stream)) {
+            manager.with_asserted_stream(*stream_id, |stream| {
+                stream.on_transmit_try_write_frames = 100;
+                stream.on_transmit_limit = Some(1);
+            });
+        }
+
+        let mut frame_buffer = OutgoingFrameBuffer::new();
+        let mut write_context = MockWriteContext::new(
+            time::now(),
+            &mut frame_buffer,
+            transmission::Constraint::None,
+            transmission::Mode::Normal,
+            endpoint::Type::Server,
+        );
+
+        // Only a single frame fits in each transmission
+        for _ in 0..TRANSMISSIONS {
+            write_context.frame_buffer.set_error_write_after_n_frames(1);
+            let _ = manager.on_transmit(&mut write_context);
+            write_context.frame_buffer.flush();
+        }
+
+        let mut remote_frames = 0;
+        manager.with_asserted_stream(remote_stream, |stream| {
+            remote_frames = 100 - stream.on_transmit_try_write_frames;
+        });
+
+        if interleave {
+            // The peer-initiated stream gets every other transmission
+            assert_eq!(remote_frames, TRANSMISSIONS / 2);
+        } else {
+            // The peer-initiated stream waits behind all of the local streams
+            assert_eq!(remote_frames, TRANSMISSIONS / 4);
+        }
+    }
+}
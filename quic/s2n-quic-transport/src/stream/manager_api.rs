@@ -5,7 +5,7 @@ use crate::{
     connection,
     contexts::{ConnectionApiCallContext, OnTransmitError, WriteContext},
     recovery::RttEstimator,
-    stream::StreamError,
+    stream::{LimitPressure, PeerBlocked, StreamError},
     transmission,
 };
 use core::{
@@ -15,8 +15,8 @@ use core::{
 use s2n_quic_core::{
     ack, endpoint,
     frame::{
-        stream::StreamRef, DataBlocked, MaxData, MaxStreamData, MaxStreams, ResetStream,
-        StopSending, StreamDataBlocked, StreamsBlocked,
+        stream::StreamRef, DataBlocked, ExpiredStreamData, MaxData, MaxStreamData, MaxStreams,
+        ResetStream, ResetStreamAt, StopSending, StreamDataBlocked, StreamsBlocked,
     },
     stream::{ops, StreamId, StreamType},
     time::{timer, Timestamp},
@@ -55,14 +55,43 @@ pub trait Manager:
     ) -> Poll<Result<Option<StreamId>, connection::Error>>;
 
     /// Opens the next local initiated stream of a certain type
+    ///
+    /// If no `context` is provided, the caller is not notified when the stream can be opened.
     fn poll_open_local_stream(
         &mut self,
         stream_type: StreamType,
         open_token: &mut connection::OpenToken,
         api_call_context: &mut ConnectionApiCallContext,
-        context: &Context,
+        context: Option<&Context>,
     ) -> Poll<Result<StreamId, connection::Error>>;
 
+    /// Cancels the pending request to open a local initiated stream of a certain type, if any
+    fn cancel_open_local_stream(
+        &mut self,
+        stream_type: StreamType,
+        open_token: &mut connection::OpenToken,
+    );
+
+    /// Returns `true` if opening a local stream of the given type requires additional credit
+    /// from the peer
+    fn is_local_stream_blocked_by_peer(&self, stream_type: StreamType) -> bool;
+
+    /// Polls whether the application has been blocked by the peer's stream limits for longer
+    /// than the configured threshold
+    fn poll_peer_blocked_timeout(
+        &mut self,
+        stream_type: StreamType,
+        now: Timestamp,
+    ) -> Poll<PeerBlocked>;
+
+    /// Polls whether the application has opened more streams of the given type than the
+    /// configured percentage of the peer's stream limit
+    fn poll_stream_limit_pressure(&mut self, stream_type: StreamType) -> Poll<LimitPressure>;
+
+    /// Polls whether the streams have used more than the configured percentage of the peer's
+    /// connection flow control limit
+    fn poll_connection_data_pressure(&mut self) -> Poll<LimitPressure>;
+
     /// This method gets called when a packet delivery got acknowledged
     fn on_packet_ack<A: ack::Set>(&mut self, ack_set: &A);
 
@@ -72,9 +101,18 @@ pub trait Manager:
     /// This method gets called when the RTT estimate is updated for the active path
     fn on_rtt_update(&mut self, rtt_estimator: &RttEstimator, now: Timestamp);
 
+    /// This method gets called when the maximum datagram size of a path is updated
+    fn on_mtu_update(&mut self, max_datagram_size: u16);
+
     /// Called when the connection timer expires
     fn on_timeout(&mut self, now: Timestamp);
 
+    /// Called when the connection wakes up after an API call
+    fn on_wakeup(&mut self, now: Timestamp);
+
+    /// Called when the connection releases its buffers after being idle
+    fn on_hibernate(&mut self);
+
     /// Closes the manager and resets all streams with the
     /// given error. The current implementation will still
     /// allow to forward frames to the contained Streams as well as to query them
@@ -113,6 +151,15 @@ pub trait Manager:
     /// a stream
     fn on_reset_stream(&mut self, frame: &ResetStream) -> Result<(), transport::Error>;
 
+    /// This is called when a `RESET_STREAM_AT` frame had been received for
+    /// a stream
+    fn on_reset_stream_at(&mut self, frame: &ResetStreamAt) -> Result<(), transport::Error>;
+
+    /// This is called when an `EXPIRED_STREAM_DATA` frame had been received for
+    /// a stream
+    fn on_expired_stream_data(&mut self, frame: &ExpiredStreamData)
+        -> Result<(), transport::Error>;
+
     /// This is called when a `MAX_STREAM_DATA` frame had been received for
     /// a stream
     fn on_max_stream_data(&mut self, frame: &MaxStreamData) -> Result<(), transport::Error>;
@@ -142,4 +189,10 @@ pub trait Manager:
 
     /// Returns whether or not streams have data to send
     fn has_pending_streams(&self) -> bool;
+
+    /// Holds back any data written to `streams` after this call until all of the data written
+    /// before it has been acknowledged on every stream in the set
+    ///
+    /// Streams which don't exist or have no unacknowledged data are ignored.
+    fn insert_send_barrier(&mut self, streams: &[StreamId]);
 }
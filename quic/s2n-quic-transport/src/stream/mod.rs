@@ -4,11 +4,15 @@
 //! This module contains the implementation of QUIC `Streams` and their management
 
 mod api;
+mod barrier;
 mod controller;
+mod deadline;
+mod expiration;
 mod incoming_connection_flow_controller;
 mod manager;
 mod manager_api;
 mod outgoing_connection_flow_controller;
+mod pressure;
 mod receive_stream;
 mod send_stream;
 mod stream_container;
@@ -20,9 +24,10 @@ mod stream_interests;
 pub(crate) mod contract;
 
 pub use api::*;
-pub use controller::Controller;
+pub use controller::{Controller, PeerBlocked};
 pub use manager::AbstractStreamManager;
 pub use manager_api::Manager;
+pub use pressure::LimitPressure;
 pub use s2n_quic_core::stream::limits::Limits;
 pub use stream_events::StreamEvents;
 pub use stream_impl::{StreamImpl, StreamTrait};
@@ -3,11 +3,12 @@
 
 use crate::{
     contexts::{OnTransmitError, WriteContext},
+    stream::pressure::{LimitPressure, Pressure},
     sync::{PeriodicSync, ValueToFrameWriter},
     transmission,
 };
 use alloc::rc::Rc;
-use core::{cell::RefCell, time::Duration};
+use core::{cell::RefCell, task::Poll, time::Duration};
 use s2n_quic_core::{
     ack,
     frame::{DataBlocked, MaxData},
@@ -29,6 +30,8 @@ struct OutgoingConnectionFlowControllerImpl {
     available_window: VarInt,
     /// For periodically sending `DATA_BLOCKED` frames when blocked by peer limits
     data_blocked_sync: PeriodicSync<VarInt, DataBlockedToFrameWriter>,
+    /// Reports when the acquired window approaches the peer's limit
+    pressure: Pressure,
 }
 
 impl OutgoingConnectionFlowControllerImpl {
@@ -37,6 +40,7 @@ impl OutgoingConnectionFlowControllerImpl {
             total_available_window: initial_window_size,
             available_window: initial_window_size,
             data_blocked_sync: PeriodicSync::new(),
+            pressure: Pressure::default(),
         }
     }
 
@@ -179,6 +183,22 @@ impl OutgoingConnectionFlowController {
             .update_sync_period(blocked_sync_period);
     }
 
+    /// Updates the percentage of the peer's `MAX_DATA` limit which can be acquired before
+    /// it's reported
+    pub fn update_pressure_threshold(&mut self, threshold: Option<u8>) {
+        self.inner.borrow_mut().pressure.update_threshold(threshold);
+    }
+
+    /// Returns the acquired window if it has crossed the configured percentage of the
+    /// peer's `MAX_DATA` limit
+    ///
+    /// Each peer limit is only reported once.
+    pub fn poll_pressure(&mut self) -> Poll<LimitPressure> {
+        let inner = &mut *self.inner.borrow_mut();
+        let acquired = inner.total_available_window - inner.available_window;
+        inner.pressure.poll(acquired, inner.total_available_window)
+    }
+
     /// Queries the component for any outgoing frames that need to get sent
     pub fn on_transmit<W: WriteContext>(&mut self, context: &mut W) -> Result<(), OnTransmitError> {
         //= https://www.rfc-editor.org/rfc/rfc9000#section-4.1
@@ -0,0 +1,105 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use core::task::Poll;
+use s2n_quic_core::varint::VarInt;
+
+/// The usage of a peer-issued limit which crossed the configured threshold
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LimitPressure {
+    /// The amount of the limit which has been used
+    pub used: VarInt,
+    /// The cumulative limit specified by the peer
+    pub limit: VarInt,
+}
+
+/// Tracks when the usage of a peer-issued limit crosses a percentage of that limit
+///
+/// Pressure is reported at most once for each limit value, so a peer raising the limit
+/// re-arms the notification.
+#[derive(Debug, Default)]
+pub(super) struct Pressure {
+    /// The percentage of the limit at which pressure is reported
+    threshold: Option<u8>,
+    /// The limit for which pressure was last reported
+    reported_limit: Option<VarInt>,
+}
+
+impl Pressure {
+    #[inline]
+    pub fn update_threshold(&mut self, threshold: Option<u8>) {
+        self.threshold = threshold;
+    }
+
+    /// Returns the usage if it has crossed the threshold for the current limit
+    #[inline]
+    pub fn poll(&mut self, used: VarInt, limit: VarInt) -> Poll<LimitPressure> {
+        let Some(threshold) = self.threshold else {
+            return Poll::Pending;
+        };
+
+        if limit == VarInt::ZERO || self.reported_limit == Some(limit) {
+            return Poll::Pending;
+        }
+
+        // widen the values so the multiplication can't overflow
+        let used_percent = used.as_u64() as u128 * 100;
+        let threshold = limit.as_u64() as u128 * threshold as u128;
+        if used_percent < threshold {
+            return Poll::Pending;
+        }
+
+        self.reported_limit = Some(limit);
+        Poll::Ready(LimitPressure { used, limit })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn disabled_test() {
+        let mut pressure = Pressure::default();
+        assert!(pressure
+            .poll(VarInt::from_u8(100), VarInt::from_u8(100))
+            .is_pending());
+    }
+
+    #[test]
+    fn threshold_test() {
+        let mut pressure = Pressure::default();
+        pressure.update_threshold(Some(80));
+
+        assert!(pressure
+            .poll(VarInt::from_u8(79), VarInt::from_u8(100))
+            .is_pending());
+        assert_eq!(
+            pressure.poll(VarInt::from_u8(80), VarInt::from_u8(100)),
+            Poll::Ready(LimitPressure {
+                used: VarInt::from_u8(80),
+                limit: VarInt::from_u8(100),
+            })
+        );
+
+        // pressure is only reported once per limit
+        assert!(pressure
+            .poll(VarInt::from_u8(90), VarInt::from_u8(100))
+            .is_pending());
+
+        // raising the limit re-arms the notification
+        assert!(pressure
+            .poll(VarInt::from_u8(90), VarInt::from_u8(200))
+            .is_pending());
+        assert!(pressure
+            .poll(VarInt::from_u8(160), VarInt::from_u8(200))
+            .is_ready());
+    }
+
+    #[test]
+    fn zero_limit_test() {
+        let mut pressure = Pressure::default();
+        pressure.update_threshold(Some(1));
+        assert!(pressure.poll(VarInt::ZERO, VarInt::ZERO).is_pending());
+    }
+}
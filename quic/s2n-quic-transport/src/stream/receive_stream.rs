@@ -4,14 +4,17 @@
 use crate::{
     contexts::{OnTransmitError, WriteContext},
     stream::{
+        deadline::Deadline,
         incoming_connection_flow_controller::IncomingConnectionFlowController,
         stream_events::StreamEvents,
         stream_interests::{StreamInterestProvider, StreamInterests},
         StreamError,
     },
     sync::{IncrementalValueSync, OnceSync, ValueToFrameWriter},
-    transmission::interest::Provider as _,
+    transmission::interest::{Provider as _, Query as _},
 };
+use alloc::{boxed::Box, vec::Vec};
+use bytes::Bytes;
 use core::{
     convert::TryFrom,
     task::{Context, Poll, Waker},
@@ -19,9 +22,13 @@ use core::{
 use s2n_quic_core::{
     ack, application,
     buffer::{self, Reassembler},
-    frame::{stream::StreamRef, MaxStreamData, ResetStream, StopSending, StreamDataBlocked},
+    frame::{
+        stream::StreamRef, ExpiredStreamData, MaxStreamData, ResetStream, ResetStreamAt,
+        StopSending, StreamDataBlocked,
+    },
     packet::number::PacketNumber,
-    stream::{ops, StreamId},
+    stream::{compression, ops, StreamId},
+    time::{timer, Timestamp},
     transport,
     varint::VarInt,
 };
@@ -84,6 +91,16 @@ pub(super) enum ReceiveStreamState {
     Reset(StreamError),
 }
 
+/// A reset received in a `RESET_STREAM_AT` frame
+///
+/// The reset takes effect once the application has read all of the data up to the reliable size.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub(super) struct PendingReset {
+    error: StreamError,
+    final_size: VarInt,
+    reliable_size: VarInt,
+}
+
 /// Keeps track of any missing data in the `Stopping` state
 #[derive(PartialEq, Debug, Clone)]
 pub(super) struct MissingData {
@@ -281,15 +298,36 @@ impl ReceiveStreamFlowController {
 
         // Enqueue Stream window updates by increasing the latest value on
         // the read window synchronisation component
-        self.read_window_sync.update_latest_value(
-            self.released_connection_window
-                .saturating_add(VarInt::from_u32(self.desired_flow_control_window)),
-        );
+        self.update_read_window();
 
         // Notify the connection flow controller about the consumed data
         self.connection_flow_controller.release_window(amount);
     }
 
+    /// Updates the relative flow control window the stream maintains
+    ///
+    /// A larger window is advertised to the peer right away. Since the peer may already be
+    /// using the previously advertised window, a smaller window only takes effect once the
+    /// application has read enough data.
+    fn set_desired_window(&mut self, desired_flow_control_window: u32) {
+        self.desired_flow_control_window = desired_flow_control_window;
+        self.read_window_sync
+            .set_threshold(VarInt::from_u32(desired_flow_control_window / 10));
+        self.update_read_window();
+    }
+
+    /// Advertises the desired window on top of the released data, if it exceeds the current one
+    fn update_read_window(&mut self) {
+        let window = self
+            .released_connection_window
+            .saturating_add(VarInt::from_u32(self.desired_flow_control_window));
+
+        // the window may have been shrunk by the application
+        if window > self.read_window_sync.latest_value() {
+            self.read_window_sync.update_latest_value(window);
+        }
+    }
+
     /// Releases all flow credits which had been acquired but not yet released
     /// through previous [`release_window`] calls.
     fn release_outstanding_window(&mut self) {
@@ -340,6 +378,8 @@ pub struct ReceiveStream {
     pub(super) flow_controller: ReceiveStreamFlowController,
     /// Synchronizes the `STOP_SENDING` flag towards the peer.
     pub(super) stop_sending_sync: OnceSync<application::Error, StopSendingToFrameWriter>,
+    /// A reset which is deferred until the application has read the reliable data
+    pub(super) pending_reset: Option<PendingReset>,
     /// The handle of a task that is currently waiting on new incoming data, along with the low
     /// watermark value.
     pub(super) read_waiter: Option<(Waker, usize)>,
@@ -347,6 +387,14 @@ pub struct ReceiveStream {
     final_state_observed: bool,
     /// Marks the stream as detached from the application
     detached: bool,
+    /// Fails reads which are blocked for longer than the configured timeout
+    read_deadline: Deadline,
+    /// Decompresses the received data before it is delivered to the application
+    decoder: Option<Box<dyn compression::Decoder>>,
+    /// Decompressed data which is waiting for a free chunk in a read request
+    decoded: Option<Bytes>,
+    /// The data which the peer gave up on since the application last read from the stream
+    expired: Option<core::ops::Range<u64>>,
 }
 
 impl ReceiveStream {
@@ -373,9 +421,14 @@ impl ReceiveStream {
                 desired_flow_control_window,
             ),
             stop_sending_sync: OnceSync::new(),
+            pending_reset: None,
             read_waiter: None,
             final_state_observed: is_closed,
             detached: is_closed,
+            read_deadline: Deadline::default(),
+            decoder: None,
+            decoded: None,
+            expired: None,
         };
 
         if is_closed {
@@ -596,6 +649,131 @@ impl ReceiveStream {
         Ok(())
     }
 
+    /// This is called when a `RESET_STREAM_AT` frame had been received for
+    /// this stream
+    pub fn on_reset_at(
+        &mut self,
+        frame: &ResetStreamAt,
+        events: &mut StreamEvents,
+    ) -> Result<(), transport::Error> {
+        // The reliable size can't exceed the final size of the stream
+        if frame.reliable_size > frame.final_size {
+            return Err(transport::Error::FRAME_ENCODING_ERROR
+                .with_reason("Reliable size exceeds the final size")
+                .with_frame_type(frame.tag().into()));
+        }
+
+        let error = StreamError::stream_reset(frame.application_error_code.into());
+
+        let is_receiving = matches!(self.state, ReceiveStreamState::Receiving)
+            && !self.receive_buffer.is_writing_complete();
+
+        // If the application has already read all of the reliable data, this behaves like a
+        // `RESET_STREAM` frame
+        if !is_receiving || frame.reliable_size.as_u64() <= self.receive_buffer.consumed_len() {
+            self.init_reset(error, Some(frame.final_size), Some(frame.tag()))?;
+
+            // We don't have to send `STOP_SENDING` anymore since the stream was reset by the peer
+            self.stop_sending_sync.stop_sync();
+
+            self.wake(events);
+
+            return Ok(());
+        }
+
+        let known_final_size = self
+            .receive_buffer
+            .final_size()
+            .or_else(|| self.pending_reset.map(|reset| reset.final_size.as_u64()));
+
+        if known_final_size.is_some_and(|size| size != frame.final_size.as_u64()) {
+            return Err(transport::Error::FINAL_SIZE_ERROR
+                .with_reason("Final size in reset frame did not match previous final size")
+                .with_frame_type(frame.tag().into()));
+        }
+
+        // The peer has reserved credits up to the final size, so we need to do the same
+        self.flow_controller
+            .acquire_window_up_to(frame.final_size, Some(frame.tag()))?;
+
+        // The reliable size can only be reduced by subsequent frames
+        let reliable_size = self.pending_reset.map_or(frame.reliable_size, |reset| {
+            reset.reliable_size.min(frame.reliable_size)
+        });
+
+        self.pending_reset = Some(PendingReset {
+            error,
+            final_size: frame.final_size,
+            reliable_size,
+        });
+
+        // The application may already have received everything it needs to observe the reset
+        self.wake(events);
+
+        Ok(())
+    }
+
+    /// This is called when an `EXPIRED_STREAM_DATA` frame had been received for
+    /// this stream
+    pub fn on_expired_stream_data(
+        &mut self,
+        frame: &ExpiredStreamData,
+        events: &mut StreamEvents,
+    ) -> Result<(), transport::Error> {
+        // Expired data only needs to be skipped while the stream is still receiving
+        if !matches!(self.state, ReceiveStreamState::Receiving) {
+            return Ok(());
+        }
+
+        // The frame may have been retransmitted or reordered with a later one
+        let consumed_len = VarInt::new(self.receive_buffer.consumed_len())
+            .expect("consumed length should always fit in a VarInt");
+        let Some(expired_len) = frame
+            .offset
+            .checked_sub(consumed_len)
+            .filter(|len| *len > VarInt::ZERO)
+        else {
+            return Ok(());
+        };
+
+        // The peer has reserved credits up to the offset, so we need to do the same
+        if self.receive_buffer.final_size().is_none() {
+            self.flow_controller
+                .acquire_window_up_to(frame.offset, None)
+                .map_err(|error| error.with_frame_type(frame.tag()))?;
+        }
+
+        // Discard the expired data, including any of it that was already received, since the
+        // application will never read it
+        self.receive_buffer.skip(expired_len).map_err(|_| {
+            transport::Error::FINAL_SIZE_ERROR
+                .with_reason("Expired stream data exceeds the final size")
+                .with_frame_type(frame.tag())
+        })?;
+        self.flow_controller.release_window(expired_len);
+
+        // Report the skipped data to the application along with the next read. Nothing was read
+        // since any previous expiration was recorded, so the ranges are contiguous.
+        let start = self
+            .expired
+            .take()
+            .map_or(consumed_len.as_u64(), |expired| expired.start);
+        self.expired = Some(start..frame.offset.as_u64());
+
+        if let Some(total_size) = self.receive_buffer.final_size() {
+            // The missing data may have been skipped, in which case there is no point in
+            // transmitting STOP_SENDING anymore.
+            if self.receive_buffer.total_received_len() == total_size {
+                self.stop_sending_sync.stop_sync();
+            }
+        }
+
+        // The data after the offset may now be available for reading
+        self.wake(events);
+
+        Ok(())
+    }
+
     /// Starts the reset procedure if the Stream has not been in a RESET state
     /// before.
     fn init_reset(
@@ -676,6 +854,7 @@ impl ReceiveStream {
         // previously released.
         self.flow_controller.release_outstanding_window();
 
+        self.pending_reset = None;
         self.state = ReceiveStreamState::Reset(error);
 
         Ok(())
@@ -702,6 +881,7 @@ impl ReceiveStream {
         stream_id: StreamId,
         context: &mut W,
     ) -> Result<(), OnTransmitError> {
+        self.read_deadline.on_transmit(context.current_time());
         self.stop_sending_sync.on_transmit(stream_id, context)?;
 
         //= https://www.rfc-editor.org/rfc/rfc9000#section-4.2
@@ -715,6 +895,23 @@ impl ReceiveStream {
             .on_transmit(stream_id, context)
     }
 
+    /// Called when the connection timer expires
+    pub fn on_timeout(&mut self, now: Timestamp, events: &mut StreamEvents) {
+        if self.read_deadline.on_timeout(now) {
+            // wake the blocked reader so it can observe the timeout
+            self.wake(events);
+
+            // the application is no longer waiting on the stream so release the unused buffer
+            // capacity. Any out-of-order data is retained so the read can be retried.
+            self.receive_buffer.shrink_to_fit();
+        }
+    }
+
+    /// Called when the connection releases its buffers after being idle
+    pub fn on_hibernate(&mut self) {
+        self.receive_buffer.shrink_to_fit();
+    }
+
     /// Wakes up the application on progress updates
     ///
     /// If there is not a registered waker and the stream is in a terminal state,
@@ -734,13 +931,56 @@ impl ReceiveStream {
 
     // These functions are called from the client API
 
+    /// Handles a request from the application on the receiving half of the stream
+    ///
+    /// If a read timeout is configured and the request remains blocked for longer than the
+    /// timeout without receiving any data, the method will return a timeout [`StreamError`].
     pub fn poll_request(
         &mut self,
         request: &mut ops::rx::Request,
         context: Option<&Context>,
+    ) -> Result<ops::rx::Response, StreamError> {
+        if let Some(timeout) = request.timeout {
+            self.read_deadline.set_timeout(timeout);
+        }
+
+        let result = self.poll_request_impl(request, context);
+
+        if context.is_none() {
+            return result;
+        }
+
+        match &result {
+            // only fail operations which didn't make any progress
+            Ok(response) if response.will_wake && response.chunks.consumed == 0 => {
+                if self.read_deadline.on_blocked() {
+                    self.read_waiter = None;
+                    return Err(StreamError::timeout());
+                }
+            }
+            _ => self.read_deadline.on_ready(),
+        }
+
+        result
+    }
+
+    fn poll_request_impl(
+        &mut self,
+        request: &mut ops::rx::Request,
+        context: Option<&Context>,
     ) -> Result<ops::rx::Response, StreamError> {
         let mut response = ops::rx::Response::default();
 
+        if let Some(buffer_size) = request.buffer_size {
+            // the peer would never be able to send any data
+            if buffer_size == 0 {
+                return Err(StreamError::invalid_configuration(
+                    "the receive buffer size must be greater than 0",
+                ));
+            }
+            self.flow_controller.set_desired_window(buffer_size);
+        }
+
         if let Some(error_code) = request.stop_sending {
             let error = StreamError::stream_reset(error_code);
 
@@ -806,6 +1046,30 @@ impl ReceiveStream {
             self.detach();
         }
 
+        if let Some(decoder) = request.decoder.take() {
+            // switching decoders in the middle of the stream would corrupt the output
+            if self.decoder.is_some() || self.receive_buffer.consumed_len() != 0 {
+                return Err(StreamError::compression_error(
+                    "the decoder must be set before any data is read",
+                ));
+            }
+            self.decoder = Some(decoder);
+        }
+
+        // Apply a reset from a `RESET_STREAM_AT` frame once all of the reliable data was read
+        if let Some(reset) = self.pending_reset.filter(|reset| {
+            matches!(self.state, ReceiveStreamState::Receiving)
+                && self.receive_buffer.consumed_len() >= reset.reliable_size.as_u64()
+        }) {
+            // The flow control window was already acquired when the frame was received
+            let reset_result = self.init_reset(reset.error, None, None);
+            debug_assert!(reset_result.is_ok());
+            self.pending_reset = None;
+
+            // We don't have to send `STOP_SENDING` anymore since the stream was reset by the peer
+            self.stop_sending_sync.stop_sync();
+        }
+
         // Do some state checks here. Only read data when the client is still
         // allowed to read (not reset).
 
@@ -858,17 +1122,29 @@ impl ReceiveStream {
                         *low_watermark = (*low_watermark).saturating_sub(data_len);
                         *high_watermark = (*high_watermark).saturating_sub(data_len);
 
+                        let data = if let Some(decoder) = self.decoder.as_mut() {
+                            let mut output = Vec::new();
+                            decoder.decode(&data, &mut output)?;
+
+                            // the decoder may need more input before producing any output
+                            if output.is_empty() {
+                                continue;
+                            }
+
+                            Bytes::from(output)
+                        } else {
+                            data.freeze()
+                        };
+
                         // replace the placeholder with the actual data
-                        let placeholder = core::mem::replace(
-                            &mut chunks[response.chunks.consumed],
-                            data.freeze(),
-                        );
+                        let placeholder =
+                            core::mem::replace(&mut chunks[response.chunks.consumed], data);
                         debug_assert!(
                             placeholder.is_empty(),
                             "the placeholder should never contain data"
                         );
 
-                        response.bytes.consumed += data_len;
+                        response.bytes.consumed += chunks[response.chunks.consumed].len();
                         response.chunks.consumed += 1;
                     } else {
                         // wake the request if we didn't consume anything
@@ -886,6 +1162,36 @@ impl ReceiveStream {
         // [`ReceiveStreamState::DataRead`] if necessary.
         if let Some(total_size) = total_size {
             if total_size == self.receive_buffer.consumed_len() {
+                if let Some(mut decoder) = self.decoder.take() {
+                    let mut output = Vec::new();
+                    decoder.finish(&mut output)?;
+                    if !output.is_empty() {
+                        self.decoded = Some(Bytes::from(output));
+                    }
+                }
+            }
+
+            if let Some(decoded) = self.decoded.take() {
+                match request
+                    .chunks
+                    .as_mut()
+                    .filter(|chunks| response.chunks.consumed < chunks.len())
+                {
+                    Some(chunks) => {
+                        response.bytes.consumed += decoded.len();
+                        chunks[response.chunks.consumed] = decoded;
+                        response.chunks.consumed += 1;
+                        should_wake = false;
+                    }
+                    None => {
+                        // keep the output until the application provides space for it
+                        self.decoded = Some(decoded);
+                        should_wake = false;
+                    }
+                }
+            }
+
+            if total_size == self.receive_buffer.consumed_len() && self.decoded.is_none() {
                 // By the time we enter the final state all synchronization
                 // should have been cancelled.
                 debug_assert!(self.stop_sending_sync.is_cancelled());
@@ -912,10 +1218,20 @@ impl ReceiveStream {
             }
         }
 
+        // the skipped data precedes the chunks which were read, or the end of the stream
+        if response.chunks.consumed > 0 || matches!(response.status, ops::Status::Finished) {
+            response.expired = self.expired.take();
+        }
+
         let (available_bytes, available_chunks) = self.receive_buffer.report();
         response.bytes.available = available_bytes;
         response.chunks.available = available_chunks;
 
+        if let Some(decoded) = self.decoded.as_ref() {
+            response.bytes.available += decoded.len();
+            response.chunks.available += 1;
+        }
+
         if should_wake {
             if let Some(context) = context {
                 // Store the waker, in order to be able to wakeup the client when
@@ -956,6 +1272,14 @@ impl ReceiveStream {
     }
 }
 
+impl timer::Provider for ReceiveStream {
+    #[inline]
+    fn timers<Q: timer::Query>(&self, query: &mut Q) -> timer::Result {
+        self.read_deadline.timers(query)?;
+        Ok(())
+    }
+}
+
 impl StreamInterestProvider for ReceiveStream {
     #[inline]
     fn stream_interests(&self, interests: &mut StreamInterests) {
@@ -969,7 +1293,13 @@ impl StreamInterestProvider for ReceiveStream {
         interests.delivery_notifications |= self.stop_sending_sync.is_inflight()
             || self.flow_controller.read_window_sync.is_inflight();
 
+        interests.timeout |= self.read_deadline.is_armed();
+
         interests.with_transmission(|query| {
+            // the read deadline is armed on the next transmission
+            if self.read_deadline.needs_arming() {
+                query.on_new_data()?;
+            }
             self.stop_sending_sync.transmission_interest(query)?;
             self.flow_controller
                 .read_window_sync
@@ -8,11 +8,11 @@ use crate::{
     transmission::interest::Provider,
 };
 use bytes::Bytes;
-use core::{convert::TryFrom, task::Poll};
+use core::{convert::TryFrom, task::Poll, time::Duration};
 use s2n_quic_core::{
     application::Error as ApplicationErrorCode,
     connection, endpoint,
-    frame::{Frame, MaxData, MaxStreamData, ResetStream, StopSending},
+    frame::{ExpiredStreamData, Frame, MaxData, MaxStreamData, ResetStream, StopSending},
     stream::{ops, StreamError, StreamType},
     transport::Error as TransportError,
     varint::VarInt,
@@ -2548,3 +2548,156 @@ fn receiving_into_non_empty_buffers_returns_an_error() {
         "data should not be lost when returning an error"
     );
 }
+
+#[test]
+fn expired_stream_data_skips_missing_data() {
+    let mut test_env = setup_receive_only_test_env();
+
+    // the data before offset 100 was lost and won't be retransmitted
+    test_env.feed_data(VarInt::from_u8(100), 100);
+    test_env.assert_no_read_data();
+
+    let frame = ExpiredStreamData {
+        stream_id: test_env.stream.stream_id.into(),
+        offset: VarInt::from_u8(100),
+    };
+    let mut events = StreamEvents::new();
+    assert!(test_env
+        .stream
+        .on_expired_stream_data(&frame, &mut events)
+        .is_ok());
+
+    let mut chunks = [Bytes::new()];
+    let response = test_env
+        .run_request(ops::Request::default().receive(&mut chunks), false)
+        .unwrap()
+        .rx
+        .unwrap();
+    assert_eq!(response.chunks.consumed, 1);
+    assert_eq!(
+        &chunks[0][..],
+        &gen_pattern_test_data(VarInt::from_u8(100), 100)[..]
+    );
+    // the skipped data is reported along with the data following it
+    assert_eq!(response.expired, Some(0..100));
+
+    // retransmitted and reordered frames are ignored
+    assert!(test_env
+        .stream
+        .on_expired_stream_data(&frame, &mut events)
+        .is_ok());
+    test_env.assert_no_read_data();
+}
+
+#[test]
+fn receive_buffer_size_updates_the_flow_control_window() {
+    let mut test_env = setup_receive_only_test_env();
+
+    let window: u64 = test_env
+        .stream
+        .receive_stream
+        .flow_controller
+        .current_stream_receive_window()
+        .into();
+
+    // growing the buffer advertises the larger window right away
+    test_env
+        .run_request(
+            ops::Request::default().with_receive_buffer_size((window * 2) as u32),
+            false,
+        )
+        .unwrap();
+    assert_eq!(
+        stream_interests(&["tx"]),
+        test_env.stream.get_stream_interests()
+    );
+
+    test_env.assert_write_frames(1);
+    let mut sent_frame = test_env.sent_frames.pop_front().expect("Frame is written");
+    assert_eq!(
+        Frame::MaxStreamData(MaxStreamData {
+            stream_id: test_env.stream.stream_id.into(),
+            maximum_stream_data: VarInt::new(window * 2).unwrap(),
+        }),
+        sent_frame.as_frame()
+    );
+
+    // an empty buffer is rejected since the peer would never be able to send any data
+    assert!(matches!(
+        test_env.run_request(ops::Request::default().with_receive_buffer_size(0), false),
+        Err(StreamError::InvalidConfiguration { .. })
+    ));
+
+    // shrinking the buffer can't revoke the advertised window
+    test_env
+        .run_request(
+            ops::Request::default().with_receive_buffer_size((window / 2) as u32),
+            false,
+        )
+        .unwrap();
+
+    // the smaller window takes effect once the data is read
+    test_env.feed_data(VarInt::from_u32(0), window as usize);
+    assert_eq!(window as usize, test_env.consume_all_data());
+    assert_eq!(
+        window * 2,
+        Into::<u64>::into(
+            test_env
+                .stream
+                .receive_stream
+                .flow_controller
+                .current_stream_receive_window()
+        )
+    );
+    test_env.assert_write_frames(0);
+
+    test_env.feed_data(VarInt::new(window).unwrap(), window as usize);
+    assert_eq!(window as usize, test_env.consume_all_data());
+    assert_eq!(
+        window * 2 + window / 2,
+        Into::<u64>::into(
+            test_env
+                .stream
+                .receive_stream
+                .flow_controller
+                .current_stream_receive_window()
+        )
+    );
+}
+
+/// Ensures a read which exceeds its timeout releases the reader, fails with a timeout error and
+/// retains any out-of-order data
+#[test]
+fn read_timeout_test() {
+    let timeout = Duration::from_millis(100);
+    let mut test_env = setup_receive_only_test_env();
+
+    test_env.feed_data(VarInt::from_u32(0), 500);
+    assert_eq!(500, test_env.consume_all_data());
+    test_env.feed_data(VarInt::from_u32(1000), 500);
+
+    let mut chunks = [Bytes::new()];
+    let mut request = ops::Request::default();
+    request.receive(&mut chunks).with_read_timeout(timeout);
+    assert!(test_env.poll_request(&mut request).is_pending());
+
+    // the deadline is armed on the next transmission
+    let _ = test_env.transmit();
+    let mut events = StreamEvents::new();
+    test_env
+        .stream
+        .on_timeout(test_env.current_time + timeout, &mut events);
+    assert_eq!(1, events.waker_count());
+    assert!(test_env.stream.receive_stream.read_waiter.is_none());
+
+    let mut chunks = [Bytes::new()];
+    let result = test_env.run_request(ops::Request::default().receive(&mut chunks), true);
+    assert!(
+        matches!(result, Err(StreamError::Timeout { .. })),
+        "{result:?}"
+    );
+
+    // the stream remains usable
+    test_env.feed_data(VarInt::from_u32(500), 500);
+    assert_eq!(1000, test_env.consume_all_data());
+}
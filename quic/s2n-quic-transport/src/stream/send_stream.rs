@@ -4,6 +4,8 @@
 use crate::{
     contexts::{OnTransmitError, WriteContext},
     stream::{
+        deadline::Deadline,
+        expiration::Expirations,
         outgoing_connection_flow_controller::OutgoingConnectionFlowController,
         stream_events::StreamEvents,
         stream_interests::{StreamInterestProvider, StreamInterests},
@@ -11,11 +13,12 @@ use crate::{
     },
     sync::{
         data_sender::{self, DataSender, OutgoingDataFlowController},
-        OnceSync, PeriodicSync, ValueToFrameWriter,
+        IncrementalValueSync, OnceSync, PeriodicSync, ValueToFrameWriter,
     },
     transmission,
-    transmission::interest::Provider as _,
+    transmission::interest::{Provider as _, Query as _},
 };
+use alloc::{boxed::Box, vec::Vec};
 use bytes::Bytes;
 use core::{
     convert::TryFrom,
@@ -24,10 +27,14 @@ use core::{
 };
 use s2n_quic_core::{
     ack, application,
-    frame::{MaxStreamData, ResetStream, StopSending, StreamDataBlocked},
+    frame::{
+        ExpiredStreamData, MaxStreamData, ResetStream, ResetStreamAt, StopSending,
+        StreamDataBlocked,
+    },
     packet::number::PacketNumber,
-    stream::{ops, StreamId},
-    time::{timer, Timestamp},
+    path::MINIMUM_MAX_DATAGRAM_SIZE,
+    stream::{compression, ops, StreamId},
+    time::{timer, Timer, Timestamp},
     transport,
     varint::VarInt,
 };
@@ -95,6 +102,10 @@ pub struct OutgoingResetData {
     final_size: VarInt,
     /// The error code which should get transmitted in the RESET frame
     application_error_code: application::Error,
+    /// The amount of data which is delivered to the peer before the reset takes effect
+    ///
+    /// If this is set, a `RESET_STREAM_AT` frame is transmitted instead of a `RESET_STREAM` frame.
+    reliable_size: Option<VarInt>,
 }
 
 /// Writes the `RESET` frames based on the streams flow control window.
@@ -108,6 +119,15 @@ impl ValueToFrameWriter<OutgoingResetData> for ResetStreamToFrameWriter {
         stream_id: StreamId,
         context: &mut W,
     ) -> Option<PacketNumber> {
+        if let Some(reliable_size) = value.reliable_size {
+            return context.write_frame(&ResetStreamAt {
+                stream_id: stream_id.into(),
+                application_error_code: value.application_error_code.into(),
+                final_size: value.final_size,
+                reliable_size,
+            });
+        }
+
         context.write_frame(&ResetStream {
             stream_id: stream_id.into(),
             application_error_code: value.application_error_code.into(),
@@ -438,6 +458,59 @@ impl ValueToFrameWriter<VarInt> for StreamDataBlockedToFrameWriter {
     }
 }
 
+/// Writes the `EXPIRED_STREAM_DATA` frames.
+#[derive(Debug, Default)]
+pub(super) struct ExpiredStreamDataToFrameWriter {}
+
+impl ValueToFrameWriter<VarInt> for ExpiredStreamDataToFrameWriter {
+    fn write_value_as_frame<W: WriteContext>(
+        &self,
+        value: VarInt,
+        stream_id: StreamId,
+        context: &mut W,
+    ) -> Option<PacketNumber> {
+        context.write_frame(&ExpiredStreamData {
+            stream_id: stream_id.into(),
+            offset: value,
+        })
+    }
+}
+
+/// Holds back small writes so they can be combined into fewer packets
+#[derive(Debug)]
+struct Coalescing {
+    /// The maximum amount of time new data is held back. A zero delay disables coalescing.
+    delay: Duration,
+    /// The amount of unsent data at which held data is released, since it's enough to fill a
+    /// packet on the active path
+    threshold: u64,
+    /// Whether new data is currently being held back
+    is_holding: bool,
+    /// Releases the held data once it expires
+    ///
+    /// The API calls don't have access to the current time so the timer is armed on the next
+    /// transmission attempt.
+    timer: Timer,
+}
+
+impl Default for Coalescing {
+    fn default() -> Self {
+        Self {
+            delay: Duration::ZERO,
+            threshold: MINIMUM_MAX_DATAGRAM_SIZE as u64,
+            is_holding: false,
+            timer: Timer::default(),
+        }
+    }
+}
+
+impl Coalescing {
+    #[inline]
+    fn is_enabled(&self) -> bool {
+        self.delay > Duration::ZERO
+    }
+}
+
 /// The sending half of a stream
 #[derive(Debug)]
 pub struct SendStream {
@@ -447,6 +520,11 @@ pub struct SendStream {
     pub(super) data_sender: DataSender<StreamFlowController, data_sender::writer::Stream>,
     /// Synchronizes sending a `RESET` to the receiver
     pub(super) reset_sync: OnceSync<OutgoingResetData, ResetStreamToFrameWriter>,
+    /// A `RESET_STREAM_AT` which is waiting for enough flow control credits to be acquired
+    /// before the final size of the stream can be transmitted
+    pending_reset: Option<OutgoingResetData>,
+    /// Whether the peer supports resetting the stream with `RESET_STREAM_AT` frames
+    reset_stream_at_enabled: bool,
     /// The handle of a task that is currently waiting on new incoming data or
     /// on waiting for the finalization process to complete.
     ///
@@ -457,6 +535,20 @@ pub struct SendStream {
     final_state_observed: bool,
     /// Marks the stream as detached from the application
     detached: bool,
+    /// Coalesces small writes into fewer packets
+    coalescing: Coalescing,
+    /// Fails writes which are blocked for longer than the configured timeout
+    write_deadline: Deadline,
+    /// Tracks the data which is given up after its expiration
+    expirations: Expirations,
+    /// The expiration of data which is sent without an explicit one
+    default_expiration: Option<Duration>,
+    /// Synchronizes the offset before which the data expired to the peer
+    expired_sync: IncrementalValueSync<VarInt, ExpiredStreamDataToFrameWriter>,
+    /// Whether the peer supports skipping expired data with `EXPIRED_STREAM_DATA` frames
+    partial_reliability_enabled: bool,
+    /// Compresses the application data before it is buffered for transmission
+    encoder: Option<Box<dyn compression::Encoder>>,
 }
 
 impl SendStream {
@@ -465,6 +557,8 @@ impl SendStream {
         is_closed: bool,
         initial_window: VarInt,
         max_buffer_capacity: u32,
+        reset_stream_at_enabled: bool,
+        partial_reliability_enabled: bool,
     ) -> SendStream {
         // If the stream is created in closed state directly move into the
         // terminal state.
@@ -482,9 +576,22 @@ impl SendStream {
             state,
             data_sender,
             reset_sync: OnceSync::new(),
+            pending_reset: None,
+            reset_stream_at_enabled,
             write_waiter: None,
             final_state_observed: is_closed,
             detached: is_closed,
+            coalescing: Coalescing::default(),
+            write_deadline: Deadline::default(),
+            expirations: Expirations::default(),
+            default_expiration: None,
+            expired_sync: IncrementalValueSync::new(
+                VarInt::from_u8(0),
+                VarInt::from_u8(0),
+                VarInt::from_u8(1),
+            ),
+            partial_reliability_enabled,
+            encoder: None,
         };
 
         if is_closed {
@@ -509,7 +616,9 @@ impl SendStream {
         // The reason for this is that we allow users to enqueue more data than
         // the maximum flow control window.
 
-        if let SendStreamState::Sending = self.state {
+        // The window is also needed after a `RESET_STREAM_AT` was initiated, since the stream
+        // continues to transmit data up to the reliable size.
+        if let SendStreamState::Sending | SendStreamState::ResetSent(_) = self.state {
             self.data_sender
                 .flow_controller_mut()
                 .set_max_stream_data(frame.maximum_stream_data);
@@ -567,7 +676,8 @@ impl SendStream {
         //# code.
         let error = StreamError::stream_reset(frame.application_error_code.into());
 
-        if self.init_reset(ResetSource::StopSendingFrame, error) == InitResetResult::ResetInitiated
+        if self.init_reset(ResetSource::StopSendingFrame, error, None)
+            == InitResetResult::ResetInitiated
         {
             // Return the waker to wake up potential users of the stream.
             // If the Stream got reset, then blocked writers need to get woken up.
@@ -583,6 +693,10 @@ impl SendStream {
         self.data_sender
             .flow_controller_mut()
             .on_packet_ack(ack_set);
+        self.expired_sync.on_packet_ack(ack_set);
+
+        // acknowledging data may allow the following expired data to be given up
+        self.expire_data();
 
         let should_flush = self.write_waiter.as_ref().is_some_and(|w| w.1);
         let mut should_wake = false;
@@ -614,14 +728,22 @@ impl SendStream {
                         // final state due.
                         //
                         // In this state we also wake up the application,
-                        // since the finish operation has been confirmed.
-                        should_wake = true;
+                        // since the finish operation has been confirmed. The peer also
+                        // needs to acknowledge any expired data it was told to skip.
+                        should_wake = self.expired_sync.is_delivered();
                     }
                     _ => {}
                 }
             }
             SendStreamState::ResetSent(error_code) => {
-                if self.reset_sync.on_packet_ack(ack_set).is_ready() {
+                let _ = self.reset_sync.on_packet_ack(ack_set);
+
+                // If the stream was reset with `RESET_STREAM_AT`, the data up to the reliable
+                // size also needs to be acknowledged before the reset is complete.
+                if self.reset_sync.is_delivered()
+                    && self.data_sender.is_acknowledged()
+                    && self.expired_sync.is_delivered()
+                {
                     // A reset had been acknowledged. Enter the terminal state.
                     self.state = SendStreamState::ResetAcknowledged(error_code);
 
@@ -644,6 +766,7 @@ impl SendStream {
             .flow_controller_mut()
             .on_packet_loss(ack_set);
         self.reset_sync.on_packet_loss(ack_set);
+        self.expired_sync.on_packet_loss(ack_set);
     }
 
     /// Queries the component for any outgoing frames that need to get sent
@@ -652,11 +775,31 @@ impl SendStream {
         stream_id: StreamId,
         context: &mut W,
     ) -> Result<(), OnTransmitError> {
+        if self.coalescing.is_holding && !self.coalescing.timer.is_armed() {
+            self.coalescing
+                .timer
+                .set(context.current_time() + self.coalescing.delay);
+        }
+        self.write_deadline.on_transmit(context.current_time());
+        self.expirations.arm(context.current_time());
+
+        // give up the expired data before it's transmitted, since it may have been waiting on
+        // flow control credits
+        self.expire_data();
+
         self.reset_sync.on_transmit(stream_id, context)?;
         self.data_sender.on_transmit(stream_id.into(), context)?;
         self.data_sender
             .flow_controller_mut()
-            .on_transmit(stream_id, context)
+            .on_transmit(stream_id, context)?;
+        self.expired_sync.on_transmit(stream_id, context)?;
+
+        // transmitting data may have acquired enough window for a pending `RESET_STREAM_AT`
+        if self.flush_pending_reset() {
+            self.reset_sync.on_transmit(stream_id, context)?;
+        }
+
+        Ok(())
     }
 
     /// Updates the period at which `STREAM_DATA_BLOCKED` frames are sent to the peer
@@ -667,9 +810,56 @@ impl SendStream {
             .update_blocked_sync_period(blocked_sync_period)
     }
 
+    /// Returns `true` if the stream has expirations which start once the connection wakes up
+    pub fn needs_wakeup(&self) -> bool {
+        self.expirations.needs_arming()
+    }
+
+    /// Called when the connection wakes up after an API call
+    ///
+    /// This starts the lifetime of the data which was written since the last wakeup.
+    pub fn on_wakeup(&mut self, now: Timestamp) {
+        self.expirations.arm(now);
+    }
+
     /// Called when the connection timer expires
-    pub fn on_timeout(&mut self, now: Timestamp) {
-        self.data_sender.flow_controller_mut().on_timeout(now)
+    pub fn on_timeout(&mut self, now: Timestamp, events: &mut StreamEvents) {
+        self.data_sender.flow_controller_mut().on_timeout(now);
+
+        if self.coalescing.timer.poll_expiration(now).is_ready() {
+            self.release_held_data();
+        }
+
+        if self.write_deadline.on_timeout(now) {
+            // wake the blocked writer so it can observe the timeout
+            self.wake(events);
+
+            // the application is no longer waiting on the stream so release the unused buffer
+            // capacity. Any pending data is retained until it's acknowledged.
+            self.data_sender.shrink_to_fit();
+        }
+
+        if self.expirations.on_timeout(now) && self.expire_data() {
+            // giving up the expired data releases space in the send buffer
+            self.wake(events);
+        }
+    }
+
+    /// Called when the maximum datagram size of the active path changes
+    pub fn on_mtu_update(&mut self, max_datagram_size: u16) {
+        self.coalescing.threshold = max_datagram_size as u64;
+
+        // a smaller MTU may mean the held data already fills a packet
+        if self.coalescing.is_holding
+            && self.data_sender.unsent_len().as_u64() >= self.coalescing.threshold
+        {
+            self.release_held_data();
+        }
+    }
+
+    /// Called when the connection releases its buffers after being idle
+    pub fn on_hibernate(&mut self) {
+        self.data_sender.shrink_to_fit();
     }
 
     /// A reset that is triggered without having received a `RESET` frame.
@@ -678,6 +868,7 @@ impl SendStream {
             // This is remote in a sense we do not have to emit a message
             ResetSource::InternalReset,
             error,
+            None,
         );
 
         // Return the waker to wake up potential users of the stream.
@@ -701,13 +892,66 @@ impl SendStream {
     pub fn on_connection_window_available(&mut self) {
         // Outstanding flow control requests are only fulfilled if the Stream
         // was still trying to send data.
-        if let SendStreamState::Sending = self.state {
+        if let SendStreamState::Sending | SendStreamState::ResetSent(_) = self.state {
             self.data_sender
                 .flow_controller_mut()
                 .try_acquire_connection_window();
         }
     }
 
+    /// Returns the offset of the next byte written by the application
+    pub fn send_offset(&self) -> VarInt {
+        self.data_sender.total_enqueued_len()
+    }
+
+    /// Holds back data at or beyond `offset` until called with `None`
+    pub fn set_send_barrier(&mut self, offset: Option<VarInt>) {
+        self.data_sender.set_transmission_limit(offset);
+    }
+
+    /// Returns `true` if the data before `offset` no longer needs to be delivered
+    pub fn is_send_acknowledged(&self, offset: VarInt) -> bool {
+        self.data_sender.is_acknowledged_up_to(offset)
+    }
+
+    /// Gives up the delivery of the expired data
+    ///
+    /// Returns `true` if any data expired.
+    fn expire_data(&mut self) -> bool {
+        let mut any_expired = false;
+
+        while let Some(offset) = self
+            .expirations
+            .poll_expired(|offset| self.data_sender.is_acknowledged_up_to(offset))
+        {
+            // the rest of the data is given up once more flow control credits are available
+            let Some(offset) = self.data_sender.expire(offset) else {
+                break;
+            };
+            self.expired_sync.update_latest_value(offset);
+            any_expired = true;
+        }
+
+        any_expired
+    }
+
+    /// Returns `true` if the peer acknowledged all of the data and the FIN, or was told to
+    /// skip the data which expired
+    fn is_finished(&self) -> bool {
+        self.data_sender.state() == data_sender::State::Finished && self.expired_sync.is_delivered()
+    }
+
+    /// Transmits any new data that is held back for coalescing
+    fn release_held_data(&mut self) {
+        if !self.coalescing.is_holding {
+            return;
+        }
+
+        self.coalescing.is_holding = false;
+        self.coalescing.timer.cancel();
+        self.data_sender.hold_new_data(false);
+    }
+
     /// Wakes up the application on progress updates
     ///
     /// If there is not a registered waker and the stream is in a terminal state,
@@ -744,10 +988,43 @@ impl SendStream {
     ///
     /// If the `Stream` gets reset while waiting for acknowledgement of all
     /// outstanding data the method will return a [`StreamError`].
+    ///
+    /// If a write timeout is configured and the request remains blocked for longer than the
+    /// timeout without making any progress, the method will return a timeout [`StreamError`].
     pub fn poll_request(
         &mut self,
         request: &mut ops::tx::Request,
         context: Option<&Context>,
+    ) -> Result<ops::tx::Response, StreamError> {
+        if let Some(timeout) = request.timeout {
+            self.write_deadline.set_timeout(timeout);
+        }
+
+        let result = self.poll_request_impl(request, context);
+
+        // Resets are excluded since they don't fail
+        if context.is_none() || request.reset.is_some() {
+            return result;
+        }
+
+        match &result {
+            // only fail operations which didn't make any progress
+            Ok(response) if response.will_wake && response.chunks.consumed == 0 => {
+                if self.write_deadline.on_blocked() {
+                    self.write_waiter = None;
+                    return Err(StreamError::timeout());
+                }
+            }
+            _ => self.write_deadline.on_ready(),
+        }
+
+        result
+    }
+
+    fn poll_request_impl(
+        &mut self,
+        request: &mut ops::tx::Request,
+        context: Option<&Context>,
     ) -> Result<ops::tx::Response, StreamError> {
         let mut response = ops::tx::Response::default();
 
@@ -755,6 +1032,38 @@ impl SendStream {
             self.detach();
         }
 
+        if let Some(delay) = request.coalescing_delay {
+            self.coalescing.delay = delay;
+
+            if !self.coalescing.is_enabled() {
+                self.release_held_data();
+            }
+        }
+
+        if let Some(buffer_size) = request.buffer_size {
+            // the stream would never be able to buffer any data
+            if buffer_size == 0 {
+                return Err(StreamError::invalid_configuration(
+                    "the send buffer size must be greater than 0",
+                ));
+            }
+            self.data_sender.set_max_buffer_capacity(buffer_size);
+        }
+
+        if let Some(lifetime) = request.default_expiration {
+            self.default_expiration = Some(lifetime).filter(|lifetime| !lifetime.is_zero());
+        }
+
+        if let Some(encoder) = request.encoder.take() {
+            // switching encoders in the middle of the stream would corrupt the peer's output
+            if self.encoder.is_some() || self.data_sender.total_enqueued_len() != VarInt::ZERO {
+                return Err(StreamError::compression_error(
+                    "the encoder must be set before any data is sent",
+                ));
+            }
+            self.encoder = Some(encoder);
+        }
+
         macro_rules! store_waker {
             ($should_flush:expr) => {
                 // Store the waker, in order to be able to wakeup the caller
@@ -777,6 +1086,7 @@ impl SendStream {
             let _ = self.init_reset(
                 ResetSource::LocalApplication,
                 StreamError::stream_reset(error_code),
+                request.reliable_size,
             );
 
             // mark the stream as resetting
@@ -813,6 +1123,11 @@ impl SendStream {
         }
 
         if let Some(chunks) = request.chunks.as_mut().filter(|chunks| !chunks.is_empty()) {
+            let expiration = request
+                .expiration
+                .or(self.default_expiration)
+                .filter(|_| self.partial_reliability_enabled);
+
             for chunk in chunks.iter_mut() {
                 // empty chunks are automatically consumed
                 if chunk.is_empty() {
@@ -825,17 +1140,27 @@ impl SendStream {
                 if !self.can_push() {
                     store_waker!(false);
 
+                    // the buffer is full so there's no reason to hold back any data
+                    self.release_held_data();
+
                     // no more progress can be made on the operation
                     return Ok(response);
                 }
 
+                let start = self.data_sender.total_enqueued_len();
+                self.push(chunk)?;
+
                 response.bytes.consumed += chunk.len();
                 response.chunks.consumed += 1;
+                *chunk = Bytes::new();
 
-                self.data_sender
-                    .push(core::mem::replace(chunk, Bytes::new()));
+                if let Some(lifetime) = expiration {
+                    let end = self.data_sender.total_enqueued_len();
+                    self.expirations.push(start, end, lifetime);
+                }
             }
-        } else if !request.finish && !request.flush && context.is_some() {
+        } else if !request.finish && !request.flush && !request.await_finished && context.is_some()
+        {
             // if `chunks` are `None` or `Some(&[])` and we're not ending or flushing the stream,
             // the caller is only interested in notifications of state changes.
 
@@ -850,9 +1175,15 @@ impl SendStream {
             }
         }
 
+        if request.flush || request.finish {
+            self.flush_encoder(request.finish)?;
+        }
+
+        self.coalesce(request);
+
         if request.finish {
             match self.data_sender.state() {
-                data_sender::State::Finished => {
+                data_sender::State::Finished if self.is_finished() => {
                     // All packets incl. the one with the FIN flag had been acknowledged.
                     // => We are done with this stream!
                     // We just record here that the user actually has retrieved the
@@ -876,6 +1207,18 @@ impl SendStream {
                     }
                 }
             }
+        } else if request.await_finished {
+            if self.is_finished() {
+                // The peer acknowledged all of the data and the FIN
+                self.final_state_observed = true;
+                self.write_waiter = None;
+                response.status = ops::Status::Finished;
+                return Ok(response);
+            }
+
+            // block the request until the application finishes the stream and the peer has
+            // ACKed the last frame
+            store_waker!(true);
         } else if request.flush && !self.data_sender.is_empty() {
             // notify callers once the buffer has been flushed
             store_waker!(true);
@@ -891,9 +1234,12 @@ impl SendStream {
             data_sender::State::Finishing(_) => {
                 response.status = ops::Status::Finishing;
             }
-            data_sender::State::Finished => {
+            data_sender::State::Finished if self.is_finished() => {
                 response.status = ops::Status::Finished;
             }
+            data_sender::State::Finished => {
+                response.status = ops::Status::Finishing;
+            }
             data_sender::State::Cancelled(error) => {
                 // TODO determine if the peer has acknowledged the reset
                 response.status = ops::Status::Reset(error);
@@ -903,6 +1249,74 @@ impl SendStream {
         Ok(response)
     }
 
+    /// Enqueues the chunk for transmission, compressing it if an encoder is configured
+    fn push(&mut self, chunk: &Bytes) -> Result<(), StreamError> {
+        if let Some(encoder) = self.encoder.as_mut() {
+            let mut output = Vec::new();
+            encoder.encode(chunk, &mut output)?;
+            self.push_encoded(output);
+        } else {
+            self.data_sender.push(chunk.clone());
+        }
+
+        Ok(())
+    }
+
+    /// Enqueues the output buffered in the encoder, terminating the compressed stream if
+    /// `finish` is set
+    fn flush_encoder(&mut self, finish: bool) -> Result<(), StreamError> {
+        if self.data_sender.state() != data_sender::State::Sending {
+            return Ok(());
+        }
+
+        let mut output = Vec::new();
+
+        if finish {
+            if let Some(mut encoder) = self.encoder.take() {
+                encoder.finish(&mut output)?;
+            }
+        } else if let Some(encoder) = self.encoder.as_mut() {
+            encoder.flush(&mut output)?;
+        }
+
+        self.push_encoded(output);
+
+        Ok(())
+    }
+
+    #[inline]
+    fn push_encoded(&mut self, output: Vec<u8>) {
+        // encoders can buffer input without producing any output
+        if !output.is_empty() {
+            self.data_sender.push(Bytes::from(output));
+        }
+    }
+
+    /// Decides if the data pushed by the request should be held back for coalescing
+    fn coalesce(&mut self, request: &ops::tx::Request) {
+        if !self.coalescing.is_enabled() {
+            return;
+        }
+
+        // flushing or finishing the stream sends everything that's buffered
+        if request.flush || request.finish || !self.can_push() {
+            self.release_held_data();
+            return;
+        }
+
+        let unsent_len = self.data_sender.unsent_len().as_u64();
+
+        if unsent_len >= self.coalescing.threshold {
+            self.release_held_data();
+            return;
+        }
+
+        if unsent_len > 0 && !self.coalescing.is_holding {
+            self.coalescing.is_holding = true;
+            self.data_sender.hold_new_data(true);
+        }
+    }
+
     fn detach(&mut self) {
         self.detached = true;
         self.write_waiter = None;
@@ -914,8 +1328,7 @@ impl SendStream {
             }
             // If we are finished sending and the application isn't subscribed to updates
             SendStreamState::Sending => {
-                self.final_state_observed |=
-                    matches!(self.data_sender.state(), data_sender::State::Finished);
+                self.final_state_observed |= self.is_finished();
             }
             _ => {}
         }
@@ -950,7 +1363,15 @@ impl SendStream {
     /// Starts the reset procedure if the Stream has not been in a RESET state
     /// before. The method will return whether calling this method caused the
     /// `Stream` to enter a RESET state.
-    fn init_reset(&mut self, reason: ResetSource, error: StreamError) -> InitResetResult {
+    ///
+    /// If a `reliable_size` is provided and the peer supports `RESET_STREAM_AT`, the data up to
+    /// that offset continues to be delivered to the peer.
+    fn init_reset(
+        &mut self,
+        reason: ResetSource,
+        error: StreamError,
+        reliable_size: Option<VarInt>,
+    ) -> InitResetResult {
         match self.state {
             SendStreamState::ResetSent(_) | SendStreamState::ResetAcknowledged(_) => {
                 return InitResetResult::ResetNotNecessary
@@ -963,6 +1384,11 @@ impl SendStream {
             SendStreamState::Sending => {}
         }
 
+        // the held data will either be dropped or needs to be delivered up to the reliable size
+        self.release_held_data();
+        // the remaining data is either dropped or delivered reliably
+        self.expirations.clear();
+
         self.state = if reason.is_internal() {
             // Internal Resets do not require an ACK
             SendStreamState::ResetAcknowledged(error)
@@ -981,9 +1407,22 @@ impl SendStream {
         //# a stream; this causes the sending part of that stream to open and
         //# then immediately transition to the "Reset Sent" state.
 
-        // Clear the send buffer. Since we initiated a RESET, there is no need
-        // to send or resend the remaining data.
-        self.data_sender.stop_sending(error);
+        let reliable_size = reliable_size
+            .filter(|_| self.reset_stream_at_enabled && !reason.is_internal())
+            .map(|size| size.min(self.data_sender.total_enqueued_len()))
+            .filter(|size| *size > VarInt::from_u8(0));
+
+        if let Some(reliable_size) = reliable_size {
+            // Only drop the data past the reliable size. The remaining data is still
+            // transmitted and retransmitted until the peer acknowledges it.
+            self.data_sender.stop_sending_at(error, reliable_size);
+        } else {
+            // Clear the send buffer. Since we initiated a RESET, there is no need
+            // to send or resend the remaining data.
+            self.data_sender.stop_sending(error);
+            // The peer discards all of the data so it no longer needs to skip the expired data
+            self.expired_sync.stop_sync();
+        }
 
         // For an internal reset (which provides no error_code) we do not need
         // to transmit the reset frame
@@ -997,13 +1436,12 @@ impl SendStream {
                 // Stream and can not use for other Streams. Therefore we
                 // deliver this value to the peer - even if we have actually
                 // transmitted less data actually.
-                self.reset_sync.request_delivery(OutgoingResetData {
+                self.pending_reset = Some(OutgoingResetData {
                     application_error_code: error,
-                    final_size: self
-                        .data_sender
-                        .flow_controller()
-                        .acquired_connection_flow_controller_window(),
+                    final_size: VarInt::from_u8(0),
+                    reliable_size,
                 });
+                self.flush_pending_reset();
             }
             (false, _) => {
                 unreachable!("Non internal reasons must be accommodated by an error code")
@@ -1015,12 +1453,44 @@ impl SendStream {
 
         InitResetResult::ResetInitiated
     }
+
+    /// Requests delivery of the pending reset once the final size of the stream is known
+    ///
+    /// Returns `true` if the reset was scheduled for delivery.
+    fn flush_pending_reset(&mut self) -> bool {
+        let acquired_window = self
+            .data_sender
+            .flow_controller()
+            .acquired_connection_flow_controller_window();
+
+        let Some(mut reset) = self.pending_reset else {
+            return false;
+        };
+
+        // The final size can't be smaller than the reliable size. Wait until enough window
+        // has been acquired for the data up to the reliable size.
+        if reset
+            .reliable_size
+            .is_some_and(|size| acquired_window < size)
+        {
+            return false;
+        }
+
+        reset.final_size = acquired_window;
+        self.pending_reset = None;
+        self.reset_sync.request_delivery(reset);
+
+        true
+    }
 }
 
 impl timer::Provider for SendStream {
     #[inline]
     fn timers<Q: timer::Query>(&self, query: &mut Q) -> timer::Result {
         self.data_sender.flow_controller().timers(query)?;
+        self.coalescing.timer.timers(query)?;
+        self.write_deadline.timers(query)?;
+        self.expirations.timers(query)?;
         Ok(())
     }
 }
@@ -1044,15 +1514,35 @@ impl StreamInterestProvider for SendStream {
             //# A sender MUST NOT send a STREAM or
             //# STREAM_DATA_BLOCKED frame for a stream in the "Reset Sent" state or
             //# any terminal state -- that is, after sending a RESET_STREAM frame.
-            SendStreamState::ResetSent(_) => {
-                interests.with_transmission(|query| self.reset_sync.transmission_interest(query))
-            }
+            //
+            // The only exception is a reset with `RESET_STREAM_AT`, which continues to transmit
+            // data up to the reliable size.
+            SendStreamState::ResetSent(_) => interests.with_transmission(|query| {
+                if self.pending_reset.is_some() || !self.data_sender.is_acknowledged() {
+                    self.data_sender.transmission_interest(query)?;
+                    self.data_sender
+                        .flow_controller()
+                        .transmission_interest(query)?;
+                }
+                self.reset_sync.transmission_interest(query)?;
+                self.expired_sync.transmission_interest(query)?;
+                Ok(())
+            }),
             _ => interests.with_transmission(|query| {
+                // the coalescing timer and write deadline are armed on the next transmission, as
+                // are any expirations which weren't armed by a wakeup yet
+                if (self.coalescing.is_holding && !self.coalescing.timer.is_armed())
+                    || self.write_deadline.needs_arming()
+                    || self.expirations.needs_arming()
+                {
+                    query.on_new_data()?;
+                }
                 self.data_sender.transmission_interest(query)?;
                 self.data_sender
                     .flow_controller()
                     .transmission_interest(query)?;
                 self.reset_sync.transmission_interest(query)?;
+                self.expired_sync.transmission_interest(query)?;
                 Ok(())
             }),
         }
@@ -1072,8 +1562,13 @@ impl StreamInterestProvider for SendStream {
             _ => {}
         }
 
-        interests.delivery_notifications |=
-            self.data_sender.is_inflight() || self.reset_sync.is_inflight();
+        interests.timeout |= self.coalescing.timer.is_armed()
+            || self.write_deadline.is_armed()
+            || self.expirations.is_armed();
+
+        interests.delivery_notifications |= self.data_sender.is_inflight()
+            || self.reset_sync.is_inflight()
+            || self.expired_sync.is_inflight();
     }
 }
 
@@ -13,7 +13,7 @@ use s2n_codec::DecoderBufferMut;
 use s2n_quic_core::{
     application::Error as ApplicationErrorCode,
     connection, endpoint,
-    frame::{Frame, MaxData, MaxStreamData, StopSending},
+    frame::{ExpiredStreamData, Frame, MaxData, MaxStreamData, StopSending},
     packet::number::PacketNumber,
     stream::{ops, StreamType},
     transmission,
@@ -2854,3 +2854,589 @@ fn can_query_stream_readiness() {
         }
     }
 }
+
+#[test]
+fn small_writes_are_coalesced_until_the_delay_expires() {
+    let mut test_env = setup_send_only_test_env();
+    let delay = Duration::from_millis(25);
+
+    test_env
+        .run_request(
+            ops::Request::default()
+                .with_coalescing_delay(delay)
+                .send(&mut gen_pattern_test_chunks(VarInt::from_u8(0), &[100])),
+            false,
+        )
+        .expect("request should succeed");
+
+    execute_instructions(
+        &mut test_env,
+        &[
+            // the stream needs a transmission to arm the timer
+            Instruction::CheckInterests(stream_interests(&["tx"])),
+            Instruction::CheckNoTx,
+            Instruction::CheckInterests(stream_interests(&["timeout"])),
+            Instruction::EnqueueData(VarInt::from_u8(100), 100, true),
+            Instruction::CheckNoTx,
+            Instruction::CheckInterests(stream_interests(&["timeout"])),
+        ],
+    );
+
+    // the data is still held before the delay expires
+    test_env
+        .stream
+        .on_timeout(test_env.current_time + delay / 2, &mut StreamEvents::new());
+    assert_eq!(
+        test_env.stream.get_stream_interests(),
+        stream_interests(&["timeout"])
+    );
+
+    test_env.current_time += delay;
+    test_env
+        .stream
+        .on_timeout(test_env.current_time, &mut StreamEvents::new());
+
+    execute_instructions(
+        &mut test_env,
+        &[
+            Instruction::CheckInterests(stream_interests(&["tx"])),
+            Instruction::CheckDataTx(VarInt::from_u8(0), 200, false, false, pn(0)),
+            Instruction::CheckInterests(stream_interests(&["ack"])),
+        ],
+    );
+}
+
+#[test]
+fn coalesced_writes_are_released() {
+    let delay = Duration::from_millis(25);
+    let threshold = MINIMUM_MAX_DATAGRAM_SIZE as usize;
+
+    // filling a packet
+    let mut test_env = setup_send_only_test_env();
+    test_env
+        .run_request(
+            ops::Request::default().with_coalescing_delay(delay).send(
+                &mut gen_pattern_test_chunks(VarInt::from_u8(0), &[threshold]),
+            ),
+            false,
+        )
+        .expect("request should succeed");
+    execute_instructions(
+        &mut test_env,
+        &[
+            Instruction::CheckInterests(stream_interests(&["tx"])),
+            Instruction::CheckDataTx(VarInt::from_u8(0), threshold, false, false, pn(0)),
+        ],
+    );
+
+    // flushing, finishing and disabling coalescing
+    let releases: [(fn(&mut ops::Request), bool); 3] = [
+        (
+            |request| {
+                request.flush();
+            },
+            false,
+        ),
+        (
+            |request| {
+                request.finish();
+            },
+            true,
+        ),
+        (
+            |request| {
+                request.with_coalescing_delay(Duration::ZERO);
+            },
+            false,
+        ),
+    ];
+
+    for (release, is_fin) in releases {
+        let mut test_env = setup_send_only_test_env();
+        test_env
+            .run_request(
+                ops::Request::default()
+                    .with_coalescing_delay(delay)
+                    .send(&mut gen_pattern_test_chunks(VarInt::from_u8(0), &[100])),
+                false,
+            )
+            .expect("request should succeed");
+
+        execute_instructions(
+            &mut test_env,
+            &[
+                Instruction::CheckNoTx,
+                Instruction::CheckInterests(stream_interests(&["timeout"])),
+            ],
+        );
+
+        let mut request = ops::Request::default();
+        release(&mut request);
+        let _ = test_env.run_request(&mut request, false);
+
+        execute_instructions(
+            &mut test_env,
+            &[
+                Instruction::CheckInterests(stream_interests(&["tx"])),
+                Instruction::CheckDataTx(VarInt::from_u8(0), 100, is_fin, false, pn(0)),
+            ],
+        );
+    }
+}
+
+#[test]
+fn coalescing_threshold_follows_mtu() {
+    let delay = Duration::from_millis(25);
+    let threshold = MINIMUM_MAX_DATAGRAM_SIZE as usize;
+
+    let mut test_env = setup_send_only_test_env();
+    test_env.stream.on_mtu_update(1500);
+
+    // a packet on the minimum MTU isn't enough to fill a packet on the larger path
+    test_env
+        .run_request(
+            ops::Request::default().with_coalescing_delay(delay).send(
+                &mut gen_pattern_test_chunks(VarInt::from_u8(0), &[threshold]),
+            ),
+            false,
+        )
+        .expect("request should succeed");
+    execute_instructions(
+        &mut test_env,
+        &[
+            Instruction::CheckNoTx,
+            Instruction::CheckInterests(stream_interests(&["timeout"])),
+        ],
+    );
+
+    // shrinking the MTU releases the held data
+    test_env.stream.on_mtu_update(MINIMUM_MAX_DATAGRAM_SIZE);
+    execute_instructions(
+        &mut test_env,
+        &[
+            Instruction::CheckInterests(stream_interests(&["tx"])),
+            Instruction::CheckDataTx(VarInt::from_u8(0), threshold, false, false, pn(0)),
+        ],
+    );
+}
+
+#[test]
+fn await_finished_waits_for_fin_ack() {
+    let mut test_env = setup_send_only_test_env();
+
+    execute_instructions(
+        &mut test_env,
+        &[
+            Instruction::EnqueueData(VarInt::from_u8(0), 100, true),
+            Instruction::CheckDataTx(VarInt::from_u8(0), 100, false, false, pn(0)),
+            Instruction::AckPacket(pn(0), ExpectWakeup(Some(false))),
+        ],
+    );
+
+    // all of the data was acknowledged but the stream hasn't been finished yet
+    assert!(test_env
+        .poll_request(ops::Request::default().await_finished())
+        .is_pending());
+
+    // finishing the stream doesn't complete the request until the FIN is acknowledged
+    test_env
+        .run_request(ops::Request::default().finish(), false)
+        .expect("finish should succeed");
+    assert!(test_env
+        .poll_request(ops::Request::default().await_finished())
+        .is_pending());
+
+    execute_instructions(
+        &mut test_env,
+        &[
+            Instruction::CheckDataTx(VarInt::from_u8(100), 0, true, false, pn(1)),
+            Instruction::AckPacket(pn(1), ExpectWakeup(Some(true))),
+        ],
+    );
+
+    let response = test_env
+        .poll_request(ops::Request::default().await_finished())
+        .map(|response| response.unwrap().tx().unwrap().status);
+    assert_eq!(response, Poll::Ready(ops::Status::Finished));
+}
+
+#[test]
+fn send_barrier_holds_back_data_and_fin() {
+    let mut test_env = setup_send_only_test_env();
+
+    execute_instructions(
+        &mut test_env,
+        &[Instruction::EnqueueData(VarInt::from_u8(0), 100, true)],
+    );
+
+    let offset = test_env.stream.send_offset();
+    assert_eq!(offset, VarInt::from_u8(100));
+    test_env.stream.set_send_barrier(Some(offset));
+    assert!(!test_env.stream.is_send_acknowledged(offset));
+
+    execute_instructions(
+        &mut test_env,
+        &[
+            Instruction::EnqueueData(VarInt::from_u8(100), 100, true),
+            Instruction::Finish(false),
+            Instruction::CheckDataTx(VarInt::from_u8(0), 100, false, false, pn(0)),
+            // the data after the barrier isn't transmitted
+            Instruction::CheckInterests(stream_interests(&["ack"])),
+            Instruction::CheckNoTx,
+            Instruction::AckPacket(pn(0), ExpectWakeup(None)),
+        ],
+    );
+
+    assert!(test_env.stream.is_send_acknowledged(offset));
+    assert!(!test_env
+        .stream
+        .is_send_acknowledged(test_env.stream.send_offset()));
+    test_env.stream.set_send_barrier(None);
+
+    execute_instructions(
+        &mut test_env,
+        &[
+            Instruction::CheckInterests(stream_interests(&["tx"])),
+            Instruction::CheckDataTx(VarInt::from_u8(100), 100, true, false, pn(1)),
+        ],
+    );
+}
+
+#[test]
+fn expired_data_is_not_retransmitted() {
+    let test_env_config = TestEnvironmentConfig {
+        stream_id: StreamId::initial(endpoint::Type::Server, StreamType::Unidirectional),
+        partial_reliability_enabled: true,
+        ..Default::default()
+    };
+    let mut test_env = setup_stream_test_env_with_config(test_env_config);
+    let lifetime = Duration::from_millis(100);
+
+    test_env
+        .run_request(
+            ops::Request::default()
+                .with_expiration(lifetime)
+                .send(&mut gen_pattern_test_chunks(VarInt::from_u8(0), &[100])),
+            false,
+        )
+        .expect("request should succeed");
+
+    execute_instructions(
+        &mut test_env,
+        &[
+            Instruction::CheckDataTx(VarInt::from_u8(0), 100, false, false, pn(0)),
+            // the expiration is armed once the data is transmitted
+            Instruction::CheckInterests(stream_interests(&["ack", "timeout"])),
+            Instruction::NackPacket(pn(0)),
+            Instruction::CheckInterests(stream_interests(&["lost", "timeout"])),
+        ],
+    );
+
+    test_env.current_time += lifetime;
+    test_env
+        .stream
+        .on_timeout(test_env.current_time, &mut StreamEvents::new());
+
+    // the peer is told to skip the expired data instead of receiving a retransmission
+    let mut sent_frame = test_env.transmit().expect("no frame was written");
+    assert_eq!(
+        Frame::ExpiredStreamData(ExpiredStreamData {
+            stream_id: test_env.stream.stream_id.into(),
+            offset: VarInt::from_u8(100),
+        }),
+        sent_frame.as_frame()
+    );
+
+    execute_instructions(
+        &mut test_env,
+        &[
+            Instruction::CheckNoTx,
+            Instruction::CheckInterests(stream_interests(&["ack"])),
+            Instruction::AckPacket(pn(1), ExpectWakeup(None)),
+            Instruction::Finish(false),
+            Instruction::CheckDataTx(VarInt::from_u8(100), 0, true, false, pn(2)),
+        ],
+    );
+}
+
+#[test]
+fn unsent_expired_data_is_dropped() {
+    let test_env_config = TestEnvironmentConfig {
+        stream_id: StreamId::initial(endpoint::Type::Server, StreamType::Unidirectional),
+        partial_reliability_enabled: true,
+        ..Default::default()
+    };
+    let mut test_env = setup_stream_test_env_with_config(test_env_config);
+    let lifetime = Duration::from_millis(100);
+
+    test_env
+        .run_request(
+            ops::Request::default()
+                .with_expiration(lifetime)
+                .send(&mut gen_pattern_test_chunks(VarInt::from_u8(0), &[100])),
+            false,
+        )
+        .expect("request should succeed");
+
+    // the lifetime starts when the connection wakes up after the write
+    assert!(test_env.stream.needs_wakeup());
+    test_env.stream.on_wakeup(test_env.current_time);
+    assert!(!test_env.stream.needs_wakeup());
+
+    test_env.current_time += lifetime;
+    test_env
+        .stream
+        .on_timeout(test_env.current_time, &mut StreamEvents::new());
+
+    // the peer is told to skip the data instead of it being transmitted
+    let mut sent_frame = test_env.transmit().expect("no frame was written");
+    assert_eq!(
+        Frame::ExpiredStreamData(ExpiredStreamData {
+            stream_id: test_env.stream.stream_id.into(),
+            offset: VarInt::from_u8(100),
+        }),
+        sent_frame.as_frame()
+    );
+
+    execute_instructions(
+        &mut test_env,
+        &[
+            Instruction::CheckNoTx,
+            Instruction::AckPacket(pn(0), ExpectWakeup(None)),
+            Instruction::Finish(false),
+            Instruction::CheckDataTx(VarInt::from_u8(100), 0, true, false, pn(1)),
+        ],
+    );
+}
+
+#[test]
+fn expiration_is_ignored_without_partial_reliability() {
+    let mut test_env = setup_send_only_test_env();
+    let lifetime = Duration::from_millis(100);
+
+    test_env
+        .run_request(
+            ops::Request::default()
+                .with_expiration(lifetime)
+                .send(&mut gen_pattern_test_chunks(VarInt::from_u8(0), &[100])),
+            false,
+        )
+        .expect("request should succeed");
+
+    execute_instructions(
+        &mut test_env,
+        &[
+            Instruction::CheckDataTx(VarInt::from_u8(0), 100, false, false, pn(0)),
+            Instruction::CheckInterests(stream_interests(&["ack"])),
+            Instruction::NackPacket(pn(0)),
+        ],
+    );
+
+    test_env.current_time += lifetime;
+    test_env
+        .stream
+        .on_timeout(test_env.current_time, &mut StreamEvents::new());
+
+    // the data is still delivered reliably
+    execute_instructions(
+        &mut test_env,
+        &[Instruction::CheckDataTx(
+            VarInt::from_u8(0),
+            100,
+            false,
+            false,
+            pn(1),
+        )],
+    );
+}
+
+#[test]
+fn send_buffer_size_limits_buffered_data() {
+    let test_env_config = TestEnvironmentConfig {
+        max_send_buffer_size: 1500,
+        stream_id: StreamId::initial(endpoint::Type::Client, StreamType::Unidirectional),
+        local_endpoint_type: endpoint::Type::Client,
+        ..Default::default()
+    };
+    let mut test_env = setup_stream_test_env_with_config(test_env_config);
+
+    let response = test_env
+        .run_request(
+            ops::Request::default()
+                .with_send_buffer_size(1000)
+                .send(&mut gen_pattern_test_chunks(VarInt::from_u8(0), &[800])),
+            false,
+        )
+        .expect("request should succeed");
+    assert_eq!(response.tx().unwrap().bytes.available, 200);
+
+    // lowering the size doesn't drop any buffered data but blocks further writes
+    assert_eq!(
+        test_env.run_request(
+            ops::Request::default()
+                .with_send_buffer_size(500)
+                .send(&mut gen_pattern_test_chunks(VarInt::from_u16(800), &[100])),
+            true,
+        ),
+        Ok(ops::Response {
+            tx: Some(ops::tx::Response {
+                bytes: ops::Bytes {
+                    available: 0,
+                    consumed: 0
+                },
+                chunks: ops::Chunks {
+                    available: 0,
+                    consumed: 0,
+                },
+                status: ops::Status::Open,
+                will_wake: true,
+            }),
+            rx: None,
+        }),
+    );
+    assert_eq!(
+        test_env.stream.send_stream.data_sender.total_enqueued_len(),
+        VarInt::from_u16(800)
+    );
+
+    // raising the size allows the write to make progress
+    let response = test_env
+        .run_request(
+            ops::Request::default()
+                .with_send_buffer_size(2000)
+                .send(&mut gen_pattern_test_chunks(VarInt::from_u16(800), &[100])),
+            false,
+        )
+        .expect("request should succeed");
+    assert_eq!(response.tx().unwrap().chunks.consumed, 1);
+    assert_eq!(response.tx().unwrap().bytes.available, 1100);
+
+    // an empty buffer is rejected since the stream would never make progress
+    assert!(matches!(
+        test_env.run_request(ops::Request::default().with_send_buffer_size(0), false),
+        Err(StreamError::InvalidConfiguration { .. })
+    ));
+}
+
+#[test]
+fn default_expiration_applies_to_writes_without_one() {
+    let test_env_config = TestEnvironmentConfig {
+        stream_id: StreamId::initial(endpoint::Type::Server, StreamType::Unidirectional),
+        partial_reliability_enabled: true,
+        ..Default::default()
+    };
+    let mut test_env = setup_stream_test_env_with_config(test_env_config);
+    let lifetime = Duration::from_millis(100);
+
+    test_env
+        .run_request(
+            ops::Request::default()
+                .with_default_expiration(lifetime)
+                .send(&mut gen_pattern_test_chunks(VarInt::from_u8(0), &[100])),
+            false,
+        )
+        .expect("request should succeed");
+
+    // an explicit expiration takes precedence over the default
+    test_env
+        .run_request(
+            ops::Request::default()
+                .with_expiration(lifetime * 2)
+                .send(&mut gen_pattern_test_chunks(VarInt::from_u8(100), &[100])),
+            false,
+        )
+        .expect("request should succeed");
+
+    // disabling the default expiration delivers the following data reliably
+    test_env
+        .run_request(
+            ops::Request::default()
+                .with_default_expiration(Duration::ZERO)
+                .send(&mut gen_pattern_test_chunks(VarInt::from_u8(200), &[50])),
+            false,
+        )
+        .expect("request should succeed");
+
+    execute_instructions(
+        &mut test_env,
+        &[
+            Instruction::CheckDataTx(VarInt::from_u8(0), 250, false, false, pn(0)),
+            Instruction::NackPacket(pn(0)),
+        ],
+    );
+
+    // only the data sent with the default expiration is given up after its lifetime
+    test_env.current_time += lifetime;
+    test_env
+        .stream
+        .on_timeout(test_env.current_time, &mut StreamEvents::new());
+    assert_eq!(
+        test_env.stream.send_stream.expired_sync.latest_value(),
+        VarInt::from_u8(100)
+    );
+
+    test_env.current_time += lifetime;
+    test_env
+        .stream
+        .on_timeout(test_env.current_time, &mut StreamEvents::new());
+
+    // the reliable data is still retransmitted while the peer is told to skip the expired data
+    test_env.assert_write_of(VarInt::from_u8(200), 50, false, false, pn(1));
+    let mut sent_frame = test_env
+        .sent_frames
+        .pop_front()
+        .expect("no frame was written");
+    assert_eq!(
+        Frame::ExpiredStreamData(ExpiredStreamData {
+            stream_id: test_env.stream.stream_id.into(),
+            offset: VarInt::from_u8(200),
+        }),
+        sent_frame.as_frame()
+    );
+}
+
+#[test]
+fn unsent_data_with_default_expiration_is_dropped() {
+    let test_env_config = TestEnvironmentConfig {
+        stream_id: StreamId::initial(endpoint::Type::Server, StreamType::Unidirectional),
+        partial_reliability_enabled: true,
+        ..Default::default()
+    };
+    let mut test_env = setup_stream_test_env_with_config(test_env_config);
+    let lifetime = Duration::from_millis(100);
+
+    test_env
+        .run_request(
+            ops::Request::default().with_default_expiration(lifetime),
+            false,
+        )
+        .expect("request should succeed");
+
+    // writes without an expiration use the default one
+    test_env
+        .run_request(
+            ops::Request::default().send(&mut gen_pattern_test_chunks(VarInt::from_u8(0), &[100])),
+            false,
+        )
+        .expect("request should succeed");
+
+    // the lifetime starts when the connection wakes up after the write
+    assert!(test_env.stream.needs_wakeup());
+    test_env.stream.on_wakeup(test_env.current_time);
+
+    test_env.current_time += lifetime;
+    test_env
+        .stream
+        .on_timeout(test_env.current_time, &mut StreamEvents::new());
+
+    // the data is dropped without ever being transmitted
+    let mut sent_frame = test_env.transmit().expect("no frame was written");
+    assert_eq!(
+        Frame::ExpiredStreamData(ExpiredStreamData {
+            stream_id: test_env.stream.stream_id.into(),
+            offset: VarInt::from_u8(100),
+        }),
+        sent_frame.as_frame()
+    );
+    execute_instructions(&mut test_env, &[Instruction::CheckNoTx]);
+}
@@ -17,7 +17,7 @@ use core::{cell::RefCell, ops::Deref};
 use intrusive_collections::{
     intrusive_adapter, KeyAdapter, LinkedList, LinkedListLink, RBTree, RBTreeLink,
 };
-use s2n_quic_core::{stream::StreamId, time::timer};
+use s2n_quic_core::{endpoint, stream::StreamId, time::timer};
 
 // Intrusive list adapter for managing the list of `done` streams
 intrusive_adapter!(DoneStreamsAdapter<S> = Rc<StreamNode<S>>: StreamNode<S> {
@@ -53,6 +53,12 @@ intrusive_adapter!(WaitingForStreamFlowControlCreditsAdapter<S> = Rc<StreamNode<
     waiting_for_stream_flow_control_credits_link: LinkedListLink
 });
 
+// Intrusive list adapter for managing the list of
+// `waiting_for_timeout` streams
+intrusive_adapter!(WaitingForTimeoutAdapter<S> = Rc<StreamNode<S>>: StreamNode<S> {
+    waiting_for_timeout_link: LinkedListLink
+});
+
 // Intrusive red black tree adapter for managing all streams in a tree for
 // lookup by Stream ID
 intrusive_adapter!(StreamTreeAdapter<S> = Rc<StreamNode<S>>: StreamNode<S> {
@@ -65,6 +71,10 @@ intrusive_adapter!(StreamTreeAdapter<S> = Rc<StreamNode<S>>: StreamNode<S> {
 struct StreamNode<S> {
     /// This contains the actual implementation of the `Stream`
     inner: RefCell<S>,
+    /// The endpoint which initiated the Stream
+    ///
+    /// This is stored outside of `inner` so it can be read while the Stream is borrowed.
+    initiator: endpoint::Type,
     /// Allows the Stream to be part of the `stream_map` collection
     tree_link: RBTreeLink,
     /// Allows the Stream to be part of the `done_streams` collection
@@ -79,13 +89,16 @@ struct StreamNode<S> {
     waiting_for_connection_flow_control_credits_link: LinkedListLink,
     /// Allows the Stream to be part of the `waiting_for_stream_flow_control_credits` collection
     waiting_for_stream_flow_control_credits_link: LinkedListLink,
+    /// Allows the Stream to be part of the `waiting_for_timeout` collection
+    waiting_for_timeout_link: LinkedListLink,
 }
 
 impl<S> StreamNode<S> {
     /// Creates a new `StreamNode` which wraps the given Stream implementation of type `S`
-    pub fn new(stream_impl: S) -> StreamNode<S> {
+    pub fn new(stream_impl: S, initiator: endpoint::Type) -> StreamNode<S> {
         StreamNode {
             inner: RefCell::new(stream_impl),
+            initiator,
             tree_link: RBTreeLink::new(),
             done_streams_link: LinkedListLink::new(),
             waiting_for_frame_delivery_link: LinkedListLink::new(),
@@ -93,6 +106,7 @@ impl<S> StreamNode<S> {
             waiting_for_retransmission_link: LinkedListLink::new(),
             waiting_for_connection_flow_control_credits_link: LinkedListLink::new(),
             waiting_for_stream_flow_control_credits_link: LinkedListLink::new(),
+            waiting_for_timeout_link: LinkedListLink::new(),
         }
     }
 }
@@ -134,7 +148,11 @@ struct InterestLists<S> {
     /// packet loss notifications
     waiting_for_frame_delivery: LinkedList<WaitingForFrameDeliveryAdapter<S>>,
     /// Streams which need to transmit data
+    ///
+    /// If initiators are interleaved, this only contains the client-initiated Streams.
     waiting_for_transmission: LinkedList<WaitingForTransmissionAdapter<S>>,
+    /// Server-initiated Streams which need to transmit data, if initiators are interleaved
+    waiting_for_server_transmission: LinkedList<WaitingForTransmissionAdapter<S>>,
     /// Streams which need to transmit data
     waiting_for_retransmission: LinkedList<WaitingForRetransmissionAdapter<S>>,
     /// Streams which are blocked on transmission due to waiting on the
@@ -145,9 +163,15 @@ struct InterestLists<S> {
     /// stream flow control window to increase
     waiting_for_stream_flow_control_credits:
         LinkedList<WaitingForStreamFlowControlCreditsAdapter<S>>,
+    /// Streams which have armed a timer that isn't related to flow control
+    waiting_for_timeout: LinkedList<WaitingForTimeoutAdapter<S>>,
     transmission_counter: u8,
     retransmission_counter: u8,
     transmission_limit: u8,
+    /// Set if transmission alternates between streams initiated by each endpoint
+    interleave_initiators: bool,
+    /// The initiator of the streams which are served first on the next transmission
+    next_initiator: endpoint::Type,
 }
 
 impl<S: StreamTrait> InterestLists<S> {
@@ -156,6 +180,7 @@ impl<S: StreamTrait> InterestLists<S> {
             done_streams: LinkedList::new(DoneStreamsAdapter::new()),
             waiting_for_frame_delivery: LinkedList::new(WaitingForFrameDeliveryAdapter::new()),
             waiting_for_transmission: LinkedList::new(WaitingForTransmissionAdapter::new()),
+            waiting_for_server_transmission: LinkedList::new(WaitingForTransmissionAdapter::new()),
             waiting_for_retransmission: LinkedList::new(WaitingForRetransmissionAdapter::new()),
             waiting_for_connection_flow_control_credits: LinkedList::new(
                 WaitingForConnectionFlowControlCreditsAdapter::new(),
@@ -163,12 +188,35 @@ impl<S: StreamTrait> InterestLists<S> {
             waiting_for_stream_flow_control_credits: LinkedList::new(
                 WaitingForStreamFlowControlCreditsAdapter::new(),
             ),
+            waiting_for_timeout: LinkedList::new(WaitingForTimeoutAdapter::new()),
             transmission_counter: 0,
             retransmission_counter: 0,
             transmission_limit: connection_limits.stream_batch_size(),
+            interleave_initiators: connection_limits.stream_initiator_interleaving(),
+            next_initiator: endpoint::Type::Client,
+        }
+    }
+
+    /// Returns the list of Streams initiated by `initiator` which need to transmit data
+    #[inline]
+    fn transmission_list(
+        &mut self,
+        initiator: endpoint::Type,
+    ) -> &mut LinkedList<WaitingForTransmissionAdapter<S>> {
+        if self.interleave_initiators && initiator.is_server() {
+            &mut self.waiting_for_server_transmission
+        } else {
+            &mut self.waiting_for_transmission
         }
     }
 
+    /// Returns true if any Stream needs to transmit data
+    #[inline]
+    fn has_transmission_interest(&self) -> bool {
+        !self.waiting_for_transmission.is_empty()
+            || !self.waiting_for_server_transmission.is_empty()
+    }
+
     /// Update all interest lists based on latest interest reported by a Node
     fn update_interests(&mut self, node: &Rc<StreamNode<S>>, interests: StreamInterests) -> bool {
         // Note that all comparisons start by checking whether the stream is
@@ -180,16 +228,18 @@ impl<S: StreamTrait> InterestLists<S> {
 
         macro_rules! sync_interests {
             ($interest:expr, $link_name:ident, $list_name:ident) => {
+                sync_interests!($interest, $link_name, self.$list_name)
+            };
+            ($interest:expr, $link_name:ident, $list:expr) => {
                 if $interest != node.$link_name.is_linked() {
                     if $interest {
-                        self.$list_name.push_back(node.clone());
+                        $list.push_back(node.clone());
                     } else {
                         // Safety: We know that the node is only ever part of this list.
                         // While elements are in temporary lists, they always get unlinked
                         // from those temporary lists while their interest is updated.
                         let mut cursor = unsafe {
-                            self.$list_name
-                                .cursor_mut_from_ptr(node.deref() as *const StreamNode<S>)
+                            $list.cursor_mut_from_ptr(node.deref() as *const StreamNode<S>)
                         };
                         cursor.remove();
                     }
@@ -206,7 +256,7 @@ impl<S: StreamTrait> InterestLists<S> {
         sync_interests!(
             matches!(interests.transmission, transmission::Interest::NewData),
             waiting_for_transmission_link,
-            waiting_for_transmission
+            self.transmission_list(node.initiator)
         );
         sync_interests!(
             matches!(interests.transmission, transmission::Interest::LostData),
@@ -223,6 +273,11 @@ impl<S: StreamTrait> InterestLists<S> {
             waiting_for_stream_flow_control_credits_link,
             waiting_for_stream_flow_control_credits
         );
+        sync_interests!(
+            interests.timeout,
+            waiting_for_timeout_link,
+            waiting_for_timeout
+        );
 
         if interests.retained == node.done_streams_link.is_linked() {
             if !interests.retained {
@@ -400,7 +455,8 @@ impl<S: StreamTrait> StreamContainer<S> {
         // would be better to avoid future bugs
         let interests = stream.get_stream_interests();
 
-        let new_stream = Rc::new(StreamNode::new(stream));
+        let initiator = stream.stream_id().initiator();
+        let new_stream = Rc::new(StreamNode::new(stream, initiator));
 
         self.interest_lists.update_interests(&new_stream, interests);
 
@@ -506,7 +562,17 @@ impl<S: StreamTrait> StreamContainer<S> {
             }
 
             remove_stream_from_list!(waiting_for_frame_delivery, waiting_for_frame_delivery_link);
-            remove_stream_from_list!(waiting_for_transmission, waiting_for_transmission_link);
+            if stream.waiting_for_transmission_link.is_linked() {
+                // Safety: The Stream is linked and is only ever part of the transmission list
+                // for its initiator
+                let mut cursor = unsafe {
+                    self.interest_lists
+                        .transmission_list(stream.initiator)
+                        .cursor_mut_from_ptr(stream_ptr)
+                };
+                let remove_result = cursor.remove();
+                debug_assert!(remove_result.is_some());
+            }
             remove_stream_from_list!(waiting_for_retransmission, waiting_for_retransmission_link);
             remove_stream_from_list!(
                 waiting_for_connection_flow_control_credits,
@@ -516,6 +582,7 @@ impl<S: StreamTrait> StreamContainer<S> {
                 waiting_for_stream_flow_control_credits,
                 waiting_for_stream_flow_control_credits_link
             );
+            remove_stream_from_list!(waiting_for_timeout, waiting_for_timeout_link);
 
             controller.on_close_stream(stream.inner.borrow().stream_id());
         }
@@ -584,6 +651,24 @@ impl<S: StreamTrait> StreamContainer<S> {
         );
     }
 
+    /// Iterates over all `Stream`s which have armed a timer that isn't
+    /// related to flow control, and executes the given function on each `Stream`
+    ///
+    /// The `stream::Controller` will be notified of streams that have been
+    /// closed to allow for further streams to be opened.
+    pub fn iterate_timeout_list<F>(&mut self, controller: &mut stream::Controller, mut func: F)
+    where
+        F: FnMut(&mut S),
+    {
+        iterate_uninterruptible!(
+            self,
+            waiting_for_timeout,
+            waiting_for_timeout_link,
+            controller,
+            func
+        );
+    }
+
     #[cfg(test)]
     pub fn iterate_transmission_list<F>(&mut self, controller: &mut stream::Controller, mut func: F)
     where
@@ -607,6 +692,11 @@ impl<S: StreamTrait> StreamContainer<S> {
     where
         F: FnMut(&mut S) -> StreamContainerIterationResult,
     {
+        if self.interest_lists.interleave_initiators {
+            self.send_on_interleaved_transmission_list(controller, func);
+            return;
+        }
+
         send_on_transmission_list!(
             self,
             waiting_for_transmission,
@@ -618,6 +708,91 @@ impl<S: StreamTrait> StreamContainer<S> {
         );
     }
 
+    /// Iterates over the `Stream`s which are waiting for transmission, alternating between the
+    /// `Stream`s initiated by each endpoint
+    ///
+    /// The `Stream`s of each initiator are kept in separate lists, which are merged as they are
+    /// visited so the cost doesn't depend on the number of waiting `Stream`s. The initiator which
+    /// is served first alternates on each call so neither set of `Stream`s can starve the other.
+    fn send_on_interleaved_transmission_list<F>(
+        &mut self,
+        controller: &mut stream::Controller,
+        mut func: F,
+    ) where
+        F: FnMut(&mut S) -> StreamContainerIterationResult,
+    {
+        let first_initiator = self.interest_lists.next_initiator;
+        let second_initiator = first_initiator.peer_type();
+        self.interest_lists.next_initiator = second_initiator;
+
+        // Head node gets pushed to the back of the list if it has run out of sending credits
+        if self.interest_lists.transmission_counter >= self.interest_lists.transmission_limit {
+            let list = self.interest_lists.transmission_list(first_initiator);
+            if let Some(node) = list.pop_front() {
+                list.push_back(node);
+                self.interest_lists.transmission_counter = 0;
+            }
+        }
+
+        let mut first = self
+            .interest_lists
+            .transmission_list(first_initiator)
+            .take();
+        let mut second = self
+            .interest_lists
+            .transmission_list(second_initiator)
+            .take();
+
+        let mut head_node = true;
+        let mut serve_first = true;
+        loop {
+            let stream = if serve_first {
+                first.pop_front().or_else(|| second.pop_front())
+            } else {
+                second.pop_front().or_else(|| first.pop_front())
+            };
+            let Some(stream) = stream else {
+                break;
+            };
+            serve_first = !serve_first;
+
+            debug_assert!(!stream.waiting_for_transmission_link.is_linked());
+            let mut mut_stream = stream.inner.borrow_mut();
+            let result = func(&mut *mut_stream);
+
+            // Update the interests after the interaction
+            let interests = mut_stream.get_stream_interests();
+            self.interest_lists.update_interests(&stream, interests);
+
+            if head_node {
+                if matches!(result, StreamContainerIterationResult::Continue) {
+                    self.interest_lists.transmission_counter += 1;
+                }
+
+                if !matches!(interests.transmission, transmission::Interest::NewData) {
+                    self.interest_lists.transmission_counter = 0;
+                }
+                head_node = false;
+            }
+
+            if let StreamContainerIterationResult::BreakAndInsertAtBack = result {
+                self.interest_lists
+                    .transmission_list(first_initiator)
+                    .back_mut()
+                    .splice_after(first);
+                self.interest_lists
+                    .transmission_list(second_initiator)
+                    .back_mut()
+                    .splice_after(second);
+                break;
+            }
+        }
+
+        if !self.interest_lists.done_streams.is_empty() {
+            self.finalize_done_streams(controller);
+        }
+    }
+
     #[cfg(test)]
     pub fn iterate_retransmission_list<F>(
         &mut self,
@@ -696,7 +871,7 @@ impl<S: StreamTrait> StreamContainer<S> {
 
     /// Returns whether or not streams have data to send
     pub fn has_pending_streams(&self) -> bool {
-        !self.interest_lists.waiting_for_transmission.is_empty()
+        self.interest_lists.has_transmission_interest()
             || !self.interest_lists.waiting_for_retransmission.is_empty()
     }
 }
@@ -712,6 +887,9 @@ impl<S: StreamTrait> timer::Provider for StreamContainer<S> {
         {
             stream.inner.borrow().timers(query)?;
         }
+        for stream in self.interest_lists.waiting_for_timeout.iter() {
+            stream.inner.borrow().timers(query)?;
+        }
         Ok(())
     }
 }
@@ -724,7 +902,7 @@ impl<S: StreamTrait> transmission::interest::Provider for StreamContainer<S> {
     ) -> transmission::interest::Result {
         if !self.interest_lists.waiting_for_retransmission.is_empty() {
             query.on_lost_data()?;
-        } else if !self.interest_lists.waiting_for_transmission.is_empty() {
+        } else if self.interest_lists.has_transmission_interest() {
             query.on_new_data()?;
         }
 
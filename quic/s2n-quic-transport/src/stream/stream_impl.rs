@@ -16,7 +16,10 @@ use crate::{
 use core::{task::Context, time::Duration};
 use s2n_quic_core::{
     ack, endpoint,
-    frame::{stream::StreamRef, MaxStreamData, ResetStream, StopSending, StreamDataBlocked},
+    frame::{
+        stream::StreamRef, ExpiredStreamData, MaxStreamData, ResetStream, ResetStreamAt,
+        StopSending, StreamDataBlocked,
+    },
     stream::{ops, StreamId},
     time::{timer, Timestamp},
     transport,
@@ -42,6 +45,12 @@ pub struct StreamConfig {
     pub initial_send_window: VarInt,
     /// The maximum buffered amount of data on the sending side
     pub max_send_buffer_size: u32,
+    /// Whether both peers negotiated support for `RESET_STREAM_AT` frames
+    pub reset_stream_at_enabled: bool,
+    /// Whether both peers negotiated support for partially reliable streams
+    pub partial_reliability_enabled: bool,
+    /// The maximum datagram size of the active path
+    pub max_datagram_size: u16,
 }
 
 /// A trait which represents an internally used `Stream`
@@ -78,6 +87,22 @@ pub trait StreamTrait: StreamInterestProvider + timer::Provider + core::fmt::Deb
         events: &mut StreamEvents,
     ) -> Result<(), transport::Error>;
 
+    /// This is called when a `RESET_STREAM_AT` frame had been received for
+    /// this stream
+    fn on_reset_at(
+        &mut self,
+        frame: &ResetStreamAt,
+        events: &mut StreamEvents,
+    ) -> Result<(), transport::Error>;
+
+    /// This is called when an `EXPIRED_STREAM_DATA` frame had been received for
+    /// this stream
+    fn on_expired_stream_data(
+        &mut self,
+        frame: &ExpiredStreamData,
+        events: &mut StreamEvents,
+    ) -> Result<(), transport::Error>;
+
     /// This is called when a `MAX_STREAM_DATA` frame had been received for
     /// this stream
     fn on_max_stream_data(
@@ -105,7 +130,19 @@ pub trait StreamTrait: StreamInterestProvider + timer::Provider + core::fmt::Deb
     fn update_blocked_sync_period(&mut self, blocked_sync_period: Duration);
 
     /// Called when the connection timer expires
-    fn on_timeout(&mut self, now: Timestamp);
+    fn on_timeout(&mut self, now: Timestamp, events: &mut StreamEvents);
+
+    /// Returns `true` if the stream needs the connection to wake up after an API call
+    fn needs_wakeup(&self) -> bool;
+
+    /// Called when the connection wakes up after an API call
+    fn on_wakeup(&mut self, now: Timestamp);
+
+    /// Called when the maximum datagram size of the active path changes
+    fn on_mtu_update(&mut self, max_datagram_size: u16);
+
+    /// Called when the connection releases its buffers after being idle
+    fn on_hibernate(&mut self);
 
     /// This method gets called when a stream gets reset due to a reason that is
     /// not related to a frame. E.g. due to a connection failure.
@@ -122,6 +159,15 @@ pub trait StreamTrait: StreamInterestProvider + timer::Provider + core::fmt::Deb
     /// This method is called when a connection window is available
     fn on_connection_window_available(&mut self);
 
+    /// Returns the offset of the next byte written to the sending side of the stream
+    fn send_offset(&self) -> VarInt;
+
+    /// Holds back sending data at or beyond `offset` until called with `None`
+    fn set_send_barrier(&mut self, offset: Option<VarInt>);
+
+    /// Returns `true` if the sent data before `offset` no longer needs to be delivered
+    fn is_send_acknowledged(&self, offset: VarInt) -> bool;
+
     // These functions are called from the client API
 
     fn poll_request(
@@ -197,6 +243,16 @@ impl StreamTrait for StreamImpl {
         let send_is_closed = config.stream_id.stream_type().is_unidirectional()
             && config.stream_id.initiator() != config.local_endpoint_type;
 
+        let mut send_stream = SendStream::new(
+            config.outgoing_connection_flow_controller,
+            send_is_closed,
+            config.initial_send_window,
+            config.max_send_buffer_size,
+            config.reset_stream_at_enabled,
+            config.partial_reliability_enabled,
+        );
+        send_stream.on_mtu_update(config.max_datagram_size);
+
         StreamImpl {
             stream_id: config.stream_id,
             receive_stream: ReceiveStream::new(
@@ -206,12 +262,7 @@ impl StreamTrait for StreamImpl {
                 config.desired_flow_control_window,
             ),
             has_send: !send_is_closed,
-            send_stream: SendStream::new(
-                config.outgoing_connection_flow_controller,
-                send_is_closed,
-                config.initial_send_window,
-                config.max_send_buffer_size,
-            ),
+            send_stream,
         }
     }
 
@@ -249,6 +300,24 @@ impl StreamTrait for StreamImpl {
         self.receive_stream.on_reset(frame, events)
     }
 
+    #[inline]
+    fn on_reset_at(
+        &mut self,
+        frame: &ResetStreamAt,
+        events: &mut StreamEvents,
+    ) -> Result<(), transport::Error> {
+        self.receive_stream.on_reset_at(frame, events)
+    }
+
+    #[inline]
+    fn on_expired_stream_data(
+        &mut self,
+        frame: &ExpiredStreamData,
+        events: &mut StreamEvents,
+    ) -> Result<(), transport::Error> {
+        self.receive_stream.on_expired_stream_data(frame, events)
+    }
+
     #[inline]
     fn on_max_stream_data(
         &mut self,
@@ -295,8 +364,30 @@ impl StreamTrait for StreamImpl {
     }
 
     #[inline]
-    fn on_timeout(&mut self, now: Timestamp) {
-        self.send_stream.on_timeout(now)
+    fn on_timeout(&mut self, now: Timestamp, events: &mut StreamEvents) {
+        self.receive_stream.on_timeout(now, events);
+        self.send_stream.on_timeout(now, events)
+    }
+
+    #[inline]
+    fn needs_wakeup(&self) -> bool {
+        self.send_stream.needs_wakeup()
+    }
+
+    #[inline]
+    fn on_wakeup(&mut self, now: Timestamp) {
+        self.send_stream.on_wakeup(now)
+    }
+
+    #[inline]
+    fn on_mtu_update(&mut self, max_datagram_size: u16) {
+        self.send_stream.on_mtu_update(max_datagram_size);
+    }
+
+    #[inline]
+    fn on_hibernate(&mut self) {
+        self.receive_stream.on_hibernate();
+        self.send_stream.on_hibernate();
     }
 
     #[inline]
@@ -327,6 +418,21 @@ impl StreamTrait for StreamImpl {
         self.send_stream.on_connection_window_available()
     }
 
+    #[inline]
+    fn send_offset(&self) -> VarInt {
+        self.send_stream.send_offset()
+    }
+
+    #[inline]
+    fn set_send_barrier(&mut self, offset: Option<VarInt>) {
+        self.send_stream.set_send_barrier(offset)
+    }
+
+    #[inline]
+    fn is_send_acknowledged(&self, offset: VarInt) -> bool {
+        self.send_stream.is_send_acknowledged(offset)
+    }
+
     // These functions are called from the client API
 
     fn poll_request(
@@ -349,6 +455,7 @@ impl StreamTrait for StreamImpl {
 impl timer::Provider for StreamImpl {
     #[inline]
     fn timers<Q: timer::Query>(&self, query: &mut Q) -> timer::Result {
+        self.receive_stream.timers(query)?;
         self.send_stream.timers(query)?;
         Ok(())
     }
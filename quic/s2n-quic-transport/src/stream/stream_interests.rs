@@ -14,6 +14,9 @@ pub struct StreamInterests {
     /// Is `true` if the `Stream` wants to transmit data but is blocked on
     /// insufficient stream flow control credits
     pub stream_flow_control_credits: bool,
+    /// Is `true` if the `Stream` has armed a timer which isn't related to
+    /// flow control and needs to be notified when it expires
+    pub timeout: bool,
     /// Is `true` if the `Stream` is still wanting to make progress. Otherwise
     /// the stream will be removed from the `Stream` map.
     pub retained: bool,
@@ -29,6 +32,7 @@ impl StreamInterests {
     pub fn merge(&mut self, other: &Self) {
         self.connection_flow_control_credits |= other.connection_flow_control_credits;
         self.stream_flow_control_credits |= other.stream_flow_control_credits;
+        self.timeout |= other.timeout;
         self.retained |= other.retained;
         self.delivery_notifications |= other.delivery_notifications;
         let _ = self.transmission.on_interest(other.transmission);
@@ -19,6 +19,7 @@ use s2n_quic_core::{
     endpoint,
     frame::{stream::Stream as StreamFrame, Frame, ResetStream, StreamDataBlocked},
     packet::number::{PacketNumber, PacketNumberSpace},
+    path::MINIMUM_MAX_DATAGRAM_SIZE,
     stream::{ops, StreamError, StreamId, StreamType},
     time::{clock::testing as time, Timestamp},
     transport,
@@ -116,6 +117,7 @@ pub fn pn(nr: usize) -> PacketNumber {
 /// - tx => transmission::Interest::NewData
 /// - fin => finalization
 /// - cf => connection_flow_control_credits
+/// - timeout => timeout
 pub fn stream_interests(interests: &[&str]) -> StreamInterests {
     let mut result = StreamInterests {
         retained: true,
@@ -129,6 +131,7 @@ pub fn stream_interests(interests: &[&str]) -> StreamInterests {
             "fin" => result.retained = false,
             "cf" => result.connection_flow_control_credits = true,
             "sf" => result.stream_flow_control_credits = true,
+            "timeout" => result.timeout = true,
             other => unreachable!("Unsupported interest {}", other),
         }
     }
@@ -486,6 +489,8 @@ pub struct TestEnvironmentConfig {
     pub transmission_constraint: transmission::Constraint,
     pub local_endpoint_type: endpoint::Type,
     pub max_packet_size: Option<usize>,
+    pub reset_stream_at_enabled: bool,
+    pub partial_reliability_enabled: bool,
 }
 
 impl Default for TestEnvironmentConfig {
@@ -514,6 +519,8 @@ impl TestEnvironmentConfig {
             max_send_buffer_size: TestEnvironment::DEFAULT_MAX_SEND_BUFFER_SIZE,
             transmission_constraint: transmission::Constraint::None,
             max_packet_size: None,
+            reset_stream_at_enabled: false,
+            partial_reliability_enabled: false,
         }
     }
 }
@@ -544,6 +551,9 @@ pub fn setup_stream_test_env_with_config(config: TestEnvironmentConfig) -> TestE
         desired_flow_control_window: config.desired_flow_control_window,
         initial_send_window: VarInt::new(config.initial_send_window).unwrap(),
         max_send_buffer_size: config.max_send_buffer_size as u32,
+        reset_stream_at_enabled: config.reset_stream_at_enabled,
+        partial_reliability_enabled: config.partial_reliability_enabled,
+        max_datagram_size: MINIMUM_MAX_DATAGRAM_SIZE,
     });
 
     let (waker, wake_counter) = new_count_waker();
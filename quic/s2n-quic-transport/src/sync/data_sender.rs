@@ -123,6 +123,11 @@ pub struct DataSender<FlowController, ChunkToFrameWriter> {
     /// Whether the size of the send stream is known and a FIN flag is already
     /// enqueued.
     state: State,
+    /// Whether data which hasn't been transmitted yet is held back. Lost data is still
+    /// retransmitted.
+    is_holding_new_data: bool,
+    /// New data at or beyond this offset is held back, along with the FIN
+    transmission_limit: Option<VarInt>,
 }
 
 impl<FlowController: OutgoingDataFlowController, Writer: FrameWriter>
@@ -144,6 +149,8 @@ impl<FlowController: OutgoingDataFlowController, Writer: FrameWriter>
             lost: IntervalSet::new(),
             max_buffer_capacity: VarInt::from_u32(max_buffer_capacity),
             state: State::Sending,
+            is_holding_new_data: false,
+            transmission_limit: None,
         }
     }
 
@@ -190,6 +197,104 @@ impl<FlowController: OutgoingDataFlowController, Writer: FrameWriter>
         self.check_integrity();
     }
 
+    /// Stops sending outgoing data at or beyond `reliable_size`.
+    ///
+    /// This is a one-way operation - sending can not be resumed.
+    ///
+    /// Data before `reliable_size` continues to be transmitted and retransmitted until it
+    /// has been acknowledged by the peer. All other outgoing data is removed from the buffer.
+    pub fn stop_sending_at(&mut self, error: StreamError, reliable_size: VarInt) {
+        if self.state == State::Finished {
+            return;
+        }
+
+        self.state = State::Cancelled(error);
+
+        let total_len = self.buffer.total_len();
+        if reliable_size < total_len {
+            self.pending
+                .remove(reliable_size..total_len)
+                .expect("pending should not have a limit");
+            self.lost
+                .intersection(&self.pending)
+                .expect("lost has no interval limit");
+        }
+
+        self.buffer.truncate(reliable_size);
+        self.transmission_offset = self.transmission_offset.min(self.buffer.total_len());
+
+        if self.pending.is_empty() {
+            // everything up to the reliable size was already acknowledged
+            self.buffer.release_all();
+            self.transmissions.finish();
+            self.transmission_offset = self.buffer.total_len();
+        }
+
+        self.check_integrity();
+    }
+
+    /// Gives up the delivery of all of the data before `offset`
+    ///
+    /// Lost data before the offset is no longer retransmitted and data which wasn't transmitted
+    /// yet is dropped. The peer can only be told to skip data within the flow control window, so
+    /// the offset is limited to the window which can be acquired for it. Returns the offset up to
+    /// which the data expired, if any data expired.
+    pub fn expire(&mut self, offset: VarInt) -> Option<VarInt> {
+        if !matches!(self.state, State::Sending | State::Finishing(_)) {
+            return None;
+        }
+
+        let mut offset = offset.min(self.buffer.total_len());
+
+        if offset > self.transmission_offset {
+            let window = self
+                .flow_controller_mut()
+                .acquire_flow_control_window(offset);
+            offset = offset.min(window.max(self.transmission_offset));
+        }
+
+        if self.is_acknowledged_up_to(offset) {
+            return None;
+        }
+
+        self.pending
+            .remove(VarInt::from_u8(0)..offset)
+            .expect("pending should not have a limit");
+        self.lost
+            .intersection(&self.pending)
+            .expect("lost has no interval limit");
+        // The expired data is never transmitted
+        self.transmission_offset = self.transmission_offset.max(offset);
+
+        if let Some(first) = self.pending.min_value() {
+            self.buffer.release(first);
+        } else {
+            self.buffer.release_all();
+            // We don't need to track transmissions for expired ranges
+            self.transmissions.clear();
+        }
+
+        // The FIN may have been acknowledged before the expired data
+        if matches!(self.state, State::Finishing(FinState::Acknowledged)) && self.is_idle() {
+            self.state = State::Finished;
+            self.flow_controller_mut().finish();
+            self.buffer.release_all();
+        }
+
+        self.check_integrity();
+
+        Some(offset)
+    }
+
+    /// Releases any excess capacity held by the sender
+    ///
+    /// This doesn't affect any enqueued data.
+    pub fn shrink_to_fit(&mut self) {
+        self.buffer.shrink_to_fit();
+        self.pending.shrink_to_fit();
+        self.lost.shrink_to_fit();
+    }
+
     /// Returns the amount of bytes that have ever been enqueued for writing on
     /// this Stream. This equals the offset of the highest enqueued byte + 1.
     pub fn total_enqueued_len(&self) -> VarInt {
@@ -201,11 +306,52 @@ impl<FlowController: OutgoingDataFlowController, Writer: FrameWriter>
         self.buffer.is_empty()
     }
 
+    /// Returns the amount of enqueued data which hasn't been transmitted yet
+    pub fn unsent_len(&self) -> VarInt {
+        self.buffer.total_len() - self.transmission_offset
+    }
+
+    /// Holds back the transmission of new data until called with `false`
+    ///
+    /// Callers should release the data before finishing the sender.
+    pub fn hold_new_data(&mut self, hold: bool) {
+        self.is_holding_new_data = hold;
+    }
+
+    /// Holds back new data at or beyond `limit`, along with the FIN, until called with `None`
+    ///
+    /// Lost data is still retransmitted.
+    pub fn set_transmission_limit(&mut self, limit: Option<VarInt>) {
+        self.transmission_limit = limit;
+    }
+
+    /// Returns the offset up to which new data can be transmitted
+    #[inline]
+    fn transmission_end(&self) -> VarInt {
+        let total_len = self.buffer.total_len();
+        self.transmission_limit
+            .map_or(total_len, |limit| limit.min(total_len))
+    }
+
     /// Returns the state of the sender
     pub fn state(&self) -> State {
         self.state
     }
 
+    /// Returns `true` if all of the enqueued data has been acknowledged by the peer
+    pub fn is_acknowledged(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Returns `true` if all of the data before `offset` has been acknowledged by the peer
+    ///
+    /// Data which was discarded after the sender was cancelled is also considered acknowledged.
+    pub fn is_acknowledged_up_to(&self, offset: VarInt) -> bool {
+        self.pending
+            .min_value()
+            .map_or(true, |start| start >= offset)
+    }
+
     /// Returns `true` if the delivery is currently in progress.
     pub fn is_inflight(&self) -> bool {
         !self.transmissions.is_empty() || self.state.is_inflight()
@@ -238,6 +384,14 @@ impl<FlowController: OutgoingDataFlowController, Writer: FrameWriter>
             .unwrap_or(usize::MAX)
     }
 
+    /// Sets the maximum amount of data which is buffered for sending
+    ///
+    /// Lowering the capacity doesn't drop any enqueued data but prevents further data from being
+    /// enqueued until enough of it has been acknowledged.
+    pub fn set_max_buffer_capacity(&mut self, max_buffer_capacity: u32) {
+        self.max_buffer_capacity = VarInt::from_u32(max_buffer_capacity);
+    }
+
     /// Enqueues the data for transmission.
     ///
     /// It is only allowed to enqueue bytes if they do not overflow the maximum
@@ -326,6 +480,12 @@ impl<FlowController: OutgoingDataFlowController, Writer: FrameWriter>
             self.buffer.release_all();
         }
 
+        // If the stream was reset after delivering a prefix of the data, no more data
+        // will be transmitted once the prefix has been acknowledged.
+        if any_acked && matches!(self.state, State::Cancelled(_)) && self.is_idle() {
+            self.flow_controller_mut().finish();
+        }
+
         self.check_integrity();
     }
 
@@ -399,17 +559,22 @@ impl<FlowController: OutgoingDataFlowController, Writer: FrameWriter>
         let is_blocked = self.flow_controller().is_blocked();
 
         // try to transmit the enqueued ranges
-        let total_len = self.buffer.total_len();
+        let transmission_end = self.transmission_end();
+        let is_limited = transmission_end < self.buffer.total_len();
 
         let starting_transmission_offset = self.transmission_offset;
 
-        if !is_blocked && constraint.can_transmit() && self.transmission_offset < total_len {
+        if !is_blocked
+            && !self.is_holding_new_data
+            && constraint.can_transmit()
+            && self.transmission_offset < transmission_end
+        {
             let mut viewer = self.buffer.viewer();
             self.transmission_offset = self
                 .transmissions
                 .transmit_interval(
                     &mut viewer,
-                    (self.transmission_offset..total_len).into(),
+                    (self.transmission_offset..transmission_end).into(),
                     &mut self.state,
                     writer_context,
                     context,
@@ -417,7 +582,8 @@ impl<FlowController: OutgoingDataFlowController, Writer: FrameWriter>
                 .end_exclusive();
         }
 
-        if Writer::WRITES_FIN && self.state.can_transmit_fin(constraint, is_blocked) {
+        if Writer::WRITES_FIN && !is_limited && self.state.can_transmit_fin(constraint, is_blocked)
+        {
             self.transmissions.transmit_fin(
                 &self.buffer,
                 &mut self.state,
@@ -515,7 +681,10 @@ impl<F: OutgoingDataFlowController, W: FrameWriter> transmission::interest::Prov
                 State::Finishing(FinState::Lost) => {
                     return query.on_lost_data();
                 }
-                State::Finishing(FinState::Pending) if !self.flow_controller().is_blocked() => {
+                State::Finishing(FinState::Pending)
+                    if !self.flow_controller().is_blocked()
+                        && self.transmission_end() == self.buffer.total_len() =>
+                {
                     query.on_new_data()?;
                 }
                 _ => {}
@@ -524,7 +693,8 @@ impl<F: OutgoingDataFlowController, W: FrameWriter> transmission::interest::Prov
 
         if !self.lost.is_empty() {
             query.on_lost_data()?;
-        } else if self.transmission_offset < self.buffer.total_len()
+        } else if self.transmission_offset < self.transmission_end()
+            && !self.is_holding_new_data
             && !self.flow_controller().is_blocked()
         {
             query.on_new_data()?;
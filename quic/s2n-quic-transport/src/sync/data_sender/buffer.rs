@@ -126,6 +126,40 @@ impl Buffer {
         self.check_integrity();
     }
 
+    /// Drops all of the enqueued data at or beyond the provided offset
+    ///
+    /// Data which has already been released is not affected.
+    pub fn truncate(&mut self, len: VarInt) {
+        let len = len.max(self.head);
+
+        loop {
+            let excess = self.total_len().saturating_sub(len);
+            if excess == VarInt::from_u8(0) {
+                break;
+            }
+
+            let Some(chunk) = self.chunks.back_mut() else {
+                break;
+            };
+            let chunk_len = VarInt::try_from(chunk.len()).unwrap();
+
+            // if the entire chunk is past the offset, drop it
+            if chunk_len <= excess {
+                self.chunks.pop_back();
+                self.pending_len -= chunk_len;
+                continue;
+            }
+
+            // only part of the chunk is past the offset
+            chunk
+                .data
+                .truncate((chunk_len - excess).try_into().unwrap());
+            self.pending_len -= excess;
+        }
+
+        self.check_integrity();
+    }
+
     /// Releases all of the currently enqueued chunks
     pub fn release_all(&mut self) {
         self.chunks.clear();
@@ -135,6 +169,11 @@ impl Buffer {
         self.check_integrity();
     }
 
+    /// Releases any excess capacity held by the buffer
+    pub fn shrink_to_fit(&mut self) {
+        self.chunks.shrink_to_fit();
+    }
+
     /// Returns a Viewer for the buffer
     #[inline]
     pub fn viewer(&self) -> Viewer {
@@ -457,6 +496,29 @@ mod tests {
         assert!(buffer.chunks.is_empty());
     }
 
+    #[test]
+    fn truncate_test() {
+        let mut buffer = Buffer::default();
+
+        buffer.push(Bytes::from_static(&[0, 1, 2]));
+        buffer.push(Bytes::from_static(&[3, 4, 5]));
+        buffer.push(Bytes::from_static(&[6, 7, 8]));
+        buffer.release(VarInt::from_u8(1));
+
+        // drop the last chunk and part of the second one
+        buffer.truncate(VarInt::from_u8(4));
+        assert_eq!(buffer.total_len(), VarInt::from_u8(4));
+        assert_eq!(buffer.enqueued_len(), VarInt::from_u8(3));
+        assert_eq!(buffer.chunks.len(), 2);
+        assert_eq!(buffer.chunks[1][..], [3]);
+
+        // released data is not affected
+        buffer.truncate(VarInt::from_u8(0));
+        assert_eq!(buffer.total_len(), VarInt::from_u8(1));
+        assert_eq!(buffer.enqueued_len(), VarInt::from_u8(0));
+        assert!(buffer.chunks.is_empty());
+    }
+
     #[test]
     fn varint_max_test() {
         let mut buffer = almost_full_buffer();
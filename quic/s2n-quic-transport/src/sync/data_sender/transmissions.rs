@@ -6,6 +6,7 @@ use super::{
     FinState, FrameWriter, OutgoingDataFlowController, State,
 };
 use crate::contexts::{OnTransmitError, WriteContext};
+use alloc::vec::Vec;
 use core::num::NonZeroU16;
 use s2n_quic_core::{
     ack,
@@ -191,6 +191,17 @@ impl Writer for PingWriter {
     }
 }
 
+pub type GoAway = Flag<GoAwayWriter>;
+
+#[derive(Debug, Default)]
+pub struct GoAwayWriter;
+
+impl Writer for GoAwayWriter {
+    fn write_frame<W: WriteContext>(&mut self, context: &mut W) -> Option<PacketNumber> {
+        context.write_frame(&s2n_quic_core::frame::GoAway)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
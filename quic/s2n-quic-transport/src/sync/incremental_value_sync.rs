@@ -58,6 +58,12 @@ impl<
         self.delivery.is_cancelled()
     }
 
+    /// Returns `true` if the latest value has been acknowledged by the peer or the
+    /// synchronization has been cancelled
+    pub fn is_delivered(&self) -> bool {
+        self.delivery.is_cancelled() || self.latest_value == self.value_ackd_up_to
+    }
+
     /// Returns `true` if the delivery is current in progress.
     /// A packet has been sent, but no acknowledgement has been retrieved so far.
     pub fn is_inflight(&self) -> bool {
@@ -73,6 +79,12 @@ impl<
         self.request_delivery_if_necessary();
     }
 
+    /// Updates the amount the value needs to increase by before an update is sent
+    pub fn set_threshold(&mut self, threshold: T) {
+        self.threshold = threshold;
+        self.request_delivery_if_necessary();
+    }
+
     /// Stop to synchronize the value to the peer
     pub fn stop_sync(&mut self) {
         self.delivery.cancel();
@@ -46,6 +46,12 @@ impl<T: Copy + Clone + Eq + PartialEq, S: ValueToFrameWriter<T>> OnceSync<T, S>
         self.delivery.is_inflight()
     }
 
+    /// Returns `true` if the value has been acknowledged by the peer
+    #[inline]
+    pub fn is_delivered(&self) -> bool {
+        self.delivery.is_delivered()
+    }
+
     /// Returns `true` if the synchronization has been cancelled
     #[inline]
     pub fn is_cancelled(&self) -> bool {
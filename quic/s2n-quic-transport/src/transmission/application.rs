@@ -10,7 +10,10 @@ use crate::{
     recovery,
     space::{datagram, CryptoStream, HandshakeStatus},
     stream::Manager as _,
-    sync::{flag, flag::Ping},
+    sync::{
+        flag,
+        flag::{GoAway, Ping},
+    },
     transmission::{self, Mode, Provider as _},
 };
 use core::ops::RangeInclusive;
@@ -33,6 +36,7 @@ impl<'a, Config: endpoint::Config> Payload<'a, Config> {
         ack_manager: &'a mut AckManager,
         handshake_status: &'a mut HandshakeStatus,
         ping: &'a mut flag::Ping,
+        go_away: &'a mut flag::GoAway,
         stream_manager: &'a mut Config::StreamManager,
         recovery_manager: &'a mut recovery::Manager<Config>,
         crypto_stream: &'a mut CryptoStream,
@@ -49,6 +53,7 @@ impl<'a, Config: endpoint::Config> Payload<'a, Config> {
                     ack_manager,
                     handshake_status,
                     ping,
+                    go_away,
                     stream_manager,
                     local_id_registry,
                     path_manager,
@@ -103,6 +108,7 @@ pub struct Normal<'a, Config: endpoint::Config> {
     ack_manager: &'a mut AckManager,
     handshake_status: &'a mut HandshakeStatus,
     ping: &'a mut Ping,
+    go_away: &'a mut GoAway,
     stream_manager: &'a mut Config::StreamManager,
     local_id_registry: &'a mut connection::LocalIdRegistry,
     path_manager: &'a mut path::Manager<Config>,
@@ -178,6 +184,8 @@ impl<Config: endpoint::Config> Normal<'_, Config> {
         // complete as soon as possible
         self.dc_manager.on_transmit(context);
 
+        let _ = self.go_away.on_transmit(context);
+
         let _ = self.crypto_stream.tx.on_transmit((), context);
 
         //= https://www.rfc-editor.org/rfc/rfc9000#section-8.2
@@ -209,6 +217,7 @@ impl<Config: endpoint::Config> transmission::interest::Provider for Normal<'_, C
             .active_path()
             .transmission_interest(query)?;
         self.ping.transmission_interest(query)?;
+        self.go_away.transmission_interest(query)?;
         self.dc_manager.transmission_interest(query)?;
         Ok(())
     }
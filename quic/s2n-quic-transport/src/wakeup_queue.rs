@@ -5,12 +5,12 @@
 //! reception or timers. This queue is used in case connections inside the endpoint
 //! change their readiness state (e.g. they get ready to write).
 
-use alloc::{collections::VecDeque, sync::Arc};
+use crate::connection::Mutex;
+use alloc::{collections::VecDeque, sync::Arc, task::Wake};
 use core::{
     sync::atomic::{AtomicBool, Ordering},
     task::{Context, Waker},
 };
-use std::{sync::Mutex, task::Wake};
 
 /// The shared state of the [`WakeupQueue`].
 #[derive(Debug)]
@@ -10,21 +10,27 @@ use core::{
     future::Future,
     pin::Pin,
     task::{Context, Poll},
+    time::Duration,
 };
-use s2n_quic_transport::endpoint::{connect, handle::Connector};
+use s2n_quic_transport::endpoint::{connect, delay, handle::Connector};
 
 mod builder;
+pub mod pool;
 mod providers;
+pub mod retry;
 
 pub use builder::*;
-pub use connect::Connect;
+pub use connect::{Connect, HostError, DEFAULT_ATTEMPT_DELAY};
+pub use pool::Pool;
 pub use providers::*;
+use std::{collections::VecDeque, sync::Arc};
 
 /// A QUIC client endpoint, capable of opening connections
 #[derive(Clone)]
 pub struct Client {
     connector: Connector,
     local_addr: s2n_quic_core::inet::SocketAddress,
+    resolver: Arc<dyn dns::Resolver>,
 }
 
 impl fmt::Debug for Client {
@@ -105,9 +111,103 @@ impl Client {
     /// #    Ok(())
     /// # }
     /// ```
+    ///
+    /// Multiple addresses for the same server can be raced against each other by specifying
+    /// [additional addresses](Connect::with_additional_addresses). The first connection to
+    /// complete the handshake is returned.
+    ///
+    /// ```rust,no_run
+    /// # use std::error::Error;
+    /// use s2n_quic::{client::Connect, Client};
+    /// use std::{net::ToSocketAddrs, path::Path};
+    ///
+    /// # async fn connect() -> Result<(), Box<dyn Error>> {
+    /// let client = Client::builder()
+    ///     .with_tls(Path::new("./certs/cert.pem"))?
+    ///     .with_io("[::]:0")?
+    ///     .start()?;
+    ///
+    /// let addrs = "localhost:443".to_socket_addrs()?;
+    /// let connect = Connect::new_racing(addrs)
+    ///     .ok_or("no addresses found")?
+    ///     .with_server_name("localhost");
+    /// let connection = client.connect(connect).await?;
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Hostnames can be resolved by the client with the configured [DNS provider](dns). All of
+    /// the resolved addresses are raced against each other.
+    ///
+    /// ```rust,no_run
+    /// # use std::error::Error;
+    /// use s2n_quic::{client::Connect, Client};
+    /// use std::path::Path;
+    ///
+    /// # async fn connect() -> Result<(), Box<dyn Error>> {
+    /// let client = Client::builder()
+    ///     .with_tls(Path::new("./certs/cert.pem"))?
+    ///     .with_io("[::]:0")?
+    ///     .start()?;
+    ///
+    /// let connect = Connect::from_host("localhost:443")?;
+    /// let connection = client.connect(connect).await?;
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
     pub fn connect(&self, connect: Connect) -> ConnectionAttempt {
-        let attempt = self.connector.connect(connect);
-        ConnectionAttempt(attempt)
+        if let Some(host) = connect.host() {
+            let resolve = self.resolver.resolve(host);
+            return ConnectionAttempt(AttemptState::Resolving(Resolving {
+                connector: self.connector.clone(),
+                connect: Some(connect),
+                resolve,
+            }));
+        }
+
+        ConnectionAttempt(start(&self.connector, connect))
+    }
+
+    /// Establishes a connection to the specified endpoint, retrying failed attempts according to
+    /// the provided [`retry::Policy`]
+    ///
+    /// Attempts which fail with a [retryable](retry::is_retryable) error are retried after an
+    /// exponentially increasing, jittered delay. The error of the last attempt is returned if
+    /// the policy stops retrying.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use std::error::Error;
+    /// use s2n_quic::{
+    ///     client::{retry, Connect},
+    ///     Client,
+    /// };
+    /// use std::{net::SocketAddr, path::Path};
+    ///
+    /// # async fn connect() -> Result<(), Box<dyn Error>> {
+    /// let client = Client::builder()
+    ///     .with_tls(Path::new("./certs/cert.pem"))?
+    ///     .with_io("0.0.0.0:0")?
+    ///     .start()?;
+    ///
+    /// let addr: SocketAddr = "127.0.0.1:443".parse()?;
+    /// let connect = Connect::new(addr).with_server_name("localhost");
+    /// let connection = client
+    ///     .connect_with_retry(connect, retry::Policy::default())
+    ///     .await?;
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub async fn connect_with_retry(
+        &self,
+        connect: Connect,
+        policy: retry::Policy,
+    ) -> Result<Connection, connection::Error> {
+        policy.connect(self, connect).await
     }
 
     /// Wait for the client endpoint to finish handling all outstanding connections
@@ -166,19 +266,189 @@ impl Client {
     pub fn local_addr(&self) -> Result<std::net::SocketAddr, std::io::Error> {
         Ok(self.local_addr.into())
     }
+
+    /// Notifies the client that the local network has changed
+    ///
+    /// This should be called when the platform reports a network change, such as an interface
+    /// going up or down or an address change. Each established connection immediately probes
+    /// its path to the peer rather than waiting to detect the change through packet loss. If
+    /// the local address has changed, this allows the peer to validate the new address and
+    /// migrate the connection to it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use std::error::Error;
+    /// # use s2n_quic::Client;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let client = Client::bind("0.0.0.0:0")?;
+    ///
+    /// // called from the platform's network change callback
+    /// client.notify_network_change();
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn notify_network_change(&self) {
+        self.connector.notify_network_change()
+    }
 }
 
 #[must_use = "futures do nothing unless you `.await` or poll them"]
-pub struct ConnectionAttempt(connect::Attempt);
+pub struct ConnectionAttempt(AttemptState);
+
+enum AttemptState {
+    Resolving(Resolving),
+    Single(connect::Attempt),
+    Racing(Race),
+}
+
+fn start(connector: &Connector, connect: Connect) -> AttemptState {
+    if connect.is_racing() {
+        let delay = connect.attempt_delay();
+        let pending = connect.into_racing_attempts().into();
+        return AttemptState::Racing(Race {
+            connector: connector.clone(),
+            pending,
+            attempts: Vec::new(),
+            delay,
+            timer: None,
+            error: None,
+        });
+    }
+
+    AttemptState::Single(connector.connect(connect))
+}
 
 impl Future for ConnectionAttempt {
     type Output = Result<Connection, connection::Error>;
 
     fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
-        match Pin::new(&mut self.0).poll(cx) {
+        if let AttemptState::Resolving(resolving) = &mut self.0 {
+            match resolving.poll(cx) {
+                Poll::Ready(Ok(state)) => self.0 = state,
+                Poll::Ready(Err(err)) => return Poll::Ready(Err(err)),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+
+        let result = match &mut self.0 {
+            AttemptState::Resolving(_) => unreachable!("resolution has completed"),
+            AttemptState::Single(attempt) => Pin::new(attempt).poll(cx),
+            AttemptState::Racing(race) => race.poll(cx),
+        };
+
+        match result {
             Poll::Ready(Ok(conn)) => Poll::Ready(Ok(Connection::new(conn))),
             Poll::Ready(Err(err)) => Poll::Ready(Err(err)),
             Poll::Pending => Poll::Pending,
         }
     }
 }
+
+/// Resolves the host of a connection attempt before connecting
+struct Resolving {
+    connector: Connector,
+    connect: Option<Connect>,
+    resolve: dns::Resolve,
+}
+
+impl Resolving {
+    fn poll(&mut self, cx: &mut Context) -> Poll<Result<AttemptState, connection::Error>> {
+        let endpoints = match self.resolve.as_mut().poll(cx) {
+            Poll::Ready(Ok(endpoints)) => endpoints,
+            Poll::Ready(Err(err)) => {
+                let reason = connection::error::Reason::new(&err.to_string());
+                return Poll::Ready(Err(connection::Error::host_resolution_failed(reason)));
+            }
+            Poll::Pending => return Poll::Pending,
+        };
+
+        let connect = self
+            .connect
+            .take()
+            .expect("resolution should only complete once");
+
+        let connect = connect
+            .with_resolved_addresses(dns::addresses(endpoints))
+            .ok_or_else(connection::Error::no_valid_path)?;
+
+        Poll::Ready(Ok(start(&self.connector, connect)))
+    }
+}
+
+/// Races connection attempts across multiple addresses
+///
+/// A new attempt is started every `delay` or as soon as all of the in-flight attempts
+/// have failed. The delay is measured with the endpoint clock. The first attempt to succeed is
+/// returned and the remaining attempts are dropped, which makes the endpoint close their
+/// connections without waiting for the handshakes to complete.
+struct Race {
+    connector: Connector,
+    pending: VecDeque<Connect>,
+    attempts: Vec<connect::Attempt>,
+    delay: Duration,
+    timer: Option<delay::Delay>,
+    error: Option<connection::Error>,
+}
+
+impl Race {
+    fn poll(
+        &mut self,
+        cx: &mut Context,
+    ) -> Poll<Result<s2n_quic_transport::connection::Connection, connection::Error>> {
+        loop {
+            while !self.pending.is_empty() && self.should_start(cx) {
+                if let Some(connect) = self.pending.pop_front() {
+                    self.attempts.push(self.connector.connect(connect));
+                    self.timer = self.new_timer();
+                }
+            }
+
+            let mut index = 0;
+            while index < self.attempts.len() {
+                match Pin::new(&mut self.attempts[index]).poll(cx) {
+                    Poll::Ready(Ok(conn)) => return Poll::Ready(Ok(conn)),
+                    Poll::Ready(Err(err)) => {
+                        self.error = Some(err);
+                        self.attempts.swap_remove(index);
+                    }
+                    Poll::Pending => index += 1,
+                }
+            }
+
+            if !self.attempts.is_empty() {
+                return Poll::Pending;
+            }
+
+            // all of the in-flight attempts failed; try the next address immediately
+            if self.pending.is_empty() {
+                let error = self
+                    .error
+                    .take()
+                    .unwrap_or_else(connection::Error::unspecified);
+                return Poll::Ready(Err(error));
+            }
+        }
+    }
+
+    /// Returns `true` if nothing is in flight or the attempt delay has elapsed
+    fn should_start(&mut self, cx: &mut Context) -> bool {
+        if self.attempts.is_empty() {
+            return true;
+        }
+
+        match self.timer.as_mut() {
+            Some(timer) => Pin::new(timer).poll(cx).is_ready(),
+            None => true,
+        }
+    }
+
+    fn new_timer(&self) -> Option<delay::Delay> {
+        if self.delay.is_zero() {
+            return None;
+        }
+
+        Some(self.connector.delay(self.delay))
+    }
+}
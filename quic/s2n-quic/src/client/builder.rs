@@ -285,6 +285,45 @@ impl<Providers: ClientProviders> Builder<Providers> {
         ClientProviders
     );
 
+    impl_provider_method!(
+        /// Sets the DNS provider for the [`Client`]
+        ///
+        /// The DNS provider resolves the hosts of connection attempts created with
+        /// [`Connect::from_host`](crate::client::Connect::from_host).
+        ///
+        /// # Examples
+        ///
+        /// Resolves all hostnames to a fixed address
+        ///
+        /// ```rust,no_run
+        /// # use std::error::Error;
+        /// use s2n_quic::{Client, provider::dns};
+        /// use std::net::SocketAddr;
+        /// #
+        /// # #[tokio::main]
+        /// # async fn main() -> Result<(), Box<dyn Error>> {
+        /// struct MyResolver(SocketAddr);
+        ///
+        /// impl dns::Resolver for MyResolver {
+        ///     fn resolve(&self, _host: &dns::Host) -> dns::Resolve {
+        ///         let endpoint = dns::Endpoint::new([self.0]);
+        ///         Box::pin(async move { Ok(vec![endpoint]) })
+        ///     }
+        /// }
+        ///
+        /// let client = Client::builder()
+        ///     .with_dns(MyResolver("127.0.0.1:443".parse()?))?
+        ///     .with_io("0.0.0.0:0")?
+        ///     .start()?;
+        /// #
+        /// #    Ok(())
+        /// # }
+        /// ```
+        with_dns,
+        dns,
+        ClientProviders
+    );
+
     #[cfg(any(test, feature = "unstable-provider-packet-interceptor"))]
     impl_provider_method!(
         /// Sets the packet interceptor provider for the [`Client`]
@@ -293,6 +332,24 @@ impl<Providers: ClientProviders> Builder<Providers> {
         ClientProviders
     );
 
+    #[cfg(any(test, feature = "unstable-provider-compatibility"))]
+    impl_provider_method!(
+        /// Sets the compatibility provider for the [`Client`]
+        with_compatibility,
+        compatibility,
+        ClientProviders
+    );
+
+    #[cfg(any(test, feature = "unstable-provider-path-cache"))]
+    impl_provider_method!(
+        /// Sets the path cache provider for the [`Client`]
+        ///
+        /// The cache is shared by all of the connections on the endpoint.
+        with_path_cache,
+        path_cache,
+        ClientProviders
+    );
+
     #[cfg(any(test, feature = "unstable-provider-random"))]
     impl_provider_method!(
         /// Sets the random provider for the [`Client`]
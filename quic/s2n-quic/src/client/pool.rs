@@ -0,0 +1,416 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A pool of reusable client connections
+//!
+//! Protocols such as HTTP/3 multiplex many requests over a single connection. The [`Pool`]
+//! deduplicates connections to the same server so that concurrent callers share an established
+//! connection rather than each performing a handshake. Connections which have not been used for
+//! the configured [idle timeout](Pool::with_idle_timeout) are closed and removed from the pool.
+//!
+//! Connections are reaped on each call to [`Pool::connect`]. Applications which want closed and
+//! idle connections released promptly should also spawn the future returned by
+//! [`Pool::reaper`], which reaps the pool on the endpoint clock.
+
+use super::{Client, Connect};
+use crate::connection::{self, Connection};
+use bytes::Bytes;
+use core::{
+    fmt,
+    future::Future,
+    ops::{Deref, DerefMut},
+    time::Duration,
+};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, MutexGuard},
+    time::Instant,
+};
+
+/// The default amount of time an unused connection is kept in the pool
+pub const DEFAULT_IDLE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The default interval at which the [`Pool::reaper`] checks the pooled connections
+pub const DEFAULT_REAP_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The application error code used when closing idle connections
+const IDLE_ERROR_CODE: u32 = 0;
+
+/// A pool of client connections, keyed by server name, address and application protocol
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # use std::error::Error;
+/// use s2n_quic::{client::{Connect, Pool}, Client};
+/// use std::{net::SocketAddr, path::Path, time::Duration};
+///
+/// # async fn connect() -> Result<(), Box<dyn Error>> {
+/// let client = Client::builder()
+///     .with_tls(Path::new("./certs/cert.pem"))?
+///     .with_io("0.0.0.0:0")?
+///     .start()?;
+///
+/// let pool = Pool::new(client).with_idle_timeout(Duration::from_secs(60));
+///
+/// // release closed and idle connections in the background
+/// tokio::spawn(pool.reaper());
+///
+/// let addr: SocketAddr = "127.0.0.1:443".parse()?;
+/// let connect = Connect::new(addr).with_server_name("localhost");
+///
+/// // both handles share the same underlying connection
+/// let mut a = pool.connect(connect.clone()).await?;
+/// let mut b = pool.connect(connect).await?;
+/// assert_eq!(a.id(), b.id());
+///
+/// let stream = a.open_bidirectional_stream().await?;
+/// #
+/// #    Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct Pool {
+    idle_timeout: Duration,
+    reap_interval: Duration,
+    inner: Arc<Inner>,
+}
+
+impl fmt::Debug for Pool {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Pool")
+            .field("client", &self.inner.client)
+            .field("idle_timeout", &self.idle_timeout)
+            .field("reap_interval", &self.reap_interval)
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+impl Pool {
+    /// Creates a pool which opens connections with the provided client
+    pub fn new(client: Client) -> Self {
+        Self {
+            idle_timeout: DEFAULT_IDLE_TIMEOUT,
+            reap_interval: DEFAULT_REAP_INTERVAL,
+            inner: Arc::new(Inner {
+                client,
+                state: Default::default(),
+            }),
+        }
+    }
+
+    /// Sets the amount of time a connection without any outstanding [`Handle`]s is kept in the pool
+    ///
+    /// Defaults to [`DEFAULT_IDLE_TIMEOUT`].
+    #[must_use]
+    pub fn with_idle_timeout(mut self, idle_timeout: Duration) -> Self {
+        self.idle_timeout = idle_timeout;
+        self
+    }
+
+    /// Sets the interval at which the [`Self::reaper`] checks the pooled connections
+    ///
+    /// Defaults to [`DEFAULT_REAP_INTERVAL`].
+    #[must_use]
+    pub fn with_reap_interval(mut self, reap_interval: Duration) -> Self {
+        self.reap_interval = reap_interval;
+        self
+    }
+
+    /// Returns a handle to a pooled connection for `connect`, establishing one if needed
+    ///
+    /// Any open connection with the same server name and address is reused, regardless of its
+    /// negotiated application protocol.
+    pub async fn connect(&self, connect: Connect) -> connection::Result<Handle> {
+        self.connect_impl(connect, None).await
+    }
+
+    /// Returns a handle to a pooled connection for `connect` which negotiated the provided
+    /// application protocol, establishing one if needed
+    ///
+    /// If a newly established connection negotiates a different application protocol, it is
+    /// still added to the pool but an error is returned.
+    pub async fn connect_with_application_protocol<P: Into<Bytes>>(
+        &self,
+        connect: Connect,
+        application_protocol: P,
+    ) -> connection::Result<Handle> {
+        self.connect_impl(connect, Some(application_protocol.into()))
+            .await
+    }
+
+    async fn connect_impl(
+        &self,
+        connect: Connect,
+        application_protocol: Option<Bytes>,
+    ) -> connection::Result<Handle> {
+        let connecting = {
+            let mut state = self.inner.lock();
+            state.reap(self.idle_timeout);
+
+            if let Some(handle) = self.checkout(&mut state, &connect, &application_protocol) {
+                return Ok(handle);
+            }
+
+            state
+                .slots
+                .entry(connect.clone())
+                .or_default()
+                .connecting
+                .clone()
+        };
+
+        // only allow a single in-flight attempt for each key so concurrent callers share the
+        // resulting connection
+        let _connecting = connecting.lock().await;
+
+        {
+            let mut state = self.inner.lock();
+            if let Some(handle) = self.checkout(&mut state, &connect, &application_protocol) {
+                return Ok(handle);
+            }
+        }
+
+        let connection = self.inner.client.connect(connect.clone()).await?;
+        let negotiated = connection.application_protocol()?;
+
+        let mut state = self.inner.lock();
+        let slot = state.slots.entry(connect.clone()).or_default();
+        slot.entries.push(Entry {
+            connection,
+            application_protocol: negotiated.clone(),
+            active: 0,
+            idle_since: Instant::now(),
+        });
+
+        if application_protocol.map_or(false, |expected| expected != negotiated) {
+            return Err(connection::Error::invalid_configuration(
+                "the negotiated application protocol did not match the requested protocol",
+            ));
+        }
+
+        Ok(self
+            .checkout(&mut state, &connect, &Some(negotiated))
+            .expect("entry was just inserted"))
+    }
+
+    /// Closes and removes all of the connections which have exceeded the idle timeout
+    ///
+    /// This is called automatically on every [`Self::connect`] call and periodically by the
+    /// [`Self::reaper`].
+    pub fn reap(&self) {
+        self.inner.lock().reap(self.idle_timeout);
+    }
+
+    /// Returns a future which reaps the pool every [reap interval](Self::with_reap_interval)
+    ///
+    /// The interval is measured with the endpoint clock, so the future doesn't depend on the
+    /// async runtime it is spawned on. It completes once every clone of the pool has been dropped
+    /// or the client endpoint has shut down.
+    pub fn reaper(&self) -> impl Future<Output = ()> + Send + 'static {
+        let inner = Arc::downgrade(&self.inner);
+        let idle_timeout = self.idle_timeout;
+        let reap_interval = self.reap_interval;
+
+        async move {
+            loop {
+                // only hold on to the pool while reaping so dropping it stops the reaper
+                let delay = {
+                    let Some(inner) = inner.upgrade() else {
+                        return;
+                    };
+
+                    inner.lock().reap(idle_timeout);
+
+                    let connector = &inner.client.connector;
+                    if !connector.is_open() {
+                        return;
+                    }
+
+                    connector.delay(reap_interval)
+                };
+
+                delay.await;
+            }
+        }
+    }
+
+    /// Returns the number of connections in the pool
+    pub fn len(&self) -> usize {
+        self.inner
+            .lock()
+            .slots
+            .values()
+            .map(|slot| slot.entries.len())
+            .sum()
+    }
+
+    /// Returns `true` if the pool has no connections
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn checkout(
+        &self,
+        state: &mut State,
+        connect: &Connect,
+        application_protocol: &Option<Bytes>,
+    ) -> Option<Handle> {
+        let slot = state.slots.get_mut(connect)?;
+
+        // prefer the least used connection to spread the load
+        let entry = slot
+            .entries
+            .iter_mut()
+            .filter(|entry| entry.is_open())
+            .filter(|entry| {
+                application_protocol
+                    .as_ref()
+                    .map_or(true, |expected| *expected == entry.application_protocol)
+            })
+            .min_by_key(|entry| entry.active)?;
+
+        entry.active += 1;
+
+        Some(Handle {
+            inner: entry.connection.handle(),
+            connect: connect.clone(),
+            pool: self.inner.clone(),
+        })
+    }
+}
+
+struct Inner {
+    client: Client,
+    state: Mutex<State>,
+}
+
+impl Inner {
+    fn lock(&self) -> MutexGuard<State> {
+        self.state.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+#[derive(Default)]
+struct State {
+    slots: HashMap<Connect, Slot>,
+}
+
+impl State {
+    fn reap(&mut self, idle_timeout: Duration) {
+        let now = Instant::now();
+
+        self.slots.retain(|_connect, slot| {
+            slot.entries.retain(|entry| {
+                if !entry.is_open() {
+                    return false;
+                }
+
+                if entry.active == 0
+                    && now.saturating_duration_since(entry.idle_since) >= idle_timeout
+                {
+                    entry.connection.close(IDLE_ERROR_CODE.into());
+                    return false;
+                }
+
+                true
+            });
+
+            // keep the slot around while an attempt is in flight
+            !slot.entries.is_empty() || Arc::strong_count(&slot.connecting) > 1
+        });
+    }
+
+    fn release(&mut self, connect: &Connect, id: u64) {
+        let Some(slot) = self.slots.get_mut(connect) else {
+            return;
+        };
+
+        let Some(index) = slot
+            .entries
+            .iter()
+            .position(|entry| entry.connection.id() == id)
+        else {
+            return;
+        };
+
+        let entry = &mut slot.entries[index];
+
+        // release closed connections as soon as they're no longer in use
+        if !entry.is_open() {
+            slot.entries.swap_remove(index);
+            return;
+        }
+
+        entry.active -= 1;
+        if entry.active == 0 {
+            entry.idle_since = Instant::now();
+        }
+    }
+}
+
+#[derive(Default)]
+struct Slot {
+    entries: Vec<Entry>,
+    connecting: Arc<tokio::sync::Mutex<()>>,
+}
+
+struct Entry {
+    connection: Connection,
+    application_protocol: Bytes,
+    /// The number of outstanding handles to the connection
+    active: usize,
+    /// The last time the number of outstanding handles dropped to zero
+    idle_since: Instant,
+}
+
+impl Entry {
+    #[inline]
+    fn is_open(&self) -> bool {
+        // the connection API returns an error once the connection is closed
+        self.connection.remote_addr().is_ok()
+    }
+}
+
+/// A handle to a pooled connection
+///
+/// The connection is considered in use until the handle is dropped. Cloning the inner
+/// [`connection::Handle`] does not extend the usage tracked by the pool.
+pub struct Handle {
+    inner: connection::Handle,
+    connect: Connect,
+    pool: Arc<Inner>,
+}
+
+impl fmt::Debug for Handle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Handle")
+            .field("connect", &self.connect)
+            .field("connection", &self.inner)
+            .finish()
+    }
+}
+
+impl Deref for Handle {
+    type Target = connection::Handle;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl DerefMut for Handle {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
+
+impl Drop for Handle {
+    fn drop(&mut self) {
+        let mut state = self.pool.lock();
+        state.release(&self.connect, self.inner.id());
+    }
+}
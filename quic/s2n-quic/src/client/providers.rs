@@ -5,6 +5,7 @@ use super::*;
 use core::marker::PhantomData;
 use s2n_quic_core::{connection::id::Generator, crypto, path};
 use s2n_quic_transport::{connection, endpoint, stream};
+use std::sync::Arc;
 
 impl_providers_state! {
     #[derive(Debug, Default)]
@@ -23,6 +24,9 @@ impl_providers_state! {
         tls: Tls,
         datagram: Datagram,
         dc: Dc,
+        compatibility: Compatibility,
+        path_cache: PathCache,
+        dns: Dns,
     }
 
     /// Opaque trait containing all of the configured providers
@@ -44,6 +48,9 @@ impl<
         Tls: tls::Provider,
         Datagram: datagram::Provider,
         Dc: dc::Provider,
+        Compatibility: compatibility::Provider,
+        PathCache: path_cache::Provider,
+        Dns: dns::Provider,
     >
     Providers<
         CongestionController,
@@ -60,6 +67,9 @@ impl<
         Tls,
         Datagram,
         Dc,
+        Compatibility,
+        PathCache,
+        Dns,
     >
 {
     pub fn start(self) -> Result<Client, StartError> {
@@ -78,6 +88,9 @@ impl<
             tls,
             datagram,
             dc,
+            compatibility,
+            path_cache,
+            dns,
         } = self;
 
         let congestion_controller = congestion_controller.start().map_err(StartError::new)?;
@@ -89,6 +102,8 @@ impl<
         let stateless_reset_token = stateless_reset_token.start().map_err(StartError::new)?;
         let random = random.start().map_err(StartError::new)?;
         let endpoint_limits = EndpointLimits;
+        let accept_filter = AcceptFilter;
+        let address_attestation = AddressAttestation;
         let limits = limits.start().map_err(StartError::new)?;
         let mtu = mtu.start().map_err(StartError::new)?;
         let event = event.start().map_err(StartError::new)?;
@@ -98,6 +113,9 @@ impl<
         let tls = tls.start_client().map_err(StartError::new)?;
         let datagram = datagram.start().map_err(StartError::new)?;
         let dc = dc.start().map_err(StartError::new)?;
+        let compatibility = Arc::new(compatibility.start().map_err(StartError::new)?);
+        let path_cache = Arc::new(path_cache.start().map_err(StartError::new)?);
+        let resolver = Arc::new(dns.start().map_err(StartError::new)?);
 
         // Validate providers
         // TODO: Add more validation https://github.com/aws/s2n-quic/issues/285
@@ -120,6 +138,8 @@ impl<
             stateless_reset_token,
             random,
             endpoint_limits,
+            accept_filter,
+            address_attestation,
             event,
             limits,
             mtu,
@@ -130,6 +150,8 @@ impl<
             path_migration,
             datagram,
             dc,
+            compatibility,
+            path_cache,
         };
 
         let (endpoint, connector) = endpoint::Endpoint::new_client(endpoint_config);
@@ -140,6 +162,7 @@ impl<
         Ok(Client {
             connector,
             local_addr,
+            resolver,
         })
     }
 }
@@ -156,6 +179,27 @@ impl endpoint::limits::Limiter for EndpointLimits {
     }
 }
 
+#[derive(Debug)]
+struct AcceptFilter;
+
+impl endpoint::accept_filter::Filter for AcceptFilter {
+    fn on_connection_attempt(
+        &mut self,
+        _attempt: &endpoint::accept_filter::ConnectionAttempt,
+    ) -> endpoint::accept_filter::Outcome {
+        unreachable!("accept filters should not be used with clients")
+    }
+}
+
+#[derive(Debug)]
+struct AddressAttestation;
+
+impl endpoint::address_attestation::Validator for AddressAttestation {
+    fn is_attested(&mut self, _attestation: &endpoint::address_attestation::Attestation) -> bool {
+        unreachable!("address attestations should not be used with clients")
+    }
+}
+
 #[derive(Debug)]
 struct Token;
 
@@ -217,6 +261,8 @@ struct EndpointConfig<
     Tls,
     Datagram,
     Dc,
+    Compatibility,
+    PathCache,
 > {
     congestion_controller: CongestionController,
     connection_close_formatter: ConnectionCloseFormatter,
@@ -225,6 +271,8 @@ struct EndpointConfig<
     stateless_reset_token: StatelessResetToken,
     random: Random,
     endpoint_limits: EndpointLimits,
+    accept_filter: AcceptFilter,
+    address_attestation: AddressAttestation,
     event: Event,
     limits: Limits,
     mtu: path::mtu::Manager<Mtu>,
@@ -235,6 +283,8 @@ struct EndpointConfig<
     path_migration: PathMigration,
     datagram: Datagram,
     dc: Dc,
+    compatibility: Arc<Compatibility>,
+    path_cache: Arc<PathCache>,
 }
 
 impl<
@@ -252,6 +302,8 @@ impl<
         Tls: crypto::tls::Endpoint,
         Datagram: s2n_quic_core::datagram::Endpoint,
         Dc: s2n_quic_core::dc::Endpoint,
+        Compatibility: s2n_quic_core::connection::compatibility::Policy,
+        PathCache: s2n_quic_core::path::cache::Cache,
     > core::fmt::Debug
     for EndpointConfig<
         CongestionController,
@@ -268,6 +320,8 @@ impl<
         Tls,
         Datagram,
         Dc,
+        Compatibility,
+        PathCache,
     >
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -290,6 +344,8 @@ impl<
         Tls: crypto::tls::Endpoint,
         Datagram: s2n_quic_core::datagram::Endpoint,
         Dc: s2n_quic_core::dc::Endpoint,
+        Compatibility: s2n_quic_core::connection::compatibility::Policy,
+        PathCache: s2n_quic_core::path::cache::Cache,
     > endpoint::Config
     for EndpointConfig<
         CongestionController,
@@ -306,6 +362,8 @@ impl<
         Tls,
         Datagram,
         Dc,
+        Compatibility,
+        PathCache,
     >
 {
     type ConnectionIdFormat = ConnectionID;
@@ -318,6 +376,8 @@ impl<
     type ConnectionLock = std::sync::Mutex<Self::Connection>;
     type CongestionControllerEndpoint = CongestionController;
     type EndpointLimits = EndpointLimits;
+    type AcceptFilter = AcceptFilter;
+    type AddressAttestation = AddressAttestation;
     type EventSubscriber = Event;
     type TLSEndpoint = Tls;
     type TokenFormat = Token;
@@ -328,6 +388,8 @@ impl<
     type PacketInterceptor = PacketInterceptor;
     type DatagramEndpoint = Datagram;
     type DcEndpoint = Dc;
+    type CompatibilityPolicy = Compatibility;
+    type PathCache = PathCache;
 
     const ENDPOINT_TYPE: endpoint::Type = endpoint::Type::Client;
 
@@ -341,6 +403,8 @@ impl<
             random_generator: &mut self.random,
             tls: &mut self.tls,
             endpoint_limits: &mut self.endpoint_limits,
+            accept_filter: &mut self.accept_filter,
+            address_attestation: &mut self.address_attestation,
             token: &mut self.token,
             connection_limits: &mut self.limits,
             mtu: &mut self.mtu,
@@ -348,6 +412,8 @@ impl<
             path_migration: &mut self.path_migration,
             datagram: &mut self.datagram,
             dc: &mut self.dc,
+            compatibility: &self.compatibility,
+            path_cache: &self.path_cache,
         }
     }
 }
@@ -0,0 +1,282 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Retrying connection attempts with exponential backoff
+//!
+//! A [`Policy`] is passed to [`Client::connect_with_retry`] to retry failed connection attempts.
+//! After each failure, the client waits for an exponentially increasing, randomly jittered delay
+//! before trying again. Delays are measured with the endpoint clock. Retries stop after the
+//! configured number of attempts, once the [retry budget](Policy::with_budget) is exhausted, or if
+//! the error is [not retryable](is_retryable).
+
+use super::{Client, Connect};
+use crate::connection::{self, Connection};
+use core::time::Duration;
+use futures::future::{self, Either};
+use rand::Rng;
+use s2n_quic_core::{endpoint, transport};
+
+/// The default maximum number of connection attempts, including the first attempt
+pub const DEFAULT_MAX_ATTEMPTS: u32 = 5;
+
+/// The default delay before the first retry
+pub const DEFAULT_INITIAL_BACKOFF: Duration = Duration::from_millis(100);
+
+/// The default upper bound on the delay between attempts
+pub const DEFAULT_MAX_BACKOFF: Duration = Duration::from_secs(10);
+
+/// The default factor the delay is multiplied by after each retry
+pub const DEFAULT_MULTIPLIER: u32 = 2;
+
+/// Configures how failed connection attempts are retried
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # use std::error::Error;
+/// use s2n_quic::{
+///     client::{retry, Connect},
+///     Client,
+/// };
+/// use std::{net::SocketAddr, path::Path, time::Duration};
+///
+/// # async fn connect() -> Result<(), Box<dyn Error>> {
+/// let client = Client::builder()
+///     .with_tls(Path::new("./certs/cert.pem"))?
+///     .with_io("0.0.0.0:0")?
+///     .start()?;
+///
+/// let policy = retry::Policy::default()
+///     .with_max_attempts(10)
+///     .with_max_backoff(Duration::from_secs(5))
+///     .with_budget(Duration::from_secs(30));
+///
+/// let addr: SocketAddr = "127.0.0.1:443".parse()?;
+/// let connect = Connect::new(addr).with_server_name("localhost");
+/// let connection = client.connect_with_retry(connect, policy).await?;
+/// #
+/// #    Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Policy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    multiplier: u32,
+    jitter: bool,
+    budget: Option<Duration>,
+    is_retryable: fn(&connection::Error) -> bool,
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        Self {
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            initial_backoff: DEFAULT_INITIAL_BACKOFF,
+            max_backoff: DEFAULT_MAX_BACKOFF,
+            multiplier: DEFAULT_MULTIPLIER,
+            jitter: true,
+            budget: None,
+            is_retryable,
+        }
+    }
+}
+
+impl Policy {
+    /// Sets the maximum number of connection attempts, including the first attempt
+    /// (default: 5)
+    ///
+    /// A value of 1 disables retries. Values less than 1 are treated as 1.
+    #[must_use]
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Sets the delay before the first retry (default: 100ms)
+    #[must_use]
+    pub fn with_initial_backoff(mut self, initial_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    /// Sets the upper bound on the delay between attempts (default: 10s)
+    #[must_use]
+    pub fn with_max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    /// Sets the factor the delay is multiplied by after each retry (default: 2)
+    ///
+    /// Values less than 1 are treated as 1, which retries with a constant delay.
+    #[must_use]
+    pub fn with_multiplier(mut self, multiplier: u32) -> Self {
+        self.multiplier = multiplier.max(1);
+        self
+    }
+
+    /// Configures randomizing the delay between attempts (default: true)
+    ///
+    /// With jitter enabled, each delay is chosen uniformly between zero and the current backoff.
+    /// This avoids many clients retrying in lockstep after a shared failure, such as a server
+    /// restart.
+    #[must_use]
+    pub fn with_jitter(mut self, enabled: bool) -> Self {
+        self.jitter = enabled;
+        self
+    }
+
+    /// Sets the total amount of time that can be spent on connection attempts (default: none)
+    ///
+    /// The budget is measured with the endpoint clock. A retry is not started if the budget
+    /// elapses before its delay completes. In-flight attempts are not interrupted by the budget;
+    /// their duration is bounded by the connection [limits](crate::provider::limits).
+    #[must_use]
+    pub fn with_budget(mut self, budget: Duration) -> Self {
+        self.budget = Some(budget);
+        self
+    }
+
+    /// Overrides the function which classifies errors as retryable (default: [`is_retryable`])
+    #[must_use]
+    pub fn with_retryable(mut self, is_retryable: fn(&connection::Error) -> bool) -> Self {
+        self.is_retryable = is_retryable;
+        self
+    }
+
+    /// Returns the delay before the retry with the given index, starting at 0
+    fn delay<R: Rng>(&self, retry: u32, rng: &mut R) -> Duration {
+        let factor = self.multiplier.checked_pow(retry).unwrap_or(u32::MAX);
+        let backoff = self
+            .initial_backoff
+            .checked_mul(factor)
+            .unwrap_or(Duration::MAX)
+            .min(self.max_backoff);
+
+        if !self.jitter || backoff.is_zero() {
+            return backoff;
+        }
+
+        rng.gen_range(Duration::ZERO..=backoff)
+    }
+
+    pub(super) async fn connect(
+        self,
+        client: &Client,
+        connect: Connect,
+    ) -> Result<Connection, connection::Error> {
+        let mut deadline = self.budget.map(|budget| client.connector.delay(budget));
+        let mut attempts = 0;
+
+        loop {
+            attempts += 1;
+
+            let error = match client.connect(connect.clone()).await {
+                Ok(connection) => return Ok(connection),
+                Err(error) => error,
+            };
+
+            if attempts >= self.max_attempts || !(self.is_retryable)(&error) {
+                return Err(error);
+            }
+
+            let delay = self.delay(attempts - 1, &mut rand::thread_rng());
+            let delay = client.connector.delay(delay);
+
+            if let Some(deadline) = deadline.as_mut() {
+                if let Either::Right(_) = future::select(delay, deadline).await {
+                    return Err(error);
+                }
+            } else {
+                delay.await;
+            }
+        }
+    }
+}
+
+/// Returns `true` if a connection attempt which failed with the given error could succeed if
+/// retried
+///
+/// Errors caused by the peer being unreachable, overloaded or having lost the connection state
+/// are retryable. Errors caused by the configuration, such as a failed certificate validation, or
+/// by an explicit decision of the peer's application are not, since another attempt is expected
+/// to fail in the same way.
+pub fn is_retryable(error: &connection::Error) -> bool {
+    use connection::Error;
+
+    match error {
+        Error::IdleTimerExpired { .. }
+        | Error::MaxHandshakeDurationExceeded { .. }
+        | Error::NoValidPath { .. }
+        | Error::StatelessReset { .. } => true,
+        Error::Transport {
+            code,
+            initiator: endpoint::Location::Remote,
+            ..
+        } => {
+            *code == transport::error::Code::CONNECTION_REFUSED
+                || *code == transport::error::Code::INTERNAL_ERROR
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+
+    #[test]
+    fn backoff_test() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+        let policy = Policy::default()
+            .with_initial_backoff(Duration::from_millis(100))
+            .with_max_backoff(Duration::from_secs(1))
+            .with_jitter(false);
+
+        let delays: Vec<_> = (0..6).map(|retry| policy.delay(retry, &mut rng)).collect();
+        assert_eq!(
+            delays,
+            [100, 200, 400, 800, 1000, 1000].map(Duration::from_millis)
+        );
+
+        // large retry counts saturate at the max backoff
+        assert_eq!(policy.delay(u32::MAX, &mut rng), Duration::from_secs(1));
+
+        let policy = policy.with_multiplier(1);
+        assert_eq!(policy.delay(5, &mut rng), Duration::from_millis(100));
+    }
+
+    #[test]
+    fn jitter_test() {
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(123);
+        let policy = Policy::default();
+
+        for retry in 0..10 {
+            let backoff = policy.with_jitter(false).delay(retry, &mut rng);
+            let delay = policy.delay(retry, &mut rng);
+            assert!(delay <= backoff, "{delay:?} > {backoff:?}");
+        }
+    }
+
+    #[test]
+    fn is_retryable_test() {
+        assert!(is_retryable(&connection::Error::idle_timer_expired()));
+        assert!(is_retryable(&connection::Error::no_valid_path()));
+        assert!(is_retryable(&connection::Error::stateless_reset()));
+        assert!(is_retryable(
+            &connection::Error::max_handshake_duration_exceeded(Duration::from_secs(1))
+        ));
+
+        assert!(!is_retryable(&connection::Error::endpoint_closing()));
+        assert!(!is_retryable(&connection::Error::invalid_configuration(
+            "invalid"
+        )));
+        assert!(!is_retryable(&connection::Error::closed(
+            endpoint::Location::Remote
+        )));
+        assert!(!is_retryable(&connection::Error::application(1u8.into())));
+    }
+}
@@ -11,10 +11,17 @@ mod handle;
 
 pub use acceptor::*;
 pub use handle::*;
-pub use s2n_quic_core::connection::Error;
+pub use s2n_quic_core::{connection::Error, path::Stats as PathStats};
 
 pub mod error {
-    pub use s2n_quic_core::transport::error::Code;
+    pub use s2n_quic_core::{
+        connection::error::{CloseSource, Reason},
+        transport::error::Code,
+    };
+}
+
+pub mod mtu {
+    pub use s2n_quic_core::path::mtu::{History, HistoryEntry, HISTORY_CAPACITY};
 }
 
 pub type Result<T, E = Error> = core::result::Result<T, E>;
@@ -83,3 +90,24 @@ impl Connection {
         (handle, acceptor)
     }
 }
+
+/// Yields each [`PeerStream`](crate::stream::PeerStream) opened by the peer
+///
+/// This is equivalent to calling [`Connection::accept`] in a loop. The stream ends once the
+/// connection is closed without an error.
+impl futures::stream::Stream for Connection {
+    type Item = Result<crate::stream::PeerStream>;
+
+    #[inline]
+    fn poll_next(
+        mut self: core::pin::Pin<&mut Self>,
+        cx: &mut core::task::Context<'_>,
+    ) -> core::task::Poll<Option<Self::Item>> {
+        match core::task::ready!(self.poll_accept(cx)) {
+            Ok(Some(stream)) => Some(Ok(stream)),
+            Ok(None) => None,
+            Err(err) => Some(Err(err)),
+        }
+        .into()
+    }
+}
@@ -28,7 +28,65 @@ macro_rules! impl_handle_api {
             &mut self,
             stream_type: $crate::stream::Type,
         ) -> $crate::connection::Result<$crate::stream::LocalStream> {
-            futures::future::poll_fn(|cx| self.poll_open_stream(stream_type, cx)).await
+            use s2n_quic_core::stream::StreamType;
+            use $crate::stream::{BidirectionalStream, SendStream};
+
+            let mut open = $crate::connection::OpenGuard::new(&mut self.0, stream_type);
+            let stream = futures::future::poll_fn(|cx| open.poll_open(cx)).await?;
+
+            Ok(if stream_type == StreamType::Unidirectional {
+                SendStream::new(stream.into()).into()
+            } else {
+                BidirectionalStream::new(stream).into()
+            })
+        }
+
+        /// Opens a new [`LocalStream`](`crate::stream::LocalStream`) with a specific type, waiting
+        /// at most `timeout` for stream capacity
+        ///
+        /// Once the call has been waiting for longer than the `timeout`, it fails with a
+        /// [`connection::Error::OpenStreamTimeout`](crate::connection::Error::OpenStreamTimeout).
+        /// The connection remains open and the call can be retried. The deadline is tracked by the
+        /// connection itself, so no timer or watchdog task is required by the application.
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::connection::Result<()> {
+        /// #   use s2n_quic::stream;
+        /// #   let mut handle: s2n_quic::connection::Handle = todo!();
+        /// #
+        /// let timeout = core::time::Duration::from_secs(1);
+        ///
+        /// match handle.open_stream_with_timeout(stream::Type::Bidirectional, timeout).await {
+        ///     Ok(stream) => println!("Stream opened with id: {}", stream.id()),
+        ///     Err(s2n_quic::connection::Error::OpenStreamTimeout { .. }) => {
+        ///         println!("The peer didn't issue stream credits in time")
+        ///     }
+        ///     Err(error) => return Err(error),
+        /// }
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub async fn open_stream_with_timeout(
+            &mut self,
+            stream_type: $crate::stream::Type,
+            timeout: core::time::Duration,
+        ) -> $crate::connection::Result<$crate::stream::LocalStream> {
+            use s2n_quic_core::stream::StreamType;
+            use $crate::stream::{BidirectionalStream, SendStream};
+
+            let mut open =
+                $crate::connection::OpenGuard::new(&mut self.0, stream_type).with_timeout(timeout);
+            let stream = futures::future::poll_fn(|cx| open.poll_open(cx)).await?;
+
+            Ok(if stream_type == StreamType::Unidirectional {
+                SendStream::new(stream.into()).into()
+            } else {
+                BidirectionalStream::new(stream).into()
+            })
         }
 
         /// Polls opening a [`LocalStream`](`crate::stream::LocalStream`) with a specific type
@@ -82,7 +140,14 @@ macro_rules! impl_handle_api {
         pub async fn open_bidirectional_stream(
             &mut self,
         ) -> $crate::connection::Result<$crate::stream::BidirectionalStream> {
-            futures::future::poll_fn(|cx| self.poll_open_bidirectional_stream(cx)).await
+            use s2n_quic_core::stream::StreamType;
+            use $crate::stream::BidirectionalStream;
+
+            let mut open =
+                $crate::connection::OpenGuard::new(&mut self.0, StreamType::Bidirectional);
+            let stream = futures::future::poll_fn(|cx| open.poll_open(cx)).await?;
+
+            Ok(BidirectionalStream::new(stream))
         }
 
         /// Polls opening a [`BidirectionalStream`](`crate::stream::BidirectionalStream`)
@@ -125,7 +190,14 @@ macro_rules! impl_handle_api {
         pub async fn open_send_stream(
             &mut self,
         ) -> $crate::connection::Result<$crate::stream::SendStream> {
-            futures::future::poll_fn(|cx| self.poll_open_send_stream(cx)).await
+            use s2n_quic_core::stream::StreamType;
+            use $crate::stream::SendStream;
+
+            let mut open =
+                $crate::connection::OpenGuard::new(&mut self.0, StreamType::Unidirectional);
+            let stream = futures::future::poll_fn(|cx| open.poll_open(cx)).await?;
+
+            Ok(SendStream::new(stream.into()))
         }
 
         /// Polls opening a [`SendStream`](`crate::stream::SendStream`)
@@ -145,7 +217,186 @@ macro_rules! impl_handle_api {
             })
         }
 
+        /// Opens a new [`LocalStream`](`crate::stream::LocalStream`) with a specific type, without
+        /// waiting for stream capacity
+        ///
+        /// The method will return
+        ///  - `Ok(Some(stream))` if a stream of the requested type was opened
+        ///  - `Ok(None)` if the local or peer stream limits don't currently allow opening the stream
+        ///  - `Err(stream_error)` if the stream could not be opened due to an error
+        ///
+        /// Use [`Self::is_open_stream_blocked`] to check if the peer's limits are preventing the
+        /// stream from being opened.
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::connection::Result<()> {
+        /// #   use s2n_quic::stream;
+        /// #   let mut handle: s2n_quic::connection::Handle = todo!();
+        /// #
+        /// match handle.try_open_stream(stream::Type::Bidirectional)? {
+        ///     Some(stream) => println!("Stream opened with id: {}", stream.id()),
+        ///     None => println!("No stream capacity available"),
+        /// }
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub fn try_open_stream(
+            &mut self,
+            stream_type: $crate::stream::Type,
+        ) -> $crate::connection::Result<Option<$crate::stream::LocalStream>> {
+            use s2n_quic_core::stream::StreamType;
+            use $crate::stream::{BidirectionalStream, SendStream};
+
+            Ok(match self.0.try_open_stream(stream_type)? {
+                Some(stream) if stream_type == StreamType::Unidirectional => {
+                    Some(SendStream::new(stream.into()).into())
+                }
+                Some(stream) => Some(BidirectionalStream::new(stream).into()),
+                None => None,
+            })
+        }
+
+        /// Opens a new [`BidirectionalStream`](`crate::stream::BidirectionalStream`), without
+        /// waiting for stream capacity
+        ///
+        /// See [`Self::try_open_stream`] for more details.
+        #[inline]
+        pub fn try_open_bidirectional_stream(
+            &mut self,
+        ) -> $crate::connection::Result<Option<$crate::stream::BidirectionalStream>> {
+            use s2n_quic_core::stream::StreamType;
+            use $crate::stream::BidirectionalStream;
+
+            let stream = self.0.try_open_stream(StreamType::Bidirectional)?;
+            Ok(stream.map(BidirectionalStream::new))
+        }
+
+        /// Opens a [`SendStream`](`crate::stream::SendStream`), without waiting for stream
+        /// capacity
+        ///
+        /// See [`Self::try_open_stream`] for more details.
+        #[inline]
+        pub fn try_open_send_stream(
+            &mut self,
+        ) -> $crate::connection::Result<Option<$crate::stream::SendStream>> {
+            use s2n_quic_core::stream::StreamType;
+            use $crate::stream::SendStream;
+
+            let stream = self.0.try_open_stream(StreamType::Unidirectional)?;
+            Ok(stream.map(|stream| SendStream::new(stream.into())))
+        }
+
+        /// Returns `true` if opening a stream of the given type is blocked until the peer
+        /// issues additional stream credit with a MAX_STREAMS frame
+        ///
+        /// This can be used to tell if a pending call to open a stream is waiting on the peer.
+        /// A peer which never issues additional credit causes the call to wait indefinitely, so
+        /// applications may want to apply a timeout with [`Self::open_stream_with_timeout`]. The
+        /// [`Limits::with_stream_open_blocked_threshold`](crate::provider::limits::Limits::with_stream_open_blocked_threshold)
+        /// setting emits the `on_stream_open_blocked` event when the connection has been blocked
+        /// for longer than the configured duration.
+        #[inline]
+        pub fn is_open_stream_blocked(
+            &self,
+            stream_type: $crate::stream::Type,
+        ) -> $crate::connection::Result<bool> {
+            self.0.is_open_stream_blocked(stream_type)
+        }
+
+        /// Transmits all of the packets the connection is currently able to send
+        ///
+        /// This waits until the connection has written its pending frames to the endpoint's
+        /// transmission queue, to be sent on the socket. Unlike
+        /// [`SendStream::flush`](crate::stream::SendStream::flush), this doesn't wait for the peer
+        /// to acknowledge the data, so it can be used at latency-critical boundaries, such as the
+        /// end of a message, without waiting for a round trip.
+        ///
+        /// Packets are still subject to pacing. Data which is blocked by the congestion
+        /// controller, or which a stream is holding back with a
+        /// [coalescing delay](crate::stream::SendStream::set_coalescing_delay), is not waited
+        /// for.
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::stream::Result<()> {
+        /// #   let mut connection: s2n_quic::connection::Handle = todo!();
+        /// #   let mut stream: s2n_quic::stream::SendStream = todo!();
+        /// #
+        /// stream.send(bytes::Bytes::from_static(b"end of message")).await?;
+        /// // the message has been written to the socket, but may not have been received yet
+        /// connection.flush_transport().await?;
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub async fn flush_transport(&mut self) -> $crate::connection::Result<()> {
+            futures::future::poll_fn(|cx| self.poll_flush_transport(cx)).await
+        }
+
+        /// Polls transmitting all of the packets the connection is currently able to send
+        ///
+        /// See [`Self::flush_transport`] for more details.
+        #[inline]
+        pub fn poll_flush_transport(
+            &mut self,
+            cx: &mut core::task::Context,
+        ) -> core::task::Poll<$crate::connection::Result<()>> {
+            s2n_quic_core::task::waker::debug_assert_contract(cx, |cx| {
+                self.0.poll_flush_transport(cx)
+            })
+        }
+
+        /// Waits until the handshake has been confirmed by both endpoints
+        ///
+        /// Connections are handed to the application once the local endpoint completes the
+        /// handshake. Servers confirm the handshake at that point, while clients wait for the
+        /// server to acknowledge completion with a `HANDSHAKE_DONE` frame. Once this returns,
+        /// both peers are known to have completed the handshake, which allows applications to
+        /// gate work on it without inferring it from stream activity or events.
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::connection::Result<()> {
+        /// #   let mut connection: s2n_quic::connection::Handle = todo!();
+        /// #
+        /// connection.handshake_completed().await?;
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub async fn handshake_completed(&mut self) -> $crate::connection::Result<()> {
+            futures::future::poll_fn(|cx| self.poll_handshake_completed(cx)).await
+        }
+
+        /// Polls the handshake being confirmed by both endpoints
+        ///
+        /// See [`Self::handshake_completed`] for more details.
+        #[inline]
+        pub fn poll_handshake_completed(
+            &mut self,
+            cx: &mut core::task::Context,
+        ) -> core::task::Poll<$crate::connection::Result<()>> {
+            s2n_quic_core::task::waker::debug_assert_contract(cx, |cx| {
+                self.0.poll_handshake_completed(cx)
+            })
+        }
+
         /// Returns the local address that this connection is bound to.
+        ///
+        /// The address is taken from the active path rather than the endpoint's socket, so it
+        /// reflects the address the peer is sending to, including after the local address is
+        /// rebound. The addresses of all paths are available through [`Self::path_stats`].
+        ///
+        /// If the platform doesn't report the destination address of received packets, the IP
+        /// may be unspecified.
         #[inline]
         pub fn local_addr(&self) -> $crate::connection::Result<std::net::SocketAddr> {
             self.0.local_address().map(std::net::SocketAddr::from)
@@ -157,7 +408,52 @@ macro_rules! impl_handle_api {
             self.0.remote_address().map(std::net::SocketAddr::from)
         }
 
+        /// Returns the RTT, loss and ECN statistics for each path of the connection
+        ///
+        /// The connection maintains a path for each remote address it has communicated with,
+        /// including paths that are no longer active after a migration.
+        #[inline]
+        pub fn path_stats(&self) -> $crate::connection::Result<Vec<$crate::connection::PathStats>> {
+            self.0.path_stats()
+        }
+
+        /// Returns the recent MTU probing history of the active path
+        ///
+        /// The history includes each probe that was sent, acknowledged or lost, along with any
+        /// black holes that were detected, which can reveal MTU oscillation that isn't visible
+        /// from the current MTU alone.
+        ///
+        /// The history is only recorded if it was enabled with `with_history` on the
+        /// `mtu::Config` provided to the endpoint; otherwise the returned history is empty.
+        #[inline]
+        pub fn mtu_history(&self) -> $crate::connection::Result<$crate::connection::mtu::History> {
+            self.0.mtu_history()
+        }
+
         /// Returns the negotiated server name the connection is using.
+        ///
+        /// On the server, this is the server name (SNI) the client presented during the
+        /// handshake, which can be used to route connections for virtual hosts. On the client,
+        /// this is the server name the connection was opened with. `None` is returned if no
+        /// server name was presented or the handshake hasn't negotiated it yet.
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::connection::Result<()> {
+        /// #   let mut server: s2n_quic::Server = todo!();
+        /// #
+        /// while let Some(connection) = server.accept().await {
+        ///     match connection.server_name()?.as_deref() {
+        ///         Some("a.example.com") => { /* handle a.example.com */ }
+        ///         Some("b.example.com") => { /* handle b.example.com */ }
+        ///         _ => connection.close(0u8.into()),
+        ///     }
+        /// }
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
         #[inline]
         pub fn server_name(&self) -> $crate::connection::Result<Option<$crate::server::Name>> {
             self.0.server_name()
@@ -169,6 +465,16 @@ macro_rules! impl_handle_api {
             self.0.application_protocol()
         }
 
+        /// Returns the identifier of the shared compression dictionary negotiated for the
+        /// connection, if any
+        ///
+        /// Stream compression is only available if both endpoints advertised the same
+        /// dictionary identifier in their [`Limits`](crate::provider::limits::Limits).
+        #[inline]
+        pub fn compression_dictionary_id(&self) -> $crate::connection::Result<Option<u64>> {
+            self.0.compression_dictionary_id()
+        }
+
         /// Returns the internal identifier for the [`Connection`](`crate::Connection`)
         ///
         /// Note: This internal identifier is not the same as the connection ID included in packet
@@ -194,6 +500,124 @@ macro_rules! impl_handle_api {
             self.0.keep_alive(enabled)
         }
 
+        /// Changes the idle timeout of the connection
+        ///
+        /// The effective idle timeout is the smaller of `timeout` and the value negotiated with
+        /// the peer during the handshake, so it can only be shortened. If neither peer advertised
+        /// an idle timeout, `timeout` is used as is. This can be useful when
+        /// the connection transitions from interactive use to running in the background. As
+        /// with the negotiated value, the idle timeout is never shorter than three times the
+        /// current probe timeout.
+        ///
+        /// Returns an error if the handshake hasn't completed or if `timeout` is zero.
+        #[inline]
+        pub fn set_idle_timeout(
+            &mut self,
+            timeout: core::time::Duration,
+        ) -> $crate::connection::Result<()> {
+            self.0.set_idle_timeout(timeout)
+        }
+
+        /// Inserts a barrier across the sending side of the given streams
+        ///
+        /// Any data written to the streams after the barrier is held back until all of the data
+        /// written before it has been acknowledged by the peer on every stream in the set. This
+        /// can be used to order writes across streams, for example to ensure a set of objects is
+        /// received before the manifest that references them.
+        ///
+        /// Streams are identified by their [`id`](crate::stream::SendStream::id). Streams which
+        /// haven't been opened yet are held back from their first byte once they're opened.
+        /// Streams which were already closed, or which can't be sent on, are ignored.
+        #[inline]
+        pub fn insert_send_barrier<I: IntoIterator<Item = u64>>(
+            &mut self,
+            streams: I,
+        ) -> $crate::connection::Result<()> {
+            use s2n_quic_core::{stream::StreamId, varint::VarInt};
+
+            let streams: Vec<_> = streams
+                .into_iter()
+                .filter_map(|id| VarInt::new(id).ok().map(StreamId::from_varint))
+                .collect();
+
+            self.0.insert_send_barrier(&streams)
+        }
+
+        /// Requests the peer move to a new connection by sending a GOAWAY frame
+        ///
+        /// This can be used to coordinate connection draining with a load balancer, regardless
+        /// of the application protocol. The connection remains usable; the client is expected
+        /// to stop opening new streams and reconnect at a time of its choosing.
+        ///
+        /// Returns `false` if the peer did not advertise support for the GOAWAY frame with
+        /// [`Limits::with_go_away`](crate::provider::limits::Limits::with_go_away), in which
+        /// case the application should fall back to its own draining mechanism. Only servers
+        /// are able to send the frame.
+        #[inline]
+        pub fn go_away(&mut self) -> $crate::connection::Result<bool> {
+            self.0.go_away()
+        }
+
+        /// Returns `true` if the server has requested the client move to a new connection
+        ///
+        /// Applications can also be notified with the `on_go_away_received` event.
+        #[inline]
+        pub fn is_go_away_received(&self) -> $crate::connection::Result<bool> {
+            self.0.is_go_away_received()
+        }
+
+        /// Rotates the connection IDs used on the active path
+        ///
+        /// The next packets are sent to a connection ID the peer has provided but not yet been
+        /// used, and the previous connection ID is retired. The peer is also requested to retire
+        /// the connection IDs issued by the local endpoint, which are replaced with new ones.
+        /// Packets sent before and after the rotation are harder to correlate for on-path
+        /// observers. Rotations can also be performed automatically with
+        /// [`Limits::with_connection_id_rotation_policy`](crate::provider::limits::Limits::with_connection_id_rotation_policy).
+        ///
+        /// Returns `false` if the peer hasn't provided an unused connection ID or the handshake
+        /// hasn't been confirmed yet.
+        #[inline]
+        pub fn rotate_connection_id(&mut self) -> $crate::connection::Result<bool> {
+            self.0.rotate_connection_id()
+        }
+
+        /// Exports `len` bytes of keying material bound to the connection's TLS session
+        ///
+        /// The keying material is derived with the TLS exporter defined in
+        /// [RFC 5705](https://www.rfc-editor.org/rfc/rfc5705) and
+        /// [RFC 8446](https://www.rfc-editor.org/rfc/rfc8446#section-7.5) using the provided
+        /// `label` and `context`. Both endpoints derive the same value, which makes it
+        /// suitable for binding application-layer authentication to the connection.
+        ///
+        /// Exporting must be enabled with
+        /// [`Limits::with_keying_material_export`](crate::provider::limits::Limits::with_keying_material_export),
+        /// otherwise an error is returned. An error is also returned if the TLS provider
+        /// doesn't support exporting keying material.
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # fn test() -> s2n_quic::connection::Result<()> {
+        /// #   let handle: s2n_quic::connection::Handle = todo!();
+        /// #
+        /// let token = handle.export_keying_material(b"EXPORTER-my-app", b"session", 32)?;
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub fn export_keying_material(
+            &self,
+            label: &[u8],
+            context: &[u8],
+            len: usize,
+        ) -> $crate::connection::Result<Vec<u8>> {
+            let mut output = vec![0; len];
+            self.0.export_keying_material(label, context, &mut output)?;
+            Ok(output)
+        }
+
         /// Closes the Connection with the provided error code
         ///
         /// This will immediately terminate all outstanding streams.
@@ -215,6 +639,64 @@ macro_rules! impl_handle_api {
             self.0.close(error_code)
         }
 
+        /// Closes the Connection with the provided error code and reason phrase
+        ///
+        /// The reason is sent to the peer, which can read it with [`Self::close_reason`].
+        /// Reasons longer than
+        /// [`Reason::MAX_FRAME_LEN`](crate::connection::error::Reason::MAX_FRAME_LEN) bytes are
+        /// truncated. This leaves room for structured payloads, such as a JSON object, to be
+        /// encoded in the reason.
+        ///
+        /// The [`connection::Error::Application`](crate::connection::Error::Application) observed by
+        /// the peer only includes the first
+        /// [`Reason::MAX_LEN`](crate::connection::error::Reason::MAX_LEN) bytes of the reason.
+        ///
+        /// This will immediately terminate all outstanding streams.
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::connection::Result<()> {
+        /// #   let mut connection: s2n_quic::connection::Handle = todo!();
+        /// #
+        /// const MY_ERROR_CODE:u32 = 99;
+        /// connection.close_with_reason(MY_ERROR_CODE.into(), "database unavailable");
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub fn close_with_reason(&self, error_code: $crate::application::Error, reason: &str) {
+            self.0.close_with_reason(error_code, reason)
+        }
+
+        /// Returns the reason phrase of the CONNECTION_CLOSE frame which closed the connection
+        ///
+        /// This is either the reason sent by the peer, for any kind of close including transport
+        /// errors and closes without an error, or the reason provided to
+        /// [`Self::close_with_reason`]. `None` is returned while the connection is open or if the
+        /// close didn't include a reason.
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::connection::Result<()> {
+        /// #   let mut connection: s2n_quic::Connection = todo!();
+        /// #
+        /// if let Err(error) = connection.accept().await {
+        ///     if let Some(reason) = connection.close_reason()? {
+        ///         println!("connection closed with {error}: {reason:?}");
+        ///     }
+        /// }
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub fn close_reason(&self) -> $crate::connection::Result<Option<::bytes::Bytes>> {
+            self.0.close_reason()
+        }
+
         /// API for querying the connection's
         /// [`Subscriber::ConnectionContext`](crate::provider::event::Subscriber::ConnectionContext).
         ///
@@ -232,6 +714,10 @@ macro_rules! impl_handle_api {
         /// Given that it is possible to compose Subscriber, which can have different
         /// ConnectionContext types, this function traverses all Subscribers, executes
         /// and short-circuiting on the first match.
+        /// This includes Subscribers composed in tuples as well as those wrapped by
+        /// other Subscribers, such as the
+        /// [`Ledger`](crate::provider::event::accounting::Ledger) of an
+        /// [`accounting::Subscriber`](crate::provider::event::accounting::Subscriber).
         ///
         /// # Examples
         ///
@@ -386,6 +872,25 @@ macro_rules! impl_handle_api {
             query.into()
         }
 
+        /// Returns a copy of the connection's event context of the given type
+        ///
+        /// This is a typed shorthand for [`Self::query_event_context`], which makes it easy to
+        /// look up contexts when multiple Subscribers are composed. As with
+        /// [`Self::query_event_context`], all of the configured Subscribers are searched and the
+        /// first context of the requested type is returned.
+        ///
+        /// ```ignore
+        /// let ledger = connection.event_context::<MyLedger>()?;
+        /// ```
+        pub fn event_context<EventContext>(
+            &self,
+        ) -> core::result::Result<EventContext, s2n_quic_core::query::Error>
+        where
+            EventContext: 'static + Clone,
+        {
+            self.query_event_context(|event_context: &EventContext| event_context.clone())
+        }
+
         /// API for querying the connection's datagram endpoint.
         ///
         ///  Provides mutable access to `Sender` or `Receiver`.
@@ -413,6 +918,37 @@ macro_rules! impl_handle_api {
 
             query.into()
         }
+
+        /// Enqueues a batch of unreliable datagrams on the default datagram sender
+        ///
+        /// All of the datagrams are enqueued while holding the connection lock once and are
+        /// written back-to-back without ceding packet space to stream data. This packs multiple
+        /// DATAGRAM frames into each packet, so the packets are the same size and can be sent as
+        /// segments of a single GSO datagram.
+        ///
+        /// Returns the number of datagrams that were enqueued. See
+        /// [`Sender::send_datagram_batch`](s2n_quic_core::datagram::default::Sender::send_datagram_batch)
+        /// for details.
+        ///
+        /// ```ignore
+        /// let datagrams = frames.iter().map(|frame| frame.encode());
+        /// let sent = connection.send_datagram_batch(datagrams)??;
+        /// ```
+        #[inline]
+        pub fn send_datagram_batch<I>(
+            &self,
+            datagrams: I,
+        ) -> core::result::Result<
+            core::result::Result<usize, s2n_quic_core::datagram::default::DatagramError>,
+            s2n_quic_core::query::Error,
+        >
+        where
+            I: IntoIterator<Item = ::bytes::Bytes>,
+        {
+            self.datagram_mut(|sender: &mut s2n_quic_core::datagram::default::Sender| {
+                sender.send_datagram_batch(datagrams)
+            })
+        }
     };
 }
 
@@ -422,3 +958,60 @@ pub struct Handle(pub(crate) s2n_quic_transport::connection::Connection);
 impl Handle {
     impl_handle_api!(|handle, call| call!(handle));
 }
+
+/// Cancels a pending open request if the future opening the stream is dropped before it
+/// completes
+///
+/// Otherwise the request would still be considered blocked on the peer's stream limits.
+pub(crate) struct OpenGuard<'a> {
+    connection: &'a mut s2n_quic_transport::connection::Connection,
+    stream_type: s2n_quic_core::stream::StreamType,
+    timeout: Option<core::time::Duration>,
+    is_pending: bool,
+}
+
+impl<'a> OpenGuard<'a> {
+    #[inline]
+    pub(crate) fn new(
+        connection: &'a mut s2n_quic_transport::connection::Connection,
+        stream_type: s2n_quic_core::stream::StreamType,
+    ) -> Self {
+        Self {
+            connection,
+            stream_type,
+            timeout: None,
+            is_pending: false,
+        }
+    }
+
+    /// Fails the request if it waits for stream capacity for longer than the `timeout`
+    #[inline]
+    pub(crate) fn with_timeout(mut self, timeout: core::time::Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    #[inline]
+    pub(crate) fn poll_open(
+        &mut self,
+        cx: &mut core::task::Context,
+    ) -> core::task::Poll<super::Result<s2n_quic_transport::stream::Stream>> {
+        let stream_type = self.stream_type;
+        let timeout = self.timeout;
+        let poll = s2n_quic_core::task::waker::debug_assert_contract(cx, |cx| {
+            self.connection
+                .poll_open_stream_with_timeout(stream_type, timeout, cx)
+        });
+        self.is_pending = poll.is_pending();
+        poll
+    }
+}
+
+impl Drop for OpenGuard<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        if self.is_pending {
+            self.connection.cancel_open_stream(self.stream_type);
+        }
+    }
+}
@@ -22,6 +22,14 @@
 //! that multiple servers handle address tokens, this provider should not be used. Instead, a custom
 //! implementation of [`provider::address_token::Format`] should be specified.
 //!
+//! The provider isn't available on wasm targets, where address tokens are disabled by default.
+//!
+//! ### `provider-conformance`
+//!
+//! Enables the [`provider::conformance`] test suites, which validate custom implementations of
+//! the congestion controller, connection ID and address token providers against the contract
+//! the endpoint relies on. The suites are intended to be called from a provider's own tests.
+//!
 //! ### `provider-event-tracing`
 //!
 //! Enables event integration with [`tracing`](https://docs.rs/tracing). The
@@ -36,6 +44,9 @@
 //! Enables platform detection for the recommended implementation of TLS. Currently, this uses
 //! [`s2n-tls`][s2n-tls] on unix-like platforms and [`rustls`][rustls] on everything else.
 //!
+//! Neither implementation supports wasm targets, so endpoints on those targets need to be
+//! configured with their own TLS provider.
+//!
 //! ### `provider-tls-rustls`
 //!
 //! Enables the [`rustls`][rustls] TLS provider. The provider will be available at
@@ -122,7 +133,7 @@ pub mod server;
 pub mod stream;
 
 pub mod application {
-    pub use s2n_quic_core::application::Error;
+    pub use s2n_quic_core::application::{registry, Error};
 }
 
 pub use client::Client;
@@ -10,20 +10,66 @@ mod macros;
 pub mod address_token;
 pub mod congestion_controller;
 pub mod connection_id;
+pub mod dns;
 pub mod endpoint_limits;
 pub mod event;
 pub mod io;
 pub mod limits;
 pub mod mtu;
+pub mod runtime;
 pub mod stateless_reset_token;
 pub mod tls;
 
+#[cfg(any(test, feature = "provider-conformance"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "provider-conformance")))]
+pub mod conformance;
+
 // These providers are not currently exposed to applications
 #[allow(dead_code)]
 pub(crate) mod path_migration;
 #[allow(dead_code)]
 pub(crate) mod sync;
 
+cfg_if!(
+    if #[cfg(any(test, feature = "unstable-provider-accept-filter"))] {
+        #[cfg_attr(docsrs, doc(cfg(feature = "unstable-provider-accept-filter")))]
+        pub mod accept_filter;
+    } else {
+        #[allow(dead_code)]
+        pub(crate) mod accept_filter;
+    }
+);
+
+cfg_if!(
+    if #[cfg(any(test, feature = "unstable-provider-address-attestation"))] {
+        #[cfg_attr(docsrs, doc(cfg(feature = "unstable-provider-address-attestation")))]
+        pub mod address_attestation;
+    } else {
+        #[allow(dead_code)]
+        pub(crate) mod address_attestation;
+    }
+);
+
+cfg_if!(
+    if #[cfg(any(test, feature = "unstable-provider-compatibility"))] {
+        #[cfg_attr(docsrs, doc(cfg(feature = "unstable-provider-compatibility")))]
+        pub mod compatibility;
+    } else {
+        #[allow(dead_code)]
+        pub(crate) mod compatibility;
+    }
+);
+
+cfg_if!(
+    if #[cfg(any(test, feature = "unstable-provider-path-cache"))] {
+        #[cfg_attr(docsrs, doc(cfg(feature = "unstable-provider-path-cache")))]
+        pub mod path_cache;
+    } else {
+        #[allow(dead_code)]
+        pub(crate) mod path_cache;
+    }
+);
+
 cfg_if!(
     if #[cfg(any(test, feature = "unstable-provider-connection-close-formatter"))] {
         #[cfg_attr(docsrs, doc(cfg(feature = "unstable-provider-connection-close-formatter")))]
@@ -0,0 +1,161 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Allows servers to reject connection attempts before performing any handshake work
+//!
+//! The filter is invoked for every Initial packet which would create a new connection with
+//! the client's address and connection ID and, when the ClientHello fits in the packet, the
+//! server name and offered application protocols. Rejected attempts can be dropped, deferred
+//! with a Retry packet, or closed with a transport error code. Allowed attempts can override some
+//! of the server's [`Limits`](crate::provider::limits::Limits) for the connection, e.g. to give
+//! trusted peers larger limits.
+//!
+//! Applications can also make these decisions with a closure by using [`from_fn`].
+
+pub use s2n_quic_core::endpoint::accept_filter::{
+    from_fn, AllowAll, ApplicationProtocols, ConnectionAttempt, Filter, FromFn, Outcome, Overrides,
+};
+use std::net::{IpAddr, SocketAddr};
+
+pub trait Provider: 'static {
+    type Filter: 'static + Filter;
+    type Error: core::fmt::Display + Send + Sync;
+
+    /// Starts the accept filter provider
+    fn start(self) -> Result<Self::Filter, Self::Error>;
+}
+
+pub use AllowAll as Default;
+
+impl_provider_utils!();
+
+impl<T: 'static + Filter> Provider for T {
+    type Filter = T;
+    type Error = core::convert::Infallible;
+
+    fn start(self) -> Result<Self::Filter, Self::Error> {
+        Ok(self)
+    }
+}
+
+/// A filter which rejects connection attempts from a list of IP prefixes
+///
+/// # Examples
+///
+/// ```rust
+/// use s2n_quic::provider::accept_filter::{Denylist, Outcome};
+///
+/// let denylist = Denylist::default()
+///     .with_prefix([192, 0, 2, 0], 24)
+///     .with_prefix([0x2001, 0xdb8, 0, 0, 0, 0, 0, 0], 32)
+///     .with_outcome(Outcome::close());
+/// ```
+#[derive(Clone, Debug)]
+pub struct Denylist {
+    prefixes: Vec<(IpAddr, u8)>,
+    outcome: Outcome,
+}
+
+impl core::default::Default for Denylist {
+    fn default() -> Self {
+        Self {
+            prefixes: Vec::new(),
+            outcome: Outcome::drop(),
+        }
+    }
+}
+
+impl Denylist {
+    /// Rejects peers with an address in the prefix
+    ///
+    /// The prefix length is clamped to the length of the address.
+    #[must_use]
+    pub fn with_prefix<A: Into<IpAddr>>(mut self, ip: A, prefix_len: u8) -> Self {
+        let ip = ip.into();
+        let max_len = if ip.is_ipv4() { 32 } else { 128 };
+        self.prefixes.push((ip, prefix_len.min(max_len)));
+        self
+    }
+
+    /// Sets the outcome for rejected peers
+    ///
+    /// Defaults to [`Outcome::drop`].
+    #[must_use]
+    pub fn with_outcome(mut self, outcome: Outcome) -> Self {
+        self.outcome = outcome;
+        self
+    }
+
+    fn contains(&self, ip: IpAddr) -> bool {
+        // treat IPv4-mapped IPv6 addresses the same as IPv4 addresses
+        let ip = match ip {
+            IpAddr::V6(v6) => v6.to_ipv4_mapped().map_or(ip, IpAddr::V4),
+            ip => ip,
+        };
+
+        self.prefixes
+            .iter()
+            .any(|(prefix, len)| match (prefix, ip) {
+                (IpAddr::V4(prefix), IpAddr::V4(ip)) => {
+                    matches_prefix(u32::from(*prefix), u32::from(ip), *len, 32)
+                }
+                (IpAddr::V6(prefix), IpAddr::V6(ip)) => {
+                    matches_prefix(u128::from(*prefix), u128::from(ip), *len, 128)
+                }
+                _ => false,
+            })
+    }
+}
+
+#[inline]
+fn matches_prefix<T>(prefix: T, ip: T, len: u8, bits: u8) -> bool
+where
+    T: Copy + PartialEq + core::ops::Shr<u8, Output = T>,
+{
+    if len == 0 {
+        return true;
+    }
+
+    let shift = bits - len;
+    prefix >> shift == ip >> shift
+}
+
+impl Filter for Denylist {
+    fn on_connection_attempt(&mut self, attempt: &ConnectionAttempt) -> Outcome {
+        let ip = SocketAddr::from(&attempt.remote_address).ip();
+
+        if self.contains(ip) {
+            self.outcome
+        } else {
+            Outcome::allow()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::net::Ipv6Addr;
+
+    #[test]
+    fn denylist_test() {
+        let denylist = Denylist::default()
+            .with_prefix([192, 0, 2, 0], 24)
+            .with_prefix([198, 51, 100, 7], 32)
+            .with_prefix([0x2001, 0xdb8, 0, 0, 0, 0, 0, 0], 32);
+
+        assert!(denylist.contains([192, 0, 2, 200].into()));
+        assert!(!denylist.contains([192, 0, 3, 1].into()));
+        assert!(denylist.contains([198, 51, 100, 7].into()));
+        assert!(!denylist.contains([198, 51, 100, 8].into()));
+        assert!(denylist.contains([0x2001, 0xdb8, 1, 0, 0, 0, 0, 1].into()));
+        assert!(!denylist.contains([0x2001, 0xdb9, 0, 0, 0, 0, 0, 1].into()));
+
+        let mapped: Ipv6Addr = "::ffff:192.0.2.1".parse().unwrap();
+        assert!(denylist.contains(mapped.into()));
+
+        let everything = Denylist::default().with_prefix([0, 0, 0, 0], 0);
+        assert!(everything.contains([203, 0, 113, 1].into()));
+        assert!(!everything.contains(Ipv6Addr::LOCALHOST.into()));
+    }
+}
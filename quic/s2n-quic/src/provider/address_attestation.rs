@@ -0,0 +1,32 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Allows servers to validate a client's address with an out-of-band attestation
+//!
+//! Clients present the attestation in the token field of their Initial packets with
+//! [`Connect::with_token`](crate::client::Connect::with_token). Attested clients are considered
+//! to have validated their address so the server doesn't need to send a Retry packet, saving a
+//! round trip.
+
+pub use s2n_quic_core::endpoint::address_attestation::{Attestation, Disabled, Validator};
+
+pub trait Provider: 'static {
+    type Validator: 'static + Validator;
+    type Error: core::fmt::Display + Send + Sync;
+
+    /// Starts the address attestation provider
+    fn start(self) -> Result<Self::Validator, Self::Error>;
+}
+
+pub use Disabled as Default;
+
+impl_provider_utils!();
+
+impl<T: 'static + Validator> Provider for T {
+    type Validator = T;
+    type Error = core::convert::Infallible;
+
+    fn start(self) -> Result<Self::Validator, Self::Error> {
+        Ok(self)
+    }
+}
@@ -506,6 +506,17 @@ mod tests {
         }
     }
 
+    #[test]
+    fn conformance_test() {
+        let clock = Arc::new(time::testing::MockClock::new());
+        time::testing::set_local_clock(clock.clone());
+
+        let mut format = get_test_format();
+        crate::provider::conformance::address_token::check(&mut format, || {
+            clock.adjust_by(TEST_KEY_ROTATION_PERIOD * 2)
+        });
+    }
+
     #[test]
     fn test_valid_retry_tokens() {
         let clock = Arc::new(time::testing::MockClock::new());
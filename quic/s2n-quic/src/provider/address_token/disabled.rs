@@ -0,0 +1,84 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Provider which disables Address Validation tokens
+//!
+//! No tokens are issued and every token received from a client is rejected. Since a Retry
+//! packet can't be sent without a token, clients are never asked to validate their address.
+//!
+//! This is the default on targets where the cryptographic primitives needed by the
+//! [`default`](super::default) provider aren't available, such as `wasm32-wasi`.
+
+use s2n_quic_core::connection;
+
+#[derive(Debug, Default)]
+pub struct Provider(());
+
+impl super::Provider for Provider {
+    type Format = Format;
+    type Error = core::convert::Infallible;
+
+    fn start(self) -> Result<Self::Format, Self::Error> {
+        Ok(Format(()))
+    }
+}
+
+#[derive(Debug)]
+pub struct Format(());
+
+impl super::Format for Format {
+    const TOKEN_LEN: usize = 0;
+
+    fn generate_new_token(
+        &mut self,
+        _context: &mut super::Context<'_>,
+        _source_connection_id: &connection::LocalId,
+        _output_buffer: &mut [u8],
+    ) -> Option<()> {
+        None
+    }
+
+    fn generate_retry_token(
+        &mut self,
+        _context: &mut super::Context<'_>,
+        _original_destination_connection_id: &connection::InitialId,
+        _output_buffer: &mut [u8],
+    ) -> Option<()> {
+        None
+    }
+
+    fn validate_token(
+        &mut self,
+        _context: &mut super::Context<'_>,
+        _token: &[u8],
+    ) -> Option<connection::InitialId> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::address_token::Provider as _;
+    use s2n_quic_core::{
+        inet::SocketAddress,
+        random,
+        token::{Context, Format as _},
+    };
+
+    #[test]
+    fn disabled_test() {
+        let mut format = Provider::default().start().unwrap();
+        let peer_conn_id = connection::PeerId::try_from_bytes(&[2, 4, 6, 8, 10]).unwrap();
+        let orig_conn_id =
+            connection::InitialId::try_from_bytes(&[0, 1, 2, 3, 4, 5, 6, 7]).unwrap();
+        let addr = SocketAddress::default();
+        let mut random = random::testing::Generator(5);
+        let mut context = Context::new(&addr, &peer_conn_id, &mut random);
+
+        assert!(format
+            .generate_retry_token(&mut context, &orig_conn_id, &mut [])
+            .is_none());
+        assert_eq!(format.validate_token(&mut context, &[1, 2, 3]), None);
+    }
+}
@@ -13,8 +13,14 @@ pub trait Provider: 'static {
     fn start(self) -> Result<Self::Format, Self::Error>;
 }
 
+// the default provider relies on `s2n-quic-crypto`, which doesn't support the wasm targets
+#[cfg(not(target_family = "wasm"))]
 pub mod default;
+pub mod disabled;
 
+#[cfg(not(target_family = "wasm"))]
 pub use default::Provider as Default;
+#[cfg(target_family = "wasm")]
+pub use disabled::Provider as Default;
 
 impl_provider_utils!();
@@ -0,0 +1,152 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Allows applications to relax selected strictness checks for peers which are known to be
+//! nonconformant
+//!
+//! By default, all of the checks are enforced. Every relaxation that is applied to a connection
+//! emits a `compatibility_relaxation_applied` event.
+
+pub use s2n_quic_core::connection::compatibility::{Peer, Policy, Relaxations, Strict};
+use std::net::{IpAddr, SocketAddr};
+
+pub trait Provider: 'static {
+    type Policy: 'static + Policy;
+    type Error: 'static + core::fmt::Display + Send + Sync;
+
+    fn start(self) -> Result<Self::Policy, Self::Error>;
+}
+
+pub use Strict as Default;
+
+impl_provider_utils!();
+
+impl<T: 'static + Policy> Provider for T {
+    type Policy = T;
+    type Error = core::convert::Infallible;
+
+    fn start(self) -> Result<Self::Policy, Self::Error> {
+        Ok(self)
+    }
+}
+
+/// A policy which selects relaxations by matching peers against a list of rules
+///
+/// The relaxations of every matching rule are combined.
+///
+/// # Examples
+///
+/// ```rust
+/// use s2n_quic::provider::compatibility::{Relaxations, Rules};
+///
+/// let relaxed = Relaxations::STRICT.with_missing_initial_source_connection_id(true);
+///
+/// let rules = Rules::default()
+///     .with_server_name("*.legacy.example.com", relaxed)
+///     .with_application_protocol("hq-29", relaxed);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Rules {
+    rules: Vec<(Rule, Relaxations)>,
+}
+
+#[derive(Clone, Debug)]
+enum Rule {
+    ServerName(String),
+    ApplicationProtocol(Vec<u8>),
+    Ip(IpAddr),
+}
+
+impl Rules {
+    /// Applies the relaxations to peers with a matching server name
+    ///
+    /// The pattern can either be an exact name or a wildcard of the form `*.example.com`.
+    #[must_use]
+    pub fn with_server_name<P: Into<String>>(
+        mut self,
+        pattern: P,
+        relaxations: Relaxations,
+    ) -> Self {
+        self.rules
+            .push((Rule::ServerName(pattern.into()), relaxations));
+        self
+    }
+
+    /// Applies the relaxations to peers which negotiated the application protocol
+    #[must_use]
+    pub fn with_application_protocol<P: AsRef<[u8]>>(
+        mut self,
+        protocol: P,
+        relaxations: Relaxations,
+    ) -> Self {
+        self.rules.push((
+            Rule::ApplicationProtocol(protocol.as_ref().to_vec()),
+            relaxations,
+        ));
+        self
+    }
+
+    /// Applies the relaxations to peers with the IP address
+    #[must_use]
+    pub fn with_ip<A: Into<IpAddr>>(mut self, ip: A, relaxations: Relaxations) -> Self {
+        self.rules.push((Rule::Ip(ip.into()), relaxations));
+        self
+    }
+}
+
+impl Policy for Rules {
+    fn on_peer(&self, peer: &Peer) -> Relaxations {
+        let mut relaxations = Relaxations::STRICT;
+
+        for (rule, rule_relaxations) in &self.rules {
+            let matches = match rule {
+                Rule::ServerName(pattern) => peer
+                    .server_name
+                    .map_or(false, |server_name| server_name.matches(pattern)),
+                Rule::ApplicationProtocol(protocol) => peer.application_protocol == &protocol[..],
+                Rule::Ip(ip) => SocketAddr::from(&peer.remote_address).ip() == *ip,
+            };
+
+            if matches {
+                relaxations = relaxations.union(*rule_relaxations);
+            }
+        }
+
+        relaxations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use s2n_quic_core::{application::ServerName, inet::SocketAddress};
+
+    #[test]
+    fn rules_test() {
+        let isid = Relaxations::STRICT.with_missing_initial_source_connection_id(true);
+        let odcid = Relaxations::STRICT.with_missing_original_destination_connection_id(true);
+
+        let legacy_ip: IpAddr = [192, 0, 2, 1].into();
+        let rules = Rules::default()
+            .with_server_name("*.legacy.example.com", isid)
+            .with_application_protocol("hq-29", odcid)
+            .with_ip(legacy_ip, odcid);
+
+        let addr: SocketAddress = SocketAddr::new([198, 51, 100, 1].into(), 443).into();
+        let legacy_addr: SocketAddress = SocketAddr::new(legacy_ip, 443).into();
+        let legacy_name = ServerName::from("device.legacy.example.com");
+        let other_name = ServerName::from("example.com");
+
+        let relaxations = rules.on_peer(&Peer::new(&addr, Some(&other_name), b"h3"));
+        assert!(relaxations.is_strict());
+
+        let relaxations = rules.on_peer(&Peer::new(&addr, Some(&legacy_name), b"h3"));
+        assert_eq!(relaxations, isid);
+
+        let relaxations = rules.on_peer(&Peer::new(&addr, Some(&legacy_name), b"hq-29"));
+        assert_eq!(relaxations, isid.union(odcid));
+
+        let relaxations = rules.on_peer(&Peer::new(&legacy_addr, None, b"h3"));
+        assert_eq!(relaxations, odcid);
+    }
+}
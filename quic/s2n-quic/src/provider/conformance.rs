@@ -0,0 +1,37 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Test suites for validating custom provider implementations
+//!
+//! Each suite drives a provider through the same trait methods the endpoint calls and panics
+//! with a description of the violated requirement. The inputs generated by the suites are
+//! derived from fixed seeds, so a failure is reproducible as long as the provider itself is
+//! deterministic. The suites can be run from a regular `#[test]` function in the provider's own
+//! CI.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use s2n_quic::provider::{congestion_controller, conformance, connection_id};
+//!
+//! conformance::congestion_controller::check(&mut congestion_controller::Cubic::default());
+//! conformance::connection_id::check(&mut connection_id::default::Format::default());
+//! ```
+
+/// Checks for implementations of [`congestion_controller::Endpoint`](super::congestion_controller::Endpoint)
+pub mod congestion_controller {
+    pub use s2n_quic_core::conformance::congestion_controller::{
+        bytes_in_flight, check, initial_window, minimum_window, persistent_congestion,
+        random_operations,
+    };
+}
+
+/// Checks for implementations of [`connection_id::Format`](super::connection_id::Format)
+pub mod connection_id {
+    pub use s2n_quic_core::conformance::connection_id::{check, length, lifetime, uniqueness};
+}
+
+/// Checks for implementations of [`address_token::Format`](super::address_token::Format)
+pub mod address_token {
+    pub use s2n_quic_core::conformance::token::{check, expiry, retry_token};
+}
@@ -14,6 +14,13 @@ pub trait Provider: 'static {
 
 pub use default::Provider as Default;
 
+#[cfg(all(
+    feature = "provider-connection-id-authenticated",
+    not(target_family = "wasm")
+))]
+#[cfg_attr(docsrs, doc(cfg(feature = "provider-connection-id-authenticated")))]
+pub mod authenticated;
+
 impl_provider_utils!();
 
 impl<T: 'static + Format> Provider for T {
@@ -59,6 +66,7 @@ pub mod default {
         len: usize,
         lifetime: Option<Duration>,
         rotate_handshake_connection_id: bool,
+        shard: Option<u8>,
     }
 
     impl Default for Format {
@@ -67,6 +75,7 @@ pub mod default {
                 len: DEFAULT_LEN,
                 lifetime: None,
                 rotate_handshake_connection_id: true,
+                shard: None,
             }
         }
     }
@@ -76,6 +85,17 @@ pub mod default {
         pub fn builder() -> Builder {
             Builder::default()
         }
+
+        /// Generates a connection Id with the bytes produced by `rng`
+        fn generate_with<R: RngCore>(&self, rng: &mut R) -> connection::LocalId {
+            let mut id = [0u8; connection::id::MAX_LEN];
+            let id = &mut id[..self.len];
+            rng.fill_bytes(id);
+            if let (Some(shard), Some(first)) = (self.shard, id.first_mut()) {
+                *first = shard;
+            }
+            (&*id).try_into().expect("length already checked")
+        }
     }
 
     /// A builder for [`Format`] providers
@@ -84,6 +104,7 @@ pub mod default {
         len: usize,
         lifetime: Option<Duration>,
         rotate_handshake_connection_id: bool,
+        shard: Option<u8>,
     }
 
     impl Default for Builder {
@@ -92,14 +113,23 @@ pub mod default {
                 len: DEFAULT_LEN,
                 lifetime: None,
                 rotate_handshake_connection_id: true,
+                shard: None,
             }
         }
     }
 
     impl Builder {
         /// Sets the length of the generated connection Id
+        ///
+        /// A length of `0` configures the endpoint to use zero-length connection Ids. This is
+        /// only suitable for point-to-point deployments where the endpoint has a single active
+        /// connection on its socket, as packets can no longer be routed by connection Id and
+        /// the connection cannot be migrated by the peer. The endpoint will refuse to open
+        /// additional connections while a zero-length connection Id is in use.
         pub fn with_len(mut self, len: usize) -> Result<Self, connection::id::Error> {
-            if !(connection::LocalId::MIN_LEN..=connection::id::MAX_LEN).contains(&len) {
+            let is_empty = len == 0;
+            if !is_empty && !(connection::LocalId::MIN_LEN..=connection::id::MAX_LEN).contains(&len)
+            {
                 return Err(connection::id::Error::InvalidLength);
             }
             self.len = len;
@@ -129,22 +159,30 @@ pub mod default {
             Ok(self)
         }
 
+        /// Sets the index of the shard which issues the connection Ids (default: disabled)
+        ///
+        /// The first byte of each generated connection Id is set to `index`, which allows packets
+        /// to be steered to the endpoint which issued the Id, as done by the `server::shard`
+        /// module on Linux.
+        pub fn with_shard(mut self, index: u8) -> Result<Self, core::convert::Infallible> {
+            self.shard = Some(index);
+            Ok(self)
+        }
+
         /// Builds the [`Format`] into a provider
         pub fn build(self) -> Result<Format, core::convert::Infallible> {
             Ok(Format {
                 len: self.len,
                 lifetime: self.lifetime,
                 rotate_handshake_connection_id: self.rotate_handshake_connection_id,
+                shard: self.shard,
             })
         }
     }
 
     impl Generator for Format {
         fn generate(&mut self, _connection_info: &ConnectionInfo) -> connection::LocalId {
-            let mut id = [0u8; connection::id::MAX_LEN];
-            let id = &mut id[..self.len];
-            rand::thread_rng().fill_bytes(id);
-            (&*id).try_into().expect("length already checked")
+            self.generate_with(&mut rand::thread_rng())
         }
 
         fn lifetime(&self) -> Option<Duration> {
@@ -169,6 +207,46 @@ pub mod default {
     #[cfg(test)]
     mod tests {
         use super::*;
+        use rand_chacha::ChaCha8Rng;
+
+        /// Generates Ids with a seeded RNG so the conformance checks are reproducible
+        #[derive(Debug)]
+        struct SeededFormat {
+            format: Format,
+            rng: ChaCha8Rng,
+        }
+
+        impl Generator for SeededFormat {
+            fn generate(&mut self, _connection_info: &ConnectionInfo) -> connection::LocalId {
+                self.format.generate_with(&mut self.rng)
+            }
+
+            fn lifetime(&self) -> Option<Duration> {
+                self.format.lifetime()
+            }
+
+            fn rotate_handshake_connection_id(&self) -> bool {
+                self.format.rotate_handshake_connection_id()
+            }
+        }
+
+        impl Validator for SeededFormat {
+            fn validate(&self, connection_info: &ConnectionInfo, buffer: &[u8]) -> Option<usize> {
+                self.format.validate(connection_info, buffer)
+            }
+        }
+
+        #[test]
+        #[cfg_attr(miri, ignore)] // This test is too expensive for miri to complete in a reasonable amount of time
+        fn conformance_test() {
+            for len in connection::LocalId::MIN_LEN..=connection::id::MAX_LEN {
+                let mut format = SeededFormat {
+                    format: Format::builder().with_len(len).unwrap().build().unwrap(),
+                    rng: ChaCha8Rng::seed_from_u64(len as u64),
+                };
+                crate::provider::conformance::connection_id::check(&mut format);
+            }
+        }
 
         #[test]
         fn generator_test() {
@@ -206,6 +284,11 @@ pub mod default {
                     .err()
             );
 
+            let mut format = Format::builder().with_len(0).unwrap().build().unwrap();
+            let id = format.generate(&connection_info);
+            assert!(id.is_empty());
+            assert_eq!(format.validate(&connection_info, &[1, 2, 3]), Some(0));
+
             let lifetime = Duration::from_secs(1000);
             let format = Format::builder()
                 .with_lifetime(lifetime)
@@ -245,6 +328,13 @@ pub mod default {
                 .build()
                 .unwrap();
             assert!(!format.rotate_handshake_connection_id());
+
+            let mut format = Format::builder().with_shard(7).unwrap().build().unwrap();
+            for _ in 0..10 {
+                let id = format.generate(&connection_info);
+                assert_eq!(id.as_ref()[0], 7);
+                assert_eq!(id.len(), DEFAULT_LEN);
+            }
         }
     }
 }
@@ -0,0 +1,390 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A connection ID format which authenticates each ID with a keyed MAC
+//!
+//! Each generated connection ID has the following layout:
+//!
+//! ```text
+//! Connection ID {
+//!   Routing Data (..),
+//!   Nonce (..),
+//!   Tag (..),
+//! }
+//! ```
+//!
+//! The tag is a truncated HMAC-SHA256 over the routing data and nonce. Packets with short headers
+//! whose destination connection ID doesn't carry a valid tag are dropped while decoding, before
+//! the endpoint looks up the connection. This makes it expensive for an off-path attacker to
+//! produce packets which reach the connection map.
+//!
+//! Endpoints sharing traffic, such as several servers behind a load balancer, should be
+//! configured with the same key.
+
+use core::{
+    convert::{Infallible, TryInto},
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+use rand::prelude::*;
+use s2n_quic_core::connection::{
+    self,
+    id::{ConnectionInfo, Generator, Validator},
+};
+use s2n_quic_crypto::{constant_time, hmac};
+use std::sync::Arc;
+
+/// The default number of random bytes in each connection ID
+const DEFAULT_NONCE_LEN: usize = 8;
+/// The default number of tag bytes in each connection ID
+const DEFAULT_TAG_LEN: usize = 8;
+/// Fewer tag bytes than this would make forging a valid ID too easy
+const MIN_TAG_LEN: usize = 4;
+/// Fewer nonce bytes than this would risk generating duplicate IDs
+const MIN_NONCE_LEN: usize = 4;
+
+#[derive(Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// The combined length of the routing data, nonce and tag is not a valid connection ID length
+    InvalidLength,
+    /// The tag is shorter than 4 bytes or longer than 32 bytes
+    InvalidTagLength,
+    /// The nonce is shorter than 4 bytes
+    InvalidNonceLength,
+    /// The key is empty
+    InvalidKey,
+    /// The lifetime is outside of the supported range
+    InvalidLifetime,
+}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let message = match self {
+            Self::InvalidLength => "the connection ID length is invalid",
+            Self::InvalidTagLength => "the tag length must be between 4 and 32 bytes",
+            Self::InvalidNonceLength => "the nonce length must be at least 4 bytes",
+            Self::InvalidKey => "the key must not be empty",
+            Self::InvalidLifetime => "the connection ID lifetime is invalid",
+        };
+        f.write_str(message)
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Counters for connection IDs checked by the [`Format`]
+#[derive(Debug, Default)]
+pub struct Stats {
+    accepted: AtomicU64,
+    rejected: AtomicU64,
+}
+
+impl Stats {
+    /// The number of connection IDs with a valid tag
+    pub fn accepted(&self) -> u64 {
+        self.accepted.load(Ordering::Relaxed)
+    }
+
+    /// The number of connection IDs which were rejected, causing the packet to be dropped
+    pub fn rejected(&self) -> u64 {
+        self.rejected.load(Ordering::Relaxed)
+    }
+}
+
+/// A builder for [`Format`] providers
+#[derive(Debug)]
+pub struct Builder {
+    key: Option<Vec<u8>>,
+    routing_data: Vec<u8>,
+    nonce_len: usize,
+    tag_len: usize,
+    lifetime: Option<Duration>,
+    rotate_handshake_connection_id: bool,
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self {
+            key: None,
+            routing_data: Vec::new(),
+            nonce_len: DEFAULT_NONCE_LEN,
+            tag_len: DEFAULT_TAG_LEN,
+            lifetime: None,
+            rotate_handshake_connection_id: true,
+        }
+    }
+}
+
+impl Builder {
+    /// Sets the key used to authenticate connection IDs
+    ///
+    /// If no key is set, a random key is generated, which is only suitable for a single endpoint.
+    pub fn with_key(mut self, key: &[u8]) -> Result<Self, Error> {
+        if key.is_empty() {
+            return Err(Error::InvalidKey);
+        }
+        self.key = Some(key.to_vec());
+        Ok(self)
+    }
+
+    /// Sets the routing data placed at the start of each connection ID
+    ///
+    /// The routing data is not encrypted and can be read by load balancers to route packets.
+    /// The total length of the connection ID is checked when the format is built.
+    #[must_use]
+    pub fn with_routing_data(mut self, routing_data: &[u8]) -> Self {
+        self.routing_data = routing_data.to_vec();
+        self
+    }
+
+    /// Sets the number of random bytes in each connection ID (default: 8)
+    pub fn with_nonce_len(mut self, len: usize) -> Result<Self, Error> {
+        if len < MIN_NONCE_LEN {
+            return Err(Error::InvalidNonceLength);
+        }
+        self.nonce_len = len;
+        Ok(self)
+    }
+
+    /// Sets the number of tag bytes in each connection ID (default: 8)
+    pub fn with_tag_len(mut self, len: usize) -> Result<Self, Error> {
+        if !(MIN_TAG_LEN..=32).contains(&len) {
+            return Err(Error::InvalidTagLength);
+        }
+        self.tag_len = len;
+        Ok(self)
+    }
+
+    /// Sets the lifetime of each generated connection ID
+    pub fn with_lifetime(mut self, lifetime: Duration) -> Result<Self, Error> {
+        if !(connection::id::MIN_LIFETIME..=connection::id::MAX_LIFETIME).contains(&lifetime) {
+            return Err(Error::InvalidLifetime);
+        }
+        self.lifetime = Some(lifetime);
+        Ok(self)
+    }
+
+    /// Enables/disables rotation of the connection ID used during the handshake (default: enabled)
+    pub fn with_handshake_connection_id_rotation(mut self, enabled: bool) -> Result<Self, Error> {
+        self.rotate_handshake_connection_id = enabled;
+        Ok(self)
+    }
+
+    /// Builds the [`Format`] into a provider
+    pub fn build(self) -> Result<Format, Error> {
+        let len = self.routing_data.len() + self.nonce_len + self.tag_len;
+        if !(connection::LocalId::MIN_LEN..=connection::id::MAX_LEN).contains(&len) {
+            return Err(Error::InvalidLength);
+        }
+
+        let key = if let Some(key) = self.key {
+            hmac::Key::new(hmac::HMAC_SHA256, &key)
+        } else {
+            let mut key = [0u8; 32];
+            rand::thread_rng().fill_bytes(&mut key);
+            hmac::Key::new(hmac::HMAC_SHA256, &key)
+        };
+
+        Ok(Format {
+            key,
+            routing_data: self.routing_data,
+            nonce_len: self.nonce_len,
+            tag_len: self.tag_len,
+            lifetime: self.lifetime,
+            rotate_handshake_connection_id: self.rotate_handshake_connection_id,
+            stats: Default::default(),
+        })
+    }
+}
+
+/// Connection ID format which authenticates routing data with a keyed MAC
+#[derive(Debug)]
+pub struct Format {
+    key: hmac::Key,
+    routing_data: Vec<u8>,
+    nonce_len: usize,
+    tag_len: usize,
+    lifetime: Option<Duration>,
+    rotate_handshake_connection_id: bool,
+    stats: Arc<Stats>,
+}
+
+impl Format {
+    /// Creates a builder for the format
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+
+    /// Returns a handle to the counters for validated connection IDs
+    ///
+    /// The handle can be retained after the format is passed to the endpoint.
+    pub fn stats(&self) -> Arc<Stats> {
+        self.stats.clone()
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.routing_data.len() + self.nonce_len + self.tag_len
+    }
+
+    #[inline]
+    fn tag(&self, authenticated: &[u8]) -> hmac::Tag {
+        hmac::sign(&self.key, authenticated)
+    }
+}
+
+impl Generator for Format {
+    fn generate(&mut self, _connection_info: &ConnectionInfo) -> connection::LocalId {
+        let mut id = [0u8; connection::id::MAX_LEN];
+        let len = self.len();
+        let routing_len = self.routing_data.len();
+        let authenticated_len = routing_len + self.nonce_len;
+
+        id[..routing_len].copy_from_slice(&self.routing_data);
+        rand::thread_rng().fill_bytes(&mut id[routing_len..authenticated_len]);
+
+        let tag = self.tag(&id[..authenticated_len]);
+        id[authenticated_len..len].copy_from_slice(&tag.as_ref()[..self.tag_len]);
+
+        (&id[..len]).try_into().expect("length already checked")
+    }
+
+    fn lifetime(&self) -> Option<Duration> {
+        self.lifetime
+    }
+
+    fn rotate_handshake_connection_id(&self) -> bool {
+        self.rotate_handshake_connection_id
+    }
+}
+
+impl Validator for Format {
+    fn validate(&self, _connection_info: &ConnectionInfo, buffer: &[u8]) -> Option<usize> {
+        let len = self.len();
+        let id = buffer.get(..len)?;
+        let authenticated_len = len - self.tag_len;
+        let (authenticated, tag) = id.split_at(authenticated_len);
+
+        let expected = self.tag(authenticated);
+        let is_valid = id.starts_with(&self.routing_data)
+            && constant_time::verify_slices_are_equal(&expected.as_ref()[..self.tag_len], tag)
+                .is_ok();
+
+        if is_valid {
+            self.stats.accepted.fetch_add(1, Ordering::Relaxed);
+            Some(len)
+        } else {
+            self.stats.rejected.fetch_add(1, Ordering::Relaxed);
+            None
+        }
+    }
+}
+
+/// Provides an authenticated connection ID [`Format`]
+#[derive(Debug)]
+pub struct Provider(Format);
+
+impl From<Format> for Provider {
+    fn from(format: Format) -> Self {
+        Self(format)
+    }
+}
+
+impl super::Provider for Provider {
+    type Format = Format;
+    type Error = Infallible;
+
+    fn start(self) -> Result<Self::Format, Self::Error> {
+        Ok(self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn connection_info_test<F: FnOnce(&ConnectionInfo)>(f: F) {
+        let remote_address = &s2n_quic_core::inet::SocketAddress::default();
+        let connection_info = ConnectionInfo::new(remote_address);
+        f(&connection_info)
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)] // This test is too expensive for miri to complete in a reasonable amount of time
+    fn conformance_test() {
+        let mut format = Format::builder()
+            .with_key(b"conformance")
+            .unwrap()
+            .with_routing_data(&[1, 2])
+            .build()
+            .unwrap();
+        crate::provider::conformance::connection_id::check(&mut format);
+    }
+
+    #[test]
+    fn round_trip_test() {
+        connection_info_test(|connection_info| {
+            let mut format = Format::builder()
+                .with_key(b"secret")
+                .unwrap()
+                .with_routing_data(&[0xab, 0xcd])
+                .build()
+                .unwrap();
+            let stats = format.stats();
+
+            let id = format.generate(connection_info);
+            assert_eq!(id.len(), 2 + DEFAULT_NONCE_LEN + DEFAULT_TAG_LEN);
+            assert_eq!(&id.as_ref()[..2], &[0xab, 0xcd]);
+
+            // extra bytes after the connection ID are ignored
+            let mut packet = id.as_ref().to_vec();
+            packet.extend_from_slice(&[1, 2, 3]);
+            assert_eq!(format.validate(connection_info, &packet), Some(id.len()));
+            assert_eq!(stats.accepted(), 1);
+
+            // IDs generated with a different key are rejected
+            let mut other = Format::builder()
+                .with_key(b"other")
+                .unwrap()
+                .with_routing_data(&[0xab, 0xcd])
+                .build()
+                .unwrap();
+            let forged = other.generate(connection_info);
+            assert_eq!(format.validate(connection_info, forged.as_ref()), None);
+
+            // any modification invalidates the tag
+            for idx in 0..id.len() {
+                let mut forged = id.as_ref().to_vec();
+                forged[idx] ^= 1;
+                assert_eq!(format.validate(connection_info, &forged), None);
+            }
+
+            // truncated IDs are rejected
+            assert_eq!(format.validate(connection_info, &id.as_ref()[1..]), None);
+
+            assert_eq!(stats.accepted(), 1);
+            assert_eq!(stats.rejected(), 2 + id.len() as u64);
+        });
+    }
+
+    #[test]
+    fn builder_test() {
+        assert_eq!(
+            Format::builder().with_tag_len(MIN_TAG_LEN - 1).err(),
+            Some(Error::InvalidTagLength)
+        );
+        assert_eq!(
+            Format::builder().with_nonce_len(MIN_NONCE_LEN - 1).err(),
+            Some(Error::InvalidNonceLength)
+        );
+        assert_eq!(
+            Format::builder().with_key(&[]).err(),
+            Some(Error::InvalidKey)
+        );
+        assert_eq!(
+            Format::builder().with_routing_data(&[0; 16]).build().err(),
+            Some(Error::InvalidLength)
+        );
+    }
+}
@@ -0,0 +1,204 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Provides hostname resolution for client connection attempts
+//!
+//! When a [`Connect`](crate::client::Connect) is created with
+//! [`Connect::from_host`](crate::client::Connect::from_host), the client resolves the hostname with
+//! the configured [`Resolver`] before connecting. The default resolver uses the operating system's
+//! resolver to look up `A` and `AAAA` records.
+//!
+//! Applications can implement a custom [`Resolver`] to look up `SVCB` or `HTTPS` records
+//! ([RFC 9460](https://www.rfc-editor.org/rfc/rfc9460)) and return the advertised alternative
+//! endpoints, ordered by their [priority](Endpoint::with_priority).
+
+use core::{fmt, future::Future, pin::Pin};
+use std::{io, net::SocketAddr, sync::Arc};
+
+pub use s2n_quic_transport::endpoint::connect::Host;
+
+/// The future returned by a [`Resolver`]
+pub type Resolve = Pin<Box<dyn Future<Output = io::Result<Vec<Endpoint>>> + Send>>;
+
+/// Resolves hostnames to the endpoints which serve them
+pub trait Resolver: 'static + Send + Sync {
+    /// Resolves the provided host
+    ///
+    /// Each returned [`Endpoint`] corresponds to an alternative endpoint for the service. The
+    /// client attempts to connect to the endpoints in priority order.
+    fn resolve(&self, host: &Host) -> Resolve;
+}
+
+impl<T: Resolver> Resolver for Arc<T> {
+    #[inline]
+    fn resolve(&self, host: &Host) -> Resolve {
+        (**self).resolve(host)
+    }
+}
+
+/// An endpoint which serves a resolved host
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Endpoint {
+    priority: u16,
+    addresses: Vec<SocketAddr>,
+}
+
+impl Endpoint {
+    /// The priority used for endpoints without an explicit priority
+    ///
+    /// This sorts after all of the `SVCB` service mode priorities, which makes it suitable for
+    /// addresses obtained from `A` and `AAAA` records.
+    pub const DEFAULT_PRIORITY: u16 = u16::MAX;
+
+    /// Creates an endpoint reachable at the provided addresses
+    pub fn new<I: IntoIterator<Item = SocketAddr>>(addresses: I) -> Self {
+        Self {
+            priority: Self::DEFAULT_PRIORITY,
+            addresses: addresses.into_iter().collect(),
+        }
+    }
+
+    /// Sets the priority of the endpoint
+    ///
+    /// Endpoints with lower values are attempted first, matching the `SvcPriority` field of
+    /// `SVCB` records.
+    #[must_use]
+    pub fn with_priority(mut self, priority: u16) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Returns the priority of the endpoint
+    #[inline]
+    pub fn priority(&self) -> u16 {
+        self.priority
+    }
+
+    /// Returns the addresses of the endpoint
+    #[inline]
+    pub fn addresses(&self) -> &[SocketAddr] {
+        &self.addresses
+    }
+}
+
+/// Orders the resolved endpoints by priority and flattens them into a list of addresses
+pub(crate) fn addresses(mut endpoints: Vec<Endpoint>) -> Vec<SocketAddr> {
+    // the sort is stable so endpoints with the same priority keep the resolver's order
+    endpoints.sort_by_key(|endpoint| endpoint.priority);
+
+    let mut addresses: Vec<SocketAddr> = vec![];
+    for endpoint in endpoints {
+        for address in endpoint.addresses {
+            if !addresses.contains(&address) {
+                addresses.push(address);
+            }
+        }
+    }
+    addresses
+}
+
+pub trait Provider: 'static {
+    type Resolver: 'static + Resolver;
+    type Error: 'static + fmt::Display + Send + Sync;
+
+    fn start(self) -> Result<Self::Resolver, Self::Error>;
+}
+
+pub use system::Resolver as Default;
+
+impl_provider_utils!();
+
+impl<T: Resolver> Provider for T {
+    type Resolver = T;
+    type Error = core::convert::Infallible;
+
+    fn start(self) -> Result<Self::Resolver, Self::Error> {
+        Ok(self)
+    }
+}
+
+pub mod system {
+    use super::{Endpoint, Host, Resolve};
+    use std::{io, net::ToSocketAddrs};
+    use tokio::sync::oneshot;
+
+    /// Resolves hostnames with the operating system's resolver
+    ///
+    /// The lookup blocks, so it's performed on the tokio blocking thread pool when called from
+    /// within a tokio runtime and on a dedicated thread otherwise.
+    #[derive(Clone, Copy, Debug, Default)]
+    pub struct Resolver;
+
+    impl super::Resolver for Resolver {
+        fn resolve(&self, host: &Host) -> Resolve {
+            let query = (host.name().to_string(), host.port());
+            let (sender, receiver) = oneshot::channel();
+
+            let lookup = move || {
+                let addresses = query.to_socket_addrs().map(Endpoint::new);
+                // the attempt may have been dropped while resolving
+                let _ = sender.send(addresses);
+            };
+
+            let spawned = match tokio::runtime::Handle::try_current() {
+                Ok(handle) => {
+                    handle.spawn_blocking(lookup);
+                    Ok(())
+                }
+                Err(_) => std::thread::Builder::new()
+                    .name("s2n-quic-dns".into())
+                    .spawn(lookup)
+                    .map(|_| ()),
+            };
+
+            Box::pin(async move {
+                spawned?;
+
+                let endpoint = receiver.await.map_err(|_| {
+                    io::Error::new(io::ErrorKind::Other, "the resolver stopped unexpectedly")
+                })??;
+
+                Ok(vec![endpoint])
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn addresses_test() {
+        let a: SocketAddr = "127.0.0.1:443".parse().unwrap();
+        let b: SocketAddr = "[::1]:443".parse().unwrap();
+        let c: SocketAddr = "127.0.0.2:8443".parse().unwrap();
+
+        let endpoints = vec![
+            Endpoint::new([a, b]),
+            Endpoint::new([c]).with_priority(1),
+            Endpoint::new([b]).with_priority(2),
+        ];
+
+        assert_eq!(addresses(endpoints), [c, b, a]);
+    }
+
+    /// Ensures the system resolver doesn't need a tokio runtime to start a lookup
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn system_resolver_outside_runtime_test() {
+        let connect = crate::client::Connect::from_host("localhost:443").unwrap();
+        let resolve = system::Resolver.resolve(connect.host().unwrap());
+
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        let endpoints = runtime.block_on(resolve).unwrap();
+
+        assert!(!endpoints[0].addresses().is_empty());
+        assert!(endpoints[0]
+            .addresses()
+            .iter()
+            .all(|address| address.port() == 443));
+    }
+}
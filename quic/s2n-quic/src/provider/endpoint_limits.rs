@@ -4,7 +4,7 @@
 //! Allows applications to limit peer's ability to open new connections
 
 pub use s2n_quic_core::endpoint::{
-    limits::{ConnectionAttempt, Outcome},
+    limits::{ConnectionAttempt, DropReason, Outcome},
     Limiter,
 };
 use s2n_quic_core::{event::Timestamp, path::THROTTLED_PORTS_LEN};
@@ -87,9 +87,67 @@ impl BasicRateLimiter {
     }
 }
 
+/// Limits the rate of new connections with a token bucket
+///
+/// The bucket holds up to `burst` tokens and is refilled at `rate` tokens per second. Each
+/// connection attempt takes a single token.
+#[derive(Clone, Copy, Debug)]
+struct TokenBucket {
+    rate: u32,
+    burst: u32,
+    tokens: u32,
+    /// The last time the bucket was refilled, relative to the start of the process
+    refilled_at: Option<Duration>,
+}
+
+impl TokenBucket {
+    fn new(rate: u32, burst: u32) -> Self {
+        // the bucket needs to hold at least a single token for any attempt to succeed
+        let burst = burst.max(1);
+
+        Self {
+            rate,
+            burst,
+            tokens: burst,
+            refilled_at: None,
+        }
+    }
+
+    /// Takes a token from the bucket, returning `false` if the bucket is empty
+    fn try_acquire(&mut self, timestamp: Timestamp) -> bool {
+        let now = timestamp.duration_since_start();
+        let refilled_at = *self.refilled_at.get_or_insert(now);
+
+        if self.rate > 0 {
+            let interval = Duration::from_secs(1) / self.rate;
+            let elapsed = now.saturating_sub(refilled_at);
+            let count = elapsed.as_nanos() / interval.as_nanos().max(1);
+
+            if count > 0 {
+                let count = count.min(self.burst as u128) as u32;
+                self.tokens = self.tokens.saturating_add(count).min(self.burst);
+
+                // carry over any partial intervals unless the bucket is full
+                self.refilled_at = Some(if self.tokens == self.burst {
+                    now
+                } else {
+                    refilled_at + interval * count
+                });
+            }
+        }
+
+        if self.tokens == 0 {
+            return false;
+        }
+
+        self.tokens -= 1;
+        true
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{BasicRateLimiter, THROTTLED_PORT_LIMIT, THROTTLE_FREQUENCY};
+    use super::{BasicRateLimiter, TokenBucket, THROTTLED_PORT_LIMIT, THROTTLE_FREQUENCY};
     use core::time::Duration;
     use s2n_quic_core::{
         endpoint::limits::ConnectionAttempt,
@@ -141,6 +199,34 @@ mod tests {
         }
     }
 
+    #[test]
+    fn token_bucket() {
+        let mut mock_clock = MockClock::default();
+        let mut bucket = TokenBucket::new(10, 5);
+
+        // the bucket starts full so the burst is allowed immediately
+        for _ in 0..5 {
+            assert!(bucket.try_acquire(mock_clock.get_time().into_event()));
+        }
+        assert!(!bucket.try_acquire(mock_clock.get_time().into_event()));
+
+        // a single token is added every 100ms
+        mock_clock.inc_by(Duration::from_millis(150));
+        assert!(bucket.try_acquire(mock_clock.get_time().into_event()));
+        assert!(!bucket.try_acquire(mock_clock.get_time().into_event()));
+
+        // the partial interval is carried over
+        mock_clock.inc_by(Duration::from_millis(50));
+        assert!(bucket.try_acquire(mock_clock.get_time().into_event()));
+
+        // the bucket never holds more than the burst
+        mock_clock.inc_by(Duration::from_secs(60));
+        for _ in 0..5 {
+            assert!(bucket.try_acquire(mock_clock.get_time().into_event()));
+        }
+        assert!(!bucket.try_acquire(mock_clock.get_time().into_event()));
+    }
+
     #[test]
     fn throttle_constants_changed() {
         // If the constants change consider modifying the above test cases to make sure we are
@@ -177,22 +263,74 @@ pub mod default {
     ///     Ok(())
     /// # }
     /// ```
+    ///
+    /// Cap the rate of new connections and drop attempts during a handshake flood.
+    ///
+    /// ```rust
+    /// use s2n_quic::provider::endpoint_limits;
+    /// # use std::error::Error;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn Error>> {
+    /// let limits = endpoint_limits::Default::builder()
+    ///     .with_connection_rate_limit(1000, 200)?
+    ///     .with_inflight_handshake_drop_limit(5000)?
+    ///     .build();
+    ///
+    ///     Ok(())
+    /// # }
+    /// ```
     #[derive(Default)]
     pub struct Builder {
         max_inflight_handshake_limit: Option<usize>,
+        inflight_handshake_drop_limit: Option<usize>,
+        connection_rate_limit: Option<(u32, u32)>,
     }
 
     impl Builder {
         /// Sets limit on inflight handshakes
+        ///
+        /// Connection attempts beyond the limit are deferred with a Retry packet.
         pub fn with_inflight_handshake_limit(mut self, limit: usize) -> Result<Self, Infallible> {
             self.max_inflight_handshake_limit = Some(limit);
             Ok(self)
         }
 
+        /// Sets a hard limit on inflight handshakes
+        ///
+        /// Connection attempts beyond the limit are dropped without sending any packets, which
+        /// bounds the amount of handshake work during a flood. Dropped attempts are reported with
+        /// the `InflightHandshakeLimitExceeded` reason in the `EndpointDatagramDropped` event.
+        pub fn with_inflight_handshake_drop_limit(
+            mut self,
+            limit: usize,
+        ) -> Result<Self, Infallible> {
+            self.inflight_handshake_drop_limit = Some(limit);
+            Ok(self)
+        }
+
+        /// Limits the rate of new connections with a token bucket
+        ///
+        /// Up to `burst` connection attempts are allowed at once, after which attempts are
+        /// allowed at `connections_per_second`. Attempts exceeding the rate are dropped and
+        /// reported with the `ConnectionRateLimited` reason in the `EndpointDatagramDropped`
+        /// event.
+        pub fn with_connection_rate_limit(
+            mut self,
+            connections_per_second: u32,
+            burst: u32,
+        ) -> Result<Self, Infallible> {
+            self.connection_rate_limit = Some((connections_per_second, burst));
+            Ok(self)
+        }
+
         /// Build the limits
         pub fn build(self) -> Result<Limits, Infallible> {
             Ok(Limits {
                 max_inflight_handshake_limit: self.max_inflight_handshake_limit,
+                inflight_handshake_drop_limit: self.inflight_handshake_drop_limit,
+                connection_rate_limiter: self
+                    .connection_rate_limit
+                    .map(|(rate, burst)| TokenBucket::new(rate, burst)),
                 rate_limiter: [BasicRateLimiter::default(); THROTTLED_PORTS_LEN],
             })
         }
@@ -202,6 +340,9 @@ pub mod default {
     pub struct Limits {
         /// Maximum number of handshakes to allow before Retry packets are queued
         max_inflight_handshake_limit: Option<usize>,
+        /// Maximum number of handshakes to allow before connection attempts are dropped
+        inflight_handshake_drop_limit: Option<usize>,
+        connection_rate_limiter: Option<TokenBucket>,
         rate_limiter: [BasicRateLimiter; THROTTLED_PORTS_LEN],
     }
 
@@ -227,6 +368,18 @@ pub mod default {
                 }
             }
 
+            if let Some(limit) = self.inflight_handshake_drop_limit {
+                if info.inflight_handshakes >= limit {
+                    return Outcome::drop_with_reason(DropReason::InflightHandshakeLimitExceeded);
+                }
+            }
+
+            if let Some(rate_limiter) = self.connection_rate_limiter.as_mut() {
+                if !rate_limiter.try_acquire(info.timestamp) {
+                    return Outcome::drop_with_reason(DropReason::RateLimited);
+                }
+            }
+
             if let Some(limit) = self.max_inflight_handshake_limit {
                 if info.inflight_handshakes >= limit {
                     return Outcome::retry();
@@ -242,6 +395,8 @@ pub mod default {
         fn default() -> Self {
             Self {
                 max_inflight_handshake_limit: None,
+                inflight_handshake_drop_limit: None,
+                connection_rate_limiter: None,
                 rate_limiter: [BasicRateLimiter::default(); THROTTLED_PORTS_LEN],
             }
         }
@@ -257,6 +412,51 @@ pub mod default {
         assert_eq!(elp.max_inflight_handshake_limit, Some(100));
     }
 
+    #[test]
+    fn handshake_flood_test() {
+        use s2n_quic_core::{
+            event::IntoEvent,
+            inet::SocketAddress,
+            time::{testing::Clock as MockClock, Clock},
+        };
+
+        let remote_address = SocketAddress::default();
+        let mut limits = Limits::builder()
+            .with_inflight_handshake_limit(3)
+            .unwrap()
+            .with_inflight_handshake_drop_limit(10)
+            .unwrap()
+            .with_connection_rate_limit(1, 5)
+            .unwrap()
+            .build()
+            .unwrap();
+        let mock_clock = MockClock::default();
+        let attempt = |inflight_handshakes| {
+            ConnectionAttempt::new(
+                inflight_handshakes,
+                0,
+                &remote_address,
+                mock_clock.get_time().into_event(),
+            )
+        };
+
+        assert_eq!(limits.on_connection_attempt(&attempt(0)), Outcome::allow());
+        assert_eq!(limits.on_connection_attempt(&attempt(3)), Outcome::retry());
+        assert_eq!(
+            limits.on_connection_attempt(&attempt(10)),
+            Outcome::drop_with_reason(DropReason::InflightHandshakeLimitExceeded)
+        );
+
+        // the attempts dropped by the handshake limit don't consume the rate limit
+        for _ in 0..3 {
+            assert_eq!(limits.on_connection_attempt(&attempt(0)), Outcome::allow());
+        }
+        assert_eq!(
+            limits.on_connection_attempt(&attempt(0)),
+            Outcome::drop_with_reason(DropReason::RateLimited)
+        );
+    }
+
     #[test]
     fn blocked_port_connection_attempt() {
         use s2n_quic_core::{
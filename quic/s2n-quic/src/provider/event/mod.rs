@@ -7,7 +7,7 @@ use cfg_if::cfg_if;
 pub use s2n_quic_core::{
     endpoint::Location,
     event::{
-        api as events,
+        accounting, api as events,
         api::{ConnectionInfo, ConnectionMeta},
         supervisor, Event, Meta, Subscriber, Timestamp,
     },
@@ -33,6 +33,10 @@ pub mod tracing;
 #[cfg(feature = "provider-event-console-perf")]
 pub mod console_perf;
 
+/// This module contains event integration with [`serde`](https://docs.rs/serde)
+#[cfg(feature = "provider-event-serde")]
+pub use s2n_quic_core::event::export;
+
 cfg_if! {
     if #[cfg(any(feature = "provider-event-tracing", test))] {
         pub use self::tracing as default;
@@ -19,18 +19,26 @@ pub trait Provider: 'static {
 #[cfg(any(test, feature = "unstable-provider-io-testing"))]
 pub mod testing;
 
-#[cfg(feature = "unstable-provider-io-turmoil")]
+#[cfg(feature = "provider-io-turmoil")]
 pub mod turmoil;
 
 #[cfg(feature = "unstable-provider-io-xdp")]
 pub mod xdp;
 
+pub mod datagram;
+
+// the wasm targets don't provide OS sockets so endpoints use a host-provided datagram transport
+#[cfg(not(target_family = "wasm"))]
 pub mod tokio;
 
+#[cfg(target_family = "wasm")]
+pub use self::datagram as default;
+#[cfg(not(target_family = "wasm"))]
 pub use self::tokio as default;
 
 pub use default::Provider as Default;
 
+#[cfg(not(target_family = "wasm"))]
 impl TryInto for u16 {
     type Error = io::Error;
     type Provider = Default;
@@ -42,6 +50,7 @@ impl TryInto for u16 {
 
 macro_rules! impl_socket_addrs {
     ($ty:ty) => {
+        #[cfg(not(target_family = "wasm"))]
         impl TryInto for $ty {
             type Error = io::Error;
             type Provider = Default;
@@ -62,4 +71,43 @@ impl_socket_addrs!(std::net::SocketAddr);
 impl_socket_addrs!(std::net::SocketAddrV4);
 impl_socket_addrs!(std::net::SocketAddrV6);
 
+/// Binds a socket to each address, which are all managed by the same endpoint
+#[cfg(not(target_family = "wasm"))]
+impl TryInto for &[std::net::SocketAddr] {
+    type Error = io::Error;
+    type Provider = Default;
+
+    fn try_into(self) -> io::Result<Self::Provider> {
+        let (addr, additional_addrs) = self
+            .split_first()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "missing bind address"))?;
+
+        let mut builder = Default::builder().with_receive_address(*addr)?;
+        for addr in additional_addrs {
+            builder = builder.with_additional_address(*addr)?;
+        }
+        builder.build()
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+impl<const N: usize> TryInto for [std::net::SocketAddr; N] {
+    type Error = io::Error;
+    type Provider = Default;
+
+    fn try_into(self) -> io::Result<Self::Provider> {
+        TryInto::try_into(&self[..])
+    }
+}
+
+#[cfg(not(target_family = "wasm"))]
+impl TryInto for Vec<std::net::SocketAddr> {
+    type Error = io::Error;
+    type Provider = Default;
+
+    fn try_into(self) -> io::Result<Self::Provider> {
+        TryInto::try_into(&self[..])
+    }
+}
+
 impl_provider_utils!();
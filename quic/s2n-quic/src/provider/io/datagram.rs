@@ -0,0 +1,27 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Provides an implementation of the [`io::Provider`](crate::provider::io::Provider)
+//! over a host-provided datagram [`Transport`]
+//!
+//! This allows endpoints to run in environments without OS sockets, such as `wasm32-wasi`, where
+//! the host exposes a UDP-like socket. The endpoint task is spawned with the configured
+//! [`Spawner`](crate::provider::runtime::Spawner).
+
+use s2n_quic_core::{endpoint::Endpoint, inet::SocketAddress};
+use s2n_quic_platform::io::datagram;
+use std::io;
+
+pub use self::datagram::{Builder, Io as Provider, Transport};
+
+impl super::Provider for Provider {
+    type PathHandle = datagram::PathHandle;
+    type Error = io::Error;
+
+    fn start<E: Endpoint<PathHandle = Self::PathHandle>>(
+        self,
+        endpoint: E,
+    ) -> Result<SocketAddress, Self::Error> {
+        Provider::start(self, endpoint)
+    }
+}
@@ -8,6 +8,8 @@ use std::io;
 
 pub use self::testing::*;
 
+pub mod sim;
+
 pub type Error = Box<dyn 'static + std::error::Error>;
 pub type Result<T = (), E = Error> = core::result::Result<T, E>;
 
@@ -0,0 +1,121 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! A deterministic network simulation for testing QUIC endpoints
+//!
+//! Every endpoint in a simulation is attached to the same simulated network and shares a
+//! simulated clock. The clock only advances once all of the tasks are waiting on a timer, so
+//! simulations run as fast as possible and always produce the same result for a given seed.
+//!
+//! A simulation is made up of:
+//!
+//! * A network [`Model`], which controls the delay, jitter, loss, and corruption applied to
+//!   every packet.
+//! * Any number of hosts, each created with [`Handle::builder`] and optionally bound to a
+//!   specific address with [`Builder::with_address`].
+//! * Tasks, which are spawned with [`spawn`]. The simulation runs until all of the tasks spawned
+//!   with [`primary::spawn`] have completed.
+//!
+//! ```rust,no_run
+//! use core::time::Duration;
+//! use s2n_quic::{client::Connect, provider::io::testing::sim, Client, Server};
+//! # const CERT_PEM: &str = "";
+//! # const KEY_PEM: &str = "";
+//!
+//! # fn main() -> sim::Result {
+//! let model = sim::Model::default();
+//! model.set_delay(Duration::from_millis(10)).set_drop_rate(0.01);
+//!
+//! sim::run(model, |handle| {
+//!     let mut server = Server::builder()
+//!         .with_io(handle.builder().build()?)?
+//!         .with_tls((CERT_PEM, KEY_PEM))?
+//!         .start()?;
+//!     let server_addr = server.local_addr()?;
+//!
+//!     sim::spawn(async move {
+//!         while let Some(_connection) = server.accept().await {
+//!             // handle the connection
+//!         }
+//!     });
+//!
+//!     let client = Client::builder()
+//!         .with_io(handle.builder().build()?)?
+//!         .with_tls(CERT_PEM)?
+//!         .start()?;
+//!
+//!     sim::primary::spawn(async move {
+//!         let connect = Connect::new(server_addr).with_server_name("localhost");
+//!         let _connection = client.connect(connect).await.unwrap();
+//!
+//!         // keep the connection open for a second of simulated time
+//!         sim::time::delay(Duration::from_secs(1)).await;
+//!     });
+//!
+//!     Ok(())
+//! })?;
+//! # Ok(())
+//! # }
+//! ```
+
+pub use super::{
+    test as run, test_seed as run_with_seed, Builder, Error, Handle, Io, Model, Network, Result,
+    TxRecorder,
+};
+pub use s2n_quic_core::havoc;
+pub use s2n_quic_platform::io::testing::{network::Packet, primary, spawn};
+
+use s2n_quic_platform::io::testing::{network::Buffers, rand};
+
+pub mod time {
+    //! Controls the simulated clock
+    //!
+    //! The clock normally only moves forward once every task is waiting on a timer. [`advance`]
+    //! can be used to skip ahead immediately, for example to simulate an endpoint which was
+    //! suspended.
+
+    pub use s2n_quic_platform::io::testing::time::{advance, delay, delay_until, now, Timer};
+}
+
+/// Applies a [`havoc::Strategy`] to the payload of every packet sent on the network
+///
+/// The strategy is applied before the packets are handed to the network model, so `Havoc`
+/// should be placed first when combined with a [`Model`]:
+///
+/// ```rust,no_run
+/// use s2n_quic::provider::io::testing::sim::{self, havoc::{self, Strategy as _}};
+///
+/// let model = sim::Model::default();
+/// let network = (sim::Havoc::new(havoc::Mutate.randomly()), model);
+/// ```
+///
+/// The strategy is driven by the simulation's random number generator so the corruption is
+/// reproducible for a given seed.
+#[derive(Clone, Debug, Default)]
+pub struct Havoc<S> {
+    strategy: S,
+}
+
+impl<S: havoc::Strategy> Havoc<S> {
+    pub fn new(strategy: S) -> Self {
+        Self { strategy }
+    }
+}
+
+impl<S: havoc::Strategy> Network for Havoc<S> {
+    fn execute(&mut self, buffers: &Buffers) -> usize {
+        buffers.pending_transmission_mut(|packet| {
+            if packet.payload.is_empty() {
+                return;
+            }
+
+            let len = self
+                .strategy
+                .havoc_slice(&mut rand::Havoc, &mut packet.payload);
+            packet.payload.truncate(len);
+        });
+
+        // corrupting packets doesn't transmit anything
+        0
+    }
+}
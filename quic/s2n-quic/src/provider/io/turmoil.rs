@@ -3,12 +3,35 @@
 
 //! Provides an implementation of the [`io::Provider`](crate::provider::io::Provider)
 //! using the [`Turmoil network simulator`](https://docs.rs/turmoil).
+//!
+//! In addition to the conditions configured on the simulation, a [`Network`] can be shared
+//! between hosts to apply latency, jitter and loss to each pair of hosts:
+//!
+//! ```rust,ignore
+//! use s2n_quic::provider::io::turmoil::{Builder, Link, Network};
+//! use std::time::Duration;
+//!
+//! let network = Network::new(seed);
+//! network.set_link(
+//!     "client",
+//!     "server",
+//!     Link::new()
+//!         .with_latency(Duration::from_millis(50))
+//!         .with_jitter(Duration::from_millis(10))
+//!         .with_loss(0.01),
+//! );
+//!
+//! let io = Builder::default()
+//!     .with_address(addr)?
+//!     .with_network(network)
+//!     .build()?;
+//! ```
 
 use s2n_quic_core::{endpoint::Endpoint, inet::SocketAddress};
 use s2n_quic_platform::io::turmoil;
 use std::io;
 
-pub use self::turmoil::{Builder, Io as Provider};
+pub use self::turmoil::{Builder, Io as Provider, Link, Network};
 
 impl super::Provider for Provider {
     type PathHandle = turmoil::PathHandle;
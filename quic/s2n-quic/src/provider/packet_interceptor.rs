@@ -4,7 +4,8 @@
 // this is only exposed as an unstable provider so we get warnings without this
 #[allow(unused_imports)]
 pub use s2n_quic_core::packet::interceptor::{
-    loss, Disabled, Havoc, Interceptor as PacketInterceptor, Loss,
+    frame, loss, mirror, pcap, rewrite, Disabled, FrameInterceptor, Frames, Havoc,
+    Interceptor as PacketInterceptor, Loss, Mirror, Rewrite,
 };
 
 /// Provides packet_interceptor support for an endpoint
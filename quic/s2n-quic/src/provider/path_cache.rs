@@ -0,0 +1,151 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Shares path characteristics between connections to the same peer
+//!
+//! When a connection closes, the smoothed RTT, MTU, and congestion window of its active path are
+//! stored in the cache. New connections to the same peer use the stored values to seed their
+//! initial RTT estimate and MTU, and as a hint for the initial congestion window and pacing rate.
+//!
+//! By default, nothing is shared between connections.
+
+pub use s2n_quic_core::path::cache::{Cache, Disabled, Entry, PathInfo};
+use std::{
+    collections::{HashMap, VecDeque},
+    net::{IpAddr, SocketAddr},
+    sync::Mutex,
+};
+
+pub trait Provider: 'static {
+    type Cache: 'static + Cache;
+    type Error: 'static + core::fmt::Display + Send + Sync;
+
+    fn start(self) -> Result<Self::Cache, Self::Error>;
+}
+
+pub use Disabled as Default;
+
+impl_provider_utils!();
+
+impl<T: 'static + Cache> Provider for T {
+    type Cache = T;
+    type Error = core::convert::Infallible;
+
+    fn start(self) -> Result<Self::Cache, Self::Error> {
+        Ok(self)
+    }
+}
+
+/// An in-memory cache keyed by the IP address of the peer
+///
+/// Once the cache holds `capacity` peers, the least recently stored peer is evicted.
+///
+/// # Examples
+///
+/// ```rust
+/// use s2n_quic::provider::path_cache::Memory;
+///
+/// let cache = Memory::new(1024);
+/// ```
+#[derive(Debug)]
+pub struct Memory {
+    capacity: usize,
+    state: Mutex<State>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    entries: HashMap<IpAddr, Entry>,
+    order: VecDeque<IpAddr>,
+}
+
+impl Memory {
+    /// Creates a cache which stores up to `capacity` peers
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            state: Mutex::new(State::default()),
+        }
+    }
+
+    fn key(path: &PathInfo) -> IpAddr {
+        let ip = SocketAddr::from(&path.remote_address).ip();
+        // IPv4 peers may be reported as IPv4-mapped addresses on dual-stack sockets
+        match ip {
+            IpAddr::V6(ip) => ip.to_ipv4_mapped().map_or(IpAddr::V6(ip), IpAddr::V4),
+            ip => ip,
+        }
+    }
+}
+
+impl Cache for Memory {
+    fn get(&self, path: &PathInfo) -> Option<Entry> {
+        let state = self.state.lock().ok()?;
+        state.entries.get(&Self::key(path)).copied()
+    }
+
+    fn insert(&self, path: &PathInfo, entry: Entry) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+        let key = Self::key(path);
+
+        if state.entries.insert(key, entry).is_some() {
+            state.order.retain(|ip| *ip != key);
+        } else if state.order.len() == self.capacity {
+            if let Some(evicted) = state.order.pop_front() {
+                state.entries.remove(&evicted);
+            }
+        }
+
+        state.order.push_back(key);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::time::Duration;
+    use s2n_quic_core::inet::SocketAddress;
+
+    fn entry(rtt_ms: u64) -> Entry {
+        Entry {
+            smoothed_rtt: Duration::from_millis(rtt_ms),
+            min_rtt: Duration::from_millis(rtt_ms),
+            mtu: 1500,
+            congestion_window: 12_000,
+        }
+    }
+
+    #[test]
+    fn memory_test() {
+        let cache = Memory::new(2);
+
+        let a: SocketAddress = SocketAddr::new([192, 0, 2, 1].into(), 443).into();
+        let a_other_port: SocketAddress = SocketAddr::new([192, 0, 2, 1].into(), 8443).into();
+        let a_mapped: SocketAddress =
+            SocketAddr::new("::ffff:192.0.2.1".parse().unwrap(), 443).into();
+        let b: SocketAddress = SocketAddr::new([192, 0, 2, 2].into(), 443).into();
+        let c: SocketAddress = SocketAddr::new([192, 0, 2, 3].into(), 443).into();
+
+        assert_eq!(cache.get(&PathInfo::new(&a)), None);
+
+        cache.insert(&PathInfo::new(&a), entry(10));
+        assert_eq!(cache.get(&PathInfo::new(&a)), Some(entry(10)));
+        assert_eq!(cache.get(&PathInfo::new(&a_other_port)), Some(entry(10)));
+        assert_eq!(cache.get(&PathInfo::new(&a_mapped)), Some(entry(10)));
+
+        cache.insert(&PathInfo::new(&b), entry(20));
+        // refreshing `a` makes `b` the least recently stored peer
+        cache.insert(&PathInfo::new(&a), entry(30));
+        cache.insert(&PathInfo::new(&c), entry(40));
+
+        assert_eq!(cache.get(&PathInfo::new(&a)), Some(entry(30)));
+        assert_eq!(cache.get(&PathInfo::new(&b)), None);
+        assert_eq!(cache.get(&PathInfo::new(&c)), Some(entry(40)));
+    }
+}
@@ -0,0 +1,86 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Provides control over where the endpoint tasks are run
+//!
+//! The [`Spawner`] trait and the [`Task`]s it receives don't depend on any particular async
+//! runtime. How much of the runtime can be replaced depends on the IO provider:
+//!
+//! * The [`tokio`](crate::provider::io::tokio) IO provider spawns its socket and endpoint tasks
+//!   on the ambient tokio runtime. A specific runtime [`Handle`] can instead be provided with
+//!   [`Builder::with_handle`](crate::provider::io::tokio::Builder::with_handle), and a
+//!   [`Spawner`] can be provided with
+//!   [`Builder::with_spawner`](crate::provider::io::tokio::Builder::with_spawner). The tasks
+//!   still register their sockets and timers with the tokio runtime, which enters its context
+//!   on every poll, so that runtime must continue to be driven while the endpoint is running.
+//! * The [`datagram`](crate::provider::io::datagram) IO provider performs all IO and timers
+//!   through the application's [`Transport`](crate::provider::io::datagram::Transport) and
+//!   always requires a [`Spawner`]. This allows endpoints to run entirely on other runtimes,
+//!   such as `glommio` or `monoio`, by implementing the transport over that runtime's UDP
+//!   socket and timers.
+//!
+//! ```rust,no_run
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use s2n_quic::{
+//!     provider::{io::tokio::Builder, runtime::Task},
+//!     Server,
+//! };
+//!
+//! // the application's executor
+//! let executor = tokio::runtime::Runtime::new()?;
+//! let executor = executor.handle().clone();
+//!
+//! let io = Builder::default()
+//!     .with_receive_address("127.0.0.1:443".parse()?)?
+//!     .with_spawner(move |task: Task| {
+//!         executor.spawn(task);
+//!     })
+//!     .build()?;
+//!
+//! let server = Server::builder().with_io(io)?;
+//! # let _ = server;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Without tokio, the tasks can be handed to any executor:
+//!
+//! ```rust,no_run
+//! # use core::task::{Context, Poll};
+//! # use s2n_quic::provider::io::datagram::Transport;
+//! # use std::{io, time::Duration};
+//! # struct RuntimeSocket;
+//! # impl Transport for RuntimeSocket {
+//! #     fn local_address(&self) -> io::Result<s2n_quic_core::inet::SocketAddress> { todo!() }
+//! #     fn poll_recv(&mut self, _: &mut Context, _: &mut [u8]) -> Poll<io::Result<(s2n_quic_core::inet::SocketAddress, usize)>> { todo!() }
+//! #     fn poll_send(&mut self, _: &mut Context, _: s2n_quic_core::inet::SocketAddress, _: &[u8]) -> Poll<io::Result<()>> { todo!() }
+//! #     fn poll_timer(&mut self, _: &mut Context, _: Duration) -> Poll<()> { todo!() }
+//! # }
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! use s2n_quic::{
+//!     provider::{io::datagram::Builder, runtime::Task},
+//!     Server,
+//! };
+//! use std::sync::{mpsc, Mutex};
+//!
+//! // tasks are forwarded to the application's executor, which polls them to completion
+//! let (tasks, executor) = mpsc::channel::<Task>();
+//! let tasks = Mutex::new(tasks);
+//!
+//! let io = Builder::default()
+//!     // a `Transport` implemented over the executor's UDP socket and timers
+//!     .with_transport(RuntimeSocket)
+//!     .with_spawner(move |task: Task| {
+//!         let _ = tasks.lock().unwrap().send(task);
+//!     })
+//!     .build()?;
+//!
+//! let server = Server::builder().with_io(io)?;
+//! # let _ = (server, executor);
+//! # Ok(())
+//! # }
+//! ```
+
+pub use s2n_quic_platform::io::spawner::{Spawner, Task};
+#[cfg(not(target_family = "wasm"))]
+pub use tokio::runtime::Handle;
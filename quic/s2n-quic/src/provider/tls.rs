@@ -6,6 +6,8 @@
 use cfg_if::cfg_if;
 use s2n_quic_core::crypto;
 
+pub use crypto::tls::{ocsp, signer};
+
 pub trait Provider {
     type Server: 'static + crypto::tls::Endpoint;
     type Client: 'static + crypto::tls::Endpoint;
@@ -21,7 +23,7 @@ pub trait Provider {
 impl_provider_utils!();
 
 cfg_if! {
-    if #[cfg(feature = "provider-tls-default")] {
+    if #[cfg(all(feature = "provider-tls-default", not(target_family = "wasm")))] {
         #[cfg_attr(docsrs, doc(cfg(feature = "provider-tls-default")))]
         pub mod default {
             //! Provides the recommended implementation of TLS using platform detection
@@ -42,153 +44,235 @@ cfg_if! {
 #[derive(Debug, Default)]
 pub struct Default;
 
-impl Provider for Default {
-    type Server = default::Server;
-    type Client = default::Client;
-    type Error = core::convert::Infallible;
+// wasm targets don't have a default TLS implementation, so one must be configured explicitly
+cfg_if! {
+    if #[cfg(any(
+        not(target_family = "wasm"),
+        feature = "provider-tls-rustls",
+        feature = "provider-tls-s2n"
+    ))] {
+        impl Provider for Default {
+            type Server = default::Server;
+            type Client = default::Client;
+            type Error = core::convert::Infallible;
 
-    fn start_server(self) -> Result<Self::Server, Self::Error> {
-        Ok(Self::Server::default())
-    }
+            fn start_server(self) -> Result<Self::Server, Self::Error> {
+                Ok(Self::Server::default())
+            }
 
-    fn start_client(self) -> Result<Self::Client, Self::Error> {
-        Ok(Self::Client::default())
-    }
-}
+            fn start_client(self) -> Result<Self::Client, Self::Error> {
+                Ok(Self::Client::default())
+            }
+        }
 
-impl Provider for (&std::path::Path, &std::path::Path) {
-    type Server = <Default as Provider>::Server;
-    type Client = <Default as Provider>::Client;
-    type Error = Box<dyn std::error::Error + Send + Sync>;
+        impl Provider for (&std::path::Path, &std::path::Path) {
+            type Server = <Default as Provider>::Server;
+            type Client = <Default as Provider>::Client;
+            type Error = Box<dyn std::error::Error + Send + Sync>;
 
-    fn start_server(self) -> Result<Self::Server, Self::Error> {
-        let server = default::Server::builder()
-            .with_certificate(self.0, self.1)?
-            .build()?;
+            fn start_server(self) -> Result<Self::Server, Self::Error> {
+                let server = default::Server::builder()
+                    .with_certificate(self.0, self.1)?
+                    .build()?;
 
-        Ok(server)
-    }
+                Ok(server)
+            }
 
-    fn start_client(self) -> Result<Self::Client, Self::Error> {
-        // TODO support private key
-        let client = default::Client::builder()
-            .with_certificate(self.0)?
-            .build()?;
+            fn start_client(self) -> Result<Self::Client, Self::Error> {
+                // TODO support private key
+                let client = default::Client::builder()
+                    .with_certificate(self.0)?
+                    .build()?;
 
-        Ok(client)
-    }
-}
+                Ok(client)
+            }
+        }
 
-impl Provider for &std::path::Path {
-    type Server = <Default as Provider>::Server;
-    type Client = <Default as Provider>::Client;
-    type Error = Box<dyn std::error::Error + Send + Sync>;
+        impl Provider for &std::path::Path {
+            type Server = <Default as Provider>::Server;
+            type Client = <Default as Provider>::Client;
+            type Error = Box<dyn std::error::Error + Send + Sync>;
 
-    fn start_server(self) -> Result<Self::Server, Self::Error> {
-        let empty_cert: &[u8] = &[];
-        let server = default::Server::builder()
-            .with_certificate(empty_cert, self)?
-            .build()?;
+            fn start_server(self) -> Result<Self::Server, Self::Error> {
+                let empty_cert: &[u8] = &[];
+                let server = default::Server::builder()
+                    .with_certificate(empty_cert, self)?
+                    .build()?;
 
-        Ok(server)
-    }
+                Ok(server)
+            }
 
-    fn start_client(self) -> Result<Self::Client, Self::Error> {
-        let client = default::Client::builder().with_certificate(self)?.build()?;
+            fn start_client(self) -> Result<Self::Client, Self::Error> {
+                let client = default::Client::builder().with_certificate(self)?.build()?;
 
-        Ok(client)
-    }
-}
+                Ok(client)
+            }
+        }
 
-impl Provider for (&[u8], &[u8]) {
-    type Server = <Default as Provider>::Server;
-    type Client = <Default as Provider>::Client;
-    type Error = Box<dyn std::error::Error + Send + Sync>;
+        impl Provider for (&[u8], &[u8]) {
+            type Server = <Default as Provider>::Server;
+            type Client = <Default as Provider>::Client;
+            type Error = Box<dyn std::error::Error + Send + Sync>;
 
-    fn start_server(self) -> Result<Self::Server, Self::Error> {
-        let server = default::Server::builder()
-            .with_certificate(self.0, self.1)?
-            .build()?;
+            fn start_server(self) -> Result<Self::Server, Self::Error> {
+                let server = default::Server::builder()
+                    .with_certificate(self.0, self.1)?
+                    .build()?;
 
-        Ok(server)
-    }
+                Ok(server)
+            }
 
-    fn start_client(self) -> Result<Self::Client, Self::Error> {
-        // TODO support private key
-        let client = default::Client::builder()
-            .with_certificate(self.0)?
-            .build()?;
+            fn start_client(self) -> Result<Self::Client, Self::Error> {
+                // TODO support private key
+                let client = default::Client::builder()
+                    .with_certificate(self.0)?
+                    .build()?;
 
-        Ok(client)
-    }
-}
+                Ok(client)
+            }
+        }
+
+        impl Provider for &[u8] {
+            type Server = <Default as Provider>::Server;
+            type Client = <Default as Provider>::Client;
+            type Error = Box<dyn std::error::Error + Send + Sync>;
 
-impl Provider for &[u8] {
-    type Server = <Default as Provider>::Server;
-    type Client = <Default as Provider>::Client;
-    type Error = Box<dyn std::error::Error + Send + Sync>;
+            fn start_server(self) -> Result<Self::Server, Self::Error> {
+                let empty_cert = &[][..];
+                let server = default::Server::builder()
+                    .with_certificate(empty_cert, self)?
+                    .build()?;
 
-    fn start_server(self) -> Result<Self::Server, Self::Error> {
-        let empty_cert = &[][..];
-        let server = default::Server::builder()
-            .with_certificate(empty_cert, self)?
-            .build()?;
+                Ok(server)
+            }
 
-        Ok(server)
-    }
+            fn start_client(self) -> Result<Self::Client, Self::Error> {
+                let client = default::Client::builder().with_certificate(self)?.build()?;
 
-    fn start_client(self) -> Result<Self::Client, Self::Error> {
-        let client = default::Client::builder().with_certificate(self)?.build()?;
+                Ok(client)
+            }
+        }
 
-        Ok(client)
-    }
-}
+        impl Provider for (&str, &str) {
+            type Server = <Default as Provider>::Server;
+            type Client = <Default as Provider>::Client;
+            type Error = Box<dyn std::error::Error + Send + Sync>;
+
+            fn start_server(self) -> Result<Self::Server, Self::Error> {
+                let server = default::Server::builder()
+                    .with_certificate(self.0, self.1)?
+                    .build()?;
 
-impl Provider for (&str, &str) {
-    type Server = <Default as Provider>::Server;
-    type Client = <Default as Provider>::Client;
-    type Error = Box<dyn std::error::Error + Send + Sync>;
+                Ok(server)
+            }
 
-    fn start_server(self) -> Result<Self::Server, Self::Error> {
-        let server = default::Server::builder()
-            .with_certificate(self.0, self.1)?
-            .build()?;
+            fn start_client(self) -> Result<Self::Client, Self::Error> {
+                // TODO support private key
+                let client = default::Client::builder()
+                    .with_certificate(self.0)?
+                    .build()?;
 
-        Ok(server)
-    }
+                Ok(client)
+            }
+        }
 
-    fn start_client(self) -> Result<Self::Client, Self::Error> {
-        // TODO support private key
-        let client = default::Client::builder()
-            .with_certificate(self.0)?
-            .build()?;
+        impl Provider for &str {
+            type Server = <Default as Provider>::Server;
+            type Client = <Default as Provider>::Client;
+            type Error = Box<dyn std::error::Error + Send + Sync>;
 
-        Ok(client)
-    }
-}
+            fn start_server(self) -> Result<Self::Server, Self::Error> {
+                let empty_cert = "";
+                let server = default::Server::builder()
+                    .with_certificate(empty_cert, self)?
+                    .build()?;
 
-impl Provider for &str {
-    type Server = <Default as Provider>::Server;
-    type Client = <Default as Provider>::Client;
-    type Error = Box<dyn std::error::Error + Send + Sync>;
+                Ok(server)
+            }
 
-    fn start_server(self) -> Result<Self::Server, Self::Error> {
-        let empty_cert = "";
-        let server = default::Server::builder()
-            .with_certificate(empty_cert, self)?
-            .build()?;
+            fn start_client(self) -> Result<Self::Client, Self::Error> {
+                let client = default::Client::builder().with_certificate(self)?.build()?;
 
-        Ok(server)
-    }
+                Ok(client)
+            }
+        }
+
+        /// Creates a TLS provider which selects a server certificate based on the client's SNI
+        ///
+        /// Each entry maps a server name pattern to a certificate and private key pair. Patterns can
+        /// either be an exact name (e.g. `example.com`) or a wildcard name (e.g. `*.example.com`) and
+        /// are checked in order. The first entry is used if the client doesn't send a server name or
+        /// none of the patterns match.
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # use std::{error::Error, path::Path};
+        /// use s2n_quic::{provider::tls, Server};
+        /// #
+        /// # fn main() -> Result<(), Box<dyn Error>> {
+        /// let example = (Path::new("./certs/example.pem"), Path::new("./certs/example.key"));
+        /// let internal = (Path::new("./certs/internal.pem"), Path::new("./certs/internal.key"));
+        ///
+        /// let server = Server::builder()
+        ///     .with_tls(tls::sni_map([("example.com", example), ("*.internal", internal)]))?
+        ///     .with_io("127.0.0.1:443")?
+        ///     .start()?;
+        /// #
+        /// #    Ok(())
+        /// # }
+        /// ```
+        pub fn sni_map<I, N, C, K>(entries: I) -> SniMap<C, K>
+        where
+            I: IntoIterator<Item = (N, (C, K))>,
+            N: Into<String>,
+        {
+            let entries = entries
+                .into_iter()
+                .map(|(name, (certificate, private_key))| (name.into(), certificate, private_key))
+                .collect();
+            SniMap { entries }
+        }
+
+        /// A TLS provider which selects a server certificate based on the client's SNI
+        ///
+        /// See [`sni_map`] for more details.
+        #[derive(Debug)]
+        pub struct SniMap<C, K> {
+            entries: Vec<(String, C, K)>,
+        }
 
-    fn start_client(self) -> Result<Self::Client, Self::Error> {
-        let client = default::Client::builder().with_certificate(self)?.build()?;
+        impl<C, K> Provider for SniMap<C, K>
+        where
+            C: default::certificate::IntoCertificate,
+            K: default::certificate::IntoPrivateKey,
+        {
+            type Server = <Default as Provider>::Server;
+            type Client = <Default as Provider>::Client;
+            type Error = Box<dyn std::error::Error + Send + Sync>;
 
-        Ok(client)
+            fn start_server(self) -> Result<Self::Server, Self::Error> {
+                if self.entries.is_empty() {
+                    return Err("at least one SNI certificate must be provided".into());
+                }
+
+                let mut server = default::Server::builder();
+
+                for (server_name, certificate, private_key) in self.entries {
+                    server = server.with_sni_certificate(server_name, certificate, private_key)?;
+                }
+
+                Ok(server.build()?)
+            }
+
+            fn start_client(self) -> Result<Self::Client, Self::Error> {
+                Err("cannot create a client from an SNI map".into())
+            }
+        }
     }
 }
 
-#[cfg(feature = "provider-tls-default")]
+#[cfg(all(feature = "provider-tls-default", not(target_family = "wasm")))]
 mod default_tls {
     pub use s2n_quic_tls_default::*;
 
@@ -234,7 +318,7 @@ mod default_tls {
         }
     }
 }
-#[cfg(not(feature = "provider-tls-default"))]
+#[cfg(not(all(feature = "provider-tls-default", not(target_family = "wasm"))))]
 mod default_tls {
     // TODO stub out default that fails with error when started
 }
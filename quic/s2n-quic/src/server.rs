@@ -2,14 +2,19 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{connection::Connection, provider::*};
+use bytes::Bytes;
 use core::{
     fmt,
     task::{Context, Poll},
 };
 use s2n_quic_transport::endpoint::handle::Acceptor;
+use std::collections::HashMap;
 
+pub mod alpn;
 mod builder;
 mod providers;
+#[cfg(target_os = "linux")]
+pub mod shard;
 
 pub use builder::*;
 pub use providers::*;
@@ -103,6 +108,39 @@ impl Server {
         })
     }
 
+    /// Splits the server into an [`alpn::Acceptor`] for each of the application `protocols`
+    ///
+    /// The application protocol is negotiated during the handshake, so it's known as soon as a
+    /// connection is accepted. Each acceptor only yields the connections which negotiated its
+    /// protocol, which allows multi-protocol servers to dispatch connections without inspecting
+    /// their streams. The `protocols` should match the ones configured on the TLS provider;
+    /// connections which negotiated any other protocol are closed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use std::{error::Error, path::Path};
+    /// # use s2n_quic::Server;
+    /// #
+    /// # fn main() -> Result<(), Box<dyn Error>> {
+    /// let server = Server::builder()
+    ///     .with_tls((Path::new("./certs/cert.pem"), Path::new("./certs/key.pem")))?
+    ///     .with_io("127.0.0.1:443")?
+    ///     .start()?;
+    ///
+    /// let mut acceptors = server.split_by_application_protocol(["h3", "custom"]);
+    /// let h3 = acceptors.remove(&b"h3"[..]).unwrap();
+    /// #
+    /// #    Ok(())
+    /// # }
+    /// ```
+    pub fn split_by_application_protocol<I: IntoIterator<Item = P>, P: AsRef<[u8]>>(
+        self,
+        protocols: I,
+    ) -> HashMap<Bytes, alpn::Acceptor> {
+        alpn::split(self, protocols)
+    }
+
     /// Returns the local address that this listener is bound to.
     ///
     /// This can be useful, for example, when binding to port `0` to figure out which
@@ -128,6 +166,15 @@ impl Server {
     pub fn local_addr(&self) -> Result<std::net::SocketAddr, std::io::Error> {
         Ok(self.local_addr.into())
     }
+
+    /// Notifies the server that the local network has changed
+    ///
+    /// This should be called when the platform reports a network change, such as an interface
+    /// going up or down or an address change. Each established connection immediately probes
+    /// its path to the peer rather than waiting to detect the change through packet loss.
+    pub fn notify_network_change(&self) {
+        self.acceptor.notify_network_change()
+    }
 }
 
 impl futures::stream::Stream for Server {
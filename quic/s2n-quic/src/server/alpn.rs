@@ -0,0 +1,246 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Dispatches accepted connections by their negotiated application protocol
+//!
+//! Servers which support several application protocols, such as HTTP/3 alongside a custom
+//! protocol, can split the [`Server`] into one [`Acceptor`] per protocol with
+//! [`Server::split_by_application_protocol`]. Each acceptor only yields the connections which
+//! negotiated its protocol, so the application doesn't need to inspect the first bytes of a
+//! connection to decide how to handle it.
+
+use super::Server;
+use crate::connection::Connection;
+use bytes::Bytes;
+use core::{
+    fmt,
+    task::{Context, Poll, Waker},
+};
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex, MutexGuard},
+    task::Wake,
+};
+
+/// Accepts the connections which negotiated a single application protocol
+///
+/// The acceptors created from a [`Server`] share it: whichever acceptor is polled accepts the
+/// next connection and hands it to the acceptor for its protocol. Connections which negotiated
+/// a protocol without an acceptor, for example because it was dropped, are closed.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # use std::{error::Error, path::Path};
+/// # use s2n_quic::Server;
+/// #
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn Error>> {
+/// let server = Server::builder()
+///     .with_tls((Path::new("./certs/cert.pem"), Path::new("./certs/key.pem")))?
+///     .with_io("127.0.0.1:443")?
+///     .start()?;
+///
+/// let mut acceptors = server.split_by_application_protocol(["h3", "custom"]);
+/// let mut h3 = acceptors.remove(&b"h3"[..]).unwrap();
+/// let mut custom = acceptors.remove(&b"custom"[..]).unwrap();
+///
+/// tokio::spawn(async move {
+///     while let Some(connection) = custom.accept().await {
+///         println!("custom connection: {:?}", connection.remote_addr());
+///     }
+/// });
+///
+/// while let Some(connection) = h3.accept().await {
+///     println!("h3 connection: {:?}", connection.remote_addr());
+/// }
+/// #
+/// #    Ok(())
+/// # }
+/// ```
+pub struct Acceptor {
+    protocol: Bytes,
+    shared: Arc<Shared>,
+}
+
+struct Shared {
+    state: Mutex<State>,
+    /// The wakers of the acceptors waiting for a connection
+    ///
+    /// These are kept separately from the state so the server can wake the acceptors while the
+    /// state is locked.
+    wakers: Mutex<HashMap<Bytes, Waker>>,
+}
+
+struct State {
+    server: Server,
+    queues: HashMap<Bytes, VecDeque<Connection>>,
+    is_closed: bool,
+}
+
+impl Shared {
+    fn state(&self) -> MutexGuard<State> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn wakers(&self) -> MutexGuard<HashMap<Bytes, Waker>> {
+        self.wakers
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Wakes all of the waiting acceptors when the server has a new connection
+impl Wake for Shared {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref()
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        let wakers = core::mem::take(&mut *self.wakers());
+        for waker in wakers.into_values() {
+            waker.wake();
+        }
+    }
+}
+
+pub(super) fn split<I: IntoIterator<Item = P>, P: AsRef<[u8]>>(
+    server: Server,
+    protocols: I,
+) -> HashMap<Bytes, Acceptor> {
+    let mut protocols: Vec<Bytes> = protocols
+        .into_iter()
+        .map(|protocol| Bytes::copy_from_slice(protocol.as_ref()))
+        .collect();
+    // each protocol can only have a single acceptor
+    protocols.sort();
+    protocols.dedup();
+
+    let shared = Arc::new(Shared {
+        state: Mutex::new(State {
+            server,
+            queues: protocols
+                .iter()
+                .map(|protocol| (protocol.clone(), VecDeque::new()))
+                .collect(),
+            is_closed: false,
+        }),
+        wakers: Mutex::new(HashMap::new()),
+    });
+
+    protocols
+        .into_iter()
+        .map(|protocol| {
+            let acceptor = Acceptor {
+                protocol: protocol.clone(),
+                shared: shared.clone(),
+            };
+            (protocol, acceptor)
+        })
+        .collect()
+}
+
+impl Acceptor {
+    /// Returns the application protocol of the connections yielded by this acceptor
+    #[inline]
+    pub fn application_protocol(&self) -> &Bytes {
+        &self.protocol
+    }
+
+    /// Accepts a new incoming [`Connection`] which negotiated the acceptor's protocol
+    ///
+    /// Returns `None` once the server has closed and all of the connections for the protocol
+    /// have been accepted.
+    pub async fn accept(&mut self) -> Option<Connection> {
+        futures::future::poll_fn(|cx| self.poll_accept(cx)).await
+    }
+
+    /// Attempts to accept a new incoming [`Connection`] which negotiated the acceptor's
+    /// protocol
+    ///
+    /// See [`Server::poll_accept`] for a description of the return values.
+    pub fn poll_accept(&mut self, cx: &mut Context) -> Poll<Option<Connection>> {
+        s2n_quic_core::task::waker::debug_assert_contract(cx, |cx| {
+            let mut state = self.shared.state();
+
+            loop {
+                if let Some(connection) = state
+                    .queues
+                    .get_mut(&self.protocol)
+                    .and_then(VecDeque::pop_front)
+                {
+                    return Poll::Ready(Some(connection));
+                }
+
+                if state.is_closed {
+                    return Poll::Ready(None);
+                }
+
+                // register the waker before polling the server so a connection accepted by
+                // another acceptor in the meantime isn't missed
+                self.shared
+                    .wakers()
+                    .insert(self.protocol.clone(), cx.waker().clone());
+
+                let waker = Waker::from(self.shared.clone());
+                let mut server_cx = Context::from_waker(&waker);
+
+                match state.server.poll_accept(&mut server_cx) {
+                    Poll::Ready(Some(connection)) => {
+                        let protocol = connection.application_protocol().ok();
+                        let queue = protocol
+                            .as_ref()
+                            .and_then(|protocol| state.queues.get_mut(protocol));
+
+                        // connections without an acceptor are closed by dropping them
+                        if let Some(queue) = queue {
+                            queue.push_back(connection);
+                        }
+
+                        if let Some(waker) = protocol
+                            .filter(|protocol| *protocol != self.protocol)
+                            .and_then(|protocol| self.shared.wakers().remove(&protocol))
+                        {
+                            waker.wake();
+                        }
+                    }
+                    Poll::Ready(None) => {
+                        state.is_closed = true;
+                        self.shared.wake_by_ref();
+                    }
+                    Poll::Pending => return Poll::Pending,
+                }
+            }
+        })
+    }
+}
+
+impl Drop for Acceptor {
+    fn drop(&mut self) {
+        // close the connections which were waiting to be accepted
+        let queue = self.shared.state().queues.remove(&self.protocol);
+        self.shared.wakers().remove(&self.protocol);
+        drop(queue);
+    }
+}
+
+impl fmt::Debug for Acceptor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Acceptor")
+            .field("application_protocol", &self.protocol)
+            .finish()
+    }
+}
+
+impl futures::stream::Stream for Acceptor {
+    type Item = Connection;
+
+    fn poll_next(
+        mut self: core::pin::Pin<&mut Self>,
+        cx: &mut core::task::Context<'_>,
+    ) -> core::task::Poll<Option<Self::Item>> {
+        self.poll_accept(cx)
+    }
+}
@@ -343,6 +343,45 @@ impl<Providers: ServerProviders> Builder<Providers> {
         ServerProviders
     );
 
+    #[cfg(any(test, feature = "unstable-provider-accept-filter"))]
+    impl_provider_method!(
+        /// Sets the accept filter provider for the [`Server`]
+        ///
+        /// The filter is invoked before any handshake work is performed for a connection
+        /// attempt.
+        with_accept_filter,
+        accept_filter,
+        ServerProviders
+    );
+
+    #[cfg(any(test, feature = "unstable-provider-address-attestation"))]
+    impl_provider_method!(
+        /// Sets the address attestation provider for the [`Server`]
+        ///
+        /// Clients with an attested address are not sent a Retry packet.
+        with_address_attestation,
+        address_attestation,
+        ServerProviders
+    );
+
+    #[cfg(any(test, feature = "unstable-provider-compatibility"))]
+    impl_provider_method!(
+        /// Sets the compatibility provider for the [`Server`]
+        with_compatibility,
+        compatibility,
+        ServerProviders
+    );
+
+    #[cfg(any(test, feature = "unstable-provider-path-cache"))]
+    impl_provider_method!(
+        /// Sets the path cache provider for the [`Server`]
+        ///
+        /// The cache is shared by all of the connections on the endpoint.
+        with_path_cache,
+        path_cache,
+        ServerProviders
+    );
+
     #[cfg(any(test, feature = "unstable-provider-random"))]
     impl_provider_method!(
         /// Sets the random provider for the [`Server`]
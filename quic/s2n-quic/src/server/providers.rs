@@ -5,6 +5,7 @@ use super::*;
 use core::marker::PhantomData;
 use s2n_quic_core::{connection::id::Generator, crypto, path};
 use s2n_quic_transport::{connection, endpoint, stream};
+use std::sync::Arc;
 
 impl_providers_state! {
     #[derive(Debug, Default)]
@@ -26,6 +27,10 @@ impl_providers_state! {
         address_token: AddressToken,
         datagram: Datagram,
         dc: Dc,
+        compatibility: Compatibility,
+        path_cache: PathCache,
+        accept_filter: AcceptFilter,
+        address_attestation: AddressAttestation,
     }
 
     /// Opaque trait containing all of the configured providers
@@ -50,6 +55,10 @@ impl<
         AddressToken: address_token::Provider,
         Datagram: datagram::Provider,
         Dc: dc::Provider,
+        Compatibility: compatibility::Provider,
+        PathCache: path_cache::Provider,
+        AcceptFilter: accept_filter::Provider,
+        AddressAttestation: address_attestation::Provider,
     >
     Providers<
         CongestionController,
@@ -69,6 +78,10 @@ impl<
         AddressToken,
         Datagram,
         Dc,
+        Compatibility,
+        PathCache,
+        AcceptFilter,
+        AddressAttestation,
     >
 {
     pub fn start(self) -> Result<Server, StartError> {
@@ -90,6 +103,10 @@ impl<
             tls,
             datagram,
             dc,
+            compatibility,
+            path_cache,
+            accept_filter,
+            address_attestation,
         } = self;
 
         let congestion_controller = congestion_controller.start().map_err(StartError::new)?;
@@ -110,6 +127,10 @@ impl<
         let tls = tls.start_server().map_err(StartError::new)?;
         let datagram = datagram.start().map_err(StartError::new)?;
         let dc = dc.start().map_err(StartError::new)?;
+        let compatibility = Arc::new(compatibility.start().map_err(StartError::new)?);
+        let path_cache = Arc::new(path_cache.start().map_err(StartError::new)?);
+        let accept_filter = accept_filter.start().map_err(StartError::new)?;
+        let address_attestation = address_attestation.start().map_err(StartError::new)?;
 
         // Validate providers
         // TODO: Add more validation https://github.com/aws/s2n-quic/issues/285
@@ -142,6 +163,10 @@ impl<
             path_migration,
             datagram,
             dc,
+            compatibility,
+            path_cache,
+            accept_filter,
+            address_attestation,
         };
 
         let (endpoint, acceptor) = endpoint::Endpoint::new_server(endpoint_config);
@@ -175,6 +200,10 @@ struct EndpointConfig<
     AddressToken,
     Datagram,
     Dc,
+    Compatibility,
+    PathCache,
+    AcceptFilter,
+    AddressAttestation,
 > {
     congestion_controller: CongestionController,
     connection_close_formatter: ConnectionCloseFormatter,
@@ -193,6 +222,10 @@ struct EndpointConfig<
     path_migration: PathMigration,
     datagram: Datagram,
     dc: Dc,
+    compatibility: Arc<Compatibility>,
+    path_cache: Arc<PathCache>,
+    accept_filter: AcceptFilter,
+    address_attestation: AddressAttestation,
 }
 
 impl<
@@ -213,6 +246,10 @@ impl<
         AddressToken: address_token::Format,
         Datagram: s2n_quic_core::datagram::Endpoint,
         Dc: s2n_quic_core::dc::Endpoint,
+        Compatibility: s2n_quic_core::connection::compatibility::Policy,
+        PathCache: s2n_quic_core::path::cache::Cache,
+        AcceptFilter: s2n_quic_core::endpoint::accept_filter::Filter,
+        AddressAttestation: s2n_quic_core::endpoint::address_attestation::Validator,
     > core::fmt::Debug
     for EndpointConfig<
         CongestionController,
@@ -232,6 +269,10 @@ impl<
         AddressToken,
         Datagram,
         Dc,
+        Compatibility,
+        PathCache,
+        AcceptFilter,
+        AddressAttestation,
     >
 {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -257,6 +298,10 @@ impl<
         AddressToken: address_token::Format,
         Datagram: s2n_quic_core::datagram::Endpoint,
         Dc: s2n_quic_core::dc::Endpoint,
+        Compatibility: s2n_quic_core::connection::compatibility::Policy,
+        PathCache: s2n_quic_core::path::cache::Cache,
+        AcceptFilter: s2n_quic_core::endpoint::accept_filter::Filter,
+        AddressAttestation: s2n_quic_core::endpoint::address_attestation::Validator,
     > endpoint::Config
     for EndpointConfig<
         CongestionController,
@@ -276,6 +321,10 @@ impl<
         AddressToken,
         Datagram,
         Dc,
+        Compatibility,
+        PathCache,
+        AcceptFilter,
+        AddressAttestation,
     >
 {
     type ConnectionIdFormat = ConnectionID;
@@ -288,6 +337,8 @@ impl<
     type ConnectionLock = std::sync::Mutex<Self::Connection>;
     type CongestionControllerEndpoint = CongestionController;
     type EndpointLimits = EndpointLimits;
+    type AcceptFilter = AcceptFilter;
+    type AddressAttestation = AddressAttestation;
     type EventSubscriber = Event;
     type TLSEndpoint = Tls;
     type TokenFormat = AddressToken;
@@ -298,6 +349,8 @@ impl<
     type PacketInterceptor = PacketInterceptor;
     type DatagramEndpoint = Datagram;
     type DcEndpoint = Dc;
+    type CompatibilityPolicy = Compatibility;
+    type PathCache = PathCache;
 
     const ENDPOINT_TYPE: endpoint::Type = endpoint::Type::Server;
 
@@ -311,6 +364,8 @@ impl<
             random_generator: &mut self.random,
             tls: &mut self.tls,
             endpoint_limits: &mut self.endpoint_limits,
+            accept_filter: &mut self.accept_filter,
+            address_attestation: &mut self.address_attestation,
             token: &mut self.address_token,
             connection_limits: &mut self.limits,
             mtu: &mut self.mtu,
@@ -318,6 +373,8 @@ impl<
             path_migration: &mut self.path_migration,
             datagram: &mut self.datagram,
             dc: &mut self.dc,
+            compatibility: &self.compatibility,
+            path_cache: &self.path_cache,
         }
     }
 }
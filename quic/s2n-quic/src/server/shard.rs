@@ -0,0 +1,314 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Runs a server as multiple endpoints which share a port
+//!
+//! A single endpoint processes all of its connections on one task. Servers which need to scale
+//! across cores can instead start a [`Shard`] per core, each with its own endpoint and socket
+//! bound to the same port with `SO_REUSEPORT`. Packets are steered to the shard which issued
+//! their destination connection ID by a program attached to the sockets, so connections stay on
+//! the same shard even after the peer migrates to a new address.
+//!
+//! The connections from all of the shards can be accepted with a single [`Sharded`] handle, or
+//! each shard can be accepted from independently with [`Sharded::into_servers`].
+//!
+//! The program selects a socket by its position in the reuse port group. When a socket is
+//! closed, the kernel moves the last socket of the group into its position, which would steer
+//! connections to the wrong shard. The sockets of all of the shards are therefore kept open by a
+//! [`Group`] until it is dropped, even after the server of a shard closes. Packets for the
+//! connection IDs of a closed shard are dropped, rather than delivered to another shard.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! # use std::{error::Error, path::Path};
+//! # use s2n_quic::{server::shard, Server};
+//! #
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn Error>> {
+//! let mut server = shard::Builder::new("0.0.0.0:443".parse()?)
+//!     .with_shard_count(4)?
+//!     .start(|shard| -> Result<Server, Box<dyn Error>> {
+//!         let server = Server::builder()
+//!             .with_tls((Path::new("./certs/cert.pem"), Path::new("./certs/key.pem")))?
+//!             .with_io(shard.io()?.build()?)?
+//!             .with_connection_id(shard.connection_id())?
+//!             .start()?;
+//!         Ok(server)
+//!     })?;
+//!
+//! while let Some(connection) = server.accept().await {
+//!     println!("new connection: {:?}", connection.remote_addr());
+//! }
+//! #
+//! #    Ok(())
+//! # }
+//! ```
+
+use super::Server;
+use crate::{
+    connection::Connection,
+    provider::{connection_id, io::tokio},
+};
+use core::{
+    fmt,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use s2n_quic_platform::socket::{options::Options, shard};
+use std::{
+    io,
+    net::{SocketAddr, UdpSocket},
+};
+
+pub use s2n_quic_platform::{socket::shard::MAX_SHARDS, syscall::pin_current_thread};
+
+/// Configures and starts a [`Sharded`] server
+#[derive(Debug)]
+pub struct Builder {
+    addr: SocketAddr,
+    count: usize,
+    handles: Vec<::tokio::runtime::Handle>,
+}
+
+impl Builder {
+    /// Creates a builder for shards bound to `addr`
+    ///
+    /// By default, a shard is started for each of the available CPUs.
+    pub fn new(addr: SocketAddr) -> Self {
+        let count = std::thread::available_parallelism()
+            .map_or(1, |count| count.get())
+            .min(MAX_SHARDS);
+
+        Self {
+            addr,
+            count,
+            handles: Vec::new(),
+        }
+    }
+
+    /// Sets the number of shards to start
+    ///
+    /// The count must be between 1 and [`MAX_SHARDS`].
+    pub fn with_shard_count(mut self, count: usize) -> io::Result<Self> {
+        if !(1..=MAX_SHARDS).contains(&count) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("shard count must be between 1 and {MAX_SHARDS}"),
+            ));
+        }
+        self.count = count;
+        Ok(self)
+    }
+
+    /// Sets the runtimes which drive the IO of the shards
+    ///
+    /// Shards are assigned to the runtimes in a round-robin fashion, so providing a
+    /// single-threaded runtime per core, with its thread pinned by [`pin_current_thread`], runs
+    /// each shard on its own core. By default, the IO of all of the shards is driven by the
+    /// runtime which calls [`Builder::start`].
+    #[must_use]
+    pub fn with_runtime_handles<I: IntoIterator<Item = ::tokio::runtime::Handle>>(
+        mut self,
+        handles: I,
+    ) -> Self {
+        self.handles = handles.into_iter().collect();
+        self
+    }
+
+    /// Binds the sockets and starts a server for each of the shards with `start`
+    ///
+    /// The server for each shard must be configured with the shard's [`io`](Shard::io) and
+    /// [`connection_id`](Shard::connection_id) providers.
+    pub fn start<F, E>(self, mut start: F) -> Result<Sharded, E>
+    where
+        F: FnMut(&Shard) -> Result<Server, E>,
+        E: From<io::Error>,
+    {
+        let sockets = shard::bind(Options::new(self.addr), self.count)?;
+
+        let mut servers = Vec::with_capacity(sockets.len());
+
+        for (index, socket) in sockets.iter().enumerate() {
+            let handle = if self.handles.is_empty() {
+                None
+            } else {
+                Some(self.handles[index % self.handles.len()].clone())
+            };
+
+            let shard = Shard {
+                index,
+                count: self.count,
+                socket,
+                handle,
+            };
+
+            servers.push(Some(start(&shard)?));
+        }
+
+        Ok(Sharded {
+            servers,
+            next: 0,
+            group: Group { sockets },
+        })
+    }
+}
+
+/// The configuration of a single shard
+#[derive(Debug)]
+pub struct Shard<'a> {
+    index: usize,
+    count: usize,
+    socket: &'a UdpSocket,
+    handle: Option<::tokio::runtime::Handle>,
+}
+
+impl Shard<'_> {
+    /// Returns the index of the shard
+    #[inline]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the total number of shards
+    #[inline]
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Returns an IO provider builder which uses the shard's socket
+    ///
+    /// Additional options can be configured on the builder, as long as the socket isn't replaced.
+    pub fn io(&self) -> io::Result<tokio::Builder> {
+        let mut builder = tokio::Builder::default()
+            .with_rx_socket(self.socket.try_clone()?)?
+            // GRO is not compatible with the steering program
+            .with_gro_disabled()?;
+
+        if let Some(handle) = self.handle.clone() {
+            builder = builder.with_handle(handle);
+        }
+
+        Ok(builder)
+    }
+
+    /// Returns a connection ID provider which issues IDs that are steered to the shard
+    pub fn connection_id(&self) -> connection_id::default::Format {
+        connection_id::default::Format::builder()
+            .with_shard(self.index as u8)
+            .and_then(|builder| builder.build())
+            .expect("the default format is infallible")
+    }
+}
+
+/// Keeps the sockets of all of the shards open in the reuse port group
+///
+/// The group should be held until all of the shards have closed. See the [module
+/// documentation](self) for more details.
+#[derive(Debug)]
+pub struct Group {
+    sockets: Vec<UdpSocket>,
+}
+
+impl Group {
+    /// Returns the number of sockets in the group
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.sockets.len()
+    }
+
+    /// Returns `true` if the group has no sockets
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.sockets.is_empty()
+    }
+}
+
+/// Accepts connections from all of the shards
+///
+/// The shards are polled in a round-robin fashion, so a busy shard doesn't starve the others.
+pub struct Sharded {
+    servers: Vec<Option<Server>>,
+    next: usize,
+    group: Group,
+}
+
+impl fmt::Debug for Sharded {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Sharded")
+            .field("local_addr", &self.local_addr().ok())
+            .field("shards", &self.servers.len())
+            .finish()
+    }
+}
+
+impl Sharded {
+    /// Accepts a new incoming [`Connection`] from any of the shards
+    ///
+    /// Returns `None` once all of the shards have closed.
+    pub async fn accept(&mut self) -> Option<Connection> {
+        futures::future::poll_fn(|cx| self.poll_accept(cx)).await
+    }
+
+    /// Attempts to accept a new incoming [`Connection`] from any of the shards
+    ///
+    /// See [`Server::poll_accept`] for the return values.
+    pub fn poll_accept(&mut self, cx: &mut Context) -> Poll<Option<Connection>> {
+        let len = self.servers.len();
+        let mut is_open = false;
+
+        for offset in 0..len {
+            let index = (self.next + offset) % len;
+            let Some(server) = self.servers[index].as_mut() else {
+                continue;
+            };
+
+            match server.poll_accept(cx) {
+                Poll::Ready(Some(connection)) => {
+                    // start with the next shard on the following call
+                    self.next = (index + 1) % len;
+                    return Poll::Ready(Some(connection));
+                }
+                Poll::Ready(None) => {
+                    self.servers[index] = None;
+                }
+                Poll::Pending => {
+                    is_open = true;
+                }
+            }
+        }
+
+        if is_open {
+            Poll::Pending
+        } else {
+            Poll::Ready(None)
+        }
+    }
+
+    /// Returns the local address that the shards are bound to
+    pub fn local_addr(&self) -> Result<SocketAddr, io::Error> {
+        self.servers
+            .iter()
+            .flatten()
+            .next()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotConnected, "all shards are closed"))?
+            .local_addr()
+    }
+
+    /// Returns the server for each of the open shards, along with the [`Group`] which keeps
+    /// their sockets open
+    ///
+    /// This can be used to accept connections on the same core as the shard which received them.
+    pub fn into_servers(self) -> (Vec<Server>, Group) {
+        let servers = self.servers.into_iter().flatten().collect();
+        (servers, self.group)
+    }
+}
+
+impl futures::stream::Stream for Sharded {
+    type Item = Connection;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.poll_accept(cx)
+    }
+}
@@ -15,6 +15,10 @@ mod bidirectional;
 mod local;
 mod peer;
 
+#[cfg(any(test, feature = "unstable-stream-compression"))]
+#[cfg_attr(docsrs, doc(cfg(feature = "unstable-stream-compression")))]
+pub mod compression;
+
 pub use s2n_quic_core::stream::{StreamError as Error, StreamType as Type};
 
 pub use bidirectional::*;
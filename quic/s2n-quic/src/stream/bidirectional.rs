@@ -4,6 +4,11 @@
 use s2n_quic_transport::stream::Stream;
 
 /// A QUIC stream that may send and receive data.
+///
+/// The stream implements the `AsyncRead` and `AsyncWrite` traits from both [`tokio::io`] and
+/// [`futures::io`], so it can be used directly with codecs that require both, such as
+/// `tokio_util::codec::Framed`. The stream can also be [split](Self::split) into halves which
+/// implement the individual traits.
 #[derive(Debug)]
 pub struct BidirectionalStream(Stream);
 
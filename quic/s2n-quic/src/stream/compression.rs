@@ -0,0 +1,61 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+//! Transparent per-stream compression of stream data
+//!
+//! Compression is opted into on a per-stream basis with
+//! [`SendStream::set_encoder`](crate::stream::SendStream::set_encoder) and
+//! [`ReceiveStream::set_decoder`](crate::stream::ReceiveStream::set_decoder). The stream applies
+//! the codec below the application API: the application continues to send and receive
+//! uncompressed data, while the compressed data is what gets buffered, packetized and counted
+//! against flow control.
+//!
+//! Codecs which rely on a shared dictionary should only be used if the peer supports the same
+//! dictionary. Each endpoint advertises the identifier of its dictionary with
+//! [`Limits::with_compression_dictionary_id`](crate::provider::limits::Limits::with_compression_dictionary_id)
+//! and the negotiated identifier is available with
+//! [`compression_dictionary_id`](crate::connection::Handle::compression_dictionary_id).
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! # use s2n_quic::stream::compression::Codec;
+//! # async fn test<C: Codec>(codec: C) -> Result<(), Box<dyn std::error::Error>> {
+//! #   let mut connection: s2n_quic::connection::Connection = todo!();
+//! #
+//! let mut stream = connection.open_send_stream().await?;
+//! stream.set_encoder(codec.encoder())?;
+//!
+//! stream.send(bytes::Bytes::from_static(b"hello")).await?;
+//! stream.close().await?;
+//! #
+//! #   Ok(())
+//! # }
+//! ```
+
+pub use s2n_quic_core::stream::compression::{Decoder, Encoder};
+
+/// Creates the [`Encoder`]s and [`Decoder`]s for compressed streams
+pub trait Codec: 'static + Send + Sync {
+    type Encoder: Encoder;
+    type Decoder: Decoder;
+
+    /// Returns the identifier of the shared dictionary required by the codec, if any
+    fn dictionary_id(&self) -> Option<u64> {
+        None
+    }
+
+    /// Creates an encoder for a single stream
+    fn encoder(&self) -> Self::Encoder;
+
+    /// Creates a decoder for a single stream
+    fn decoder(&self) -> Self::Decoder;
+
+    /// Returns `true` if the codec can be used on a connection with the negotiated dictionary
+    fn is_supported(&self, negotiated_dictionary_id: Option<u64>) -> bool {
+        match self.dictionary_id() {
+            Some(id) => negotiated_dictionary_id == Some(id),
+            None => true,
+        }
+    }
+}
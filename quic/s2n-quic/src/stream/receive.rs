@@ -4,6 +4,9 @@
 use s2n_quic_transport::stream;
 
 /// A QUIC stream that is only allowed to receive data.
+///
+/// The stream implements [`tokio::io::AsyncRead`] and [`futures::io::AsyncRead`], so it can be
+/// used with existing readers and codecs without an adapter type.
 #[derive(Debug)]
 pub struct ReceiveStream(stream::ReceiveStream);
 
@@ -71,6 +74,80 @@ macro_rules! impl_receive_stream_api {
             $dispatch_body
         }
 
+        /// Receives a chunk of data from a partially reliable stream.
+        ///
+        /// Along with the chunk, this returns the range of stream offsets which the peer gave up
+        /// on right before it, if any. The data in the range was sent with an expiration, such as
+        /// with [`SendStream::send_expiring`](crate::stream::SendStream::send_expiring), and is
+        /// never delivered. Once the stream finished, the range precedes the end of the stream
+        /// instead.
+        ///
+        /// Partial reliability needs to be enabled on both endpoints with
+        /// [`Limits::with_partial_reliability`](crate::provider::limits::Limits::with_partial_reliability).
+        ///
+        /// # Return value
+        ///
+        /// The function returns:
+        ///
+        /// - `Ok((expired, Some(chunk)))` if the stream is open and data was available.
+        /// - `Ok((expired, None))` if the stream was finished and all of the data was consumed.
+        /// - `Err(e)` if the stream encountered a [`stream::Error`](crate::stream::Error).
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::stream::Result<()> {
+        /// #   let mut stream: s2n_quic::stream::ReceiveStream = todo!();
+        /// #
+        /// loop {
+        ///     let (expired, chunk) = stream.receive_expiring().await?;
+        ///
+        ///     if let Some(expired) = expired {
+        ///         println!("skipped: {:?}", expired);
+        ///     }
+        ///
+        ///     let Some(chunk) = chunk else {
+        ///         break;
+        ///     };
+        ///     println!("received: {:?}", chunk);
+        /// }
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub async fn receive_expiring(
+            &mut self,
+        ) -> $crate::stream::Result<(Option<core::ops::Range<u64>>, Option<bytes::Bytes>)> {
+            ::futures::future::poll_fn(|cx| self.poll_receive_expiring(cx)).await
+        }
+
+        /// Poll for a chunk of data from a partially reliable stream.
+        ///
+        /// See [`Self::receive_expiring`] for more details on the expired ranges and
+        /// [`Self::poll_receive`] for a description of the return values.
+        #[inline]
+        pub fn poll_receive_expiring(
+            &mut self,
+            cx: &mut core::task::Context,
+        ) -> core::task::Poll<
+            $crate::stream::Result<(Option<core::ops::Range<u64>>, Option<bytes::Bytes>)>,
+        > {
+            macro_rules! $dispatch {
+                () => {
+                    Err($crate::stream::Error::non_readable()).into()
+                };
+                ($variant: expr) => {
+                    s2n_quic_core::task::waker::debug_assert_contract(cx, |cx| {
+                        $variant.poll_receive_expiring(cx)
+                    })
+                };
+            }
+
+            let $stream = self;
+            $dispatch_body
+        }
+
         /// Receives a slice of chunks of data from the stream.
         ///
         /// This can be more efficient than calling [`receive`](Self::receive) for each chunk,
@@ -208,6 +285,135 @@ macro_rules! impl_receive_stream_api {
             $dispatch_body
         }
 
+        /// Sets the maximum amount of time a receive operation may wait for data.
+        ///
+        /// Once a receive operation has been waiting for longer than the `timeout`, it fails with
+        /// a [`stream::Error::Timeout`](crate::stream::Error::Timeout). The stream remains usable
+        /// and the operation can be retried. Unlike wrapping the operation in an external timer,
+        /// the deadline is tracked by the stream itself.
+        ///
+        /// The timeout is disabled by default. Passing `None` disables it again.
+        ///
+        /// # Return value
+        ///
+        /// The function returns:
+        /// - `Ok(())` if the timeout was set successfully.
+        /// - `Err(e)` if the stream encountered a [`stream::Error`](crate::stream::Error). The
+        ///   stream may have been reset previously, or the connection itself was closed.
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::stream::Result<()> {
+        /// #   let mut stream: s2n_quic::stream::ReceiveStream = todo!();
+        /// #
+        /// stream.set_read_timeout(Some(core::time::Duration::from_secs(5)))?;
+        ///
+        /// match stream.receive().await {
+        ///     Ok(chunk) => println!("received {chunk:?}"),
+        ///     Err(s2n_quic::stream::Error::Timeout { .. }) => println!("the peer is unresponsive"),
+        ///     Err(err) => return Err(err),
+        /// }
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub fn set_read_timeout(
+            &mut self,
+            timeout: Option<core::time::Duration>,
+        ) -> $crate::stream::Result<()> {
+            macro_rules! $dispatch {
+                () => {
+                    Err($crate::stream::Error::non_readable())
+                };
+                ($variant: expr) => {
+                    $variant.set_read_timeout(timeout)
+                };
+            }
+
+            let $stream = self;
+            $dispatch_body
+        }
+
+        /// Sets the maximum amount of data the peer may send ahead of the application reading it.
+        ///
+        /// This limits the amount of data buffered on the stream by adjusting the flow control
+        /// window advertised to the peer. A larger size is advertised right away. Since the peer
+        /// may already be using the previously advertised window, a smaller size only takes
+        /// effect as the application reads the buffered data.
+        ///
+        /// By default, the stream uses the window configured for the connection with the
+        /// [`Limits`](crate::provider::limits::Limits) data window settings.
+        ///
+        /// # Return value
+        ///
+        /// The function returns:
+        /// - `Ok(())` if the size was set successfully.
+        /// - `Err(e)` if the stream encountered a [`stream::Error`](crate::stream::Error). The
+        ///   stream may have been reset previously, or the connection itself was closed. A size of
+        ///   `0` is rejected with a
+        ///   [`stream::Error::InvalidConfiguration`](crate::stream::Error::InvalidConfiguration).
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::stream::Result<()> {
+        /// #   let mut stream: s2n_quic::stream::ReceiveStream = todo!();
+        /// #
+        /// // allow the peer to send at most 16KB of unread data
+        /// stream.set_receive_buffer_size(16 * 1024)?;
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub fn set_receive_buffer_size(&mut self, size: u32) -> $crate::stream::Result<()> {
+            macro_rules! $dispatch {
+                () => {
+                    Err($crate::stream::Error::non_readable())
+                };
+                ($variant: expr) => {
+                    $variant.set_receive_buffer_size(size)
+                };
+            }
+
+            let $stream = self;
+            $dispatch_body
+        }
+
+        /// Decompresses all of the data received on the stream with the provided decoder.
+        ///
+        /// The application receives the decompressed data, while the flow control window and
+        /// watermarks refer to the compressed data. See the
+        /// [`compression`](crate::stream::compression) module for more details.
+        ///
+        /// # Return value
+        ///
+        /// The function returns:
+        /// - `Ok(())` if the decoder was set successfully.
+        /// - `Err(e)` if the stream encountered a [`stream::Error`](crate::stream::Error). An
+        ///   error is also returned if data was already read from the stream.
+        #[cfg(any(test, feature = "unstable-stream-compression"))]
+        #[cfg_attr(docsrs, doc(cfg(feature = "unstable-stream-compression")))]
+        #[inline]
+        pub fn set_decoder<D: $crate::stream::compression::Decoder>(
+            &mut self,
+            decoder: D,
+        ) -> $crate::stream::Result<()> {
+            macro_rules! $dispatch {
+                () => {
+                    Err($crate::stream::Error::non_readable())
+                };
+                ($variant: expr) => {
+                    $variant.set_decoder(Box::new(decoder))
+                };
+            }
+
+            let $stream = self;
+            $dispatch_body
+        }
+
         /// Create a batch request for receiving data
         #[inline]
         pub(crate) fn rx_request(
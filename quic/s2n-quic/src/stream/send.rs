@@ -4,6 +4,10 @@
 use s2n_quic_transport::stream;
 
 /// A QUIC stream that is only allowed to send data.
+///
+/// The stream implements [`tokio::io::AsyncWrite`] and [`futures::io::AsyncWrite`], so it can be
+/// used with existing writers and codecs without an adapter type. Shutting down the writer
+/// closes the stream.
 #[derive(Debug)]
 pub struct SendStream(stream::SendStream);
 
@@ -11,6 +15,11 @@ macro_rules! impl_send_stream_api {
     (| $stream:ident, $dispatch:ident | $dispatch_body:expr) => {
         /// Enqueues a chunk of data for sending it towards the peer.
         ///
+        /// The chunk is not copied. The stream keeps a reference to it until the peer has
+        /// acknowledged the data, so the underlying allocation can't be reused before then.
+        /// Applications which manage their own buffers can use [`Self::send_owned`] to be notified
+        /// when a buffer is released.
+        ///
         /// # Return value
         ///
         /// The function returns:
@@ -68,8 +77,135 @@ macro_rules! impl_send_stream_api {
             $dispatch_body
         }
 
+        /// Enqueues a chunk of data which expires after `lifetime` for sending it towards the peer.
+        ///
+        /// The lifetime starts when the chunk is enqueued. Once it has passed, the chunk is no
+        /// longer transmitted or retransmitted and the peer skips over it instead of waiting for
+        /// it. This avoids
+        /// head-of-line blocking on data which is no longer useful, such as late frames of a live
+        /// media stream. Data sent without an expiration is still delivered reliably, unless a
+        /// stream-wide expiration is configured with [`Self::set_default_expiration`].
+        ///
+        /// Partial reliability needs to be enabled on both endpoints with
+        /// [`Limits::with_partial_reliability`](crate::provider::limits::Limits::with_partial_reliability).
+        /// Otherwise, the chunk is delivered reliably.
+        ///
+        /// # Return value
+        ///
+        /// The function returns:
+        ///
+        /// - `Ok(())` if the data was enqueued for sending.
+        /// - `Err(e)` if the stream encountered a [`stream::Error`](crate::stream::Error).
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::stream::Result<()> {
+        /// #   let stream: s2n_quic::stream::SendStream = todo!();
+        /// #
+        /// let frame = bytes::Bytes::from_static(&[1, 2, 3, 4]);
+        /// // the frame is no longer useful after 100ms
+        /// stream
+        ///     .send_expiring(frame, core::time::Duration::from_millis(100))
+        ///     .await?;
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub async fn send_expiring(
+            &mut self,
+            mut data: bytes::Bytes,
+            lifetime: core::time::Duration,
+        ) -> $crate::stream::Result<()> {
+            ::futures::future::poll_fn(|cx| self.poll_send_expiring(&mut data, lifetime, cx)).await
+        }
+
+        /// Enqueues a chunk of data which expires after `lifetime` for sending it towards the peer.
+        ///
+        /// See [`Self::send_expiring`] for more details on expiring data and [`Self::poll_send`]
+        /// for a description of the return values.
+        #[inline]
+        pub fn poll_send_expiring(
+            &mut self,
+            chunk: &mut bytes::Bytes,
+            lifetime: core::time::Duration,
+            cx: &mut core::task::Context,
+        ) -> core::task::Poll<$crate::stream::Result<()>> {
+            macro_rules! $dispatch {
+                () => {
+                    Err($crate::stream::Error::non_writable()).into()
+                };
+                ($variant: expr) => {
+                    s2n_quic_core::task::waker::debug_assert_contract(cx, |cx| {
+                        $variant.poll_send_expiring(chunk, lifetime, cx)
+                    })
+                };
+            }
+
+            let $stream = self;
+            $dispatch_body
+        }
+
+        /// Sets the expiration of all of the data which is subsequently sent without an explicit
+        /// one.
+        ///
+        /// This applies the partial reliability of [`Self::send_expiring`] to every write on the
+        /// stream, including writes through [`Self::send`], [`Self::send_vectored`] and the
+        /// `AsyncWrite` implementations. The lifetime of each write starts when it's enqueued, so
+        /// data which is still waiting to be sent once its lifetime has passed is dropped as well.
+        /// Data which was already sent is not affected. Passing `None` delivers the following
+        /// data reliably again, which is the default.
+        ///
+        /// Partial reliability needs to be enabled on both endpoints with
+        /// [`Limits::with_partial_reliability`](crate::provider::limits::Limits::with_partial_reliability).
+        /// Otherwise, the data is delivered reliably.
+        ///
+        /// # Return value
+        ///
+        /// The function returns:
+        /// - `Ok(())` if the expiration was set successfully.
+        /// - `Err(e)` if the stream encountered a [`stream::Error`](crate::stream::Error). The
+        ///   stream may have been reset previously, or the connection itself was closed.
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::stream::Result<()> {
+        /// #   let mut stream: s2n_quic::stream::SendStream = todo!();
+        /// #
+        /// // each frame of the live stream is only useful for 100ms
+        /// stream.set_default_expiration(Some(core::time::Duration::from_millis(100)))?;
+        ///
+        /// let frame = bytes::Bytes::from_static(&[1, 2, 3, 4]);
+        /// stream.send(frame).await?;
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub fn set_default_expiration(
+            &mut self,
+            lifetime: Option<core::time::Duration>,
+        ) -> $crate::stream::Result<()> {
+            macro_rules! $dispatch {
+                () => {
+                    Err($crate::stream::Error::non_writable())
+                };
+                ($variant: expr) => {
+                    $variant.set_default_expiration(lifetime)
+                };
+            }
+
+            let $stream = self;
+            $dispatch_body
+        }
+
         /// Enqueues a slice of chunks of data for sending it towards the peer.
         ///
+        /// This avoids copying separately allocated parts of a message, such as a header and its
+        /// payload, into a single buffer before sending them.
+        ///
         /// # Return value
         ///
         /// The function returns:
@@ -146,6 +282,100 @@ macro_rules! impl_send_stream_api {
             $dispatch_body
         }
 
+        /// Enqueues an application-owned buffer for sending it towards the peer without copying it.
+        ///
+        /// The stream holds on to the buffer until all of its data has been acknowledged by the
+        /// peer or the stream is reset, after which the buffer is dropped. Applications which
+        /// manage a pool of buffers can return them to the pool in their [`Drop`] implementation.
+        ///
+        /// # Buffer reuse
+        ///
+        /// Data enqueued on a stream may need to be retransmitted, so the stream can't hand a
+        /// buffer back as soon as the call returns. Reusing the same allocation for each write
+        /// requires copying the data, which [`Self::send`] avoids for [`Bytes`](bytes::Bytes) by
+        /// taking a reference-counted handle instead. A pool can achieve the same with owned
+        /// buffers by reclaiming them once they're dropped by the stream.
+        ///
+        /// # Return value
+        ///
+        /// The function returns:
+        ///
+        /// - `Ok(())` if the buffer was enqueued for sending.
+        /// - `Err(e)` if the stream encountered a [`stream::Error`](crate::stream::Error).
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::stream::Result<()> {
+        /// #   let stream: s2n_quic::stream::SendStream = todo!();
+        /// #
+        /// struct Pooled(Vec<u8>);
+        ///
+        /// impl AsRef<[u8]> for Pooled {
+        ///     fn as_ref(&self) -> &[u8] {
+        ///         &self.0
+        ///     }
+        /// }
+        ///
+        /// impl Drop for Pooled {
+        ///     fn drop(&mut self) {
+        ///         // return the allocation to the pool
+        ///     }
+        /// }
+        ///
+        /// stream.send_owned(Pooled(vec![1, 2, 3, 4])).await?;
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub async fn send_owned<B>(&mut self, buffer: B) -> $crate::stream::Result<()>
+        where
+            B: AsRef<[u8]> + Send + 'static,
+        {
+            let mut buffer = Some(buffer);
+            ::futures::future::poll_fn(|cx| self.poll_send_owned(&mut buffer, cx)).await
+        }
+
+        /// Polls enqueueing an application-owned buffer for sending it towards the peer without
+        /// copying it.
+        ///
+        /// See [`Self::send_owned`] for details on when the buffer is dropped.
+        ///
+        /// # Return value
+        ///
+        /// The function returns:
+        ///
+        /// - `Poll::Pending` if the stream's send buffer capacity is currently exhausted. In this case,
+        ///   the caller should retry sending after the [`Waker`](core::task::Waker) on the provided
+        ///   [`Context`](core::task::Context) is notified. The `buffer` is left in place.
+        /// - `Poll::Ready(Ok(()))` if the buffer was enqueued for sending. The provided `buffer` will
+        ///   be replaced with `None`.
+        /// - `Poll::Ready(Err(e))` if the stream encountered a [`stream::Error`](crate::stream::Error).
+        #[inline]
+        pub fn poll_send_owned<B>(
+            &mut self,
+            buffer: &mut Option<B>,
+            cx: &mut core::task::Context,
+        ) -> core::task::Poll<$crate::stream::Result<()>>
+        where
+            B: AsRef<[u8]> + Send + 'static,
+        {
+            if buffer
+                .as_ref()
+                .map_or(true, |buffer| buffer.as_ref().is_empty())
+            {
+                *buffer = None;
+                return Ok(()).into();
+            }
+
+            // the whole chunk is accepted as long as the stream has any capacity available
+            ::core::task::ready!(self.poll_send_ready(cx))?;
+
+            let chunk = bytes::Bytes::from_owner(buffer.take().expect("buffer checked above"));
+            self.send_data(chunk).into()
+        }
+
         /// Polls send readiness for the given stream.
         ///
         /// This method _must_ be called before calling [`send_data`](Self::send_data).
@@ -363,6 +593,72 @@ macro_rules! impl_send_stream_api {
             $dispatch_body
         }
 
+        /// Waits for the peer to acknowledge all of the data and the end of the stream.
+        ///
+        /// Unlike [`close`](Self::close), this method doesn't finish the stream. It only resolves
+        /// once the stream has been finished, either with [`finish`](Self::finish) or
+        /// [`close`](Self::close), and the peer has acknowledged receiving every byte. This
+        /// can be used to ensure data is durably received by the peer before discarding any
+        /// local copies.
+        ///
+        /// # Return value
+        ///
+        /// The function returns:
+        /// - `Ok(())` if the stream was finished and all of the data was acknowledged by the peer.
+        /// - `Err(e)` if the stream encountered a [`stream::Error`](crate::stream::Error).
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::stream::Result<()> {
+        /// #   let stream: s2n_quic::stream::SendStream = todo!();
+        /// #
+        /// let data = bytes::Bytes::from_static(&[1, 2, 3, 4]);
+        /// stream.send(data).await?;
+        /// stream.finish()?;
+        /// stream.finished().await?;
+        /// // at this point, the peer has acknowledged all of the `data` and the end of the stream
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub async fn finished(&mut self) -> $crate::stream::Result<()> {
+            ::futures::future::poll_fn(|cx| self.poll_finished(cx)).await
+        }
+
+        /// Polls for the peer to acknowledge all of the data and the end of the stream.
+        ///
+        /// # Return value
+        ///
+        /// The function returns:
+        /// - `Poll::Pending` if the stream hasn't been finished or the peer hasn't acknowledged
+        ///   all of the data yet. In this case, the caller should retry after the
+        ///   [`Waker`](core::task::Waker) on the provided [`Context`](core::task::Context) is
+        ///   notified.
+        /// - `Poll::Ready(Ok(()))` if the stream was finished and all of the data was
+        ///   acknowledged by the peer.
+        /// - `Poll::Ready(Err(e))` if the stream encountered a [`stream::Error`](crate::stream::Error).
+        #[inline]
+        pub fn poll_finished(
+            &mut self,
+            cx: &mut core::task::Context,
+        ) -> core::task::Poll<$crate::stream::Result<()>> {
+            macro_rules! $dispatch {
+                () => {
+                    Err($crate::stream::Error::non_writable()).into()
+                };
+                ($variant: expr) => {
+                    s2n_quic_core::task::waker::debug_assert_contract(cx, |cx| {
+                        $variant.poll_finished(cx)
+                    })
+                };
+            }
+
+            let $stream = self;
+            $dispatch_body
+        }
+
         /// Closes the stream with an [error code](crate::application::Error).
         ///
         /// After calling this, the stream is closed and will not accept any additional data to be
@@ -392,6 +688,194 @@ macro_rules! impl_send_stream_api {
             let $stream = self;
             $dispatch_body
         }
+
+        /// Closes the stream with an [error code](crate::application::Error) after delivering
+        /// the data up to `offset`.
+        ///
+        /// Unlike [`reset`](Self::reset), the data that was sent before `offset` continues to be
+        /// transmitted until the peer has received it. The peer will then be notified of the
+        /// [error code](crate::application::Error). Any data past the offset is discarded.
+        ///
+        /// This requires both endpoints to enable
+        /// [`Limits::with_reset_stream_at`](crate::provider::limits::Limits::with_reset_stream_at).
+        /// Otherwise, this behaves like [`reset`](Self::reset).
+        ///
+        /// # Return value
+        ///
+        /// The function returns:
+        /// - `Ok(())` if the stream was reset successfully.
+        /// - `Err(e)` if the stream encountered a [`stream::Error`](crate::stream::Error). The
+        ///   stream may have been reset previously, or the connection itself was closed.
+        #[inline]
+        pub fn reset_at(
+            &mut self,
+            error_code: $crate::application::Error,
+            offset: u64,
+        ) -> $crate::stream::Result<()> {
+            // offsets past the end of the stream are clamped to the amount of data sent
+            let offset = s2n_quic_core::varint::VarInt::new(offset)
+                .unwrap_or(s2n_quic_core::varint::VarInt::MAX);
+
+            macro_rules! $dispatch {
+                () => {
+                    Err($crate::stream::Error::non_writable())
+                };
+                ($variant: expr) => {
+                    $variant.reset_at(error_code, offset)
+                };
+            }
+
+            let $stream = self;
+            $dispatch_body
+        }
+
+        /// Holds back small writes for up to `delay` so they can be combined into fewer packets.
+        ///
+        /// Held data is transmitted as soon as enough has been buffered to fill a packet, the
+        /// delay expires, or the stream is [flushed](Self::flush) or [finished](Self::finish).
+        /// This trades latency for efficiency on streams which perform many small writes.
+        ///
+        /// Coalescing is disabled by default. Setting a zero `delay` disables it again and
+        /// immediately transmits any data that is being held.
+        ///
+        /// # Return value
+        ///
+        /// The function returns:
+        /// - `Ok(())` if the delay was set successfully.
+        /// - `Err(e)` if the stream encountered a [`stream::Error`](crate::stream::Error). The
+        ///   stream may have been reset previously, or the connection itself was closed.
+        #[inline]
+        pub fn set_coalescing_delay(
+            &mut self,
+            delay: core::time::Duration,
+        ) -> $crate::stream::Result<()> {
+            macro_rules! $dispatch {
+                () => {
+                    Err($crate::stream::Error::non_writable())
+                };
+                ($variant: expr) => {
+                    $variant.set_coalescing_delay(delay)
+                };
+            }
+
+            let $stream = self;
+            $dispatch_body
+        }
+
+        /// Sets the maximum amount of time a send operation may wait for the stream to make
+        /// progress.
+        ///
+        /// This applies to operations waiting for send buffer capacity, [`flush`](Self::flush),
+        /// [`close`](Self::close) and [`finished`](Self::finished). Once an operation has been
+        /// waiting for longer than the `timeout`, it fails with a
+        /// [`stream::Error::Timeout`](crate::stream::Error::Timeout). The stream remains usable
+        /// and the operation can be retried.
+        ///
+        /// The timeout is disabled by default. Passing `None` disables it again.
+        ///
+        /// # Return value
+        ///
+        /// The function returns:
+        /// - `Ok(())` if the timeout was set successfully.
+        /// - `Err(e)` if the stream encountered a [`stream::Error`](crate::stream::Error). The
+        ///   stream may have been reset previously, or the connection itself was closed.
+        #[inline]
+        pub fn set_write_timeout(
+            &mut self,
+            timeout: Option<core::time::Duration>,
+        ) -> $crate::stream::Result<()> {
+            macro_rules! $dispatch {
+                () => {
+                    Err($crate::stream::Error::non_writable())
+                };
+                ($variant: expr) => {
+                    $variant.set_write_timeout(timeout)
+                };
+            }
+
+            let $stream = self;
+            $dispatch_body
+        }
+
+        /// Sets the maximum amount of data which is buffered on the stream until it is
+        /// acknowledged by the peer.
+        ///
+        /// Once the buffer is full, sending blocks until the peer acknowledges some of the data.
+        /// Lowering the size doesn't drop any data which is already buffered, but prevents
+        /// additional data from being sent until the buffer drains below the new size. This allows
+        /// memory-constrained applications to limit buffering on individual streams.
+        ///
+        /// By default, the stream uses the size configured for the connection with
+        /// [`Limits::with_max_send_buffer_size`](crate::provider::limits::Limits::with_max_send_buffer_size).
+        ///
+        /// # Return value
+        ///
+        /// The function returns:
+        /// - `Ok(())` if the size was set successfully.
+        /// - `Err(e)` if the stream encountered a [`stream::Error`](crate::stream::Error). The
+        ///   stream may have been reset previously, or the connection itself was closed. A size of
+        ///   `0` is rejected with a
+        ///   [`stream::Error::InvalidConfiguration`](crate::stream::Error::InvalidConfiguration).
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// # async fn test() -> s2n_quic::stream::Result<()> {
+        /// #   let mut stream: s2n_quic::stream::SendStream = todo!();
+        /// #
+        /// // buffer at most 16KB of unacknowledged data
+        /// stream.set_send_buffer_size(16 * 1024)?;
+        /// #
+        /// #   Ok(())
+        /// # }
+        /// ```
+        #[inline]
+        pub fn set_send_buffer_size(&mut self, size: u32) -> $crate::stream::Result<()> {
+            macro_rules! $dispatch {
+                () => {
+                    Err($crate::stream::Error::non_writable())
+                };
+                ($variant: expr) => {
+                    $variant.set_send_buffer_size(size)
+                };
+            }
+
+            let $stream = self;
+            $dispatch_body
+        }
+
+        /// Compresses all of the following data sent on the stream with the provided encoder.
+        ///
+        /// The application keeps sending uncompressed data while the compressed output is what
+        /// gets buffered and transmitted. Flushing and closing the stream also flush and finish
+        /// the encoder. See the [`compression`](crate::stream::compression) module for more
+        /// details.
+        ///
+        /// # Return value
+        ///
+        /// The function returns:
+        /// - `Ok(())` if the encoder was set successfully.
+        /// - `Err(e)` if the stream encountered a [`stream::Error`](crate::stream::Error). An
+        ///   error is also returned if data was already sent on the stream.
+        #[cfg(any(test, feature = "unstable-stream-compression"))]
+        #[cfg_attr(docsrs, doc(cfg(feature = "unstable-stream-compression")))]
+        #[inline]
+        pub fn set_encoder<E: $crate::stream::compression::Encoder>(
+            &mut self,
+            encoder: E,
+        ) -> $crate::stream::Result<()> {
+            macro_rules! $dispatch {
+                () => {
+                    Err($crate::stream::Error::non_writable())
+                };
+                ($variant: expr) => {
+                    $variant.set_encoder(Box::new(encoder))
+                };
+            }
+
+            let $stream = self;
+            $dispatch_body
+        }
     };
 }
 
@@ -28,17 +28,59 @@ mod resumption;
 mod setup;
 use setup::*;
 
+mod accept_filter;
+mod accept_stream;
+mod accounting;
+mod address_attestation;
+mod alpn;
+mod async_io;
 mod blackhole;
+mod close_reason;
+mod closing_period;
+mod compatibility;
+mod compression;
+mod connect_race;
+mod connect_retry;
+mod connection_hibernation;
+mod connection_id_rotation;
 mod connection_migration;
+mod datagram_io;
+mod datagram_only;
 mod deduplicate;
+mod dns;
+mod event_context;
+mod event_loop_profile;
+mod flush_transport;
+mod go_away;
 mod handshake_cid_rotation;
+mod handshake_completed;
+mod idle_timeout;
+mod initial_packet_number;
 mod interceptor;
+mod limit_overrides;
+mod limit_pressure;
+mod max_udp_payload_size;
 mod mtu;
+mod network_change;
 mod no_tls;
+mod packet_budget;
+mod partial_reliability;
+mod path_cache;
+mod path_stats;
 mod platform_events;
+mod pool;
 mod pto;
+mod reset_stream_at;
 mod self_test;
+mod send_owned;
+mod server_name;
+mod sim;
 mod skip_packets;
+mod stream_finished;
+mod stream_open_blocked;
+mod stream_timeout;
+mod transmission_weight;
+mod zero_length_connection_id;
 
 // TODO: https://github.com/aws/s2n-quic/issues/1726
 //
@@ -0,0 +1,239 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::{
+    accept_filter::{self, ConnectionAttempt, Filter, Outcome, Overrides},
+    io::testing::{Handle, Result},
+};
+use s2n_quic_core::{connection::Error, transport};
+
+#[derive(Debug)]
+struct Attempt {
+    is_address_validated: bool,
+    server_name: Option<String>,
+    application_protocols: Vec<Vec<u8>>,
+}
+
+/// Records every connection attempt and returns the configured outcome for unvalidated peers
+#[derive(Clone)]
+struct Recorder {
+    attempts: Arc<Mutex<Vec<Attempt>>>,
+    outcome: Outcome,
+}
+
+impl Recorder {
+    fn new(outcome: Outcome) -> Self {
+        Self {
+            attempts: Default::default(),
+            outcome,
+        }
+    }
+}
+
+impl Filter for Recorder {
+    fn on_connection_attempt(&mut self, attempt: &ConnectionAttempt) -> Outcome {
+        self.attempts.lock().unwrap().push(Attempt {
+            is_address_validated: attempt.is_address_validated,
+            server_name: attempt.server_name.map(String::from),
+            application_protocols: attempt
+                .application_protocols
+                .into_iter()
+                .flatten()
+                .map(<[u8]>::to_vec)
+                .collect(),
+        });
+
+        if attempt.is_address_validated {
+            Outcome::allow()
+        } else {
+            self.outcome
+        }
+    }
+}
+
+fn start_filtered_server(handle: &Handle, filter: impl Filter) -> Result<SocketAddr> {
+    let server = Server::builder()
+        .with_io(handle.builder().build()?)?
+        .with_tls(SERVER_CERTS)?
+        .with_event(tracing_events())?
+        .with_random(Random::with_seed(456))?
+        .with_accept_filter(filter)?
+        .start()?;
+
+    start_server(server)
+}
+
+/// Ensures the filter is provided with the server name and application protocols in the
+/// ClientHello
+#[test]
+fn accept_filter_client_hello_test() {
+    let model = Model::default();
+    let filter = Recorder::new(Outcome::allow());
+    let attempts = filter.attempts.clone();
+
+    test(model, |handle| {
+        let server_addr = start_filtered_server(handle, filter)?;
+        client(handle, server_addr)
+    })
+    .unwrap();
+
+    let attempts = attempts.lock().unwrap();
+    assert_eq!(attempts.len(), 1);
+    assert!(!attempts[0].is_address_validated);
+    assert_eq!(attempts[0].server_name.as_deref(), Some("localhost"));
+    assert!(attempts[0]
+        .application_protocols
+        .iter()
+        .any(|protocol| protocol == b"h3"));
+}
+
+/// Ensures a rejected connection attempt is closed with the provided error
+#[test]
+fn accept_filter_close_test() {
+    let model = Model::default();
+    let filter = Recorder::new(Outcome::close());
+    let attempts = filter.attempts.clone();
+
+    test(model, |handle| {
+        let server_addr = start_filtered_server(handle, filter)?;
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let error = client.connect(connect).await.unwrap_err();
+
+            match error {
+                Error::Transport { code, .. } => {
+                    assert_eq!(code, transport::Error::CONNECTION_REFUSED.code)
+                }
+                error => panic!("unexpected error: {error:?}"),
+            }
+        });
+
+        Ok(())
+    })
+    .unwrap();
+
+    assert_eq!(attempts.lock().unwrap().len(), 1);
+}
+
+/// Ensures a dropped connection attempt is never answered and doesn't leave any state behind
+#[test]
+fn accept_filter_drop_test() {
+    let model = Model::default();
+    let filter = Recorder::new(Outcome::drop());
+    let attempts = filter.attempts.clone();
+
+    test(model, |handle| {
+        let server_addr = start_filtered_server(handle, filter)?;
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let error = client.connect(connect).await.unwrap_err();
+
+            // the server never responds so the client gives up on the handshake
+            assert!(
+                matches!(
+                    error,
+                    Error::IdleTimerExpired { .. } | Error::MaxHandshakeDurationExceeded { .. }
+                ),
+                "unexpected error: {error:?}"
+            );
+        });
+
+        Ok(())
+    })
+    .unwrap();
+
+    // every retransmitted Initial packet is filtered again since no connection was created
+    let attempts = attempts.lock().unwrap();
+    assert!(attempts.len() > 1, "{}", attempts.len());
+    assert!(attempts.iter().all(|attempt| !attempt.is_address_validated));
+}
+
+/// Ensures the filter can require peers to validate their address with a Retry packet
+#[test]
+fn accept_filter_retry_test() {
+    let model = Model::default();
+    let filter = Recorder::new(Outcome::retry());
+    let attempts = filter.attempts.clone();
+
+    test(model, |handle| {
+        let server_addr = start_filtered_server(handle, filter)?;
+        client(handle, server_addr)
+    })
+    .unwrap();
+
+    let attempts = attempts.lock().unwrap();
+    assert_eq!(attempts.len(), 2);
+    assert!(!attempts[0].is_address_validated);
+    assert!(attempts[1].is_address_validated);
+}
+
+/// Ensures a closure can decide the outcome from the peer's address and connection ID
+#[test]
+fn accept_filter_from_fn_test() {
+    let model = Model::default();
+    let attempts = Arc::new(Mutex::new(vec![]));
+    let filter = accept_filter::from_fn({
+        let attempts = attempts.clone();
+        move |attempt| {
+            attempts.lock().unwrap().push((
+                attempt.remote_address.to_string(),
+                attempt.destination_connection_id.to_vec(),
+            ));
+
+            if attempt.is_address_validated {
+                Outcome::allow()
+            } else {
+                Outcome::retry()
+            }
+        }
+    });
+
+    test(model, |handle| {
+        let server_addr = start_filtered_server(handle, filter)?;
+        client(handle, server_addr)
+    })
+    .unwrap();
+
+    let attempts = attempts.lock().unwrap();
+    assert_eq!(attempts.len(), 2);
+    // both attempts come from the same client
+    assert_eq!(attempts[0].0, attempts[1].0);
+    // the second attempt uses the connection ID chosen by the server in the Retry packet
+    assert!(attempts[0].1.len() >= 8);
+    assert_ne!(attempts[0].1, attempts[1].1);
+}
+
+/// Ensures the limits overridden by the filter are advertised to the peer
+#[test]
+fn accept_filter_overrides_test() {
+    let model = Model::default();
+    let max_idle_timeout = Duration::from_secs(300);
+    let overrides = Overrides::default()
+        .with_max_idle_timeout(max_idle_timeout)
+        .unwrap()
+        .with_max_open_remote_bidirectional_streams(1000)
+        .unwrap();
+    let filter = Recorder::new(Outcome::allow_with_overrides(overrides));
+    let received = recorder::TransportParametersReceived::new();
+    let received_params = received.events();
+
+    test(model, |handle| {
+        let server_addr = start_filtered_server(handle, filter)?;
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event((tracing_events(), received))?
+            .with_random(Random::with_seed(123))?
+            .start()?;
+        start_client(client, server_addr, Data::new(10_000))
+    })
+    .unwrap();
+
+    let received_params = received_params.lock().unwrap();
+    assert_eq!(*received_params, [(max_idle_timeout, 1000)]);
+}
@@ -0,0 +1,59 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::stream::PeerStream;
+use futures::{StreamExt, TryStreamExt};
+
+const STREAMS: usize = 3;
+
+/// Ensures the server and connections can be driven as [`futures::Stream`]s
+#[test]
+fn accept_stream_test() {
+    let model = Model::default();
+
+    test(model, |handle| {
+        let mut server = build_server(handle)?;
+        let server_addr = server.local_addr()?;
+
+        spawn(async move {
+            while let Some(connection) = server.next().await {
+                spawn(async move {
+                    let streams: Vec<_> = connection.take(STREAMS).try_collect().await.unwrap();
+                    assert_eq!(streams.len(), STREAMS);
+
+                    for stream in streams {
+                        let PeerStream::Bidirectional(mut stream) = stream else {
+                            panic!("expected a bidirectional stream");
+                        };
+                        let chunk = stream.receive().await.unwrap().unwrap();
+                        stream.send(chunk).await.unwrap();
+                        stream.finish().unwrap();
+                    }
+                });
+            }
+        });
+
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            let mut streams = vec![];
+            for _ in 0..STREAMS {
+                let mut stream = connection.open_bidirectional_stream().await.unwrap();
+                stream.send(Bytes::from_static(b"hello")).await.unwrap();
+                streams.push(stream);
+            }
+
+            for mut stream in streams {
+                let chunk = stream.receive().await.unwrap().unwrap();
+                assert_eq!(chunk, Bytes::from_static(b"hello"));
+            }
+        });
+
+        Ok(())
+    })
+    .unwrap();
+}
@@ -0,0 +1,68 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::event::{accounting, ConnectionInfo, ConnectionMeta};
+
+#[derive(Clone, Default)]
+struct Accounting {
+    opened: Arc<Mutex<u64>>,
+    finalized: Arc<Mutex<Vec<accounting::Totals>>>,
+}
+
+impl accounting::Accounting for Accounting {
+    type Ledger = Self;
+
+    fn on_connection_opened(
+        &mut self,
+        _meta: &ConnectionMeta,
+        _info: &ConnectionInfo,
+    ) -> Self::Ledger {
+        *self.opened.lock().unwrap() += 1;
+        self.clone()
+    }
+}
+
+impl accounting::Ledger for Accounting {
+    fn on_connection_finalized(&mut self, totals: &accounting::Totals) {
+        self.finalized.lock().unwrap().push(*totals);
+    }
+}
+
+/// Ensures each connection is reported exactly once with the exact number of stream bytes
+/// transferred
+#[test]
+fn accounting_totals_test() {
+    let model = Model::default();
+    let accounting = Accounting::default();
+    let opened = accounting.opened.clone();
+    let finalized = accounting.finalized.clone();
+    let len = 100_000;
+
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event((tracing_events(), accounting::Subscriber::new(accounting)))?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+
+        let client = build_client(handle)?;
+        let addr = start_server(server)?;
+        start_client(client, addr, Data::new(len))?;
+        Ok(addr)
+    })
+    .unwrap();
+
+    assert_eq!(*opened.lock().unwrap(), 1);
+
+    let finalized = finalized.lock().unwrap();
+    assert_eq!(finalized.len(), 1, "totals must be reported exactly once");
+
+    let totals = &finalized[0];
+    // the server echoes all of the data back to the client
+    assert_eq!(totals.stream_bytes_received, len);
+    assert_eq!(totals.stream_bytes_sent, len);
+    assert!(totals.bytes_received > totals.stream_bytes_received);
+    assert!(totals.bytes_sent > totals.stream_bytes_sent);
+}
@@ -0,0 +1,113 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::{
+    accept_filter::{self, ConnectionAttempt, Filter},
+    address_attestation::{Attestation, Validator},
+    io::testing::{Handle, Result},
+};
+
+const VOUCHER: &[u8] = b"voucher";
+
+/// Attests any peer which presents the expected voucher
+#[derive(Clone, Default)]
+struct Vouchers {
+    tokens: Arc<Mutex<Vec<Vec<u8>>>>,
+}
+
+impl Validator for Vouchers {
+    fn is_attested(&mut self, attestation: &Attestation) -> bool {
+        self.tokens.lock().unwrap().push(attestation.token.to_vec());
+        attestation.token == VOUCHER
+    }
+}
+
+/// Requires address validation for every connection attempt and records if it was validated
+#[derive(Clone, Default)]
+struct RequireRetry {
+    attempts: Arc<Mutex<Vec<bool>>>,
+}
+
+impl Filter for RequireRetry {
+    fn on_connection_attempt(&mut self, attempt: &ConnectionAttempt) -> accept_filter::Outcome {
+        self.attempts
+            .lock()
+            .unwrap()
+            .push(attempt.is_address_validated);
+        accept_filter::Outcome::retry()
+    }
+}
+
+fn start_attesting_server(
+    handle: &Handle,
+    vouchers: Vouchers,
+    filter: RequireRetry,
+) -> Result<SocketAddr> {
+    let server = Server::builder()
+        .with_io(handle.builder().build()?)?
+        .with_tls(SERVER_CERTS)?
+        .with_event(tracing_events())?
+        .with_random(Random::with_seed(456))?
+        .with_accept_filter(filter)?
+        .with_address_attestation(vouchers)?
+        .start()?;
+
+    start_server(server)
+}
+
+fn connect_with_token(handle: &Handle, server_addr: SocketAddr, token: &[u8]) -> Result {
+    let client = build_client(handle)?;
+    let connect = Connect::new(server_addr)
+        .with_server_name("localhost")
+        .with_token(token);
+
+    primary::spawn(async move {
+        client.connect(connect).await.unwrap();
+    });
+
+    Ok(())
+}
+
+/// Ensures clients with an attested address are not sent a Retry packet
+#[test]
+fn address_attestation_test() {
+    let model = Model::default();
+    let vouchers = Vouchers::default();
+    let tokens = vouchers.tokens.clone();
+    let filter = RequireRetry::default();
+    let attempts = filter.attempts.clone();
+
+    test(model, |handle| {
+        let server_addr = start_attesting_server(handle, vouchers, filter)?;
+        connect_with_token(handle, server_addr, VOUCHER)
+    })
+    .unwrap();
+
+    assert_eq!(*attempts.lock().unwrap(), [true]);
+    assert_eq!(*tokens.lock().unwrap(), [VOUCHER]);
+}
+
+/// Ensures clients without an attestation validate their address with a Retry packet
+#[test]
+fn address_attestation_missing_test() {
+    let model = Model::default();
+    let vouchers = Vouchers::default();
+    let tokens = vouchers.tokens.clone();
+    let filter = RequireRetry::default();
+    let attempts = filter.attempts.clone();
+
+    test(model, |handle| {
+        let server_addr = start_attesting_server(handle, vouchers, filter)?;
+        connect_with_token(handle, server_addr, &[])
+    })
+    .unwrap();
+
+    assert_eq!(*attempts.lock().unwrap(), [false, true]);
+
+    // the second Initial packet contains the Retry token, which isn't a voucher
+    let tokens = tokens.lock().unwrap();
+    assert_eq!(tokens.len(), 2);
+    assert!(tokens[0].is_empty());
+    assert_ne!(tokens[1], VOUCHER);
+}
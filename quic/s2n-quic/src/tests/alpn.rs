@@ -0,0 +1,61 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::tls;
+
+const PROTOCOLS: [&[u8]; 2] = [b"h3", b"custom"];
+
+/// Ensures each acceptor only yields the connections which negotiated its protocol
+#[test]
+fn split_by_application_protocol_test() {
+    let model = Model::default();
+    let accepted = Arc::new(Mutex::new(vec![]));
+
+    test(model, |handle| {
+        let tls = tls::default::Server::builder()
+            .with_certificate(certificates::CERT_PEM, certificates::KEY_PEM)?
+            .with_application_protocols(PROTOCOLS.iter())?
+            .build()?;
+
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(tls)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(123))?
+            .start()?;
+        let server_addr = server.local_addr()?;
+
+        let acceptors = server.split_by_application_protocol(PROTOCOLS);
+        assert_eq!(acceptors.len(), PROTOCOLS.len());
+
+        for (protocol, mut acceptor) in acceptors {
+            let accepted = accepted.clone();
+            spawn(async move {
+                while let Some(connection) = acceptor.accept().await {
+                    assert_eq!(connection.application_protocol().unwrap(), protocol);
+                    accepted.lock().unwrap().push(protocol.clone());
+                }
+            });
+        }
+
+        for protocol in [&b"custom"[..], b"h3", b"custom"] {
+            let client = build_client_with_alpn(handle, protocol)?;
+            primary::spawn(async move {
+                let connect = Connect::new(server_addr).with_server_name("localhost");
+                let connection = client.connect(connect).await.unwrap();
+                assert_eq!(connection.application_protocol().unwrap(), protocol);
+
+                // keep the connection open until the server has accepted it
+                delay(Duration::from_secs(1)).await;
+            });
+        }
+
+        Ok(())
+    })
+    .unwrap();
+
+    let mut accepted = accepted.lock().unwrap().clone();
+    accepted.sort();
+    assert_eq!(accepted, [&b"custom"[..], b"custom", b"h3"]);
+}
@@ -0,0 +1,81 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::io::testing::Result;
+
+const LINES: &[&str] = &["hello", "from", "the", "client"];
+
+/// Starts a server which echoes each line it receives in uppercase
+///
+/// The stream is used with the tokio I/O traits without splitting it.
+fn start_echo_server(handle: &io::Handle) -> Result<SocketAddr> {
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+    let mut server = build_server(handle)?;
+    let server_addr = server.local_addr()?;
+
+    spawn(async move {
+        while let Some(mut connection) = server.accept().await {
+            spawn(async move {
+                while let Ok(Some(stream)) = connection.accept_bidirectional_stream().await {
+                    spawn(async move {
+                        let mut stream = BufReader::new(stream);
+                        let mut line = String::new();
+
+                        while stream.read_line(&mut line).await.unwrap() > 0 {
+                            stream
+                                .write_all(line.to_uppercase().as_bytes())
+                                .await
+                                .unwrap();
+                            line.clear();
+                        }
+
+                        stream.shutdown().await.unwrap();
+                    });
+                }
+            });
+        }
+    });
+
+    Ok(server_addr)
+}
+
+/// Ensures streams can be used with code written against the tokio and futures I/O traits
+#[test]
+fn async_io_test() {
+    let model = Model::default();
+
+    test(model, |handle| {
+        let server_addr = start_echo_server(handle)?;
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            use futures::io::{AsyncReadExt, AsyncWriteExt};
+
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+
+            for line in LINES {
+                stream
+                    .write_all(format!("{line}\n").as_bytes())
+                    .await
+                    .unwrap();
+            }
+            AsyncWriteExt::close(&mut stream).await.unwrap();
+
+            let mut response = String::new();
+            stream.read_to_string(&mut response).await.unwrap();
+
+            let expected: String = LINES
+                .iter()
+                .map(|line| format!("{}\n", line.to_uppercase()))
+                .collect();
+            assert_eq!(response, expected);
+        });
+
+        Ok(())
+    })
+    .unwrap();
+}
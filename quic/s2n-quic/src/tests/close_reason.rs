@@ -0,0 +1,71 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use s2n_quic_core::{
+    connection::{error::Reason, Error},
+    endpoint,
+};
+
+const ERROR_CODE: u32 = 123;
+// the reason is longer than what fits in the error so it's only fully available on the connection
+const REASON: &str = "{\"error\":\"draining\",\"retry_after_ms\":500}";
+
+/// Ensures the reason provided when closing a connection is received by the peer
+#[test]
+fn close_reason_test() {
+    let model = Model::default();
+
+    test(model, |handle| {
+        let mut server = build_server(handle)?;
+        let server_addr = server.local_addr()?;
+
+        spawn(async move {
+            let mut connection = server.accept().await.unwrap();
+
+            // wait for the client to open a stream so the handshake is confirmed
+            let _stream = connection.accept_bidirectional_stream().await.unwrap();
+            connection.close_with_reason(ERROR_CODE.into(), REASON);
+            assert_eq!(
+                connection.close_reason().unwrap().as_deref(),
+                Some(REASON.as_bytes())
+            );
+
+            // wait for the client to receive the close before dropping
+            delay(Duration::from_millis(100)).await;
+        });
+
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+            stream.send(Bytes::from_static(b"hello")).await.unwrap();
+            assert_eq!(connection.close_reason().unwrap(), None);
+
+            match connection.accept().await.unwrap_err() {
+                Error::Application {
+                    error,
+                    reason,
+                    initiator,
+                    ..
+                } => {
+                    assert_eq!(error, ERROR_CODE.into());
+                    assert_eq!(reason.as_str(), &REASON[..Reason::MAX_LEN]);
+                    assert_eq!(initiator, endpoint::Location::Remote);
+                }
+                error => panic!("unexpected error: {error:?}"),
+            }
+
+            assert_eq!(
+                connection.close_reason().unwrap().as_deref(),
+                Some(REASON.as_bytes())
+            );
+        });
+
+        Ok(())
+    })
+    .unwrap();
+}
@@ -0,0 +1,70 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::limits::Limits;
+use recorder::PacketDropReason;
+use s2n_quic_core::connection::Error;
+
+/// Ensures a closing connection releases its packet protection keys while still responding to
+/// the peer with the CONNECTION_CLOSE packet
+#[test]
+fn closing_period_release_test() {
+    let model = Model::default();
+    model.set_delay(Duration::from_millis(50));
+
+    let dropped = recorder::PacketDropped::new();
+    let dropped_reasons = dropped.events();
+
+    test(model, |handle| {
+        let limits = Limits::default()
+            .with_closing_period_multiplier(10)
+            .unwrap();
+        let mut server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event((tracing_events(), dropped))?
+            .with_random(Random::with_seed(456))?
+            .with_limits(limits)?
+            .start()?;
+        let server_addr = server.local_addr()?;
+
+        spawn(async move {
+            let mut connection = server.accept().await.unwrap();
+            let mut stream = connection.accept_receive_stream().await.unwrap().unwrap();
+
+            // close the connection while the client still has data in flight
+            stream.receive().await.unwrap();
+            connection.close(123u8.into());
+
+            // keep the endpoint around for the closing period
+            delay(Duration::from_secs(5)).await;
+        });
+
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            let mut stream = connection.open_send_stream().await.unwrap();
+            while stream.send(Bytes::from(vec![42u8; 1000])).await.is_ok() {}
+
+            // the close is still delivered after the server released its keys
+            match connection.accept().await.unwrap_err() {
+                Error::Application { error, .. } => assert_eq!(error, 123u8.into()),
+                error => panic!("unexpected error: {error:?}"),
+            }
+        });
+
+        Ok(())
+    })
+    .unwrap();
+
+    // the packets which arrived during the closing period couldn't be decrypted anymore
+    let dropped_reasons = dropped_reasons.lock().unwrap();
+    assert!(
+        dropped_reasons.contains(&PacketDropReason::ConnectionError),
+        "{dropped_reasons:?}"
+    );
+}
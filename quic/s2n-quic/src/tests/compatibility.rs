@@ -0,0 +1,139 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::{
+    connection::Error,
+    provider::{compatibility::Relaxations, tls::Provider},
+};
+use recorder::CompatibilityRelaxationApplied;
+use s2n_codec::{DecoderBuffer, EncoderValue};
+use s2n_quic_core::{
+    application::ServerName,
+    crypto::tls::{self, null, Endpoint as _},
+    transport::{self, parameters::ClientTransportParameters},
+};
+
+/// A TLS provider whose clients omit the `initial_source_connection_id` transport parameter
+#[derive(Default)]
+struct NonconformantTlsProvider;
+
+impl Provider for NonconformantTlsProvider {
+    type Server = null::Endpoint;
+    type Client = NonconformantClient;
+    type Error = String;
+
+    fn start_server(self) -> Result<Self::Server, Self::Error> {
+        Ok(Self::Server::default())
+    }
+
+    fn start_client(self) -> Result<Self::Client, Self::Error> {
+        Ok(NonconformantClient(null::Endpoint::default()))
+    }
+}
+
+struct NonconformantClient(null::Endpoint);
+
+impl tls::Endpoint for NonconformantClient {
+    type Session = null::Session;
+
+    fn new_server_session<Params: EncoderValue>(
+        &mut self,
+        transport_parameters: &Params,
+    ) -> Self::Session {
+        self.0.new_server_session(transport_parameters)
+    }
+
+    fn new_client_session<Params: EncoderValue>(
+        &mut self,
+        transport_parameters: &Params,
+        server_name: ServerName,
+    ) -> Self::Session {
+        let transport_parameters = transport_parameters.encode_to_vec();
+        let (mut transport_parameters, _) = DecoderBuffer::new(&transport_parameters)
+            .decode::<ClientTransportParameters>()
+            .unwrap();
+        transport_parameters.initial_source_connection_id = None;
+        self.0
+            .new_client_session(&transport_parameters, server_name)
+    }
+
+    fn max_tag_length(&self) -> usize {
+        self.0.max_tag_length()
+    }
+}
+
+/// Connects a nonconformant client to a server with the provided relaxations
+fn run(
+    relaxations: Relaxations,
+) -> (
+    Result<(), Error>,
+    Vec<events::CompatibilityRelaxationApplied>,
+) {
+    let model = Model::default();
+    let recorder = CompatibilityRelaxationApplied::new();
+    let applied = recorder.events();
+    let outcome = Arc::new(Mutex::new(None));
+    let client_outcome = outcome.clone();
+
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(NonconformantTlsProvider)?
+            .with_compatibility(relaxations)?
+            .with_event((tracing_events(), recorder))?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+        let server_addr = start_server(server)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(NonconformantTlsProvider)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let result = client.connect(connect).await.map(|_connection| ());
+            *client_outcome.lock().unwrap() = Some(result);
+        });
+
+        Ok(())
+    })
+    .unwrap();
+
+    let outcome = outcome.lock().unwrap().take().unwrap();
+    let applied = applied.lock().unwrap().clone();
+    (outcome, applied)
+}
+
+/// Ensures the server accepts a client which omits the `initial_source_connection_id` when the
+/// relaxation is enabled, and reports the relaxation
+#[test]
+fn compatibility_relaxation_applied_test() {
+    let relaxations = Relaxations::STRICT.with_missing_initial_source_connection_id(true);
+    let (outcome, applied) = run(relaxations);
+
+    outcome.unwrap();
+    assert_eq!(applied.len(), 1);
+    assert!(matches!(
+        applied[0].relaxation,
+        events::CompatibilityRelaxation::MissingInitialSourceConnectionId { .. }
+    ));
+}
+
+/// Ensures the server rejects a client which omits the `initial_source_connection_id` when
+/// the relaxation is disabled
+#[test]
+fn compatibility_relaxation_disabled_test() {
+    let (outcome, applied) = run(Relaxations::STRICT);
+
+    match outcome.unwrap_err() {
+        Error::Transport { code, .. } => {
+            assert_eq!(code, transport::Error::TRANSPORT_PARAMETER_ERROR.code)
+        }
+        error => panic!("unexpected error: {error:?}"),
+    }
+    assert!(applied.is_empty());
+}
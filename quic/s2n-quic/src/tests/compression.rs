@@ -0,0 +1,184 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::{
+    provider::{io::testing::Result, limits::Limits},
+    stream::{
+        compression::{self, Codec},
+        Error as StreamError,
+    },
+};
+
+const DICTIONARY_ID: u64 = 7;
+
+/// A run-length encoding codec which emits `(count, byte)` pairs
+struct RunLength;
+
+impl Codec for RunLength {
+    type Encoder = RunLengthEncoder;
+    type Decoder = RunLengthDecoder;
+
+    fn dictionary_id(&self) -> Option<u64> {
+        Some(DICTIONARY_ID)
+    }
+
+    fn encoder(&self) -> Self::Encoder {
+        RunLengthEncoder::default()
+    }
+
+    fn decoder(&self) -> Self::Decoder {
+        RunLengthDecoder::default()
+    }
+}
+
+#[derive(Default)]
+struct RunLengthEncoder {
+    run: Option<(u8, u8)>,
+}
+
+impl compression::Encoder for RunLengthEncoder {
+    fn encode(&mut self, input: &[u8], output: &mut Vec<u8>) -> Result<(), StreamError> {
+        for &byte in input {
+            match self.run.as_mut() {
+                Some((count, value)) if *value == byte && *count < u8::MAX => *count += 1,
+                _ => {
+                    self.flush(output)?;
+                    self.run = Some((1, byte));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self, output: &mut Vec<u8>) -> Result<(), StreamError> {
+        if let Some((count, value)) = self.run.take() {
+            output.extend_from_slice(&[count, value]);
+        }
+        Ok(())
+    }
+
+    fn finish(&mut self, output: &mut Vec<u8>) -> Result<(), StreamError> {
+        self.flush(output)
+    }
+}
+
+#[derive(Default)]
+struct RunLengthDecoder {
+    count: Option<u8>,
+}
+
+impl compression::Decoder for RunLengthDecoder {
+    fn decode(&mut self, input: &[u8], output: &mut Vec<u8>) -> Result<(), StreamError> {
+        for &byte in input {
+            if let Some(count) = self.count.take() {
+                output.extend(core::iter::repeat(byte).take(count as usize));
+            } else {
+                self.count = Some(byte);
+            }
+        }
+        Ok(())
+    }
+
+    fn finish(&mut self, _output: &mut Vec<u8>) -> Result<(), StreamError> {
+        if self.count.is_some() {
+            return Err(StreamError::compression_error("truncated run"));
+        }
+        Ok(())
+    }
+}
+
+/// Ensures compressed stream data round trips through an echo server
+#[test]
+fn compression_round_trip_test() {
+    let model = Model::default();
+    test(model, |handle| {
+        let limits = Limits::default()
+            .with_compression_dictionary_id(DICTIONARY_ID)
+            .unwrap();
+        let server_addr = start_server(build_server_with_limits(handle, limits)?)?;
+        let client = build_client_with_limits(handle, limits)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            let negotiated = connection.compression_dictionary_id().unwrap();
+            assert_eq!(negotiated, Some(DICTIONARY_ID));
+            assert!(RunLength.is_supported(negotiated));
+
+            // the echo server returns the compressed data as is
+            let stream = connection.open_bidirectional_stream().await.unwrap();
+            let (mut recv, mut send) = stream.split();
+            send.set_encoder(RunLength.encoder()).unwrap();
+            recv.set_decoder(RunLength.decoder()).unwrap();
+
+            let mut expected = vec![];
+            for byte in 0..16u8 {
+                let chunk = vec![byte; 1000];
+                expected.extend_from_slice(&chunk);
+                send.send(chunk.into()).await.unwrap();
+            }
+            send.close().await.unwrap();
+
+            let mut actual = vec![];
+            while let Some(chunk) = recv.receive().await.unwrap() {
+                actual.extend_from_slice(&chunk);
+            }
+
+            assert_eq!(actual, expected);
+        });
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+/// Ensures the dictionary is only negotiated if both endpoints advertise the same identifier
+#[test]
+fn compression_dictionary_mismatch_test() {
+    let model = Model::default();
+    test(model, |handle| {
+        let server_addr = server(handle)?;
+        let limits = Limits::default()
+            .with_compression_dictionary_id(DICTIONARY_ID)
+            .unwrap();
+        let client = build_client_with_limits(handle, limits)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let connection = client.connect(connect).await.unwrap();
+
+            let negotiated = connection.compression_dictionary_id().unwrap();
+            assert_eq!(negotiated, None);
+            assert!(!RunLength.is_supported(negotiated));
+        });
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+/// Ensures the encoder can't be changed once data was sent on the stream
+#[test]
+fn compression_after_send_test() {
+    let model = Model::default();
+    test(model, |handle| {
+        let server_addr = server(handle)?;
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            let mut stream = connection.open_send_stream().await.unwrap();
+            stream.send(Bytes::from_static(b"hello")).await.unwrap();
+
+            let error = stream.set_encoder(RunLength.encoder()).unwrap_err();
+            assert!(matches!(error, StreamError::CompressionError { .. }));
+        });
+
+        Ok(())
+    })
+    .unwrap();
+}
@@ -0,0 +1,78 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::connection;
+use recorder::ConnectionClosed;
+
+/// Ensures a racing connection attempt skips unreachable addresses
+#[test]
+fn connect_race_test() {
+    let model = Model::default();
+    test(model, |handle| {
+        let server_addr = server(handle)?;
+
+        // nothing is listening on this address so the attempt will never complete
+        let mut unreachable_addr = server_addr;
+        unreachable_addr.set_port(server_addr.port() + 1);
+
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new_racing([unreachable_addr, server_addr])
+                .unwrap()
+                .with_server_name("localhost");
+            let connection = client.connect(connect).await.unwrap();
+
+            assert_eq!(connection.remote_addr().unwrap(), server_addr);
+        });
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+/// Ensures the next address is only tried after the attempt delay and the losing attempt is
+/// closed as soon as the race is won
+#[test]
+fn connect_race_delay_test() {
+    const ATTEMPT_DELAY: Duration = Duration::from_secs(1);
+
+    let model = Model::default();
+    test(model, |handle| {
+        let server_addr = server(handle)?;
+
+        let mut unreachable_addr = server_addr;
+        unreachable_addr.set_port(server_addr.port() + 1);
+
+        let recorder = ConnectionClosed::new();
+        let closed = recorder.events();
+        let client = build_client_with_events(handle, recorder)?;
+
+        primary::spawn(async move {
+            let start = io::now();
+            let connect = Connect::new_racing([unreachable_addr, server_addr])
+                .unwrap()
+                .with_attempt_delay(ATTEMPT_DELAY)
+                .with_server_name("localhost");
+            let connection = client.connect(connect).await.unwrap();
+
+            assert_eq!(connection.remote_addr().unwrap(), server_addr);
+            let elapsed = io::now() - start;
+            assert!(elapsed >= ATTEMPT_DELAY, "{elapsed:?}");
+
+            // the losing attempt is closed without waiting for its handshake to time out
+            delay(Duration::from_millis(100)).await;
+            let closed = closed.lock().unwrap();
+            assert_eq!(closed.len(), 1);
+            assert!(
+                matches!(closed[0], connection::Error::Closed { .. }),
+                "{:?}",
+                closed[0]
+            );
+        });
+
+        Ok(())
+    })
+    .unwrap();
+}
@@ -0,0 +1,175 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::{client::retry, connection};
+use recorder::ConnectionStarted;
+
+/// Ensures retryable errors are retried until the policy's attempts are exhausted
+#[test]
+fn connect_retry_exhausted_test() {
+    let model = Model::default();
+    test(model, |handle| {
+        let server_addr = server(handle)?;
+
+        // nothing is listening on this address so the attempts will never complete
+        let mut unreachable_addr = server_addr;
+        unreachable_addr.set_port(server_addr.port() + 1);
+
+        let recorder = ConnectionStarted::new();
+        let attempts = recorder.events();
+        let client = build_client_with_events(handle, recorder)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(unreachable_addr).with_server_name("localhost");
+            let policy = retry::Policy::default().with_max_attempts(3);
+            let error = client
+                .connect_with_retry(connect, policy)
+                .await
+                .unwrap_err();
+
+            assert!(retry::is_retryable(&error), "{error:?}");
+            assert_eq!(attempts.lock().unwrap().len(), 3);
+        });
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+/// Ensures errors which aren't classified as retryable are returned immediately
+#[test]
+fn connect_retry_fatal_test() {
+    let model = Model::default();
+    test(model, |handle| {
+        let server_addr = server(handle)?;
+
+        let mut unreachable_addr = server_addr;
+        unreachable_addr.set_port(server_addr.port() + 1);
+
+        let recorder = ConnectionStarted::new();
+        let attempts = recorder.events();
+        let client = build_client_with_events(handle, recorder)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(unreachable_addr).with_server_name("localhost");
+            let policy = retry::Policy::default()
+                .with_max_attempts(3)
+                .with_retryable(|_: &connection::Error| false);
+            assert!(client.connect_with_retry(connect, policy).await.is_err());
+            assert_eq!(attempts.lock().unwrap().len(), 1);
+        });
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+/// Ensures a successful attempt is returned without retrying
+#[test]
+fn connect_retry_success_test() {
+    let model = Model::default();
+    test(model, |handle| {
+        let server_addr = server(handle)?;
+
+        let recorder = ConnectionStarted::new();
+        let attempts = recorder.events();
+        let client = build_client_with_events(handle, recorder)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let connection = client
+                .connect_with_retry(connect, retry::Policy::default())
+                .await
+                .unwrap();
+
+            assert_eq!(connection.remote_addr().unwrap(), server_addr);
+            assert_eq!(attempts.lock().unwrap().len(), 1);
+        });
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+/// Ensures the client waits for the backoff between attempts
+#[test]
+fn connect_retry_delay_test() {
+    let model = Model::default();
+    test(model, |handle| {
+        let server_addr = server(handle)?;
+
+        let mut unreachable_addr = server_addr;
+        unreachable_addr.set_port(server_addr.port() + 1);
+
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(unreachable_addr).with_server_name("localhost");
+            let policy = retry::Policy::default()
+                .with_initial_backoff(Duration::from_secs(1))
+                .with_jitter(false);
+
+            // measure how long a single attempt takes to fail
+            let start = io::now();
+            let _ = client
+                .connect_with_retry(connect.clone(), policy.with_max_attempts(1))
+                .await
+                .unwrap_err();
+            let attempt = io::now() - start;
+
+            // three attempts are separated by a 1s and a 2s backoff
+            let start = io::now();
+            let _ = client
+                .connect_with_retry(connect, policy.with_max_attempts(3))
+                .await
+                .unwrap_err();
+            let backoff = (io::now() - start) - attempt * 3;
+
+            assert!(backoff >= Duration::from_secs(3), "{backoff:?}");
+            assert!(backoff < Duration::from_millis(3100), "{backoff:?}");
+        });
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+/// Ensures no more attempts are made once the retry budget elapses
+#[test]
+fn connect_retry_budget_test() {
+    let model = Model::default();
+    test(model, |handle| {
+        let server_addr = server(handle)?;
+
+        let mut unreachable_addr = server_addr;
+        unreachable_addr.set_port(server_addr.port() + 1);
+
+        let recorder = ConnectionStarted::new();
+        let attempts = recorder.events();
+        let client = build_client_with_events(handle, recorder)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(unreachable_addr).with_server_name("localhost");
+            let policy = retry::Policy::default()
+                .with_initial_backoff(Duration::from_secs(60))
+                .with_jitter(false)
+                .with_budget(Duration::from_secs(30));
+
+            let start = io::now();
+            let error = client
+                .connect_with_retry(connect, policy)
+                .await
+                .unwrap_err();
+            let elapsed = io::now() - start;
+
+            assert!(retry::is_retryable(&error), "{error:?}");
+            assert_eq!(attempts.lock().unwrap().len(), 1);
+            // the client stops waiting for the backoff once the budget elapses
+            assert!(elapsed < Duration::from_secs(31), "{elapsed:?}");
+        });
+
+        Ok(())
+    })
+    .unwrap();
+}
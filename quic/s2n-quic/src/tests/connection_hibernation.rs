@@ -0,0 +1,62 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::limits::Limits;
+use recorder::ConnectionHibernated;
+
+/// Ensures idle connections hibernate on both endpoints and deliver intact data once they become
+/// active again
+#[test]
+fn connection_hibernation_test() {
+    let model = Model::default();
+    let threshold = Duration::from_secs(1);
+
+    test(model, |handle| {
+        let limits = Limits::default()
+            .with_idle_hibernation_threshold(threshold)
+            .unwrap();
+
+        let server_addr = start_server(build_server_with_limits(handle, limits)?)?;
+        let hibernated = ConnectionHibernated::new();
+        let hibernated_events = hibernated.events();
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event((tracing_events(), hibernated))?
+            .with_random(Random::with_seed(123))?
+            .with_limits(limits)?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+
+            for round in 1..=3u8 {
+                // the server echoes the data back through its own hibernated buffers
+                stream.send(vec![round; 10_000].into()).await.unwrap();
+                let mut received = 0;
+                while received < 10_000 {
+                    let chunk = stream.receive().await.unwrap().unwrap();
+                    assert!(chunk.iter().all(|byte| *byte == round));
+                    received += chunk.len();
+                }
+
+                // the connection is still active
+                assert_eq!(hibernated_events.lock().unwrap().len(), round as usize - 1);
+
+                delay(threshold * 3).await;
+
+                // each idle period hibernates the connection once
+                let events = hibernated_events.lock().unwrap();
+                assert_eq!(events.len(), round as usize);
+                assert!(events.iter().all(|event| *event == threshold));
+            }
+        });
+
+        Ok(())
+    })
+    .unwrap();
+}
@@ -0,0 +1,95 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::limits::Limits;
+use s2n_quic_core::{
+    connection::id::RotationPolicy,
+    event::api::{Frame, FrameSent},
+};
+
+/// Ensures the application can rotate the connection IDs of an established connection
+#[test]
+fn rotate_connection_id_test() {
+    let model = Model::default();
+    let client_subscriber = recorder::FrameSent::new();
+    let client_events = client_subscriber.events();
+
+    test(model, |handle| {
+        let server_addr = server(handle)?;
+        let client = build_client_with_events(handle, client_subscriber)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            // wait for the server to provide additional connection IDs
+            delay(Duration::from_secs(1)).await;
+            assert!(connection.rotate_connection_id().unwrap());
+
+            // the connection is still usable after the rotation
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+            stream.send(Bytes::from_static(b"hello")).await.unwrap();
+            stream.finish().unwrap();
+            assert_eq!(
+                stream.receive().await.unwrap(),
+                Some(Bytes::from_static(b"hello"))
+            );
+        });
+
+        Ok(())
+    })
+    .unwrap();
+
+    let client_events = client_events.lock().unwrap();
+
+    // the handshake connection ID and the rotated connection ID are retired
+    assert_eq!(retire_connection_id_count(&client_events), 2);
+
+    // the server is requested to retire the connection IDs issued by the client
+    assert!(max_retire_prior_to(&client_events) > 1);
+}
+
+/// Ensures connection IDs are rotated automatically with the configured policy
+#[test]
+fn rotation_policy_test() {
+    let model = Model::default();
+    let client_subscriber = recorder::FrameSent::new();
+    let client_events = client_subscriber.events();
+
+    test(model, |handle| {
+        let server_addr = server(handle)?;
+        let limits = Limits::default()
+            .with_connection_id_rotation_policy(RotationPolicy::AfterPackets(20))?;
+        let client = build_client_with_limits_and_events(handle, limits, client_subscriber)?;
+        start_client(client, server_addr, Data::new(100_000))?;
+
+        Ok(())
+    })
+    .unwrap();
+
+    let client_events = client_events.lock().unwrap();
+
+    // connection IDs are retired in addition to the handshake connection ID
+    assert!(retire_connection_id_count(&client_events) > 1);
+}
+
+fn retire_connection_id_count(events: &[FrameSent]) -> usize {
+    events
+        .iter()
+        .filter(|frame_sent| matches!(frame_sent.frame, Frame::RetireConnectionId { .. }))
+        .count()
+}
+
+fn max_retire_prior_to(events: &[FrameSent]) -> u64 {
+    events
+        .iter()
+        .filter_map(|frame_sent| match frame_sent.frame {
+            Frame::NewConnectionId {
+                retire_prior_to, ..
+            } => Some(retire_prior_to),
+            _ => None,
+        })
+        .max()
+        .unwrap_or(0)
+}
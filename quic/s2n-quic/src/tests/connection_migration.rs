@@ -22,6 +22,7 @@ where
 
     let expected_paths = Arc::new(Mutex::new(vec![]));
     let expected_paths_pub = expected_paths.clone();
+    let expected_paths_client = expected_paths.clone();
 
     let on_socket = move |socket: io::Socket| {
         spawn(async move {
@@ -85,6 +86,13 @@ where
                 stream.receive().await.unwrap().is_none(),
                 "stream should be finished"
             );
+
+            // the connection should report the address the socket was last rebound to
+            let local_addr = *expected_paths_client.lock().unwrap().last().unwrap();
+            assert_eq!(conn.local_addr().unwrap(), local_addr);
+            let path_stats = conn.path_stats().unwrap();
+            assert_eq!(path_stats.len(), 1);
+            assert_eq!(SocketAddr::from(path_stats[0].local_address), local_addr);
         });
 
         Ok(addr)
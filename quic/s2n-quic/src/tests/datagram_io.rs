@@ -0,0 +1,140 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::io::datagram::{self, Transport};
+use core::{
+    future::Future,
+    pin::Pin,
+    sync::atomic::{AtomicUsize, Ordering},
+    task::{ready, Context, Poll},
+};
+use s2n_quic_core::inet::SocketAddress;
+use tokio::{net::UdpSocket, runtime::Handle, time::Sleep};
+
+/// A transport over a tokio UDP socket which reports every other send as blocked
+struct BlockingTransport {
+    socket: UdpSocket,
+    timer: Pin<Box<Sleep>>,
+    is_blocked: bool,
+    blocked: Arc<AtomicUsize>,
+}
+
+impl BlockingTransport {
+    async fn bind(blocked: Arc<AtomicUsize>) -> Self {
+        Self {
+            socket: UdpSocket::bind("127.0.0.1:0").await.unwrap(),
+            timer: Box::pin(tokio::time::sleep(Duration::ZERO)),
+            is_blocked: false,
+            blocked,
+        }
+    }
+}
+
+impl Transport for BlockingTransport {
+    fn local_address(&self) -> std::io::Result<SocketAddress> {
+        Ok(self.socket.local_addr()?.into())
+    }
+
+    fn poll_recv(
+        &mut self,
+        cx: &mut Context,
+        buffer: &mut [u8],
+    ) -> Poll<std::io::Result<(SocketAddress, usize)>> {
+        let mut buffer = tokio::io::ReadBuf::new(buffer);
+        let remote_address = ready!(self.socket.poll_recv_from(cx, &mut buffer))?;
+        Poll::Ready(Ok((remote_address.into(), buffer.filled().len())))
+    }
+
+    fn poll_send(
+        &mut self,
+        cx: &mut Context,
+        remote_address: SocketAddress,
+        payload: &[u8],
+    ) -> Poll<std::io::Result<()>> {
+        self.is_blocked = !self.is_blocked;
+        if self.is_blocked {
+            self.blocked.fetch_add(1, Ordering::Relaxed);
+            cx.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+
+        let remote_address: SocketAddr = remote_address.into();
+        ready!(self.socket.poll_send_to(cx, payload, remote_address))?;
+        Poll::Ready(Ok(()))
+    }
+
+    fn poll_timer(&mut self, cx: &mut Context, duration: Duration) -> Poll<()> {
+        let deadline = tokio::time::Instant::now() + duration;
+        self.timer.as_mut().reset(deadline);
+        self.timer.as_mut().poll(cx)
+    }
+}
+
+async fn build_io(blocked: &Arc<AtomicUsize>) -> datagram::Provider {
+    datagram::Builder::default()
+        .with_transport(BlockingTransport::bind(blocked.clone()).await)
+        .with_spawner(Handle::current())
+        .build()
+        .unwrap()
+}
+
+/// Ensures a connection completes over the datagram IO provider when the transport
+/// periodically can't accept datagrams, which requires the blocked datagrams to be retried
+#[tokio::test]
+async fn datagram_io_blocked_send_test() {
+    let blocked = Arc::new(AtomicUsize::new(0));
+
+    let mut server = Server::builder()
+        .with_io(build_io(&blocked).await)
+        .unwrap()
+        .with_tls(SERVER_CERTS)
+        .unwrap()
+        .start()
+        .unwrap();
+    let server_addr = server.local_addr().unwrap();
+
+    tokio::spawn(async move {
+        let mut connection = server.accept().await.unwrap();
+        let mut stream = connection
+            .accept_bidirectional_stream()
+            .await
+            .unwrap()
+            .unwrap();
+        while let Some(chunk) = stream.receive().await.unwrap() {
+            stream.send(chunk).await.unwrap();
+        }
+        stream.finish().unwrap();
+    });
+
+    let client = Client::builder()
+        .with_io(build_io(&blocked).await)
+        .unwrap()
+        .with_tls(certificates::CERT_PEM)
+        .unwrap()
+        .start()
+        .unwrap();
+
+    let connect = Connect::new(server_addr).with_server_name("localhost");
+    let mut connection = client.connect(connect).await.unwrap();
+    let stream = connection.open_bidirectional_stream().await.unwrap();
+    let (mut recv, mut send) = stream.split();
+
+    let mut send_data = Data::new(100_000);
+    let mut recv_data = send_data;
+
+    let reader = tokio::spawn(async move {
+        while let Some(chunk) = recv.receive().await.unwrap() {
+            recv_data.receive(&[chunk]);
+        }
+        assert!(recv_data.is_finished());
+    });
+
+    while let Some(chunk) = send_data.send_one(usize::MAX) {
+        send.send(chunk).await.unwrap();
+    }
+    send.finish().unwrap();
+
+    reader.await.unwrap();
+    assert!(blocked.load(Ordering::Relaxed) > 0);
+}
@@ -0,0 +1,29 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::{connection, provider::limits::Limits};
+
+/// Ensures datagram-only connections are rejected without a datagram provider
+#[test]
+fn datagram_only_without_provider_test() {
+    let model = Model::default();
+    test(model, |handle| {
+        let server_addr = server(handle)?;
+        let limits = Limits::default().with_datagram_only(true).unwrap();
+        let client = build_client_with_limits(handle, limits)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let result = client.connect(connect).await;
+
+            assert!(matches!(
+                result,
+                Err(connection::Error::InvalidConfiguration { .. })
+            ));
+        });
+
+        Ok(())
+    })
+    .unwrap();
+}
@@ -0,0 +1,117 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::{connection, provider::dns};
+
+/// Resolves every host to the configured endpoints
+struct StaticResolver(Vec<dns::Endpoint>);
+
+/// Fails to resolve every host
+struct FailingResolver;
+
+impl dns::Resolver for FailingResolver {
+    fn resolve(&self, _host: &dns::Host) -> dns::Resolve {
+        let error = std::io::Error::new(std::io::ErrorKind::NotFound, "no such host");
+        Box::pin(async move { Err(error) })
+    }
+}
+
+impl dns::Resolver for StaticResolver {
+    fn resolve(&self, host: &dns::Host) -> dns::Resolve {
+        assert_eq!(&**host.name(), "localhost");
+        let endpoints = self.0.clone();
+        Box::pin(async move { Ok(endpoints) })
+    }
+}
+
+/// Ensures hosts are resolved with the configured DNS provider, in priority order
+#[test]
+fn dns_resolver_test() {
+    let model = Model::default();
+    test(model, |handle| {
+        let server_addr = server(handle)?;
+
+        // nothing is listening on this address so the attempt will never complete
+        let mut unreachable_addr = server_addr;
+        unreachable_addr.set_port(server_addr.port() + 1);
+
+        let resolver = StaticResolver(vec![
+            dns::Endpoint::new([unreachable_addr]),
+            dns::Endpoint::new([server_addr]).with_priority(1),
+        ]);
+
+        let client = Client::builder()
+            .with_io(handle.builder().build().unwrap())?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(123))?
+            .with_dns(resolver)?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::from_host("localhost:443").unwrap();
+            let connection = client.connect(connect).await.unwrap();
+
+            assert_eq!(connection.remote_addr().unwrap(), server_addr);
+        });
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+/// Ensures connection attempts fail if the host doesn't resolve to any addresses
+#[test]
+fn dns_empty_test() {
+    let model = Model::default();
+    test(model, |handle| {
+        let client = Client::builder()
+            .with_io(handle.builder().build().unwrap())?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(123))?
+            .with_dns(StaticResolver(vec![]))?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::from_host("localhost:443").unwrap();
+            let error = client.connect(connect).await.unwrap_err();
+
+            assert!(matches!(error, connection::Error::NoValidPath { .. }));
+        });
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+/// Ensures the resolver's error is returned to the application
+#[test]
+fn dns_error_test() {
+    let model = Model::default();
+    test(model, |handle| {
+        let client = Client::builder()
+            .with_io(handle.builder().build().unwrap())?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(123))?
+            .with_dns(FailingResolver)?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::from_host("localhost:443").unwrap();
+            let error = client.connect(connect).await.unwrap_err();
+
+            match error {
+                connection::Error::HostResolutionFailed { reason, .. } => {
+                    assert_eq!(reason.as_str(), "no such host");
+                }
+                error => panic!("unexpected error: {error:?}"),
+            }
+        });
+
+        Ok(())
+    })
+    .unwrap();
+}
@@ -0,0 +1,77 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::event::{accounting, query, ConnectionInfo, ConnectionMeta, Subscriber};
+
+#[derive(Clone, Debug, PartialEq)]
+struct Tag(&'static str);
+
+struct Tagger(&'static str);
+
+impl Subscriber for Tagger {
+    type ConnectionContext = Tag;
+
+    fn create_connection_context(
+        &mut self,
+        _meta: &ConnectionMeta,
+        _info: &ConnectionInfo,
+    ) -> Self::ConnectionContext {
+        Tag(self.0)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Ledger(u64);
+
+impl accounting::Ledger for Ledger {
+    fn on_connection_finalized(&mut self, _totals: &accounting::Totals) {}
+}
+
+struct Accounting;
+
+impl accounting::Accounting for Accounting {
+    type Ledger = Ledger;
+
+    fn on_connection_opened(
+        &mut self,
+        _meta: &ConnectionMeta,
+        _info: &ConnectionInfo,
+    ) -> Self::Ledger {
+        Ledger(7)
+    }
+}
+
+/// Ensures contexts can be queried by type from nested and wrapping subscribers
+#[test]
+fn typed_event_context_test() {
+    let model = Model::default();
+
+    test(model, |handle| {
+        let server = build_server(handle)?;
+        let addr = start_server(server)?;
+        let client = build_client_with_events(
+            handle,
+            (
+                (Tagger("first"), accounting::Subscriber::new(Accounting)),
+                Tagger("second"),
+            ),
+        )?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let connection = client.connect(connect).await.unwrap();
+
+            // the first context of each type is returned
+            assert_eq!(connection.event_context::<Tag>().unwrap(), Tag("first"));
+            assert_eq!(connection.event_context::<Ledger>().unwrap(), Ledger(7));
+            assert!(matches!(
+                connection.event_context::<u64>(),
+                Err(query::Error::ContextTypeMismatch)
+            ));
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+}
@@ -0,0 +1,79 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+#[derive(Clone, Default)]
+struct ProfileRecorder {
+    events: Arc<Mutex<Vec<events::PlatformEventLoopProfile>>>,
+}
+
+impl events::Subscriber for ProfileRecorder {
+    type ConnectionContext = ();
+
+    fn create_connection_context(
+        &mut self,
+        _meta: &events::ConnectionMeta,
+        _info: &events::ConnectionInfo,
+    ) -> Self::ConnectionContext {
+    }
+
+    fn on_platform_event_loop_profile(
+        &mut self,
+        _meta: &events::EndpointMeta,
+        event: &events::PlatformEventLoopProfile,
+    ) {
+        self.events.lock().unwrap().push(event.clone());
+    }
+}
+
+#[test]
+fn event_loop_profile_test() {
+    let model = Model::default();
+    let interval = Duration::from_millis(10);
+    let server_recorder = ProfileRecorder::default();
+    let server_events = server_recorder.events.clone();
+    let client_recorder = ProfileRecorder::default();
+    let client_events = client_recorder.events.clone();
+
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(
+                handle
+                    .builder()
+                    .with_event_loop_profiling(interval)
+                    .build()?,
+            )?
+            .with_tls(SERVER_CERTS)?
+            .with_event((tracing_events(), server_recorder))?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+
+        // profiling is disabled on the client
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event((tracing_events(), client_recorder))?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+
+        let addr = start_server(server)?;
+        start_client(client, addr, Data::new(1000))?;
+        Ok(addr)
+    })
+    .unwrap();
+
+    let server_events = server_events.lock().unwrap();
+    assert!(!server_events.is_empty());
+
+    for event in server_events.iter() {
+        assert!(event.interval >= interval, "{event:?}");
+    }
+
+    // the server spent time processing the client's packets
+    assert!(server_events
+        .iter()
+        .any(|event| !event.rx_parse.is_zero() && !event.tx_packing.is_zero()));
+
+    assert!(client_events.lock().unwrap().is_empty());
+}
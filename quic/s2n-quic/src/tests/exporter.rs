@@ -9,6 +9,9 @@ use crate::provider::event::events::{
     self, CipherSuite, ConnectionInfo, ConnectionMeta, Subscriber,
 };
 
+const LABEL: &[u8] = b"EXPERIMENTAL EXPORTER s2n-quic";
+const CONTEXT: &[u8] = b"some context";
+
 struct Exporter;
 
 #[derive(Default)]
@@ -38,7 +41,7 @@ impl Subscriber for Exporter {
         let mut key = [0; 32];
         event
             .session
-            .tls_exporter(b"EXPERIMENTAL EXPORTER s2n-quic", b"some context", &mut key)
+            .tls_exporter(LABEL, CONTEXT, &mut key)
             .unwrap();
         context.key = Some(key);
         context.cipher_suite = Some(event.session.cipher_suite());
@@ -152,3 +155,96 @@ fn happy_case() {
         assert_eq!(client_key, &server_key[..]);
     });
 }
+
+/// Ensures keying material can be exported from the connection after the handshake
+#[test]
+fn export_keying_material_test() {
+    let model = Model::default();
+
+    test(model, |handle| {
+        let limits =
+            crate::provider::limits::Limits::default().with_keying_material_export(true)?;
+
+        let mut server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event((Exporter, tracing_events()))?
+            .with_limits(limits)?
+            .start()?;
+        let addr = server.local_addr()?;
+
+        spawn(async move {
+            while let Some(mut connection) = server.accept().await {
+                spawn(async move {
+                    // keying material can still be exported after the handshake has completed
+                    delay(Duration::from_secs(1)).await;
+                    let key = connection
+                        .export_keying_material(LABEL, CONTEXT, 32)
+                        .unwrap();
+                    let mut stream = connection.open_send_stream().await.unwrap();
+                    stream.send(Bytes::from(key)).await.unwrap();
+                    stream.finish().unwrap();
+                });
+            }
+        });
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event((Exporter, tracing_events()))?
+            .with_limits(limits)?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            let client_key = connection
+                .export_keying_material(LABEL, CONTEXT, 32)
+                .unwrap();
+
+            // the exported value matches the one derived when the exporter was ready
+            let event_key = connection
+                .query_event_context(|ctx: &ExporterContext| ctx.key.unwrap())
+                .unwrap();
+            assert_eq!(client_key, event_key);
+
+            // a different label derives a different value
+            let other_key = connection
+                .export_keying_material(b"EXPORTER-other", CONTEXT, 32)
+                .unwrap();
+            assert_ne!(client_key, other_key);
+
+            // both the server and the client are expected to derive the same key
+            let mut stream = connection.accept_receive_stream().await.unwrap().unwrap();
+            let server_key = stream.receive().await.unwrap().unwrap();
+            assert_eq!(client_key, &server_key[..]);
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+}
+
+/// Ensures keying material can't be exported unless it was enabled in the limits
+#[test]
+fn export_keying_material_disabled_test() {
+    let model = Model::default();
+
+    test(model, |handle| {
+        let addr = server(handle)?;
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let connection = client.connect(connect).await.unwrap();
+
+            assert!(connection
+                .export_keying_material(LABEL, CONTEXT, 32)
+                .is_err());
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+}
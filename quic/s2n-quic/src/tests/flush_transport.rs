@@ -0,0 +1,46 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use recorder::PacketSent;
+
+/// Ensures `flush_transport` waits for pending packets to be transmitted
+#[test]
+fn flush_transport_test() {
+    let model = Model::default();
+
+    test(model, |handle| {
+        let server_addr = server(handle)?;
+        let packet_sent = PacketSent::new();
+        let packets = packet_sent.events();
+        let client = build_client_with_events(handle, packet_sent)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            // flushing resolves even if no stream data is pending
+            connection.flush_transport().await.unwrap();
+
+            let mut stream = connection.open_send_stream().await.unwrap();
+            let sent_before = packets.lock().unwrap().len();
+
+            // the stream data is only buffered by `send`, so it's the transport flush which
+            // waits for it to be transmitted
+            stream.send(Bytes::from_static(b"hello")).await.unwrap();
+            connection.flush_transport().await.unwrap();
+
+            assert!(
+                packets.lock().unwrap().len() > sent_before,
+                "the stream data should be transmitted before the flush completes"
+            );
+
+            // flushing a closed connection returns the close error
+            connection.close(123u8.into());
+            assert!(connection.flush_transport().await.is_err());
+        });
+
+        Ok(())
+    })
+    .unwrap();
+}
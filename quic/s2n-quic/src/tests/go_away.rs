@@ -0,0 +1,98 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::{
+    io::testing::{Handle, Result},
+    limits::Limits,
+};
+
+/// Starts a server which sends a GOAWAY to every accepted connection
+///
+/// The result of each `go_away` call is recorded in `sent`.
+fn start_draining_server(
+    handle: &Handle,
+    limits: Limits,
+    sent: Arc<Mutex<Vec<bool>>>,
+) -> Result<SocketAddr> {
+    let mut server = Server::builder()
+        .with_io(handle.builder().build()?)?
+        .with_tls(SERVER_CERTS)?
+        .with_event(tracing_events())?
+        .with_random(Random::with_seed(456))?
+        .with_limits(limits)?
+        .start()?;
+    let server_addr = server.local_addr()?;
+
+    spawn(async move {
+        while let Some(mut connection) = server.accept().await {
+            let result = connection.go_away().unwrap();
+            sent.lock().unwrap().push(result);
+
+            spawn(async move {
+                // keep the connection open until the client closes it
+                while let Ok(Some(_)) = connection.accept().await {}
+            });
+        }
+    });
+
+    Ok(server_addr)
+}
+
+/// Ensures the client is notified when the server requests a move to a new connection
+#[test]
+fn go_away_test() {
+    let model = Model::default();
+    let sent = Arc::new(Mutex::new(vec![]));
+    let server_sent = sent.clone();
+
+    test(model, |handle| {
+        let limits = Limits::default().with_go_away(true).unwrap();
+        let server_addr = start_draining_server(handle, limits, server_sent)?;
+        let client = build_client_with_limits(handle, limits)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            delay(Duration::from_secs(1)).await;
+            assert!(connection.is_go_away_received().unwrap());
+
+            // the connection is still usable after the GOAWAY is received
+            connection.ping().unwrap();
+            assert!(connection.go_away().is_err(), "clients can't send GOAWAY");
+        });
+
+        Ok(())
+    })
+    .unwrap();
+
+    assert_eq!(*sent.lock().unwrap(), [true]);
+}
+
+/// Ensures GOAWAY frames are not sent to peers which don't support them
+#[test]
+fn go_away_unsupported_test() {
+    let model = Model::default();
+    let sent = Arc::new(Mutex::new(vec![]));
+    let server_sent = sent.clone();
+
+    test(model, |handle| {
+        let limits = Limits::default().with_go_away(true).unwrap();
+        let server_addr = start_draining_server(handle, limits, server_sent)?;
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let connection = client.connect(connect).await.unwrap();
+
+            delay(Duration::from_secs(1)).await;
+            assert!(!connection.is_go_away_received().unwrap());
+        });
+
+        Ok(())
+    })
+    .unwrap();
+
+    assert_eq!(*sent.lock().unwrap(), [false]);
+}
@@ -0,0 +1,77 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+/// Ensures applications can wait for the handshake to be confirmed on both endpoints
+#[test]
+fn handshake_completed_test() {
+    let model = Model::default();
+    model.set_delay(Duration::from_millis(50));
+
+    test(model, |handle| {
+        let mut server = build_server(handle)?;
+        let server_addr = server.local_addr()?;
+        let client = build_client(handle)?;
+
+        spawn(async move {
+            let mut connection = server.accept().await.unwrap();
+
+            // the server confirms the handshake as soon as it completes
+            connection.handshake_completed().await.unwrap();
+
+            let mut stream = connection
+                .accept_bidirectional_stream()
+                .await
+                .unwrap()
+                .unwrap();
+            while let Some(chunk) = stream.receive().await.unwrap() {
+                stream.send(chunk).await.unwrap();
+            }
+        });
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            // the client waits for the HANDSHAKE_DONE frame from the server
+            connection.handshake_completed().await.unwrap();
+
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+            stream.send(Bytes::from_static(b"hello")).await.unwrap();
+            stream.finish().unwrap();
+            assert_eq!(
+                stream.receive().await.unwrap(),
+                Some(Bytes::from_static(b"hello"))
+            );
+
+            // the future resolves immediately once the handshake has been confirmed
+            connection.handshake_completed().await.unwrap();
+        });
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+/// Ensures the future returns an error if the connection closes
+#[test]
+fn handshake_completed_closed_test() {
+    let model = Model::default();
+
+    test(model, |handle| {
+        let server_addr = server(handle)?;
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            connection.close(123u8.into());
+            assert!(connection.handshake_completed().await.is_err());
+        });
+
+        Ok(())
+    })
+    .unwrap();
+}
@@ -0,0 +1,100 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::connection;
+use s2n_quic_core::endpoint;
+
+const IDLE_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Ensures the application can shorten the idle timeout after the handshake and the peer is
+/// notified when it expires
+#[test]
+fn set_idle_timeout_test() {
+    let model = Model::default();
+    let server_result = Arc::new(Mutex::new(None));
+
+    test(model, |handle| {
+        let mut server = build_server(handle)?;
+        let server_addr = server.local_addr()?;
+        let client = build_client(handle)?;
+
+        let server_result = server_result.clone();
+        spawn(async move {
+            let mut connection = server.accept().await.unwrap();
+            let start = io::now();
+            let result = connection.accept_bidirectional_stream().await;
+            *server_result.lock().unwrap() = Some((result.map(|_| ()), io::now() - start));
+        });
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            assert!(connection.set_idle_timeout(Duration::ZERO).is_err());
+            connection.set_idle_timeout(IDLE_TIMEOUT).unwrap();
+
+            // the connection times out well before the negotiated idle timeout
+            let start = io::now();
+            let result = connection.accept_bidirectional_stream().await;
+            assert!(
+                matches!(result, Err(connection::Error::IdleTimerExpired { .. })),
+                "{result:?}"
+            );
+            assert_eq!(
+                result.unwrap_err().close_source(),
+                Some(connection::error::CloseSource::IdleTimeout)
+            );
+            let elapsed = io::now() - start;
+            assert!(elapsed >= IDLE_TIMEOUT);
+            assert!(elapsed < Duration::from_secs(5), "{elapsed:?}");
+
+            // give the server time to process the CONNECTION_CLOSE
+            delay(Duration::from_secs(1)).await;
+        });
+
+        Ok(())
+    })
+    .unwrap();
+
+    // the client closed the connection immediately rather than leaving the server to time out
+    let (result, elapsed) = server_result.lock().unwrap().take().unwrap();
+    assert!(
+        matches!(
+            result,
+            Err(connection::Error::Closed {
+                initiator: endpoint::Location::Remote,
+                ..
+            })
+        ),
+        "{result:?}"
+    );
+    assert!(elapsed < Duration::from_secs(5), "{elapsed:?}");
+}
+
+/// Ensures keep alive continues to hold the connection open with a shortened idle timeout
+#[test]
+fn set_idle_timeout_keep_alive_test() {
+    let model = Model::default();
+
+    test(model, |handle| {
+        let server_addr = server(handle)?;
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            connection.set_idle_timeout(IDLE_TIMEOUT).unwrap();
+            connection.keep_alive(true).unwrap();
+
+            delay(IDLE_TIMEOUT * 5).await;
+
+            // the connection is still usable
+            connection.ping().unwrap();
+        });
+
+        Ok(())
+    })
+    .unwrap();
+}
@@ -0,0 +1,76 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::limits::Limits;
+use s2n_quic_core::event::api::PacketHeader;
+
+/// Ensures connections complete when both endpoints start each packet number space at a random
+/// offset
+#[test]
+fn initial_packet_number_offset_test() {
+    let max_offset = 1_000_000;
+    let model = Model::default();
+    let server_subscriber = recorder::PacketSent::new();
+    let server_events = server_subscriber.events();
+    let client_subscriber = recorder::PacketSent::new();
+    let client_events = client_subscriber.events();
+
+    test(model, move |handle| {
+        let limits = Limits::default()
+            .with_max_initial_packet_number_offset(max_offset)
+            .unwrap();
+
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event((tracing_events(), server_subscriber))?
+            .with_random(Random::with_seed(456))?
+            .with_limits(limits)?
+            .start()?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event((tracing_events(), client_subscriber))?
+            .with_random(Random::with_seed(456))?
+            .with_limits(limits)?
+            .start()?;
+
+        let addr = start_server(server)?;
+        start_client(client, addr, Data::new(100_000))?;
+        Ok(addr)
+    })
+    .unwrap();
+
+    for events in [server_events, client_events] {
+        let events = events.lock().unwrap();
+
+        let first = |filter: fn(&PacketHeader) -> Option<u64>| {
+            events
+                .iter()
+                .filter_map(|event| filter(&event.packet_header))
+                .min()
+                .expect("packets should be sent in every space")
+        };
+
+        let initial = first(|header| match header {
+            PacketHeader::Initial { number, .. } => Some(*number),
+            _ => None,
+        });
+        let handshake = first(|header| match header {
+            PacketHeader::Handshake { number, .. } => Some(*number),
+            _ => None,
+        });
+        let one_rtt = first(|header| match header {
+            PacketHeader::OneRtt { number, .. } => Some(*number),
+            _ => None,
+        });
+
+        // with this seed none of the spaces should start at 0
+        for number in [initial, handshake, one_rtt] {
+            assert_ne!(number, 0);
+            assert!(number <= max_offset as u64);
+        }
+    }
+}
@@ -2,6 +2,21 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::*;
+use crate::provider::{
+    io::testing::{Handle, Result},
+    packet_interceptor::{
+        rewrite::{Action, Rule},
+        FrameInterceptor, Frames, Rewrite,
+    },
+};
+use s2n_codec::{Encoder, EncoderBuffer, EncoderValue};
+use s2n_quic_core::{
+    connection::Error,
+    event::api::Subject,
+    frame::{Frame, FrameRef, NewToken},
+    packet::interceptor::Packet,
+    varint::VarInt,
+};
 
 fn intercept_loss(loss: Loss<Random>) {
     let model = Model::default();
@@ -40,3 +55,203 @@ fn interceptor_failure_test() {
             .build(),
     )
 }
+
+/// A frame type which isn't defined by the library
+const UNKNOWN_FRAME_TAG: VarInt = VarInt::from_u32(0x7a7a);
+
+/// Injects a frame into every 1-RTT packet and records the frames received from the peer
+#[derive(Default)]
+struct Recorder {
+    inject: Vec<u8>,
+    new_tokens: Arc<Mutex<Vec<Vec<u8>>>>,
+    unknown_frames: Arc<Mutex<Vec<VarInt>>>,
+}
+
+impl FrameInterceptor for Recorder {
+    fn on_rx_frame(&mut self, _subject: &Subject, _packet: &Packet, frame: &FrameRef) {
+        if let Frame::NewToken(frame) = frame {
+            self.new_tokens.lock().unwrap().push(frame.token.to_vec());
+        }
+    }
+
+    fn on_rx_unknown_frame(&mut self, _subject: &Subject, _packet: &Packet, tag: VarInt) {
+        self.unknown_frames.lock().unwrap().push(tag);
+    }
+
+    fn inject_tx_frames(
+        &mut self,
+        _subject: &Subject,
+        packet: &Packet,
+        buffer: &mut EncoderBuffer,
+    ) {
+        if packet.number.space().is_application_data()
+            && buffer.remaining_capacity() >= self.inject.len()
+        {
+            buffer.write_slice(&self.inject);
+        }
+    }
+}
+
+fn start_intercepted_server(handle: &Handle, recorder: Recorder) -> Result<SocketAddr> {
+    let server = Server::builder()
+        .with_io(handle.builder().build()?)?
+        .with_tls(SERVER_CERTS)?
+        .with_event(tracing_events())?
+        .with_random(Random::with_seed(456))?
+        .with_packet_interceptor(Frames(recorder))?
+        .start()?;
+
+    start_server(server)
+}
+
+fn build_intercepted_client(handle: &Handle, recorder: Recorder) -> Result<Client> {
+    Ok(Client::builder()
+        .with_io(handle.builder().build()?)?
+        .with_tls(certificates::CERT_PEM)?
+        .with_event(tracing_events())?
+        .with_random(Random::with_seed(123))?
+        .with_packet_interceptor(Frames(recorder))?
+        .start()?)
+}
+
+/// Ensures the peer closes the connection when it receives a frame type it doesn't understand
+#[test]
+fn frame_interceptor_unknown_frame_test() {
+    let model = Model::default();
+    let server = Recorder::default();
+    let unknown_frames = server.unknown_frames.clone();
+
+    test(model, |handle| {
+        let server_addr = start_intercepted_server(handle, server)?;
+
+        let inject = UNKNOWN_FRAME_TAG.encode_to_vec();
+        let client = build_intercepted_client(
+            handle,
+            Recorder {
+                inject,
+                ..Default::default()
+            },
+        )?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let error = match client.connect(connect).await {
+                Ok(mut connection) => connection.accept().await.unwrap_err(),
+                Err(error) => error,
+            };
+
+            // the exact error code isn't checked since unknown frames are currently
+            // reported as a PROTOCOL_VIOLATION
+            assert!(
+                matches!(error, Error::Transport { .. }),
+                "unexpected error: {error:?}"
+            );
+        });
+
+        Ok(())
+    })
+    .unwrap();
+
+    let unknown_frames = unknown_frames.lock().unwrap();
+    assert!(!unknown_frames.is_empty());
+    assert!(unknown_frames.iter().all(|tag| *tag == UNKNOWN_FRAME_TAG));
+}
+
+/// Ensures the client ignores NEW_TOKEN frames from the server
+#[test]
+fn frame_interceptor_new_token_test() {
+    let model = Model::default();
+    let token = b"token".to_vec();
+
+    let client = Recorder::default();
+    let new_tokens = client.new_tokens.clone();
+
+    test(model, |handle| {
+        let inject = NewToken { token: &token }.encode_to_vec();
+        let server_addr = start_intercepted_server(
+            handle,
+            Recorder {
+                inject,
+                ..Default::default()
+            },
+        )?;
+
+        let client = build_intercepted_client(handle, client)?;
+        start_client(client, server_addr, Data::new(10_000))
+    })
+    .unwrap();
+
+    let new_tokens = new_tokens.lock().unwrap();
+    assert!(!new_tokens.is_empty());
+    assert!(new_tokens.iter().all(|received| *received == token));
+}
+
+fn intercept_rewrite(client_rewrite: Rewrite, server_rewrite: Rewrite) {
+    let model = Model::default();
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .with_packet_interceptor(server_rewrite)?
+            .start()?;
+        let server_address = start_server(server)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(123))?
+            .with_packet_interceptor(client_rewrite)?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_address).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+
+            stream.send(Bytes::from_static(b"hello")).await.unwrap();
+            stream.finish().unwrap();
+
+            let mut response = vec![];
+            while response.len() < 5 {
+                let chunk = stream.receive().await.unwrap().unwrap();
+                response.extend_from_slice(&chunk);
+            }
+            assert_eq!(response, b"hello");
+        });
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+/// STREAM frame types
+const STREAM: core::ops::RangeInclusive<u64> = 0x08..=0x0f;
+/// ACK frame types
+const ACK: core::ops::RangeInclusive<u64> = 0x02..=0x03;
+
+/// Ensures streams complete when datagrams carrying STREAM and ACK frames are duplicated and
+/// delayed
+#[test]
+fn rewrite_duplicate_delay_test() {
+    intercept_rewrite(
+        Rewrite::default()
+            .with_tx_rule(Rule::for_frame_types(STREAM, Action::Duplicate(2)))
+            .with_tx_rule(Rule::for_frame_types(
+                ACK,
+                Action::Delay(Duration::from_millis(20)),
+            )),
+        Rewrite::default().with_tx_rule(Rule::for_frame_types(STREAM, Action::Duplicate(1))),
+    )
+}
+
+/// Ensures streams recover when the first datagrams carrying STREAM frames are dropped
+#[test]
+fn rewrite_drop_test() {
+    intercept_rewrite(
+        Rewrite::default().with_tx_rule(Rule::for_frame_types(STREAM, Action::Drop).with_limit(2)),
+        Rewrite::default().with_rx_rule(Rule::for_frame_types(STREAM, Action::Drop).with_limit(1)),
+    )
+}
@@ -0,0 +1,81 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::{provider::limits::Overrides, stream};
+
+/// Ensures the client's per-connection overrides are advertised to the server
+#[test]
+fn connect_limit_overrides_test() {
+    let model = Model::default();
+
+    test(model, |handle| {
+        let mut server = build_server(handle)?;
+        let server_addr = server.local_addr()?;
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let mut connection = server.accept().await.unwrap();
+            connection.handshake_completed().await.unwrap();
+
+            // the client doesn't allow the server to open any unidirectional streams
+            assert!(connection
+                .is_open_stream_blocked(stream::Type::Unidirectional)
+                .unwrap());
+            assert!(connection.try_open_send_stream().unwrap().is_none());
+
+            // the bidirectional stream limit is taken from the client's limits
+            assert!(!connection
+                .is_open_stream_blocked(stream::Type::Bidirectional)
+                .unwrap());
+        });
+
+        primary::spawn(async move {
+            let overrides = Overrides::default()
+                .with_max_open_remote_unidirectional_streams(0)
+                .unwrap();
+            let connect = Connect::new(server_addr)
+                .with_server_name("localhost")
+                .with_limits(overrides);
+            let mut connection = client.connect(connect).await.unwrap();
+            connection.handshake_completed().await.unwrap();
+
+            // keep the connection open until the server has checked its limits
+            delay(Duration::from_secs(1)).await;
+        });
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+/// Ensures connections without overrides use the endpoint's limits
+#[test]
+fn connect_without_limit_overrides_test() {
+    let model = Model::default();
+
+    test(model, |handle| {
+        let mut server = build_server(handle)?;
+        let server_addr = server.local_addr()?;
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let mut connection = server.accept().await.unwrap();
+            connection.handshake_completed().await.unwrap();
+
+            assert!(!connection
+                .is_open_stream_blocked(stream::Type::Unidirectional)
+                .unwrap());
+        });
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+            connection.handshake_completed().await.unwrap();
+            delay(Duration::from_secs(1)).await;
+        });
+
+        Ok(())
+    })
+    .unwrap();
+}
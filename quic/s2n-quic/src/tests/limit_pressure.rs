@@ -0,0 +1,128 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::{
+    io::testing::{Handle, Result},
+    limits::Limits,
+};
+use recorder::{ConnectionDataPressure, StreamLimitPressure};
+
+const MAX_STREAMS: u64 = 5;
+const MAX_DATA: u64 = 10_000;
+
+/// Starts a server which accepts streams but never reads from them, so the client's limits are
+/// never raised
+fn start_server(handle: &Handle) -> Result<SocketAddr> {
+    let limits = Limits::default()
+        .with_max_open_remote_bidirectional_streams(MAX_STREAMS)
+        .unwrap()
+        .with_data_window(MAX_DATA)
+        .unwrap();
+
+    let mut server = Server::builder()
+        .with_io(handle.builder().build()?)?
+        .with_tls(SERVER_CERTS)?
+        .with_event(tracing_events())?
+        .with_random(Random::with_seed(456))?
+        .with_limits(limits)?
+        .start()?;
+    let server_addr = server.local_addr()?;
+
+    spawn(async move {
+        while let Some(mut connection) = server.accept().await {
+            spawn(async move {
+                let mut streams = vec![];
+                while let Ok(Some(stream)) = connection.accept_bidirectional_stream().await {
+                    streams.push(stream);
+                }
+            });
+        }
+    });
+
+    Ok(server_addr)
+}
+
+/// Ensures applications are notified before the peer's limits are exhausted
+#[test]
+fn limit_pressure_test() {
+    let model = Model::default();
+
+    test(model, |handle| {
+        let server_addr = start_server(handle)?;
+        let stream_pressure = StreamLimitPressure::new();
+        let stream_events = stream_pressure.events();
+        let data_pressure = ConnectionDataPressure::new();
+        let data_events = data_pressure.events();
+
+        let limits = Limits::default()
+            .with_stream_limit_pressure_threshold(80)
+            .unwrap()
+            .with_connection_data_pressure_threshold(50)
+            .unwrap();
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event((tracing_events(), (stream_pressure, data_pressure)))?
+            .with_random(Random::with_seed(123))?
+            .with_limits(limits)?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            let mut streams = vec![];
+            for _ in 0..3 {
+                let mut stream = connection.open_bidirectional_stream().await.unwrap();
+                stream.send(Bytes::from_static(b"hello")).await.unwrap();
+                streams.push(stream);
+            }
+
+            delay(Duration::from_millis(100)).await;
+            assert!(stream_events.lock().unwrap().is_empty());
+            assert!(data_events.lock().unwrap().is_empty());
+
+            // crossing 80% of the stream limit is reported
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+            // crossing 50% of the connection flow control limit is reported
+            stream
+                .send(vec![42; MAX_DATA as usize / 2].into())
+                .await
+                .unwrap();
+            streams.push(stream);
+
+            delay(Duration::from_millis(100)).await;
+
+            // opening the last stream doesn't report the same limit again
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+            stream.send(Bytes::from_static(b"hello")).await.unwrap();
+            streams.push(stream);
+
+            delay(Duration::from_millis(100)).await;
+
+            {
+                let events = stream_events.lock().unwrap();
+                assert_eq!(events.len(), 1, "each limit is reported once");
+                assert!(matches!(
+                    events[0].stream_type,
+                    events::StreamType::Bidirectional { .. }
+                ));
+                assert_eq!(events[0].opened_streams, 4);
+                assert_eq!(events[0].stream_limit, MAX_STREAMS);
+            }
+
+            {
+                let events = data_events.lock().unwrap();
+                assert_eq!(events.len(), 1, "each limit is reported once");
+                assert!(events[0].bytes_sent >= MAX_DATA / 2);
+                assert_eq!(events[0].max_data, MAX_DATA);
+            }
+
+            connection.close(123u8.into());
+        });
+
+        Ok(())
+    })
+    .unwrap();
+}
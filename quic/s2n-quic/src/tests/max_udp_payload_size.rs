@@ -0,0 +1,54 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::limits::Limits;
+
+/// Ensures the peer limits its datagrams to the advertised max_udp_payload_size, even
+/// when its path would allow larger datagrams
+#[test]
+fn max_udp_payload_size_enforcement_test() {
+    let max_udp_payload_size = 1300;
+    let model = Model::default();
+    let subscriber = recorder::DatagramDropped::new();
+    let datagram_dropped_events = subscriber.events();
+    let mtu_subscriber = recorder::MtuUpdated::new();
+    let mtu_updated_events = mtu_subscriber.events();
+
+    test(model, move |handle| {
+        let limits = Limits::default()
+            .with_max_udp_payload_size(max_udp_payload_size)
+            .unwrap();
+        let server = Server::builder()
+            .with_io(handle.builder().with_max_mtu(1500).build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event((tracing_events(), subscriber))?
+            .with_random(Random::with_seed(456))?
+            .with_limits(limits)?
+            .start()?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().with_max_mtu(1500).build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event((tracing_events(), mtu_subscriber))?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+
+        let addr = start_server(server)?;
+        start_client(client, addr, Data::new(100_000))?;
+        Ok(addr)
+    })
+    .unwrap();
+
+    // the client never sends a datagram the server has to drop
+    assert!(datagram_dropped_events.lock().unwrap().is_empty());
+
+    // the client probed for a larger MTU but never beyond the server's limit
+    let mtu_updated_events = mtu_updated_events.lock().unwrap();
+    let last_event = mtu_updated_events.last().unwrap();
+    assert!(last_event.search_complete);
+    assert!(last_event.mtu > 1200);
+    for event in mtu_updated_events.iter() {
+        assert!(event.mtu <= max_udp_payload_size);
+    }
+}
@@ -611,3 +611,78 @@ impl Interceptor for TruncatePadding {
         }
     }
 }
+
+/// Ensures the MTU probing history records both lost and acknowledged probes
+#[test]
+fn mtu_history() {
+    let model = Model::default();
+    // the network supports less than the first probe size, so it is lost
+    model.set_max_udp_payload(1400);
+
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().with_max_mtu(1500).build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+        let mtu_config = mtu::Config::builder()
+            .with_max_mtu(1500)
+            .unwrap()
+            .with_history(true)
+            .build()
+            .unwrap();
+        let client = Client::builder()
+            .with_io(handle.builder().with_max_mtu(1500).build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .with_mtu(CustomMtu(mtu_config))?
+            .start()?;
+        let server_addr = start_server(server)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            let stream = connection.open_bidirectional_stream().await.unwrap();
+            let (mut recv, mut send) = stream.split();
+
+            let mut send_data = Data::new(1_000_000);
+            let mut recv_data = send_data;
+
+            primary::spawn(async move {
+                while let Some(chunk) = send_data.send_one(usize::MAX) {
+                    send.send(chunk).await.unwrap();
+                }
+            });
+
+            while let Some(chunk) = recv.receive().await.unwrap() {
+                recv_data.receive(&[chunk]);
+                if recv_data.is_finished() {
+                    break;
+                }
+            }
+
+            let history = connection.mtu_history().unwrap();
+            let history: Vec<_> = history.iter().collect();
+
+            let sent = |entry: &&mtu::HistoryEntry| {
+                matches!(entry, mtu::HistoryEntry::ProbeSent { .. })
+            };
+            let lost = |entry: &&mtu::HistoryEntry| {
+                matches!(entry, mtu::HistoryEntry::ProbeLost { size, .. } if *size > 1400)
+            };
+            let acked = |entry: &&mtu::HistoryEntry| {
+                matches!(entry, mtu::HistoryEntry::ProbeAcknowledged { size } if *size <= 1400)
+            };
+
+            assert!(history.iter().any(sent), "{history:?}");
+            assert!(history.iter().any(lost), "{history:?}");
+            assert!(history.iter().any(acked), "{history:?}");
+        });
+
+        Ok(())
+    })
+    .unwrap();
+}
@@ -0,0 +1,76 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+/// Ensures the server switches to the client's new address as soon as the application
+/// notifies the client of a network change, rather than waiting for the next transmission
+#[test]
+fn notify_network_change_test() {
+    let model = Model::default();
+    let rtt = Duration::from_millis(100);
+    model.set_delay(rtt / 2);
+
+    let client_socket = Arc::new(Mutex::new(None));
+    let client_socket_pub = client_socket.clone();
+    let on_socket = move |socket: io::Socket| {
+        *client_socket_pub.lock().unwrap() = Some(socket);
+    };
+
+    let new_addr = Arc::new(Mutex::new(None));
+    let new_addr_pub = new_addr.clone();
+
+    let active_paths = recorder::ActivePathUpdated::new();
+    let active_path_sub = active_paths.clone();
+
+    test(model, move |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event((tracing_events(), active_path_sub))?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+
+        let client_io = handle.builder().on_socket(on_socket).build()?;
+
+        let client = Client::builder()
+            .with_io(client_io)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+
+        let addr = start_server(server)?;
+        primary::spawn(async move {
+            let connect = Connect::new(addr).with_server_name("localhost");
+            let mut conn = client.connect(connect).await.unwrap();
+            let mut stream = conn.open_bidirectional_stream().await.unwrap();
+            stream.send(Bytes::from_static(b"A")).await.unwrap();
+
+            // wait for the connection to go idle
+            delay(rtt * 10).await;
+
+            let socket = client_socket.lock().unwrap().take().unwrap();
+            let mut local_addr = socket.local_addr().unwrap();
+            local_addr.set_port(local_addr.port() + 1);
+            socket.rebind(local_addr);
+            *new_addr_pub.lock().unwrap() = Some(local_addr);
+
+            client.notify_network_change();
+
+            // the peer should observe the new address within a single round trip
+            delay(rtt * 2).await;
+
+            drop(conn);
+        });
+
+        Ok(addr)
+    })
+    .unwrap();
+
+    let new_addr = new_addr
+        .lock()
+        .unwrap()
+        .expect("client should have rebound");
+    assert_eq!(&*active_paths.events().lock().unwrap(), &[new_addr]);
+}
@@ -0,0 +1,82 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::limits::Limits;
+use s2n_quic_core::{
+    connection::Error,
+    endpoint,
+    event::api::{PacketBudget, PacketHeader},
+    transport,
+};
+
+const MAX_CRYPTO_BYTES: u32 = 100;
+
+/// Ensures a packet exceeding one of the processing budgets closes the connection
+///
+/// The client's first Initial packet carries the entire ClientHello, which is larger than the
+/// server's CRYPTO budget.
+#[test]
+fn packet_budget_exceeded_test() {
+    let model = Model::default();
+    let budget_subscriber = recorder::PacketBudgetExceeded::new();
+    let budget_events = budget_subscriber.events();
+    let closed_subscriber = recorder::ConnectionClosed::new();
+    let closed_events = closed_subscriber.events();
+
+    test(model, |handle| {
+        let limits = Limits::default()
+            .with_max_crypto_bytes_per_packet(MAX_CRYPTO_BYTES)
+            .unwrap();
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event((tracing_events(), (budget_subscriber, closed_subscriber)))?
+            .with_random(Random::with_seed(456))?
+            .with_limits(limits)?
+            .start()?;
+        let server_addr = start_server(server)?;
+
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(123))?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            match client.connect(connect).await.unwrap_err() {
+                Error::Transport {
+                    code, initiator, ..
+                } => {
+                    assert_eq!(code, transport::Error::PROTOCOL_VIOLATION.code);
+                    assert_eq!(initiator, endpoint::Location::Remote);
+                }
+                error => panic!("unexpected error: {error:?}"),
+            }
+        });
+
+        Ok(())
+    })
+    .unwrap();
+
+    let budget_events = budget_events.lock().unwrap();
+    assert_eq!(budget_events.len(), 1, "{budget_events:?}");
+    let event = &budget_events[0];
+    assert!(matches!(event.budget, PacketBudget::CryptoBytes { .. }));
+    assert!(matches!(event.packet_header, PacketHeader::Initial { .. }));
+    assert_eq!(event.limit, MAX_CRYPTO_BYTES as u64);
+
+    let closed_events = closed_events.lock().unwrap();
+    assert_eq!(closed_events.len(), 1, "{closed_events:?}");
+    match closed_events[0] {
+        Error::Transport {
+            code, initiator, ..
+        } => {
+            assert_eq!(code, transport::Error::PROTOCOL_VIOLATION.code);
+            assert_eq!(initiator, endpoint::Location::Local);
+        }
+        error => panic!("unexpected error: {error:?}"),
+    }
+}
@@ -0,0 +1,156 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::limits::Limits;
+use core::ops::Range;
+
+const CHUNK_LEN: usize = 1_000;
+
+/// The ranges of a stream which were delivered to and skipped by the reader
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Received {
+    delivered: Vec<Range<u64>>,
+    expired: Vec<Range<u64>>,
+}
+
+impl Received {
+    fn on_expired(&mut self, range: Range<u64>) {
+        push_range(&mut self.expired, range);
+    }
+
+    fn on_chunk(&mut self, offset: u64, chunk: &[u8]) {
+        // every write is filled with its index so the data can be checked against its offset
+        for (index, byte) in chunk.iter().enumerate() {
+            assert_eq!(*byte as u64, (offset + index as u64) / CHUNK_LEN as u64);
+        }
+
+        push_range(&mut self.delivered, offset..offset + chunk.len() as u64);
+    }
+}
+
+/// Pushes the range onto the list, merging it with the previous one if they're adjacent
+fn push_range(ranges: &mut Vec<Range<u64>>, range: Range<u64>) {
+    match ranges.last_mut() {
+        Some(last) if last.end == range.start => last.end = range.end,
+        _ => ranges.push(range),
+    }
+}
+
+/// Returns a write which is filled with its index
+fn chunk(index: usize) -> Bytes {
+    Bytes::from(vec![index as u8; CHUNK_LEN])
+}
+
+/// Starts a server which records the ranges the reader observed on each stream
+fn start_server(mut server: Server, received: Arc<Mutex<Vec<Received>>>) {
+    spawn(async move {
+        while let Some(mut connection) = server.accept().await {
+            let received = received.clone();
+            spawn(async move {
+                while let Ok(Some(mut stream)) = connection.accept_receive_stream().await {
+                    let received = received.clone();
+                    spawn(async move {
+                        let mut stream_received = Received::default();
+                        let mut offset = 0;
+
+                        loop {
+                            let (expired, chunk) = stream.receive_expiring().await.unwrap();
+
+                            // the skipped data always precedes the next chunk
+                            if let Some(expired) = expired {
+                                assert_eq!(expired.start, offset);
+                                offset = expired.end;
+                                stream_received.on_expired(expired);
+                            }
+
+                            let Some(chunk) = chunk else {
+                                break;
+                            };
+                            stream_received.on_chunk(offset, &chunk);
+                            offset += chunk.len() as u64;
+                        }
+
+                        received.lock().unwrap().push(stream_received);
+                    });
+                }
+            });
+        }
+    });
+}
+
+/// Sends a reliable write, an expiring write during a blackhole and another reliable write
+fn run(client_limits: Limits, server_limits: Limits) -> Vec<Received> {
+    let model = Model::default();
+    model.set_delay(Duration::from_millis(50));
+
+    let received = Arc::new(Mutex::new(vec![]));
+    let server_received = received.clone();
+
+    test(model.clone(), |handle| {
+        let server = build_server_with_limits(handle, server_limits)?;
+        let server_addr = server.local_addr()?;
+        start_server(server, server_received);
+
+        let client = build_client_with_limits(handle, client_limits)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            let mut stream = connection.open_send_stream().await.unwrap();
+            stream.send(chunk(0)).await.unwrap();
+            stream.flush().await.unwrap();
+
+            // the expiring write is lost and expires before the network recovers
+            model.set_drop_rate(1.0);
+            stream
+                .send_expiring(chunk(1), Duration::from_millis(10))
+                .await
+                .unwrap();
+            delay(Duration::from_millis(100)).await;
+            model.set_drop_rate(0.0);
+
+            stream.send(chunk(2)).await.unwrap();
+
+            // the stream finishes once all of the data was either delivered or skipped
+            stream.close().await.unwrap();
+        });
+
+        Ok(())
+    })
+    .unwrap();
+
+    let mut received = received.lock().unwrap();
+    core::mem::take(&mut *received)
+}
+
+/// Ensures the reader skips the expired write and is told exactly which data was skipped
+#[test]
+fn partial_reliability_test() {
+    let limits = Limits::default().with_partial_reliability(true).unwrap();
+    let chunk_len = CHUNK_LEN as u64;
+
+    assert_eq!(
+        run(limits, limits),
+        [Received {
+            delivered: vec![0..chunk_len, 2 * chunk_len..3 * chunk_len],
+            expired: vec![chunk_len..2 * chunk_len],
+        }]
+    );
+}
+
+/// Ensures expiring data is delivered reliably if the peer doesn't support partial reliability
+#[test]
+fn partial_reliability_unsupported_test() {
+    let limits = Limits::default().with_partial_reliability(true).unwrap();
+    let chunk_len = CHUNK_LEN as u64;
+
+    assert_eq!(
+        run(limits, Limits::default()),
+        [Received {
+            delivered: vec![0..3 * chunk_len],
+            expired: vec![],
+        }]
+    );
+}
@@ -0,0 +1,161 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::path_cache::{Cache, Entry, Memory, PathInfo};
+
+/// Records the result of every lookup in the cache
+#[derive(Debug)]
+struct Recorder {
+    cache: Memory,
+    lookups: Arc<Mutex<Vec<Option<Entry>>>>,
+}
+
+impl Cache for Recorder {
+    fn get(&self, path: &PathInfo) -> Option<Entry> {
+        let entry = self.cache.get(path);
+        self.lookups.lock().unwrap().push(entry);
+        entry
+    }
+
+    fn insert(&self, path: &PathInfo, entry: Entry) {
+        self.cache.insert(path, entry)
+    }
+}
+
+/// The MTU and congestion window a connection started with
+#[derive(Clone, Copy, Debug, Default)]
+struct PathStart {
+    mtu: u16,
+    congestion_window: Option<u32>,
+}
+
+/// Records how each connection's path started out
+#[derive(Clone, Default)]
+struct PathStarts {
+    connections: Arc<Mutex<Vec<PathStart>>>,
+}
+
+impl events::Subscriber for PathStarts {
+    /// The index of the connection
+    type ConnectionContext = usize;
+
+    fn create_connection_context(
+        &mut self,
+        _meta: &events::ConnectionMeta,
+        _info: &events::ConnectionInfo,
+    ) -> Self::ConnectionContext {
+        let mut connections = self.connections.lock().unwrap();
+        connections.push(PathStart::default());
+        connections.len() - 1
+    }
+
+    fn on_mtu_updated(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &events::ConnectionMeta,
+        event: &events::MtuUpdated,
+    ) {
+        if matches!(event.cause, events::MtuUpdatedCause::NewPath { .. }) {
+            self.connections.lock().unwrap()[*context].mtu = event.mtu;
+        }
+    }
+
+    fn on_recovery_metrics(
+        &mut self,
+        context: &mut Self::ConnectionContext,
+        _meta: &events::ConnectionMeta,
+        event: &events::RecoveryMetrics,
+    ) {
+        self.connections.lock().unwrap()[*context]
+            .congestion_window
+            .get_or_insert(event.congestion_window);
+    }
+}
+
+/// Ensures a closed connection's path characteristics are used by the next connection to the
+/// same peer
+#[test]
+fn path_cache_test() {
+    let model = Model::default();
+    let delay = Duration::from_millis(100);
+    model.set_delay(delay);
+
+    let lookups = Arc::new(Mutex::new(vec![]));
+    let cache = Recorder {
+        cache: Memory::new(16),
+        lookups: lookups.clone(),
+    };
+    let starts = PathStarts::default();
+    let connections = starts.connections.clone();
+
+    test(model, |handle| {
+        let server_addr = server(handle)?;
+        let client = build_client_with_path_cache(handle, cache, starts)?;
+
+        primary::spawn(async move {
+            for _ in 0..2 {
+                let connect = Connect::new(server_addr).with_server_name("localhost");
+                let mut connection = client.connect(connect).await.unwrap();
+
+                // exchange enough data for the window to grow and the MTU to be probed
+                let stream = connection.open_bidirectional_stream().await.unwrap();
+                let (mut recv, mut send) = stream.split();
+
+                let mut send_data = Data::new(200_000);
+                let mut recv_data = send_data;
+
+                primary::spawn(async move {
+                    while let Some(chunk) = send_data.send_one(usize::MAX) {
+                        send.send(chunk).await.unwrap();
+                    }
+                    send.finish().unwrap();
+                });
+
+                while let Some(chunk) = recv.receive().await.unwrap() {
+                    recv_data.receive(&[chunk]);
+                }
+                assert!(recv_data.is_finished());
+
+                connection.close(123u8.into());
+            }
+        });
+
+        Ok(())
+    })
+    .unwrap();
+
+    let lookups = lookups.lock().unwrap();
+    assert_eq!(lookups.len(), 2);
+    assert_eq!(
+        lookups[0], None,
+        "nothing is cached for the first connection"
+    );
+
+    let entry = lookups[1].expect("the first connection should be cached on close");
+    assert!(entry.smoothed_rtt >= delay);
+    assert!(entry.min_rtt >= delay);
+    assert!(
+        entry.mtu > 1228,
+        "the first connection should have probed the MTU"
+    );
+
+    let connections = connections.lock().unwrap();
+    assert_eq!(connections.len(), 2);
+    let (first, second) = (connections[0], connections[1]);
+
+    // the second connection starts with the MTU the first one discovered
+    assert!(
+        second.mtu > first.mtu,
+        "{second:?} should start with a larger MTU than {first:?}"
+    );
+
+    // and resumes with a larger congestion window than the first one started with
+    let first_window = first.congestion_window.unwrap();
+    let second_window = second.congestion_window.unwrap();
+    assert!(
+        second_window > first_window,
+        "{second:?} should start with a larger window than {first:?}"
+    );
+    assert!(second_window <= entry.congestion_window);
+}
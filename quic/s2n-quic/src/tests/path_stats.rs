@@ -0,0 +1,60 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+/// Ensures the connection reports RTT and loss statistics for its path
+#[test]
+fn path_stats_test() {
+    let model = Model::default();
+    let rtt = Duration::from_millis(100);
+    model.set_delay(rtt / 2);
+    model.set_drop_rate(0.05);
+
+    test(model, |handle| {
+        let server_addr = server(handle)?;
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            let stream = connection.open_bidirectional_stream().await.unwrap();
+            let (mut recv, mut send) = stream.split();
+
+            let mut send_data = Data::new(100_000);
+            let mut recv_data = send_data;
+
+            primary::spawn(async move {
+                while let Some(chunk) = send_data.send_one(usize::MAX) {
+                    send.send(chunk).await.unwrap();
+                }
+            });
+
+            while let Some(chunk) = recv.receive().await.unwrap() {
+                recv_data.receive(&[chunk]);
+                if recv_data.is_finished() {
+                    break;
+                }
+            }
+
+            let stats = connection.path_stats().unwrap();
+            assert_eq!(stats.len(), 1);
+
+            let stats = &stats[0];
+            assert!(stats.is_active);
+            assert_eq!(
+                std::net::SocketAddr::from(stats.remote_address),
+                server_addr
+            );
+            assert!(stats.min_rtt >= rtt);
+            assert!(stats.smoothed_rtt >= rtt);
+            assert!(stats.latest_rtt >= rtt);
+            assert!(stats.lost_packets > 0);
+            assert!(stats.lost_bytes > 0);
+        });
+
+        Ok(())
+    })
+    .unwrap();
+}
@@ -0,0 +1,112 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::client::Pool;
+
+/// Ensures the pool reuses connections to the same server
+#[test]
+fn pool_reuse_test() {
+    let model = Model::default();
+    test(model, |handle| {
+        let server_addr = server(handle)?;
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let pool = Pool::new(client).with_idle_timeout(Duration::from_secs(3600));
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+
+            let a = pool.connect(connect.clone()).await.unwrap();
+            let b = pool.connect(connect.clone()).await.unwrap();
+            assert_eq!(a.id(), b.id());
+            assert_eq!(pool.len(), 1);
+
+            // the connection stays pooled after all of the handles are dropped
+            let id = a.id();
+            drop(a);
+            drop(b);
+            pool.reap();
+            assert_eq!(pool.len(), 1);
+
+            let mut c = pool
+                .connect_with_application_protocol(connect, &b"h3"[..])
+                .await
+                .unwrap();
+            assert_eq!(c.id(), id);
+
+            let mut stream = c.open_bidirectional_stream().await.unwrap();
+            stream.send(Bytes::from_static(b"hello")).await.unwrap();
+        });
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+/// Ensures the pool closes connections which have been idle for the configured timeout
+#[test]
+fn pool_idle_timeout_test() {
+    let model = Model::default();
+    test(model, |handle| {
+        let server_addr = server(handle)?;
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let pool = Pool::new(client).with_idle_timeout(Duration::ZERO);
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+
+            let a = pool.connect(connect.clone()).await.unwrap();
+
+            // connections with outstanding handles are never reaped
+            pool.reap();
+            assert_eq!(pool.len(), 1);
+
+            let id = a.id();
+            drop(a);
+            pool.reap();
+            assert!(pool.is_empty());
+
+            let b = pool.connect(connect).await.unwrap();
+            assert_ne!(b.id(), id);
+        });
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+/// Ensures the reaper releases connections without any calls into the pool
+#[test]
+fn pool_reaper_test() {
+    let model = Model::default();
+    test(model, |handle| {
+        let server_addr = server(handle)?;
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let pool = Pool::new(client)
+                .with_idle_timeout(Duration::ZERO)
+                .with_reap_interval(Duration::from_millis(100));
+            spawn(pool.reaper());
+
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+
+            // closed connections are released as soon as the last handle is dropped
+            let a = pool.connect(connect.clone()).await.unwrap();
+            a.close(0u32.into());
+            drop(a);
+            assert!(pool.is_empty());
+
+            // idle connections are released on the next reap interval
+            let b = pool.connect(connect).await.unwrap();
+            drop(b);
+            assert_eq!(pool.len(), 1);
+
+            delay(Duration::from_millis(200)).await;
+            assert!(pool.is_empty());
+        });
+
+        Ok(())
+    })
+    .unwrap();
+}
@@ -59,6 +59,26 @@ macro_rules! event_recorder {
 event_recorder!(FrameSent, FrameSent, on_frame_sent);
 event_recorder!(PacketSent, PacketSent, on_packet_sent);
 event_recorder!(MtuUpdated, MtuUpdated, on_mtu_updated);
+event_recorder!(StreamOpenBlocked, StreamOpenBlocked, on_stream_open_blocked);
+event_recorder!(
+    StreamLimitPressure,
+    StreamLimitPressure,
+    on_stream_limit_pressure
+);
+event_recorder!(
+    ConnectionDataPressure,
+    ConnectionDataPressure,
+    on_connection_data_pressure
+);
+event_recorder!(
+    ConnectionHibernated,
+    ConnectionHibernated,
+    on_connection_hibernated,
+    Duration,
+    |event: &events::ConnectionHibernated, storage: &mut Vec<Duration>| {
+        storage.push(event.idle_threshold)
+    }
+);
 event_recorder!(
     PathUpdated,
     RecoveryMetrics,
@@ -85,6 +105,16 @@ event_recorder!(
     HandshakeStatusUpdated,
     on_handshake_status_updated
 );
+event_recorder!(
+    PacketBudgetExceeded,
+    PacketBudgetExceeded,
+    on_packet_budget_exceeded
+);
+event_recorder!(
+    CompatibilityRelaxationApplied,
+    CompatibilityRelaxationApplied,
+    on_compatibility_relaxation_applied
+);
 
 event_recorder!(
     ActivePathUpdated,
@@ -166,6 +196,16 @@ event_recorder!(
     }
 );
 
+event_recorder!(
+    ConnectionClosed,
+    ConnectionClosed,
+    on_connection_closed,
+    s2n_quic_core::connection::Error,
+    |event: &events::ConnectionClosed, storage: &mut Vec<s2n_quic_core::connection::Error>| {
+        storage.push(event.error);
+    }
+);
+
 use s2n_quic_core::event::api::DatagramDropReason;
 pub struct DatagramDroppedEvent {
     pub remote_addr: SocketAddr,
@@ -190,3 +230,14 @@ event_recorder!(
         storage.push(event.into());
     }
 );
+
+event_recorder!(
+    TransportParametersReceived,
+    TransportParametersReceived,
+    on_transport_parameters_received,
+    (Duration, u64),
+    |event: &events::TransportParametersReceived, storage: &mut Vec<(Duration, u64)>| {
+        let params = &event.transport_parameters;
+        storage.push((params.max_idle_timeout, params.initial_max_streams_bidi));
+    }
+);
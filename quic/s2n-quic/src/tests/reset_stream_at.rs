@@ -0,0 +1,115 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::{
+    application::Error as ApplicationError,
+    provider::{
+        io::testing::{Handle, Result},
+        limits::Limits,
+    },
+    stream,
+};
+
+const LEN: usize = 100_000;
+const ERROR_CODE: u32 = 123;
+
+/// Starts a server which records how much data was received on each stream before it was reset
+fn start_server(
+    handle: &Handle,
+    limits: Limits,
+    received: Arc<Mutex<Vec<usize>>>,
+) -> Result<SocketAddr> {
+    let mut server = Server::builder()
+        .with_io(handle.builder().build()?)?
+        .with_tls(SERVER_CERTS)?
+        .with_event(tracing_events())?
+        .with_random(Random::with_seed(456))?
+        .with_limits(limits)?
+        .start()?;
+    let server_addr = server.local_addr()?;
+
+    spawn(async move {
+        while let Some(mut connection) = server.accept().await {
+            let received = received.clone();
+            spawn(async move {
+                while let Ok(Some(mut stream)) = connection.accept_receive_stream().await {
+                    let received = received.clone();
+                    spawn(async move {
+                        let mut len = 0;
+                        loop {
+                            match stream.receive().await {
+                                Ok(Some(chunk)) => len += chunk.len(),
+                                Ok(None) => panic!("the stream should be reset"),
+                                Err(err) => {
+                                    assert!(matches!(
+                                        err,
+                                        stream::Error::StreamReset { error, .. }
+                                            if error == ApplicationError::from(ERROR_CODE)
+                                    ));
+                                    break;
+                                }
+                            }
+                        }
+                        received.lock().unwrap().push(len);
+                    });
+                }
+            });
+        }
+    });
+
+    Ok(server_addr)
+}
+
+fn run(client_limits: Limits, server_limits: Limits) -> Vec<usize> {
+    let model = Model::default();
+    let received = Arc::new(Mutex::new(vec![]));
+    let server_received = received.clone();
+
+    test(model, |handle| {
+        let server_addr = start_server(handle, server_limits, server_received)?;
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(123))?
+            .with_limits(client_limits)?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            let mut stream = connection.open_send_stream().await.unwrap();
+            stream.send(Bytes::from(vec![42; LEN])).await.unwrap();
+
+            // reset the stream before the data has been transmitted
+            stream.reset_at(ERROR_CODE.into(), LEN as u64).unwrap();
+
+            // give the peer time to receive the data
+            delay(Duration::from_secs(5)).await;
+        });
+
+        Ok(())
+    })
+    .unwrap();
+
+    let received = received.lock().unwrap();
+    received.clone()
+}
+
+/// Ensures all of the data up to the reliable size is delivered before the stream is reset
+#[test]
+fn reset_stream_at_test() {
+    let limits = Limits::default().with_reset_stream_at(true).unwrap();
+    assert_eq!(run(limits, limits), [LEN]);
+}
+
+/// Ensures `reset_at` behaves like `reset` if the peer doesn't support RESET_STREAM_AT
+#[test]
+fn reset_stream_at_unsupported_test() {
+    let limits = Limits::default().with_reset_stream_at(true).unwrap();
+    let received = run(limits, Limits::default());
+    assert_eq!(received.len(), 1);
+    assert!(received[0] < LEN, "{received:?}");
+}
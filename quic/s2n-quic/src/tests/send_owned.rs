@@ -0,0 +1,89 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+const CHUNK_LEN: usize = 10_000;
+const CHUNKS: usize = 10;
+
+/// A buffer which records when the stream releases it
+struct Pooled {
+    data: Vec<u8>,
+    released: Arc<AtomicUsize>,
+}
+
+impl AsRef<[u8]> for Pooled {
+    fn as_ref(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+impl Drop for Pooled {
+    fn drop(&mut self) {
+        self.released.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Ensures owned buffers are delivered without changes and released once they're acknowledged
+#[test]
+fn send_owned_test() {
+    let model = Model::default();
+
+    test(model, |handle| {
+        let mut server = build_server(handle)?;
+        let server_addr = server.local_addr()?;
+        let client = build_client(handle)?;
+
+        spawn(async move {
+            let mut connection = server.accept().await.unwrap();
+            let mut stream = connection.accept_receive_stream().await.unwrap().unwrap();
+
+            let mut received = vec![];
+            while let Some(chunk) = stream.receive().await.unwrap() {
+                received.extend_from_slice(&chunk);
+            }
+
+            assert_eq!(received.len(), CHUNK_LEN * CHUNKS);
+            for (index, chunk) in received.chunks(CHUNK_LEN).enumerate() {
+                assert!(chunk.iter().all(|byte| *byte == index as u8));
+            }
+        });
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+            let mut stream = connection.open_send_stream().await.unwrap();
+
+            let released = Arc::new(AtomicUsize::new(0));
+
+            // empty buffers are released immediately
+            let empty = Pooled {
+                data: vec![],
+                released: released.clone(),
+            };
+            stream.send_owned(empty).await.unwrap();
+            assert_eq!(released.load(Ordering::Relaxed), 1);
+
+            for index in 0..CHUNKS {
+                let buffer = Pooled {
+                    data: vec![index as u8; CHUNK_LEN],
+                    released: released.clone(),
+                };
+                stream.send_owned(buffer).await.unwrap();
+            }
+
+            // all of the buffers are released once the peer acknowledged the data
+            stream.flush().await.unwrap();
+            assert_eq!(released.load(Ordering::Relaxed), CHUNKS + 1);
+
+            stream.finish().unwrap();
+        });
+
+        Ok(())
+    })
+    .unwrap();
+}
@@ -0,0 +1,55 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+fn run(server_name: &'static str) -> Vec<Option<String>> {
+    let model = Model::default();
+    let accepted = Arc::new(Mutex::new(vec![]));
+    let server_accepted = accepted.clone();
+
+    test(model, |handle| {
+        let mut server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+        let server_addr = server.local_addr()?;
+
+        spawn(async move {
+            while let Some(connection) = server.accept().await {
+                let server_name = connection.server_name().unwrap();
+                server_accepted
+                    .lock()
+                    .unwrap()
+                    .push(server_name.map(|name| name.to_string()));
+            }
+        });
+
+        let client = build_client(handle)?;
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name(server_name);
+            let connection = client.connect(connect).await.unwrap();
+            assert_eq!(
+                connection.server_name().unwrap().as_deref(),
+                Some(server_name)
+            );
+
+            // keep the connection open until the server has accepted it
+            delay(Duration::from_secs(1)).await;
+        });
+
+        Ok(())
+    })
+    .unwrap();
+
+    let accepted = accepted.lock().unwrap();
+    accepted.clone()
+}
+
+/// Ensures the server can read the server name presented by the client from the connection
+#[test]
+fn server_name_test() {
+    assert_eq!(run("localhost"), [Some("localhost".to_string())]);
+}
@@ -4,8 +4,10 @@
 use crate::{
     client::Connect,
     provider::{
-        event,
+        connection_id, event,
         io::testing::{primary, spawn, Handle, Result},
+        limits::Limits,
+        path_cache, tls,
     },
     stream::PeerStream,
     Client, Server,
@@ -105,6 +107,33 @@ pub fn build_server(handle: &Handle) -> Result<Server> {
         .start()?)
 }
 
+/// Builds a server which applies `limits` to each of its connections
+pub fn build_server_with_limits(handle: &Handle, limits: Limits) -> Result<Server> {
+    Ok(Server::builder()
+        .with_io(handle.builder().build().unwrap())?
+        .with_tls(SERVER_CERTS)?
+        .with_event(tracing_events())?
+        .with_random(Random::with_seed(123))?
+        .with_limits(limits)?
+        .start()?)
+}
+
+/// Builds a server which uses zero-length connection IDs
+pub fn build_zero_length_server(handle: &Handle) -> Result<Server> {
+    let cid_format = connection_id::default::Format::builder()
+        .with_len(0)
+        .unwrap()
+        .build()?;
+
+    Ok(Server::builder()
+        .with_io(handle.builder().build().unwrap())?
+        .with_tls(SERVER_CERTS)?
+        .with_event(tracing_events())?
+        .with_random(Random::with_seed(456))?
+        .with_connection_id(cid_format)?
+        .start()?)
+}
+
 pub fn client(handle: &Handle, server_addr: SocketAddr) -> Result {
     let client = build_client(handle)?;
     start_client(client, server_addr, Data::new(10_000))
@@ -150,6 +179,92 @@ pub fn build_client(handle: &Handle) -> Result<Client> {
         .start()?)
 }
 
+/// Builds a client which also publishes its events to `subscriber`
+pub fn build_client_with_events<S: 'static + event::Subscriber>(
+    handle: &Handle,
+    subscriber: S,
+) -> Result<Client> {
+    Ok(Client::builder()
+        .with_io(handle.builder().build().unwrap())?
+        .with_tls(certificates::CERT_PEM)?
+        .with_event((tracing_events(), subscriber))?
+        .with_random(Random::with_seed(123))?
+        .start()?)
+}
+
+/// Builds a client which shares path characteristics between its connections through `cache`
+pub fn build_client_with_path_cache<C: path_cache::Provider, S: 'static + event::Subscriber>(
+    handle: &Handle,
+    cache: C,
+    subscriber: S,
+) -> Result<Client> {
+    Ok(Client::builder()
+        .with_io(handle.builder().build().unwrap())?
+        .with_tls(certificates::CERT_PEM)?
+        .with_event((tracing_events(), subscriber))?
+        .with_random(Random::with_seed(123))?
+        .with_path_cache(cache)?
+        .start()?)
+}
+
+/// Builds a client which only offers the `protocol` application protocol
+pub fn build_client_with_alpn(handle: &Handle, protocol: &[u8]) -> Result<Client> {
+    let tls = tls::default::Client::builder()
+        .with_certificate(certificates::CERT_PEM)?
+        .with_application_protocols([protocol].iter())?
+        .build()?;
+
+    Ok(Client::builder()
+        .with_io(handle.builder().build().unwrap())?
+        .with_tls(tls)?
+        .with_event(tracing_events())?
+        .with_random(Random::with_seed(123))?
+        .start()?)
+}
+
+/// Builds a client which applies `limits` to each of its connections
+pub fn build_client_with_limits(handle: &Handle, limits: Limits) -> Result<Client> {
+    Ok(Client::builder()
+        .with_io(handle.builder().build().unwrap())?
+        .with_tls(certificates::CERT_PEM)?
+        .with_event(tracing_events())?
+        .with_random(Random::with_seed(123))?
+        .with_limits(limits)?
+        .start()?)
+}
+
+/// Builds a client which applies `limits` to each of its connections and also publishes its
+/// events to `subscriber`
+pub fn build_client_with_limits_and_events<S: 'static + event::Subscriber>(
+    handle: &Handle,
+    limits: Limits,
+    subscriber: S,
+) -> Result<Client> {
+    Ok(Client::builder()
+        .with_io(handle.builder().build().unwrap())?
+        .with_tls(certificates::CERT_PEM)?
+        .with_event((tracing_events(), subscriber))?
+        .with_random(Random::with_seed(123))?
+        .with_limits(limits)?
+        .start()?)
+}
+
+/// Builds a client which uses zero-length connection IDs
+pub fn build_zero_length_client(handle: &Handle) -> Result<Client> {
+    let cid_format = connection_id::default::Format::builder()
+        .with_len(0)
+        .unwrap()
+        .build()?;
+
+    Ok(Client::builder()
+        .with_io(handle.builder().build().unwrap())?
+        .with_tls(certificates::CERT_PEM)?
+        .with_event(tracing_events())?
+        .with_random(Random::with_seed(456))?
+        .with_connection_id(cid_format)?
+        .start()?)
+}
+
 pub fn client_server(handle: &Handle) -> Result<SocketAddr> {
     let addr = server(handle)?;
     client(handle, addr)?;
@@ -220,7 +335,6 @@ impl crate::provider::random::Generator for Random {
 #[cfg(not(target_os = "windows"))]
 mod mtls {
     use super::*;
-    use crate::provider::tls;
 
     pub fn build_client_mtls_provider(ca_cert: &str) -> Result<tls::default::Client> {
         let tls = tls::default::Client::builder()
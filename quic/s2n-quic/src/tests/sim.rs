@@ -0,0 +1,89 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use io::sim::{self, havoc::Strategy as _};
+
+/// Ensures hosts can be bound to a specific address on the simulated network
+#[test]
+fn sim_host_address_test() {
+    let addr: SocketAddr = "10.0.0.1:4433".parse().unwrap();
+
+    sim::run(Model::default(), |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().with_address(addr.into()).build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .start()?;
+
+        let server_addr = start_server(server)?;
+        assert_eq!(server_addr, addr);
+
+        client(handle, server_addr)?;
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+/// Ensures simulations which corrupt packets are reproducible for a given seed
+#[test]
+fn sim_havoc_deterministic_test() {
+    let run = || {
+        let network = (
+            sim::Havoc::new(sim::havoc::Mutate.randomly()),
+            Model::default(),
+        );
+
+        sim::run_with_seed(network, 123, |handle| {
+            let server_addr = server(handle)?;
+            let client = build_client(handle)?;
+
+            primary::spawn(async move {
+                let connect = Connect::new(server_addr).with_server_name("localhost");
+                // the connection may not succeed with the corrupted packets
+                let _ = client.connect(connect).await;
+            });
+
+            Ok(())
+        })
+        .unwrap()
+    };
+
+    assert_eq!(run(), run());
+}
+
+/// Ensures advancing the clock skips time for every task in the simulation
+#[test]
+fn sim_advance_test() {
+    let woke_after = Arc::new(Mutex::new(None));
+
+    sim::run(Model::default(), |_handle| {
+        let sleeper = woke_after.clone();
+        sim::spawn(async move {
+            let start = sim::time::now();
+            sim::time::delay(Duration::from_secs(10)).await;
+            let elapsed = sim::time::now().saturating_duration_since(start);
+            *sleeper.lock().unwrap() = Some(elapsed);
+        });
+
+        sim::primary::spawn(async move {
+            // let the sleeper register its timer
+            sim::time::delay(Duration::from_millis(1)).await;
+
+            let before = sim::time::now();
+            sim::time::advance(Duration::from_secs(5));
+            let skipped = sim::time::now().saturating_duration_since(before);
+            assert_eq!(skipped, Duration::from_secs(5));
+
+            sim::time::delay(Duration::from_secs(20)).await;
+        });
+
+        Ok(())
+    })
+    .unwrap();
+
+    // the sleeper woke at its original deadline rather than 5 seconds later
+    assert_eq!(*woke_after.lock().unwrap(), Some(Duration::from_secs(10)));
+}
@@ -0,0 +1,47 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use futures::FutureExt;
+
+/// Ensures `finished` only resolves once the stream was finished and acknowledged by the peer
+#[test]
+fn stream_finished_test() {
+    let model = Model::default();
+    model.set_delay(Duration::from_millis(50));
+
+    test(model, |handle| {
+        let server_addr = server(handle)?;
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            let mut stream = connection.open_send_stream().await.unwrap();
+            stream.send(vec![42; 10_000].into()).await.unwrap();
+            stream.flush().await.unwrap();
+
+            // all of the data was acknowledged but the stream hasn't been finished yet
+            delay(Duration::from_millis(200)).await;
+            assert!(stream.finished().now_or_never().is_none());
+
+            stream.finish().unwrap();
+            // the FIN still needs to be acknowledged by the peer
+            assert!(stream.finished().now_or_never().is_none());
+            stream.finished().await.unwrap();
+
+            // the stream remains finished
+            assert!(matches!(stream.finished().now_or_never(), Some(Ok(()))));
+
+            // resetting the stream means the data will never be acknowledged
+            let mut stream = connection.open_send_stream().await.unwrap();
+            stream.send(Bytes::from_static(b"hello")).await.unwrap();
+            stream.reset(123u8.into()).unwrap();
+            assert!(stream.finished().await.is_err());
+        });
+
+        Ok(())
+    })
+    .unwrap();
+}
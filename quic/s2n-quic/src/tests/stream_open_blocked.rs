@@ -0,0 +1,164 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::{
+    provider::{
+        io::testing::{Handle, Result},
+        limits::Limits,
+    },
+    stream,
+};
+use recorder::StreamOpenBlocked;
+
+/// Starts a server which allows a single bidirectional stream and never releases it
+fn start_holding_server(handle: &Handle) -> Result<SocketAddr> {
+    let limits = Limits::default()
+        .with_max_open_remote_bidirectional_streams(1)
+        .unwrap();
+
+    let mut server = build_server_with_limits(handle, limits)?;
+    let server_addr = server.local_addr()?;
+
+    spawn(async move {
+        while let Some(mut connection) = server.accept().await {
+            spawn(async move {
+                let mut streams = vec![];
+                while let Ok(Some(stream)) = connection.accept_bidirectional_stream().await {
+                    streams.push(stream);
+                }
+            });
+        }
+    });
+
+    Ok(server_addr)
+}
+
+/// Builds a client which reports opens that are blocked for longer than `threshold`
+fn build_blocked_client(
+    handle: &Handle,
+    threshold: Duration,
+    blocked: StreamOpenBlocked,
+) -> Result<Client> {
+    let limits = Limits::default()
+        .with_stream_open_blocked_threshold(threshold)
+        .unwrap();
+
+    Ok(Client::builder()
+        .with_io(handle.builder().build()?)?
+        .with_tls(certificates::CERT_PEM)?
+        .with_event((tracing_events(), blocked))?
+        .with_random(Random::with_seed(123))?
+        .with_limits(limits)?
+        .start()?)
+}
+
+/// Ensures applications can tell when opening a stream is blocked on the peer's stream limit
+#[test]
+fn stream_open_blocked_test() {
+    let model = Model::default();
+    let threshold = Duration::from_millis(100);
+
+    test(model, |handle| {
+        let server_addr = start_holding_server(handle)?;
+        let blocked = StreamOpenBlocked::new();
+        let blocked_events = blocked.events();
+        let client = build_blocked_client(handle, threshold, blocked)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            let _stream = connection
+                .try_open_bidirectional_stream()
+                .unwrap()
+                .expect("the peer allows a single stream");
+
+            assert!(connection
+                .try_open_bidirectional_stream()
+                .unwrap()
+                .is_none());
+            assert!(connection
+                .is_open_stream_blocked(stream::Type::Bidirectional)
+                .unwrap());
+            assert!(!connection
+                .is_open_stream_blocked(stream::Type::Unidirectional)
+                .unwrap());
+
+            // the pending open is woken with the error once the connection is closed
+            let mut handle = connection.handle();
+            primary::spawn(async move {
+                assert!(handle.open_bidirectional_stream().await.is_err());
+            });
+
+            delay(threshold * 5).await;
+
+            {
+                let events = blocked_events.lock().unwrap();
+                assert_eq!(events.len(), 1, "each blocked period is reported once");
+                assert!(matches!(
+                    events[0].stream_type,
+                    events::StreamType::Bidirectional { .. }
+                ));
+                assert_eq!(events[0].stream_limit, 1);
+                assert!(events[0].duration >= threshold);
+            }
+
+            connection.close(123u8.into());
+        });
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+/// Ensures only opens which wait for the peer are reported as blocked
+#[test]
+fn stream_open_not_waiting_test() {
+    let model = Model::default();
+    let threshold = Duration::from_millis(100);
+
+    test(model, |handle| {
+        let server_addr = start_holding_server(handle)?;
+        let blocked = StreamOpenBlocked::new();
+        let blocked_events = blocked.events();
+        let client = build_blocked_client(handle, threshold, blocked)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+
+            let _stream = connection
+                .try_open_bidirectional_stream()
+                .unwrap()
+                .expect("the peer allows a single stream");
+
+            // non-blocking opens don't wait for the peer
+            assert!(connection
+                .try_open_bidirectional_stream()
+                .unwrap()
+                .is_none());
+
+            delay(threshold * 5).await;
+            assert!(blocked_events.lock().unwrap().is_empty());
+
+            // cancelled opens no longer wait for the peer
+            let open = connection.open_bidirectional_stream();
+            let cancel = delay(threshold / 2);
+            futures::pin_mut!(open);
+            futures::pin_mut!(cancel);
+            assert!(matches!(
+                futures::future::select(open, cancel).await,
+                futures::future::Either::Right(_)
+            ));
+
+            delay(threshold * 5).await;
+            assert!(blocked_events.lock().unwrap().is_empty());
+
+            connection.close(123u8.into());
+        });
+
+        Ok(())
+    })
+    .unwrap();
+}
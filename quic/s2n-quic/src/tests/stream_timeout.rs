@@ -0,0 +1,143 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::{
+    connection,
+    provider::{io::testing::Result, limits::Limits},
+    stream::{self, Error},
+};
+
+const TIMEOUT: Duration = Duration::from_millis(100);
+
+/// Starts a server which accepts streams but neither reads nor writes until the client is
+/// expected to have timed out
+fn start_server(handle: &io::Handle) -> Result<SocketAddr> {
+    let limits = Limits::default().with_data_window(1000).unwrap();
+
+    let mut server = Server::builder()
+        .with_io(handle.builder().build()?)?
+        .with_tls(SERVER_CERTS)?
+        .with_event(tracing_events())?
+        .with_random(Random::with_seed(456))?
+        .with_limits(limits)?
+        .start()?;
+    let server_addr = server.local_addr()?;
+
+    spawn(async move {
+        while let Some(mut connection) = server.accept().await {
+            spawn(async move {
+                while let Ok(Some(mut stream)) = connection.accept_bidirectional_stream().await {
+                    spawn(async move {
+                        delay(TIMEOUT * 5).await;
+                        let _ = stream.send(Bytes::from_static(b"hello")).await;
+                        delay(Duration::from_secs(5)).await;
+                    });
+                }
+            });
+        }
+    });
+
+    Ok(server_addr)
+}
+
+/// Ensures blocked stream operations fail with a timeout error and leave the stream usable
+#[test]
+fn stream_timeout_test() {
+    let model = Model::default();
+
+    test(model, |handle| {
+        let server_addr = start_server(handle)?;
+        let limits = Limits::default().with_max_send_buffer_size(2000).unwrap();
+        let client = Client::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(123))?
+            .with_limits(limits)?
+            .start()?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+            stream.send(Bytes::from_static(b"ping")).await.unwrap();
+
+            // the peer isn't sending anything yet
+            stream.set_read_timeout(Some(TIMEOUT)).unwrap();
+            let start = io::now();
+            let result = stream.receive().await;
+            assert!(matches!(result, Err(Error::Timeout { .. })), "{result:?}");
+            assert!(io::now() - start >= TIMEOUT);
+
+            // the stream remains usable once the timeout is disabled
+            stream.set_read_timeout(None).unwrap();
+            let chunk = stream.receive().await.unwrap();
+            assert_eq!(chunk, Some(Bytes::from_static(b"hello")));
+
+            // the peer's flow control window is exhausted so the send buffer fills up
+            stream.set_write_timeout(Some(TIMEOUT)).unwrap();
+            stream.send(vec![42; 4000].into()).await.unwrap();
+            let result = stream.send(vec![42; 4000].into()).await;
+            assert!(matches!(result, Err(Error::Timeout { .. })), "{result:?}");
+        });
+
+        Ok(())
+    })
+    .unwrap();
+}
+
+/// Ensures opening a stream fails with a timeout error when the peer doesn't issue stream credits
+#[test]
+fn open_stream_timeout_test() {
+    let model = Model::default();
+
+    test(model, |handle| {
+        // the client may only open a single bidirectional stream at a time
+        let limits = Limits::default()
+            .with_max_open_remote_bidirectional_streams(1)
+            .unwrap();
+        let mut server = build_server_with_limits(handle, limits)?;
+        let server_addr = server.local_addr()?;
+
+        spawn(async move {
+            while let Some(mut connection) = server.accept().await {
+                spawn(async move {
+                    // hold on to the streams so the client doesn't get any more credits
+                    let mut streams = vec![];
+                    while let Ok(Some(stream)) = connection.accept_bidirectional_stream().await {
+                        streams.push(stream);
+                    }
+                });
+            }
+        });
+
+        let client = build_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect).await.unwrap();
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+            stream.send(Bytes::from_static(b"ping")).await.unwrap();
+
+            // each call gets its own deadline
+            for _ in 0..2 {
+                let start = io::now();
+                let result = connection
+                    .open_stream_with_timeout(stream::Type::Bidirectional, TIMEOUT)
+                    .await;
+                assert!(
+                    matches!(result, Err(connection::Error::OpenStreamTimeout { .. })),
+                    "{result:?}"
+                );
+                assert!(io::now() - start >= TIMEOUT);
+            }
+
+            // the connection remains usable
+            stream.send(Bytes::from_static(b"ping")).await.unwrap();
+        });
+
+        Ok(())
+    })
+    .unwrap();
+}
@@ -0,0 +1,80 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::provider::limits::{Limits, Overrides};
+
+/// Ensures connections with a higher transmission weight get a larger share of the endpoint's
+/// transmission opportunities
+#[test]
+fn transmission_weight_test() {
+    let model = Model::default();
+    model.set_delay(Duration::from_millis(10));
+    // packets are spaced out on the network so the order they're transmitted in matters
+    model.set_jitter(Duration::from_micros(100));
+
+    let finished = Arc::new(Mutex::new(vec![]));
+
+    test(model, |handle| {
+        let server = Server::builder()
+            .with_io(handle.builder().build()?)?
+            .with_tls(SERVER_CERTS)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(456))?
+            .with_limits(Limits::default().with_transmission_weight(2)?)?
+            .start()?;
+        let server_addr = start_server(server)?;
+
+        // limit the client's transmit queue so the connections compete for it
+        let client = Client::builder()
+            .with_io(
+                handle
+                    .builder()
+                    .with_internal_send_buffer_size(64 * 1500)?
+                    .build()?,
+            )?
+            .with_tls(certificates::CERT_PEM)?
+            .with_event(tracing_events())?
+            .with_random(Random::with_seed(123))?
+            .start()?;
+
+        for weight in [1, 8] {
+            let client = client.clone();
+            let finished = finished.clone();
+            primary::spawn(async move {
+                let overrides = Overrides::default()
+                    .with_transmission_weight(weight)
+                    .unwrap();
+                let connect = Connect::new(server_addr)
+                    .with_server_name("localhost")
+                    .with_limits(overrides);
+                let mut connection = client.connect(connect).await.unwrap();
+
+                let stream = connection.open_bidirectional_stream().await.unwrap();
+                let (mut recv, mut send) = stream.split();
+
+                let mut send_data = Data::new(500_000);
+                let mut recv_data = send_data;
+
+                primary::spawn(async move {
+                    while let Some(chunk) = recv.receive().await.unwrap() {
+                        recv_data.receive(&[chunk]);
+                    }
+                    assert!(recv_data.is_finished());
+                    finished.lock().unwrap().push(weight);
+                });
+
+                while let Some(chunk) = send_data.send_one(usize::MAX) {
+                    send.send(chunk).await.unwrap();
+                }
+                send.finish().unwrap();
+            });
+        }
+
+        Ok(())
+    })
+    .unwrap();
+
+    // the heavier connection finishes its transfer first, even though it started second
+    assert_eq!(*finished.lock().unwrap(), [8, 1]);
+}
@@ -0,0 +1,68 @@
+// Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+/// Ensures a client using zero-length connection IDs can exchange data with a server
+#[test]
+fn zero_length_client_test() {
+    let model = Model::default();
+    test(model, |handle| {
+        let server_addr = server(handle)?;
+        let client = build_zero_length_client(handle)?;
+        start_client(client, server_addr, Data::new(10_000))?;
+        Ok(())
+    })
+    .unwrap();
+}
+
+/// Ensures both peers can use zero-length connection IDs
+#[test]
+fn zero_length_client_and_server_test() {
+    let model = Model::default();
+    test(model, |handle| {
+        let server = build_zero_length_server(handle)?;
+        let server_addr = start_server(server)?;
+        let client = build_zero_length_client(handle)?;
+        start_client(client, server_addr, Data::new(10_000))?;
+        Ok(())
+    })
+    .unwrap();
+}
+
+/// Ensures a client using zero-length connection IDs refuses to open a second connection
+#[test]
+fn zero_length_client_single_connection_test() {
+    let model = Model::default();
+    test(model, |handle| {
+        let server_addr = server(handle)?;
+        let client = build_zero_length_client(handle)?;
+
+        primary::spawn(async move {
+            let connect = Connect::new(server_addr).with_server_name("localhost");
+            let mut connection = client.connect(connect.clone()).await.unwrap();
+
+            // packets for a second connection couldn't be distinguished from the first
+            assert!(client.connect(connect.clone()).await.is_err());
+
+            // the existing connection is unaffected
+            let mut stream = connection.open_bidirectional_stream().await.unwrap();
+            stream.send(Bytes::from_static(b"hello")).await.unwrap();
+            stream.finish().unwrap();
+            assert_eq!(
+                stream.receive().await.unwrap(),
+                Some(Bytes::from_static(b"hello"))
+            );
+
+            // once the connection is closed, the connection ID can be used again
+            drop(stream);
+            connection.close(0u8.into());
+            drop(connection);
+            delay(Duration::from_secs(5)).await;
+            assert!(client.connect(connect).await.is_ok());
+        });
+
+        Ok(())
+    })
+    .unwrap();
+}